@@ -8,8 +8,10 @@ use crate::memory::{self, Memory, MemoryCategory};
 use crate::observability::{self, Observer, ObserverEvent};
 use crate::providers::{self, ChatMessage, ChatRequest, ConversationMessage, Provider};
 use crate::runtime;
+use crate::security::audit::{self, ApprovalDecision, ToolExecutionLog};
 use crate::security::SecurityPolicy;
-use crate::tools::{self, Tool, ToolSpec};
+use crate::security::{injection, redaction, roles};
+use crate::tools::{self, Tool, ToolRisk, ToolSpec};
 use anyhow::Result;
 use std::collections::HashMap;
 use std::io::Write as IoWrite;
@@ -389,7 +391,19 @@ impl Agent {
     async fn execute_tool_call(&self, call: &ParsedToolCall) -> ToolExecutionResult {
         let start = Instant::now();
 
-        let result = if let Some(tool) = self.tools.iter().find(|t| t.name() == call.name) {
+        let tool = self.tools.iter().find(|t| t.name() == call.name);
+        let denied = tool.and_then(|tool| roles::check_tool(tool.as_ref()).err());
+        let (result, success) = if let Some(reason) = denied {
+            (format!("Error: {reason}"), false)
+        } else if let Some(tool) = tool.filter(|t| super::dry_run::intercepts(t.risk())) {
+            let report = super::dry_run::report(tool.as_ref(), call.arguments.clone()).await;
+            self.observer.record_event(&ObserverEvent::ToolCall {
+                tool: call.name.clone(),
+                duration: start.elapsed(),
+                success: true,
+            });
+            (report, true)
+        } else if let Some(tool) = tool {
             match tool.execute(call.arguments.clone()).await {
                 Ok(r) => {
                     self.observer.record_event(&ObserverEvent::ToolCall {
@@ -398,9 +412,9 @@ impl Agent {
                         success: r.success,
                     });
                     if r.success {
-                        r.output
+                        (r.output, true)
                     } else {
-                        format!("Error: {}", r.error.unwrap_or(r.output))
+                        (format!("Error: {}", r.error.unwrap_or(r.output)), false)
                     }
                 }
                 Err(e) => {
@@ -409,12 +423,39 @@ impl Agent {
                         duration: start.elapsed(),
                         success: false,
                     });
-                    format!("Error executing {}: {e}", call.name)
+                    (format!("Error executing {}: {e}", call.name), false)
                 }
             }
         } else {
-            format!("Unknown tool: {}", call.name)
+            (format!("Unknown tool: {}", call.name), false)
         };
+        audit::record_tool_execution(ToolExecutionLog {
+            channel: "agent",
+            session: None,
+            tool: &call.name,
+            arguments: &call.arguments,
+            output: &result,
+            success,
+            error: (!success).then_some(result.as_str()),
+            duration_ms: u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+            risk_level: tool.map_or(ToolRisk::Medium, |t| t.risk()).as_str(),
+            approval: ApprovalDecision::NotRequired,
+        });
+        let result = super::tool_output::process_tool_output(
+            &self.config.tool_output,
+            &call.name,
+            redaction::redact(&result),
+            self.provider.as_ref(),
+            &self.model_name,
+        )
+        .await;
+        let result = injection::guard_tool_output(
+            &call.name,
+            result,
+            self.provider.as_ref(),
+            &self.model_name,
+        )
+        .await;
 
         ToolExecutionResult {
             name: call.name.clone(),
@@ -465,6 +506,10 @@ impl Agent {
     }
 
     pub async fn turn(&mut self, user_message: &str) -> Result<String> {
+        crate::tools::turn::run_with(self.run_turn(user_message)).await
+    }
+
+    async fn run_turn(&mut self, user_message: &str) -> Result<String> {
         if self.history.is_empty() {
             let system_prompt = self.build_system_prompt()?;
             self.history
@@ -486,7 +531,7 @@ impl Agent {
             .await
             .unwrap_or_default();
 
-        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
+        let now = crate::time::now_stamp();
         let enriched = if context.is_empty() {
             format!("[{now}] {user_message}")
         } else {
@@ -879,6 +924,9 @@ mod tests {
                     patterns: vec![],
                     min_length: None,
                     max_length: None,
+                    code: None,
+                    min_tool_calls: None,
+                    hours: None,
                     priority: 10,
                 }],
             })
@@ -0,0 +1,201 @@
+//! Local files attached on the command line (`ask --file`, `agent --image`).
+//!
+//! Files are stored in the workspace (through the workspace jail, so the
+//! quota applies and names are sanitized) and referenced in the message the
+//! same way channel uploads are: images as `[IMAGE:<path>]` markers for the
+//! multimodal pipeline, everything else as `[Document: <name>] <path>` for
+//! the document and file tools.
+
+use crate::config::Config;
+use crate::security::SecurityPolicy;
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+
+/// Workspace subdirectory attached files are copied into.
+const ATTACHMENTS_DIR: &str = "cli_files";
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// How an attachment is passed to the agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentKind {
+    Image,
+    Document,
+}
+
+/// A file given on the command line and whether it had to be an image.
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub path: PathBuf,
+    pub require_image: bool,
+}
+
+impl Attachment {
+    /// `--file`: any file; images are detected from their content.
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            require_image: false,
+        }
+    }
+
+    /// `--image`: must be a supported image format.
+    pub fn image(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            require_image: true,
+        }
+    }
+}
+
+/// Prefix `message` with references to `attachments`, after checking each
+/// file's type and size and copying it into the workspace.
+pub async fn attach(config: &Config, message: &str, attachments: &[Attachment]) -> Result<String> {
+    if attachments.is_empty() {
+        return Ok(message.to_string());
+    }
+
+    let policy = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let workspace = policy.workspace();
+
+    let (max_images, _) = config.multimodal.effective_limits();
+    let mut images = 0usize;
+    let mut references = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        let (kind, bytes) = inspect(config, attachment)?;
+        if kind == AttachmentKind::Image {
+            images += 1;
+            if images > max_images {
+                bail!(
+                    "Too many images: at most {max_images} per message ([multimodal].max_images)"
+                );
+            }
+        }
+        let name = attachment
+            .path
+            .file_name()
+            .map_or_else(|| "attachment".into(), |n| n.to_string_lossy().into_owned());
+        let stored = workspace
+            .store_upload(ATTACHMENTS_DIR, &name, &bytes)
+            .await
+            .map_err(|e| anyhow::anyhow!("Cannot attach {}: {e}", attachment.path.display()))?;
+        references.push(match kind {
+            AttachmentKind::Image => format!("[IMAGE:{}]", stored.display()),
+            AttachmentKind::Document => format!("[Document: {name}] {}", stored.display()),
+        });
+    }
+
+    let mut content = references.join("\n");
+    if !message.trim().is_empty() {
+        content.push_str("\n\n");
+        content.push_str(message);
+    }
+    Ok(content)
+}
+
+/// Read `attachment`, detect its kind and enforce the size limit for it.
+fn inspect(config: &Config, attachment: &Attachment) -> Result<(AttachmentKind, Vec<u8>)> {
+    let path = &attachment.path;
+    let size = std::fs::metadata(path)
+        .with_context(|| format!("Cannot attach {}", path.display()))?
+        .len();
+    let bytes = std::fs::read(path).with_context(|| format!("Cannot read {}", path.display()))?;
+
+    let kind = if crate::multimodal::sniff_image_mime(&bytes).is_some() {
+        AttachmentKind::Image
+    } else if attachment.require_image {
+        bail!(
+            "{} is not a supported image (PNG, JPEG, GIF, WebP or BMP)",
+            path.display()
+        );
+    } else {
+        AttachmentKind::Document
+    };
+
+    let (limit_mb, key) = match kind {
+        AttachmentKind::Image => (
+            config.multimodal.effective_limits().1 as u64,
+            "[multimodal].max_image_size_mb",
+        ),
+        AttachmentKind::Document => (config.documents.max_file_mb, "[documents].max_file_mb"),
+    };
+    if size > limit_mb.saturating_mul(BYTES_PER_MB) {
+        bail!(
+            "{} is {} — larger than the {limit_mb} MB limit ({key})",
+            path.display(),
+            human_size(size)
+        );
+    }
+    Ok((kind, bytes))
+}
+
+fn human_size(bytes: u64) -> String {
+    if bytes >= BYTES_PER_MB {
+        format!("{:.1} MB", bytes as f64 / BYTES_PER_MB as f64)
+    } else {
+        format!("{} KB", bytes.div_ceil(1024))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const PNG: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', 0, 0];
+
+    #[tokio::test]
+    async fn attachments_are_typed_copied_and_limited() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            ..Config::default()
+        };
+        let chart = tmp.path().join("chart.dat");
+        std::fs::write(&chart, PNG).unwrap();
+        let report = tmp.path().join("report.pdf");
+        std::fs::write(&report, b"%PDF-1.7 ...").unwrap();
+
+        let content = attach(
+            &config,
+            "summarize",
+            &[Attachment::file(&report), Attachment::file(&chart)],
+        )
+        .await
+        .unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert!(lines[0].starts_with("[Document: report.pdf] "));
+        let image_path = lines[1]
+            .strip_prefix("[IMAGE:")
+            .and_then(|rest| rest.strip_suffix(']'))
+            .unwrap();
+        let stored_dir = config
+            .workspace_dir
+            .canonicalize()
+            .unwrap()
+            .join(ATTACHMENTS_DIR);
+        assert!(Path::new(image_path).starts_with(stored_dir));
+        assert_eq!(std::fs::read(image_path).unwrap(), PNG);
+        assert_eq!(lines[3], "summarize");
+
+        let err = attach(&config, "x", &[Attachment::image(&report)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not a supported image"));
+
+        config.documents.max_file_mb = 0;
+        let err = attach(&config, "x", &[Attachment::file(&report)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("[documents].max_file_mb"));
+
+        config.multimodal.max_images = 1;
+        let err = attach(
+            &config,
+            "x",
+            &[Attachment::image(&chart), Attachment::image(&chart)],
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("Too many images"));
+    }
+}
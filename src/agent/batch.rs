@@ -0,0 +1,584 @@
+//! `zeroclaw batch`: one prompt per input record, for data jobs.
+//!
+//! Every line of the JSONL input is rendered through a prompt template
+//! ([`super::template`]: the record's fields are variables, the whole record
+//! is `item` and its line number `line`) and answered by the agent, or with
+//! `--no-tools` by a single model call. Results are appended to the output
+//! JSONL as they finish, one line per record, so that file is also the
+//! checkpoint: a rerun skips the records it already holds a successful
+//! result for and tries the others again. A failing record is retried with
+//! backoff; a `[budget]` limit, which covers the whole batch, stops it.
+
+use super::loop_::CliRuntime;
+use super::pipe::{PipeUsage, UsageObserver, EXIT_BUDGET, EXIT_FAILED, EXIT_OK};
+use crate::config::schema::ModelPricing;
+use crate::config::{Config, GuardrailDirection};
+use crate::cost::TokenUsage;
+use crate::observability::ObserverEvent;
+use crate::providers::{ChatMessage, ChatRequest};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+/// Longest wait between two attempts at a record.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// What `zeroclaw batch` was asked to do.
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    pub input: PathBuf,
+    pub template: PathBuf,
+    pub output: PathBuf,
+    /// Records in flight at once.
+    pub concurrency: usize,
+    /// Extra attempts for a failing record.
+    pub retries: u32,
+    /// One model call per record instead of the agent loop.
+    pub no_tools: bool,
+    /// Ignore the results already in `output` and overwrite it.
+    pub restart: bool,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub temperature: f64,
+}
+
+/// One input line.
+#[derive(Debug)]
+struct Record {
+    /// The record's `id` field, else its line number.
+    id: Value,
+    line: usize,
+    /// The rendered prompt, or why the template does not fit the record.
+    prompt: std::result::Result<String, String>,
+}
+
+/// One output line.
+#[derive(Debug, Serialize)]
+struct RecordResult {
+    id: Value,
+    line: usize,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    attempts: u32,
+    usage: PipeUsage,
+    cost_usd: f64,
+    duration_ms: u64,
+}
+
+/// Totals of one `zeroclaw batch` run.
+#[derive(Debug, Default)]
+pub struct BatchSummary {
+    pub records: usize,
+    /// Records the output already held a successful result for.
+    pub skipped: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Records left when a `[budget]` limit stopped the batch.
+    pub not_run: usize,
+    pub budget_exceeded: bool,
+    pub usage: PipeUsage,
+    pub cost_usd: f64,
+    /// Whether a model without a `[cost].prices` entry was used.
+    pub unpriced: bool,
+    pub output: PathBuf,
+    pub duration: Duration,
+}
+
+impl BatchSummary {
+    pub fn exit_code(&self) -> i32 {
+        if self.budget_exceeded {
+            EXIT_BUDGET
+        } else if self.failed > 0 {
+            EXIT_FAILED
+        } else {
+            EXIT_OK
+        }
+    }
+
+    /// The closing report printed to stdout.
+    pub fn report(&self) -> String {
+        let mut report = format!(
+            "{} records: {} succeeded, {} failed, {} skipped (already in {})",
+            self.records,
+            self.succeeded,
+            self.failed,
+            self.skipped,
+            self.output.display()
+        );
+        let _ = write!(
+            report,
+            "\nTokens: {} in, {} out over {} model calls in {:.1}s",
+            self.usage.input_tokens,
+            self.usage.output_tokens,
+            self.usage.llm_calls,
+            self.duration.as_secs_f64()
+        );
+        let _ = write!(report, "\nEstimated cost: ${:.4}", self.cost_usd);
+        if self.unpriced {
+            report.push_str(" (the model has no [cost].prices entry; its tokens count as $0)");
+        }
+        if self.budget_exceeded {
+            let _ = write!(
+                report,
+                "\nA [budget] limit stopped the batch; {} records were not run.",
+                self.not_run
+            );
+        }
+        if self.failed > 0 || self.not_run > 0 {
+            report.push_str("\nRun the same command again to retry the rest.");
+        }
+        report
+    }
+}
+
+/// Parse the input and render each record's prompt.
+fn parse_records(input: &str, template: &str) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+    for (index, raw) in input.lines().enumerate() {
+        let line = index + 1;
+        if raw.trim().is_empty() {
+            continue;
+        }
+        let record: Value = serde_json::from_str(raw)
+            .with_context(|| format!("Line {line} of the input is not valid JSON"))?;
+        let id = match record.get("id") {
+            Some(id @ (Value::String(_) | Value::Number(_))) => id.clone(),
+            _ => json!(line),
+        };
+        let mut vars = match &record {
+            Value::Object(fields) => fields.clone(),
+            _ => Map::new(),
+        };
+        vars.insert("item".into(), record.clone());
+        vars.insert("line".into(), json!(line));
+        let (prompt, rendered) =
+            super::template::render_with_report(template, &Value::Object(vars));
+        let prompt = if rendered.unresolved.is_empty() {
+            Ok(prompt)
+        } else {
+            let missing: Vec<&str> = rendered.unresolved.iter().map(String::as_str).collect();
+            Err(format!(
+                "The record has no value for {} in the template",
+                missing.join(", ")
+            ))
+        };
+        records.push(Record { id, line, prompt });
+    }
+    Ok(records)
+}
+
+/// IDs the output already holds a successful result for.
+fn completed_ids(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|result| result.get("ok") == Some(&Value::Bool(true)))
+        .filter_map(|result| result.get("id").map(Value::to_string))
+        .collect()
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.min(5)).min(MAX_BACKOFF)
+}
+
+/// State the workers share.
+struct Batch {
+    options: BatchOptions,
+    queue: Mutex<VecDeque<Record>>,
+    output: tokio::sync::Mutex<tokio::fs::File>,
+    prices: HashMap<String, ModelPricing>,
+    /// Set when a `[budget]` limit is reached.
+    stop: AtomicBool,
+    finished: AtomicUsize,
+    pending: usize,
+    summary: Mutex<BatchSummary>,
+}
+
+/// Why an attempt at a record failed.
+enum Failure {
+    /// Worth another attempt.
+    Retry(anyhow::Error),
+    /// Another attempt would fail the same way.
+    Final(anyhow::Error),
+}
+
+impl From<anyhow::Error> for Failure {
+    fn from(error: anyhow::Error) -> Self {
+        if crate::cost::budget::is_budget_exceeded(&error) {
+            Self::Final(error)
+        } else {
+            Self::Retry(error)
+        }
+    }
+}
+
+impl Batch {
+    /// Take records off the queue until it is empty or the batch stops.
+    async fn work(&self, mut runtime: CliRuntime) {
+        let started = Instant::now();
+        let usage = Arc::new(UsageObserver::new(Arc::clone(&runtime.observer)));
+        runtime.observer = usage.clone();
+        loop {
+            if self.stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let Some(record) = self
+                .queue
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .pop_front()
+            else {
+                break;
+            };
+            let result = self.process(&runtime, &usage, record).await;
+            self.finish(result).await;
+        }
+        runtime.finish(started);
+    }
+
+    async fn process(
+        &self,
+        runtime: &CliRuntime,
+        usage: &UsageObserver,
+        record: Record,
+    ) -> RecordResult {
+        let started = Instant::now();
+        let before = usage.usage();
+        let mut attempts = 0;
+        let outcome = match record.prompt {
+            Err(reason) => Err(anyhow::anyhow!(reason)),
+            Ok(prompt) => loop {
+                attempts += 1;
+                match self.attempt(runtime, &prompt).await {
+                    Ok(answer) => break Ok(answer),
+                    Err(Failure::Retry(e)) if attempts <= self.options.retries => {
+                        tracing::warn!(
+                            "Record {} failed (attempt {attempts}), retrying: {e:#}",
+                            record.id
+                        );
+                        tokio::time::sleep(backoff(attempts)).await;
+                    }
+                    Err(Failure::Retry(e) | Failure::Final(e)) => break Err(e),
+                }
+            },
+        };
+
+        let after = usage.usage();
+        let spent = PipeUsage {
+            input_tokens: after.input_tokens - before.input_tokens,
+            output_tokens: after.output_tokens - before.output_tokens,
+            total_tokens: after.total_tokens - before.total_tokens,
+            llm_calls: after.llm_calls - before.llm_calls,
+        };
+        let price = crate::cost::budget::price_for(&self.prices, &runtime.model_name);
+        if price.is_none() && spent.total_tokens > 0 {
+            self.summary_mut().unpriced = true;
+        }
+        let (input_price, output_price) = price.map_or((0.0, 0.0), |p| (p.input, p.output));
+        let cost_usd = TokenUsage::new(
+            &runtime.model_name,
+            spent.input_tokens,
+            spent.output_tokens,
+            input_price,
+            output_price,
+        )
+        .cost_usd;
+
+        if outcome
+            .as_ref()
+            .is_err_and(crate::cost::budget::is_budget_exceeded)
+        {
+            self.stop.store(true, Ordering::Relaxed);
+            self.summary_mut().budget_exceeded = true;
+        }
+        let (answer, error) = match outcome {
+            Ok(answer) => (Some(answer), None),
+            Err(e) => (None, Some(format!("{e:#}"))),
+        };
+        RecordResult {
+            id: record.id,
+            line: record.line,
+            ok: answer.is_some(),
+            answer,
+            error,
+            attempts,
+            usage: spent,
+            cost_usd,
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// One try at a prompt.
+    async fn attempt(
+        &self,
+        runtime: &CliRuntime,
+        prompt: &str,
+    ) -> std::result::Result<String, Failure> {
+        let prompt = runtime
+            .guard(GuardrailDirection::Input, prompt.to_string())
+            .await
+            .map_err(|blocked| Failure::Final(anyhow::anyhow!(blocked)))?;
+        let answer = if self.options.no_tools {
+            single_call(runtime, &prompt).await?
+        } else {
+            let mut history = runtime.initial_history();
+            runtime.prepare_turn(&mut history, &prompt).await;
+            runtime.run_turn(&mut history, &prompt, None, None).await?
+        };
+        runtime
+            .guard(GuardrailDirection::Output, answer)
+            .await
+            .map_err(|blocked| Failure::Final(anyhow::anyhow!(blocked)))
+    }
+
+    /// Append `result` to the output and count it.
+    async fn finish(&self, result: RecordResult) {
+        let finished = self.finished.fetch_add(1, Ordering::Relaxed) + 1;
+        match &result.error {
+            None => eprintln!(
+                "[{finished}/{}] {}: ok ({:.1}s)",
+                self.pending,
+                result.id,
+                result.duration_ms as f64 / 1000.0
+            ),
+            Some(error) => eprintln!("[{finished}/{}] {}: {error}", self.pending, result.id),
+        }
+
+        match serde_json::to_string(&result) {
+            Ok(mut line) => {
+                line.push('\n');
+                let mut output = self.output.lock().await;
+                let written = match output.write_all(line.as_bytes()).await {
+                    Ok(()) => output.flush().await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = written {
+                    tracing::error!(
+                        "Failed to write the result of record {} to {}: {e}",
+                        result.id,
+                        self.options.output.display()
+                    );
+                }
+            }
+            Err(e) => tracing::error!(
+                "Failed to serialize the result of record {}: {e}",
+                result.id
+            ),
+        }
+
+        let mut summary = self.summary_mut();
+        if result.ok {
+            summary.succeeded += 1;
+        } else {
+            summary.failed += 1;
+        }
+        summary.usage.input_tokens += result.usage.input_tokens;
+        summary.usage.output_tokens += result.usage.output_tokens;
+        summary.usage.total_tokens += result.usage.total_tokens;
+        summary.usage.llm_calls += result.usage.llm_calls;
+        summary.cost_usd += result.cost_usd;
+    }
+
+    fn summary_mut(&self) -> std::sync::MutexGuard<'_, BatchSummary> {
+        self.summary.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// `--no-tools`: the prompt as the only message, no system prompt or memory.
+async fn single_call(runtime: &CliRuntime, prompt: &str) -> Result<String> {
+    if let Some(budget) = runtime.budget.as_deref() {
+        budget.check()?;
+    }
+    let started = Instant::now();
+    let messages = [ChatMessage::user(prompt)];
+    let response = runtime
+        .provider
+        .chat(
+            ChatRequest {
+                messages: &messages,
+                tools: None,
+            },
+            &runtime.model_name,
+            runtime.temperature,
+        )
+        .await;
+    let usage = response
+        .as_ref()
+        .ok()
+        .and_then(|response| response.usage.clone())
+        .unwrap_or_default();
+    runtime.observer.record_event(&ObserverEvent::LlmResponse {
+        provider: runtime.provider_name.clone(),
+        model: runtime.model_name.clone(),
+        duration: started.elapsed(),
+        success: response.is_ok(),
+        error_message: response.as_ref().err().map(|e| e.to_string()),
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+    });
+    if let Some(budget) = runtime.budget.as_deref() {
+        budget.record_llm(
+            &runtime.model_name,
+            usage.input_tokens.unwrap_or(0),
+            usage.output_tokens.unwrap_or(0),
+        );
+    }
+    match response?.text.filter(|text| !text.trim().is_empty()) {
+        Some(text) => Ok(text),
+        None => bail!("The model returned an empty answer"),
+    }
+}
+
+/// Run the batch. Per-record failures end up in the output and the summary;
+/// `Err` means the batch could not start.
+pub async fn run(mut config: Config, options: BatchOptions) -> Result<BatchSummary> {
+    let started = Instant::now();
+    if options.concurrency == 0 {
+        bail!("--concurrency must be at least 1");
+    }
+    let template = tokio::fs::read_to_string(&options.template)
+        .await
+        .with_context(|| format!("Failed to read {}", options.template.display()))?;
+    let input = tokio::fs::read_to_string(&options.input)
+        .await
+        .with_context(|| format!("Failed to read {}", options.input.display()))?;
+    let records = parse_records(&input, &template)?;
+
+    let done = if options.restart {
+        HashSet::new()
+    } else {
+        match tokio::fs::read_to_string(&options.output).await {
+            Ok(previous) => completed_ids(&previous),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashSet::new(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read {}", options.output.display()))
+            }
+        }
+    };
+    let total = records.len();
+    let queue: VecDeque<Record> = records
+        .into_iter()
+        .filter(|record| !done.contains(&record.id.to_string()))
+        .collect();
+    let mut summary = BatchSummary {
+        records: total,
+        skipped: total - queue.len(),
+        output: options.output.clone(),
+        ..BatchSummary::default()
+    };
+    if queue.is_empty() {
+        summary.duration = started.elapsed();
+        return Ok(summary);
+    }
+
+    let output = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!options.restart)
+        .truncate(options.restart)
+        .open(&options.output)
+        .await
+        .with_context(|| format!("Failed to open {}", options.output.display()))?;
+
+    // Records are independent: nothing is remembered between them.
+    config.agent.render_markdown = false;
+    config.memory.auto_save = false;
+    config.memory.fact_extraction = false;
+    let workers = options.concurrency.min(queue.len());
+    let mut runtimes = Vec::with_capacity(workers);
+    for _ in 0..workers {
+        runtimes.push(
+            CliRuntime::new(
+                config.clone(),
+                options.provider.clone(),
+                options.model.clone(),
+                options.temperature,
+                Vec::new(),
+                false,
+                None,
+            )
+            .await?,
+        );
+    }
+    // One budget for the whole batch, however many workers run it.
+    let budget = runtimes[0].budget.clone();
+    for runtime in &mut runtimes[1..] {
+        runtime.budget.clone_from(&budget);
+    }
+
+    let batch = Batch {
+        pending: queue.len(),
+        queue: Mutex::new(queue),
+        output: tokio::sync::Mutex::new(output),
+        prices: config.cost.prices.clone(),
+        stop: AtomicBool::new(false),
+        finished: AtomicUsize::new(0),
+        summary: Mutex::new(summary),
+        options,
+    };
+    futures_util::future::join_all(runtimes.into_iter().map(|runtime| batch.work(runtime))).await;
+
+    let not_run = batch.queue.lock().unwrap_or_else(|e| e.into_inner()).len();
+    let mut summary = batch
+        .summary
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner());
+    summary.not_run = not_run;
+    summary.duration = started.elapsed();
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_render_with_their_fields_and_ids() {
+        let input = "{\"id\":\"a1\",\"name\":\"Ada\"}\n\n{\"name\":\"Bob\",\"tags\":[\"x\"]}\n{}\n";
+        let records = parse_records(input, "Clean {{ name }} (line {{ line }})").unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].id, json!("a1"));
+        assert_eq!(records[0].prompt.as_deref(), Ok("Clean Ada (line 1)"));
+        assert_eq!(records[1].id, json!(3));
+        assert_eq!(records[1].prompt.as_deref(), Ok("Clean Bob (line 3)"));
+        assert!(records[2]
+            .prompt
+            .as_ref()
+            .unwrap_err()
+            .contains("{{ name }}"));
+
+        let error = parse_records("{\"id\":1}\nnot json\n", "x").unwrap_err();
+        assert!(error.to_string().contains("Line 2"));
+    }
+
+    #[test]
+    fn only_successful_results_count_as_done() {
+        let output = "{\"id\":\"a1\",\"ok\":true}\n{\"id\":3,\"ok\":false}\ngarbage\n{\"id\":3,\"ok\":true}\n{\"id\":\"4\",\"ok\":false}\n";
+        let done = completed_ids(output);
+        assert!(done.contains(&json!("a1").to_string()));
+        assert!(done.contains(&json!(3).to_string()));
+        assert!(!done.contains(&json!("4").to_string()));
+        assert_eq!(done.len(), 2);
+
+        let summary = BatchSummary {
+            records: 5,
+            failed: 1,
+            ..BatchSummary::default()
+        };
+        assert_eq!(summary.exit_code(), EXIT_FAILED);
+        assert!(summary.report().contains("Run the same command again"));
+        assert_eq!(backoff(1), Duration::from_secs(2));
+        assert_eq!(backoff(9), MAX_BACKOFF);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::config::schema::QueryClassificationConfig;
+use chrono::NaiveTime;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ClassificationDecision {
@@ -20,41 +21,105 @@ pub fn classify(config: &QueryClassificationConfig, message: &str) -> Option<Str
 pub fn classify_with_decision(
     config: &QueryClassificationConfig,
     message: &str,
+) -> Option<ClassificationDecision> {
+    classify_request(config, &RouteContext::for_message(message))
+}
+
+/// What a model call is classified on.
+#[derive(Debug, Clone, Copy)]
+pub struct RouteContext<'a> {
+    /// The user message of the turn.
+    pub message: &'a str,
+    /// Tool calls already made in this turn.
+    pub tool_calls: usize,
+    /// Time of day in the user's timezone.
+    pub time: NaiveTime,
+}
+
+impl<'a> RouteContext<'a> {
+    /// Context for the first model call of a turn, at the user's current time.
+    pub fn for_message(message: &'a str) -> Self {
+        Self {
+            message,
+            tool_calls: 0,
+            time: crate::time::time_of_day(),
+        }
+    }
+}
+
+/// Classify one model call against the configured rules.
+///
+/// A rule matches when every condition it sets holds; keywords and patterns
+/// count as one condition that any of them satisfies. Rules that set no
+/// condition never match.
+pub fn classify_request(
+    config: &QueryClassificationConfig,
+    context: &RouteContext<'_>,
 ) -> Option<ClassificationDecision> {
     if !config.enabled || config.rules.is_empty() {
         return None;
     }
 
+    let message = context.message;
     let lower = message.to_lowercase();
     let len = message.len();
+    let has_code = looks_like_code(message);
 
     let mut rules: Vec<_> = config.rules.iter().collect();
     rules.sort_by(|a, b| b.priority.cmp(&a.priority));
 
     for rule in rules {
+        let mut conditions = 0;
+
         // Length constraints
         if let Some(min) = rule.min_length {
             if len < min {
                 continue;
             }
+            conditions += 1;
         }
         if let Some(max) = rule.max_length {
             if len > max {
                 continue;
             }
+            conditions += 1;
+        }
+        if let Some(code) = rule.code {
+            if code != has_code {
+                continue;
+            }
+            conditions += 1;
+        }
+        if let Some(min) = rule.min_tool_calls {
+            if context.tool_calls < min {
+                continue;
+            }
+            conditions += 1;
+        }
+        if let Some(hours) = rule.hours.as_deref() {
+            match parse_hours(hours) {
+                Some(window) if in_window(window, context.time) => conditions += 1,
+                _ => continue,
+            }
         }
 
         // Check keywords (case-insensitive) and patterns (case-sensitive)
-        let keyword_hit = rule
-            .keywords
-            .iter()
-            .any(|kw: &String| lower.contains(&kw.to_lowercase()));
-        let pattern_hit = rule
-            .patterns
-            .iter()
-            .any(|pat: &String| message.contains(pat.as_str()));
-
-        if keyword_hit || pattern_hit {
+        if !rule.keywords.is_empty() || !rule.patterns.is_empty() {
+            let keyword_hit = rule
+                .keywords
+                .iter()
+                .any(|kw: &String| lower.contains(&kw.to_lowercase()));
+            let pattern_hit = rule
+                .patterns
+                .iter()
+                .any(|pat: &String| message.contains(pat.as_str()));
+            if !keyword_hit && !pattern_hit {
+                continue;
+            }
+            conditions += 1;
+        }
+
+        if conditions > 0 {
             return Some(ClassificationDecision {
                 hint: rule.hint.clone(),
                 priority: rule.priority,
@@ -65,6 +130,68 @@ pub fn classify_with_decision(
     None
 }
 
+/// Whether a message contains code: a fenced block, or at least two lines
+/// that look like source code.
+pub fn looks_like_code(message: &str) -> bool {
+    const PREFIXES: &[&str] = &[
+        "fn ",
+        "pub fn ",
+        "def ",
+        "class ",
+        "import ",
+        "from ",
+        "#include",
+        "function ",
+        "const ",
+        "let ",
+        "var ",
+        "return ",
+        "if (",
+        "for (",
+        "SELECT ",
+        "$ ",
+    ];
+    if message.contains("```") {
+        return true;
+    }
+    message
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            line.ends_with(';')
+                || line.ends_with('{')
+                || *line == "}"
+                || PREFIXES.iter().any(|prefix| line.starts_with(prefix))
+        })
+        .count()
+        >= 2
+}
+
+/// Parse a local-time window such as `22-7` or `22:30-07:00`.
+pub fn parse_hours(window: &str) -> Option<(NaiveTime, NaiveTime)> {
+    fn parse_time(value: &str) -> Option<NaiveTime> {
+        let value = value.trim();
+        match value.split_once(':') {
+            Some((hour, minute)) => {
+                NaiveTime::from_hms_opt(hour.parse().ok()?, minute.parse().ok()?, 0)
+            }
+            None => NaiveTime::from_hms_opt(value.parse().ok()?, 0, 0),
+        }
+    }
+    let (start, end) = window.split_once('-')?;
+    Some((parse_time(start)?, parse_time(end)?))
+}
+
+/// Whether `time` falls in `[start, end)`, wrapping past midnight when
+/// `end` is earlier than `start`.
+fn in_window((start, end): (NaiveTime, NaiveTime), time: NaiveTime) -> bool {
+    if start <= end {
+        start <= time && time < end
+    } else {
+        time >= start || time < end
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,4 +340,83 @@ mod tests {
         assert_eq!(decision.hint, "code");
         assert_eq!(decision.priority, 10);
     }
+
+    #[test]
+    fn heuristic_conditions_route_without_keywords() {
+        let config = make_config(
+            true,
+            vec![
+                ClassificationRule {
+                    hint: "cheap".into(),
+                    hours: Some("22-7".into()),
+                    priority: 20,
+                    ..Default::default()
+                },
+                ClassificationRule {
+                    hint: "frontier".into(),
+                    min_tool_calls: Some(3),
+                    priority: 10,
+                    ..Default::default()
+                },
+                ClassificationRule {
+                    hint: "frontier".into(),
+                    code: Some(true),
+                    priority: 5,
+                    ..Default::default()
+                },
+                ClassificationRule {
+                    hint: "local".into(),
+                    max_length: Some(40),
+                    ..Default::default()
+                },
+            ],
+        );
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let at = |message, tool_calls, time| {
+            classify_request(
+                &config,
+                &RouteContext {
+                    message,
+                    tool_calls,
+                    time,
+                },
+            )
+            .map(|decision| decision.hint)
+        };
+
+        assert_eq!(at("hey, how are you?", 0, noon), Some("local".into()));
+        assert_eq!(
+            at("fix this:\n```rust\nfn main() {}\n```", 0, noon),
+            Some("frontier".into())
+        );
+        assert_eq!(at("hey", 3, noon), Some("frontier".into()));
+        let late = NaiveTime::from_hms_opt(23, 30, 0).unwrap();
+        assert_eq!(at("hey", 3, late), Some("cheap".into()));
+        assert_eq!(
+            at("tell me a long story about a dragon and a knight", 0, noon),
+            None
+        );
+    }
+
+    #[test]
+    fn code_detection_and_hour_windows() {
+        assert!(looks_like_code("let x = 1;\nlet y = 2;"));
+        assert!(!looks_like_code("Let me know; thanks"));
+
+        let window = parse_hours("22:30-07:00").unwrap();
+        assert!(in_window(
+            window,
+            NaiveTime::from_hms_opt(23, 0, 0).unwrap()
+        ));
+        assert!(in_window(
+            window,
+            NaiveTime::from_hms_opt(6, 59, 0).unwrap()
+        ));
+        assert!(!in_window(
+            window,
+            NaiveTime::from_hms_opt(12, 0, 0).unwrap()
+        ));
+        assert!(parse_hours("25-3").is_none());
+        assert!(parse_hours("evening").is_none());
+    }
 }
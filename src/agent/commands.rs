@@ -0,0 +1,631 @@
+//! Slash commands typed into a chat.
+//!
+//! The CLI, the TUI and channel adapters parse messages with [`parse`]
+//! before they reach the model, so `/model`, `/tools off browser`,
+//! `/memory search foo` and `/export` mean the same everywhere. Each front
+//! end runs the parsed command against its own session state; the helpers
+//! here hold the parts they share.
+
+use super::debug_dump::DebugDump;
+use super::dry_run::DryRun;
+use crate::memory::Memory;
+use crate::providers::ChatMessage;
+use crate::security::{roles, Role};
+use anyhow::{bail, Context, Result};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Matches returned by `/memory search`.
+const MEMORY_SEARCH_LIMIT: usize = 10;
+
+/// Workspace subdirectory `/export` writes to.
+const EXPORT_DIR: &str = "exports";
+
+/// A command as listed by `/help`.
+pub struct CommandSpec {
+    /// Canonical name, as returned by [`SlashCommand::name`].
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub summary: &'static str,
+    /// Forms offered by tab completion.
+    pub completions: &'static [&'static str],
+    /// Least privileged chat user role that may run it (`[security.roles]`).
+    pub min_role: Role,
+}
+
+/// Every slash command, in `/help` order.
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "/help",
+        usage: "/help",
+        summary: "Show this help",
+        completions: &["/help"],
+        min_role: Role::Guest,
+    },
+    CommandSpec {
+        name: "/new",
+        usage: "/new, /clear",
+        summary: "Start a fresh conversation",
+        completions: &["/new", "/clear"],
+        min_role: Role::Guest,
+    },
+    CommandSpec {
+        name: "/edit",
+        usage: "/edit [<text>]",
+        summary: "Compose the message in $EDITOR, starting from <text>",
+        completions: &["/edit"],
+        min_role: Role::Guest,
+    },
+    CommandSpec {
+        name: "/model",
+        usage: "/model [<id>]",
+        summary: "Show or switch the model",
+        completions: &["/model"],
+        min_role: Role::Member,
+    },
+    CommandSpec {
+        name: "/models",
+        usage: "/models [<provider>]",
+        summary: "List providers or switch provider",
+        completions: &["/models"],
+        min_role: Role::Member,
+    },
+    CommandSpec {
+        name: "/tools",
+        usage: "/tools [on|off <tool>...]",
+        summary: "List tools or turn them on or off for this session",
+        completions: &["/tools", "/tools on", "/tools off"],
+        min_role: Role::Member,
+    },
+    CommandSpec {
+        name: "/memory",
+        usage: "/memory search <query>",
+        summary: "Search memory",
+        completions: &["/memory search"],
+        min_role: Role::Member,
+    },
+    CommandSpec {
+        name: "/pin",
+        usage: "/pin [<text>]",
+        summary: "Keep <text>, or the last reply, in context for the whole session",
+        completions: &["/pin"],
+        min_role: Role::Member,
+    },
+    CommandSpec {
+        name: "/unpin",
+        usage: "/unpin <n>|all",
+        summary: "Remove a pin, or all of them",
+        completions: &["/unpin", "/unpin all"],
+        min_role: Role::Member,
+    },
+    CommandSpec {
+        name: "/context",
+        usage: "/context",
+        summary: "Show what occupies the context window and its token cost",
+        completions: &["/context"],
+        min_role: Role::Guest,
+    },
+    CommandSpec {
+        name: "/export",
+        usage: "/export [<file>]",
+        summary: "Save the conversation as markdown under exports/",
+        completions: &["/export"],
+        min_role: Role::Member,
+    },
+    CommandSpec {
+        name: "/budget",
+        usage: "/budget [override]",
+        summary: "Show usage against [budget] limits, or lift them",
+        completions: &["/budget", "/budget override"],
+        min_role: Role::Admin,
+    },
+    CommandSpec {
+        name: "/debug",
+        usage: "/debug [on|off]",
+        summary: "Dump each turn's prompt, tools and model responses to state/debug/",
+        completions: &["/debug", "/debug on", "/debug off"],
+        min_role: Role::Admin,
+    },
+    CommandSpec {
+        name: "/dryrun",
+        usage: "/dryrun [on|off]",
+        summary: "Have write-capable tools report what they would do instead of doing it",
+        completions: &["/dryrun", "/dryrun on", "/dryrun off"],
+        min_role: Role::Member,
+    },
+    CommandSpec {
+        name: "/undo",
+        usage: "/undo [list|<n>]",
+        summary: "Revert the files the last turn changed, or those of turn <n>",
+        completions: &["/undo", "/undo list"],
+        min_role: Role::Admin,
+    },
+    CommandSpec {
+        name: "/quit",
+        usage: "/quit, /exit",
+        summary: "Leave the chat",
+        completions: &["/quit", "/exit"],
+        min_role: Role::Guest,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolsCommand {
+    List,
+    Enable(Vec<String>),
+    Disable(Vec<String>),
+}
+
+/// `/undo`: the latest turn, the list, or turn `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoCommand {
+    /// Revert the latest turn that changed files.
+    Last,
+    /// List the turns that can be reverted.
+    List,
+    /// Revert the turn with this number.
+    Turn(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlashCommand {
+    Help,
+    /// `/new` or `/clear`.
+    New,
+    /// `/edit [<text>]`: compose the message in an editor (terminal only).
+    Edit(Option<String>),
+    /// `/model` shows the model, `/model <id>` switches to it.
+    Model(Option<String>),
+    /// `/models` lists providers, `/models <provider>` switches to it.
+    Provider(Option<String>),
+    Tools(ToolsCommand),
+    SearchMemory(String),
+    /// `/pin [<text>]`: pin `text`, or the last reply.
+    Pin(Option<String>),
+    /// `/unpin <n>` removes pin `n` (1-based), `/unpin all` removes all.
+    Unpin(Option<usize>),
+    Context,
+    /// `/export [<file>]`.
+    Export(Option<String>),
+    Budget {
+        override_limits: bool,
+    },
+    /// `/debug` shows whether prompt dumps are on, `/debug on|off` toggles them.
+    Debug(Option<bool>),
+    /// `/dryrun` shows whether dry run is on, `/dryrun on|off` toggles it.
+    DryRun(Option<bool>),
+    Undo(UndoCommand),
+    Quit,
+}
+
+/// Parse a chat message. `None` when it is not a known command (it goes to
+/// the model); `Err` carries the usage line for a malformed one. A Telegram
+/// style `@botname` suffix on the command is ignored.
+pub fn parse(content: &str) -> Option<std::result::Result<SlashCommand, String>> {
+    let trimmed = content.trim();
+    if !trimmed.starts_with('/') {
+        return None;
+    }
+    let mut parts = trimmed.split_whitespace();
+    let token = parts.next()?;
+    let name = token
+        .split('@')
+        .next()
+        .unwrap_or(token)
+        .to_ascii_lowercase();
+    let args: Vec<&str> = parts.collect();
+    let rest = (!args.is_empty()).then(|| args.join(" "));
+
+    let command = match name.as_str() {
+        "/help" => SlashCommand::Help,
+        "/new" | "/clear" => SlashCommand::New,
+        "/edit" => SlashCommand::Edit(rest),
+        "/model" => SlashCommand::Model(rest),
+        "/models" => SlashCommand::Provider(args.first().map(|p| (*p).to_string())),
+        "/tools" => {
+            let names: Vec<String> = args.iter().skip(1).map(|t| (*t).to_string()).collect();
+            match args.first().map(|a| a.to_ascii_lowercase()).as_deref() {
+                None | Some("list") => SlashCommand::Tools(ToolsCommand::List),
+                Some("on") if !names.is_empty() => SlashCommand::Tools(ToolsCommand::Enable(names)),
+                Some("off") if !names.is_empty() => {
+                    SlashCommand::Tools(ToolsCommand::Disable(names))
+                }
+                _ => return Some(Err(usage("/tools"))),
+            }
+        }
+        "/memory" => match args.split_first() {
+            Some((sub, query)) if sub.eq_ignore_ascii_case("search") && !query.is_empty() => {
+                SlashCommand::SearchMemory(query.join(" "))
+            }
+            _ => return Some(Err(usage("/memory"))),
+        },
+        "/pin" => SlashCommand::Pin(rest),
+        "/unpin" => match args.as_slice() {
+            [all] if all.eq_ignore_ascii_case("all") => SlashCommand::Unpin(None),
+            [n] => match n.trim_start_matches('#').parse() {
+                Ok(n) => SlashCommand::Unpin(Some(n)),
+                Err(_) => return Some(Err(usage("/unpin"))),
+            },
+            _ => return Some(Err(usage("/unpin"))),
+        },
+        "/context" => SlashCommand::Context,
+        "/export" => SlashCommand::Export(rest),
+        "/budget" => SlashCommand::Budget {
+            override_limits: args
+                .first()
+                .is_some_and(|arg| arg.eq_ignore_ascii_case("override")),
+        },
+        "/debug" => match args.first().map(|a| a.to_ascii_lowercase()).as_deref() {
+            None => SlashCommand::Debug(None),
+            Some("on") => SlashCommand::Debug(Some(true)),
+            Some("off") => SlashCommand::Debug(Some(false)),
+            _ => return Some(Err(usage("/debug"))),
+        },
+        "/dryrun" => match args.first().map(|a| a.to_ascii_lowercase()).as_deref() {
+            None => SlashCommand::DryRun(None),
+            Some("on") => SlashCommand::DryRun(Some(true)),
+            Some("off") => SlashCommand::DryRun(Some(false)),
+            _ => return Some(Err(usage("/dryrun"))),
+        },
+        "/undo" => match args.as_slice() {
+            [] => SlashCommand::Undo(UndoCommand::Last),
+            [list] if list.eq_ignore_ascii_case("list") => SlashCommand::Undo(UndoCommand::List),
+            [n] => match n.parse() {
+                Ok(n) => SlashCommand::Undo(UndoCommand::Turn(n)),
+                Err(_) => return Some(Err(usage("/undo"))),
+            },
+            _ => return Some(Err(usage("/undo"))),
+        },
+        "/quit" | "/exit" => SlashCommand::Quit,
+        _ => return None,
+    };
+    Some(Ok(command))
+}
+
+impl SlashCommand {
+    /// Canonical name of the command, e.g. `/new` for `/clear`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Help => "/help",
+            Self::New => "/new",
+            Self::Edit(_) => "/edit",
+            Self::Model(_) => "/model",
+            Self::Provider(_) => "/models",
+            Self::Tools(_) => "/tools",
+            Self::SearchMemory(_) => "/memory",
+            Self::Pin(_) => "/pin",
+            Self::Unpin(_) => "/unpin",
+            Self::Context => "/context",
+            Self::Export(_) => "/export",
+            Self::Budget { .. } => "/budget",
+            Self::Debug(_) => "/debug",
+            Self::DryRun(_) => "/dryrun",
+            Self::Undo(_) => "/undo",
+            Self::Quit => "/quit",
+        }
+    }
+}
+
+fn usage(name: &str) -> String {
+    let spec = COMMANDS
+        .iter()
+        .find(|spec| spec.name == name)
+        .map_or(name, |spec| spec.usage);
+    format!("Usage: {spec}")
+}
+
+/// Refuse `command` for a user with `role` when it needs a higher one.
+/// Returns the reply.
+pub fn check_role(command: &SlashCommand, role: Role) -> std::result::Result<(), String> {
+    let name = command.name();
+    let declared = COMMANDS
+        .iter()
+        .find(|spec| spec.name == name)
+        .map_or(Role::Admin, |spec| spec.min_role);
+    let required = roles::command_min_role(name, declared);
+    if role >= required {
+        Ok(())
+    } else {
+        Err(format!("⛔ `{name}` requires the {required} role."))
+    }
+}
+
+/// The `/help` listing.
+pub fn help_text() -> String {
+    let width = COMMANDS.iter().map(|c| c.usage.len()).max().unwrap_or(0);
+    let mut text = format!("{}\n", crate::i18n::t("help-header"));
+    for command in COMMANDS {
+        let summary = crate::i18n::t_or(
+            &format!("command-{}", command.name.trim_start_matches('/')),
+            command.summary,
+        );
+        let _ = writeln!(text, "  {:width$}  {summary}", command.usage);
+    }
+    text
+}
+
+/// Forms tab completion offers.
+pub fn completions() -> Vec<&'static str> {
+    COMMANDS
+        .iter()
+        .flat_map(|command| command.completions.iter().copied())
+        .collect()
+}
+
+/// Apply `/tools` to a session's `disabled` list; `available` are the tool
+/// names the session can use. Returns the reply.
+pub fn toggle_tools(
+    command: &ToolsCommand,
+    disabled: &mut Vec<String>,
+    available: &[&str],
+) -> String {
+    let (names, enable) = match command {
+        ToolsCommand::List => {
+            let enabled: Vec<&str> = available
+                .iter()
+                .copied()
+                .filter(|name| !disabled.iter().any(|d| d == name))
+                .collect();
+            let mut reply = format!("Enabled tools ({}): {}", enabled.len(), enabled.join(", "));
+            if !disabled.is_empty() {
+                let _ = write!(reply, "\nTurned off: {}", disabled.join(", "));
+            }
+            return reply;
+        }
+        ToolsCommand::Enable(names) => (names, true),
+        ToolsCommand::Disable(names) => (names, false),
+    };
+    let unknown: Vec<&str> = names
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !available.contains(name))
+        .collect();
+    if !unknown.is_empty() {
+        return format!(
+            "Unknown tool: {}. `/tools` lists the available ones.",
+            unknown.join(", ")
+        );
+    }
+    for name in names {
+        disabled.retain(|d| d != name);
+        if !enable {
+            disabled.push(name.clone());
+        }
+    }
+    format!(
+        "Turned {} for this session: {}",
+        if enable { "on" } else { "off" },
+        names.join(", ")
+    )
+}
+
+/// Apply `/debug` to a session's prompt dump. Returns the reply.
+pub fn toggle_debug(dump: &DebugDump, toggle: Option<bool>) -> String {
+    if let Some(enabled) = toggle {
+        dump.set_enabled(enabled);
+    }
+    if dump.is_enabled() {
+        format!(
+            "Debug dumps are on: each turn's prompt, tools and model responses go to {}",
+            dump.session_dir().display()
+        )
+    } else {
+        "Debug dumps are off. Turn them on with `/debug on`.".into()
+    }
+}
+
+/// Apply `/dryrun` to a session. Returns the reply.
+pub fn toggle_dry_run(dry_run: &DryRun, toggle: Option<bool>) -> String {
+    if let Some(enabled) = toggle {
+        dry_run.set_enabled(enabled);
+    }
+    if dry_run.is_enabled() {
+        "Dry run is on: file writes, shell commands and other tools that change things report \
+         what they would do instead of doing it. Turn it off with `/dryrun off` to let them run."
+            .into()
+    } else {
+        "Dry run is off. Turn it on with `/dryrun on`.".into()
+    }
+}
+
+/// `/memory search`: entries matching `query` in the current memory scope.
+pub async fn search_memory(mem: &dyn Memory, query: &str) -> String {
+    match mem.recall(query, MEMORY_SEARCH_LIMIT, None).await {
+        Ok(entries) if entries.is_empty() => format!("No memories match \"{query}\"."),
+        Ok(entries) => {
+            let mut reply = format!("Memories matching \"{query}\":");
+            for entry in entries {
+                let _ = write!(
+                    reply,
+                    "\n- {} [{}]: {}",
+                    entry.key,
+                    entry.category,
+                    crate::util::truncate_with_ellipsis(&entry.content, 200)
+                );
+            }
+            reply
+        }
+        Err(e) => format!("Memory search failed: {e}"),
+    }
+}
+
+/// `/export`: write the user and assistant turns of `messages` as markdown,
+/// headed by the session `title`, to `<workspace>/exports/<file>` and return
+/// the path. `file` must be a plain file name; it defaults to a timestamped
+/// one.
+pub fn export_transcript(
+    messages: &[ChatMessage],
+    title: Option<&str>,
+    workspace_dir: &Path,
+    file: Option<&str>,
+) -> Result<PathBuf> {
+    let now = chrono::Local::now();
+    let file = match file {
+        Some(file) => {
+            if Path::new(file).file_name().and_then(|n| n.to_str()) != Some(file) {
+                bail!("Export name must be a plain file name, not a path");
+            }
+            if Path::new(file).extension().is_some() {
+                file.to_string()
+            } else {
+                format!("{file}.md")
+            }
+        }
+        None => format!("chat-{}.md", now.format("%Y%m%d-%H%M%S")),
+    };
+
+    let mut out = format!(
+        "# {}\n\n_Exported {}_\n",
+        title.unwrap_or("ZeroClaw conversation"),
+        now.format("%Y-%m-%d %H:%M %Z")
+    );
+    for message in messages {
+        let heading = match message.role.as_str() {
+            "user" => "You",
+            "assistant" => "ZeroClaw",
+            _ => continue,
+        };
+        let _ = write!(out, "\n## {heading}\n\n{}\n", message.content.trim());
+    }
+
+    let dir = workspace_dir.join(EXPORT_DIR);
+    std::fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+    let path = dir.join(file);
+    std::fs::write(&path, out).with_context(|| format!("writing {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands_and_reports_usage() {
+        assert_eq!(parse("hello"), None);
+        assert_eq!(parse("/unknown thing"), None);
+        assert_eq!(parse("/clear"), Some(Ok(SlashCommand::New)));
+        assert_eq!(
+            parse("/model@zeroclaw_bot gpt-4o"),
+            Some(Ok(SlashCommand::Model(Some("gpt-4o".into()))))
+        );
+        assert_eq!(
+            parse("/tools off browser shell"),
+            Some(Ok(SlashCommand::Tools(ToolsCommand::Disable(vec![
+                "browser".into(),
+                "shell".into()
+            ]))))
+        );
+        assert_eq!(
+            parse("/memory search coffee order"),
+            Some(Ok(SlashCommand::SearchMemory("coffee order".into())))
+        );
+        assert_eq!(
+            parse("/pin Deploys go through staging"),
+            Some(Ok(SlashCommand::Pin(Some(
+                "Deploys go through staging".into()
+            ))))
+        );
+        assert_eq!(parse("/unpin #2"), Some(Ok(SlashCommand::Unpin(Some(2)))));
+        assert_eq!(parse("/unpin ALL"), Some(Ok(SlashCommand::Unpin(None))));
+        assert_eq!(parse("/unpin"), Some(Err("Usage: /unpin <n>|all".into())));
+        assert_eq!(
+            parse("/budget override"),
+            Some(Ok(SlashCommand::Budget {
+                override_limits: true
+            }))
+        );
+        assert_eq!(
+            parse("/debug ON"),
+            Some(Ok(SlashCommand::Debug(Some(true))))
+        );
+        assert_eq!(
+            parse("/debug loud"),
+            Some(Err("Usage: /debug [on|off]".into()))
+        );
+        assert_eq!(parse("/dryrun"), Some(Ok(SlashCommand::DryRun(None))));
+        assert_eq!(
+            parse("/dryrun off"),
+            Some(Ok(SlashCommand::DryRun(Some(false))))
+        );
+        assert_eq!(
+            parse("/undo"),
+            Some(Ok(SlashCommand::Undo(UndoCommand::Last)))
+        );
+        assert_eq!(
+            parse("/undo 3"),
+            Some(Ok(SlashCommand::Undo(UndoCommand::Turn(3))))
+        );
+        assert_eq!(
+            parse("/undo everything"),
+            Some(Err("Usage: /undo [list|<n>]".into()))
+        );
+        assert_eq!(
+            parse("/memory"),
+            Some(Err("Usage: /memory search <query>".into()))
+        );
+        assert_eq!(
+            parse("/tools off"),
+            Some(Err("Usage: /tools [on|off <tool>...]".into()))
+        );
+        assert!(completions().contains(&"/tools off"));
+    }
+
+    #[test]
+    fn commands_declare_minimum_roles() {
+        assert!(check_role(&SlashCommand::Help, Role::Guest).is_ok());
+        assert!(check_role(&SlashCommand::Model(None), Role::Guest).is_err());
+        assert!(check_role(&SlashCommand::Model(None), Role::Member).is_ok());
+        assert_eq!(
+            check_role(&SlashCommand::Debug(Some(true)), Role::Member),
+            Err("⛔ `/debug` requires the admin role.".into())
+        );
+        assert!(COMMANDS
+            .iter()
+            .all(|spec| spec.usage.starts_with(spec.name)));
+    }
+
+    #[test]
+    fn toggle_tools_tracks_disabled_names() {
+        let available = ["shell", "browser", "memory_store"];
+        let mut disabled = Vec::new();
+        let reply = toggle_tools(
+            &ToolsCommand::Disable(vec!["browser".into()]),
+            &mut disabled,
+            &available,
+        );
+        assert_eq!(reply, "Turned off for this session: browser");
+        assert_eq!(disabled, ["browser"]);
+        assert!(toggle_tools(&ToolsCommand::List, &mut disabled, &available)
+            .starts_with("Enabled tools (2): shell, memory_store"));
+        assert!(toggle_tools(
+            &ToolsCommand::Disable(vec!["nope".into()]),
+            &mut disabled,
+            &available
+        )
+        .starts_with("Unknown tool: nope"));
+        toggle_tools(
+            &ToolsCommand::Enable(vec!["browser".into()]),
+            &mut disabled,
+            &available,
+        );
+        assert!(disabled.is_empty());
+    }
+
+    #[test]
+    fn export_writes_markdown_under_the_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let messages = [
+            ChatMessage::system("prompt"),
+            ChatMessage::user("hi"),
+            ChatMessage::assistant("hello"),
+        ];
+        let path =
+            export_transcript(&messages, Some("Greetings"), dir.path(), Some("notes")).unwrap();
+        assert_eq!(path, dir.path().join("exports/notes.md"));
+        let text = std::fs::read_to_string(path).unwrap();
+        assert!(text.starts_with("# Greetings\n"));
+        assert!(text.contains("## You\n\nhi\n"));
+        assert!(!text.contains("prompt"));
+        assert!(export_transcript(&messages, None, dir.path(), Some("../escape")).is_err());
+    }
+}
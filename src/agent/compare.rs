@@ -0,0 +1,388 @@
+//! `zeroclaw compare`: one conversation, several models, answers side by side.
+//!
+//! Each model gets its own agent — same system prompt, tools and, with
+//! `--session`, the same stored conversation — and all of them run at once.
+//! Write-capable tools run as a dry run ([`super::dry_run`]) so the models do
+//! not each make the change. The answers are shown in columns sized to the
+//! terminal, each headed by the model's latency, tokens and estimated cost.
+
+use super::loop_::CliRuntime;
+use super::pipe::{PipeUsage, UsageObserver, EXIT_FAILED, EXIT_OK};
+use crate::config::{Config, GuardrailDirection};
+use crate::cost::TokenUsage;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Instant;
+use unicode_width::UnicodeWidthStr;
+
+/// Narrowest column; below it the answers are shown one after another.
+const MIN_COLUMN_WIDTH: usize = 24;
+/// Width used when stdout is not a terminal.
+const DEFAULT_WIDTH: usize = 120;
+const SEPARATOR: &str = " │ ";
+
+/// What `zeroclaw compare` was asked to do.
+#[derive(Debug, Clone)]
+pub struct CompareOptions {
+    /// The prompt; `-` reads it from stdin.
+    pub prompt: Vec<String>,
+    /// `model` or `provider:model` entries.
+    pub models: Vec<String>,
+    pub session: Option<String>,
+    pub temperature: f64,
+}
+
+/// A `--models` entry.
+#[derive(Debug, PartialEq, Eq)]
+struct Target {
+    provider: Option<String>,
+    model: String,
+}
+
+/// Split `provider:model` when the prefix is a known provider, so model
+/// names with colons (`llama3:8b`) stay whole.
+fn parse_target(spec: &str) -> Target {
+    let spec = spec.trim();
+    if let Some((provider, model)) = spec.split_once(':') {
+        let known = crate::providers::list_providers()
+            .iter()
+            .any(|info| info.name == provider || info.aliases.contains(&provider));
+        if known && !model.is_empty() {
+            return Target {
+                provider: Some(provider.to_string()),
+                model: model.to_string(),
+            };
+        }
+    }
+    Target {
+        provider: None,
+        model: spec.to_string(),
+    }
+}
+
+/// One model's answer.
+#[derive(Debug, Serialize)]
+struct Answer {
+    /// The `--models` entry.
+    target: String,
+    provider: String,
+    model: String,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    usage: PipeUsage,
+    cost_usd: f64,
+    duration_ms: u64,
+}
+
+impl Answer {
+    fn stats(&self) -> String {
+        format!(
+            "{:.1}s · {}/{} tokens · ${:.4}",
+            self.duration_ms as f64 / 1000.0,
+            self.usage.input_tokens,
+            self.usage.output_tokens,
+            self.cost_usd
+        )
+    }
+
+    fn body(&self) -> String {
+        match (&self.answer, &self.error) {
+            (Some(answer), _) => answer.trim().to_string(),
+            (None, Some(error)) => format!("Error: {error}"),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// The answers of a `zeroclaw compare` run, in `--models` order.
+#[derive(Debug, Serialize)]
+pub struct CompareReport {
+    answers: Vec<Answer>,
+}
+
+impl CompareReport {
+    pub fn exit_code(&self) -> i32 {
+        if self.answers.iter().all(|answer| answer.ok) {
+            EXIT_OK
+        } else {
+            EXIT_FAILED
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Side by side in `width` columns, or one after another when the
+    /// columns would be too narrow.
+    pub fn render(&self, width: usize) -> String {
+        let count = self.answers.len().max(1);
+        let column = width.saturating_sub(SEPARATOR.width() * (count - 1)) / count;
+        if column < MIN_COLUMN_WIDTH || count == 1 {
+            return self.render_stacked(width);
+        }
+
+        let blocks: Vec<Vec<String>> = self
+            .answers
+            .iter()
+            .map(|answer| {
+                let mut lines = crate::tui::ui::wrap(&answer.target, column);
+                lines.extend(crate::tui::ui::wrap(&answer.stats(), column));
+                lines.push("─".repeat(column));
+                lines.extend(crate::tui::ui::wrap(&answer.body(), column));
+                lines
+            })
+            .collect();
+        let header_rows = blocks
+            .iter()
+            .map(|lines| {
+                lines
+                    .iter()
+                    .position(|line| line.starts_with('─'))
+                    .unwrap_or(0)
+            })
+            .max()
+            .unwrap_or(0);
+        // Line the rules up when some headers wrapped onto more rows.
+        let blocks: Vec<Vec<String>> = blocks
+            .into_iter()
+            .map(|mut lines| {
+                let rule = lines
+                    .iter()
+                    .position(|line| line.starts_with('─'))
+                    .unwrap_or(0);
+                for _ in rule..header_rows {
+                    lines.insert(rule, String::new());
+                }
+                lines
+            })
+            .collect();
+
+        let rows = blocks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut out = String::new();
+        for row in 0..rows {
+            let cells: Vec<String> = blocks
+                .iter()
+                .map(|lines| {
+                    let cell = lines.get(row).map_or("", String::as_str);
+                    let pad = column.saturating_sub(cell.width());
+                    format!("{cell}{}", " ".repeat(pad))
+                })
+                .collect();
+            let separator = if row == header_rows {
+                "─┼─"
+            } else {
+                SEPARATOR
+            };
+            out.push_str(cells.join(separator).trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    fn render_stacked(&self, width: usize) -> String {
+        let mut out = String::new();
+        for (index, answer) in self.answers.iter().enumerate() {
+            if index > 0 {
+                out.push('\n');
+            }
+            let _ = writeln!(out, "{} ({})", answer.target, answer.stats());
+            let _ = writeln!(out, "{}", "─".repeat(width.clamp(1, 80)));
+            for line in crate::tui::ui::wrap(&answer.body(), width) {
+                let _ = writeln!(out, "{line}");
+            }
+        }
+        out
+    }
+}
+
+/// Width of the terminal on stdout.
+pub fn terminal_width() -> usize {
+    console::Term::stdout()
+        .size_checked()
+        .map_or(DEFAULT_WIDTH, |(_, cols)| usize::from(cols))
+}
+
+/// Ask `target` the prompt.
+async fn ask(config: Config, options: &CompareOptions, spec: &str, prompt: &str) -> Answer {
+    let started = Instant::now();
+    let target = parse_target(spec);
+    let mut answer = Answer {
+        target: spec.trim().to_string(),
+        provider: target.provider.clone().unwrap_or_default(),
+        model: target.model.clone(),
+        ok: false,
+        answer: None,
+        error: None,
+        usage: PipeUsage::default(),
+        cost_usd: 0.0,
+        duration_ms: 0,
+    };
+    let mut runtime = match CliRuntime::new(
+        config,
+        target.provider,
+        Some(target.model),
+        options.temperature,
+        Vec::new(),
+        false,
+        options.session.as_deref(),
+    )
+    .await
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            answer.error = Some(format!("{e:#}"));
+            return answer;
+        }
+    };
+    answer.provider.clone_from(&runtime.provider_name);
+    let usage = Arc::new(UsageObserver::new(Arc::clone(&runtime.observer)));
+    runtime.observer = usage.clone();
+
+    let started_turn = Instant::now();
+    let reply = match runtime
+        .guard(GuardrailDirection::Input, prompt.to_string())
+        .await
+    {
+        Err(blocked) => Err(anyhow::anyhow!(blocked)),
+        Ok(prompt) => {
+            let mut history = runtime.initial_history();
+            runtime.prepare_turn(&mut history, &prompt).await;
+            match runtime.run_turn(&mut history, &prompt, None, None).await {
+                Ok(reply) => Ok(runtime
+                    .guard(GuardrailDirection::Output, reply)
+                    .await
+                    .unwrap_or_else(|blocked| blocked)),
+                Err(e) => Err(e),
+            }
+        }
+    };
+    answer.duration_ms = u64::try_from(started_turn.elapsed().as_millis()).unwrap_or(u64::MAX);
+    runtime.finish(started);
+
+    answer.usage = usage.usage();
+    let (input_price, output_price) =
+        crate::cost::budget::price_for(&runtime.config.cost.prices, &runtime.model_name)
+            .map_or((0.0, 0.0), |price| (price.input, price.output));
+    answer.cost_usd = TokenUsage::new(
+        &runtime.model_name,
+        answer.usage.input_tokens,
+        answer.usage.output_tokens,
+        input_price,
+        output_price,
+    )
+    .cost_usd;
+    match reply {
+        Ok(reply) => {
+            answer.ok = true;
+            answer.answer = Some(reply);
+        }
+        Err(e) => answer.error = Some(format!("{e:#}")),
+    }
+    answer
+}
+
+/// Ask every model at once. A model that fails shows its error in its
+/// column; `Err` means nothing could be asked.
+pub async fn run(mut config: Config, options: CompareOptions) -> Result<CompareReport> {
+    let prompt = super::pipe::read_prompt(&options.prompt)?;
+    let specs: Vec<&String> = options
+        .models
+        .iter()
+        .filter(|spec| !spec.trim().is_empty())
+        .collect();
+    if specs.is_empty() {
+        bail!("--models needs at least one model");
+    }
+
+    config.agent.render_markdown = false;
+    config.agent.dry_run = true;
+    config.memory.auto_save = false;
+    config.memory.fact_extraction = false;
+    let answers = futures_util::future::join_all(
+        specs
+            .iter()
+            .map(|spec| ask(config.clone(), &options, spec, &prompt)),
+    )
+    .await;
+    Ok(CompareReport { answers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(target: &str, text: &str) -> Answer {
+        Answer {
+            target: target.into(),
+            provider: "openrouter".into(),
+            model: target.into(),
+            ok: true,
+            answer: Some(text.into()),
+            error: None,
+            usage: PipeUsage {
+                input_tokens: 100,
+                output_tokens: 20,
+                total_tokens: 120,
+                llm_calls: 1,
+            },
+            cost_usd: 0.0012,
+            duration_ms: 1500,
+        }
+    }
+
+    #[test]
+    fn targets_split_only_on_known_providers() {
+        assert_eq!(
+            parse_target("anthropic:claude-sonnet-4"),
+            Target {
+                provider: Some("anthropic".into()),
+                model: "claude-sonnet-4".into(),
+            }
+        );
+        assert_eq!(parse_target("ollama:llama3:8b").model, "llama3:8b");
+        assert_eq!(
+            parse_target(" llama3:8b "),
+            Target {
+                provider: None,
+                model: "llama3:8b".into(),
+            }
+        );
+        assert_eq!(parse_target("openai/gpt-4o").provider, None);
+    }
+
+    #[test]
+    fn answers_render_in_aligned_columns_or_stacked() {
+        let mut failed = answer("gpt-4o", "");
+        failed.ok = false;
+        failed.answer = None;
+        failed.error = Some("Missing API key".into());
+        let report = CompareReport {
+            answers: vec![
+                answer("claude-sonnet", "Paris is the capital of France."),
+                failed,
+            ],
+        };
+        assert_eq!(report.exit_code(), EXIT_FAILED);
+
+        let text = report.render(80);
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("claude-sonnet") && lines[0].ends_with("gpt-4o"));
+        assert!(lines[1].contains("1.5s · 100/20 tokens · $0.0012"));
+        assert!(lines[2].contains("─┼─"));
+        assert!(lines[3].starts_with("Paris is the capital of France."));
+        assert!(lines[3].ends_with("│ Error: Missing API key"));
+        let column = (80 - SEPARATOR.width()) / 2;
+        assert_eq!(lines[3].find('│'), Some(column + 1), "{text}");
+        assert!(text.lines().all(|line| line.width() <= 80));
+
+        let narrow = report.render(40);
+        assert!(narrow.starts_with("claude-sonnet (1.5s"), "{narrow}");
+        assert!(narrow.contains("\ngpt-4o (1.5s"));
+    }
+}
@@ -0,0 +1,265 @@
+//! The context window: what each turn sends to the model.
+//!
+//! Messages and facts pinned with `/pin` ride in the system message, which
+//! compaction and trimming never touch, so they stay in the window for the
+//! whole session. Everything else is history: it is compacted and trimmed
+//! to `[agent] max_history_messages` and, when set,
+//! `[agent] max_context_tokens` ([`trim_to_token_budget`]). `/context`
+//! shows what occupies the window with [`report`].
+
+use super::tool_output::estimate_tokens;
+use crate::providers::ChatMessage;
+use crate::util::truncate_with_ellipsis;
+use std::fmt::Write;
+
+/// Session metadata key pins are stored under.
+pub const PINS_METADATA_KEY: &str = "pins";
+
+/// Pins one session may hold.
+const MAX_PINS: usize = 20;
+
+/// Cap on one pin, in characters.
+const MAX_PIN_CHARS: usize = 2_000;
+
+/// Characters of each entry shown by `/context`.
+const REPORT_PREVIEW_CHARS: usize = 60;
+
+const PINNED_HEADER: &str = "## Pinned context\n\nThe user pinned these notes. Keep them in mind for the whole conversation.\n";
+
+/// The pinned messages and facts of one session, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pins(Vec<String>);
+
+impl Pins {
+    /// Pins stored in a session's metadata object.
+    pub fn from_metadata(metadata: &serde_json::Value) -> Self {
+        let pins = metadata
+            .get(PINS_METADATA_KEY)
+            .and_then(serde_json::Value::as_array)
+            .map(|pins| {
+                pins.iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self(pins)
+    }
+
+    /// The value to store under [`PINS_METADATA_KEY`].
+    pub fn to_metadata(&self) -> serde_json::Value {
+        serde_json::json!(self.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// `system_prompt` with the pins appended.
+    pub fn apply(&self, system_prompt: &str) -> String {
+        if self.0.is_empty() {
+            return system_prompt.to_string();
+        }
+        let mut prompt = format!("{}\n\n{PINNED_HEADER}", system_prompt.trim_end());
+        for pin in &self.0 {
+            let _ = write!(prompt, "\n- {}", pin.trim());
+        }
+        prompt
+    }
+
+    /// `/pin [<text>]`: pin `text`, or the last reply in `history` when it is
+    /// `None`. Returns the reply.
+    pub fn pin(&mut self, text: Option<&str>, history: &[ChatMessage]) -> String {
+        let text = match text.map(str::trim).filter(|text| !text.is_empty()) {
+            Some(text) => text.to_string(),
+            None => match history.iter().rev().find(|m| m.role == "assistant") {
+                Some(reply) => reply.content.trim().to_string(),
+                None => return "Nothing to pin yet. Use `/pin <text>` to pin a fact.".into(),
+            },
+        };
+        if self.0.len() >= MAX_PINS {
+            return format!("You already have {MAX_PINS} pins. Remove one with `/unpin <n>`.");
+        }
+        let text = truncate_with_ellipsis(&text, MAX_PIN_CHARS);
+        let reply = format!(
+            "📌 Pinned #{} ({} tokens): {}",
+            self.0.len() + 1,
+            estimate_tokens(&text),
+            truncate_with_ellipsis(&text, REPORT_PREVIEW_CHARS)
+        );
+        self.0.push(text);
+        reply
+    }
+
+    /// `/unpin <n>|all`: remove pin `n` (1-based), or all with `None`.
+    /// Returns the reply.
+    pub fn unpin(&mut self, index: Option<usize>) -> String {
+        match index {
+            None => {
+                let count = self.0.len();
+                self.0.clear();
+                format!("Removed {count} pin(s).")
+            }
+            Some(n) if (1..=self.0.len()).contains(&n) => {
+                let pin = self.0.remove(n - 1);
+                format!(
+                    "Unpinned #{n}: {}",
+                    truncate_with_ellipsis(&pin, REPORT_PREVIEW_CHARS)
+                )
+            }
+            Some(n) => format!("There is no pin #{n}. `/context` lists them."),
+        }
+    }
+}
+
+/// Estimated tokens of `history`.
+pub fn history_tokens(history: &[ChatMessage]) -> usize {
+    history.iter().map(|m| estimate_tokens(&m.content)).sum()
+}
+
+/// Drop the oldest messages after the system message until `history` fits
+/// in `max_tokens`; the newest message always stays. `0` means no budget.
+/// Returns how many were dropped.
+pub fn trim_to_token_budget(history: &mut Vec<ChatMessage>, max_tokens: usize) -> usize {
+    if max_tokens == 0 {
+        return 0;
+    }
+    let start = usize::from(history.first().is_some_and(|m| m.role == "system"));
+    let mut total = history_tokens(history);
+    let mut dropped = 0;
+    while total > max_tokens && start + dropped + 1 < history.len() {
+        total -= estimate_tokens(&history[start + dropped].content);
+        dropped += 1;
+    }
+    // A turn must not start with a tool result or an orphaned reply.
+    while start + dropped + 1 < history.len() && history[start + dropped].role != "user" {
+        dropped += 1;
+    }
+    history.drain(start..start + dropped);
+    dropped
+}
+
+/// Whether `history` is over `max_tokens` (`0` means no budget).
+pub fn over_token_budget(history: &[ChatMessage], max_tokens: usize) -> bool {
+    max_tokens > 0 && history_tokens(history) > max_tokens
+}
+
+/// `/context`: what occupies the window, with estimated token costs.
+/// `system_prompt` is the prompt without pins; `history` excludes the
+/// system message.
+pub fn report(
+    system_prompt: &str,
+    pins: &Pins,
+    history: &[ChatMessage],
+    max_tokens: usize,
+) -> String {
+    let history: Vec<&ChatMessage> = history.iter().filter(|m| m.role != "system").collect();
+    let system_tokens = estimate_tokens(system_prompt);
+    let pinned_tokens: usize = pins.iter().map(estimate_tokens).sum();
+    let history_tokens: usize = history.iter().map(|m| estimate_tokens(&m.content)).sum();
+    let total = system_tokens + pinned_tokens + history_tokens;
+
+    let mut out = String::from("Context window (estimated tokens):\n");
+    let _ = writeln!(out, "  System prompt     {system_tokens:>7}");
+    let _ = writeln!(
+        out,
+        "  Pinned ({:>2})       {pinned_tokens:>7}   never evicted",
+        pins.0.len()
+    );
+    for (n, pin) in pins.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "    #{} {} ({})",
+            n + 1,
+            truncate_with_ellipsis(&pin.replace('\n', " "), REPORT_PREVIEW_CHARS),
+            estimate_tokens(pin)
+        );
+    }
+    let _ = writeln!(
+        out,
+        "  History ({:>3})    {history_tokens:>7}   oldest trimmed or summarized first",
+        history.len()
+    );
+    if let Some(oldest) = history.first() {
+        let _ = writeln!(
+            out,
+            "    oldest: {}: {}",
+            oldest.role,
+            truncate_with_ellipsis(&oldest.content.replace('\n', " "), REPORT_PREVIEW_CHARS)
+        );
+    }
+    if max_tokens > 0 {
+        let _ = write!(
+            out,
+            "  Total             {total:>7} of {max_tokens} ({}%)",
+            total * 100 / max_tokens
+        );
+    } else {
+        let _ = write!(
+            out,
+            "  Total             {total:>7} (no token budget; [agent] max_context_tokens)"
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pins_ride_in_the_system_prompt_and_round_trip_metadata() {
+        let history = [
+            ChatMessage::user("hi"),
+            ChatMessage::assistant("Use port 8443."),
+        ];
+        let mut pins = Pins::default();
+        assert_eq!(pins.apply("sys"), "sys");
+        assert!(pins.pin(None, &history).starts_with("📌 Pinned #1"));
+        pins.pin(Some("  Deploys go through staging  "), &history);
+        let prompt = pins.apply("sys\n");
+        assert!(prompt.starts_with("sys\n\n## Pinned context"));
+        assert!(prompt.ends_with("- Use port 8443.\n- Deploys go through staging"));
+
+        let restored = Pins::from_metadata(&serde_json::json!({ "pins": pins.to_metadata() }));
+        assert_eq!(restored, pins);
+        assert!(pins.unpin(Some(3)).starts_with("There is no pin #3"));
+        assert!(pins.unpin(Some(1)).starts_with("Unpinned #1: Use port"));
+        assert_eq!(pins.unpin(None), "Removed 1 pin(s).");
+        assert!(pins.is_empty());
+    }
+
+    #[test]
+    fn token_budget_drops_oldest_whole_turns() {
+        let mut history = vec![
+            ChatMessage::system("s"),
+            ChatMessage::user(&"a".repeat(400)),
+            ChatMessage::assistant(&"b".repeat(400)),
+            ChatMessage::user("latest"),
+        ];
+        assert!(over_token_budget(&history, 150));
+        assert_eq!(trim_to_token_budget(&mut history, 150), 2);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].content, "latest");
+        assert_eq!(trim_to_token_budget(&mut history, 0), 0);
+    }
+
+    #[test]
+    fn report_lists_pins_and_budget_use() {
+        let mut pins = Pins::default();
+        pins.pin(Some("Prefers metric units"), &[]);
+        let history = [
+            ChatMessage::user("hello"),
+            ChatMessage::assistant("hi there"),
+        ];
+        let text = report("You are helpful.", &pins, &history, 100);
+        assert!(text.contains("Pinned ( 1)"));
+        assert!(text.contains("#1 Prefers metric units (5)"));
+        assert!(text.contains("History (  2)"));
+        assert!(text.contains("of 100 ("));
+    }
+}
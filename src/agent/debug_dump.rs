@@ -0,0 +1,297 @@
+//! Per-turn prompt dumps (`--debug-prompts`, `/debug on`).
+//!
+//! While enabled, every turn of a session gets a directory
+//! `state/debug/<session>/<timestamp>/` holding exactly what the model saw
+//! and said: the rendered system prompt, the tool schemas offered, and for
+//! each model call the message array sent and the response (or error) that
+//! came back. Nothing is scrubbed, so the files can contain secrets that
+//! reached the conversation.
+
+use super::replay::RecordedResponse;
+use crate::providers::traits::{ChatMessage, ChatResponse};
+use crate::tools::ToolSpec;
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+tokio::task_local! {
+    static CURRENT_DUMP: Arc<DebugDump>;
+}
+
+/// Run `future` with every agent loop inside it dumped to `dump` while the
+/// dump is enabled.
+pub async fn run_with<F: Future>(dump: Option<Arc<DebugDump>>, future: F) -> F::Output {
+    match dump {
+        Some(dump) => CURRENT_DUMP.scope(dump, future).await,
+        None => future.await,
+    }
+}
+
+/// The dump of the running task, if one is set and enabled.
+pub fn current() -> Option<Arc<DebugDump>> {
+    CURRENT_DUMP
+        .try_with(Arc::clone)
+        .ok()
+        .filter(|dump| dump.is_enabled())
+}
+
+/// Directory holding the dumps of a workspace.
+pub fn debug_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join("debug")
+}
+
+/// What was sent for one model call.
+#[derive(Serialize)]
+struct DumpedRequest<'a> {
+    provider: &'a str,
+    model: &'a str,
+    temperature: f64,
+    /// Whether the tool schemas went out as native tool specs rather than
+    /// being described in the system prompt.
+    native_tools: bool,
+    messages: &'a [ChatMessage],
+}
+
+/// What came back from one model call.
+#[derive(Serialize)]
+struct DumpedResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response: Option<RecordedResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Writes one session's turns under `state/debug/<session>/`.
+pub struct DebugDump {
+    session_dir: PathBuf,
+    enabled: AtomicBool,
+    /// Directory of the turn in progress.
+    turn_dir: Mutex<Option<PathBuf>>,
+    /// Model calls made so far in the turn in progress.
+    calls: AtomicUsize,
+    /// Nesting of agent loops; only the outermost one is dumped, so
+    /// sub-agents run by a tool do not interleave with their parent.
+    depth: AtomicUsize,
+    warned: AtomicBool,
+}
+
+/// Marks a running agent loop; see [`DebugDump::enter_turn`].
+pub struct TurnGuard<'a>(&'a DebugDump);
+
+impl Drop for TurnGuard<'_> {
+    fn drop(&mut self) {
+        self.0.depth.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl DebugDump {
+    pub fn new(workspace_dir: &Path, session_id: &str, enabled: bool) -> Self {
+        Self {
+            session_dir: debug_dir(workspace_dir).join(sanitize(session_id)),
+            enabled: AtomicBool::new(enabled),
+            turn_dir: Mutex::new(None),
+            calls: AtomicUsize::new(0),
+            depth: AtomicUsize::new(0),
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    /// Where this session's turns are written.
+    pub fn session_dir(&self) -> &Path {
+        &self.session_dir
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Called when an agent loop starts. The outermost one opens a turn
+    /// directory with the system prompt at the head of `history` and the
+    /// tool schemas offered to the model.
+    pub fn enter_turn(&self, history: &[ChatMessage], tools: &[ToolSpec]) -> TurnGuard<'_> {
+        if self.depth.fetch_add(1, Ordering::SeqCst) == 0 {
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S%.3f").to_string();
+            let dir = self.session_dir.join(stamp);
+            self.calls.store(0, Ordering::SeqCst);
+            *self.turn_dir.lock() = Some(dir.clone());
+            let system_prompt = history
+                .first()
+                .filter(|message| message.role == "system")
+                .map_or("", |message| message.content.as_str());
+            self.write(&dir, "system_prompt.md", system_prompt.as_bytes());
+            self.write_json(&dir, "tools.json", &tools);
+        }
+        TurnGuard(self)
+    }
+
+    fn dumping(&self) -> Option<PathBuf> {
+        if self.depth.load(Ordering::SeqCst) != 1 {
+            return None;
+        }
+        self.turn_dir.lock().clone()
+    }
+
+    /// Dump the messages about to be sent. Returns the call number to pass
+    /// to [`record_response`](Self::record_response), or `None` when this
+    /// loop is not being dumped.
+    pub fn record_request(
+        &self,
+        provider: &str,
+        model: &str,
+        temperature: f64,
+        native_tools: bool,
+        messages: &[ChatMessage],
+    ) -> Option<usize> {
+        let dir = self.dumping()?;
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        self.write_json(
+            &dir,
+            &format!("call-{call:02}-request.json"),
+            &DumpedRequest {
+                provider,
+                model,
+                temperature,
+                native_tools,
+                messages,
+            },
+        );
+        Some(call)
+    }
+
+    /// Dump the response to call number `call`.
+    pub fn record_response(&self, call: usize, result: &Result<ChatResponse>) {
+        let Some(dir) = self.dumping() else {
+            return;
+        };
+        let (response, error) = match result {
+            Ok(response) => (Some(RecordedResponse::from(response)), None),
+            Err(e) => (None, Some(format!("{e:#}"))),
+        };
+        self.write_json(
+            &dir,
+            &format!("call-{call:02}-response.json"),
+            &DumpedResponse { response, error },
+        );
+    }
+
+    fn write_json<T: Serialize>(&self, dir: &Path, name: &str, value: &T) {
+        match serde_json::to_vec_pretty(value) {
+            Ok(data) => self.write(dir, name, &data),
+            Err(e) => self.warn(dir, &anyhow::Error::from(e)),
+        }
+    }
+
+    fn write(&self, dir: &Path, name: &str, data: &[u8]) {
+        let result = std::fs::create_dir_all(dir)
+            .and_then(|()| std::fs::write(dir.join(name), data))
+            .with_context(|| format!("writing {name}"));
+        if let Err(e) = result {
+            self.warn(dir, &e);
+        }
+    }
+
+    fn warn(&self, dir: &Path, error: &anyhow::Error) {
+        if !self.warned.swap(true, Ordering::SeqCst) {
+            tracing::warn!(path = %dir.display(), "Failed to write debug dump: {error:#}");
+        }
+    }
+}
+
+fn sanitize(session_id: &str) -> String {
+    session_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn spec(name: &str) -> ToolSpec {
+        ToolSpec {
+            name: name.into(),
+            description: format!("{name} tool"),
+            parameters: serde_json::json!({"type": "object"}),
+        }
+    }
+
+    #[test]
+    fn turn_writes_prompt_tools_and_numbered_calls() {
+        let tmp = TempDir::new().unwrap();
+        let dump = DebugDump::new(tmp.path(), "telegram:alice", true);
+        let history = vec![
+            ChatMessage::system("You are ZeroClaw."),
+            ChatMessage::user("hi"),
+        ];
+        let guard = dump.enter_turn(&history, &[spec("shell")]);
+        let call = dump
+            .record_request("openai", "gpt-4o", 0.7, true, &history)
+            .unwrap();
+        dump.record_response(call, &Err(anyhow::anyhow!("rate limited")));
+        drop(guard);
+
+        let turns: Vec<PathBuf> = std::fs::read_dir(tmp.path().join("state/debug/telegram_alice"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(turns.len(), 1);
+        let turn = &turns[0];
+        assert_eq!(
+            std::fs::read_to_string(turn.join("system_prompt.md")).unwrap(),
+            "You are ZeroClaw."
+        );
+        assert!(std::fs::read_to_string(turn.join("tools.json"))
+            .unwrap()
+            .contains("\"shell\""));
+        let request: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(turn.join("call-01-request.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(request["model"], "gpt-4o");
+        assert_eq!(request["messages"][1]["content"], "hi");
+        let response = std::fs::read_to_string(turn.join("call-01-response.json")).unwrap();
+        assert!(response.contains("rate limited"));
+    }
+
+    #[test]
+    fn nested_loops_are_not_dumped() {
+        let tmp = TempDir::new().unwrap();
+        let dump = DebugDump::new(tmp.path(), "cli", true);
+        let _outer = dump.enter_turn(&[], &[]);
+        let _inner = dump.enter_turn(&[], &[]);
+        assert!(dump
+            .record_request("openai", "m", 0.0, false, &[])
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn current_is_none_while_disabled() {
+        let tmp = TempDir::new().unwrap();
+        let dump = Arc::new(DebugDump::new(tmp.path(), "cli", false));
+        run_with(Some(Arc::clone(&dump)), async {
+            assert!(current().is_none());
+        })
+        .await;
+        dump.set_enabled(true);
+        run_with(Some(dump), async {
+            assert!(current().is_some());
+        })
+        .await;
+    }
+}
@@ -0,0 +1,113 @@
+//! Dry-run mode (`--dry-run`, `/dryrun on`).
+//!
+//! While a session is in dry run, calls to tools above [`ToolRisk::Low`]
+//! are not executed. The tool describes what it would do instead
+//! ([`Tool::dry_run`]), and that description goes back to the model as the
+//! call's result. Read-only tools still run, so the agent can look around
+//! and lay out a complete plan for the user to audit before dry run is
+//! turned off.
+
+use crate::tools::{Tool, ToolRisk};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+tokio::task_local! {
+    static CURRENT: Arc<DryRun>;
+}
+
+/// Prefix of every result of a call that was not executed.
+pub const RESULT_PREFIX: &str = "[dry run: nothing was executed]";
+
+/// One session's dry-run switch.
+#[derive(Debug, Default)]
+pub struct DryRun {
+    enabled: AtomicBool,
+}
+
+impl DryRun {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Run `future` with every agent loop inside it following `dry_run`.
+pub async fn run_with<F: Future>(dry_run: Option<Arc<DryRun>>, future: F) -> F::Output {
+    match dry_run {
+        Some(dry_run) => CURRENT.scope(dry_run, future).await,
+        None => future.await,
+    }
+}
+
+/// Whether a call to a tool of `risk` is replaced by its dry-run report in
+/// the running task.
+pub fn intercepts(risk: ToolRisk) -> bool {
+    risk > ToolRisk::Low
+        && CURRENT
+            .try_with(|dry_run| dry_run.is_enabled())
+            .unwrap_or(false)
+}
+
+/// The result handed to the model for a call of `tool` that was not executed.
+pub async fn report(tool: &dyn Tool, args: serde_json::Value) -> String {
+    match tool.dry_run(args).await {
+        Ok(plan) => format!("{RESULT_PREFIX} {plan}"),
+        Err(error) => format!("{RESULT_PREFIX} {} would fail: {error}", tool.name()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ToolResult;
+    use async_trait::async_trait;
+
+    struct Writer;
+
+    #[async_trait]
+    impl Tool for Writer {
+        fn name(&self) -> &str {
+            "writer"
+        }
+
+        fn description(&self) -> &str {
+            "writes"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object"})
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            unreachable!("dry run must not execute")
+        }
+    }
+
+    #[tokio::test]
+    async fn intercepts_risky_tools_only_while_enabled() {
+        assert!(!intercepts(ToolRisk::High));
+
+        let dry_run = Arc::new(DryRun::new(true));
+        run_with(Some(Arc::clone(&dry_run)), async {
+            assert!(intercepts(ToolRisk::Medium));
+            assert!(!intercepts(ToolRisk::Low));
+            assert_eq!(
+                report(&Writer, serde_json::json!({"path": "a.txt"})).await,
+                "[dry run: nothing was executed] Would call writer with {\"path\":\"a.txt\"}"
+            );
+            dry_run.set_enabled(false);
+            assert!(!intercepts(ToolRisk::High));
+        })
+        .await;
+    }
+}
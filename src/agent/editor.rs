@@ -0,0 +1,75 @@
+//! Composing a message in the user's `$EDITOR` (`/edit`, `agent --editor`).
+
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+
+/// Used when neither `$VISUAL` nor `$EDITOR` is set.
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+/// The editor command line: `$VISUAL`, then `$EDITOR`, then a platform
+/// default. It may carry arguments, as in `code --wait`.
+pub fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+/// Open `initial` in the user's editor and return the saved text, or `None`
+/// when it was saved empty.
+pub fn compose(initial: &str) -> Result<Option<String>> {
+    compose_with(&editor_command(), initial)
+}
+
+fn compose_with(editor: &str, initial: &str) -> Result<Option<String>> {
+    let path = std::env::temp_dir().join(format!("zeroclaw-prompt-{}.md", uuid::Uuid::new_v4()));
+    std::fs::write(&path, initial).with_context(|| format!("writing {}", path.display()))?;
+
+    // Run through the shell, like git does, so the editor command can carry
+    // its own arguments and quoting.
+    #[cfg(windows)]
+    let status = Command::new("cmd")
+        .arg("/C")
+        .arg(format!("{editor} \"{}\"", path.display()))
+        .status();
+    #[cfg(not(windows))]
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{editor} \"$1\""))
+        .arg("zeroclaw")
+        .arg(&path)
+        .status();
+
+    let text = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    let status = status.with_context(|| format!("starting editor `{editor}`"))?;
+    if !status.success() {
+        bail!("Editor `{editor}` exited with {status}; nothing sent");
+    }
+    let text = text.with_context(|| format!("reading {}", path.display()))?;
+    let text = text.trim();
+    Ok((!text.is_empty()).then(|| text.to_string()))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compose_returns_the_saved_buffer() {
+        assert_eq!(
+            compose_with("printf 'line one\\n\\nline two\\n' >", "draft").unwrap(),
+            Some("line one\n\nline two".to_string())
+        );
+        assert_eq!(compose_with("printf '  \\n' >", "draft").unwrap(), None);
+        assert_eq!(
+            compose_with("true", "kept as is").unwrap(),
+            Some("kept as is".to_string())
+        );
+        assert!(compose_with("false", "draft").is_err());
+    }
+}
@@ -0,0 +1,851 @@
+//! `zeroclaw eval`: regression tests for prompts.
+//!
+//! A YAML suite lists prompts with assertions on the answers — `contains`,
+//! `not_contains`, `regex`, `json_schema` and a `rubric` graded by a model.
+//! Every case runs through the agent, with the configured system prompt and
+//! tools, once per model; the result is a pass/fail matrix with latency and
+//! cost per model, so a change to the system prompt or persona files can be
+//! checked against the answers it used to give.
+//!
+//! ```yaml
+//! name: support
+//! models: [anthropic/claude-sonnet-4, openai/gpt-4o-mini]
+//! cases:
+//!   - name: greeting
+//!     prompt: Say hello to Ada.
+//!     assert:
+//!       - contains: Ada
+//!       - rubric: Friendly, one sentence.
+//! ```
+
+use super::loop_::CliRuntime;
+use super::pipe::{PipeUsage, UsageObserver, EXIT_BUDGET, EXIT_FAILED, EXIT_OK};
+use crate::config::schema::ModelPricing;
+use crate::config::{Config, GuardrailDirection};
+use crate::cost::TokenUsage;
+use crate::providers::{ChatMessage, ChatRequest, Provider};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+const JUDGE_SYSTEM_PROMPT: &str = "You grade an AI assistant's answer against a rubric. \
+Reply with PASS or FAIL on the first line, then one sentence explaining why.";
+
+/// What `zeroclaw eval` was asked to do.
+#[derive(Debug, Clone)]
+pub struct EvalOptions {
+    pub suite: PathBuf,
+    /// Models to run, instead of the suite's `models`.
+    pub models: Vec<String>,
+    pub provider: Option<String>,
+    /// Model grading `rubric` assertions, instead of the suite's `judge_model`.
+    pub judge_model: Option<String>,
+    /// Only run cases whose name contains this.
+    pub filter: Option<String>,
+    pub temperature: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Suite {
+    name: Option<String>,
+    /// Models to compare; the configured default when empty.
+    #[serde(default)]
+    models: Vec<String>,
+    /// Model grading rubrics; the model under test when unset.
+    judge_model: Option<String>,
+    cases: Vec<Case>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Case {
+    name: String,
+    prompt: String,
+    #[serde(default, rename = "assert")]
+    assertions: Vec<Assertion>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Assertion {
+    Contains(String),
+    NotContains(String),
+    Regex(String),
+    /// The answer, or the first JSON value in it, matches this schema.
+    JsonSchema(Value),
+    /// A model judges the answer against this description.
+    Rubric(String),
+}
+
+impl Assertion {
+    fn label(&self) -> String {
+        match self {
+            Self::Contains(text) => format!("contains {text:?}"),
+            Self::NotContains(text) => format!("not_contains {text:?}"),
+            Self::Regex(pattern) => format!("regex {pattern:?}"),
+            Self::JsonSchema(_) => "json_schema".into(),
+            Self::Rubric(rubric) => format!(
+                "rubric {:?}",
+                crate::util::truncate_with_ellipsis(rubric, 40)
+            ),
+        }
+    }
+}
+
+/// Parse a suite and check it can run.
+fn parse_suite(yaml: &str) -> Result<Suite> {
+    let suite: Suite = serde_yaml::from_str(yaml)?;
+    if suite.cases.is_empty() {
+        bail!("The suite has no cases");
+    }
+    let mut names = HashSet::new();
+    for case in &suite.cases {
+        if !names.insert(case.name.as_str()) {
+            bail!("Case names must be unique; {:?} appears twice", case.name);
+        }
+        for assertion in &case.assertions {
+            if let Assertion::Regex(pattern) = assertion {
+                Regex::new(pattern)
+                    .with_context(|| format!("Case {:?} has an invalid regex", case.name))?;
+            }
+        }
+    }
+    Ok(suite)
+}
+
+/// One assertion on one answer.
+#[derive(Debug, Serialize)]
+struct Check {
+    assertion: String,
+    passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+/// One case run against one model.
+#[derive(Debug, Serialize)]
+struct CaseResult {
+    case: String,
+    model: String,
+    passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    checks: Vec<Check>,
+    usage: PipeUsage,
+    cost_usd: f64,
+    duration_ms: u64,
+}
+
+/// The outcome of a `zeroclaw eval` run.
+#[derive(Debug, Serialize)]
+pub struct EvalReport {
+    suite: String,
+    models: Vec<String>,
+    cases: Vec<String>,
+    results: Vec<CaseResult>,
+    /// Whether a `[budget]` limit stopped the run.
+    budget_exceeded: bool,
+}
+
+impl EvalReport {
+    pub fn exit_code(&self) -> i32 {
+        if self.budget_exceeded {
+            EXIT_BUDGET
+        } else if self.results.iter().all(|result| result.passed) {
+            EXIT_OK
+        } else {
+            EXIT_FAILED
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    fn result(&self, case: &str, model: &str) -> Option<&CaseResult> {
+        self.results
+            .iter()
+            .find(|result| result.case == case && result.model == model)
+    }
+
+    /// The pass/fail matrix, per-model totals and what failed.
+    pub fn render(&self) -> String {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut header = vec![String::new()];
+        header.extend(self.models.iter().cloned());
+        rows.push(header);
+        for case in &self.cases {
+            let mut row = vec![case.clone()];
+            for model in &self.models {
+                row.push(match self.result(case, model) {
+                    Some(result) => format!(
+                        "{} {:.1}s",
+                        if result.passed { "PASS" } else { "FAIL" },
+                        result.duration_ms as f64 / 1000.0
+                    ),
+                    None => "-".into(),
+                });
+            }
+            rows.push(row);
+        }
+        let mut totals = [
+            vec!["passed".to_string()],
+            vec!["avg latency".to_string()],
+            vec!["cost".to_string()],
+        ];
+        for model in &self.models {
+            let results: Vec<&CaseResult> = self
+                .results
+                .iter()
+                .filter(|result| &result.model == model)
+                .collect();
+            let passed = results.iter().filter(|result| result.passed).count();
+            let runs = u64::try_from(results.len()).unwrap_or(u64::MAX).max(1);
+            let latency = results.iter().map(|r| r.duration_ms).sum::<u64>() / runs;
+            let cost: f64 = results.iter().map(|result| result.cost_usd).sum();
+            totals[0].push(format!("{passed}/{}", results.len()));
+            totals[1].push(format!("{:.1}s", latency as f64 / 1000.0));
+            totals[2].push(format!("${cost:.4}"));
+        }
+        rows.extend(totals);
+
+        let columns = self.models.len() + 1;
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let mut out = format!(
+            "Suite {} ({} cases × {} models)\n\n",
+            self.suite,
+            self.cases.len(),
+            self.models.len()
+        );
+        for (index, row) in rows.iter().enumerate() {
+            if index == self.cases.len() + 1 {
+                out.push('\n');
+            }
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            out.push_str(cells.join("  ").trim_end());
+            out.push('\n');
+        }
+
+        let failures: Vec<String> = self
+            .results
+            .iter()
+            .filter(|result| !result.passed)
+            .flat_map(|result| {
+                let prefix = format!("{} × {}", result.case, result.model);
+                let mut lines: Vec<String> = result
+                    .checks
+                    .iter()
+                    .filter(|check| !check.passed)
+                    .map(|check| match &check.detail {
+                        Some(detail) => format!("{prefix}: {}: {detail}", check.assertion),
+                        None => format!("{prefix}: {}", check.assertion),
+                    })
+                    .collect();
+                if let Some(error) = &result.error {
+                    lines.push(format!("{prefix}: {error}"));
+                }
+                lines
+            })
+            .collect();
+        if !failures.is_empty() {
+            out.push_str("\nFailures:\n");
+            for failure in failures {
+                let _ = writeln!(out, "  {failure}");
+            }
+        }
+        if self.budget_exceeded {
+            out.push_str("\nA [budget] limit stopped the run; the remaining cases were not run.\n");
+        }
+        out
+    }
+}
+
+/// The answer as JSON: all of it, or the first object or array in it.
+fn answer_json(answer: &str) -> Option<Value> {
+    let trimmed = answer.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+    trimmed
+        .char_indices()
+        .filter(|(_, ch)| matches!(ch, '{' | '['))
+        .find_map(|(start, _)| {
+            serde_json::Deserializer::from_str(&trimmed[start..])
+                .into_iter::<Value>()
+                .next()
+                .and_then(Result::ok)
+        })
+}
+
+fn type_matches(value: &Value, name: &str) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// Check `value` against the common JSON Schema keywords (`type`, `enum`,
+/// `const`, `required`, `properties`, `additionalProperties`, `items`,
+/// `minItems`/`maxItems`, `minLength`/`maxLength`, `minimum`/`maximum` and
+/// `pattern`); others are ignored. Returns what does not match.
+fn schema_errors(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let at = if path.is_empty() { "/" } else { path };
+    match schema.get("type") {
+        Some(Value::String(name)) if !type_matches(value, name) => {
+            errors.push(format!("{at}: expected {name}"));
+            return;
+        }
+        Some(Value::Array(names))
+            if !names
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|name| type_matches(value, name)) =>
+        {
+            errors.push(format!(
+                "{at}: expected one of {}",
+                Value::Array(names.clone())
+            ));
+            return;
+        }
+        _ => {}
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            errors.push(format!(
+                "{at}: {value} is not one of {}",
+                Value::Array(allowed.clone())
+            ));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{at}: expected {expected}"));
+        }
+    }
+
+    match value {
+        Value::Object(fields) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !fields.contains_key(name) {
+                        errors.push(format!("{at}: missing {name:?}"));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (name, field) in fields {
+                let field_path = format!("{path}/{name}");
+                match properties.and_then(|properties| properties.get(name)) {
+                    Some(field_schema) => schema_errors(field_schema, field, &field_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{field_path}: not allowed"));
+                        }
+                        Some(extra @ Value::Object(_)) => {
+                            schema_errors(extra, field, &field_path, errors);
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            let len = items.len() as u64;
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if len < min {
+                    errors.push(format!("{at}: fewer than {min} items"));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if len > max {
+                    errors.push(format!("{at}: more than {max} items"));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    schema_errors(item_schema, item, &format!("{path}/{index}"), errors);
+                }
+            }
+        }
+        Value::String(text) => {
+            let len = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    errors.push(format!("{at}: shorter than {min} characters"));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    errors.push(format!("{at}: longer than {max} characters"));
+                }
+            }
+            if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                if Regex::new(pattern).is_ok_and(|re| !re.is_match(text)) {
+                    errors.push(format!("{at}: does not match {pattern:?}"));
+                }
+            }
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < min {
+                    errors.push(format!("{at}: less than {min}"));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > max {
+                    errors.push(format!("{at}: more than {max}"));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Check the assertions that need no model. `None` for rubrics.
+fn check_locally(assertion: &Assertion, answer: &str) -> Option<Check> {
+    let (passed, detail) = match assertion {
+        Assertion::Contains(text) => (answer.contains(text.as_str()), None),
+        Assertion::NotContains(text) => (!answer.contains(text.as_str()), None),
+        Assertion::Regex(pattern) => (
+            Regex::new(pattern).is_ok_and(|re| re.is_match(answer)),
+            None,
+        ),
+        Assertion::JsonSchema(schema) => match answer_json(answer) {
+            None => (false, Some("the answer holds no JSON".to_string())),
+            Some(value) => {
+                let mut errors = Vec::new();
+                schema_errors(schema, &value, "", &mut errors);
+                (
+                    errors.is_empty(),
+                    (!errors.is_empty()).then(|| errors.join("; ")),
+                )
+            }
+        },
+        Assertion::Rubric(_) => return None,
+    };
+    Some(Check {
+        assertion: assertion.label(),
+        passed,
+        detail,
+    })
+}
+
+/// Have `model` grade `answer` against `rubric`. Returns the check and the
+/// judge's token counts.
+async fn grade(
+    provider: &dyn Provider,
+    model: &str,
+    rubric: &str,
+    prompt: &str,
+    answer: &str,
+) -> (Check, (u64, u64)) {
+    let messages = [
+        ChatMessage::system(JUDGE_SYSTEM_PROMPT),
+        ChatMessage::user(format!(
+            "Rubric:\n{rubric}\n\nPrompt:\n{prompt}\n\nAnswer:\n{answer}"
+        )),
+    ];
+    let label = Assertion::Rubric(rubric.to_string()).label();
+    let response = match provider
+        .chat(
+            ChatRequest {
+                messages: &messages,
+                tools: None,
+            },
+            model,
+            0.0,
+        )
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            let check = Check {
+                assertion: label,
+                passed: false,
+                detail: Some(format!("grading failed: {e}")),
+            };
+            return (check, (0, 0));
+        }
+    };
+    let tokens = response.usage.as_ref().map_or((0, 0), |usage| {
+        (
+            usage.input_tokens.unwrap_or(0),
+            usage.output_tokens.unwrap_or(0),
+        )
+    });
+    let reply = response.text.unwrap_or_default();
+    let mut lines = reply.lines().map(str::trim).filter(|line| !line.is_empty());
+    let verdict = lines.next().unwrap_or_default().to_ascii_uppercase();
+    let reason = lines.collect::<Vec<_>>().join(" ");
+    let reason = (!reason.is_empty()).then_some(reason);
+    let check = if verdict.starts_with("PASS") {
+        Check {
+            assertion: label,
+            passed: true,
+            detail: reason,
+        }
+    } else if verdict.starts_with("FAIL") {
+        Check {
+            assertion: label,
+            passed: false,
+            detail: reason,
+        }
+    } else {
+        Check {
+            assertion: label,
+            passed: false,
+            detail: Some(format!(
+                "the judge did not answer PASS or FAIL: {}",
+                crate::util::truncate_with_ellipsis(reply.trim(), 80)
+            )),
+        }
+    };
+    (check, tokens)
+}
+
+fn cost(prices: &HashMap<String, ModelPricing>, model: &str, input: u64, output: u64) -> f64 {
+    let (input_price, output_price) = crate::cost::budget::price_for(prices, model)
+        .map_or((0.0, 0.0), |price| (price.input, price.output));
+    TokenUsage::new(model, input, output, input_price, output_price).cost_usd
+}
+
+/// Run `case` on `runtime`'s model.
+async fn run_case(
+    runtime: &CliRuntime,
+    usage: &UsageObserver,
+    judge_model: Option<&str>,
+    prices: &HashMap<String, ModelPricing>,
+    case: &Case,
+) -> (CaseResult, Option<anyhow::Error>) {
+    let started = Instant::now();
+    let before = usage.usage();
+    let answer = match runtime
+        .guard(GuardrailDirection::Input, case.prompt.clone())
+        .await
+    {
+        Err(blocked) => Err(anyhow::anyhow!(blocked)),
+        Ok(prompt) => {
+            let mut history = runtime.initial_history();
+            runtime.prepare_turn(&mut history, &prompt).await;
+            match runtime.run_turn(&mut history, &prompt, None, None).await {
+                Ok(answer) => Ok(runtime
+                    .guard(GuardrailDirection::Output, answer)
+                    .await
+                    .unwrap_or_else(|blocked| blocked)),
+                Err(e) => Err(e),
+            }
+        }
+    };
+    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    let after = usage.usage();
+    let spent = PipeUsage {
+        input_tokens: after.input_tokens - before.input_tokens,
+        output_tokens: after.output_tokens - before.output_tokens,
+        total_tokens: after.total_tokens - before.total_tokens,
+        llm_calls: after.llm_calls - before.llm_calls,
+    };
+    let mut cost_usd = cost(
+        prices,
+        &runtime.model_name,
+        spent.input_tokens,
+        spent.output_tokens,
+    );
+
+    let answer = match answer {
+        Ok(answer) => answer,
+        Err(e) => {
+            let result = CaseResult {
+                case: case.name.clone(),
+                model: runtime.model_name.clone(),
+                passed: false,
+                answer: None,
+                error: Some(format!("{e:#}")),
+                checks: Vec::new(),
+                usage: spent,
+                cost_usd,
+                duration_ms,
+            };
+            return (result, Some(e));
+        }
+    };
+
+    let judge = judge_model.unwrap_or(&runtime.model_name);
+    let mut checks = Vec::with_capacity(case.assertions.len());
+    for assertion in &case.assertions {
+        if let Some(check) = check_locally(assertion, &answer) {
+            checks.push(check);
+        } else if let Assertion::Rubric(rubric) = assertion {
+            let (check, (input, output)) = grade(
+                runtime.provider.as_ref(),
+                judge,
+                rubric,
+                &case.prompt,
+                &answer,
+            )
+            .await;
+            cost_usd += cost(prices, judge, input, output);
+            checks.push(check);
+        }
+    }
+    let result = CaseResult {
+        case: case.name.clone(),
+        model: runtime.model_name.clone(),
+        passed: checks.iter().all(|check| check.passed),
+        answer: Some(answer),
+        error: None,
+        checks,
+        usage: spent,
+        cost_usd,
+        duration_ms,
+    };
+    (result, None)
+}
+
+/// Run the suite. Failing cases end up in the report; `Err` means the suite
+/// could not run.
+pub async fn run(mut config: Config, options: EvalOptions) -> Result<EvalReport> {
+    let yaml = tokio::fs::read_to_string(&options.suite)
+        .await
+        .with_context(|| format!("Failed to read {}", options.suite.display()))?;
+    let suite = parse_suite(&yaml)
+        .with_context(|| format!("Invalid eval suite {}", options.suite.display()))?;
+    let cases: Vec<&Case> = suite
+        .cases
+        .iter()
+        .filter(|case| {
+            options
+                .filter
+                .as_deref()
+                .is_none_or(|filter| case.name.contains(filter))
+        })
+        .collect();
+    if cases.is_empty() {
+        bail!(
+            "No case name contains {:?}",
+            options.filter.unwrap_or_default()
+        );
+    }
+    let models: Vec<Option<String>> = match (&options.models[..], &suite.models[..]) {
+        ([], []) => vec![None],
+        ([], models) | (models, _) => models.iter().cloned().map(Some).collect(),
+    };
+    let judge_model = options.judge_model.or(suite.judge_model);
+
+    // Cases are independent: nothing is remembered between them.
+    config.agent.render_markdown = false;
+    config.memory.auto_save = false;
+    config.memory.fact_extraction = false;
+    let mut report = EvalReport {
+        suite: suite.name.clone().unwrap_or_else(|| {
+            options
+                .suite
+                .file_stem()
+                .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned())
+        }),
+        models: Vec::new(),
+        cases: cases.iter().map(|case| case.name.clone()).collect(),
+        results: Vec::new(),
+        budget_exceeded: false,
+    };
+    let mut budget = None;
+    'models: for model in models {
+        let started = Instant::now();
+        let mut runtime = CliRuntime::new(
+            config.clone(),
+            options.provider.clone(),
+            model,
+            options.temperature,
+            Vec::new(),
+            false,
+            None,
+        )
+        .await?;
+        // One budget for the whole run, however many models it compares.
+        match &budget {
+            None => budget.clone_from(&runtime.budget),
+            Some(shared) => runtime.budget = Some(Arc::clone(shared)),
+        }
+        let usage = Arc::new(UsageObserver::new(Arc::clone(&runtime.observer)));
+        runtime.observer = usage.clone();
+        report.models.push(runtime.model_name.clone());
+
+        for case in &cases {
+            eprintln!("{} × {}", case.name, runtime.model_name);
+            let (result, error) = run_case(
+                &runtime,
+                &usage,
+                judge_model.as_deref(),
+                &config.cost.prices,
+                case,
+            )
+            .await;
+            report.results.push(result);
+            if error
+                .as_ref()
+                .is_some_and(crate::cost::budget::is_budget_exceeded)
+            {
+                report.budget_exceeded = true;
+                runtime.finish(started);
+                break 'models;
+            }
+        }
+        runtime.finish(started);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn suite_parses_and_local_assertions_check_answers() {
+        let suite = parse_suite(
+            r#"
+name: support
+models: [a/one, b/two]
+cases:
+  - name: greeting
+    prompt: Say hello to Ada.
+    assert:
+      - contains: Ada
+      - not_contains: Bob
+      - regex: "^(Hi|Hello)"
+      - rubric: Friendly.
+  - name: extract
+    prompt: Return the user as JSON.
+    assert:
+      - json_schema:
+          type: object
+          required: [name, age]
+          properties:
+            name: { type: string, minLength: 1 }
+            age: { type: integer, minimum: 0 }
+          additionalProperties: false
+"#,
+        )
+        .unwrap();
+        assert_eq!(suite.models, ["a/one", "b/two"]);
+        let greeting = &suite.cases[0].assertions;
+        let checks: Vec<bool> = greeting
+            .iter()
+            .filter_map(|assertion| check_locally(assertion, "Hello Ada!"))
+            .map(|check| check.passed)
+            .collect();
+        assert_eq!(
+            checks,
+            [true, true, true],
+            "rubrics are not checked locally"
+        );
+        assert!(
+            !check_locally(&greeting[2], "Well, hello Ada")
+                .unwrap()
+                .passed
+        );
+
+        let schema = &suite.cases[1].assertions[0];
+        let passing = "Here you go:\n```json\n{\"name\": \"Ada\", \"age\": 36}\n```";
+        assert!(check_locally(schema, passing).unwrap().passed);
+        let failing = check_locally(schema, "{\"name\": \"\", \"age\": -1, \"x\": 1}").unwrap();
+        assert!(!failing.passed);
+        let detail = failing.detail.unwrap();
+        for error in [
+            "/name: shorter than 1",
+            "/age: less than 0",
+            "/x: not allowed",
+        ] {
+            assert!(detail.contains(error), "{detail}");
+        }
+        assert_eq!(
+            check_locally(schema, "no json here")
+                .unwrap()
+                .detail
+                .as_deref(),
+            Some("the answer holds no JSON")
+        );
+
+        let duplicate = "cases:\n  - {name: a, prompt: x}\n  - {name: a, prompt: y}\n";
+        assert!(parse_suite(duplicate).is_err());
+        let bad_regex = "cases:\n  - {name: a, prompt: x, assert: [{regex: \"(\"}]}\n";
+        assert!(parse_suite(bad_regex).is_err());
+    }
+
+    #[test]
+    fn report_renders_matrix_with_totals_and_failures() {
+        let result = |case: &str, model: &str, passed: bool| CaseResult {
+            case: case.into(),
+            model: model.into(),
+            passed,
+            answer: Some("hi".into()),
+            error: None,
+            checks: vec![Check {
+                assertion: "contains \"Ada\"".into(),
+                passed,
+                detail: None,
+            }],
+            usage: PipeUsage::default(),
+            cost_usd: 0.01,
+            duration_ms: 1500,
+        };
+        let report = EvalReport {
+            suite: "support".into(),
+            models: vec!["one".into(), "two".into()],
+            cases: vec!["greeting".into(), "extract".into()],
+            results: vec![
+                result("greeting", "one", true),
+                result("extract", "one", true),
+                result("greeting", "two", false),
+            ],
+            budget_exceeded: false,
+        };
+        let text = report.render();
+        let lines: Vec<String> = text
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect();
+        for expected in [
+            "one two",
+            "greeting PASS 1.5s FAIL 1.5s",
+            "extract PASS 1.5s -",
+            "passed 2/2 0/1",
+            "cost $0.0200 $0.0100",
+            "greeting × two: contains \"Ada\"",
+        ] {
+            assert!(lines.iter().any(|line| line == expected), "{text}");
+        }
+        assert!(
+            text.contains("extract      PASS 1.5s  -"),
+            "columns line up: {text}"
+        );
+        assert_eq!(report.exit_code(), EXIT_FAILED);
+        assert_eq!(json!(report)["results"][2]["passed"], json!(false));
+    }
+}
@@ -1,5 +1,6 @@
+use super::repl::{LineReader, PROMPT};
 use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
-use crate::config::Config;
+use crate::config::{ApprovalAction, Config};
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::multimodal;
 use crate::observability::{self, runtime_trace, Observer, ObserverEvent};
@@ -7,8 +8,10 @@ use crate::providers::{
     self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
 };
 use crate::runtime;
+use crate::security::audit::{ApprovalDecision, ToolExecutionLog};
 use crate::security::SecurityPolicy;
-use crate::tools::{self, Tool};
+use crate::security::{injection, redaction, roles};
+use crate::tools::{self, Tool, ToolRisk};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
 use regex::{Regex, RegexSet};
@@ -90,6 +93,16 @@ pub(crate) fn scrub_credentials(input: &str) -> String {
 /// used when callers omit the parameter.
 const DEFAULT_MAX_HISTORY_MESSAGES: usize = 50;
 
+/// Scope under which interactive CLI approval prompts wait for an answer.
+const CLI_APPROVAL_SCOPE: &str = "cli";
+
+/// Line-editor history of the interactive CLI, next to `config.toml`.
+const CLI_HISTORY_FILE: &str = "cli_history";
+
+/// History entry closing a CLI turn the user interrupted by typing.
+const INTERRUPTED_TURN_NOTE: &str =
+    "[Interrupted by a new message before finishing; continuing with it]";
+
 /// Keep this many most-recent non-system messages after compaction.
 const COMPACTION_KEEP_RECENT_MESSAGES: usize = 20;
 
@@ -192,6 +205,7 @@ async fn auto_compact_history(
     provider: &dyn Provider,
     model: &str,
     max_history: usize,
+    max_tokens: usize,
 ) -> Result<bool> {
     let has_system = history.first().map_or(false, |m| m.role == "system");
     let non_system_count = if has_system {
@@ -200,7 +214,7 @@ async fn auto_compact_history(
         history.len()
     };
 
-    if non_system_count <= max_history {
+    if non_system_count <= max_history && !super::context::over_token_budget(history, max_tokens) {
         return Ok(false);
     }
 
@@ -254,15 +268,21 @@ async fn build_context(mem: &dyn Memory, user_msg: &str, min_relevance_score: f6
 
         if !relevant.is_empty() {
             context.push_str("[Memory context]\n");
+            let mut cited = false;
             for entry in &relevant {
                 if memory::is_assistant_autosave_key(&entry.key) {
                     continue;
                 }
-                let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
+                let (label, is_source) = memory::indexer::context_label(&entry.key);
+                cited |= is_source;
+                let _ = writeln!(context, "- {label}: {}", entry.content);
             }
             if context == "[Memory context]\n" {
                 context.clear();
             } else {
+                if cited {
+                    context.push_str(memory::indexer::CITATION_HINT);
+                }
                 context.push('\n');
             }
         }
@@ -1015,13 +1035,38 @@ fn map_tool_name_alias(tool_name: &str) -> &str {
         // File tool variations
         "fileread" | "file_read" | "readfile" | "read_file" | "file" => "file_read",
         "filewrite" | "file_write" | "writefile" | "write_file" => "file_write",
-        "filelist" | "file_list" | "listfiles" | "list_files" => "file_list",
+        "filelist" | "file_list" | "listfiles" | "list_files" | "list_dir" | "listdir" => {
+            "list_dir"
+        }
+        "applypatch" | "apply_patch" | "patch" => "apply_patch",
         // Memory variations
         "memoryrecall" | "memory_recall" | "recall" | "memrecall" => "memory_recall",
         "memorystore" | "memory_store" | "store" | "memstore" => "memory_store",
         "memoryforget" | "memory_forget" | "forget" | "memforget" => "memory_forget",
         // HTTP variations
         "http_request" | "http" | "fetch" | "curl" | "wget" => "http_request",
+        "web_fetch" | "webfetch" | "fetch_url" | "fetchurl" | "read_url" => "web_fetch",
+        // Git variations
+        "git" | "git_operations" | "gitops" | "git_ops" => "git_operations",
+        // SQL variations
+        "sql" | "sql_query" | "query_db" | "database" | "db_query" => "sql",
+        // Code interpreter variations
+        "run_code" | "runcode" | "code_interpreter" | "execute_code" | "python" => "run_code",
+        // Clipboard variations
+        "clipboard_read" | "read_clipboard" | "get_clipboard" => "clipboard_read",
+        "clipboard_write" | "write_clipboard" | "set_clipboard" | "copy_to_clipboard" => {
+            "clipboard_write"
+        }
+        // OCR variations
+        "ocr" | "image_to_text" | "extract_text" | "read_image_text" => "ocr",
+        // Image generation variations
+        "generate_image" | "image_gen" | "imagegen" | "create_image" | "dalle" => "generate_image",
+        // Document ingestion variations
+        "read_document" | "readdocument" | "read_pdf" | "read_docx" | "read_epub" => {
+            "read_document"
+        }
+        // Calendar variations
+        "calendar" | "caldav" | "calendar_events" => "calendar",
         _ => tool_name,
     }
 }
@@ -1120,14 +1165,24 @@ fn default_param_for_tool(tool: &str) -> &'static str {
         // All file tools default to "path"
         "file_read" | "fileread" | "readfile" | "read_file" | "file" | "file_write"
         | "filewrite" | "writefile" | "write_file" | "file_edit" | "fileedit" | "editfile"
-        | "edit_file" | "file_list" | "filelist" | "listfiles" | "list_files" => "path",
+        | "edit_file" | "file_list" | "filelist" | "listfiles" | "list_files" | "list_dir"
+        | "listdir" | "read_document" | "readdocument" | "read_pdf" | "read_docx" | "read_epub" => {
+            "path"
+        }
+        "apply_patch" | "applypatch" | "patch" => "patch",
+        "run_code" | "runcode" | "code_interpreter" | "execute_code" | "python" => "code",
+        "sql" | "sql_query" | "query_db" | "database" | "db_query" => "query",
+        "generate_image" | "image_gen" | "imagegen" | "create_image" | "dalle" => "prompt",
+        "ocr" | "image_to_text" | "extract_text" | "read_image_text" => "path",
+        "clipboard_write" | "write_clipboard" | "set_clipboard" | "copy_to_clipboard" => "text",
+        "calendar" | "caldav" | "calendar_events" => "action",
         // Memory recall and forget both default to "query"
         "memory_recall" | "memoryrecall" | "recall" | "memrecall" | "memory_forget"
         | "memoryforget" | "forget" | "memforget" => "query",
         "memory_store" | "memorystore" | "store" | "memstore" => "content",
         // HTTP and browser tools default to "url"
         "http_request" | "http" | "fetch" | "curl" | "wget" | "browser_open" | "browser"
-        | "web_search" => "url",
+        | "web_search" | "web_fetch" | "webfetch" | "fetch_url" | "fetchurl" | "read_url" => "url",
         _ => "input",
     }
 }
@@ -1873,6 +1928,7 @@ pub(crate) async fn agent_turn(
     silent: bool,
     multimodal_config: &crate::config::MultimodalConfig,
     max_tool_iterations: usize,
+    tool_output: Option<&crate::config::ToolOutputConfig>,
 ) -> Result<String> {
     run_tool_call_loop(
         provider,
@@ -1891,6 +1947,8 @@ pub(crate) async fn agent_turn(
         None,
         None,
         &[],
+        tool_output,
+        None,
     )
     .await
 }
@@ -1923,7 +1981,32 @@ async fn execute_one_tool(
         });
     };
 
-    let tool_future = tool.execute(call_arguments);
+    if let Err(reason) = roles::check_tool(tool.as_ref()) {
+        let duration = start.elapsed();
+        observer.record_event(&ObserverEvent::ToolCall {
+            tool: call_name.to_string(),
+            duration,
+            success: false,
+        });
+        return Ok(ToolExecutionOutcome {
+            output: format!("Error: {reason}"),
+            success: false,
+            error_reason: Some(reason),
+            duration,
+        });
+    }
+
+    let tool_future = async {
+        if super::dry_run::intercepts(tool.risk()) {
+            Ok(tools::ToolResult {
+                success: true,
+                output: super::dry_run::report(tool.as_ref(), call_arguments).await,
+                error: None,
+            })
+        } else {
+            tool.execute(call_arguments).await
+        }
+    };
     let tool_result = if let Some(token) = cancellation_token {
         tokio::select! {
             () = token.cancelled() => return Err(ToolLoopCancelled.into()),
@@ -1943,13 +2026,13 @@ async fn execute_one_tool(
             });
             if r.success {
                 Ok(ToolExecutionOutcome {
-                    output: scrub_credentials(&r.output),
+                    output: scrub_credentials(&redaction::redact(&r.output)),
                     success: true,
                     error_reason: None,
                     duration,
                 })
             } else {
-                let reason = r.error.unwrap_or(r.output);
+                let reason = redaction::redact(&r.error.unwrap_or(r.output));
                 Ok(ToolExecutionOutcome {
                     output: format!("Error: {reason}"),
                     success: false,
@@ -1965,7 +2048,7 @@ async fn execute_one_tool(
                 duration,
                 success: false,
             });
-            let reason = format!("Error executing {call_name}: {e}");
+            let reason = redaction::redact(&format!("Error executing {call_name}: {e}"));
             Ok(ToolExecutionOutcome {
                 output: reason.clone(),
                 success: false,
@@ -1983,8 +2066,40 @@ struct ToolExecutionOutcome {
     duration: Duration,
 }
 
+/// Append a tool call to the `[security.audit]` log, if enabled.
+fn audit_tool_call(
+    channel_name: &str,
+    session: Option<&str>,
+    call: &ParsedToolCall,
+    risk: ToolRisk,
+    approval: ApprovalDecision,
+    outcome: &ToolExecutionOutcome,
+) {
+    crate::security::audit::record_tool_execution(ToolExecutionLog {
+        channel: channel_name,
+        session,
+        tool: &call.name,
+        arguments: &call.arguments,
+        output: &outcome.output,
+        success: outcome.success,
+        error: outcome.error_reason.as_deref(),
+        duration_ms: u64::try_from(outcome.duration.as_millis()).unwrap_or(u64::MAX),
+        risk_level: risk.as_str(),
+        approval,
+    });
+}
+
+/// Risk level of the named tool; unknown tools count as medium.
+fn tool_risk(tools_registry: &[Box<dyn Tool>], name: &str) -> ToolRisk {
+    tools_registry
+        .iter()
+        .find(|tool| tool.name() == name)
+        .map_or(ToolRisk::Medium, |tool| tool.risk())
+}
+
 fn should_execute_tools_in_parallel(
     tool_calls: &[ParsedToolCall],
+    tools_registry: &[Box<dyn Tool>],
     approval: Option<&ApprovalManager>,
 ) -> bool {
     if tool_calls.len() <= 1 {
@@ -1992,7 +2107,10 @@ fn should_execute_tools_in_parallel(
     }
 
     if let Some(mgr) = approval {
-        if tool_calls.iter().any(|call| mgr.needs_approval(&call.name)) {
+        if tool_calls
+            .iter()
+            .any(|call| mgr.needs_approval(&call.name, tool_risk(tools_registry, &call.name)))
+        {
             // Approval-gated calls must keep sequential handling so the caller can
             // enforce CLI prompt/deny policy consistently.
             return false;
@@ -2063,6 +2181,9 @@ async fn execute_tools_sequential(
 
 /// Execute a single turn of the agent loop: send messages, parse tool calls,
 /// execute tools, and loop until the LLM produces a final text response.
+///
+/// The turn is bracketed by `TurnStart`/`TurnEnd` observer events, which
+/// tracing backends use as the parent of the turn's provider and tool calls.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn run_tool_call_loop(
     provider: &dyn Provider,
@@ -2081,6 +2202,67 @@ pub(crate) async fn run_tool_call_loop(
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
     hooks: Option<&crate::hooks::HookRunner>,
     excluded_tools: &[String],
+    tool_output: Option<&crate::config::ToolOutputConfig>,
+    conversation: Option<&crate::conversations::SessionHandle>,
+) -> Result<String> {
+    observer.record_event(&ObserverEvent::TurnStart {
+        channel: channel_name.to_string(),
+        provider: provider_name.to_string(),
+        model: model.to_string(),
+    });
+    let started = Instant::now();
+    let result = Box::pin(crate::tools::turn::run_with(run_tool_call_loop_inner(
+        provider,
+        history,
+        tools_registry,
+        observer,
+        provider_name,
+        model,
+        temperature,
+        silent,
+        approval,
+        channel_name,
+        multimodal_config,
+        max_tool_iterations,
+        cancellation_token,
+        on_delta,
+        hooks,
+        excluded_tools,
+        tool_output,
+        conversation,
+    )))
+    .await;
+    observer.record_event(&ObserverEvent::TurnEnd {
+        duration: started.elapsed(),
+        success: result.is_ok(),
+        error_message: result
+            .as_ref()
+            .err()
+            .map(|e| providers::sanitize_api_error(&format!("{e:#}"))),
+    });
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_tool_call_loop_inner(
+    provider: &dyn Provider,
+    history: &mut Vec<ChatMessage>,
+    tools_registry: &[Box<dyn Tool>],
+    observer: &dyn Observer,
+    provider_name: &str,
+    model: &str,
+    temperature: f64,
+    silent: bool,
+    approval: Option<&ApprovalManager>,
+    channel_name: &str,
+    multimodal_config: &crate::config::MultimodalConfig,
+    max_tool_iterations: usize,
+    cancellation_token: Option<CancellationToken>,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    hooks: Option<&crate::hooks::HookRunner>,
+    excluded_tools: &[String],
+    tool_output: Option<&crate::config::ToolOutputConfig>,
+    conversation: Option<&crate::conversations::SessionHandle>,
 ) -> Result<String> {
     let max_iterations = if max_tool_iterations == 0 {
         DEFAULT_MAX_TOOL_ITERATIONS
@@ -2096,6 +2278,22 @@ pub(crate) async fn run_tool_call_loop(
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
     let turn_id = Uuid::new_v4().to_string();
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
+    // `[budget]` limits of the session this turn belongs to, if enforced.
+    let budget = crate::cost::budget::current();
+    let _budget_turn = budget
+        .as_deref()
+        .map(crate::cost::budget::SessionBudget::start_turn);
+    // `[replay]` recorder of the session, if recording.
+    let recorder = super::replay::current();
+    let _replay_turn = recorder.as_deref().map(super::replay::Recorder::enter_turn);
+    // `--debug-prompts` / `/debug on` dump of the session, if enabled.
+    let debug_dump = super::debug_dump::current();
+    let _debug_turn = debug_dump
+        .as_deref()
+        .map(|dump| dump.enter_turn(history, &tool_specs));
+    // `[query_classification]` routing of each model call, if enabled.
+    let routing = super::model_router::current();
+    let mut turn_tool_calls = 0usize;
 
     for iteration in 0..max_iterations {
         if cancellation_token
@@ -2104,8 +2302,41 @@ pub(crate) async fn run_tool_call_loop(
         {
             return Err(ToolLoopCancelled.into());
         }
+        if let Some(budget) = budget.as_deref() {
+            budget.check()?;
+        }
+
+        let routed = routing.as_ref().and_then(|(router, message)| {
+            router.pick(&super::classifier::RouteContext {
+                message,
+                tool_calls: turn_tool_calls,
+                time: crate::time::time_of_day(),
+            })
+        });
+        let (provider, provider_name, model) = match routed.as_ref() {
+            Some(routed) => {
+                tracing::debug!(
+                    target: "query_classification",
+                    hint = routed.hint,
+                    provider = routed.provider_name,
+                    model = routed.model,
+                    "Routed model call"
+                );
+                (routed.provider, routed.provider_name, routed.model)
+            }
+            None => (provider, provider_name, model),
+        };
 
-        let image_marker_count = multimodal::count_image_markers(history);
+        let mut image_marker_count = multimodal::count_image_markers(history);
+        if image_marker_count > 0 && !provider.supports_vision() && multimodal_config.ocr.fallback {
+            let replaced = multimodal::apply_ocr_fallback(history, multimodal_config).await;
+            tracing::info!(
+                provider = provider_name,
+                images = replaced,
+                "Provider lacks vision; replaced image markers with OCR text"
+            );
+            image_marker_count = multimodal::count_image_markers(history);
+        }
         if image_marker_count > 0 && !provider.supports_vision() {
             return Err(ProviderCapabilityError {
                 provider: provider_name.to_string(),
@@ -2164,6 +2395,16 @@ pub(crate) async fn run_tool_call_loop(
             None
         };
 
+        let debug_call = debug_dump.as_deref().and_then(|dump| {
+            dump.record_request(
+                provider_name,
+                model,
+                temperature,
+                use_native_tools,
+                &prepared_messages.messages,
+            )
+        });
+
         let chat_future = provider.chat(
             ChatRequest {
                 messages: &prepared_messages.messages,
@@ -2181,6 +2422,19 @@ pub(crate) async fn run_tool_call_loop(
         } else {
             chat_future.await
         };
+        if let (Some(dump), Some(call)) = (debug_dump.as_deref(), debug_call) {
+            dump.record_response(call, &chat_result);
+        }
+        if let Some(recorder) = recorder.as_deref() {
+            recorder.record_provider_call(
+                model,
+                provider.supports_vision(),
+                &prepared_messages.messages,
+                &tool_specs,
+                use_native_tools,
+                &chat_result,
+            );
+        }
 
         let (response_text, parsed_text, tool_calls, assistant_history_content, native_tool_calls) =
             match chat_result {
@@ -2202,6 +2456,23 @@ pub(crate) async fn run_tool_call_loop(
                     });
 
                     let response_text = resp.text_or_empty().to_string();
+                    if let Some(budget) = budget.as_deref() {
+                        // Providers that omit usage are charged an estimate.
+                        let estimated_input = || {
+                            prepared_messages
+                                .messages
+                                .iter()
+                                .map(|m| super::tool_output::estimate_tokens(&m.content) as u64)
+                                .sum()
+                        };
+                        budget.record_llm(
+                            model,
+                            resp_input_tokens.unwrap_or_else(estimated_input),
+                            resp_output_tokens.unwrap_or_else(|| {
+                                super::tool_output::estimate_tokens(&response_text) as u64
+                            }),
+                        );
+                    }
                     // First try native structured tool calls (OpenAI-format).
                     // Fall back to text-based parsing (XML tags, markdown blocks,
                     // GLM format) only if the provider returned no native calls —
@@ -2381,6 +2652,7 @@ pub(crate) async fn run_tool_call_loop(
 
         // Print any text the LLM produced alongside tool calls (unless silent)
         if !silent && !display_text.is_empty() {
+            super::status_line::suspend();
             print!("{display_text}");
             let _ = std::io::stdout().flush();
         }
@@ -2394,9 +2666,12 @@ pub(crate) async fn run_tool_call_loop(
         let mut individual_results: Vec<(Option<String>, String)> = Vec::new();
         let mut ordered_results: Vec<Option<(String, Option<String>, ToolExecutionOutcome)>> =
             (0..tool_calls.len()).map(|_| None).collect();
-        let allow_parallel_execution = should_execute_tools_in_parallel(&tool_calls, approval);
+        let allow_parallel_execution =
+            should_execute_tools_in_parallel(&tool_calls, tools_registry, approval);
         let mut executable_indices: Vec<usize> = Vec::new();
         let mut executable_calls: Vec<ParsedToolCall> = Vec::new();
+        let mut executable_approvals: Vec<ApprovalDecision> = Vec::new();
+        let audit_session = conversation.map(|c| c.session_id());
 
         for (idx, call) in tool_calls.iter().enumerate() {
             // ── Hook: before_tool_call (modifying) ──────────
@@ -2444,50 +2719,75 @@ pub(crate) async fn run_tool_call_loop(
             }
 
             // ── Approval hook ────────────────────────────────
+            let mut approval_decision = ApprovalDecision::NotRequired;
             if let Some(mgr) = approval {
-                if mgr.needs_approval(&tool_name) {
-                    let request = ApprovalRequest {
-                        tool_name: tool_name.clone(),
-                        arguments: tool_args.clone(),
-                    };
+                let request = ApprovalRequest {
+                    tool_name: tool_name.clone(),
+                    arguments: tool_args.clone(),
+                    risk: tool_risk(tools_registry, &tool_name),
+                };
+                let denied = match mgr.decide(&tool_name, request.risk) {
+                    ApprovalAction::Allow => None,
+                    ApprovalAction::Deny => {
+                        mgr.record_policy_denial(&request, channel_name);
+                        approval_decision = ApprovalDecision::DeniedByPolicy;
+                        Some(format!(
+                            "Denied by approval policy: '{tool_name}' ({} risk) is not allowed.",
+                            request.risk.as_str()
+                        ))
+                    }
+                    // Nothing runs in dry run, so there is nothing to approve yet.
+                    ApprovalAction::Ask if super::dry_run::intercepts(request.risk) => None,
+                    ApprovalAction::Ask => {
+                        // Pauses this turn until the user answers on the
+                        // originating channel (or the request times out).
+                        let decision = mgr.prompt(&request, channel_name).await;
+                        mgr.record_decision(&request, decision, channel_name);
+                        approval_decision = match decision {
+                            ApprovalResponse::Yes => ApprovalDecision::Approved,
+                            ApprovalResponse::Always => ApprovalDecision::AlwaysApproved,
+                            ApprovalResponse::No => ApprovalDecision::Denied,
+                        };
+                        (decision == ApprovalResponse::No).then(|| "Denied by user.".to_string())
+                    }
+                };
 
-                    // Only prompt interactively on CLI; auto-approve on other channels.
-                    let decision = if channel_name == "cli" {
-                        mgr.prompt_cli(&request)
-                    } else {
-                        ApprovalResponse::Yes
+                if let Some(denied) = denied {
+                    runtime_trace::record_event(
+                        "tool_call_result",
+                        Some(channel_name),
+                        Some(provider_name),
+                        Some(model),
+                        Some(&turn_id),
+                        Some(false),
+                        Some(&denied),
+                        serde_json::json!({
+                            "iteration": iteration + 1,
+                            "tool": tool_name.clone(),
+                            "arguments": scrub_credentials(&tool_args.to_string()),
+                        }),
+                    );
+                    let outcome = ToolExecutionOutcome {
+                        output: denied.clone(),
+                        success: false,
+                        error_reason: Some(denied),
+                        duration: Duration::ZERO,
                     };
-
-                    mgr.record_decision(&tool_name, &tool_args, decision, channel_name);
-
-                    if decision == ApprovalResponse::No {
-                        let denied = "Denied by user.".to_string();
-                        runtime_trace::record_event(
-                            "tool_call_result",
-                            Some(channel_name),
-                            Some(provider_name),
-                            Some(model),
-                            Some(&turn_id),
-                            Some(false),
-                            Some(&denied),
-                            serde_json::json!({
-                                "iteration": iteration + 1,
-                                "tool": tool_name.clone(),
-                                "arguments": scrub_credentials(&tool_args.to_string()),
-                            }),
-                        );
-                        ordered_results[idx] = Some((
-                            tool_name.clone(),
-                            call.tool_call_id.clone(),
-                            ToolExecutionOutcome {
-                                output: denied.clone(),
-                                success: false,
-                                error_reason: Some(denied),
-                                duration: Duration::ZERO,
-                            },
-                        ));
-                        continue;
-                    }
+                    audit_tool_call(
+                        channel_name,
+                        audit_session,
+                        &ParsedToolCall {
+                            name: tool_name.clone(),
+                            arguments: tool_args.clone(),
+                            tool_call_id: call.tool_call_id.clone(),
+                        },
+                        request.risk,
+                        approval_decision,
+                        &outcome,
+                    );
+                    ordered_results[idx] =
+                        Some((tool_name.clone(), call.tool_call_id.clone(), outcome));
+                    continue;
                 }
             }
 
@@ -2552,6 +2852,7 @@ pub(crate) async fn run_tool_call_loop(
             }
 
             executable_indices.push(idx);
+            executable_approvals.push(approval_decision);
             executable_calls.push(ParsedToolCall {
                 name: tool_name,
                 arguments: tool_args,
@@ -2559,6 +2860,11 @@ pub(crate) async fn run_tool_call_loop(
             });
         }
 
+        if let Some(budget) = budget.as_deref() {
+            budget.admit_tool_calls(executable_calls.len())?;
+        }
+        turn_tool_calls += executable_calls.len();
+
         let executed_outcomes = if allow_parallel_execution && executable_calls.len() > 1 {
             execute_tools_parallel(
                 &executable_calls,
@@ -2577,11 +2883,21 @@ pub(crate) async fn run_tool_call_loop(
             .await?
         };
 
-        for ((idx, call), outcome) in executable_indices
+        for (((idx, call), approval_decision), mut outcome) in executable_indices
             .iter()
             .zip(executable_calls.iter())
+            .zip(executable_approvals.iter())
             .zip(executed_outcomes.into_iter())
         {
+            audit_tool_call(
+                channel_name,
+                audit_session,
+                call,
+                tool_risk(tools_registry, &call.name),
+                *approval_decision,
+                &outcome,
+            );
+
             runtime_trace::record_event(
                 "tool_call_result",
                 Some(channel_name),
@@ -2622,6 +2938,36 @@ pub(crate) async fn run_tool_call_loop(
                 let _ = tx.send(format!("{icon} {} ({secs}s)\n", call.name)).await;
             }
 
+            if let Some(tool_output) = tool_output {
+                outcome.output = super::tool_output::process_tool_output(
+                    tool_output,
+                    &call.name,
+                    outcome.output,
+                    provider,
+                    model,
+                )
+                .await;
+            }
+            outcome.output =
+                injection::guard_tool_output(&call.name, outcome.output, provider, model).await;
+            if let Some(recorder) = recorder.as_deref() {
+                recorder.record_tool(
+                    &call.name,
+                    &call.arguments,
+                    outcome.success,
+                    &outcome.output,
+                );
+            }
+            if let Some(conversation) = conversation {
+                conversation.record_tool_call(
+                    &call.name,
+                    &call.arguments,
+                    &outcome.output,
+                    outcome.success,
+                    outcome.duration,
+                );
+            }
+
             ordered_results[*idx] = Some((call.name.clone(), call.tool_call_id.clone(), outcome));
         }
 
@@ -2716,137 +3062,42 @@ pub(crate) fn build_tool_instructions(tools_registry: &[Box<dyn Tool>]) -> Strin
     instructions
 }
 
-// ── CLI Entrypoint ───────────────────────────────────────────────────────
-// Wires up all subsystems (observer, runtime, security, memory, tools,
-// provider, hardware RAG, peripherals) and enters either single-shot or
-// interactive REPL mode. The interactive loop manages history compaction
-// and hard trimming to keep the context window bounded.
-
-#[allow(clippy::too_many_lines)]
-pub async fn run(
-    config: Config,
-    message: Option<String>,
-    provider_override: Option<String>,
-    model_override: Option<String>,
-    temperature: f64,
-    peripheral_overrides: Vec<String>,
-    interactive: bool,
-) -> Result<String> {
-    // ── Wire up agnostic subsystems ──────────────────────────────
-    let base_observer = observability::create_observer(&config.observability);
-    let observer: Arc<dyn Observer> = Arc::from(base_observer);
-    let runtime: Arc<dyn runtime::RuntimeAdapter> =
-        Arc::from(runtime::create_runtime(&config.runtime)?);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
-
-    // ── Memory (the brain) ────────────────────────────────────────
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
-        &config.memory,
-        Some(&config.storage.provider.config),
-        &config.workspace_dir,
-        config.api_key.as_deref(),
-    )?);
-    tracing::info!(backend = mem.name(), "Memory initialized");
-
-    // ── Peripherals (merge peripheral tools into registry) ─
-    if !peripheral_overrides.is_empty() {
-        tracing::info!(
-            peripherals = ?peripheral_overrides,
-            "Peripheral overrides from CLI (config boards take precedence)"
-        );
-    }
-
-    // ── Tools (including memory tools and peripherals) ────────────
-    let (composio_key, composio_entity_id) = if config.composio.enabled {
-        (
-            config.composio.api_key.as_deref(),
-            Some(config.composio.entity_id.as_str()),
-        )
-    } else {
-        (None, None)
-    };
-    let mut tools_registry = tools::all_tools_with_runtime(
-        Arc::new(config.clone()),
-        &security,
-        runtime,
-        mem.clone(),
-        composio_key,
-        composio_entity_id,
-        &config.browser,
-        &config.http_request,
-        &config.web_fetch,
-        &config.workspace_dir,
-        &config.agents,
-        config.api_key.as_deref(),
-        &config,
-    );
-
-    let peripheral_tools: Vec<Box<dyn Tool>> =
-        crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
-    if !peripheral_tools.is_empty() {
-        tracing::info!(count = peripheral_tools.len(), "Peripheral tools added");
-        tools_registry.extend(peripheral_tools);
-    }
-
-    // ── Resolve provider ─────────────────────────────────────────
-    let provider_name = provider_override
-        .as_deref()
-        .or(config.default_provider.as_deref())
-        .unwrap_or("openrouter");
-
-    let model_name = model_override
-        .as_deref()
-        .or(config.default_model.as_deref())
-        .unwrap_or("anthropic/claude-sonnet-4");
-
-    let provider_runtime_options = providers::ProviderRuntimeOptions {
-        auth_profile_override: None,
-        provider_api_url: config.api_url.clone(),
-        zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
-        secrets_encrypt: config.secrets.encrypt,
-        reasoning_enabled: config.runtime.reasoning_enabled,
+/// Apply `[guardrails]` to CLI text. `Err` carries the message shown
+/// instead when the text is blocked.
+async fn guard_cli_text(
+    guardrails: Option<&crate::security::Guardrails>,
+    provider: &dyn Provider,
+    direction: crate::config::GuardrailDirection,
+    channel_name: &str,
+    text: String,
+) -> std::result::Result<String, String> {
+    let Some(guardrails) = guardrails else {
+        return Ok(text);
     };
-
-    let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
-        provider_name,
-        config.api_key.as_deref(),
-        config.api_url.as_deref(),
-        &config.reliability,
-        &config.model_routes,
-        model_name,
-        &provider_runtime_options,
-    )?;
-
-    observer.record_event(&ObserverEvent::AgentStart {
-        provider: provider_name.to_string(),
-        model: model_name.to_string(),
-    });
-
-    // ── Hardware RAG (datasheet retrieval when peripherals + datasheet_dir) ──
-    let hardware_rag: Option<crate::rag::HardwareRag> = config
-        .peripherals
-        .datasheet_dir
-        .as_ref()
-        .filter(|d| !d.trim().is_empty())
-        .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
-        .and_then(Result::ok)
-        .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
-    if let Some(ref rag) = hardware_rag {
-        tracing::info!(chunks = rag.len(), "Hardware RAG loaded");
+    match guardrails
+        .check(provider, direction, channel_name, &text)
+        .await
+    {
+        crate::security::GuardrailVerdict::Allow => Ok(text),
+        crate::security::GuardrailVerdict::Rewrite(rewritten) => Ok(rewritten),
+        crate::security::GuardrailVerdict::Block { rule, message } => {
+            tracing::info!(%rule, ?direction, "CLI text blocked by guardrail");
+            Err(message)
+        }
     }
+}
 
-    let board_names: Vec<String> = config
-        .peripherals
-        .boards
-        .iter()
-        .map(|b| b.board.clone())
-        .collect();
-
-    // ── Build system prompt from workspace MD files (OpenClaw framework) ──
-    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
+/// Build the CLI agent system prompt from workspace MD files (OpenClaw framework),
+/// the tool list and, for non-native providers, tool-use instructions.
+#[allow(clippy::too_many_lines)]
+pub(crate) fn build_agent_system_prompt(
+    config: &Config,
+    model_name: &str,
+    tools_registry: &[Box<dyn Tool>],
+    mcp_tool_descs: &[(String, String)],
+    native_tools: bool,
+) -> String {
+    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, config);
     let mut tool_descs: Vec<(&str, &str)> = vec![
         (
             "shell",
@@ -2860,6 +3111,18 @@ pub async fn run(
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "apply_patch",
+            "Apply a unified diff to one or more files. Use when: making multi-hunk or multi-file code changes. Don't use when: a single exact replacement (file_edit) suffices.",
+        ),
+        (
+            "list_dir",
+            "List a directory. Use when: orienting in an unfamiliar tree. Don't use when: a glob_search is more targeted.",
+        ),
+        (
+            "git_operations",
+            "Structured git (status, diff, log, show, branch, add, commit). Use when: reviewing changes or preparing commits. Don't use when: the task needs remote operations such as push.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -2899,12 +3162,56 @@ pub async fn run(
         "image_info",
         "Read image file metadata (format, dimensions, size) and optionally base64-encode it. Use when: inspecting images, preparing visual data for analysis.",
     ));
+    tool_descs.push((
+        "read_document",
+        "Extract text from PDF/DOCX/EPUB files by page range, in context-sized chunks; optionally index into memory. Use when: reading reports, specs, books. Don't use when: the file is plain text (use file_read).",
+    ));
     if config.browser.enabled {
         tool_descs.push((
             "browser_open",
             "Open approved HTTPS URLs in system browser (allowlist-only, no scraping)",
         ));
     }
+    if config.code_interpreter.enabled {
+        tool_descs.push((
+            "run_code",
+            "Run a short Python or JavaScript program in a sandboxed subprocess. Use when: calculations, data analysis, file conversions. Don't use when: a shell one-liner or file tool is enough.",
+        ));
+    }
+    if config.sql.enabled {
+        tool_descs.push((
+            "sql",
+            "Query a configured database (SQLite/PostgreSQL) and get a Markdown table. Use when: answering questions from application data. Don't use when: the data is in a plain file.",
+        ));
+    }
+    if config.multimodal.ocr.enabled {
+        tool_descs.push((
+            "ocr",
+            "Extract text from an image file (screenshot, scan, photo). Use when: you need the words in an image and cannot view it directly.",
+        ));
+    }
+    if config.clipboard.enabled {
+        tool_descs.push((
+            "clipboard_read",
+            "Read the text on the user's clipboard. Use when: the user mentions something they just copied.",
+        ));
+        tool_descs.push((
+            "clipboard_write",
+            "Put text on the user's clipboard. Use when: the user asks you to copy a snippet, command or answer.",
+        ));
+    }
+    if config.image_generation.enabled {
+        tool_descs.push((
+            "generate_image",
+            "Generate an image from a text prompt and save it in the workspace; reply with the returned [IMAGE:<path>] marker to send it. Use when: the user asks for a picture, illustration or diagram-like artwork.",
+        ));
+    }
+    if config.calendar.enabled {
+        tool_descs.push((
+            "calendar",
+            "List, create, update or delete events in the user's calendar. Writes return a preview first: confirm with the user, then repeat with approved=true. Use when: the user asks what is scheduled or to schedule/move/cancel a meeting.",
+        ));
+    }
     if config.composio.enabled {
         tool_descs.push((
             "composio",
@@ -2955,12 +3262,14 @@ pub async fn run(
             "Query connected hardware for reported GPIO pins and LED pin. Use when: user asks what pins are available.",
         ));
     }
+    for (name, description) in mcp_tool_descs {
+        tool_descs.push((name.as_str(), description.as_str()));
+    }
     let bootstrap_max_chars = if config.agent.compact_context {
         Some(6000)
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
     let mut system_prompt = crate::channels::build_system_prompt_with_mode(
         &config.workspace_dir,
         model_name,
@@ -2974,239 +3283,1057 @@ pub async fn run(
 
     // Append structured tool-use instructions with schemas (only for non-native providers)
     if !native_tools {
-        system_prompt.push_str(&build_tool_instructions(&tools_registry));
+        system_prompt.push_str(&build_tool_instructions(tools_registry));
+    }
+    if let Some(section) = crate::i18n::reply_language_prompt(&config.locale) {
+        system_prompt.push_str(&section);
     }
+    system_prompt
+}
 
-    // ── Approval manager (supervised mode) ───────────────────────
-    let approval_manager = if interactive {
-        Some(ApprovalManager::from_config(&config.autonomy))
-    } else {
-        None
-    };
-    let channel_name = if interactive { "cli" } else { "daemon" };
+// ── CLI Entrypoint ───────────────────────────────────────────────────────
+// Wires up all subsystems (observer, runtime, security, memory, tools,
+// provider, hardware RAG, peripherals) and enters either single-shot or
+// interactive REPL mode. The interactive loop manages history compaction
+// and hard trimming to keep the context window bounded.
 
-    // ── Execute ──────────────────────────────────────────────────
-    let start = Instant::now();
+/// The subsystems of a CLI conversation and the per-turn steps around the
+/// tool-call loop, shared by `zeroclaw agent` and `zeroclaw tui`.
+pub(crate) struct CliRuntime {
+    pub(crate) config: Config,
+    pub(crate) observer: Arc<dyn Observer>,
+    pub(crate) mem: Arc<dyn Memory>,
+    pub(crate) tools_registry: Vec<Box<dyn Tool>>,
+    pub(crate) provider: Box<dyn Provider>,
+    pub(crate) provider_name: String,
+    pub(crate) model_name: String,
+    pub(crate) temperature: f64,
+    pub(crate) system_prompt: String,
+    pub(crate) approval_manager: Option<ApprovalManager>,
+    pub(crate) channel_name: &'static str,
+    pub(crate) conversation: Option<crate::conversations::SessionHandle>,
+    /// Earlier turns of a reopened session, replayed after the system prompt.
+    pub(crate) resumed: Vec<ChatMessage>,
+    pub(crate) budget: Option<Arc<crate::cost::budget::SessionBudget>>,
+    /// Tools turned off with `/tools off`.
+    pub(crate) disabled_tools: Vec<String>,
+    /// Role every turn runs as: the enclosing role scope when the runtime
+    /// was created, admin for the local operator.
+    role: crate::security::roles::Role,
+    /// Context pinned with `/pin`, kept in the system message.
+    pins: super::context::Pins,
+    /// User and assistant turns as shown, for `/export`.
+    transcript: std::sync::Mutex<Vec<ChatMessage>>,
+    recorder: Option<Arc<super::replay::Recorder>>,
+    /// Prompt dumps, toggled by `/debug`.
+    debug_dump: Arc<super::debug_dump::DebugDump>,
+    /// Dry run, toggled by `/dryrun`.
+    dry_run: Arc<super::dry_run::DryRun>,
+    /// File changes by turn, for `/undo`.
+    undo: Arc<super::undo::Journal>,
+    /// Session whose temp directory in the workspace tools use.
+    workspace_session: String,
+    /// Whether that directory is removed when the runtime is dropped: runs
+    /// without `--session` are not meant to be resumed.
+    ephemeral_workspace: bool,
+    model_router: Option<Arc<super::model_router::ModelRouter>>,
+    guardrails: Option<crate::security::Guardrails>,
+    hardware_rag: Option<crate::rag::HardwareRag>,
+    board_names: Vec<String>,
+    cli_user: String,
+}
 
-    let mut final_output = String::new();
+impl Drop for CliRuntime {
+    fn drop(&mut self) {
+        if self.ephemeral_workspace {
+            crate::security::workspace::end_session(
+                &self.config.workspace_dir,
+                &self.workspace_session,
+            );
+        }
+    }
+}
 
-    if let Some(msg) = message {
-        // Auto-save user message to memory (skip short/trivial messages)
-        if config.memory.auto_save && msg.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
+impl CliRuntime {
+    /// Wire up the subsystems. `interactive` enables approvals and names the
+    /// channel `cli` (`daemon` otherwise); `session` reopens a stored session
+    /// by ID or name. Created inside [`roles::run_with`](crate::security::roles::run_with),
+    /// the runtime serves a remote user as that role.
+    #[allow(clippy::too_many_lines)]
+    pub(crate) async fn new(
+        config: Config,
+        provider_override: Option<String>,
+        model_override: Option<String>,
+        temperature: f64,
+        peripheral_overrides: Vec<String>,
+        interactive: bool,
+        session: Option<&str>,
+    ) -> Result<Self> {
+        // ── Wire up agnostic subsystems ──────────────────────────────
+        let base_observer = observability::create_observer(&config.observability);
+        let observer: Arc<dyn Observer> = Arc::from(base_observer);
+        let runtime: Arc<dyn runtime::RuntimeAdapter> =
+            Arc::from(runtime::create_runtime(&config.runtime)?);
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+
+        // ── Memory (the brain) ────────────────────────────────────────
+        let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?);
+        tracing::info!(backend = mem.name(), "Memory initialized");
+
+        // ── Peripherals (merge peripheral tools into registry) ─
+        if !peripheral_overrides.is_empty() {
+            tracing::info!(
+                peripherals = ?peripheral_overrides,
+                "Peripheral overrides from CLI (config boards take precedence)"
+            );
+        }
+
+        // ── Tools (including memory tools and peripherals) ────────────
+        let (composio_key, composio_entity_id) = if config.composio.enabled {
+            (
+                config.composio.api_key.as_deref(),
+                Some(config.composio.entity_id.as_str()),
+            )
+        } else {
+            (None, None)
+        };
+        let mut tools_registry = tools::all_tools_with_runtime(
+            Arc::new(config.clone()),
+            &security,
+            runtime,
+            mem.clone(),
+            composio_key,
+            composio_entity_id,
+            &config.browser,
+            &config.http_request,
+            &config.web_fetch,
+            &config.workspace_dir,
+            &config.agents,
+            config.api_key.as_deref(),
+            &config,
+        );
+
+        let peripheral_tools: Vec<Box<dyn Tool>> =
+            crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
+        if !peripheral_tools.is_empty() {
+            tracing::info!(count = peripheral_tools.len(), "Peripheral tools added");
+            tools_registry.extend(peripheral_tools);
+        }
+
+        let mcp_tools =
+            crate::mcp::create_mcp_tools(&config.mcp, &security, &config.workspace_dir).await;
+        let mcp_tool_descs: Vec<(String, String)> = mcp_tools
+            .iter()
+            .map(|tool| (tool.name().to_string(), tool.description().to_string()))
+            .collect();
+        tools_registry.extend(mcp_tools);
+
+        // ── Resolve provider ─────────────────────────────────────────
+        let provider_name = provider_override
+            .as_deref()
+            .or(config.default_provider.as_deref())
+            .unwrap_or("openrouter")
+            .to_string();
+
+        let model_name = model_override
+            .as_deref()
+            .or(config.default_model.as_deref())
+            .unwrap_or("anthropic/claude-sonnet-4")
+            .to_string();
+
+        let provider_runtime_options = cli_provider_runtime_options(&config);
+
+        let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
+            &provider_name,
+            config.api_key.as_deref(),
+            config.api_url.as_deref(),
+            &config.reliability,
+            &config.model_routes,
+            &model_name,
+            &provider_runtime_options,
+        )?;
+
+        // Heuristic routing yields to an explicit --provider/--model.
+        let model_router = if provider_override.is_none() && model_override.is_none() {
+            super::model_router::ModelRouter::from_config(&config, &provider_runtime_options)
+                .map(Arc::new)
+        } else {
+            None
+        };
+
+        observer.record_event(&ObserverEvent::AgentStart {
+            provider: provider_name.clone(),
+            model: model_name.clone(),
+        });
+
+        // ── Hardware RAG (datasheet retrieval when peripherals + datasheet_dir) ──
+        let hardware_rag: Option<crate::rag::HardwareRag> = config
+            .peripherals
+            .datasheet_dir
+            .as_ref()
+            .filter(|d| !d.trim().is_empty())
+            .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
+            .and_then(Result::ok)
+            .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
+        if let Some(ref rag) = hardware_rag {
+            tracing::info!(chunks = rag.len(), "Hardware RAG loaded");
+        }
+
+        let board_names: Vec<String> = config
+            .peripherals
+            .boards
+            .iter()
+            .map(|b| b.board.clone())
+            .collect();
+
+        // ── Build system prompt from workspace MD files (OpenClaw framework) ──
+        let native_tools = provider.supports_native_tools();
+        let system_prompt = build_agent_system_prompt(
+            &config,
+            &model_name,
+            &tools_registry,
+            &mcp_tool_descs,
+            native_tools,
+        );
+
+        // ── Approval manager (supervised mode) ───────────────────────
+        // Created inside a role scope, the runtime answers a remote user
+        // (e.g. a gateway client): it runs as that role and calls that need
+        // approval are denied unless a prompter is added.
+        let remote_role = crate::security::roles::scoped();
+        let approval_manager = if interactive || remote_role.is_some() {
+            Some(ApprovalManager::from_root_config(&config))
+        } else {
+            None
+        };
+        let channel_name = if interactive { "cli" } else { "daemon" };
+        if session.is_some() && !config.conversations.enabled {
+            tracing::warn!("--session ignored: [conversations] is disabled");
+        }
+        let conversation = crate::conversations::open_from_config(&config).map(|store| {
+            // `--session` takes an ID or a session name; unknown values start a new session.
+            let session_id = match session {
+                Some(requested) => store
+                    .resolve_session(requested)
+                    .ok()
+                    .flatten()
+                    .map_or_else(|| requested.to_string(), |found| found.id),
+                None => format!("{channel_name}:{}", Uuid::new_v4()),
+            };
+            let handle = crate::conversations::SessionHandle::new(store, session_id);
+            if let Err(e) = handle
+                .store()
+                .ensure_session(handle.session_id(), channel_name, "user")
+            {
+                tracing::warn!("Failed to create conversation session: {e}");
+            }
+            let _ = handle.store().set_session_metadata(
+                handle.session_id(),
+                "provider",
+                &serde_json::json!(provider_name),
+            );
+            let _ = handle.store().set_session_metadata(
+                handle.session_id(),
+                "model",
+                &serde_json::json!(model_name),
+            );
+            handle
+        });
+        let resumed: Vec<ChatMessage> = match (session, conversation.as_ref()) {
+            (Some(_), Some(conversation)) => conversation
+                .store()
+                .recent_messages(conversation.session_id(), DEFAULT_MAX_HISTORY_MESSAGES)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|m| match m.role.as_str() {
+                    "user" => Some(ChatMessage::user(&m.content)),
+                    "assistant" => Some(ChatMessage::assistant(&m.content)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let pins = conversation
+            .as_ref()
+            .and_then(|conversation| {
+                conversation
+                    .store()
+                    .session(conversation.session_id())
+                    .ok()
+                    .flatten()
+            })
+            .map(|session| super::context::Pins::from_metadata(&session.metadata))
+            .unwrap_or_default();
+
+        let budget = crate::cost::budget::SessionBudget::from_config(&config);
+        let replay_session = conversation.as_ref().map_or_else(
+            || format!("{channel_name}:{}", Uuid::new_v4()),
+            |conversation| conversation.session_id().to_string(),
+        );
+        let recorder = super::replay::recorder_for(&config, &replay_session);
+        if let Some(recorder) = recorder.as_deref() {
+            tracing::info!(path = %recorder.path().display(), "Recording session for replay");
+        }
+        let debug_dump = Arc::new(super::debug_dump::DebugDump::new(
+            &config.workspace_dir,
+            &replay_session,
+            config.agent.debug_prompts,
+        ));
+        if debug_dump.is_enabled() {
+            tracing::info!(path = %debug_dump.session_dir().display(), "Dumping prompts for debugging");
+        }
+        let dry_run = Arc::new(super::dry_run::DryRun::new(config.agent.dry_run));
+        if dry_run.is_enabled() {
+            tracing::info!("Dry run: write-capable tools report instead of running");
+        }
+        let undo = Arc::new(super::undo::Journal::new(&config.workspace_dir));
+        let guardrails = crate::security::Guardrails::from_config(&config.guardrails)?;
+
+        Ok(Self {
+            config,
+            observer,
+            mem,
+            tools_registry,
+            provider,
+            provider_name,
+            model_name,
+            temperature,
+            system_prompt,
+            approval_manager,
+            channel_name,
+            conversation,
+            transcript: std::sync::Mutex::new(resumed.clone()),
+            resumed,
+            budget,
+            disabled_tools: Vec::new(),
+            role: remote_role.unwrap_or(crate::security::roles::Role::Admin),
+            pins,
+            recorder,
+            debug_dump,
+            dry_run,
+            undo,
+            workspace_session: replay_session,
+            ephemeral_workspace: session.is_none(),
+            model_router,
+            guardrails,
+            hardware_rag,
+            board_names,
+            cli_user: crate::agent::template::default_cli_user(),
+        })
+    }
+
+    /// The generated title of the session, once it has one.
+    pub(crate) fn session_title(&self) -> Option<String> {
+        let conversation = self.conversation.as_ref()?;
+        let session = conversation
+            .store()
+            .session(conversation.session_id())
+            .ok()
+            .flatten()?;
+        crate::conversations::title::title(&session).map(str::to_string)
+    }
+
+    /// Whether replies are shown as formatted markdown.
+    pub(crate) fn rich_output(&self) -> bool {
+        crate::markdown::rich_output(self.config.agent.render_markdown)
+    }
+
+    /// The system prompt and pins followed by any resumed turns.
+    pub(crate) fn initial_history(&self) -> Vec<ChatMessage> {
+        let mut history = vec![ChatMessage::system(self.pins.apply(&self.system_prompt))];
+        history.extend(self.resumed.iter().cloned());
+        history
+    }
+
+    /// Run `[guardrails]` over a prompt or reply; `Err` carries the text
+    /// shown instead of a blocked one.
+    pub(crate) async fn guard(
+        &self,
+        direction: crate::config::GuardrailDirection,
+        text: String,
+    ) -> std::result::Result<String, String> {
+        guard_cli_text(
+            self.guardrails.as_ref(),
+            self.provider.as_ref(),
+            direction,
+            self.channel_name,
+            text,
+        )
+        .await
+    }
+
+    /// Auto-save the message, refresh the system prompt and push the message
+    /// with memory and hardware context onto `history`.
+    pub(crate) async fn prepare_turn(&self, history: &mut Vec<ChatMessage>, user_input: &str) {
+        let config = &self.config;
+        // Auto-save conversation turns (skip short/trivial messages)
+        if config.memory.auto_save && user_input.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
             let user_key = autosave_memory_key("user_msg");
-            let _ = mem
-                .store(&user_key, &msg, MemoryCategory::Conversation, None)
+            let _ = self
+                .mem
+                .store(&user_key, user_input, MemoryCategory::Conversation, None)
                 .await;
         }
 
         // Inject memory + hardware RAG context into user message
-        let mem_context =
-            build_context(mem.as_ref(), &msg, config.memory.min_relevance_score).await;
+        let mem_context = build_context(
+            self.mem.as_ref(),
+            user_input,
+            config.memory.min_relevance_score,
+        )
+        .await;
         let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-        let hw_context = hardware_rag
+        let hw_context = self
+            .hardware_rag
             .as_ref()
-            .map(|r| build_hardware_context(r, &msg, &board_names, rag_limit))
+            .map(|r| build_hardware_context(r, user_input, &self.board_names, rag_limit))
             .unwrap_or_default();
         let context = format!("{mem_context}{hw_context}");
-        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
+        let now = crate::time::now_stamp();
         let enriched = if context.is_empty() {
-            format!("[{now}] {msg}")
+            format!("[{now}] {user_input}")
         } else {
-            format!("{context}[{now}] {msg}")
+            format!("{context}[{now}] {user_input}")
         };
 
-        let mut history = vec![
-            ChatMessage::system(&system_prompt),
-            ChatMessage::user(&enriched),
-        ];
+        // Re-expand the system prompt so time, memories etc. stay current.
+        let turn_prompt = if config.agent.prompt_templates {
+            let prompt_vars = crate::agent::template::PromptVars {
+                channel: self.channel_name,
+                user: &self.cli_user,
+                model: &self.model_name,
+                agent: None,
+                tools: &self.tools_registry,
+            };
+            crate::agent::template::render_for_turn(
+                &self.system_prompt,
+                &prompt_vars,
+                self.mem.as_ref(),
+                user_input,
+                None,
+            )
+            .await
+        } else {
+            self.system_prompt.clone()
+        };
+        if let Some(first) = history.first_mut().filter(|m| m.role == "system") {
+            *first = ChatMessage::system(self.pins.apply(&turn_prompt));
+        }
+        history.push(ChatMessage::user(&enriched));
+    }
+
+    /// Run the tool-call loop for a prepared turn inside the session's
+    /// [`TurnScope`](super::scope::TurnScope).
+    pub(crate) async fn run_turn(
+        &self,
+        history: &mut Vec<ChatMessage>,
+        user_input: &str,
+        cancellation: Option<CancellationToken>,
+        on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    ) -> Result<String> {
+        let mut excluded_tools = self.disabled_tools.clone();
+        excluded_tools.extend(crate::tools::toggles::disabled());
+        excluded_tools.extend(crate::security::roles::excluded_tools(
+            self.role,
+            self.tools_registry.iter(),
+        ));
+        let scope = super::scope::TurnScope {
+            role: self.role,
+            model_router: self.model_router.clone(),
+            recorder: self.recorder.clone(),
+            debug_dump: Some(Arc::clone(&self.debug_dump)),
+            dry_run: Some(Arc::clone(&self.dry_run)),
+            undo: Some(Arc::clone(&self.undo)),
+            budget: self.budget.clone(),
+            ..super::scope::TurnScope::new(self.workspace_session.clone(), user_input)
+        };
+        scope
+            .run(run_tool_call_loop(
+                self.provider.as_ref(),
+                history,
+                &self.tools_registry,
+                self.observer.as_ref(),
+                &self.provider_name,
+                &self.model_name,
+                self.temperature,
+                false,
+                self.approval_manager.as_ref(),
+                self.channel_name,
+                &self.config.multimodal,
+                self.config.agent.max_tool_iterations,
+                cancellation,
+                on_delta,
+                None,
+                &excluded_tools,
+                Some(&self.config.agent.tool_output),
+                self.conversation.as_ref(),
+            ))
+            .await
+    }
 
-        let response = run_tool_call_loop(
-            provider.as_ref(),
-            &mut history,
-            &tools_registry,
-            observer.as_ref(),
-            provider_name,
-            model_name,
-            temperature,
-            false,
-            approval_manager.as_ref(),
-            channel_name,
-            &config.multimodal,
-            config.agent.max_tool_iterations,
-            None,
-            None,
-            None,
-            &[],
+    /// Close an interrupted turn: the rounds that finished stay in history
+    /// and the next turn builds on them.
+    pub(crate) fn interrupt_turn(&self, history: &mut Vec<ChatMessage>, user_input: &str) {
+        history.push(ChatMessage::assistant(INTERRUPTED_TURN_NOTE));
+        self.record_transcript(user_input, INTERRUPTED_TURN_NOTE);
+        if let Some(conversation) = self.conversation.as_ref() {
+            conversation.append_message("user", user_input);
+            conversation.append_message("assistant", INTERRUPTED_TURN_NOTE);
+        }
+    }
+
+    /// Screen the reply, store the turn and extract facts; returns the reply
+    /// to show.
+    pub(crate) async fn finish_turn(&self, user_input: &str, response: String) -> String {
+        let response = self
+            .guard(crate::config::GuardrailDirection::Output, response)
+            .await
+            .unwrap_or_else(|blocked| blocked);
+        self.record_transcript(user_input, &response);
+        if let Some(conversation) = self.conversation.as_ref() {
+            conversation.append_message("user", user_input);
+            conversation.append_message("assistant", &response);
+            if self.config.conversations.auto_title {
+                crate::conversations::title::maybe_title(
+                    conversation.store(),
+                    conversation.session_id(),
+                    self.provider.as_ref(),
+                    self.config
+                        .conversations
+                        .title_model
+                        .as_deref()
+                        .unwrap_or(&self.model_name),
+                )
+                .await;
+            }
+        }
+        if self.config.memory.fact_extraction {
+            memory::facts::extract_and_remember(
+                self.provider.as_ref(),
+                self.config
+                    .memory
+                    .fact_extraction_model
+                    .as_deref()
+                    .unwrap_or(&self.model_name),
+                self.mem.as_ref(),
+                user_input,
+                &response,
+                None,
+                None,
+            )
+            .await;
+        }
+        self.observer.record_event(&ObserverEvent::TurnComplete);
+        response
+    }
+
+    /// Compact, then hard-trim, the history between turns. Returns whether
+    /// compaction ran.
+    pub(crate) async fn compact_history(&self, history: &mut Vec<ChatMessage>) -> bool {
+        // Auto-compaction before hard trimming to preserve long-context signal.
+        let compacted = auto_compact_history(
+            history,
+            self.provider.as_ref(),
+            &self.model_name,
+            self.config.agent.max_history_messages,
+            self.config.agent.max_context_tokens,
         )
-        .await?;
-        final_output = response.clone();
-        println!("{response}");
-        observer.record_event(&ObserverEvent::TurnComplete);
+        .await
+        .unwrap_or(false);
+        // Hard caps as a safety net.
+        trim_history(history, self.config.agent.max_history_messages);
+        super::context::trim_to_token_budget(history, self.config.agent.max_context_tokens);
+        compacted
+    }
+
+    /// Start over: reset `history`, clear the stored session and forget
+    /// conversation and daily memories. Returns how many memories went.
+    /// Pins stay; `/unpin all` removes them.
+    pub(crate) async fn clear(&self, history: &mut Vec<ChatMessage>) -> usize {
+        history.clear();
+        history.push(ChatMessage::system(self.pins.apply(&self.system_prompt)));
+        self.transcript
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        if let Some(conversation) = self.conversation.as_ref() {
+            let _ = conversation
+                .store()
+                .clear_session(conversation.session_id());
+        }
+        crate::security::workspace::end_session(
+            &self.config.workspace_dir,
+            &self.workspace_session,
+        );
+        let mut cleared = 0;
+        for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
+            let entries = self
+                .mem
+                .list(Some(&category), None)
+                .await
+                .unwrap_or_default();
+            for entry in entries {
+                if self.mem.forget(&entry.key).await.unwrap_or(false) {
+                    cleared += 1;
+                }
+            }
+        }
+        cleared
+    }
+
+    fn record_transcript(&self, user_input: &str, response: &str) {
+        let mut transcript = self.transcript.lock().unwrap_or_else(|e| e.into_inner());
+        transcript.push(ChatMessage::user(user_input));
+        transcript.push(ChatMessage::assistant(response));
+    }
+
+    /// Switch the model for the rest of the session; heuristic routing
+    /// yields to it, as it does to `--model`.
+    pub(crate) fn set_model(&mut self, model: &str) {
+        self.model_name = model.to_string();
+        self.model_router = None;
+        if let Some(conversation) = self.conversation.as_ref() {
+            let _ = conversation.store().set_session_metadata(
+                conversation.session_id(),
+                "model",
+                &serde_json::json!(model),
+            );
+        }
+    }
+
+    /// Switch provider for the rest of the session, keeping the model.
+    pub(crate) fn set_provider(&mut self, provider_name: &str) -> Result<()> {
+        self.provider = providers::create_routed_provider_with_options(
+            provider_name,
+            self.config.api_key.as_deref(),
+            self.config.api_url.as_deref(),
+            &self.config.reliability,
+            &self.config.model_routes,
+            &self.model_name,
+            &cli_provider_runtime_options(&self.config),
+        )?;
+        self.provider_name = provider_name.to_string();
+        self.model_router = None;
+        if let Some(conversation) = self.conversation.as_ref() {
+            let _ = conversation.store().set_session_metadata(
+                conversation.session_id(),
+                "provider",
+                &serde_json::json!(provider_name),
+            );
+        }
+        Ok(())
+    }
+
+    /// Store the pins with the session and put them in the system message
+    /// of `history`.
+    fn save_pins(&self, history: &mut [ChatMessage]) {
+        if let Some(conversation) = self.conversation.as_ref() {
+            let _ = conversation.store().set_session_metadata(
+                conversation.session_id(),
+                super::context::PINS_METADATA_KEY,
+                &self.pins.to_metadata(),
+            );
+        }
+        if let Some(first) = history.first_mut().filter(|m| m.role == "system") {
+            *first = ChatMessage::system(self.pins.apply(&self.system_prompt));
+        }
+    }
+
+    /// Run a slash command against `history` and return the reply. `/new`
+    /// and `/quit` need the front end and are handled there.
+    pub(crate) async fn run_command(
+        &mut self,
+        command: super::commands::SlashCommand,
+        history: &mut Vec<ChatMessage>,
+    ) -> String {
+        use super::commands::{self, SlashCommand};
+        match command {
+            SlashCommand::Help => commands::help_text(),
+            SlashCommand::Model(None) => format!(
+                "Provider: {} · model: {}\nSwitch with `/model <id>` or `/models <provider>`.",
+                self.provider_name, self.model_name
+            ),
+            SlashCommand::Model(Some(model)) => {
+                let model = model.trim_matches('`');
+                self.set_model(model);
+                format!(
+                    "Model switched to {model} (provider {}).",
+                    self.provider_name
+                )
+            }
+            SlashCommand::Provider(None) => {
+                let names: Vec<String> = providers::list_providers()
+                    .into_iter()
+                    .map(|p| p.name.to_string())
+                    .collect();
+                format!(
+                    "Provider: {}\nAvailable: {}\nSwitch with `/models <provider>`.",
+                    self.provider_name,
+                    names.join(", ")
+                )
+            }
+            SlashCommand::Provider(Some(raw)) => {
+                match crate::channels::resolve_provider_alias(&raw) {
+                    None => format!("Unknown provider {raw}. `/models` lists them."),
+                    Some(name) => match self.set_provider(&name) {
+                        Ok(()) => format!(
+                            "Provider switched to {name}; model is still {}. Use `/model <id>` to pick one it serves.",
+                            self.model_name
+                        ),
+                        Err(e) => format!(
+                            "Failed to initialize provider {name}; keeping {}.\nDetails: {}",
+                            self.provider_name,
+                            providers::sanitize_api_error(&e.to_string())
+                        ),
+                    },
+                }
+            }
+            SlashCommand::Tools(tools) => {
+                let available: Vec<&str> = self.tools_registry.iter().map(|t| t.name()).collect();
+                commands::toggle_tools(&tools, &mut self.disabled_tools, &available)
+            }
+            SlashCommand::SearchMemory(query) => {
+                commands::search_memory(self.mem.as_ref(), &query).await
+            }
+            SlashCommand::Pin(text) => {
+                let reply = self.pins.pin(text.as_deref(), history);
+                self.save_pins(history);
+                reply
+            }
+            SlashCommand::Unpin(index) => {
+                let reply = self.pins.unpin(index);
+                self.save_pins(history);
+                reply
+            }
+            SlashCommand::Context => super::context::report(
+                &self.system_prompt,
+                &self.pins,
+                history,
+                self.config.agent.max_context_tokens,
+            ),
+            SlashCommand::Export(file) => {
+                let transcript = self
+                    .transcript
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone();
+                match commands::export_transcript(
+                    &transcript,
+                    self.session_title().as_deref(),
+                    &self.config.workspace_dir,
+                    file.as_deref(),
+                ) {
+                    Ok(path) => format!("Conversation exported to {}", path.display()),
+                    Err(e) => format!("Export failed: {e:#}"),
+                }
+            }
+            SlashCommand::Budget { override_limits } => match self.budget.as_deref() {
+                None => "Budget limits are disabled ([budget].enabled = false).".into(),
+                Some(budget) if override_limits => {
+                    budget.override_limits();
+                    "Budget limits lifted for this session.".into()
+                }
+                Some(budget) => budget.report(),
+            },
+            SlashCommand::Debug(toggle) => commands::toggle_debug(&self.debug_dump, toggle),
+            SlashCommand::DryRun(toggle) => commands::toggle_dry_run(&self.dry_run, toggle),
+            SlashCommand::Undo(undo) => self.undo.run(undo).await,
+            SlashCommand::New | SlashCommand::Edit(_) | SlashCommand::Quit => String::new(),
+        }
+    }
+
+    /// Record the end of the session.
+    pub(crate) fn finish(&self, started: Instant) {
+        self.observer.record_event(&ObserverEvent::AgentEnd {
+            provider: self.provider_name.clone(),
+            model: self.model_name.clone(),
+            duration: started.elapsed(),
+            tokens_used: None,
+            cost_usd: None,
+        });
+    }
+}
+
+fn cli_provider_runtime_options(config: &Config) -> providers::ProviderRuntimeOptions {
+    providers::ProviderRuntimeOptions {
+        auth_profile_override: None,
+        provider_api_url: config.api_url.clone(),
+        zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+        secrets_encrypt: config.secrets.encrypt,
+        reasoning_enabled: config.runtime.reasoning_enabled,
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub async fn run(
+    config: Config,
+    message: Option<String>,
+    provider_override: Option<String>,
+    model_override: Option<String>,
+    temperature: f64,
+    peripheral_overrides: Vec<String>,
+    interactive: bool,
+    session: Option<String>,
+) -> Result<String> {
+    let mut runtime = CliRuntime::new(
+        config,
+        provider_override,
+        model_override,
+        temperature,
+        peripheral_overrides,
+        interactive,
+        session.as_deref(),
+    )
+    .await?;
+
+    // ── Execute ──────────────────────────────────────────────────
+    let start = Instant::now();
+    let mut final_output = String::new();
+
+    if let Some(msg) = message {
+        let msg = match runtime
+            .guard(crate::config::GuardrailDirection::Input, msg)
+            .await
+        {
+            Ok(msg) => msg,
+            Err(blocked) => {
+                println!("{blocked}");
+                return Ok(blocked);
+            }
+        };
+        let mut history = runtime.initial_history();
+        runtime.prepare_turn(&mut history, &msg).await;
+        let response = {
+            // Daemon, cron and API callers run without a terminal to draw on.
+            let mut status = interactive
+                .then(|| super::status_line::StatusLine::start(&runtime.config, false))
+                .flatten();
+            let (delta_tx, mut deltas) = tokio::sync::mpsc::channel::<String>(64);
+            let mut frames = tokio::time::interval(super::status_line::FRAME_INTERVAL);
+            let turn = runtime.run_turn(
+                &mut history,
+                &msg,
+                None,
+                status.is_some().then_some(delta_tx),
+            );
+            tokio::pin!(turn);
+            loop {
+                tokio::select! {
+                    result = &mut turn => break result,
+                    Some(delta) = deltas.recv() => {
+                        if let Some(status) = status.as_mut() {
+                            status.apply(&delta);
+                        }
+                    }
+                    _ = frames.tick(), if status.is_some() => {
+                        if let Some(status) = status.as_mut() {
+                            status.tick();
+                        }
+                    }
+                }
+            }
+        }?;
+        let response = runtime.finish_turn(&msg, response).await;
+        println!(
+            "{}",
+            crate::markdown::for_terminal(&response, runtime.rich_output())
+        );
+        final_output = response;
     } else {
-        println!("🦀 ZeroClaw Interactive Mode");
-        println!("Type /help for commands.\n");
+        println!("{}", crate::i18n::t("interactive-banner"));
+        println!("{}\n", crate::i18n::t("interactive-hint"));
         let cli = crate::channels::CliChannel::new();
+        // Input is read on its own thread so a line typed mid-turn is seen
+        // right away; approval answers come through the same lines.
+        let mut lines = LineReader::spawn(
+            runtime
+                .config
+                .config_path
+                .parent()
+                .map(|dir| dir.join(CLI_HISTORY_FILE)),
+            super::commands::completions(),
+        );
+        let pending_approvals = Arc::new(crate::approval::PendingApprovals::new());
+        let approval_timeout =
+            Duration::from_secs(runtime.config.autonomy.risk_approval.timeout_secs.max(1));
+        runtime.approval_manager = runtime.approval_manager.take().map(|manager| {
+            manager.with_prompter(Arc::new(crate::approval::ChannelApprovalPrompter::new(
+                Arc::new(crate::channels::CliChannel::new()),
+                "user",
+                None,
+                CLI_APPROVAL_SCOPE,
+                Arc::clone(&pending_approvals),
+                approval_timeout,
+            )))
+        });
+        // Input that interrupted the previous turn, or was composed with
+        // `/edit`, handled next.
+        let mut steering: Option<String> = None;
 
         // Persistent conversation history across turns
-        let mut history = vec![ChatMessage::system(&system_prompt)];
+        let mut history = runtime.initial_history();
+        if !runtime.resumed.is_empty() {
+            println!(
+                "{}\n",
+                crate::i18n::t_args(
+                    "session-resumed",
+                    &[
+                        ("session", session.as_deref().unwrap_or_default().into()),
+                        ("count", runtime.resumed.len().into()),
+                    ],
+                )
+            );
+        }
 
         loop {
-            print!("> ");
-            let _ = std::io::stdout().flush();
-
-            let mut input = String::new();
-            match std::io::stdin().read_line(&mut input) {
-                Ok(0) => break,
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("\nError reading input: {e}\n");
-                    break;
+            let input = if let Some(input) = steering.take() {
+                input
+            } else {
+                match lines.read(PROMPT).await {
+                    Some(line) => line,
+                    None => break,
                 }
-            }
+            };
 
             let user_input = input.trim().to_string();
             if user_input.is_empty() {
                 continue;
             }
-            match user_input.as_str() {
-                "/quit" | "/exit" => break,
-                "/help" => {
-                    println!("Available commands:");
-                    println!("  /help        Show this help message");
-                    println!("  /clear /new  Clear conversation history");
-                    println!("  /quit /exit  Exit interactive mode\n");
-                    continue;
-                }
-                "/clear" | "/new" => {
-                    println!(
-                        "This will clear the current conversation and delete all session memory."
-                    );
-                    println!("Core memories (long-term facts/preferences) will be preserved.");
-                    print!("Continue? [y/N] ");
-                    let _ = std::io::stdout().flush();
-
-                    let mut confirm = String::new();
-                    if std::io::stdin().read_line(&mut confirm).is_err() {
-                        continue;
-                    }
-                    if !matches!(confirm.trim().to_lowercase().as_str(), "y" | "yes") {
-                        println!("Cancelled.\n");
+            match super::commands::parse(&user_input) {
+                Some(Ok(super::commands::SlashCommand::Quit)) => break,
+                Some(Ok(super::commands::SlashCommand::New)) => {
+                    println!("{}", crate::i18n::t("clear-warning"));
+                    println!("{}", crate::i18n::t("clear-keeps-core"));
+                    let prompt = format!("{} ", crate::i18n::t("confirm-continue"));
+                    let Some(confirm) = lines.read(&prompt).await else {
+                        break;
+                    };
+                    if !crate::i18n::is_yes(&confirm) {
+                        println!("{}\n", crate::i18n::t("cancelled"));
                         continue;
                     }
 
-                    history.clear();
-                    history.push(ChatMessage::system(&system_prompt));
-                    // Clear conversation and daily memory
-                    let mut cleared = 0;
-                    for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
-                        let entries = mem.list(Some(&category), None).await.unwrap_or_default();
-                        for entry in entries {
-                            if mem.forget(&entry.key).await.unwrap_or(false) {
-                                cleared += 1;
-                            }
+                    let cleared = runtime.clear(&mut history).await;
+                    println!(
+                        "{}\n",
+                        crate::i18n::t_args("conversation-cleared", &[("count", cleared.into())])
+                    );
+                    continue;
+                }
+                Some(Ok(super::commands::SlashCommand::Edit(initial))) => {
+                    match super::editor::compose(initial.as_deref().unwrap_or_default()) {
+                        Ok(Some(text)) => {
+                            println!("{text}\n");
+                            steering = Some(text);
                         }
-                    }
-                    if cleared > 0 {
-                        println!("Conversation cleared ({cleared} memory entries removed).\n");
-                    } else {
-                        println!("Conversation cleared.\n");
+                        Ok(None) => println!("{}\n", crate::i18n::t("edit-empty")),
+                        Err(e) => println!("{e:#}\n"),
                     }
                     continue;
                 }
-                _ => {}
-            }
-
-            // Auto-save conversation turns (skip short/trivial messages)
-            if config.memory.auto_save && user_input.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
-                let user_key = autosave_memory_key("user_msg");
-                let _ = mem
-                    .store(&user_key, &user_input, MemoryCategory::Conversation, None)
-                    .await;
+                Some(Ok(command)) => {
+                    println!(
+                        "{}\n",
+                        runtime.run_command(command, &mut history).await.trim_end()
+                    );
+                    continue;
+                }
+                Some(Err(usage)) => {
+                    println!("{usage}\n");
+                    continue;
+                }
+                None => {}
             }
 
-            // Inject memory + hardware RAG context into user message
-            let mem_context =
-                build_context(mem.as_ref(), &user_input, config.memory.min_relevance_score).await;
-            let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-            let hw_context = hardware_rag
-                .as_ref()
-                .map(|r| build_hardware_context(r, &user_input, &board_names, rag_limit))
-                .unwrap_or_default();
-            let context = format!("{mem_context}{hw_context}");
-            let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
-            let enriched = if context.is_empty() {
-                format!("[{now}] {user_input}")
-            } else {
-                format!("{context}[{now}] {user_input}")
-            };
-
-            history.push(ChatMessage::user(&enriched));
-
-            let response = match run_tool_call_loop(
-                provider.as_ref(),
-                &mut history,
-                &tools_registry,
-                observer.as_ref(),
-                provider_name,
-                model_name,
-                temperature,
-                false,
-                approval_manager.as_ref(),
-                channel_name,
-                &config.multimodal,
-                config.agent.max_tool_iterations,
-                None,
-                None,
-                None,
-                &[],
-            )
-            .await
+            let user_input = match runtime
+                .guard(crate::config::GuardrailDirection::Input, user_input)
+                .await
             {
+                Ok(user_input) => user_input,
+                Err(blocked) => {
+                    println!("{blocked}\n");
+                    continue;
+                }
+            };
+            runtime.prepare_turn(&mut history, &user_input).await;
+
+            let cancellation = CancellationToken::new();
+            let result = {
+                let mut status = super::status_line::StatusLine::start(&runtime.config, true);
+                let (delta_tx, mut deltas) = tokio::sync::mpsc::channel::<String>(64);
+                let mut frames = tokio::time::interval(super::status_line::FRAME_INTERVAL);
+                let turn = runtime.run_turn(
+                    &mut history,
+                    &user_input,
+                    Some(cancellation.clone()),
+                    status.is_some().then_some(delta_tx),
+                );
+                tokio::pin!(turn);
+                loop {
+                    tokio::select! {
+                        result = &mut turn => break result,
+                        Some(delta) = deltas.recv() => {
+                            if let Some(status) = status.as_mut() {
+                                status.apply(&delta);
+                            }
+                        }
+                        _ = frames.tick(), if status.is_some() => {
+                            if let Some(status) = status.as_mut() {
+                                status.tick();
+                            }
+                        }
+                        Some(line) = lines.read(PROMPT), if steering.is_none() => {
+                            if pending_approvals.resolve(CLI_APPROVAL_SCOPE, &line)
+                                || line.trim().is_empty()
+                            {
+                                continue;
+                            }
+                            super::status_line::suspend();
+                            println!("\n{}", crate::i18n::t("interrupting"));
+                            steering = Some(line);
+                            cancellation.cancel();
+                        }
+                    }
+                }
+            };
+            let response = match result {
                 Ok(resp) => resp,
+                Err(e) if is_tool_loop_cancelled(&e) => {
+                    runtime.interrupt_turn(&mut history, &user_input);
+                    continue;
+                }
                 Err(e) => {
-                    eprintln!("\nError: {e}\n");
+                    eprintln!("\n{}\n", crate::error::terminal_message(&e));
                     continue;
                 }
             };
-            final_output = response.clone();
+            let response = runtime.finish_turn(&user_input, response).await;
+            let shown = crate::markdown::for_terminal(&response, runtime.rich_output());
+            final_output = response;
             if let Err(e) = crate::channels::Channel::send(
                 &cli,
-                &crate::channels::traits::SendMessage::new(format!("\n{response}\n"), "user"),
+                &crate::channels::traits::SendMessage::new(format!("\n{shown}\n"), "user"),
             )
             .await
             {
                 eprintln!("\nError sending CLI response: {e}\n");
             }
-            observer.record_event(&ObserverEvent::TurnComplete);
 
-            // Auto-compaction before hard trimming to preserve long-context signal.
-            if let Ok(compacted) = auto_compact_history(
-                &mut history,
-                provider.as_ref(),
-                model_name,
-                config.agent.max_history_messages,
-            )
-            .await
-            {
-                if compacted {
-                    println!("🧹 Auto-compaction complete");
-                }
+            if runtime.compact_history(&mut history).await {
+                println!("{}", crate::i18n::t("auto-compacted"));
             }
-
-            // Hard cap as a safety net.
-            trim_history(&mut history, config.agent.max_history_messages);
         }
     }
 
-    let duration = start.elapsed();
-    observer.record_event(&ObserverEvent::AgentEnd {
-        provider: provider_name.to_string(),
-        model: model_name.to_string(),
-        duration,
-        tokens_used: None,
-        cost_usd: None,
-    });
-
+    runtime.finish(start);
     Ok(final_output)
 }
 
@@ -3221,12 +4348,16 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         &config.autonomy,
         &config.workspace_dir,
     ));
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
-        &config.memory,
-        Some(&config.storage.provider.config),
-        &config.workspace_dir,
-        config.api_key.as_deref(),
-    )?);
+    // Scoped so webhook messages handled in a memory scope stay isolated.
+    let mem: Arc<dyn Memory> = Arc::new(memory::scope::ScopedMemory::new(
+        Arc::from(memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?),
+        config.memory.scopes.read_global,
+    ));
 
     let (composio_key, composio_entity_id) = if config.composio.enabled {
         (
@@ -3255,6 +4386,14 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
     tools_registry.extend(peripheral_tools);
 
+    let mcp_tools =
+        crate::mcp::create_mcp_tools(&config.mcp, &security, &config.workspace_dir).await;
+    let mcp_tool_descs: Vec<(String, String)> = mcp_tools
+        .iter()
+        .map(|tool| (tool.name().to_string(), tool.description().to_string()))
+        .collect();
+    tools_registry.extend(mcp_tools);
+
     let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
     let model_name = config
         .default_model
@@ -3297,6 +4436,9 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         ("shell", "Execute terminal commands."),
         ("file_read", "Read file contents."),
         ("file_write", "Write file contents."),
+        ("apply_patch", "Apply a unified diff to files."),
+        ("list_dir", "List a directory."),
+        ("git_operations", "Inspect and commit git changes."),
         ("memory_store", "Save to memory."),
         ("memory_recall", "Search memory."),
         ("memory_forget", "Delete a memory entry."),
@@ -3306,10 +4448,30 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         ),
         ("screenshot", "Capture a screenshot."),
         ("image_info", "Read image metadata."),
+        ("read_document", "Read PDF/DOCX/EPUB text."),
     ];
     if config.browser.enabled {
         tool_descs.push(("browser_open", "Open approved URLs in browser."));
     }
+    if config.code_interpreter.enabled {
+        tool_descs.push(("run_code", "Run Python/JavaScript in a sandbox."));
+    }
+    if config.sql.enabled {
+        tool_descs.push(("sql", "Query a configured database."));
+    }
+    if config.multimodal.ocr.enabled {
+        tool_descs.push(("ocr", "Extract text from an image."));
+    }
+    if config.clipboard.enabled {
+        tool_descs.push(("clipboard_read", "Read the clipboard."));
+        tool_descs.push(("clipboard_write", "Copy text to the clipboard."));
+    }
+    if config.image_generation.enabled {
+        tool_descs.push(("generate_image", "Generate an image from a prompt."));
+    }
+    if config.calendar.enabled {
+        tool_descs.push(("calendar", "List or change calendar events."));
+    }
     if config.composio.enabled {
         tool_descs.push(("composio", "Execute actions on 1000+ apps via Composio."));
     }
@@ -3340,6 +4502,9 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
             "Query connected hardware for reported GPIO pins and LED pin. Use when user asks what pins are available.",
         ));
     }
+    for (name, description) in &mcp_tool_descs {
+        tool_descs.push((name.as_str(), description.as_str()));
+    }
     let bootstrap_max_chars = if config.agent.compact_context {
         Some(6000)
     } else {
@@ -3359,6 +4524,9 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     if !native_tools {
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
+    if let Some(section) = crate::i18n::reply_language_prompt(&config.locale) {
+        system_prompt.push_str(&section);
+    }
 
     let mem_context = build_context(mem.as_ref(), message, config.memory.min_relevance_score).await;
     let rag_limit = if config.agent.compact_context { 2 } else { 5 };
@@ -3367,7 +4535,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         .map(|r| build_hardware_context(r, message, &board_names, rag_limit))
         .unwrap_or_default();
     let context = format!("{mem_context}{hw_context}");
-    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
+    let now = crate::time::now_stamp();
     let enriched = if context.is_empty() {
         format!("[{now}] {message}")
     } else {
@@ -3390,6 +4558,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         true,
         &config.multimodal,
         config.agent.max_tool_iterations,
+        Some(&config.agent.tool_output),
     )
     .await
 }
@@ -3703,6 +4872,8 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
         )
         .await
         .expect_err("provider without vision support should fail");
@@ -3712,6 +4883,51 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 0);
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_uses_ocr_fallback_for_non_vision_provider() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = NonVisionProvider {
+            calls: Arc::clone(&calls),
+        };
+
+        let mut history = vec![ChatMessage::user(
+            "please inspect [IMAGE:data:image/png;base64,iVBORw0KGgo=]".to_string(),
+        )];
+        let tools_registry: Vec<Box<dyn Tool>> = Vec::new();
+        let observer = NoopObserver;
+        let mut multimodal = crate::config::MultimodalConfig::default();
+        multimodal.ocr.fallback = true;
+        multimodal.ocr.tesseract_command = "zeroclaw-missing-tesseract".into();
+
+        let result = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &observer,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "cli",
+            &multimodal,
+            3,
+            None,
+            None,
+            None,
+            &[],
+            None,
+            None,
+        )
+        .await
+        .expect("OCR fallback should let a non-vision provider answer");
+
+        assert_eq!(result, "ok");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(!history[0].content.contains("[IMAGE:"));
+        assert!(history[0].content.contains("text extraction failed"));
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_rejects_oversized_image_payload() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -3730,6 +4946,7 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 1,
             allow_remote_fetch: false,
+            ocr: crate::config::OcrConfig::default(),
         };
 
         let err = run_tool_call_loop(
@@ -3749,6 +4966,8 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
         )
         .await
         .expect_err("oversized payload must fail");
@@ -3789,6 +5008,8 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
         )
         .await
         .expect("valid multimodal payload should pass");
@@ -3805,7 +5026,7 @@ mod tests {
             tool_call_id: None,
         }];
 
-        assert!(!should_execute_tools_in_parallel(&calls, None));
+        assert!(!should_execute_tools_in_parallel(&calls, &[], None));
     }
 
     #[test]
@@ -3827,6 +5048,7 @@ mod tests {
 
         assert!(!should_execute_tools_in_parallel(
             &calls,
+            &[],
             Some(&approval_mgr)
         ));
     }
@@ -3853,6 +5075,7 @@ mod tests {
 
         assert!(should_execute_tools_in_parallel(
             &calls,
+            &[],
             Some(&approval_mgr)
         ));
     }
@@ -3915,6 +5138,8 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
         )
         .await
         .expect("parallel execution should complete");
@@ -3984,6 +5209,8 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
         )
         .await
         .expect("loop should finish after deduplicating repeated calls");
@@ -4040,6 +5267,8 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
         )
         .await
         .expect("native fallback id flow should complete");
@@ -5498,10 +6727,18 @@ Let me check the result."#;
     #[test]
     fn map_tool_name_alias_direct_coverage() {
         assert_eq!(map_tool_name_alias("bash"), "shell");
-        assert_eq!(map_tool_name_alias("filelist"), "file_list");
+        assert_eq!(map_tool_name_alias("filelist"), "list_dir");
         assert_eq!(map_tool_name_alias("memorystore"), "memory_store");
         assert_eq!(map_tool_name_alias("memoryforget"), "memory_forget");
         assert_eq!(map_tool_name_alias("http"), "http_request");
+        assert_eq!(map_tool_name_alias("fetch_url"), "web_fetch");
+        assert_eq!(map_tool_name_alias("code_interpreter"), "run_code");
+        assert_eq!(map_tool_name_alias("git"), "git_operations");
+        assert_eq!(map_tool_name_alias("read_pdf"), "read_document");
+        assert_eq!(map_tool_name_alias("create_image"), "generate_image");
+        assert_eq!(map_tool_name_alias("image_to_text"), "ocr");
+        assert_eq!(map_tool_name_alias("read_clipboard"), "clipboard_read");
+        assert_eq!(map_tool_name_alias("caldav"), "calendar");
         assert_eq!(
             map_tool_name_alias("totally_unknown_tool"),
             "totally_unknown_tool"
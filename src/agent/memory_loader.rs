@@ -44,6 +44,7 @@ impl MemoryLoader for DefaultMemoryLoader {
         }
 
         let mut context = String::from("[Memory context]\n");
+        let mut cited = false;
         for entry in entries {
             if memory::is_assistant_autosave_key(&entry.key) {
                 continue;
@@ -53,7 +54,9 @@ impl MemoryLoader for DefaultMemoryLoader {
                     continue;
                 }
             }
-            let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
+            let (label, is_source) = memory::indexer::context_label(&entry.key);
+            cited |= is_source;
+            let _ = writeln!(context, "- {label}: {}", entry.content);
         }
 
         // If all entries were below threshold, return empty
@@ -61,6 +64,10 @@ impl MemoryLoader for DefaultMemoryLoader {
             return Ok(String::new());
         }
 
+        if cited {
+            context.push_str(memory::indexer::CITATION_HINT);
+        }
+
         context.push('\n');
         Ok(context)
     }
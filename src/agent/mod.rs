@@ -1,10 +1,28 @@
 #[allow(clippy::module_inception)]
 pub mod agent;
+pub mod attachments;
+pub mod batch;
 pub mod classifier;
+pub mod commands;
+pub mod compare;
+pub mod context;
+pub mod debug_dump;
 pub mod dispatcher;
+pub mod dry_run;
+pub mod editor;
+pub mod eval;
 pub mod loop_;
 pub mod memory_loader;
+pub mod model_router;
+pub mod pipe;
 pub mod prompt;
+pub mod repl;
+pub mod replay;
+pub mod scope;
+pub mod status_line;
+pub mod template;
+pub mod tool_output;
+pub mod undo;
 
 #[cfg(test)]
 mod tests;
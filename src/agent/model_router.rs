@@ -0,0 +1,160 @@
+//! Per-request model routing from `[query_classification]` heuristics.
+//!
+//! A [`ModelRouter`] evaluates the classification rules before every model
+//! call of the tool-call loop (message length, code, tool calls made so far
+//! in the turn, local time of day) and sends the call to the provider and
+//! model of the matching `[[model_routes]]` hint. Calls that match no rule,
+//! or a hint without a usable route, go to the turn's own provider and model.
+
+use super::classifier::{classify_request, RouteContext};
+use crate::config::Config;
+use crate::providers::{self, Provider, ProviderRuntimeOptions};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+tokio::task_local! {
+    static CURRENT_ROUTING: RoutingScope;
+}
+
+#[derive(Clone)]
+struct RoutingScope {
+    router: Arc<ModelRouter>,
+    message: Arc<str>,
+}
+
+/// Run `future` with model calls routed by `router`; `message` is the user
+/// message of the turn, before memory context is added.
+pub async fn run_with<F: Future>(
+    router: Option<Arc<ModelRouter>>,
+    message: &str,
+    future: F,
+) -> F::Output {
+    match router {
+        Some(router) => {
+            let scope = RoutingScope {
+                router,
+                message: Arc::from(message),
+            };
+            CURRENT_ROUTING.scope(scope, future).await
+        }
+        None => future.await,
+    }
+}
+
+/// The router of the running task and the message it routes on, if any.
+pub fn current() -> Option<(Arc<ModelRouter>, Arc<str>)> {
+    CURRENT_ROUTING
+        .try_with(|scope| (Arc::clone(&scope.router), Arc::clone(&scope.message)))
+        .ok()
+}
+
+struct RouteTarget {
+    provider_name: String,
+    provider: Arc<dyn Provider>,
+    model: String,
+}
+
+/// Where a model call was routed.
+pub struct RoutedModel<'a> {
+    pub hint: &'a str,
+    pub provider_name: &'a str,
+    pub provider: &'a dyn Provider,
+    pub model: &'a str,
+}
+
+/// Classification rules plus a provider for every routed hint.
+pub struct ModelRouter {
+    config: crate::config::QueryClassificationConfig,
+    targets: HashMap<String, RouteTarget>,
+}
+
+impl ModelRouter {
+    /// Build the router; `None` when classification is disabled or no rule
+    /// names a hint with a route. Creating providers may block briefly.
+    pub fn from_config(config: &Config, options: &ProviderRuntimeOptions) -> Option<Self> {
+        let classification = &config.query_classification;
+        if !classification.enabled || classification.rules.is_empty() {
+            return None;
+        }
+        let mut created: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+        let mut targets = HashMap::new();
+        for rule in &classification.rules {
+            if targets.contains_key(&rule.hint) {
+                continue;
+            }
+            let Some(route) = config.model_routes.iter().find(|r| r.hint == rule.hint) else {
+                tracing::warn!(
+                    hint = rule.hint.as_str(),
+                    "Classification rule names a hint without a [[model_routes]] entry"
+                );
+                continue;
+            };
+            let provider = if let Some(provider) = created.get(&route.provider) {
+                Arc::clone(provider)
+            } else {
+                let api_key = route
+                    .api_key
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|key| !key.is_empty())
+                    .or(config.api_key.as_deref());
+                let is_default =
+                    config.default_provider.as_deref() == Some(route.provider.as_str());
+                let api_url = if is_default {
+                    config.api_url.as_deref()
+                } else {
+                    None
+                };
+                match providers::create_resilient_provider_with_options(
+                    &route.provider,
+                    api_key,
+                    api_url,
+                    &config.reliability,
+                    options,
+                ) {
+                    Ok(provider) => {
+                        let provider: Arc<dyn Provider> = Arc::from(provider);
+                        created.insert(route.provider.clone(), Arc::clone(&provider));
+                        provider
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            hint = rule.hint.as_str(),
+                            provider = route.provider.as_str(),
+                            "Skipping model route whose provider failed to initialize: {e}"
+                        );
+                        continue;
+                    }
+                }
+            };
+            targets.insert(
+                rule.hint.clone(),
+                RouteTarget {
+                    provider_name: route.provider.clone(),
+                    provider,
+                    model: route.model.clone(),
+                },
+            );
+        }
+        if targets.is_empty() {
+            return None;
+        }
+        Some(Self {
+            config: classification.clone(),
+            targets,
+        })
+    }
+
+    /// Route one model call, or `None` to keep the turn's model.
+    pub fn pick(&self, context: &RouteContext<'_>) -> Option<RoutedModel<'_>> {
+        let decision = classify_request(&self.config, context)?;
+        let (hint, target) = self.targets.get_key_value(&decision.hint)?;
+        Some(RoutedModel {
+            hint,
+            provider_name: &target.provider_name,
+            provider: target.provider.as_ref(),
+            model: &target.model,
+        })
+    }
+}
@@ -0,0 +1,305 @@
+//! `zeroclaw run`: one prompt in, one answer out, for shell pipelines.
+//!
+//! Only the final answer (or, with `--json`, a single JSON object) goes to
+//! stdout — no banners, spinners or terminal formatting — and the exit code
+//! says how the run ended.
+
+use super::loop_::CliRuntime;
+use crate::config::{Config, GuardrailDirection};
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{Observer, ObserverEvent};
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::io::Read;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// The answer was printed.
+pub const EXIT_OK: i32 = 0;
+/// The run failed (provider, tool or configuration error).
+pub const EXIT_FAILED: i32 = 1;
+/// There was no prompt to run.
+pub const EXIT_USAGE: i32 = 2;
+/// A `[budget]` limit stopped the run.
+pub const EXIT_BUDGET: i32 = 3;
+/// `[guardrails]` blocked the prompt.
+pub const EXIT_BLOCKED: i32 = 4;
+
+/// What `zeroclaw run` was asked to do.
+#[derive(Debug, Clone, Default)]
+pub struct PipeOptions {
+    /// The prompt; `-` (or nothing) reads it from stdin.
+    pub prompt: Vec<String>,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub temperature: f64,
+    pub session: Option<String>,
+    pub json: bool,
+}
+
+/// Token counts reported by the provider over the run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PipeUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_tokens: u64,
+    pub llm_calls: u64,
+}
+
+/// The `--json` document.
+#[derive(Debug, Serialize)]
+struct PipeReport<'a> {
+    ok: bool,
+    exit_code: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session: Option<&'a str>,
+    provider: &'a str,
+    model: &'a str,
+    usage: PipeUsage,
+    duration_ms: u64,
+}
+
+/// Forwards to the configured observer while adding up token usage.
+pub(super) struct UsageObserver {
+    inner: Arc<dyn Observer>,
+    input_tokens: AtomicU64,
+    output_tokens: AtomicU64,
+    llm_calls: AtomicU64,
+}
+
+impl UsageObserver {
+    pub(super) fn new(inner: Arc<dyn Observer>) -> Self {
+        Self {
+            inner,
+            input_tokens: AtomicU64::new(0),
+            output_tokens: AtomicU64::new(0),
+            llm_calls: AtomicU64::new(0),
+        }
+    }
+
+    pub(super) fn usage(&self) -> PipeUsage {
+        let input_tokens = self.input_tokens.load(Ordering::Relaxed);
+        let output_tokens = self.output_tokens.load(Ordering::Relaxed);
+        PipeUsage {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+            llm_calls: self.llm_calls.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Observer for UsageObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        if let ObserverEvent::LlmResponse {
+            input_tokens,
+            output_tokens,
+            ..
+        } = event
+        {
+            self.llm_calls.fetch_add(1, Ordering::Relaxed);
+            self.input_tokens
+                .fetch_add(input_tokens.unwrap_or(0), Ordering::Relaxed);
+            self.output_tokens
+                .fetch_add(output_tokens.unwrap_or(0), Ordering::Relaxed);
+        }
+        self.inner.record_event(event);
+    }
+
+    fn record_metric(&self, metric: &ObserverMetric) {
+        self.inner.record_metric(metric);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Read the prompt from the arguments, or from stdin for `-`.
+pub(super) fn read_prompt(args: &[String]) -> Result<String> {
+    let prompt = if args.is_empty() || args == ["-"] {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        input
+    } else {
+        args.join(" ")
+    };
+    if prompt.trim().is_empty() {
+        bail!("No prompt given (pass it as an argument, or `-` to read it from stdin)");
+    }
+    Ok(prompt)
+}
+
+/// Exit code for an error from the agent loop.
+fn exit_code_for(err: &anyhow::Error) -> i32 {
+    if crate::cost::budget::is_budget_exceeded(err) {
+        EXIT_BUDGET
+    } else {
+        EXIT_FAILED
+    }
+}
+
+/// Run one prompt and print the outcome; returns the process exit code.
+pub async fn run(mut config: Config, options: PipeOptions) -> i32 {
+    let started = Instant::now();
+    let prompt = match read_prompt(&options.prompt) {
+        Ok(prompt) => prompt,
+        Err(e) => return report_error(&options, &e, EXIT_USAGE, started),
+    };
+
+    config.agent.render_markdown = false;
+    let mut runtime = match CliRuntime::new(
+        config,
+        options.provider.clone(),
+        options.model.clone(),
+        options.temperature,
+        Vec::new(),
+        false,
+        options.session.as_deref(),
+    )
+    .await
+    {
+        Ok(runtime) => runtime,
+        Err(e) => return report_error(&options, &e, EXIT_FAILED, started),
+    };
+    let usage = Arc::new(UsageObserver::new(Arc::clone(&runtime.observer)));
+    runtime.observer = usage.clone();
+
+    let (answer, exit_code) = match runtime.guard(GuardrailDirection::Input, prompt).await {
+        Err(blocked) => (Err(anyhow::anyhow!(blocked)), EXIT_BLOCKED),
+        Ok(prompt) => {
+            let mut history = runtime.initial_history();
+            runtime.prepare_turn(&mut history, &prompt).await;
+            match runtime.run_turn(&mut history, &prompt, None, None).await {
+                Ok(response) => (Ok(runtime.finish_turn(&prompt, response).await), EXIT_OK),
+                Err(e) => {
+                    let code = exit_code_for(&e);
+                    (Err(e), code)
+                }
+            }
+        }
+    };
+    runtime.finish(started);
+
+    let session = runtime
+        .conversation
+        .as_ref()
+        .map(|c| c.session_id().to_string());
+    let report = PipeReport {
+        ok: answer.is_ok(),
+        exit_code,
+        answer: answer.as_ref().ok().map(String::as_str),
+        error: None,
+        session: session.as_deref(),
+        provider: &runtime.provider_name,
+        model: &runtime.model_name,
+        usage: usage.usage(),
+        duration_ms: elapsed_ms(started),
+    };
+    match &answer {
+        Ok(answer) if !options.json => println!("{}", answer.trim_end()),
+        Ok(_) => print_json(&report),
+        Err(e) if options.json => {
+            let error = format!("{e:#}");
+            print_json(&PipeReport {
+                error: Some(&error),
+                ..report
+            });
+        }
+        Err(e) => eprintln!("Error: {e:#}"),
+    }
+    exit_code
+}
+
+fn report_error(
+    options: &PipeOptions,
+    err: &anyhow::Error,
+    exit_code: i32,
+    started: Instant,
+) -> i32 {
+    if options.json {
+        let error = format!("{err:#}");
+        print_json(&PipeReport {
+            ok: false,
+            exit_code,
+            answer: None,
+            error: Some(&error),
+            session: options.session.as_deref(),
+            provider: options.provider.as_deref().unwrap_or_default(),
+            model: options.model.as_deref().unwrap_or_default(),
+            usage: PipeUsage::default(),
+            duration_ms: elapsed_ms(started),
+        });
+    } else {
+        eprintln!("Error: {err:#}");
+    }
+    exit_code
+}
+
+fn print_json(report: &PipeReport<'_>) {
+    match serde_json::to_string(report) {
+        Ok(line) => println!("{line}"),
+        Err(e) => eprintln!("Error: {e}"),
+    }
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::NoopObserver;
+    use std::time::Duration;
+
+    #[test]
+    fn usage_observer_sums_token_counts_and_forwards() {
+        let observer = UsageObserver::new(Arc::new(NoopObserver));
+        for (input, output) in [(Some(100), Some(20)), (Some(50), None)] {
+            observer.record_event(&ObserverEvent::LlmResponse {
+                provider: "p".into(),
+                model: "m".into(),
+                duration: Duration::from_millis(1),
+                success: true,
+                error_message: None,
+                input_tokens: input,
+                output_tokens: output,
+            });
+        }
+        observer.record_event(&ObserverEvent::TurnComplete);
+        assert_eq!(
+            observer.usage(),
+            PipeUsage {
+                input_tokens: 150,
+                output_tokens: 20,
+                total_tokens: 170,
+                llm_calls: 2,
+            }
+        );
+        assert_eq!(observer.name(), "noop");
+    }
+
+    #[test]
+    fn prompt_comes_from_arguments_and_errors_map_to_exit_codes() {
+        assert_eq!(
+            read_prompt(&["what".into(), "now?".into()]).unwrap(),
+            "what now?"
+        );
+        assert!(read_prompt(&["  ".into()]).is_err());
+        assert_eq!(exit_code_for(&anyhow::anyhow!("boom")), EXIT_FAILED);
+    }
+}
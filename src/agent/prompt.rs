@@ -3,7 +3,6 @@ use crate::identity;
 use crate::skills::Skill;
 use crate::tools::Tool;
 use anyhow::Result;
-use chrono::Local;
 use std::fmt::Write;
 use std::path::Path;
 
@@ -197,12 +196,7 @@ impl PromptSection for DateTimeSection {
     }
 
     fn build(&self, _ctx: &PromptContext<'_>) -> Result<String> {
-        let now = Local::now();
-        Ok(format!(
-            "## Current Date & Time\n\n{} ({})",
-            now.format("%Y-%m-%d %H:%M:%S"),
-            now.format("%Z")
-        ))
+        Ok(crate::time::prompt_section().trim_end().to_string())
     }
 }
 
@@ -210,6 +204,7 @@ fn inject_workspace_file(prompt: &mut String, workspace_dir: &Path, filename: &s
     let path = workspace_dir.join(filename);
     match std::fs::read_to_string(&path) {
         Ok(content) => {
+            let content = crate::security::redaction::redact(&content);
             let trimmed = content.trim();
             if trimmed.is_empty() {
                 return;
@@ -430,7 +425,7 @@ mod tests {
         let payload = rendered.trim_start_matches("## Current Date & Time\n\n");
         assert!(payload.chars().any(|c| c.is_ascii_digit()));
         assert!(payload.contains(" ("));
-        assert!(payload.ends_with(')'));
+        assert!(payload.contains(")\n\nTimezone: "));
     }
 
     #[test]
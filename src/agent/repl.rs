@@ -0,0 +1,248 @@
+//! Line editing for the interactive CLI.
+//!
+//! When stdin is a terminal, lines are read with rustyline: persistent
+//! history, Ctrl-R reverse search and tab completion of slash commands.
+//! A line ending in `\` or inside an open ``` fence continues on the next
+//! line under a continuation prompt. Piped stdin is read line by line.
+
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{CompletionType, Context, Editor, Helper};
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::mpsc as std_mpsc;
+use tokio::sync::mpsc;
+
+/// Prompt for the first line of a message.
+pub const PROMPT: &str = "> ";
+
+/// Prompt for the following lines of a multi-line message.
+const CONTINUATION_PROMPT: &str = "… ";
+
+/// Entries kept in the history file.
+const MAX_HISTORY_ENTRIES: usize = 1_000;
+
+/// Add `line` to a message being typed; returns whether the message goes
+/// on (a trailing `\`, which is dropped, or an unclosed ``` fence).
+pub fn accumulate(buffer: &mut String, line: &str) -> bool {
+    if !buffer.is_empty() {
+        buffer.push('\n');
+    }
+    buffer.push_str(line);
+    if buffer.ends_with('\\') {
+        buffer.pop();
+        return true;
+    }
+    buffer.matches("```").count() % 2 == 1
+}
+
+/// Slash commands that complete `typed`.
+pub fn complete_command(commands: &[&str], typed: &str) -> Vec<String> {
+    if !typed.starts_with('/') {
+        return Vec::new();
+    }
+    commands
+        .iter()
+        .filter(|command| command.starts_with(typed) && **command != typed)
+        .map(|command| (*command).to_string())
+        .collect()
+}
+
+struct CommandHelper {
+    commands: Vec<&'static str>,
+}
+
+impl Completer for CommandHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let candidates = complete_command(&self.commands, &line[..pos])
+            .into_iter()
+            .map(|command| Pair {
+                display: command.clone(),
+                replacement: command,
+            })
+            .collect();
+        Ok((0, candidates))
+    }
+}
+
+impl Hinter for CommandHelper {
+    type Hint = String;
+}
+
+impl Highlighter for CommandHelper {}
+
+impl Validator for CommandHelper {}
+
+impl Helper for CommandHelper {}
+
+fn editor(
+    commands: Vec<&'static str>,
+    history: Option<&PathBuf>,
+) -> rustyline::Result<Editor<CommandHelper, DefaultHistory>> {
+    let config = rustyline::Config::builder()
+        .max_history_size(MAX_HISTORY_ENTRIES)?
+        .history_ignore_dups(true)?
+        .history_ignore_space(true)
+        .auto_add_history(false)
+        .completion_type(CompletionType::List)
+        .build();
+    let mut editor = Editor::with_config(config)?;
+    editor.set_helper(Some(CommandHelper { commands }));
+    if let Some(path) = history {
+        // A missing file just means no history yet.
+        let _ = editor.load_history(path);
+    }
+    Ok(editor)
+}
+
+/// Serve read requests with the line editor until stdin closes.
+fn edit_lines(
+    mut editor: Editor<CommandHelper, DefaultHistory>,
+    history: Option<PathBuf>,
+    requests: &std_mpsc::Receiver<String>,
+    lines: &mpsc::UnboundedSender<String>,
+) {
+    while let Ok(prompt) = requests.recv() {
+        let mut buffer = String::new();
+        let mut continued = false;
+        loop {
+            let shown = if continued {
+                CONTINUATION_PROMPT
+            } else {
+                prompt.as_str()
+            };
+            match editor.readline(shown) {
+                Ok(line) => {
+                    continued = accumulate(&mut buffer, &line);
+                    if !continued {
+                        break;
+                    }
+                }
+                // Ctrl-C drops what was typed so far.
+                Err(ReadlineError::Interrupted) => {
+                    buffer.clear();
+                    continued = false;
+                }
+                Err(ReadlineError::Eof) => return,
+                Err(e) => {
+                    eprintln!("\nError reading input: {e}\n");
+                    return;
+                }
+            }
+        }
+        if !buffer.trim().is_empty() && editor.add_history_entry(buffer.as_str()).unwrap_or(false) {
+            if let Some(path) = history.as_ref() {
+                if let Err(e) = editor.append_history(path) {
+                    tracing::debug!(path = %path.display(), "Failed to save CLI history: {e}");
+                }
+            }
+        }
+        if lines.send(buffer).is_err() {
+            return;
+        }
+    }
+}
+
+/// Serve read requests from piped stdin, one line each.
+fn plain_lines(requests: &std_mpsc::Receiver<String>, lines: &mpsc::UnboundedSender<String>) {
+    let mut stdin = std::io::stdin().lines();
+    while let Ok(prompt) = requests.recv() {
+        print!("{prompt}");
+        let _ = std::io::stdout().flush();
+        match stdin.next() {
+            Some(Ok(line)) => {
+                if lines.send(line).is_err() {
+                    return;
+                }
+            }
+            Some(Err(e)) => {
+                eprintln!("\nError reading input: {e}\n");
+                return;
+            }
+            None => return,
+        }
+    }
+}
+
+/// Reads input on its own thread so a line typed while the agent works is
+/// seen right away.
+pub struct LineReader {
+    requests: std_mpsc::Sender<String>,
+    lines: mpsc::UnboundedReceiver<String>,
+    /// Prompt of a read the thread is still waiting on.
+    pending: Option<String>,
+}
+
+impl LineReader {
+    /// Start the reader; `history` is the file entries are kept in and
+    /// `commands` the slash commands Tab completes.
+    pub fn spawn(history: Option<PathBuf>, commands: Vec<&'static str>) -> Self {
+        let (requests, request_rx) = std_mpsc::channel();
+        let (line_tx, lines) = mpsc::unbounded_channel();
+        std::thread::spawn(move || {
+            if std::io::stdin().is_terminal() {
+                match editor(commands, history.as_ref()) {
+                    Ok(editor) => return edit_lines(editor, history, &request_rx, &line_tx),
+                    Err(e) => tracing::warn!("Line editor unavailable, reading plain lines: {e}"),
+                }
+            }
+            plain_lines(&request_rx, &line_tx);
+        });
+        Self {
+            requests,
+            lines,
+            pending: None,
+        }
+    }
+
+    /// The next message typed after `prompt`; `None` once input ends.
+    /// Dropping the future keeps the read waiting for the next call.
+    pub async fn read(&mut self, prompt: &str) -> Option<String> {
+        if self.pending.is_some() {
+            // Output since the earlier read started may have pushed its
+            // prompt off the current line.
+            print!("{prompt}");
+            let _ = std::io::stdout().flush();
+        } else {
+            self.requests.send(prompt.to_string()).ok()?;
+            self.pending = Some(prompt.to_string());
+        }
+        let line = self.lines.recv().await;
+        self.pending = None;
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn continuation_and_command_completion() {
+        let mut buffer = String::new();
+        assert!(accumulate(&mut buffer, "first \\"));
+        assert!(accumulate(&mut buffer, "```rust"));
+        assert!(accumulate(&mut buffer, "let x = 1;"));
+        assert!(!accumulate(&mut buffer, "```"));
+        assert_eq!(buffer, "first \n```rust\nlet x = 1;\n```");
+
+        let commands = ["/help", "/budget", "/budget override", "/quit"];
+        assert_eq!(
+            complete_command(&commands, "/bu"),
+            ["/budget", "/budget override"]
+        );
+        assert_eq!(complete_command(&commands, "/budget"), ["/budget override"]);
+        assert!(complete_command(&commands, "hello /bu").is_empty());
+    }
+}
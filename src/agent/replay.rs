@@ -0,0 +1,777 @@
+//! Session recording and deterministic replay (`[replay]`).
+//!
+//! With recording enabled, the tool-call loop appends every model request and
+//! response and every tool result of a session to
+//! `state/replay/<session>.jsonl`. `zeroclaw replay <session>` then runs the
+//! same loop again with a [`ReplayProvider`] serving the recorded responses
+//! and stand-in tools returning the recorded output, and reports the first
+//! place where the requests built now differ from the recorded ones. Nothing
+//! is sent to a model and no tool runs.
+//!
+//! Recordings are plaintext, so nothing is recorded while
+//! `[storage.encryption]` is enabled.
+
+use crate::config::Config;
+use crate::observability::NoopObserver;
+use crate::providers::traits::{
+    ChatMessage, ChatRequest, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
+};
+use crate::tools::{Tool, ToolResult, ToolSpec};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Longest excerpt shown for a diverging line.
+const EXCERPT_MAX_CHARS: usize = 120;
+
+tokio::task_local! {
+    static CURRENT_RECORDER: Arc<Recorder>;
+}
+
+/// Run `future` with every agent loop inside it recorded to `recorder`.
+pub async fn run_with<F: Future>(recorder: Option<Arc<Recorder>>, future: F) -> F::Output {
+    match recorder {
+        Some(recorder) => CURRENT_RECORDER.scope(recorder, future).await,
+        None => future.await,
+    }
+}
+
+/// The recorder of the running task, if any.
+pub fn current() -> Option<Arc<Recorder>> {
+    CURRENT_RECORDER.try_with(Arc::clone).ok()
+}
+
+/// Recorder for `session_id`, or `None` when `[replay]` is disabled.
+pub fn recorder_for(config: &Config, session_id: &str) -> Option<Arc<Recorder>> {
+    config
+        .replay
+        .enabled
+        .then(|| start(&config.workspace_dir, session_id))
+        .flatten()
+}
+
+/// Recorder for `session_id` in `workspace_dir`, or `None` when storage
+/// encryption is enabled.
+pub fn start(workspace_dir: &Path, session_id: &str) -> Option<Arc<Recorder>> {
+    static WARNED: AtomicBool = AtomicBool::new(false);
+    if crate::security::storage_encryption::is_enabled() {
+        // A plaintext recording would undo encryption at rest.
+        if !WARNED.swap(true, Ordering::SeqCst) {
+            tracing::warn!("Replay recording disabled: storage encryption is enabled");
+        }
+        return None;
+    }
+    Some(Arc::new(Recorder::new(workspace_dir, session_id)))
+}
+
+/// Directory holding the recordings of a workspace.
+pub fn replay_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join("replay")
+}
+
+/// Recording file of `session_id`.
+pub fn recording_path(workspace_dir: &Path, session_id: &str) -> PathBuf {
+    let file_name: String = session_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    replay_dir(workspace_dir).join(format!("{file_name}.jsonl"))
+}
+
+/// One line of a recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReplayEvent {
+    /// Start of a user turn.
+    Turn { at: String },
+    /// A model call made by the tool-call loop.
+    Provider(ProviderCall),
+    /// A tool result, as fed back to the model.
+    Tool(ToolOutput),
+}
+
+/// A recorded model call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderCall {
+    pub model: String,
+    /// Whether tools were sent as native tool specs.
+    pub native_tools: bool,
+    pub vision: bool,
+    /// Names of the tools available to the model.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    pub messages: Vec<ChatMessage>,
+    #[serde(default)]
+    pub response: Option<RecordedResponse>,
+    /// Error returned instead of a response.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// A recorded model response.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCall>,
+    #[serde(default)]
+    pub reasoning_content: Option<String>,
+    #[serde(default)]
+    pub input_tokens: Option<u64>,
+    #[serde(default)]
+    pub output_tokens: Option<u64>,
+}
+
+impl From<&ChatResponse> for RecordedResponse {
+    fn from(response: &ChatResponse) -> Self {
+        Self {
+            text: response.text.clone(),
+            tool_calls: response.tool_calls.clone(),
+            reasoning_content: response.reasoning_content.clone(),
+            input_tokens: response.usage.as_ref().and_then(|u| u.input_tokens),
+            output_tokens: response.usage.as_ref().and_then(|u| u.output_tokens),
+        }
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> ChatResponse {
+        ChatResponse {
+            text: self.text.clone(),
+            tool_calls: self.tool_calls.clone(),
+            usage: Some(TokenUsage {
+                input_tokens: self.input_tokens,
+                output_tokens: self.output_tokens,
+            }),
+            reasoning_content: self.reasoning_content.clone(),
+        }
+    }
+}
+
+/// A recorded tool result, after output processing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolOutput {
+    pub name: String,
+    pub arguments: serde_json::Value,
+    pub success: bool,
+    pub output: String,
+}
+
+/// Appends one session's events to its recording file.
+pub struct Recorder {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+    /// Nesting of agent loops; only the outermost one (depth 1) is recorded,
+    /// so sub-agents run by a tool do not interleave with their parent.
+    depth: AtomicUsize,
+    warned: AtomicBool,
+}
+
+/// Marks a running agent loop; see [`Recorder::enter_turn`].
+pub struct TurnGuard<'a>(&'a Recorder);
+
+impl Drop for TurnGuard<'_> {
+    fn drop(&mut self) {
+        self.0.depth.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Recorder {
+    pub fn new(workspace_dir: &Path, session_id: &str) -> Self {
+        Self {
+            path: recording_path(workspace_dir, session_id),
+            write_lock: Mutex::new(()),
+            depth: AtomicUsize::new(0),
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Called when an agent loop starts; the outermost one begins a turn.
+    pub fn enter_turn(&self) -> TurnGuard<'_> {
+        if self.depth.fetch_add(1, Ordering::SeqCst) == 0 {
+            self.write(&ReplayEvent::Turn {
+                at: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+        TurnGuard(self)
+    }
+
+    fn recording(&self) -> bool {
+        self.depth.load(Ordering::SeqCst) == 1
+    }
+
+    pub fn record_provider_call(
+        &self,
+        model: &str,
+        vision: bool,
+        messages: &[ChatMessage],
+        tools: &[ToolSpec],
+        native_tools: bool,
+        result: &Result<ChatResponse>,
+    ) {
+        if !self.recording() {
+            return;
+        }
+        let (response, error) = match result {
+            Ok(response) => (Some(RecordedResponse::from(response)), None),
+            Err(e) => (None, Some(format!("{e:#}"))),
+        };
+        self.write(&ReplayEvent::Provider(ProviderCall {
+            model: model.to_string(),
+            native_tools,
+            vision,
+            tools: tools.iter().map(|spec| spec.name.clone()).collect(),
+            messages: messages.to_vec(),
+            response,
+            error,
+        }));
+    }
+
+    pub fn record_tool(
+        &self,
+        name: &str,
+        arguments: &serde_json::Value,
+        success: bool,
+        output: &str,
+    ) {
+        if !self.recording() {
+            return;
+        }
+        self.write(&ReplayEvent::Tool(ToolOutput {
+            name: name.to_string(),
+            arguments: arguments.clone(),
+            success,
+            output: output.to_string(),
+        }));
+    }
+
+    fn write(&self, event: &ReplayEvent) {
+        let _guard = self.write_lock.lock();
+        let result = (|| -> Result<()> {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            writeln!(file, "{}", serde_json::to_string(event)?)?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            if !self.warned.swap(true, Ordering::SeqCst) {
+                tracing::warn!(path = %self.path.display(), "Failed to write replay recording: {e:#}");
+            }
+        }
+    }
+}
+
+/// The model calls and tool results of one recorded turn.
+#[derive(Debug, Clone, Default)]
+pub struct RecordedTurn {
+    pub at: String,
+    pub calls: Vec<ProviderCall>,
+    pub tools: Vec<ToolOutput>,
+}
+
+/// Parse a recording into turns. Unreadable lines are skipped.
+pub fn parse_recording(raw: &str) -> Vec<RecordedTurn> {
+    let mut turns: Vec<RecordedTurn> = Vec::new();
+    for line in raw.lines().filter(|line| !line.trim().is_empty()) {
+        let Ok(event) = serde_json::from_str::<ReplayEvent>(line) else {
+            continue;
+        };
+        match event {
+            ReplayEvent::Turn { at } => turns.push(RecordedTurn {
+                at,
+                ..RecordedTurn::default()
+            }),
+            ReplayEvent::Provider(call) => {
+                if turns.is_empty() {
+                    turns.push(RecordedTurn::default());
+                }
+                turns.last_mut().expect("turn exists").calls.push(call);
+            }
+            ReplayEvent::Tool(output) => {
+                if turns.is_empty() {
+                    turns.push(RecordedTurn::default());
+                }
+                turns.last_mut().expect("turn exists").tools.push(output);
+            }
+        }
+    }
+    turns.retain(|turn| !turn.calls.is_empty());
+    turns
+}
+
+/// Where a replayed request first differs from the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// Index of the first differing message.
+    pub message: usize,
+    pub detail: String,
+}
+
+fn excerpt(text: &str) -> String {
+    crate::util::truncate_with_ellipsis(text, EXCERPT_MAX_CHARS)
+}
+
+/// Compare a replayed request with the recorded one.
+pub fn first_divergence(recorded: &[ChatMessage], replayed: &[ChatMessage]) -> Option<Divergence> {
+    for (index, (old, new)) in recorded.iter().zip(replayed).enumerate() {
+        if old.role != new.role {
+            return Some(Divergence {
+                message: index,
+                detail: format!("role was '{}', now '{}'", old.role, new.role),
+            });
+        }
+        if old.content == new.content {
+            continue;
+        }
+        let old_lines: Vec<&str> = old.content.lines().collect();
+        let new_lines: Vec<&str> = new.content.lines().collect();
+        let line = old_lines
+            .iter()
+            .zip(&new_lines)
+            .position(|(a, b)| a != b)
+            .unwrap_or(old_lines.len().min(new_lines.len()));
+        return Some(Divergence {
+            message: index,
+            detail: format!(
+                "{} message, line {}:\n      recorded: {}\n      replayed: {}",
+                old.role,
+                line + 1,
+                old_lines.get(line).map_or("(end)".into(), |l| excerpt(l)),
+                new_lines.get(line).map_or("(end)".into(), |l| excerpt(l)),
+            ),
+        });
+    }
+    if recorded.len() != replayed.len() {
+        let index = recorded.len().min(replayed.len());
+        return Some(Divergence {
+            message: index,
+            detail: format!(
+                "recorded request had {} messages, replayed one has {}",
+                recorded.len(),
+                replayed.len()
+            ),
+        });
+    }
+    None
+}
+
+/// Serves the recorded responses of one turn, in order, and checks each
+/// incoming request against the recording.
+pub struct ReplayProvider {
+    calls: Vec<ProviderCall>,
+    next: AtomicUsize,
+    divergence: Mutex<Option<(usize, Divergence)>>,
+}
+
+impl ReplayProvider {
+    pub fn new(calls: Vec<ProviderCall>) -> Self {
+        Self {
+            calls,
+            next: AtomicUsize::new(0),
+            divergence: Mutex::new(None),
+        }
+    }
+
+    /// Number of model calls served so far.
+    pub fn served(&self) -> usize {
+        self.next.load(Ordering::SeqCst)
+    }
+
+    /// First divergence seen, with the index of the model call.
+    pub fn divergence(&self) -> Option<(usize, Divergence)> {
+        self.divergence.lock().clone()
+    }
+}
+
+#[async_trait]
+impl Provider for ReplayProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        let first = self.calls.first();
+        ProviderCapabilities {
+            native_tool_calling: first.is_some_and(|call| call.native_tools),
+            vision: first.is_some_and(|call| call.vision),
+        }
+    }
+
+    async fn chat_with_system(
+        &self,
+        _system_prompt: Option<&str>,
+        _message: &str,
+        _model: &str,
+        _temperature: f64,
+    ) -> Result<String> {
+        anyhow::bail!("replay: only agent loop model calls are recorded")
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        _model: &str,
+        _temperature: f64,
+    ) -> Result<ChatResponse> {
+        let index = self.next.fetch_add(1, Ordering::SeqCst);
+        let Some(call) = self.calls.get(index) else {
+            anyhow::bail!(
+                "replay: the loop made more model calls than the {} recorded",
+                self.calls.len()
+            );
+        };
+        let mut divergence = first_divergence(&call.messages, request.messages);
+        if divergence.is_none() && call.native_tools {
+            let tools: Vec<&str> = request
+                .tools
+                .unwrap_or_default()
+                .iter()
+                .map(|spec| spec.name.as_str())
+                .collect();
+            if tools != call.tools.iter().map(String::as_str).collect::<Vec<_>>() {
+                divergence = Some(Divergence {
+                    message: 0,
+                    detail: format!(
+                        "offered tools were [{}], now [{}]",
+                        call.tools.join(", "),
+                        tools.join(", ")
+                    ),
+                });
+            }
+        }
+        if let Some(divergence) = divergence {
+            self.divergence.lock().get_or_insert((index, divergence));
+        }
+        match (&call.response, &call.error) {
+            (Some(response), _) => Ok(response.to_response()),
+            (None, Some(error)) => Err(anyhow::anyhow!("{error}")),
+            (None, None) => anyhow::bail!("replay: model call {} has no response", index + 1),
+        }
+    }
+}
+
+/// Stand-in for a tool during replay: same name and schema, recorded output.
+struct ReplayTool {
+    spec: ToolSpec,
+    outputs: Arc<Mutex<Vec<ToolOutput>>>,
+}
+
+#[async_trait]
+impl Tool for ReplayTool {
+    fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    fn description(&self) -> &str {
+        &self.spec.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.spec.parameters.clone()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        let mut outputs = self.outputs.lock();
+        let position = outputs
+            .iter()
+            .position(|o| o.name == self.spec.name && o.arguments == args)
+            .or_else(|| outputs.iter().position(|o| o.name == self.spec.name));
+        let Some(recorded) = position.map(|index| outputs.remove(index)) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("replay: no recorded output for {}", self.spec.name)),
+            });
+        };
+        // Failed results were recorded as the loop formats them.
+        Ok(if recorded.success {
+            ToolResult {
+                success: true,
+                output: recorded.output,
+                error: None,
+            }
+        } else {
+            let reason = recorded
+                .output
+                .strip_prefix("Error: ")
+                .unwrap_or(&recorded.output)
+                .to_string();
+            ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(reason),
+            }
+        })
+    }
+}
+
+/// Stand-ins for the tools offered in `turn`, using the current specs where
+/// a tool still exists.
+fn replay_tools(turn: &RecordedTurn, current: &[Box<dyn Tool>]) -> Vec<Box<dyn Tool>> {
+    let outputs = Arc::new(Mutex::new(turn.tools.clone()));
+    let mut names: Vec<String> = turn.calls[0].tools.clone();
+    for output in &turn.tools {
+        if !names.contains(&output.name) {
+            names.push(output.name.clone());
+        }
+    }
+    names
+        .into_iter()
+        .map(|name| {
+            let spec = current.iter().find(|tool| tool.name() == name).map_or_else(
+                || ToolSpec {
+                    name: name.clone(),
+                    description: String::new(),
+                    parameters: serde_json::json!({ "type": "object" }),
+                },
+                |tool| tool.spec(),
+            );
+            Box::new(ReplayTool {
+                spec,
+                outputs: Arc::clone(&outputs),
+            }) as Box<dyn Tool>
+        })
+        .collect()
+}
+
+/// Map a session id or `zeroclaw sessions` name to its recording.
+fn resolve_recording(config: &Config, session: &str) -> Result<PathBuf> {
+    let path = recording_path(&config.workspace_dir, session);
+    if path.exists() {
+        return Ok(path);
+    }
+    if let Some(store) = crate::conversations::open_from_config(config) {
+        if let Some(summary) = store.resolve_session(session)? {
+            let path = recording_path(&config.workspace_dir, &summary.id);
+            if path.exists() {
+                return Ok(path);
+            }
+        }
+    }
+    anyhow::bail!(
+        "No recording for session '{session}'. Enable [replay] and run the session again; \
+         `zeroclaw replay` lists recorded sessions."
+    )
+}
+
+fn list_recordings(config: &Config) -> Result<()> {
+    let dir = replay_dir(&config.workspace_dir);
+    let mut entries: Vec<(String, std::time::SystemTime)> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(std::result::Result::ok)
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let session = name.strip_suffix(".jsonl")?.to_string();
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((session, modified))
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    if entries.is_empty() {
+        if config.replay.enabled {
+            println!("No recorded sessions yet.");
+        } else {
+            println!("No recorded sessions. Set [replay] enabled = true to record.");
+        }
+        return Ok(());
+    }
+    entries.sort_by(|a, b| b.1.cmp(&a.1));
+    println!("Recorded sessions ({}):", dir.display());
+    for (session, modified) in entries {
+        let turns = std::fs::read_to_string(recording_path(&config.workspace_dir, &session))
+            .map(|raw| parse_recording(&raw).len())
+            .unwrap_or(0);
+        let modified: chrono::DateTime<chrono::Local> = modified.into();
+        println!(
+            "  {session}  {turns} turn(s), last {}",
+            modified.format("%Y-%m-%d %H:%M")
+        );
+    }
+    Ok(())
+}
+
+/// `zeroclaw replay [SESSION] [--turn N] [--verbose]`.
+pub async fn handle_command(
+    session: Option<String>,
+    only_turn: Option<usize>,
+    verbose: bool,
+    config: &Config,
+) -> Result<()> {
+    let Some(session) = session else {
+        return list_recordings(config);
+    };
+    let path = resolve_recording(config, &session)?;
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let turns = parse_recording(&raw);
+    if turns.is_empty() {
+        anyhow::bail!("{} contains no recorded turns", path.display());
+    }
+    let current_tools = crate::cron::pipeline::build_registry(config)?;
+
+    let mut replayed = 0usize;
+    let mut diverged = 0usize;
+    for (index, turn) in turns.iter().enumerate() {
+        let number = index + 1;
+        if only_turn.is_some_and(|n| n != number) {
+            continue;
+        }
+        replayed += 1;
+        let first = &turn.calls[0];
+        let provider = ReplayProvider::new(turn.calls.clone());
+        let tools = replay_tools(turn, &current_tools);
+        let mut history = first.messages.clone();
+        let result = super::loop_::run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools,
+            &NoopObserver,
+            "replay",
+            &first.model,
+            0.0,
+            true,
+            None,
+            "replay",
+            &config.multimodal,
+            config.agent.max_tool_iterations.max(turn.calls.len()),
+            None,
+            None,
+            None,
+            &[],
+            None,
+            None,
+        )
+        .await;
+
+        let recorded_reply = turn
+            .calls
+            .last()
+            .and_then(|call| call.response.as_ref())
+            .and_then(|response| response.text.clone())
+            .unwrap_or_default();
+        let problem = match (provider.divergence(), &result) {
+            (Some((call, divergence)), _) => Some(format!(
+                "model call {} of {} diverges at message {}: {}",
+                call + 1,
+                turn.calls.len(),
+                divergence.message,
+                divergence.detail
+            )),
+            (None, Err(e)) => Some(format!("loop failed: {e:#}")),
+            (None, Ok(_)) if provider.served() < turn.calls.len() => Some(format!(
+                "loop stopped after {} of {} recorded model calls",
+                provider.served(),
+                turn.calls.len()
+            )),
+            (None, Ok(_)) => None,
+        };
+        match problem {
+            None => println!(
+                "✅ Turn {number}: {} model call(s) match the recording",
+                turn.calls.len()
+            ),
+            Some(problem) => {
+                diverged += 1;
+                println!("❌ Turn {number}: {problem}");
+            }
+        }
+        if verbose {
+            println!("   started {}", turn.at);
+            println!("   reply: {}", excerpt(&recorded_reply));
+        }
+    }
+
+    if replayed == 0 {
+        anyhow::bail!(
+            "Session has {} turn(s); no turn matched --turn",
+            turns.len()
+        );
+    }
+    println!();
+    println!(
+        "Replayed {replayed} turn(s): {} match, {diverged} diverge.",
+        replayed - diverged
+    );
+    if diverged > 0 {
+        anyhow::bail!("{diverged} of {replayed} replayed turn(s) diverged from the recording");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_round_trips_into_turns() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let recorder = Recorder::new(tmp.path(), "telegram_alice:1");
+        assert!(recorder
+            .path()
+            .ends_with("state/replay/telegram_alice_1.jsonl"));
+        {
+            let _turn = recorder.enter_turn();
+            let messages = vec![ChatMessage::system("sys"), ChatMessage::user("hi")];
+            let response = ChatResponse {
+                text: Some("hello".into()),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            };
+            recorder.record_provider_call("m", false, &messages, &[], false, &Ok(response));
+            recorder.record_tool("shell", &serde_json::json!({"command": "ls"}), true, "a");
+            // Nested loops (sub-agents) are not recorded.
+            let _nested = recorder.enter_turn();
+            recorder.record_tool("shell", &serde_json::json!({}), true, "nested");
+        }
+        let _second = recorder.enter_turn();
+
+        let raw = std::fs::read_to_string(recorder.path()).unwrap();
+        let turns = parse_recording(&raw);
+        assert_eq!(turns.len(), 1, "turn without model calls is dropped");
+        assert_eq!(turns[0].calls[0].messages[1].content, "hi");
+        assert_eq!(turns[0].tools.len(), 1);
+        assert_eq!(turns[0].tools[0].output, "a");
+    }
+
+    #[test]
+    fn divergence_points_at_first_differing_line() {
+        let recorded = vec![
+            ChatMessage::system("You are helpful.\nTools: shell"),
+            ChatMessage::user("hi"),
+        ];
+        assert_eq!(first_divergence(&recorded, &recorded), None);
+
+        let changed = vec![
+            ChatMessage::system("You are helpful.\nTools: shell, file_read"),
+            ChatMessage::user("hi"),
+        ];
+        let divergence = first_divergence(&recorded, &changed).unwrap();
+        assert_eq!(divergence.message, 0);
+        assert!(divergence.detail.contains("line 2"));
+        assert!(divergence.detail.contains("file_read"));
+
+        let divergence = first_divergence(&recorded, &recorded[..1]).unwrap();
+        assert_eq!(divergence.message, 1);
+    }
+}
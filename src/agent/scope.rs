@@ -0,0 +1,90 @@
+//! Per-turn task-local state, entered with one call.
+//!
+//! A turn runs inside several task-local scopes: the heuristic model router,
+//! the replay recorder, prompt dumps, dry run, the undo journal, the session
+//! budget, the workspace session and the user's role. [`TurnScope`] holds
+//! all of them, so channel, CLI and gateway turns set them up the same way.
+
+use super::debug_dump::DebugDump;
+use super::dry_run::DryRun;
+use super::model_router::ModelRouter;
+use super::replay::Recorder;
+use super::undo::Journal;
+use crate::cost::budget::SessionBudget;
+use crate::security::roles::{self, Role};
+use std::future::Future;
+use std::sync::Arc;
+
+/// Everything a turn runs under. `None` leaves that scope unset.
+pub struct TurnScope<'a> {
+    /// The user message of the turn, before memory context is added.
+    pub message: &'a str,
+    /// Session whose temp directory in the workspace tools use.
+    pub session: String,
+    pub role: Role,
+    pub model_router: Option<Arc<ModelRouter>>,
+    pub recorder: Option<Arc<Recorder>>,
+    pub debug_dump: Option<Arc<DebugDump>>,
+    pub dry_run: Option<Arc<DryRun>>,
+    pub undo: Option<Arc<Journal>>,
+    pub budget: Option<Arc<SessionBudget>>,
+}
+
+impl<'a> TurnScope<'a> {
+    /// A turn answering `message` in `session`, with the caller's role and
+    /// no other scope set.
+    pub fn new(session: impl Into<String>, message: &'a str) -> Self {
+        Self {
+            message,
+            session: session.into(),
+            role: roles::current(),
+            model_router: None,
+            recorder: None,
+            debug_dump: None,
+            dry_run: None,
+            undo: None,
+            budget: None,
+        }
+    }
+
+    /// Run `future` inside every scope.
+    pub async fn run<F: Future>(self, future: F) -> F::Output {
+        let future = roles::run_with(self.role, future);
+        let future = crate::security::workspace::run_in_session(self.session, future);
+        let future = crate::cost::budget::run_with(self.budget, future);
+        let future = super::undo::run_with(self.undo, self.message, future);
+        let future = super::dry_run::run_with(self.dry_run, future);
+        let future = super::debug_dump::run_with(self.debug_dump, future);
+        let future = super::replay::run_with(self.recorder, future);
+        super::model_router::run_with(self.model_router, self.message, future).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn scope_sets_every_task_local_for_the_turn() {
+        let dry_run = Arc::new(DryRun::new(true));
+        let scope = TurnScope {
+            role: Role::Guest,
+            dry_run: Some(Arc::clone(&dry_run)),
+            ..TurnScope::new("telegram_42", "hello")
+        };
+        let (role, session, dry) = scope
+            .run(async {
+                (
+                    roles::current(),
+                    crate::security::workspace::current_session(),
+                    crate::agent::dry_run::intercepts(crate::tools::ToolRisk::High),
+                )
+            })
+            .await;
+        assert_eq!(role, Role::Guest);
+        assert_eq!(session.as_deref(), Some("telegram_42"));
+        assert!(dry);
+        assert_eq!(roles::current(), Role::Admin);
+        assert!(crate::security::workspace::current_session().is_none());
+    }
+}
@@ -0,0 +1,213 @@
+//! Live status line for `zeroclaw agent` while a turn runs.
+//!
+//! Shows a spinner, the current phase (thinking, or the tool being run),
+//! elapsed time and the number of answer tokens streamed so far, built from
+//! the same progress deltas channels use for draft updates. The line is
+//! drawn on stderr and erased before the answer is printed. It is only
+//! shown when both stdout and stderr are terminals; `NO_COLOR` turns off
+//! its styling.
+//!
+//! Anything else printed mid-turn (approval prompts, text the model writes
+//! alongside tool calls) calls [`suspend`] first, which takes the line down
+//! for the rest of the turn.
+
+use super::loop_::DRAFT_CLEAR_SENTINEL;
+use crate::config::Config;
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often the spinner advances.
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Width assumed when the terminal does not report one.
+const DEFAULT_WIDTH: usize = 80;
+
+/// Erase the current line.
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+/// Where the status line currently is on screen.
+static ON_SCREEN: AtomicU8 = AtomicU8::new(HIDDEN);
+const HIDDEN: u8 = 0;
+const CURRENT_LINE: u8 = 1;
+const ABOVE_PROMPT: u8 = 2;
+const SUSPENDED: u8 = 3;
+
+/// Erase the status line, if one is drawn, and keep it down until the next
+/// turn. Call before printing to the terminal mid-turn.
+pub fn suspend() {
+    match ON_SCREEN.swap(SUSPENDED, Ordering::AcqRel) {
+        CURRENT_LINE => erase(false),
+        ABOVE_PROMPT => erase(true),
+        _ => {}
+    }
+}
+
+fn erase(above_prompt: bool) {
+    draw(above_prompt, "");
+}
+
+fn draw(above_prompt: bool, text: &str) {
+    let mut stderr = std::io::stderr().lock();
+    let _ = if above_prompt {
+        // Save the cursor, draw one line up, restore.
+        write!(stderr, "\x1b7\x1b[1A{CLEAR_LINE}{text}\x1b8")
+    } else {
+        write!(stderr, "{CLEAR_LINE}{text}")
+    };
+    let _ = stderr.flush();
+}
+
+#[derive(Debug)]
+pub struct StatusLine {
+    started: Instant,
+    phase: String,
+    streamed_tokens: usize,
+    /// The final answer is arriving; deltas are answer text.
+    writing: bool,
+    color: bool,
+    /// Draw on the line above the cursor, leaving the cursor's line to the
+    /// interactive prompt.
+    above_prompt: bool,
+    frame: usize,
+}
+
+impl StatusLine {
+    /// A status line for one turn, or `None` when it is disabled or output
+    /// is not going to a terminal.
+    pub fn start(config: &Config, above_prompt: bool) -> Option<Self> {
+        let terminal = std::io::stdout().is_terminal()
+            && std::io::stderr().is_terminal()
+            && std::env::var("TERM").as_deref() != Ok("dumb");
+        if !config.agent.status_line || !terminal {
+            return None;
+        }
+        if above_prompt {
+            // Keep a line for the status above the prompt that follows.
+            eprintln!();
+        }
+        ON_SCREEN.store(HIDDEN, Ordering::Release);
+        Some(Self::new(console::colors_enabled_stderr(), above_prompt))
+    }
+
+    fn new(color: bool, above_prompt: bool) -> Self {
+        Self {
+            started: Instant::now(),
+            phase: "Thinking".into(),
+            streamed_tokens: 0,
+            writing: false,
+            color,
+            above_prompt,
+            frame: 0,
+        }
+    }
+
+    /// Update the phase or token count from an `on_delta` message.
+    pub fn apply(&mut self, delta: &str) {
+        if delta == DRAFT_CLEAR_SENTINEL {
+            self.writing = true;
+            self.phase = "Writing".into();
+            return;
+        }
+        if self.writing {
+            self.streamed_tokens += super::tool_output::estimate_tokens(delta);
+            return;
+        }
+        if let Some(line) = delta.lines().map(str::trim).rfind(|line| !line.is_empty()) {
+            self.phase = phase_from_progress(line);
+        }
+    }
+
+    /// Advance the spinner and redraw, unless [`suspend`]ed.
+    pub fn tick(&mut self) {
+        let position = if self.above_prompt {
+            ABOVE_PROMPT
+        } else {
+            CURRENT_LINE
+        };
+        if ON_SCREEN.load(Ordering::Acquire) == SUSPENDED {
+            return;
+        }
+        self.frame = self.frame.wrapping_add(1);
+        let width = console::Term::stderr()
+            .size_checked()
+            .map_or(DEFAULT_WIDTH, |(_, cols)| usize::from(cols));
+        let text = self.text(width.saturating_sub(1));
+        let text = if self.color {
+            console::style(text).dim().force_styling(true).to_string()
+        } else {
+            text
+        };
+        draw(self.above_prompt, &text);
+        ON_SCREEN.store(position, Ordering::Release);
+    }
+
+    /// `⠹ Thinking · 4.2s · 120 tokens`, cut to `width` columns.
+    fn text(&self, width: usize) -> String {
+        let spinner = SPINNER_FRAMES[self.frame % SPINNER_FRAMES.len()];
+        let mut text = format!(
+            "{spinner} {} · {:.1}s",
+            self.phase,
+            self.started.elapsed().as_secs_f64()
+        );
+        if self.streamed_tokens > 0 {
+            text.push_str(&format!(" · {} tokens", self.streamed_tokens));
+        }
+        crate::util::truncate_with_width(&text, width, "…")
+    }
+}
+
+impl Drop for StatusLine {
+    /// Erase the line so the answer takes its place.
+    fn drop(&mut self) {
+        if matches!(
+            ON_SCREEN.swap(HIDDEN, Ordering::AcqRel),
+            CURRENT_LINE | ABOVE_PROMPT
+        ) {
+            erase(self.above_prompt);
+        }
+    }
+}
+
+/// The phase named by a progress line such as `🤔 Thinking (round 2)...`
+/// or `⏳ shell: ls -la`.
+fn phase_from_progress(line: &str) -> String {
+    let text = line
+        .trim_start_matches(|c: char| !c.is_alphanumeric())
+        .trim_end_matches('.')
+        .trim();
+    match line.chars().next() {
+        Some('\u{23f3}') => format!("Running {text}"),
+        Some('\u{2705}' | '\u{274c}') => format!("Finished {text}"),
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_deltas_set_the_phase_then_count_answer_tokens() {
+        let mut status = StatusLine::new(false, false);
+        assert!(status.text(80).contains(" Thinking · "));
+
+        status.apply("\u{1f914} Thinking (round 2)...\n");
+        assert_eq!(status.phase, "Thinking (round 2)");
+        status.apply("\u{23f3} shell: ls -la\n");
+        assert_eq!(status.phase, "Running shell: ls -la");
+        status.apply("\u{2705} shell (1s)\n");
+        assert_eq!(status.phase, "Finished shell (1s)");
+
+        status.apply(DRAFT_CLEAR_SENTINEL);
+        status.apply("The answer is forty-two, as computed. ");
+        assert_eq!(status.phase, "Writing");
+        assert!(status.streamed_tokens > 0);
+        let text = status.text(80);
+        assert!(text.contains("Writing"));
+        assert!(text.ends_with(" tokens"));
+        assert!(console::measure_text_width(&status.text(12)) <= 12);
+    }
+}
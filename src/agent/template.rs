@@ -0,0 +1,516 @@
+//! Templating for system prompts and persona files.
+//!
+//! A minijinja-style subset is supported: `{{ var }}` with dotted paths,
+//! `{% if var %}…{% else %}…{% endif %}`, `{% for item in list %}…{% endfor %}`
+//! and `{# comments #}`. A block tag alone on its line takes its line break
+//! with it, so templates can be laid out one tag per line.
+//!
+//! Rendering is lenient: an expression that does not name a known variable
+//! is left exactly as written. Prompts that contain literal braces (JSON
+//! examples, the `{{previous}}` pipeline placeholder) pass through unchanged.
+
+use crate::config::Config;
+use crate::memory::{self, Memory};
+use crate::tools::Tool;
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// Recalled memories exposed as `memories` to templates that mention it.
+const TEMPLATE_MEMORY_LIMIT: usize = 5;
+
+/// Variables and expressions seen while rendering, for `--debug`.
+#[derive(Debug, Default)]
+pub struct RenderReport {
+    pub used: BTreeSet<String>,
+    pub unresolved: BTreeSet<String>,
+}
+
+/// Render `template` against `vars` (a JSON object).
+pub fn render(template: &str, vars: &Value) -> String {
+    render_with_report(template, vars).0
+}
+
+pub fn render_with_report(template: &str, vars: &Value) -> (String, RenderReport) {
+    let mut report = RenderReport::default();
+    if !template.contains("{{") && !template.contains("{%") && !template.contains("{#") {
+        return (template.to_string(), report);
+    }
+    let tokens = lex(template);
+    let mut pos = 0;
+    let (nodes, _) = parse_block(&tokens, &mut pos, &[]);
+    let mut out = String::with_capacity(template.len());
+    let mut scopes = vec![vars.clone()];
+    render_nodes(&nodes, &mut scopes, &mut out, &mut report);
+    (out, report)
+}
+
+/// Whether rendering `template` needs the `memories` variable, which costs
+/// a memory recall to fill.
+pub fn uses_memories(template: &str) -> bool {
+    template.contains("memories")
+}
+
+/// Per-turn values for the standard prompt variables.
+pub struct PromptVars<'a> {
+    pub channel: &'a str,
+    pub user: &'a str,
+    pub model: &'a str,
+    pub agent: Option<&'a str>,
+    pub tools: &'a [Box<dyn Tool>],
+}
+
+impl PromptVars<'_> {
+    /// `now`, `date`, `time`, `weekday`, `timezone`, `user.name`, `channel`,
+    /// `model`, `agent`, `tools` (`name`, `description`), `tool_names` and
+    /// `memories` (`key`, `content`).
+    pub fn to_value(&self, memories: &[(String, String)]) -> Value {
+        let zone = crate::time::zone();
+        let now = chrono::Utc::now();
+        json!({
+            "now": zone.format(now, "%Y-%m-%d %H:%M:%S %Z"),
+            "date": zone.format(now, "%Y-%m-%d"),
+            "time": zone.format(now, "%H:%M"),
+            "weekday": zone.format(now, "%A"),
+            "timezone": zone.format(now, "%Z"),
+            "user": {"name": self.user},
+            "channel": self.channel,
+            "model": self.model,
+            "agent": self.agent,
+            "tools": self
+                .tools
+                .iter()
+                .map(|tool| json!({"name": tool.name(), "description": tool.description()}))
+                .collect::<Vec<_>>(),
+            "tool_names": self.tools.iter().map(|tool| tool.name()).collect::<Vec<_>>(),
+            "memories": memories
+                .iter()
+                .map(|(key, content)| json!({"key": key, "content": content}))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Expand a system prompt for one turn, recalling memories for `message`
+/// only when the template uses them.
+pub async fn render_for_turn(
+    template: &str,
+    vars: &PromptVars<'_>,
+    mem: &dyn Memory,
+    message: &str,
+    session_id: Option<&str>,
+) -> String {
+    let memories = recall_for_template(template, mem, message, session_id).await;
+    render(template, &vars.to_value(&memories))
+}
+
+/// Recall memories for `message` when `template` uses them.
+pub async fn recall_for_template(
+    template: &str,
+    mem: &dyn Memory,
+    message: &str,
+    session_id: Option<&str>,
+) -> Vec<(String, String)> {
+    if !uses_memories(template) || message.trim().is_empty() {
+        return Vec::new();
+    }
+    mem.recall(message, TEMPLATE_MEMORY_LIMIT, session_id)
+        .await
+        .map(|entries| {
+            entries
+                .into_iter()
+                .map(|entry| (entry.key, entry.content))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ── Lexer ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+enum Token {
+    Text(String),
+    Expr { inner: String, raw: String },
+    Tag { inner: String, raw: String },
+}
+
+fn lex(template: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    let mut text = String::new();
+    // Whether an expression already sits on the current line.
+    let mut expr_on_line = false;
+
+    while let Some(open) = ["{{", "{%", "{#"]
+        .iter()
+        .filter_map(|delim| rest.find(delim))
+        .min()
+    {
+        let kind = &rest[open..open + 2];
+        let close = match kind {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+        let Some(close_at) = rest[open + 2..].find(close).map(|i| open + 2 + i) else {
+            break;
+        };
+        text.push_str(&rest[..open]);
+        let raw = &rest[open..close_at + 2];
+        let inner = rest[open + 2..close_at].trim().to_string();
+        rest = &rest[close_at + 2..];
+
+        if kind == "{{" {
+            expr_on_line = true;
+            tokens.push(Token::Text(std::mem::take(&mut text)));
+            tokens.push(Token::Expr {
+                inner,
+                raw: raw.to_string(),
+            });
+            continue;
+        }
+
+        // Block tags and comments alone on a line take the line with them.
+        let line_start = match text.rfind('\n') {
+            Some(i) => {
+                expr_on_line = false;
+                i + 1
+            }
+            None => 0,
+        };
+        if !expr_on_line
+            && text[line_start..].trim().is_empty()
+            && (rest.is_empty() || rest.starts_with('\n'))
+        {
+            text.truncate(line_start);
+            rest = rest.strip_prefix('\n').unwrap_or(rest);
+        }
+        tokens.push(Token::Text(std::mem::take(&mut text)));
+        if kind == "{%" {
+            tokens.push(Token::Tag {
+                inner,
+                raw: raw.to_string(),
+            });
+        }
+    }
+    text.push_str(rest);
+    tokens.push(Token::Text(text));
+    tokens
+}
+
+// ── Parser ──────────────────────────────────────────────────────
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Var {
+        path: String,
+        raw: String,
+    },
+    If {
+        path: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    For {
+        item: String,
+        path: String,
+        body: Vec<Node>,
+    },
+}
+
+/// Parse until one of `stop` tags; returns the nodes and the stop tag hit
+/// (`None` at end of input).
+fn parse_block(tokens: &[Token], pos: &mut usize, stop: &[&str]) -> (Vec<Node>, Option<String>) {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.get(*pos) {
+        *pos += 1;
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text.clone())),
+            Token::Expr { inner, raw } => nodes.push(if is_path(inner) {
+                Node::Var {
+                    path: inner.clone(),
+                    raw: raw.clone(),
+                }
+            } else {
+                Node::Text(raw.clone())
+            }),
+            Token::Tag { inner, raw } => {
+                let words: Vec<&str> = inner.split_whitespace().collect();
+                match words.as_slice() {
+                    [word] if stop.contains(word) => return (nodes, Some((*word).to_string())),
+                    ["if", path] if is_path(path) => {
+                        let start = *pos;
+                        let (then, end) = parse_block(tokens, pos, &["else", "endif"]);
+                        let otherwise = match end.as_deref() {
+                            Some("else") => match parse_block(tokens, pos, &["endif"]) {
+                                (otherwise, Some(_)) => otherwise,
+                                (_, None) => {
+                                    *pos = start;
+                                    nodes.push(Node::Text(raw.clone()));
+                                    continue;
+                                }
+                            },
+                            Some(_) => Vec::new(),
+                            None => {
+                                *pos = start;
+                                nodes.push(Node::Text(raw.clone()));
+                                continue;
+                            }
+                        };
+                        nodes.push(Node::If {
+                            path: (*path).to_string(),
+                            then,
+                            otherwise,
+                        });
+                    }
+                    ["for", item, "in", path] if is_ident(item) && is_path(path) => {
+                        let start = *pos;
+                        match parse_block(tokens, pos, &["endfor"]) {
+                            (body, Some(_)) => nodes.push(Node::For {
+                                item: (*item).to_string(),
+                                path: (*path).to_string(),
+                                body,
+                            }),
+                            (_, None) => {
+                                *pos = start;
+                                nodes.push(Node::Text(raw.clone()));
+                            }
+                        }
+                    }
+                    _ => nodes.push(Node::Text(raw.clone())),
+                }
+            }
+        }
+    }
+    (nodes, None)
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_path(s: &str) -> bool {
+    !s.is_empty() && s.split('.').all(is_ident)
+}
+
+// ── Renderer ────────────────────────────────────────────────────
+
+fn lookup<'a>(scopes: &'a [Value], path: &str) -> Option<&'a Value> {
+    let mut parts = path.split('.');
+    let head = parts.next()?;
+    let mut value = scopes.iter().rev().find_map(|scope| scope.get(head))?;
+    for part in parts {
+        value = value.get(part)?;
+    }
+    Some(value)
+}
+
+fn truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(map)) => !map.is_empty(),
+        Some(Value::Number(n)) => n.as_f64().is_some_and(|n| n != 0.0),
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(display).collect::<Vec<_>>().join(", "),
+        Value::Object(map) => map.get("name").map_or_else(|| value.to_string(), display),
+        other => other.to_string(),
+    }
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    scopes: &mut Vec<Value>,
+    out: &mut String,
+    report: &mut RenderReport,
+) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var { path, raw } => match lookup(scopes, path) {
+                Some(value) => {
+                    report.used.insert(path.clone());
+                    out.push_str(&display(value));
+                }
+                None => {
+                    report.unresolved.insert(raw.clone());
+                    out.push_str(raw);
+                }
+            },
+            Node::If {
+                path,
+                then,
+                otherwise,
+            } => {
+                report.used.insert(path.clone());
+                let branch = if truthy(lookup(scopes, path)) {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, scopes, out, report);
+            }
+            Node::For { item, path, body } => {
+                report.used.insert(path.clone());
+                let items = match lookup(scopes, path) {
+                    Some(Value::Array(items)) => items.clone(),
+                    _ => Vec::new(),
+                };
+                let count = items.len();
+                for (index, value) in items.into_iter().enumerate() {
+                    let mut scope = Map::new();
+                    scope.insert(item.clone(), value);
+                    scope.insert(
+                        "loop".into(),
+                        json!({"index": index + 1, "first": index == 0, "last": index + 1 == count}),
+                    );
+                    scopes.push(Value::Object(scope));
+                    render_nodes(body, scopes, out, report);
+                    scopes.pop();
+                }
+            }
+        }
+    }
+}
+
+// ── `zeroclaw prompt render` ────────────────────────────────────
+
+pub async fn handle_command(command: crate::PromptCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::PromptCommands::Render {
+            channel,
+            user,
+            message,
+            debug,
+        } => {
+            let tools_registry = crate::cron::pipeline::build_registry(config)?;
+            let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+                &config.memory,
+                Some(&config.storage.provider.config),
+                &config.workspace_dir,
+                config.api_key.as_deref(),
+            )?);
+            let model = config
+                .default_model
+                .as_deref()
+                .unwrap_or("anthropic/claude-sonnet-4");
+            let template = crate::agent::loop_::build_agent_system_prompt(
+                config,
+                model,
+                &tools_registry,
+                &[],
+                true,
+            );
+            let template = crate::channels::build_channel_system_prompt(&template, &channel, "");
+
+            let user = user.unwrap_or_else(default_cli_user);
+            let memories = recall_for_template(
+                &template,
+                mem.as_ref(),
+                message.as_deref().unwrap_or(""),
+                None,
+            )
+            .await;
+            let vars = PromptVars {
+                channel: &channel,
+                user: &user,
+                model,
+                agent: None,
+                tools: &tools_registry,
+            }
+            .to_value(&memories);
+            let (prompt, report) = if config.agent.prompt_templates {
+                render_with_report(&template, &vars)
+            } else {
+                (template, RenderReport::default())
+            };
+
+            if debug {
+                println!("── Variables ──");
+                println!("{}", serde_json::to_string_pretty(&vars)?);
+                println!("\n── Used ──");
+                println!("{}", join_or_none(&report.used));
+                println!("\n── Left as written (unknown variables) ──");
+                println!("{}", join_or_none(&report.unresolved));
+                println!(
+                    "\n── Prompt ({} chars, ~{} tokens) ──",
+                    prompt.chars().count(),
+                    super::tool_output::estimate_tokens(&prompt)
+                );
+            }
+            println!("{prompt}");
+            Ok(())
+        }
+    }
+}
+
+fn join_or_none(items: &BTreeSet<String>) -> String {
+    if items.is_empty() {
+        "(none)".into()
+    } else {
+        items.iter().cloned().collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// The name the CLI reports as `user.name`.
+pub fn default_cli_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "user".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_variables_conditionals_and_loops() {
+        let vars = json!({
+            "user": {"name": "Ada"},
+            "channel": "telegram",
+            "memories": [{"key": "tz", "content": "lives in Lisbon"}],
+            "tool_names": ["shell", "file_read"],
+        });
+        let template = "Hi {{ user.name }} on {{channel}}.\n\
+                        {% if memories %}\n\
+                        Known:\n\
+                        {% for m in memories %}\n\
+                        - {{ m.content }}\n\
+                        {% endfor %}\n\
+                        {% else %}\n\
+                        Nothing known.\n\
+                        {% endif %}\n\
+                        Tools: {{ tool_names }}{# internal #}";
+        assert_eq!(
+            render(template, &vars),
+            "Hi Ada on telegram.\nKnown:\n- lives in Lisbon\nTools: shell, file_read"
+        );
+    }
+
+    #[test]
+    fn unknown_expressions_and_broken_tags_are_left_as_written() {
+        let vars = json!({"channel": "cli"});
+        let template = "Use {{previous}} and {\"a\": {\"b\": 1}} {% if channel %}ok";
+        let (out, report) = render_with_report(template, &vars);
+        assert_eq!(out, template);
+        assert!(report.unresolved.contains("{{previous}}"));
+        assert_eq!(
+            render("{{ a | upper }} {%raw%}", &vars),
+            "{{ a | upper }} {%raw%}"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Tool-output post-processing.
+//!
+//! Runs the processors configured in `[agent.tool_output]` between tool
+//! execution and prompt insertion, so a single noisy tool call cannot blow
+//! the context budget.
+
+use crate::config::{ToolOutputConfig, ToolOutputProcessor};
+use crate::providers::Provider;
+use crate::security::{LeakDetector, LeakResult};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Rough chars-per-token ratio used for budget estimates.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Cap on the output handed to the summary model.
+const SUMMARY_MAX_SOURCE_CHARS: usize = 48_000;
+
+/// CSI sequences (colors, cursor movement) and OSC sequences (titles, hyperlinks).
+static ANSI_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]").unwrap()
+});
+
+/// Estimated token count of `text`.
+pub(crate) fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Apply the processors configured for `tool_name` to `output`.
+///
+/// `provider`/`model` are the active ones; `summarize` uses them unless
+/// `summary_model` overrides the model. A failed summary leaves the output
+/// unchanged so later processors still apply.
+pub(crate) async fn process_tool_output(
+    config: &ToolOutputConfig,
+    tool_name: &str,
+    mut output: String,
+    provider: &dyn Provider,
+    model: &str,
+) -> String {
+    let processors = config.tools.get(tool_name).unwrap_or(&config.processors);
+    for processor in processors {
+        output = match processor {
+            ToolOutputProcessor::StripAnsi => strip_ansi(&output),
+            ToolOutputProcessor::Redact => redact(output),
+            ToolOutputProcessor::Truncate => truncate_to_tokens(&output, config.max_tokens),
+            ToolOutputProcessor::Summarize => {
+                if estimate_tokens(&output) <= config.summarize_above_tokens {
+                    output
+                } else {
+                    let model = config.summary_model.as_deref().unwrap_or(model);
+                    match summarize(&output, tool_name, provider, model).await {
+                        Ok(summary) => summary,
+                        Err(e) => {
+                            tracing::warn!(tool = tool_name, "Tool output summary failed: {e}");
+                            output
+                        }
+                    }
+                }
+            }
+        };
+    }
+    output
+}
+
+fn strip_ansi(text: &str) -> String {
+    ANSI_REGEX.replace_all(text, "").into_owned()
+}
+
+fn redact(text: String) -> String {
+    match LeakDetector::new().scan(&text) {
+        LeakResult::Clean => text,
+        LeakResult::Detected { redacted, .. } => redacted,
+    }
+}
+
+/// Keep roughly two thirds of the budget from the head and one third from
+/// the tail, since errors and totals usually land at the end.
+fn truncate_to_tokens(text: &str, max_tokens: usize) -> String {
+    let total_chars = text.chars().count();
+    let max_chars = max_tokens.saturating_mul(CHARS_PER_TOKEN);
+    if total_chars <= max_chars {
+        return text.to_string();
+    }
+    let head_chars = max_chars * 2 / 3;
+    let tail_chars = max_chars - head_chars;
+    let head: String = text.chars().take(head_chars).collect();
+    let tail: String = text.chars().skip(total_chars - tail_chars).collect();
+    let omitted = estimate_tokens(text).saturating_sub(max_tokens);
+    format!("{head}\n... [~{omitted} tokens of tool output omitted] ...\n{tail}")
+}
+
+async fn summarize(
+    text: &str,
+    tool_name: &str,
+    provider: &dyn Provider,
+    model: &str,
+) -> anyhow::Result<String> {
+    let source = truncate_to_tokens(text, SUMMARY_MAX_SOURCE_CHARS / CHARS_PER_TOKEN);
+    let system = "You condense tool output for an AI agent. Keep every fact the agent may need: identifiers, paths, numbers, errors, warnings and final results. Drop repetition, progress noise and boilerplate. Output plain text only.";
+    let user = format!("Condense the output of the `{tool_name}` tool:\n\n{source}");
+    let summary = provider
+        .chat_with_system(Some(system), &user, model, 0.0)
+        .await?;
+    if summary.trim().is_empty() {
+        anyhow::bail!("summary model returned an empty response");
+    }
+    Ok(format!(
+        "[Summarized tool output, originally ~{} tokens]\n{}",
+        estimate_tokens(text),
+        summary.trim()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    struct FixedProvider(&'static str);
+
+    #[async_trait]
+    impl Provider for FixedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    fn config(processors: Vec<ToolOutputProcessor>) -> ToolOutputConfig {
+        ToolOutputConfig {
+            processors,
+            ..ToolOutputConfig::default()
+        }
+    }
+
+    #[test]
+    fn strips_color_and_osc_sequences() {
+        let input = "\x1b[1;31merror\x1b[0m \x1b]8;;https://x\x07link\x1b]8;;\x07";
+        assert_eq!(strip_ansi(input), "error link");
+    }
+
+    #[test]
+    fn truncation_keeps_head_and_tail() {
+        let input = format!("START{}END", "x".repeat(1000));
+        let out = truncate_to_tokens(&input, 20);
+        assert!(out.starts_with("START"));
+        assert!(out.ends_with("END"));
+        assert!(out.contains("tokens of tool output omitted"));
+        assert_eq!(truncate_to_tokens("short", 20), "short");
+    }
+
+    #[tokio::test]
+    async fn processors_run_in_configured_order() {
+        let cfg = ToolOutputConfig {
+            max_tokens: 5,
+            ..config(vec![
+                ToolOutputProcessor::StripAnsi,
+                ToolOutputProcessor::Truncate,
+            ])
+        };
+        let provider = FixedProvider("unused");
+        let out = process_tool_output(
+            &cfg,
+            "shell",
+            format!("\x1b[32m{}\x1b[0m", "a".repeat(100)),
+            &provider,
+            "m",
+        )
+        .await;
+        assert!(!out.contains('\x1b'));
+        assert!(out.contains("omitted"));
+    }
+
+    #[tokio::test]
+    async fn per_tool_processors_override_defaults() {
+        let mut cfg = config(vec![ToolOutputProcessor::StripAnsi]);
+        cfg.tools.insert("raw".into(), Vec::new());
+        let provider = FixedProvider("unused");
+        let out = process_tool_output(&cfg, "raw", "\x1b[1mbold".into(), &provider, "m").await;
+        assert_eq!(out, "\x1b[1mbold");
+    }
+
+    #[tokio::test]
+    async fn summarize_only_above_threshold() {
+        let cfg = ToolOutputConfig {
+            summarize_above_tokens: 10,
+            ..config(vec![ToolOutputProcessor::Summarize])
+        };
+        let provider = FixedProvider("3 files changed");
+        let short = process_tool_output(&cfg, "shell", "ok".into(), &provider, "m").await;
+        assert_eq!(short, "ok");
+        let long = process_tool_output(&cfg, "shell", "y".repeat(200), &provider, "m").await;
+        assert!(long.starts_with("[Summarized tool output"));
+        assert!(long.ends_with("3 files changed"));
+    }
+
+    #[tokio::test]
+    async fn redact_masks_detected_keys() {
+        let cfg = config(vec![ToolOutputProcessor::Redact]);
+        let provider = FixedProvider("unused");
+        let key = format!("sk_live_{}", "a1B2".repeat(8));
+        let out = process_tool_output(&cfg, "shell", format!("key={key}"), &provider, "m").await;
+        assert!(!out.contains(&key));
+    }
+}
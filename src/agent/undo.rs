@@ -0,0 +1,358 @@
+//! Undo of the file changes a turn made (`/undo`).
+//!
+//! Before `file_write`, `file_edit` or `apply_patch` first change a file in
+//! a turn, the file's content is noted in the session's [`Journal`]
+//! ([`record`]); when the turn ends, so is what it left behind. `/undo`
+//! puts back everything the latest turn that changed files changed, and
+//! `/undo <n>` the turn numbered `n` in `/undo list`. A file that changed
+//! again since — in a later turn, by a shell command or by hand — is left
+//! alone and reported, so undo never throws away work it does not know
+//! about. Changes made by `shell` and other tools are not journaled.
+
+use super::commands::UndoCommand;
+use crate::util::truncate_with_ellipsis;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+tokio::task_local! {
+    static CURRENT: TurnScope;
+}
+
+/// Turns kept per session; older ones can no longer be undone.
+const MAX_TURNS: usize = 20;
+/// Files larger than this are not kept in memory. Their previous version is
+/// still in the workspace backups.
+const MAX_SNAPSHOT_BYTES: u64 = 8 * 1024 * 1024;
+
+#[derive(Clone)]
+struct TurnScope {
+    journal: Arc<Journal>,
+    turn: usize,
+}
+
+/// A file's content at one point of a turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Snapshot {
+    Missing,
+    Content(Vec<u8>),
+    TooLarge,
+}
+
+impl Snapshot {
+    async fn take(path: &Path) -> Self {
+        match tokio::fs::metadata(path).await {
+            Ok(meta) if meta.len() > MAX_SNAPSHOT_BYTES => Self::TooLarge,
+            Ok(_) => tokio::fs::read(path)
+                .await
+                .map_or(Self::Missing, Self::Content),
+            Err(_) => Self::Missing,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FileChange {
+    path: PathBuf,
+    before: Snapshot,
+    after: Snapshot,
+}
+
+#[derive(Debug)]
+struct Turn {
+    number: usize,
+    request: String,
+    at: DateTime<Utc>,
+    files: Vec<FileChange>,
+    /// Whether the turn has ended; running turns cannot be undone.
+    finished: bool,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    turns: VecDeque<Turn>,
+    last_number: usize,
+}
+
+/// One session's file changes, by turn.
+#[derive(Debug)]
+pub struct Journal {
+    workspace_dir: PathBuf,
+    state: Mutex<State>,
+}
+
+impl Journal {
+    pub fn new(workspace_dir: &Path) -> Self {
+        Self {
+            workspace_dir: workspace_dir.to_path_buf(),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn begin_turn(&self, request: &str) -> usize {
+        let mut state = self.state();
+        state.last_number += 1;
+        let number = state.last_number;
+        state.turns.push_back(Turn {
+            number,
+            request: request.trim().to_string(),
+            at: Utc::now(),
+            files: Vec::new(),
+            finished: false,
+        });
+        number
+    }
+
+    /// Note what turn `number` left behind, or forget it if it changed no
+    /// files.
+    async fn end_turn(&self, number: usize) {
+        let paths: Vec<PathBuf> = self
+            .state()
+            .turns
+            .iter()
+            .find(|turn| turn.number == number)
+            .map(|turn| turn.files.iter().map(|file| file.path.clone()).collect())
+            .unwrap_or_default();
+        let mut afters = Vec::with_capacity(paths.len());
+        for path in &paths {
+            afters.push(Snapshot::take(path).await);
+        }
+
+        let mut state = self.state();
+        if let Some(turn) = state.turns.iter_mut().find(|turn| turn.number == number) {
+            for (file, after) in turn.files.iter_mut().zip(afters) {
+                file.after = after;
+            }
+            turn.finished = true;
+        }
+        state
+            .turns
+            .retain(|turn| turn.number != number || !turn.files.is_empty());
+        while state.turns.len() > MAX_TURNS {
+            state.turns.pop_front();
+        }
+    }
+
+    async fn record(&self, number: usize, path: &Path) {
+        let known = |state: &State| {
+            state
+                .turns
+                .iter()
+                .find(|turn| turn.number == number)
+                .is_none_or(|turn| turn.files.iter().any(|file| file.path == path))
+        };
+        if known(&self.state()) {
+            return;
+        }
+        let before = Snapshot::take(path).await;
+        let mut state = self.state();
+        if known(&state) {
+            return;
+        }
+        if let Some(turn) = state.turns.iter_mut().find(|turn| turn.number == number) {
+            turn.files.push(FileChange {
+                path: path.to_path_buf(),
+                before,
+                after: Snapshot::Missing,
+            });
+        }
+    }
+
+    fn label(&self, path: &Path) -> String {
+        path.strip_prefix(&self.workspace_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    /// Run `/undo`. Returns the reply.
+    pub async fn run(&self, command: UndoCommand) -> String {
+        let turn = {
+            let mut state = self.state();
+            let index = match command {
+                UndoCommand::List => return self.list(&state),
+                UndoCommand::Last => state.turns.iter().rposition(|turn| turn.finished),
+                UndoCommand::Turn(number) => state
+                    .turns
+                    .iter()
+                    .position(|turn| turn.finished && turn.number == number),
+            };
+            match index.and_then(|index| state.turns.remove(index)) {
+                Some(turn) => turn,
+                None if !state.turns.iter().any(|turn| turn.finished) => {
+                    return "No file changes to undo in this session.".into()
+                }
+                None => {
+                    return "No such turn. `/undo list` shows the turns that can be undone.".into()
+                }
+            }
+        };
+        self.revert(turn).await
+    }
+
+    fn list(&self, state: &State) -> String {
+        if !state.turns.iter().any(|turn| turn.finished) {
+            return "No file changes to undo in this session.".into();
+        }
+        let zone = crate::time::zone();
+        let mut reply = String::from("Turns that changed files (newest last):\n");
+        for turn in state.turns.iter().filter(|turn| turn.finished) {
+            let files: Vec<String> = turn.files.iter().map(|f| self.label(&f.path)).collect();
+            let _ = writeln!(
+                reply,
+                "{}. {} \"{}\": {}",
+                turn.number,
+                zone.format(turn.at, "%H:%M"),
+                truncate_with_ellipsis(&turn.request, 60),
+                files.join(", ")
+            );
+        }
+        reply.push_str("`/undo` reverts the newest, `/undo <n>` turn n.");
+        reply
+    }
+
+    async fn revert(&self, turn: Turn) -> String {
+        let mut restored = Vec::new();
+        let mut skipped = Vec::new();
+        for file in &turn.files {
+            let label = self.label(&file.path);
+            match restore(file).await {
+                Ok(()) => restored.push(label),
+                Err(reason) => skipped.push(format!("{label} ({reason})")),
+            }
+        }
+
+        let mut reply = format!(
+            "Undid turn {} \"{}\".",
+            turn.number,
+            truncate_with_ellipsis(&turn.request, 60)
+        );
+        if !restored.is_empty() {
+            let _ = write!(reply, "\nRestored: {}", restored.join(", "));
+        }
+        if !skipped.is_empty() {
+            let _ = write!(reply, "\nLeft as is: {}", skipped.join(", "));
+        }
+        reply
+    }
+}
+
+/// Put `file` back to its content before the turn, if nothing changed it
+/// since.
+async fn restore(file: &FileChange) -> Result<(), String> {
+    if file.after == Snapshot::TooLarge || file.before == Snapshot::TooLarge {
+        return Err(format!(
+            "too large to keep; see {}",
+            crate::tools::file_changes::BACKUP_DIR
+        ));
+    }
+    if Snapshot::take(&file.path).await != file.after {
+        return Err("changed since".into());
+    }
+    match &file.before {
+        Snapshot::Missing => match tokio::fs::remove_file(&file.path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.to_string()),
+        },
+        Snapshot::Content(content) => {
+            if let Some(parent) = file.path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            tokio::fs::write(&file.path, content)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        Snapshot::TooLarge => unreachable!("checked above"),
+    }
+}
+
+/// Run one turn, answering `request`, with file changes inside it going to
+/// `journal`.
+pub async fn run_with<F: Future>(
+    journal: Option<Arc<Journal>>,
+    request: &str,
+    future: F,
+) -> F::Output {
+    let Some(journal) = journal else {
+        return future.await;
+    };
+    let turn = journal.begin_turn(request);
+    let scope = TurnScope {
+        journal: Arc::clone(&journal),
+        turn,
+    };
+    let output = CURRENT.scope(scope, future).await;
+    journal.end_turn(turn).await;
+    output
+}
+
+/// Note `path`'s content before a tool changes it in the running turn.
+pub async fn record(path: &Path) {
+    let Ok(scope) = CURRENT.try_with(TurnScope::clone) else {
+        return;
+    };
+    scope.journal.record(scope.turn, path).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn undo_restores_turn_and_spares_later_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Arc::new(Journal::new(dir.path()));
+        let edited = dir.path().join("notes.md");
+        let created = dir.path().join("new.txt");
+        std::fs::write(&edited, "v1").unwrap();
+
+        run_with(Some(Arc::clone(&journal)), "rewrite notes", async {
+            record(&edited).await;
+            std::fs::write(&edited, "v2").unwrap();
+            record(&created).await;
+            std::fs::write(&created, "hello").unwrap();
+            record(&edited).await;
+            std::fs::write(&edited, "v3").unwrap();
+        })
+        .await;
+        run_with(Some(Arc::clone(&journal)), "just chat", async {}).await;
+        let list = journal.run(UndoCommand::List).await;
+        assert!(list.contains("1. ") && list.contains("\"rewrite notes\": notes.md, new.txt"));
+        assert!(
+            !list.contains("2. "),
+            "turns without file changes are dropped"
+        );
+
+        let reply = journal.run(UndoCommand::Last).await;
+        assert!(reply.contains("Restored: notes.md, new.txt"), "{reply}");
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "v1");
+        assert!(!created.exists());
+        assert_eq!(
+            journal.run(UndoCommand::Last).await,
+            "No file changes to undo in this session."
+        );
+
+        run_with(Some(Arc::clone(&journal)), "edit again", async {
+            record(&edited).await;
+            std::fs::write(&edited, "v4").unwrap();
+        })
+        .await;
+        std::fs::write(&edited, "by hand").unwrap();
+        let reply = journal.run(UndoCommand::Turn(3)).await;
+        assert!(
+            reply.contains("Left as is: notes.md (changed since)"),
+            "{reply}"
+        );
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "by hand");
+    }
+}
@@ -1,15 +1,27 @@
 //! Interactive approval workflow for supervised mode.
 //!
-//! Provides a pre-execution hook that prompts the user before tool calls,
-//! with session-scoped "Always" allowlists and audit logging.
-
-use crate::config::AutonomyConfig;
+//! Provides a pre-execution hook that decides, per tool call, whether to run,
+//! refuse or ask. The decision combines the tool's [`ToolRisk`], the
+//! `[autonomy.risk_approval]` level mapping, the `auto_approve` /
+//! `always_ask` lists and a session-scoped "Always" allowlist. "Ask" prompts
+//! on the CLI or, through an [`ApprovalPrompter`], on the chat channel the
+//! request came from. Every decision is kept in an in-memory trail and, when
+//! `[security.audit]` is enabled, appended to the audit log.
+
+use crate::channels::traits::{Channel, SendMessage};
+use crate::config::{ApprovalAction, AutonomyConfig, Config, RiskApprovalConfig};
+use crate::security::audit::AuditLogger;
 use crate::security::AutonomyLevel;
+use crate::tools::ToolRisk;
+use async_trait::async_trait;
 use chrono::Utc;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot;
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -18,6 +30,7 @@ use std::io::{self, BufRead, Write};
 pub struct ApprovalRequest {
     pub tool_name: String,
     pub arguments: serde_json::Value,
+    pub risk: ToolRisk,
 }
 
 /// The user's response to an approval request.
@@ -38,14 +51,24 @@ pub struct ApprovalLogEntry {
     pub timestamp: String,
     pub tool_name: String,
     pub arguments_summary: String,
+    pub risk: ToolRisk,
     pub decision: ApprovalResponse,
+    /// `true` when the call was refused by a `deny` rule without asking.
+    pub by_policy: bool,
     pub channel: String,
 }
 
+/// Asks a human to approve a tool call somewhere other than the local CLI.
+#[async_trait]
+pub trait ApprovalPrompter: Send + Sync {
+    async fn prompt(&self, request: &ApprovalRequest) -> ApprovalResponse;
+}
+
 // ── ApprovalManager ──────────────────────────────────────────────
 
 /// Manages the interactive approval workflow.
 ///
+/// - Maps tool risk levels to allow / deny / ask (`risk_approval`)
 /// - Checks config-level `auto_approve` / `always_ask` lists
 /// - Maintains a session-scoped "always" allowlist
 /// - Records an audit trail of all decisions
@@ -54,12 +77,20 @@ pub struct ApprovalManager {
     auto_approve: HashSet<String>,
     /// Tools that always need approval, ignoring session allowlist.
     always_ask: HashSet<String>,
+    /// Risk level → action mapping and per-tool overrides.
+    risk_policy: RiskApprovalConfig,
     /// Autonomy level from config.
     autonomy_level: AutonomyLevel,
     /// Session-scoped allowlist built from "Always" responses.
     session_allowlist: Mutex<HashSet<String>>,
     /// Audit trail of approval decisions.
     audit_log: Mutex<Vec<ApprovalLogEntry>>,
+    /// Asks on a chat channel; `None` means CLI-only prompting.
+    prompter: Option<Arc<dyn ApprovalPrompter>>,
+    /// Persistent audit log (`[security.audit]`).
+    audit_logger: Option<Arc<AuditLogger>>,
+    /// Sender identity recorded in the persistent audit log.
+    actor: Option<String>,
 }
 
 impl ApprovalManager {
@@ -68,67 +99,171 @@ impl ApprovalManager {
         Self {
             auto_approve: config.auto_approve.iter().cloned().collect(),
             always_ask: config.always_ask.iter().cloned().collect(),
+            risk_policy: config.risk_approval.clone(),
             autonomy_level: config.level,
             session_allowlist: Mutex::new(HashSet::new()),
             audit_log: Mutex::new(Vec::new()),
+            prompter: None,
+            audit_logger: None,
+            actor: None,
         }
     }
 
-    /// Check whether a tool call requires interactive approval.
-    ///
-    /// Returns `true` if the call needs a prompt, `false` if it can proceed.
-    pub fn needs_approval(&self, tool_name: &str) -> bool {
-        // Full autonomy never prompts.
-        if self.autonomy_level == AutonomyLevel::Full {
-            return false;
+    /// Create from the root config, writing decisions to the audit log when
+    /// `[security.audit]` is enabled.
+    pub fn from_root_config(config: &Config) -> Self {
+        let manager = Self::from_config(&config.autonomy);
+        match audit_logger_for(config) {
+            Some(logger) => manager.with_audit_logger(logger),
+            None => manager,
         }
+    }
 
-        // ReadOnly blocks everything — handled elsewhere; no prompt needed.
-        if self.autonomy_level == AutonomyLevel::ReadOnly {
-            return false;
-        }
+    /// Ask through `prompter` instead of stdin.
+    #[must_use]
+    pub fn with_prompter(mut self, prompter: Arc<dyn ApprovalPrompter>) -> Self {
+        self.prompter = Some(prompter);
+        self
+    }
 
-        // always_ask overrides everything.
-        if self.always_ask.contains(tool_name) {
-            return true;
+    /// Append every decision to `logger`.
+    #[must_use]
+    pub fn with_audit_logger(mut self, logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    /// Record `actor` as the deciding user in the audit log.
+    #[must_use]
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    /// Seed the session allowlist (e.g. carried over from an earlier turn).
+    #[must_use]
+    pub fn with_session_allowlist(self, allowlist: HashSet<String>) -> Self {
+        *self.session_allowlist.lock() = allowlist;
+        self
+    }
+
+    /// Decide what to do with a call to `tool_name` at the given risk level.
+    pub fn decide(&self, tool_name: &str, risk: ToolRisk) -> ApprovalAction {
+        let action = if let Some(action) = self.risk_policy.tools.get(tool_name) {
+            *action
+        } else if self.always_ask.contains(tool_name) {
+            // always_ask overrides the level mapping and auto_approve.
+            ApprovalAction::Ask
+        } else if self.auto_approve.contains(tool_name) {
+            ApprovalAction::Allow
+        } else {
+            match risk {
+                ToolRisk::Low => self.risk_policy.low,
+                ToolRisk::Medium => self.risk_policy.medium,
+                ToolRisk::High => self.risk_policy.high,
+            }
+        };
+
+        if action != ApprovalAction::Ask {
+            return action;
         }
 
-        // auto_approve skips the prompt.
-        if self.auto_approve.contains(tool_name) {
-            return false;
+        // Full autonomy never prompts; ReadOnly blocks side effects in the
+        // security policy, so there is nothing to ask about. Deny rules
+        // still apply at every level.
+        if matches!(
+            self.autonomy_level,
+            AutonomyLevel::Full | AutonomyLevel::ReadOnly
+        ) {
+            return ApprovalAction::Allow;
         }
 
         // Session allowlist (from prior "Always" responses).
-        let allowlist = self.session_allowlist.lock();
-        if allowlist.contains(tool_name) {
-            return false;
+        if !self.always_ask.contains(tool_name) && self.session_allowlist.lock().contains(tool_name)
+        {
+            return ApprovalAction::Allow;
         }
 
-        // Default: supervised mode requires approval.
-        true
+        ApprovalAction::Ask
+    }
+
+    /// Check whether a tool call requires interactive approval.
+    ///
+    /// Returns `true` if the call needs a prompt, `false` if it can proceed
+    /// (or is denied outright).
+    pub fn needs_approval(&self, tool_name: &str, risk: ToolRisk) -> bool {
+        self.decide(tool_name, risk) == ApprovalAction::Ask
+    }
+
+    /// Ask for a decision on `channel`.
+    ///
+    /// The CLI prompts on stdin. Other channels need a prompter; without one
+    /// the call is denied, since nobody can answer.
+    pub async fn prompt(&self, request: &ApprovalRequest, channel: &str) -> ApprovalResponse {
+        if let Some(prompter) = &self.prompter {
+            return prompter.prompt(request).await;
+        }
+        if channel == "cli" {
+            return self.prompt_cli(request);
+        }
+        tracing::warn!(
+            tool = %request.tool_name,
+            channel,
+            "Tool call needs approval but the channel cannot ask; denying"
+        );
+        ApprovalResponse::No
     }
 
     /// Record an approval decision and update session state.
     pub fn record_decision(
         &self,
-        tool_name: &str,
-        args: &serde_json::Value,
+        request: &ApprovalRequest,
         decision: ApprovalResponse,
         channel: &str,
     ) {
         // If "Always", add to session allowlist.
         if decision == ApprovalResponse::Always {
             let mut allowlist = self.session_allowlist.lock();
-            allowlist.insert(tool_name.to_string());
+            allowlist.insert(request.tool_name.clone());
+        }
+        self.append(request, decision, false, channel);
+    }
+
+    /// Record a call refused by a `deny` rule.
+    pub fn record_policy_denial(&self, request: &ApprovalRequest, channel: &str) {
+        self.append(request, ApprovalResponse::No, true, channel);
+    }
+
+    fn append(
+        &self,
+        request: &ApprovalRequest,
+        decision: ApprovalResponse,
+        by_policy: bool,
+        channel: &str,
+    ) {
+        let summary = summarize_args(&request.arguments);
+        if let Some(logger) = &self.audit_logger {
+            let allowed = decision != ApprovalResponse::No;
+            if let Err(e) = logger.log_tool_approval(
+                channel,
+                self.actor.as_deref(),
+                &format!("{}({summary})", request.tool_name),
+                request.risk.as_str(),
+                allowed && !by_policy,
+                allowed,
+            ) {
+                tracing::warn!("Failed to write approval audit entry: {e}");
+            }
         }
 
         // Append to audit log.
-        let summary = summarize_args(args);
         let entry = ApprovalLogEntry {
             timestamp: Utc::now().to_rfc3339(),
-            tool_name: tool_name.to_string(),
+            tool_name: request.tool_name.clone(),
             arguments_summary: summary,
+            risk: request.risk,
             decision,
+            by_policy,
             channel: channel.to_string(),
         };
         let mut log = self.audit_log.lock();
@@ -146,19 +281,239 @@ impl ApprovalManager {
     }
 
     /// Prompt the user on the CLI and return their decision.
-    ///
-    /// For non-CLI channels, returns `Yes` automatically (interactive
-    /// approval is only supported on CLI for now).
     pub fn prompt_cli(&self, request: &ApprovalRequest) -> ApprovalResponse {
         prompt_cli_interactive(request)
     }
 }
 
+fn audit_logger_for(config: &Config) -> Option<Arc<AuditLogger>> {
+    if !config.security.audit.enabled {
+        return None;
+    }
+    let zeroclaw_dir = config
+        .config_path
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_default();
+    match AuditLogger::shared(config.security.audit.clone(), zeroclaw_dir) {
+        Ok(logger) => Some(logger),
+        Err(e) => {
+            tracing::warn!("Approval audit log unavailable: {e}");
+            None
+        }
+    }
+}
+
+// ── Channel prompt ───────────────────────────────────────────────
+
+/// Approval state shared by a channel runtime: chat prompts waiting for a
+/// reply and the "Always" allowlist of each conversation.
+pub struct ChannelApprovals {
+    autonomy: AutonomyConfig,
+    audit_logger: Option<Arc<AuditLogger>>,
+    pending: Arc<PendingApprovals>,
+    allowlists: Mutex<HashMap<String, HashSet<String>>>,
+}
+
+impl ChannelApprovals {
+    pub fn from_root_config(config: &Config) -> Self {
+        Self {
+            autonomy: config.autonomy.clone(),
+            audit_logger: audit_logger_for(config),
+            pending: Arc::new(PendingApprovals::new()),
+            allowlists: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn pending(&self) -> &PendingApprovals {
+        &self.pending
+    }
+
+    /// Build the approval manager for one inbound message. Prompts go back
+    /// to `reply_target` and are answered by the next message in `scope`.
+    pub fn manager(
+        &self,
+        channel: Arc<dyn Channel>,
+        reply_target: &str,
+        thread_ts: Option<String>,
+        sender: &str,
+        scope: &str,
+    ) -> ApprovalManager {
+        let prompter = ChannelApprovalPrompter::new(
+            channel,
+            reply_target,
+            thread_ts,
+            scope,
+            Arc::clone(&self.pending),
+            Duration::from_secs(self.autonomy.risk_approval.timeout_secs.max(1)),
+        );
+        let allowlist = self
+            .allowlists
+            .lock()
+            .get(scope)
+            .cloned()
+            .unwrap_or_default();
+        let manager = ApprovalManager::from_config(&self.autonomy)
+            .with_prompter(Arc::new(prompter))
+            .with_actor(sender)
+            .with_session_allowlist(allowlist);
+        match &self.audit_logger {
+            Some(logger) => manager.with_audit_logger(Arc::clone(logger)),
+            None => manager,
+        }
+    }
+
+    /// Keep the conversation's "Always" answers for its next turn.
+    pub fn save_session(&self, scope: &str, manager: &ApprovalManager) {
+        let allowlist = manager.session_allowlist();
+        if !allowlist.is_empty() {
+            self.allowlists.lock().insert(scope.to_string(), allowlist);
+        }
+    }
+}
+
+/// Approval requests waiting for a reply on a chat channel, keyed by
+/// conversation scope (channel, reply target and sender).
+///
+/// The channel dispatcher offers every inbound message to [`Self::resolve`]
+/// first; a message that answers a pending request is consumed instead of
+/// starting a new agent turn.
+#[derive(Default)]
+pub struct PendingApprovals {
+    waiting: Mutex<HashMap<String, oneshot::Sender<String>>>,
+}
+
+impl PendingApprovals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start waiting for a reply in `scope`. A newer request replaces an
+    /// older one, which then resolves as denied.
+    pub fn register(&self, scope: &str) -> oneshot::Receiver<String> {
+        let (tx, rx) = oneshot::channel();
+        self.waiting.lock().insert(scope.to_string(), tx);
+        rx
+    }
+
+    /// Hand `reply` to the request waiting in `scope`. Returns `true` when
+    /// the message was consumed as an approval reply.
+    pub fn resolve(&self, scope: &str, reply: &str) -> bool {
+        let Some(tx) = self.waiting.lock().remove(scope) else {
+            return false;
+        };
+        tx.send(reply.to_string()).is_ok()
+    }
+
+    /// Stop waiting in `scope`.
+    pub fn cancel(&self, scope: &str) {
+        self.waiting.lock().remove(scope);
+    }
+}
+
+/// Asks for approval by messaging the conversation the request came from
+/// and waiting for the same sender's next message.
+pub struct ChannelApprovalPrompter {
+    channel: Arc<dyn Channel>,
+    reply_target: String,
+    thread_ts: Option<String>,
+    scope: String,
+    pending: Arc<PendingApprovals>,
+    timeout: Duration,
+}
+
+impl ChannelApprovalPrompter {
+    pub fn new(
+        channel: Arc<dyn Channel>,
+        reply_target: impl Into<String>,
+        thread_ts: Option<String>,
+        scope: impl Into<String>,
+        pending: Arc<PendingApprovals>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            channel,
+            reply_target: reply_target.into(),
+            thread_ts,
+            scope: scope.into(),
+            pending,
+            timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl ApprovalPrompter for ChannelApprovalPrompter {
+    async fn prompt(&self, request: &ApprovalRequest) -> ApprovalResponse {
+        // Register before sending so a fast reply cannot slip past.
+        let reply = self.pending.register(&self.scope);
+        let message = format_channel_prompt(request, self.timeout);
+        if let Err(e) = self
+            .channel
+            .send(&SendMessage::new(message, &self.reply_target).in_thread(self.thread_ts.clone()))
+            .await
+        {
+            tracing::warn!("Failed to send approval request: {e}");
+            self.pending.cancel(&self.scope);
+            return ApprovalResponse::No;
+        }
+
+        match tokio::time::timeout(self.timeout, reply).await {
+            Ok(Ok(text)) => parse_reply(&text).unwrap_or(ApprovalResponse::No),
+            Ok(Err(_)) => ApprovalResponse::No,
+            Err(_) => {
+                self.pending.cancel(&self.scope);
+                let _ = self
+                    .channel
+                    .send(
+                        &SendMessage::new(
+                            format!(
+                                "⏱️ No answer for `{}`; the call was not run.",
+                                request.tool_name
+                            ),
+                            &self.reply_target,
+                        )
+                        .in_thread(self.thread_ts.clone()),
+                    )
+                    .await;
+                ApprovalResponse::No
+            }
+        }
+    }
+}
+
+fn format_channel_prompt(request: &ApprovalRequest, timeout: Duration) -> String {
+    format!(
+        "🔐 Approval needed: `{}` ({} risk)\n{}\n\nReply `yes`, `no`, or `always` within {}s. \
+         Any other reply denies the call.",
+        request.tool_name,
+        request.risk.as_str(),
+        summarize_args(&request.arguments),
+        timeout.as_secs()
+    )
+}
+
+/// Interpret a chat reply to an approval request.
+pub fn parse_reply(text: &str) -> Option<ApprovalResponse> {
+    let word = text
+        .trim()
+        .trim_start_matches('/')
+        .trim_end_matches(['.', '!'])
+        .to_ascii_lowercase();
+    match word.as_str() {
+        "y" | "yes" | "approve" | "ok" | "👍" => Some(ApprovalResponse::Yes),
+        "n" | "no" | "deny" | "👎" => Some(ApprovalResponse::No),
+        "a" | "always" => Some(ApprovalResponse::Always),
+        _ => None,
+    }
+}
+
 // ── CLI prompt ───────────────────────────────────────────────────
 
 /// Display the approval prompt and read user input from stdin.
 fn prompt_cli_interactive(request: &ApprovalRequest) -> ApprovalResponse {
     let summary = summarize_args(&request.arguments);
+    crate::agent::status_line::suspend();
     eprintln!();
     eprintln!("🔧 Agent wants to execute: {}", request.tool_name);
     eprintln!("   {summary}");
@@ -230,6 +585,14 @@ mod tests {
         }
     }
 
+    fn request(tool: &str, args: serde_json::Value) -> ApprovalRequest {
+        ApprovalRequest {
+            tool_name: tool.into(),
+            arguments: args,
+            risk: ToolRisk::Medium,
+        }
+    }
+
     fn full_config() -> AutonomyConfig {
         AutonomyConfig {
             level: AutonomyLevel::Full,
@@ -242,29 +605,29 @@ mod tests {
     #[test]
     fn auto_approve_tools_skip_prompt() {
         let mgr = ApprovalManager::from_config(&supervised_config());
-        assert!(!mgr.needs_approval("file_read"));
-        assert!(!mgr.needs_approval("memory_recall"));
+        assert!(!mgr.needs_approval("file_read", ToolRisk::Medium));
+        assert!(!mgr.needs_approval("memory_recall", ToolRisk::Medium));
     }
 
     #[test]
     fn always_ask_tools_always_prompt() {
         let mgr = ApprovalManager::from_config(&supervised_config());
-        assert!(mgr.needs_approval("shell"));
+        assert!(mgr.needs_approval("shell", ToolRisk::Medium));
     }
 
     #[test]
     fn unknown_tool_needs_approval_in_supervised() {
         let mgr = ApprovalManager::from_config(&supervised_config());
-        assert!(mgr.needs_approval("file_write"));
-        assert!(mgr.needs_approval("http_request"));
+        assert!(mgr.needs_approval("file_write", ToolRisk::Medium));
+        assert!(mgr.needs_approval("http_request", ToolRisk::Medium));
     }
 
     #[test]
     fn full_autonomy_never_prompts() {
         let mgr = ApprovalManager::from_config(&full_config());
-        assert!(!mgr.needs_approval("shell"));
-        assert!(!mgr.needs_approval("file_write"));
-        assert!(!mgr.needs_approval("anything"));
+        assert!(!mgr.needs_approval("shell", ToolRisk::Medium));
+        assert!(!mgr.needs_approval("file_write", ToolRisk::Medium));
+        assert!(!mgr.needs_approval("anything", ToolRisk::Medium));
     }
 
     #[test]
@@ -274,7 +637,7 @@ mod tests {
             ..AutonomyConfig::default()
         };
         let mgr = ApprovalManager::from_config(&config);
-        assert!(!mgr.needs_approval("shell"));
+        assert!(!mgr.needs_approval("shell", ToolRisk::Medium));
     }
 
     // ── session allowlist ────────────────────────────────────
@@ -282,17 +645,16 @@ mod tests {
     #[test]
     fn always_response_adds_to_session_allowlist() {
         let mgr = ApprovalManager::from_config(&supervised_config());
-        assert!(mgr.needs_approval("file_write"));
+        assert!(mgr.needs_approval("file_write", ToolRisk::Medium));
 
         mgr.record_decision(
-            "file_write",
-            &serde_json::json!({"path": "test.txt"}),
+            &request("file_write", serde_json::json!({"path": "test.txt"})),
             ApprovalResponse::Always,
             "cli",
         );
 
         // Now file_write should be in session allowlist.
-        assert!(!mgr.needs_approval("file_write"));
+        assert!(!mgr.needs_approval("file_write", ToolRisk::Medium));
     }
 
     #[test]
@@ -301,26 +663,24 @@ mod tests {
 
         // Even after "Always" for shell, it should still prompt.
         mgr.record_decision(
-            "shell",
-            &serde_json::json!({"command": "ls"}),
+            &request("shell", serde_json::json!({"command": "ls"})),
             ApprovalResponse::Always,
             "cli",
         );
 
         // shell is in always_ask, so it still needs approval.
-        assert!(mgr.needs_approval("shell"));
+        assert!(mgr.needs_approval("shell", ToolRisk::Medium));
     }
 
     #[test]
     fn yes_response_does_not_add_to_allowlist() {
         let mgr = ApprovalManager::from_config(&supervised_config());
         mgr.record_decision(
-            "file_write",
-            &serde_json::json!({}),
+            &request("file_write", serde_json::json!({})),
             ApprovalResponse::Yes,
             "cli",
         );
-        assert!(mgr.needs_approval("file_write"));
+        assert!(mgr.needs_approval("file_write", ToolRisk::Medium));
     }
 
     // ── audit log ────────────────────────────────────────────
@@ -330,14 +690,15 @@ mod tests {
         let mgr = ApprovalManager::from_config(&supervised_config());
 
         mgr.record_decision(
-            "shell",
-            &serde_json::json!({"command": "rm -rf ./build/"}),
+            &request("shell", serde_json::json!({"command": "rm -rf ./build/"})),
             ApprovalResponse::No,
             "cli",
         );
         mgr.record_decision(
-            "file_write",
-            &serde_json::json!({"path": "out.txt", "content": "hello"}),
+            &request(
+                "file_write",
+                serde_json::json!({"path": "out.txt", "content": "hello"}),
+            ),
             ApprovalResponse::Yes,
             "cli",
         );
@@ -354,8 +715,7 @@ mod tests {
     fn audit_log_contains_timestamp_and_channel() {
         let mgr = ApprovalManager::from_config(&supervised_config());
         mgr.record_decision(
-            "shell",
-            &serde_json::json!({"command": "ls"}),
+            &request("shell", serde_json::json!({"command": "ls"})),
             ApprovalResponse::Yes,
             "telegram",
         );
@@ -418,9 +778,192 @@ mod tests {
         let req = ApprovalRequest {
             tool_name: "shell".into(),
             arguments: serde_json::json!({"command": "echo hi"}),
+            risk: ToolRisk::High,
         };
         let json = serde_json::to_string(&req).unwrap();
         let parsed: ApprovalRequest = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.tool_name, "shell");
+        assert_eq!(parsed.risk, ToolRisk::High);
+    }
+
+    // ── risk levels ──────────────────────────────────────────
+
+    #[test]
+    fn risk_levels_map_to_actions() {
+        let mut config = AutonomyConfig::default();
+        config.risk_approval.high = ApprovalAction::Deny;
+        let mgr = ApprovalManager::from_config(&config);
+        assert_eq!(mgr.decide("list_dir", ToolRisk::Low), ApprovalAction::Allow);
+        assert_eq!(
+            mgr.decide("file_write", ToolRisk::Medium),
+            ApprovalAction::Ask
+        );
+        assert_eq!(mgr.decide("shell", ToolRisk::High), ApprovalAction::Deny);
+    }
+
+    #[test]
+    fn per_tool_override_beats_lists_and_levels() {
+        let mut config = supervised_config();
+        config
+            .risk_approval
+            .tools
+            .insert("shell".into(), ApprovalAction::Allow);
+        config
+            .risk_approval
+            .tools
+            .insert("file_read".into(), ApprovalAction::Deny);
+        let mgr = ApprovalManager::from_config(&config);
+        assert_eq!(mgr.decide("shell", ToolRisk::High), ApprovalAction::Allow);
+        assert_eq!(mgr.decide("file_read", ToolRisk::Low), ApprovalAction::Deny);
+    }
+
+    #[test]
+    fn always_ask_applies_to_low_risk_tools() {
+        let config = AutonomyConfig {
+            always_ask: vec!["web_search".into()],
+            ..AutonomyConfig::default()
+        };
+        let mgr = ApprovalManager::from_config(&config);
+        assert_eq!(mgr.decide("web_search", ToolRisk::Low), ApprovalAction::Ask);
+    }
+
+    #[test]
+    fn deny_rules_apply_under_full_autonomy() {
+        let mut config = full_config();
+        config.risk_approval.high = ApprovalAction::Deny;
+        let mgr = ApprovalManager::from_config(&config);
+        assert_eq!(mgr.decide("shell", ToolRisk::High), ApprovalAction::Deny);
+        assert_eq!(
+            mgr.decide("file_write", ToolRisk::Medium),
+            ApprovalAction::Allow
+        );
+    }
+
+    #[test]
+    fn seeded_session_allowlist_skips_prompt() {
+        let mgr = ApprovalManager::from_config(&supervised_config())
+            .with_session_allowlist(HashSet::from(["file_write".to_string()]));
+        assert!(!mgr.needs_approval("file_write", ToolRisk::Medium));
+    }
+
+    #[test]
+    fn policy_denials_are_logged() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        mgr.record_policy_denial(&request("shell", serde_json::json!({})), "discord");
+        let log = mgr.audit_log();
+        assert_eq!(log.len(), 1);
+        assert!(log[0].by_policy);
+        assert_eq!(log[0].decision, ApprovalResponse::No);
+    }
+
+    #[test]
+    fn decisions_are_written_to_audit_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let logger = AuditLogger::new(
+            crate::config::AuditConfig::default(),
+            tmp.path().to_path_buf(),
+        )
+        .unwrap();
+        let mgr = ApprovalManager::from_config(&supervised_config())
+            .with_audit_logger(Arc::new(logger))
+            .with_actor("alice");
+        mgr.record_decision(
+            &request("file_write", serde_json::json!({"path": "a.txt"})),
+            ApprovalResponse::Yes,
+            "telegram",
+        );
+        let content = std::fs::read_to_string(tmp.path().join("audit.log")).unwrap();
+        assert!(content.contains("tool_approval"));
+        assert!(content.contains("file_write(path: a.txt)"));
+        assert!(content.contains("alice"));
+    }
+
+    // ── prompting ────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn non_cli_without_prompter_denies() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        let decision = mgr
+            .prompt(&request("file_write", serde_json::json!({})), "webhook")
+            .await;
+        assert_eq!(decision, ApprovalResponse::No);
+    }
+
+    #[test]
+    fn parses_chat_replies() {
+        assert_eq!(parse_reply(" Yes! "), Some(ApprovalResponse::Yes));
+        assert_eq!(parse_reply("/always"), Some(ApprovalResponse::Always));
+        assert_eq!(parse_reply("n"), Some(ApprovalResponse::No));
+        assert_eq!(parse_reply("what does this do?"), None);
+    }
+
+    struct RecordingChannel {
+        sent: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.sent.lock().push(message.content.clone());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<crate::channels::traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn channel_prompter_waits_for_reply() {
+        let channel = Arc::new(RecordingChannel {
+            sent: Mutex::new(Vec::new()),
+        });
+        let pending = Arc::new(PendingApprovals::new());
+        let prompter = ChannelApprovalPrompter::new(
+            channel.clone(),
+            "chat-1",
+            None,
+            "test_chat-1_alice",
+            pending.clone(),
+            Duration::from_secs(5),
+        );
+        let req = request("file_write", serde_json::json!({"path": "a.txt"}));
+        let waiting = tokio::spawn(async move { prompter.prompt(&req).await });
+
+        assert!(!pending.resolve("test_chat-1_bob", "yes"));
+        while !pending.resolve("test_chat-1_alice", "always") {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(waiting.await.unwrap(), ApprovalResponse::Always);
+        assert!(channel.sent.lock()[0].contains("`file_write` (medium risk)"));
+    }
+
+    #[tokio::test]
+    async fn channel_prompter_times_out_as_denied() {
+        let channel = Arc::new(RecordingChannel {
+            sent: Mutex::new(Vec::new()),
+        });
+        let pending = Arc::new(PendingApprovals::new());
+        let prompter = ChannelApprovalPrompter::new(
+            channel.clone(),
+            "chat-1",
+            None,
+            "scope",
+            pending.clone(),
+            Duration::from_millis(20),
+        );
+        let decision = prompter
+            .prompt(&request("shell", serde_json::json!({"command": "ls"})))
+            .await;
+        assert_eq!(decision, ApprovalResponse::No);
+        assert!(!pending.resolve("scope", "yes"));
+        assert_eq!(channel.sent.lock().len(), 2);
     }
 }
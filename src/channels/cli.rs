@@ -19,6 +19,7 @@ impl Channel for CliChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        crate::agent::status_line::suspend();
         println!("{}", message.content);
         Ok(())
     }
@@ -40,6 +41,7 @@ impl Channel for CliChannel {
             let msg = ChannelMessage {
                 id: Uuid::new_v4().to_string(),
                 sender: "user".to_string(),
+                sender_name: None,
                 reply_target: "user".to_string(),
                 content: line,
                 channel: "cli".to_string(),
@@ -106,6 +108,7 @@ mod tests {
         let msg = ChannelMessage {
             id: "test-id".into(),
             sender: "user".into(),
+            sender_name: None,
             reply_target: "user".into(),
             content: "hello".into(),
             channel: "cli".into(),
@@ -125,6 +128,7 @@ mod tests {
         let msg = ChannelMessage {
             id: "id".into(),
             sender: "s".into(),
+            sender_name: None,
             reply_target: "s".into(),
             content: "c".into(),
             channel: "ch".into(),
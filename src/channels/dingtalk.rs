@@ -267,6 +267,7 @@ impl Channel for DingTalkChannel {
                     let channel_msg = ChannelMessage {
                         id: Uuid::new_v4().to_string(),
                         sender: sender_id.to_string(),
+                        sender_name: None,
                         reply_target: chat_id,
                         content: content.to_string(),
                         channel: "dingtalk".to_string(),
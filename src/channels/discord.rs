@@ -1,4 +1,6 @@
+use super::draft::{self, DraftThrottle, DRAFT_PLACEHOLDER};
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::StreamMode;
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
@@ -17,6 +19,8 @@ pub struct DiscordChannel {
     listen_to_bots: bool,
     mention_only: bool,
     typing_handles: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+    stream_mode: StreamMode,
+    drafts: DraftThrottle,
 }
 
 impl DiscordChannel {
@@ -34,9 +38,22 @@ impl DiscordChannel {
             listen_to_bots,
             mention_only,
             typing_handles: Mutex::new(HashMap::new()),
+            stream_mode: StreamMode::Off,
+            drafts: DraftThrottle::new(1000),
         }
     }
 
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.drafts = DraftThrottle::new(draft_update_interval_ms);
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.discord")
     }
@@ -53,6 +70,44 @@ impl DiscordChannel {
         let part = token.split('.').next()?;
         base64_decode(part)
     }
+
+    /// Replace the content of a message the bot sent.
+    async fn edit_message(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        content: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        Ok(self
+            .http_client()
+            .patch(discord_message_url(channel_id, message_id))
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&json!({ "content": content }))
+            .send()
+            .await?)
+    }
+
+    async fn delete_message(&self, channel_id: &str, message_id: &str) -> anyhow::Result<()> {
+        let resp = self
+            .http_client()
+            .delete(discord_message_url(channel_id, message_id))
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord delete message failed ({status}): {err}");
+        }
+        Ok(())
+    }
+}
+
+fn discord_message_url(channel_id: &str, message_id: &str) -> String {
+    format!("https://discord.com/api/v10/channels/{channel_id}/messages/{message_id}")
 }
 
 /// Process Discord message attachments and return a string to append to the
@@ -759,6 +814,12 @@ impl Channel for DiscordChannel {
                             format!("discord_{message_id}")
                         },
                         sender: author_id.to_string(),
+                        sender_name: d.get("author").and_then(|a| {
+                            ["global_name", "username"]
+                                .iter()
+                                .find_map(|key| a.get(*key).and_then(|n| n.as_str()))
+                                .map(str::to_string)
+                        }),
                         reply_target: if channel_id.is_empty() {
                             author_id.to_string()
                         } else {
@@ -880,12 +941,157 @@ impl Channel for DiscordChannel {
 
         Ok(())
     }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+
+        let content = if message.content.is_empty() {
+            DRAFT_PLACEHOLDER
+        } else {
+            &message.content
+        };
+        let url = format!(
+            "https://discord.com/api/v10/channels/{}/messages",
+            message.recipient
+        );
+        let resp = self
+            .http_client()
+            .post(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&json!({
+                "content": draft::truncate_for_edit(content, DISCORD_MAX_MESSAGE_LENGTH)
+            }))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord send draft failed ({status}): {err}");
+        }
+
+        let body: serde_json::Value = resp.json().await?;
+        let message_id = body
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string);
+        if let Some(id) = message_id.as_deref() {
+            self.drafts.sent(id);
+        }
+        Ok(message_id)
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        if !self.drafts.ready(message_id) {
+            return Ok(());
+        }
+
+        let content = super::strip_tool_call_tags(text);
+        let resp = self
+            .edit_message(
+                recipient,
+                message_id,
+                &draft::truncate_for_edit(&content, DISCORD_MAX_MESSAGE_LENGTH),
+            )
+            .await?;
+
+        let status = resp.status();
+        if status.is_success() {
+            self.drafts.sent(message_id);
+        } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let wait =
+                draft::retry_after(resp.headers()).unwrap_or(std::time::Duration::from_secs(1));
+            self.drafts.back_off(message_id, wait);
+        } else {
+            let err = resp.text().await.unwrap_or_default();
+            tracing::debug!("Discord draft edit failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.drafts.finish(message_id);
+
+        // Attachments need a new message, and long replies are split.
+        let content = super::strip_tool_call_tags(text);
+        let (_, attachments) = parse_attachment_markers(&content);
+        if attachments.is_empty() && content.chars().count() <= DISCORD_MAX_MESSAGE_LENGTH {
+            match self.edit_message(recipient, message_id, &content).await {
+                Ok(resp) if resp.status().is_success() => return Ok(()),
+                Ok(resp) => {
+                    let status = resp.status();
+                    let err = resp.text().await.unwrap_or_default();
+                    tracing::warn!(
+                        "Discord draft finalize failed ({status}): {err}; sending as new message"
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("Discord draft finalize failed: {e}; sending as new message");
+                }
+            }
+        }
+
+        if let Err(e) = self.delete_message(recipient, message_id).await {
+            tracing::debug!("Failed to delete Discord draft: {e}");
+        }
+        self.send(&SendMessage::new(content, recipient)).await
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.drafts.finish(message_id);
+        self.delete_message(recipient, message_id).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn supports_draft_updates_respects_stream_mode() {
+        let off = DiscordChannel::new("fake".into(), None, vec![], false, false);
+        assert!(!off.supports_draft_updates());
+
+        let partial = DiscordChannel::new("fake".into(), None, vec![], false, false)
+            .with_streaming(StreamMode::Partial, 60_000);
+        assert!(partial.supports_draft_updates());
+    }
+
+    #[tokio::test]
+    async fn drafts_are_off_by_default_and_edits_are_throttled() {
+        let off = DiscordChannel::new("fake".into(), None, vec![], false, false);
+        let id = off
+            .send_draft(&SendMessage::new("draft", "123"))
+            .await
+            .unwrap();
+        assert!(id.is_none());
+
+        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false)
+            .with_streaming(StreamMode::Partial, 60_000);
+        ch.drafts.sent("42");
+        // Within the interval the edit is skipped without a network call.
+        assert!(ch.update_draft("123", "42", "more text").await.is_ok());
+    }
+
     #[test]
     fn discord_channel_name() {
         let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
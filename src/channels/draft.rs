@@ -0,0 +1,114 @@
+//! Pacing for streamed replies (`stream_mode = "partial"`).
+//!
+//! Channels that stream by editing a placeholder message ask
+//! [`DraftThrottle::ready`] before each edit, so a draft is edited at most
+//! once per `draft_update_interval_ms`, and push the next edit back when the
+//! platform answers with a rate limit. Edits skipped here are not lost: each
+//! one carries the whole accumulated text, and `finalize_draft` writes the
+//! complete reply.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Shown in the placeholder until the first edit.
+pub const DRAFT_PLACEHOLDER: &str = "...";
+
+/// Marks a mid-stream edit cut to the platform's length cap.
+const TRUNCATED_SUFFIX: &str = " …";
+
+pub struct DraftThrottle {
+    interval: Duration,
+    next_edit: Mutex<HashMap<String, Instant>>,
+}
+
+impl DraftThrottle {
+    pub fn new(interval_ms: u64) -> Self {
+        Self {
+            interval: Duration::from_millis(interval_ms),
+            next_edit: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether draft `key` may be edited now.
+    pub fn ready(&self, key: &str) -> bool {
+        self.next_edit
+            .lock()
+            .get(key)
+            .is_none_or(|next| Instant::now() >= *next)
+    }
+
+    /// Record that draft `key` was just sent or edited.
+    pub fn sent(&self, key: &str) {
+        self.next_edit
+            .lock()
+            .insert(key.to_string(), Instant::now() + self.interval);
+    }
+
+    /// Hold edits of draft `key` for `retry_after` (a platform rate limit).
+    pub fn back_off(&self, key: &str, retry_after: Duration) {
+        self.next_edit.lock().insert(
+            key.to_string(),
+            Instant::now() + retry_after.max(self.interval),
+        );
+    }
+
+    /// Forget draft `key` once it is finalized or cancelled.
+    pub fn finish(&self, key: &str) {
+        self.next_edit.lock().remove(key);
+    }
+}
+
+/// `text` cut to at most `max_chars` characters for a mid-stream edit.
+pub fn truncate_for_edit(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let keep = max_chars.saturating_sub(TRUNCATED_SUFFIX.chars().count());
+    let mut out: String = text.chars().take(keep).collect();
+    out.push_str(TRUNCATED_SUFFIX);
+    out
+}
+
+/// `Retry-After` of a rate-limited response, in seconds (fractions allowed).
+pub fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_are_spaced_by_the_interval() {
+        let throttle = DraftThrottle::new(60_000);
+        assert!(throttle.ready("42"));
+        throttle.sent("42");
+        assert!(!throttle.ready("42"));
+        assert!(throttle.ready("43"));
+        throttle.finish("42");
+        assert!(throttle.ready("42"));
+
+        let unthrottled = DraftThrottle::new(0);
+        unthrottled.sent("42");
+        assert!(unthrottled.ready("42"));
+        unthrottled.back_off("42", Duration::from_secs(5));
+        assert!(!unthrottled.ready("42"));
+    }
+
+    #[test]
+    fn long_drafts_are_cut_on_char_boundaries() {
+        assert_eq!(truncate_for_edit("short", 10), "short");
+        let cut = truncate_for_edit(&"😀".repeat(30), 10);
+        assert_eq!(cut.chars().count(), 10);
+        assert!(cut.ends_with(" …"));
+    }
+}
@@ -456,6 +456,7 @@ impl EmailChannel {
                 id: email.msg_id,
                 reply_target: email.sender.clone(),
                 sender: email.sender,
+                sender_name: None,
                 content: email.content,
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
@@ -224,6 +224,7 @@ end tell"#
                         let msg = ChannelMessage {
                             id: rowid.to_string(),
                             sender: sender.clone(),
+                            sender_name: None,
                             reply_target: sender.clone(),
                             content: text,
                             channel: "imessage".to_string(),
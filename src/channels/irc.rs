@@ -572,6 +572,7 @@ impl Channel for IrcChannel {
                     let channel_msg = ChannelMessage {
                         id: format!("irc_{}_{seq}", chrono::Utc::now().timestamp_millis()),
                         sender: sender_nick.to_string(),
+                        sender_name: None,
                         reply_target,
                         content,
                         channel: "irc".to_string(),
@@ -813,6 +813,7 @@ impl LarkChannel {
                     let channel_msg = ChannelMessage {
                         id: Uuid::new_v4().to_string(),
                         sender: lark_msg.chat_id.clone(),
+                        sender_name: None,
                         reply_target: lark_msg.chat_id.clone(),
                         content: text,
                         channel: self.channel_name().to_string(),
@@ -1115,6 +1116,7 @@ impl LarkChannel {
         messages.push(ChannelMessage {
             id: Uuid::new_v4().to_string(),
             sender: chat_id.to_string(),
+            sender_name: None,
             reply_target: chat_id.to_string(),
             content: text,
             channel: self.channel_name().to_string(),
@@ -209,6 +209,7 @@ impl LinqChannel {
             id: Uuid::new_v4().to_string(),
             reply_target,
             sender: normalized_from,
+            sender_name: None,
             content,
             channel: "linq".to_string(),
             timestamp,
@@ -0,0 +1,655 @@
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Rolling window used for the outgoing post rate limit.
+const POST_RATE_WINDOW: Duration = Duration::from_secs(3600);
+/// Visibility used when a reply target carries no (or an unknown) visibility.
+const FALLBACK_VISIBILITY: &str = "direct";
+
+/// Mastodon channel — streams mentions via the user notification stream and
+/// replies in-thread through the REST API.
+///
+/// Reply targets are packed as `status_id:visibility:acct` so that replies
+/// keep the visibility of the incoming post and mention its author.
+pub struct MastodonChannel {
+    instance_url: String,
+    access_token: String,
+    allowed_users: Vec<String>,
+    max_posts_per_hour: u32,
+    max_status_chars: usize,
+    /// Timestamps of statuses posted within the current rate window.
+    recent_posts: Mutex<VecDeque<Instant>>,
+}
+
+// ── Mastodon API JSON shapes ─────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct Notification {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    account: Option<Account>,
+    #[serde(default)]
+    status: Option<Status>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Account {
+    id: String,
+    acct: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Status {
+    id: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    visibility: String,
+    #[serde(default)]
+    created_at: Option<String>,
+}
+
+/// Parsed form of a packed reply target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReplyTarget {
+    in_reply_to_id: Option<String>,
+    visibility: &'static str,
+    acct: String,
+}
+
+impl MastodonChannel {
+    pub fn new(
+        instance_url: String,
+        access_token: String,
+        allowed_users: Vec<String>,
+        max_posts_per_hour: u32,
+        max_status_chars: usize,
+    ) -> Self {
+        let instance_url = instance_url.trim_end_matches('/').to_string();
+        Self {
+            instance_url,
+            access_token,
+            allowed_users,
+            max_posts_per_hour,
+            max_status_chars,
+            recent_posts: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client("channel.mastodon")
+    }
+
+    /// Check if an account handle is in the allowlist.
+    /// Empty list means deny everyone. "*" means allow everyone.
+    fn is_user_allowed(&self, acct: &str) -> bool {
+        let acct = acct.trim_start_matches('@');
+        self.allowed_users
+            .iter()
+            .any(|u| u == "*" || u.trim_start_matches('@').eq_ignore_ascii_case(acct))
+    }
+
+    /// Fetch the bot's own account so self-mentions can be ignored.
+    async fn get_bot_account(&self) -> Option<Account> {
+        self.http_client()
+            .get(format!(
+                "{}/api/v1/accounts/verify_credentials",
+                self.instance_url
+            ))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await
+            .ok()?
+            .json()
+            .await
+            .ok()
+    }
+
+    /// Reserve one slot in the rolling post window, failing when the hourly
+    /// budget is exhausted. A budget of zero disables the limit.
+    fn try_reserve_post(&self) -> Result<()> {
+        if self.max_posts_per_hour == 0 {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let mut recent = self.recent_posts.lock();
+        while recent
+            .front()
+            .is_some_and(|posted| now.duration_since(*posted) >= POST_RATE_WINDOW)
+        {
+            recent.pop_front();
+        }
+
+        if recent.len() >= self.max_posts_per_hour as usize {
+            bail!(
+                "Mastodon rate limit reached ({} posts per hour)",
+                self.max_posts_per_hour
+            );
+        }
+
+        recent.push_back(now);
+        Ok(())
+    }
+
+    /// Post a single status and return its ID.
+    async fn post_status(
+        &self,
+        text: &str,
+        in_reply_to_id: Option<&str>,
+        visibility: &str,
+    ) -> Result<String> {
+        self.try_reserve_post()?;
+
+        let mut body = serde_json::json!({
+            "status": text,
+            "visibility": visibility,
+        });
+        if let Some(parent) = in_reply_to_id {
+            body["in_reply_to_id"] = serde_json::json!(parent);
+        }
+
+        let resp = self
+            .http_client()
+            .post(format!("{}/api/v1/statuses", self.instance_url))
+            .bearer_auth(&self.access_token)
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
+            bail!("Mastodon post failed ({status}): {body}");
+        }
+
+        let posted: serde_json::Value = resp.json().await?;
+        Ok(posted
+            .get("id")
+            .and_then(|id| id.as_str())
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    /// Convert a notification into a channel message when it is an allowed mention.
+    fn parse_notification(
+        &self,
+        notification: &Notification,
+        bot_account_id: &str,
+        bot_username: &str,
+    ) -> Option<ChannelMessage> {
+        if notification.kind != "mention" {
+            return None;
+        }
+
+        let account = notification.account.as_ref()?;
+        let status = notification.status.as_ref()?;
+
+        if account.id == bot_account_id {
+            return None;
+        }
+
+        if !self.is_user_allowed(&account.acct) {
+            tracing::warn!(
+                "Mastodon: ignoring mention from unauthorized account: {}",
+                account.acct
+            );
+            return None;
+        }
+
+        let text = strip_leading_mentions(&status_html_to_text(&status.content), bot_username);
+        if text.is_empty() {
+            return None;
+        }
+
+        let visibility = normalize_visibility(&status.visibility);
+        let timestamp = status
+            .created_at
+            .as_deref()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .and_then(|ts| u64::try_from(ts.timestamp()).ok())
+            .unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+            });
+
+        Some(ChannelMessage {
+            id: format!("mastodon_{}", status.id),
+            sender: account.acct.clone(),
+            sender_name: None,
+            reply_target: format!("{}:{}:{}", status.id, visibility, account.acct),
+            content: text,
+            channel: "mastodon".to_string(),
+            timestamp,
+            thread_ts: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Channel for MastodonChannel {
+    fn name(&self) -> &str {
+        "mastodon"
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        let target = parse_reply_target(&message.recipient);
+        let prefix = if target.acct.is_empty() {
+            String::new()
+        } else {
+            format!("@{} ", target.acct)
+        };
+
+        // Each chunk replies to the previous one so long answers read as a thread.
+        let mut parent = target.in_reply_to_id.clone();
+        for chunk in split_status(
+            &message.content,
+            prefix.chars().count(),
+            self.max_status_chars,
+        ) {
+            let text = format!("{prefix}{chunk}");
+            let posted_id = self
+                .post_status(&text, parent.as_deref(), target.visibility)
+                .await?;
+            if !posted_id.is_empty() {
+                parent = Some(posted_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+        let (bot_account_id, bot_username) = match self.get_bot_account().await {
+            Some(account) => (account.id, account.acct),
+            None => {
+                bail!("Mastodon verify_credentials failed; check instance_url and access_token")
+            }
+        };
+
+        let url = format!("{}/api/v1/streaming/user/notification", self.instance_url);
+        tracing::info!(
+            "Mastodon channel listening for mentions as @{bot_username} on {}...",
+            self.instance_url
+        );
+
+        let resp = self
+            .http_client()
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .header("Accept", "text/event-stream")
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            bail!("Mastodon stream returned {status}: {body}");
+        }
+
+        let mut bytes_stream = resp.bytes_stream();
+        let mut buffer = String::new();
+        let mut current_event = String::new();
+        let mut current_data = String::new();
+
+        loop {
+            let chunk = tokio::select! {
+                _ = tx.closed() => {
+                    tracing::info!("Mastodon channel shutting down");
+                    return Ok(());
+                }
+                next = bytes_stream.next() => next,
+            };
+            let Some(chunk) = chunk else {
+                bail!("Mastodon stream ended");
+            };
+            let chunk = match chunk {
+                Ok(c) => c,
+                Err(e) => bail!("Mastodon stream error: {e}"),
+            };
+
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim_end_matches('\r').to_string();
+                buffer = buffer[newline_pos + 1..].to_string();
+
+                // Skip SSE comments (keepalive)
+                if line.starts_with(':') {
+                    continue;
+                }
+
+                if line.is_empty() {
+                    // Empty line = event boundary, dispatch accumulated data
+                    if current_event == "notification" && !current_data.is_empty() {
+                        match serde_json::from_str::<Notification>(&current_data) {
+                            Ok(notification) => {
+                                if let Some(msg) = self.parse_notification(
+                                    &notification,
+                                    &bot_account_id,
+                                    &bot_username,
+                                ) {
+                                    if tx.send(msg).await.is_err() {
+                                        return Ok(());
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                tracing::warn!("Mastodon parse error: {e}");
+                            }
+                        }
+                    }
+                    current_event.clear();
+                    current_data.clear();
+                } else if let Some(event) = line.strip_prefix("event:") {
+                    current_event = event.trim().to_string();
+                } else if let Some(data) = line.strip_prefix("data:") {
+                    if !current_data.is_empty() {
+                        current_data.push('\n');
+                    }
+                    current_data.push_str(data.trim_start());
+                }
+            }
+        }
+    }
+
+    async fn health_check(&self) -> bool {
+        self.http_client()
+            .get(format!(
+                "{}/api/v1/accounts/verify_credentials",
+                self.instance_url
+            ))
+            .bearer_auth(&self.access_token)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .map(|r| r.status().is_success())
+            .unwrap_or(false)
+    }
+}
+
+/// Map an incoming status visibility onto the value used for the reply.
+/// Unknown values fall back to the most restrictive visibility.
+fn normalize_visibility(visibility: &str) -> &'static str {
+    match visibility {
+        "public" => "public",
+        "unlisted" => "unlisted",
+        "private" => "private",
+        _ => FALLBACK_VISIBILITY,
+    }
+}
+
+/// Unpack `status_id:visibility:acct`. Anything else is treated as a bare
+/// account handle and answered with a new direct message.
+fn parse_reply_target(recipient: &str) -> ReplyTarget {
+    let mut parts = recipient.splitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(status_id), Some(visibility), Some(acct)) if !status_id.is_empty() => ReplyTarget {
+            in_reply_to_id: Some(status_id.to_string()),
+            visibility: normalize_visibility(visibility),
+            acct: acct.trim_start_matches('@').to_string(),
+        },
+        _ => ReplyTarget {
+            in_reply_to_id: None,
+            visibility: FALLBACK_VISIBILITY,
+            acct: recipient.trim().trim_start_matches('@').to_string(),
+        },
+    }
+}
+
+/// Convert Mastodon status HTML into plain text.
+///
+/// Paragraph and line breaks become newlines, tags are dropped and the
+/// handful of entities Mastodon emits are decoded.
+fn status_html_to_text(html: &str) -> String {
+    let html = html
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n")
+        .replace("</p>", "\n\n");
+
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Remove the bot's own `@handle` mentions from the start of a status so the
+/// agent only sees the actual request.
+fn strip_leading_mentions(text: &str, bot_username: &str) -> String {
+    let bot_local = bot_username.split('@').next().unwrap_or_default();
+    let mut rest = text.trim_start();
+
+    while let Some(candidate) = rest.strip_prefix('@') {
+        let end = candidate
+            .find(char::is_whitespace)
+            .unwrap_or(candidate.len());
+        let handle = &candidate[..end];
+        let local = handle.split('@').next().unwrap_or_default();
+        if bot_local.is_empty() || !local.eq_ignore_ascii_case(bot_local) {
+            break;
+        }
+        rest = candidate[end..].trim_start();
+    }
+
+    rest.trim().to_string()
+}
+
+/// Split a reply into status-sized chunks, leaving room for the mention prefix.
+/// Prefers breaking on whitespace; falls back to hard character splits.
+fn split_status(text: &str, prefix_chars: usize, max_status_chars: usize) -> Vec<String> {
+    let budget = max_status_chars.saturating_sub(prefix_chars).max(1);
+    let mut chunks = Vec::new();
+    let mut rest = text.trim();
+
+    while rest.chars().count() > budget {
+        let hard_end = rest
+            .char_indices()
+            .nth(budget)
+            .map_or(rest.len(), |(idx, _)| idx);
+        let split_at = rest[..hard_end]
+            .rfind(char::is_whitespace)
+            .filter(|idx| *idx > 0)
+            .unwrap_or(hard_end);
+        chunks.push(rest[..split_at].trim_end().to_string());
+        rest = rest[split_at..].trim_start();
+    }
+
+    if !rest.is_empty() || chunks.is_empty() {
+        chunks.push(rest.to_string());
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_channel(allowed: Vec<String>, max_posts_per_hour: u32) -> MastodonChannel {
+        MastodonChannel::new(
+            "https://mastodon.example/".into(),
+            "token".into(),
+            allowed,
+            max_posts_per_hour,
+            500,
+        )
+    }
+
+    fn mention(acct: &str, visibility: &str, content: &str) -> Notification {
+        serde_json::from_value(serde_json::json!({
+            "id": "n1",
+            "type": "mention",
+            "account": { "id": "acc-1", "acct": acct },
+            "status": {
+                "id": "109",
+                "content": content,
+                "visibility": visibility,
+                "created_at": "2025-01-02T03:04:05.000Z"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn mastodon_url_trimming() {
+        let ch = make_channel(vec![], 30);
+        assert_eq!(ch.instance_url, "https://mastodon.example");
+    }
+
+    #[test]
+    fn mastodon_name() {
+        assert_eq!(make_channel(vec![], 30).name(), "mastodon");
+    }
+
+    #[test]
+    fn mastodon_allowlist_semantics() {
+        let empty = make_channel(vec![], 30);
+        assert!(!empty.is_user_allowed("alice"));
+
+        let wildcard = make_channel(vec!["*".into()], 30);
+        assert!(wildcard.is_user_allowed("anyone@remote.example"));
+
+        let explicit = make_channel(vec!["@Alice@remote.example".into()], 30);
+        assert!(explicit.is_user_allowed("alice@remote.example"));
+        assert!(!explicit.is_user_allowed("alice"));
+    }
+
+    #[test]
+    fn mastodon_parses_mention_with_matching_visibility() {
+        let ch = make_channel(vec!["*".into()], 30);
+        let n = mention(
+            "alice@remote.example",
+            "unlisted",
+            "<p><span class=\"h-card\"><a href=\"https://mastodon.example/@bot\">@<span>bot</span></a></span> what&#39;s up?</p>",
+        );
+
+        let msg = ch.parse_notification(&n, "bot-id", "bot").unwrap();
+        assert_eq!(msg.sender, "alice@remote.example");
+        assert_eq!(msg.content, "what's up?");
+        assert_eq!(msg.reply_target, "109:unlisted:alice@remote.example");
+        assert_eq!(msg.channel, "mastodon");
+        assert_eq!(msg.timestamp, 1_735_787_045);
+    }
+
+    #[test]
+    fn mastodon_ignores_non_mentions_and_self() {
+        let ch = make_channel(vec!["*".into()], 30);
+
+        let mut favourite = mention("alice", "public", "<p>@bot hi</p>");
+        favourite.kind = "favourite".into();
+        assert!(ch.parse_notification(&favourite, "bot-id", "bot").is_none());
+
+        let own = mention("bot", "public", "<p>@bot hi</p>");
+        assert!(ch.parse_notification(&own, "acc-1", "bot").is_none());
+    }
+
+    #[test]
+    fn mastodon_ignores_unauthorized_and_empty_mentions() {
+        let ch = make_channel(vec!["alice".into()], 30);
+        let stranger = mention("mallory", "public", "<p>@bot hi</p>");
+        assert!(ch.parse_notification(&stranger, "bot-id", "bot").is_none());
+
+        let bare = mention("alice", "public", "<p>@bot</p>");
+        assert!(ch.parse_notification(&bare, "bot-id", "bot").is_none());
+    }
+
+    #[test]
+    fn mastodon_reply_target_roundtrip() {
+        let target = parse_reply_target("109:private:alice@remote.example");
+        assert_eq!(target.in_reply_to_id.as_deref(), Some("109"));
+        assert_eq!(target.visibility, "private");
+        assert_eq!(target.acct, "alice@remote.example");
+    }
+
+    #[test]
+    fn mastodon_bare_recipient_becomes_direct_message() {
+        let target = parse_reply_target("@alice");
+        assert_eq!(target.in_reply_to_id, None);
+        assert_eq!(target.visibility, "direct");
+        assert_eq!(target.acct, "alice");
+    }
+
+    #[test]
+    fn mastodon_unknown_visibility_falls_back_to_direct() {
+        assert_eq!(normalize_visibility("public"), "public");
+        assert_eq!(normalize_visibility("limited"), "direct");
+        assert_eq!(normalize_visibility(""), "direct");
+    }
+
+    #[test]
+    fn mastodon_html_to_text_handles_breaks_and_entities() {
+        let text = status_html_to_text("<p>one<br>two &amp; three</p><p>&lt;four&gt;</p>");
+        assert_eq!(text, "one\ntwo & three\n\n<four>");
+    }
+
+    #[test]
+    fn mastodon_strip_leading_mentions_keeps_other_handles() {
+        assert_eq!(strip_leading_mentions("@bot @bot hi", "bot"), "hi");
+        assert_eq!(
+            strip_leading_mentions("@Bot@mastodon.example ask @carol", "bot"),
+            "ask @carol"
+        );
+        assert_eq!(strip_leading_mentions("@carol hi", "bot"), "@carol hi");
+    }
+
+    #[test]
+    fn mastodon_split_status_respects_limit() {
+        let text = "word ".repeat(300);
+        let chunks = split_status(&text, 10, 100);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 90));
+        assert_eq!(chunks.join(" ").split_whitespace().count(), 300);
+    }
+
+    #[test]
+    fn mastodon_split_status_hard_splits_long_words() {
+        let chunks = split_status(&"x".repeat(25), 0, 10);
+        assert_eq!(chunks, vec!["x".repeat(10), "x".repeat(10), "x".repeat(5)]);
+    }
+
+    #[test]
+    fn mastodon_rate_limit_blocks_after_budget() {
+        let ch = make_channel(vec![], 2);
+        assert!(ch.try_reserve_post().is_ok());
+        assert!(ch.try_reserve_post().is_ok());
+        let err = ch.try_reserve_post().unwrap_err();
+        assert!(err.to_string().contains("rate limit"));
+    }
+
+    #[test]
+    fn mastodon_rate_limit_zero_is_unlimited() {
+        let ch = make_channel(vec![], 0);
+        for _ in 0..100 {
+            assert!(ch.try_reserve_post().is_ok());
+        }
+    }
+}
@@ -4,10 +4,12 @@ use matrix_sdk::{
     authentication::matrix::MatrixSession,
     config::SyncSettings,
     ruma::{
-        events::room::message::{
-            MessageType, OriginalSyncRoomMessageEvent, RoomMessageEventContent,
+        api::client::receipt::create_receipt::v3::ReceiptType,
+        events::{
+            receipt::ReceiptThread,
+            room::message::{MessageType, OriginalSyncRoomMessageEvent, RoomMessageEventContent},
         },
-        OwnedRoomId, OwnedUserId,
+        OwnedEventId, OwnedRoomId, OwnedUserId,
     },
     Client as MatrixSdkClient, LoopCtrl, Room, RoomState, SessionMeta, SessionTokens,
 };
@@ -413,6 +415,28 @@ impl MatrixChannel {
         )
     }
 
+    async fn joined_room(&self) -> anyhow::Result<Room> {
+        let client = self.matrix_client().await?;
+        let target_room_id = self.target_room_id().await?;
+        let target_room: OwnedRoomId = target_room_id.parse()?;
+
+        let mut room = client.get_room(&target_room);
+        if room.is_none() {
+            let _ = client.sync_once(SyncSettings::new()).await;
+            room = client.get_room(&target_room);
+        }
+
+        let Some(room) = room else {
+            anyhow::bail!("Matrix room '{}' not found in joined rooms", target_room_id);
+        };
+
+        if room.state() != RoomState::Joined {
+            anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
+        }
+
+        Ok(room)
+    }
+
     async fn ensure_room_accessible(&self, room_id: &str) -> anyhow::Result<()> {
         let encoded_room = Self::encode_path_segment(room_id);
         let url = format!(
@@ -529,24 +553,7 @@ impl Channel for MatrixChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let client = self.matrix_client().await?;
-        let target_room_id = self.target_room_id().await?;
-        let target_room: OwnedRoomId = target_room_id.parse()?;
-
-        let mut room = client.get_room(&target_room);
-        if room.is_none() {
-            let _ = client.sync_once(SyncSettings::new()).await;
-            room = client.get_room(&target_room);
-        }
-
-        let Some(room) = room else {
-            anyhow::bail!("Matrix room '{}' not found in joined rooms", target_room_id);
-        };
-
-        if room.state() != RoomState::Joined {
-            anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
-        }
-
+        let room = self.joined_room().await?;
         room.send(RoomMessageEventContent::text_markdown(&message.content))
             .await?;
 
@@ -637,6 +644,7 @@ impl Channel for MatrixChannel {
                 let msg = ChannelMessage {
                     id: event_id,
                     sender: sender.clone(),
+                    sender_name: None,
                     reply_target: sender,
                     content: body,
                     channel: "matrix".to_string(),
@@ -673,6 +681,25 @@ impl Channel for MatrixChannel {
         Ok(())
     }
 
+    async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+        self.joined_room().await?.typing_notice(true).await?;
+        Ok(())
+    }
+
+    async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+        self.joined_room().await?.typing_notice(false).await?;
+        Ok(())
+    }
+
+    async fn mark_read(&self, message: &ChannelMessage) -> anyhow::Result<()> {
+        let event_id: OwnedEventId = message.id.parse()?;
+        self.joined_room()
+            .await?
+            .send_single_receipt(ReceiptType::Read, ReceiptThread::Unthreaded, event_id)
+            .await?;
+        Ok(())
+    }
+
     async fn health_check(&self) -> bool {
         let Ok(room_id) = self.target_room_id().await else {
             return false;
@@ -267,6 +267,25 @@ impl Channel for MattermostChannel {
         }
         Ok(())
     }
+
+    async fn mark_read(&self, message: &ChannelMessage) -> Result<()> {
+        // Mattermost tracks reads per channel: viewing it marks it read.
+        let channel_id = message
+            .reply_target
+            .split_once(':')
+            .map_or(message.reply_target.as_str(), |(channel, _)| channel);
+        let resp = self
+            .http_client()
+            .post(format!("{}/api/v4/channels/members/me/view", self.base_url))
+            .bearer_auth(&self.bot_token)
+            .json(&serde_json::json!({ "channel_id": channel_id }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            bail!("Mattermost channel view failed ({})", resp.status());
+        }
+        Ok(())
+    }
 }
 
 impl MattermostChannel {
@@ -316,6 +335,7 @@ impl MattermostChannel {
         Some(ChannelMessage {
             id: format!("mattermost_{id}"),
             sender: user_id.to_string(),
+            sender_name: None,
             reply_target,
             content,
             channel: "mattermost".to_string(),
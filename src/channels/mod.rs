@@ -18,18 +18,27 @@ pub mod clawdtalk;
 pub mod cli;
 pub mod dingtalk;
 pub mod discord;
+pub mod draft;
 pub mod email_channel;
+pub mod github;
 pub mod imessage;
 pub mod irc;
 #[cfg(feature = "channel-lark")]
 pub mod lark;
 pub mod linq;
+pub mod mastodon;
 #[cfg(feature = "channel-matrix")]
 pub mod matrix;
 pub mod mattermost;
 pub mod nextcloud_talk;
 pub mod nostr;
+pub mod outbox;
+pub mod pairing;
+pub mod participants;
+pub mod presence;
 pub mod qq;
+pub mod rate_limit;
+pub mod router;
 pub mod signal;
 pub mod slack;
 pub mod telegram;
@@ -41,17 +50,20 @@ pub mod whatsapp;
 pub mod whatsapp_storage;
 #[cfg(feature = "whatsapp-web")]
 pub mod whatsapp_web;
+pub mod xmpp;
 
 pub use clawdtalk::{ClawdTalkChannel, ClawdTalkConfig};
 pub use cli::CliChannel;
 pub use dingtalk::DingTalkChannel;
 pub use discord::DiscordChannel;
 pub use email_channel::EmailChannel;
+pub use github::GitHubChannel;
 pub use imessage::IMessageChannel;
 pub use irc::IrcChannel;
 #[cfg(feature = "channel-lark")]
 pub use lark::LarkChannel;
 pub use linq::LinqChannel;
+pub use mastodon::MastodonChannel;
 #[cfg(feature = "channel-matrix")]
 pub use matrix::MatrixChannel;
 pub use mattermost::MattermostChannel;
@@ -66,18 +78,20 @@ pub use wati::WatiChannel;
 pub use whatsapp::WhatsAppChannel;
 #[cfg(feature = "whatsapp-web")]
 pub use whatsapp_web::WhatsAppWebChannel;
+pub use xmpp::XmppChannel;
 
 use crate::agent::loop_::{build_tool_instructions, run_tool_call_loop, scrub_credentials};
 use crate::config::Config;
 use crate::identity;
 use crate::memory::{self, Memory};
-use crate::observability::{self, runtime_trace, Observer};
+use crate::observability::{self, runtime_trace, Observer, ObserverEvent};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
-use crate::security::SecurityPolicy;
+use crate::security::{roles, GuardrailVerdict, Role, SecurityPolicy};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
+use presence::ChannelPresence;
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
@@ -111,7 +125,6 @@ const CHANNEL_MESSAGE_TIMEOUT_SCALE_CAP: u64 = 4;
 const CHANNEL_PARALLELISM_PER_CHANNEL: usize = 4;
 const CHANNEL_MIN_IN_FLIGHT_MESSAGES: usize = 8;
 const CHANNEL_MAX_IN_FLIGHT_MESSAGES: usize = 64;
-const CHANNEL_TYPING_REFRESH_INTERVAL_SECS: u64 = 4;
 const CHANNEL_HEALTH_HEARTBEAT_SECS: u64 = 30;
 const MODEL_CACHE_FILE: &str = "models_cache.json";
 const MODEL_CACHE_PREVIEW_LIMIT: usize = 10;
@@ -120,11 +133,27 @@ const MEMORY_CONTEXT_ENTRY_MAX_CHARS: usize = 800;
 const MEMORY_CONTEXT_MAX_CHARS: usize = 4_000;
 const CHANNEL_HISTORY_COMPACT_KEEP_MESSAGES: usize = 12;
 const CHANNEL_HISTORY_COMPACT_CONTENT_CHARS: usize = 600;
+/// Tool results kept from a turn interrupted by a newer message.
+const INTERRUPTED_RESULT_EXCERPT_CHARS: usize = 400;
+const INTERRUPTED_NOTE_MAX_CHARS: usize = 2_000;
 /// Guardrail for hook-modified outbound channel content.
 const CHANNEL_HOOK_MAX_OUTBOUND_CHARS: usize = 20_000;
 
 type ProviderCacheMap = Arc<Mutex<HashMap<String, Arc<dyn Provider>>>>;
 type RouteSelectionMap = Arc<Mutex<HashMap<String, ChannelRouteSelection>>>;
+/// Tools each conversation turned off with `/tools off`.
+type DisabledToolsMap = Arc<Mutex<HashMap<String, Vec<String>>>>;
+/// Context each conversation pinned with `/pin`, loaded from the
+/// conversation store on first use.
+type PinsMap = Arc<Mutex<HashMap<String, crate::agent::context::Pins>>>;
+/// Prompt dumps of conversations that used `/debug` or started while
+/// `[agent].debug_prompts` was on.
+type DebugDumpMap = Arc<Mutex<HashMap<String, Arc<crate::agent::debug_dump::DebugDump>>>>;
+/// Dry-run switches of conversations that used `/dryrun` or started while
+/// `[agent].dry_run` was on.
+type DryRunMap = Arc<Mutex<HashMap<String, Arc<crate::agent::dry_run::DryRun>>>>;
+/// File changes of each conversation by turn, for `/undo`.
+type UndoJournalMap = Arc<Mutex<HashMap<String, Arc<crate::agent::undo::Journal>>>>;
 
 fn effective_channel_message_timeout_secs(configured: u64) -> u64 {
     configured.max(MIN_CHANNEL_MESSAGE_TIMEOUT_SECS)
@@ -152,6 +181,22 @@ enum ChannelRuntimeCommand {
     ShowModel,
     SetModel(String),
     NewSession,
+    ShowBudget,
+    OverrideBudget,
+    ShowHelp,
+    Tools(crate::agent::commands::ToolsCommand),
+    SearchMemory(String),
+    Pin(Option<String>),
+    Unpin(Option<usize>),
+    ShowContext,
+    Export(Option<String>),
+    Debug(Option<bool>),
+    DryRun(Option<bool>),
+    Undo(crate::agent::commands::UndoCommand),
+    /// A malformed command; carries its usage line.
+    Usage(String),
+    /// A command above the sender's role; carries the refusal.
+    Denied(String),
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -173,6 +218,7 @@ struct ChannelRuntimeDefaults {
     api_key: Option<String>,
     api_url: Option<String>,
     reliability: crate::config::ReliabilityConfig,
+    non_cli_excluded_tools: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -209,21 +255,73 @@ struct ChannelRuntimeContext {
     model: Arc<String>,
     temperature: f64,
     auto_save_memory: bool,
+    /// Which memory scope each channel/contact stores to and recalls from.
+    memory_scopes: Arc<memory::scope::MemoryScopePolicy>,
     max_tool_iterations: usize,
     min_relevance_score: f64,
+    /// Extract durable facts from each exchange into long-term memory.
+    fact_extraction: bool,
+    /// Model used for fact extraction (`None` = the route's model).
+    fact_extraction_model: Option<String>,
     conversation_histories: ConversationHistoryMap,
+    /// Persistent copy of the histories (`None` keeps them in memory only).
+    conversation_store: Option<Arc<crate::conversations::ConversationStore>>,
+    /// Session policies and idle archiving from `[conversations]`.
+    conversations: Arc<crate::config::ConversationsConfig>,
     provider_cache: ProviderCacheMap,
     route_overrides: RouteSelectionMap,
+    disabled_tools: DisabledToolsMap,
+    pins: PinsMap,
+    /// `[agent].max_context_tokens`: token budget of each turn's context.
+    max_context_tokens: usize,
     api_key: Option<String>,
     api_url: Option<String>,
     reliability: Arc<crate::config::ReliabilityConfig>,
     provider_runtime_options: providers::ProviderRuntimeOptions,
     workspace_dir: Arc<PathBuf>,
     message_timeout_secs: u64,
+    /// Telegram's `interrupt_on_new_message`.
     interrupt_on_new_message: bool,
+    /// `[channels_config].interrupt_on_new_message`: interrupt on every channel.
+    interrupt_all_channels: bool,
+    /// `[channels_config].read_receipts`: mark inbound messages read.
+    read_receipts: bool,
+    /// Queue for replies the channel could not deliver (`None` when
+    /// `[channels_config.outbox]` is disabled).
+    outbox: Option<Arc<outbox::Outbox>>,
     multimodal: crate::config::MultimodalConfig,
+    tool_output: crate::config::ToolOutputConfig,
     hooks: Option<Arc<crate::hooks::HookRunner>>,
     non_cli_excluded_tools: Arc<Vec<String>>,
+    /// Tool-call approval prompts on the originating channel (`None` runs
+    /// tools without approval checks).
+    approvals: Option<Arc<crate::approval::ChannelApprovals>>,
+    /// Agent selection per channel and `@agent` prefix from `[routing]`.
+    agent_router: Arc<router::AgentRouter>,
+    /// Per-conversation `[budget]` limits (`None` when disabled).
+    budgets: Option<Arc<crate::cost::budget::SessionBudgets>>,
+    /// `[agent].prompt_templates`: expand template variables per message.
+    prompt_templates: bool,
+    /// `[guardrails]` filters (`None` when disabled).
+    guardrails: Option<Arc<crate::security::Guardrails>>,
+    /// `[replay].enabled`: record each conversation for `zeroclaw replay`.
+    replay: bool,
+    /// `[agent].debug_prompts`: dump every conversation's turns.
+    debug_prompts: bool,
+    debug_dumps: DebugDumpMap,
+    /// `[agent].dry_run`: start every conversation in dry run.
+    dry_run: bool,
+    dry_runs: DryRunMap,
+    undo_journals: UndoJournalMap,
+    /// `[query_classification]` heuristics routing model calls to
+    /// `[[model_routes]]` (`None` when disabled).
+    model_router: Option<Arc<crate::agent::model_router::ModelRouter>>,
+    /// Approved chat channel senders (`None` when `[security.pairing]` is
+    /// disabled).
+    pairing: Option<Arc<pairing::PairingStore>>,
+    /// `[channels_config.rate_limit]` per-sender and per-channel limits
+    /// (`None` when none are set).
+    rate_limiter: Option<Arc<rate_limit::RateLimiter>>,
 }
 
 #[derive(Clone)]
@@ -267,14 +365,42 @@ fn conversation_memory_key(msg: &traits::ChannelMessage) -> String {
     }
 }
 
-fn conversation_history_key(msg: &traits::ChannelMessage) -> String {
+fn conversation_history_key(
+    conversations: &crate::config::ConversationsConfig,
+    msg: &traits::ChannelMessage,
+) -> String {
     // Include thread_ts for per-topic session isolation in forum groups
-    match &msg.thread_ts {
-        Some(tid) => format!("{}_{}_{}", msg.channel, tid, msg.sender),
-        None => format!("{}_{}", msg.channel, msg.sender),
+    match (&msg.thread_ts, conversations.policy_for(&msg.channel)) {
+        (Some(tid), crate::config::SessionPolicy::PerThread) => {
+            format!("{}_{}_{}", msg.channel, tid, msg.sender)
+        }
+        (Some(tid), crate::config::SessionPolicy::PerChat) => {
+            format!("{}_{}_{}", msg.channel, msg.reply_target, tid)
+        }
+        (None, crate::config::SessionPolicy::PerChat) => {
+            format!("{}_{}", msg.channel, msg.reply_target)
+        }
+        _ => format!("{}_{}", msg.channel, msg.sender),
+    }
+}
+
+/// Conversations with a routed agent are kept apart from the main agent's,
+/// so `@agent` messages do not mix histories.
+fn agent_history_key(key: String, agent: Option<&str>) -> String {
+    match agent {
+        Some(agent) => format!("{key}@{agent}"),
+        None => key,
     }
 }
 
+/// Memory scope for a message under `[memory.scopes]`.
+fn memory_scope(
+    policy: &memory::scope::MemoryScopePolicy,
+    msg: &traits::ChannelMessage,
+) -> memory::scope::MemoryScope {
+    policy.resolve(&msg.channel, &msg.sender)
+}
+
 fn interruption_scope_key(msg: &traits::ChannelMessage) -> String {
     format!("{}_{}_{}", msg.channel, msg.reply_target, msg.sender)
 }
@@ -415,7 +541,7 @@ fn channel_delivery_instructions(channel_name: &str) -> Option<&'static str> {
     }
 }
 
-fn build_channel_system_prompt(
+pub(crate) fn build_channel_system_prompt(
     base_prompt: &str,
     channel_name: &str,
     reply_target: &str,
@@ -436,7 +562,8 @@ fn build_channel_system_prompt(
              reply_target={reply_target}. When scheduling delayed messages or reminders \
              via cron_add for this conversation, use delivery={{\"mode\":\"announce\",\
              \"channel\":\"{channel_name}\",\"to\":\"{reply_target}\"}} so the message \
-             reaches the user."
+             reaches the user. Likewise pass notify={{\"channel\":\"{channel_name}\",\
+             \"to\":\"{reply_target}\"}} when enqueueing background work with the task tool."
         );
         prompt.push_str(&context);
     }
@@ -477,52 +604,68 @@ fn normalize_cached_channel_turns(turns: Vec<ChatMessage>) -> Vec<ChatMessage> {
     normalized
 }
 
+/// Tools a conversation on `channel_name` can turn on and off.
+fn channel_tool_names<'a>(ctx: &'a ChannelRuntimeContext, channel_name: &str) -> Vec<&'a str> {
+    ctx.tools_registry
+        .iter()
+        .map(|tool| tool.name())
+        .filter(|name| {
+            channel_name == "cli" || !ctx.non_cli_excluded_tools.iter().any(|ex| ex == name)
+        })
+        .collect()
+}
+
 fn supports_runtime_model_switch(channel_name: &str) -> bool {
     matches!(channel_name, "telegram" | "discord")
 }
 
-fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRuntimeCommand> {
-    if !supports_runtime_model_switch(channel_name) {
-        return None;
-    }
-
-    let trimmed = content.trim();
-    if !trimmed.starts_with('/') {
-        return None;
+fn parse_runtime_command(
+    channel_name: &str,
+    content: &str,
+    role: Role,
+) -> Option<ChannelRuntimeCommand> {
+    use crate::agent::commands::SlashCommand;
+
+    let command = match crate::agent::commands::parse(content)? {
+        Ok(command) => command,
+        Err(usage) => return Some(ChannelRuntimeCommand::Usage(usage)),
+    };
+    if let Err(reply) = crate::agent::commands::check_role(&command, role) {
+        return Some(ChannelRuntimeCommand::Denied(reply));
     }
-
-    let mut parts = trimmed.split_whitespace();
-    let command_token = parts.next()?;
-    let base_command = command_token
-        .split('@')
-        .next()
-        .unwrap_or(command_token)
-        .to_ascii_lowercase();
-
-    match base_command.as_str() {
-        "/models" => {
-            if let Some(provider) = parts.next() {
-                Some(ChannelRuntimeCommand::SetProvider(
-                    provider.trim().to_string(),
-                ))
-            } else {
-                Some(ChannelRuntimeCommand::ShowProviders)
-            }
-        }
-        "/model" => {
-            let model = parts.collect::<Vec<_>>().join(" ").trim().to_string();
-            if model.is_empty() {
-                Some(ChannelRuntimeCommand::ShowModel)
-            } else {
-                Some(ChannelRuntimeCommand::SetModel(model))
-            }
-        }
-        "/new" => Some(ChannelRuntimeCommand::NewSession),
-        _ => None,
+    // Model switching and `/new` are limited to channels whose users may
+    // change the route; the rest work everywhere (`/budget` must, since a
+    // stopped run needs a way to resume).
+    match command {
+        SlashCommand::Budget { override_limits } => Some(if override_limits {
+            ChannelRuntimeCommand::OverrideBudget
+        } else {
+            ChannelRuntimeCommand::ShowBudget
+        }),
+        SlashCommand::Help => Some(ChannelRuntimeCommand::ShowHelp),
+        SlashCommand::Tools(tools) => Some(ChannelRuntimeCommand::Tools(tools)),
+        SlashCommand::SearchMemory(query) => Some(ChannelRuntimeCommand::SearchMemory(query)),
+        SlashCommand::Pin(text) => Some(ChannelRuntimeCommand::Pin(text)),
+        SlashCommand::Unpin(index) => Some(ChannelRuntimeCommand::Unpin(index)),
+        SlashCommand::Context => Some(ChannelRuntimeCommand::ShowContext),
+        SlashCommand::Export(file) => Some(ChannelRuntimeCommand::Export(file)),
+        SlashCommand::Debug(toggle) => Some(ChannelRuntimeCommand::Debug(toggle)),
+        SlashCommand::DryRun(toggle) => Some(ChannelRuntimeCommand::DryRun(toggle)),
+        SlashCommand::Undo(undo) => Some(ChannelRuntimeCommand::Undo(undo)),
+        // Terminal-only commands go to the model like any other text.
+        SlashCommand::Quit | SlashCommand::Edit(_) => None,
+        _ if !supports_runtime_model_switch(channel_name) => None,
+        SlashCommand::Provider(None) => Some(ChannelRuntimeCommand::ShowProviders),
+        SlashCommand::Provider(Some(provider)) => {
+            Some(ChannelRuntimeCommand::SetProvider(provider))
+        }
+        SlashCommand::Model(None) => Some(ChannelRuntimeCommand::ShowModel),
+        SlashCommand::Model(Some(model)) => Some(ChannelRuntimeCommand::SetModel(model)),
+        SlashCommand::New => Some(ChannelRuntimeCommand::NewSession),
     }
 }
 
-fn resolve_provider_alias(name: &str) -> Option<String> {
+pub(crate) fn resolve_provider_alias(name: &str) -> Option<String> {
     let candidate = name.trim();
     if candidate.is_empty() {
         return None;
@@ -565,6 +708,7 @@ fn runtime_defaults_from_config(config: &Config) -> ChannelRuntimeDefaults {
         api_key: config.api_key.clone(),
         api_url: config.api_url.clone(),
         reliability: config.reliability.clone(),
+        non_cli_excluded_tools: config.autonomy.non_cli_excluded_tools.clone(),
     }
 }
 
@@ -592,6 +736,7 @@ fn runtime_defaults_snapshot(ctx: &ChannelRuntimeContext) -> ChannelRuntimeDefau
         api_key: ctx.api_key.clone(),
         api_url: ctx.api_url.clone(),
         reliability: (*ctx.reliability).clone(),
+        non_cli_excluded_tools: (*ctx.non_cli_excluded_tools).clone(),
     }
 }
 
@@ -632,6 +777,8 @@ async fn load_runtime_defaults_from_config_file(path: &Path) -> Result<ChannelRu
     if let Some(zeroclaw_dir) = path.parent() {
         let store = crate::security::SecretStore::new(zeroclaw_dir, parsed.secrets.encrypt);
         decrypt_optional_secret_for_runtime_reload(&store, &mut parsed.api_key, "config.api_key")?;
+        crate::security::SecretResolvers::from_config(&parsed.secrets, zeroclaw_dir)
+            .resolve_optional(&mut parsed.api_key, "config.api_key")?;
     }
 
     parsed.apply_env_overrides();
@@ -659,6 +806,16 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
     }
 
     let next_defaults = load_runtime_defaults_from_config_file(&config_path).await?;
+    apply_runtime_defaults(ctx, &config_path, next_defaults, Some(stamp)).await
+}
+
+/// Swap in new provider defaults and record the file stamp they came from.
+async fn apply_runtime_defaults(
+    ctx: &ChannelRuntimeContext,
+    config_path: &Path,
+    next_defaults: ChannelRuntimeDefaults,
+    stamp: Option<ConfigFileStamp>,
+) -> Result<()> {
     let next_default_provider = providers::create_resilient_provider_with_options(
         &next_defaults.default_provider,
         next_defaults.api_key.as_deref(),
@@ -689,10 +846,10 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
             .lock()
             .unwrap_or_else(|e| e.into_inner());
         store.insert(
-            config_path.clone(),
+            config_path.to_path_buf(),
             RuntimeConfigState {
                 defaults: next_defaults.clone(),
-                last_applied_stamp: Some(stamp),
+                last_applied_stamp: stamp,
             },
         );
     }
@@ -708,6 +865,30 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
     Ok(())
 }
 
+/// Apply a config pushed by the daemon's config watcher: provider defaults,
+/// excluded tools and channel allowlists that can change at runtime.
+async fn apply_reloaded_config(ctx: &ChannelRuntimeContext, config: &Config) {
+    if let Some(config_path) = runtime_config_path(ctx) {
+        let stamp = config_file_stamp(&config_path).await;
+        if let Err(err) = apply_runtime_defaults(
+            ctx,
+            &config_path,
+            runtime_defaults_from_config(config),
+            stamp,
+        )
+        .await
+        {
+            tracing::warn!("Failed to apply reloaded provider settings: {err}");
+        }
+    }
+    if let (Some(telegram), Some(channel)) = (
+        config.channels_config.telegram.as_ref(),
+        ctx.channels_by_name.get("telegram"),
+    ) {
+        channel.reload_allowed_users(&telegram.allowed_users);
+    }
+}
+
 fn default_route_selection(ctx: &ChannelRuntimeContext) -> ChannelRouteSelection {
     let defaults = runtime_defaults_snapshot(ctx);
     ChannelRouteSelection {
@@ -725,6 +906,23 @@ fn get_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str) -> Channel
         .unwrap_or_else(|| default_route_selection(ctx))
 }
 
+/// Like [`get_route_selection`], defaulting to the routed agent's provider and model.
+fn get_agent_route_selection(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    agent: &crate::config::DelegateAgentConfig,
+) -> ChannelRouteSelection {
+    ctx.route_overrides
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(sender_key)
+        .cloned()
+        .unwrap_or_else(|| ChannelRouteSelection {
+            provider: agent.provider.clone(),
+            model: agent.model.clone(),
+        })
+}
+
 fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: ChannelRouteSelection) {
     let default_route = default_route_selection(ctx);
     let mut routes = ctx
@@ -738,11 +936,174 @@ fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: Chan
     }
 }
 
+/// Undo journal of a conversation, created on first use.
+fn sender_undo_journal(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+) -> Arc<crate::agent::undo::Journal> {
+    Arc::clone(
+        ctx.undo_journals
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(sender_key.to_string())
+            .or_insert_with(|| Arc::new(crate::agent::undo::Journal::new(&ctx.workspace_dir))),
+    )
+}
+
+/// Dry-run switch of a conversation: the one `/dryrun` set up, or a new one
+/// when `[agent].dry_run` is on.
+fn sender_dry_run(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+) -> Option<Arc<crate::agent::dry_run::DryRun>> {
+    let mut dry_runs = ctx.dry_runs.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(dry_run) = dry_runs.get(sender_key) {
+        return Some(Arc::clone(dry_run));
+    }
+    if !ctx.dry_run {
+        return None;
+    }
+    let dry_run = Arc::new(crate::agent::dry_run::DryRun::new(true));
+    dry_runs.insert(sender_key.to_string(), Arc::clone(&dry_run));
+    Some(dry_run)
+}
+
+/// Prompt dump of a conversation: the one `/debug` set up, or a new one
+/// when `[agent].debug_prompts` is on.
+fn sender_debug_dump(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+) -> Option<Arc<crate::agent::debug_dump::DebugDump>> {
+    let mut dumps = ctx.debug_dumps.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(dump) = dumps.get(sender_key) {
+        return Some(Arc::clone(dump));
+    }
+    if !ctx.debug_prompts {
+        return None;
+    }
+    let dump = Arc::new(crate::agent::debug_dump::DebugDump::new(
+        ctx.workspace_dir.as_path(),
+        sender_key,
+        ctx.debug_prompts,
+    ));
+    dumps.insert(sender_key.to_string(), Arc::clone(&dump));
+    Some(dump)
+}
+
+/// Cached turns of `sender_key`.
+fn sender_turns(ctx: &ChannelRuntimeContext, sender_key: &str) -> Vec<ChatMessage> {
+    ctx.conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(sender_key)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Context `sender_key` pinned with `/pin`.
+fn sender_pins(ctx: &ChannelRuntimeContext, sender_key: &str) -> crate::agent::context::Pins {
+    let mut pins = ctx.pins.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(found) = pins.get(sender_key) {
+        return found.clone();
+    }
+    let stored = ctx
+        .conversation_store
+        .as_ref()
+        .and_then(|store| store.session(sender_key).ok().flatten())
+        .map(|session| crate::agent::context::Pins::from_metadata(&session.metadata))
+        .unwrap_or_default();
+    pins.insert(sender_key.to_string(), stored.clone());
+    stored
+}
+
+fn save_sender_pins(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    pins: crate::agent::context::Pins,
+) {
+    if let Some(store) = ctx.conversation_store.as_ref() {
+        if let Err(e) = store.set_session_metadata(
+            sender_key,
+            crate::agent::context::PINS_METADATA_KEY,
+            &pins.to_metadata(),
+        ) {
+            tracing::warn!("Failed to store pins of {sender_key}: {e}");
+        }
+    }
+    ctx.pins
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(sender_key.to_string(), pins);
+}
+
+/// Forget `sender_key`'s turns, budget and temp files. Pins stay.
 fn clear_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
     ctx.conversation_histories
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .remove(sender_key);
+    if let Some(budgets) = ctx.budgets.as_ref() {
+        budgets.reset(sender_key);
+    }
+    if let Some(store) = ctx.conversation_store.as_ref() {
+        if let Err(e) = store.clear_session(sender_key) {
+            tracing::warn!("Failed to clear stored conversation {sender_key}: {e}");
+        }
+    }
+    crate::security::workspace::end_session(&ctx.workspace_dir, sender_key);
+}
+
+/// Archive the stored session once it has been idle longer than
+/// `archive_after_idle_hours`, so this message starts a new conversation.
+fn archive_idle_session(ctx: &ChannelRuntimeContext, sender_key: &str) {
+    let hours = ctx.conversations.archive_after_idle_hours;
+    let Some(store) = ctx.conversation_store.as_ref().filter(|_| hours > 0) else {
+        return;
+    };
+    match store.archive_if_idle(sender_key, chrono::Duration::hours(i64::from(hours))) {
+        Ok(Some(archived)) => {
+            ctx.conversation_histories
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(sender_key);
+            ctx.pins
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(sender_key);
+            crate::security::workspace::end_session(&ctx.workspace_dir, sender_key);
+            tracing::info!("Archived idle conversation {sender_key} as {archived}");
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to archive idle conversation {sender_key}: {e}"),
+    }
+}
+
+/// Seed the in-memory history from the conversation store after a restart.
+fn hydrate_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
+    let Some(store) = ctx.conversation_store.as_ref() else {
+        return;
+    };
+    let mut histories = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if histories.contains_key(sender_key) {
+        return;
+    }
+    match store.recent_messages(sender_key, MAX_CHANNEL_HISTORY) {
+        Ok(messages) if !messages.is_empty() => {
+            let turns = messages
+                .into_iter()
+                .map(|m| ChatMessage {
+                    role: m.role,
+                    content: m.content,
+                })
+                .collect();
+            histories.insert(sender_key.to_string(), turns);
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to load stored conversation {sender_key}: {e}"),
+    }
 }
 
 fn compact_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) -> bool {
@@ -785,6 +1146,11 @@ fn append_sender_turn(ctx: &ChannelRuntimeContext, sender_key: &str, turn: ChatM
         .conversation_histories
         .lock()
         .unwrap_or_else(|e| e.into_inner());
+    if let Some(store) = ctx.conversation_store.as_ref() {
+        if let Err(e) = store.append_message(sender_key, &turn.role, &turn.content) {
+            tracing::warn!("Failed to persist conversation turn for {sender_key}: {e}");
+        }
+    }
     let turns = histories.entry(sender_key.to_string()).or_default();
     turns.push(turn);
     while turns.len() > MAX_CHANNEL_HISTORY {
@@ -816,6 +1182,9 @@ fn rollback_orphan_user_turn(
     if turns.is_empty() {
         histories.remove(sender_key);
     }
+    if let Some(store) = ctx.conversation_store.as_ref() {
+        let _ = store.remove_last_message_if(sender_key, "user", expected_content);
+    }
     true
 }
 
@@ -916,6 +1285,41 @@ async fn get_or_create_provider(
     Ok(Arc::clone(cached))
 }
 
+/// Provider for a routed agent. Agents without their own `api_key` share the
+/// runtime's providers; the others get one cached per agent.
+async fn get_or_create_agent_provider(
+    ctx: &ChannelRuntimeContext,
+    agent_name: &str,
+    agent: &crate::config::DelegateAgentConfig,
+    provider_name: &str,
+) -> anyhow::Result<Arc<dyn Provider>> {
+    if agent.api_key.is_none() {
+        return get_or_create_provider(ctx, provider_name).await;
+    }
+    let cache_key = format!("agent:{agent_name}:{provider_name}");
+    if let Some(existing) = ctx
+        .provider_cache
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&cache_key)
+        .cloned()
+    {
+        return Ok(existing);
+    }
+
+    let provider = create_resilient_provider_nonblocking(
+        provider_name,
+        agent.api_key.clone(),
+        None,
+        ctx.reliability.as_ref().clone(),
+        ctx.provider_runtime_options.clone(),
+    )
+    .await?;
+    let provider: Arc<dyn Provider> = Arc::from(provider);
+    let mut cache = ctx.provider_cache.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(Arc::clone(cache.entry(cache_key).or_insert(provider)))
+}
+
 async fn create_resilient_provider_nonblocking(
     provider_name: &str,
     api_key: Option<String>,
@@ -995,9 +1399,11 @@ fn build_providers_help_response(current: &ChannelRouteSelection) -> String {
 async fn handle_runtime_command_if_needed(
     ctx: &ChannelRuntimeContext,
     msg: &traits::ChannelMessage,
+    agent_name: Option<&str>,
     target_channel: Option<&Arc<dyn Channel>>,
+    role: Role,
 ) -> bool {
-    let Some(command) = parse_runtime_command(&msg.channel, &msg.content) else {
+    let Some(command) = parse_runtime_command(&msg.channel, &msg.content, role) else {
         return false;
     };
 
@@ -1005,8 +1411,14 @@ async fn handle_runtime_command_if_needed(
         return true;
     };
 
-    let sender_key = conversation_history_key(msg);
-    let mut current = get_route_selection(ctx, &sender_key);
+    let sender_key = agent_history_key(
+        conversation_history_key(&ctx.conversations, msg),
+        agent_name,
+    );
+    let mut current = match agent_name.and_then(|name| ctx.agent_router.agent(name)) {
+        Some(agent) => get_agent_route_selection(ctx, &sender_key, agent),
+        None => get_route_selection(ctx, &sender_key),
+    };
 
     let response = match command {
         ChannelRuntimeCommand::ShowProviders => build_providers_help_response(&current),
@@ -1020,20 +1432,28 @@ async fn handle_runtime_command_if_needed(
                             clear_sender_history(ctx, &sender_key);
                         }
 
-                        format!(
-                            "Provider switched to `{provider_name}` for this sender session. Current model is `{}`.\nUse `/model <model-id>` to set a provider-compatible model.",
-                            current.model
+                        crate::i18n::t_args(
+                            "provider-switched",
+                            &[
+                                ("provider", provider_name.as_str().into()),
+                                ("model", current.model.as_str().into()),
+                            ],
                         )
                     }
                     Err(err) => {
                         let safe_err = providers::sanitize_api_error(&err.to_string());
-                        format!(
-                            "Failed to initialize provider `{provider_name}`. Route unchanged.\nDetails: {safe_err}"
+                        crate::i18n::t_args(
+                            "provider-failed",
+                            &[
+                                ("provider", provider_name.as_str().into()),
+                                ("error", safe_err.into()),
+                            ],
                         )
                     }
                 },
-                None => format!(
-                    "Unknown provider `{raw_provider}`. Use `/models` to list valid providers."
+                None => crate::i18n::t_args(
+                    "provider-unknown",
+                    &[("provider", raw_provider.as_str().into())],
                 ),
             }
         }
@@ -1043,22 +1463,131 @@ async fn handle_runtime_command_if_needed(
         ChannelRuntimeCommand::SetModel(raw_model) => {
             let model = raw_model.trim().trim_matches('`').to_string();
             if model.is_empty() {
-                "Model ID cannot be empty. Use `/model <model-id>`.".to_string()
+                crate::i18n::t("model-empty")
             } else {
                 current.model = model.clone();
                 set_route_selection(ctx, &sender_key, current.clone());
                 clear_sender_history(ctx, &sender_key);
 
-                format!(
-                    "Model switched to `{model}` for provider `{}` in this sender session.",
-                    current.provider
+                crate::i18n::t_args(
+                    "model-switched",
+                    &[
+                        ("model", model.as_str().into()),
+                        ("provider", current.provider.as_str().into()),
+                    ],
                 )
             }
         }
         ChannelRuntimeCommand::NewSession => {
             clear_sender_history(ctx, &sender_key);
-            "Conversation history cleared. Starting fresh.".to_string()
+            crate::i18n::t("history-cleared")
+        }
+        ChannelRuntimeCommand::ShowBudget => match ctx.budgets.as_ref() {
+            Some(budgets) => budgets.get(&sender_key).report(),
+            None => crate::i18n::t("budget-disabled"),
+        },
+        ChannelRuntimeCommand::OverrideBudget => match ctx.budgets.as_ref() {
+            Some(budgets) => {
+                budgets.get(&sender_key).override_limits();
+                crate::i18n::t("budget-lifted")
+            }
+            None => crate::i18n::t("budget-disabled"),
+        },
+        ChannelRuntimeCommand::ShowHelp => crate::agent::commands::help_text(),
+        ChannelRuntimeCommand::Tools(tools) => {
+            let available = channel_tool_names(ctx, &msg.channel);
+            let mut disabled_tools = ctx.disabled_tools.lock().unwrap_or_else(|e| e.into_inner());
+            let disabled = disabled_tools.entry(sender_key.clone()).or_default();
+            let reply = crate::agent::commands::toggle_tools(&tools, disabled, &available);
+            if disabled.is_empty() {
+                disabled_tools.remove(&sender_key);
+            }
+            reply
+        }
+        ChannelRuntimeCommand::SearchMemory(query) => {
+            crate::agent::commands::search_memory(ctx.memory.as_ref(), &query).await
+        }
+        ChannelRuntimeCommand::Pin(text) => {
+            hydrate_sender_history(ctx, &sender_key);
+            let turns = sender_turns(ctx, &sender_key);
+            let mut pins = sender_pins(ctx, &sender_key);
+            let reply = pins.pin(text.as_deref(), &turns);
+            save_sender_pins(ctx, &sender_key, pins);
+            reply
+        }
+        ChannelRuntimeCommand::Unpin(index) => {
+            let mut pins = sender_pins(ctx, &sender_key);
+            let reply = pins.unpin(index);
+            save_sender_pins(ctx, &sender_key, pins);
+            reply
+        }
+        ChannelRuntimeCommand::ShowContext => {
+            hydrate_sender_history(ctx, &sender_key);
+            let turns = normalize_cached_channel_turns(sender_turns(ctx, &sender_key));
+            let system_prompt = build_channel_system_prompt(
+                ctx.system_prompt.as_str(),
+                &msg.channel,
+                &msg.reply_target,
+            );
+            crate::agent::context::report(
+                &system_prompt,
+                &sender_pins(ctx, &sender_key),
+                &turns,
+                ctx.max_context_tokens,
+            )
+        }
+        ChannelRuntimeCommand::Export(file) => {
+            let turns = sender_turns(ctx, &sender_key);
+            let title = ctx
+                .conversation_store
+                .as_ref()
+                .and_then(|store| store.session(&sender_key).ok().flatten())
+                .and_then(|session| {
+                    crate::conversations::title::title(&session).map(str::to_string)
+                });
+            match crate::agent::commands::export_transcript(
+                &turns,
+                title.as_deref(),
+                ctx.workspace_dir.as_path(),
+                file.as_deref(),
+            ) {
+                Ok(path) => crate::i18n::t_args(
+                    "export-done",
+                    &[("path", path.display().to_string().into())],
+                ),
+                Err(e) => {
+                    crate::i18n::t_args("export-failed", &[("error", format!("{e:#}").into())])
+                }
+            }
         }
+        ChannelRuntimeCommand::Debug(toggle) => {
+            let dump = Arc::clone(
+                ctx.debug_dumps
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .entry(sender_key.clone())
+                    .or_insert_with(|| {
+                        Arc::new(crate::agent::debug_dump::DebugDump::new(
+                            ctx.workspace_dir.as_path(),
+                            &sender_key,
+                            ctx.debug_prompts,
+                        ))
+                    }),
+            );
+            crate::agent::commands::toggle_debug(&dump, toggle)
+        }
+        ChannelRuntimeCommand::DryRun(toggle) => {
+            let dry_run = Arc::clone(
+                ctx.dry_runs
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .entry(sender_key.clone())
+                    .or_insert_with(|| Arc::new(crate::agent::dry_run::DryRun::new(ctx.dry_run))),
+            );
+            crate::agent::commands::toggle_dry_run(&dry_run, toggle)
+        }
+        ChannelRuntimeCommand::Undo(undo) => sender_undo_journal(ctx, &sender_key).run(undo).await,
+        ChannelRuntimeCommand::Usage(usage) | ChannelRuntimeCommand::Denied(usage) => usage,
     };
 
     if let Err(err) = channel
@@ -1078,12 +1607,14 @@ async fn build_memory_context(
     mem: &dyn Memory,
     user_msg: &str,
     min_relevance_score: f64,
+    session_id: Option<&str>,
 ) -> String {
     let mut context = String::new();
 
-    if let Ok(entries) = mem.recall(user_msg, 5, None).await {
+    if let Ok(entries) = mem.recall(user_msg, 5, session_id).await {
         let mut included = 0usize;
         let mut used_chars = 0usize;
+        let mut cited = false;
 
         for entry in entries.iter().filter(|e| match e.score {
             Some(score) => score >= min_relevance_score,
@@ -1103,7 +1634,9 @@ async fn build_memory_context(
                 entry.content.clone()
             };
 
-            let line = format!("- {}: {}\n", entry.key, content);
+            let (label, is_source) = memory::indexer::context_label(&entry.key);
+            cited |= is_source;
+            let line = format!("- {label}: {content}\n");
             let line_chars = line.chars().count();
             if used_chars + line_chars > MEMORY_CONTEXT_MAX_CHARS {
                 break;
@@ -1119,6 +1652,9 @@ async fn build_memory_context(
         }
 
         if included > 0 {
+            if cited {
+                context.push_str(memory::indexer::CITATION_HINT);
+            }
             context.push('\n');
         }
     }
@@ -1126,6 +1662,51 @@ async fn build_memory_context(
     context
 }
 
+/// Keep the work of a turn cut short by a newer message: the tools it ran
+/// and excerpts of their results go into the sender's history, so the next
+/// turn continues from there with the new instruction instead of starting
+/// over. Turns that ran no tools leave no trace.
+fn record_interrupted_turn(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    history: &[ChatMessage],
+    start_index: usize,
+) {
+    if let Some(note) = interrupted_turn_note(history, start_index) {
+        append_sender_turn(ctx, sender_key, ChatMessage::assistant(note));
+    }
+}
+
+fn interrupted_turn_note(history: &[ChatMessage], start_index: usize) -> Option<String> {
+    let tool_summary = extract_tool_context_summary(history, start_index);
+    if tool_summary.is_empty() {
+        return None;
+    }
+    let mut note = format!(
+        "{tool_summary}\n[Interrupted by a newer message before finishing. Results so far:]"
+    );
+    let mut used = 0;
+    for msg in history.iter().skip(start_index) {
+        let result = match msg.role.as_str() {
+            // Native tool results are JSON envelopes around the output.
+            "tool" => serde_json::from_str::<serde_json::Value>(&msg.content)
+                .ok()
+                .and_then(|v| v.get("content")?.as_str().map(str::to_string))
+                .unwrap_or_else(|| msg.content.clone()),
+            "user" if msg.content.starts_with("[Tool results]") => msg.content.clone(),
+            _ => continue,
+        };
+        let excerpt = truncate_with_ellipsis(result.trim(), INTERRUPTED_RESULT_EXCERPT_CHARS);
+        used += excerpt.chars().count();
+        if used > INTERRUPTED_NOTE_MAX_CHARS {
+            break;
+        }
+        note.push('\n');
+        note.push_str(&excerpt);
+    }
+    Some(note)
+}
+
 /// Extract a compact summary of tool interactions from history messages added
 /// during `run_tool_call_loop`. Scans assistant messages for `<tool_call>` tags
 /// or native tool-call JSON to collect tool names used.
@@ -1389,6 +1970,7 @@ fn spawn_supervised_listener(
     tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
+    shutdown: CancellationToken,
 ) -> tokio::task::JoinHandle<()> {
     spawn_supervised_listener_with_health_interval(
         ch,
@@ -1396,15 +1978,19 @@ fn spawn_supervised_listener(
         initial_backoff_secs,
         max_backoff_secs,
         Duration::from_secs(CHANNEL_HEALTH_HEARTBEAT_SECS),
+        shutdown,
     )
 }
 
+/// Run a channel listener, restarting it with backoff whenever it exits,
+/// until `shutdown` is cancelled or the message bus closes.
 fn spawn_supervised_listener_with_health_interval(
     ch: Arc<dyn Channel>,
     tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
     health_interval: Duration,
+    shutdown: CancellationToken,
 ) -> tokio::task::JoinHandle<()> {
     let health_interval = if health_interval.is_zero() {
         Duration::from_secs(1)
@@ -1419,6 +2005,7 @@ fn spawn_supervised_listener_with_health_interval(
 
         loop {
             crate::health::mark_component_ok(&component);
+            outbox::notify_reconnected();
             let mut health = tokio::time::interval(health_interval);
             health.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
             let result = {
@@ -1430,11 +2017,16 @@ fn spawn_supervised_listener_with_health_interval(
                         _ = health.tick() => {
                             crate::health::mark_component_ok(&component);
                         }
-                        result = &mut listen_future => break result,
+                        result = &mut listen_future => break Some(result),
+                        () = shutdown.cancelled() => break None,
                     }
                 }
             };
 
+            let Some(result) = result else {
+                tracing::info!("Channel {} stopped for shutdown", ch.name());
+                break;
+            };
             if tx.is_closed() {
                 break;
             }
@@ -1453,7 +2045,10 @@ fn spawn_supervised_listener_with_health_interval(
             }
 
             crate::health::bump_component_restart(&component);
-            tokio::time::sleep(Duration::from_secs(backoff)).await;
+            tokio::select! {
+                () = tokio::time::sleep(Duration::from_secs(backoff)) => {}
+                () = shutdown.cancelled() => break,
+            }
             // Double backoff AFTER sleeping so first error uses initial_backoff
             backoff = backoff.saturating_mul(2).min(max_backoff);
         }
@@ -1475,49 +2070,102 @@ fn log_worker_join_result(result: Result<(), tokio::task::JoinError>) {
     }
 }
 
-fn spawn_scoped_typing_task(
-    channel: Arc<dyn Channel>,
-    recipient: String,
-    cancellation_token: CancellationToken,
-) -> tokio::task::JoinHandle<()> {
-    let stop_signal = cancellation_token;
-    let refresh_interval = Duration::from_secs(CHANNEL_TYPING_REFRESH_INTERVAL_SECS);
-    let handle = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(refresh_interval);
-        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+/// Outcome of the `[security.pairing]` check for one message.
+enum PairingCheck {
+    /// Pairing is disabled or does not apply to the channel.
+    NotRequired,
+    /// The sender is paired with this role.
+    Paired(Role),
+    /// The sender is not paired and has been sent a code; drop the message.
+    Blocked,
+}
 
-        loop {
-            tokio::select! {
-                () = stop_signal.cancelled() => break,
-                _ = interval.tick() => {
-                    if let Err(e) = channel.start_typing(&recipient).await {
-                        tracing::debug!("Failed to start typing on {}: {e}", channel.name());
-                    }
-                }
+/// Gate `msg` on `[security.pairing]`, replying with a pairing code to
+/// senders who are not paired yet.
+async fn check_pairing(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    channel: Option<&Arc<dyn Channel>>,
+) -> PairingCheck {
+    let Some(store) = ctx.pairing.as_deref() else {
+        return PairingCheck::NotRequired;
+    };
+    if msg.channel == "cli" {
+        return PairingCheck::NotRequired;
+    }
+    let reply = match store.role(&msg.channel, &msg.sender) {
+        Ok(Some(role)) => return PairingCheck::Paired(role),
+        Ok(None) => match store.request_code(&msg.channel, &msg.sender) {
+            Ok(code) => {
+                tracing::info!(
+                    channel = %msg.channel,
+                    sender = %msg.sender,
+                    %code,
+                    "Message from unpaired sender; approve with `zeroclaw pair approve {code}`"
+                );
+                pairing::approval_message(&code)
             }
+            Err(e) => {
+                tracing::error!("Failed to create pairing code: {e:#}");
+                "⚠️ Pairing is unavailable right now. Please try again later.".to_string()
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to read pairing store: {e:#}");
+            "⚠️ Pairing is unavailable right now. Please try again later.".to_string()
         }
-
-        if let Err(e) = channel.stop_typing(&recipient).await {
-            tracing::debug!("Failed to stop typing on {}: {e}", channel.name());
-        }
-    });
-
-    handle
+    };
+    if let Some(channel) = channel {
+        let _ = channel
+            .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+            .await;
+    }
+    PairingCheck::Blocked
 }
 
+/// Handle one message inside its memory scope, so every memory access made
+/// while answering it (context, auto-save, tools) is confined to that scope.
 async fn process_channel_message(
+    ctx: Arc<ChannelRuntimeContext>,
+    mut msg: traits::ChannelMessage,
+    cancellation_token: CancellationToken,
+) {
+    let agent = ctx
+        .agent_router
+        .route(&msg.channel, &msg.content)
+        .map(|routed| {
+            msg.content = routed.content;
+            routed.agent
+        });
+    let scope = agent
+        .as_deref()
+        .and_then(|name| ctx.agent_router.agent(name)?.memory_scope.clone())
+        .map_or_else(
+            || memory_scope(&ctx.memory_scopes, &msg),
+            memory::scope::MemoryScope::Private,
+        );
+    memory::scope::run_in_scope(
+        scope.clone(),
+        process_channel_message_in_scope(ctx, msg, agent, scope, cancellation_token),
+    )
+    .await;
+}
+
+async fn process_channel_message_in_scope(
     ctx: Arc<ChannelRuntimeContext>,
     msg: traits::ChannelMessage,
+    agent_name: Option<String>,
+    scope: memory::scope::MemoryScope,
     cancellation_token: CancellationToken,
 ) {
     if cancellation_token.is_cancelled() {
         return;
     }
 
-    println!(
-        "  💬 [{}] from {}: {}",
-        msg.channel,
-        msg.sender,
+    tracing::info!(
+        channel = %msg.channel,
+        sender = %msg.sender,
+        "Inbound message: {}",
         truncate_with_ellipsis(&msg.content, 80)
     );
     runtime_trace::record_event(
@@ -1535,6 +2183,10 @@ async fn process_channel_message(
             "content_preview": truncate_with_ellipsis(&msg.content, 160),
         }),
     );
+    ctx.observer.record_event(&ObserverEvent::ChannelMessage {
+        channel: msg.channel.clone(),
+        direction: "inbound".into(),
+    });
 
     // ── Hook: on_message_received (modifying) ────────────
     let msg = if let Some(hooks) = &ctx.hooks {
@@ -1550,17 +2202,50 @@ async fn process_channel_message(
     };
 
     let target_channel = ctx.channels_by_name.get(&msg.channel).cloned();
+    let paired_role = match check_pairing(ctx.as_ref(), &msg, target_channel.as_ref()).await {
+        PairingCheck::NotRequired => None,
+        PairingCheck::Paired(role) => Some(role),
+        PairingCheck::Blocked => return,
+    };
+    let sender_role = if msg.channel == "cli" {
+        Role::Admin
+    } else {
+        roles::resolve(&msg.channel, &msg.sender, paired_role)
+    };
     if let Err(err) = maybe_apply_runtime_config_update(ctx.as_ref()).await {
         tracing::warn!("Failed to apply runtime config update: {err}");
     }
-    if handle_runtime_command_if_needed(ctx.as_ref(), &msg, target_channel.as_ref()).await {
+    let agent = agent_name
+        .as_deref()
+        .and_then(|name| Some((name, ctx.agent_router.agent(name)?)));
+    if handle_runtime_command_if_needed(
+        ctx.as_ref(),
+        &msg,
+        agent.map(|(name, _)| name),
+        target_channel.as_ref(),
+        sender_role,
+    )
+    .await
+    {
         return;
     }
 
-    let history_key = conversation_history_key(&msg);
-    let route = get_route_selection(ctx.as_ref(), &history_key);
+    let history_key = agent_history_key(
+        conversation_history_key(&ctx.conversations, &msg),
+        agent.map(|(name, _)| name),
+    );
+    let route = match agent {
+        Some((_, agent)) => get_agent_route_selection(ctx.as_ref(), &history_key, agent),
+        None => get_route_selection(ctx.as_ref(), &history_key),
+    };
     let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
-    let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
+    let provider = match agent {
+        Some((name, agent)) => {
+            get_or_create_agent_provider(ctx.as_ref(), name, agent, &route.provider).await
+        }
+        None => get_or_create_provider(ctx.as_ref(), &route.provider).await,
+    };
+    let active_provider = match provider {
         Ok(provider) => provider,
         Err(err) => {
             let safe_err = providers::sanitize_api_error(&err.to_string());
@@ -1579,22 +2264,72 @@ async fn process_channel_message(
             return;
         }
     };
+
+    // ── Guardrails: inbound ──────────────────────────────
+    let msg = match ctx.guardrails.as_deref() {
+        Some(guardrails) => match guardrails
+            .check(
+                active_provider.as_ref(),
+                crate::config::GuardrailDirection::Input,
+                &msg.channel,
+                &msg.content,
+            )
+            .await
+        {
+            GuardrailVerdict::Allow => msg,
+            GuardrailVerdict::Rewrite(content) => traits::ChannelMessage { content, ..msg },
+            GuardrailVerdict::Block { rule, message } => {
+                tracing::info!(
+                    channel = %msg.channel,
+                    sender = %msg.sender,
+                    %rule,
+                    "incoming message blocked by guardrail"
+                );
+                if let Some(channel) = target_channel.as_ref() {
+                    let _ = channel
+                        .send(
+                            &SendMessage::new(message, &msg.reply_target)
+                                .in_thread(msg.thread_ts.clone()),
+                        )
+                        .await;
+                }
+                return;
+            }
+        },
+        None => msg,
+    };
+    // In a shared conversation every turn says who wrote it.
+    let shared_chat =
+        ctx.conversations.policy_for(&msg.channel) == crate::config::SessionPolicy::PerChat;
+    let turn_content = if shared_chat {
+        participants::attribute(&msg, &msg.content)
+    } else {
+        msg.content.clone()
+    };
     if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
         let autosave_key = conversation_memory_key(&msg);
         let _ = ctx
             .memory
             .store(
                 &autosave_key,
-                &msg.content,
+                &turn_content,
                 crate::memory::MemoryCategory::Conversation,
-                None,
+                scope.session_id(),
             )
             .await;
     }
 
-    println!("  ⏳ Processing message...");
     let started_at = Instant::now();
 
+    archive_idle_session(ctx.as_ref(), &history_key);
+    let conversation = ctx.conversation_store.as_ref().map(|store| {
+        if let Err(e) = store.ensure_session(&history_key, &msg.channel, &msg.sender) {
+            tracing::warn!("Failed to create conversation session {history_key}: {e}");
+        }
+        crate::conversations::SessionHandle::new(Arc::clone(store), history_key.clone())
+    });
+    hydrate_sender_history(ctx.as_ref(), &history_key);
+
     let had_prior_history = ctx
         .conversation_histories
         .lock()
@@ -1603,7 +2338,7 @@ async fn process_channel_message(
         .is_some_and(|turns| !turns.is_empty());
 
     // Preserve user turn before the LLM call so interrupted requests keep context.
-    append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&msg.content));
+    append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&turn_content));
 
     // Build history from per-sender conversation cache.
     let prior_turns_raw = ctx
@@ -1613,27 +2348,63 @@ async fn process_channel_message(
         .get(&history_key)
         .cloned()
         .unwrap_or_default();
+    let group_prompt = shared_chat.then(|| participants::prompt_section(&prior_turns_raw));
     let mut prior_turns = normalize_cached_channel_turns(prior_turns_raw);
 
     // Only enrich with memory context when there is no prior conversation
     // history. Follow-up turns already include context from previous messages.
     if !had_prior_history {
-        let memory_context =
-            build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
+        let memory_context = build_memory_context(
+            ctx.memory.as_ref(),
+            &msg.content,
+            ctx.min_relevance_score,
+            scope.session_id(),
+        )
+        .await;
         if let Some(last_turn) = prior_turns.last_mut() {
             if last_turn.role == "user" && !memory_context.is_empty() {
-                last_turn.content = format!("{memory_context}{}", msg.content);
+                last_turn.content = format!("{memory_context}{turn_content}");
             }
         }
     }
 
-    let system_prompt =
+    let mut system_prompt =
         build_channel_system_prompt(ctx.system_prompt.as_str(), &msg.channel, &msg.reply_target);
+    if let Some((name, agent)) = agent {
+        system_prompt = router::AgentRouter::system_prompt(&system_prompt, name, agent);
+    }
+    if ctx.prompt_templates {
+        let vars = crate::agent::template::PromptVars {
+            channel: &msg.channel,
+            user: &msg.sender,
+            model: &route.model,
+            agent: agent.map(|(name, _)| name),
+            tools: ctx.tools_registry.as_ref(),
+        };
+        system_prompt = crate::agent::template::render_for_turn(
+            &system_prompt,
+            &vars,
+            ctx.memory.as_ref(),
+            &msg.content,
+            scope.session_id(),
+        )
+        .await;
+    }
+    if let Some(group_prompt) = group_prompt {
+        system_prompt = format!("{}\n\n{group_prompt}", system_prompt.trim_end());
+    }
+    let system_prompt = sender_pins(ctx.as_ref(), &history_key).apply(&system_prompt);
     let mut history = vec![ChatMessage::system(system_prompt)];
     history.extend(prior_turns);
+    crate::agent::context::trim_to_token_budget(&mut history, ctx.max_context_tokens);
+    // Replies screened by guardrails are only shown once filtered.
     let use_streaming = target_channel
         .as_ref()
-        .is_some_and(|ch| ch.supports_draft_updates());
+        .is_some_and(|ch| ch.supports_draft_updates())
+        && !ctx
+            .guardrails
+            .as_deref()
+            .is_some_and(|guardrails| guardrails.screens_output(&msg.channel));
 
     tracing::debug!(
         channel = %msg.channel,
@@ -1709,15 +2480,9 @@ async fn process_channel_message(
         }
     }
 
-    let typing_cancellation = target_channel.as_ref().map(|_| CancellationToken::new());
-    let typing_task = match (target_channel.as_ref(), typing_cancellation.as_ref()) {
-        (Some(channel), Some(token)) => Some(spawn_scoped_typing_task(
-            Arc::clone(channel),
-            msg.reply_target.clone(),
-            token.clone(),
-        )),
-        _ => None,
-    };
+    let presence = target_channel
+        .as_ref()
+        .map(|channel| Arc::clone(channel).begin_presence(&msg, ctx.read_receipts));
 
     // Record history length before tool loop so we can extract tool context after.
     let history_len_before_tools = history.len();
@@ -1727,34 +2492,94 @@ async fn process_channel_message(
         Cancelled,
     }
 
+    let approval_scope = interruption_scope_key(&msg);
+    let approval_manager = match (&ctx.approvals, target_channel.as_ref()) {
+        (Some(approvals), Some(channel)) => Some(approvals.manager(
+            Arc::clone(channel),
+            &msg.reply_target,
+            msg.thread_ts.clone(),
+            &msg.sender,
+            &approval_scope,
+        )),
+        _ => None,
+    };
+
+    let mut excluded_tools = if msg.channel == "cli" {
+        Vec::new()
+    } else {
+        runtime_defaults.non_cli_excluded_tools.clone()
+    };
+    let mut temperature = runtime_defaults.temperature;
+    if let Some((_, agent)) = agent {
+        excluded_tools.extend(router::AgentRouter::excluded_tools(
+            agent,
+            ctx.tools_registry.iter().map(|tool| tool.name()),
+        ));
+        temperature = agent.temperature.unwrap_or(temperature);
+    }
+    if let Some(disabled) = ctx
+        .disabled_tools
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&history_key)
+    {
+        excluded_tools.extend(disabled.iter().cloned());
+    }
+    excluded_tools.extend(crate::tools::toggles::disabled());
+    excluded_tools.extend(roles::excluded_tools(
+        sender_role,
+        ctx.tools_registry.iter(),
+    ));
+
+    // Heuristic routing yields to an agent's model and to `/model` overrides.
+    let heuristic_router = ctx.model_router.clone().filter(|_| {
+        agent.is_none()
+            && !ctx
+                .route_overrides
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .contains_key(&history_key)
+    });
+
     let timeout_budget_secs =
         channel_message_timeout_budget_secs(ctx.message_timeout_secs, ctx.max_tool_iterations);
     let llm_result = tokio::select! {
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
-            run_tool_call_loop(
+            crate::agent::scope::TurnScope {
+                role: sender_role,
+                model_router: heuristic_router,
+                recorder: ctx
+                    .replay
+                    .then(|| crate::agent::replay::start(&ctx.workspace_dir, &history_key))
+                    .flatten(),
+                debug_dump: sender_debug_dump(ctx.as_ref(), &history_key),
+                dry_run: sender_dry_run(ctx.as_ref(), &history_key),
+                undo: Some(sender_undo_journal(ctx.as_ref(), &history_key)),
+                budget: ctx.budgets.as_ref().map(|budgets| budgets.get(&history_key)),
+                ..crate::agent::scope::TurnScope::new(history_key.clone(), &msg.content)
+            }
+            .run(run_tool_call_loop(
                 active_provider.as_ref(),
                 &mut history,
                 ctx.tools_registry.as_ref(),
                 ctx.observer.as_ref(),
                 route.provider.as_str(),
                 route.model.as_str(),
-                runtime_defaults.temperature,
+                temperature,
                 true,
-                None,
+                approval_manager.as_ref(),
                 msg.channel.as_str(),
                 &ctx.multimodal,
                 ctx.max_tool_iterations,
                 Some(cancellation_token.clone()),
                 delta_tx,
                 ctx.hooks.as_deref(),
-                if msg.channel == "cli" {
-                    &[]
-                } else {
-                    ctx.non_cli_excluded_tools.as_ref()
-                },
-            ),
+                excluded_tools.as_slice(),
+                Some(&ctx.tool_output),
+                conversation.as_ref(),
+            )),
         ) => LlmExecutionResult::Completed(result),
     };
 
@@ -1762,11 +2587,13 @@ async fn process_channel_message(
         let _ = handle.await;
     }
 
-    if let Some(token) = typing_cancellation.as_ref() {
-        token.cancel();
+    if let (Some(approvals), Some(manager)) = (&ctx.approvals, approval_manager.as_ref()) {
+        approvals.pending().cancel(&approval_scope);
+        approvals.save_session(&approval_scope, manager);
     }
-    if let Some(handle) = typing_task {
-        log_worker_join_result(handle.await);
+
+    if let Some(presence) = presence {
+        log_worker_join_result(presence.end().await);
     }
 
     let reaction_done_emoji = match &llm_result {
@@ -1794,6 +2621,12 @@ async fn process_channel_message(
                     "elapsed_ms": started_at.elapsed().as_millis(),
                 }),
             );
+            record_interrupted_turn(
+                ctx.as_ref(),
+                &history_key,
+                &history,
+                history_len_before_tools,
+            );
             if let (Some(channel), Some(draft_id)) =
                 (target_channel.as_ref(), draft_message_id.as_deref())
             {
@@ -1870,6 +2703,30 @@ async fn process_channel_message(
             } else {
                 sanitized_response
             };
+            let delivered_response = match ctx.guardrails.as_deref() {
+                Some(guardrails) => match guardrails
+                    .check(
+                        active_provider.as_ref(),
+                        crate::config::GuardrailDirection::Output,
+                        &msg.channel,
+                        &delivered_response,
+                    )
+                    .await
+                {
+                    GuardrailVerdict::Allow => delivered_response,
+                    GuardrailVerdict::Rewrite(rewritten) => rewritten,
+                    GuardrailVerdict::Block { rule, message } => {
+                        tracing::info!(
+                            channel = %msg.channel,
+                            sender = %msg.sender,
+                            %rule,
+                            "outgoing reply blocked by guardrail"
+                        );
+                        message
+                    }
+                },
+                None => delivered_response,
+            };
             runtime_trace::record_event(
                 "channel_message_outbound",
                 Some(msg.channel.as_str()),
@@ -1884,6 +2741,10 @@ async fn process_channel_message(
                     "response": scrub_credentials(&delivered_response),
                 }),
             );
+            ctx.observer.record_event(&ObserverEvent::ChannelMessage {
+                channel: msg.channel.clone(),
+                direction: "outbound".into(),
+            });
 
             // Extract condensed tool-use context from the history messages
             // added during run_tool_call_loop, so the LLM retains awareness
@@ -1900,9 +2761,60 @@ async fn process_channel_message(
                 &history_key,
                 ChatMessage::assistant(&history_response),
             );
-            println!(
-                "  🤖 Reply ({}ms): {}",
-                started_at.elapsed().as_millis(),
+            if ctx.fact_extraction {
+                let provider = Arc::clone(&active_provider);
+                let memory = Arc::clone(&ctx.memory);
+                let model = ctx
+                    .fact_extraction_model
+                    .clone()
+                    .unwrap_or_else(|| route.model.clone());
+                let scope = scope.clone();
+                let user_message = msg.content.clone();
+                let reply = delivered_response.clone();
+                let session = scope.session_id().map(str::to_string);
+                let speaker = (shared_chat || msg.sender_name.is_some())
+                    .then(|| participants::display_name(&msg).to_string());
+                // Task-locals do not follow `tokio::spawn`; carry the scope over.
+                tokio::spawn(memory::scope::run_in_scope(scope, async move {
+                    crate::memory::facts::extract_and_remember(
+                        provider.as_ref(),
+                        &model,
+                        memory.as_ref(),
+                        &user_message,
+                        &reply,
+                        speaker.as_deref(),
+                        session.as_deref(),
+                    )
+                    .await;
+                }));
+            }
+            if let Some(store) = ctx
+                .conversation_store
+                .as_ref()
+                .filter(|_| ctx.conversations.auto_title)
+            {
+                let provider = Arc::clone(&active_provider);
+                let store = Arc::clone(store);
+                let model = ctx
+                    .conversations
+                    .title_model
+                    .clone()
+                    .unwrap_or_else(|| route.model.clone());
+                let session_id = history_key.clone();
+                tokio::spawn(async move {
+                    crate::conversations::title::maybe_title(
+                        &store,
+                        &session_id,
+                        provider.as_ref(),
+                        &model,
+                    )
+                    .await;
+                });
+            }
+            tracing::info!(
+                channel = %msg.channel,
+                elapsed_ms = started_at.elapsed().as_millis(),
+                "Reply: {}",
                 truncate_with_ellipsis(&delivered_response, 80)
             );
             if let Some(channel) = target_channel.as_ref() {
@@ -1912,21 +2824,23 @@ async fn process_channel_message(
                         .await
                     {
                         tracing::warn!("Failed to finalize draft: {e}; sending as new message");
-                        let _ = channel
-                            .send(
-                                &SendMessage::new(&delivered_response, &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
-                            )
-                            .await;
+                        let _ = outbox::send_or_queue(
+                            ctx.outbox.as_deref(),
+                            channel.as_ref(),
+                            &SendMessage::new(&delivered_response, &msg.reply_target)
+                                .in_thread(msg.thread_ts.clone()),
+                        )
+                        .await;
                     }
-                } else if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await
+                } else if let Err(e) = outbox::send_or_queue(
+                    ctx.outbox.as_deref(),
+                    channel.as_ref(),
+                    &SendMessage::new(delivered_response, &msg.reply_target)
+                        .in_thread(msg.thread_ts.clone()),
+                )
+                .await
                 {
-                    eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                    tracing::error!(channel = channel.name(), "Failed to reply: {e}");
                 }
             }
         }
@@ -1951,6 +2865,12 @@ async fn process_channel_message(
                         "elapsed_ms": started_at.elapsed().as_millis(),
                     }),
                 );
+                record_interrupted_turn(
+                    ctx.as_ref(),
+                    &history_key,
+                    &history,
+                    history_len_before_tools,
+                );
                 if let (Some(channel), Some(draft_id)) =
                     (target_channel.as_ref(), draft_message_id.as_deref())
                 {
@@ -1965,10 +2885,11 @@ async fn process_channel_message(
                 } else {
                     "⚠️ Context window exceeded for this conversation. Please resend your last message."
                 };
-                eprintln!(
-                    "  ⚠️ Context window exceeded after {}ms; sender history compacted={}",
-                    started_at.elapsed().as_millis(),
-                    compacted
+                tracing::warn!(
+                    channel = %msg.channel,
+                    elapsed_ms = started_at.elapsed().as_millis(),
+                    compacted,
+                    "Context window exceeded"
                 );
                 runtime_trace::record_event(
                     "channel_message_error",
@@ -1984,6 +2905,10 @@ async fn process_channel_message(
                         "history_compacted": compacted,
                     }),
                 );
+                ctx.observer.record_event(&ObserverEvent::Error {
+                    component: "channel".into(),
+                    message: "context window exceeded".into(),
+                });
                 if let Some(channel) = target_channel.as_ref() {
                     if let Some(ref draft_id) = draft_message_id {
                         let _ = channel
@@ -1999,9 +2924,12 @@ async fn process_channel_message(
                     }
                 }
             } else {
-                eprintln!(
-                    "  ❌ LLM error after {}ms: {e}",
-                    started_at.elapsed().as_millis()
+                let code = crate::error::classify(&e).map(crate::error::ErrorKind::code);
+                tracing::error!(
+                    channel = %msg.channel,
+                    elapsed_ms = started_at.elapsed().as_millis(),
+                    code,
+                    "LLM error: {e}"
                 );
                 let safe_error = providers::sanitize_api_error(&e.to_string());
                 runtime_trace::record_event(
@@ -2017,11 +2945,15 @@ async fn process_channel_message(
                         "elapsed_ms": started_at.elapsed().as_millis(),
                     }),
                 );
+                ctx.observer.record_event(&ObserverEvent::Error {
+                    component: "channel".into(),
+                    message: safe_error.clone(),
+                });
                 let should_rollback_user_turn = e
                     .downcast_ref::<providers::ProviderCapabilityError>()
                     .is_some_and(|capability| capability.capability.eq_ignore_ascii_case("vision"));
                 let rolled_back = should_rollback_user_turn
-                    && rollback_orphan_user_turn(ctx.as_ref(), &history_key, &msg.content);
+                    && rollback_orphan_user_turn(ctx.as_ref(), &history_key, &turn_content);
 
                 if !rolled_back {
                     // Close the orphan user turn so subsequent messages don't
@@ -2032,15 +2964,20 @@ async fn process_channel_message(
                         ChatMessage::assistant("[Task failed — not continuing this request]"),
                     );
                 }
+                let error_text = if crate::cost::budget::is_budget_exceeded(&e) {
+                    format!("⛔ {e}")
+                } else {
+                    format!("⚠️ {}", crate::error::user_message(&e))
+                };
                 if let Some(channel) = target_channel.as_ref() {
                     if let Some(ref draft_id) = draft_message_id {
                         let _ = channel
-                            .finalize_draft(&msg.reply_target, draft_id, &format!("⚠️ Error: {e}"))
+                            .finalize_draft(&msg.reply_target, draft_id, &error_text)
                             .await;
                     } else {
                         let _ = channel
                             .send(
-                                &SendMessage::new(format!("⚠️ Error: {e}"), &msg.reply_target)
+                                &SendMessage::new(error_text, &msg.reply_target)
                                     .in_thread(msg.thread_ts.clone()),
                             )
                             .await;
@@ -2066,10 +3003,14 @@ async fn process_channel_message(
                     "elapsed_ms": started_at.elapsed().as_millis(),
                 }),
             );
-            eprintln!(
-                "  ❌ {} (elapsed: {}ms)",
-                timeout_msg,
-                started_at.elapsed().as_millis()
+            ctx.observer.record_event(&ObserverEvent::Error {
+                component: "channel".into(),
+                message: timeout_msg.clone(),
+            });
+            tracing::error!(
+                channel = %msg.channel,
+                elapsed_ms = started_at.elapsed().as_millis(),
+                "{timeout_msg}"
             );
             // Close the orphan user turn so subsequent messages don't
             // inherit this timed-out request as unfinished context.
@@ -2108,10 +3049,18 @@ async fn process_channel_message(
     }
 }
 
+/// Dispatch bus messages to workers until the bus closes or `shutdown` is
+/// cancelled. Either way, in-flight messages are allowed to finish.
+/// Inbound messages waiting for a free worker (`zeroclaw status`).
+pub(crate) const CHANNEL_QUEUE_DEPTH_GAUGE: &str = "channel_queue_depth";
+/// Messages being answered right now.
+pub(crate) const CHANNEL_IN_FLIGHT_GAUGE: &str = "channel_in_flight";
+
 async fn run_message_dispatch_loop(
     mut rx: tokio::sync::mpsc::Receiver<traits::ChannelMessage>,
     ctx: Arc<ChannelRuntimeContext>,
     max_in_flight_messages: usize,
+    shutdown: CancellationToken,
 ) {
     let semaphore = Arc::new(tokio::sync::Semaphore::new(max_in_flight_messages));
     let mut workers = tokio::task::JoinSet::new();
@@ -2120,20 +3069,78 @@ async fn run_message_dispatch_loop(
         InFlightSenderTaskState,
     >::new()));
     let task_sequence = Arc::new(AtomicU64::new(1));
+    let in_flight_count = Arc::new(AtomicU64::new(0));
+
+    loop {
+        let msg = tokio::select! {
+            msg = rx.recv() => match msg {
+                Some(msg) => msg,
+                None => break,
+            },
+            () = shutdown.cancelled() => {
+                tracing::info!(
+                    in_flight = workers.len(),
+                    "Channel dispatch stopping; draining in-flight messages"
+                );
+                break;
+            }
+        };
+        // A reply to a pending approval prompt resumes the waiting turn
+        // instead of starting a new one.
+        if let Some(approvals) = &ctx.approvals {
+            if approvals
+                .pending()
+                .resolve(&interruption_scope_key(&msg), &msg.content)
+            {
+                continue;
+            }
+        }
+
+        let interrupt_enabled = ctx.interrupt_all_channels
+            || (ctx.interrupt_on_new_message && msg.channel == "telegram");
+        let turn_permit = match ctx.rate_limiter.as_ref().filter(|_| msg.channel != "cli") {
+            Some(limiter) => match limiter.admit(&msg, interrupt_enabled) {
+                Ok(permit) => Some(permit),
+                Err(rejection) => {
+                    tracing::info!(
+                        channel = %msg.channel,
+                        sender = %msg.sender,
+                        ?rejection,
+                        "Inbound message dropped by rate limit"
+                    );
+                    if limiter.should_notify(&msg) {
+                        if let Some(channel) = ctx.channels_by_name.get(&msg.channel).cloned() {
+                            workers.spawn(async move {
+                                let reply =
+                                    SendMessage::new(rejection.message(), &msg.reply_target)
+                                        .in_thread(msg.thread_ts.clone());
+                                let _ = channel.send(&reply).await;
+                            });
+                        }
+                    }
+                    continue;
+                }
+            },
+            None => None,
+        };
 
-    while let Some(msg) = rx.recv().await {
         let permit = match Arc::clone(&semaphore).acquire_owned().await {
             Ok(permit) => permit,
             Err(_) => break,
         };
+        crate::health::set_gauge(CHANNEL_QUEUE_DEPTH_GAUGE, rx.len() as u64);
+        crate::health::set_gauge(
+            CHANNEL_IN_FLIGHT_GAUGE,
+            in_flight_count.fetch_add(1, Ordering::Relaxed) + 1,
+        );
 
         let worker_ctx = Arc::clone(&ctx);
         let in_flight = Arc::clone(&in_flight_by_sender);
         let task_sequence = Arc::clone(&task_sequence);
+        let in_flight_count = Arc::clone(&in_flight_count);
         workers.spawn(async move {
             let _permit = permit;
-            let interrupt_enabled =
-                worker_ctx.interrupt_on_new_message && msg.channel == "telegram";
+            let _turn_permit = turn_permit;
             let sender_scope_key = interruption_scope_key(&msg);
             let cancellation_token = CancellationToken::new();
             let completion = Arc::new(InFlightTaskCompletion::new());
@@ -2176,6 +3183,12 @@ async fn run_message_dispatch_loop(
             }
 
             completion.mark_done();
+            crate::health::set_gauge(
+                CHANNEL_IN_FLIGHT_GAUGE,
+                in_flight_count
+                    .fetch_sub(1, Ordering::Relaxed)
+                    .saturating_sub(1),
+            );
         });
 
         while let Some(result) = workers.try_join_next() {
@@ -2361,9 +3374,7 @@ pub fn build_system_prompt_with_mode(
                 }
                 Err(e) => {
                     // Log error but don't fail - fall back to OpenClaw
-                    eprintln!(
-                        "Warning: Failed to load AIEOS identity: {e}. Using OpenClaw format."
-                    );
+                    tracing::warn!("Failed to load AIEOS identity: {e}. Using OpenClaw format.");
                     let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
                     load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
                 }
@@ -2380,13 +3391,7 @@ pub fn build_system_prompt_with_mode(
     }
 
     // ── 6. Date & Time ──────────────────────────────────────────
-    let now = chrono::Local::now();
-    let _ = writeln!(
-        prompt,
-        "## Current Date & Time\n\n{} ({})\n",
-        now.format("%Y-%m-%d %H:%M:%S"),
-        now.format("%Z")
-    );
+    let _ = writeln!(prompt, "{}", crate::time::prompt_section());
 
     // ── 7. Runtime ──────────────────────────────────────────────
     let host =
@@ -2424,6 +3429,7 @@ fn inject_workspace_file(
     let path = workspace_dir.join(filename);
     match std::fs::read_to_string(&path) {
         Ok(content) => {
+            let content = crate::security::redaction::redact(&content);
             let trimmed = content.trim();
             if trimmed.is_empty() {
                 return;
@@ -2655,11 +3661,42 @@ pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Con
         crate::ChannelCommands::BindTelegram { identity } => {
             bind_telegram_identity(config, &identity).await
         }
+        crate::ChannelCommands::Outbox { channel, clear } => {
+            let outbox =
+                outbox::Outbox::open(&config.workspace_dir, &config.channels_config.outbox)?;
+            if clear {
+                let dropped = outbox.clear(channel.as_deref())?;
+                println!("Dropped {dropped} queued message(s).");
+                return Ok(());
+            }
+            let queued = outbox.pending(channel.as_deref())?;
+            if queued.is_empty() {
+                println!("No queued messages.");
+                return Ok(());
+            }
+            println!("Queued messages ({}):", queued.len());
+            for item in queued {
+                println!(
+                    "  #{} {} → {} (queued {}, {} failed attempt(s)): {}",
+                    item.id,
+                    item.channel,
+                    item.message.recipient,
+                    item.queued_at,
+                    item.attempts,
+                    truncate_with_ellipsis(&item.message.content, 60)
+                );
+                if let Some(error) = item.last_error {
+                    println!("      last error: {error}");
+                }
+            }
+            Ok(())
+        }
     }
 }
 
+/// Outcome of a channel health check.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ChannelHealthState {
+pub enum ChannelHealthState {
     Healthy,
     Unhealthy,
     Timeout,
@@ -2697,7 +3734,8 @@ fn collect_configured_channels(
                 )
                 .with_streaming(tg.stream_mode, tg.draft_update_interval_ms)
                 .with_transcription(config.transcription.clone())
-                .with_workspace_dir(config.workspace_dir.clone()),
+                .with_workspace_dir(config.workspace_dir.clone())
+                .with_workspace_quota_mb(config.autonomy.workspace_quota_mb),
             ),
         });
     }
@@ -2705,24 +3743,30 @@ fn collect_configured_channels(
     if let Some(ref dc) = config.channels_config.discord {
         channels.push(ConfiguredChannel {
             display_name: "Discord",
-            channel: Arc::new(DiscordChannel::new(
-                dc.bot_token.clone(),
-                dc.guild_id.clone(),
-                dc.allowed_users.clone(),
-                dc.listen_to_bots,
-                dc.mention_only,
-            )),
+            channel: Arc::new(
+                DiscordChannel::new(
+                    dc.bot_token.clone(),
+                    dc.guild_id.clone(),
+                    dc.allowed_users.clone(),
+                    dc.listen_to_bots,
+                    dc.mention_only,
+                )
+                .with_streaming(dc.stream_mode, dc.draft_update_interval_ms),
+            ),
         });
     }
 
     if let Some(ref sl) = config.channels_config.slack {
         channels.push(ConfiguredChannel {
             display_name: "Slack",
-            channel: Arc::new(SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.channel_id.clone(),
-                sl.allowed_users.clone(),
-            )),
+            channel: Arc::new(
+                SlackChannel::new(
+                    sl.bot_token.clone(),
+                    sl.channel_id.clone(),
+                    sl.allowed_users.clone(),
+                )
+                .with_streaming(sl.stream_mode, sl.draft_update_interval_ms),
+            ),
         });
     }
 
@@ -2956,6 +4000,33 @@ fn collect_configured_channels(
         });
     }
 
+    if let Some(ref md) = config.channels_config.mastodon {
+        channels.push(ConfiguredChannel {
+            display_name: "Mastodon",
+            channel: Arc::new(MastodonChannel::new(
+                md.instance_url.clone(),
+                md.access_token.clone(),
+                md.allowed_users.clone(),
+                md.max_posts_per_hour,
+                md.max_status_chars,
+            )),
+        });
+    }
+
+    if let Some(ref gh) = config.channels_config.github {
+        channels.push(ConfiguredChannel {
+            display_name: "GitHub",
+            channel: Arc::new(GitHubChannel::from_config(gh)),
+        });
+    }
+
+    if let Some(ref xm) = config.channels_config.xmpp {
+        channels.push(ConfiguredChannel {
+            display_name: "XMPP",
+            channel: Arc::new(XmppChannel::from_config(xm)),
+        });
+    }
+
     if let Some(ref ct) = config.channels_config.clawdtalk {
         channels.push(ConfiguredChannel {
             display_name: "ClawdTalk",
@@ -2966,9 +4037,11 @@ fn collect_configured_channels(
     channels
 }
 
-/// Run health checks for configured channels.
-pub async fn doctor_channels(config: Config) -> Result<()> {
-    let mut channels = collect_configured_channels(&config, "health check");
+/// Run the health check of every configured real-time channel (10s timeout each).
+pub async fn check_channel_health(
+    config: &Config,
+) -> Result<Vec<(&'static str, ChannelHealthState)>> {
+    let mut channels = collect_configured_channels(config, "health check");
 
     if let Some(ref ns) = config.channels_config.nostr {
         channels.push(ConfiguredChannel {
@@ -2979,7 +4052,19 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
         });
     }
 
-    if channels.is_empty() {
+    let mut states = Vec::with_capacity(channels.len());
+    for configured in channels {
+        let result =
+            tokio::time::timeout(Duration::from_secs(10), configured.channel.health_check()).await;
+        states.push((configured.display_name, classify_health_result(&result)));
+    }
+    Ok(states)
+}
+
+/// Run health checks for configured channels.
+pub async fn doctor_channels(config: Config) -> Result<()> {
+    let states = check_channel_health(&config).await?;
+    if states.is_empty() {
         println!("No real-time channels configured. Run `zeroclaw onboard` first.");
         return Ok(());
     }
@@ -2991,26 +4076,19 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     let mut unhealthy = 0_u32;
     let mut timeout = 0_u32;
 
-    for configured in channels {
-        let result =
-            tokio::time::timeout(Duration::from_secs(10), configured.channel.health_check()).await;
-        let state = classify_health_result(&result);
-
+    for (display_name, state) in states {
         match state {
             ChannelHealthState::Healthy => {
                 healthy += 1;
-                println!("  ✅ {:<9} healthy", configured.display_name);
+                println!("  ✅ {display_name:<9} healthy");
             }
             ChannelHealthState::Unhealthy => {
                 unhealthy += 1;
-                println!(
-                    "  ❌ {:<9} unhealthy (auth/config/network)",
-                    configured.display_name
-                );
+                println!("  ❌ {display_name:<9} unhealthy (auth/config/network)");
             }
             ChannelHealthState::Timeout => {
                 timeout += 1;
-                println!("  ⏱️  {:<9} timed out (>10s)", configured.display_name);
+                println!("  ⏱️  {display_name:<9} timed out (>10s)");
             }
         }
     }
@@ -3024,9 +4102,31 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     Ok(())
 }
 
-/// Start all configured channels and route messages to the agent
-#[allow(clippy::too_many_lines)]
+/// Start all configured channels and run until Ctrl+C.
 pub async fn start_channels(config: Config) -> Result<()> {
+    let shutdown = CancellationToken::new();
+    let signal_token = shutdown.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            signal_token.cancel();
+        }
+    });
+    start_channels_with_shutdown(config, shutdown).await
+}
+
+/// Start all configured channels concurrently on one message bus.
+///
+/// Each channel runs under its own supervised listener and keeps its own
+/// conversation histories (keyed by channel, thread and sender). Memory is
+/// scoped as configured in `[memory.scopes]` (per channel by default).
+/// Cancelling `shutdown` stops every listener, stops accepting new messages
+/// and returns once in-flight messages have finished; the caller decides
+/// whether to restart.
+#[allow(clippy::too_many_lines)]
+pub async fn start_channels_with_shutdown(
+    config: Config,
+    shutdown: CancellationToken,
+) -> Result<()> {
     let provider_name = resolved_default_provider(&config);
     let provider_runtime_options = providers::ProviderRuntimeOptions {
         auth_profile_override: None,
@@ -3076,12 +4176,16 @@ pub async fn start_channels(config: Config) -> Result<()> {
     ));
     let model = resolved_default_model(&config);
     let temperature = config.default_temperature;
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
-        &config.memory,
-        Some(&config.storage.provider.config),
-        &config.workspace_dir,
-        config.api_key.as_deref(),
-    )?);
+    let memory_scopes = Arc::new(memory::scope::MemoryScopePolicy::from_config(&config));
+    let mem: Arc<dyn Memory> = Arc::new(memory::scope::ScopedMemory::new(
+        Arc::from(memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?),
+        memory_scopes.read_global(),
+    ));
     let (composio_key, composio_entity_id) = if config.composio.enabled {
         (
             config.composio.api_key.as_deref(),
@@ -3092,7 +4196,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     };
     // Build system prompt from workspace identity files + skills
     let workspace = config.workspace_dir.clone();
-    let tools_registry = Arc::new(tools::all_tools_with_runtime(
+    let mut tools_registry = tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
         runtime,
@@ -3106,7 +4210,14 @@ pub async fn start_channels(config: Config) -> Result<()> {
         &config.agents,
         config.api_key.as_deref(),
         &config,
-    ));
+    );
+    let mcp_tools = crate::mcp::create_mcp_tools(&config.mcp, &security, &workspace).await;
+    let mcp_tool_descs: Vec<(String, String)> = mcp_tools
+        .iter()
+        .map(|tool| (tool.name().to_string(), tool.description().to_string()))
+        .collect();
+    tools_registry.extend(mcp_tools);
+    let tools_registry = Arc::new(tools_registry);
 
     let skills = crate::skills::load_skills_with_config(&workspace, &config);
 
@@ -3124,6 +4235,18 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "apply_patch",
+            "Apply a unified diff to one or more files. Use when: making multi-hunk or multi-file code changes. Don't use when: a single exact replacement (file_edit) suffices.",
+        ),
+        (
+            "list_dir",
+            "List a directory. Use when: orienting in an unfamiliar tree. Don't use when: a glob_search is more targeted.",
+        ),
+        (
+            "git_operations",
+            "Structured git (status, diff, log, show, branch, add, commit). Use when: reviewing changes or preparing commits. Don't use when: the task needs remote operations such as push.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -3136,6 +4259,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "read_document",
+            "Extract text from PDF/DOCX/EPUB files by page range, in context-sized chunks; optionally index into memory. Use when: reading reports, specs, books. Don't use when: the file is plain text (use file_read).",
+        ),
     ];
 
     if config.browser.enabled {
@@ -3144,6 +4271,36 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "Open approved HTTPS URLs in system browser (allowlist-only, no scraping)",
         ));
     }
+    if config.code_interpreter.enabled {
+        tool_descs.push((
+            "run_code",
+            "Run a short Python or JavaScript program in a sandboxed subprocess. Use when: calculations, data analysis, file conversions. Don't use when: a shell one-liner or file tool is enough.",
+        ));
+    }
+    if config.sql.enabled {
+        tool_descs.push((
+            "sql",
+            "Query a configured database (SQLite/PostgreSQL) and get a Markdown table. Use when: answering questions from application data. Don't use when: the data is in a plain file.",
+        ));
+    }
+    if config.multimodal.ocr.enabled {
+        tool_descs.push((
+            "ocr",
+            "Extract text from an image file (screenshot, scan, photo). Use when: you need the words in an image and cannot view it directly.",
+        ));
+    }
+    if config.image_generation.enabled {
+        tool_descs.push((
+            "generate_image",
+            "Generate an image from a text prompt and save it in the workspace; reply with the returned [IMAGE:<path>] marker to send it. Use when: the user asks for a picture, illustration or diagram-like artwork.",
+        ));
+    }
+    if config.calendar.enabled {
+        tool_descs.push((
+            "calendar",
+            "List, create, update or delete events in the user's calendar. Writes return a preview first: confirm with the user, then repeat with approved=true. Use when: the user asks what is scheduled or to schedule/move/cancel a meeting.",
+        ));
+    }
     if config.composio.enabled {
         tool_descs.push((
             "composio",
@@ -3165,6 +4322,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
         ));
     }
 
+    for (name, description) in &mcp_tool_descs {
+        tool_descs.push((name.as_str(), description.as_str()));
+    }
+
     // Filter out tools excluded for non-CLI channels so the system prompt
     // does not advertise them for channel-driven runs.
     let excluded = &config.autonomy.non_cli_excluded_tools;
@@ -3191,6 +4352,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
     if !native_tools {
         system_prompt.push_str(&build_tool_instructions(tools_registry.as_ref()));
     }
+    if let Some(section) = crate::i18n::reply_language_prompt(&config.locale) {
+        system_prompt.push_str(&section);
+    }
 
     if !skills.is_empty() {
         println!(
@@ -3227,9 +4391,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
         Some(&config.storage.provider.config),
     );
     println!(
-        "  🧠 Memory:   {} (auto-save: {})",
+        "  🧠 Memory:   {} (auto-save: {}, scope: {})",
         effective_backend,
-        if config.memory.auto_save { "on" } else { "off" }
+        if config.memory.auto_save { "on" } else { "off" },
+        memory_scopes.describe()
     );
     println!(
         "  📡 Channels: {}",
@@ -3265,6 +4430,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
             tx.clone(),
             initial_backoff_secs,
             max_backoff_secs,
+            shutdown.clone(),
         ));
     }
     drop(tx); // Drop our copy so rx closes when all channels stop
@@ -3275,7 +4441,19 @@ pub async fn start_channels(config: Config) -> Result<()> {
             .map(|ch| (ch.name().to_string(), Arc::clone(ch)))
             .collect::<HashMap<_, _>>(),
     );
-    let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
+    let reply_outbox = outbox::Outbox::from_config(&config).map(Arc::new);
+    let outbox_delivery = reply_outbox.as_ref().map(|queue| {
+        outbox::spawn_delivery(
+            Arc::clone(queue),
+            Arc::clone(&channels_by_name),
+            Duration::from_secs(config.channels_config.outbox.retry_interval_secs),
+            shutdown.clone(),
+        )
+    });
+    let max_in_flight_messages = match config.channels_config.rate_limit.max_concurrent_turns {
+        0 => compute_max_in_flight_messages(channels.len()),
+        cap => cap,
+    };
 
     println!("  🚦 In-flight message limit: {max_in_flight_messages}");
 
@@ -3289,6 +4467,17 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .as_ref()
         .is_some_and(|tg| tg.interrupt_on_new_message);
 
+    let model_router = {
+        let config = config.clone();
+        let options = provider_runtime_options.clone();
+        tokio::task::spawn_blocking(move || {
+            crate::agent::model_router::ModelRouter::from_config(&config, &options)
+        })
+        .await
+        .context("failed to join model router initialization task")?
+        .map(Arc::new)
+    };
+
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
         provider: Arc::clone(&provider),
@@ -3300,11 +4489,19 @@ pub async fn start_channels(config: Config) -> Result<()> {
         model: Arc::new(model.clone()),
         temperature,
         auto_save_memory: config.memory.auto_save,
+        memory_scopes,
         max_tool_iterations: config.agent.max_tool_iterations,
         min_relevance_score: config.memory.min_relevance_score,
+        fact_extraction: config.memory.fact_extraction,
+        fact_extraction_model: config.memory.fact_extraction_model.clone(),
         conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+        conversation_store: crate::conversations::open_from_config(&config),
+        conversations: Arc::new(config.conversations.clone()),
         provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
         route_overrides: Arc::new(Mutex::new(HashMap::new())),
+        disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+        pins: Arc::new(Mutex::new(HashMap::new())),
+        max_context_tokens: config.agent.max_context_tokens,
         api_key: config.api_key.clone(),
         api_url: config.api_url.clone(),
         reliability: Arc::new(config.reliability.clone()),
@@ -3312,7 +4509,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
         workspace_dir: Arc::new(config.workspace_dir.clone()),
         message_timeout_secs,
         interrupt_on_new_message,
+        interrupt_all_channels: config.channels_config.interrupt_on_new_message,
         multimodal: config.multimodal.clone(),
+        tool_output: config.agent.tool_output.clone(),
         hooks: if config.hooks.enabled {
             let mut runner = crate::hooks::HookRunner::new();
             if config.hooks.builtin.command_logger {
@@ -3323,15 +4522,58 @@ pub async fn start_channels(config: Config) -> Result<()> {
             None
         },
         non_cli_excluded_tools: Arc::new(config.autonomy.non_cli_excluded_tools.clone()),
+        approvals: Some(Arc::new(
+            crate::approval::ChannelApprovals::from_root_config(&config),
+        )),
+        agent_router: Arc::new(router::AgentRouter::from_config(&config)),
+        budgets: crate::cost::budget::SessionBudgets::from_config(&config).map(Arc::new),
+        prompt_templates: config.agent.prompt_templates,
+        guardrails: crate::security::Guardrails::from_config(&config.guardrails)?.map(Arc::new),
+        replay: config.replay.enabled,
+        debug_prompts: config.agent.debug_prompts,
+        debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+        dry_run: config.agent.dry_run,
+        dry_runs: Arc::new(Mutex::new(HashMap::new())),
+        undo_journals: Arc::new(Mutex::new(HashMap::new())),
+        read_receipts: config.channels_config.read_receipts,
+        outbox: reply_outbox.clone(),
+        model_router,
+        pairing: if config.security.pairing.enabled {
+            Some(Arc::new(pairing::PairingStore::open(
+                &config.workspace_dir,
+                config.security.pairing.code_ttl_secs,
+            )?))
+        } else {
+            None
+        },
+        rate_limiter: rate_limit::RateLimiter::from_config(&config.channels_config.rate_limit),
+    });
+
+    let mut reloads = crate::config::reload::subscribe();
+    let reload_ctx = Arc::clone(&runtime_ctx);
+    let reload_task = tokio::spawn(async move {
+        while reloads.changed().await.is_ok() {
+            let reloaded = reloads.borrow_and_update().clone();
+            if let Some(config) = reloaded {
+                apply_reloaded_config(&reload_ctx, &config).await;
+            }
+        }
     });
 
-    run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
+    run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages, shutdown.clone()).await;
+    reload_task.abort();
+    if let Some(delivery) = outbox_delivery {
+        delivery.abort();
+    }
 
     // Wait for all channel tasks
     for h in handles {
         let _ = h.await;
     }
 
+    if shutdown.is_cancelled() {
+        println!("  🛑 Channels stopped");
+    }
     Ok(())
 }
 
@@ -3375,27 +4617,100 @@ mod tests {
             MIN_CHANNEL_MESSAGE_TIMEOUT_SECS
         );
         assert_eq!(
-            effective_channel_message_timeout_secs(15),
-            MIN_CHANNEL_MESSAGE_TIMEOUT_SECS
+            effective_channel_message_timeout_secs(15),
+            MIN_CHANNEL_MESSAGE_TIMEOUT_SECS
+        );
+        assert_eq!(effective_channel_message_timeout_secs(300), 300);
+    }
+
+    #[test]
+    fn channel_message_timeout_budget_scales_with_tool_iterations() {
+        assert_eq!(channel_message_timeout_budget_secs(300, 1), 300);
+        assert_eq!(channel_message_timeout_budget_secs(300, 2), 600);
+        assert_eq!(channel_message_timeout_budget_secs(300, 3), 900);
+    }
+
+    #[test]
+    fn channel_message_timeout_budget_uses_safe_defaults_and_cap() {
+        // 0 iterations falls back to 1x timeout budget.
+        assert_eq!(channel_message_timeout_budget_secs(300, 0), 300);
+        // Large iteration counts are capped to avoid runaway waits.
+        assert_eq!(
+            channel_message_timeout_budget_secs(300, 10),
+            300 * CHANNEL_MESSAGE_TIMEOUT_SCALE_CAP
+        );
+    }
+
+    #[test]
+    fn budget_command_parses_on_every_channel() {
+        assert_eq!(
+            parse_runtime_command("slack", "/budget", Role::Admin),
+            Some(ChannelRuntimeCommand::ShowBudget)
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/budget@zeroclaw_bot override", Role::Admin),
+            Some(ChannelRuntimeCommand::OverrideBudget)
         );
-        assert_eq!(effective_channel_message_timeout_secs(300), 300);
+        assert_eq!(parse_runtime_command("slack", "/models", Role::Admin), None);
     }
 
     #[test]
-    fn channel_message_timeout_budget_scales_with_tool_iterations() {
-        assert_eq!(channel_message_timeout_budget_secs(300, 1), 300);
-        assert_eq!(channel_message_timeout_budget_secs(300, 2), 600);
-        assert_eq!(channel_message_timeout_budget_secs(300, 3), 900);
+    fn shared_chat_commands_parse_on_every_channel() {
+        assert_eq!(
+            parse_runtime_command("slack", "/tools off browser", Role::Admin),
+            Some(ChannelRuntimeCommand::Tools(
+                crate::agent::commands::ToolsCommand::Disable(vec!["browser".into()])
+            ))
+        );
+        assert_eq!(
+            parse_runtime_command("whatsapp", "/memory search coffee", Role::Admin),
+            Some(ChannelRuntimeCommand::SearchMemory("coffee".into()))
+        );
+        assert_eq!(
+            parse_runtime_command("whatsapp", "/unpin 2", Role::Member),
+            Some(ChannelRuntimeCommand::Unpin(Some(2)))
+        );
+        assert_eq!(
+            parse_runtime_command("discord", "/debug on", Role::Admin),
+            Some(ChannelRuntimeCommand::Debug(Some(true)))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/dryrun on", Role::Member),
+            Some(ChannelRuntimeCommand::DryRun(Some(true)))
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/undo 2", Role::Admin),
+            Some(ChannelRuntimeCommand::Undo(
+                crate::agent::commands::UndoCommand::Turn(2)
+            ))
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/memory", Role::Admin),
+            Some(ChannelRuntimeCommand::Usage(
+                "Usage: /memory search <query>".into()
+            ))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/model gpt-4o", Role::Admin),
+            Some(ChannelRuntimeCommand::SetModel("gpt-4o".into()))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/quit", Role::Admin),
+            None
+        );
     }
 
     #[test]
-    fn channel_message_timeout_budget_uses_safe_defaults_and_cap() {
-        // 0 iterations falls back to 1x timeout budget.
-        assert_eq!(channel_message_timeout_budget_secs(300, 0), 300);
-        // Large iteration counts are capped to avoid runaway waits.
+    fn parse_runtime_command_refuses_commands_above_the_sender_role() {
         assert_eq!(
-            channel_message_timeout_budget_secs(300, 10),
-            300 * CHANNEL_MESSAGE_TIMEOUT_SCALE_CAP
+            parse_runtime_command("slack", "/budget override", Role::Member),
+            Some(ChannelRuntimeCommand::Denied(
+                "⛔ `/budget` requires the admin role.".into()
+            ))
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/help", Role::Guest),
+            Some(ChannelRuntimeCommand::ShowHelp)
         );
     }
 
@@ -3521,21 +4836,47 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(histories)),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -3570,21 +4911,47 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -3599,6 +4966,91 @@ mod tests {
         assert_eq!(turns[0].content, "hello");
     }
 
+    #[test]
+    fn stored_sender_history_is_restored_after_restart() {
+        let tmp = TempDir::new().unwrap();
+        let store = Arc::new(crate::conversations::ConversationStore::open(tmp.path()).unwrap());
+        let sender = "telegram_u9".to_string();
+        let make_ctx = || ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("system".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: Some(Arc::clone(&store)),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            interrupt_on_new_message: false,
+            interrupt_all_channels: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
+            hooks: None,
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
+        };
+
+        let first = make_ctx();
+        append_sender_turn(&first, &sender, ChatMessage::user("hello"));
+        append_sender_turn(&first, &sender, ChatMessage::assistant("hi there"));
+
+        let restarted = make_ctx();
+        hydrate_sender_history(&restarted, &sender);
+        let histories = restarted
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let turns = histories.get(&sender).expect("history should be restored");
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[1].content, "hi there");
+        drop(histories);
+
+        let mut pins = sender_pins(&first, &sender);
+        pins.pin(None, &sender_turns(&first, &sender));
+        save_sender_pins(&first, &sender, pins);
+        clear_sender_history(&first, &sender);
+        let restarted = make_ctx();
+        assert_eq!(
+            sender_pins(&restarted, &sender).iter().collect::<Vec<_>>(),
+            ["hi there"]
+        );
+    }
+
     #[test]
     fn rollback_orphan_user_turn_removes_only_latest_matching_user_turn() {
         let sender = "telegram_u3".to_string();
@@ -3622,21 +5074,47 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(histories)),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -4097,11 +5575,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4109,8 +5595,26 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
         });
 
@@ -4119,6 +5623,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-1".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-42".to_string(),
                 content: "What is the BTC price now?".to_string(),
                 channel: "test-channel".to_string(),
@@ -4156,11 +5661,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4168,8 +5681,26 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
         });
 
@@ -4178,6 +5709,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-telegram-tool-1".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-telegram".to_string(),
                 content: "What is the BTC price now?".to_string(),
                 channel: "telegram".to_string(),
@@ -4229,11 +5761,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4241,9 +5781,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -4251,6 +5809,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-raw-json".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-raw".to_string(),
                 content: "What is the BTC price now?".to_string(),
                 channel: "test-channel".to_string(),
@@ -4288,11 +5847,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4300,9 +5867,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -4310,6 +5895,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-2".to_string(),
                 sender: "bob".to_string(),
+                sender_name: None,
                 reply_target: "chat-84".to_string(),
                 content: "What is the BTC price now?".to_string(),
                 channel: "test-channel".to_string(),
@@ -4356,11 +5942,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4368,9 +5962,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -4378,6 +5990,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-cmd-1".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-1".to_string(),
                 content: "/models openrouter".to_string(),
                 channel: "telegram".to_string(),
@@ -4445,11 +6058,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(route_overrides)),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4457,9 +6078,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -4467,6 +6106,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-routed-1".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-1".to_string(),
                 content: "hello routed provider".to_string(),
                 channel: "telegram".to_string(),
@@ -4489,6 +6129,132 @@ BTC is currently around $65,000 based on latest tool output."#
         );
     }
 
+    #[tokio::test]
+    async fn process_channel_message_routes_mentioned_agent_to_its_model() {
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let default_provider_impl = Arc::new(ModelCaptureProvider::default());
+        let default_provider: Arc<dyn Provider> = default_provider_impl.clone();
+        let agent_provider_impl = Arc::new(ModelCaptureProvider::default());
+        let agent_provider: Arc<dyn Provider> = agent_provider_impl.clone();
+
+        let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+        provider_cache_seed.insert("test-provider".to_string(), Arc::clone(&default_provider));
+        provider_cache_seed.insert("openrouter".to_string(), agent_provider);
+
+        let mut config = Config::default();
+        config.agents.insert(
+            "coder".to_string(),
+            crate::config::DelegateAgentConfig {
+                provider: "openrouter".to_string(),
+                model: "code-model".to_string(),
+                system_prompt: Some("You write Rust.".to_string()),
+                api_key: None,
+                temperature: None,
+                max_depth: 3,
+                agentic: false,
+                allowed_tools: Vec::new(),
+                max_iterations: 10,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: 0,
+            },
+        );
+
+        let conversation_histories = Arc::new(Mutex::new(HashMap::new()));
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&default_provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
+            conversation_histories: Arc::clone(&conversation_histories),
+            conversation_store: None,
+            provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            interrupt_all_channels: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::from_config(&config)),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-agent-1".to_string(),
+                sender: "alice".to_string(),
+                sender_name: None,
+                reply_target: "chat-1".to_string(),
+                content: "@coder fix the build".to_string(),
+                channel: "telegram".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert_eq!(default_provider_impl.call_count.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            agent_provider_impl
+                .models
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_slice(),
+            &["code-model".to_string()]
+        );
+        let histories = conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let turns = histories
+            .get("telegram_alice@coder")
+            .expect("agent conversation is kept under its own key");
+        assert_eq!(turns[0].content, "fix the build");
+        assert!(!histories.contains_key("telegram_alice"));
+    }
+
     #[tokio::test]
     async fn process_channel_message_prefers_cached_default_provider_instance() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -4516,11 +6282,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4528,9 +6302,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -4538,6 +6330,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-default-provider-cache".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-1".to_string(),
                 content: "hello cached default provider".to_string(),
                 channel: "telegram".to_string(),
@@ -4582,6 +6375,7 @@ BTC is currently around $65,000 based on latest tool output."#
                         api_key: None,
                         api_url: None,
                         reliability: crate::config::ReliabilityConfig::default(),
+                        non_cli_excluded_tools: Vec::new(),
                     },
                     last_applied_stamp: None,
                 },
@@ -4599,11 +6393,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("startup-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4614,9 +6416,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -4624,6 +6444,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-runtime-store-model".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-1".to_string(),
                 content: "hello runtime defaults".to_string(),
                 channel: "telegram".to_string(),
@@ -4673,11 +6494,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 12,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4685,9 +6514,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -4695,6 +6542,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-iter-success".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-iter-success".to_string(),
                 content: "Loop until done".to_string(),
                 channel: "test-channel".to_string(),
@@ -4733,11 +6581,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 3,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4745,9 +6601,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -4755,6 +6629,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-iter-fail".to_string(),
                 sender: "bob".to_string(),
+                sender_name: None,
                 reply_target: "chat-iter-fail".to_string(),
                 content: "Loop forever".to_string(),
                 channel: "test-channel".to_string(),
@@ -4904,11 +6779,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4916,15 +6799,34 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
         tx.send(traits::ChannelMessage {
             id: "1".to_string(),
             sender: "alice".to_string(),
+            sender_name: None,
             reply_target: "alice".to_string(),
             content: "hello".to_string(),
             channel: "test-channel".to_string(),
@@ -4936,6 +6838,7 @@ BTC is currently around $65,000 based on latest tool output."#
         tx.send(traits::ChannelMessage {
             id: "2".to_string(),
             sender: "bob".to_string(),
+            sender_name: None,
             reply_target: "bob".to_string(),
             content: "world".to_string(),
             channel: "test-channel".to_string(),
@@ -4947,7 +6850,7 @@ BTC is currently around $65,000 based on latest tool output."#
         drop(tx);
 
         let started = Instant::now();
-        run_message_dispatch_loop(rx, runtime_ctx, 2).await;
+        run_message_dispatch_loop(rx, runtime_ctx, 2, CancellationToken::new()).await;
         let elapsed = started.elapsed();
 
         assert!(
@@ -4984,11 +6887,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -4996,9 +6907,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: true,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5006,6 +6935,7 @@ BTC is currently around $65,000 based on latest tool output."#
             tx.send(traits::ChannelMessage {
                 id: "msg-1".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-1".to_string(),
                 content: "forwarded content".to_string(),
                 channel: "telegram".to_string(),
@@ -5018,6 +6948,7 @@ BTC is currently around $65,000 based on latest tool output."#
             tx.send(traits::ChannelMessage {
                 id: "msg-2".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-1".to_string(),
                 content: "summarize this".to_string(),
                 channel: "telegram".to_string(),
@@ -5028,7 +6959,7 @@ BTC is currently around $65,000 based on latest tool output."#
             .unwrap();
         });
 
-        run_message_dispatch_loop(rx, runtime_ctx, 4).await;
+        run_message_dispatch_loop(rx, runtime_ctx, 4, CancellationToken::new()).await;
         send_task.await.unwrap();
 
         let sent_messages = channel_impl.sent_messages.lock().await;
@@ -5076,11 +7007,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -5088,9 +7027,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: true,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5098,6 +7055,7 @@ BTC is currently around $65,000 based on latest tool output."#
             tx.send(traits::ChannelMessage {
                 id: "msg-a".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-1".to_string(),
                 content: "first chat".to_string(),
                 channel: "telegram".to_string(),
@@ -5110,6 +7068,7 @@ BTC is currently around $65,000 based on latest tool output."#
             tx.send(traits::ChannelMessage {
                 id: "msg-b".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-2".to_string(),
                 content: "second chat".to_string(),
                 channel: "telegram".to_string(),
@@ -5120,7 +7079,7 @@ BTC is currently around $65,000 based on latest tool output."#
             .unwrap();
         });
 
-        run_message_dispatch_loop(rx, runtime_ctx, 4).await;
+        run_message_dispatch_loop(rx, runtime_ctx, 4, CancellationToken::new()).await;
         send_task.await.unwrap();
 
         let sent_messages = channel_impl.sent_messages.lock().await;
@@ -5150,11 +7109,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -5162,9 +7129,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -5172,6 +7157,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "typing-msg".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-typing".to_string(),
                 content: "hello".to_string(),
                 channel: "test-channel".to_string(),
@@ -5209,11 +7195,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -5221,9 +7215,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -5231,6 +7243,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "react-msg".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-react".to_string(),
                 content: "hello".to_string(),
                 channel: "test-channel".to_string(),
@@ -5607,6 +7620,7 @@ BTC is currently around $65,000 based on latest tool output."#
         let msg = traits::ChannelMessage {
             id: "msg_abc123".into(),
             sender: "U123".into(),
+            sender_name: None,
             reply_target: "C456".into(),
             content: "hello".into(),
             channel: "slack".into(),
@@ -5622,6 +7636,7 @@ BTC is currently around $65,000 based on latest tool output."#
         let msg1 = traits::ChannelMessage {
             id: "msg_1".into(),
             sender: "U123".into(),
+            sender_name: None,
             reply_target: "C456".into(),
             content: "first".into(),
             channel: "slack".into(),
@@ -5631,6 +7646,7 @@ BTC is currently around $65,000 based on latest tool output."#
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
             sender: "U123".into(),
+            sender_name: None,
             reply_target: "C456".into(),
             content: "second".into(),
             channel: "slack".into(),
@@ -5644,6 +7660,38 @@ BTC is currently around $65,000 based on latest tool output."#
         );
     }
 
+    #[test]
+    fn conversation_history_key_follows_session_policy() {
+        let msg = traits::ChannelMessage {
+            id: "msg_1".into(),
+            sender: "U123".into(),
+            sender_name: None,
+            reply_target: "C456".into(),
+            content: "hello".into(),
+            channel: "slack".into(),
+            timestamp: 1,
+            thread_ts: Some("1700.01".into()),
+        };
+        let mut conversations = crate::config::ConversationsConfig::default();
+        assert_eq!(
+            conversation_history_key(&conversations, &msg),
+            "slack_1700.01_U123"
+        );
+
+        conversations
+            .channels
+            .insert("slack".into(), crate::config::SessionPolicy::Continuous);
+        assert_eq!(conversation_history_key(&conversations, &msg), "slack_U123");
+
+        conversations
+            .channels
+            .insert("slack".into(), crate::config::SessionPolicy::PerChat);
+        assert_eq!(
+            conversation_history_key(&conversations, &msg),
+            "slack_C456_1700.01"
+        );
+    }
+
     #[tokio::test]
     async fn autosave_keys_preserve_multiple_conversation_facts() {
         let tmp = TempDir::new().unwrap();
@@ -5652,6 +7700,7 @@ BTC is currently around $65,000 based on latest tool output."#
         let msg1 = traits::ChannelMessage {
             id: "msg_1".into(),
             sender: "U123".into(),
+            sender_name: None,
             reply_target: "C456".into(),
             content: "I'm Paul".into(),
             channel: "slack".into(),
@@ -5661,6 +7710,7 @@ BTC is currently around $65,000 based on latest tool output."#
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
             sender: "U123".into(),
+            sender_name: None,
             reply_target: "C456".into(),
             content: "I'm 45".into(),
             channel: "slack".into(),
@@ -5699,7 +7749,7 @@ BTC is currently around $65,000 based on latest tool output."#
             .await
             .unwrap();
 
-        let context = build_memory_context(&mem, "age", 0.0).await;
+        let context = build_memory_context(&mem, "age", 0.0, None).await;
         assert!(context.contains("[Memory context]"));
         assert!(context.contains("Age is 45"));
     }
@@ -5725,11 +7775,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -5737,9 +7795,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -5747,6 +7823,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-a".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-1".to_string(),
                 content: "hello".to_string(),
                 channel: "test-channel".to_string(),
@@ -5762,6 +7839,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-b".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-1".to_string(),
                 content: "follow up".to_string(),
                 channel: "test-channel".to_string(),
@@ -5810,11 +7888,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -5822,9 +7908,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -5832,6 +7936,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "msg-ctx-1".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-ctx".to_string(),
                 content: "hello".to_string(),
                 channel: "test-channel".to_string(),
@@ -5895,11 +8000,19 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(histories)),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -5907,9 +8020,27 @@ BTC is currently around $65,000 based on latest tool output."#
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -5917,6 +8048,7 @@ BTC is currently around $65,000 based on latest tool output."#
             traits::ChannelMessage {
                 id: "tg-msg-1".to_string(),
                 sender: "alice".to_string(),
+                sender_name: None,
                 reply_target: "chat-telegram".to_string(),
                 content: "hello".to_string(),
                 channel: "telegram".to_string(),
@@ -5968,6 +8100,23 @@ BTC is currently around $65,000 based on latest tool output."#
         assert_eq!(summary, "[Used tools: shell, web_search]");
     }
 
+    #[test]
+    fn interrupted_turn_note_keeps_tool_results_for_the_next_turn() {
+        let history = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("check the build"),
+            ChatMessage::assistant(
+                r#"{"content":null,"tool_calls":[{"id":"1","name":"shell","arguments":"{}"}]}"#,
+            ),
+            ChatMessage::tool(r#"{"tool_call_id":"1","content":"2 tests failed"}"#),
+        ];
+
+        let note = interrupted_turn_note(&history, 2).unwrap();
+        assert!(note.starts_with("[Used tools: shell]\n[Interrupted by a newer message"));
+        assert!(note.ends_with("\n2 tests failed"));
+        assert!(interrupted_turn_note(&history[..2], 2).is_none());
+    }
+
     #[test]
     fn extract_tool_context_summary_collects_prompt_mode_tool_result_names() {
         let history = vec![
@@ -6237,6 +8386,74 @@ This is an example JSON object for profile settings."#;
             .any(|entry| entry.channel.name() == "mattermost"));
     }
 
+    #[test]
+    fn collect_configured_channels_includes_mastodon_when_configured() {
+        let mut config = Config::default();
+        config.channels_config.mastodon = Some(crate::config::schema::MastodonConfig {
+            instance_url: "https://mastodon.example".to_string(),
+            access_token: "test-token".to_string(),
+            allowed_users: vec!["*".to_string()],
+            max_posts_per_hour: 30,
+            max_status_chars: 500,
+        });
+
+        let channels = collect_configured_channels(&config, "test");
+
+        assert!(channels
+            .iter()
+            .any(|entry| entry.display_name == "Mastodon"));
+        assert!(channels
+            .iter()
+            .any(|entry| entry.channel.name() == "mastodon"));
+    }
+
+    #[test]
+    fn collect_configured_channels_includes_github_when_configured() {
+        let mut config = Config::default();
+        config.channels_config.github = Some(crate::config::schema::GitHubConfig {
+            api_url: "https://api.github.com".to_string(),
+            bot_login: "zeroclaw-bot".to_string(),
+            access_token: Some("test-token".to_string()),
+            app_id: None,
+            installation_id: None,
+            private_key_path: None,
+            webhook_secret: None,
+            allowed_users: vec!["*".to_string()],
+            patch_approvers: vec![],
+            checkouts: std::collections::HashMap::new(),
+            max_context_chars: 12_000,
+        });
+
+        let channels = collect_configured_channels(&config, "test");
+
+        assert!(channels.iter().any(|entry| entry.display_name == "GitHub"));
+        assert!(channels
+            .iter()
+            .any(|entry| entry.channel.name() == "github"));
+    }
+
+    #[test]
+    fn collect_configured_channels_includes_xmpp_when_configured() {
+        let mut config = Config::default();
+        config.channels_config.xmpp = Some(crate::config::schema::XmppConfig {
+            jid: "bot@example.org".to_string(),
+            password: "test-password".to_string(),
+            server: None,
+            port: 5222,
+            direct_tls: false,
+            resource: "zeroclaw".to_string(),
+            rooms: vec![],
+            nickname: None,
+            allowed_users: vec!["*".to_string()],
+            mention_only: true,
+        });
+
+        let channels = collect_configured_channels(&config, "test");
+
+        assert!(channels.iter().any(|entry| entry.display_name == "XMPP"));
+        assert!(channels.iter().any(|entry| entry.channel.name() == "xmpp"));
+    }
+
     struct AlwaysFailChannel {
         name: &'static str,
         calls: Arc<AtomicUsize>,
@@ -6295,7 +8512,7 @@ This is an example JSON object for profile settings."#;
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
-        let handle = spawn_supervised_listener(channel, tx, 1, 1);
+        let handle = spawn_supervised_listener(channel, tx, 1, 1, CancellationToken::new());
 
         tokio::time::sleep(Duration::from_millis(80)).await;
         drop(rx);
@@ -6330,6 +8547,7 @@ This is an example JSON object for profile settings."#;
             1,
             1,
             Duration::from_millis(20),
+            CancellationToken::new(),
         );
 
         tokio::time::sleep(Duration::from_millis(35)).await;
@@ -6358,6 +8576,119 @@ This is an example JSON object for profile settings."#;
         assert!(calls.load(Ordering::SeqCst) >= 1);
     }
 
+    #[tokio::test]
+    async fn supervised_listener_stops_on_shutdown_while_bus_is_open() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let channel: Arc<dyn Channel> = Arc::new(BlockUntilClosedChannel {
+            name: format!("test-supervised-shutdown-{}", uuid::Uuid::new_v4()),
+            calls: Arc::clone(&calls),
+        });
+
+        let (tx, _rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
+        let shutdown = CancellationToken::new();
+        let handle = spawn_supervised_listener(channel, tx, 1, 1, shutdown.clone());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        shutdown.cancel();
+        let join = tokio::time::timeout(Duration::from_secs(1), handle).await;
+        assert!(
+            join.is_ok(),
+            "listener should stop once shutdown is cancelled"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn dispatch_loop_returns_on_shutdown_with_open_bus() {
+        let (_tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            interrupt_all_channels: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
+        });
+
+        let join = tokio::time::timeout(
+            Duration::from_secs(1),
+            run_message_dispatch_loop(rx, runtime_ctx, 1, shutdown),
+        )
+        .await;
+        assert!(
+            join.is_ok(),
+            "dispatch loop should stop once shutdown is cancelled"
+        );
+    }
+
+    #[test]
+    fn memory_session_is_scoped_per_channel_unless_shared() {
+        let msg = traits::ChannelMessage {
+            id: "1".into(),
+            sender: "alice".into(),
+            sender_name: None,
+            reply_target: "alice".into(),
+            content: "hello".into(),
+            channel: "telegram".into(),
+            timestamp: 0,
+            thread_ts: None,
+        };
+        let mut config = Config::default();
+        let policy = memory::scope::MemoryScopePolicy::from_config(&config);
+        assert_eq!(memory_scope(&policy, &msg).session_id(), Some("telegram"));
+        config.channels_config.shared_memory = true;
+        let policy = memory::scope::MemoryScopePolicy::from_config(&config);
+        assert_eq!(memory_scope(&policy, &msg).session_id(), None);
+    }
+
     #[test]
     fn maybe_restart_daemon_systemd_args_regression() {
         assert_eq!(
@@ -6444,11 +8775,19 @@ This is an example JSON object for profile settings."#;
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -6456,9 +8795,27 @@ This is an example JSON object for profile settings."#;
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -6467,6 +8824,7 @@ This is an example JSON object for profile settings."#;
             traits::ChannelMessage {
                 id: "msg-photo-1".to_string(),
                 sender: "zeroclaw_user".to_string(),
+                sender_name: None,
                 reply_target: "chat-photo".to_string(),
                 content: "[IMAGE:/tmp/workspace/photo_99_1.jpg]\n\nWhat is this?".to_string(),
                 channel: "test-channel".to_string(),
@@ -6510,11 +8868,19 @@ This is an example JSON object for profile settings."#;
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_scopes: Arc::default(),
+            conversations: Arc::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            fact_extraction: false,
+            fact_extraction_model: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            disabled_tools: Arc::new(Mutex::new(HashMap::new())),
+            pins: Arc::new(Mutex::new(HashMap::new())),
+            max_context_tokens: 0,
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
@@ -6522,9 +8888,27 @@ This is an example JSON object for profile settings."#;
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
+            interrupt_all_channels: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            tool_output: crate::config::ToolOutputConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            approvals: None,
+            agent_router: Arc::new(router::AgentRouter::default()),
+            budgets: None,
+            prompt_templates: false,
+            guardrails: None,
+            replay: false,
+            pairing: None,
+            rate_limiter: None,
+            debug_prompts: false,
+            debug_dumps: Arc::new(Mutex::new(HashMap::new())),
+            dry_run: false,
+            dry_runs: Arc::new(Mutex::new(HashMap::new())),
+            undo_journals: Arc::new(Mutex::new(HashMap::new())),
+            read_receipts: true,
+            outbox: None,
+            model_router: None,
         });
 
         process_channel_message(
@@ -6532,6 +8916,7 @@ This is an example JSON object for profile settings."#;
             traits::ChannelMessage {
                 id: "msg-photo-1".to_string(),
                 sender: "zeroclaw_user".to_string(),
+                sender_name: None,
                 reply_target: "chat-photo".to_string(),
                 content: "[IMAGE:/tmp/workspace/photo_99_1.jpg]\n\nWhat is this?".to_string(),
                 channel: "test-channel".to_string(),
@@ -6547,6 +8932,7 @@ This is an example JSON object for profile settings."#;
             traits::ChannelMessage {
                 id: "msg-text-2".to_string(),
                 sender: "zeroclaw_user".to_string(),
+                sender_name: None,
                 reply_target: "chat-photo".to_string(),
                 content: "What is WAL?".to_string(),
                 channel: "test-channel".to_string(),
@@ -164,6 +164,7 @@ impl NextcloudTalkChannel {
             id: message_id,
             reply_target: room_token.to_string(),
             sender: actor_id.to_string(),
+            sender_name: None,
             content: content.to_string(),
             channel: "nextcloud_talk".to_string(),
             timestamp,
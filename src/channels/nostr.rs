@@ -248,6 +248,7 @@ impl Channel for NostrChannel {
                         let msg = ChannelMessage {
                             id,
                             sender: sender_hex.clone(),
+                            sender_name: None,
                             reply_target: sender_hex,
                             content,
                             channel: "nostr".to_string(),
@@ -0,0 +1,415 @@
+//! Durable queue for outbound messages (`[channels_config.outbox]`).
+//!
+//! When a channel cannot take a reply or a scheduled announcement — the
+//! network is down, the platform is unreachable, the listener is
+//! reconnecting — [`send_or_queue`] stores it in `state/outbox.db` instead of
+//! dropping it. The channel runtime delivers queued messages in the order
+//! they were queued whenever a listener (re)connects and every
+//! `retry_interval_secs` in between. While a recipient has messages queued,
+//! newer ones for that recipient are queued behind them, so nothing arrives
+//! out of order.
+
+use super::traits::{Channel, SendMessage};
+use crate::config::ChannelOutboxConfig;
+use anyhow::{Context, Result};
+use chrono::{SecondsFormat, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+/// Schema history, applied by [`crate::util::sqlite::migrate`].
+const MIGRATIONS: &[&str] = &[
+    // v1: queued messages, delivered in id order
+    "CREATE TABLE outbox (
+        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+        channel     TEXT NOT NULL,
+        recipient   TEXT NOT NULL,
+        content     TEXT NOT NULL,
+        subject     TEXT,
+        thread_ts   TEXT,
+        queued_at   TEXT NOT NULL,
+        attempts    INTEGER NOT NULL DEFAULT 0,
+        last_error  TEXT
+    );
+    CREATE INDEX idx_outbox_channel ON outbox(channel, id);",
+];
+
+/// Wakes the delivery task when a channel listener (re)connects.
+static RECONNECTED: OnceLock<Notify> = OnceLock::new();
+
+fn reconnected() -> &'static Notify {
+    RECONNECTED.get_or_init(Notify::new)
+}
+
+/// Tell the delivery task that a channel listener has (re)connected.
+pub fn notify_reconnected() {
+    reconnected().notify_one();
+}
+
+/// A message waiting in the queue.
+#[derive(Debug, Clone)]
+pub struct QueuedMessage {
+    pub id: i64,
+    pub channel: String,
+    pub message: SendMessage,
+    pub queued_at: String,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+/// How [`send_or_queue`] handled a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    Sent,
+    Queued,
+}
+
+/// SQLite-backed outbound queue, shared by the channel runtime, the
+/// scheduler and the CLI.
+pub struct Outbox {
+    conn: Mutex<Connection>,
+    max_age: chrono::Duration,
+}
+
+impl Outbox {
+    /// Open (or create) the queue in `workspace_dir/state/outbox.db`.
+    pub fn open(workspace_dir: &Path, config: &ChannelOutboxConfig) -> Result<Self> {
+        let db_path = workspace_dir.join("state").join("outbox.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = crate::security::storage_encryption::open(&db_path)
+            .with_context(|| format!("Failed to open outbox {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        crate::util::sqlite::migrate(&conn, "Outbox", MIGRATIONS)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            max_age: i64::try_from(config.max_age_hours)
+                .ok()
+                .and_then(chrono::Duration::try_hours)
+                .unwrap_or(chrono::Duration::MAX),
+        })
+    }
+
+    /// The queue for `config`, or `None` when `[channels_config.outbox]` is
+    /// disabled or the database cannot be opened.
+    pub fn from_config(config: &crate::config::Config) -> Option<Self> {
+        if !config.channels_config.outbox.enabled {
+            return None;
+        }
+        match Self::open(&config.workspace_dir, &config.channels_config.outbox) {
+            Ok(outbox) => Some(outbox),
+            Err(e) => {
+                tracing::warn!("Outbound message queue unavailable: {e:#}");
+                None
+            }
+        }
+    }
+
+    /// Add `message` for `channel` to the end of the queue.
+    pub fn enqueue(&self, channel: &str, message: &SendMessage, error: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT INTO outbox (channel, recipient, content, subject, thread_ts, queued_at, last_error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                channel,
+                message.recipient,
+                message.content,
+                message.subject,
+                message.thread_ts,
+                now(),
+                error,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Whether `recipient` on `channel` has messages waiting.
+    pub fn has_pending(&self, channel: &str, recipient: &str) -> Result<bool> {
+        let count: i64 = self.conn.lock().query_row(
+            "SELECT COUNT(*) FROM outbox WHERE channel = ?1 AND recipient = ?2",
+            params![channel, recipient],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Queued messages, oldest first; all channels when `channel` is `None`.
+    pub fn pending(&self, channel: Option<&str>) -> Result<Vec<QueuedMessage>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, channel, recipient, content, subject, thread_ts, queued_at, attempts, last_error
+             FROM outbox WHERE ?1 IS NULL OR channel = ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![channel], |row| {
+            Ok(QueuedMessage {
+                id: row.get(0)?,
+                channel: row.get(1)?,
+                message: SendMessage {
+                    recipient: row.get(2)?,
+                    content: row.get(3)?,
+                    subject: row.get(4)?,
+                    thread_ts: row.get(5)?,
+                },
+                queued_at: row.get(6)?,
+                attempts: row.get(7)?,
+                last_error: row.get(8)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Channels with messages waiting.
+    pub fn pending_channels(&self) -> Result<Vec<String>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare("SELECT DISTINCT channel FROM outbox ORDER BY channel")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    fn delivered(&self, id: i64) -> Result<()> {
+        self.conn
+            .lock()
+            .execute("DELETE FROM outbox WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    fn failed(&self, id: i64, error: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "UPDATE outbox SET attempts = attempts + 1, last_error = ?2 WHERE id = ?1",
+            params![id, error],
+        )?;
+        Ok(())
+    }
+
+    /// Drop messages older than `max_age_hours`. Returns how many.
+    pub fn prune_expired(&self) -> Result<usize> {
+        let Some(cutoff) = Utc::now().checked_sub_signed(self.max_age) else {
+            return Ok(0);
+        };
+        let cutoff = cutoff.to_rfc3339_opts(SecondsFormat::Secs, true);
+        Ok(self
+            .conn
+            .lock()
+            .execute("DELETE FROM outbox WHERE queued_at < ?1", params![cutoff])?)
+    }
+
+    /// Drop queued messages; all channels when `channel` is `None`.
+    /// Returns how many.
+    pub fn clear(&self, channel: Option<&str>) -> Result<usize> {
+        Ok(self.conn.lock().execute(
+            "DELETE FROM outbox WHERE ?1 IS NULL OR channel = ?1",
+            params![channel],
+        )?)
+    }
+
+    /// Deliver the queue of `channel` in order. A recipient whose message
+    /// fails keeps the rest of its messages queued for the next attempt.
+    /// Returns how many were delivered.
+    pub async fn flush(&self, channel: &dyn Channel) -> Result<usize> {
+        let mut blocked = HashSet::new();
+        let mut sent = 0;
+        for queued in self.pending(Some(channel.name()))? {
+            if blocked.contains(&queued.message.recipient) {
+                continue;
+            }
+            match channel.send(&queued.message).await {
+                Ok(()) => {
+                    self.delivered(queued.id)?;
+                    sent += 1;
+                }
+                Err(e) => {
+                    self.failed(queued.id, &e.to_string())?;
+                    blocked.insert(queued.message.recipient);
+                }
+            }
+        }
+        Ok(sent)
+    }
+}
+
+/// Send `message` on `channel`, queueing it when the channel cannot take it
+/// or when older messages for the same recipient are still queued. Without
+/// an outbox this is a plain send.
+pub async fn send_or_queue(
+    outbox: Option<&Outbox>,
+    channel: &dyn Channel,
+    message: &SendMessage,
+) -> Result<Delivery> {
+    let Some(outbox) = outbox else {
+        channel.send(message).await?;
+        return Ok(Delivery::Sent);
+    };
+    if outbox.has_pending(channel.name(), &message.recipient)? {
+        outbox.enqueue(channel.name(), message, "queued behind earlier messages")?;
+        return Ok(Delivery::Queued);
+    }
+    match channel.send(message).await {
+        Ok(()) => Ok(Delivery::Sent),
+        Err(e) => {
+            tracing::warn!(
+                channel = channel.name(),
+                "Send failed, queueing for delivery on reconnect: {e}"
+            );
+            outbox.enqueue(channel.name(), message, &e.to_string())?;
+            Ok(Delivery::Queued)
+        }
+    }
+}
+
+/// Deliver queued messages for `channels` whenever a listener reconnects
+/// and every `retry_interval`, until `shutdown`.
+pub fn spawn_delivery(
+    outbox: Arc<Outbox>,
+    channels: Arc<HashMap<String, Arc<dyn Channel>>>,
+    retry_interval: Duration,
+    shutdown: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut retry = tokio::time::interval(retry_interval.max(Duration::from_secs(1)));
+        retry.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = retry.tick() => {}
+                () = reconnected().notified() => {}
+                () = shutdown.cancelled() => break,
+            }
+            match outbox.prune_expired() {
+                Ok(0) => {}
+                Ok(dropped) => tracing::warn!("Dropped {dropped} expired queued message(s)"),
+                Err(e) => tracing::warn!("Failed to prune outbound queue: {e}"),
+            }
+            let names = match outbox.pending_channels() {
+                Ok(names) => names,
+                Err(e) => {
+                    tracing::warn!("Failed to read outbound queue: {e}");
+                    continue;
+                }
+            };
+            for name in names {
+                let Some(channel) = channels.get(&name) else {
+                    continue;
+                };
+                match outbox.flush(channel.as_ref()).await {
+                    Ok(0) => {}
+                    Ok(sent) => tracing::info!("Delivered {sent} queued message(s) on {name}"),
+                    Err(e) => tracing::warn!("Failed to deliver queued messages on {name}: {e}"),
+                }
+            }
+        }
+    })
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Fails every send while `offline` is set.
+    #[derive(Default)]
+    struct FlakyChannel {
+        offline: AtomicBool,
+        sent: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Channel for FlakyChannel {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn send(&self, message: &SendMessage) -> Result<()> {
+            if self.offline.load(Ordering::SeqCst) {
+                bail!("connection refused");
+            }
+            self.sent
+                .lock()
+                .push(format!("{}:{}", message.recipient, message.content));
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<crate::channels::traits::ChannelMessage>,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn messages_queued_while_offline_are_delivered_in_order() {
+        let workspace = tempfile::tempdir().unwrap();
+        let outbox = Outbox::open(workspace.path(), &ChannelOutboxConfig::default()).unwrap();
+        let channel = FlakyChannel::default();
+
+        channel.offline.store(true, Ordering::SeqCst);
+        for text in ["one", "two"] {
+            let delivery = send_or_queue(Some(&outbox), &channel, &SendMessage::new(text, "bob"))
+                .await
+                .unwrap();
+            assert_eq!(delivery, Delivery::Queued);
+        }
+        assert_eq!(outbox.flush(&channel).await.unwrap(), 0);
+        assert_eq!(outbox.pending(None).unwrap()[0].attempts, 1);
+
+        channel.offline.store(false, Ordering::SeqCst);
+        let delivery = send_or_queue(Some(&outbox), &channel, &SendMessage::new("three", "bob"))
+            .await
+            .unwrap();
+        assert_eq!(
+            delivery,
+            Delivery::Queued,
+            "must not overtake queued messages"
+        );
+        let delivery = send_or_queue(Some(&outbox), &channel, &SendMessage::new("hi", "amy"))
+            .await
+            .unwrap();
+        assert_eq!(delivery, Delivery::Sent);
+
+        assert_eq!(outbox.flush(&channel).await.unwrap(), 3);
+        assert_eq!(
+            *channel.sent.lock(),
+            ["amy:hi", "bob:one", "bob:two", "bob:three"]
+        );
+        assert!(outbox.pending(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn expired_messages_are_pruned() {
+        let workspace = tempfile::tempdir().unwrap();
+        let outbox = Outbox::open(workspace.path(), &ChannelOutboxConfig::default()).unwrap();
+        for text in ["old", "new"] {
+            outbox
+                .enqueue("flaky", &SendMessage::new(text, "bob"), "offline")
+                .unwrap();
+        }
+        outbox
+            .conn
+            .lock()
+            .execute(
+                "UPDATE outbox SET queued_at = '2000-01-01T00:00:00Z' WHERE content = 'old'",
+                [],
+            )
+            .unwrap();
+        assert_eq!(outbox.prune_expired().unwrap(), 1);
+        assert_eq!(
+            outbox.pending(Some("flaky")).unwrap()[0].message.content,
+            "new"
+        );
+    }
+}
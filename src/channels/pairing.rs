@@ -0,0 +1,351 @@
+//! Operator approval for chat channel users (`[security.pairing]`).
+//!
+//! A sender the store does not know gets a one-time code in reply instead of
+//! an answer. The operator approves the code from the local CLI with
+//! `zeroclaw pair approve <code>`, which records the sender's channel, ID and
+//! role in `state/pairing.db`; the daemon reads the same database on every
+//! message, so approval takes effect without a restart.
+
+use crate::config::Config;
+use crate::security::Role;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+/// Schema history, applied by [`crate::util::sqlite::migrate`].
+const MIGRATIONS: &[&str] = &[
+    // v1: approved senders and outstanding codes
+    "CREATE TABLE paired_users (
+        channel      TEXT NOT NULL,
+        sender       TEXT NOT NULL,
+        role         TEXT NOT NULL,
+        approved_at  TEXT NOT NULL,
+        PRIMARY KEY (channel, sender)
+    );
+    CREATE TABLE pairing_requests (
+        code        TEXT PRIMARY KEY,
+        channel     TEXT NOT NULL,
+        sender      TEXT NOT NULL,
+        created_at  TEXT NOT NULL
+    );
+    CREATE INDEX idx_pairing_requests_sender ON pairing_requests(channel, sender);",
+];
+
+/// Unambiguous characters for codes read off a phone and typed in a terminal.
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LEN: usize = 8;
+
+/// An approved sender.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairedUser {
+    pub channel: String,
+    pub sender: String,
+    pub role: Role,
+    pub approved_at: String,
+}
+
+/// A code waiting for operator approval.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairingRequest {
+    pub code: String,
+    pub channel: String,
+    pub sender: String,
+    pub created_at: String,
+}
+
+/// SQLite-backed pairing state, shared by the daemon and the CLI.
+pub struct PairingStore {
+    conn: Mutex<Connection>,
+    code_ttl: chrono::Duration,
+}
+
+impl PairingStore {
+    /// Open (or create) the store in `workspace_dir/state/pairing.db`.
+    pub fn open(workspace_dir: &Path, code_ttl_secs: u64) -> Result<Self> {
+        let db_path = workspace_dir.join("state").join("pairing.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open pairing store {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        crate::util::sqlite::migrate(&conn, "Pairing store", MIGRATIONS)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            code_ttl: chrono::Duration::seconds(i64::try_from(code_ttl_secs).unwrap_or(i64::MAX)),
+        })
+    }
+
+    /// Role of an approved sender, or `None` when they are not paired.
+    pub fn role(&self, channel: &str, sender: &str) -> Result<Option<Role>> {
+        let role: Option<String> = self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT role FROM paired_users WHERE channel = ?1 AND sender = ?2",
+                params![channel, sender],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(role.map(|role| role.parse().unwrap_or(Role::Guest)))
+    }
+
+    /// The code for `sender` to hand to the operator. A sender who writes
+    /// again before their code expires gets the same one.
+    pub fn request_code(&self, channel: &str, sender: &str) -> Result<String> {
+        let conn = self.conn.lock();
+        self.prune_expired(&conn)?;
+        let existing: Option<String> = conn
+            .query_row(
+                "SELECT code FROM pairing_requests WHERE channel = ?1 AND sender = ?2",
+                params![channel, sender],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(code) = existing {
+            return Ok(code);
+        }
+        let code = generate_code();
+        conn.execute(
+            "INSERT INTO pairing_requests (code, channel, sender, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![code, channel, sender, now()],
+        )?;
+        Ok(code)
+    }
+
+    /// Approve the sender who was given `code`.
+    pub fn approve(&self, code: &str, role: Role) -> Result<PairedUser> {
+        let code = code.trim().to_ascii_uppercase();
+        let conn = self.conn.lock();
+        self.prune_expired(&conn)?;
+        let request: Option<(String, String)> = conn
+            .query_row(
+                "SELECT channel, sender FROM pairing_requests WHERE code = ?1",
+                params![code],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((channel, sender)) = request else {
+            bail!("Pairing code {code} is unknown or has expired");
+        };
+        let approved_at = now();
+        conn.execute(
+            "INSERT INTO paired_users (channel, sender, role, approved_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(channel, sender) DO UPDATE SET role = ?3, approved_at = ?4",
+            params![channel, sender, role.as_str(), approved_at],
+        )?;
+        conn.execute(
+            "DELETE FROM pairing_requests WHERE channel = ?1 AND sender = ?2",
+            params![channel, sender],
+        )?;
+        Ok(PairedUser {
+            channel,
+            sender,
+            role,
+            approved_at,
+        })
+    }
+
+    /// Remove an approved sender. Returns whether one was removed.
+    pub fn revoke(&self, channel: &str, sender: &str) -> Result<bool> {
+        let removed = self.conn.lock().execute(
+            "DELETE FROM paired_users WHERE channel = ?1 AND sender = ?2",
+            params![channel, sender],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Approved senders, by channel then sender.
+    pub fn users(&self) -> Result<Vec<PairedUser>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT channel, sender, role, approved_at FROM paired_users
+             ORDER BY channel, sender",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let role: String = row.get(2)?;
+            Ok(PairedUser {
+                channel: row.get(0)?,
+                sender: row.get(1)?,
+                role: role.parse().unwrap_or(Role::Guest),
+                approved_at: row.get(3)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    /// Codes still waiting for approval, oldest first.
+    pub fn pending(&self) -> Result<Vec<PairingRequest>> {
+        let conn = self.conn.lock();
+        self.prune_expired(&conn)?;
+        let mut stmt = conn.prepare(
+            "SELECT code, channel, sender, created_at FROM pairing_requests
+             ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(PairingRequest {
+                code: row.get(0)?,
+                channel: row.get(1)?,
+                sender: row.get(2)?,
+                created_at: row.get(3)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    fn prune_expired(&self, conn: &Connection) -> Result<()> {
+        let cutoff = timestamp(Utc::now() - self.code_ttl);
+        conn.execute(
+            "DELETE FROM pairing_requests WHERE created_at < ?1",
+            params![cutoff],
+        )?;
+        Ok(())
+    }
+}
+
+/// Reply to a sender who is not paired yet.
+pub fn approval_message(code: &str) -> String {
+    format!(
+        "🔐 This assistant needs operator approval before it can talk to you.\n\nAsk the operator to run:\n`zeroclaw pair approve {code}`\n\nThen send your message again."
+    )
+}
+
+/// Fixed-width RFC 3339, so stored timestamps compare as strings.
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn now() -> String {
+    timestamp(Utc::now())
+}
+
+fn generate_code() -> String {
+    let bytes: [u8; CODE_LEN] = rand::random();
+    bytes
+        .iter()
+        .map(|b| char::from(CODE_ALPHABET[usize::from(*b) % CODE_ALPHABET.len()]))
+        .collect()
+}
+
+fn format_time(raw: &str) -> String {
+    DateTime::parse_from_rfc3339(raw).map_or_else(
+        |_| raw.to_string(),
+        |time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        },
+    )
+}
+
+pub fn handle_command(command: crate::PairCommands, config: &Config) -> Result<()> {
+    let pairing = &config.security.pairing;
+    let store = PairingStore::open(&config.workspace_dir, pairing.code_ttl_secs)?;
+    match command {
+        crate::PairCommands::List => {
+            if !pairing.enabled {
+                println!(
+                    "ℹ️  Pairing is disabled; set [security.pairing] enabled = true to require it."
+                );
+            }
+            let pending = store.pending()?;
+            if !pending.is_empty() {
+                println!("Waiting for approval:");
+                for request in pending {
+                    println!(
+                        "  {}  {}:{}  (since {})",
+                        request.code,
+                        request.channel,
+                        request.sender,
+                        format_time(&request.created_at)
+                    );
+                }
+                println!();
+            }
+            let users = store.users()?;
+            if users.is_empty() {
+                println!("No paired users.");
+            } else {
+                println!("Paired users:");
+                for user in users {
+                    println!(
+                        "  {:<6} {}:{}  (approved {})",
+                        user.role.as_str(),
+                        user.channel,
+                        user.sender,
+                        format_time(&user.approved_at)
+                    );
+                }
+            }
+            Ok(())
+        }
+        crate::PairCommands::Approve { code, role } => {
+            let role = match role {
+                Some(role) => role.parse()?,
+                None => pairing.default_role,
+            };
+            let user = store.approve(&code, role)?;
+            println!(
+                "✅ Paired {}:{} as {}",
+                user.channel,
+                user.sender,
+                user.role.as_str()
+            );
+            Ok(())
+        }
+        crate::PairCommands::Revoke { channel, sender } => {
+            if store.revoke(&channel, &sender)? {
+                println!("🛑 Revoked {channel}:{sender}");
+                Ok(())
+            } else {
+                bail!("{channel}:{sender} is not paired")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn approving_a_code_pairs_its_sender() {
+        let tmp = TempDir::new().unwrap();
+        let store = PairingStore::open(tmp.path(), 3600).unwrap();
+        assert_eq!(store.role("telegram", "alice").unwrap(), None);
+
+        let code = store.request_code("telegram", "alice").unwrap();
+        assert_eq!(code.len(), CODE_LEN);
+        assert_eq!(store.request_code("telegram", "alice").unwrap(), code);
+
+        let user = store.approve(&code.to_lowercase(), Role::Admin).unwrap();
+        assert_eq!(user.sender, "alice");
+        assert_eq!(store.role("telegram", "alice").unwrap(), Some(Role::Admin));
+        assert_eq!(store.role("discord", "alice").unwrap(), None);
+        assert!(store.pending().unwrap().is_empty());
+        assert!(store.approve(&code, Role::Member).is_err());
+
+        assert!(store.revoke("telegram", "alice").unwrap());
+        assert_eq!(store.role("telegram", "alice").unwrap(), None);
+    }
+
+    #[test]
+    fn expired_codes_cannot_be_approved() {
+        let tmp = TempDir::new().unwrap();
+        let store = PairingStore::open(tmp.path(), 0).unwrap();
+        let code = store.request_code("slack", "U123").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(store.approve(&code, Role::Member).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! Who said what in shared conversations.
+//!
+//! Under `session_policy = "per_chat"` everyone in a group chat talks in one
+//! conversation. Each user turn is then kept as `[<name>] <text>`, so the
+//! model (and the stored history) can tell people apart; the system prompt
+//! lists who has spoken, and fact extraction is told the speaker so memories
+//! read "Alice prefers…" rather than "The user prefers…".
+
+use super::traits::ChannelMessage;
+use crate::providers::ChatMessage;
+use std::fmt::Write;
+
+/// Participants listed in the system prompt, most recent speakers first.
+const MAX_LISTED_PARTICIPANTS: usize = 20;
+
+/// How `msg`'s sender is named: the platform's display name, else the
+/// sender ID.
+pub fn display_name(msg: &ChannelMessage) -> &str {
+    msg.sender_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(&msg.sender)
+}
+
+/// `content` attributed to `msg`'s sender.
+pub fn attribute(msg: &ChannelMessage, content: &str) -> String {
+    // Brackets would end the attribution early.
+    let name = display_name(msg).replace(['[', ']'], "");
+    format!("[{name}] {content}")
+}
+
+/// Speaker of an attributed user turn.
+pub fn speaker(turn: &ChatMessage) -> Option<&str> {
+    if turn.role != "user" {
+        return None;
+    }
+    let rest = turn.content.strip_prefix('[')?;
+    let (name, _) = rest.split_once("] ")?;
+    Some(name).filter(|name| !name.is_empty())
+}
+
+/// System prompt section for a shared conversation: how turns are
+/// attributed and who has spoken in `turns`.
+pub fn prompt_section(turns: &[ChatMessage]) -> String {
+    let mut names: Vec<&str> = Vec::new();
+    for name in turns.iter().rev().filter_map(speaker) {
+        if !names.contains(&name) && names.len() < MAX_LISTED_PARTICIPANTS {
+            names.push(name);
+        }
+    }
+    let mut section = String::from(
+        "## Group conversation\n\n\
+         Several people share this conversation. Each user message starts with the sender's \
+         name in brackets, e.g. `[Alice] hi`. Keep track of who said what, address people by \
+         name when it helps, and attribute preferences and facts to the person they belong to. \
+         Do not start your own replies with a bracketed name.\n",
+    );
+    if !names.is_empty() {
+        let _ = write!(section, "\nParticipants so far: {}\n", names.join(", "));
+    }
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(sender: &str, sender_name: Option<&str>) -> ChannelMessage {
+        ChannelMessage {
+            id: "m1".into(),
+            sender: sender.into(),
+            sender_name: sender_name.map(str::to_string),
+            reply_target: "group-1".into(),
+            content: "hi".into(),
+            channel: "telegram".into(),
+            timestamp: 0,
+            thread_ts: None,
+        }
+    }
+
+    #[test]
+    fn turns_are_attributed_by_display_name() {
+        let alice = message("alice_42", Some("Alice [admin]"));
+        let turn = ChatMessage::user(attribute(&alice, "I take my coffee black"));
+        assert_eq!(turn.content, "[Alice admin] I take my coffee black");
+        assert_eq!(speaker(&turn), Some("Alice admin"));
+
+        let bob = message("U123", Some("  "));
+        assert_eq!(attribute(&bob, "hello"), "[U123] hello");
+        assert_eq!(speaker(&ChatMessage::assistant("[U123] hello")), None);
+        assert_eq!(speaker(&ChatMessage::user("[Tool results]\nok")), None);
+    }
+
+    #[test]
+    fn prompt_lists_recent_speakers_once() {
+        let turns = [
+            ChatMessage::user("[Alice] hi"),
+            ChatMessage::assistant("Hello Alice"),
+            ChatMessage::user("[Bob] and me?"),
+            ChatMessage::user("[Alice] him too"),
+        ];
+        assert!(prompt_section(&turns).ends_with("Participants so far: Alice, Bob\n"));
+        assert!(!prompt_section(&[]).contains("Participants"));
+    }
+}
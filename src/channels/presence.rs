@@ -0,0 +1,151 @@
+//! Presence while a message is handled.
+//!
+//! When processing of an inbound message starts, the message is marked read
+//! (`[channels_config] read_receipts`) and a typing indicator is shown in its
+//! chat, refreshed until the reply is out. Adapters only implement the
+//! [`Channel`] primitives their platform has (`mark_read`, `start_typing`,
+//! `stop_typing`); every channel gets the turn-level behaviour from
+//! [`ChannelPresence`], whether it is driven by the dispatch loop or by a
+//! gateway webhook.
+
+use super::traits::{Channel, ChannelMessage};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Typing indicators expire after 5–10s on most platforms.
+pub const TYPING_REFRESH_INTERVAL: Duration = Duration::from_secs(4);
+
+pub trait ChannelPresence {
+    /// Mark `msg` read (when `read_receipts` is set) and show typing in its
+    /// chat until [`Presence::end`].
+    fn begin_presence(self: Arc<Self>, msg: &ChannelMessage, read_receipts: bool) -> Presence;
+}
+
+impl<C: Channel + ?Sized + 'static> ChannelPresence for C {
+    fn begin_presence(self: Arc<Self>, msg: &ChannelMessage, read_receipts: bool) -> Presence {
+        let stop = CancellationToken::new();
+        let stop_signal = stop.clone();
+        let channel = self;
+        let msg = msg.clone();
+        let task = tokio::spawn(async move {
+            if read_receipts {
+                if let Err(e) = channel.mark_read(&msg).await {
+                    tracing::debug!("Failed to mark message read on {}: {e}", channel.name());
+                }
+            }
+
+            let mut interval = tokio::time::interval(TYPING_REFRESH_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    () = stop_signal.cancelled() => break,
+                    _ = interval.tick() => {
+                        if let Err(e) = channel.start_typing(&msg.reply_target).await {
+                            tracing::debug!("Failed to start typing on {}: {e}", channel.name());
+                        }
+                    }
+                }
+            }
+
+            if let Err(e) = channel.stop_typing(&msg.reply_target).await {
+                tracing::debug!("Failed to stop typing on {}: {e}", channel.name());
+            }
+        });
+        Presence { stop, task }
+    }
+}
+
+/// Presence shown for one message; see [`ChannelPresence`].
+pub struct Presence {
+    stop: CancellationToken,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Presence {
+    /// Stop the typing indicator and wait until it is cleared.
+    pub async fn end(self) -> Result<(), tokio::task::JoinError> {
+        self.stop.cancel();
+        self.task.await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::SendMessage;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    struct PresenceLog(Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl Channel for PresenceLog {
+        fn name(&self) -> &str {
+            "log"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+            self.0.lock().push(format!("typing {recipient}"));
+            Ok(())
+        }
+
+        async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+            self.0.lock().push(format!("stop {recipient}"));
+            Ok(())
+        }
+
+        async fn mark_read(&self, message: &ChannelMessage) -> anyhow::Result<()> {
+            self.0.lock().push(format!("read {}", message.id));
+            Ok(())
+        }
+    }
+
+    fn message() -> ChannelMessage {
+        ChannelMessage {
+            id: "m1".into(),
+            sender: "alice".into(),
+            sender_name: None,
+            reply_target: "chat-1".into(),
+            content: "hi".into(),
+            channel: "log".into(),
+            timestamp: 0,
+            thread_ts: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn messages_are_read_then_typing_runs_until_the_end() {
+        let channel = Arc::new(PresenceLog::default());
+        let presence = Arc::clone(&channel).begin_presence(&message(), true);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        presence.end().await.unwrap();
+        assert_eq!(
+            *channel.0.lock(),
+            ["read m1", "typing chat-1", "stop chat-1"]
+        );
+
+        let channel = Arc::new(PresenceLog::default());
+        let dyn_channel: Arc<dyn Channel> = channel.clone();
+        let presence = dyn_channel.begin_presence(&message(), false);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        presence.end().await.unwrap();
+        assert!(!channel
+            .0
+            .lock()
+            .iter()
+            .any(|entry| entry.starts_with("read")));
+    }
+}
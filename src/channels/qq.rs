@@ -453,6 +453,7 @@ impl Channel for QQChannel {
                             let channel_msg = ChannelMessage {
                                 id: Uuid::new_v4().to_string(),
                                 sender: user_openid.to_string(),
+                                sender_name: None,
                                 reply_target: chat_id,
                                 content,
                                 channel: "qq".to_string(),
@@ -491,6 +492,7 @@ impl Channel for QQChannel {
                             let channel_msg = ChannelMessage {
                                 id: Uuid::new_v4().to_string(),
                                 sender: author_id.to_string(),
+                                sender_name: None,
                                 reply_target: chat_id,
                                 content,
                                 channel: "qq".to_string(),
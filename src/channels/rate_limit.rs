@@ -0,0 +1,324 @@
+//! Inbound message rate limits (`[channels_config.rate_limit]`).
+//!
+//! The dispatch loop asks [`RateLimiter::admit`] before a message takes a
+//! worker. Messages over a per-sender or per-channel rate, or beyond the
+//! number of turns a sender or channel may have running, are dropped before
+//! they reach the provider; the sender gets a short backoff reply, at most
+//! once per `notice_cooldown_secs`, so a flood is not answered with one.
+
+use super::traits::ChannelMessage;
+use crate::config::ChannelRateLimitConfig;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// How often idle keys are swept from the windows.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Why a message was not admitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// Over a messages-per-minute limit; the window frees up after this long.
+    Rate { retry_after: Duration },
+    /// The sender already has `user_concurrent_turns` turns running.
+    SenderBusy,
+    /// The channel already has `channel_concurrent_turns` turns running.
+    ChannelBusy,
+}
+
+impl Rejection {
+    /// Backoff reply for the sender.
+    pub fn message(self) -> String {
+        match self {
+            Self::Rate { retry_after } => format!(
+                "⏳ You're sending messages faster than I can answer. Please wait {}s and try again.",
+                retry_after.as_secs().max(1)
+            ),
+            Self::SenderBusy => {
+                "⏳ I'm still working on your earlier message. Please wait for the answer before sending another."
+                    .to_string()
+            }
+            Self::ChannelBusy => {
+                "⏳ This channel is busy right now. Please try again in a minute.".to_string()
+            }
+        }
+    }
+}
+
+/// A message that was admitted, holding its sender's and channel's turn
+/// slots until dropped.
+pub struct TurnPermit {
+    limiter: Arc<RateLimiter>,
+    sender_key: String,
+    channel: String,
+}
+
+impl Drop for TurnPermit {
+    fn drop(&mut self) {
+        let mut state = self.limiter.state.lock();
+        release(&mut state.sender_turns, &self.sender_key);
+        release(&mut state.channel_turns, &self.channel);
+    }
+}
+
+fn release(turns: &mut HashMap<String, usize>, key: &str) {
+    if let Some(count) = turns.get_mut(key) {
+        *count = count.saturating_sub(1);
+        if *count == 0 {
+            turns.remove(key);
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    sender_messages: HashMap<String, VecDeque<Instant>>,
+    channel_messages: HashMap<String, VecDeque<Instant>>,
+    sender_turns: HashMap<String, usize>,
+    channel_turns: HashMap<String, usize>,
+    notices: HashMap<String, Instant>,
+    last_sweep: Option<Instant>,
+}
+
+pub struct RateLimiter {
+    config: ChannelRateLimitConfig,
+    state: Mutex<State>,
+}
+
+impl RateLimiter {
+    /// `None` when `config` sets no per-sender or per-channel limit.
+    pub fn from_config(config: &ChannelRateLimitConfig) -> Option<Arc<Self>> {
+        let limited = config.user_messages_per_minute > 0
+            || config.channel_messages_per_minute > 0
+            || config.user_concurrent_turns > 0
+            || config.channel_concurrent_turns > 0;
+        limited.then(|| {
+            Arc::new(Self {
+                config: config.clone(),
+                state: Mutex::new(State::default()),
+            })
+        })
+    }
+
+    /// Admit `msg` or say why not. `interrupting` messages replace the
+    /// sender's running turn, so they skip the sender's turn limit.
+    pub fn admit(
+        self: &Arc<Self>,
+        msg: &ChannelMessage,
+        interrupting: bool,
+    ) -> Result<TurnPermit, Rejection> {
+        self.admit_at(msg, interrupting, Instant::now())
+    }
+
+    fn admit_at(
+        self: &Arc<Self>,
+        msg: &ChannelMessage,
+        interrupting: bool,
+        now: Instant,
+    ) -> Result<TurnPermit, Rejection> {
+        let sender_key = format!("{}:{}", msg.channel, msg.sender);
+        let config = &self.config;
+        let mut state = self.state.lock();
+        state.sweep(now, Duration::from_secs(config.notice_cooldown_secs));
+
+        let sender_wait = window_wait(
+            state.sender_messages.get_mut(&sender_key),
+            config.user_messages_per_minute,
+            now,
+        );
+        let channel_wait = window_wait(
+            state.channel_messages.get_mut(&msg.channel),
+            config.channel_messages_per_minute,
+            now,
+        );
+        if let Some(retry_after) = sender_wait.max(channel_wait) {
+            return Err(Rejection::Rate { retry_after });
+        }
+        let running = |turns: &HashMap<String, usize>, key: &str| -> usize {
+            turns.get(key).copied().unwrap_or(0)
+        };
+        if !interrupting
+            && config.user_concurrent_turns > 0
+            && running(&state.sender_turns, &sender_key) >= config.user_concurrent_turns
+        {
+            return Err(Rejection::SenderBusy);
+        }
+        if config.channel_concurrent_turns > 0
+            && running(&state.channel_turns, &msg.channel) >= config.channel_concurrent_turns
+        {
+            return Err(Rejection::ChannelBusy);
+        }
+
+        if config.user_messages_per_minute > 0 {
+            state
+                .sender_messages
+                .entry(sender_key.clone())
+                .or_default()
+                .push_back(now);
+        }
+        if config.channel_messages_per_minute > 0 {
+            state
+                .channel_messages
+                .entry(msg.channel.clone())
+                .or_default()
+                .push_back(now);
+        }
+        *state.sender_turns.entry(sender_key.clone()).or_default() += 1;
+        *state.channel_turns.entry(msg.channel.clone()).or_default() += 1;
+        Ok(TurnPermit {
+            limiter: Arc::clone(self),
+            sender_key,
+            channel: msg.channel.clone(),
+        })
+    }
+
+    /// Whether the sender of a rejected message should be told, i.e. they
+    /// have not been sent a backoff reply within `notice_cooldown_secs`.
+    pub fn should_notify(&self, msg: &ChannelMessage) -> bool {
+        let cooldown = Duration::from_secs(self.config.notice_cooldown_secs);
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        let key = format!("{}:{}", msg.channel, msg.sender);
+        if state
+            .notices
+            .get(&key)
+            .is_some_and(|sent| now.duration_since(*sent) < cooldown)
+        {
+            return false;
+        }
+        state.notices.insert(key, now);
+        true
+    }
+}
+
+impl State {
+    fn sweep(&mut self, now: Instant, notice_cooldown: Duration) {
+        if self
+            .last_sweep
+            .is_some_and(|last| now.duration_since(last) < SWEEP_INTERVAL)
+        {
+            return;
+        }
+        self.last_sweep = Some(now);
+        for windows in [&mut self.sender_messages, &mut self.channel_messages] {
+            windows.retain(|_, times| {
+                times
+                    .back()
+                    .is_some_and(|t| now.duration_since(*t) < WINDOW)
+            });
+        }
+        self.notices
+            .retain(|_, sent| now.duration_since(*sent) < notice_cooldown);
+    }
+}
+
+/// How long until `times` has room under `limit` messages per minute, or
+/// `None` when it has room now. Drops entries older than the window.
+fn window_wait(
+    times: Option<&mut VecDeque<Instant>>,
+    limit: u32,
+    now: Instant,
+) -> Option<Duration> {
+    let times = times?;
+    while times
+        .front()
+        .is_some_and(|t| now.duration_since(*t) >= WINDOW)
+    {
+        times.pop_front();
+    }
+    if limit == 0 || times.len() < limit as usize {
+        return None;
+    }
+    times
+        .front()
+        .map(|oldest| WINDOW.saturating_sub(now.duration_since(*oldest)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(channel: &str, sender: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "1".into(),
+            sender: sender.into(),
+            sender_name: None,
+            reply_target: sender.into(),
+            content: "hi".into(),
+            channel: channel.into(),
+            timestamp: 0,
+            thread_ts: None,
+        }
+    }
+
+    fn limiter(config: ChannelRateLimitConfig) -> Arc<RateLimiter> {
+        RateLimiter::from_config(&config).expect("limits configured")
+    }
+
+    #[test]
+    fn no_limits_means_no_limiter() {
+        assert!(RateLimiter::from_config(&ChannelRateLimitConfig::default()).is_none());
+    }
+
+    #[test]
+    fn sender_rate_resets_after_the_window() {
+        let limiter = limiter(ChannelRateLimitConfig {
+            user_messages_per_minute: 2,
+            ..ChannelRateLimitConfig::default()
+        });
+        let start = Instant::now();
+        let alice = message("telegram", "alice");
+        drop(limiter.admit_at(&alice, false, start).unwrap());
+        drop(limiter.admit_at(&alice, false, start).unwrap());
+        let Err(Rejection::Rate { retry_after }) =
+            limiter.admit_at(&alice, false, start + Duration::from_secs(10))
+        else {
+            panic!("third message should be rate limited");
+        };
+        assert_eq!(retry_after, Duration::from_secs(50));
+        assert!(limiter
+            .admit_at(&message("telegram", "bob"), false, start)
+            .is_ok());
+        assert!(limiter.admit_at(&alice, false, start + WINDOW).is_ok());
+    }
+
+    #[test]
+    fn concurrent_turns_are_held_by_the_permit() {
+        let limiter = limiter(ChannelRateLimitConfig {
+            user_concurrent_turns: 1,
+            channel_concurrent_turns: 2,
+            ..ChannelRateLimitConfig::default()
+        });
+        let now = Instant::now();
+        let alice = message("discord", "alice");
+        let first = limiter.admit_at(&alice, false, now).unwrap();
+        assert_eq!(
+            limiter.admit_at(&alice, false, now).err(),
+            Some(Rejection::SenderBusy)
+        );
+        let interrupt = limiter.admit_at(&alice, true, now).unwrap();
+        assert_eq!(
+            limiter
+                .admit_at(&message("discord", "bob"), false, now)
+                .err(),
+            Some(Rejection::ChannelBusy)
+        );
+        drop((first, interrupt));
+        assert!(limiter.admit_at(&alice, false, now).is_ok());
+    }
+
+    #[test]
+    fn backoff_replies_respect_the_cooldown() {
+        let limiter = limiter(ChannelRateLimitConfig {
+            user_messages_per_minute: 1,
+            ..ChannelRateLimitConfig::default()
+        });
+        let alice = message("slack", "alice");
+        assert!(limiter.should_notify(&alice));
+        assert!(!limiter.should_notify(&alice));
+        assert!(limiter.should_notify(&message("slack", "bob")));
+    }
+}
@@ -0,0 +1,189 @@
+//! Agent routing for channel messages.
+//!
+//! `[routing]` maps channels to `[agents.<name>]` entries, and a message that
+//! starts with `@<agent>` goes to that agent instead. A routed message is
+//! answered with the agent's provider, model, temperature, persona prompt,
+//! tool allowlist and memory scope; everything else comes from the main
+//! agent. Messages that route nowhere are handled by the main agent.
+
+use crate::config::{Config, DelegateAgentConfig, RoutingConfig};
+use std::collections::HashMap;
+
+/// Picks the agent that answers a channel message.
+#[derive(Debug, Clone, Default)]
+pub struct AgentRouter {
+    agents: HashMap<String, DelegateAgentConfig>,
+    routing: RoutingConfig,
+}
+
+/// A message routed to a named agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutedMessage {
+    pub agent: String,
+    /// Message text with any `@agent` prefix removed.
+    pub content: String,
+}
+
+impl AgentRouter {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            agents: config.agents.clone(),
+            routing: config.routing.clone(),
+        }
+    }
+
+    pub fn agent(&self, name: &str) -> Option<&DelegateAgentConfig> {
+        self.agents.get(name)
+    }
+
+    /// The agent for a message on `channel`: an `@agent` prefix first, then
+    /// the channel's entry, then `default_agent`. `None` = the main agent.
+    pub fn route(&self, channel: &str, content: &str) -> Option<RoutedMessage> {
+        if self.routing.mention_prefix {
+            if let Some((agent, rest)) = self.mention(content) {
+                return Some(RoutedMessage {
+                    agent: agent.to_string(),
+                    content: rest.to_string(),
+                });
+            }
+        }
+        let agent = self
+            .routing
+            .channels
+            .get(channel)
+            .or(self.routing.default_agent.as_ref())
+            .filter(|agent| self.agents.contains_key(agent.as_str()))?;
+        Some(RoutedMessage {
+            agent: agent.clone(),
+            content: content.to_string(),
+        })
+    }
+
+    /// `@coder fix the build` → (`coder`, `fix the build`), if `coder` is a
+    /// defined agent and some text follows.
+    fn mention<'a>(&'a self, content: &'a str) -> Option<(&'a str, &'a str)> {
+        let rest = content.trim_start().strip_prefix('@')?;
+        let end = rest.find(char::is_whitespace)?;
+        let name = rest[..end].trim_end_matches([':', ',']);
+        let (agent, _) = self.agents.get_key_value(name).or_else(|| {
+            self.agents
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        })?;
+        let text = rest[end..].trim_start();
+        (!text.is_empty()).then_some((agent.as_str(), text))
+    }
+
+    /// Tools hidden from `agent`: everything outside its `allowed_tools`
+    /// (an empty list allows every tool).
+    pub fn excluded_tools<'a>(
+        agent: &DelegateAgentConfig,
+        tool_names: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<String> {
+        if agent.allowed_tools.is_empty() {
+            return Vec::new();
+        }
+        tool_names
+            .into_iter()
+            .filter(|name| !agent.allowed_tools.iter().any(|allowed| allowed == name))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// `base` followed by the agent's persona prompt, if it has one.
+    pub fn system_prompt(base: &str, name: &str, agent: &DelegateAgentConfig) -> String {
+        match agent.system_prompt.as_deref().map(str::trim) {
+            Some(persona) if !persona.is_empty() => {
+                format!("{base}\n\n## Agent: {name}\n\n{persona}")
+            }
+            _ => base.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(model: &str) -> DelegateAgentConfig {
+        DelegateAgentConfig {
+            provider: "openrouter".into(),
+            model: model.into(),
+            system_prompt: None,
+            api_key: None,
+            temperature: None,
+            max_depth: 3,
+            agentic: false,
+            allowed_tools: Vec::new(),
+            max_iterations: 10,
+            memory_scope: None,
+            timeout_secs: None,
+            max_result_chars: 0,
+        }
+    }
+
+    fn router(default_agent: Option<&str>) -> AgentRouter {
+        AgentRouter {
+            agents: HashMap::from([
+                ("coder".to_string(), agent("code-model")),
+                ("household".to_string(), agent("chat-model")),
+            ]),
+            routing: RoutingConfig {
+                default_agent: default_agent.map(str::to_string),
+                channels: HashMap::from([("discord".to_string(), "coder".to_string())]),
+                mention_prefix: true,
+            },
+        }
+    }
+
+    #[test]
+    fn channel_entry_then_default_agent() {
+        let router = router(Some("household"));
+        assert_eq!(router.route("discord", "hi").unwrap().agent, "coder");
+        assert_eq!(router.route("telegram", "hi").unwrap().agent, "household");
+        assert_eq!(router(None).route("telegram", "hi"), None);
+    }
+
+    #[test]
+    fn mention_prefix_overrides_channel_and_is_stripped() {
+        let router = router(None);
+        assert_eq!(
+            router.route("discord", "@Household: buy milk"),
+            Some(RoutedMessage {
+                agent: "household".into(),
+                content: "buy milk".into(),
+            })
+        );
+        // Unknown names and bare mentions are ordinary text.
+        assert_eq!(router.route("slack", "@someone hello"), None);
+        assert_eq!(router.route("slack", "@coder"), None);
+
+        let mut no_prefix = router.clone();
+        no_prefix.routing.mention_prefix = false;
+        assert_eq!(
+            no_prefix
+                .route("discord", "@household buy milk")
+                .unwrap()
+                .agent,
+            "coder"
+        );
+    }
+
+    #[test]
+    fn allowlist_and_persona_apply_to_routed_agent() {
+        let mut coder = agent("code-model");
+        assert!(AgentRouter::excluded_tools(&coder, ["shell", "file_read"]).is_empty());
+        coder.allowed_tools = vec!["file_read".into()];
+        assert_eq!(
+            AgentRouter::excluded_tools(&coder, ["shell", "file_read"]),
+            vec!["shell"]
+        );
+
+        assert_eq!(AgentRouter::system_prompt("base", "coder", &coder), "base");
+        coder.system_prompt = Some("You write Rust.".into());
+        assert_eq!(
+            AgentRouter::system_prompt("base", "coder", &coder),
+            "base\n\n## Agent: coder\n\nYou write Rust."
+        );
+    }
+}
@@ -44,6 +44,9 @@ struct Envelope {
     source: Option<String>,
     #[serde(rename = "sourceNumber", default)]
     source_number: Option<String>,
+    /// Profile name of the sender.
+    #[serde(rename = "sourceName", default)]
+    source_name: Option<String>,
     #[serde(rename = "dataMessage", default)]
     data_message: Option<DataMessage>,
     #[serde(rename = "storyMessage", default)]
@@ -261,6 +264,7 @@ impl SignalChannel {
         Some(ChannelMessage {
             id: format!("sig_{timestamp}"),
             sender: sender.clone(),
+            sender_name: envelope.source_name.clone().filter(|name| !name.is_empty()),
             reply_target: target,
             content: text.to_string(),
             channel: "signal".to_string(),
@@ -483,6 +487,25 @@ impl Channel for SignalChannel {
         // auto-expire after ~15s on the client side.
         Ok(())
     }
+
+    async fn mark_read(&self, message: &ChannelMessage) -> anyhow::Result<()> {
+        // Receipts name the message by its sent timestamp, kept in the id.
+        let Some(timestamp) = message
+            .id
+            .strip_prefix("sig_")
+            .and_then(|ts| ts.parse::<u64>().ok())
+        else {
+            return Ok(());
+        };
+        let params = serde_json::json!({
+            "recipient": [&message.sender],
+            "targetTimestamp": [timestamp],
+            "type": "read",
+            "account": &self.account,
+        });
+        self.rpc_request("sendReceipt", params).await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -515,6 +538,7 @@ mod tests {
         Envelope {
             source: source_number.map(String::from),
             source_number: source_number.map(String::from),
+            source_name: None,
             data_message: message.map(|m| DataMessage {
                 message: Some(m.to_string()),
                 timestamp: Some(1_700_000_000_000),
@@ -737,6 +761,7 @@ mod tests {
         let env = Envelope {
             source: Some("uuid-123".to_string()),
             source_number: Some("+1111111111".to_string()),
+            source_name: None,
             data_message: None,
             story_message: None,
             timestamp: Some(1000),
@@ -749,6 +774,7 @@ mod tests {
         let env = Envelope {
             source: Some("uuid-123".to_string()),
             source_number: None,
+            source_name: None,
             data_message: None,
             story_message: None,
             timestamp: Some(1000),
@@ -770,6 +796,7 @@ mod tests {
         let env = Envelope {
             source: Some(uuid.to_string()),
             source_number: None,
+            source_name: None,
             data_message: Some(DataMessage {
                 message: Some("Hello from privacy user".to_string()),
                 timestamp: Some(1_700_000_000_000),
@@ -803,6 +830,7 @@ mod tests {
         let env = Envelope {
             source: Some(uuid.to_string()),
             source_number: None,
+            source_name: None,
             data_message: Some(DataMessage {
                 message: Some("Group msg from privacy user".to_string()),
                 timestamp: Some(1_700_000_000_000),
@@ -828,6 +856,7 @@ mod tests {
         let env = Envelope {
             source: None,
             source_number: None,
+            source_name: None,
             data_message: None,
             story_message: None,
             timestamp: None,
@@ -880,6 +909,7 @@ mod tests {
         let env = Envelope {
             source: Some("+1111111111".to_string()),
             source_number: Some("+1111111111".to_string()),
+            source_name: None,
             data_message: Some(DataMessage {
                 message: None,
                 timestamp: Some(1_700_000_000_000),
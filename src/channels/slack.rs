@@ -1,13 +1,24 @@
+use super::draft::{self, DraftThrottle, DRAFT_PLACEHOLDER};
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::StreamMode;
 use async_trait::async_trait;
+use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+/// Streamed drafts are cut to this many characters until finalized; Slack
+/// recommends keeping message text under 4,000 characters.
+const SLACK_MAX_DRAFT_CHARS: usize = 4000;
+
 /// Slack channel — polls conversations.history via Web API
 pub struct SlackChannel {
     bot_token: String,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    stream_mode: StreamMode,
+    drafts: DraftThrottle,
+    /// Thread of each open draft, for replies that cannot be edited in place.
+    draft_threads: Mutex<HashMap<String, String>>,
 }
 
 impl SlackChannel {
@@ -16,7 +27,56 @@ impl SlackChannel {
             bot_token,
             channel_id,
             allowed_users,
+            stream_mode: StreamMode::Off,
+            drafts: DraftThrottle::new(1000),
+            draft_threads: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.drafts = DraftThrottle::new(draft_update_interval_ms);
+        self
+    }
+
+    /// Call a Web API `method` that posts JSON and return its response.
+    /// Slack reports most errors with HTTP 200 and `"ok": false`.
+    async fn call_api(
+        &self,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let resp = self
+            .http_client()
+            .post(format!("https://slack.com/api/{method}"))
+            .bearer_auth(&self.bot_token)
+            .json(body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+        if !status.is_success() {
+            anyhow::bail!("Slack {method} failed ({status}): {body}");
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+            let err = parsed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack {method} failed: {err}");
         }
+        Ok(parsed)
     }
 
     fn http_client(&self) -> reqwest::Client {
@@ -194,35 +254,7 @@ impl Channel for SlackChannel {
             body["thread_ts"] = serde_json::json!(ts);
         }
 
-        let resp = self
-            .http_client()
-            .post("https://slack.com/api/chat.postMessage")
-            .bearer_auth(&self.bot_token)
-            .json(&body)
-            .send()
-            .await?;
-
-        let status = resp.status();
-        let body = resp
-            .text()
-            .await
-            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
-
-        if !status.is_success() {
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
-        }
-
-        // Slack returns 200 for most app-level errors; check JSON "ok" field
-        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
-        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
-            let err = parsed
-                .get("error")
-                .and_then(|e| e.as_str())
-                .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
-        }
-
-        Ok(())
+        self.call_api("chat.postMessage", &body).await.map(|_| ())
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
@@ -361,6 +393,7 @@ impl Channel for SlackChannel {
                         let channel_msg = ChannelMessage {
                             id: format!("slack_{channel_id}_{ts}"),
                             sender: user.to_string(),
+                            sender_name: None,
                             reply_target: channel_id.clone(),
                             content: text.to_string(),
                             channel: "slack".to_string(),
@@ -389,12 +422,142 @@ impl Channel for SlackChannel {
             .map(|r| r.status().is_success())
             .unwrap_or(false)
     }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+
+        let text = if message.content.is_empty() {
+            DRAFT_PLACEHOLDER
+        } else {
+            &message.content
+        };
+        let mut body = serde_json::json!({
+            "channel": message.recipient,
+            "text": draft::truncate_for_edit(text, SLACK_MAX_DRAFT_CHARS),
+        });
+        if let Some(ref ts) = message.thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+
+        let resp = self.call_api("chat.postMessage", &body).await?;
+        let ts = resp
+            .get("ts")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string);
+        if let Some(ts) = ts.as_deref() {
+            self.drafts.sent(ts);
+            if let Some(thread) = message.thread_ts.clone() {
+                self.draft_threads.lock().insert(ts.to_string(), thread);
+            }
+        }
+        Ok(ts)
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        if !self.drafts.ready(message_id) {
+            return Ok(());
+        }
+
+        let text = super::strip_tool_call_tags(text);
+        let resp = self
+            .http_client()
+            .post("https://slack.com/api/chat.update")
+            .bearer_auth(&self.bot_token)
+            .json(&serde_json::json!({
+                "channel": recipient,
+                "ts": message_id,
+                "text": draft::truncate_for_edit(&text, SLACK_MAX_DRAFT_CHARS),
+            }))
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let wait = draft::retry_after(resp.headers()).unwrap_or(Duration::from_secs(1));
+            self.drafts.back_off(message_id, wait);
+            return Ok(());
+        }
+        let body: serde_json::Value = resp.json().await.unwrap_or_default();
+        if status.is_success() && body.get("ok") == Some(&serde_json::Value::Bool(true)) {
+            self.drafts.sent(message_id);
+        } else {
+            tracing::debug!("Slack draft edit failed ({status}): {body}");
+        }
+        Ok(())
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.drafts.finish(message_id);
+        let thread = self.draft_threads.lock().remove(message_id);
+
+        let text = super::strip_tool_call_tags(text);
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id,
+            "text": text,
+        });
+        match self.call_api("chat.update", &body).await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::warn!("{e}; sending as new message");
+                if let Err(e) = self.cancel_draft(recipient, message_id).await {
+                    tracing::debug!("Failed to delete Slack draft: {e}");
+                }
+                self.send(&SendMessage::new(text, recipient).in_thread(thread))
+                    .await
+            }
+        }
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.drafts.finish(message_id);
+        self.draft_threads.lock().remove(message_id);
+        let body = serde_json::json!({ "channel": recipient, "ts": message_id });
+        self.call_api("chat.delete", &body).await.map(|_| ())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn drafts_follow_stream_mode_and_edits_are_throttled() {
+        let off = SlackChannel::new("xoxb-fake".into(), None, vec![]);
+        assert!(!off.supports_draft_updates());
+        let id = off
+            .send_draft(&SendMessage::new("draft", "C123"))
+            .await
+            .unwrap();
+        assert!(id.is_none());
+
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec![])
+            .with_streaming(StreamMode::Partial, 60_000);
+        assert!(ch.supports_draft_updates());
+        ch.drafts.sent("1700000000.000100");
+        // Within the interval the edit is skipped without a network call.
+        assert!(ch
+            .update_draft("C123", "1700000000.000100", "more text")
+            .await
+            .is_ok());
+    }
+
     #[test]
     fn slack_channel_name() {
         let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
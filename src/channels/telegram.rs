@@ -311,6 +311,7 @@ pub struct TelegramChannel {
     transcription: Option<crate::config::TranscriptionConfig>,
     voice_transcriptions: Mutex<std::collections::HashMap<String, String>>,
     workspace_dir: Option<std::path::PathBuf>,
+    workspace_quota_mb: u64,
 }
 
 impl TelegramChannel {
@@ -342,6 +343,7 @@ impl TelegramChannel {
             transcription: None,
             voice_transcriptions: Mutex::new(std::collections::HashMap::new()),
             workspace_dir: None,
+            workspace_quota_mb: 0,
         }
     }
 
@@ -351,6 +353,12 @@ impl TelegramChannel {
         self
     }
 
+    /// Count saved attachments against `[autonomy] workspace_quota_mb`.
+    pub fn with_workspace_quota_mb(mut self, quota_mb: u64) -> Self {
+        self.workspace_quota_mb = quota_mb;
+        self
+    }
+
     /// Configure streaming mode for progressive draft updates.
     pub fn with_streaming(
         mut self,
@@ -904,10 +912,10 @@ Allowlist Telegram username (without '@') or numeric user ID.",
 
     /// Attempt to parse a Telegram update as a document/photo attachment.
     ///
-    /// Downloads the file to `{workspace_dir}/telegram_files/` and returns a
-    /// `ChannelMessage` with the local file path. Returns `None` if the message
-    /// is not an attachment, workspace_dir is not configured, or the file exceeds
-    /// size limits.
+    /// Stores the file under `{workspace_dir}/telegram_files/` through the
+    /// workspace jail and returns a `ChannelMessage` with the local file path.
+    /// Returns `None` if the message is not an attachment, workspace_dir is not
+    /// configured, or the file exceeds size limits or the workspace quota.
     async fn try_parse_attachment_message(
         &self,
         update: &serde_json::Value,
@@ -965,12 +973,6 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             None
         })?;
 
-        let save_dir = workspace.join("telegram_files");
-        if let Err(e) = tokio::fs::create_dir_all(&save_dir).await {
-            tracing::warn!("Failed to create telegram_files directory: {e}");
-            return None;
-        }
-
         // Download file from Telegram
         let tg_file_path = match self.get_file_path(&attachment.file_id).await {
             Ok(p) => p,
@@ -998,11 +1000,25 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             }
         };
 
-        let local_path = save_dir.join(&local_filename);
-        if let Err(e) = tokio::fs::write(&local_path, &file_data).await {
-            tracing::warn!("Failed to save attachment to {}: {e}", local_path.display());
-            return None;
-        }
+        // The file name comes from the sender: store it through the
+        // workspace jail so it cannot leave `telegram_files/`.
+        let local_filename = crate::security::workspace::safe_file_name(&local_filename);
+        let policy = crate::security::SecurityPolicy {
+            workspace_dir: workspace.clone(),
+            workspace_quota_mb: self.workspace_quota_mb,
+            ..crate::security::SecurityPolicy::default()
+        };
+        let local_path = match policy
+            .workspace()
+            .store_upload("telegram_files", &local_filename, &file_data)
+            .await
+        {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::warn!("Failed to save attachment {local_filename}: {e}");
+                return None;
+            }
+        };
 
         // Build message content.
         // Photos with image extensions use [IMAGE:] marker so the multimodal
@@ -1024,6 +1040,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         Some(ChannelMessage {
             id: format!("telegram_{chat_id}_{message_id}"),
             sender: sender_identity,
+            sender_name: Self::sender_display_name(message),
             reply_target,
             content,
             channel: "telegram".to_string(),
@@ -1141,6 +1158,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         Some(ChannelMessage {
             id: format!("telegram_{chat_id}_{message_id}"),
             sender: sender_identity,
+            sender_name: Self::sender_display_name(message),
             reply_target,
             content,
             channel: "telegram".to_string(),
@@ -1173,6 +1191,17 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         (username, sender_id, sender_identity)
     }
 
+    /// The sender's first and last name, as Telegram shows them.
+    fn sender_display_name(message: &serde_json::Value) -> Option<String> {
+        let from = message.get("from")?;
+        let name = ["first_name", "last_name"]
+            .iter()
+            .filter_map(|key| from.get(*key).and_then(serde_json::Value::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(name.trim().to_string()).filter(|name| !name.is_empty())
+    }
+
     /// Extract reply context from a Telegram `reply_to_message`, if present.
     fn extract_reply_context(&self, message: &serde_json::Value) -> Option<String> {
         let reply = message.get("reply_to_message")?;
@@ -1297,6 +1326,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         Some(ChannelMessage {
             id: format!("telegram_{chat_id}_{message_id}"),
             sender: sender_identity,
+            sender_name: Self::sender_display_name(message),
             reply_target,
             content,
             channel: "telegram".to_string(),
@@ -2143,6 +2173,15 @@ impl Channel for TelegramChannel {
         "telegram"
     }
 
+    fn reload_allowed_users(&self, allowed_users: &[String]) -> bool {
+        let normalized = Self::normalize_allowed_users(allowed_users.to_vec());
+        match self.allowed_users.write() {
+            Ok(mut users) => *users = normalized,
+            Err(e) => *e.into_inner() = normalized,
+        }
+        true
+    }
+
     fn supports_draft_updates(&self) -> bool {
         self.stream_mode != StreamMode::Off
     }
@@ -2901,6 +2940,14 @@ mod tests {
         assert!(ch.is_user_allowed("alice"));
     }
 
+    #[test]
+    fn telegram_reload_replaces_allowed_users() {
+        let ch = TelegramChannel::new("t".into(), vec!["alice".into()], false);
+        assert!(ch.reload_allowed_users(&["@bob".into()]));
+        assert!(ch.is_user_allowed("bob"));
+        assert!(!ch.is_user_allowed("alice"));
+    }
+
     #[test]
     fn telegram_user_denied_empty() {
         let ch = TelegramChannel::new("t".into(), vec![], false);
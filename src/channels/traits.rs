@@ -5,6 +5,9 @@ use async_trait::async_trait;
 pub struct ChannelMessage {
     pub id: String,
     pub sender: String,
+    /// Display name of the sender, when the platform sends one. Shared
+    /// conversations (`session_policy = "per_chat"`) attribute turns by it.
+    pub sender_name: Option<String>,
     pub reply_target: String,
     pub content: String,
     pub channel: String,
@@ -74,7 +77,8 @@ pub trait Channel: Send + Sync {
     }
 
     /// Signal that the bot is processing a response (e.g. "typing" indicator).
-    /// Implementations should repeat the indicator as needed for their platform.
+    /// Called again every few seconds while the reply is produced (see
+    /// [`super::presence`]).
     async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
         Ok(())
     }
@@ -84,6 +88,18 @@ pub trait Channel: Send + Sync {
         Ok(())
     }
 
+    /// Mark an inbound message as read (a read receipt), once processing of
+    /// it starts.
+    async fn mark_read(&self, _message: &ChannelMessage) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Replace the allowed sender list after a config reload. Returns `false`
+    /// when the channel only reads its allowlist at startup.
+    fn reload_allowed_users(&self, _allowed_users: &[String]) -> bool {
+        false
+    }
+
     /// Whether this channel supports progressive message updates via draft edits.
     fn supports_draft_updates(&self) -> bool {
         false
@@ -167,6 +183,7 @@ mod tests {
             tx.send(ChannelMessage {
                 id: "1".into(),
                 sender: "tester".into(),
+                sender_name: None,
                 reply_target: "tester".into(),
                 content: "hello".into(),
                 channel: "dummy".into(),
@@ -183,6 +200,7 @@ mod tests {
         let message = ChannelMessage {
             id: "42".into(),
             sender: "alice".into(),
+            sender_name: None,
             reply_target: "alice".into(),
             content: "ping".into(),
             channel: "dummy".into(),
@@ -204,8 +222,20 @@ mod tests {
         let channel = DummyChannel;
 
         assert!(channel.health_check().await);
+        assert!(!channel.reload_allowed_users(&["bob".to_string()]));
         assert!(channel.start_typing("bob").await.is_ok());
         assert!(channel.stop_typing("bob").await.is_ok());
+        let message = ChannelMessage {
+            id: "1".into(),
+            sender: "bob".into(),
+            sender_name: None,
+            reply_target: "bob".into(),
+            content: "hi".into(),
+            channel: "dummy".into(),
+            timestamp: 0,
+            thread_ts: None,
+        };
+        assert!(channel.mark_read(&message).await.is_ok());
         assert!(channel
             .send(&SendMessage::new("hello", "bob"))
             .await
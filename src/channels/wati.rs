@@ -159,6 +159,7 @@ impl WatiChannel {
             id: Uuid::new_v4().to_string(),
             reply_target: normalized_phone.clone(),
             sender: normalized_phone,
+            sender_name: None,
             content: text.to_string(),
             channel: "wati".to_string(),
             timestamp,
@@ -134,10 +134,16 @@ impl WhatsAppChannel {
                                 .as_secs()
                         });
 
+                    let id = msg
+                        .get("id")
+                        .and_then(|i| i.as_str())
+                        .map_or_else(|| Uuid::new_v4().to_string(), ToString::to_string);
+
                     messages.push(ChannelMessage {
-                        id: Uuid::new_v4().to_string(),
+                        id,
                         reply_target: normalized_from.clone(),
                         sender: normalized_from,
+                        sender_name: None,
                         content,
                         channel: "whatsapp".to_string(),
                         timestamp,
@@ -217,6 +223,34 @@ impl Channel for WhatsAppChannel {
         Ok(())
     }
 
+    async fn mark_read(&self, message: &ChannelMessage) -> anyhow::Result<()> {
+        // Only Cloud API message ids (`wamid.…`) can be marked read.
+        if !message.id.starts_with("wamid.") {
+            return Ok(());
+        }
+        let url = format!(
+            "https://graph.facebook.com/v18.0/{}/messages",
+            self.endpoint_id
+        );
+        ensure_https(&url)?;
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .json(&serde_json::json!({
+                "messaging_product": "whatsapp",
+                "status": "read",
+                "message_id": message.id,
+            }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("WhatsApp read receipt failed: {}", resp.status());
+        }
+        Ok(())
+    }
+
     async fn health_check(&self) -> bool {
         // Check if we can reach the WhatsApp API
         let url = format!("https://graph.facebook.com/v18.0/{}", self.endpoint_id);
@@ -319,6 +353,7 @@ mod tests {
 
         let msgs = ch.parse_webhook_payload(&payload);
         assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].id, "wamid.xxx");
         assert_eq!(msgs[0].sender, "+1234567890");
         assert_eq!(msgs[0].content, "Hello ZeroClaw!");
         assert_eq!(msgs[0].channel, "whatsapp");
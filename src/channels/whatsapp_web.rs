@@ -381,6 +381,7 @@ impl Channel for WhatsAppWebChannel {
                                         id: uuid::Uuid::new_v4().to_string(),
                                         channel: "whatsapp".to_string(),
                                         sender: normalized.clone(),
+                                        sender_name: None,
                                         // Reply to the originating chat JID (DM or group).
                                         reply_target: chat,
                                         content: trimmed.to_string(),
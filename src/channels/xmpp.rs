@@ -0,0 +1,1270 @@
+use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::schema::XmppConfig;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, Mutex};
+use tokio_rustls::rustls;
+
+/// If no data arrives for this long the connection is considered dead.
+const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+/// Idle interval after which a whitespace keepalive is sent.
+const KEEPALIVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+const NS_STREAMS: &str = "http://etherx.jabber.org/streams";
+const NS_OMEMO: &str = "eu.siacs.conversations.axolotl";
+const NS_MUC_USER: &str = "http://jabber.org/protocol/muc#user";
+const OMEMO_NOTICE: &str = "I can't read OMEMO-encrypted messages. \
+Please disable encryption for this conversation and send your message again.";
+
+type TlsStream = tokio_rustls::client::TlsStream<TcpStream>;
+type WriteHalf = tokio::io::WriteHalf<TlsStream>;
+
+/// XMPP (Jabber) client channel.
+///
+/// Connects over STARTTLS (or direct TLS), authenticates with SASL
+/// SCRAM-SHA-256 or PLAIN, and handles one-to-one chats plus multi-user chat
+/// rooms (XEP-0045). OMEMO-encrypted messages are not decrypted; senders get a
+/// plaintext notice instead.
+pub struct XmppChannel {
+    jid: String,
+    password: String,
+    server: String,
+    port: u16,
+    direct_tls: bool,
+    resource: String,
+    rooms: Vec<String>,
+    nickname: String,
+    allowed_users: Vec<String>,
+    mention_only: bool,
+    /// Real bare JIDs of room occupants (`room@server/nick` → JID), as the
+    /// rooms announce them in presence. Anonymous rooms announce none.
+    occupants: std::sync::Mutex<HashMap<String, String>>,
+    writer: Arc<Mutex<Option<WriteHalf>>>,
+}
+
+/// A unit read from the XML stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Frame {
+    StreamStart,
+    StreamEnd,
+    Stanza(String),
+}
+
+/// What an inbound `<message>` stanza means for the bot.
+#[derive(Debug, Clone)]
+enum Inbound {
+    Message(ChannelMessage),
+    /// OMEMO-encrypted message; reply target for the notice.
+    Encrypted(String),
+    Ignore,
+}
+
+impl XmppChannel {
+    pub fn from_config(config: &XmppConfig) -> Self {
+        let (local, domain) = split_jid(&config.jid);
+        let server = config
+            .server
+            .clone()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| domain.to_string());
+        let nickname = config
+            .nickname
+            .clone()
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| local.to_string());
+
+        Self {
+            jid: bare_jid(&config.jid).to_string(),
+            password: config.password.clone(),
+            server,
+            port: config.port,
+            direct_tls: config.direct_tls,
+            resource: config.resource.clone(),
+            rooms: config
+                .rooms
+                .iter()
+                .map(|r| r.to_ascii_lowercase())
+                .collect(),
+            nickname,
+            allowed_users: config.allowed_users.clone(),
+            mention_only: config.mention_only,
+            occupants: std::sync::Mutex::new(HashMap::new()),
+            writer: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn domain(&self) -> &str {
+        split_jid(&self.jid).1
+    }
+
+    fn is_room(&self, bare: &str) -> bool {
+        self.rooms.iter().any(|r| r.eq_ignore_ascii_case(bare))
+    }
+
+    fn is_user_allowed(&self, candidates: &[&str]) -> bool {
+        self.allowed_users
+            .iter()
+            .any(|u| u == "*" || candidates.iter().any(|c| u.eq_ignore_ascii_case(c)))
+    }
+
+    fn occupants(&self) -> std::sync::MutexGuard<'_, HashMap<String, String>> {
+        self.occupants.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Note the real JID a room announces for an occupant, or forget the
+    /// occupant when they leave.
+    fn track_occupant(&self, presence: &str) {
+        let Some(from) = root_attr(presence, "from") else {
+            return;
+        };
+        let Some((room, nick)) = from.split_once('/') else {
+            return;
+        };
+        if !self.is_room(room) {
+            return;
+        }
+        let key = format!("{}/{nick}", room.to_ascii_lowercase());
+        let real = muc_real_jid(presence)
+            .filter(|_| root_attr(presence, "type").as_deref() != Some("unavailable"));
+        match real {
+            Some(jid) => self.occupants().insert(key, jid),
+            None => self.occupants().remove(&key),
+        };
+    }
+
+    /// Interpret an inbound `<message>` stanza.
+    fn parse_message(&self, stanza: &str) -> Inbound {
+        let Some(from) = root_attr(stanza, "from") else {
+            return Inbound::Ignore;
+        };
+        let kind = root_attr(stanza, "type").unwrap_or_else(|| "normal".into());
+        if kind == "error" {
+            return Inbound::Ignore;
+        }
+
+        let bare = bare_jid(&from).to_ascii_lowercase();
+        let resource = from.split_once('/').map_or("", |(_, r)| r);
+        let is_groupchat = kind == "groupchat";
+
+        // Room history replays and our own echoes are not new requests.
+        if is_groupchat && (has_child(stanza, "delay") || resource == self.nickname) {
+            return Inbound::Ignore;
+        }
+
+        let (sender, reply_target, allowed) = if is_groupchat {
+            if !self.is_room(&bare) || resource.is_empty() {
+                return Inbound::Ignore;
+            }
+            // Nicknames are picked freely by whoever joins, so only the real
+            // JID the room announced counts; without one (anonymous rooms)
+            // the room itself must be allowed.
+            let occupant = format!("{bare}/{resource}");
+            let allowed = match self.occupants().get(&occupant) {
+                Some(jid) => self.is_user_allowed(&[jid.as_str()]),
+                None => self.is_user_allowed(&[bare.as_str()]),
+            };
+            (from.clone(), bare.clone(), allowed)
+        } else {
+            if bare == self.jid.to_ascii_lowercase() {
+                return Inbound::Ignore;
+            }
+            let allowed = self.is_user_allowed(&[bare.as_str()]);
+            (bare.clone(), bare.clone(), allowed)
+        };
+
+        if !allowed {
+            tracing::warn!(
+                "XMPP: ignoring message from unauthorized user: {from}. \
+                Add to channels_config.xmpp.allowed_users in config.toml."
+            );
+            return Inbound::Ignore;
+        }
+
+        if has_child_ns(stanza, "encrypted", NS_OMEMO) {
+            return Inbound::Encrypted(reply_target);
+        }
+
+        let Some(body) = child_text(stanza, "body") else {
+            return Inbound::Ignore;
+        };
+
+        let content = if is_groupchat {
+            match strip_nick_mention(&body, &self.nickname) {
+                Some(stripped) => stripped,
+                None if self.mention_only => return Inbound::Ignore,
+                None => body.trim().to_string(),
+            }
+        } else {
+            body.trim().to_string()
+        };
+        if content.is_empty() {
+            return Inbound::Ignore;
+        }
+
+        let id = root_attr(stanza, "id").unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        Inbound::Message(ChannelMessage {
+            id: format!("xmpp_{id}"),
+            sender,
+            sender_name: None,
+            reply_target,
+            content,
+            channel: "xmpp".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: None,
+        })
+    }
+
+    fn tls_connector(&self) -> tokio_rustls::TlsConnector {
+        let root_store: rustls::RootCertStore =
+            webpki_roots::TLS_SERVER_ROOTS.iter().cloned().collect();
+        let tls_config = rustls::ClientConfig::builder()
+            .with_root_certificates(root_store)
+            .with_no_client_auth();
+        tokio_rustls::TlsConnector::from(Arc::new(tls_config))
+    }
+
+    /// Open a TCP connection and upgrade it to TLS (directly or via STARTTLS).
+    async fn open_tls(&self) -> Result<TlsStream> {
+        let addr = format!("{}:{}", self.server, self.port);
+        let mut tcp = TcpStream::connect(&addr)
+            .await
+            .with_context(|| format!("XMPP connect to {addr} failed"))?;
+
+        if !self.direct_tls {
+            let mut buffer = StanzaBuffer::default();
+            tcp.write_all(stream_header(self.domain()).as_bytes())
+                .await?;
+            let features = read_stanza(&mut tcp, &mut buffer).await?;
+            if !has_child(&features, "starttls") {
+                bail!("XMPP server does not offer STARTTLS; refusing to authenticate in plaintext");
+            }
+            tcp.write_all(b"<starttls xmlns='urn:ietf:params:xml:ns:xmpp-tls'/>")
+                .await?;
+            let reply = read_stanza(&mut tcp, &mut buffer).await?;
+            if root_name(&reply) != Some("proceed") {
+                bail!("XMPP STARTTLS was rejected by the server");
+            }
+        }
+
+        let domain = rustls::pki_types::ServerName::try_from(self.domain().to_string())?;
+        Ok(self.tls_connector().connect(domain, tcp).await?)
+    }
+
+    /// Run SASL authentication on a freshly (re)started stream.
+    async fn authenticate<R, W>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+        buffer: &mut StanzaBuffer,
+        features: &str,
+    ) -> Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let username = split_jid(&self.jid).0;
+
+        if features.contains(">SCRAM-SHA-256<") {
+            let mut scram = ScramSha256::new(username, &self.password);
+            writer
+                .write_all(
+                    format!(
+                        "<auth xmlns='urn:ietf:params:xml:ns:xmpp-sasl' mechanism='SCRAM-SHA-256'>{}</auth>",
+                        STANDARD.encode(scram.client_first())
+                    )
+                    .as_bytes(),
+                )
+                .await?;
+
+            let challenge = read_stanza(reader, buffer).await?;
+            if root_name(&challenge) != Some("challenge") {
+                bail!(
+                    "XMPP SASL authentication failed: {}",
+                    sasl_failure_reason(&challenge)
+                );
+            }
+            let server_first = decode_sasl_payload(&challenge)?;
+            let client_final = scram.client_final(&server_first)?;
+            writer
+                .write_all(
+                    format!(
+                        "<response xmlns='urn:ietf:params:xml:ns:xmpp-sasl'>{}</response>",
+                        STANDARD.encode(client_final)
+                    )
+                    .as_bytes(),
+                )
+                .await?;
+
+            let outcome = read_stanza(reader, buffer).await?;
+            if root_name(&outcome) != Some("success") {
+                bail!(
+                    "XMPP SASL authentication failed: {}",
+                    sasl_failure_reason(&outcome)
+                );
+            }
+            let server_final = decode_sasl_payload(&outcome)?;
+            if !scram.verify_server_final(&server_final) {
+                bail!("XMPP server signature mismatch during SCRAM-SHA-256");
+            }
+            return Ok(());
+        }
+
+        if features.contains(">PLAIN<") {
+            let payload = STANDARD.encode(format!("\0{username}\0{}", self.password));
+            writer
+                .write_all(
+                    format!(
+                        "<auth xmlns='urn:ietf:params:xml:ns:xmpp-sasl' mechanism='PLAIN'>{payload}</auth>"
+                    )
+                    .as_bytes(),
+                )
+                .await?;
+            let outcome = read_stanza(reader, buffer).await?;
+            if root_name(&outcome) != Some("success") {
+                bail!(
+                    "XMPP SASL authentication failed: {}",
+                    sasl_failure_reason(&outcome)
+                );
+            }
+            return Ok(());
+        }
+
+        bail!("XMPP server offers no supported SASL mechanism (need SCRAM-SHA-256 or PLAIN)")
+    }
+
+    async fn write_stanza(&self, stanza: &str) -> Result<()> {
+        let mut guard = self.writer.lock().await;
+        let writer = guard
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("XMPP not connected"))?;
+        writer.write_all(stanza.as_bytes()).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    fn message_stanza(&self, to: &str, body: &str) -> String {
+        let kind = if self.is_room(bare_jid(to)) {
+            "groupchat"
+        } else {
+            "chat"
+        };
+        format!(
+            "<message to='{}' type='{kind}' id='{}'><body>{}</body></message>",
+            escape_xml(to),
+            uuid::Uuid::new_v4(),
+            escape_xml(body)
+        )
+    }
+
+    /// Respond to iq/presence stanzas that need protocol-level handling.
+    async fn handle_control_stanza(&self, stanza: &str) -> Result<()> {
+        match root_name(stanza) {
+            Some("iq") => {
+                let kind = root_attr(stanza, "type").unwrap_or_default();
+                if kind != "get" && kind != "set" {
+                    return Ok(());
+                }
+                let id = escape_xml(&root_attr(stanza, "id").unwrap_or_default());
+                let to = root_attr(stanza, "from")
+                    .map(|from| format!(" to='{}'", escape_xml(&from)))
+                    .unwrap_or_default();
+                let reply = if kind == "get" && has_child_ns(stanza, "ping", "urn:xmpp:ping") {
+                    format!("<iq type='result' id='{id}'{to}/>")
+                } else {
+                    format!(
+                        "<iq type='error' id='{id}'{to}><error type='cancel'>\
+                        <service-unavailable xmlns='urn:ietf:params:xml:ns:xmpp-stanzas'/>\
+                        </error></iq>"
+                    )
+                };
+                self.write_stanza(&reply).await
+            }
+            Some("presence") => {
+                self.track_occupant(stanza);
+                // Let allowed users add the bot to their roster.
+                if root_attr(stanza, "type").as_deref() != Some("subscribe") {
+                    return Ok(());
+                }
+                let Some(from) = root_attr(stanza, "from") else {
+                    return Ok(());
+                };
+                let bare = bare_jid(&from).to_string();
+                if !self.is_user_allowed(&[bare.as_str()]) {
+                    tracing::warn!("XMPP: ignoring subscription request from {bare}");
+                    return Ok(());
+                }
+                let to = escape_xml(&bare);
+                self.write_stanza(&format!(
+                    "<presence to='{to}' type='subscribed'/><presence to='{to}' type='subscribe'/>"
+                ))
+                .await
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for XmppChannel {
+    fn name(&self) -> &str {
+        "xmpp"
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        let stanza = self.message_stanza(&message.recipient, &message.content);
+        self.write_stanza(&stanza).await
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+        tracing::info!(
+            "XMPP channel connecting to {}:{} as {}...",
+            self.server,
+            self.port,
+            self.jid
+        );
+
+        let tls = self.open_tls().await?;
+        let (mut reader, mut writer) = tokio::io::split(tls);
+        let mut buffer = StanzaBuffer::default();
+
+        writer
+            .write_all(stream_header(self.domain()).as_bytes())
+            .await?;
+        let features = read_stanza(&mut reader, &mut buffer).await?;
+        self.authenticate(&mut reader, &mut writer, &mut buffer, &features)
+            .await?;
+
+        // Authenticated: restart the stream and bind a resource.
+        writer
+            .write_all(stream_header(self.domain()).as_bytes())
+            .await?;
+        let _features = read_stanza(&mut reader, &mut buffer).await?;
+        writer
+            .write_all(
+                format!(
+                    "<iq type='set' id='bind_1'><bind xmlns='urn:ietf:params:xml:ns:xmpp-bind'>\
+                    <resource>{}</resource></bind></iq>",
+                    escape_xml(&self.resource)
+                )
+                .as_bytes(),
+            )
+            .await?;
+        let bound = read_stanza(&mut reader, &mut buffer).await?;
+        if root_attr(&bound, "type").as_deref() != Some("result") {
+            bail!("XMPP resource binding failed");
+        }
+        let full_jid = child_text(&bound, "jid").unwrap_or_else(|| self.jid.clone());
+        tracing::info!("XMPP channel connected as {full_jid}");
+        self.occupants().clear();
+
+        writer.write_all(b"<presence/>").await?;
+        for room in &self.rooms {
+            writer
+                .write_all(
+                    format!(
+                        "<presence to='{}/{}'><x xmlns='http://jabber.org/protocol/muc'>\
+                        <history maxstanzas='0'/></x></presence>",
+                        escape_xml(room),
+                        escape_xml(&self.nickname)
+                    )
+                    .as_bytes(),
+                )
+                .await?;
+            tracing::info!("XMPP joining room {room} as {}", self.nickname);
+        }
+        writer.flush().await?;
+
+        {
+            let mut guard = self.writer.lock().await;
+            *guard = Some(writer);
+        }
+
+        let mut chunk = vec![0_u8; 8192];
+        let mut idle = std::time::Duration::ZERO;
+        loop {
+            let frame = match buffer.next_frame() {
+                Some(frame) => frame,
+                None => {
+                    match tokio::time::timeout(KEEPALIVE_INTERVAL, reader.read(&mut chunk)).await {
+                        Ok(read) => {
+                            let n = read?;
+                            if n == 0 {
+                                bail!("XMPP connection closed by server");
+                            }
+                            idle = std::time::Duration::ZERO;
+                            buffer.extend(&chunk[..n]);
+                        }
+                        Err(_) => {
+                            idle += KEEPALIVE_INTERVAL;
+                            if idle >= READ_TIMEOUT {
+                                bail!("XMPP read timed out (no data for {READ_TIMEOUT:?})");
+                            }
+                            // Whitespace keepalive (RFC 6120 §4.6.1).
+                            self.write_stanza(" ").await?;
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            let stanza = match frame {
+                Frame::Stanza(stanza) => stanza,
+                Frame::StreamStart => continue,
+                Frame::StreamEnd => bail!("XMPP stream closed by server"),
+            };
+
+            if root_name(&stanza) != Some("message") {
+                self.handle_control_stanza(&stanza).await?;
+                continue;
+            }
+
+            match self.parse_message(&stanza) {
+                Inbound::Message(msg) => {
+                    if tx.send(msg).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                Inbound::Encrypted(reply_target) => {
+                    tracing::warn!("XMPP: received OMEMO-encrypted message from {reply_target}");
+                    let notice = self.message_stanza(&reply_target, OMEMO_NOTICE);
+                    self.write_stanza(&notice).await?;
+                }
+                Inbound::Ignore => {}
+            }
+        }
+    }
+
+    async fn health_check(&self) -> bool {
+        let addr = format!("{}:{}", self.server, self.port);
+        tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            TcpStream::connect(&addr),
+        )
+        .await
+        .is_ok_and(|conn| conn.is_ok())
+    }
+}
+
+fn stream_header(domain: &str) -> String {
+    format!(
+        "<?xml version='1.0'?><stream:stream to='{}' version='1.0' \
+        xmlns='jabber:client' xmlns:stream='{NS_STREAMS}'>",
+        escape_xml(domain)
+    )
+}
+
+/// Split `local@domain/resource` into `(local, domain)`.
+fn split_jid(jid: &str) -> (&str, &str) {
+    let bare = bare_jid(jid);
+    match bare.split_once('@') {
+        Some((local, domain)) => (local, domain),
+        None => ("", bare),
+    }
+}
+
+fn bare_jid(jid: &str) -> &str {
+    jid.split_once('/').map_or(jid, |(bare, _)| bare)
+}
+
+/// Whether `nick` appears in `text` as a word of its own, not inside
+/// another (`bot` in "the robot").
+fn mentions_word(text: &str, nick: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    text.match_indices(nick).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + nick.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+/// Return the message with a leading `nick:` / `nick,` / `@nick` mention
+/// removed, or `None` if the nickname is not mentioned as a word.
+fn strip_nick_mention(body: &str, nick: &str) -> Option<String> {
+    let trimmed = body.trim();
+    // ASCII folding keeps byte offsets aligned with `trimmed`.
+    let lower = trimmed.to_ascii_lowercase();
+    let nick_lower = nick.to_ascii_lowercase();
+    if nick_lower.is_empty() || !mentions_word(&lower, &nick_lower) {
+        return None;
+    }
+
+    let without_at = lower.strip_prefix('@').unwrap_or(&lower);
+    let offset = lower.len() - without_at.len();
+    if let Some(rest) = without_at.strip_prefix(&nick_lower) {
+        if rest.is_empty() || rest.starts_with([':', ',', ' ']) {
+            let start = offset + nick_lower.len();
+            return Some(
+                trimmed[start..]
+                    .trim_start_matches([':', ','])
+                    .trim()
+                    .to_string(),
+            );
+        }
+    }
+    Some(trimmed.to_string())
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn unescape_xml(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let decoded = after.find(';').and_then(|semi| {
+            let entity = &after[1..semi];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            ch.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &after[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &after[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Find the end (index past `>`) of the tag starting at `start`, honoring quotes.
+fn tag_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut quote: Option<u8> = None;
+    for (i, &b) in bytes.iter().enumerate().skip(start + 1) {
+        match (quote, b) {
+            (Some(q), _) if b == q => quote = None,
+            (Some(_), _) => {}
+            (None, b'"' | b'\'') => quote = Some(b),
+            (None, b'>') => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn tag_name(tag: &str) -> &str {
+    tag.trim_start_matches(['<', '/'])
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or("")
+}
+
+/// Incremental splitter turning the XML byte stream into top-level stanzas.
+#[derive(Debug, Default)]
+struct StanzaBuffer {
+    bytes: Vec<u8>,
+}
+
+impl StanzaBuffer {
+    fn extend(&mut self, data: &[u8]) {
+        self.bytes.extend_from_slice(data);
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        let bytes = &self.bytes;
+        let mut pos = 0;
+        let mut depth = 0_usize;
+        let mut stanza_start = None;
+
+        while pos < bytes.len() {
+            if bytes[pos] != b'<' {
+                pos += 1;
+                continue;
+            }
+
+            if bytes[pos..].starts_with(b"<!--") {
+                let end = find_bytes(&bytes[pos..], b"-->")? + pos + 3;
+                pos = end;
+                continue;
+            }
+
+            let end = tag_end(bytes, pos)?;
+            let tag = String::from_utf8_lossy(&bytes[pos..end]).into_owned();
+
+            if tag.starts_with("<?") {
+                pos = end;
+                if depth == 0 {
+                    self.bytes.drain(..pos);
+                    return self.next_frame();
+                }
+                continue;
+            }
+
+            let name = tag_name(&tag);
+            if tag.starts_with("</") {
+                if depth == 0 {
+                    self.bytes.drain(..end);
+                    return (name == "stream:stream")
+                        .then_some(Frame::StreamEnd)
+                        .or_else(|| self.next_frame());
+                }
+                depth -= 1;
+                if depth == 0 {
+                    let start = stanza_start.unwrap_or(0);
+                    let stanza = String::from_utf8_lossy(&bytes[start..end]).into_owned();
+                    self.bytes.drain(..end);
+                    return Some(Frame::Stanza(stanza));
+                }
+            } else if tag.ends_with("/>") {
+                if depth == 0 {
+                    self.bytes.drain(..end);
+                    return Some(Frame::Stanza(tag));
+                }
+            } else if depth == 0 && name == "stream:stream" {
+                self.bytes.drain(..end);
+                return Some(Frame::StreamStart);
+            } else {
+                if depth == 0 {
+                    stanza_start = Some(pos);
+                }
+                depth += 1;
+            }
+            pos = end;
+        }
+
+        // Only whitespace/keepalives seen so far: discard them.
+        if depth == 0 && stanza_start.is_none() {
+            self.bytes.clear();
+        }
+        None
+    }
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    buffer: &mut StanzaBuffer,
+) -> Result<Frame> {
+    let mut chunk = vec![0_u8; 8192];
+    loop {
+        if let Some(frame) = buffer.next_frame() {
+            return Ok(frame);
+        }
+        let n = tokio::time::timeout(READ_TIMEOUT, reader.read(&mut chunk))
+            .await
+            .map_err(|_| anyhow::anyhow!("XMPP read timed out (no data for {READ_TIMEOUT:?})"))??;
+        if n == 0 {
+            bail!("XMPP connection closed by server");
+        }
+        buffer.extend(&chunk[..n]);
+    }
+}
+
+/// Read the next stanza, skipping stream headers.
+async fn read_stanza<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    buffer: &mut StanzaBuffer,
+) -> Result<String> {
+    loop {
+        match read_frame(reader, buffer).await? {
+            Frame::StreamStart => {}
+            Frame::StreamEnd => bail!("XMPP stream closed by server"),
+            Frame::Stanza(stanza) => {
+                if root_name(&stanza) == Some("stream:error") {
+                    bail!(
+                        "XMPP stream error: {}",
+                        first_child_name(&stanza).unwrap_or("unknown")
+                    );
+                }
+                return Ok(stanza);
+            }
+        }
+    }
+}
+
+fn root_name(stanza: &str) -> Option<&str> {
+    let name = tag_name(stanza.trim_start());
+    (!name.is_empty()).then_some(name)
+}
+
+/// Attribute of the root element.
+fn root_attr(stanza: &str, name: &str) -> Option<String> {
+    let end = tag_end(stanza.as_bytes(), 0)?;
+    attr_value(&stanza[..end], name)
+}
+
+fn attr_value(tag: &str, name: &str) -> Option<String> {
+    for quote in ['\'', '"'] {
+        let needle = format!(" {name}={quote}");
+        if let Some(start) = tag.find(&needle) {
+            let value_start = start + needle.len();
+            let value_end = tag[value_start..].find(quote)? + value_start;
+            return Some(unescape_xml(&tag[value_start..value_end]));
+        }
+    }
+    None
+}
+
+/// Byte offset of the first `<name` element (with a proper tag boundary).
+fn find_child(stanza: &str, name: &str) -> Option<usize> {
+    let needle = format!("<{name}");
+    let mut cursor = 0;
+    while let Some(offset) = stanza[cursor..].find(&needle) {
+        let start = cursor + offset;
+        let after = start + needle.len();
+        if stanza[after..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_whitespace() || c == '>' || c == '/')
+        {
+            return Some(start);
+        }
+        cursor = after;
+    }
+    None
+}
+
+fn has_child(stanza: &str, name: &str) -> bool {
+    find_child(stanza, name).is_some()
+}
+
+fn has_child_ns(stanza: &str, name: &str, ns: &str) -> bool {
+    let Some(start) = find_child(stanza, name) else {
+        return false;
+    };
+    tag_end(stanza.as_bytes(), start)
+        .and_then(|end| attr_value(&stanza[start..end], "xmlns"))
+        .is_some_and(|xmlns| xmlns == ns)
+}
+
+fn child_text(stanza: &str, name: &str) -> Option<String> {
+    let start = find_child(stanza, name)?;
+    let open_end = tag_end(stanza.as_bytes(), start)?;
+    if stanza[..open_end].ends_with("/>") {
+        return Some(String::new());
+    }
+    let close = format!("</{name}>");
+    let close_start = stanza[open_end..].find(&close)? + open_end;
+    Some(unescape_xml(&stanza[open_end..close_start]))
+}
+
+/// Bare real JID from a `<x xmlns='…muc#user'><item jid='…'/></x>` child.
+/// Only trusted on presence: the room writes it there, while a message's
+/// payload comes from the occupant.
+fn muc_real_jid(stanza: &str) -> Option<String> {
+    let mut cursor = 0;
+    while let Some(offset) = find_child(&stanza[cursor..], "x") {
+        let start = cursor + offset;
+        let end = tag_end(stanza.as_bytes(), start)?;
+        cursor = end;
+        if attr_value(&stanza[start..end], "xmlns").as_deref() != Some(NS_MUC_USER)
+            || stanza[..end].ends_with("/>")
+        {
+            continue;
+        }
+        let body = &stanza[end..];
+        let body = body.find("</x>").map_or(body, |close| &body[..close]);
+        let item = find_child(body, "item")?;
+        let item_end = tag_end(body.as_bytes(), item)?;
+        return attr_value(&body[item..item_end], "jid")
+            .map(|jid| bare_jid(&jid).to_ascii_lowercase());
+    }
+    None
+}
+
+fn first_child_name(stanza: &str) -> Option<&str> {
+    let open_end = tag_end(stanza.as_bytes(), 0)?;
+    let next = stanza[open_end..].find('<')? + open_end;
+    let name = tag_name(&stanza[next..]);
+    (!name.is_empty()).then_some(name)
+}
+
+fn decode_sasl_payload(stanza: &str) -> Result<String> {
+    let open_end = tag_end(stanza.as_bytes(), 0).unwrap_or(stanza.len());
+    let inner = stanza[open_end..]
+        .rsplit_once("</")
+        .map_or("", |(inner, _)| inner)
+        .trim();
+    let decoded = STANDARD
+        .decode(inner)
+        .context("invalid base64 in XMPP SASL payload")?;
+    Ok(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+fn sasl_failure_reason(stanza: &str) -> String {
+    first_child_name(stanza)
+        .filter(|name| *name != "text")
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// SASL SCRAM-SHA-256 client (RFC 5802 / RFC 7677) without channel binding.
+struct ScramSha256 {
+    password: String,
+    client_nonce: String,
+    client_first_bare: String,
+    server_signature: Option<Vec<u8>>,
+}
+
+impl ScramSha256 {
+    fn new(username: &str, password: &str) -> Self {
+        Self::with_nonce(
+            username,
+            password,
+            &uuid::Uuid::new_v4().simple().to_string(),
+        )
+    }
+
+    fn with_nonce(username: &str, password: &str, nonce: &str) -> Self {
+        let username = username.replace('=', "=3D").replace(',', "=2C");
+        Self {
+            password: password.to_string(),
+            client_nonce: nonce.to_string(),
+            client_first_bare: format!("n={username},r={nonce}"),
+            server_signature: None,
+        }
+    }
+
+    fn client_first(&self) -> String {
+        format!("n,,{}", self.client_first_bare)
+    }
+
+    fn client_final(&mut self, server_first: &str) -> Result<String> {
+        use ring::{digest, hmac, pbkdf2};
+
+        let mut nonce = None;
+        let mut salt = None;
+        let mut iterations = None;
+        for part in server_first.split(',') {
+            if let Some(value) = part.strip_prefix("r=") {
+                nonce = Some(value);
+            } else if let Some(value) = part.strip_prefix("s=") {
+                salt = Some(STANDARD.decode(value).context("invalid SCRAM salt")?);
+            } else if let Some(value) = part.strip_prefix("i=") {
+                iterations = value.parse::<u32>().ok();
+            }
+        }
+        let (Some(nonce), Some(salt), Some(iterations)) = (nonce, salt, iterations) else {
+            bail!("malformed SCRAM server-first message");
+        };
+        if !nonce.starts_with(&self.client_nonce) {
+            bail!("SCRAM server nonce does not extend client nonce");
+        }
+        let iterations = std::num::NonZeroU32::new(iterations)
+            .ok_or_else(|| anyhow::anyhow!("SCRAM iteration count must be positive"))?;
+
+        let mut salted = [0_u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            &salt,
+            self.password.as_bytes(),
+            &mut salted,
+        );
+        let salted_key = hmac::Key::new(hmac::HMAC_SHA256, &salted);
+        let client_key = hmac::sign(&salted_key, b"Client Key");
+        let stored_key = digest::digest(&digest::SHA256, client_key.as_ref());
+
+        let without_proof = format!("c=biws,r={nonce}");
+        let auth_message = format!("{},{server_first},{without_proof}", self.client_first_bare);
+
+        let client_signature = hmac::sign(
+            &hmac::Key::new(hmac::HMAC_SHA256, stored_key.as_ref()),
+            auth_message.as_bytes(),
+        );
+        let proof: Vec<u8> = client_key
+            .as_ref()
+            .iter()
+            .zip(client_signature.as_ref())
+            .map(|(a, b)| a ^ b)
+            .collect();
+
+        let server_key = hmac::sign(&salted_key, b"Server Key");
+        let server_signature = hmac::sign(
+            &hmac::Key::new(hmac::HMAC_SHA256, server_key.as_ref()),
+            auth_message.as_bytes(),
+        );
+        self.server_signature = Some(server_signature.as_ref().to_vec());
+
+        Ok(format!("{without_proof},p={}", STANDARD.encode(proof)))
+    }
+
+    fn verify_server_final(&self, server_final: &str) -> bool {
+        let Some(expected) = &self.server_signature else {
+            return false;
+        };
+        server_final
+            .split(',')
+            .find_map(|part| part.strip_prefix("v="))
+            .and_then(|value| STANDARD.decode(value).ok())
+            .is_some_and(|value| &value == expected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_config() -> XmppConfig {
+        XmppConfig {
+            jid: "bot@example.org".into(),
+            password: "secret".into(),
+            server: None,
+            port: 5222,
+            direct_tls: false,
+            resource: "zeroclaw".into(),
+            rooms: vec!["Lounge@conference.example.org".into()],
+            nickname: None,
+            allowed_users: vec!["alice@example.org".into(), "carol@example.org".into()],
+            mention_only: true,
+        }
+    }
+
+    fn channel() -> XmppChannel {
+        XmppChannel::from_config(&make_config())
+    }
+
+    #[test]
+    fn from_config_derives_server_and_nickname() {
+        let ch = channel();
+        assert_eq!(ch.server, "example.org");
+        assert_eq!(ch.nickname, "bot");
+        assert_eq!(ch.name(), "xmpp");
+        assert!(ch.is_room("lounge@conference.example.org"));
+    }
+
+    #[test]
+    fn parses_direct_chat_message() {
+        let ch = channel();
+        let stanza = "<message from='Alice@example.org/phone' type='chat' id='m1'>\
+            <body>hi &amp; hello</body></message>";
+        let Inbound::Message(msg) = ch.parse_message(stanza) else {
+            panic!("expected message");
+        };
+        assert_eq!(msg.sender, "alice@example.org");
+        assert_eq!(msg.reply_target, "alice@example.org");
+        assert_eq!(msg.content, "hi & hello");
+        assert_eq!(msg.id, "xmpp_m1");
+    }
+
+    #[test]
+    fn rejects_unauthorized_and_bodyless_messages() {
+        let ch = channel();
+        let stranger =
+            "<message from='mallory@evil.example/x' type='chat'><body>hi</body></message>";
+        assert!(matches!(ch.parse_message(stranger), Inbound::Ignore));
+
+        let chat_state = "<message from='alice@example.org/x' type='chat'>\
+            <composing xmlns='http://jabber.org/protocol/chatstates'/></message>";
+        assert!(matches!(ch.parse_message(chat_state), Inbound::Ignore));
+    }
+
+    #[test]
+    fn groupchat_requires_mention_and_skips_history() {
+        let ch = channel();
+        ch.track_occupant(
+            "<presence from='lounge@conference.example.org/carol'>\
+            <x xmlns='http://jabber.org/protocol/muc#user'>\
+            <item affiliation='member' jid='carol@example.org/laptop' role='participant'/></x></presence>",
+        );
+        let mention = "<message from='lounge@conference.example.org/carol' type='groupchat'>\
+            <body>bot: what time is it?</body></message>";
+        let Inbound::Message(msg) = ch.parse_message(mention) else {
+            panic!("expected message");
+        };
+        assert_eq!(msg.reply_target, "lounge@conference.example.org");
+        assert_eq!(msg.sender, "lounge@conference.example.org/carol");
+        assert_eq!(msg.content, "what time is it?");
+
+        let chatter = "<message from='lounge@conference.example.org/carol' type='groupchat'>\
+            <body>just chatting</body></message>";
+        assert!(matches!(ch.parse_message(chatter), Inbound::Ignore));
+
+        let history = "<message from='lounge@conference.example.org/carol' type='groupchat'>\
+            <body>bot: old</body><delay xmlns='urn:xmpp:delay' stamp='2025-01-01T00:00:00Z'/></message>";
+        assert!(matches!(ch.parse_message(history), Inbound::Ignore));
+
+        let own = "<message from='lounge@conference.example.org/bot' type='groupchat'>\
+            <body>bot: echo</body></message>";
+        assert!(matches!(ch.parse_message(own), Inbound::Ignore));
+    }
+
+    #[test]
+    fn groupchat_allowlist_matches_real_jids_not_nicknames() {
+        let ch = channel();
+        let from_alice = "<message from='lounge@conference.example.org/alice' type='groupchat'>\
+            <body>bot: delete everything</body></message>";
+        // Anonymous room: the nickname alone proves nothing.
+        assert!(matches!(ch.parse_message(from_alice), Inbound::Ignore));
+
+        // Mallory joins as "alice"; the room announces the real JID.
+        ch.track_occupant(
+            "<presence from='lounge@conference.example.org/alice'>\
+            <x xmlns='vcard-temp:x:update'/>\
+            <x xmlns='http://jabber.org/protocol/muc#user'>\
+            <item jid='mallory@evil.example/x' role='participant'/></x></presence>",
+        );
+        assert!(matches!(ch.parse_message(from_alice), Inbound::Ignore));
+
+        // A real JID smuggled into the message payload is not trusted.
+        let smuggled = "<message from='lounge@conference.example.org/alice' type='groupchat'>\
+            <x xmlns='http://jabber.org/protocol/muc#user'><item jid='alice@example.org'/></x>\
+            <body>bot: delete everything</body></message>";
+        assert!(matches!(ch.parse_message(smuggled), Inbound::Ignore));
+
+        ch.track_occupant(
+            "<presence from='lounge@conference.example.org/alice' type='unavailable'>\
+            <x xmlns='http://jabber.org/protocol/muc#user'><item jid='mallory@evil.example/x'/></x></presence>",
+        );
+        ch.track_occupant(
+            "<presence from='lounge@conference.example.org/alice'>\
+            <x xmlns='http://jabber.org/protocol/muc#user'><item jid='Alice@example.org/phone'/></x></presence>",
+        );
+        assert!(matches!(ch.parse_message(from_alice), Inbound::Message(_)));
+
+        let mut config = make_config();
+        config.allowed_users = vec!["lounge@conference.example.org".into()];
+        let room_allowed = XmppChannel::from_config(&config);
+        let anyone = "<message from='lounge@conference.example.org/dave' type='groupchat'>\
+            <body>bot: hi</body></message>";
+        assert!(matches!(
+            room_allowed.parse_message(anyone),
+            Inbound::Message(_)
+        ));
+    }
+
+    #[test]
+    fn omemo_messages_get_notice() {
+        let ch = channel();
+        let stanza = "<message from='alice@example.org/x' type='chat'>\
+            <encrypted xmlns='eu.siacs.conversations.axolotl'><header sid='1'/></encrypted>\
+            <body>I sent you an OMEMO encrypted message</body></message>";
+        assert!(matches!(
+            ch.parse_message(stanza),
+            Inbound::Encrypted(target) if target == "alice@example.org"
+        ));
+    }
+
+    #[test]
+    fn message_stanza_picks_type_and_escapes() {
+        let ch = channel();
+        let room = ch.message_stanza("lounge@conference.example.org", "a < b");
+        assert!(room.contains("type='groupchat'"));
+        assert!(room.contains("<body>a &lt; b</body>"));
+
+        let chat = ch.message_stanza("alice@example.org", "hi");
+        assert!(chat.contains("type='chat'"));
+    }
+
+    #[test]
+    fn stanza_buffer_frames_stream() {
+        let mut buffer = StanzaBuffer::default();
+        buffer.extend(
+            b"<?xml version='1.0'?><stream:stream xmlns='jabber:client' id='x'>\
+            <stream:features><starttls xmlns='urn:ietf:params:xml:ns:xmpp-tls'><required/></starttls></stream:features> \
+            <message to='a' body='x>y'><body>hel",
+        );
+        assert_eq!(buffer.next_frame(), Some(Frame::StreamStart));
+        let Some(Frame::Stanza(features)) = buffer.next_frame() else {
+            panic!("expected features");
+        };
+        assert!(has_child(&features, "starttls"));
+        assert_eq!(buffer.next_frame(), None);
+
+        buffer.extend(b"lo</body></message><proceed xmlns='urn:ietf:params:xml:ns:xmpp-tls'/></stream:stream>");
+        let Some(Frame::Stanza(message)) = buffer.next_frame() else {
+            panic!("expected message");
+        };
+        assert_eq!(child_text(&message, "body").as_deref(), Some("hello"));
+        assert_eq!(root_attr(&message, "body").as_deref(), Some("x>y"));
+        assert_eq!(
+            buffer.next_frame(),
+            Some(Frame::Stanza(
+                "<proceed xmlns='urn:ietf:params:xml:ns:xmpp-tls'/>".into()
+            ))
+        );
+        assert_eq!(buffer.next_frame(), Some(Frame::StreamEnd));
+    }
+
+    #[test]
+    fn strip_nick_mention_variants() {
+        assert_eq!(
+            strip_nick_mention("Bot, hello", "bot").as_deref(),
+            Some("hello")
+        );
+        assert_eq!(
+            strip_nick_mention("@bot hello", "bot").as_deref(),
+            Some("hello")
+        );
+        assert_eq!(
+            strip_nick_mention("ask bot about it", "bot").as_deref(),
+            Some("ask bot about it")
+        );
+        assert_eq!(strip_nick_mention("nothing here", "bot"), None);
+        assert_eq!(strip_nick_mention("the robot broke", "bot"), None);
+        assert_eq!(strip_nick_mention("bots: all of them", "bot"), None);
+        assert_eq!(
+            strip_nick_mention("thanks, bot!", "bot").as_deref(),
+            Some("thanks, bot!")
+        );
+    }
+
+    #[test]
+    fn scram_sha256_matches_rfc7677_vector() {
+        let mut scram = ScramSha256::with_nonce("user", "pencil", "rOprNGfwEbeRWgbNEkqO");
+        assert_eq!(scram.client_first(), "n,,n=user,r=rOprNGfwEbeRWgbNEkqO");
+
+        let server_first = "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+            s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+        let client_final = scram.client_final(server_first).unwrap();
+        assert_eq!(
+            client_final,
+            "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+            p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ="
+        );
+        assert!(scram.verify_server_final("v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4="));
+        assert!(!scram.verify_server_final("v=AAAA"));
+    }
+
+    #[test]
+    fn scram_rejects_foreign_nonce() {
+        let mut scram = ScramSha256::with_nonce("user", "pencil", "abc");
+        assert!(scram.client_final("r=xyz,s=QUJD,i=4096").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Values offered by shell completion for arguments clap cannot know
+//! statically: stored session names and IDs, and cached model IDs.
+//!
+//! These run inside `COMPLETE=<shell> zeroclaw …` on every Tab press, so
+//! they only read what is already on disk and never create config, stores
+//! or log output.
+
+use crate::config::Config;
+use clap_complete::CompletionCandidate;
+use std::path::{Path, PathBuf};
+
+/// Sessions offered, most recently active first.
+const SESSION_CANDIDATE_LIMIT: usize = 200;
+
+/// The workspace directory, resolved the way `Config::load_or_init` does
+/// but without creating anything.
+fn workspace_dir() -> Option<PathBuf> {
+    // Completion may run inside or outside the async runtime; resolve on a
+    // thread of its own either way.
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .ok()?;
+                runtime.block_on(Config::resolve_paths()).ok()
+            })
+            .join()
+            .ok()
+            .flatten()
+    })
+    .map(|(_, workspace_dir)| workspace_dir)
+}
+
+/// Session names and IDs for `--session` and `zeroclaw sessions <cmd>`.
+pub fn sessions() -> Vec<CompletionCandidate> {
+    workspace_dir()
+        .map(|dir| sessions_in(&dir))
+        .unwrap_or_default()
+}
+
+fn sessions_in(workspace_dir: &Path) -> Vec<CompletionCandidate> {
+    let db = workspace_dir.join("state").join("conversations.db");
+    if !db.exists() {
+        return Vec::new();
+    }
+    let Ok(store) = crate::conversations::ConversationStore::open(workspace_dir) else {
+        return Vec::new();
+    };
+    let sessions = store
+        .list_sessions(SESSION_CANDIDATE_LIMIT)
+        .unwrap_or_default();
+    let mut candidates = Vec::new();
+    for session in sessions {
+        let help = format!("{} · {} messages", session.channel, session.message_count);
+        if let Some(name) = session.name.as_deref() {
+            candidates.push(CompletionCandidate::new(name).help(Some(help.clone().into())));
+        }
+        candidates.push(CompletionCandidate::new(&session.id).help(Some(help.into())));
+    }
+    candidates
+}
+
+/// Model IDs from the cache `zeroclaw models refresh` writes, for `--model`.
+pub fn models() -> Vec<CompletionCandidate> {
+    workspace_dir()
+        .map(|dir| models_in(&dir))
+        .unwrap_or_default()
+}
+
+fn models_in(workspace_dir: &Path) -> Vec<CompletionCandidate> {
+    #[derive(serde::Deserialize)]
+    struct Cache {
+        entries: Vec<Entry>,
+    }
+    #[derive(serde::Deserialize)]
+    struct Entry {
+        provider: String,
+        models: Vec<String>,
+    }
+
+    let path = workspace_dir.join("state").join("models_cache.json");
+    let Some(cache) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Cache>(&raw).ok())
+    else {
+        return Vec::new();
+    };
+    let mut seen = std::collections::HashSet::new();
+    cache
+        .entries
+        .into_iter()
+        .flat_map(|entry| {
+            let provider = entry.provider;
+            entry
+                .models
+                .into_iter()
+                .map(move |model| (model, provider.clone()))
+        })
+        .filter(|(model, _)| seen.insert(model.clone()))
+        .map(|(model, provider)| CompletionCandidate::new(model).help(Some(provider.into())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(candidates: &[CompletionCandidate]) -> Vec<String> {
+        candidates
+            .iter()
+            .map(|c| c.get_value().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn candidates_come_from_the_session_store_and_model_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(sessions_in(dir.path()).is_empty());
+        assert!(models_in(dir.path()).is_empty());
+
+        let store = crate::conversations::ConversationStore::open(dir.path()).unwrap();
+        store.ensure_session("cli:abc", "cli", "user").unwrap();
+        store.rename_session("cli:abc", Some("work")).unwrap();
+        assert_eq!(values(&sessions_in(dir.path())), ["work", "cli:abc"]);
+
+        std::fs::write(
+            dir.path().join("state/models_cache.json"),
+            r#"{"entries":[
+                {"provider":"openai","fetched_at_unix":1,"models":["gpt-4o","o3"]},
+                {"provider":"openrouter","fetched_at_unix":1,"models":["gpt-4o","x/y"]}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(values(&models_in(dir.path())), ["gpt-4o", "o3", "x/y"]);
+    }
+}
@@ -0,0 +1,211 @@
+//! Comments for a serialized `config.toml`.
+//!
+//! The descriptions come from the config's JSON Schema, i.e. the doc comments
+//! on the config structs, so a freshly written file documents itself without
+//! a second copy of the text to keep in sync.
+
+use super::Config;
+use serde_json::Value;
+
+/// Insert a `# description` comment above every section header and key of
+/// `toml` (as produced by `toml::to_string_pretty` for a [`Config`]).
+pub fn annotate(toml: &str) -> String {
+    let schema = schemars::schema_for!(Config).to_value();
+    let mut out = String::with_capacity(toml.len() * 2);
+    let mut section: Vec<String> = Vec::new();
+    let mut in_multiline_string = false;
+
+    for line in toml.lines() {
+        if in_multiline_string {
+            in_multiline_string = !closes_multiline_string(line);
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+
+        let path = if let Some(header) = section_header(line) {
+            section = split_key(header);
+            Some(section.clone())
+        } else if let Some(key) = key_of(line) {
+            let mut path = section.clone();
+            path.extend(split_key(key));
+            Some(path)
+        } else {
+            None
+        };
+        if let Some(description) = path.and_then(|path| describe(&schema, &path)) {
+            if section_header(line).is_some() && !out.is_empty() && !out.ends_with("\n\n") {
+                out.push('\n');
+            }
+            for comment in description.lines() {
+                let comment = comment.trim_end();
+                if comment.is_empty() {
+                    out.push_str("#\n");
+                } else {
+                    out.push_str("# ");
+                    out.push_str(comment);
+                    out.push('\n');
+                }
+            }
+        }
+
+        out.push_str(line);
+        out.push('\n');
+        if key_of(line).is_some() && opens_multiline_string(line) {
+            in_multiline_string = true;
+        }
+    }
+    out
+}
+
+/// `a.b` for `[a.b]` and `[[a.b]]` lines.
+fn section_header(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let inner = line
+        .strip_prefix("[[")
+        .and_then(|l| l.strip_suffix("]]"))
+        .or_else(|| line.strip_prefix('[').and_then(|l| l.strip_suffix(']')))?;
+    Some(inner.trim())
+}
+
+/// The key of a top-level `key = value` line. Indented lines belong to a
+/// multi-line array or inline table and are skipped.
+fn key_of(line: &str) -> Option<&str> {
+    if line.starts_with(char::is_whitespace) || line.starts_with(['[', '#', ']', '}']) {
+        return None;
+    }
+    let (key, _) = line.split_once(" = ")?;
+    Some(key.trim())
+}
+
+/// Split a dotted TOML key, honouring quoted segments.
+fn split_key(key: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    for c in key.chars() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '.') => segments.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    segments.push(current.trim().to_string());
+    segments
+}
+
+fn opens_multiline_string(line: &str) -> bool {
+    ["\"\"\"", "'''"]
+        .iter()
+        .any(|delim| line.matches(delim).count() % 2 == 1)
+}
+
+fn closes_multiline_string(line: &str) -> bool {
+    opens_multiline_string(line)
+}
+
+/// The description of the schema at `path`, preferring the field's own doc
+/// comment over the doc comment of its type.
+fn describe(root: &Value, path: &[String]) -> Option<String> {
+    let mut schema = root;
+    let mut field: Option<&Value> = None;
+    for segment in path {
+        let object = resolve(root, schema);
+        let next = object
+            .get("properties")
+            .and_then(|p| p.get(segment))
+            .or_else(|| object.get("additionalProperties").filter(|v| v.is_object()))
+            .or_else(|| {
+                // `[[custom_tools]]` and `custom_tools.0` address array items.
+                let items = object.get("items")?;
+                segment.parse::<usize>().ok().map(|_| items)
+            })?;
+        field = Some(next);
+        schema = next;
+    }
+    let field = field?;
+    let text = field
+        .get("description")
+        .or_else(|| resolve(root, field).get("description"))?
+        .as_str()?;
+    Some(text.trim().to_string())
+}
+
+/// Follow `$ref`, and the non-null branch of `anyOf`/`oneOf`/`allOf`, to the
+/// schema that carries `properties`.
+fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        if let Some(target) = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+        {
+            return resolve(root, target);
+        }
+    }
+    for combinator in ["anyOf", "oneOf", "allOf"] {
+        if let Some(branches) = schema.get(combinator).and_then(Value::as_array) {
+            if let Some(branch) = branches
+                .iter()
+                .find(|b| b.get("type").and_then(Value::as_str) != Some("null"))
+            {
+                return resolve(root, branch);
+            }
+        }
+    }
+    // Arrays of structs: `[[custom_tools]]` describes the item type.
+    if let Some(items) = schema.get("items").filter(|i| i.get("$ref").is_some()) {
+        if schema.get("properties").is_none() {
+            return resolve(root, items);
+        }
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_and_sections_get_their_doc_comments() {
+        let annotated = annotate(
+            "default_provider = \"openrouter\"\ndefault_temperature = 0.7\n\n[gateway]\nport = 42617\n",
+        );
+        assert!(annotated.contains("# Default provider ID or alias"));
+        let port = annotated.find("port = 42617").unwrap();
+        let comment = annotated[..port].rfind("\n# ").unwrap();
+        assert!(annotated[..port].rfind("[gateway]").unwrap() < comment);
+        // The original lines are kept verbatim and in order.
+        let stripped: Vec<&str> = annotated
+            .lines()
+            .filter(|l| !l.starts_with('#') && !l.is_empty())
+            .collect();
+        assert_eq!(
+            stripped,
+            vec![
+                "default_provider = \"openrouter\"",
+                "default_temperature = 0.7",
+                "[gateway]",
+                "port = 42617"
+            ]
+        );
+    }
+
+    #[test]
+    fn annotated_default_config_still_parses() {
+        let toml = toml::to_string_pretty(&Config::default()).unwrap();
+        let annotated = annotate(&toml);
+        assert!(annotated.lines().filter(|l| l.starts_with("# ")).count() > 20);
+        let reparsed: toml::Table = toml::from_str(&annotated).unwrap();
+        let original: toml::Table = toml::from_str(&toml).unwrap();
+        assert_eq!(reparsed, original);
+    }
+
+    #[test]
+    fn quoted_keys_split_on_unquoted_dots_only() {
+        assert_eq!(
+            split_key("agents.\"a.b\".model"),
+            vec!["agents", "a.b", "model"]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! `ZEROCLAW__<KEY>__<KEY>=value` overrides for any config key.
+//!
+//! Each variable names a key path with double underscores between segments:
+//! `ZEROCLAW__GATEWAY__PORT=8080` sets `gateway.port`, and
+//! `ZEROCLAW__CHANNELS_CONFIG__TELEGRAM__BOT_TOKEN=...` sets
+//! `channels_config.telegram.bot_token`. Segments are lowercased. Overrides
+//! are applied to the parsed file before it is deserialized, so they are
+//! type-checked and reported exactly like keys written in `config.toml`.
+
+use super::Config;
+use anyhow::{bail, Result};
+use toml::{Table, Value};
+
+/// Prefix of override variables.
+pub const PREFIX: &str = "ZEROCLAW__";
+
+/// Whether any override variable is set.
+pub fn present() -> bool {
+    std::env::vars_os().any(|(name, _)| name.to_string_lossy().starts_with(PREFIX))
+}
+
+/// Apply overrides from the process environment to a parsed config file.
+/// Returns the dotted keys that were set.
+pub fn apply(table: &mut Table) -> Result<Vec<String>> {
+    apply_from(table, std::env::vars())
+}
+
+fn apply_from(
+    table: &mut Table,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<Vec<String>> {
+    let mut overrides: Vec<(Vec<String>, String)> = vars
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let path = name.strip_prefix(PREFIX)?;
+            let segments: Vec<String> = path.split("__").map(str::to_lowercase).collect();
+            (!segments.iter().any(String::is_empty)).then_some((segments, value))
+        })
+        .collect();
+    if overrides.is_empty() {
+        return Ok(Vec::new());
+    }
+    // Parents first, so `ZEROCLAW__GATEWAY__PORT` refines `ZEROCLAW__GATEWAY`.
+    overrides.sort();
+    overrides.sort_by_key(|(segments, _)| segments.len());
+
+    let defaults = Value::try_from(Config::default()).ok();
+    let mut applied = Vec::with_capacity(overrides.len());
+    for (segments, raw) in overrides {
+        let key = segments.join(".");
+        let template = lookup(table, &segments)
+            .or_else(|| defaults.as_ref().and_then(|d| lookup_value(d, &segments)))
+            .cloned();
+        set(
+            table,
+            &segments,
+            typed_value(&raw, template.as_ref()),
+            defaults.as_ref(),
+        )
+        .map_err(|e| anyhow::anyhow!("{PREFIX}{}: {e}", name_of(&segments)))?;
+        applied.push(key);
+    }
+    Ok(applied)
+}
+
+fn name_of(segments: &[String]) -> String {
+    segments
+        .iter()
+        .map(|s| s.to_uppercase())
+        .collect::<Vec<_>>()
+        .join("__")
+}
+
+fn lookup<'a>(table: &'a Table, segments: &[String]) -> Option<&'a Value> {
+    let (first, rest) = segments.split_first()?;
+    let value = table.get(first)?;
+    if rest.is_empty() {
+        Some(value)
+    } else {
+        lookup_value(value, rest)
+    }
+}
+
+fn lookup_value<'a>(value: &'a Value, segments: &[String]) -> Option<&'a Value> {
+    match value {
+        Value::Table(table) => lookup(table, segments),
+        Value::Array(items) => {
+            let (first, rest) = segments.split_first()?;
+            let item = items.get(first.parse::<usize>().ok()?)?;
+            if rest.is_empty() {
+                Some(item)
+            } else {
+                lookup_value(item, rest)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Set `segments` in `table`. Missing sections are created from their
+/// defaults so required sibling keys are present.
+fn set(
+    table: &mut Table,
+    segments: &[String],
+    value: Value,
+    defaults: Option<&Value>,
+) -> Result<()> {
+    let Some((last, parents)) = segments.split_last() else {
+        bail!("empty key");
+    };
+    let mut current = table;
+    let mut defaults = defaults;
+    for (depth, segment) in parents.iter().enumerate() {
+        defaults = defaults.and_then(|d| d.get(segment.as_str()));
+        let entry = current.entry(segment.clone()).or_insert_with(|| {
+            defaults
+                .filter(|d| d.is_table())
+                .cloned()
+                .unwrap_or_else(|| Value::Table(Table::new()))
+        });
+        current = match entry {
+            Value::Table(table) => table,
+            Value::Array(items) => {
+                let index = segments
+                    .get(depth + 1)
+                    .and_then(|s| s.parse::<usize>().ok());
+                let Some(item) = index.and_then(|i| items.get_mut(i)) else {
+                    bail!("{segment} is an array; use an existing index");
+                };
+                let rest = &segments[depth + 2..];
+                if rest.is_empty() {
+                    *item = value;
+                    return Ok(());
+                }
+                let Value::Table(item) = item else {
+                    bail!("items of {segment} are not tables");
+                };
+                return set(item, rest, value, None);
+            }
+            _ => bail!("{} is not a table", segments[..=depth].join(".")),
+        };
+    }
+    current.insert(last.clone(), value);
+    Ok(())
+}
+
+/// Interpret `raw` with the type of the value it replaces: strings stay
+/// strings, arrays accept comma-separated items, and anything else is read
+/// as a TOML literal (falling back to a string).
+fn typed_value(raw: &str, template: Option<&Value>) -> Value {
+    match template {
+        Some(Value::String(_)) => Value::String(raw.to_string()),
+        Some(Value::Array(items)) if !raw.trim_start().starts_with('[') => Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| typed_value(item, items.first()))
+                .collect(),
+        ),
+        _ => parse_literal(raw).unwrap_or_else(|| Value::String(raw.to_string())),
+    }
+}
+
+fn parse_literal(raw: &str) -> Option<Value> {
+    toml::from_str::<Table>(&format!("value = {raw}"))
+        .ok()?
+        .remove("value")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn nested_keys_are_set_with_the_type_they_replace() {
+        let mut table: Table = toml::from_str(
+            "default_model = \"a\"\ndefault_temperature = 0.7\n[gateway]\nport = 1\n",
+        )
+        .unwrap();
+        let applied = apply_from(
+            &mut table,
+            vars(&[
+                ("ZEROCLAW__GATEWAY__PORT", "8080"),
+                ("ZEROCLAW__DEFAULT_MODEL", "1.5"),
+                ("ZEROCLAW__AUTONOMY__ALLOWED_COMMANDS", "git, ls"),
+                ("ZEROCLAW__MEMORY__AUTO_SAVE", "false"),
+                ("OTHER__GATEWAY__PORT", "1"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(applied.len(), 4);
+        assert_eq!(table["gateway"]["port"].as_integer(), Some(8080));
+        assert_eq!(table["default_model"].as_str(), Some("1.5"));
+        assert_eq!(
+            table["autonomy"]["allowed_commands"],
+            Value::Array(vec!["git".into(), "ls".into()])
+        );
+        assert_eq!(table["memory"]["auto_save"].as_bool(), Some(false));
+
+        // `[autonomy]` was created from its defaults, so it still deserializes.
+        let config: Config = table.try_into().unwrap();
+        assert_eq!(config.gateway.port, 8080);
+        assert_eq!(config.autonomy.allowed_commands, vec!["git", "ls"]);
+        assert!(!config.memory.auto_save);
+    }
+
+    #[test]
+    fn array_items_are_addressed_by_index() {
+        let mut table: Table =
+            toml::from_str("[[custom_tools]]\nname = \"a\"\n[[custom_tools]]\nname = \"b\"\n")
+                .unwrap();
+        apply_from(
+            &mut table,
+            vars(&[("ZEROCLAW__CUSTOM_TOOLS__1__NAME", "c")]),
+        )
+        .unwrap();
+        assert_eq!(table["custom_tools"][1]["name"].as_str(), Some("c"));
+        assert!(apply_from(
+            &mut table,
+            vars(&[("ZEROCLAW__CUSTOM_TOOLS__5__NAME", "x")])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn scalar_parent_is_an_error_naming_the_variable() {
+        let mut table: Table = toml::from_str("default_model = \"a\"\n").unwrap();
+        let err = apply_from(&mut table, vars(&[("ZEROCLAW__DEFAULT_MODEL__X", "1")]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("ZEROCLAW__DEFAULT_MODEL__X"));
+        assert!(err.contains("not a table"));
+    }
+}
@@ -0,0 +1,283 @@
+//! Config layout versions and the migrations between them.
+//!
+//! `config.toml` records the layout it was written with in `schema_version`
+//! (files without it are version 0). On load, every migration newer than the
+//! file's version is applied in order to the parsed TOML; when that changes
+//! anything, the original file is kept as a backup and the upgraded file is
+//! written in its place. `zeroclaw config migrate --dry-run` previews the
+//! same changes.
+//!
+//! To rename or move keys in a release, bump [`CURRENT_VERSION`] and append
+//! a [`Migration`] to [`MIGRATIONS`].
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Layout version written by this build.
+pub const CURRENT_VERSION: u32 = 1;
+
+const VERSION_KEY: &str = "schema_version";
+
+/// One step from `to - 1` to `to`.
+struct Migration {
+    to: u32,
+    apply: fn(&mut Table, &mut Vec<String>),
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    to: 1,
+    apply: canonical_key_names,
+}];
+
+/// Version 1: legacy spellings accepted as aliases become the canonical keys.
+fn canonical_key_names(table: &mut Table, changes: &mut Vec<String>) {
+    for (from, to) in [
+        ("model_provider", "default_provider"),
+        ("model", "default_model"),
+        ("composio.enable", "composio.enabled"),
+        ("heartbeat.channel", "heartbeat.target"),
+        ("heartbeat.recipient", "heartbeat.to"),
+        ("feeds.channel", "feeds.target"),
+        ("feeds.recipient", "feeds.to"),
+        (
+            "storage.provider.config.dbURL",
+            "storage.provider.config.db_url",
+        ),
+        (
+            "storage.provider.config.database_url",
+            "storage.provider.config.db_url",
+        ),
+        (
+            "storage.provider.config.databaseUrl",
+            "storage.provider.config.db_url",
+        ),
+    ] {
+        rename(table, from, to, changes);
+    }
+}
+
+/// What migrating a config did, or would do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub from: u32,
+    pub to: u32,
+    /// Human-readable description of each change, in order.
+    pub changes: Vec<String>,
+}
+
+impl Outcome {
+    /// Whether the file needs rewriting. A bare version bump does not count.
+    pub fn is_noop(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Upgrade a parsed config in place to [`CURRENT_VERSION`].
+pub fn migrate(table: &mut Table) -> Outcome {
+    let from = match table.get(VERSION_KEY) {
+        Some(Value::Integer(v)) => u32::try_from(*v).unwrap_or(0),
+        _ => 0,
+    };
+    let mut changes = Vec::new();
+    if from > CURRENT_VERSION {
+        tracing::warn!(
+            "config.toml has schema_version {from}, newer than this build supports \
+             ({CURRENT_VERSION}); newer keys may be ignored"
+        );
+        return Outcome {
+            from,
+            to: from,
+            changes,
+        };
+    }
+    for migration in MIGRATIONS.iter().filter(|m| m.to > from) {
+        (migration.apply)(table, &mut changes);
+    }
+    table.insert(
+        VERSION_KEY.into(),
+        Value::Integer(i64::from(CURRENT_VERSION)),
+    );
+    Outcome {
+        from,
+        to: CURRENT_VERSION,
+        changes,
+    }
+}
+
+/// Migrate config file contents. Returns the outcome and the upgraded file,
+/// which is `contents` unchanged when there is nothing to do.
+pub fn upgrade(contents: &str) -> Result<(Outcome, String)> {
+    let mut table: Table = toml::from_str(contents).context("Failed to parse config file")?;
+    let outcome = migrate(&mut table);
+    if outcome.is_noop() {
+        return Ok((outcome, contents.to_string()));
+    }
+    let upgraded = toml::to_string_pretty(&table).context("Failed to serialize migrated config")?;
+    Ok((outcome, upgraded))
+}
+
+/// Copy `path` to a backup next to it, then write `upgraded` in its place.
+/// Returns the backup path.
+pub async fn write_with_backup(path: &Path, upgraded: &str, outcome: &Outcome) -> Result<PathBuf> {
+    let backup = backup_path(path, outcome.from);
+    tokio::fs::copy(path, &backup).await.with_context(|| {
+        format!(
+            "Failed to back up {} to {}",
+            path.display(),
+            backup.display()
+        )
+    })?;
+    let tmp = path.with_extension("toml.migrating");
+    tokio::fs::write(&tmp, upgraded)
+        .await
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    tokio::fs::rename(&tmp, path)
+        .await
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(backup)
+}
+
+/// `config.toml.v0.bak`, or `config.toml.v0.<timestamp>.bak` if that exists.
+fn backup_path(path: &Path, from: u32) -> PathBuf {
+    let name = path.file_name().map_or_else(
+        || "config.toml".into(),
+        |n| n.to_string_lossy().into_owned(),
+    );
+    let plain = path.with_file_name(format!("{name}.v{from}.bak"));
+    if !plain.exists() {
+        return plain;
+    }
+    let stamp = chrono::Local::now().format("%Y%m%dT%H%M%S");
+    path.with_file_name(format!("{name}.v{from}.{stamp}.bak"))
+}
+
+/// Move the value at dotted path `from` to `to`. If `to` is already set the
+/// legacy key is dropped.
+fn rename(table: &mut Table, from: &str, to: &str, changes: &mut Vec<String>) {
+    let Some(value) = take(table, from) else {
+        return;
+    };
+    if get(table, to).is_some() {
+        changes.push(format!("removed `{from}` (`{to}` is already set)"));
+        return;
+    }
+    put(table, to, value);
+    changes.push(format!("renamed `{from}` to `{to}`"));
+}
+
+fn get<'a>(table: &'a Table, path: &str) -> Option<&'a Value> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (parent_table(table, parent)?, key),
+        None => (table, path),
+    };
+    parent.get(key)
+}
+
+fn parent_table<'a>(table: &'a Table, path: &str) -> Option<&'a Table> {
+    path.split('.')
+        .try_fold(table, |table, segment| table.get(segment)?.as_table())
+}
+
+fn take(table: &mut Table, path: &str) -> Option<Value> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (
+            parent.split('.').try_fold(table, |table, segment| {
+                table.get_mut(segment)?.as_table_mut()
+            })?,
+            key,
+        ),
+        None => (table, path),
+    };
+    parent.remove(key)
+}
+
+fn put(table: &mut Table, path: &str, value: Value) {
+    let mut segments: Vec<&str> = path.split('.').collect();
+    let key = segments.pop().unwrap_or(path);
+    let mut current = table;
+    for segment in segments {
+        let entry = current
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Table(Table::new()));
+        if !entry.is_table() {
+            *entry = Value::Table(Table::new());
+        }
+        current = entry.as_table_mut().expect("just ensured a table");
+    }
+    current.insert(key.to_string(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_keys_are_renamed_and_version_stamped() {
+        let mut table: Table = toml::from_str(
+            r#"
+model_provider = "anthropic"
+model = "claude"
+default_temperature = 0.7
+
+[storage.provider.config]
+dbURL = "postgres://localhost/zc"
+"#,
+        )
+        .unwrap();
+        let outcome = migrate(&mut table);
+        assert_eq!(outcome.from, 0);
+        assert_eq!(outcome.to, CURRENT_VERSION);
+        assert_eq!(outcome.changes.len(), 3);
+        assert_eq!(table["default_provider"].as_str(), Some("anthropic"));
+        assert_eq!(table["default_model"].as_str(), Some("claude"));
+        assert!(table.get("model").is_none());
+        assert_eq!(
+            table["storage"]["provider"]["config"]["db_url"].as_str(),
+            Some("postgres://localhost/zc")
+        );
+        assert_eq!(
+            table[VERSION_KEY].as_integer(),
+            Some(i64::from(CURRENT_VERSION))
+        );
+
+        // Migrating again finds nothing to do.
+        assert!(migrate(&mut table).is_noop());
+    }
+
+    #[test]
+    fn canonical_key_wins_over_legacy_spelling() {
+        let mut table: Table =
+            toml::from_str("model = \"old\"\ndefault_model = \"new\"\n").unwrap();
+        let outcome = migrate(&mut table);
+        assert_eq!(
+            outcome.changes,
+            vec!["removed `model` (`default_model` is already set)".to_string()]
+        );
+        assert_eq!(table["default_model"].as_str(), Some("new"));
+    }
+
+    #[test]
+    fn current_files_are_returned_unchanged() {
+        let contents = "# comment kept\ndefault_temperature = 0.7\n";
+        let (outcome, upgraded) = upgrade(contents).unwrap();
+        assert!(outcome.is_noop());
+        assert_eq!(upgraded, contents);
+    }
+
+    #[tokio::test]
+    async fn rewrite_keeps_a_backup_of_the_original() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        let original = "model = \"claude\"\ndefault_temperature = 0.7\n";
+        std::fs::write(&path, original).unwrap();
+
+        let (outcome, upgraded) = upgrade(original).unwrap();
+        let backup = write_with_backup(&path, &upgraded, &outcome).await.unwrap();
+        assert_eq!(backup, tmp.path().join("config.toml.v0.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), original);
+        let rewritten = std::fs::read_to_string(&path).unwrap();
+        assert!(rewritten.contains("default_model = \"claude\""));
+        assert!(rewritten.contains("schema_version = 1"));
+    }
+}
@@ -1,21 +1,39 @@
+pub mod annotate;
+pub mod env_overrides;
+pub mod migrate;
+pub mod reload;
 pub mod schema;
 pub mod traits;
+pub mod validate;
 
 #[allow(unused_imports)]
 pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
     build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    AgentConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
-    BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config, CostConfig,
-    CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EmbeddingRouteConfig,
-    EstopConfig, FeishuConfig, GatewayConfig, HardwareConfig, HardwareTransport, HeartbeatConfig,
-    HooksConfig, HttpRequestConfig, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig,
-    MemoryConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig,
-    OtpConfig, OtpMethod, PeripheralBoardConfig, PeripheralsConfig, ProxyConfig, ProxyScope,
-    QdrantConfig, QueryClassificationConfig, ReliabilityConfig, ResourceLimitsConfig,
-    RuntimeConfig, SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig,
-    SkillsConfig, SkillsPromptInjectionMode, SlackConfig, StorageConfig, StorageProviderConfig,
-    StorageProviderSection, StreamMode, TelegramConfig, TranscriptionConfig, TunnelConfig,
+    AgentConfig, ApprovalAction, AuditConfig, AutonomyConfig, BrowserComputerUseConfig,
+    BrowserConfig, BudgetConfig, BudgetLimitsConfig, BuiltinHooksConfig, CalendarConfig,
+    ChannelOutboxConfig, ChannelPairingConfig, ChannelRateLimitConfig, ChannelsConfig,
+    ClassificationRule, ClipboardConfig, CodeInterpreterConfig, CommandRulesConfig, ComposioConfig,
+    Config, ConversationsConfig, CostConfig, CronConfig, CustomToolConfig, CustomToolHttpConfig,
+    DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, DocumentToolConfig,
+    EmbeddingRouteConfig, EstopConfig, FeedSourceConfig, FeedsConfig, FeishuConfig, GatewayConfig,
+    GitToolConfig, GuardrailAction, GuardrailClassifierConfig, GuardrailDirection,
+    GuardrailRuleConfig, GuardrailsConfig, HardwareConfig, HardwareTransport, HeartbeatConfig,
+    HeartbeatMode, HooksConfig, HttpRequestConfig, HttpSecretConfig, IMessageConfig,
+    IdentityConfig, ImageGenerationConfig, ImageGenerationProvider, InjectionAction,
+    InjectionCheck, InjectionDefenseConfig, LarkConfig, LocaleConfig, LoggingConfig, MatrixConfig,
+    McpConfig, McpServerConfig, McpTransport, MemoryCompactionConfig, MemoryConfig,
+    MemoryScopeMode, MemoryScopesConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig,
+    NotificationsConfig, ObservabilityConfig, OcrConfig, OtpConfig, OtpMethod,
+    PeripheralBoardConfig, PeripheralsConfig, PipelineStepConfig, PluginPermissions,
+    PluginWorkspaceAccess, PluginsConfig, ProfileConfig, ProxyConfig, ProxyScope, QdrantConfig,
+    QueryClassificationConfig, RedactionConfig, RedisMemoryConfig, ReliabilityConfig, ReplayConfig,
+    ResourceLimitsConfig, RiskApprovalConfig, RolesConfig, RoutingConfig, RuntimeConfig,
+    SandboxBackend, SandboxConfig, ScheduleEntryConfig, SchedulerConfig, SecretsConfig,
+    SecurityConfig, SessionPolicy, SkillsConfig, SkillsPromptInjectionMode, SlackConfig,
+    SqlConnectionConfig, SqlToolConfig, StorageConfig, StorageEncryptionConfig, StorageKeySource,
+    StorageProviderConfig, StorageProviderSection, StreamMode, TasksConfig, TelegramConfig,
+    ToolOutputConfig, ToolOutputProcessor, ToolRuntimeConfig, TranscriptionConfig, TunnelConfig,
     WebFetchConfig, WebSearchConfig, WebhookConfig,
 };
 
@@ -53,6 +71,8 @@ mod tests {
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
         };
 
         let lark = LarkConfig {
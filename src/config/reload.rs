@@ -0,0 +1,264 @@
+//! Config hot reload.
+//!
+//! The daemon watches `config.toml` and re-reads it when it changes or on
+//! `SIGHUP`. Settings listed in [`HOT_PATHS`] are applied to running
+//! components; every other change is reported as needing a restart.
+
+use super::Config;
+use crate::security::{PolicyLists, SecurityPolicy};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+/// How often the config file's modification time and size are checked.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Settings that running components pick up without a restart. A change is
+/// hot when its path equals one of these or lies beneath it.
+const HOT_PATHS: &[&str] = &[
+    "default_provider",
+    "default_model",
+    "default_temperature",
+    "api_key",
+    "api_url",
+    "reliability",
+    "autonomy.allowed_commands",
+    "autonomy.denied_commands",
+    "autonomy.forbidden_paths",
+    "autonomy.allowed_roots",
+    "autonomy.command_rules",
+    "autonomy.non_cli_excluded_tools",
+    "channels_config.telegram.allowed_users",
+    "security.injection",
+    "security.roles",
+    "locale.language",
+    "locale.timezone",
+];
+
+/// Changes are reported at most this many keys deep, e.g.
+/// `channels_config.telegram.allowed_users`.
+const REPORT_DEPTH: usize = 3;
+
+/// Which changed settings were applied and which wait for a restart.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadReport {
+    pub applied: Vec<String>,
+    pub restart_required: Vec<String>,
+}
+
+/// Compare two configs and classify every changed setting.
+pub fn diff(old: &Config, new: &Config) -> ReloadReport {
+    let old = serde_json::to_value(old).unwrap_or(Value::Null);
+    let new = serde_json::to_value(new).unwrap_or(Value::Null);
+    let mut changed = Vec::new();
+    changed_paths(&old, &new, "", 0, &mut changed);
+
+    let mut report = ReloadReport::default();
+    for path in changed {
+        // The active profile is already merged into the effective keys.
+        if path == "profile" || path.starts_with("profile.") {
+            continue;
+        }
+        if is_hot(&path) {
+            report.applied.push(path);
+        } else {
+            report.restart_required.push(path);
+        }
+    }
+    report
+}
+
+fn changed_paths(old: &Value, new: &Value, prefix: &str, depth: usize, out: &mut Vec<String>) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) if depth < REPORT_DEPTH => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                changed_paths(
+                    a.get(key).unwrap_or(&Value::Null),
+                    b.get(key).unwrap_or(&Value::Null),
+                    &path,
+                    depth + 1,
+                    out,
+                );
+            }
+        }
+        _ => out.push(prefix.to_string()),
+    }
+}
+
+fn is_hot(path: &str) -> bool {
+    HOT_PATHS.iter().any(|hot| {
+        path == *hot
+            || path
+                .strip_prefix(hot)
+                .is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+fn sender() -> &'static watch::Sender<Option<Arc<Config>>> {
+    static SENDER: OnceLock<watch::Sender<Option<Arc<Config>>>> = OnceLock::new();
+    SENDER.get_or_init(|| watch::channel(None).0)
+}
+
+/// Receive every config applied by a reload. Holds `None` until the first one.
+pub fn subscribe() -> watch::Receiver<Option<Arc<Config>>> {
+    sender().subscribe()
+}
+
+/// Push a reloaded config to running components.
+pub fn apply(config: Config) {
+    SecurityPolicy::apply_reloaded_lists(
+        PolicyLists::from_config(&config.autonomy, &config.workspace_dir),
+        &config.workspace_dir,
+    );
+    crate::security::injection::configure(&config.security.injection);
+    crate::security::roles::configure(&config.security.roles);
+    crate::i18n::configure(&config.locale);
+    crate::time::configure(&config.locale);
+    sender().send_replace(Some(Arc::new(config)));
+}
+
+/// Watch `config.config_path` until `shutdown`, reloading when the file
+/// changes or the process receives `SIGHUP`.
+///
+/// Restart-only changes are compared against the config the process started
+/// with, so they keep being reported until the daemon is restarted.
+pub async fn watch(startup: Config, shutdown: CancellationToken) {
+    let path = startup.config_path.clone();
+    let mut current = startup.clone();
+    let mut stamp = file_stamp(&path).await;
+    let mut poll = tokio::time::interval(POLL_INTERVAL);
+    poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut hangup = hangup_signal();
+
+    loop {
+        let forced = tokio::select! {
+            () = shutdown.cancelled() => return,
+            _ = poll.tick() => false,
+            () = next_hangup(&mut hangup) => true,
+        };
+        let next_stamp = file_stamp(&path).await;
+        if !forced && next_stamp == stamp {
+            continue;
+        }
+        stamp = next_stamp;
+
+        let next = match load(&path, &startup).await {
+            Ok(next) => next,
+            Err(e) => {
+                tracing::warn!("Config reload failed, keeping the running config: {e:#}");
+                continue;
+            }
+        };
+        let applied = diff(&current, &next).applied;
+        let restart_required = diff(&startup, &next).restart_required;
+        if applied.is_empty() && restart_required.is_empty() {
+            tracing::info!(forced, "Config reloaded; nothing changed");
+        }
+        if !applied.is_empty() {
+            tracing::info!(forced, "Config reloaded; applied: {}", applied.join(", "));
+        }
+        if !restart_required.is_empty() {
+            tracing::warn!(
+                "Config changes that take effect after a restart: {}",
+                restart_required.join(", ")
+            );
+        }
+        apply(next.clone());
+        current = next;
+    }
+}
+
+/// Re-read the file with the profile the process started with.
+async fn load(path: &Path, startup: &Config) -> anyhow::Result<Config> {
+    let mut next = Config::load_from_path(path, startup.workspace_dir.clone()).await?;
+    if let Some(profile) = &startup.active_profile {
+        next.apply_profile(profile)?;
+        next.validate()?;
+        next.apply_env_overrides();
+    }
+    Ok(next)
+}
+
+async fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+#[cfg(unix)]
+type Hangup = Option<tokio::signal::unix::Signal>;
+#[cfg(not(unix))]
+type Hangup = ();
+
+#[cfg(unix)]
+fn hangup_signal() -> Hangup {
+    match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(signal) => Some(signal),
+        Err(e) => {
+            tracing::warn!("Cannot listen for SIGHUP; config reloads on file changes only: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn hangup_signal() -> Hangup {}
+
+/// Resolve on the next `SIGHUP`; never resolves where signals are unavailable.
+#[cfg(unix)]
+async fn next_hangup(signal: &mut Hangup) {
+    match signal {
+        Some(signal) if signal.recv().await.is_some() => {}
+        _ => std::future::pending().await,
+    }
+}
+
+#[cfg(not(unix))]
+async fn next_hangup(_signal: &mut Hangup) {
+    std::future::pending().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_separates_hot_and_restart_changes() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.default_temperature = 0.2;
+        new.autonomy.allowed_commands.push("docker".into());
+        new.gateway.port += 1;
+        new.memory.backend = "markdown".into();
+
+        let report = diff(&old, &new);
+        assert_eq!(
+            report.applied,
+            ["autonomy.allowed_commands", "default_temperature"]
+        );
+        assert_eq!(report.restart_required, ["gateway.port", "memory.backend"]);
+        assert_eq!(diff(&new, &new), ReloadReport::default());
+    }
+
+    #[test]
+    fn hot_paths_match_whole_keys_only() {
+        assert!(is_hot("reliability.provider_retries"));
+        assert!(is_hot("channels_config.telegram.allowed_users"));
+        assert!(is_hot("security.roles.users"));
+        assert!(!is_hot("api_url_extra"));
+        assert!(!is_hot("channels_config.telegram.bot_token"));
+        assert!(!is_hot("autonomy.level"));
+    }
+}
@@ -0,0 +1,599 @@
+//! `zeroclaw config validate`.
+//!
+//! Checks a config file without starting anything: TOML syntax, key names
+//! and value types, the rules enforced by [`Config::validate`], referenced
+//! files and executables, and provider/channel credentials. With
+//! [`Options::probe`] it also contacts the configured provider and channels.
+//! Findings carry the `line:column` of the key they are about whenever it
+//! appears in the file.
+
+use super::{Config, McpTransport};
+use crate::channels::ChannelHealthState;
+use crate::providers::{self, Provider};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use toml::de::{DeTable, DeValue};
+use toml::Spanned;
+
+/// Severity of a finding. Only errors make validation fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        })
+    }
+}
+
+/// One problem found in the config file.
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub level: Level,
+    /// Dotted config key the finding is about, when known.
+    pub key: Option<String>,
+    /// 1-based line and column of `key` (or its nearest parent) in the file.
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+/// Result of validating one config file.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub path: PathBuf,
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    pub fn errors(&self) -> usize {
+        self.count(Level::Error)
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.count(Level::Warning)
+    }
+
+    fn count(&self, level: Level) -> usize {
+        self.findings.iter().filter(|f| f.level == level).count()
+    }
+}
+
+impl fmt::Display for Report {
+    /// One `path:line:column: level: message` line per finding, the format
+    /// editors and CI annotators understand.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for finding in &self.findings {
+            match finding.location {
+                Some((line, column)) => write!(f, "{}:{line}:{column}: ", self.path.display())?,
+                None => write!(f, "{}: ", self.path.display())?,
+            }
+            writeln!(f, "{}: {}", finding.level, finding.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// What to check beyond the file itself.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Validate with this `[profile.<name>]` applied.
+    pub profile: Option<String>,
+    /// Contact the provider and channels to confirm they are reachable.
+    pub probe: bool,
+}
+
+/// Validate the config file at `path`. Relative paths in the config resolve
+/// against `workspace_dir`.
+pub async fn validate_file(path: &Path, workspace_dir: PathBuf, options: &Options) -> Report {
+    let mut validator = Validator {
+        source: String::new(),
+        index: HashMap::new(),
+        report: Report {
+            path: path.to_path_buf(),
+            findings: Vec::new(),
+        },
+    };
+    match tokio::fs::read_to_string(path).await {
+        Ok(source) => validator.source = source,
+        Err(e) => {
+            validator.push(Level::Error, None, format!("cannot read config file: {e}"));
+            return validator.report;
+        }
+    }
+    if !validator.check_syntax() {
+        return validator.report;
+    }
+    validator.check_migrations();
+
+    let mut config = match Config::load_from_path_read_only(path, workspace_dir).await {
+        Ok(config) => config,
+        Err(e) => {
+            validator.push_error(&e);
+            return validator.report;
+        }
+    };
+    if let Some(profile) = &options.profile {
+        if let Err(e) = config.apply_profile(profile) {
+            validator.push_error(&e);
+            return validator.report;
+        }
+        if let Err(e) = config.validate() {
+            validator.push_error(&e);
+            return validator.report;
+        }
+    }
+
+    validator.check_paths(&config);
+    validator.check_credentials(&config);
+    if options.probe {
+        validator.probe(&config).await;
+    }
+    validator.report
+}
+
+struct Validator {
+    source: String,
+    /// Byte span of every key in the file, by normalized dotted path.
+    index: HashMap<String, Range<usize>>,
+    report: Report,
+}
+
+impl Validator {
+    /// Parse the file and deserialize it into [`Config`], reporting syntax
+    /// errors, type mismatches and unknown keys. Returns whether the file
+    /// deserialized.
+    fn check_syntax(&mut self) -> bool {
+        let index = DeTable::parse(&self.source).map(|table| {
+            let mut index = HashMap::new();
+            index_table("", table.get_ref(), &mut index);
+            index
+        });
+        match index {
+            Ok(index) => self.index = index,
+            Err(e) => {
+                self.push_at(e.span(), Level::Error, e.message().to_string());
+                return false;
+            }
+        }
+
+        let mut unknown = Vec::new();
+        let parsed: Result<Config, toml::de::Error> =
+            match toml::de::Deserializer::parse(&self.source) {
+                Ok(deserializer) => serde_ignored::deserialize(deserializer, |path| {
+                    unknown.push(path.to_string());
+                }),
+                Err(e) => Err(e),
+            };
+        for key in unknown {
+            let key = normalize_key(&key);
+            self.push(
+                Level::Warning,
+                Some(&key),
+                format!("unknown key `{key}` is ignored"),
+            );
+        }
+        match parsed {
+            Ok(_) => true,
+            Err(e) => {
+                self.push_at(e.span(), Level::Error, e.message().to_string());
+                false
+            }
+        }
+    }
+
+    /// Report changes the migrator would make, without rewriting the file.
+    fn check_migrations(&mut self) {
+        let Ok((migration, _)) = super::migrate::upgrade(&self.source) else {
+            return;
+        };
+        for change in migration.changes {
+            self.push(
+                Level::Warning,
+                None,
+                format!(
+                    "pending migration to schema version {}: {change} (run `zeroclaw config migrate`)",
+                    migration.to
+                ),
+            );
+        }
+    }
+
+    fn check_paths(&mut self, config: &Config) {
+        let workspace = &config.workspace_dir;
+        let mut files: Vec<(String, &str)> = Vec::new();
+        if config.identity.format == "aieos" {
+            if let Some(path) = &config.identity.aieos_path {
+                files.push(("identity.aieos_path".into(), path.as_str()));
+            }
+        }
+        if let Some(path) = &config.browser.native_chrome_path {
+            files.push(("browser.native_chrome_path".into(), path.as_str()));
+        }
+        if let Some(path) = config
+            .channels_config
+            .github
+            .as_ref()
+            .and_then(|github| github.private_key_path.as_deref())
+        {
+            files.push(("channels_config.github.private_key_path".into(), path));
+        }
+        for (name, server) in config.mcp.servers.iter().filter(|(_, s)| s.enabled) {
+            if let Some(cwd) = &server.cwd {
+                files.push((format!("mcp.servers.{name}.cwd"), cwd.as_str()));
+            }
+            if server.transport == McpTransport::Stdio {
+                if let Some(command) = &server.command {
+                    if which::which(command).is_err() && !expand(workspace, command).is_file() {
+                        let key = format!("mcp.servers.{name}.command");
+                        self.push(
+                            Level::Error,
+                            Some(&key),
+                            format!("{key}: `{command}` is not an executable on PATH"),
+                        );
+                    }
+                }
+            }
+        }
+        for (key, path) in files {
+            let resolved = expand(workspace, path);
+            if !resolved.exists() {
+                self.push(
+                    Level::Error,
+                    Some(&key),
+                    format!("{key}: {} does not exist", resolved.display()),
+                );
+            }
+        }
+
+        for (i, root) in config.autonomy.allowed_roots.iter().enumerate() {
+            let resolved = expand(workspace, root);
+            if !resolved.is_dir() {
+                let key = format!("autonomy.allowed_roots[{i}]");
+                self.push(
+                    Level::Warning,
+                    Some(&key),
+                    format!("{key}: {} is not a directory", resolved.display()),
+                );
+            }
+        }
+    }
+
+    fn check_credentials(&mut self, config: &Config) {
+        let provider = config.default_provider.as_deref().unwrap_or("openrouter");
+        self.check_provider_credential("default_provider", provider, config.api_key.as_deref());
+        for (name, agent) in &config.agents {
+            self.check_provider_credential(
+                &format!("agents.{name}.provider"),
+                &agent.provider,
+                agent.api_key.as_deref(),
+            );
+        }
+
+        let channels = &config.channels_config;
+        let tokens = [
+            (
+                "channels_config.telegram.bot_token",
+                channels.telegram.as_ref().map(|c| c.bot_token.as_str()),
+            ),
+            (
+                "channels_config.discord.bot_token",
+                channels.discord.as_ref().map(|c| c.bot_token.as_str()),
+            ),
+            (
+                "channels_config.slack.bot_token",
+                channels.slack.as_ref().map(|c| c.bot_token.as_str()),
+            ),
+            (
+                "channels_config.mattermost.bot_token",
+                channels.mattermost.as_ref().map(|c| c.bot_token.as_str()),
+            ),
+            (
+                "channels_config.matrix.access_token",
+                channels.matrix.as_ref().map(|c| c.access_token.as_str()),
+            ),
+            (
+                "channels_config.linq.api_token",
+                channels.linq.as_ref().map(|c| c.api_token.as_str()),
+            ),
+            (
+                "channels_config.wati.api_token",
+                channels.wati.as_ref().map(|c| c.api_token.as_str()),
+            ),
+            (
+                "channels_config.nextcloud_talk.app_token",
+                channels
+                    .nextcloud_talk
+                    .as_ref()
+                    .map(|c| c.app_token.as_str()),
+            ),
+            (
+                "channels_config.mastodon.access_token",
+                channels.mastodon.as_ref().map(|c| c.access_token.as_str()),
+            ),
+            (
+                "channels_config.xmpp.password",
+                channels.xmpp.as_ref().map(|c| c.password.as_str()),
+            ),
+        ];
+        for (key, token) in tokens {
+            if token.is_some_and(|t| t.trim().is_empty()) {
+                self.push(Level::Error, Some(key), format!("{key} must not be empty"));
+            }
+        }
+    }
+
+    fn check_provider_credential(&mut self, key: &str, provider: &str, api_key: Option<&str>) {
+        let local = providers::list_providers()
+            .iter()
+            .any(|p| p.local && (p.name == provider || p.aliases.iter().any(|a| *a == provider)));
+        if local || provider.starts_with("custom:") || providers::has_credential(provider, api_key)
+        {
+            return;
+        }
+        self.push(
+            Level::Warning,
+            Some(key),
+            format!(
+                "{key}: no credential found for provider '{provider}' \
+                 (set api_key or the provider's API key environment variable)"
+            ),
+        );
+    }
+
+    async fn probe(&mut self, config: &Config) {
+        let name = config.default_provider.as_deref().unwrap_or("openrouter");
+        let result = match providers::create_provider_with_url(
+            name,
+            config.api_key.as_deref(),
+            config.api_url.as_deref(),
+        ) {
+            Ok(provider) => provider.warmup().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            self.push(
+                Level::Error,
+                Some("default_provider"),
+                format!("provider '{name}' is not reachable: {e:#}"),
+            );
+        }
+
+        match crate::channels::check_channel_health(config).await {
+            Ok(states) => {
+                for (display_name, state) in states {
+                    let key = format!(
+                        "channels_config.{}",
+                        display_name.to_lowercase().replace(' ', "_")
+                    );
+                    match state {
+                        ChannelHealthState::Healthy => {}
+                        ChannelHealthState::Unhealthy => self.push(
+                            Level::Error,
+                            Some(&key),
+                            format!("{display_name} channel failed its health check (auth/config/network)"),
+                        ),
+                        ChannelHealthState::Timeout => self.push(
+                            Level::Warning,
+                            Some(&key),
+                            format!("{display_name} channel health check timed out (>10s)"),
+                        ),
+                    }
+                }
+            }
+            Err(e) => self.push(
+                Level::Error,
+                Some("channels_config"),
+                format!("channel setup failed: {e:#}"),
+            ),
+        }
+    }
+
+    /// Report an error from config loading, located by the dotted key its
+    /// message starts with (e.g. `gateway.host must not be empty`).
+    fn push_error(&mut self, error: &anyhow::Error) {
+        let message = error.to_string();
+        let key = message_key(&message);
+        self.push(Level::Error, key.as_deref(), format!("{error:#}"));
+    }
+
+    fn push(&mut self, level: Level, key: Option<&str>, message: String) {
+        let span = key.and_then(|key| locate(&self.index, key));
+        self.push_at(span, level, message);
+        if let Some(finding) = self.report.findings.last_mut() {
+            finding.key = key.map(str::to_string);
+        }
+    }
+
+    fn push_at(&mut self, span: Option<Range<usize>>, level: Level, message: String) {
+        self.report.findings.push(Finding {
+            level,
+            key: None,
+            location: span.map(|span| line_column(&self.source, span.start)),
+            message,
+        });
+    }
+}
+
+fn index_table(prefix: &str, table: &DeTable<'_>, index: &mut HashMap<String, Range<usize>>) {
+    for (key, value) in table.iter() {
+        let path = if prefix.is_empty() {
+            key.get_ref().to_string()
+        } else {
+            format!("{prefix}.{}", key.get_ref())
+        };
+        index.entry(path.clone()).or_insert_with(|| key.span());
+        index_value(&path, value, index);
+    }
+}
+
+fn index_value(
+    path: &str,
+    value: &Spanned<DeValue<'_>>,
+    index: &mut HashMap<String, Range<usize>>,
+) {
+    match value.get_ref() {
+        DeValue::Table(table) => index_table(path, table, index),
+        DeValue::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let item_path = format!("{path}.{i}");
+                index
+                    .entry(item_path.clone())
+                    .or_insert_with(|| item.span());
+                index_value(&item_path, item, index);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `custom_tools[0].name` and serde_ignored's `channels_config.telegram.?.x`
+/// both become plain dotted paths (`custom_tools.0.name`).
+fn normalize_key(key: &str) -> String {
+    key.replace('[', ".")
+        .replace(']', "")
+        .split('.')
+        .filter(|segment| !segment.is_empty() && *segment != "?")
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Span of `key`, or of its nearest parent present in the file.
+fn locate(index: &HashMap<String, Range<usize>>, key: &str) -> Option<Range<usize>> {
+    let key = normalize_key(key);
+    let mut path = key.as_str();
+    loop {
+        if let Some(span) = index.get(path) {
+            return Some(span.clone());
+        }
+        path = &path[..path.rfind('.')?];
+    }
+}
+
+/// The config key a validation message starts with, if any.
+fn message_key(message: &str) -> Option<String> {
+    let token = message.split_whitespace().next()?.trim_end_matches(':');
+    let looks_like_key = (token.contains('.') || token.contains('['))
+        && token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '[' | ']'));
+    looks_like_key.then(|| normalize_key(token))
+}
+
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = source.get(..offset).unwrap_or(source);
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+fn expand(workspace: &Path, path: &str) -> PathBuf {
+    let expanded = PathBuf::from(shellexpand::tilde(path).as_ref());
+    if expanded.is_absolute() {
+        expanded
+    } else {
+        workspace.join(expanded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn validate(toml: &str) -> Report {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(&path, toml).unwrap();
+        validate_file(&path, tmp.path().to_path_buf(), &Options::default()).await
+    }
+
+    #[tokio::test]
+    async fn syntax_error_is_located() {
+        let report = validate("default_model = \"x\"\n[gateway\nport = 1\n").await;
+        assert_eq!(report.errors(), 1);
+        assert_eq!(report.findings[0].location.map(|(line, _)| line), Some(2));
+    }
+
+    #[tokio::test]
+    async fn type_mismatch_points_at_value() {
+        let report = validate("default_temperature = 0.7\n[gateway]\nport = \"eighty\"\n").await;
+        assert_eq!(report.errors(), 1);
+        assert_eq!(report.findings[0].location.map(|(line, _)| line), Some(3));
+    }
+
+    #[tokio::test]
+    async fn unknown_keys_warn_at_their_line() {
+        let report =
+            validate("default_temperature = 0.5\n\n[gateway]\nhots = \"127.0.0.1\"\n").await;
+        assert_eq!(report.errors(), 0);
+        let finding = report
+            .findings
+            .iter()
+            .find(|f| f.key.as_deref() == Some("gateway.hots"))
+            .expect("unknown key reported");
+        assert_eq!(finding.level, Level::Warning);
+        assert_eq!(finding.location, Some((4, 1)));
+    }
+
+    #[tokio::test]
+    async fn semantic_errors_are_located_by_key() {
+        let report =
+            validate("default_temperature = 0.7\n[gateway]\nport = 8080\nhost = \"\"\n").await;
+        let finding = &report.findings[0];
+        assert_eq!(finding.level, Level::Error);
+        assert_eq!(finding.key.as_deref(), Some("gateway.host"));
+        assert_eq!(finding.location, Some((4, 1)));
+        assert!(report
+            .to_string()
+            .contains("config.toml:4:1: error: gateway.host"));
+    }
+
+    #[tokio::test]
+    async fn missing_paths_and_commands_are_errors() {
+        let report = validate(
+            "default_temperature = 0.7\n[mcp.servers.files]\ncommand = \"zeroclaw-no-such-binary\"\ncwd = \"missing-dir\"\n",
+        )
+        .await;
+        let keys: Vec<_> = report
+            .findings
+            .iter()
+            .filter(|f| f.level == Level::Error)
+            .filter_map(|f| f.key.as_deref())
+            .collect();
+        assert!(keys.contains(&"mcp.servers.files.command"));
+        assert!(keys.contains(&"mcp.servers.files.cwd"));
+    }
+
+    #[test]
+    fn keys_are_normalized_and_found_by_parent() {
+        assert_eq!(normalize_key("custom_tools[2].name"), "custom_tools.2.name");
+        assert_eq!(
+            normalize_key("channels_config.telegram.?.x"),
+            "channels_config.telegram.x"
+        );
+        assert_eq!(
+            message_key("gateway.host must not be empty").as_deref(),
+            Some("gateway.host")
+        );
+        assert_eq!(message_key("Failed to read config file"), None);
+
+        let mut index = HashMap::new();
+        index.insert("gateway".to_string(), 10..17);
+        assert_eq!(locate(&index, "gateway.port"), Some(10..17));
+        assert_eq!(locate(&index, "memory.backend"), None);
+        assert_eq!(line_column("a = 1\nbb = 2\n", 8), (2, 3));
+    }
+}
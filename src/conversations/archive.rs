@@ -0,0 +1,469 @@
+//! Session export and import.
+//!
+//! A session can be written as JSON (a lossless archive that `import` reads
+//! back), Markdown, or a self-contained HTML page. Markdown and HTML show
+//! tool calls next to the reply they fed into and render attachment markers
+//! (`[IMAGE:...]`, `[DOCUMENT:...]`, ...) as images and links.
+
+use super::{ConversationStore, SessionSummary, StoredMessage, StoredToolCall};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Value of [`SessionArchive::format`].
+const ARCHIVE_FORMAT: &str = "zeroclaw-session";
+const ARCHIVE_VERSION: u32 = 1;
+
+/// Tool output longer than this is cut in Markdown and HTML exports.
+const MAX_TOOL_OUTPUT_CHARS: usize = 4_000;
+
+/// Output format for `zeroclaw export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    #[value(name = "md", alias = "markdown")]
+    Markdown,
+    Json,
+    Html,
+}
+
+/// Everything stored for one session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionArchive {
+    pub format: String,
+    pub version: u32,
+    pub session: SessionSummary,
+    pub messages: Vec<StoredMessage>,
+    #[serde(default)]
+    pub tool_calls: Vec<StoredToolCall>,
+}
+
+/// Load a session from the store.
+pub fn load(store: &ConversationStore, session_id: &str) -> Result<SessionArchive> {
+    let session = store
+        .session(session_id)?
+        .with_context(|| format!("No session '{session_id}' in the conversation store"))?;
+    Ok(SessionArchive {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        messages: store.recent_messages(session_id, usize::MAX)?,
+        tool_calls: store.tool_calls(session_id)?,
+        session,
+    })
+}
+
+/// Render an archive in `format`.
+pub fn render(archive: &SessionArchive, format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(archive)? + "\n"),
+        ExportFormat::Markdown => Ok(render_markdown(archive)),
+        ExportFormat::Html => Ok(render_html(archive)),
+    }
+}
+
+/// Parse a JSON archive produced by `export --format json`.
+pub fn parse(raw: &str) -> Result<SessionArchive> {
+    let archive: SessionArchive =
+        serde_json::from_str(raw).context("Not a ZeroClaw session archive (expected JSON)")?;
+    if archive.format != ARCHIVE_FORMAT {
+        bail!("Unsupported archive format '{}'", archive.format);
+    }
+    if archive.version > ARCHIVE_VERSION {
+        bail!(
+            "Archive version {} is newer than this build supports ({ARCHIVE_VERSION})",
+            archive.version
+        );
+    }
+    Ok(archive)
+}
+
+/// Write an archive into the store, optionally under a different id.
+/// Returns the id it was stored as.
+pub fn import(
+    store: &ConversationStore,
+    mut archive: SessionArchive,
+    rename: Option<&str>,
+    replace: bool,
+) -> Result<String> {
+    if let Some(id) = rename {
+        archive.session.id = id.to_string();
+    }
+    store
+        .restore_session(
+            &archive.session,
+            &archive.messages,
+            &archive.tool_calls,
+            replace,
+        )
+        .context("use --as <id> to import under another id, or --replace to overwrite")?;
+    Ok(archive.session.id)
+}
+
+/// One item in display order.
+enum Entry<'a> {
+    Message(&'a StoredMessage),
+    ToolCall(&'a StoredToolCall),
+}
+
+/// Messages in order, with each tool call placed before the assistant reply
+/// of the turn it ran in. Tool calls are persisted while the turn runs and
+/// the turn's messages afterwards, so timestamps alone would put them first.
+fn timeline(archive: &SessionArchive) -> Vec<Entry<'_>> {
+    let mut calls = archive.tool_calls.iter().peekable();
+    let mut entries = Vec::with_capacity(archive.messages.len() + archive.tool_calls.len());
+    for message in &archive.messages {
+        if message.role == "assistant" {
+            while let Some(call) = calls.next_if(|c| c.created_at <= message.created_at) {
+                entries.push(Entry::ToolCall(call));
+            }
+        }
+        entries.push(Entry::Message(message));
+    }
+    entries.extend(calls.map(Entry::ToolCall));
+    entries
+}
+
+/// Replace `[KIND:target]` attachment markers using `render`.
+fn replace_markers(content: &str, render: impl Fn(&str, &str) -> String) -> String {
+    const KINDS: [&str; 5] = ["IMAGE", "DOCUMENT", "VIDEO", "AUDIO", "VOICE"];
+    let mut out = String::with_capacity(content.len());
+    let mut cursor = 0;
+    while let Some(rel) = content[cursor..].find('[') {
+        let start = cursor + rel;
+        out.push_str(&content[cursor..start]);
+        let marker = content[start + 1..].find(']').and_then(|len| {
+            let (kind, target) = content[start + 1..start + 1 + len].split_once(':')?;
+            let target = target.trim();
+            (KINDS.contains(&kind) && !target.is_empty()).then_some((kind, target, len))
+        });
+        if let Some((kind, target, len)) = marker {
+            out.push_str(&render(kind, target));
+            cursor = start + len + 2;
+        } else {
+            out.push('[');
+            cursor = start + 1;
+        }
+    }
+    out.push_str(&content[cursor..]);
+    out
+}
+
+fn attachment_name(target: &str) -> &str {
+    if target.starts_with("data:") {
+        return "inline data";
+    }
+    target
+        .rsplit(['/', '\\'])
+        .find(|part| !part.is_empty())
+        .unwrap_or(target)
+}
+
+/// Whether a marker target may become a link or image source: web URLs,
+/// inline data and plain paths, but no other schemes (`javascript:` etc.).
+fn is_linkable(target: &str) -> bool {
+    let lower = target.to_ascii_lowercase();
+    match lower.split_once(':') {
+        Some((scheme, _)) if scheme.len() > 1 && !scheme.contains(['/', '\\']) => {
+            matches!(scheme, "http" | "https" | "data" | "file")
+        }
+        _ => true,
+    }
+}
+
+fn clip(text: &str) -> String {
+    if text.chars().count() <= MAX_TOOL_OUTPUT_CHARS {
+        return text.to_string();
+    }
+    let mut clipped: String = text.chars().take(MAX_TOOL_OUTPUT_CHARS).collect();
+    clipped.push_str("\n… (truncated)");
+    clipped
+}
+
+/// Arguments as pretty JSON when they parse, otherwise verbatim.
+fn pretty_arguments(raw: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(raw)
+        .and_then(|v| serde_json::to_string_pretty(&v))
+        .unwrap_or_else(|_| raw.to_string())
+}
+
+/// A fence longer than any backtick run in `text`.
+fn fence_for(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn role_title(role: &str) -> String {
+    let mut chars = role.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+fn render_markdown(archive: &SessionArchive) -> String {
+    let session = &archive.session;
+    let mut out = match super::title::title(session) {
+        Some(title) => format!("# {title}\n\n- Session: `{}`\n", session.id),
+        None => format!("# Session `{}`\n\n", session.id),
+    };
+    if !session.channel.is_empty() {
+        let _ = writeln!(out, "- Channel: {}", session.channel);
+    }
+    if !session.sender.is_empty() {
+        let _ = writeln!(out, "- Sender: {}", session.sender);
+    }
+    let _ = writeln!(out, "- Started: {}", session.created_at);
+    let _ = writeln!(out, "- Last activity: {}", session.updated_at);
+    if let Some(metadata) = session.metadata.as_object() {
+        for (key, value) in metadata {
+            if key == super::title::TITLE_METADATA_KEY {
+                continue;
+            }
+            let value = value
+                .as_str()
+                .map_or_else(|| value.to_string(), str::to_string);
+            let _ = writeln!(out, "- {}: {value}", role_title(key));
+        }
+    }
+
+    for entry in timeline(archive) {
+        match entry {
+            Entry::Message(message) => {
+                let body = replace_markers(&message.content, |kind, target| {
+                    let name = attachment_name(target);
+                    if !is_linkable(target) {
+                        format!("📎 *{} attachment* `{target}`", kind.to_lowercase())
+                    } else if kind == "IMAGE" && !target.starts_with("data:") {
+                        format!("![{name}]({target})")
+                    } else if target.starts_with("data:") {
+                        format!("📎 *{} attachment ({name})*", kind.to_lowercase())
+                    } else {
+                        format!("📎 [{name}]({target})")
+                    }
+                });
+                let _ = write!(
+                    out,
+                    "\n## {} · {}\n\n{}\n",
+                    role_title(&message.role),
+                    message.created_at,
+                    body.trim_end()
+                );
+            }
+            Entry::ToolCall(call) => {
+                let status = if call.success { "ok" } else { "failed" };
+                let arguments = pretty_arguments(&call.arguments);
+                let output = clip(&call.output);
+                let arg_fence = fence_for(&arguments);
+                let out_fence = fence_for(&output);
+                let _ = write!(
+                    out,
+                    "\n> 🔧 **{}** ({status}, {} ms)\n\n<details><summary>Arguments and output</summary>\n\n\
+                     {arg_fence}json\n{arguments}\n{arg_fence}\n\n{out_fence}\n{output}\n{out_fence}\n\n</details>\n",
+                    call.tool, call.duration_ms
+                );
+            }
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:52rem;margin:2rem auto;\
+padding:0 1rem;color:#222}header{color:#555;border-bottom:1px solid #ddd;margin-bottom:1rem}\
+.msg{margin:1rem 0;padding:.75rem 1rem;border-radius:8px;white-space:pre-wrap}\
+.user{background:#eef4ff}.assistant{background:#f5f5f5}.meta{font-size:.8rem;color:#777}\
+details{margin:.5rem 0 .5rem 1rem;font-size:.9rem}pre{overflow-x:auto;background:#fafafa;\
+padding:.5rem;border:1px solid #eee}img{max-width:100%}.failed summary{color:#b00}";
+
+fn render_html(archive: &SessionArchive) -> String {
+    let session = &archive.session;
+    let id = escape_html(&session.id);
+    let titled = super::title::title(session);
+    let heading = titled.map_or_else(|| format!("Session {id}"), escape_html);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{heading}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n\
+         <header><h1>{heading}</h1>\n<p>"
+    );
+    if titled.is_some() {
+        let _ = write!(out, "Session: {id}<br>");
+    }
+    for (label, value) in [
+        ("Channel", session.channel.as_str()),
+        ("Sender", session.sender.as_str()),
+        ("Started", session.created_at.as_str()),
+        ("Last activity", session.updated_at.as_str()),
+    ] {
+        if !value.is_empty() {
+            let _ = write!(out, "{label}: {}<br>", escape_html(value));
+        }
+    }
+    out.push_str("</p></header>\n");
+
+    for entry in timeline(archive) {
+        match entry {
+            Entry::Message(message) => {
+                let escaped = escape_html(&message.content);
+                // Markers survive escaping unchanged; targets are escaped already.
+                let body = replace_markers(&escaped, |kind, target| {
+                    let name = attachment_name(target);
+                    if !is_linkable(target) {
+                        format!("📎 {} <code>{target}</code>", kind.to_lowercase())
+                    } else if kind == "IMAGE" {
+                        format!("<img src=\"{target}\" alt=\"{name}\">")
+                    } else {
+                        format!(
+                            "<a href=\"{target}\">📎 {} ({name})</a>",
+                            kind.to_lowercase()
+                        )
+                    }
+                });
+                let role = escape_html(&message.role);
+                let _ = writeln!(
+                    out,
+                    "<div class=\"msg {role}\"><div class=\"meta\">{} · {}</div>{}</div>",
+                    escape_html(&role_title(&message.role)),
+                    escape_html(&message.created_at),
+                    body.trim_end()
+                );
+            }
+            Entry::ToolCall(call) => {
+                let class = if call.success { "ok" } else { "failed" };
+                let status = if call.success { "ok" } else { "failed" };
+                let _ = writeln!(
+                    out,
+                    "<details class=\"{class}\"><summary>🔧 {} ({status}, {} ms)</summary>\
+                     <pre>{}</pre><pre>{}</pre></details>",
+                    escape_html(&call.tool),
+                    call.duration_ms,
+                    escape_html(&pretty_arguments(&call.arguments)),
+                    escape_html(&clip(&call.output))
+                );
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversations::ToolCallRecord;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn sample_store() -> (TempDir, ConversationStore) {
+        let tmp = TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        store
+            .ensure_session("tg_alice", "telegram", "alice")
+            .unwrap();
+        store
+            .append_message("tg_alice", "user", "list files [IMAGE:/tmp/shot.png]")
+            .unwrap();
+        store
+            .record_tool_call(
+                "tg_alice",
+                &ToolCallRecord {
+                    tool: "shell",
+                    arguments: &serde_json::json!({"command": "ls"}),
+                    output: "a.txt\n<b>.txt",
+                    success: true,
+                    duration: Duration::from_millis(7),
+                },
+            )
+            .unwrap();
+        store
+            .append_message("tg_alice", "assistant", "Two files.")
+            .unwrap();
+        (tmp, store)
+    }
+
+    #[test]
+    fn json_roundtrip_imports_under_new_id() {
+        let (_tmp, store) = sample_store();
+        let json = render(&load(&store, "tg_alice").unwrap(), ExportFormat::Json).unwrap();
+
+        let other = TempDir::new().unwrap();
+        let target = ConversationStore::open(other.path()).unwrap();
+        let id = import(&target, parse(&json).unwrap(), None, false).unwrap();
+        assert_eq!(id, "tg_alice");
+        assert!(import(&target, parse(&json).unwrap(), None, false).is_err());
+        import(&target, parse(&json).unwrap(), None, true).unwrap();
+        import(&target, parse(&json).unwrap(), Some("copy"), false).unwrap();
+
+        let copy = load(&target, "copy").unwrap();
+        assert_eq!(copy.session.channel, "telegram");
+        assert_eq!(copy.messages.len(), 2);
+        assert_eq!(copy.tool_calls[0].tool, "shell");
+        assert_eq!(
+            target.session("tg_alice").unwrap().unwrap().message_count,
+            2
+        );
+    }
+
+    #[test]
+    fn markdown_places_tool_calls_before_reply_and_links_attachments() {
+        let (_tmp, store) = sample_store();
+        let md = render(&load(&store, "tg_alice").unwrap(), ExportFormat::Markdown).unwrap();
+        let user = md.find("list files").unwrap();
+        let tool = md.find("**shell**").unwrap();
+        let reply = md.find("Two files.").unwrap();
+        assert!(user < tool && tool < reply);
+        assert!(md.contains("![shot.png](/tmp/shot.png)"));
+        assert!(md.contains("\"command\": \"ls\""));
+    }
+
+    #[test]
+    fn html_escapes_content() {
+        let (_tmp, store) = sample_store();
+        let html = render(&load(&store, "tg_alice").unwrap(), ExportFormat::Html).unwrap();
+        assert!(html.contains("&lt;b&gt;.txt"));
+        assert!(html.contains("<img src=\"/tmp/shot.png\""));
+        assert!(!html.contains("<b>.txt"));
+        assert!(html.contains("<h1>Session tg_alice</h1>"));
+
+        store
+            .set_session_metadata("tg_alice", "title", &serde_json::json!("Files & <dirs>"))
+            .unwrap();
+        let html = render(&load(&store, "tg_alice").unwrap(), ExportFormat::Html).unwrap();
+        assert!(html.contains("<h1>Files &amp; &lt;dirs&gt;</h1>"));
+        assert!(html.contains("Session: tg_alice<br>"));
+    }
+
+    #[test]
+    fn parse_rejects_foreign_json() {
+        assert!(parse("{\"format\":\"other\",\"version\":1}").is_err());
+        assert!(parse("# Session").is_err());
+    }
+
+    #[test]
+    fn only_safe_targets_become_links() {
+        assert!(is_linkable("https://example.com/a.png"));
+        assert!(is_linkable("/tmp/a.pdf"));
+        assert!(is_linkable("C:\\docs\\a.pdf"));
+        assert!(!is_linkable("javascript:alert(1)"));
+        assert!(!is_linkable("JavaScript:alert(1)"));
+    }
+
+    #[test]
+    fn replace_markers_keeps_unknown_brackets() {
+        let out = replace_markers("see [1] and [DOCUMENT:/a/b.pdf]", |k, t| {
+            format!("<{k}|{t}>")
+        });
+        assert_eq!(out, "see [1] and <DOCUMENT|/a/b.pdf>");
+    }
+}
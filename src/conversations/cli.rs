@@ -0,0 +1,484 @@
+use super::archive::{self, ExportFormat};
+use super::{ConversationStore, SearchFilter, SearchHit, SessionSummary};
+use crate::config::Config;
+use crate::util::truncate_with_width;
+use crate::SessionCommands;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use console::style;
+
+/// Terminal width used when stdout is not a TTY.
+const FALLBACK_WIDTH: usize = 100;
+
+/// Options for `zeroclaw search`.
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    pub channel: Option<String>,
+    pub role: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub limit: usize,
+}
+
+/// Handle `zeroclaw search`: print matching messages, best first.
+///
+/// Returns the session of the best match so the caller can reopen it.
+pub fn handle_search(
+    config: &Config,
+    query: &str,
+    options: &SearchOptions,
+) -> Result<Option<String>> {
+    if query.trim().is_empty() {
+        bail!("Search query must not be empty");
+    }
+    let filter = SearchFilter {
+        channel: options.channel.clone(),
+        role: options.role.clone(),
+        since: options
+            .since
+            .as_deref()
+            .map(|s| parse_date_bound(s, false))
+            .transpose()?,
+        until: options
+            .until
+            .as_deref()
+            .map(|s| parse_date_bound(s, true))
+            .transpose()?,
+        limit: options.limit.max(1),
+    };
+
+    let store = ConversationStore::open(&config.workspace_dir)?;
+    let hits = store.search(query, &filter)?;
+    if hits.is_empty() {
+        println!("No messages match \"{query}\".");
+        return Ok(None);
+    }
+
+    let width = console::Term::stdout()
+        .size_checked()
+        .map_or(FALLBACK_WIDTH, |(_, cols)| usize::from(cols));
+    for hit in &hits {
+        print_hit(hit, width);
+    }
+    println!(
+        "\n{} match(es). Reopen one with `zeroclaw agent --session <session>`.",
+        hits.len()
+    );
+    Ok(hits.into_iter().next().map(|hit| hit.message.session_id))
+}
+
+/// Handle `zeroclaw export`: write a session to `output`, or stdout.
+pub fn handle_export(
+    config: &Config,
+    session_id: &str,
+    format: ExportFormat,
+    output: Option<&std::path::Path>,
+) -> Result<()> {
+    let store = ConversationStore::open(&config.workspace_dir)?;
+    let rendered = archive::render(&archive::load(&store, session_id)?, format)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!(
+                "{} Exported session {session_id} to {}",
+                style("✓").green().bold(),
+                path.display()
+            );
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// Handle `zeroclaw import`: restore a session from a JSON export.
+pub fn handle_import(
+    config: &Config,
+    file: &std::path::Path,
+    rename: Option<&str>,
+    replace: bool,
+) -> Result<()> {
+    let raw = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let parsed = archive::parse(&raw)?;
+    let messages = parsed.messages.len();
+    let store = ConversationStore::open(&config.workspace_dir)?;
+    let id = archive::import(&store, parsed, rename, replace)?;
+    println!(
+        "{} Imported session {id} ({messages} messages)",
+        style("✓").green().bold()
+    );
+    Ok(())
+}
+
+/// Handle `zeroclaw sessions`.
+pub fn handle_sessions(command: SessionCommands, config: &Config) -> Result<()> {
+    let store = ConversationStore::open(&config.workspace_dir)?;
+    match command {
+        SessionCommands::List {
+            all,
+            channel,
+            limit,
+        } => list_sessions(&store, all, channel.as_deref(), limit),
+        SessionCommands::Show { session, limit } => {
+            show_session(&store, &find_session(&store, &session)?, limit)
+        }
+        SessionCommands::Reset { session, yes } => {
+            let session = find_session(&store, &session)?;
+            if !yes && !confirm(&format!("Clear all messages in '{}'?", session.id))? {
+                return Ok(());
+            }
+            store.clear_session(&session.id)?;
+            crate::security::workspace::end_session(&config.workspace_dir, &session.id);
+            println!(
+                "{} Reset session {} ({} messages removed)",
+                style("✓").green().bold(),
+                session.id,
+                session.message_count
+            );
+            Ok(())
+        }
+        SessionCommands::Delete { session, yes } => {
+            let session = find_session(&store, &session)?;
+            if !yes && !confirm(&format!("Delete session '{}'?", session.id))? {
+                return Ok(());
+            }
+            store.delete_session(&session.id)?;
+            crate::security::workspace::end_session(&config.workspace_dir, &session.id);
+            println!(
+                "{} Deleted session {}",
+                style("✓").green().bold(),
+                session.id
+            );
+            Ok(())
+        }
+        SessionCommands::Rename { session, name } => {
+            let session = find_session(&store, &session)?;
+            let name = name.as_deref().map(str::trim).filter(|n| !n.is_empty());
+            store.rename_session(&session.id, name)?;
+            match name {
+                Some(name) => println!(
+                    "{} Session {} is now named '{name}'",
+                    style("✓").green().bold(),
+                    session.id
+                ),
+                None => println!(
+                    "{} Removed the name of session {}",
+                    style("✓").green().bold(),
+                    session.id
+                ),
+            }
+            Ok(())
+        }
+        SessionCommands::Checkpoint { session, name } => {
+            let session = find_session(&store, &session)?;
+            let name = name.as_deref().map(str::trim).filter(|n| !n.is_empty());
+            let checkpoint = store.create_checkpoint(&session.id, name)?;
+            println!(
+                "{} Checkpoint '{}' at message #{} of {}",
+                style("✓").green().bold(),
+                checkpoint.name,
+                checkpoint.message_id,
+                session.id
+            );
+            println!(
+                "  Branch from it with: zeroclaw branch {} --from {}",
+                session.id, checkpoint.name
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Handle `zeroclaw branch`: copy a session up to a message or checkpoint
+/// into a new session. Returns the branch's id.
+pub fn handle_branch(
+    config: &Config,
+    session: &str,
+    from: &str,
+    name: Option<&str>,
+) -> Result<String> {
+    let store = ConversationStore::open(&config.workspace_dir)?;
+    let source = find_session(&store, session)?;
+    let from_message = store.resolve_message(&source.id, from)?;
+    let mut n = store
+        .list_sessions(usize::MAX)?
+        .into_iter()
+        .filter(|s| s.parent_id.as_deref() == Some(source.id.as_str()))
+        .count()
+        + 1;
+    while store
+        .resolve_session(&format!("{}~{n}", source.id))?
+        .is_some()
+    {
+        n += 1;
+    }
+    let branch_id = format!("{}~{n}", source.id);
+    let branch = store.branch_session(&source.id, from_message, &branch_id)?;
+    if let Some(name) = name.map(str::trim).filter(|n| !n.is_empty()) {
+        store.rename_session(&branch.id, Some(name))?;
+    }
+    println!(
+        "{} Branched {} at message #{from_message} into {} ({} messages)",
+        style("✓").green().bold(),
+        source.id,
+        branch.id,
+        branch.message_count
+    );
+    println!("  Continue it with: zeroclaw agent --session {}", branch.id);
+    Ok(branch.id)
+}
+
+fn find_session(store: &ConversationStore, id_or_name: &str) -> Result<SessionSummary> {
+    store
+        .resolve_session(id_or_name)?
+        .with_context(|| format!("No session with ID or name '{id_or_name}'"))
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    let confirmed = dialoguer::Confirm::new()
+        .with_prompt(format!("  {prompt}"))
+        .default(false)
+        .interact()?;
+    if !confirmed {
+        println!("Aborted.");
+    }
+    Ok(confirmed)
+}
+
+fn list_sessions(
+    store: &ConversationStore,
+    all: bool,
+    channel: Option<&str>,
+    limit: usize,
+) -> Result<()> {
+    let sessions: Vec<SessionSummary> = store
+        .list_sessions(usize::MAX)?
+        .into_iter()
+        .filter(|s| all || s.archived_at.is_none())
+        .filter(|s| channel.is_none_or(|c| s.channel == c))
+        .take(limit.max(1))
+        .collect();
+    let sessions = branch_tree(sessions);
+    if sessions.is_empty() {
+        println!("No sessions found.");
+        return Ok(());
+    }
+
+    let width = console::Term::stdout()
+        .size_checked()
+        .map_or(FALLBACK_WIDTH, |(_, cols)| usize::from(cols));
+    println!(
+        "{}",
+        style(format!(
+            "{:<16}  {:<10}  {:>5}  {:<12}  {:<24}  SESSION",
+            "LAST ACTIVE", "CHANNEL", "MSGS", "NAME", "TITLE"
+        ))
+        .bold()
+    );
+    for (prefix, session) in &sessions {
+        let channel = if session.channel.is_empty() {
+            "-"
+        } else {
+            session.channel.as_str()
+        };
+        let line = format!(
+            "{:<16}  {:<10}  {:>5}  {:<12}  {:<24}  {}",
+            local_time(&session.updated_at),
+            truncate_with_width(channel, 10, "…"),
+            session.message_count,
+            truncate_with_width(session.name.as_deref().unwrap_or("-"), 12, "…"),
+            truncate_with_width(super::title::title(session).unwrap_or("-"), 24, "…"),
+            format!("{prefix}{}", session.id)
+        );
+        let line = truncate_with_width(&line, width, "…");
+        if session.archived_at.is_some() {
+            println!("{}", style(format!("{line} (archived)")).dim());
+        } else {
+            println!("{line}");
+        }
+    }
+    Ok(())
+}
+
+fn show_session(store: &ConversationStore, session: &SessionSummary, limit: usize) -> Result<()> {
+    println!("{}", style(&session.id).bold());
+    if let Some(name) = &session.name {
+        println!("  Name:     {name}");
+    }
+    if let Some(title) = super::title::title(session) {
+        println!("  Title:    {title}");
+    }
+    if !session.channel.is_empty() {
+        println!("  Channel:  {}", session.channel);
+    }
+    if !session.sender.is_empty() {
+        println!("  Contact:  {}", session.sender);
+    }
+    println!("  Created:  {}", local_time(&session.created_at));
+    println!("  Active:   {}", local_time(&session.updated_at));
+    if let Some(archived_at) = &session.archived_at {
+        println!("  Archived: {}", local_time(archived_at));
+    }
+    println!("  Messages: {}", session.message_count);
+    if let Some(parent) = &session.parent_id {
+        match session.branched_from {
+            Some(message) => println!("  Branched: from {parent} at message #{message}"),
+            None => println!("  Branched: from {parent}"),
+        }
+    }
+    let checkpoints = store.checkpoints(&session.id)?;
+    if !checkpoints.is_empty() {
+        let list: Vec<String> = checkpoints
+            .iter()
+            .map(|c| format!("{} (#{})", c.name, c.message_id))
+            .collect();
+        println!("  Checkpoints: {}", list.join(", "));
+    }
+
+    let messages = store.recent_messages(&session.id, limit.max(1))?;
+    if !messages.is_empty() {
+        println!();
+        if messages.len() < session.message_count {
+            println!(
+                "{}",
+                style(format!(
+                    "… {} earlier messages",
+                    session.message_count - messages.len()
+                ))
+                .dim()
+            );
+        }
+        for message in &messages {
+            println!(
+                "{} {}",
+                style(format!(
+                    "#{} [{}] {}:",
+                    message.id,
+                    local_time(&message.created_at),
+                    message.role
+                ))
+                .dim(),
+                message.content
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Order sessions so branches follow their parent, each paired with the
+/// tree prefix drawn before its id. Sessions whose parent is not listed are
+/// roots and keep their order; branches are listed oldest first.
+fn branch_tree(sessions: Vec<SessionSummary>) -> Vec<(String, SessionSummary)> {
+    fn visit(
+        index: usize,
+        sessions: &[SessionSummary],
+        children: &std::collections::HashMap<&str, Vec<usize>>,
+        prefix: &str,
+        branch: &str,
+        out: &mut Vec<(String, usize)>,
+    ) {
+        out.push((format!("{prefix}{branch}"), index));
+        let Some(kids) = children.get(sessions[index].id.as_str()) else {
+            return;
+        };
+        let nested = match branch {
+            "" => String::new(),
+            "└─ " => format!("{prefix}   "),
+            _ => format!("{prefix}│  "),
+        };
+        for (position, &kid) in kids.iter().enumerate() {
+            let branch = if position + 1 == kids.len() {
+                "└─ "
+            } else {
+                "├─ "
+            };
+            visit(kid, sessions, children, &nested, branch, out);
+        }
+    }
+
+    let listed: std::collections::HashSet<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    let mut children: std::collections::HashMap<&str, Vec<usize>> =
+        std::collections::HashMap::new();
+    let mut roots = Vec::new();
+    for (index, session) in sessions.iter().enumerate() {
+        match session.parent_id.as_deref().filter(|p| listed.contains(p)) {
+            Some(parent) => children.entry(parent).or_default().push(index),
+            None => roots.push(index),
+        }
+    }
+    for kids in children.values_mut() {
+        kids.sort_by(|a, b| sessions[*a].created_at.cmp(&sessions[*b].created_at));
+    }
+
+    let mut order = Vec::with_capacity(sessions.len());
+    for root in roots {
+        visit(root, &sessions, &children, "", "", &mut order);
+    }
+    let mut slots: Vec<Option<SessionSummary>> = sessions.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .filter_map(|(prefix, index)| slots[index].take().map(|session| (prefix, session)))
+        .collect()
+}
+
+/// A stored timestamp as `YYYY-MM-DD HH:MM` in `[locale] timezone`, or
+/// unchanged if unparsable.
+fn local_time(timestamp: &str) -> String {
+    crate::time::display_stored(timestamp, "%Y-%m-%d %H:%M")
+}
+
+fn print_hit(hit: &SearchHit, width: usize) {
+    let when = local_time(&hit.message.created_at);
+    let channel = if hit.channel.is_empty() {
+        "-"
+    } else {
+        hit.channel.as_str()
+    };
+    let header = format!(
+        "{when}  {channel}  {}  {}",
+        hit.message.role, hit.message.session_id
+    );
+    let snippet: String = hit.snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    println!("{}", style(truncate_with_width(&header, width, "…")).dim());
+    println!(
+        "  {}",
+        truncate_with_width(&snippet, width.saturating_sub(2), "…")
+    );
+}
+
+/// Parse `YYYY-MM-DD` or RFC 3339. A bare date used as an upper bound covers
+/// the whole day.
+pub fn parse_date_bound(value: &str, end_of_range: bool) -> Result<DateTime<Utc>> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(value) {
+        return Ok(t.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date '{value}' (expected YYYY-MM-DD or RFC 3339)"))?;
+    let date = if end_of_range {
+        date + Duration::days(1)
+    } else {
+        date
+    };
+    Ok(date.and_time(chrono::NaiveTime::MIN).and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_bounds_cover_whole_days() {
+        let since = parse_date_bound("2026-03-01", false).unwrap();
+        let until = parse_date_bound("2026-03-01", true).unwrap();
+        assert_eq!(since.to_rfc3339(), "2026-03-01T00:00:00+00:00");
+        assert_eq!(until.to_rfc3339(), "2026-03-02T00:00:00+00:00");
+        assert_eq!(
+            parse_date_bound("2026-03-01T12:00:00+02:00", true)
+                .unwrap()
+                .to_rfc3339(),
+            "2026-03-01T10:00:00+00:00"
+        );
+        assert!(parse_date_bound("yesterday", false).is_err());
+    }
+}
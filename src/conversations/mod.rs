@@ -0,0 +1,106 @@
+//! Persistent conversation history.
+//!
+//! Sessions, messages and tool calls from the CLI and channels are written
+//! to a SQLite store so history survives restarts and other commands can
+//! query it.
+
+pub mod archive;
+pub mod cli;
+pub mod store;
+pub mod title;
+
+pub use store::{
+    Checkpoint, ConversationStore, SearchFilter, SearchHit, SessionSummary, StoredMessage,
+    StoredToolCall, ToolCallRecord,
+};
+
+use crate::config::Config;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Open the store configured in `[conversations]`, or `None` when disabled.
+///
+/// Failures are logged rather than returned: losing persistence should not
+/// stop the agent from answering.
+pub fn open_from_config(config: &Config) -> Option<Arc<ConversationStore>> {
+    if !config.conversations.enabled {
+        return None;
+    }
+    match ConversationStore::open(&config.workspace_dir) {
+        Ok(store) => {
+            if config.conversations.retention_days > 0 {
+                match store.prune_older_than(config.conversations.retention_days) {
+                    Ok(0) => {}
+                    Ok(removed) => tracing::info!("Pruned {removed} expired conversation sessions"),
+                    Err(e) => tracing::warn!("Failed to prune conversation store: {e}"),
+                }
+            }
+            let idle_hours = config.conversations.archive_after_idle_hours;
+            if idle_hours > 0 {
+                match store.archive_idle(chrono::Duration::hours(i64::from(idle_hours))) {
+                    Ok(0) => {}
+                    Ok(archived) => {
+                        tracing::info!("Archived {archived} idle conversation sessions")
+                    }
+                    Err(e) => tracing::warn!("Failed to archive idle conversations: {e}"),
+                }
+            }
+            Some(Arc::new(store))
+        }
+        Err(e) => {
+            tracing::warn!("Conversation store unavailable: {e:#}");
+            None
+        }
+    }
+}
+
+/// A store bound to one session, handed to the tool loop so tool calls land
+/// in the right conversation.
+#[derive(Clone)]
+pub struct SessionHandle {
+    store: Arc<ConversationStore>,
+    session_id: String,
+}
+
+impl SessionHandle {
+    pub fn new(store: Arc<ConversationStore>, session_id: impl Into<String>) -> Self {
+        Self {
+            store,
+            session_id: session_id.into(),
+        }
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    pub fn store(&self) -> &ConversationStore {
+        &self.store
+    }
+
+    pub fn append_message(&self, role: &str, content: &str) {
+        if let Err(e) = self.store.append_message(&self.session_id, role, content) {
+            tracing::warn!(session = %self.session_id, "Failed to persist message: {e}");
+        }
+    }
+
+    pub fn record_tool_call(
+        &self,
+        tool: &str,
+        arguments: &serde_json::Value,
+        output: &str,
+        success: bool,
+        duration: Duration,
+    ) {
+        let record = ToolCallRecord {
+            tool,
+            arguments,
+            output,
+            success,
+            duration,
+        };
+        if let Err(e) = self.store.record_tool_call(&self.session_id, &record) {
+            tracing::warn!(session = %self.session_id, "Failed to persist tool call: {e}");
+        }
+    }
+}
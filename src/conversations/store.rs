@@ -0,0 +1,1250 @@
+//! SQLite conversation store.
+//!
+//! One database (`state/conversations.db` in the workspace) holds every
+//! session, its messages and the tool calls made while answering them.
+//! The schema is versioned with `PRAGMA user_version`; [`MIGRATIONS`] is
+//! append-only, so older databases upgrade in place on open.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Schema history, applied by [`crate::util::sqlite::migrate`].
+const MIGRATIONS: &[&str] = &[
+    // v1: sessions, messages, tool calls
+    "CREATE TABLE sessions (
+        id          TEXT PRIMARY KEY,
+        channel     TEXT NOT NULL DEFAULT '',
+        sender      TEXT NOT NULL DEFAULT '',
+        metadata    TEXT NOT NULL DEFAULT '{}',
+        created_at  TEXT NOT NULL,
+        updated_at  TEXT NOT NULL
+    );
+    CREATE INDEX idx_sessions_updated ON sessions(updated_at);
+
+    CREATE TABLE messages (
+        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+        session_id  TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+        role        TEXT NOT NULL,
+        content     TEXT NOT NULL,
+        created_at  TEXT NOT NULL
+    );
+    CREATE INDEX idx_messages_session ON messages(session_id, id);
+
+    CREATE TABLE tool_calls (
+        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+        session_id  TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+        tool        TEXT NOT NULL,
+        arguments   TEXT NOT NULL,
+        output      TEXT NOT NULL,
+        success     INTEGER NOT NULL,
+        duration_ms INTEGER NOT NULL,
+        created_at  TEXT NOT NULL
+    );
+    CREATE INDEX idx_tool_calls_session ON tool_calls(session_id, id);
+    CREATE INDEX idx_tool_calls_tool ON tool_calls(tool);",
+    // v2: full-text index over message content
+    "CREATE VIRTUAL TABLE messages_fts USING fts5(
+        content, content=messages, content_rowid=id
+    );
+    CREATE TRIGGER messages_fts_ai AFTER INSERT ON messages BEGIN
+        INSERT INTO messages_fts(rowid, content) VALUES (new.id, new.content);
+    END;
+    CREATE TRIGGER messages_fts_ad AFTER DELETE ON messages BEGIN
+        INSERT INTO messages_fts(messages_fts, rowid, content)
+        VALUES ('delete', old.id, old.content);
+    END;
+    CREATE TRIGGER messages_fts_au AFTER UPDATE ON messages BEGIN
+        INSERT INTO messages_fts(messages_fts, rowid, content)
+        VALUES ('delete', old.id, old.content);
+        INSERT INTO messages_fts(rowid, content) VALUES (new.id, new.content);
+    END;
+    INSERT INTO messages_fts(messages_fts) VALUES ('rebuild');",
+    // v3: session names and archiving
+    "ALTER TABLE sessions ADD COLUMN name TEXT;
+    ALTER TABLE sessions ADD COLUMN archived_at TEXT;
+    CREATE UNIQUE INDEX idx_sessions_name ON sessions(name);",
+    // v4: branches and checkpoints
+    "ALTER TABLE sessions ADD COLUMN parent_id TEXT;
+    ALTER TABLE sessions ADD COLUMN branched_from INTEGER;
+    CREATE INDEX idx_sessions_parent ON sessions(parent_id);
+
+    CREATE TABLE checkpoints (
+        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+        session_id  TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+        name        TEXT NOT NULL,
+        message_id  INTEGER NOT NULL,
+        created_at  TEXT NOT NULL,
+        UNIQUE(session_id, name)
+    );",
+];
+
+/// Columns read by [`session_from_row`].
+const SESSION_COLUMNS: &str = "s.id, s.channel, s.sender, s.metadata, s.created_at, s.updated_at,
+    (SELECT COUNT(*) FROM messages m WHERE m.session_id = s.id), s.name, s.archived_at,
+    s.parent_id, s.branched_from";
+
+/// A session row plus its message count.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub channel: String,
+    pub sender: String,
+    pub metadata: serde_json::Value,
+    pub created_at: String,
+    pub updated_at: String,
+    pub message_count: usize,
+    /// Optional unique name set with `zeroclaw sessions rename`.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// When the session was archived for inactivity, if it was.
+    #[serde(default)]
+    pub archived_at: Option<String>,
+    /// Session this one was branched from, if it is a branch.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// Last message of the parent copied into this branch.
+    #[serde(default)]
+    pub branched_from: Option<i64>,
+}
+
+/// A persisted chat message.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StoredMessage {
+    pub id: i64,
+    pub session_id: String,
+    pub role: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+/// A named point in a session to branch from later.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Checkpoint {
+    pub session_id: String,
+    pub name: String,
+    /// Newest message of the session when the checkpoint was taken.
+    pub message_id: i64,
+    pub created_at: String,
+}
+
+/// Filters for [`ConversationStore::search`].
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilter {
+    pub channel: Option<String>,
+    pub role: Option<String>,
+    /// Only messages created at or after this instant.
+    pub since: Option<DateTime<Utc>>,
+    /// Only messages created before this instant.
+    pub until: Option<DateTime<Utc>>,
+    pub limit: usize,
+}
+
+/// A message matching a full-text search, best match first.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub message: StoredMessage,
+    pub channel: String,
+    /// Matching excerpt with the hit terms wrapped in `[` `]`.
+    pub snippet: String,
+}
+
+/// A tool call to persist.
+#[derive(Debug, Clone)]
+pub struct ToolCallRecord<'a> {
+    pub tool: &'a str,
+    pub arguments: &'a serde_json::Value,
+    pub output: &'a str,
+    pub success: bool,
+    pub duration: Duration,
+}
+
+/// A persisted tool call.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StoredToolCall {
+    pub id: i64,
+    pub session_id: String,
+    pub tool: String,
+    pub arguments: String,
+    pub output: String,
+    pub success: bool,
+    pub duration_ms: u64,
+    pub created_at: String,
+}
+
+/// SQLite-backed store for sessions, messages and tool calls.
+pub struct ConversationStore {
+    conn: Mutex<Connection>,
+    db_path: PathBuf,
+}
+
+impl ConversationStore {
+    /// Open (or create) the store in `workspace_dir/state/conversations.db`.
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        Self::open_at(&workspace_dir.join("state").join("conversations.db"))
+    }
+
+    /// Open (or create) the store at an explicit path.
+    pub fn open_at(db_path: &Path) -> Result<Self> {
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = crate::security::storage_encryption::open(db_path)
+            .with_context(|| format!("Failed to open conversation store {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA foreign_keys = ON;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        crate::util::sqlite::migrate(&conn, "Conversation store", MIGRATIONS)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            db_path: db_path.to_path_buf(),
+        })
+    }
+
+    /// Current schema version.
+    pub fn schema_version(&self) -> Result<usize> {
+        let conn = self.conn.lock();
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        Ok(usize::try_from(version).unwrap_or(0))
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    /// Create the session if it does not exist yet; fills in channel/sender
+    /// when the row was created implicitly by an earlier write.
+    pub fn ensure_session(&self, session_id: &str, channel: &str, sender: &str) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO sessions (id, channel, sender, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(id) DO UPDATE SET
+                 channel = CASE WHEN sessions.channel = '' THEN excluded.channel ELSE sessions.channel END,
+                 sender  = CASE WHEN sessions.sender  = '' THEN excluded.sender  ELSE sessions.sender  END",
+            params![session_id, channel, sender, now],
+        )?;
+        Ok(())
+    }
+
+    /// Set one top-level key in the session's metadata object.
+    pub fn set_session_metadata(
+        &self,
+        session_id: &str,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> Result<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        touch_session(&tx, session_id)?;
+        let raw: String = tx.query_row(
+            "SELECT metadata FROM sessions WHERE id = ?1",
+            params![session_id],
+            |row| row.get(0),
+        )?;
+        let mut metadata: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(&raw).unwrap_or_default();
+        metadata.insert(key.to_string(), value.clone());
+        tx.execute(
+            "UPDATE sessions SET metadata = ?2 WHERE id = ?1",
+            params![session_id, serde_json::Value::Object(metadata).to_string()],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Append a message and return its id.
+    pub fn append_message(&self, session_id: &str, role: &str, content: &str) -> Result<i64> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let now = touch_session(&tx, session_id)?;
+        tx.execute(
+            "INSERT INTO messages (session_id, role, content, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![session_id, role, content, now],
+        )?;
+        let id = tx.last_insert_rowid();
+        tx.commit()?;
+        Ok(id)
+    }
+
+    /// Delete the session's newest message if it has this role and content.
+    pub fn remove_last_message_if(
+        &self,
+        session_id: &str,
+        role: &str,
+        content: &str,
+    ) -> Result<bool> {
+        let conn = self.conn.lock();
+        let last: Option<(i64, String, String)> = conn
+            .query_row(
+                "SELECT id, role, content FROM messages WHERE session_id = ?1 ORDER BY id DESC LIMIT 1",
+                params![session_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+        match last {
+            Some((id, last_role, last_content)) if last_role == role && last_content == content => {
+                conn.execute("DELETE FROM messages WHERE id = ?1", params![id])?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// The newest `limit` messages of a session, oldest first.
+    pub fn recent_messages(&self, session_id: &str, limit: usize) -> Result<Vec<StoredMessage>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, role, content, created_at FROM (
+                 SELECT * FROM messages WHERE session_id = ?1 ORDER BY id DESC LIMIT ?2
+             ) ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map(params![session_id, sql_limit(limit)], message_from_row)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    /// Up to `limit` messages of a session newer than message `after_id`,
+    /// oldest first.
+    pub fn messages_after(
+        &self,
+        session_id: &str,
+        after_id: i64,
+        limit: usize,
+    ) -> Result<Vec<StoredMessage>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, role, content, created_at FROM messages
+             WHERE session_id = ?1 AND id > ?2 ORDER BY id ASC LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(
+            params![session_id, after_id, sql_limit(limit)],
+            message_from_row,
+        )?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    /// Persist one tool call.
+    pub fn record_tool_call(&self, session_id: &str, call: &ToolCallRecord<'_>) -> Result<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let now = touch_session(&tx, session_id)?;
+        tx.execute(
+            "INSERT INTO tool_calls (session_id, tool, arguments, output, success, duration_ms, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                session_id,
+                call.tool,
+                call.arguments.to_string(),
+                call.output,
+                call.success,
+                i64::try_from(call.duration.as_millis()).unwrap_or(i64::MAX),
+                now
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Tool calls of a session, oldest first.
+    pub fn tool_calls(&self, session_id: &str) -> Result<Vec<StoredToolCall>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, tool, arguments, output, success, duration_ms, created_at
+             FROM tool_calls WHERE session_id = ?1 ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map(params![session_id], |row| {
+            Ok(StoredToolCall {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                tool: row.get(2)?,
+                arguments: row.get(3)?,
+                output: row.get(4)?,
+                success: row.get(5)?,
+                duration_ms: u64::try_from(row.get::<_, i64>(6)?).unwrap_or(0),
+                created_at: row.get(7)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    /// Drop a session's messages, tool calls and title but keep the session
+    /// row and the rest of its metadata.
+    pub fn clear_session(&self, session_id: &str) -> Result<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM messages WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        tx.execute(
+            "DELETE FROM tool_calls WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        tx.execute(
+            "DELETE FROM checkpoints WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        tx.execute(
+            "UPDATE sessions SET metadata = json_remove(metadata, ?2) WHERE id = ?1",
+            params![
+                session_id,
+                format!("$.{}", super::title::TITLE_METADATA_KEY)
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Mark the session's newest message as checkpoint `name` (default
+    /// `cp<N>`). Fails when the session has no messages or the name is taken.
+    pub fn create_checkpoint(&self, session_id: &str, name: Option<&str>) -> Result<Checkpoint> {
+        let conn = self.conn.lock();
+        let message_id: i64 = conn
+            .query_row(
+                "SELECT MAX(id) FROM messages WHERE session_id = ?1",
+                params![session_id],
+                |row| row.get::<_, Option<i64>>(0),
+            )?
+            .with_context(|| format!("Session '{session_id}' has no messages to checkpoint"))?;
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => {
+                let count: i64 = conn.query_row(
+                    "SELECT COUNT(*) FROM checkpoints WHERE session_id = ?1",
+                    params![session_id],
+                    |row| row.get(0),
+                )?;
+                format!("cp{}", count + 1)
+            }
+        };
+        if name.parse::<i64>().is_ok() {
+            anyhow::bail!("Checkpoint names cannot be numbers; they would shadow message IDs");
+        }
+        let now = Utc::now().to_rfc3339();
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO checkpoints (session_id, name, message_id, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![session_id, name, message_id, now],
+        )?;
+        if inserted == 0 {
+            anyhow::bail!("Session '{session_id}' already has a checkpoint named '{name}'");
+        }
+        Ok(Checkpoint {
+            session_id: session_id.to_string(),
+            name,
+            message_id,
+            created_at: now,
+        })
+    }
+
+    /// Checkpoints of a session, oldest first.
+    pub fn checkpoints(&self, session_id: &str) -> Result<Vec<Checkpoint>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT session_id, name, message_id, created_at
+             FROM checkpoints WHERE session_id = ?1 ORDER BY message_id ASC, id ASC",
+        )?;
+        let rows = stmt.query_map(params![session_id], |row| {
+            Ok(Checkpoint {
+                session_id: row.get(0)?,
+                name: row.get(1)?,
+                message_id: row.get(2)?,
+                created_at: row.get(3)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    /// Resolve a message ID or checkpoint name to a message of this session.
+    pub fn resolve_message(&self, session_id: &str, reference: &str) -> Result<i64> {
+        let conn = self.conn.lock();
+        let found: Option<i64> = match reference.trim().trim_start_matches('#').parse::<i64>() {
+            Ok(id) => conn
+                .query_row(
+                    "SELECT id FROM messages WHERE id = ?1 AND session_id = ?2",
+                    params![id, session_id],
+                    |row| row.get(0),
+                )
+                .optional()?,
+            Err(_) => conn
+                .query_row(
+                    "SELECT message_id FROM checkpoints WHERE session_id = ?1 AND name = ?2",
+                    params![session_id, reference.trim()],
+                    |row| row.get(0),
+                )
+                .optional()?,
+        };
+        found.with_context(|| {
+            format!("Session '{session_id}' has no message or checkpoint '{reference}'")
+        })
+    }
+
+    /// Start session `branch_id` as a copy of `source_id` up to and including
+    /// message `from_message`, with the tool calls made until then. The
+    /// source is left untouched.
+    pub fn branch_session(
+        &self,
+        source_id: &str,
+        from_message: i64,
+        branch_id: &str,
+    ) -> Result<SessionSummary> {
+        {
+            let mut conn = self.conn.lock();
+            let tx = conn.transaction()?;
+            let cutoff: String = tx
+                .query_row(
+                    "SELECT created_at FROM messages WHERE id = ?1 AND session_id = ?2",
+                    params![from_message, source_id],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .with_context(|| {
+                    format!("Message {from_message} does not belong to session '{source_id}'")
+                })?;
+            let now = Utc::now().to_rfc3339();
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO sessions
+                     (id, channel, sender, metadata, created_at, updated_at, parent_id, branched_from)
+                 SELECT ?2, channel, sender, metadata, ?3, ?3, id, ?4
+                 FROM sessions WHERE id = ?1",
+                params![source_id, branch_id, now, from_message],
+            )?;
+            if inserted == 0 {
+                anyhow::bail!("Session '{branch_id}' already exists");
+            }
+            tx.execute(
+                "INSERT INTO messages (session_id, role, content, created_at)
+                 SELECT ?2, role, content, created_at FROM messages
+                 WHERE session_id = ?1 AND id <= ?3 ORDER BY id",
+                params![source_id, branch_id, from_message],
+            )?;
+            tx.execute(
+                "INSERT INTO tool_calls
+                     (session_id, tool, arguments, output, success, duration_ms, created_at)
+                 SELECT ?2, tool, arguments, output, success, duration_ms, created_at
+                 FROM tool_calls WHERE session_id = ?1 AND created_at <= ?3 ORDER BY id",
+                params![source_id, branch_id, cutoff],
+            )?;
+            tx.commit()?;
+        }
+        self.session(branch_id)?
+            .with_context(|| format!("Branch '{branch_id}' was not created"))
+    }
+
+    /// Sessions ordered by most recent activity.
+    pub fn list_sessions(&self, limit: usize) -> Result<Vec<SessionSummary>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {SESSION_COLUMNS}
+             FROM sessions s ORDER BY s.updated_at DESC, s.id ASC LIMIT ?1"
+        ))?;
+        let rows = stmt.query_map(params![sql_limit(limit)], session_from_row)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    /// One session by id.
+    pub fn session(&self, session_id: &str) -> Result<Option<SessionSummary>> {
+        let conn = self.conn.lock();
+        conn.query_row(
+            &format!("SELECT {SESSION_COLUMNS} FROM sessions s WHERE s.id = ?1"),
+            params![session_id],
+            session_from_row,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    /// Look a session up by id, falling back to its name.
+    pub fn resolve_session(&self, id_or_name: &str) -> Result<Option<SessionSummary>> {
+        let conn = self.conn.lock();
+        conn.query_row(
+            &format!(
+                "SELECT {SESSION_COLUMNS} FROM sessions s
+                 WHERE s.id = ?1 OR s.name = ?1
+                 ORDER BY s.id = ?1 DESC LIMIT 1"
+            ),
+            params![id_or_name],
+            session_from_row,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    /// Give a session a unique name, or remove it with `None`.
+    pub fn rename_session(&self, session_id: &str, name: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock();
+        if let Some(name) = name {
+            let taken: Option<String> = conn
+                .query_row(
+                    "SELECT id FROM sessions WHERE name = ?1 AND id != ?2",
+                    params![name, session_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(other) = taken {
+                anyhow::bail!("Session name '{name}' is already used by {other}");
+            }
+        }
+        let updated = conn.execute(
+            "UPDATE sessions SET name = ?2 WHERE id = ?1",
+            params![session_id, name],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Session '{session_id}' not found");
+        }
+        Ok(())
+    }
+
+    /// Delete a session with its messages and tool calls. Returns whether it existed.
+    pub fn delete_session(&self, session_id: &str) -> Result<bool> {
+        let conn = self.conn.lock();
+        let removed = conn.execute("DELETE FROM sessions WHERE id = ?1", params![session_id])?;
+        Ok(removed > 0)
+    }
+
+    /// Move a session's history to a new `<id>@<timestamp>` session marked
+    /// as archived, so the next message on `session_id` starts fresh.
+    /// Returns the archived session's id.
+    pub fn archive_session(&self, session_id: &str) -> Result<String> {
+        let now = Utc::now();
+        let archived_id = format!("{session_id}@{}", now.format("%Y%m%dT%H%M%S"));
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let name: Option<String> = tx
+            .query_row(
+                "SELECT name FROM sessions WHERE id = ?1",
+                params![session_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .with_context(|| format!("Session '{session_id}' not found"))?;
+        // The name is unique, so release it before the copy takes it over.
+        tx.execute(
+            "UPDATE sessions SET name = NULL WHERE id = ?1",
+            params![session_id],
+        )?;
+        tx.execute(
+            "INSERT INTO sessions (id, channel, sender, metadata, created_at, updated_at, name, archived_at,
+                                   parent_id, branched_from)
+             SELECT ?2, channel, sender, metadata, created_at, updated_at, ?3, ?4, parent_id, branched_from
+             FROM sessions WHERE id = ?1",
+            params![session_id, archived_id, name, now.to_rfc3339()],
+        )?;
+        // Branches keep pointing at the history they were copied from.
+        tx.execute(
+            "UPDATE sessions SET parent_id = ?2 WHERE parent_id = ?1",
+            params![session_id, archived_id],
+        )?;
+        for table in ["messages", "tool_calls", "checkpoints"] {
+            tx.execute(
+                &format!("UPDATE {table} SET session_id = ?2 WHERE session_id = ?1"),
+                params![session_id, archived_id],
+            )?;
+        }
+        tx.execute("DELETE FROM sessions WHERE id = ?1", params![session_id])?;
+        tx.commit()?;
+        Ok(archived_id)
+    }
+
+    /// Archive the session if it has messages and no activity for `max_idle`.
+    /// Returns the archived session's id when it was archived.
+    pub fn archive_if_idle(
+        &self,
+        session_id: &str,
+        max_idle: chrono::Duration,
+    ) -> Result<Option<String>> {
+        let cutoff = (Utc::now() - max_idle).to_rfc3339();
+        let idle: bool = self.conn.lock().query_row(
+            "SELECT EXISTS(
+                 SELECT 1 FROM sessions s
+                 WHERE s.id = ?1 AND s.archived_at IS NULL AND s.updated_at < ?2
+                   AND EXISTS(SELECT 1 FROM messages m WHERE m.session_id = s.id)
+             )",
+            params![session_id, cutoff],
+            |row| row.get(0),
+        )?;
+        if !idle {
+            return Ok(None);
+        }
+        self.archive_session(session_id).map(Some)
+    }
+
+    /// Archive every active session idle for longer than `max_idle`.
+    /// Returns the number archived.
+    pub fn archive_idle(&self, max_idle: chrono::Duration) -> Result<usize> {
+        let cutoff = (Utc::now() - max_idle).to_rfc3339();
+        let ids: Vec<String> = {
+            let conn = self.conn.lock();
+            let mut stmt = conn.prepare(
+                "SELECT s.id FROM sessions s
+                 WHERE s.archived_at IS NULL AND s.updated_at < ?1
+                   AND EXISTS(SELECT 1 FROM messages m WHERE m.session_id = s.id)",
+            )?;
+            let rows = stmt.query_map(params![cutoff], |row| row.get(0))?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()?
+        };
+        for id in &ids {
+            self.archive_session(id)?;
+        }
+        Ok(ids.len())
+    }
+
+    /// Unarchived sessions, and how many of them had activity within
+    /// `window`.
+    pub fn session_counts(&self, window: chrono::Duration) -> Result<(usize, usize)> {
+        let cutoff = (Utc::now() - window).to_rfc3339();
+        let conn = self.conn.lock();
+        let (total, active): (i64, i64) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(updated_at >= ?1), 0)
+             FROM sessions WHERE archived_at IS NULL",
+            params![cutoff],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok((
+            usize::try_from(total).unwrap_or(0),
+            usize::try_from(active).unwrap_or(0),
+        ))
+    }
+
+    /// Write a complete session with its original timestamps, e.g. from an
+    /// archive. Message and tool-call ids are reassigned; the rows'
+    /// `session_id` fields are ignored in favour of `session.id`.
+    ///
+    /// Fails if the session exists unless `replace` is set, in which case the
+    /// existing session is deleted first.
+    pub fn restore_session(
+        &self,
+        session: &SessionSummary,
+        messages: &[StoredMessage],
+        tool_calls: &[StoredToolCall],
+        replace: bool,
+    ) -> Result<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sessions WHERE id = ?1)",
+            params![session.id],
+            |row| row.get(0),
+        )?;
+        if exists {
+            if !replace {
+                anyhow::bail!("Session '{}' already exists", session.id);
+            }
+            tx.execute("DELETE FROM sessions WHERE id = ?1", params![session.id])?;
+        }
+        // Names are unique; an imported name already in use is dropped.
+        tx.execute(
+            "INSERT INTO sessions (id, channel, sender, metadata, created_at, updated_at, name, archived_at,
+                                   parent_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6,
+                     CASE WHEN EXISTS(SELECT 1 FROM sessions WHERE name = ?7) THEN NULL ELSE ?7 END,
+                     ?8, ?9)",
+            params![
+                session.id,
+                session.channel,
+                session.sender,
+                session.metadata.to_string(),
+                session.created_at,
+                session.updated_at,
+                session.name,
+                session.archived_at,
+                session.parent_id,
+            ],
+        )?;
+        for message in messages {
+            tx.execute(
+                "INSERT INTO messages (session_id, role, content, created_at) VALUES (?1, ?2, ?3, ?4)",
+                params![session.id, message.role, message.content, message.created_at],
+            )?;
+        }
+        for call in tool_calls {
+            tx.execute(
+                "INSERT INTO tool_calls (session_id, tool, arguments, output, success, duration_ms, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    session.id,
+                    call.tool,
+                    call.arguments,
+                    call.output,
+                    call.success,
+                    i64::try_from(call.duration_ms).unwrap_or(i64::MAX),
+                    call.created_at,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Full-text search over message content.
+    ///
+    /// Every whitespace-separated word in `query` must appear in the message;
+    /// words are matched as literal tokens, so FTS5 operators are not
+    /// interpreted. Results are ranked by BM25.
+    pub fn search(&self, query: &str, filter: &SearchFilter) -> Result<Vec<SearchHit>> {
+        let Some(fts_query) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT m.id, m.session_id, m.role, m.content, m.created_at, s.channel,
+                    snippet(messages_fts, 0, '[', ']', '…', 16)
+             FROM messages_fts
+             JOIN messages m ON m.id = messages_fts.rowid
+             JOIN sessions s ON s.id = m.session_id
+             WHERE messages_fts MATCH ?1
+               AND (?2 IS NULL OR s.channel = ?2)
+               AND (?3 IS NULL OR m.role = ?3)
+               AND (?4 IS NULL OR m.created_at >= ?4)
+               AND (?5 IS NULL OR m.created_at < ?5)
+             ORDER BY bm25(messages_fts), m.id DESC
+             LIMIT ?6",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                fts_query,
+                filter.channel,
+                filter.role,
+                filter.since.map(|t| t.to_rfc3339()),
+                filter.until.map(|t| t.to_rfc3339()),
+                sql_limit(filter.limit),
+            ],
+            |row| {
+                Ok(SearchHit {
+                    message: message_from_row(row)?,
+                    channel: row.get(5)?,
+                    snippet: row.get(6)?,
+                })
+            },
+        )?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    /// Delete sessions with no activity in the last `days` days. Returns the count removed.
+    pub fn prune_older_than(&self, days: u32) -> Result<usize> {
+        let cutoff = (Utc::now() - chrono::Duration::days(i64::from(days))).to_rfc3339();
+        let conn = self.conn.lock();
+        let removed = conn.execute(
+            "DELETE FROM sessions WHERE updated_at < ?1",
+            params![cutoff],
+        )?;
+        Ok(removed)
+    }
+}
+
+/// Create the session row if needed, bump `updated_at` and clear any archive
+/// mark (continuing an archived session reactivates it). Returns the timestamp.
+fn touch_session(conn: &Connection, session_id: &str) -> Result<String> {
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO sessions (id, created_at, updated_at) VALUES (?1, ?2, ?2)
+         ON CONFLICT(id) DO UPDATE SET updated_at = excluded.updated_at, archived_at = NULL",
+        params![session_id, now],
+    )?;
+    Ok(now)
+}
+
+fn session_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionSummary> {
+    let metadata: String = row.get(3)?;
+    Ok(SessionSummary {
+        id: row.get(0)?,
+        channel: row.get(1)?,
+        sender: row.get(2)?,
+        metadata: serde_json::from_str(&metadata).unwrap_or_default(),
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+        message_count: usize::try_from(row.get::<_, i64>(6)?).unwrap_or(0),
+        name: row.get(7)?,
+        archived_at: row.get(8)?,
+        parent_id: row.get(9)?,
+        branched_from: row.get(10)?,
+    })
+}
+
+fn message_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<StoredMessage> {
+    Ok(StoredMessage {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        role: row.get(2)?,
+        content: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+/// Quote each word of `query` as an FTS5 string so user input cannot form
+/// operators or syntax errors. `None` when there is nothing to search for.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+fn sql_limit(limit: usize) -> i64 {
+    i64::try_from(limit).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn store() -> (TempDir, ConversationStore) {
+        let tmp = TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        (tmp, store)
+    }
+
+    #[test]
+    fn migrations_run_once_and_set_version() {
+        let tmp = TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        assert_eq!(store.schema_version().unwrap(), MIGRATIONS.len());
+        drop(store);
+        let reopened = ConversationStore::open(tmp.path()).unwrap();
+        assert_eq!(reopened.schema_version().unwrap(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn messages_survive_reopen() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let store = ConversationStore::open(tmp.path()).unwrap();
+            store
+                .ensure_session("telegram_alice", "telegram", "alice")
+                .unwrap();
+            store
+                .append_message("telegram_alice", "user", "hi")
+                .unwrap();
+            store
+                .append_message("telegram_alice", "assistant", "hello")
+                .unwrap();
+        }
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        let messages = store.recent_messages("telegram_alice", 10).unwrap();
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant"]);
+        assert_eq!(messages[1].content, "hello");
+    }
+
+    #[test]
+    fn recent_messages_returns_newest_in_order() {
+        let (_tmp, store) = store();
+        for i in 0..5 {
+            store.append_message("s", "user", &format!("m{i}")).unwrap();
+        }
+        let contents: Vec<String> = store
+            .recent_messages("s", 2)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(contents, ["m3", "m4"]);
+
+        let after = store.recent_messages("s", 5).unwrap()[2].id;
+        let newer: Vec<String> = store
+            .messages_after("s", after, 1)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(newer, ["m3"]);
+    }
+
+    #[test]
+    fn remove_last_message_only_when_matching() {
+        let (_tmp, store) = store();
+        store.append_message("s", "user", "first").unwrap();
+        store.append_message("s", "user", "second").unwrap();
+        assert!(!store.remove_last_message_if("s", "user", "first").unwrap());
+        assert!(store.remove_last_message_if("s", "user", "second").unwrap());
+        assert_eq!(store.recent_messages("s", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn tool_calls_and_metadata_are_recorded() {
+        let (_tmp, store) = store();
+        store.ensure_session("cli:1", "cli", "user").unwrap();
+        store
+            .set_session_metadata("cli:1", "model", &serde_json::json!("gpt"))
+            .unwrap();
+        store
+            .record_tool_call(
+                "cli:1",
+                &ToolCallRecord {
+                    tool: "shell",
+                    arguments: &serde_json::json!({"command": "ls"}),
+                    output: "a\nb",
+                    success: true,
+                    duration: Duration::from_millis(12),
+                },
+            )
+            .unwrap();
+        let calls = store.tool_calls("cli:1").unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].tool, "shell");
+        assert_eq!(calls[0].duration_ms, 12);
+
+        let sessions = store.list_sessions(10).unwrap();
+        assert_eq!(sessions[0].channel, "cli");
+        assert_eq!(sessions[0].metadata["model"], "gpt");
+    }
+
+    #[test]
+    fn clear_session_keeps_session_row() {
+        let (_tmp, store) = store();
+        store.append_message("s", "user", "hi").unwrap();
+        store.clear_session("s").unwrap();
+        assert!(store.recent_messages("s", 10).unwrap().is_empty());
+        assert_eq!(store.list_sessions(10).unwrap()[0].message_count, 0);
+    }
+
+    #[test]
+    fn search_matches_all_terms_and_applies_filters() {
+        let (_tmp, store) = store();
+        store.ensure_session("tg", "telegram", "alice").unwrap();
+        store.ensure_session("dc", "discord", "bob").unwrap();
+        store
+            .append_message("tg", "user", "deploy the staging cluster")
+            .unwrap();
+        store
+            .append_message("tg", "assistant", "staging deploy finished")
+            .unwrap();
+        store
+            .append_message("dc", "user", "how do I deploy to staging?")
+            .unwrap();
+        store.append_message("dc", "user", "deploy prod").unwrap();
+
+        let all = store
+            .search(
+                "deploy staging",
+                &SearchFilter {
+                    limit: 10,
+                    ..SearchFilter::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(all.iter().all(|h| h.snippet.contains("[deploy]")));
+
+        let filtered = store
+            .search(
+                "deploy staging",
+                &SearchFilter {
+                    channel: Some("telegram".into()),
+                    role: Some("user".into()),
+                    limit: 10,
+                    ..SearchFilter::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].message.session_id, "tg");
+        assert_eq!(filtered[0].channel, "telegram");
+
+        let future = store
+            .search(
+                "deploy",
+                &SearchFilter {
+                    since: Some(Utc::now() + chrono::Duration::days(1)),
+                    limit: 10,
+                    ..SearchFilter::default()
+                },
+            )
+            .unwrap();
+        assert!(future.is_empty());
+    }
+
+    #[test]
+    fn search_treats_operators_as_text_and_forgets_cleared_messages() {
+        let (_tmp, store) = store();
+        store
+            .append_message("s", "user", "what does NOT \"quoted\" mean")
+            .unwrap();
+        let filter = SearchFilter {
+            limit: 5,
+            ..SearchFilter::default()
+        };
+        assert_eq!(store.search("NOT \"quoted", &filter).unwrap().len(), 1);
+        assert!(store.search("   ", &filter).unwrap().is_empty());
+
+        store.clear_session("s").unwrap();
+        assert!(store.search("quoted", &filter).unwrap().is_empty());
+    }
+
+    #[test]
+    fn sessions_resolve_by_name_and_names_stay_unique() {
+        let (_tmp, store) = store();
+        store.append_message("a", "user", "hi").unwrap();
+        store.append_message("b", "user", "hi").unwrap();
+        store.rename_session("a", Some("work")).unwrap();
+        assert_eq!(store.resolve_session("work").unwrap().unwrap().id, "a");
+        assert_eq!(store.resolve_session("b").unwrap().unwrap().id, "b");
+        assert!(store.rename_session("b", Some("work")).is_err());
+        assert!(store.rename_session("missing", Some("x")).is_err());
+
+        assert!(store.delete_session("a").unwrap());
+        assert!(!store.delete_session("a").unwrap());
+        assert!(store.resolve_session("work").unwrap().is_none());
+        store.rename_session("b", Some("work")).unwrap();
+    }
+
+    #[test]
+    fn idle_sessions_are_archived_under_a_new_id() {
+        let (_tmp, store) = store();
+        store.ensure_session("slack_bob", "slack", "bob").unwrap();
+        store
+            .append_message("slack_bob", "user", "old topic")
+            .unwrap();
+        store.rename_session("slack_bob", Some("bob")).unwrap();
+        store
+            .append_message("fresh", "user", "still going")
+            .unwrap();
+        store
+            .conn
+            .lock()
+            .execute(
+                "UPDATE sessions SET updated_at = '2020-01-01T00:00:00+00:00' WHERE id = 'slack_bob'",
+                [],
+            )
+            .unwrap();
+
+        let idle = chrono::Duration::hours(1);
+        assert!(store.archive_if_idle("fresh", idle).unwrap().is_none());
+        let archived = store.archive_if_idle("slack_bob", idle).unwrap().unwrap();
+        assert!(archived.starts_with("slack_bob@"));
+        assert!(store.session("slack_bob").unwrap().is_none());
+
+        let summary = store.session(&archived).unwrap().unwrap();
+        assert!(summary.archived_at.is_some());
+        assert_eq!(summary.name.as_deref(), Some("bob"));
+        assert_eq!(summary.channel, "slack");
+        assert_eq!(store.recent_messages(&archived, 10).unwrap().len(), 1);
+        assert_eq!(
+            store
+                .search(
+                    "topic",
+                    &SearchFilter {
+                        limit: 5,
+                        ..SearchFilter::default()
+                    }
+                )
+                .unwrap()[0]
+                .message
+                .session_id,
+            archived
+        );
+
+        // Nothing left to archive, and continuing the archive reactivates it.
+        assert_eq!(store.archive_idle(idle).unwrap(), 0);
+        store
+            .append_message(&archived, "user", "back again")
+            .unwrap();
+        assert!(store
+            .session(&archived)
+            .unwrap()
+            .unwrap()
+            .archived_at
+            .is_none());
+    }
+
+    #[test]
+    fn branches_copy_history_up_to_a_message_or_checkpoint() {
+        let (_tmp, store) = store();
+        store.ensure_session("cli:main", "cli", "user").unwrap();
+        store
+            .append_message("cli:main", "user", "plan a trip")
+            .unwrap();
+        let reply = store
+            .append_message("cli:main", "assistant", "where to?")
+            .unwrap();
+        let checkpoint = store.create_checkpoint("cli:main", None).unwrap();
+        assert_eq!(checkpoint.name, "cp1");
+        assert_eq!(checkpoint.message_id, reply);
+        assert!(store.create_checkpoint("cli:main", Some("cp1")).is_err());
+        assert!(store.create_checkpoint("cli:main", Some("7")).is_err());
+        store.append_message("cli:main", "user", "Japan").unwrap();
+
+        assert_eq!(store.resolve_message("cli:main", "cp1").unwrap(), reply);
+        assert_eq!(
+            store
+                .resolve_message("cli:main", &format!("#{reply}"))
+                .unwrap(),
+            reply
+        );
+        assert!(store.resolve_message("other", &reply.to_string()).is_err());
+
+        let branch = store
+            .branch_session("cli:main", reply, "cli:main:b1")
+            .unwrap();
+        assert_eq!(branch.parent_id.as_deref(), Some("cli:main"));
+        assert_eq!(branch.branched_from, Some(reply));
+        assert_eq!(branch.channel, "cli");
+        assert_eq!(branch.message_count, 2);
+        store.append_message("cli:main:b1", "user", "Peru").unwrap();
+        assert_eq!(store.recent_messages("cli:main", 10).unwrap().len(), 3);
+        assert!(store
+            .branch_session("cli:main", reply, "cli:main:b1")
+            .is_err());
+
+        // Archiving the parent keeps the branch attached to its history.
+        let archived = store.archive_session("cli:main").unwrap();
+        assert_eq!(
+            store
+                .session("cli:main:b1")
+                .unwrap()
+                .unwrap()
+                .parent_id
+                .as_deref(),
+            Some(archived.as_str())
+        );
+        assert_eq!(store.checkpoints(&archived).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn session_counts_split_recent_from_idle() {
+        let tmp = TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        store.append_message("cli:new", "user", "hi").unwrap();
+        store.append_message("cli:old", "user", "hi").unwrap();
+        store
+            .conn
+            .lock()
+            .execute(
+                "UPDATE sessions SET updated_at = '2020-01-01T00:00:00+00:00' WHERE id = 'cli:old'",
+                [],
+            )
+            .unwrap();
+
+        assert_eq!(
+            store.session_counts(chrono::Duration::hours(1)).unwrap(),
+            (2, 1)
+        );
+    }
+
+    #[test]
+    fn rejects_newer_schema() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("c.db");
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch("PRAGMA user_version = 999;").unwrap();
+        }
+        assert!(ConversationStore::open_at(&path).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Session titles.
+//!
+//! Once a session has [`TITLE_AFTER_MESSAGES`] messages, a short title is
+//! generated from its opening turns (`[conversations] auto_title`, with
+//! `title_model` for a cheaper model) and stored in the session metadata.
+//! `zeroclaw sessions list`, exports and the TUI show it; `/new` clears it
+//! so the next conversation gets its own.
+
+use super::{ConversationStore, SessionSummary};
+use crate::providers::Provider;
+use anyhow::Result;
+
+/// Session metadata key the title is stored under.
+pub const TITLE_METADATA_KEY: &str = "title";
+
+/// Messages (user and assistant) a session needs before it is titled.
+pub const TITLE_AFTER_MESSAGES: usize = 4;
+
+/// Opening messages the title is generated from.
+const TITLE_SOURCE_MESSAGES: usize = 6;
+
+/// Characters of each message sent to the titling model.
+const TITLE_SOURCE_CHARS: usize = 600;
+
+const MAX_TITLE_CHARS: usize = 60;
+
+const TITLE_PROMPT: &str = "You name conversations. Reply with a short title (at most six words) \
+that says what the conversation is about, like a chat app would show in its session list. \
+No quotes, no trailing punctuation, nothing else.";
+
+/// The stored title of `session`, if it has one.
+pub fn title(session: &SessionSummary) -> Option<&str> {
+    session
+        .metadata
+        .get(TITLE_METADATA_KEY)
+        .and_then(serde_json::Value::as_str)
+        .filter(|title| !title.is_empty())
+}
+
+/// Title `session_id` when it is long enough and has none yet. Returns the
+/// new title.
+pub async fn maybe_title(
+    store: &ConversationStore,
+    session_id: &str,
+    provider: &dyn Provider,
+    model: &str,
+) -> Option<String> {
+    let session = store.session(session_id).ok().flatten()?;
+    if title(&session).is_some() || session.message_count < TITLE_AFTER_MESSAGES {
+        return None;
+    }
+    match generate(store, session_id, provider, model).await {
+        Ok(Some(title)) => {
+            if let Err(e) = store.set_session_metadata(
+                session_id,
+                TITLE_METADATA_KEY,
+                &serde_json::json!(title),
+            ) {
+                tracing::warn!("Failed to store title of session {session_id}: {e}");
+            }
+            tracing::debug!(session = session_id, %title, "Session titled");
+            Some(title)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            tracing::debug!("Failed to title session {session_id}: {e}");
+            None
+        }
+    }
+}
+
+async fn generate(
+    store: &ConversationStore,
+    session_id: &str,
+    provider: &dyn Provider,
+    model: &str,
+) -> Result<Option<String>> {
+    let opening = store.messages_after(session_id, 0, TITLE_SOURCE_MESSAGES)?;
+    let transcript: Vec<String> = opening
+        .iter()
+        .filter(|m| m.role == "user" || m.role == "assistant")
+        .map(|m| {
+            format!(
+                "{}: {}",
+                m.role,
+                crate::util::truncate_with_ellipsis(m.content.trim(), TITLE_SOURCE_CHARS)
+            )
+        })
+        .collect();
+    let raw = provider
+        .chat_with_system(Some(TITLE_PROMPT), &transcript.join("\n\n"), model, 0.2)
+        .await?;
+    Ok(clean_title(&raw))
+}
+
+/// The model's reply reduced to a one-line title.
+fn clean_title(raw: &str) -> Option<String> {
+    let line = raw.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let line = line.strip_prefix("Title:").unwrap_or(line);
+    let title = line
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '*' | '#'))
+        .trim_end_matches(['.', '!'])
+        .trim();
+    (!title.is_empty()).then(|| crate::util::truncate_with_ellipsis(title, MAX_TITLE_CHARS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn model_replies_are_reduced_to_one_line() {
+        assert_eq!(
+            clean_title("\n\"Planning a Lisbon trip.\"\nHope that helps!").as_deref(),
+            Some("Planning a Lisbon trip")
+        );
+        assert_eq!(
+            clean_title("Title: **Rust borrow checker errors**").as_deref(),
+            Some("Rust borrow checker errors")
+        );
+        assert_eq!(clean_title("  \n\"\""), None);
+    }
+
+    #[test]
+    fn titles_live_in_session_metadata_and_reset_with_the_session() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        store.append_message("s1", "user", "hi").unwrap();
+        store
+            .set_session_metadata("s1", TITLE_METADATA_KEY, &serde_json::json!("Greetings"))
+            .unwrap();
+        let session = store.session("s1").unwrap().unwrap();
+        assert_eq!(title(&session), Some("Greetings"));
+
+        store.clear_session("s1").unwrap();
+        let session = store.session("s1").unwrap().unwrap();
+        assert_eq!(title(&session), None);
+    }
+}
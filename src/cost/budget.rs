@@ -0,0 +1,516 @@
+//! Hard budget limits for agent runs (`[budget]`).
+//!
+//! A [`SessionBudget`] counts tokens, estimated spend, tool invocations and
+//! active run time for one session and adds the same amounts to a per-day
+//! ledger in `state/budget.json`, shared by every session in the workspace.
+//! The tool-call loop consults the budget of the running task (see
+//! [`run_with`]) before each model call and tool invocation. Once a limit is
+//! reached the run stops with [`BudgetExceeded`], and keeps stopping, until
+//! the user explicitly overrides it.
+
+use super::types::TokenUsage;
+use crate::config::schema::ModelPricing;
+use crate::config::{BudgetConfig, BudgetLimitsConfig, Config};
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+tokio::task_local! {
+    static CURRENT_BUDGET: Arc<SessionBudget>;
+}
+
+/// Run `future` with `budget` enforced on every agent loop inside it.
+pub async fn run_with<F: Future>(budget: Option<Arc<SessionBudget>>, future: F) -> F::Output {
+    match budget {
+        Some(budget) => CURRENT_BUDGET.scope(budget, future).await,
+        None => future.await,
+    }
+}
+
+/// The budget of the running task, if any.
+pub fn current() -> Option<Arc<SessionBudget>> {
+    CURRENT_BUDGET.try_with(Arc::clone).ok()
+}
+
+/// Usage counted against a budget.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    pub tokens: u64,
+    pub cost_usd: f64,
+    pub tool_calls: u64,
+    pub wall_secs: f64,
+}
+
+impl BudgetUsage {
+    fn add(&mut self, other: &Self) {
+        self.tokens = self.tokens.saturating_add(other.tokens);
+        self.cost_usd += other.cost_usd;
+        self.tool_calls = self.tool_calls.saturating_add(other.tool_calls);
+        self.wall_secs += other.wall_secs;
+    }
+
+    /// The first limit this usage has reached, after `pending_tool_calls`
+    /// more tool invocations.
+    fn first_exceeded(
+        &self,
+        limits: &BudgetLimitsConfig,
+        pending_tool_calls: u64,
+    ) -> Option<(&'static str, String, String)> {
+        if let Some(max) = limits.max_tokens.filter(|max| self.tokens >= *max) {
+            return Some(("tokens", self.tokens.to_string(), max.to_string()));
+        }
+        if let Some(max) = limits.max_cost_usd.filter(|max| self.cost_usd >= *max) {
+            return Some((
+                "estimated spend",
+                format!("${:.2}", self.cost_usd),
+                format!("${max:.2}"),
+            ));
+        }
+        let tool_calls = self.tool_calls.saturating_add(pending_tool_calls);
+        if let Some(max) = limits.max_tool_calls.filter(|max| {
+            if pending_tool_calls == 0 {
+                tool_calls >= *max
+            } else {
+                tool_calls > *max
+            }
+        }) {
+            return Some(("tool calls", self.tool_calls.to_string(), max.to_string()));
+        }
+        if let Some(max) = limits
+            .max_wall_secs
+            .filter(|max| self.wall_secs >= *max as f64)
+        {
+            return Some((
+                "seconds of run time",
+                format!("{:.0}", self.wall_secs),
+                max.to_string(),
+            ));
+        }
+        None
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} tokens, ${:.2}, {} tool calls, {:.0}s",
+            self.tokens, self.cost_usd, self.tool_calls, self.wall_secs
+        )
+    }
+}
+
+/// Which set of limits was reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetScope {
+    Session,
+    Daily,
+}
+
+/// Error returned by the agent loop when a budget limit is reached.
+#[derive(Debug, Clone)]
+pub struct BudgetExceeded {
+    pub scope: BudgetScope,
+    pub metric: &'static str,
+    pub used: String,
+    pub limit: String,
+}
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scope = match self.scope {
+            BudgetScope::Session => "Session",
+            BudgetScope::Daily => "Daily",
+        };
+        write!(
+            f,
+            "{scope} budget exceeded ({}: {} used, limit {}). The agent stopped to avoid \
+             runaway spend; send `/budget override` (or rerun with `--budget-override`) to continue.",
+            self.metric, self.used, self.limit
+        )
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+pub fn is_budget_exceeded(err: &anyhow::Error) -> bool {
+    err.chain().any(|source| source.is::<BudgetExceeded>())
+}
+
+/// Usage of all sessions on one UTC day.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DailyLedger {
+    date: String,
+    #[serde(flatten)]
+    usage: BudgetUsage,
+}
+
+/// Limits and running totals for one session.
+pub struct SessionBudget {
+    session: BudgetLimitsConfig,
+    daily: BudgetLimitsConfig,
+    prices: HashMap<String, ModelPricing>,
+    ledger_path: PathBuf,
+    state: Mutex<SessionState>,
+    overridden: AtomicBool,
+}
+
+#[derive(Default)]
+struct SessionState {
+    usage: BudgetUsage,
+    /// Start of the not-yet-counted part of the running turn.
+    active_since: Option<Instant>,
+}
+
+impl SessionBudget {
+    /// A fresh session budget, or `None` when `[budget]` is disabled.
+    pub fn from_config(config: &Config) -> Option<Arc<Self>> {
+        config.budget.enabled.then(|| {
+            Arc::new(Self::new(
+                config.budget.session.clone(),
+                config.budget.daily.clone(),
+                config.cost.prices.clone(),
+                &config.workspace_dir,
+                config.budget.override_limits,
+            ))
+        })
+    }
+
+    fn new(
+        session: BudgetLimitsConfig,
+        daily: BudgetLimitsConfig,
+        prices: HashMap<String, ModelPricing>,
+        workspace_dir: &Path,
+        overridden: bool,
+    ) -> Self {
+        Self {
+            session,
+            daily,
+            prices,
+            ledger_path: workspace_dir.join("state").join("budget.json"),
+            state: Mutex::new(SessionState::default()),
+            overridden: AtomicBool::new(overridden),
+        }
+    }
+
+    /// Count run time until the returned guard is dropped.
+    pub fn start_turn(&self) -> ActiveTurn<'_> {
+        self.state.lock().active_since = Some(Instant::now());
+        ActiveTurn { budget: self }
+    }
+
+    /// Fail if any session or daily limit has been reached.
+    pub fn check(&self) -> std::result::Result<(), BudgetExceeded> {
+        self.check_with_pending(0)
+    }
+
+    /// Count `count` tool invocations, failing instead if they would go over
+    /// a limit.
+    pub fn admit_tool_calls(&self, count: usize) -> std::result::Result<(), BudgetExceeded> {
+        let count = count as u64;
+        self.check_with_pending(count)?;
+        self.record(BudgetUsage {
+            tool_calls: count,
+            ..BudgetUsage::default()
+        });
+        Ok(())
+    }
+
+    /// Count one model response. Spend is estimated from `[cost].prices`;
+    /// models without a price count tokens only.
+    pub fn record_llm(&self, model: &str, input_tokens: u64, output_tokens: u64) {
+        let (input_price, output_price) = self
+            .price_for(model)
+            .map_or((0.0, 0.0), |price| (price.input, price.output));
+        let usage = TokenUsage::new(
+            model,
+            input_tokens,
+            output_tokens,
+            input_price,
+            output_price,
+        );
+        self.record(BudgetUsage {
+            tokens: usage.total_tokens,
+            cost_usd: usage.cost_usd,
+            ..BudgetUsage::default()
+        });
+    }
+
+    /// Lift the limits for the rest of this session. Usage keeps counting.
+    pub fn override_limits(&self) {
+        self.overridden.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_overridden(&self) -> bool {
+        self.overridden.load(Ordering::Relaxed)
+    }
+
+    /// Usage so far against the configured limits, for `/budget`.
+    pub fn report(&self) -> String {
+        self.record(BudgetUsage::default());
+        let session = self.state.lock().usage;
+        let daily = self.load_ledger().usage;
+        let mut out = format!(
+            "Session: {}{}\nToday: {}{}",
+            session.describe(),
+            describe_limits(&self.session),
+            daily.describe(),
+            describe_limits(&self.daily)
+        );
+        if self.is_overridden() {
+            out.push_str("\nLimits are overridden for this session.");
+        }
+        out
+    }
+
+    fn check_with_pending(
+        &self,
+        pending_tool_calls: u64,
+    ) -> std::result::Result<(), BudgetExceeded> {
+        let daily = self.record(BudgetUsage::default());
+        if self.is_overridden() {
+            return Ok(());
+        }
+        let session = self.state.lock().usage;
+        for (scope, usage, limits) in [
+            (BudgetScope::Session, session, &self.session),
+            (BudgetScope::Daily, daily, &self.daily),
+        ] {
+            if let Some((metric, used, limit)) = usage.first_exceeded(limits, pending_tool_calls) {
+                return Err(BudgetExceeded {
+                    scope,
+                    metric,
+                    used,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Add `usage` plus the run time since the last call to the session and
+    /// today's ledger, returning today's totals.
+    fn record(&self, mut usage: BudgetUsage) -> BudgetUsage {
+        {
+            let mut state = self.state.lock();
+            if let Some(since) = state.active_since.as_mut() {
+                usage.wall_secs += since.elapsed().as_secs_f64();
+                *since = Instant::now();
+            }
+            state.usage.add(&usage);
+        }
+
+        let mut ledger = self.load_ledger();
+        ledger.usage.add(&usage);
+        if usage != BudgetUsage::default() {
+            if let Err(e) = self.save_ledger(&ledger) {
+                tracing::warn!("Failed to update budget ledger: {e}");
+            }
+        }
+        ledger.usage
+    }
+
+    fn load_ledger(&self) -> DailyLedger {
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        std::fs::read_to_string(&self.ledger_path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<DailyLedger>(&raw).ok())
+            .filter(|ledger| ledger.date == today)
+            .unwrap_or(DailyLedger {
+                date: today,
+                usage: BudgetUsage::default(),
+            })
+    }
+
+    fn save_ledger(&self, ledger: &DailyLedger) -> Result<()> {
+        if let Some(parent) = self.ledger_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&self.ledger_path, serde_json::to_vec(ledger)?)
+            .with_context(|| format!("Failed to write {}", self.ledger_path.display()))
+    }
+
+    fn price_for(&self, model: &str) -> Option<&ModelPricing> {
+        price_for(&self.prices, model)
+    }
+}
+
+/// The `[cost].prices` entry of `model`, matched by full ID or by the name
+/// after the provider prefix.
+pub fn price_for<'a>(
+    prices: &'a HashMap<String, ModelPricing>,
+    model: &str,
+) -> Option<&'a ModelPricing> {
+    prices.get(model).or_else(|| {
+        prices
+            .iter()
+            .find(|(name, _)| name.rsplit_once('/').is_some_and(|(_, bare)| bare == model))
+            .map(|(_, price)| price)
+    })
+}
+
+/// Guard returned by [`SessionBudget::start_turn`].
+pub struct ActiveTurn<'a> {
+    budget: &'a SessionBudget,
+}
+
+impl Drop for ActiveTurn<'_> {
+    fn drop(&mut self) {
+        self.budget.record(BudgetUsage::default());
+        self.budget.state.lock().active_since = None;
+    }
+}
+
+fn describe_limits(limits: &BudgetLimitsConfig) -> String {
+    if limits.is_unlimited() {
+        return " (no limits)".into();
+    }
+    let mut parts = Vec::new();
+    if let Some(max) = limits.max_tokens {
+        parts.push(format!("{max} tokens"));
+    }
+    if let Some(max) = limits.max_cost_usd {
+        parts.push(format!("${max:.2}"));
+    }
+    if let Some(max) = limits.max_tool_calls {
+        parts.push(format!("{max} tool calls"));
+    }
+    if let Some(max) = limits.max_wall_secs {
+        parts.push(format!("{max}s"));
+    }
+    format!(" (limits: {})", parts.join(", "))
+}
+
+/// Session budgets of channel conversations, keyed by conversation.
+pub struct SessionBudgets {
+    config: BudgetConfig,
+    prices: HashMap<String, ModelPricing>,
+    workspace_dir: PathBuf,
+    sessions: Mutex<HashMap<String, Arc<SessionBudget>>>,
+}
+
+impl SessionBudgets {
+    /// `None` when `[budget]` is disabled.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        config.budget.enabled.then(|| Self {
+            config: config.budget.clone(),
+            prices: config.cost.prices.clone(),
+            workspace_dir: config.workspace_dir.clone(),
+            sessions: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The budget for `key`, started on first use.
+    pub fn get(&self, key: &str) -> Arc<SessionBudget> {
+        let mut sessions = self.sessions.lock();
+        Arc::clone(sessions.entry(key.to_string()).or_insert_with(|| {
+            Arc::new(SessionBudget::new(
+                self.config.session.clone(),
+                self.config.daily.clone(),
+                self.prices.clone(),
+                &self.workspace_dir,
+                self.config.override_limits,
+            ))
+        }))
+    }
+
+    /// Forget the budget for `key`, so the next message starts a new session.
+    pub fn reset(&self, key: &str) {
+        self.sessions.lock().remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn budget(
+        tmp: &TempDir,
+        session: BudgetLimitsConfig,
+        daily: BudgetLimitsConfig,
+    ) -> SessionBudget {
+        let mut prices = HashMap::new();
+        prices.insert(
+            "anthropic/claude-test".to_string(),
+            ModelPricing {
+                input: 1.0,
+                output: 2.0,
+            },
+        );
+        SessionBudget::new(session, daily, prices, tmp.path(), false)
+    }
+
+    #[test]
+    fn session_limit_stops_until_overridden() {
+        let tmp = TempDir::new().unwrap();
+        let budget = budget(
+            &tmp,
+            BudgetLimitsConfig {
+                max_tokens: Some(1_000),
+                ..BudgetLimitsConfig::default()
+            },
+            BudgetLimitsConfig::default(),
+        );
+        assert!(budget.check().is_ok());
+        budget.record_llm("claude-test", 900, 200);
+
+        let err = budget.check().unwrap_err();
+        assert_eq!(err.scope, BudgetScope::Session);
+        assert_eq!(err.metric, "tokens");
+        assert!(err.to_string().contains("1100 used, limit 1000"));
+        assert!(err.to_string().contains("/budget override"));
+
+        budget.override_limits();
+        assert!(budget.check().is_ok());
+        assert!(budget.report().contains("overridden"));
+    }
+
+    #[test]
+    fn tool_calls_are_admitted_up_to_the_limit() {
+        let tmp = TempDir::new().unwrap();
+        let budget = budget(
+            &tmp,
+            BudgetLimitsConfig {
+                max_tool_calls: Some(3),
+                ..BudgetLimitsConfig::default()
+            },
+            BudgetLimitsConfig::default(),
+        );
+        budget.admit_tool_calls(2).unwrap();
+        let err = budget.admit_tool_calls(2).unwrap_err();
+        assert_eq!(err.metric, "tool calls");
+        budget.admit_tool_calls(1).unwrap();
+        assert!(budget.check().is_err());
+    }
+
+    #[test]
+    fn daily_ledger_is_shared_across_sessions_and_prices_spend() {
+        let tmp = TempDir::new().unwrap();
+        let daily = BudgetLimitsConfig {
+            max_cost_usd: Some(1.0),
+            ..BudgetLimitsConfig::default()
+        };
+        let first = budget(&tmp, BudgetLimitsConfig::default(), daily.clone());
+        // 200k input at $1/M + 200k output at $2/M = $0.60
+        first.record_llm("anthropic/claude-test", 200_000, 200_000);
+        assert!(first.check().is_ok());
+
+        let second = budget(&tmp, BudgetLimitsConfig::default(), daily);
+        second.record_llm("claude-test", 200_000, 200_000);
+        let err = second.check().unwrap_err();
+        assert_eq!(err.scope, BudgetScope::Daily);
+        assert_eq!(err.used, "$1.20");
+
+        std::fs::write(
+            tmp.path().join("state/budget.json"),
+            r#"{"date":"2000-01-01","tokens":5,"cost_usd":9.0,"tool_calls":0,"wall_secs":0.0}"#,
+        )
+        .unwrap();
+        assert!(second.check().is_ok());
+    }
+}
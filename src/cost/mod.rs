@@ -1,3 +1,4 @@
+pub mod budget;
 pub mod tracker;
 pub mod types;
 
@@ -2,6 +2,7 @@ use crate::config::Config;
 use crate::security::SecurityPolicy;
 use anyhow::{bail, Result};
 
+pub(crate) mod pipeline;
 mod schedule;
 mod store;
 mod types;
@@ -15,10 +16,85 @@ pub use schedule::{
 #[allow(unused_imports)]
 pub use store::{
     add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
-    record_last_run, record_run, remove_job, reschedule_after_run, update_job,
+    record_last_run, record_run, remove_job, reschedule_after_run, sync_declared_jobs, update_job,
+    DECLARED_JOB_PREFIX,
 };
 pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
+pub async fn handle_schedule_command(
+    command: crate::ScheduleCommands,
+    config: &Config,
+) -> Result<()> {
+    sync_declared_jobs(config)?;
+    match command {
+        crate::ScheduleCommands::List => {
+            if config.schedule.is_empty() {
+                println!("No [schedule] entries in config.");
+                println!("\nExample:");
+                println!("  [schedule.morning-digest]");
+                println!("  cron = \"0 8 * * *\"");
+                println!("  prompt = \"Summarize unread RSS\"");
+                println!("  channel = \"telegram\"");
+                println!("  to = \"123456789\"");
+                return Ok(());
+            }
+            let mut names: Vec<&String> = config.schedule.keys().collect();
+            names.sort();
+            println!("🕒 Declared schedules ({}):", names.len());
+            for name in names {
+                let entry = &config.schedule[name];
+                let Ok(job) = get_job(config, &format!("{DECLARED_JOB_PREFIX}{name}")) else {
+                    println!("- {name} | {} | invalid schedule, skipped", entry.cron);
+                    continue;
+                };
+                let next = if job.enabled {
+                    crate::time::display(job.next_run)
+                } else {
+                    "disabled".to_string()
+                };
+                let last = job.last_run.map_or_else(
+                    || "never".to_string(),
+                    |at| {
+                        format!(
+                            "{} ({})",
+                            crate::time::display(at),
+                            job.last_status.as_deref().unwrap_or("n/a")
+                        )
+                    },
+                );
+                println!("- {name} | {} | next={next} | last={last}", entry.cron);
+                if !entry.pipeline.is_empty() {
+                    let tools: Vec<&str> = entry
+                        .pipeline
+                        .iter()
+                        .map(|step| step.tool.as_str())
+                        .collect();
+                    println!("    pipeline: {}", tools.join(" → "));
+                }
+                if let Some(prompt) = &entry.prompt {
+                    println!("    prompt: {prompt}");
+                }
+                if let (Some(channel), Some(to)) = (&entry.channel, &entry.to) {
+                    println!("    deliver: {channel} → {to}");
+                }
+            }
+            Ok(())
+        }
+        crate::ScheduleCommands::RunNow { name } => {
+            if !config.schedule.contains_key(&name) {
+                bail!("No [schedule.{name}] entry in config");
+            }
+            let job = get_job(config, &format!("{DECLARED_JOB_PREFIX}{name}"))?;
+            let (success, output) = scheduler::run_job_now(config, &job).await;
+            println!("{output}");
+            if !success {
+                bail!("Schedule '{name}' failed");
+            }
+            Ok(())
+        }
+    }
+}
+
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
     match command {
@@ -35,13 +111,13 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             for job in jobs {
                 let last_run = job
                     .last_run
-                    .map_or_else(|| "never".into(), |d| d.to_rfc3339());
+                    .map_or_else(|| "never".into(), crate::time::display);
                 let last_status = job.last_status.unwrap_or_else(|| "n/a".into());
                 println!(
                     "- {} | {:?} | next={} | last={} ({})",
                     job.id,
                     job.schedule,
-                    job.next_run.to_rfc3339(),
+                    crate::time::display(job.next_run),
                     last_run,
                     last_status,
                 );
@@ -66,18 +142,17 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             let job = add_shell_job(config, None, schedule, &command)?;
             println!("✅ Added cron job {}", job.id);
             println!("  Expr: {}", job.expression);
-            println!("  Next: {}", job.next_run.to_rfc3339());
+            println!("  Next: {}", crate::time::display(job.next_run));
             println!("  Cmd : {}", job.command);
             Ok(())
         }
         crate::CronCommands::AddAt { at, command } => {
-            let at = chrono::DateTime::parse_from_rfc3339(&at)
-                .map_err(|e| anyhow::anyhow!("Invalid RFC3339 timestamp for --at: {e}"))?
-                .with_timezone(&chrono::Utc);
+            let at = crate::time::parse_instant(&at, crate::time::zone(), chrono::Utc::now())
+                .map_err(|e| anyhow::anyhow!("Invalid time for --at: {e}"))?;
             let schedule = Schedule::At { at };
             let job = add_shell_job(config, None, schedule, &command)?;
             println!("✅ Added one-shot cron job {}", job.id);
-            println!("  At  : {}", job.next_run.to_rfc3339());
+            println!("  At  : {}", crate::time::display(job.next_run));
             println!("  Cmd : {}", job.command);
             Ok(())
         }
@@ -86,14 +161,14 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             let job = add_shell_job(config, None, schedule, &command)?;
             println!("✅ Added interval cron job {}", job.id);
             println!("  Every(ms): {every_ms}");
-            println!("  Next     : {}", job.next_run.to_rfc3339());
+            println!("  Next     : {}", crate::time::display(job.next_run));
             println!("  Cmd      : {}", job.command);
             Ok(())
         }
         crate::CronCommands::Once { delay, command } => {
             let job = add_once(config, &delay, &command)?;
             println!("✅ Added one-shot cron job {}", job.id);
-            println!("  At  : {}", job.next_run.to_rfc3339());
+            println!("  At  : {}", crate::time::display(job.next_run));
             println!("  Cmd : {}", job.command);
             Ok(())
         }
@@ -145,7 +220,7 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             let job = update_job(config, &id, patch)?;
             println!("\u{2705} Updated cron job {}", job.id);
             println!("  Expr: {}", job.expression);
-            println!("  Next: {}", job.next_run.to_rfc3339());
+            println!("  Next: {}", crate::time::display(job.next_run));
             println!("  Cmd : {}", job.command);
             Ok(())
         }
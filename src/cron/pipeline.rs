@@ -0,0 +1,249 @@
+//! Tool pipelines for `[schedule.<name>]` entries.
+//!
+//! Steps run in order against the full tool registry. A `{{previous}}`
+//! placeholder in any string argument is replaced by the output of the step
+//! before it, so e.g. a `web_fetch` result can feed a `memory_store` call.
+
+use crate::config::{Config, PipelineStepConfig};
+use crate::memory::{self, Memory};
+use crate::security::audit::{self, ApprovalDecision, ToolExecutionLog};
+use crate::security::SecurityPolicy;
+use crate::tools::{self, Tool};
+use anyhow::{bail, Result};
+use std::sync::Arc;
+use std::time::Instant;
+
+const PREVIOUS_PLACEHOLDER: &str = "{{previous}}";
+
+/// Output of one pipeline step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepOutput {
+    pub tool: String,
+    pub output: String,
+}
+
+/// Run every step, stopping at the first failure.
+pub async fn run(config: &Config, steps: &[PipelineStepConfig]) -> Result<Vec<StepOutput>> {
+    let registry = build_registry(config)?;
+    run_with_tools(&registry, steps).await
+}
+
+async fn run_with_tools(
+    registry: &[Box<dyn Tool>],
+    steps: &[PipelineStepConfig],
+) -> Result<Vec<StepOutput>> {
+    let mut outputs: Vec<StepOutput> = Vec::with_capacity(steps.len());
+    for (index, step) in steps.iter().enumerate() {
+        let Some(tool) = registry.iter().find(|tool| tool.name() == step.tool) else {
+            bail!("step {}: unknown tool '{}'", index + 1, step.tool);
+        };
+        // Run from the gateway, steps are held to `[gateway] role`.
+        if let Err(denied) = crate::security::roles::check_tool(tool.as_ref()) {
+            bail!("step {}: {denied}", index + 1);
+        }
+        let previous = outputs.last().map_or("", |o| o.output.as_str());
+        let args = if step.args.is_null() {
+            serde_json::json!({})
+        } else {
+            substitute_previous(&step.args, previous)
+        };
+        let start = Instant::now();
+        let result = tool.execute(args.clone()).await;
+        let (output, success) = match &result {
+            Ok(r) if r.success => (r.output.clone(), true),
+            Ok(r) => (r.error.clone().unwrap_or_else(|| r.output.clone()), false),
+            Err(e) => (format!("{e:#}"), false),
+        };
+        audit::record_tool_execution(ToolExecutionLog {
+            channel: "cron",
+            session: None,
+            tool: &step.tool,
+            arguments: &args,
+            output: &output,
+            success,
+            error: (!success).then_some(output.as_str()),
+            duration_ms: u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+            risk_level: tool.risk().as_str(),
+            approval: ApprovalDecision::NotRequired,
+        });
+        let result = result?;
+        if !result.success {
+            return Err(crate::error::Error::ToolFailed {
+                tool: step.tool.clone(),
+                message: format!(
+                    "step {} ({}) failed: {}",
+                    index + 1,
+                    step.tool,
+                    result.error.unwrap_or(result.output)
+                ),
+            }
+            .into());
+        }
+        outputs.push(StepOutput {
+            tool: step.tool.clone(),
+            output: result.output,
+        });
+    }
+    Ok(outputs)
+}
+
+/// The prompt followed by each step's output, for entries with both.
+pub fn prompt_with_outputs(prompt: &str, outputs: &[StepOutput]) -> String {
+    if outputs.is_empty() {
+        return prompt.to_string();
+    }
+    let mut text = format!("{prompt}\n\nPipeline output:");
+    for (index, step) in outputs.iter().enumerate() {
+        text.push_str(&format!(
+            "\n\n### Step {} ({})\n{}",
+            index + 1,
+            step.tool,
+            step.output
+        ));
+    }
+    text
+}
+
+fn substitute_previous(value: &serde_json::Value, previous: &str) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) => {
+            serde_json::Value::String(s.replace(PREVIOUS_PLACEHOLDER, previous))
+        }
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| substitute_previous(item, previous))
+            .collect(),
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(key, item)| (key.clone(), substitute_previous(item, previous)))
+            .collect(),
+        other => other.clone(),
+    }
+}
+
+pub(crate) fn build_registry(config: &Config) -> Result<Vec<Box<dyn Tool>>> {
+    let runtime: Arc<dyn crate::runtime::RuntimeAdapter> =
+        Arc::from(crate::runtime::create_runtime(&config.runtime)?);
+    let security = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
+    ));
+    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
+        &config.memory,
+        &config.embedding_routes,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?);
+    let (composio_key, composio_entity_id) = if config.composio.enabled {
+        (
+            config.composio.api_key.as_deref(),
+            Some(config.composio.entity_id.as_str()),
+        )
+    } else {
+        (None, None)
+    };
+    Ok(tools::all_tools_with_runtime(
+        Arc::new(config.clone()),
+        &security,
+        runtime,
+        mem,
+        composio_key,
+        composio_entity_id,
+        &config.browser,
+        &config.http_request,
+        &config.web_fetch,
+        &config.workspace_dir,
+        &config.agents,
+        config.api_key.as_deref(),
+        config,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ToolResult;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct UpperTool;
+
+    #[async_trait]
+    impl Tool for UpperTool {
+        fn name(&self) -> &str {
+            "upper"
+        }
+
+        fn description(&self) -> &str {
+            "Uppercases `text`."
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({"type": "object"})
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+            let text = args["text"].as_str().unwrap_or_default();
+            Ok(ToolResult {
+                success: !text.is_empty(),
+                output: text.to_uppercase(),
+                error: text.is_empty().then(|| "empty text".to_string()),
+            })
+        }
+    }
+
+    fn step(text: &str) -> PipelineStepConfig {
+        PipelineStepConfig {
+            tool: "upper".into(),
+            args: json!({"text": text}),
+        }
+    }
+
+    #[tokio::test]
+    async fn steps_chain_through_previous_placeholder() {
+        let registry: Vec<Box<dyn Tool>> = vec![Box::new(UpperTool)];
+        let outputs = run_with_tools(&registry, &[step("feed"), step("got {{previous}}!")])
+            .await
+            .unwrap();
+        assert_eq!(outputs[1].output, "GOT FEED!");
+        assert_eq!(
+            prompt_with_outputs("Summarize", &outputs[..1]),
+            "Summarize\n\nPipeline output:\n\n### Step 1 (upper)\nFEED"
+        );
+    }
+
+    #[tokio::test]
+    async fn failing_or_unknown_step_stops_the_pipeline() {
+        let registry: Vec<Box<dyn Tool>> = vec![Box::new(UpperTool)];
+        let err = run_with_tools(&registry, &[step(""), step("never")])
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("step 1 (upper) failed: empty text"));
+
+        let missing = PipelineStepConfig {
+            tool: "nope".into(),
+            args: serde_json::Value::Null,
+        };
+        let err = run_with_tools(&registry, &[missing]).await.unwrap_err();
+        assert!(err.to_string().contains("unknown tool 'nope'"));
+    }
+
+    #[tokio::test]
+    async fn steps_above_the_current_role_are_refused() {
+        use crate::security::roles::{run_with, Role};
+
+        let registry: Vec<Box<dyn Tool>> = vec![Box::new(UpperTool)];
+        let err = run_with(Role::Member, run_with_tools(&registry, &[step("x")]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("step 1: Tool `upper` requires"));
+        assert!(
+            run_with(Role::Admin, run_with_tools(&registry, &[step("x")]))
+                .await
+                .is_ok()
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::channels::{
-    Channel, DiscordChannel, MattermostChannel, SendMessage, SlackChannel, TelegramChannel,
+    outbox, Channel, DiscordChannel, MattermostChannel, SendMessage, SlackChannel, TelegramChannel,
 };
 use crate::config::Config;
+use crate::cron::pipeline;
 use crate::cron::{
     due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
     update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
@@ -29,6 +30,9 @@ pub async fn run(config: Config) -> Result<()> {
     ));
 
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
+    if let Err(e) = crate::cron::sync_declared_jobs(&config) {
+        tracing::warn!("Failed to sync [schedule] entries: {e}");
+    }
 
     loop {
         interval.tick().await;
@@ -53,6 +57,15 @@ pub async fn execute_job_now(config: &Config, job: &CronJob) -> (bool, String) {
     execute_job_with_retry(config, &security, job).await
 }
 
+/// Run a job immediately with delivery, run history and rescheduling, as if
+/// it had come due.
+pub async fn run_job_now(config: &Config, job: &CronJob) -> (bool, String) {
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let (_, success, output) =
+        execute_and_persist_job(config, &security, job, SCHEDULER_COMPONENT).await;
+    (success, output)
+}
+
 async fn execute_job_with_retry(
     config: &Config,
     security: &SecurityPolicy,
@@ -66,6 +79,7 @@ async fn execute_job_with_retry(
         let (success, output) = match job.job_type {
             JobType::Shell => run_job_command(config, security, job).await,
             JobType::Agent => run_agent_job(config, security, job).await,
+            JobType::Declared => run_declared_job(config, security, job).await,
         };
         last_output = output;
 
@@ -135,35 +149,36 @@ async fn execute_and_persist_job(
     (job.id.clone(), success, output)
 }
 
-async fn run_agent_job(
-    config: &Config,
-    security: &SecurityPolicy,
-    job: &CronJob,
-) -> (bool, String) {
+/// Policy checks shared by agent and declared jobs; `Err` carries the
+/// non-retryable "blocked by security policy" output.
+fn enforce_autonomous_run(security: &SecurityPolicy) -> Result<(), String> {
     if !security.can_act() {
-        return (
-            false,
-            "blocked by security policy: autonomy is read-only".to_string(),
-        );
+        return Err("blocked by security policy: autonomy is read-only".to_string());
     }
 
     if security.is_rate_limited() {
-        return (
-            false,
-            "blocked by security policy: rate limit exceeded".to_string(),
-        );
+        return Err("blocked by security policy: rate limit exceeded".to_string());
     }
 
     if !security.record_action() {
-        return (
-            false,
-            "blocked by security policy: action budget exhausted".to_string(),
-        );
+        return Err("blocked by security policy: action budget exhausted".to_string());
+    }
+    Ok(())
+}
+
+async fn run_agent_job(
+    config: &Config,
+    security: &SecurityPolicy,
+    job: &CronJob,
+) -> (bool, String) {
+    if let Err(blocked) = enforce_autonomous_run(security) {
+        return (false, blocked);
     }
     let name = job.name.clone().unwrap_or_else(|| "cron-job".to_string());
     let prompt = job.prompt.clone().unwrap_or_default();
     let prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
     let model_override = job.model.clone();
+    let session = format!("cron:{}:{}", job.id, uuid::Uuid::new_v4());
 
     let run_result = match job.session_target {
         SessionTarget::Main | SessionTarget::Isolated => {
@@ -175,24 +190,97 @@ async fn run_agent_job(
                 config.default_temperature,
                 vec![],
                 false,
+                Some(session.clone()),
             )
             .await
         }
     };
 
     match run_result {
-        Ok(response) => (
-            true,
-            if response.trim().is_empty() {
-                "agent job executed".to_string()
-            } else {
-                response
-            },
-        ),
+        Ok(response) if response.trim().is_empty() => (true, "agent job executed".to_string()),
+        Ok(response) => {
+            notify_if_undelivered(config, job, &name, &response, &session);
+            (true, response)
+        }
         Err(e) => (false, format!("agent job failed: {e}")),
     }
 }
 
+/// Run a `[schedule.<name>]` entry: its tool pipeline, then its prompt with
+/// the pipeline output appended. Without a prompt, the last step's output
+/// is the job output.
+async fn run_declared_job(
+    config: &Config,
+    security: &SecurityPolicy,
+    job: &CronJob,
+) -> (bool, String) {
+    let name = job.name.clone().unwrap_or_default();
+    let Some(entry) = config.schedule.get(&name) else {
+        return (
+            false,
+            format!("schedule entry '{name}' is no longer in the config"),
+        );
+    };
+    if let Err(blocked) = enforce_autonomous_run(security) {
+        return (false, blocked);
+    }
+
+    let outputs = match pipeline::run(config, &entry.pipeline).await {
+        Ok(outputs) => outputs,
+        Err(e) => return (false, format!("pipeline failed: {e}")),
+    };
+
+    let prompt = entry
+        .prompt
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    let Some(prompt) = prompt else {
+        return (
+            true,
+            outputs
+                .last()
+                .map(|step| step.output.clone())
+                .unwrap_or_default(),
+        );
+    };
+
+    let prompt = pipeline::prompt_with_outputs(prompt, &outputs);
+    let session = format!("schedule:{name}:{}", uuid::Uuid::new_v4());
+    match crate::agent::run(
+        config.clone(),
+        Some(format!("[schedule:{name}] {prompt}")),
+        None,
+        entry.model.clone(),
+        config.default_temperature,
+        vec![],
+        false,
+        Some(session.clone()),
+    )
+    .await
+    {
+        Ok(response) if response.trim().is_empty() => (true, "schedule executed".to_string()),
+        Ok(response) => {
+            notify_if_undelivered(config, job, &name, &response, &session);
+            (true, response)
+        }
+        Err(e) => (false, format!("schedule failed: {e}")),
+    }
+}
+
+/// Agent output from a job without channel delivery goes to the desktop.
+fn notify_if_undelivered(config: &Config, job: &CronJob, name: &str, output: &str, session: &str) {
+    if job.delivery.mode.eq_ignore_ascii_case("announce") {
+        return;
+    }
+    crate::notifications::agent_output(
+        config,
+        &format!("Scheduled job “{name}”"),
+        output,
+        Some(&format!("zeroclaw agent --session {session}")),
+    );
+}
+
 async fn persist_job_result(
     config: &Config,
     job: &CronJob,
@@ -305,19 +393,18 @@ pub(crate) async fn deliver_announcement(
     target: &str,
     output: &str,
 ) -> Result<()> {
-    match channel.to_ascii_lowercase().as_str() {
+    let channel: Box<dyn Channel> = match channel.to_ascii_lowercase().as_str() {
         "telegram" => {
             let tg = config
                 .channels_config
                 .telegram
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("telegram channel not configured"))?;
-            let channel = TelegramChannel::new(
+            Box::new(TelegramChannel::new(
                 tg.bot_token.clone(),
                 tg.allowed_users.clone(),
                 tg.mention_only,
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
+            ))
         }
         "discord" => {
             let dc = config
@@ -325,14 +412,13 @@ pub(crate) async fn deliver_announcement(
                 .discord
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("discord channel not configured"))?;
-            let channel = DiscordChannel::new(
+            Box::new(DiscordChannel::new(
                 dc.bot_token.clone(),
                 dc.guild_id.clone(),
                 dc.allowed_users.clone(),
                 dc.listen_to_bots,
                 dc.mention_only,
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
+            ))
         }
         "slack" => {
             let sl = config
@@ -340,12 +426,11 @@ pub(crate) async fn deliver_announcement(
                 .slack
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("slack channel not configured"))?;
-            let channel = SlackChannel::new(
+            Box::new(SlackChannel::new(
                 sl.bot_token.clone(),
                 sl.channel_id.clone(),
                 sl.allowed_users.clone(),
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
+            ))
         }
         "mattermost" => {
             let mm = config
@@ -353,19 +438,26 @@ pub(crate) async fn deliver_announcement(
                 .mattermost
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("mattermost channel not configured"))?;
-            let channel = MattermostChannel::new(
+            Box::new(MattermostChannel::new(
                 mm.url.clone(),
                 mm.bot_token.clone(),
                 mm.channel_id.clone(),
                 mm.allowed_users.clone(),
                 mm.thread_replies.unwrap_or(true),
                 mm.mention_only.unwrap_or(false),
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
+            ))
         }
         other => anyhow::bail!("unsupported delivery channel: {other}"),
-    }
+    };
 
+    // Queued output goes out once the channel runtime reaches the channel.
+    let outbox = outbox::Outbox::from_config(config);
+    outbox::send_or_queue(
+        outbox.as_ref(),
+        channel.as_ref(),
+        &SendMessage::new(output, target),
+    )
+    .await?;
     Ok(())
 }
 
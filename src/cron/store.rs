@@ -113,6 +113,91 @@ pub fn add_agent_job(
     get_job(config, &id)
 }
 
+/// ID prefix of jobs mirrored from `[schedule.<name>]` entries.
+pub const DECLARED_JOB_PREFIX: &str = "schedule:";
+
+/// Mirror `[schedule.<name>]` entries into the job table as `declared` jobs
+/// with ID `schedule:<name>`. New entries are inserted, changed cron
+/// expressions are rescheduled, and jobs whose entry was removed are
+/// deleted. Entries with an invalid expression are skipped with a warning.
+pub fn sync_declared_jobs(config: &Config) -> Result<()> {
+    let now = Utc::now();
+    let mut keep = Vec::new();
+    for (name, entry) in &config.schedule {
+        let schedule = Schedule::Cron {
+            expr: entry.cron.clone(),
+            tz: entry.tz.clone(),
+        };
+        let next_run = match next_run_for_schedule(&schedule, now) {
+            Ok(next_run) => next_run,
+            Err(e) => {
+                tracing::warn!("Skipping [schedule.{name}]: {e}");
+                continue;
+            }
+        };
+        let id = format!("{DECLARED_JOB_PREFIX}{name}");
+        let schedule_json = serde_json::to_string(&schedule)?;
+        let delivery = match (&entry.channel, &entry.to) {
+            (Some(channel), Some(to)) => DeliveryConfig {
+                mode: "announce".to_string(),
+                channel: Some(channel.clone()),
+                to: Some(to.clone()),
+                best_effort: true,
+            },
+            _ => DeliveryConfig::default(),
+        };
+
+        with_connection(config, |conn| {
+            conn.execute(
+                "INSERT INTO cron_jobs (
+                    id, expression, command, schedule, job_type, prompt, name, session_target,
+                    model, enabled, delivery, delete_after_run, created_at, next_run
+                 ) VALUES (?1, ?2, '', ?3, 'declared', ?4, ?5, 'isolated', ?6, ?7, ?8, 0, ?9, ?10)
+                 ON CONFLICT(id) DO UPDATE SET
+                    next_run = CASE WHEN cron_jobs.schedule = excluded.schedule
+                                    THEN cron_jobs.next_run ELSE excluded.next_run END,
+                    expression = excluded.expression,
+                    schedule = excluded.schedule,
+                    job_type = 'declared',
+                    prompt = excluded.prompt,
+                    name = excluded.name,
+                    model = excluded.model,
+                    enabled = excluded.enabled,
+                    delivery = excluded.delivery",
+                params![
+                    id,
+                    entry.cron,
+                    schedule_json,
+                    entry.prompt,
+                    name,
+                    entry.model,
+                    if entry.enabled { 1 } else { 0 },
+                    serde_json::to_string(&delivery)?,
+                    now.to_rfc3339(),
+                    next_run.to_rfc3339(),
+                ],
+            )
+            .context("Failed to sync declared schedule")?;
+            Ok(())
+        })?;
+        keep.push(id);
+    }
+
+    with_connection(config, |conn| {
+        let mut stmt = conn.prepare("SELECT id FROM cron_jobs WHERE job_type = 'declared'")?;
+        let stale: Vec<String> = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|id| !keep.contains(id))
+            .collect();
+        for id in stale {
+            conn.execute("DELETE FROM cron_jobs WHERE id = ?1", params![id])?;
+        }
+        Ok(())
+    })
+}
+
 pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
@@ -629,6 +714,55 @@ mod tests {
         assert!(!recurring.delete_after_run);
     }
 
+    #[test]
+    fn sync_declared_jobs_mirrors_schedule_entries() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        let entry = |cron: &str| crate::config::ScheduleEntryConfig {
+            cron: cron.into(),
+            tz: None,
+            prompt: Some("Summarize unread RSS".into()),
+            pipeline: Vec::new(),
+            model: None,
+            channel: Some("telegram".into()),
+            to: Some("42".into()),
+            enabled: true,
+        };
+        config.schedule.insert("morning".into(), entry("0 8 * * *"));
+        config.schedule.insert("broken".into(), entry("not cron"));
+
+        sync_declared_jobs(&config).unwrap();
+        let job = get_job(&config, "schedule:morning").unwrap();
+        assert_eq!(job.job_type, JobType::Declared);
+        assert_eq!(job.name.as_deref(), Some("morning"));
+        assert_eq!(job.delivery.mode, "announce");
+        assert_eq!(job.delivery.to.as_deref(), Some("42"));
+        assert!(get_job(&config, "schedule:broken").is_err());
+
+        // Unchanged entries keep their next run; changed expressions reschedule.
+        let first_next = job.next_run;
+        sync_declared_jobs(&config).unwrap();
+        assert_eq!(
+            get_job(&config, "schedule:morning").unwrap().next_run,
+            first_next
+        );
+        config
+            .schedule
+            .insert("morning".into(), entry("30 6 * * *"));
+        sync_declared_jobs(&config).unwrap();
+        assert_eq!(
+            get_job(&config, "schedule:morning").unwrap().expression,
+            "30 6 * * *"
+        );
+
+        // Removing the entry removes the job, but leaves other jobs alone.
+        add_job(&config, "*/5 * * * *", "echo ok").unwrap();
+        config.schedule.clear();
+        sync_declared_jobs(&config).unwrap();
+        assert!(get_job(&config, "schedule:morning").is_err());
+        assert_eq!(list_jobs(&config).unwrap().len(), 1);
+    }
+
     #[test]
     fn add_list_remove_roundtrip() {
         let tmp = TempDir::new().unwrap();
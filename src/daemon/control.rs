@@ -0,0 +1,455 @@
+//! Control socket of the running daemon.
+//!
+//! The daemon listens on [`socket_path`](super::lifecycle::socket_path) — a
+//! Unix domain socket, or a named pipe on Windows — and speaks
+//! newline-delimited JSON-RPC 2.0: one request object per line, one response
+//! per request, any number of requests per connection. `zeroclaw daemon
+//! status|stop|restart` are clients; scripts can send messages, read sessions
+//! and switch tools the same way. See `docs/commands-reference.md` for the
+//! method list.
+
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+/// JSON-RPC "parse error".
+const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC "method not found".
+const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC "invalid params".
+const INVALID_PARAMS: i64 = -32602;
+/// Application error: the request was valid but handling it failed.
+const SERVER_ERROR: i64 = -32000;
+
+/// Sessions returned by `sessions.list` unless `limit` says otherwise.
+const DEFAULT_SESSION_LIMIT: usize = 50;
+/// Messages returned by `sessions.show` unless `limit` says otherwise.
+const DEFAULT_MESSAGE_LIMIT: usize = 100;
+
+/// What a control client asked the daemon to do with itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Stop,
+    Restart,
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Response {
+    jsonrpc: String,
+    id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+type RpcResult = std::result::Result<Value, (i64, String)>;
+
+fn invalid_params(error: impl std::fmt::Display) -> (i64, String) {
+    (INVALID_PARAMS, format!("invalid params: {error}"))
+}
+
+fn server_error(error: &anyhow::Error) -> (i64, String) {
+    (SERVER_ERROR, format!("{error:#}"))
+}
+
+#[derive(Debug, Deserialize)]
+struct SendParams {
+    message: String,
+    /// Session id or name to continue; a one-off turn when absent.
+    #[serde(default)]
+    session: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ListParams {
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShowParams {
+    session: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolsParams {
+    tools: Vec<String>,
+}
+
+/// Parse `params`, treating a missing value as `{}`.
+fn params<T: serde::de::DeserializeOwned>(params: &Value) -> std::result::Result<T, (i64, String)> {
+    let params = if params.is_null() {
+        json!({})
+    } else {
+        params.clone()
+    };
+    serde_json::from_value(params).map_err(invalid_params)
+}
+
+/// Serves control requests until the task is aborted.
+pub struct Server {
+    config: Config,
+    /// Differs between daemon runs even when a restart keeps the PID.
+    instance: String,
+    actions: mpsc::Sender<Action>,
+}
+
+impl Server {
+    pub fn new(config: Config, actions: mpsc::Sender<Action>) -> Self {
+        Self {
+            config,
+            instance: uuid::Uuid::new_v4().to_string(),
+            actions,
+        }
+    }
+
+    /// Bind `path`, replacing a socket left behind by a previous daemon
+    /// (the PID file already guarantees none is running).
+    #[cfg(unix)]
+    pub fn spawn(self, path: &Path) -> Result<tokio::task::JoinHandle<()>> {
+        use std::os::unix::fs::PermissionsExt;
+
+        if path.exists() {
+            std::fs::remove_file(path)
+                .with_context(|| format!("removing stale socket {}", path.display()))?;
+        }
+        let listener = tokio::net::UnixListener::bind(path)
+            .with_context(|| format!("binding control socket {}", path.display()))?;
+        // Anyone who can connect can stop the daemon: owner only.
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+        let server = std::sync::Arc::new(self);
+        Ok(tokio::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        tracing::warn!("Control socket accept failed: {e}");
+                        continue;
+                    }
+                };
+                let server = std::sync::Arc::clone(&server);
+                tokio::spawn(async move {
+                    let (reader, writer) = stream.into_split();
+                    if let Err(e) = server.serve(reader, writer).await {
+                        tracing::debug!("Control connection ended: {e}");
+                    }
+                });
+            }
+        }))
+    }
+
+    /// Create the named pipe `path` and serve one client per pipe instance.
+    #[cfg(windows)]
+    pub fn spawn(self, path: &Path) -> Result<tokio::task::JoinHandle<()>> {
+        use tokio::net::windows::named_pipe::ServerOptions;
+
+        let name = path.as_os_str().to_owned();
+        // Pipes are local-only here and default to the creating user's ACL.
+        let mut pipe = ServerOptions::new()
+            .first_pipe_instance(true)
+            .reject_remote_clients(true)
+            .create(&name)
+            .with_context(|| format!("creating control pipe {}", path.display()))?;
+
+        let server = std::sync::Arc::new(self);
+        Ok(tokio::spawn(async move {
+            loop {
+                if let Err(e) = pipe.connect().await {
+                    tracing::warn!("Control pipe connect failed: {e}");
+                    continue;
+                }
+                let connected = pipe;
+                pipe = match ServerOptions::new()
+                    .reject_remote_clients(true)
+                    .create(&name)
+                {
+                    Ok(next) => next,
+                    Err(e) => {
+                        tracing::error!("Cannot create control pipe instance: {e}");
+                        return;
+                    }
+                };
+                let server = std::sync::Arc::clone(&server);
+                tokio::spawn(async move {
+                    let (reader, writer) = tokio::io::split(connected);
+                    if let Err(e) = server.serve(reader, writer).await {
+                        tracing::debug!("Control connection ended: {e}");
+                    }
+                });
+            }
+        }))
+    }
+
+    async fn serve<R, W>(&self, reader: R, mut writer: W) -> std::io::Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut lines = BufReader::new(reader).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = self.handle_line(&line).await;
+            let mut out = serde_json::to_vec(&response).unwrap_or_default();
+            out.push(b'\n');
+            writer.write_all(&out).await?;
+            writer.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_line(&self, line: &str) -> Response {
+        let request = match serde_json::from_str::<Request>(line) {
+            Ok(request) => request,
+            Err(e) => return Response::error(Value::Null, PARSE_ERROR, e.to_string()),
+        };
+        match self.dispatch(&request).await {
+            Ok(result) => Response::result(request.id, result),
+            Err((code, message)) => Response::error(request.id, code, message),
+        }
+    }
+
+    async fn dispatch(&self, request: &Request) -> RpcResult {
+        match request.method.as_str() {
+            "status" => Ok(self.status()),
+            "stop" => Ok(self.request(Action::Stop)),
+            "restart" => Ok(self.request(Action::Restart)),
+            "message.send" => self.send_message(params(&request.params)?).await,
+            "sessions.list" => self.list_sessions(&params(&request.params)?),
+            "sessions.show" => self.show_session(&params(&request.params)?),
+            "tools.list" => Ok(json!({ "disabled": crate::tools::toggles::disabled() })),
+            "tools.enable" => Ok(Self::toggle_tools(&params(&request.params)?, true)),
+            "tools.disable" => Ok(Self::toggle_tools(&params(&request.params)?, false)),
+            other => Err((METHOD_NOT_FOUND, format!("unknown method '{other}'"))),
+        }
+    }
+
+    fn status(&self) -> Value {
+        let mut status = crate::health::snapshot_json();
+        if let Some(obj) = status.as_object_mut() {
+            obj.insert("version".into(), json!(env!("CARGO_PKG_VERSION")));
+            obj.insert("config_path".into(), json!(self.config.config_path));
+            obj.insert("instance".into(), json!(self.instance));
+            obj.insert("circuits".into(), crate::resilience::snapshot_json());
+        }
+        status
+    }
+
+    fn request(&self, action: Action) -> Value {
+        // Only the first stop/restart counts; later ones find the queue full.
+        let _ = self.actions.try_send(action);
+        json!({ "pid": std::process::id(), "instance": self.instance })
+    }
+
+    /// One agent turn with the full toolset, persisted to `session` when given.
+    async fn send_message(&self, params: SendParams) -> RpcResult {
+        if params.message.trim().is_empty() {
+            return Err(invalid_params("message must not be empty"));
+        }
+        let reply = Box::pin(crate::agent::run(
+            self.config.clone(),
+            Some(params.message),
+            None,
+            None,
+            self.config.default_temperature,
+            vec![],
+            false,
+            params.session.clone(),
+        ))
+        .await
+        .map_err(|e| server_error(&e))?;
+        Ok(json!({ "reply": reply, "session": params.session }))
+    }
+
+    fn store(&self) -> std::result::Result<crate::conversations::ConversationStore, (i64, String)> {
+        crate::conversations::ConversationStore::open(&self.config.workspace_dir)
+            .map_err(|e| server_error(&e))
+    }
+
+    fn list_sessions(&self, params: &ListParams) -> RpcResult {
+        let sessions = self
+            .store()?
+            .list_sessions(params.limit.unwrap_or(DEFAULT_SESSION_LIMIT))
+            .map_err(|e| server_error(&e))?;
+        Ok(json!(sessions))
+    }
+
+    fn show_session(&self, params: &ShowParams) -> RpcResult {
+        let store = self.store()?;
+        let session = store
+            .resolve_session(&params.session)
+            .map_err(|e| server_error(&e))?
+            .ok_or_else(|| invalid_params(format!("no session '{}'", params.session)))?;
+        let messages = store
+            .recent_messages(&session.id, params.limit.unwrap_or(DEFAULT_MESSAGE_LIMIT))
+            .map_err(|e| server_error(&e))?;
+        Ok(json!({ "session": session, "messages": messages }))
+    }
+
+    fn toggle_tools(params: &ToolsParams, enabled: bool) -> Value {
+        json!({ "disabled": crate::tools::toggles::set_enabled(&params.tools, enabled) })
+    }
+}
+
+impl Response {
+    fn result(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".into(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    fn error(id: Value, code: i64, message: String) -> Self {
+        Self {
+            jsonrpc: "2.0".into(),
+            id,
+            result: None,
+            error: Some(RpcError { code, message }),
+        }
+    }
+}
+
+/// Send one request to the daemon listening on `path` and return its result.
+pub async fn call(path: &Path, method: &str, params: Value) -> Result<Value> {
+    let not_running = || {
+        format!(
+            "no daemon is listening on {} — start one with `zeroclaw daemon`",
+            path.display()
+        )
+    };
+    #[cfg(unix)]
+    let stream = tokio::net::UnixStream::connect(path)
+        .await
+        .with_context(not_running)?;
+    #[cfg(windows)]
+    let stream = tokio::net::windows::named_pipe::ClientOptions::new()
+        .open(path.as_os_str())
+        .with_context(not_running)?;
+    let (reader, mut writer) = tokio::io::split(stream);
+
+    let mut request = serde_json::to_vec(&json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    }))?;
+    request.push(b'\n');
+    writer.write_all(&request).await?;
+
+    let line = BufReader::new(reader)
+        .lines()
+        .next_line()
+        .await?
+        .context("daemon closed the control connection without answering")?;
+    let response: Response = serde_json::from_str(&line).context("invalid control response")?;
+    if let Some(error) = response.error {
+        bail!("daemon: {}", error.message);
+    }
+    Ok(response.result.unwrap_or(Value::Null))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn exchange(server: &Server, requests: &[&str]) -> Vec<Response> {
+        let input = requests.join("\n") + "\n";
+        let mut output = Vec::new();
+        server.serve(input.as_bytes(), &mut output).await.unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn test_server(tmp: &tempfile::TempDir) -> (Server, mpsc::Receiver<Action>) {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let (tx, rx) = mpsc::channel(1);
+        (Server::new(config, tx), rx)
+    }
+
+    #[tokio::test]
+    async fn serves_status_and_forwards_lifecycle_actions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (server, mut rx) = test_server(&tmp);
+        let responses = exchange(
+            &server,
+            &[
+                r#"{"jsonrpc":"2.0","id":1,"method":"status"}"#,
+                r#"{"jsonrpc":"2.0","id":2,"method":"restart"}"#,
+                r#"{"jsonrpc":"2.0","id":3,"method":"bogus"}"#,
+                "not json",
+            ],
+        )
+        .await;
+
+        assert_eq!(responses.len(), 4);
+        let status = responses[0].result.as_ref().unwrap();
+        assert_eq!(status["pid"], json!(std::process::id()));
+        assert_eq!(status["config_path"], json!(tmp.path().join("config.toml")));
+        assert_eq!(rx.try_recv().unwrap(), Action::Restart);
+        assert_eq!(responses[2].error.as_ref().unwrap().code, METHOD_NOT_FOUND);
+        assert_eq!(responses[3].error.as_ref().unwrap().code, PARSE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn reads_sessions_and_validates_params() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (server, _rx) = test_server(&tmp);
+        let store =
+            crate::conversations::ConversationStore::open(&server.config.workspace_dir).unwrap();
+        store.ensure_session("cli:abc", "cli", "user").unwrap();
+        store.append_message("cli:abc", "user", "hello").unwrap();
+        store.rename_session("cli:abc", Some("work")).unwrap();
+
+        let responses = exchange(
+            &server,
+            &[
+                r#"{"jsonrpc":"2.0","id":1,"method":"sessions.list"}"#,
+                r#"{"jsonrpc":"2.0","id":2,"method":"sessions.show","params":{"session":"work"}}"#,
+                r#"{"jsonrpc":"2.0","id":3,"method":"sessions.show","params":{"session":"nope"}}"#,
+                r#"{"jsonrpc":"2.0","id":4,"method":"message.send","params":{}}"#,
+            ],
+        )
+        .await;
+
+        let sessions = responses[0].result.as_ref().unwrap();
+        assert_eq!(sessions[0]["id"], "cli:abc");
+        let shown = responses[1].result.as_ref().unwrap();
+        assert_eq!(shown["messages"][0]["content"], "hello");
+        assert_eq!(responses[2].error.as_ref().unwrap().code, INVALID_PARAMS);
+        assert_eq!(responses[3].error.as_ref().unwrap().code, INVALID_PARAMS);
+    }
+}
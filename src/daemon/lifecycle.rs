@@ -0,0 +1,187 @@
+//! Process lifecycle for `zeroclaw daemon`: the PID file, termination
+//! signals, systemd readiness notification and re-exec on restart.
+
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// `<config dir>/daemon.pid`, next to `daemon_state.json`.
+pub fn pid_file_path(config: &Config) -> PathBuf {
+    super::state_file_path(config).with_file_name("daemon.pid")
+}
+
+/// Where the running daemon accepts control requests: `<config dir>/daemon.sock`,
+/// or on Windows a named pipe unique to the config directory.
+pub fn socket_path(config: &Config) -> PathBuf {
+    let socket = super::state_file_path(config).with_file_name("daemon.sock");
+    if cfg!(windows) {
+        use sha2::{Digest, Sha256};
+        let digest = Sha256::digest(socket.to_string_lossy().as_bytes());
+        PathBuf::from(format!(r"\\.\pipe\zeroclaw-{}", hex::encode(&digest[..8])))
+    } else {
+        socket
+    }
+}
+
+/// Holds the PID file for the life of the daemon and removes it on drop.
+#[derive(Debug)]
+pub struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    /// Write our PID to `path`, refusing when it names another live process.
+    /// A file left behind by a crashed daemon is overwritten.
+    pub fn acquire(path: &Path) -> Result<Self> {
+        if let Some(pid) = read_pid(path) {
+            if pid != std::process::id() && process_alive(pid) {
+                bail!(
+                    "ZeroClaw daemon is already running (pid {pid}); \
+                     stop it with `zeroclaw daemon stop`"
+                );
+            }
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        std::fs::write(path, format!("{}\n", std::process::id()))
+            .with_context(|| format!("writing PID file {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        // A restarted daemon may already have replaced the file.
+        if read_pid(&self.path) == Some(std::process::id()) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// The PID recorded in `path`, if the file exists and holds one.
+pub fn read_pid(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    // 0 and negative PIDs address process groups, never one process.
+    let Some(pid) = libc::pid_t::try_from(pid).ok().filter(|pid| *pid > 0) else {
+        return false;
+    };
+    // Signal 0 only checks for existence; EPERM still means it exists.
+    // SAFETY: kill(2) with signal 0 sends nothing and touches no memory; `pid`
+    // is positive, so it names a single process.
+    let delivered = unsafe { libc::kill(pid, 0) } == 0;
+    delivered || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    // Without a cheap liveness probe, trust the daemon to clean up after itself.
+    true
+}
+
+/// Resolve on Ctrl+C or, on Unix, SIGTERM (what systemd, launchd and
+/// `kill` send).
+pub async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                return;
+            }
+            Err(e) => tracing::warn!("Cannot listen for SIGTERM: {e}"),
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Send `state` (e.g. `READY=1`) to systemd when it started us with
+/// `Type=notify`; a no-op everywhere else.
+pub fn sd_notify(state: &str) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::net::UnixDatagram;
+
+        let Some(target) = std::env::var_os("NOTIFY_SOCKET") else {
+            return;
+        };
+        let Ok(socket) = UnixDatagram::unbound() else {
+            return;
+        };
+        let target = PathBuf::from(target);
+        let sent = match target.to_str().and_then(|t| t.strip_prefix('@')) {
+            #[cfg(target_os = "linux")]
+            Some(name) => {
+                use std::os::linux::net::SocketAddrExt;
+                std::os::unix::net::SocketAddr::from_abstract_name(name.as_bytes())
+                    .and_then(|addr| socket.send_to_addr(state.as_bytes(), &addr))
+            }
+            _ => socket.send_to(state.as_bytes(), &target),
+        };
+        if let Err(e) = sent {
+            tracing::debug!("sd_notify({state:?}) failed: {e}");
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = state;
+}
+
+/// Replace this process with a fresh `zeroclaw` started with the same
+/// arguments. Only returns on failure.
+#[cfg(unix)]
+pub fn reexec() -> Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    let exe = std::env::current_exe().context("Failed to resolve current executable")?;
+    let err = std::process::Command::new(exe)
+        .args(std::env::args_os().skip(1))
+        .exec();
+    Err(err).context("re-executing zeroclaw daemon")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pid_file_is_exclusive_and_removed_on_drop() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("daemon.pid");
+
+        let held = PidFile::acquire(&path).unwrap();
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+        drop(held);
+        assert!(!path.exists());
+
+        #[cfg(unix)]
+        {
+            // A stale file from a process that no longer exists is taken over.
+            std::fs::write(&path, "999999999\n").unwrap();
+            let held = PidFile::acquire(&path).unwrap();
+            assert_eq!(read_pid(&path), Some(std::process::id()));
+            drop(held);
+
+            // PID 0 names our own process group, not a running daemon.
+            std::fs::write(&path, "0\n").unwrap();
+            let held = PidFile::acquire(&path).unwrap();
+            assert_eq!(read_pid(&path), Some(std::process::id()));
+            drop(held);
+
+            // PID 1 is always alive.
+            std::fs::write(&path, "1\n").unwrap();
+            let err = PidFile::acquire(&path).unwrap_err();
+            assert!(err.to_string().contains("already running (pid 1)"));
+        }
+    }
+}
@@ -1,14 +1,26 @@
+mod control;
+mod lifecycle;
+mod status;
+
+pub use status::print_live as print_live_status;
+
 use crate::config::Config;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use chrono::Utc;
 use std::future::Future;
 use std::path::PathBuf;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 const STATUS_FLUSH_SECONDS: u64 = 5;
+/// How long channels get to finish in-flight messages on shutdown.
+const SHUTDOWN_GRACE_SECONDS: u64 = 30;
+/// How long `daemon stop|restart` wait for the daemon to go away or come back.
+const CONTROL_WAIT_SECONDS: u64 = SHUTDOWN_GRACE_SECONDS + 15;
 
 pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
+    let pid_file = lifecycle::PidFile::acquire(&lifecycle::pid_file_path(&config))?;
     let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
     let max_backoff = config
         .reliability
@@ -23,7 +35,20 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 .await;
     }
 
-    let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
+    let shutdown = CancellationToken::new();
+    let mut handles: Vec<JoinHandle<()>> = vec![
+        spawn_state_writer(config.clone()),
+        tokio::spawn(crate::config::reload::watch(
+            config.clone(),
+            shutdown.clone(),
+        )),
+    ];
+    // Components that observe `shutdown` and are given time to drain.
+    let mut graceful_handles: Vec<JoinHandle<()>> = Vec::new();
+
+    let (control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+    let socket_path = lifecycle::socket_path(&config);
+    handles.push(control::Server::new(config.clone(), control_tx).spawn(&socket_path)?);
 
     {
         let gateway_cfg = config.clone();
@@ -32,6 +57,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             "gateway",
             initial_backoff,
             max_backoff,
+            shutdown.clone(),
             move || {
                 let cfg = gateway_cfg.clone();
                 let host = gateway_host.clone();
@@ -43,13 +69,16 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     {
         if has_supervised_channels(&config) {
             let channels_cfg = config.clone();
-            handles.push(spawn_component_supervisor(
+            let channels_shutdown = shutdown.clone();
+            graceful_handles.push(spawn_component_supervisor(
                 "channels",
                 initial_backoff,
                 max_backoff,
+                shutdown.clone(),
                 move || {
                     let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
+                    let token = channels_shutdown.clone();
+                    async move { crate::channels::start_channels_with_shutdown(cfg, token).await }
                 },
             ));
         } else {
@@ -64,6 +93,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             "heartbeat",
             initial_backoff,
             max_backoff,
+            shutdown.clone(),
             move || {
                 let cfg = heartbeat_cfg.clone();
                 async move { Box::pin(run_heartbeat_worker(cfg)).await }
@@ -77,6 +107,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             "scheduler",
             initial_backoff,
             max_backoff,
+            shutdown.clone(),
             move || {
                 let cfg = scheduler_cfg.clone();
                 async move { crate::cron::scheduler::run(cfg).await }
@@ -87,13 +118,74 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if config.tasks.enabled {
+        let tasks_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "tasks",
+            initial_backoff,
+            max_backoff,
+            shutdown.clone(),
+            move || {
+                let cfg = tasks_cfg.clone();
+                async move { crate::tasks::runner::run(cfg).await }
+            },
+        ));
+    } else {
+        crate::health::mark_component_ok("tasks");
+    }
+
+    let feeds_enabled = config.feeds.enabled && !config.feeds.sources.is_empty();
+    if feeds_enabled {
+        let feeds_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "feeds",
+            initial_backoff,
+            max_backoff,
+            shutdown.clone(),
+            move || {
+                let cfg = feeds_cfg.clone();
+                async move { crate::feeds::run(cfg).await }
+            },
+        ));
+    }
+
+    if config.memory.compaction.enabled {
+        let compaction_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "memory_compaction",
+            initial_backoff,
+            max_backoff,
+            shutdown.clone(),
+            move || {
+                let cfg = compaction_cfg.clone();
+                async move { crate::memory::compaction::run(cfg).await }
+            },
+        ));
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
-    println!("   Components: gateway, channels, heartbeat, scheduler");
-    println!("   Ctrl+C to stop");
-
-    tokio::signal::ctrl_c().await?;
+    let mut components = vec!["gateway", "channels", "heartbeat", "scheduler"];
+    if feeds_enabled {
+        components.push("feeds");
+    }
+    println!("   Components: {}", components.join(", "));
+    println!(
+        "   Config:   {} (reloads on change or SIGHUP)",
+        config.config_path.display()
+    );
+    println!("   Control:  {}", socket_path.display());
+    println!("   Ctrl+C or `zeroclaw daemon stop` to stop");
+
+    lifecycle::sd_notify("READY=1\nSTATUS=Running");
+    let action = tokio::select! {
+        () = lifecycle::shutdown_signal() => control::Action::Stop,
+        Some(action) = control_rx.recv() => action,
+    };
+    lifecycle::sd_notify("STOPPING=1\nSTATUS=Draining in-flight messages");
+    tracing::info!("Daemon shutting down ({action:?})");
     crate::health::mark_component_error("daemon", "shutdown requested");
+    shutdown.cancel();
 
     for handle in &handles {
         handle.abort();
@@ -101,10 +193,109 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     for handle in handles {
         let _ = handle.await;
     }
+    for handle in graceful_handles {
+        let abort = handle.abort_handle();
+        if tokio::time::timeout(Duration::from_secs(SHUTDOWN_GRACE_SECONDS), handle)
+            .await
+            .is_err()
+        {
+            tracing::warn!("Component did not stop within {SHUTDOWN_GRACE_SECONDS}s; aborting");
+            abort.abort();
+        }
+    }
+
+    let _ = std::fs::remove_file(&socket_path);
+    drop(pid_file);
+    if action == control::Action::Restart {
+        #[cfg(unix)]
+        lifecycle::reexec()?;
+    }
+    Ok(())
+}
 
+/// `zeroclaw daemon status|stop|restart`: talk to the running daemon over
+/// its control socket.
+pub async fn handle_command(config: &Config, command: &crate::DaemonCommands) -> Result<()> {
+    let socket = lifecycle::socket_path(config);
+    match command {
+        crate::DaemonCommands::Status => {
+            let status = control::call(&socket, "status", serde_json::Value::Null).await?;
+            print_status(&status);
+        }
+        crate::DaemonCommands::Stop => {
+            let reply = control::call(&socket, "stop", serde_json::Value::Null).await?;
+            println!("Stopping daemon (pid {})…", reply["pid"]);
+            let stopped = wait_for(|| async {
+                control::call(&socket, "status", serde_json::Value::Null)
+                    .await
+                    .is_err()
+            })
+            .await;
+            if !stopped {
+                bail!("daemon did not stop within {CONTROL_WAIT_SECONDS}s");
+            }
+            println!("✅ Daemon stopped");
+        }
+        crate::DaemonCommands::Restart => {
+            let reply = control::call(&socket, "restart", serde_json::Value::Null).await?;
+            let old_instance = reply["instance"].clone();
+            println!("Restarting daemon (pid {})…", reply["pid"]);
+            let restarted = wait_for(|| async {
+                control::call(&socket, "status", serde_json::Value::Null)
+                    .await
+                    .is_ok_and(|status| status["instance"] != old_instance)
+            })
+            .await;
+            if !restarted {
+                bail!("daemon did not come back within {CONTROL_WAIT_SECONDS}s");
+            }
+            println!("✅ Daemon restarted");
+        }
+    }
     Ok(())
 }
 
+/// Poll `done` every 250ms for up to [`CONTROL_WAIT_SECONDS`].
+async fn wait_for<F, Fut>(mut done: F) -> bool
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(CONTROL_WAIT_SECONDS);
+    while tokio::time::Instant::now() < deadline {
+        if done().await {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    false
+}
+
+fn print_status(status: &serde_json::Value) {
+    println!("🧠 ZeroClaw daemon");
+    println!("   PID:      {}", status["pid"]);
+    println!(
+        "   Version:  {}",
+        status["version"].as_str().unwrap_or("unknown")
+    );
+    println!("   Uptime:   {}s", status["uptime_seconds"]);
+    println!(
+        "   Config:   {}",
+        status["config_path"].as_str().unwrap_or("unknown")
+    );
+    if let Some(components) = status["components"].as_object() {
+        println!("   Components:");
+        for (name, component) in components {
+            let state = component["status"].as_str().unwrap_or("unknown");
+            let icon = if state == "ok" { "✅" } else { "❌" };
+            match component["last_error"].as_str() {
+                Some(error) if state != "ok" => println!("     {icon} {name}: {error}"),
+                _ => println!("     {icon} {name}"),
+            }
+        }
+    }
+}
+
 pub fn state_file_path(config: &Config) -> PathBuf {
     config
         .config_path
@@ -129,6 +320,7 @@ fn spawn_state_writer(config: Config) -> JoinHandle<()> {
                     "written_at".into(),
                     serde_json::json!(Utc::now().to_rfc3339()),
                 );
+                obj.insert("circuits".into(), crate::resilience::snapshot_json());
             }
             let data = serde_json::to_vec_pretty(&json).unwrap_or_else(|_| b"{}".to_vec());
             let _ = tokio::fs::write(&path, data).await;
@@ -136,10 +328,13 @@ fn spawn_state_writer(config: Config) -> JoinHandle<()> {
     })
 }
 
+/// Run a daemon component, restarting it with backoff whenever it exits.
+/// Once `shutdown` is cancelled the component is not restarted.
 fn spawn_component_supervisor<F, Fut>(
     name: &'static str,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
+    shutdown: CancellationToken,
     mut run_component: F,
 ) -> JoinHandle<()>
 where
@@ -152,7 +347,12 @@ where
 
         loop {
             crate::health::mark_component_ok(name);
-            match run_component().await {
+            let result = run_component().await;
+            if shutdown.is_cancelled() {
+                tracing::info!("Daemon component '{name}' stopped");
+                break;
+            }
+            match result {
                 Ok(()) => {
                     crate::health::mark_component_error(name, "component exited unexpectedly");
                     tracing::warn!("Daemon component '{name}' exited unexpectedly");
@@ -166,7 +366,10 @@ where
             }
 
             crate::health::bump_component_restart(name);
-            tokio::time::sleep(Duration::from_secs(backoff)).await;
+            tokio::select! {
+                () = tokio::time::sleep(Duration::from_secs(backoff)) => {}
+                () = shutdown.cancelled() => break,
+            }
             // Double backoff AFTER sleeping so first error uses initial_backoff
             backoff = backoff.saturating_mul(2).min(max_backoff);
         }
@@ -191,6 +394,10 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
 
         let file_tasks = engine.collect_tasks().await?;
         let tasks = heartbeat_tasks_for_tick(file_tasks, config.heartbeat.message.as_deref());
+        if config.heartbeat.mode == crate::config::HeartbeatMode::Review {
+            run_heartbeat_review(&config, &tasks, delivery.as_ref()).await;
+            continue;
+        }
         if tasks.is_empty() {
             continue;
         }
@@ -198,6 +405,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
         for task in tasks {
             let prompt = format!("[Heartbeat Task] {task}");
             let temp = config.default_temperature;
+            let session = heartbeat_session_id();
             match crate::agent::run(
                 config.clone(),
                 Some(prompt),
@@ -206,6 +414,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                 temp,
                 vec![],
                 false,
+                Some(session.clone()),
             )
             .await
             {
@@ -216,22 +425,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                     } else {
                         output
                     };
-                    if let Some((channel, target)) = &delivery {
-                        if let Err(e) = crate::cron::scheduler::deliver_announcement(
-                            &config,
-                            channel,
-                            target,
-                            &announcement,
-                        )
-                        .await
-                        {
-                            crate::health::mark_component_error(
-                                "heartbeat",
-                                format!("delivery failed: {e}"),
-                            );
-                            tracing::warn!("Heartbeat delivery failed: {e}");
-                        }
-                    }
+                    deliver_heartbeat(&config, delivery.as_ref(), &session, &announcement).await;
                 }
                 Err(e) => {
                     crate::health::mark_component_error("heartbeat", e.to_string());
@@ -242,6 +436,96 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
     }
 }
 
+/// One review-mode tick: a single agent turn over the checklist and pending
+/// background tasks. The user is only messaged when the agent rates its
+/// finding at or above `importance_threshold`.
+async fn run_heartbeat_review(
+    config: &Config,
+    checklist: &[String],
+    delivery: Option<&(String, String)>,
+) {
+    let pending = if config.tasks.enabled {
+        crate::tasks::TaskStore::open(&config.workspace_dir)
+            .and_then(|store| store.list(false, 20))
+            .unwrap_or_else(|e| {
+                tracing::warn!("Heartbeat could not read pending tasks: {e}");
+                Vec::new()
+            })
+    } else {
+        Vec::new()
+    };
+    if checklist.is_empty() && pending.is_empty() {
+        return;
+    }
+
+    let prompt = crate::heartbeat::engine::HeartbeatEngine::review_prompt(checklist, &pending);
+    let session = heartbeat_session_id();
+    let output = match crate::agent::run(
+        config.clone(),
+        Some(prompt),
+        None,
+        None,
+        config.default_temperature,
+        vec![],
+        false,
+        Some(session.clone()),
+    )
+    .await
+    {
+        Ok(output) => output,
+        Err(e) => {
+            crate::health::mark_component_error("heartbeat", e.to_string());
+            tracing::warn!("Heartbeat review failed: {e}");
+            return;
+        }
+    };
+    crate::health::mark_component_ok("heartbeat");
+
+    match crate::heartbeat::engine::HeartbeatEngine::parse_review(&output) {
+        Some(decision)
+            if decision.importance >= config.heartbeat.importance_threshold
+                && !decision.message.is_empty() =>
+        {
+            deliver_heartbeat(config, delivery, &session, &decision.message).await;
+        }
+        Some(decision) => tracing::debug!(
+            importance = decision.importance,
+            "Heartbeat review below threshold; not messaging"
+        ),
+        None => tracing::warn!("Heartbeat review reply had no importance rating; ignoring"),
+    }
+}
+
+/// Each heartbeat turn gets its own session so its output can be opened later.
+fn heartbeat_session_id() -> String {
+    format!("heartbeat:{}", uuid::Uuid::new_v4())
+}
+
+/// Send heartbeat output to the configured channel, or show it as a desktop
+/// notification when there is none.
+async fn deliver_heartbeat(
+    config: &Config,
+    delivery: Option<&(String, String)>,
+    session: &str,
+    text: &str,
+) {
+    let Some((channel, target)) = delivery else {
+        crate::notifications::agent_output(
+            config,
+            "Heartbeat",
+            text,
+            Some(&format!("zeroclaw agent --session {session}")),
+        );
+        return;
+    };
+    if let Err(e) =
+        crate::cron::scheduler::deliver_announcement(config, channel, target, text).await
+    {
+        crate::health::mark_component_error("heartbeat", format!("delivery failed: {e}"));
+        tracing::warn!("Heartbeat delivery failed: {e}");
+    }
+}
+
 fn heartbeat_tasks_for_tick(
     file_tasks: Vec<String>,
     fallback_message: Option<&str>,
@@ -352,9 +636,13 @@ mod tests {
 
     #[tokio::test]
     async fn supervisor_marks_error_and_restart_on_failure() {
-        let handle = spawn_component_supervisor("daemon-test-fail", 1, 1, || async {
-            anyhow::bail!("boom")
-        });
+        let handle = spawn_component_supervisor(
+            "daemon-test-fail",
+            1,
+            1,
+            CancellationToken::new(),
+            || async { anyhow::bail!("boom") },
+        );
 
         tokio::time::sleep(Duration::from_millis(50)).await;
         handle.abort();
@@ -372,7 +660,13 @@ mod tests {
 
     #[tokio::test]
     async fn supervisor_marks_unexpected_exit_as_error() {
-        let handle = spawn_component_supervisor("daemon-test-exit", 1, 1, || async { Ok(()) });
+        let handle = spawn_component_supervisor(
+            "daemon-test-exit",
+            1,
+            1,
+            CancellationToken::new(),
+            || async { Ok(()) },
+        );
 
         tokio::time::sleep(Duration::from_millis(50)).await;
         handle.abort();
@@ -388,6 +682,33 @@ mod tests {
             .contains("component exited unexpectedly"));
     }
 
+    #[tokio::test]
+    async fn supervisor_stops_restarting_after_shutdown() {
+        let shutdown = CancellationToken::new();
+        let runs = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = std::sync::Arc::clone(&runs);
+        let token = shutdown.clone();
+        let handle =
+            spawn_component_supervisor("daemon-test-shutdown", 1, 1, shutdown.clone(), move || {
+                let counter = std::sync::Arc::clone(&counter);
+                let token = token.clone();
+                async move {
+                    counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    token.cancelled().await;
+                    Ok(())
+                }
+            });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        shutdown.cancel();
+        let join = tokio::time::timeout(Duration::from_secs(1), handle).await;
+        assert!(
+            join.is_ok(),
+            "supervisor should exit once shutdown is cancelled"
+        );
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn detects_no_supervised_channels() {
         let config = Config::default();
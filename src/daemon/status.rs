@@ -0,0 +1,296 @@
+//! Live section of `zeroclaw status`: what the running daemon reports about
+//! its channels, circuits and queues, plus session and memory counts read
+//! straight from the workspace.
+
+use super::{control, lifecycle, state_file_path};
+use crate::config::Config;
+use crate::conversations::ConversationStore;
+use crate::memory::{self, Memory};
+use crate::tasks::{TaskStatus, TaskStore};
+use anyhow::Result;
+use serde_json::Value;
+use std::io::Write;
+use std::path::Path;
+use tokio::time::Duration;
+
+/// Sessions updated within this window count as active.
+const ACTIVE_SESSION_WINDOW_MINUTES: i64 = 60;
+
+/// Where the daemon figures came from.
+enum Source {
+    /// Answered over the control socket just now.
+    Live,
+    /// Read from `daemon_state.json`; the daemon may be gone.
+    StateFile,
+}
+
+/// Print the live section once, or redraw it every `interval` until Ctrl+C
+/// when `watch` is set.
+pub async fn print_live(config: &Config, watch: bool, interval: Duration) -> Result<()> {
+    // Opened once: the factory runs hygiene and may connect to a remote
+    // backend, which is too heavy to repeat on every refresh.
+    let mem = memory::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )
+    .ok();
+
+    if !watch {
+        for line in render(config, mem.as_deref()).await {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+
+    let mut ticker = tokio::time::interval(interval.max(Duration::from_secs(1)));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+        let lines = render(config, mem.as_deref()).await;
+        let mut stdout = std::io::stdout().lock();
+        // Home the cursor and clear the screen so the view refreshes in place.
+        write!(stdout, "\x1b[H\x1b[2J")?;
+        writeln!(
+            stdout,
+            "🦀 ZeroClaw Status — every {}s, Ctrl+C to exit",
+            interval.as_secs().max(1)
+        )?;
+        writeln!(stdout)?;
+        for line in lines {
+            writeln!(stdout, "{line}")?;
+        }
+        stdout.flush()?;
+    }
+}
+
+async fn render(config: &Config, mem: Option<&dyn Memory>) -> Vec<String> {
+    let mut lines = Vec::new();
+    let state = daemon_state(config).await;
+    match &state {
+        None => {
+            lines.push("Daemon:      ⚪ not running (start `zeroclaw daemon`)".to_string());
+            lines.push(String::new());
+        }
+        Some((state, source)) => render_daemon(state, source, &mut lines),
+    }
+    lines.push("Queues:".to_string());
+    if let Some((state, _)) = &state {
+        lines.push(channel_queue_line(state));
+    }
+    render_workspace(config, mem, &mut lines).await;
+    lines
+}
+
+async fn daemon_state(config: &Config) -> Option<(Value, Source)> {
+    let socket = lifecycle::socket_path(config);
+    if let Ok(status) = control::call(&socket, "status", Value::Null).await {
+        return Some((status, Source::Live));
+    }
+    let raw = std::fs::read_to_string(state_file_path(config)).ok()?;
+    let state = serde_json::from_str(&raw).ok()?;
+    Some((state, Source::StateFile))
+}
+
+fn render_daemon(state: &Value, source: &Source, lines: &mut Vec<String>) {
+    let uptime = format_uptime(state["uptime_seconds"].as_u64().unwrap_or(0));
+    match source {
+        Source::Live => lines.push(format!(
+            "Daemon:      🟢 running (pid {}, up {uptime})",
+            state["pid"]
+        )),
+        Source::StateFile => lines.push(format!(
+            "Daemon:      ⚪ not answering; last state written {} (was up {uptime})",
+            state["written_at"].as_str().unwrap_or("at an unknown time")
+        )),
+    }
+    lines.push(String::new());
+
+    let components = state["components"].as_object();
+    let (channels, others): (Vec<_>, Vec<_>) = components
+        .into_iter()
+        .flatten()
+        .partition(|(name, _)| name.starts_with("channel:"));
+
+    lines.push("Channels:".to_string());
+    if channels.is_empty() {
+        lines.push("  (no channel listeners running)".to_string());
+    }
+    for (name, component) in channels {
+        let name = name.trim_start_matches("channel:");
+        lines.push(format!(
+            "  {}",
+            describe_component(name, component, "connected")
+        ));
+    }
+    lines.push(String::new());
+
+    lines.push("Components:".to_string());
+    for (name, component) in others {
+        lines.push(format!("  {}", describe_component(name, component, "ok")));
+    }
+    lines.push(String::new());
+
+    lines.push("Providers & tools:".to_string());
+    let circuits = crate::resilience::describe_circuits(&state["circuits"]);
+    if circuits.is_empty() {
+        lines.push("  (no provider or tool calls recorded)".to_string());
+    }
+    lines.extend(circuits);
+    lines.push(String::new());
+}
+
+fn channel_queue_line(state: &Value) -> String {
+    let gauge = |name: &str| state["gauges"][name].as_u64().unwrap_or(0);
+    format!(
+        "  Channel messages:  {} queued, {} in flight",
+        gauge(crate::channels::CHANNEL_QUEUE_DEPTH_GAUGE),
+        gauge(crate::channels::CHANNEL_IN_FLIGHT_GAUGE)
+    )
+}
+
+fn describe_component(name: &str, component: &Value, ok_label: &str) -> String {
+    let restarts = component["restart_count"].as_u64().unwrap_or(0);
+    let restarts = match restarts {
+        0 => String::new(),
+        1 => " (1 restart)".to_string(),
+        n => format!(" ({n} restarts)"),
+    };
+    match component["status"].as_str().unwrap_or("unknown") {
+        "ok" => format!("✅ {name:<12} {ok_label}{restarts}"),
+        "error" => format!(
+            "❌ {name:<12} {}{restarts}",
+            component["last_error"].as_str().unwrap_or("error")
+        ),
+        other => format!("🟡 {name:<12} {other}{restarts}"),
+    }
+}
+
+async fn render_workspace(config: &Config, mem: Option<&dyn Memory>, lines: &mut Vec<String>) {
+    match TaskStore::open(&config.workspace_dir).and_then(|store| store.list(false, usize::MAX)) {
+        Ok(tasks) => {
+            let running = tasks
+                .iter()
+                .filter(|task| task.status == TaskStatus::Running)
+                .count();
+            lines.push(format!(
+                "  Background tasks:  {} queued, {running} running",
+                tasks.len() - running
+            ));
+        }
+        Err(e) => lines.push(format!("  Background tasks:  unavailable ({e})")),
+    }
+    lines.push(String::new());
+
+    let window = chrono::Duration::minutes(ACTIVE_SESSION_WINDOW_MINUTES);
+    match ConversationStore::open(&config.workspace_dir)
+        .and_then(|store| store.session_counts(window))
+    {
+        Ok((total, active)) => lines.push(format!(
+            "Sessions:    {active} active in the last hour ({total} open)"
+        )),
+        Err(e) => lines.push(format!("Sessions:    unavailable ({e})")),
+    }
+
+    let on_disk = format_bytes(dir_size(&config.workspace_dir.join("memory")));
+    match mem {
+        Some(mem) => {
+            let entries = match mem.count().await {
+                Ok(count) => count.to_string(),
+                Err(_) => "?".to_string(),
+            };
+            let health = if mem.health_check().await {
+                ""
+            } else {
+                " ❌ unhealthy"
+            };
+            lines.push(format!(
+                "Memory:      {} — {entries} entries, {on_disk} on disk{health}",
+                mem.name()
+            ));
+        }
+        None => lines.push(format!("Memory:      unavailable ({on_disk} on disk)")),
+    }
+}
+
+/// Total size of the files under `path`, or 0 when it does not exist.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map_or(0, |meta| meta.len()),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn format_bytes(bytes: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let size = bytes as f64;
+    if size >= 1024.0 * 1024.0 {
+        format!("{:.1} MB", size / (1024.0 * 1024.0))
+    } else if size >= 1024.0 {
+        format!("{:.1} KB", size / 1024.0)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+fn format_uptime(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86_400, seconds / 3600 % 24, seconds / 60 % 60);
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {}s", seconds % 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn format_uptime_uses_largest_units() {
+        assert_eq!(format_uptime(42), "42s");
+        assert_eq!(format_uptime(125), "2m 5s");
+        assert_eq!(format_uptime(7_500), "2h 5m");
+        assert_eq!(format_uptime(90_061), "1d 1h 1m");
+    }
+
+    #[test]
+    fn render_daemon_splits_channels_from_components() {
+        let state = json!({
+            "pid": 7,
+            "uptime_seconds": 60,
+            "components": {
+                "channel:telegram": {"status": "ok", "restart_count": 0},
+                "channel:discord": {"status": "error", "last_error": "401", "restart_count": 2},
+                "gateway": {"status": "ok", "restart_count": 0}
+            },
+            "gauges": {"channel_queue_depth": 3, "channel_in_flight": 1},
+            "circuits": {}
+        });
+        let mut lines = Vec::new();
+        render_daemon(&state, &Source::Live, &mut lines);
+        let text = lines.join("\n");
+
+        assert!(text.contains("running (pid 7, up 1m 0s)"));
+        assert!(text.contains("✅ telegram     connected"));
+        assert!(text.contains("❌ discord      401 (2 restarts)"));
+        assert!(text.contains("✅ gateway      ok"));
+        assert!(channel_queue_line(&state).contains("3 queued, 1 in flight"));
+    }
+}
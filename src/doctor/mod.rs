@@ -8,6 +8,18 @@ const DAEMON_STALE_SECONDS: i64 = 30;
 const SCHEDULER_STALE_SECONDS: i64 = 120;
 const CHANNEL_STALE_SECONDS: i64 = 300;
 const COMMAND_VERSION_PREVIEW_CHARS: usize = 60;
+const PROBE_TIMEOUT_SECONDS: u64 = 15;
+/// Fetched with `HEAD` to read a trusted `Date` header for the clock check.
+const CLOCK_REFERENCE_URL: &str = "https://www.cloudflare.com";
+const CLOCK_SKEW_WARN_SECONDS: i64 = 30;
+const CLOCK_SKEW_ERROR_SECONDS: i64 = 300;
+/// SQLite databases the runtime keeps, relative to the workspace.
+const WORKSPACE_DATABASES: &[&str] = &[
+    "memory/brain.db",
+    "state/conversations.db",
+    "state/tasks.db",
+    "cron/jobs.db",
+];
 
 // ── Diagnostic item ──────────────────────────────────────────────
 
@@ -25,12 +37,16 @@ pub struct DiagResult {
     pub severity: Severity,
     pub category: String,
     pub message: String,
+    /// What to do about a warning or error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
 }
 
 struct DiagItem {
     severity: Severity,
     category: &'static str,
     message: String,
+    hint: Option<String>,
 }
 
 impl DiagItem {
@@ -39,6 +55,7 @@ impl DiagItem {
             severity: Severity::Ok,
             category,
             message: msg.into(),
+            hint: None,
         }
     }
     fn warn(category: &'static str, msg: impl Into<String>) -> Self {
@@ -46,6 +63,7 @@ impl DiagItem {
             severity: Severity::Warn,
             category,
             message: msg.into(),
+            hint: None,
         }
     }
     fn error(category: &'static str, msg: impl Into<String>) -> Self {
@@ -53,9 +71,15 @@ impl DiagItem {
             severity: Severity::Error,
             category,
             message: msg.into(),
+            hint: None,
         }
     }
 
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
     fn icon(&self) -> &'static str {
         match self.severity {
             Severity::Ok => "✅",
@@ -69,6 +93,7 @@ impl DiagItem {
             severity: self.severity,
             category: self.category.to_string(),
             message: self.message,
+            hint: self.hint,
         }
     }
 }
@@ -76,21 +101,43 @@ impl DiagItem {
 // ── Public entry points ──────────────────────────────────────────
 
 /// Run diagnostics and return structured results (for API/web dashboard).
+///
+/// Only local checks run here; see [`diagnose_full`] for the network probes.
 pub fn diagnose(config: &Config) -> Vec<DiagResult> {
     let mut items: Vec<DiagItem> = Vec::new();
+    check_local(config, &mut items);
+    items.into_iter().map(DiagItem::into_result).collect()
+}
 
-    check_config_semantics(config, &mut items);
-    check_workspace(config, &mut items);
-    check_daemon_state(config, &mut items);
-    check_environment(&mut items);
-    check_cli_tools(&mut items);
+/// Run every diagnostic: the config file, local checks, and — unless
+/// `offline` — provider reachability, channel credentials and clock skew.
+pub async fn diagnose_full(config: &Config, offline: bool) -> Vec<DiagResult> {
+    let mut items: Vec<DiagItem> = Vec::new();
+
+    check_config_file(config, &mut items).await;
+    check_local(config, &mut items);
+    if !offline {
+        check_provider_reachability(config, &mut items).await;
+        check_channel_credentials(config, &mut items).await;
+        check_clock_skew(&mut items).await;
+    }
 
     items.into_iter().map(DiagItem::into_result).collect()
 }
 
+fn check_local(config: &Config, items: &mut Vec<DiagItem>) {
+    check_config_semantics(config, items);
+    check_workspace(config, items);
+    check_databases(config, items);
+    check_sandbox(config, items);
+    check_daemon_state(config, items);
+    check_environment(items);
+    check_cli_tools(items);
+}
+
 /// Run diagnostics and print human-readable report to stdout.
-pub fn run(config: &Config) -> Result<()> {
-    let results = diagnose(config);
+pub async fn run(config: &Config, offline: bool) -> Result<()> {
+    let results = diagnose_full(config, offline).await;
 
     // Print report
     println!("🩺 ZeroClaw Doctor (enhanced)");
@@ -108,6 +155,9 @@ pub fn run(config: &Config) -> Result<()> {
             Severity::Error => "❌",
         };
         println!("    {} {}", icon, item.message);
+        if let Some(hint) = &item.hint {
+            println!("       → {hint}");
+        }
     }
 
     let errors = results
@@ -426,10 +476,13 @@ fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
             format!("config file: {}", config.config_path.display()),
         ));
     } else {
-        items.push(DiagItem::error(
-            cat,
-            format!("config file not found: {}", config.config_path.display()),
-        ));
+        items.push(
+            DiagItem::error(
+                cat,
+                format!("config file not found: {}", config.config_path.display()),
+            )
+            .hint("run `zeroclaw onboard` to create one"),
+        );
     }
 
     // Provider validity
@@ -653,6 +706,36 @@ fn embedding_provider_validation_error(name: &str) -> Option<String> {
     }
 }
 
+/// Findings of `zeroclaw config validate` for the active config file.
+async fn check_config_file(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "config";
+    if !config.config_path.exists() {
+        return;
+    }
+
+    let report = crate::config::validate::validate_file(
+        &config.config_path,
+        config.workspace_dir.clone(),
+        &crate::config::validate::Options::default(),
+    )
+    .await;
+    if report.findings.is_empty() {
+        items.push(DiagItem::ok(cat, "config file is valid"));
+        return;
+    }
+    for finding in report.findings {
+        let message = match finding.location {
+            Some((line, column)) => format!("line {line}:{column}: {}", finding.message),
+            None => finding.message,
+        };
+        let item = match finding.level {
+            crate::config::validate::Level::Error => DiagItem::error(cat, message),
+            crate::config::validate::Level::Warning => DiagItem::warn(cat, message),
+        };
+        items.push(item.hint("run `zeroclaw config validate` for the full report"));
+    }
+}
+
 // ── Workspace integrity ──────────────────────────────────────────
 
 fn check_workspace(config: &Config, items: &mut Vec<DiagItem>) {
@@ -710,7 +793,7 @@ fn check_workspace(config: &Config, items: &mut Vec<DiagItem>) {
             items.push(DiagItem::warn(
                 cat,
                 format!("low disk space: only {avail_mb} MB available"),
-            ));
+            ).hint("free space on this volume; memory, sessions and logs stop saving when it fills"));
         }
     }
 
@@ -766,6 +849,87 @@ fn workspace_probe_path(workspace_dir: &Path) -> std::path::PathBuf {
     ))
 }
 
+// ── Databases ────────────────────────────────────────────────────
+
+fn check_databases(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "databases";
+    let mut found = false;
+    for name in WORKSPACE_DATABASES {
+        let path = config.workspace_dir.join(name);
+        if !path.is_file() {
+            continue;
+        }
+        found = true;
+        match sqlite_quick_check(&path) {
+            Ok(()) => items.push(DiagItem::ok(cat, format!("{name} passes integrity check"))),
+            Err(problem) => items.push(
+                DiagItem::error(cat, format!("{name} is damaged: {problem}")).hint(format!(
+                    "stop the daemon, back up {} and restore it with `sqlite3 {name} .recover`",
+                    path.display()
+                )),
+            ),
+        }
+    }
+    if !found {
+        items.push(DiagItem::ok(cat, "no databases created yet"));
+    }
+}
+
+/// `PRAGMA quick_check` on a read-only connection; `Err` describes the first problem.
+fn sqlite_quick_check(path: &Path) -> std::result::Result<(), String> {
+    let conn = rusqlite::Connection::open_with_flags(
+        path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| e.to_string())?;
+    let result: String = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if result == "ok" {
+        Ok(())
+    } else {
+        Err(result)
+    }
+}
+
+// ── Sandbox ──────────────────────────────────────────────────────
+
+fn check_sandbox(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "sandbox";
+
+    match crate::security::landlock::LandlockSandbox::probe() {
+        Ok(_) => items.push(DiagItem::ok(cat, "landlock available")),
+        Err(e) => items.push(
+            DiagItem::warn(cat, format!("landlock unavailable: {e}"))
+                .hint("needs Linux 5.13+ and a build with `--features sandbox-landlock`"),
+        ),
+    }
+    match crate::security::seccomp::SeccompSandbox::probe() {
+        Ok(_) => items.push(DiagItem::ok(cat, "seccomp available")),
+        Err(e) => items.push(DiagItem::warn(cat, format!("seccomp unavailable: {e}"))),
+    }
+
+    let active = crate::security::create_sandbox(&config.security, &config.workspace_dir);
+    if active.name() == "none" && config.security.sandbox.enabled != Some(false) {
+        items.push(
+            DiagItem::warn(
+                cat,
+                "no OS sandbox active; shell commands rely on policy checks only",
+            )
+            .hint("set [security.sandbox] backend to an available backend above"),
+        );
+    } else {
+        items.push(DiagItem::ok(
+            cat,
+            format!(
+                "active backend: {} — {}",
+                active.name(),
+                active.description()
+            ),
+        ));
+    }
+}
+
 // ── Daemon state (original logic, preserved) ─────────────────────
 
 fn check_daemon_state(config: &Config, items: &mut Vec<DiagItem>) {
@@ -773,13 +937,16 @@ fn check_daemon_state(config: &Config, items: &mut Vec<DiagItem>) {
     let state_file = crate::daemon::state_file_path(config);
 
     if !state_file.exists() {
-        items.push(DiagItem::error(
-            cat,
-            format!(
-                "state file not found: {} — is the daemon running?",
-                state_file.display()
-            ),
-        ));
+        items.push(
+            DiagItem::error(
+                cat,
+                format!(
+                    "state file not found: {} — is the daemon running?",
+                    state_file.display()
+                ),
+            )
+            .hint("start it with `zeroclaw daemon` or `zeroclaw service start`"),
+        );
         return;
     }
 
@@ -900,6 +1067,140 @@ fn check_daemon_state(config: &Config, items: &mut Vec<DiagItem>) {
     }
 }
 
+// ── Network probes ───────────────────────────────────────────────
+
+async fn check_provider_reachability(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "provider";
+    let name = config.default_provider.as_deref().unwrap_or("openrouter");
+    let provider = match crate::providers::create_provider_with_url(
+        name,
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+    ) {
+        Ok(provider) => provider,
+        Err(e) => {
+            items.push(DiagItem::error(
+                cat,
+                format!(
+                    "cannot create provider \"{name}\": {}",
+                    format_error_chain(&e)
+                ),
+            ));
+            return;
+        }
+    };
+
+    match tokio::time::timeout(
+        std::time::Duration::from_secs(PROBE_TIMEOUT_SECONDS),
+        provider.warmup(),
+    )
+    .await
+    {
+        Ok(Ok(())) => items.push(DiagItem::ok(cat, format!("\"{name}\" is reachable"))),
+        Ok(Err(e)) => {
+            let detail = format_error_chain(&e);
+            let hint = match classify_model_probe_error(&detail) {
+                ModelProbeOutcome::AuthOrAccess => {
+                    "check `api_key` in config.toml or the provider's API key environment variable"
+                }
+                _ => "check network access and `api_url`",
+            };
+            items.push(
+                DiagItem::error(cat, format!("\"{name}\" is not reachable: {detail}")).hint(hint),
+            );
+        }
+        Err(_) => items.push(
+            DiagItem::error(
+                cat,
+                format!("\"{name}\" did not respond within {PROBE_TIMEOUT_SECONDS}s"),
+            )
+            .hint("check network access, proxy settings and `api_url`"),
+        ),
+    }
+}
+
+async fn check_channel_credentials(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "channels";
+    let states = match crate::channels::check_channel_health(config).await {
+        Ok(states) => states,
+        Err(e) => {
+            items.push(DiagItem::error(
+                cat,
+                format!("channel setup failed: {}", format_error_chain(&e)),
+            ));
+            return;
+        }
+    };
+    if states.is_empty() {
+        items.push(DiagItem::ok(cat, "no real-time channels configured"));
+        return;
+    }
+    for (display_name, state) in states {
+        let section = display_name.to_lowercase().replace(' ', "_");
+        match state {
+            crate::channels::ChannelHealthState::Healthy => {
+                items.push(DiagItem::ok(
+                    cat,
+                    format!("{display_name} credentials accepted"),
+                ));
+            }
+            crate::channels::ChannelHealthState::Unhealthy => items.push(
+                DiagItem::error(cat, format!("{display_name} failed its health check")).hint(
+                    format!("verify the token and IDs in [channels_config.{section}]"),
+                ),
+            ),
+            crate::channels::ChannelHealthState::Timeout => items.push(
+                DiagItem::warn(cat, format!("{display_name} health check timed out (>10s)"))
+                    .hint("check network access to the service"),
+            ),
+        }
+    }
+}
+
+async fn check_clock_skew(items: &mut Vec<DiagItem>) {
+    let cat = "clock";
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "doctor.clock",
+        PROBE_TIMEOUT_SECONDS,
+        PROBE_TIMEOUT_SECONDS,
+    );
+    let Ok(response) = client.head(CLOCK_REFERENCE_URL).send().await else {
+        items.push(DiagItem::warn(
+            cat,
+            format!("could not reach {CLOCK_REFERENCE_URL} to compare clocks"),
+        ));
+        return;
+    };
+    let Some(remote) = response
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+    else {
+        items.push(DiagItem::warn(
+            cat,
+            "reference server sent no usable Date header",
+        ));
+        return;
+    };
+    let skew = Utc::now()
+        .signed_duration_since(remote.with_timezone(&Utc))
+        .num_seconds();
+    items.push(clock_skew_item(cat, skew));
+}
+
+fn clock_skew_item(cat: &'static str, skew: i64) -> DiagItem {
+    let direction = if skew >= 0 { "ahead" } else { "behind" };
+    let message = format!("local clock is {}s {direction}", skew.abs());
+    let hint = "enable NTP time sync (e.g. `timedatectl set-ntp true`); \
+                skewed clocks break TLS, OTP codes and signed webhooks";
+    match skew.abs() {
+        s if s <= CLOCK_SKEW_WARN_SECONDS => DiagItem::ok(cat, message),
+        s if s <= CLOCK_SKEW_ERROR_SECONDS => DiagItem::warn(cat, message).hint(hint),
+        _ => DiagItem::error(cat, message).hint(hint),
+    }
+}
+
 // ── Environment checks ───────────────────────────────────────────
 
 fn check_environment(items: &mut Vec<DiagItem>) {
@@ -1033,6 +1334,31 @@ mod tests {
         assert!(invalid_unknown.contains("Unknown provider"));
     }
 
+    #[test]
+    fn clock_skew_thresholds() {
+        assert_eq!(clock_skew_item("clock", 2).severity, Severity::Ok);
+        assert_eq!(clock_skew_item("clock", -90).severity, Severity::Warn);
+        let far = clock_skew_item("clock", 3600);
+        assert_eq!(far.severity, Severity::Error);
+        assert!(far.message.contains("3600s ahead"));
+        assert!(far.hint.is_some());
+    }
+
+    #[test]
+    fn database_check_flags_corrupt_files() {
+        let tmp = TempDir::new().unwrap();
+        let good = tmp.path().join("good.db");
+        rusqlite::Connection::open(&good)
+            .unwrap()
+            .execute_batch("CREATE TABLE t (x INTEGER); INSERT INTO t VALUES (1);")
+            .unwrap();
+        assert!(sqlite_quick_check(&good).is_ok());
+
+        let bad = tmp.path().join("bad.db");
+        std::fs::write(&bad, b"definitely not a sqlite database").unwrap();
+        assert!(sqlite_quick_check(&bad).is_err());
+    }
+
     #[test]
     fn diag_item_icons() {
         assert_eq!(DiagItem::ok("t", "m").icon(), "✅");
@@ -1281,6 +1607,9 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: 0,
             },
         );
         config.agents.insert(
@@ -1295,6 +1624,9 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: 0,
             },
         );
 
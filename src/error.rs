@@ -0,0 +1,255 @@
+//! Typed errors with stable codes, and the messages users see for them.
+//!
+//! Most of the crate returns `anyhow::Error`. Code that knows what went wrong
+//! in a way the user can act on returns (or wraps) an [`Error`]; [`classify`]
+//! finds one anywhere in a chain and otherwise recognises common provider and
+//! transport failures from their HTTP status or text. Channels and the CLI
+//! show [`user_message`] instead of the raw chain.
+
+use serde::Serialize;
+
+/// Failure categories users can act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    ProviderAuth,
+    RateLimited,
+    Network,
+    ToolFailed,
+    Config,
+    SandboxDenied,
+}
+
+impl ErrorKind {
+    /// Stable code shown to users and in logs. Codes are never reused or
+    /// renumbered; new kinds get new codes.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::ProviderAuth => "ZC1001",
+            Self::RateLimited => "ZC1002",
+            Self::Network => "ZC1003",
+            Self::ToolFailed => "ZC2001",
+            Self::Config => "ZC3001",
+            Self::SandboxDenied => "ZC4001",
+        }
+    }
+}
+
+/// An error whose cause is known. `Display` is the underlying message, so
+/// wrapping an existing error in one does not change what logs show.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum Error {
+    /// The provider rejected the credentials, or none are configured.
+    #[error("{message}")]
+    ProviderAuth { provider: String, message: String },
+    /// The provider asked us to slow down (HTTP 429).
+    #[error("{message}")]
+    RateLimited { provider: String, message: String },
+    /// The provider or another remote endpoint could not be reached.
+    #[error("{message}")]
+    Network { message: String },
+    /// A tool failed in a way that ended the turn.
+    #[error("{message}")]
+    ToolFailed { tool: String, message: String },
+    /// `config.toml` is invalid or names something that does not exist.
+    #[error("{message}")]
+    Config { message: String },
+    /// The security policy or OS sandbox refused an action.
+    #[error("{message}")]
+    SandboxDenied { message: String },
+}
+
+impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::ProviderAuth { .. } => ErrorKind::ProviderAuth,
+            Self::RateLimited { .. } => ErrorKind::RateLimited,
+            Self::Network { .. } => ErrorKind::Network,
+            Self::ToolFailed { .. } => ErrorKind::ToolFailed,
+            Self::Config { .. } => ErrorKind::Config,
+            Self::SandboxDenied { .. } => ErrorKind::SandboxDenied,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.kind().code()
+    }
+}
+
+/// What went wrong, as far as it can be told from `err`. `None` for
+/// failures without a user-actionable category.
+pub fn classify(err: &anyhow::Error) -> Option<ErrorKind> {
+    if let Some(typed) = err
+        .chain()
+        .find_map(|source| source.downcast_ref::<Error>())
+    {
+        return Some(typed.kind());
+    }
+    if let Some(http) = err
+        .chain()
+        .find_map(|source| source.downcast_ref::<reqwest::Error>())
+    {
+        match http.status().map(|status| status.as_u16()) {
+            Some(401 | 403) => return Some(ErrorKind::ProviderAuth),
+            Some(429) => return Some(ErrorKind::RateLimited),
+            Some(_) => {}
+            None if http.is_connect() || http.is_timeout() || http.is_request() => {
+                return Some(ErrorKind::Network);
+            }
+            None => {}
+        }
+    }
+    if err.chain().any(|source| source.is::<toml::de::Error>()) {
+        return Some(ErrorKind::Config);
+    }
+    classify_text(&format!("{err:#}"))
+}
+
+/// Recognise failures that only survive as text, e.g. after the reliable
+/// provider folded every attempt into one message.
+fn classify_text(message: &str) -> Option<ErrorKind> {
+    let lower = message.to_lowercase();
+    let has = |hints: &[&str]| hints.iter().any(|hint| lower.contains(hint));
+
+    if has(&[
+        "api key not set",
+        "invalid api key",
+        "invalid_api_key",
+        "incorrect api key",
+        "api key not valid",
+        "authentication_error",
+        "(401 unauthorized)",
+        "(403 forbidden)",
+    ]) {
+        return Some(ErrorKind::ProviderAuth);
+    }
+    if lower.contains("(429 too many requests)")
+        || has(&["rate limit exceeded", "rate_limit_error"])
+    {
+        return Some(ErrorKind::RateLimited);
+    }
+    if has(&[
+        "error sending request",
+        "connection refused",
+        "dns error",
+        "failed to lookup address",
+        "network is unreachable",
+    ]) {
+        return Some(ErrorKind::Network);
+    }
+    None
+}
+
+/// The message a chat user sees for `err`: what happened and what to do
+/// next, tagged with the error code, for known failures; the error itself
+/// otherwise.
+pub fn user_message(err: &anyhow::Error) -> String {
+    let Some(kind) = classify(err) else {
+        return format!("Error: {err}");
+    };
+    let typed = err
+        .chain()
+        .find_map(|source| source.downcast_ref::<Error>());
+    let detail = || crate::providers::sanitize_api_error(&err.to_string());
+    let text = match kind {
+        ErrorKind::ProviderAuth => match typed {
+            Some(Error::ProviderAuth { provider, .. }) => format!(
+                "API key for {provider} is invalid or missing — run `zeroclaw onboard` or update it in config.toml."
+            ),
+            _ => "API key invalid or missing — run `zeroclaw onboard` or update it in config.toml."
+                .to_string(),
+        },
+        ErrorKind::RateLimited => {
+            "The model provider is rate limiting requests. Wait a minute and try again.".to_string()
+        }
+        ErrorKind::Network => {
+            "Could not reach the model provider. Check the network connection (and `api_url`), then try again."
+                .to_string()
+        }
+        ErrorKind::ToolFailed => match typed {
+            Some(Error::ToolFailed { tool, message }) => format!("Tool `{tool}` failed: {message}"),
+            _ => format!("A tool failed: {}", detail()),
+        },
+        ErrorKind::Config => format!(
+            "Configuration problem: {} — fix config.toml, then run `zeroclaw doctor`.",
+            detail()
+        ),
+        ErrorKind::SandboxDenied => format!(
+            "Blocked by the security policy: {} — change [autonomy] if this should be allowed.",
+            detail()
+        ),
+    };
+    format!("{text} [{}]", kind.code())
+}
+
+/// [`user_message`] for a terminal, where the user is also the operator:
+/// known failures keep the underlying error as a details line.
+pub fn terminal_message(err: &anyhow::Error) -> String {
+    if classify(err).is_none() {
+        return format!("Error: {err:#}");
+    }
+    format!(
+        "{}\nDetails: {}",
+        user_message(err),
+        crate::providers::sanitize_api_error(&format!("{err:#}"))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_errors_are_found_anywhere_in_the_chain() {
+        let err = anyhow::Error::from(Error::ProviderAuth {
+            provider: "openai".into(),
+            message: "openai API key not set".into(),
+        })
+        .context("turn failed");
+        assert_eq!(classify(&err), Some(ErrorKind::ProviderAuth));
+        assert_eq!(
+            user_message(&err),
+            "API key for openai is invalid or missing — run `zeroclaw onboard` or update it in config.toml. [ZC1001]"
+        );
+    }
+
+    #[test]
+    fn folded_provider_errors_are_classified_from_text() {
+        let auth = anyhow::anyhow!(
+            "All providers/models failed. Attempts:\nprovider=openai model=gpt-4o attempt 1/3: non_retryable; error=openai API error (401 Unauthorized): bad key"
+        );
+        assert_eq!(classify(&auth), Some(ErrorKind::ProviderAuth));
+        let limited = anyhow::anyhow!("groq API error (429 Too Many Requests): slow down");
+        assert_eq!(classify(&limited), Some(ErrorKind::RateLimited));
+        assert!(user_message(&limited).ends_with("[ZC1002]"));
+    }
+
+    #[test]
+    fn unknown_errors_keep_their_text() {
+        let err = anyhow::anyhow!("Agent exceeded maximum tool iterations (3)");
+        assert_eq!(classify(&err), None);
+        assert_eq!(
+            user_message(&err),
+            "Error: Agent exceeded maximum tool iterations (3)"
+        );
+    }
+
+    #[test]
+    fn codes_are_stable() {
+        let codes: Vec<&str> = [
+            ErrorKind::ProviderAuth,
+            ErrorKind::RateLimited,
+            ErrorKind::Network,
+            ErrorKind::ToolFailed,
+            ErrorKind::Config,
+            ErrorKind::SandboxDenied,
+        ]
+        .into_iter()
+        .map(ErrorKind::code)
+        .collect();
+        assert_eq!(
+            codes,
+            ["ZC1001", "ZC1002", "ZC1003", "ZC2001", "ZC3001", "ZC4001"]
+        );
+    }
+}
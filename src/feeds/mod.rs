@@ -0,0 +1,350 @@
+//! RSS/Atom ingestion: poll configured feeds, run new entries through the
+//! digest prompt, and deliver the result to a channel.
+
+pub mod parser;
+
+use crate::config::{Config, FeedSourceConfig};
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use parser::FeedEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::time::Duration;
+
+/// Remembered entry IDs per feed; older IDs are dropped first.
+const MAX_SEEN_IDS_PER_FEED: usize = 500;
+/// Per-entry summary budget in the digest prompt.
+const MAX_SUMMARY_CHARS: usize = 400;
+/// Model reply meaning "nothing worth delivering".
+const NO_DIGEST_SENTINEL: &str = "NO_DIGEST";
+const FETCH_TIMEOUT_SECS: u64 = 30;
+
+/// Entry IDs already processed, keyed by feed URL.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SeenState {
+    #[serde(default)]
+    feeds: HashMap<String, Vec<String>>,
+}
+
+/// A new entry together with the display name of its feed.
+#[derive(Debug, Clone)]
+pub struct SourcedEntry {
+    pub source: String,
+    pub entry: FeedEntry,
+}
+
+pub fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("feeds").join("seen.json")
+}
+
+async fn load_state(path: &Path) -> SeenState {
+    match tokio::fs::read_to_string(path).await {
+        Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|e| {
+            tracing::warn!("Feeds: ignoring corrupt state file {}: {e}", path.display());
+            SeenState::default()
+        }),
+        Err(_) => SeenState::default(),
+    }
+}
+
+async fn save_state(path: &Path, state: &SeenState) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let data = serde_json::to_vec_pretty(state)?;
+    tokio::fs::write(path, data)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Return entries whose IDs are not in `seen`, recording them.
+///
+/// On the first poll of a feed the current entries are only recorded, so
+/// enabling a feed does not flood the digest with its whole back catalogue.
+fn take_new_entries(
+    seen: &mut Vec<String>,
+    first_poll: bool,
+    entries: Vec<FeedEntry>,
+) -> Vec<FeedEntry> {
+    let mut fresh = Vec::new();
+    for entry in entries {
+        if !seen.contains(&entry.id) {
+            seen.push(entry.id.clone());
+            fresh.push(entry);
+        }
+    }
+    if seen.len() > MAX_SEEN_IDS_PER_FEED {
+        let excess = seen.len() - MAX_SEEN_IDS_PER_FEED;
+        seen.drain(..excess);
+    }
+
+    if first_poll {
+        Vec::new()
+    } else {
+        fresh
+    }
+}
+
+async fn fetch_feed(
+    client: &reqwest::Client,
+    source: &FeedSourceConfig,
+) -> Result<parser::ParsedFeed> {
+    let resp = client
+        .get(&source.url)
+        .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+        .header(
+            "Accept",
+            "application/rss+xml, application/atom+xml, application/xml;q=0.9, */*;q=0.5",
+        )
+        .send()
+        .await?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        anyhow::bail!("HTTP {status}");
+    }
+
+    parser::parse_feed(&resp.text().await?)
+}
+
+/// Poll every source once and return the new entries in source order.
+pub async fn collect_new_entries(config: &Config) -> Result<Vec<SourcedEntry>> {
+    let path = state_path(&config.workspace_dir);
+    let mut state = load_state(&path).await;
+    let client = crate::config::build_runtime_proxy_client("feeds.fetch");
+    let mut collected = Vec::new();
+
+    for source in &config.feeds.sources {
+        let feed = match fetch_feed(&client, source).await {
+            Ok(feed) => feed,
+            Err(e) => {
+                tracing::warn!("Feeds: failed to poll {}: {e:#}", source.url);
+                continue;
+            }
+        };
+
+        let first_poll = !state.feeds.contains_key(&source.url);
+        let seen = state.feeds.entry(source.url.clone()).or_default();
+        let fresh = take_new_entries(seen, first_poll, feed.entries);
+        if first_poll {
+            tracing::info!(
+                "Feeds: seeded {} with {} existing entries",
+                source.url,
+                seen.len()
+            );
+        }
+
+        let name = source
+            .name
+            .clone()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| {
+                if feed.title.is_empty() {
+                    source.url.clone()
+                } else {
+                    feed.title.clone()
+                }
+            });
+        collected.extend(fresh.into_iter().map(|entry| SourcedEntry {
+            source: name.clone(),
+            entry,
+        }));
+    }
+
+    save_state(&path, &state).await?;
+    Ok(collected)
+}
+
+/// Build the model prompt for a batch of new entries.
+pub fn build_digest_prompt(instruction: &str, entries: &[SourcedEntry]) -> String {
+    let mut prompt = format!("[Feed Digest] {}\n\nNew entries:\n", instruction.trim());
+    for (index, item) in entries.iter().enumerate() {
+        prompt.push_str(&format!(
+            "\n{}. [{}] {}\n",
+            index + 1,
+            item.source,
+            item.entry.title
+        ));
+        if !item.entry.link.is_empty() {
+            prompt.push_str(&format!("   {}\n", item.entry.link));
+        }
+        if let Some(published) = &item.entry.published {
+            prompt.push_str(&format!("   Published: {published}\n"));
+        }
+        if !item.entry.summary.is_empty() {
+            prompt.push_str(&format!(
+                "   {}\n",
+                truncate_with_ellipsis(&item.entry.summary, MAX_SUMMARY_CHARS)
+            ));
+        }
+    }
+    prompt
+}
+
+fn is_empty_digest(output: &str) -> bool {
+    let trimmed = output.trim();
+    trimmed.is_empty() || trimmed.eq_ignore_ascii_case(NO_DIGEST_SENTINEL)
+}
+
+fn delivery_target(config: &Config) -> Result<Option<(String, String)>> {
+    let channel = config
+        .feeds
+        .target
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let target = config
+        .feeds
+        .to
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    match (channel, target) {
+        (None, None) => Ok(None),
+        (Some(_), None) => anyhow::bail!("feeds.to is required when feeds.target is set"),
+        (None, Some(_)) => anyhow::bail!("feeds.target is required when feeds.to is set"),
+        (Some(channel), Some(target)) => Ok(Some((channel.to_string(), target.to_string()))),
+    }
+}
+
+/// Run one poll → digest → deliver cycle.
+pub async fn run_once(config: &Config) -> Result<()> {
+    let delivery = delivery_target(config)?;
+    let mut entries = collect_new_entries(config).await?;
+    if entries.is_empty() {
+        tracing::debug!("Feeds: no new entries");
+        return Ok(());
+    }
+
+    let limit = config.feeds.max_entries_per_digest.max(1);
+    if entries.len() > limit {
+        tracing::info!(
+            "Feeds: {} new entries, digesting the first {limit}",
+            entries.len()
+        );
+        entries.truncate(limit);
+    }
+
+    let prompt = build_digest_prompt(&config.feeds.prompt, &entries);
+    let digest = crate::agent::process_message(config.clone(), &prompt).await?;
+    if is_empty_digest(&digest) {
+        tracing::info!("Feeds: model found nothing worth delivering");
+        return Ok(());
+    }
+
+    match delivery {
+        Some((channel, target)) => {
+            crate::cron::scheduler::deliver_announcement(config, &channel, &target, &digest)
+                .await?;
+            tracing::info!("Feeds: delivered digest of {} entries", entries.len());
+        }
+        None => tracing::info!("Feeds digest (no delivery target configured):\n{digest}"),
+    }
+    Ok(())
+}
+
+/// Daemon worker: poll on `feeds.poll_interval_minutes`.
+pub async fn run(config: Config) -> Result<()> {
+    delivery_target(&config)?;
+
+    let interval_mins = config.feeds.poll_interval_minutes.max(5);
+    let mut interval = tokio::time::interval(Duration::from_secs(u64::from(interval_mins) * 60));
+
+    loop {
+        interval.tick().await;
+        match run_once(&config).await {
+            Ok(()) => crate::health::mark_component_ok("feeds"),
+            Err(e) => {
+                crate::health::mark_component_error("feeds", e.to_string());
+                tracing::warn!("Feeds poll failed: {e:#}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str) -> FeedEntry {
+        FeedEntry {
+            id: id.into(),
+            title: format!("Title {id}"),
+            link: format!("https://example.com/{id}"),
+            summary: "Summary".into(),
+            published: None,
+        }
+    }
+
+    #[test]
+    fn first_poll_records_without_yielding() {
+        let mut seen = Vec::new();
+        assert!(take_new_entries(&mut seen, true, vec![entry("a")]).is_empty());
+        assert_eq!(seen, vec!["a"]);
+    }
+
+    #[test]
+    fn take_new_entries_skips_seen_and_caps_history() {
+        let mut seen = vec!["a".to_string()];
+        let fresh = take_new_entries(&mut seen, false, vec![entry("a"), entry("b")]);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].id, "b");
+        assert_eq!(seen, vec!["a", "b"]);
+
+        let mut seen: Vec<String> = (0..MAX_SEEN_IDS_PER_FEED).map(|i| i.to_string()).collect();
+        take_new_entries(&mut seen, false, vec![entry("new")]);
+        assert_eq!(seen.len(), MAX_SEEN_IDS_PER_FEED);
+        assert_eq!(seen.first().map(String::as_str), Some("1"));
+        assert_eq!(seen.last().map(String::as_str), Some("new"));
+    }
+
+    #[test]
+    fn digest_prompt_lists_entries() {
+        let entries = vec![SourcedEntry {
+            source: "Example".into(),
+            entry: entry("a"),
+        }];
+        let prompt = build_digest_prompt("Summarize.", &entries);
+        assert!(prompt.starts_with("[Feed Digest] Summarize."));
+        assert!(prompt.contains("1. [Example] Title a"));
+        assert!(prompt.contains("https://example.com/a"));
+    }
+
+    #[test]
+    fn no_digest_sentinel_is_detected() {
+        assert!(is_empty_digest("  no_digest \n"));
+        assert!(is_empty_digest(""));
+        assert!(!is_empty_digest("Top stories: ..."));
+    }
+
+    #[test]
+    fn delivery_target_requires_both_fields() {
+        let mut config = Config::default();
+        assert!(delivery_target(&config).unwrap().is_none());
+
+        config.feeds.target = Some("telegram".into());
+        assert!(delivery_target(&config).is_err());
+
+        config.feeds.to = Some("42".into());
+        assert_eq!(
+            delivery_target(&config).unwrap(),
+            Some(("telegram".into(), "42".into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn state_roundtrips_through_workspace() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = state_path(tmp.path());
+        let mut state = SeenState::default();
+        state
+            .feeds
+            .insert("https://example.com/feed".into(), vec!["a".into()]);
+        save_state(&path, &state).await.unwrap();
+
+        let loaded = load_state(&path).await;
+        assert_eq!(loaded.feeds["https://example.com/feed"], vec!["a"]);
+    }
+}
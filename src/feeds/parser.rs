@@ -0,0 +1,326 @@
+//! Minimal, tolerant RSS 2.0 / Atom parser.
+//!
+//! Only the handful of elements needed for digests are extracted; anything
+//! else in the document is ignored. Malformed markup degrades to missing
+//! fields rather than errors.
+
+use anyhow::{bail, Result};
+
+/// A single feed item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEntry {
+    /// Stable identifier (`guid` / `id`, falling back to link, then title).
+    pub id: String,
+    pub title: String,
+    pub link: String,
+    /// Plain-text summary with markup stripped.
+    pub summary: String,
+    /// Publication date exactly as written in the feed.
+    pub published: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedFeed {
+    pub title: String,
+    pub entries: Vec<FeedEntry>,
+}
+
+/// Parse an RSS or Atom document.
+pub fn parse_feed(xml: &str) -> Result<ParsedFeed> {
+    let (item_tag, is_atom) = if find_open_tag(xml, "feed", 0).is_some() {
+        ("entry", true)
+    } else if find_open_tag(xml, "rss", 0).is_some() || find_open_tag(xml, "rdf:RDF", 0).is_some() {
+        ("item", false)
+    } else {
+        bail!("document is neither RSS nor Atom");
+    };
+
+    let header_end = find_open_tag(xml, item_tag, 0).map_or(xml.len(), |(start, _)| start);
+    let title = first_text(&xml[..header_end], &["title"]).unwrap_or_default();
+
+    let entries = elements(xml, item_tag)
+        .into_iter()
+        .map(|(_, body)| parse_entry(body, is_atom))
+        .filter(|entry| !entry.id.is_empty())
+        .collect();
+
+    Ok(ParsedFeed { title, entries })
+}
+
+fn parse_entry(body: &str, is_atom: bool) -> FeedEntry {
+    let title = first_text(body, &["title"]).unwrap_or_default();
+    let link = if is_atom {
+        atom_link(body)
+    } else {
+        first_text(body, &["link"])
+    }
+    .unwrap_or_default();
+    let summary = first_text(
+        body,
+        &["description", "summary", "content:encoded", "content"],
+    )
+    .map(|html| html_to_text(&html))
+    .unwrap_or_default();
+    let published = first_text(body, &["pubDate", "published", "updated", "dc:date"]);
+    let id = first_text(body, &["guid", "id"])
+        .filter(|id| !id.is_empty())
+        .or_else(|| (!link.is_empty()).then(|| link.clone()))
+        .unwrap_or_else(|| title.clone());
+
+    FeedEntry {
+        id,
+        title,
+        link,
+        summary,
+        published,
+    }
+}
+
+/// Atom entries carry links as attributes; prefer `rel="alternate"` (or no rel).
+fn atom_link(body: &str) -> Option<String> {
+    let links = elements(body, "link");
+    links
+        .iter()
+        .find(|(attrs, _)| matches!(attr(attrs, "rel").as_deref(), None | Some("alternate")))
+        .or_else(|| links.first())
+        .and_then(|(attrs, _)| attr(attrs, "href"))
+}
+
+/// Locate `<tag` (followed by whitespace, `>` or `/`) at or after `from`.
+/// Returns the start of the tag and the index just past its closing `>`.
+fn find_open_tag(xml: &str, tag: &str, from: usize) -> Option<(usize, usize)> {
+    let needle = format!("<{tag}");
+    let mut cursor = from;
+    while let Some(offset) = xml[cursor..].find(&needle) {
+        let start = cursor + offset;
+        let after = start + needle.len();
+        match xml[after..].chars().next() {
+            Some(c) if c.is_whitespace() || c == '>' || c == '/' => {
+                let end = xml[after..].find('>')? + after + 1;
+                return Some((start, end));
+            }
+            _ => cursor = after,
+        }
+    }
+    None
+}
+
+/// All `<tag ...>inner</tag>` (or self-closing `<tag .../>`) occurrences as
+/// `(attributes, inner)` pairs. Nested elements of the same name are not supported.
+fn elements<'a>(xml: &'a str, tag: &str) -> Vec<(&'a str, &'a str)> {
+    let close = format!("</{tag}>");
+    let mut out = Vec::new();
+    let mut cursor = 0;
+
+    while let Some((start, open_end)) = find_open_tag(xml, tag, cursor) {
+        let open = &xml[start + tag.len() + 1..open_end - 1];
+        if let Some(attrs) = open.strip_suffix('/') {
+            out.push((attrs, ""));
+            cursor = open_end;
+            continue;
+        }
+        let Some(close_offset) = xml[open_end..].find(&close) else {
+            break;
+        };
+        out.push((open, &xml[open_end..open_end + close_offset]));
+        cursor = open_end + close_offset + close.len();
+    }
+
+    out
+}
+
+fn first_text(xml: &str, tags: &[&str]) -> Option<String> {
+    tags.iter().find_map(|tag| {
+        elements(xml, tag)
+            .into_iter()
+            .next()
+            .map(|(_, inner)| decode_text(inner))
+            .filter(|text| !text.is_empty())
+    })
+}
+
+fn attr(attrs: &str, name: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let needle = format!("{name}={quote}");
+        let mut cursor = 0;
+        while let Some(offset) = attrs[cursor..].find(&needle) {
+            let start = cursor + offset;
+            let preceded_by_space = attrs[..start]
+                .chars()
+                .next_back()
+                .is_none_or(char::is_whitespace);
+            let value_start = start + needle.len();
+            if preceded_by_space {
+                let value_end = attrs[value_start..].find(quote)? + value_start;
+                return Some(decode_entities(&attrs[value_start..value_end]));
+            }
+            cursor = value_start;
+        }
+    }
+    None
+}
+
+/// Unwrap CDATA sections and decode entities.
+fn decode_text(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find("<![CDATA[") {
+        out.push_str(&decode_entities(&rest[..start]));
+        let body = &rest[start + 9..];
+        match body.find("]]>") {
+            Some(end) => {
+                out.push_str(&body[..end]);
+                rest = &body[end + 3..];
+            }
+            None => {
+                out.push_str(body);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(&decode_entities(rest));
+    out.trim().to_string()
+}
+
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let Some(semi) = after.find(';').filter(|&i| i <= 10) else {
+            out.push('&');
+            rest = &after[1..];
+            continue;
+        };
+        let entity = &after[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &after[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &after[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn html_to_text(html: &str) -> String {
+    nanohtml2text::html2text(html)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+  <channel>
+    <title>Example &amp; Co</title>
+    <link>https://example.com</link>
+    <item>
+      <title>First post</title>
+      <link>https://example.com/1</link>
+      <guid isPermaLink="false">post-1</guid>
+      <pubDate>Mon, 06 Jan 2025 10:00:00 GMT</pubDate>
+      <description><![CDATA[<p>Hello   world</p>]]></description>
+    </item>
+    <item>
+      <title>Second &lt;post&gt;</title>
+      <link>https://example.com/2</link>
+      <description>Plain &#8212; text</description>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title type="text">Atom Blog</title>
+  <link href="https://blog.example.org/" />
+  <entry>
+    <title>Entry one</title>
+    <link rel="edit" href="https://blog.example.org/edit/1"/>
+    <link rel="alternate" href="https://blog.example.org/1"/>
+    <id>tag:blog.example.org,2025:1</id>
+    <updated>2025-01-06T10:00:00Z</updated>
+    <summary type="html">&lt;p&gt;Short&lt;/p&gt;</summary>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn parses_rss_items() {
+        let feed = parse_feed(RSS).unwrap();
+        assert_eq!(feed.title, "Example & Co");
+        assert_eq!(feed.entries.len(), 2);
+
+        let first = &feed.entries[0];
+        assert_eq!(first.id, "post-1");
+        assert_eq!(first.title, "First post");
+        assert_eq!(first.link, "https://example.com/1");
+        assert_eq!(first.summary, "Hello world");
+        assert_eq!(
+            first.published.as_deref(),
+            Some("Mon, 06 Jan 2025 10:00:00 GMT")
+        );
+
+        let second = &feed.entries[1];
+        assert_eq!(second.id, "https://example.com/2");
+        assert_eq!(second.title, "Second <post>");
+        assert_eq!(second.summary, "Plain \u{2014} text");
+    }
+
+    #[test]
+    fn parses_atom_entries() {
+        let feed = parse_feed(ATOM).unwrap();
+        assert_eq!(feed.title, "Atom Blog");
+        assert_eq!(feed.entries.len(), 1);
+
+        let entry = &feed.entries[0];
+        assert_eq!(entry.id, "tag:blog.example.org,2025:1");
+        assert_eq!(entry.link, "https://blog.example.org/1");
+        assert_eq!(entry.summary, "Short");
+        assert_eq!(entry.published.as_deref(), Some("2025-01-06T10:00:00Z"));
+    }
+
+    #[test]
+    fn rejects_non_feed_documents() {
+        assert!(parse_feed("<html><body>nope</body></html>").is_err());
+    }
+
+    #[test]
+    fn open_tag_matching_requires_boundary() {
+        let xml = "<itemized>x</itemized><item>y</item>";
+        let found = elements(xml, "item");
+        assert_eq!(found, vec![("", "y")]);
+    }
+
+    #[test]
+    fn decode_entities_leaves_unknown_sequences() {
+        assert_eq!(decode_entities("a &bogus; b & c"), "a &bogus; b & c");
+        assert_eq!(decode_entities("&#x41;&#66;"), "AB");
+    }
+}
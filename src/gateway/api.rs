@@ -23,7 +23,7 @@ fn extract_bearer_token(headers: &HeaderMap) -> Option<&str> {
 }
 
 /// Verify bearer token against PairingGuard. Returns error response if unauthorized.
-fn require_auth(
+pub(super) fn require_auth(
     state: &AppState,
     headers: &HeaderMap,
 ) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
@@ -52,6 +52,16 @@ pub struct MemoryQuery {
     pub category: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct ConversationSearchQuery {
+    pub q: String,
+    pub channel: Option<String>,
+    pub role: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub limit: Option<usize>,
+}
+
 #[derive(Deserialize)]
 pub struct MemoryStoreBody {
     pub key: String,
@@ -59,6 +69,23 @@ pub struct MemoryStoreBody {
     pub category: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct MessageBody {
+    pub message: String,
+    /// Session ID or name to continue; a new `api:<uuid>` session otherwise.
+    pub session: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct LimitQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct UsageQuery {
+    pub days: Option<u32>,
+}
+
 #[derive(Deserialize)]
 pub struct CronAddBody {
     pub name: Option<String>,
@@ -297,6 +324,148 @@ pub async fn handle_api_cron_delete(
     }
 }
 
+/// POST /api/cron/:id/pause — stop a cron job from running
+pub async fn handle_api_cron_pause(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    set_cron_paused(&state, &headers, &id, true)
+}
+
+/// POST /api/cron/:id/resume — let a paused cron job run again
+pub async fn handle_api_cron_resume(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    set_cron_paused(&state, &headers, &id, false)
+}
+
+fn set_cron_paused(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: &str,
+    paused: bool,
+) -> axum::response::Response {
+    if let Err(e) = require_auth(state, headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let result = crate::cron::list_jobs(&config).and_then(|jobs| {
+        if !jobs.iter().any(|job| job.id == id) {
+            return Ok(None);
+        }
+        let job = if paused {
+            crate::cron::pause_job(&config, id)?
+        } else {
+            crate::cron::resume_job(&config, id)?
+        };
+        Ok(Some(job))
+    });
+    match result {
+        Ok(Some(job)) => Json(serde_json::json!({"status": "ok", "job": job})).into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No cron job '{id}'")})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to update cron job: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/schedules — declared `[schedule.<name>]` entries with their runs
+pub async fn handle_api_schedules(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if let Err(e) = crate::cron::sync_declared_jobs(&config) {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to load schedules: {e}")})),
+        )
+            .into_response();
+    }
+    let mut names: Vec<&String> = config.schedule.keys().collect();
+    names.sort();
+    let schedules: Vec<serde_json::Value> = names
+        .into_iter()
+        .map(|name| {
+            let entry = &config.schedule[name];
+            let job = crate::cron::get_job(
+                &config,
+                &format!("{}{name}", crate::cron::DECLARED_JOB_PREFIX),
+            )
+            .ok();
+            serde_json::json!({
+                "name": name,
+                "cron": entry.cron,
+                "prompt": entry.prompt,
+                "channel": entry.channel,
+                "to": entry.to,
+                "pipeline": entry.pipeline.iter().map(|step| &step.tool).collect::<Vec<_>>(),
+                "enabled": job.as_ref().is_some_and(|job| job.enabled),
+                "next_run": job.as_ref().map(|job| job.next_run.to_rfc3339()),
+                "last_run": job.as_ref().and_then(|job| job.last_run.map(|t| t.to_rfc3339())),
+                "last_status": job.as_ref().and_then(|job| job.last_status.clone()),
+            })
+        })
+        .collect();
+
+    Json(serde_json::json!({"schedules": schedules})).into_response()
+}
+
+/// POST /api/schedules/:name/run — run a schedule entry now, including delivery
+pub async fn handle_api_schedule_run(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if !config.schedule.contains_key(&name) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No [schedule.{name}] entry in config")})),
+        )
+            .into_response();
+    }
+    let job = match crate::cron::sync_declared_jobs(&config).and_then(|()| {
+        crate::cron::get_job(
+            &config,
+            &format!("{}{name}", crate::cron::DECLARED_JOB_PREFIX),
+        )
+    }) {
+        Ok(job) => job,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Failed to load schedule: {e}")})),
+            )
+                .into_response();
+        }
+    };
+    let (success, output) = crate::security::roles::run_with(
+        config.gateway.role,
+        crate::cron::scheduler::run_job_now(&config, &job),
+    )
+    .await;
+    Json(serde_json::json!({"success": success, "output": output})).into_response()
+}
+
 /// GET /api/integrations — list all integrations with status
 pub async fn handle_api_integrations(
     State(state): State<AppState>,
@@ -458,6 +627,62 @@ pub async fn handle_api_memory_delete(
     }
 }
 
+/// GET /api/conversations/search — full-text search over stored messages
+pub async fn handle_api_conversation_search(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<ConversationSearchQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let bound = |value: Option<&str>, end: bool| {
+        value
+            .map(|v| crate::conversations::cli::parse_date_bound(v, end))
+            .transpose()
+    };
+    let (since, until) = match (
+        bound(params.since.as_deref(), false),
+        bound(params.until.as_deref(), true),
+    ) {
+        (Ok(since), Ok(until)) => (since, until),
+        (Err(e), _) | (_, Err(e)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+    let filter = crate::conversations::SearchFilter {
+        channel: params.channel,
+        role: params.role,
+        since,
+        until,
+        limit: params.limit.unwrap_or(20).clamp(1, 200),
+    };
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        crate::conversations::ConversationStore::open(&workspace_dir)?.search(&params.q, &filter)
+    })
+    .await;
+    match result {
+        Ok(Ok(hits)) => Json(serde_json::json!({"results": hits})).into_response(),
+        Ok(Err(e)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Conversation search failed: {e}")})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Conversation search failed: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
 /// GET /api/cost — cost summary
 pub async fn handle_api_cost(
     State(state): State<AppState>,
@@ -491,6 +716,176 @@ pub async fn handle_api_cost(
     }
 }
 
+/// POST /api/messages — run one agent turn and return the reply
+pub async fn handle_api_message_send(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<MessageBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    if body.message.trim().is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "message must not be empty"})),
+        )
+            .into_response();
+    }
+
+    let config = state.config.lock().clone();
+    // An unknown session value starts a session with that ID.
+    let session = body
+        .session
+        .unwrap_or_else(|| format!("api:{}", uuid::Uuid::new_v4()));
+    let temperature = config.default_temperature;
+    // The turn runs as `[gateway] role`; calls that need approval are denied.
+    let result = crate::security::roles::run_with(
+        config.gateway.role,
+        Box::pin(crate::agent::run(
+            config.clone(),
+            Some(body.message),
+            None,
+            None,
+            temperature,
+            vec![],
+            false,
+            Some(session.clone()),
+        )),
+    )
+    .await;
+    match result {
+        Ok(reply) => {
+            // Report the canonical ID when a session name was given.
+            let session = crate::conversations::ConversationStore::open(&config.workspace_dir)
+                .ok()
+                .and_then(|store| store.resolve_session(&session).ok().flatten())
+                .map_or(session, |found| found.id);
+            Json(serde_json::json!({"reply": reply, "session": session})).into_response()
+        }
+        Err(e) => {
+            let status = if crate::cost::budget::is_budget_exceeded(&e) {
+                StatusCode::TOO_MANY_REQUESTS
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            };
+            (
+                status,
+                Json(serde_json::json!({
+                    "error": crate::providers::sanitize_api_error(&format!("{e:#}"))
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// GET /api/sessions — stored conversation sessions, most recent first
+pub async fn handle_api_sessions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<LimitQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let limit = params.limit.unwrap_or(50).clamp(1, 500);
+    match crate::conversations::ConversationStore::open(&workspace_dir)
+        .and_then(|store| store.list_sessions(limit))
+    {
+        Ok(sessions) => Json(serde_json::json!({"sessions": sessions})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to list sessions: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/sessions/:id — a session (by ID or name) and its recent messages
+pub async fn handle_api_session(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(params): Query<LimitQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let limit = params.limit.unwrap_or(100).clamp(1, 1000);
+    let result = crate::conversations::ConversationStore::open(&workspace_dir).and_then(|store| {
+        let Some(session) = store.resolve_session(&id)? else {
+            return Ok(None);
+        };
+        let messages = store.recent_messages(&session.id, limit)?;
+        Ok(Some((session, messages)))
+    });
+    match result {
+        Ok(Some((session, messages))) => {
+            Json(serde_json::json!({"session": session, "messages": messages})).into_response()
+        }
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No session '{id}'")})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to load session: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/usage — spend and token totals, plus daily spend for `days` days
+pub async fn handle_api_usage(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<UsageQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let Some(ref tracker) = state.cost_tracker else {
+        return Json(serde_json::json!({
+            "enabled": false,
+            "summary": serde_json::Value::Null,
+            "daily": [],
+        }))
+        .into_response();
+    };
+    let days = params.days.unwrap_or(7).clamp(1, 90);
+    let today = chrono::Utc::now().date_naive();
+    let daily: Vec<serde_json::Value> = (0..days)
+        .rev()
+        .filter_map(|offset| today.checked_sub_days(chrono::Days::new(u64::from(offset))))
+        .map(|date| {
+            serde_json::json!({
+                "date": date.to_string(),
+                "cost_usd": tracker.get_daily_cost(date).unwrap_or(0.0),
+            })
+        })
+        .collect();
+    match tracker.get_summary() {
+        Ok(summary) => Json(serde_json::json!({
+            "enabled": true,
+            "summary": summary,
+            "daily": daily,
+        }))
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Usage summary failed: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
 /// GET /api/cli-tools — discovered CLI tools
 pub async fn handle_api_cli_tools(
     State(state): State<AppState>,
@@ -702,6 +1097,8 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
     mask_optional_secret(&mut masked.browser.computer_use.api_key);
     mask_optional_secret(&mut masked.web_search.brave_api_key);
     mask_optional_secret(&mut masked.storage.provider.config.db_url);
+    mask_optional_secret(&mut masked.image_generation.api_key);
+    mask_optional_secret(&mut masked.calendar.password);
     mask_optional_secret(&mut masked.memory.qdrant.api_key);
     if let Some(cloudflare) = masked.tunnel.cloudflare.as_mut() {
         mask_required_secret(&mut cloudflare.token);
@@ -713,6 +1110,21 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
     for agent in masked.agents.values_mut() {
         mask_optional_secret(&mut agent.api_key);
     }
+    for connection in masked.sql.connections.values_mut() {
+        mask_optional_secret(&mut connection.url);
+    }
+    for secret in masked.http_request.secrets.values_mut() {
+        mask_required_secret(&mut secret.value);
+    }
+    for server in masked.mcp.servers.values_mut() {
+        server.env.values_mut().for_each(mask_required_secret);
+        server.headers.values_mut().for_each(mask_required_secret);
+    }
+    for tool in &mut masked.custom_tools {
+        if let Some(http) = tool.http.as_mut() {
+            http.headers.values_mut().for_each(mask_required_secret);
+        }
+    }
     for route in &mut masked.model_routes {
         mask_optional_secret(&mut route.api_key);
     }
@@ -779,6 +1191,16 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
     if let Some(nostr) = masked.channels_config.nostr.as_mut() {
         mask_required_secret(&mut nostr.private_key);
     }
+    if let Some(mastodon) = masked.channels_config.mastodon.as_mut() {
+        mask_required_secret(&mut mastodon.access_token);
+    }
+    if let Some(github) = masked.channels_config.github.as_mut() {
+        mask_optional_secret(&mut github.access_token);
+        mask_optional_secret(&mut github.webhook_secret);
+    }
+    if let Some(xmpp) = masked.channels_config.xmpp.as_mut() {
+        mask_required_secret(&mut xmpp.password);
+    }
     if let Some(clawdtalk) = masked.channels_config.clawdtalk.as_mut() {
         mask_required_secret(&mut clawdtalk.api_key);
         mask_optional_secret(&mut clawdtalk.webhook_secret);
@@ -815,6 +1237,11 @@ fn restore_masked_sensitive_fields(
         &mut incoming.storage.provider.config.db_url,
         &current.storage.provider.config.db_url,
     );
+    restore_optional_secret(
+        &mut incoming.image_generation.api_key,
+        &current.image_generation.api_key,
+    );
+    restore_optional_secret(&mut incoming.calendar.password, &current.calendar.password);
     restore_optional_secret(
         &mut incoming.memory.qdrant.api_key,
         &current.memory.qdrant.api_key,
@@ -837,6 +1264,44 @@ fn restore_masked_sensitive_fields(
             restore_optional_secret(&mut agent.api_key, &current_agent.api_key);
         }
     }
+    for (name, connection) in &mut incoming.sql.connections {
+        if let Some(current_connection) = current.sql.connections.get(name) {
+            restore_optional_secret(&mut connection.url, &current_connection.url);
+        }
+    }
+    for (name, secret) in &mut incoming.http_request.secrets {
+        if let Some(current_secret) = current.http_request.secrets.get(name) {
+            restore_required_secret(&mut secret.value, &current_secret.value);
+        }
+    }
+    for (name, server) in &mut incoming.mcp.servers {
+        if let Some(current_server) = current.mcp.servers.get(name) {
+            for (key, value) in &mut server.env {
+                if let Some(current_value) = current_server.env.get(key) {
+                    restore_required_secret(value, current_value);
+                }
+            }
+            for (key, value) in &mut server.headers {
+                if let Some(current_value) = current_server.headers.get(key) {
+                    restore_required_secret(value, current_value);
+                }
+            }
+        }
+    }
+    for tool in &mut incoming.custom_tools {
+        let current_http = current
+            .custom_tools
+            .iter()
+            .find(|current_tool| current_tool.name == tool.name)
+            .and_then(|current_tool| current_tool.http.as_ref());
+        if let (Some(http), Some(current_http)) = (tool.http.as_mut(), current_http) {
+            for (key, value) in &mut http.headers {
+                if let Some(current_value) = current_http.headers.get(key) {
+                    restore_required_secret(value, current_value);
+                }
+            }
+        }
+    }
     restore_model_route_api_keys(&mut incoming.model_routes, &current.model_routes);
     restore_embedding_route_api_keys(&mut incoming.embedding_routes, &current.embedding_routes);
 
@@ -959,6 +1424,25 @@ fn restore_masked_sensitive_fields(
     ) {
         restore_required_secret(&mut incoming_ch.private_key, &current_ch.private_key);
     }
+    if let (Some(incoming_ch), Some(current_ch)) = (
+        incoming.channels_config.mastodon.as_mut(),
+        current.channels_config.mastodon.as_ref(),
+    ) {
+        restore_required_secret(&mut incoming_ch.access_token, &current_ch.access_token);
+    }
+    if let (Some(incoming_ch), Some(current_ch)) = (
+        incoming.channels_config.github.as_mut(),
+        current.channels_config.github.as_ref(),
+    ) {
+        restore_optional_secret(&mut incoming_ch.access_token, &current_ch.access_token);
+        restore_optional_secret(&mut incoming_ch.webhook_secret, &current_ch.webhook_secret);
+    }
+    if let (Some(incoming_ch), Some(current_ch)) = (
+        incoming.channels_config.xmpp.as_mut(),
+        current.channels_config.xmpp.as_ref(),
+    ) {
+        restore_required_secret(&mut incoming_ch.password, &current_ch.password);
+    }
     if let (Some(incoming_ch), Some(current_ch)) = (
         incoming.channels_config.clawdtalk.as_mut(),
         current.channels_config.clawdtalk.as_ref(),
@@ -1393,4 +1877,381 @@ mod tests {
             .iter()
             .all(|route| route.api_key.as_deref() != Some(MASKED_SECRET)));
     }
+
+    struct StubProvider;
+
+    #[async_trait::async_trait]
+    impl crate::providers::Provider for StubProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok("ok".into())
+        }
+    }
+
+    const TOKEN: &str = "test-token";
+
+    fn test_state(workspace: &std::path::Path) -> AppState {
+        let mut config = crate::config::Config::default();
+        config.workspace_dir = workspace.to_path_buf();
+        config.config_path = workspace.join("config.toml");
+        let cost_tracker =
+            crate::cost::CostTracker::new(config.cost.clone(), workspace).expect("cost tracker");
+        AppState {
+            config: std::sync::Arc::new(parking_lot::Mutex::new(config)),
+            provider: std::sync::Arc::new(StubProvider),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: std::sync::Arc::new(crate::memory::NoneMemory::new()),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: std::sync::Arc::new(crate::security::pairing::PairingGuard::new(
+                true,
+                &[TOKEN.to_string()],
+            )),
+            trust_forwarded_headers: false,
+            rate_limiter: std::sync::Arc::new(super::super::GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: std::sync::Arc::new(super::super::IdempotencyStore::new(
+                std::time::Duration::from_secs(300),
+                1000,
+            )),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            github: None,
+            github_webhook_secret: None,
+            observer: std::sync::Arc::new(crate::observability::NoopObserver),
+            tools_registry: std::sync::Arc::new(Vec::new()),
+            cost_tracker: Some(std::sync::Arc::new(cost_tracker)),
+            event_tx: tokio::sync::broadcast::channel(16).0,
+        }
+    }
+
+    fn authorized() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Bearer {TOKEN}").parse().unwrap(),
+        );
+        headers
+    }
+
+    async fn json_body(response: axum::response::Response) -> serde_json::Value {
+        use http_body_util::BodyExt;
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn store_with_sessions(workspace: &std::path::Path) -> String {
+        let store = crate::conversations::ConversationStore::open(workspace).unwrap();
+        for session in ["s1", "s2", "s3"] {
+            store.ensure_session(session, "api", "tester").unwrap();
+            store.append_message(session, "user", "first").unwrap();
+            store
+                .append_message(session, "assistant", "second")
+                .unwrap();
+        }
+        "s3".into()
+    }
+
+    #[tokio::test]
+    async fn api_handlers_require_a_bearer_token() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = test_state(tmp.path());
+        let mut wrong = HeaderMap::new();
+        wrong.insert(header::AUTHORIZATION, "Bearer nope".parse().unwrap());
+
+        for headers in [HeaderMap::new(), wrong] {
+            let responses = [
+                handle_api_message_send(
+                    State(state.clone()),
+                    headers.clone(),
+                    Json(MessageBody {
+                        message: "hi".into(),
+                        session: None,
+                    }),
+                )
+                .await
+                .into_response(),
+                handle_api_sessions(
+                    State(state.clone()),
+                    headers.clone(),
+                    Query(LimitQuery { limit: None }),
+                )
+                .await
+                .into_response(),
+                handle_api_session(
+                    State(state.clone()),
+                    headers.clone(),
+                    Path("s1".into()),
+                    Query(LimitQuery { limit: None }),
+                )
+                .await
+                .into_response(),
+                handle_api_schedules(State(state.clone()), headers.clone())
+                    .await
+                    .into_response(),
+                handle_api_schedule_run(State(state.clone()), headers.clone(), Path("x".into()))
+                    .await
+                    .into_response(),
+                handle_api_usage(
+                    State(state.clone()),
+                    headers.clone(),
+                    Query(UsageQuery { days: None }),
+                )
+                .await
+                .into_response(),
+                handle_api_cron_pause(State(state.clone()), headers.clone(), Path("x".into()))
+                    .await
+                    .into_response(),
+                handle_api_cron_resume(State(state.clone()), headers.clone(), Path("x".into()))
+                    .await
+                    .into_response(),
+                super::super::sse::handle_sse_session_events(
+                    State(state.clone()),
+                    headers.clone(),
+                    Path("s1".into()),
+                )
+                .await
+                .into_response(),
+            ];
+            for response in responses {
+                assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn message_send_rejects_an_empty_message() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let response = handle_api_message_send(
+            State(test_state(tmp.path())),
+            authorized(),
+            Json(MessageBody {
+                message: "  ".into(),
+                session: None,
+            }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn session_handlers_clamp_limit_and_report_unknown_sessions() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = test_state(tmp.path());
+        let session = store_with_sessions(tmp.path());
+
+        let response = handle_api_sessions(
+            State(state.clone()),
+            authorized(),
+            Query(LimitQuery { limit: None }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            json_body(response).await["sessions"]
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+
+        let response = handle_api_sessions(
+            State(state.clone()),
+            authorized(),
+            Query(LimitQuery { limit: Some(0) }),
+        )
+        .await
+        .into_response();
+        assert_eq!(
+            json_body(response).await["sessions"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let response = handle_api_session(
+            State(state.clone()),
+            authorized(),
+            Path(session.clone()),
+            Query(LimitQuery { limit: Some(0) }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json_body(response).await;
+        assert_eq!(body["session"]["id"], "s3");
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["content"], "second");
+
+        let response = handle_api_session(
+            State(state.clone()),
+            authorized(),
+            Path(session),
+            Query(LimitQuery {
+                limit: Some(usize::MAX),
+            }),
+        )
+        .await
+        .into_response();
+        assert_eq!(
+            json_body(response).await["messages"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let response = handle_api_session(
+            State(state),
+            authorized(),
+            Path("missing".into()),
+            Query(LimitQuery { limit: None }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn usage_clamps_days() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = test_state(tmp.path());
+
+        for (days, expected) in [(None, 7), (Some(0), 1), (Some(30), 30), (Some(1000), 90)] {
+            let response = handle_api_usage(
+                State(state.clone()),
+                authorized(),
+                Query(UsageQuery { days }),
+            )
+            .await
+            .into_response();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = json_body(response).await;
+            assert_eq!(body["enabled"], true);
+            assert_eq!(
+                body["daily"].as_array().unwrap().len(),
+                expected,
+                "{days:?}"
+            );
+        }
+
+        let mut untracked = state;
+        untracked.cost_tracker = None;
+        let response = handle_api_usage(
+            State(untracked),
+            authorized(),
+            Query(UsageQuery { days: None }),
+        )
+        .await
+        .into_response();
+        assert_eq!(json_body(response).await["enabled"], false);
+    }
+
+    #[tokio::test]
+    async fn schedule_handlers_report_unknown_entries() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = test_state(tmp.path());
+
+        let response = handle_api_schedules(State(state.clone()), authorized())
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(json_body(response).await["schedules"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+
+        let response = handle_api_schedule_run(State(state), authorized(), Path("nightly".into()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn cron_pause_and_resume_report_missing_jobs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = test_state(tmp.path());
+        let config = state.config.lock().clone();
+        let job = crate::cron::add_job(&config, "*/5 * * * *", "echo hi").unwrap();
+
+        let response =
+            handle_api_cron_pause(State(state.clone()), authorized(), Path(job.id.clone()))
+                .await
+                .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json_body(response).await["job"]["enabled"], false);
+
+        let response = handle_api_cron_resume(State(state.clone()), authorized(), Path(job.id))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json_body(response).await["job"]["enabled"], true);
+
+        for response in [
+            handle_api_cron_pause(State(state.clone()), authorized(), Path("nope".into()))
+                .await
+                .into_response(),
+            handle_api_cron_resume(State(state), authorized(), Path("nope".into()))
+                .await
+                .into_response(),
+        ] {
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
+
+    #[tokio::test]
+    async fn session_events_stream_only_messages_stored_after_opening() {
+        use http_body_util::BodyExt;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = test_state(tmp.path());
+        let session = store_with_sessions(tmp.path());
+
+        let response = super::super::sse::handle_sse_session_events(
+            State(state.clone()),
+            authorized(),
+            Path("missing".into()),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = super::super::sse::handle_sse_session_events(
+            State(state),
+            authorized(),
+            Path(session.clone()),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        crate::conversations::ConversationStore::open(tmp.path())
+            .unwrap()
+            .append_message(&session, "user", "third")
+            .unwrap();
+
+        let mut body = response.into_body();
+        let frame = tokio::time::timeout(std::time::Duration::from_secs(5), body.frame())
+            .await
+            .expect("an event within the poll interval")
+            .unwrap()
+            .unwrap();
+        let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+        assert!(text.starts_with("event: message\n"), "{text}");
+        assert!(text.contains(r#""content":"third""#), "{text}");
+        assert!(!text.contains("second"), "{text}");
+    }
 }
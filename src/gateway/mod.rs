@@ -8,12 +8,14 @@
 //! - Header sanitization (handled by axum/hyper)
 
 pub mod api;
+pub mod openai_compat;
 pub mod sse;
 pub mod static_files;
 pub mod ws;
 
 use crate::channels::{
-    Channel, LinqChannel, NextcloudTalkChannel, SendMessage, WatiChannel, WhatsAppChannel,
+    presence::ChannelPresence, Channel, GitHubChannel, LinqChannel, NextcloudTalkChannel,
+    SendMessage, WatiChannel, WhatsAppChannel,
 };
 use crate::config::Config;
 use crate::cost::CostTracker;
@@ -47,6 +49,9 @@ use uuid::Uuid;
 pub const MAX_BODY_SIZE: usize = 65_536;
 /// Request timeout (30s) — prevents slow-loris attacks
 pub const REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Timeout for API routes that run a full agent turn (`/api/messages`,
+/// `/api/schedules/{name}/run`).
+pub const AGENT_REQUEST_TIMEOUT_SECS: u64 = 600;
 /// Sliding window used by gateway rate limiting.
 pub const RATE_LIMIT_WINDOW_SECS: u64 = 60;
 /// Fallback max distinct client keys tracked in gateway rate limiter.
@@ -74,6 +79,10 @@ fn nextcloud_talk_memory_key(msg: &crate::channels::traits::ChannelMessage) -> S
     format!("nextcloud_talk_{}_{}", msg.sender, msg.id)
 }
 
+fn github_memory_key(msg: &crate::channels::traits::ChannelMessage) -> String {
+    format!("github_{}_{}", msg.sender, msg.id)
+}
+
 fn hash_webhook_secret(value: &str) -> String {
     use sha2::{Digest, Sha256};
 
@@ -302,6 +311,9 @@ pub struct AppState {
     /// Nextcloud Talk webhook secret for signature verification
     pub nextcloud_talk_webhook_secret: Option<Arc<str>>,
     pub wati: Option<Arc<WatiChannel>>,
+    pub github: Option<Arc<GitHubChannel>>,
+    /// GitHub webhook secret for `X-Hub-Signature-256` verification
+    pub github_webhook_secret: Option<Arc<str>>,
     /// Observability backend for metrics scraping
     pub observer: Arc<dyn crate::observability::Observer>,
     /// Registered tool specs (for web dashboard tools page)
@@ -356,12 +368,16 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .clone()
         .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
     let temperature = config.default_temperature;
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
-        &config.memory,
-        Some(&config.storage.provider.config),
-        &config.workspace_dir,
-        config.api_key.as_deref(),
-    )?);
+    // Pass-through for the API; webhook messages run inside their memory scope.
+    let mem: Arc<dyn Memory> = Arc::new(memory::scope::ScopedMemory::new(
+        Arc::from(memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?),
+        config.memory.scopes.read_global,
+    ));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -528,6 +544,32 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             })
             .map(Arc::from);
 
+    // GitHub channel (if configured)
+    let github_channel: Option<Arc<GitHubChannel>> = config
+        .channels_config
+        .github
+        .as_ref()
+        .map(|gh| Arc::new(GitHubChannel::from_config(gh)));
+
+    // GitHub webhook secret for signature verification
+    // Priority: environment variable > config file
+    let github_webhook_secret: Option<Arc<str>> = std::env::var("ZEROCLAW_GITHUB_WEBHOOK_SECRET")
+        .ok()
+        .and_then(|secret| {
+            let secret = secret.trim();
+            (!secret.is_empty()).then(|| secret.to_owned())
+        })
+        .or_else(|| {
+            config.channels_config.github.as_ref().and_then(|gh| {
+                gh.webhook_secret
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|secret| !secret.is_empty())
+                    .map(ToOwned::to_owned)
+            })
+        })
+        .map(Arc::from);
+
     // ── Pairing guard ──────────────────────────────────────
     let pairing = Arc::new(PairingGuard::new(
         config.gateway.require_pairing,
@@ -590,8 +632,13 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     if nextcloud_talk_channel.is_some() {
         println!("  POST /nextcloud-talk — Nextcloud Talk bot webhook");
     }
+    if github_channel.is_some() {
+        println!("  POST /github    — GitHub issue/PR comment webhook");
+    }
     println!("  GET  /api/*     — REST API (bearer token required)");
+    println!("  POST /api/messages — {{\"message\": \"...\", \"session\": \"...\"}} agent turn");
     println!("  GET  /ws/chat   — WebSocket agent chat");
+    println!("  GET  /chat      — browser chat page");
     println!("  GET  /health    — health check");
     println!("  GET  /metrics   — Prometheus metrics");
     if let Some(code) = pairing.pairing_code() {
@@ -641,6 +688,8 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         nextcloud_talk: nextcloud_talk_channel,
         nextcloud_talk_webhook_secret,
         wati: wati_channel,
+        github: github_channel,
+        github_webhook_secret,
         observer: broadcast_observer,
         tools_registry,
         cost_tracker,
@@ -652,6 +701,20 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/config", put(api::handle_api_config_put))
         .layer(RequestBodyLimitLayer::new(1_048_576));
 
+    // Agent turns outlive the default request timeout
+    let agent_router = Router::new()
+        .route("/api/messages", post(api::handle_api_message_send))
+        .route(
+            "/api/schedules/{name}/run",
+            post(api::handle_api_schedule_run),
+        )
+        .with_state(state.clone())
+        .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
+        .layer(TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            Duration::from_secs(AGENT_REQUEST_TIMEOUT_SECS),
+        ));
+
     // Build router with middleware
     let app = Router::new()
         // ── Existing routes ──
@@ -665,6 +728,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/wati", get(handle_wati_verify))
         .route("/wati", post(handle_wati_webhook))
         .route("/nextcloud-talk", post(handle_nextcloud_talk_webhook))
+        .route("/github", post(handle_github_webhook))
         // ── Web Dashboard API routes ──
         .route("/api/status", get(api::handle_api_status))
         .route("/api/config", get(api::handle_api_config_get))
@@ -672,6 +736,16 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/cron", get(api::handle_api_cron_list))
         .route("/api/cron", post(api::handle_api_cron_add))
         .route("/api/cron/{id}", delete(api::handle_api_cron_delete))
+        .route("/api/cron/{id}/pause", post(api::handle_api_cron_pause))
+        .route("/api/cron/{id}/resume", post(api::handle_api_cron_resume))
+        .route("/api/schedules", get(api::handle_api_schedules))
+        .route("/api/sessions", get(api::handle_api_sessions))
+        .route("/api/sessions/{id}", get(api::handle_api_session))
+        .route(
+            "/api/sessions/{id}/events",
+            get(sse::handle_sse_session_events),
+        )
+        .route("/api/usage", get(api::handle_api_usage))
         .route("/api/integrations", get(api::handle_api_integrations))
         .route(
             "/api/doctor",
@@ -680,6 +754,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/memory", get(api::handle_api_memory_list))
         .route("/api/memory", post(api::handle_api_memory_store))
         .route("/api/memory/{key}", delete(api::handle_api_memory_delete))
+        .route(
+            "/api/conversations/search",
+            get(api::handle_api_conversation_search),
+        )
         .route("/api/cost", get(api::handle_api_cost))
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
@@ -687,6 +765,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/events", get(sse::handle_sse_events))
         // ── WebSocket agent chat ──
         .route("/ws/chat", get(ws::handle_ws_chat))
+        .route("/chat", get(static_files::handle_chat_page))
         // ── Static assets (web dashboard) ──
         .route("/_app/{*path}", get(static_files::handle_static))
         // ── Config PUT with larger body limit ──
@@ -697,6 +776,8 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(REQUEST_TIMEOUT_SECS),
         ))
+        // ── Agent turns with a longer timeout ──
+        .merge(agent_router)
         // ── SPA fallback: non-API GET requests serve index.html ──
         .fallback(get(static_files::handle_spa_fallback));
 
@@ -835,14 +916,18 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
     // workspace-aware system context before model invocation.
     let system_prompt = {
         let config_guard = state.config.lock();
-        crate::channels::build_system_prompt(
+        let mut prompt = crate::channels::build_system_prompt(
             &config_guard.workspace_dir,
             &state.model,
             &[], // tools - empty for simple chat
             &[], // skills
             Some(&config_guard.identity),
             None, // bootstrap_max_chars - use default
-        )
+        );
+        if let Some(section) = crate::i18n::reply_language_prompt(&config_guard.locale) {
+            prompt.push_str(&section);
+        }
+        prompt
     };
 
     let mut messages = Vec::with_capacity(1 + user_messages.len());
@@ -860,6 +945,15 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
 }
 
 /// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk).
+/// Memory scope for a message received by a webhook channel.
+fn webhook_memory_scope(
+    state: &AppState,
+    msg: &crate::channels::traits::ChannelMessage,
+) -> crate::memory::scope::MemoryScope {
+    crate::memory::scope::MemoryScopePolicy::from_config(&state.config.lock())
+        .resolve(&msg.channel, &msg.sender)
+}
+
 async fn run_gateway_chat_with_tools(state: &AppState, message: &str) -> anyhow::Result<String> {
     let config = state.config.lock().clone();
     crate::agent::process_message(config, message).await
@@ -1175,41 +1269,49 @@ async fn handle_whatsapp_message(
 
     // Process each message
     for msg in &messages {
-        tracing::info!(
-            "WhatsApp message from {}: {}",
-            msg.sender,
-            truncate_with_ellipsis(&msg.content, 50)
-        );
+        let scope = webhook_memory_scope(&state, msg);
+        crate::memory::scope::run_in_scope(scope, async {
+            tracing::info!(
+                "WhatsApp message from {}: {}",
+                msg.sender,
+                truncate_with_ellipsis(&msg.content, 50)
+            );
 
-        // Auto-save to memory
-        if state.auto_save {
-            let key = whatsapp_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
-        }
+            // Auto-save to memory
+            if state.auto_save {
+                let key = whatsapp_memory_key(msg);
+                let _ = state
+                    .mem
+                    .store(&key, &msg.content, MemoryCategory::Conversation, None)
+                    .await;
+            }
 
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
-            Ok(response) => {
-                // Send reply via WhatsApp
-                if let Err(e) = wa
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
-                {
-                    tracing::error!("Failed to send WhatsApp reply: {e}");
+            let read_receipts = state.config.lock().channels_config.read_receipts;
+            let presence = Arc::clone(wa).begin_presence(msg, read_receipts);
+            let reply = run_gateway_chat_with_tools(&state, &msg.content).await;
+            let _ = presence.end().await;
+            match reply {
+                Ok(response) => {
+                    // Send reply via WhatsApp
+                    if let Err(e) = wa
+                        .send(&SendMessage::new(response, &msg.reply_target))
+                        .await
+                    {
+                        tracing::error!("Failed to send WhatsApp reply: {e}");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("LLM error for WhatsApp message: {e:#}");
+                    let _ = wa
+                        .send(&SendMessage::new(
+                            "Sorry, I couldn't process your message right now.",
+                            &msg.reply_target,
+                        ))
+                        .await;
                 }
             }
-            Err(e) => {
-                tracing::error!("LLM error for WhatsApp message: {e:#}");
-                let _ = wa
-                    .send(&SendMessage::new(
-                        "Sorry, I couldn't process your message right now.",
-                        &msg.reply_target,
-                    ))
-                    .await;
-            }
-        }
+        })
+        .await;
     }
 
     // Acknowledge the webhook
@@ -1282,42 +1384,50 @@ async fn handle_linq_webhook(
 
     // Process each message
     for msg in &messages {
-        tracing::info!(
-            "Linq message from {}: {}",
-            msg.sender,
-            truncate_with_ellipsis(&msg.content, 50)
-        );
+        let scope = webhook_memory_scope(&state, msg);
+        crate::memory::scope::run_in_scope(scope, async {
+            tracing::info!(
+                "Linq message from {}: {}",
+                msg.sender,
+                truncate_with_ellipsis(&msg.content, 50)
+            );
 
-        // Auto-save to memory
-        if state.auto_save {
-            let key = linq_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
-        }
+            // Auto-save to memory
+            if state.auto_save {
+                let key = linq_memory_key(msg);
+                let _ = state
+                    .mem
+                    .store(&key, &msg.content, MemoryCategory::Conversation, None)
+                    .await;
+            }
 
-        // Call the LLM
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
-            Ok(response) => {
-                // Send reply via Linq
-                if let Err(e) = linq
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
-                {
-                    tracing::error!("Failed to send Linq reply: {e}");
+            // Call the LLM
+            let read_receipts = state.config.lock().channels_config.read_receipts;
+            let presence = Arc::clone(linq).begin_presence(msg, read_receipts);
+            let reply = run_gateway_chat_with_tools(&state, &msg.content).await;
+            let _ = presence.end().await;
+            match reply {
+                Ok(response) => {
+                    // Send reply via Linq
+                    if let Err(e) = linq
+                        .send(&SendMessage::new(response, &msg.reply_target))
+                        .await
+                    {
+                        tracing::error!("Failed to send Linq reply: {e}");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("LLM error for Linq message: {e:#}");
+                    let _ = linq
+                        .send(&SendMessage::new(
+                            "Sorry, I couldn't process your message right now.",
+                            &msg.reply_target,
+                        ))
+                        .await;
                 }
             }
-            Err(e) => {
-                tracing::error!("LLM error for Linq message: {e:#}");
-                let _ = linq
-                    .send(&SendMessage::new(
-                        "Sorry, I couldn't process your message right now.",
-                        &msg.reply_target,
-                    ))
-                    .await;
-            }
-        }
+        })
+        .await;
     }
 
     // Acknowledge the webhook
@@ -1374,42 +1484,50 @@ async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl
 
     // Process each message
     for msg in &messages {
-        tracing::info!(
-            "WATI message from {}: {}",
-            msg.sender,
-            truncate_with_ellipsis(&msg.content, 50)
-        );
+        let scope = webhook_memory_scope(&state, msg);
+        crate::memory::scope::run_in_scope(scope, async {
+            tracing::info!(
+                "WATI message from {}: {}",
+                msg.sender,
+                truncate_with_ellipsis(&msg.content, 50)
+            );
 
-        // Auto-save to memory
-        if state.auto_save {
-            let key = wati_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
-        }
+            // Auto-save to memory
+            if state.auto_save {
+                let key = wati_memory_key(msg);
+                let _ = state
+                    .mem
+                    .store(&key, &msg.content, MemoryCategory::Conversation, None)
+                    .await;
+            }
 
-        // Call the LLM
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
-            Ok(response) => {
-                // Send reply via WATI
-                if let Err(e) = wati
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
-                {
-                    tracing::error!("Failed to send WATI reply: {e}");
+            // Call the LLM
+            let read_receipts = state.config.lock().channels_config.read_receipts;
+            let presence = Arc::clone(wati).begin_presence(msg, read_receipts);
+            let reply = run_gateway_chat_with_tools(&state, &msg.content).await;
+            let _ = presence.end().await;
+            match reply {
+                Ok(response) => {
+                    // Send reply via WATI
+                    if let Err(e) = wati
+                        .send(&SendMessage::new(response, &msg.reply_target))
+                        .await
+                    {
+                        tracing::error!("Failed to send WATI reply: {e}");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("LLM error for WATI message: {e:#}");
+                    let _ = wati
+                        .send(&SendMessage::new(
+                            "Sorry, I couldn't process your message right now.",
+                            &msg.reply_target,
+                        ))
+                        .await;
                 }
             }
-            Err(e) => {
-                tracing::error!("LLM error for WATI message: {e:#}");
-                let _ = wati
-                    .send(&SendMessage::new(
-                        "Sorry, I couldn't process your message right now.",
-                        &msg.reply_target,
-                    ))
-                    .await;
-            }
-        }
+        })
+        .await;
     }
 
     // Acknowledge the webhook
@@ -1480,44 +1598,235 @@ async fn handle_nextcloud_talk_webhook(
     }
 
     for msg in &messages {
-        tracing::info!(
-            "Nextcloud Talk message from {}: {}",
-            msg.sender,
-            truncate_with_ellipsis(&msg.content, 50)
+        let scope = webhook_memory_scope(&state, msg);
+        crate::memory::scope::run_in_scope(scope, async {
+            tracing::info!(
+                "Nextcloud Talk message from {}: {}",
+                msg.sender,
+                truncate_with_ellipsis(&msg.content, 50)
+            );
+
+            if state.auto_save {
+                let key = nextcloud_talk_memory_key(msg);
+                let _ = state
+                    .mem
+                    .store(&key, &msg.content, MemoryCategory::Conversation, None)
+                    .await;
+            }
+
+            let read_receipts = state.config.lock().channels_config.read_receipts;
+            let presence = Arc::clone(nextcloud_talk).begin_presence(msg, read_receipts);
+            let reply = run_gateway_chat_with_tools(&state, &msg.content).await;
+            let _ = presence.end().await;
+            match reply {
+                Ok(response) => {
+                    if let Err(e) = nextcloud_talk
+                        .send(&SendMessage::new(response, &msg.reply_target))
+                        .await
+                    {
+                        tracing::error!("Failed to send Nextcloud Talk reply: {e}");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("LLM error for Nextcloud Talk message: {e:#}");
+                    let _ = nextcloud_talk
+                        .send(&SendMessage::new(
+                            "Sorry, I couldn't process your message right now.",
+                            &msg.reply_target,
+                        ))
+                        .await;
+                }
+            }
+        })
+        .await;
+    }
+
+    (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
+}
+
+/// POST /github — issue and pull request comment webhook (GitHub webhooks / GitHub App)
+async fn handle_github_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Some(ref github) = state.github else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "GitHub not configured"})),
         );
+    };
+
+    // ── Security: Verify X-Hub-Signature-256 if webhook secret is configured ──
+    if let Some(ref webhook_secret) = state.github_webhook_secret {
+        let signature = headers
+            .get("X-Hub-Signature-256")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
 
-        if state.auto_save {
-            let key = nextcloud_talk_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
+        if !crate::channels::github::verify_github_signature(webhook_secret, &body, signature) {
+            tracing::warn!(
+                "GitHub webhook signature verification failed (signature: {})",
+                if signature.is_empty() {
+                    "missing"
+                } else {
+                    "invalid"
+                }
+            );
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid signature"})),
+            );
         }
+    }
+
+    let event = headers
+        .get("X-GitHub-Event")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+
+    // Parse JSON body
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
 
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
-            Ok(response) => {
-                if let Err(e) = nextcloud_talk
-                    .send(&SendMessage::new(response, &msg.reply_target))
+    let messages = github.parse_webhook_payload(event, &payload);
+    if messages.is_empty() {
+        // Acknowledge pings and events that do not mention the bot.
+        return (StatusCode::OK, Json(serde_json::json!({"status": "ok"})));
+    }
+
+    for msg in &messages {
+        let scope = webhook_memory_scope(&state, msg);
+        crate::memory::scope::run_in_scope(scope, async {
+            tracing::info!(
+                "GitHub mention from {} on {}: {}",
+                msg.sender,
+                msg.reply_target,
+                truncate_with_ellipsis(&msg.content, 50)
+            );
+
+            if GitHubChannel::is_apply_command(msg) {
+                let reply = apply_github_patch(&state, github, msg).await;
+                if let Err(e) = github
+                    .send(&SendMessage::new(reply, &msg.reply_target))
                     .await
                 {
-                    tracing::error!("Failed to send Nextcloud Talk reply: {e}");
+                    tracing::error!("Failed to send GitHub reply: {e}");
                 }
+                return;
             }
-            Err(e) => {
-                tracing::error!("LLM error for Nextcloud Talk message: {e:#}");
-                let _ = nextcloud_talk
-                    .send(&SendMessage::new(
-                        "Sorry, I couldn't process your message right now.",
-                        &msg.reply_target,
-                    ))
+
+            if state.auto_save {
+                let key = github_memory_key(msg);
+                let _ = state
+                    .mem
+                    .store(&key, &msg.content, MemoryCategory::Conversation, None)
                     .await;
             }
-        }
+
+            let prompt = match github.build_prompt(msg).await {
+                Ok(prompt) => prompt,
+                Err(e) => {
+                    tracing::warn!("GitHub: failed to fetch thread context: {e:#}");
+                    msg.content.clone()
+                }
+            };
+
+            let read_receipts = state.config.lock().channels_config.read_receipts;
+            let presence = Arc::clone(github).begin_presence(msg, read_receipts);
+            let reply = run_gateway_chat_with_tools(&state, &prompt).await;
+            let _ = presence.end().await;
+            match reply {
+                Ok(mut response) => {
+                    if github.remember_patch(&msg.reply_target, &response) {
+                        response.push_str(&github.apply_hint());
+                    }
+                    if let Err(e) = github
+                        .send(&SendMessage::new(response, &msg.reply_target))
+                        .await
+                    {
+                        tracing::error!("Failed to send GitHub reply: {e}");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("LLM error for GitHub mention: {e:#}");
+                    let _ = github
+                        .send(&SendMessage::new(
+                            "Sorry, I couldn't process this request right now.",
+                            &msg.reply_target,
+                        ))
+                        .await;
+                }
+            }
+        })
+        .await;
     }
 
     (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
 }
 
+/// Apply the patch the bot suggested earlier in a thread, after an approver
+/// asked for it. Never touches the model; returns the comment to post.
+async fn apply_github_patch(
+    state: &AppState,
+    github: &GitHubChannel,
+    msg: &crate::channels::traits::ChannelMessage,
+) -> String {
+    use crate::tools::Tool;
+
+    if !github.is_patch_approver(&msg.sender) {
+        tracing::warn!(
+            "GitHub: {} is not in channels_config.github.patch_approvers; refusing to apply patch",
+            msg.sender
+        );
+        return format!(
+            "@{} is not allowed to apply patches in this repository.",
+            msg.sender
+        );
+    }
+
+    let Some(thread) = crate::channels::github::GitHubThread::parse(&msg.reply_target) else {
+        return "Could not determine the repository for this thread.".into();
+    };
+    let Some(checkout) = github.checkout_for(&thread.repo) else {
+        return format!("No local checkout is configured for `{}`.", thread.repo);
+    };
+    let Some(patch) = github.take_pending_patch(&msg.reply_target) else {
+        return "There is no pending patch in this thread.".into();
+    };
+
+    let config = state.config.lock().clone();
+    let checkout_dir = std::path::PathBuf::from(checkout);
+    let security = Arc::new(SecurityPolicy::from_config(&config.autonomy, &checkout_dir));
+    let tool = tools::GitOperationsTool::new(security, checkout_dir);
+
+    match tool
+        .execute(serde_json::json!({ "operation": "apply", "patch": patch }))
+        .await
+    {
+        Ok(result) if result.success => {
+            tracing::info!(
+                "GitHub: applied patch on {} for {}",
+                thread.repo,
+                msg.sender
+            );
+            format!(
+                "Patch applied to `{checkout}`:\n\n```\n{}\n```",
+                result.output.trim()
+            )
+        }
+        Ok(result) => format!(
+            "Patch was not applied: {}",
+            result.error.unwrap_or_else(|| "unknown error".into())
+        ),
+        Err(e) => format!("Patch was not applied: {e}"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1596,6 +1905,8 @@ mod tests {
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
             wati: None,
+            github: None,
+            github_webhook_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
@@ -1645,6 +1956,8 @@ mod tests {
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
             wati: None,
+            github: None,
+            github_webhook_secret: None,
             observer,
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
@@ -1842,6 +2155,7 @@ mod tests {
         let msg = ChannelMessage {
             id: "wamid-123".into(),
             sender: "+1234567890".into(),
+            sender_name: None,
             reply_target: "+1234567890".into(),
             content: "hello".into(),
             channel: "whatsapp".into(),
@@ -2011,6 +2325,8 @@ mod tests {
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
             wati: None,
+            github: None,
+            github_webhook_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
@@ -2075,6 +2391,8 @@ mod tests {
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
             wati: None,
+            github: None,
+            github_webhook_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
@@ -2151,6 +2469,8 @@ mod tests {
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
             wati: None,
+            github: None,
+            github_webhook_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
@@ -2199,6 +2519,8 @@ mod tests {
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
             wati: None,
+            github: None,
+            github_webhook_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
@@ -2252,6 +2574,8 @@ mod tests {
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
             wati: None,
+            github: None,
+            github_webhook_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
@@ -2310,6 +2634,8 @@ mod tests {
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
             wati: None,
+            github: None,
+            github_webhook_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
@@ -2364,6 +2690,8 @@ mod tests {
             nextcloud_talk: Some(channel),
             nextcloud_talk_webhook_secret: Some(Arc::from(secret)),
             wati: None,
+            github: None,
+            github_webhook_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
@@ -2387,6 +2715,70 @@ mod tests {
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
     }
 
+    #[tokio::test]
+    async fn github_webhook_rejects_invalid_signature() {
+        let provider_impl = Arc::new(MockProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+        let memory: Arc<dyn Memory> = Arc::new(MockMemory);
+
+        let channel = Arc::new(GitHubChannel::from_config(
+            &crate::config::schema::GitHubConfig {
+                api_url: "https://api.github.com".into(),
+                bot_login: "zeroclaw-bot".into(),
+                access_token: Some("token".into()),
+                app_id: None,
+                installation_id: None,
+                private_key_path: None,
+                webhook_secret: None,
+                allowed_users: vec!["*".into()],
+                patch_approvers: vec![],
+                checkouts: HashMap::new(),
+                max_context_chars: 12_000,
+            },
+        ));
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider,
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: memory,
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            github: Some(channel),
+            github_webhook_secret: Some(Arc::from("github-test-secret")),
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-GitHub-Event", HeaderValue::from_static("issue_comment"));
+        headers.insert(
+            "X-Hub-Signature-256",
+            HeaderValue::from_static("sha256=deadbeef"),
+        );
+
+        let body = r#"{"action":"created","issue":{"number":1},"comment":{"id":1,"body":"@zeroclaw-bot hi","user":{"login":"alice"}},"repository":{"full_name":"octo/repo"}}"#;
+        let response = handle_github_webhook(State(state), headers, Bytes::from(body))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
+    }
+
     // ══════════════════════════════════════════════════════════
     // WhatsApp Signature Verification Tests (CWE-345 Prevention)
     // ══════════════════════════════════════════════════════════
@@ -0,0 +1,522 @@
+//! OpenAI-compatible chat API (`zeroclaw serve --openai-compat`).
+//!
+//! Exposes `POST /v1/chat/completions` (plain and streaming) and
+//! `GET /v1/models` so editors, chat UIs and SDKs built for the OpenAI API
+//! can use the agent as if it were a model. Each request runs a full agent
+//! turn — tools, memory context, guardrails, fact extraction — on the
+//! conversation the client sends; the client owns the history, so nothing
+//! is stored as a zeroclaw session.
+//!
+//! Requests need `Authorization: Bearer <token>` with a gateway pairing
+//! token unless `[gateway].require_pairing` is off. Turns run as
+//! `[gateway].role`, and tool calls that need approval are denied.
+
+use crate::agent::loop_::{CliRuntime, DRAFT_CLEAR_SENTINEL};
+use crate::agent::tool_output::estimate_tokens;
+use crate::config::{Config, GuardrailDirection};
+use crate::providers::ChatMessage;
+use crate::security::pairing::{is_public_bind, PairingGuard};
+use anyhow::Result;
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+use tokio_util::sync::CancellationToken;
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
+
+/// Port used when `--port` is not given.
+pub const DEFAULT_PORT: u16 = 8787;
+/// Model ID advertised by `/v1/models`; any requested model name is accepted.
+pub const MODEL_ID: &str = "zeroclaw";
+/// Conversations are sent whole on every request, so allow more than the
+/// gateway's webhook limit.
+const MAX_BODY_SIZE: usize = 4 * 1024 * 1024;
+/// Agent turns with tool calls can run for minutes.
+const REQUEST_TIMEOUT_SECS: u64 = 600;
+
+#[derive(Clone)]
+struct ServeState {
+    runtime: Arc<CliRuntime>,
+    pairing: Arc<PairingGuard>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionRequest {
+    #[serde(default)]
+    model: Option<String>,
+    messages: Vec<RequestMessage>,
+    #[serde(default)]
+    stream: bool,
+    #[serde(default)]
+    stream_options: Option<StreamOptions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamOptions {
+    #[serde(default)]
+    include_usage: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct RequestMessage {
+    role: String,
+    #[serde(default)]
+    content: Option<serde_json::Value>,
+}
+
+/// A request turned into agent input: extra system instructions, earlier
+/// turns and the message to answer.
+#[derive(Debug)]
+struct Conversation {
+    system: Vec<String>,
+    history: Vec<ChatMessage>,
+    prompt: String,
+}
+
+/// Flatten OpenAI message content (a string or an array of parts) to text;
+/// image parts become `[IMAGE:<url>]` markers for the multimodal pipeline.
+fn content_text(content: Option<&serde_json::Value>) -> String {
+    match content {
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(serde_json::Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| match part["type"].as_str() {
+                Some("text") => part["text"].as_str().map(str::to_string),
+                Some("image_url") => part["image_url"]["url"]
+                    .as_str()
+                    .or_else(|| part["image_url"].as_str())
+                    .map(|url| format!("[IMAGE:{url}]")),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn conversation(messages: &[RequestMessage]) -> Result<Conversation, String> {
+    let Some((last, earlier)) = messages.split_last() else {
+        return Err("`messages` must not be empty".into());
+    };
+    if last.role != "user" {
+        return Err("the last message must have role `user`".into());
+    }
+    let prompt = content_text(last.content.as_ref());
+    if prompt.trim().is_empty() {
+        return Err("the last user message is empty".into());
+    }
+    let mut system = Vec::new();
+    let mut history = Vec::new();
+    for message in earlier {
+        let text = content_text(message.content.as_ref());
+        if text.trim().is_empty() {
+            continue;
+        }
+        match message.role.as_str() {
+            "system" | "developer" => system.push(text),
+            "user" => history.push(ChatMessage::user(text)),
+            "assistant" => history.push(ChatMessage::assistant(text)),
+            // The client's own tool calls mean nothing to the agent's tools.
+            _ => {}
+        }
+    }
+    Ok(Conversation {
+        system,
+        history,
+        prompt,
+    })
+}
+
+/// Estimated token counts; the agent may make several model calls per
+/// request, so exact provider usage does not map onto one completion.
+fn usage(conversation: &Conversation, answer: &str) -> serde_json::Value {
+    let prompt_tokens = conversation
+        .system
+        .iter()
+        .map(String::as_str)
+        .chain(conversation.history.iter().map(|m| m.content.as_str()))
+        .chain(std::iter::once(conversation.prompt.as_str()))
+        .map(estimate_tokens)
+        .sum::<usize>();
+    let completion_tokens = estimate_tokens(answer);
+    json!({
+        "prompt_tokens": prompt_tokens,
+        "completion_tokens": completion_tokens,
+        "total_tokens": prompt_tokens + completion_tokens,
+    })
+}
+
+fn error_response(status: StatusCode, kind: &str, message: &str) -> Response {
+    (
+        status,
+        Json(json!({
+            "error": { "message": message, "type": kind, "code": serde_json::Value::Null }
+        })),
+    )
+        .into_response()
+}
+
+fn authorize(state: &ServeState, headers: &HeaderMap) -> Result<(), Response> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if state.pairing.is_authenticated(token) {
+        Ok(())
+    } else {
+        Err(error_response(
+            StatusCode::UNAUTHORIZED,
+            "invalid_request_error",
+            "Invalid API key — use a gateway pairing token as the bearer token",
+        ))
+    }
+}
+
+/// GET /v1/models
+async fn handle_models(State(state): State<ServeState>, headers: HeaderMap) -> Response {
+    if let Err(denied) = authorize(&state, &headers) {
+        return denied;
+    }
+    Json(json!({
+        "object": "list",
+        "data": [{
+            "id": MODEL_ID,
+            "object": "model",
+            "created": 0,
+            "owned_by": "zeroclaw",
+        }],
+    }))
+    .into_response()
+}
+
+/// POST /v1/chat/completions
+async fn handle_chat_completions(
+    State(state): State<ServeState>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> Response {
+    if let Err(denied) = authorize(&state, &headers) {
+        return denied;
+    }
+    let request: ChatCompletionRequest = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "invalid_request_error",
+                &format!("Invalid request body: {e}"),
+            )
+        }
+    };
+    let conversation = match conversation(&request.messages) {
+        Ok(conversation) => conversation,
+        Err(message) => {
+            return error_response(StatusCode::BAD_REQUEST, "invalid_request_error", &message)
+        }
+    };
+    let model = request.model.unwrap_or_else(|| MODEL_ID.to_string());
+    let id = format!("chatcmpl-{}", uuid::Uuid::new_v4().simple());
+    let created = chrono::Utc::now().timestamp();
+
+    if request.stream {
+        let include_usage = request.stream_options.is_some_and(|o| o.include_usage);
+        return stream_completion(state, conversation, id, created, model, include_usage);
+    }
+
+    match run_turn(&state.runtime, &conversation, None, None).await {
+        Ok(answer) => Json(json!({
+            "id": id,
+            "object": "chat.completion",
+            "created": created,
+            "model": model,
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": answer },
+                "finish_reason": "stop",
+            }],
+            "usage": usage(&conversation, &answer),
+        }))
+        .into_response(),
+        Err(e) => turn_error(&e),
+    }
+}
+
+fn turn_error(e: &anyhow::Error) -> Response {
+    if crate::cost::budget::is_budget_exceeded(e) {
+        return error_response(
+            StatusCode::TOO_MANY_REQUESTS,
+            "insufficient_quota",
+            &e.to_string(),
+        );
+    }
+    error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "server_error",
+        &crate::providers::sanitize_api_error(&format!("{e:#}")),
+    )
+}
+
+/// Run one agent turn over `conversation`, sending progress and answer
+/// deltas to `on_delta` when streaming.
+async fn run_turn(
+    runtime: &CliRuntime,
+    conversation: &Conversation,
+    cancellation: Option<CancellationToken>,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+) -> Result<String> {
+    let prompt = match runtime
+        .guard(GuardrailDirection::Input, conversation.prompt.clone())
+        .await
+    {
+        Ok(prompt) => prompt,
+        Err(blocked) => return Ok(blocked),
+    };
+    let mut history = runtime.initial_history();
+    if !conversation.system.is_empty() {
+        if let Some(first) = history.first_mut() {
+            first.content = format!(
+                "{}\n\n## Client instructions\n\n{}",
+                first.content,
+                conversation.system.join("\n\n")
+            );
+        }
+    }
+    history.extend(conversation.history.iter().cloned());
+    runtime.prepare_turn(&mut history, &prompt).await;
+    let answer = runtime
+        .run_turn(&mut history, &prompt, cancellation, on_delta)
+        .await?;
+    Ok(runtime.finish_turn(&prompt, answer).await)
+}
+
+fn chunk(id: &str, created: i64, model: &str, delta: serde_json::Value) -> serde_json::Value {
+    json!({
+        "id": id,
+        "object": "chat.completion.chunk",
+        "created": created,
+        "model": model,
+        "choices": [{ "index": 0, "delta": delta, "finish_reason": serde_json::Value::Null }],
+    })
+}
+
+/// Answer as `chat.completion.chunk` server-sent events ending in
+/// `data: [DONE]`. Progress before the answer is not sent; the answer is
+/// streamed as the agent loop releases it. Disconnecting cancels the turn.
+fn stream_completion(
+    state: ServeState,
+    conversation: Conversation,
+    id: String,
+    created: i64,
+    model: String,
+    include_usage: bool,
+) -> Response {
+    let (events_tx, events_rx) = tokio::sync::mpsc::channel::<Result<Event, Infallible>>(64);
+    let cancellation = CancellationToken::new();
+    let turn_cancellation = cancellation.clone();
+
+    tokio::spawn(async move {
+        let send = |value: serde_json::Value| {
+            let events_tx = events_tx.clone();
+            async move {
+                events_tx
+                    .send(Ok(Event::default().data(value.to_string())))
+                    .await
+                    .is_ok()
+            }
+        };
+        if !send(chunk(&id, created, &model, json!({ "role": "assistant" }))).await {
+            return;
+        }
+
+        let (delta_tx, mut deltas) = tokio::sync::mpsc::channel::<String>(64);
+        let turn = run_turn(
+            &state.runtime,
+            &conversation,
+            Some(turn_cancellation.clone()),
+            Some(delta_tx),
+        );
+        tokio::pin!(turn);
+        let mut answering = false;
+        let mut streamed = String::new();
+        let result = loop {
+            tokio::select! {
+                result = &mut turn => break result,
+                Some(delta) = deltas.recv() => {
+                    if delta == DRAFT_CLEAR_SENTINEL {
+                        answering = true;
+                    } else if answering {
+                        streamed.push_str(&delta);
+                        if !send(chunk(&id, created, &model, json!({ "content": delta }))).await {
+                            turn_cancellation.cancel();
+                        }
+                    }
+                }
+            }
+        };
+
+        let answer = match result {
+            Ok(answer) => answer,
+            Err(e) => {
+                let message = crate::providers::sanitize_api_error(&format!("{e:#}"));
+                send(json!({ "error": { "message": message, "type": "server_error" } })).await;
+                let _ = events_tx.send(Ok(Event::default().data("[DONE]"))).await;
+                return;
+            }
+        };
+        // Guardrails may have replaced the reply, or nothing was streamed.
+        if answer != streamed {
+            let rest = answer.strip_prefix(streamed.as_str()).unwrap_or(&answer);
+            send(chunk(&id, created, &model, json!({ "content": rest }))).await;
+        }
+        let mut last = chunk(&id, created, &model, json!({}));
+        last["choices"][0]["finish_reason"] = json!("stop");
+        send(last).await;
+        if include_usage {
+            send(json!({
+                "id": id,
+                "object": "chat.completion.chunk",
+                "created": created,
+                "model": model,
+                "choices": [],
+                "usage": usage(&conversation, &answer),
+            }))
+            .await;
+        }
+        let _ = events_tx.send(Ok(Event::default().data("[DONE]"))).await;
+    });
+
+    // Dropping the stream (client gone) cancels the turn.
+    let guard = cancellation.drop_guard();
+    let stream = ReceiverStream::new(events_rx).map(move |event| {
+        let _ = &guard;
+        event
+    });
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Run the OpenAI-compatible server until Ctrl+C.
+pub async fn serve(host: &str, port: u16, config: Config) -> Result<()> {
+    if is_public_bind(host) && config.tunnel.provider == "none" && !config.gateway.allow_public_bind
+    {
+        anyhow::bail!(
+            "🛑 Refusing to bind to {host} — the agent would be exposed to the internet.\n\
+             Fix: use --host 127.0.0.1 (default) or set [gateway] allow_public_bind = true."
+        );
+    }
+    let pairing = Arc::new(PairingGuard::new(
+        config.gateway.require_pairing,
+        &config.gateway.paired_tokens,
+    ));
+    let temperature = config.default_temperature;
+    let role = config.gateway.role;
+    // Requests run as `[gateway] role`; calls that need approval are denied,
+    // since nobody can answer a prompt mid-request.
+    let mut runtime = crate::security::roles::run_with(
+        role,
+        CliRuntime::new(config, None, None, temperature, Vec::new(), false, None),
+    )
+    .await?;
+    // Clients keep the history; don't file every request into one session.
+    runtime.conversation = None;
+
+    let addr: SocketAddr = format!("{host}:{port}").parse()?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let local = listener.local_addr()?;
+    println!("🦀 ZeroClaw OpenAI-compatible API on http://{local}/v1");
+    println!(
+        "  Agent: {} / {} (model id `{MODEL_ID}`)",
+        runtime.provider_name, runtime.model_name
+    );
+    println!("  POST /v1/chat/completions — chat (set \"stream\": true for SSE)");
+    println!("  GET  /v1/models           — model list");
+    println!("  Role: {role} (`[gateway] role`)");
+    if !pairing.require_pairing() {
+        println!("  ⚠️  Pairing: DISABLED (all requests accepted)");
+    } else if pairing.is_paired() {
+        println!("  🔒 API key: a gateway pairing token");
+    } else {
+        println!("  🔒 No pairing tokens yet — pair with `zeroclaw gateway` first (POST /pair)");
+    }
+    println!("  Press Ctrl+C to stop.\n");
+
+    let state = ServeState {
+        runtime: Arc::new(runtime),
+        pairing,
+    };
+    let app = Router::new()
+        .route("/v1/chat/completions", post(handle_chat_completions))
+        .route("/v1/models", get(handle_models))
+        .with_state(state)
+        .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
+        .layer(TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            Duration::from_secs(REQUEST_TIMEOUT_SECS),
+        ));
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: serde_json::Value) -> RequestMessage {
+        RequestMessage {
+            role: role.into(),
+            content: Some(content),
+        }
+    }
+
+    #[test]
+    fn requests_become_agent_input() {
+        let parsed = conversation(&[
+            message("system", json!("Answer tersely.")),
+            message("user", json!("hi")),
+            message("assistant", json!("hello")),
+            message("tool", json!("ignored")),
+            message(
+                "user",
+                json!([
+                    { "type": "text", "text": "what is this?" },
+                    { "type": "image_url", "image_url": { "url": "https://x.test/a.png" } }
+                ]),
+            ),
+        ])
+        .unwrap();
+        assert_eq!(parsed.system, ["Answer tersely."]);
+        assert_eq!(parsed.history.len(), 2);
+        assert_eq!(parsed.history[1].role, "assistant");
+        assert_eq!(parsed.prompt, "what is this?\n[IMAGE:https://x.test/a.png]");
+
+        assert!(conversation(&[]).is_err());
+        assert!(conversation(&[message("assistant", json!("x"))]).is_err());
+
+        let usage = usage(&parsed, "a short answer");
+        assert_eq!(
+            usage["total_tokens"].as_u64().unwrap(),
+            usage["prompt_tokens"].as_u64().unwrap() + usage["completion_tokens"].as_u64().unwrap()
+        );
+    }
+}
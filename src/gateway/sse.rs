@@ -4,7 +4,7 @@
 
 use super::AppState;
 use axum::{
-    extract::State,
+    extract::{Path, State},
     http::{header, HeaderMap, StatusCode},
     response::{
         sse::{Event, KeepAlive, Sse},
@@ -12,6 +12,7 @@ use axum::{
     },
 };
 use std::convert::Infallible;
+use std::time::Duration;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt;
 
@@ -57,6 +58,95 @@ pub async fn handle_sse_events(
         .into_response()
 }
 
+/// How often a session event stream checks for new messages.
+const SESSION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Messages fetched per poll of a session event stream.
+const SESSION_POLL_BATCH: usize = 100;
+
+/// GET /api/sessions/:id/events — SSE stream of messages appended to a
+/// session (by ID or name). Each `message` event carries one stored message;
+/// only messages written after the stream opens are sent.
+pub async fn handle_sse_session_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = super::api::require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let store = match crate::conversations::ConversationStore::open(&workspace_dir) {
+        Ok(store) => store,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to open conversation store: {e}"),
+            )
+                .into_response();
+        }
+    };
+    let session = match store.resolve_session(&id) {
+        Ok(Some(session)) => session,
+        Ok(None) => return (StatusCode::NOT_FOUND, format!("No session '{id}'")).into_response(),
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to load session: {e}"),
+            )
+                .into_response();
+        }
+    };
+    let last_id = match store.recent_messages(&session.id, 1) {
+        Ok(messages) => messages.last().map_or(0, |m| m.id),
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to load session: {e}"),
+            )
+                .into_response();
+        }
+    };
+
+    let stream = futures_util::stream::unfold(
+        (
+            store,
+            session.id,
+            last_id,
+            std::collections::VecDeque::new(),
+        ),
+        |(store, session_id, mut last_id, mut pending)| async move {
+            loop {
+                if let Some(message) = pending.pop_front() {
+                    let event = Event::default()
+                        .event("message")
+                        .json_data(&message)
+                        .unwrap_or_else(|_| Event::default().comment("unserializable message"));
+                    return Some((
+                        Ok::<_, Infallible>(event),
+                        (store, session_id, last_id, pending),
+                    ));
+                }
+                tokio::time::sleep(SESSION_POLL_INTERVAL).await;
+                match store.messages_after(&session_id, last_id, SESSION_POLL_BATCH) {
+                    Ok(messages) => {
+                        if let Some(last) = messages.last() {
+                            last_id = last.id;
+                        }
+                        pending.extend(messages);
+                    }
+                    Err(e) => tracing::warn!("Session event stream for {session_id}: {e}"),
+                }
+            }
+        },
+    );
+
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
 /// Broadcast observer that forwards events to the SSE broadcast channel.
 pub struct BroadcastObserver {
     inner: Box<dyn crate::observability::Observer>,
@@ -4,7 +4,7 @@
 
 use axum::{
     http::{header, StatusCode, Uri},
-    response::IntoResponse,
+    response::{Html, IntoResponse},
 };
 use rust_embed::Embed;
 
@@ -24,6 +24,15 @@ pub async fn handle_spa_fallback() -> impl IntoResponse {
     serve_embedded_file("index.html")
 }
 
+/// Standalone chat page at `/chat`: a single HTML file with no build step.
+/// It asks for a pairing code or token and talks to `/ws/chat`.
+const CHAT_PAGE: &str = include_str!("chat.html");
+
+/// GET /chat — minimal chat UI for browsers on the LAN
+pub async fn handle_chat_page() -> impl IntoResponse {
+    ([(header::CACHE_CONTROL, "no-cache")], Html(CHAT_PAGE))
+}
+
 fn serve_embedded_file(path: &str) -> impl IntoResponse {
     match WebAssets::get(path) {
         Some(content) => {
@@ -1,15 +1,33 @@
 //! WebSocket agent chat handler.
 //!
+//! Each connection is one agent session with the full toolset, memory and
+//! guardrails, persisted like CLI sessions (`?session=<id or name>` continues
+//! one; a new `web:<uuid>` session is started otherwise).
+//!
 //! Protocol:
 //! ```text
+//! Server -> Client: {"type":"session","session":"web:1b2c..."}
 //! Client -> Server: {"type":"message","content":"Hello"}
+//! Server -> Client: {"type":"tool_call","name":"shell","args":{"hint":"ls -la"}}
+//! Server -> Client: {"type":"tool_result","name":"shell","success":true,"output":"finished in 1s"}
 //! Server -> Client: {"type":"chunk","content":"Hi! "}
-//! Server -> Client: {"type":"tool_call","name":"shell","args":{...}}
-//! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
 //! Server -> Client: {"type":"done","full_response":"..."}
 //! ```
+//!
+//! Turns run as `[gateway] role`. A tool call that needs approval is asked
+//! over the socket, and the next message sent during the turn answers it:
+//!
+//! ```text
+//! Server -> Client: {"type":"approval","content":"🔐 Approval needed: `shell` ..."}
+//! Client -> Server: {"type":"message","content":"yes"}
+//! ```
 
 use super::AppState;
+use crate::agent::loop_::{is_tool_loop_cancelled, CliRuntime, DRAFT_CLEAR_SENTINEL};
+use crate::approval::{ChannelApprovalPrompter, PendingApprovals};
+use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::GuardrailDirection;
+use async_trait::async_trait;
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -17,12 +35,46 @@ use axum::{
     },
     response::IntoResponse,
 };
+use futures_util::stream::SplitSink;
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// Scope of the socket's pending approval; each socket has its own
+/// [`PendingApprovals`].
+const WS_APPROVAL_SCOPE: &str = "web";
+
+/// Approval prompts for one socket, forwarded as `approval` messages.
+struct SocketChannel {
+    prompts: mpsc::UnboundedSender<String>,
+}
+
+#[async_trait]
+impl Channel for SocketChannel {
+    fn name(&self) -> &str {
+        "web"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.prompts
+            .send(message.content.clone())
+            .map_err(|_| anyhow::anyhow!("socket closed"))
+    }
+
+    async fn listen(&self, _tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        // Answers arrive through the socket loop.
+        Ok(())
+    }
+}
 
 #[derive(Deserialize)]
 pub struct WsQuery {
     pub token: Option<String>,
+    /// Session ID or name to continue.
+    pub session: Option<String>,
 }
 
 /// GET /ws/chat — WebSocket upgrade for agent chat
@@ -43,13 +95,80 @@ pub async fn handle_ws_chat(
         }
     }
 
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    let session = params
+        .session
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| format!("web:{}", uuid::Uuid::new_v4()));
+    ws.on_upgrade(move |socket| handle_socket(socket, state, session))
         .into_response()
 }
 
-async fn handle_socket(socket: WebSocket, state: AppState) {
+async fn send_json(sender: &mut SplitSink<WebSocket, Message>, value: serde_json::Value) -> bool {
+    sender
+        .send(Message::Text(value.to_string().into()))
+        .await
+        .is_ok()
+}
+
+async fn handle_socket(socket: WebSocket, state: AppState, session: String) {
     let (mut sender, mut receiver) = socket.split();
 
+    let config = state.config.lock().clone();
+    let temperature = config.default_temperature;
+    let role = config.gateway.role;
+    let mut runtime = match crate::security::roles::run_with(
+        role,
+        CliRuntime::new(
+            config,
+            None,
+            None,
+            temperature,
+            Vec::new(),
+            false,
+            Some(&session),
+        ),
+    )
+    .await
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            let message = crate::providers::sanitize_api_error(&format!("{e:#}"));
+            send_json(
+                &mut sender,
+                serde_json::json!({"type": "error", "message": message}),
+            )
+            .await;
+            return;
+        }
+    };
+    let pending_approvals = Arc::new(PendingApprovals::new());
+    let (prompt_tx, mut approval_prompts) = mpsc::unbounded_channel();
+    let approval_timeout =
+        Duration::from_secs(runtime.config.autonomy.risk_approval.timeout_secs.max(1));
+    runtime.approval_manager = runtime.approval_manager.take().map(|manager| {
+        manager.with_prompter(Arc::new(ChannelApprovalPrompter::new(
+            Arc::new(SocketChannel { prompts: prompt_tx }),
+            "user",
+            None,
+            WS_APPROVAL_SCOPE,
+            Arc::clone(&pending_approvals),
+            approval_timeout,
+        )))
+    });
+    let session_id = runtime
+        .conversation
+        .as_ref()
+        .map_or(session, |c| c.session_id().to_string());
+    if !send_json(
+        &mut sender,
+        serde_json::json!({"type": "session", "session": session_id}),
+    )
+    .await
+    {
+        return;
+    }
+    let mut history = runtime.initial_history();
+
     while let Some(msg) = receiver.next().await {
         let msg = match msg {
             Ok(Message::Text(text)) => text,
@@ -78,80 +197,120 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
             continue;
         }
 
-        // Process message with the LLM provider
-        let provider_label = state
-            .config
-            .lock()
-            .default_provider
-            .clone()
-            .unwrap_or_else(|| "unknown".to_string());
-
         // Broadcast agent_start event
         let _ = state.event_tx.send(serde_json::json!({
             "type": "agent_start",
-            "provider": provider_label,
-            "model": state.model,
+            "provider": runtime.provider_name,
+            "model": runtime.model_name,
         }));
 
-        // Simple single-turn chat (no streaming for now — use provider.chat_with_system)
-        let system_prompt = {
-            let config_guard = state.config.lock();
-            crate::channels::build_system_prompt(
-                &config_guard.workspace_dir,
-                &state.model,
-                &[],
-                &[],
-                Some(&config_guard.identity),
-                None,
-            )
+        let content = match runtime.guard(GuardrailDirection::Input, content).await {
+            Ok(content) => content,
+            Err(blocked) => {
+                send_json(
+                    &mut sender,
+                    serde_json::json!({"type": "done", "full_response": blocked}),
+                )
+                .await;
+                continue;
+            }
         };
+        runtime.prepare_turn(&mut history, &content).await;
 
-        let messages = vec![
-            crate::providers::ChatMessage::system(system_prompt),
-            crate::providers::ChatMessage::user(&content),
-        ];
-
-        let multimodal_config = state.config.lock().multimodal.clone();
-        let prepared =
-            match crate::multimodal::prepare_messages_for_provider(&messages, &multimodal_config)
-                .await
-            {
-                Ok(p) => p,
-                Err(e) => {
-                    let err = serde_json::json!({
-                        "type": "error",
-                        "message": format!("Multimodal prep failed: {e}")
-                    });
-                    let _ = sender.send(Message::Text(err.to_string().into())).await;
-                    continue;
+        let cancellation = CancellationToken::new();
+        let mut closed = false;
+        let result = {
+            let (delta_tx, mut deltas) = tokio::sync::mpsc::channel::<String>(64);
+            let turn = runtime.run_turn(
+                &mut history,
+                &content,
+                Some(cancellation.clone()),
+                Some(delta_tx),
+            );
+            tokio::pin!(turn);
+            let mut answering = false;
+            loop {
+                tokio::select! {
+                    result = &mut turn => break result,
+                    Some(delta) = deltas.recv() => {
+                        let events = if delta == DRAFT_CLEAR_SENTINEL {
+                            answering = true;
+                            Vec::new()
+                        } else if answering {
+                            vec![serde_json::json!({"type": "chunk", "content": delta})]
+                        } else {
+                            delta.lines().filter_map(progress_event).collect()
+                        };
+                        for event in events {
+                            if !send_json(&mut sender, event).await {
+                                cancellation.cancel();
+                            }
+                        }
+                    }
+                    Some(prompt) = approval_prompts.recv() => {
+                        let event = serde_json::json!({"type": "approval", "content": prompt});
+                        if !send_json(&mut sender, event).await {
+                            cancellation.cancel();
+                        }
+                    }
+                    incoming = receiver.next(), if !closed => match incoming {
+                        Some(Ok(Message::Text(text))) => {
+                            let answer = serde_json::from_str::<serde_json::Value>(&text)
+                                .ok()
+                                .and_then(|v| v["content"].as_str().map(str::to_string));
+                            if answer.is_some_and(|answer| {
+                                pending_approvals.resolve(WS_APPROVAL_SCOPE, &answer)
+                            }) {
+                                continue;
+                            }
+                            let busy = serde_json::json!({
+                                "type": "error",
+                                "message": "Still working on the previous message",
+                            });
+                            let _ = sender.send(Message::Text(busy.to_string().into())).await;
+                        }
+                        Some(Ok(Message::Close(_)) | Err(_)) | None => {
+                            // The browser went away; stop the turn.
+                            closed = true;
+                            cancellation.cancel();
+                        }
+                        Some(Ok(_)) => {}
+                    },
                 }
-            };
+            }
+        };
 
-        match state
-            .provider
-            .chat_with_history(&prepared.messages, &state.model, state.temperature)
-            .await
-        {
+        match result {
             Ok(response) => {
-                // Send the full response as a done message
-                let done = serde_json::json!({
-                    "type": "done",
-                    "full_response": response,
-                });
-                let _ = sender.send(Message::Text(done.to_string().into())).await;
+                let response = runtime.finish_turn(&content, response).await;
+                send_json(
+                    &mut sender,
+                    serde_json::json!({"type": "done", "full_response": response}),
+                )
+                .await;
 
                 // Broadcast agent_end event
                 let _ = state.event_tx.send(serde_json::json!({
                     "type": "agent_end",
-                    "provider": provider_label,
-                    "model": state.model,
+                    "provider": runtime.provider_name,
+                    "model": runtime.model_name,
                 }));
+                runtime.compact_history(&mut history).await;
+            }
+            Err(e) if is_tool_loop_cancelled(&e) => {
+                runtime.interrupt_turn(&mut history, &content);
             }
             Err(e) => {
-                let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+                let sanitized = crate::providers::sanitize_api_error(&format!("{e:#}"));
+                let code = crate::error::classify(&e).map(crate::error::ErrorKind::code);
                 let err = serde_json::json!({
                     "type": "error",
-                    "message": sanitized,
+                    "code": code,
+                    "message": if code.is_some() {
+                        crate::error::user_message(&e)
+                    } else {
+                        sanitized.clone()
+                    },
                 });
                 let _ = sender.send(Message::Text(err.to_string().into())).await;
 
@@ -163,5 +322,65 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                 }));
             }
         }
+        if closed {
+            break;
+        }
+    }
+}
+
+/// Turn a tool progress line (`⏳ shell: ls -la`, `✅ shell (1s)`) into a
+/// `tool_call` or `tool_result` message. Other progress is dropped.
+fn progress_event(line: &str) -> Option<serde_json::Value> {
+    let line = line.trim();
+    if let Some(call) = line.strip_prefix('\u{23f3}') {
+        let (name, hint) = call.trim().split_once(": ").unwrap_or((call.trim(), ""));
+        return Some(serde_json::json!({
+            "type": "tool_call",
+            "name": name,
+            "args": {"hint": hint},
+        }));
+    }
+    let (success, finished) = if let Some(rest) = line.strip_prefix('\u{2705}') {
+        (true, rest)
+    } else {
+        (false, line.strip_prefix('\u{274c}')?)
+    };
+    let (name, elapsed) = finished.trim().rsplit_once(" (")?;
+    let elapsed = elapsed.trim_end_matches(')');
+    Some(serde_json::json!({
+        "type": "tool_result",
+        "name": name,
+        "success": success,
+        "output": if success {
+            format!("finished in {elapsed}")
+        } else {
+            format!("failed after {elapsed}")
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_progress_becomes_tool_messages() {
+        assert_eq!(
+            progress_event("\u{23f3} shell: ls -la\n").unwrap(),
+            serde_json::json!({"type": "tool_call", "name": "shell", "args": {"hint": "ls -la"}})
+        );
+        assert_eq!(
+            progress_event("\u{23f3} memory_recall").unwrap()["name"],
+            "memory_recall"
+        );
+        let result = progress_event("\u{274c} web_fetch (3s)").unwrap();
+        assert_eq!(result["name"], "web_fetch");
+        assert_eq!(result["success"], false);
+        assert_eq!(result["output"], "failed after 3s");
+        assert_eq!(
+            progress_event("\u{2705} shell (0s)").unwrap()["type"],
+            "tool_result"
+        );
+        assert!(progress_event("\u{1f914} Thinking...").is_none());
     }
 }
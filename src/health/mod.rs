@@ -20,11 +20,14 @@ pub struct HealthSnapshot {
     pub updated_at: String,
     pub uptime_seconds: u64,
     pub components: BTreeMap<String, ComponentHealth>,
+    /// Point-in-time counts such as queue depths, keyed by name.
+    pub gauges: BTreeMap<String, u64>,
 }
 
 struct HealthRegistry {
     started_at: Instant,
     components: Mutex<BTreeMap<String, ComponentHealth>>,
+    gauges: Mutex<BTreeMap<String, u64>>,
 }
 
 static REGISTRY: OnceLock<HealthRegistry> = OnceLock::new();
@@ -33,6 +36,7 @@ fn registry() -> &'static HealthRegistry {
     REGISTRY.get_or_init(|| HealthRegistry {
         started_at: Instant::now(),
         components: Mutex::new(BTreeMap::new()),
+        gauges: Mutex::new(BTreeMap::new()),
     })
 }
 
@@ -82,14 +86,21 @@ pub fn bump_component_restart(component: &str) {
     });
 }
 
+/// Record the current value of a gauge, e.g. `channel_queue_depth`.
+pub fn set_gauge(name: &str, value: u64) {
+    registry().gauges.lock().insert(name.to_string(), value);
+}
+
 pub fn snapshot() -> HealthSnapshot {
     let components = registry().components.lock().clone();
+    let gauges = registry().gauges.lock().clone();
 
     HealthSnapshot {
         pid: std::process::id(),
         updated_at: now_rfc3339(),
         uptime_seconds: registry().started_at.elapsed().as_secs(),
         components,
+        gauges,
     }
 }
 
@@ -181,4 +192,15 @@ mod tests {
         assert!(component_json["last_ok"].as_str().is_some());
         assert!(json["uptime_seconds"].as_u64().is_some());
     }
+
+    #[test]
+    fn set_gauge_overwrites_previous_value() {
+        let gauge = unique_component("health-gauge");
+
+        set_gauge(&gauge, 3);
+        set_gauge(&gauge, 1);
+
+        assert_eq!(snapshot().gauges.get(&gauge), Some(&1));
+        assert_eq!(snapshot_json()["gauges"][&gauge], 1);
+    }
 }
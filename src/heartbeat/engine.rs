@@ -1,11 +1,20 @@
 use crate::config::HeartbeatConfig;
 use crate::observability::{Observer, ObserverEvent};
+use crate::tasks::Task;
 use anyhow::Result;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::time::{self, Duration};
 use tracing::{info, warn};
 
+/// Outcome of a review-mode heartbeat tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewDecision {
+    /// 0 (nothing to report) to 10 (urgent).
+    pub importance: u8,
+    pub message: String,
+}
+
 /// Heartbeat engine — reads HEARTBEAT.md and executes tasks periodically
 pub struct HeartbeatEngine {
     config: HeartbeatConfig,
@@ -64,9 +73,17 @@ impl HeartbeatEngine {
         Ok(self.collect_tasks().await?.len())
     }
 
-    /// Read HEARTBEAT.md and return all parsed tasks.
+    /// Read the checklist file (HEARTBEAT.md unless `checklist` is set) and
+    /// return all parsed tasks.
     pub async fn collect_tasks(&self) -> Result<Vec<String>> {
-        let heartbeat_path = self.workspace_dir.join("HEARTBEAT.md");
+        let heartbeat_path = self.workspace_dir.join(
+            self.config
+                .checklist
+                .as_deref()
+                .map(str::trim)
+                .filter(|path| !path.is_empty())
+                .unwrap_or("HEARTBEAT.md"),
+        );
         if !heartbeat_path.exists() {
             return Ok(Vec::new());
         }
@@ -85,6 +102,54 @@ impl HeartbeatEngine {
             .collect()
     }
 
+    /// Prompt for a review-mode tick: the checklist, the pending background
+    /// tasks, and the JSON reply the agent must give.
+    pub fn review_prompt(checklist: &[String], pending: &[Task]) -> String {
+        let mut prompt = String::from(
+            "[Heartbeat Review] Go through the checklist and pending background tasks below. \
+             Use tools to check whatever needs checking, then decide whether anything needs \
+             the user's attention right now.\n",
+        );
+        if !checklist.is_empty() {
+            prompt.push_str("\nChecklist:\n");
+            for item in checklist {
+                prompt.push_str(&format!("- {item}\n"));
+            }
+        }
+        if !pending.is_empty() {
+            prompt.push_str("\nPending background tasks:\n");
+            for task in pending {
+                prompt.push_str(&format!("- {}\n", task.summary_line()));
+            }
+        }
+        prompt.push_str(
+            "\nReply with only a JSON object: {\"importance\": <0-10>, \"message\": \"<what to \
+             tell the user>\"}. Use 0 and an empty message when nothing needs attention; 10 \
+             means urgent.",
+        );
+        prompt
+    }
+
+    /// Parse the agent's review reply. `None` when it holds no JSON object
+    /// with an `importance`.
+    pub fn parse_review(output: &str) -> Option<ReviewDecision> {
+        #[derive(serde::Deserialize)]
+        struct Reply {
+            importance: f64,
+            #[serde(default)]
+            message: String,
+        }
+        let start = output.find('{')?;
+        let end = output.rfind('}')?;
+        let reply: Reply = serde_json::from_str(output.get(start..=end)?).ok()?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let importance = reply.importance.clamp(0.0, 10.0).round() as u8;
+        Some(ReviewDecision {
+            importance,
+            message: reply.message.trim().to_string(),
+        })
+    }
+
     /// Create a default HEARTBEAT.md if it doesn't exist
     pub async fn ensure_heartbeat_file(workspace_dir: &Path) -> Result<()> {
         let path = workspace_dir.join("HEARTBEAT.md");
@@ -285,6 +350,47 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
 
+    #[test]
+    fn review_prompt_lists_checklist_and_pending_tasks() {
+        let task = Task {
+            id: 3,
+            title: "Migrate wiki".into(),
+            steps: vec!["export".into(), "import".into()],
+            results: vec!["done".into()],
+            status: crate::tasks::TaskStatus::Running,
+            notify: None,
+            error: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let prompt = HeartbeatEngine::review_prompt(&["Check disk space".into()], &[task]);
+        assert!(prompt.contains("Checklist:\n- Check disk space\n"));
+        assert!(prompt.contains("- #3 [running] Migrate wiki (1/2 steps)"));
+        assert!(prompt.contains("\"importance\""));
+    }
+
+    #[test]
+    fn parse_review_reads_json_reply() {
+        let reply = "Checked everything.\n```json\n{\"importance\": 8, \"message\": \" Disk is 95% full \"}\n```";
+        assert_eq!(
+            HeartbeatEngine::parse_review(reply),
+            Some(ReviewDecision {
+                importance: 8,
+                message: "Disk is 95% full".into(),
+            })
+        );
+        assert_eq!(
+            HeartbeatEngine::parse_review("{\"importance\": 42}")
+                .unwrap()
+                .importance,
+            10
+        );
+        assert_eq!(
+            HeartbeatEngine::parse_review("All good, nothing to report."),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn run_returns_immediately_when_disabled() {
         let observer: Arc<dyn Observer> = Arc::new(crate::observability::NoopObserver);
@@ -0,0 +1,56 @@
+# Deutsch
+
+## Interaktive Sitzungen (zeroclaw agent, zeroclaw tui)
+
+interactive-banner = 🦀 ZeroClaw – interaktiver Modus
+interactive-hint = /help zeigt die Befehle. Wer tippt, während der Agent arbeitet, unterbricht und lenkt ihn um.
+session-resumed = Sitzung { $session } fortgesetzt ({ $count } frühere Nachrichten).
+clear-warning = Das löscht die aktuelle Unterhaltung und das gesamte Sitzungsgedächtnis.
+clear-keeps-core = Kernerinnerungen (dauerhafte Fakten und Vorlieben) bleiben erhalten.
+confirm-continue = Fortfahren? [j/N]
+answer-yes = j ja
+cancelled = Abgebrochen.
+conversation-cleared =
+    { $count ->
+        [0] Unterhaltung gelöscht.
+       *[other] Unterhaltung gelöscht ({ $count } Gedächtniseinträge entfernt).
+    }
+edit-empty = Leere Nachricht; nichts gesendet.
+interrupting = ↪️  Unterbreche, um deiner neuen Nachricht zu folgen...
+interrupted = Unterbrochen.
+auto-compacted = 🧹 Verlauf automatisch verdichtet
+help-header = Verfügbare Befehle:
+
+command-help = Diese Hilfe anzeigen
+command-new = Eine neue Unterhaltung beginnen
+command-edit = Die Nachricht in $EDITOR verfassen, ausgehend von <text>
+command-model = Das Modell anzeigen oder wechseln
+command-models = Anbieter auflisten oder den Anbieter wechseln
+command-tools = Werkzeuge auflisten oder für diese Sitzung ein- und ausschalten
+command-memory = Das Gedächtnis durchsuchen
+command-pin = <text> oder die letzte Antwort für die ganze Sitzung im Kontext halten
+command-unpin = Eine oder alle Pins entfernen
+command-context = Zeigen, was das Kontextfenster belegt und wie viele Tokens es kostet
+command-export = Die Unterhaltung als Markdown unter exports/ speichern
+command-budget = Verbrauch gegenüber den [budget]-Limits zeigen oder die Limits aufheben
+command-debug = Prompt, Werkzeuge und Modellantworten jeder Runde nach state/debug/ schreiben
+command-dryrun = Werkzeuge, die etwas ändern, nur beschreiben lassen, was sie tun würden
+command-undo = Die Dateiänderungen der letzten Runde zurücknehmen, oder die der Runde <n>
+command-quit = Den Chat verlassen
+
+## Befehle im Chat auf Kanälen
+
+history-cleared = Unterhaltung gelöscht. Wir fangen neu an.
+budget-disabled = Budgetlimits sind deaktiviert.
+budget-lifted = Budgetlimits für diese Unterhaltung aufgehoben. Der Verbrauch wird weiter gezählt; `/new` stellt sie wieder her.
+export-done = Unterhaltung exportiert nach `{ $path }`.
+export-failed = Export fehlgeschlagen: { $error }
+model-empty = Die Modell-ID darf nicht leer sein. Verwende `/model <model-id>`.
+model-switched = Modell für diese Sitzung auf `{ $model }` (Anbieter `{ $provider }`) gewechselt.
+provider-switched =
+    Anbieter für diese Sitzung auf `{ $provider }` gewechselt. Aktuelles Modell ist `{ $model }`.
+    Mit `/model <model-id>` wählst du ein Modell, das dieser Anbieter bedient.
+provider-failed =
+    Anbieter `{ $provider }` konnte nicht gestartet werden. Route unverändert.
+    Details: { $error }
+provider-unknown = Unbekannter Anbieter `{ $provider }`. `/models` listet die gültigen Anbieter.
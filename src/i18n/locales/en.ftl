@@ -0,0 +1,42 @@
+# English strings. This file is the reference: every message here must exist
+# in the other locales too. Slash command summaries live in
+# `agent::commands::COMMANDS`; translations add them as `command-<name>`.
+
+## Interactive sessions (zeroclaw agent, zeroclaw tui)
+
+interactive-banner = 🦀 ZeroClaw Interactive Mode
+interactive-hint = Type /help for commands. Typing while the agent works interrupts and steers it.
+session-resumed = Resumed session { $session } ({ $count } earlier messages).
+clear-warning = This will clear the current conversation and delete all session memory.
+clear-keeps-core = Core memories (long-term facts/preferences) will be preserved.
+confirm-continue = Continue? [y/N]
+# Space-separated answers that confirm a prompt. English `y` and `yes` always work.
+answer-yes = y yes
+cancelled = Cancelled.
+conversation-cleared =
+    { $count ->
+        [0] Conversation cleared.
+       *[other] Conversation cleared ({ $count } memory entries removed).
+    }
+edit-empty = Empty message; nothing sent.
+interrupting = ↪️  Interrupting to follow your new message...
+interrupted = Interrupted.
+auto-compacted = 🧹 Auto-compaction complete
+help-header = Available commands:
+
+## In-chat commands on channels
+
+history-cleared = Conversation history cleared. Starting fresh.
+budget-disabled = Budget limits are disabled.
+budget-lifted = Budget limits lifted for this conversation. Usage is still counted; `/new` restores them.
+export-done = Conversation exported to `{ $path }`.
+export-failed = Export failed: { $error }
+model-empty = Model ID cannot be empty. Use `/model <model-id>`.
+model-switched = Model switched to `{ $model }` for provider `{ $provider }` in this sender session.
+provider-switched =
+    Provider switched to `{ $provider }` for this sender session. Current model is `{ $model }`.
+    Use `/model <model-id>` to set a provider-compatible model.
+provider-failed =
+    Failed to initialize provider `{ $provider }`. Route unchanged.
+    Details: { $error }
+provider-unknown = Unknown provider `{ $provider }`. Use `/models` to list valid providers.
@@ -0,0 +1,56 @@
+# Español
+
+## Sesiones interactivas (zeroclaw agent, zeroclaw tui)
+
+interactive-banner = 🦀 ZeroClaw – modo interactivo
+interactive-hint = Escribe /help para ver los comandos. Escribir mientras el agente trabaja lo interrumpe y lo redirige.
+session-resumed = Sesión { $session } reanudada ({ $count } mensajes anteriores).
+clear-warning = Esto borra la conversación actual y toda la memoria de la sesión.
+clear-keeps-core = Los recuerdos básicos (hechos y preferencias duraderos) se conservan.
+confirm-continue = ¿Continuar? [s/N]
+answer-yes = s si sí
+cancelled = Cancelado.
+conversation-cleared =
+    { $count ->
+        [0] Conversación borrada.
+       *[other] Conversación borrada ({ $count } entradas de memoria eliminadas).
+    }
+edit-empty = Mensaje vacío; no se envió nada.
+interrupting = ↪️  Interrumpiendo para seguir tu nuevo mensaje...
+interrupted = Interrumpido.
+auto-compacted = 🧹 Historial compactado automáticamente
+help-header = Comandos disponibles:
+
+command-help = Mostrar esta ayuda
+command-new = Empezar una conversación nueva
+command-edit = Redactar el mensaje en $EDITOR, a partir de <text>
+command-model = Mostrar o cambiar el modelo
+command-models = Listar proveedores o cambiar de proveedor
+command-tools = Listar herramientas o activarlas y desactivarlas en esta sesión
+command-memory = Buscar en la memoria
+command-pin = Mantener <text>, o la última respuesta, en el contexto durante toda la sesión
+command-unpin = Quitar un anclaje, o todos
+command-context = Mostrar qué ocupa la ventana de contexto y cuántos tokens cuesta
+command-export = Guardar la conversación como markdown en exports/
+command-budget = Mostrar el consumo frente a los límites de [budget], o levantarlos
+command-debug = Volcar el prompt, las herramientas y las respuestas del modelo de cada turno en state/debug/
+command-dryrun = Hacer que las herramientas que cambian algo describan lo que harían sin hacerlo
+command-undo = Deshacer los cambios en archivos del último turno, o los del turno <n>
+command-quit = Salir del chat
+
+## Comandos en los canales
+
+history-cleared = Historial de la conversación borrado. Empezamos de nuevo.
+budget-disabled = Los límites de presupuesto están desactivados.
+budget-lifted = Límites de presupuesto levantados para esta conversación. El consumo se sigue contando; `/new` los restablece.
+export-done = Conversación exportada a `{ $path }`.
+export-failed = La exportación falló: { $error }
+model-empty = El ID del modelo no puede estar vacío. Usa `/model <model-id>`.
+model-switched = Modelo cambiado a `{ $model }` (proveedor `{ $provider }`) en esta sesión.
+provider-switched =
+    Proveedor cambiado a `{ $provider }` en esta sesión. El modelo actual es `{ $model }`.
+    Usa `/model <model-id>` para elegir un modelo compatible.
+provider-failed =
+    No se pudo inicializar el proveedor `{ $provider }`. Ruta sin cambios.
+    Detalles: { $error }
+provider-unknown = Proveedor desconocido `{ $provider }`. `/models` lista los proveedores válidos.
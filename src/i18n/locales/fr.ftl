@@ -0,0 +1,56 @@
+# Français
+
+## Sessions interactives (zeroclaw agent, zeroclaw tui)
+
+interactive-banner = 🦀 ZeroClaw – mode interactif
+interactive-hint = Tapez /help pour les commandes. Écrire pendant que l’agent travaille l’interrompt et le réoriente.
+session-resumed = Session { $session } reprise ({ $count } messages précédents).
+clear-warning = Cela efface la conversation en cours et toute la mémoire de session.
+clear-keeps-core = Les souvenirs essentiels (faits et préférences durables) sont conservés.
+confirm-continue = Continuer ? [o/N]
+answer-yes = o oui
+cancelled = Annulé.
+conversation-cleared =
+    { $count ->
+        [0] Conversation effacée.
+       *[other] Conversation effacée ({ $count } entrées de mémoire supprimées).
+    }
+edit-empty = Message vide ; rien n’a été envoyé.
+interrupting = ↪️  Interruption pour suivre votre nouveau message...
+interrupted = Interrompu.
+auto-compacted = 🧹 Historique compacté automatiquement
+help-header = Commandes disponibles :
+
+command-help = Afficher cette aide
+command-new = Commencer une nouvelle conversation
+command-edit = Rédiger le message dans $EDITOR, à partir de <text>
+command-model = Afficher ou changer le modèle
+command-models = Lister les fournisseurs ou changer de fournisseur
+command-tools = Lister les outils ou les activer et désactiver pour cette session
+command-memory = Chercher dans la mémoire
+command-pin = Garder <text>, ou la dernière réponse, dans le contexte pour toute la session
+command-unpin = Retirer un épinglage, ou tous
+command-context = Montrer ce qui occupe la fenêtre de contexte et son coût en tokens
+command-export = Enregistrer la conversation en markdown dans exports/
+command-budget = Montrer la consommation face aux limites [budget], ou les lever
+command-debug = Écrire le prompt, les outils et les réponses du modèle de chaque tour dans state/debug/
+command-dryrun = Faire décrire aux outils qui modifient quelque chose ce qu'ils feraient, sans le faire
+command-undo = Annuler les modifications de fichiers du dernier tour, ou celles du tour <n>
+command-quit = Quitter le chat
+
+## Commandes dans les canaux
+
+history-cleared = Historique de conversation effacé. On repart de zéro.
+budget-disabled = Les limites de budget sont désactivées.
+budget-lifted = Limites de budget levées pour cette conversation. La consommation reste comptée ; `/new` les rétablit.
+export-done = Conversation exportée vers `{ $path }`.
+export-failed = Échec de l’export : { $error }
+model-empty = L’identifiant du modèle ne peut pas être vide. Utilisez `/model <model-id>`.
+model-switched = Modèle changé pour `{ $model }` (fournisseur `{ $provider }`) dans cette session.
+provider-switched =
+    Fournisseur changé pour `{ $provider }` dans cette session. Le modèle actuel est `{ $model }`.
+    Utilisez `/model <model-id>` pour choisir un modèle compatible.
+provider-failed =
+    Impossible d’initialiser le fournisseur `{ $provider }`. Route inchangée.
+    Détails : { $error }
+provider-unknown = Fournisseur inconnu `{ $provider }`. `/models` liste les fournisseurs valides.
@@ -0,0 +1,277 @@
+//! Localized user-facing strings.
+//!
+//! What ZeroClaw itself prints and sends — the interactive session chrome of
+//! `zeroclaw agent` and `zeroclaw tui`, `/help`, and the replies of in-chat
+//! commands on channels — is looked up by message ID in the Fluent files
+//! under `src/i18n/locales/`, which are compiled in. `[locale] language`
+//! picks the language; unset, it follows `LC_ALL`, `LC_MESSAGES` and
+//! `LANG`. Anything a translation lacks falls back to English.
+//!
+//! `[locale] reply_language` is separate: [`reply_language_prompt`] asks the
+//! model to answer in that language, whatever the interface uses.
+
+use crate::config::LocaleConfig;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use parking_lot::RwLock;
+use std::sync::{Arc, OnceLock};
+use unic_langid::LanguageIdentifier;
+
+/// Shipped locales by primary language subtag. English comes first and is
+/// the fallback for every other locale.
+const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("locales/en.ftl")),
+    ("de", include_str!("locales/de.ftl")),
+    ("es", include_str!("locales/es.ftl")),
+    ("fr", include_str!("locales/fr.ftl")),
+];
+
+/// Names used in the reply-language instruction for common tags.
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("ar", "Arabic"),
+    ("de", "German"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("hi", "Hindi"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+    ("sv", "Swedish"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("vi", "Vietnamese"),
+    ("zh", "Chinese"),
+];
+
+/// Messages of one language, with English behind it.
+pub struct Localizer {
+    language: &'static str,
+    /// The chosen locale first, English last.
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Localizer {
+    /// Strings for `language`, a BCP 47 or POSIX tag (`de`, `fr-CA`,
+    /// `es_ES.UTF-8`). Unknown languages get English.
+    pub fn new(language: &str) -> Self {
+        let wanted = primary_subtag(language);
+        let (code, source) = LOCALES
+            .iter()
+            .copied()
+            .find(|(code, _)| *code == wanted)
+            .unwrap_or(LOCALES[0]);
+        let mut bundles = vec![bundle(code, source)];
+        if code != LOCALES[0].0 {
+            bundles.push(bundle(LOCALES[0].0, LOCALES[0].1));
+        }
+        Self {
+            language: code,
+            bundles,
+        }
+    }
+
+    /// Primary subtag of the locale in use, e.g. `de`.
+    pub fn language(&self) -> &'static str {
+        self.language
+    }
+
+    /// Message `id` formatted with `args`, or `None` when no locale has it.
+    pub fn format(&self, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        self.bundles.iter().find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                tracing::debug!("Errors formatting message {id}: {errors:?}");
+            }
+            Some(text.into_owned())
+        })
+    }
+}
+
+fn bundle(code: &str, source: &str) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = code.parse().expect("shipped locale tags are valid");
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // Bidi isolation marks show up as stray characters in terminals and chat apps.
+    bundle.set_use_isolating(false);
+    let resource =
+        FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+            tracing::warn!("Errors parsing the {code} locale: {errors:?}");
+            resource
+        });
+    if let Err(errors) = bundle.add_resource(resource) {
+        tracing::warn!("Errors loading the {code} locale: {errors:?}");
+    }
+    bundle
+}
+
+/// `de` for `de`, `de-AT` or `de_AT.UTF-8`.
+fn primary_subtag(tag: &str) -> String {
+    tag.split(['-', '_', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// The interface language: `[locale] language`, else the POSIX locale
+/// environment, else English.
+fn interface_language(config: &LocaleConfig) -> String {
+    let configured = config
+        .language
+        .as_deref()
+        .map(str::trim)
+        .filter(|language| !language.is_empty());
+    if let Some(language) = configured {
+        return language.to_string();
+    }
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .unwrap_or_else(|| "en".to_string())
+}
+
+static LOCALIZER: OnceLock<RwLock<Arc<Localizer>>> = OnceLock::new();
+
+fn localizer_lock() -> &'static RwLock<Arc<Localizer>> {
+    LOCALIZER.get_or_init(|| RwLock::new(Arc::new(Localizer::new("en"))))
+}
+
+/// Use `[locale]` from now on.
+pub fn configure(config: &LocaleConfig) {
+    let localizer = Localizer::new(&interface_language(config));
+    tracing::debug!(language = localizer.language(), "Interface language set");
+    *localizer_lock().write() = Arc::new(localizer);
+}
+
+/// Message `id` in the interface language; the ID itself if it is unknown.
+pub fn t(id: &str) -> String {
+    localizer_lock()
+        .read()
+        .format(id, None)
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Message `id` with its `{ $name }` placeables filled from `args`.
+pub fn t_args(id: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    localizer_lock()
+        .read()
+        .format(id, Some(&fluent_args))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Message `id`, or `fallback` when no locale has it. For strings whose
+/// English text lives in code, such as slash command summaries.
+pub fn t_or(id: &str, fallback: &str) -> String {
+    localizer_lock()
+        .read()
+        .format(id, None)
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// Whether `answer` confirms a `[y/N]` prompt in the interface language.
+pub fn is_yes(answer: &str) -> bool {
+    let answer = answer.trim().to_lowercase();
+    matches!(answer.as_str(), "y" | "yes")
+        || t("answer-yes").split_whitespace().any(|yes| yes == answer)
+}
+
+/// System prompt section asking for replies in `[locale] reply_language`.
+pub fn reply_language_prompt(config: &LocaleConfig) -> Option<String> {
+    let language = config
+        .reply_language
+        .as_deref()
+        .map(str::trim)
+        .filter(|language| !language.is_empty())?;
+    let name = LANGUAGE_NAMES
+        .iter()
+        .find(|(code, _)| language.eq_ignore_ascii_case(code))
+        .map_or(language, |(_, name)| *name);
+    Some(format!(
+        "\n\n## Language\n\nAlways reply in {name}, unless the user explicitly asks for another \
+         language. Keep code, commands, file paths and quoted text as they are.\n"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_ids(source: &str) -> Vec<&str> {
+        source
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id))
+            .collect()
+    }
+
+    #[test]
+    fn every_locale_translates_every_message_and_command() {
+        let english = message_ids(LOCALES[0].1);
+        assert!(english.contains(&"history-cleared"));
+        for (code, source) in &LOCALES[1..] {
+            let ids = message_ids(source);
+            for id in &english {
+                assert!(ids.contains(id), "{code} lacks {id}");
+            }
+            for command in crate::agent::commands::COMMANDS {
+                let id = format!("command-{}", command.name.trim_start_matches('/'));
+                assert!(ids.contains(&id.as_str()), "{code} lacks {id}");
+            }
+        }
+    }
+
+    #[test]
+    fn messages_format_with_arguments_and_plurals() {
+        let german = Localizer::new("de_DE.UTF-8");
+        assert_eq!(german.language(), "de");
+        let mut args = FluentArgs::new();
+        args.set("count", 3);
+        assert_eq!(
+            german
+                .format("conversation-cleared", Some(&args))
+                .as_deref(),
+            Some("Unterhaltung gelöscht (3 Gedächtniseinträge entfernt).")
+        );
+        let mut args = FluentArgs::new();
+        args.set("count", 0);
+        assert_eq!(
+            Localizer::new("en")
+                .format("conversation-cleared", Some(&args))
+                .as_deref(),
+            Some("Conversation cleared.")
+        );
+
+        let unknown = Localizer::new("tlh");
+        assert_eq!(unknown.language(), "en");
+        assert_eq!(
+            unknown.format("cancelled", None).as_deref(),
+            Some("Cancelled.")
+        );
+        assert_eq!(unknown.format("no-such-message", None), None);
+    }
+
+    #[test]
+    fn reply_language_names_common_tags() {
+        let mut config = LocaleConfig::default();
+        assert_eq!(reply_language_prompt(&config), None);
+        config.reply_language = Some("DE".into());
+        assert!(reply_language_prompt(&config)
+            .unwrap()
+            .contains("Always reply in German,"));
+        config.reply_language = Some("Brazilian Portuguese".into());
+        assert!(reply_language_prompt(&config)
+            .unwrap()
+            .contains("Always reply in Brazilian Portuguese,"));
+    }
+}
@@ -36,37 +36,51 @@
 )]
 
 use clap::Subcommand;
+use clap_complete::ArgValueCandidates;
 use serde::{Deserialize, Serialize};
 
 pub mod agent;
 pub(crate) mod approval;
 pub(crate) mod auth;
 pub mod channels;
+pub(crate) mod completions;
 pub mod config;
+pub mod conversations;
 pub(crate) mod cost;
 pub(crate) mod cron;
 pub(crate) mod daemon;
 pub(crate) mod doctor;
+pub mod error;
+pub(crate) mod feeds;
 pub mod gateway;
 pub(crate) mod hardware;
 pub(crate) mod health;
 pub(crate) mod heartbeat;
 pub mod hooks;
+pub(crate) mod i18n;
 pub(crate) mod identity;
 pub(crate) mod integrations;
+pub(crate) mod markdown;
+pub mod mcp;
 pub mod memory;
 pub(crate) mod migration;
 pub(crate) mod multimodal;
+pub(crate) mod notifications;
 pub mod observability;
 pub(crate) mod onboard;
 pub mod peripherals;
+pub mod plugins;
 pub mod providers;
 pub mod rag;
+pub(crate) mod resilience;
 pub mod runtime;
 pub(crate) mod security;
 pub(crate) mod service;
 pub(crate) mod skills;
+pub(crate) mod tasks;
+pub(crate) mod time;
 pub mod tools;
+pub(crate) mod tui;
 pub(crate) mod tunnel;
 pub(crate) mod util;
 
@@ -89,6 +103,17 @@ pub enum ServiceCommands {
     Uninstall,
 }
 
+/// Commands for a running `zeroclaw daemon`, sent over its control socket
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DaemonCommands {
+    /// Show the running daemon's PID, uptime and component health
+    Status,
+    /// Stop the daemon after in-flight messages finish
+    Stop,
+    /// Restart the daemon in place (same PID) to pick up a new binary or config
+    Restart,
+}
+
 /// Channel management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ChannelCommands {
@@ -136,6 +161,14 @@ Examples:
         /// Telegram identity to allow (username without '@' or numeric user ID)
         identity: String,
     },
+    /// Show or clear messages queued for delivery when a channel reconnects
+    Outbox {
+        /// Only this channel (e.g. telegram)
+        channel: Option<String>,
+        /// Drop the queued messages instead of listing them
+        #[arg(long)]
+        clear: bool,
+    },
 }
 
 /// Skills management subcommands
@@ -200,17 +233,20 @@ Examples:
         /// Command to run
         command: String,
     },
-    /// Add a one-shot scheduled task at an RFC3339 timestamp
+    /// Add a one-shot scheduled task at a given time
     #[command(long_about = "\
-Add a one-shot task that fires at a specific UTC timestamp.
+Add a one-shot task that fires at a specific time.
 
-The timestamp must be in RFC 3339 format (e.g. 2025-01-15T14:00:00Z).
+The time is an RFC 3339 timestamp (e.g. 2025-01-15T14:00:00Z), a wall-clock
+time in [locale] timezone (e.g. 2025-01-15T09:00) or a phrase such as
+'tomorrow 9am', 'next friday 3pm' or 'in 2 hours'.
 
 Examples:
   zeroclaw cron add-at 2025-01-15T14:00:00Z 'Send reminder'
-  zeroclaw cron add-at 2025-12-31T23:59:00Z 'Happy New Year!'")]
+  zeroclaw cron add-at 'next friday 3pm' 'Send reminder'
+  zeroclaw cron add-at '2025-12-31T23:59' 'Happy New Year!'")]
     AddAt {
-        /// One-shot timestamp in RFC3339 format
+        /// When to run: RFC 3339, YYYY-MM-DDTHH:MM or a phrase like 'tomorrow 9am'
         at: String,
         /// Command to run
         command: String,
@@ -290,6 +326,62 @@ Examples:
     },
 }
 
+/// Declared schedule subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ScheduleCommands {
+    /// List `[schedule.<name>]` entries with their next and last runs
+    List,
+    /// Run a schedule entry immediately, including delivery
+    RunNow {
+        /// Entry name (the `<name>` in `[schedule.<name>]`)
+        name: String,
+    },
+}
+
+/// Background task subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TaskCommands {
+    /// List tasks, newest first
+    List {
+        /// Include completed, failed and cancelled tasks
+        #[arg(long)]
+        all: bool,
+        /// Maximum number of tasks to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Show a task's steps and checkpointed results
+    Show {
+        /// Task number
+        id: i64,
+    },
+    /// Cancel a queued or running task
+    Cancel {
+        /// Task number
+        id: i64,
+    },
+}
+
+/// System prompt subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PromptCommands {
+    /// Print the fully expanded system prompt
+    Render {
+        /// Channel to render for (sets `{{ channel }}` and channel instructions)
+        #[arg(long, default_value = "cli")]
+        channel: String,
+        /// Value for `{{ user.name }}` (defaults to $USER)
+        #[arg(long)]
+        user: Option<String>,
+        /// Sample message used to recall `{{ memories }}`
+        #[arg(long)]
+        message: Option<String>,
+        /// Also print the variables, which were used and which were left as written
+        #[arg(long)]
+        debug: bool,
+    },
+}
+
 /// Memory management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MemoryCommands {
@@ -327,6 +419,145 @@ pub enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Store a memory entry (embedded for semantic recall)
+    Add {
+        /// Text to remember (omit when using --file)
+        content: Option<String>,
+        /// Entry key (default: generated)
+        #[arg(long)]
+        key: Option<String>,
+        /// Category (default: core, or document with --file)
+        #[arg(long)]
+        category: Option<String>,
+        /// Chunk and store a text/markdown document
+        #[arg(long, conflicts_with = "content")]
+        file: Option<std::path::PathBuf>,
+    },
+    /// Search memories by meaning (vector + keyword hybrid)
+    Search {
+        /// Search query
+        query: String,
+        /// Maximum number of results
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+    /// Delete a memory entry by key (supports prefix match)
+    Forget {
+        /// Memory key to delete
+        key: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Summarize old episodic memories, prune expired entries, and remove duplicates
+    Compact,
+}
+
+/// Conversation session subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SessionCommands {
+    /// List sessions, most recently active first
+    List {
+        /// Include sessions archived for inactivity
+        #[arg(long)]
+        all: bool,
+        /// Only sessions from this channel
+        #[arg(long)]
+        channel: Option<String>,
+        /// Maximum number of sessions to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Show a session's details and latest messages
+    Show {
+        /// Session ID or name
+        #[arg(add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: String,
+        /// Number of messages to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Clear a session's messages and tool calls but keep the session
+    Reset {
+        /// Session ID or name
+        #[arg(add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Delete a session with its messages and tool calls
+    Delete {
+        /// Session ID or name
+        #[arg(add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Name a session so it can be referred to by name
+    Rename {
+        /// Session ID or current name
+        #[arg(add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: String,
+        /// New name (omit to remove the name)
+        name: Option<String>,
+    },
+    /// Mark the session's latest message as a checkpoint to branch from later
+    Checkpoint {
+        /// Session ID or name
+        #[arg(add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: String,
+        /// Checkpoint name (default: cp1, cp2, …)
+        name: Option<String>,
+    },
+}
+
+/// Audit log subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AuditCommands {
+    /// Show audit entries, oldest first
+    Show {
+        /// Only entries newer than this: a duration (30m, 24h, 7d) or a date
+        #[arg(long)]
+        since: Option<String>,
+        /// Only executions of this tool
+        #[arg(long)]
+        tool: Option<String>,
+        /// Only entries from this session
+        #[arg(long)]
+        session: Option<String>,
+        /// Maximum number of entries to display (the most recent)
+        #[arg(long, default_value = "50")]
+        limit: usize,
+        /// Print raw JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check the hash chain for edited or removed entries
+    Verify,
+}
+
+/// Chat channel pairing subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PairCommands {
+    /// List codes waiting for approval and paired users
+    List,
+    /// Approve the sender who was given a pairing code
+    Approve {
+        /// Code the sender received
+        code: String,
+        /// Role to grant: admin, member or guest (default: [security.pairing] default_role)
+        #[arg(long)]
+        role: Option<String>,
+    },
+    /// Remove a paired user
+    Revoke {
+        /// Channel name, e.g. telegram
+        channel: String,
+        /// Sender ID as shown by `zeroclaw pair list`
+        sender: String,
+    },
 }
 
 /// Integration subcommands
@@ -34,11 +34,11 @@
 
 use anyhow::{bail, Context, Result};
 use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::ArgValueCandidates;
 use dialoguer::{Input, Password};
 use serde::{Deserialize, Serialize};
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use tracing::{info, warn};
-use tracing_subscriber::{fmt, EnvFilter};
 
 fn parse_temperature(s: &str) -> std::result::Result<f64, String> {
     let t: f64 = s.parse().map_err(|e| format!("{e}"))?;
@@ -48,38 +48,67 @@ fn parse_temperature(s: &str) -> std::result::Result<f64, String> {
     Ok(t)
 }
 
+/// `--file` and `--image` arguments, in that order.
+fn cli_attachments(
+    files: Vec<std::path::PathBuf>,
+    images: Vec<std::path::PathBuf>,
+) -> Vec<agent::attachments::Attachment> {
+    files
+        .into_iter()
+        .map(agent::attachments::Attachment::file)
+        .chain(
+            images
+                .into_iter()
+                .map(agent::attachments::Attachment::image),
+        )
+        .collect()
+}
+
 mod agent;
 mod approval;
 mod auth;
 mod channels;
+mod completions;
 mod rag {
     pub use zeroclaw::rag::*;
 }
 mod config;
+mod conversations;
 mod cost;
 mod cron;
 mod daemon;
 mod doctor;
+mod error;
+mod feeds;
 mod gateway;
 mod hardware;
 mod health;
 mod heartbeat;
 mod hooks;
+mod i18n;
 mod identity;
 mod integrations;
+mod markdown;
+mod mcp;
 mod memory;
 mod migration;
 mod multimodal;
+mod notifications;
 mod observability;
 mod onboard;
 mod peripherals;
+mod plugins;
 mod providers;
+mod resilience;
 mod runtime;
 mod security;
 mod service;
 mod skillforge;
 mod skills;
+mod tasks;
+mod time;
 mod tools;
+mod tui;
 mod tunnel;
 mod util;
 
@@ -87,8 +116,9 @@ use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    AuditCommands, ChannelCommands, CronCommands, DaemonCommands, HardwareCommands,
+    IntegrationCommands, MigrateCommands, PairCommands, PeripheralCommands, PromptCommands,
+    ScheduleCommands, ServiceCommands, SessionCommands, SkillCommands, TaskCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -127,6 +157,10 @@ struct Cli {
     #[arg(long, global = true)]
     config_dir: Option<String>,
 
+    /// Apply a [profile.<name>] section of config.toml (default: $ZEROCLAW_PROFILE)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -135,7 +169,7 @@ struct Cli {
 enum Commands {
     /// Initialize your workspace and configuration
     Onboard {
-        /// Run the full interactive wizard (default is quick setup)
+        /// Run the full interactive wizard (default on a terminal when no quick-setup flags are given)
         #[arg(long)]
         interactive: bool,
 
@@ -173,6 +207,9 @@ Examples:
   zeroclaw agent                              # interactive session
   zeroclaw agent -m \"Summarize today's logs\"  # single message
   zeroclaw agent -p anthropic --model claude-sonnet-4-20250514
+  zeroclaw agent --plain                      # raw markdown replies
+  zeroclaw agent --editor                     # compose the message in $EDITOR
+  zeroclaw agent -m \"What's in this?\" --image chart.png
   zeroclaw agent --peripheral nucleo-f401re:/dev/ttyACM0")]
     Agent {
         /// Single message mode (don't enter interactive mode)
@@ -184,7 +221,7 @@ Examples:
         provider: Option<String>,
 
         /// Model to use
-        #[arg(long)]
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::models))]
         model: Option<String>,
 
         /// Temperature (0.0 - 2.0)
@@ -194,6 +231,323 @@ Examples:
         /// Attach a peripheral (board:path, e.g. nucleo-f401re:/dev/ttyACM0)
         #[arg(long)]
         peripheral: Vec<String>,
+
+        /// Continue a stored conversation session by ID or name (see `zeroclaw sessions list`)
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: Option<String>,
+
+        /// Run past the `[budget]` session and daily limits (usage is still recorded)
+        #[arg(long)]
+        budget_override: bool,
+
+        /// Print replies as raw markdown instead of formatted text
+        #[arg(long)]
+        plain: bool,
+
+        /// Compose the message in $EDITOR (starting from --message, if given) and send it
+        #[arg(long)]
+        editor: bool,
+
+        /// Attach a local file to the message (repeatable; images are detected)
+        #[arg(long, value_name = "PATH")]
+        file: Vec<std::path::PathBuf>,
+
+        /// Attach a local image to the message (repeatable)
+        #[arg(long, value_name = "PATH")]
+        image: Vec<std::path::PathBuf>,
+
+        /// Write each turn's system prompt, tool schemas, messages and model responses to state/debug/
+        #[arg(long)]
+        debug_prompts: bool,
+
+        /// Report what write-capable tools would do instead of running them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Ask the agent a single question, optionally about local files
+    #[command(long_about = "\
+Ask the agent a single question, optionally about local files.
+
+Shorthand for `zeroclaw agent -m`. Files given with --file or --image are \
+copied into the workspace and attached the way channel uploads are: \
+images (detected from their content, not the extension) go to the model \
+as vision input, other files are handed to the document tools. Sizes are \
+limited by [multimodal].max_image_size_mb and [documents].max_file_mb.
+
+Examples:
+  zeroclaw ask \"What changed in the release notes?\" --file CHANGELOG.md
+  zeroclaw ask --file report.pdf --image chart.png \"summarize\"
+  zeroclaw ask --session work \"and the follow-up?\"")]
+    Ask {
+        /// The question (words are joined with spaces)
+        #[arg(required = true, num_args = 1..)]
+        message: Vec<String>,
+
+        /// Attach a local file (repeatable; images are detected)
+        #[arg(long, value_name = "PATH")]
+        file: Vec<std::path::PathBuf>,
+
+        /// Attach a local image (repeatable)
+        #[arg(long, value_name = "PATH")]
+        image: Vec<std::path::PathBuf>,
+
+        /// Provider to use (openrouter, anthropic, openai, openai-codex)
+        #[arg(short, long)]
+        provider: Option<String>,
+
+        /// Model to use
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::models))]
+        model: Option<String>,
+
+        /// Continue a stored conversation session by ID or name
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: Option<String>,
+
+        /// Print the reply as raw markdown instead of formatted text
+        #[arg(long)]
+        plain: bool,
+
+        /// Write the system prompt, tool schemas, messages and model responses to state/debug/
+        #[arg(long)]
+        debug_prompts: bool,
+
+        /// Report what write-capable tools would do instead of running them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Run one prompt non-interactively, for scripts and pipelines
+    #[command(long_about = "\
+Run one prompt non-interactively, for scripts and pipelines.
+
+Reads the prompt from the arguments, or from stdin when given `-`. Only \
+the final answer is written to stdout, as plain text; logs and errors go \
+to stderr. Tool approvals are not prompted for, as in the daemon.
+
+Exit codes: 0 answered, 1 the run failed, 2 no prompt, 3 a [budget] limit \
+was reached, 4 [guardrails] blocked the prompt.
+
+With --json, stdout is a single JSON object with ok, exit_code, answer or \
+error, session, provider, model, usage (input_tokens, output_tokens, \
+total_tokens, llm_calls) and duration_ms.
+
+Examples:
+  git diff | zeroclaw run - > review.md
+  echo \"Summarize: $(cat notes.txt)\" | zeroclaw run - --json | jq .usage
+  zeroclaw run \"What is 2+2?\" --model gpt-4o-mini")]
+    Run {
+        /// The prompt, or `-` to read it from stdin
+        #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+        prompt: Vec<String>,
+
+        /// Print a JSON object with the answer, token usage and exit code
+        #[arg(long)]
+        json: bool,
+
+        /// Provider to use (openrouter, anthropic, openai, openai-codex)
+        #[arg(short, long)]
+        provider: Option<String>,
+
+        /// Model to use
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::models))]
+        model: Option<String>,
+
+        /// Temperature (0.0 - 2.0)
+        #[arg(short, long, default_value = "0.7", value_parser = parse_temperature)]
+        temperature: f64,
+
+        /// Continue a stored conversation session by ID or name
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: Option<String>,
+    },
+
+    /// Run a prompt template over every record of a JSONL file
+    #[command(long_about = "\
+Run a prompt template over every record of a JSONL file.
+
+Each input line is a JSON object; its fields fill the template's \
+{{ placeholders }}, along with {{ item }} (the whole record) and {{ line }}. \
+Each prompt runs through the agent, or with --no-tools as a single model \
+call. Results are appended to the output as JSON lines with id, line, ok, \
+answer or error, attempts, usage, cost_usd and duration_ms. The id is the \
+record's `id` field, or its line number.
+
+Rerunning the same command skips the records that already have a \
+successful result in the output and retries the rest; --restart starts \
+over. A failing record is retried with backoff. A [budget] limit stops the \
+batch. The closing summary on stdout lists totals and the estimated cost.
+
+Exit codes: 0 every record succeeded, 1 some failed, 3 a [budget] limit \
+was reached.
+
+Examples:
+  zeroclaw batch --input items.jsonl --template prompt.tmpl --output results.jsonl
+  zeroclaw batch --input rows.jsonl --template clean.tmpl --output out.jsonl --concurrency 4 --no-tools")]
+    Batch {
+        /// JSONL file with one record per line
+        #[arg(long, value_name = "PATH")]
+        input: std::path::PathBuf,
+
+        /// Prompt template filled from each record
+        #[arg(long, value_name = "PATH")]
+        template: std::path::PathBuf,
+
+        /// JSONL file results are appended to; also the resume checkpoint
+        #[arg(long, value_name = "PATH")]
+        output: std::path::PathBuf,
+
+        /// Records processed at once
+        #[arg(long, default_value_t = 1)]
+        concurrency: usize,
+
+        /// Extra attempts for a failing record
+        #[arg(long, default_value_t = 2)]
+        retries: u32,
+
+        /// Send each prompt as a single model call, without tools or memory
+        #[arg(long)]
+        no_tools: bool,
+
+        /// Ignore earlier results in the output and overwrite it
+        #[arg(long)]
+        restart: bool,
+
+        /// Provider to use (openrouter, anthropic, openai, openai-codex)
+        #[arg(short, long)]
+        provider: Option<String>,
+
+        /// Model to use
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::models))]
+        model: Option<String>,
+
+        /// Temperature (0.0 - 2.0)
+        #[arg(short, long, default_value = "0.7", value_parser = parse_temperature)]
+        temperature: f64,
+    },
+
+    /// Run a YAML suite of prompts with assertions against one or more models
+    #[command(long_about = "\
+Run a YAML suite of prompts with assertions against one or more models.
+
+Each case's prompt runs through the agent, with the configured system \
+prompt and tools, once per model. Assertions: contains, not_contains, \
+regex, json_schema (the answer, or the first JSON value in it) and rubric \
+(graded PASS/FAIL by judge_model, or by the model under test). Prints a \
+pass/fail matrix with per-model latency and estimated cost, then the \
+failed assertions.
+
+Exit codes: 0 every case passed, 1 a case failed or the suite is invalid, \
+3 a [budget] limit was reached.
+
+Examples:
+  zeroclaw eval evals/support.yaml
+  zeroclaw eval evals/support.yaml --model openai/gpt-4o-mini --model anthropic/claude-sonnet-4
+  zeroclaw eval evals/support.yaml --case greeting --json")]
+    Eval {
+        /// The suite file
+        suite: std::path::PathBuf,
+
+        /// Model to run, instead of the suite's `models` (repeatable)
+        #[arg(long = "model", add = ArgValueCandidates::new(crate::completions::models))]
+        models: Vec<String>,
+
+        /// Provider to use (openrouter, anthropic, openai, openai-codex)
+        #[arg(short, long)]
+        provider: Option<String>,
+
+        /// Model grading rubric assertions, instead of the suite's `judge_model`
+        #[arg(long)]
+        judge_model: Option<String>,
+
+        /// Only run cases whose name contains this
+        #[arg(long = "case")]
+        filter: Option<String>,
+
+        /// Temperature (0.0 - 2.0)
+        #[arg(short, long, default_value = "0.7", value_parser = parse_temperature)]
+        temperature: f64,
+
+        /// Print the results as JSON instead of the matrix
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Ask several models the same prompt and show the answers side by side
+    #[command(long_about = "\
+Ask several models the same prompt and show the answers side by side.
+
+Every model gets its own agent, with the configured system prompt and \
+tools, and all of them run at once. Entries are `model` for the \
+configured provider or `provider:model`. With --session, each model \
+continues the same stored conversation (nothing is added to it). \
+Write-capable tools run as a dry run so the change is not made once per \
+model.
+
+The answers are shown in columns fitted to the terminal width, each \
+headed by latency, tokens and the cost estimated from [cost].prices; when \
+the columns would be too narrow they are shown one after another.
+
+Examples:
+  zeroclaw compare \"Explain CRDTs in two sentences\" --models gpt-4o,anthropic:claude-sonnet-4,ollama:llama3
+  git diff | zeroclaw compare - --models openai:gpt-4o-mini,openai:gpt-4o --json")]
+    Compare {
+        /// The prompt, or `-` to read it from stdin
+        #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+        prompt: Vec<String>,
+
+        /// Comma-separated `model` or `provider:model` entries
+        #[arg(long, required = true, value_delimiter = ',')]
+        models: Vec<String>,
+
+        /// Continue a stored conversation session by ID or name
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: Option<String>,
+
+        /// Temperature (0.0 - 2.0)
+        #[arg(short, long, default_value = "0.7", value_parser = parse_temperature)]
+        temperature: f64,
+
+        /// Print the answers as JSON instead of columns
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Chat with the agent in a full-screen terminal UI
+    #[command(long_about = "\
+Chat with the agent in a full-screen terminal UI.
+
+Runs the same agent as `zeroclaw agent` in a scrollable transcript, with \
+the reply streaming in and tool calls listed in an activity sidebar \
+(shown on terminals at least 80 columns wide). Enter sends; Alt+Enter or \
+Ctrl+J adds a line. Typing while the agent works steers it; Esc \
+interrupts. PgUp/PgDn and the mouse wheel scroll. Approval prompts appear \
+in the transcript and are answered in the input box.
+
+Examples:
+  zeroclaw tui
+  zeroclaw tui --session work
+  zeroclaw tui -p anthropic --model claude-sonnet-4-20250514")]
+    Tui {
+        /// Provider to use (openrouter, anthropic, openai, openai-codex)
+        #[arg(short, long)]
+        provider: Option<String>,
+
+        /// Model to use
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::models))]
+        model: Option<String>,
+
+        /// Temperature (0.0 - 2.0)
+        #[arg(short, long, default_value = "0.7", value_parser = parse_temperature)]
+        temperature: f64,
+
+        /// Continue a stored conversation session by ID or name (see `zeroclaw sessions list`)
+        #[arg(long, add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: Option<String>,
+
+        /// Show replies as raw markdown instead of formatted text
+        #[arg(long)]
+        plain: bool,
     },
 
     /// Start the gateway server (webhooks, websockets)
@@ -219,6 +573,35 @@ Examples:
         host: Option<String>,
     },
 
+    /// Serve the agent over an OpenAI-compatible HTTP API
+    #[command(long_about = "\
+Serve the agent over an OpenAI-compatible HTTP API.
+
+With --openai-compat, exposes POST /v1/chat/completions (including \
+\"stream\": true server-sent events) and GET /v1/models, so editors, chat \
+UIs and OpenAI SDKs can talk to the agent as if it were a model. Every \
+request runs a full agent turn with tools and memory; the client keeps \
+the conversation history. Authenticate with a gateway pairing token as \
+the API key.
+
+Examples:
+  zeroclaw serve --openai-compat
+  zeroclaw serve --openai-compat --port 8787
+  OPENAI_BASE_URL=http://127.0.0.1:8787/v1 OPENAI_API_KEY=<token> some-openai-client")]
+    Serve {
+        /// Expose the OpenAI-compatible chat completions API (currently the only mode)
+        #[arg(long, required = true)]
+        openai_compat: bool,
+
+        /// Port to listen on
+        #[arg(short, long, default_value_t = gateway::openai_compat::DEFAULT_PORT)]
+        port: u16,
+
+        /// Host to bind to; defaults to config gateway.host
+        #[arg(long)]
+        host: Option<String>,
+    },
+
     /// Start long-running autonomous runtime (gateway + channels + heartbeat + scheduler)
     #[command(long_about = "\
 Start the long-running autonomous daemon.
@@ -231,10 +614,18 @@ production or as an always-on assistant.
 Use 'zeroclaw service install' to register the daemon as an OS \
 service (systemd/launchd) for auto-start on boot.
 
+The daemon writes daemon.pid next to config.toml and refuses to start \
+twice. SIGTERM or Ctrl+C stop it after in-flight messages finish, and \
+under systemd it reports readiness with sd_notify. The status, stop and \
+restart subcommands talk to it over daemon.sock.
+
 Examples:
   zeroclaw daemon                   # use config defaults
   zeroclaw daemon -p 9090           # gateway on port 9090
-  zeroclaw daemon --host 127.0.0.1  # localhost only")]
+  zeroclaw daemon --host 127.0.0.1  # localhost only
+  zeroclaw daemon status            # ask the running daemon
+  zeroclaw daemon stop              # drain in-flight messages and exit
+  zeroclaw daemon restart           # restart in place")]
     Daemon {
         /// Port to listen on (use 0 for random available port); defaults to config gateway.port
         #[arg(short, long)]
@@ -243,6 +634,9 @@ Examples:
         /// Host to bind to; defaults to config gateway.host
         #[arg(long)]
         host: Option<String>,
+
+        #[command(subcommand)]
+        daemon_command: Option<DaemonCommands>,
     },
 
     /// Manage OS service lifecycle (launchd/systemd user service)
@@ -255,14 +649,54 @@ Examples:
         service_command: ServiceCommands,
     },
 
-    /// Run diagnostics for daemon/scheduler/channel freshness
+    /// Diagnose config, credentials, databases, sandbox, daemon and clock
+    #[command(long_about = "\
+Diagnose the installation and print pass/warn/fail results with hints.
+
+Checks the config file, workspace and disk space, SQLite database \
+integrity, Landlock/seccomp sandbox availability, daemon freshness and \
+local tools. Unless --offline is given it also contacts the default \
+provider, runs every configured channel's health check (credentials) and \
+compares the local clock against a reference server.
+
+Examples:
+  zeroclaw doctor
+  zeroclaw doctor --offline
+  zeroclaw doctor models --provider openai")]
     Doctor {
+        /// Skip checks that need the network (provider, channels, clock)
+        #[arg(long)]
+        offline: bool,
+
         #[command(subcommand)]
         doctor_command: Option<DoctorCommands>,
     },
 
     /// Show system status (full details)
-    Status,
+    #[command(long_about = "\
+Show configuration plus live health of the running daemon.
+
+The live section lists each channel's connection state, provider and tool \
+circuit breakers, channel and background-task queue depths, active \
+sessions, memory store size and daemon uptime. It is fetched over the \
+daemon's control socket, falling back to the last daemon_state.json.
+
+With --watch only the live section is shown, redrawn in place every \
+--interval seconds until Ctrl+C.
+
+Examples:
+  zeroclaw status
+  zeroclaw status --watch
+  zeroclaw status --watch --interval 5")]
+    Status {
+        /// Refresh the live section in place until Ctrl+C
+        #[arg(long)]
+        watch: bool,
+
+        /// Seconds between refreshes with --watch
+        #[arg(long, default_value_t = 2, requires = "watch")]
+        interval: u64,
+    },
 
     /// Engage, inspect, and resume emergency-stop states.
     ///
@@ -317,6 +751,89 @@ Examples:
         cron_command: CronCommands,
     },
 
+    /// List and run prompts and tool pipelines declared in `[schedule]`
+    #[command(long_about = "\
+List and run prompts and tool pipelines declared in config.
+
+Each [schedule.<name>] entry has a cron expression and a prompt, a tool \
+pipeline, or both. The daemon's scheduler runs them and delivers the \
+output to the entry's channel. Entries also appear in `zeroclaw cron \
+list` as jobs with ID schedule:<name>.
+
+Examples:
+  zeroclaw schedule list
+  zeroclaw schedule run-now morning-digest")]
+    Schedule {
+        #[command(subcommand)]
+        schedule_command: ScheduleCommands,
+    },
+
+    /// List, inspect and cancel background tasks
+    #[command(long_about = "\
+List, inspect and cancel background tasks.
+
+Tasks are multi-step jobs the agent enqueues with its `task` tool. The \
+daemon runs them one step at a time, checkpointing each step's result so \
+a restart resumes where it stopped.
+
+Examples:
+  zeroclaw tasks list
+  zeroclaw tasks list --all
+  zeroclaw tasks show 3
+  zeroclaw tasks cancel 3")]
+    Tasks {
+        #[command(subcommand)]
+        task_command: TaskCommands,
+    },
+
+    /// Preview the system prompt after template expansion
+    #[command(long_about = "\
+Preview the system prompt after template expansion.
+
+Workspace persona files and the system prompt may use template \
+variables such as {{ now }}, {{ user.name }}, {{ channel }}, {{ tools }} \
+and {{ memories }}. `render` prints the prompt exactly as the agent \
+would see it; --debug also lists the variables and any expressions left \
+as written.
+
+Examples:
+  zeroclaw prompt render
+  zeroclaw prompt render --channel telegram --user alice
+  zeroclaw prompt render --message \"trip plans\" --debug")]
+    Prompt {
+        #[command(subcommand)]
+        prompt_command: PromptCommands,
+    },
+
+    /// Re-run a recorded session against its recorded responses
+    #[command(long_about = "\
+Re-run a recorded session against its recorded responses.
+
+With [replay] enabled, every model request/response and tool result of a \
+session is recorded under state/replay/. Replay feeds the recorded \
+responses and tool output back through the agent loop, so no tokens are \
+spent and no tool runs, and reports the first model request that the \
+current build constructs differently. Exits non-zero when any turn \
+diverges. Without a session, lists the recorded sessions.
+
+Examples:
+  zeroclaw replay
+  zeroclaw replay cli:3f2a9c1e-...
+  zeroclaw replay telegram_alice --turn 4 --verbose")]
+    Replay {
+        /// Session ID or name (omit to list recordings)
+        #[arg(add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: Option<String>,
+
+        /// Replay only this turn (1-based)
+        #[arg(long)]
+        turn: Option<usize>,
+
+        /// Show each turn's start time and recorded reply
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
     /// Manage provider model catalogs
     Models {
         #[command(subcommand)]
@@ -405,36 +922,283 @@ Examples:
         peripheral_command: zeroclaw::PeripheralCommands,
     },
 
-    /// Manage agent memory (list, get, stats, clear)
+    /// Manage agent memory (list, get, add, search, forget, compact, stats, clear)
     #[command(long_about = "\
 Manage agent memory entries.
 
-List, inspect, and clear memory entries stored by the agent. \
-Supports filtering by category and session, pagination, and \
-batch clearing with confirmation.
+List, inspect, add, search, and clear memory entries stored by the \
+agent. Added entries and documents are embedded for semantic recall \
+when an embedding provider is configured. Supports filtering by \
+category and session, pagination, and batch clearing with confirmation.
 
 Examples:
   zeroclaw memory stats
   zeroclaw memory list
   zeroclaw memory list --category core --limit 10
   zeroclaw memory get <key>
+  zeroclaw memory add \"Prefers metric units\"
+  zeroclaw memory add --file notes/architecture.md
+  zeroclaw memory search \"deployment checklist\"
+  zeroclaw memory forget <key>
+  zeroclaw memory compact
   zeroclaw memory clear --category conversation --yes")]
     Memory {
         #[command(subcommand)]
         memory_command: MemoryCommands,
     },
 
+    /// Index a directory of documents into memory for cited answers
+    #[command(long_about = "\
+Index a directory of notes and documents into memory.
+
+Markdown and text files (and PDFs when built with the rag-pdf \
+feature) are split into chunks, embedded, and stored with their file \
+path and line span. Answers that draw on indexed chunks cite them as \
+path:Lstart-Lend. Re-running only re-indexes files that changed and \
+removes chunks of deleted files; --watch keeps doing so on an interval.
+
+Examples:
+  zeroclaw index ~/notes
+  zeroclaw index ./docs --watch
+  zeroclaw index ./docs --watch --interval 30")]
+    Index {
+        /// Directory to index
+        dir: std::path::PathBuf,
+
+        /// Keep running and re-index changed files
+        #[arg(long)]
+        watch: bool,
+
+        /// Seconds between re-scans in watch mode
+        #[arg(long, default_value_t = 10)]
+        interval: u64,
+    },
+
+    /// Search past conversation messages by keyword
+    #[command(long_about = "\
+Search past conversation messages by keyword.
+
+Every word must appear in a message; results are ranked by relevance and \
+show the time, channel, role and session of each match. Dates accept \
+YYYY-MM-DD or RFC 3339; --until includes the whole given day. --open \
+continues the best-matching session in interactive mode.
+
+Examples:
+  zeroclaw search \"staging deploy\"
+  zeroclaw search invoice --channel telegram --role user
+  zeroclaw search backup --since 2026-01-01 --until 2026-01-31
+  zeroclaw search \"release notes\" --open")]
+    Search {
+        /// Words to search for
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Only messages from this channel (cli, telegram, discord, ...)
+        #[arg(long)]
+        channel: Option<String>,
+
+        /// Only messages with this role (user or assistant)
+        #[arg(long)]
+        role: Option<String>,
+
+        /// Only messages on or after this date
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only messages on or before this date
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Maximum number of results
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+
+        /// Reopen the best-matching session in interactive mode
+        #[arg(long)]
+        open: bool,
+    },
+
+    /// List, inspect, reset, delete and name conversation sessions
+    #[command(long_about = "\
+Manage conversation sessions.
+
+Channel sessions are keyed by channel, contact and (under the default \
+per_thread policy) thread. Sessions idle longer than \
+[conversations].archive_after_idle_hours are archived under \
+<id>@<timestamp> and hidden from `list` unless --all is given. Anywhere \
+a session is expected, its name works too.
+
+Examples:
+  zeroclaw sessions list
+  zeroclaw sessions list --all --channel telegram
+  zeroclaw sessions show telegram_alice
+  zeroclaw sessions rename telegram_alice alice
+  zeroclaw sessions checkpoint alice before-refactor
+  zeroclaw sessions reset alice --yes
+  zeroclaw sessions delete cli:1b2c...")]
+    Sessions {
+        #[command(subcommand)]
+        session_command: SessionCommands,
+    },
+
+    /// Review the tool execution audit log
+    #[command(long_about = "\
+Review the tool execution audit log.
+
+With [security.audit] enabled (the default), every tool call the agent \
+makes is appended to the audit log: time, channel, session, full \
+arguments, success, a SHA-256 of the output and the approval decision. \
+Each entry carries the hash of the one before it, so `verify` detects \
+entries that were edited or removed.
+
+Examples:
+  zeroclaw audit show --since 24h
+  zeroclaw audit show --tool shell --since 2026-01-01
+  zeroclaw audit show --session telegram_alice --json
+  zeroclaw audit verify")]
+    Audit {
+        #[command(subcommand)]
+        audit_command: AuditCommands,
+    },
+
+    /// Approve chat channel users who asked to pair
+    #[command(long_about = "\
+Approve chat channel users who asked to pair.
+
+With [security.pairing] enabled, anyone on a chat channel who is not \
+paired yet gets a one-time code instead of an answer. Approving the code \
+pairs the sender with a role: admin (every tool), user (every tool except \
+high-risk ones such as shell) or guest (conversation only). Approval takes \
+effect on the sender's next message, without restarting the daemon.
+
+Examples:
+  zeroclaw pair list
+  zeroclaw pair approve K7QH2M9X
+  zeroclaw pair approve K7QH2M9X --role admin
+  zeroclaw pair revoke telegram 123456789")]
+    Pair {
+        #[command(subcommand)]
+        pair_command: PairCommands,
+    },
+
+    /// Branch a session from an earlier message or checkpoint
+    #[command(long_about = "\
+Branch a session from an earlier message or checkpoint.
+
+The branch is a new session holding a copy of the history up to and \
+including the given message; the original session is left untouched. \
+--from takes a message ID (as shown by `zeroclaw sessions show`) or the \
+name of a checkpoint set with `zeroclaw sessions checkpoint`. Branches \
+are listed under their parent in `zeroclaw sessions list`.
+
+Examples:
+  zeroclaw branch alice --from 42
+  zeroclaw branch alice --from before-refactor --name alice-retry
+  zeroclaw agent --session alice~1")]
+    Branch {
+        /// Session ID or name to branch from
+        #[arg(add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: String,
+
+        /// Message ID or checkpoint name to branch at
+        #[arg(long)]
+        from: String,
+
+        /// Name for the new branch
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Export a conversation session as Markdown, JSON or HTML
+    #[command(long_about = "\
+Export a conversation session as Markdown, JSON or HTML.
+
+JSON is a complete archive that `zeroclaw import` can restore on another \
+machine. Markdown and HTML are for reading and sharing: tool calls appear \
+before the reply they fed into and attachments render as images or links. \
+Find session IDs with `zeroclaw search`.
+
+Examples:
+  zeroclaw export telegram_alice
+  zeroclaw export cli:1b2c... --format html -o chat.html
+  zeroclaw export telegram_alice --format json -o alice.json")]
+    Export {
+        /// Session ID
+        #[arg(add = ArgValueCandidates::new(crate::completions::sessions))]
+        session: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = conversations::archive::ExportFormat::Markdown)]
+        format: conversations::archive::ExportFormat,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+
+    /// Import a conversation session from a JSON export
+    #[command(long_about = "\
+Import a conversation session from a JSON export.
+
+The session keeps its ID, messages, tool calls and timestamps. Importing \
+over an existing session fails unless --replace is given; --as stores it \
+under a different ID instead.
+
+Examples:
+  zeroclaw import alice.json
+  zeroclaw import alice.json --as alice-archive
+  zeroclaw import alice.json --replace")]
+    Import {
+        /// JSON file written by `zeroclaw export --format json`
+        file: std::path::PathBuf,
+
+        /// Store the session under this ID
+        #[arg(long = "as", value_name = "ID")]
+        rename: Option<String>,
+
+        /// Overwrite an existing session with the same ID
+        #[arg(long)]
+        replace: bool,
+    },
+
+    /// Serve ZeroClaw tools and memory over MCP (stdio)
+    #[command(long_about = "\
+Serve ZeroClaw's tools and memory as a Model Context Protocol server.
+
+Speaks MCP over stdin/stdout so other agents and editors (Claude Desktop, \
+IDE assistants) can call your configured tools and read your memory. \
+Logs go to stderr. The usual autonomy and workspace rules apply to \
+every call.
+
+Examples:
+  zeroclaw mcp-serve
+  zeroclaw mcp-serve --tool memory_recall --tool web_search
+  zeroclaw mcp-serve --no-resources")]
+    McpServe {
+        /// Only expose these tools (repeatable; default: all)
+        #[arg(long = "tool")]
+        tools: Vec<String>,
+
+        /// Do not expose memory entries as resources
+        #[arg(long)]
+        no_resources: bool,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
 
-Inspect and export configuration settings. Use 'schema' to dump \
-the full JSON Schema for the config file, which documents every \
-available key, type, and default value.
+Inspect and export configuration settings. Use 'validate' to check \
+config.toml without starting anything, and 'schema' to dump the full \
+JSON Schema for the config file, which documents every available key, \
+type, and default value.
 
 Examples:
+  zeroclaw config validate            # check syntax, keys, paths, credentials
+  zeroclaw config validate --probe    # also contact the provider and channels
+  zeroclaw config validate --file ./staging.toml
   zeroclaw config schema              # print JSON Schema to stdout
-  zeroclaw config schema > schema.json")]
+  zeroclaw config schema --output ~/.zeroclaw/config.schema.json")]
     Config {
         #[command(subcommand)]
         config_command: ConfigCommands,
@@ -444,23 +1208,56 @@ Examples:
     #[command(long_about = "\
 Generate shell completion scripts for `zeroclaw`.
 
-The script is printed to stdout so it can be sourced directly:
+The script is printed to stdout so it can be sourced directly. It asks \
+`zeroclaw` for candidates on each Tab press, so besides subcommands and \
+flags it completes stored session names (`--session`, `zeroclaw sessions \
+show`, ...) and model IDs cached by `zeroclaw models refresh` (`--model`). \
+Re-source it after upgrading.
 
 Examples:
   source <(zeroclaw completions bash)
-  zeroclaw completions zsh > ~/.zfunc/_zeroclaw
+  source <(zeroclaw completions zsh)
   zeroclaw completions fish > ~/.config/fish/completions/zeroclaw.fish")]
     Completions {
         /// Target shell
         #[arg(value_enum)]
         shell: CompletionShell,
     },
+
+    /// Print the zeroclaw(1) man page to stdout
+    #[command(long_about = "\
+Print the zeroclaw(1) man page to stdout, generated from the CLI definitions.
+
+Examples:
+  zeroclaw man | man -l -
+  zeroclaw man > ~/.local/share/man/man1/zeroclaw.1")]
+    Man,
 }
 
 #[derive(Subcommand, Debug)]
 enum ConfigCommands {
+    /// Check the config file and report problems with their file:line:column
+    Validate {
+        /// Config file to check (default: the active config.toml)
+        #[arg(long)]
+        file: Option<std::path::PathBuf>,
+
+        /// Also contact the configured provider and channels
+        #[arg(long)]
+        probe: bool,
+    },
+    /// Upgrade config.toml to the current schema version, keeping a backup
+    Migrate {
+        /// Print the changes without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Dump the full configuration JSON Schema to stdout
-    Schema,
+    Schema {
+        /// Write the schema to this file instead of stdout
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -486,17 +1283,23 @@ enum EstopSubcommands {
 
 #[derive(Subcommand, Debug)]
 enum AuthCommands {
-    /// Login with OAuth (OpenAI Codex or Gemini)
+    /// Login with OAuth (OpenAI Codex or Gemini) or store an API key in the OS keyring
     Login {
-        /// Provider (`openai-codex` or `gemini`)
-        #[arg(long)]
-        provider: String,
+        /// Provider, e.g. `openai`, `anthropic`, `openrouter`, `openai-codex` or `gemini`
+        #[arg(value_name = "PROVIDER", required_unless_present = "provider_flag")]
+        provider: Option<String>,
+        /// Provider (same as PROVIDER)
+        #[arg(long = "provider", conflicts_with = "provider")]
+        provider_flag: Option<String>,
         /// Profile name (default: default)
         #[arg(long, default_value = "default")]
         profile: String,
         /// Use OAuth device-code flow
         #[arg(long)]
         device_code: bool,
+        /// Store an API key in the OS keyring instead of logging in with OAuth (gemini)
+        #[arg(long)]
+        api_key: bool,
     },
     /// Complete OAuth by pasting redirect URL or auth code
     PasteRedirect {
@@ -655,6 +1458,47 @@ enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Store a memory entry (embedded for semantic recall)
+    Add {
+        /// Text to remember (omit when using --file)
+        content: Option<String>,
+        /// Entry key (default: generated)
+        #[arg(long)]
+        key: Option<String>,
+        /// Category (default: core, or document with --file)
+        #[arg(long)]
+        category: Option<String>,
+        /// Chunk and store a text/markdown document
+        #[arg(long, conflicts_with = "content")]
+        file: Option<std::path::PathBuf>,
+    },
+    /// Search memories by meaning (vector + keyword hybrid)
+    Search {
+        /// Search query
+        query: String,
+        /// Maximum number of results
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+    /// Delete a memory entry by key (supports prefix match)
+    Forget {
+        /// Memory key to delete
+        key: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Summarize old episodic memories, prune expired entries, and remove duplicates
+    Compact,
+}
+
+/// The profile from `--profile`, falling back to `$ZEROCLAW_PROFILE`.
+fn selected_profile(cli: &Cli) -> Option<String> {
+    cli.profile
+        .clone()
+        .or_else(|| std::env::var("ZEROCLAW_PROFILE").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
 }
 
 #[tokio::main]
@@ -667,6 +1511,12 @@ async fn main() -> Result<()> {
         eprintln!("Warning: Failed to install default crypto provider: {e:?}");
     }
 
+    // Answer a shell's Tab press (`COMPLETE=<shell> zeroclaw -- …`) before
+    // anything else can write to stdout.
+    clap_complete::CompleteEnv::with_factory(Cli::command)
+        .var(COMPLETE_ENV_VAR)
+        .complete();
+
     let cli = Cli::parse();
 
     if let Some(config_dir) = &cli.config_dir {
@@ -683,15 +1533,108 @@ async fn main() -> Result<()> {
         write_shell_completion(*shell, &mut stdout)?;
         return Ok(());
     }
+    if let Commands::Man = &cli.command {
+        let mut stdout = std::io::stdout().lock();
+        write_man_page(&mut stdout)?;
+        return Ok(());
+    }
 
-    // Initialize logging - respects RUST_LOG env var, defaults to INFO
-    let subscriber = fmt::Subscriber::builder()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .finish();
+    // Validation must report a broken config rather than fail to load it, and
+    // must not create one; it runs before logging so findings stay readable.
+    if let Commands::Config {
+        config_command: ConfigCommands::Validate { file, probe },
+    } = &cli.command
+    {
+        let (path, workspace_dir) = match file {
+            Some(file) => (
+                file.clone(),
+                file.parent()
+                    .unwrap_or_else(|| std::path::Path::new("."))
+                    .join("workspace"),
+            ),
+            None => Config::resolve_paths().await?,
+        };
+        let options = config::validate::Options {
+            profile: selected_profile(&cli),
+            probe: *probe,
+        };
+        let report = config::validate::validate_file(&path, workspace_dir, &options).await;
+        print!("{report}");
+        if report.errors() > 0 {
+            bail!(
+                "{} has {} error(s) and {} warning(s)",
+                path.display(),
+                report.errors(),
+                report.warnings()
+            );
+        }
+        if report.warnings() > 0 {
+            println!(
+                "{} is valid ({} warning(s))",
+                path.display(),
+                report.warnings()
+            );
+        } else {
+            println!("✅ {} is valid", path.display());
+        }
+        return Ok(());
+    }
 
-    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    if let Commands::Config {
+        config_command: ConfigCommands::Migrate { dry_run },
+    } = &cli.command
+    {
+        let (path, _) = Config::resolve_paths().await?;
+        let contents = tokio::fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let (migration, upgraded) = config::migrate::upgrade(&contents)?;
+        if migration.is_noop() {
+            println!(
+                "✅ {} is up to date (schema version {})",
+                path.display(),
+                migration.to
+            );
+            return Ok(());
+        }
+        println!(
+            "{} schema version {} → {}:",
+            path.display(),
+            migration.from,
+            migration.to
+        );
+        for change in &migration.changes {
+            println!("  - {change}");
+        }
+        if *dry_run {
+            println!("Dry run: nothing was written.");
+        } else {
+            let backup = config::migrate::write_with_backup(&path, &upgraded, &migration).await?;
+            println!("✅ Migrated. The original is at {}", backup.display());
+        }
+        return Ok(());
+    }
+
+    // Logging starts from `[logging]` alone; configured secrets are masked
+    // once the full config is loaded below.
+    let console = match cli.command {
+        // stdout carries the MCP protocol or the answer; keep logs on stderr.
+        Commands::McpServe { .. }
+        | Commands::Run { .. }
+        | Commands::Batch { .. }
+        | Commands::Eval { .. }
+        | Commands::Compare { .. } => observability::logging::ConsoleTarget::Stderr,
+        // Log lines would tear through the full-screen UI.
+        Commands::Tui { .. } => observability::logging::ConsoleTarget::Off,
+        _ => observability::logging::ConsoleTarget::Stdout,
+    };
+    let (config_path, _) = Config::resolve_paths().await?;
+    let logging = observability::logging::read_logging_section(&config_path);
+    let config_dir = config_path
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_default();
+    observability::logging::init(&logging, &config_dir, console)?;
 
     // Onboard runs quick setup by default, or the interactive wizard with --interactive.
     // The onboard wizard uses reqwest::blocking internally, which creates its own
@@ -726,6 +1669,16 @@ async fn main() -> Result<()> {
         if channels_only && force {
             bail!("--channels-only does not accept --force");
         }
+        // A bare `zeroclaw onboard` on a terminal walks through the wizard;
+        // quick setup is for scripts and for the flags it accepts.
+        let interactive = interactive
+            || (!channels_only
+                && api_key.is_none()
+                && provider.is_none()
+                && model.is_none()
+                && memory.is_none()
+                && std::io::stdin().is_terminal()
+                && std::io::stdout().is_terminal());
         let config = if channels_only {
             onboard::run_channels_repair_wizard().await
         } else if interactive {
@@ -742,50 +1695,281 @@ async fn main() -> Result<()> {
         }?;
         // Auto-start channels if user said yes during wizard
         if std::env::var("ZEROCLAW_AUTOSTART_CHANNELS").as_deref() == Ok("1") {
+            security::storage_encryption::init(&config.storage.encryption)?;
             channels::start_channels(config).await?;
         }
-        return Ok(());
-    }
+        return Ok(());
+    }
+
+    // All other commands need config loaded first
+    let mut config = Config::load_or_init().await?;
+    if let Some(profile) = selected_profile(&cli) {
+        config.apply_profile(&profile)?;
+        config.validate()?;
+    }
+    config.apply_env_overrides();
+    observability::logging::register_secrets(config.secret_values());
+    resilience::configure(&config.reliability);
+    security::audit::configure(&config);
+    security::redaction::configure(&config.security.redaction);
+    security::roles::configure(&config.security.roles);
+    i18n::configure(&config.locale);
+    time::configure(&config.locale);
+    security::injection::configure(&config.security.injection);
+    security::keyring::configure(&config.secrets);
+    security::storage_encryption::init(&config.storage.encryption)?;
+    observability::runtime_trace::init_from_config(&config.observability, &config.workspace_dir);
+    if config.security.otp.enabled {
+        let config_dir = config
+            .config_path
+            .parent()
+            .context("Config path must have a parent directory")?;
+        let store = security::SecretStore::new(config_dir, config.secrets.encrypt);
+        let (_validator, enrollment_uri) =
+            security::OtpValidator::from_config(&config.security.otp, config_dir, &store)?;
+        if let Some(uri) = enrollment_uri {
+            println!("Initialized OTP secret for ZeroClaw.");
+            println!("Enrollment URI: {uri}");
+        }
+    }
+
+    match cli.command {
+        Commands::Onboard { .. } => unreachable!(),
+        Commands::Completions { .. } | Commands::Man => unreachable!(),
+
+        Commands::Agent {
+            message,
+            provider,
+            model,
+            temperature,
+            peripheral,
+            session,
+            budget_override,
+            plain,
+            editor,
+            file,
+            image,
+            debug_prompts,
+            dry_run,
+        } => {
+            config.budget.override_limits = budget_override;
+            if plain {
+                config.agent.render_markdown = false;
+            }
+            if debug_prompts {
+                config.agent.debug_prompts = true;
+            }
+            if dry_run {
+                config.agent.dry_run = true;
+            }
+            let message = if editor {
+                let Some(composed) = agent::editor::compose(message.as_deref().unwrap_or(""))?
+                else {
+                    bail!("Empty message; nothing sent");
+                };
+                Some(composed)
+            } else {
+                message
+            };
+            let attachments = cli_attachments(file, image);
+            let message = match message {
+                Some(message) => {
+                    Some(agent::attachments::attach(&config, &message, &attachments).await?)
+                }
+                None if !attachments.is_empty() => {
+                    bail!("--file and --image need a message (--message or --editor)")
+                }
+                None => None,
+            };
+            agent::run(
+                config,
+                message,
+                provider,
+                model,
+                temperature,
+                peripheral,
+                true,
+                session,
+            )
+            .await
+            .map(|_| ())
+        }
+
+        Commands::Ask {
+            message,
+            file,
+            image,
+            provider,
+            model,
+            session,
+            plain,
+            debug_prompts,
+            dry_run,
+        } => {
+            if plain {
+                config.agent.render_markdown = false;
+            }
+            if debug_prompts {
+                config.agent.debug_prompts = true;
+            }
+            if dry_run {
+                config.agent.dry_run = true;
+            }
+            let message = agent::attachments::attach(
+                &config,
+                &message.join(" "),
+                &cli_attachments(file, image),
+            )
+            .await?;
+            let temperature = config.default_temperature;
+            agent::run(
+                config,
+                Some(message),
+                provider,
+                model,
+                temperature,
+                Vec::new(),
+                true,
+                session,
+            )
+            .await
+            .map(|_| ())
+        }
 
-    // All other commands need config loaded first
-    let mut config = Config::load_or_init().await?;
-    config.apply_env_overrides();
-    observability::runtime_trace::init_from_config(&config.observability, &config.workspace_dir);
-    if config.security.otp.enabled {
-        let config_dir = config
-            .config_path
-            .parent()
-            .context("Config path must have a parent directory")?;
-        let store = security::SecretStore::new(config_dir, config.secrets.encrypt);
-        let (_validator, enrollment_uri) =
-            security::OtpValidator::from_config(&config.security.otp, config_dir, &store)?;
-        if let Some(uri) = enrollment_uri {
-            println!("Initialized OTP secret for ZeroClaw.");
-            println!("Enrollment URI: {uri}");
+        Commands::Run {
+            prompt,
+            json,
+            provider,
+            model,
+            temperature,
+            session,
+        } => {
+            let code = agent::pipe::run(
+                config,
+                agent::pipe::PipeOptions {
+                    prompt,
+                    provider,
+                    model,
+                    temperature,
+                    session,
+                    json,
+                },
+            )
+            .await;
+            let _ = std::io::stdout().flush();
+            std::process::exit(code);
         }
-    }
-
-    match cli.command {
-        Commands::Onboard { .. } => unreachable!(),
-        Commands::Completions { .. } => unreachable!(),
 
-        Commands::Agent {
-            message,
+        Commands::Batch {
+            input,
+            template,
+            output,
+            concurrency,
+            retries,
+            no_tools,
+            restart,
             provider,
             model,
             temperature,
-            peripheral,
-        } => agent::run(
-            config,
-            message,
+        } => {
+            let summary = agent::batch::run(
+                config,
+                agent::batch::BatchOptions {
+                    input,
+                    template,
+                    output,
+                    concurrency,
+                    retries,
+                    no_tools,
+                    restart,
+                    provider,
+                    model,
+                    temperature,
+                },
+            )
+            .await?;
+            println!("{}", summary.report());
+            let _ = std::io::stdout().flush();
+            std::process::exit(summary.exit_code());
+        }
+
+        Commands::Eval {
+            suite,
+            models,
+            provider,
+            judge_model,
+            filter,
+            temperature,
+            json,
+        } => {
+            let report = agent::eval::run(
+                config,
+                agent::eval::EvalOptions {
+                    suite,
+                    models,
+                    provider,
+                    judge_model,
+                    filter,
+                    temperature,
+                },
+            )
+            .await?;
+            if json {
+                println!("{}", report.to_json());
+            } else {
+                print!("{}", report.render());
+            }
+            let _ = std::io::stdout().flush();
+            std::process::exit(report.exit_code());
+        }
+
+        Commands::Compare {
+            prompt,
+            models,
+            session,
+            temperature,
+            json,
+        } => {
+            let report = agent::compare::run(
+                config,
+                agent::compare::CompareOptions {
+                    prompt,
+                    models,
+                    session,
+                    temperature,
+                },
+            )
+            .await?;
+            if json {
+                println!("{}", report.to_json());
+            } else {
+                print!("{}", report.render(agent::compare::terminal_width()));
+            }
+            let _ = std::io::stdout().flush();
+            std::process::exit(report.exit_code());
+        }
+
+        Commands::Tui {
             provider,
             model,
             temperature,
-            peripheral,
-            true,
-        )
-        .await
-        .map(|_| ()),
+            session,
+            plain,
+        } => {
+            if plain {
+                config.agent.render_markdown = false;
+            }
+            tui::run(config, provider, model, temperature, session).await
+        }
+
+        Commands::Serve {
+            openai_compat: _,
+            port,
+            host,
+        } => {
+            let host = host.unwrap_or_else(|| config.gateway.host.clone());
+            gateway::openai_compat::serve(&host, port, config).await
+        }
 
         Commands::Gateway { port, host } => {
             let port = port.unwrap_or(config.gateway.port);
@@ -798,7 +1982,16 @@ async fn main() -> Result<()> {
             gateway::run_gateway(&host, port, config).await
         }
 
-        Commands::Daemon { port, host } => {
+        Commands::Daemon {
+            daemon_command: Some(daemon_command),
+            ..
+        } => daemon::handle_command(&config, &daemon_command).await,
+
+        Commands::Daemon {
+            port,
+            host,
+            daemon_command: None,
+        } => {
             let port = port.unwrap_or(config.gateway.port);
             let host = host.unwrap_or_else(|| config.gateway.host.clone());
             if port == 0 {
@@ -809,7 +2002,11 @@ async fn main() -> Result<()> {
             daemon::run(config, host, port).await
         }
 
-        Commands::Status => {
+        Commands::Status { watch, interval } => {
+            if watch {
+                let interval = std::time::Duration::from_secs(interval);
+                return daemon::print_live_status(&config, true, interval).await;
+            }
             println!("🦀 ZeroClaw Status");
             println!();
             println!("Version:     {}", env!("CARGO_PKG_VERSION"));
@@ -899,8 +2096,9 @@ async fn main() -> Result<()> {
                 }
             );
             println!("  Boards:    {}", config.peripherals.boards.len());
-
-            Ok(())
+            println!();
+            daemon::print_live_status(&config, false, std::time::Duration::from_secs(interval))
+                .await
         }
 
         Commands::Estop {
@@ -911,6 +2109,18 @@ async fn main() -> Result<()> {
         } => handle_estop_command(&config, estop_command, level, domains, tools),
 
         Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
+        Commands::Schedule { schedule_command } => {
+            cron::handle_schedule_command(schedule_command, &config).await
+        }
+        Commands::Tasks { task_command } => tasks::handle_command(task_command, &config),
+        Commands::Prompt { prompt_command } => {
+            agent::template::handle_command(prompt_command, &config).await
+        }
+        Commands::Replay {
+            session,
+            turn,
+            verbose,
+        } => agent::replay::handle_command(session, turn, verbose, &config).await,
 
         Commands::Models { model_command } => match model_command {
             ModelCommands::Refresh {
@@ -975,7 +2185,10 @@ async fn main() -> Result<()> {
             service::handle_command(&service_command, &config, init_system)
         }
 
-        Commands::Doctor { doctor_command } => match doctor_command {
+        Commands::Doctor {
+            offline,
+            doctor_command,
+        } => match doctor_command {
             Some(DoctorCommands::Models {
                 provider,
                 use_cache,
@@ -992,7 +2205,7 @@ async fn main() -> Result<()> {
                 contains.as_deref(),
                 limit,
             ),
-            None => doctor::run(&config),
+            None => doctor::run(&config, offline).await,
         },
 
         Commands::Channel { channel_command } => match channel_command {
@@ -1015,6 +2228,82 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Index {
+            dir,
+            watch,
+            interval,
+        } => memory::cli::handle_index(&config, &dir, watch, interval).await,
+
+        Commands::Search {
+            query,
+            channel,
+            role,
+            since,
+            until,
+            limit,
+            open,
+        } => {
+            let options = conversations::cli::SearchOptions {
+                channel,
+                role,
+                since,
+                until,
+                limit,
+            };
+            let best = conversations::cli::handle_search(&config, &query.join(" "), &options)?;
+            let temperature = config.default_temperature;
+            match best {
+                Some(session) if open => agent::run(
+                    config,
+                    None,
+                    None,
+                    None,
+                    temperature,
+                    vec![],
+                    true,
+                    Some(session),
+                )
+                .await
+                .map(|_| ()),
+                _ => Ok(()),
+            }
+        }
+
+        Commands::Sessions { session_command } => {
+            conversations::cli::handle_sessions(session_command, &config)
+        }
+
+        Commands::Audit { audit_command } => {
+            security::audit::handle_command(audit_command, &config)
+        }
+
+        Commands::Pair { pair_command } => channels::pairing::handle_command(pair_command, &config),
+
+        Commands::Branch {
+            session,
+            from,
+            name,
+        } => {
+            conversations::cli::handle_branch(&config, &session, &from, name.as_deref()).map(|_| ())
+        }
+
+        Commands::Export {
+            session,
+            format,
+            output,
+        } => conversations::cli::handle_export(&config, &session, format, output.as_deref()),
+
+        Commands::Import {
+            file,
+            rename,
+            replace,
+        } => conversations::cli::handle_import(&config, &file, rename.as_deref(), replace),
+
+        Commands::McpServe {
+            tools,
+            no_resources,
+        } => mcp::server::serve_stdio(config, &tools, !no_resources).await,
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
@@ -1026,14 +2315,23 @@ async fn main() -> Result<()> {
         }
 
         Commands::Config { config_command } => match config_command {
-            ConfigCommands::Schema => {
+            ConfigCommands::Schema { output } => {
                 let schema = schemars::schema_for!(config::Config);
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&schema).expect("failed to serialize JSON Schema")
-                );
+                let json =
+                    serde_json::to_string_pretty(&schema).expect("failed to serialize JSON Schema");
+                match output {
+                    Some(path) => {
+                        std::fs::write(path, format!("{json}\n"))
+                            .with_context(|| format!("Failed to write {}", path.display()))?;
+                        println!("Wrote config JSON Schema to {}", path.display());
+                    }
+                    None => println!("{json}"),
+                }
                 Ok(())
             }
+            ConfigCommands::Validate { .. } | ConfigCommands::Migrate { .. } => {
+                unreachable!("handled before config load")
+            }
         },
     }
 }
@@ -1204,23 +2502,33 @@ fn print_estop_status(state: &security::EstopState) {
     }
 }
 
-fn write_shell_completion<W: Write>(shell: CompletionShell, writer: &mut W) -> Result<()> {
-    use clap_complete::generate;
-    use clap_complete::shells;
+/// Environment variable through which the shell asks for completions.
+const COMPLETE_ENV_VAR: &str = "COMPLETE";
 
-    let mut cmd = Cli::command();
-    let bin_name = cmd.get_name().to_string();
+/// Write the script that registers `zeroclaw` completions with `shell`. It
+/// calls back into `zeroclaw` on each Tab press, so subcommands and flags
+/// come from the clap definitions and session names and model IDs from disk.
+fn write_shell_completion<W: Write>(shell: CompletionShell, writer: &mut W) -> Result<()> {
+    use clap_complete::env::{Bash, Elvish, EnvCompleter, Fish, Powershell, Zsh};
+
+    let completer: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Fish => &Fish,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::PowerShell => &Powershell,
+        CompletionShell::Elvish => &Elvish,
+    };
+    let cmd = Cli::command();
+    let bin_name = cmd.get_name();
+    completer.write_registration(COMPLETE_ENV_VAR, bin_name, bin_name, bin_name, writer)?;
 
-    match shell {
-        CompletionShell::Bash => generate(shells::Bash, &mut cmd, bin_name.clone(), writer),
-        CompletionShell::Fish => generate(shells::Fish, &mut cmd, bin_name.clone(), writer),
-        CompletionShell::Zsh => generate(shells::Zsh, &mut cmd, bin_name.clone(), writer),
-        CompletionShell::PowerShell => {
-            generate(shells::PowerShell, &mut cmd, bin_name.clone(), writer);
-        }
-        CompletionShell::Elvish => generate(shells::Elvish, &mut cmd, bin_name, writer),
-    }
+    writer.flush()?;
+    Ok(())
+}
 
+/// Write the `zeroclaw(1)` man page.
+fn write_man_page<W: Write>(writer: &mut W) -> Result<()> {
+    clap_mangen::Man::new(Cli::command()).render(writer)?;
     writer.flush()?;
     Ok(())
 }
@@ -1381,6 +2689,44 @@ fn format_expiry(profile: &auth::profiles::AuthProfile) -> String {
     }
 }
 
+/// `auth login` for API-key providers: store the key in the OS keyring and
+/// drop a plaintext copy of it from config.toml.
+async fn store_api_key_in_keyring(config: &Config, provider: &str) -> Result<()> {
+    if provider == "openai-codex" {
+        bail!("openai-codex uses OAuth; run `zeroclaw auth login openai-codex` without --api-key");
+    }
+    let key = read_auth_input(&format!("Paste {provider} API key"))?;
+    if key.is_empty() {
+        bail!("API key cannot be empty");
+    }
+    let service = &config.secrets.keyring_service;
+    security::keyring::set_provider_key(service, provider, &key)
+        .context("could not store the API key; set it in config.toml or the environment instead")?;
+    println!(
+        "Stored {provider} API key in the OS keyring (service {service}, account {})",
+        security::keyring::provider_account(provider)
+    );
+
+    let default_provider = config
+        .default_provider
+        .as_deref()
+        .and_then(|name| auth::normalize_provider(name).ok());
+    let plaintext_key = config
+        .api_key
+        .as_deref()
+        .is_some_and(|key| security::secret_refs::reference_for(key).is_none());
+    if plaintext_key && default_provider.as_deref() == Some(provider) {
+        let mut config = config.clone();
+        config.api_key = None;
+        config.save().await?;
+        println!(
+            "Removed the plaintext api_key from {}",
+            config.config_path.display()
+        );
+    }
+    Ok(())
+}
+
 #[allow(clippy::too_many_lines)]
 async fn handle_auth_command(auth_command: AuthCommands, config: &Config) -> Result<()> {
     let auth_service = auth::AuthService::from_config(config);
@@ -1388,10 +2734,17 @@ async fn handle_auth_command(auth_command: AuthCommands, config: &Config) -> Res
     match auth_command {
         AuthCommands::Login {
             provider,
+            provider_flag,
             profile,
             device_code,
+            api_key,
         } => {
-            let provider = auth::normalize_provider(&provider)?;
+            let provider = auth::normalize_provider(
+                provider.or(provider_flag).as_deref().unwrap_or_default(),
+            )?;
+            if api_key || !matches!(provider.as_str(), "openai-codex" | "gemini") {
+                return store_api_key_in_keyring(config, &provider).await;
+            }
             let client = reqwest::Client::new();
 
             match provider.as_str() {
@@ -1564,11 +2917,7 @@ async fn handle_auth_command(auth_command: AuthCommands, config: &Config) -> Res
                     println!("Active profile for openai-codex: {profile}");
                     Ok(())
                 }
-                _ => {
-                    bail!(
-                        "`auth login` supports --provider openai-codex or gemini, got: {provider}"
-                    );
-                }
+                _ => unreachable!("API-key providers are handled above"),
             }
         }
 
@@ -1780,6 +3129,12 @@ async fn handle_auth_command(auth_command: AuthCommands, config: &Config) -> Res
             } else {
                 println!("Auth profile not found: {provider}:{profile}");
             }
+            match security::keyring::delete_provider_key(&config.secrets.keyring_service, &provider)
+            {
+                Ok(true) => println!("Removed {provider} API key from the OS keyring"),
+                Ok(false) => {}
+                Err(e) => warn!("Could not remove {provider} API key from the OS keyring: {e:#}"),
+            }
             Ok(())
         }
 
@@ -1930,6 +3285,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn man_page_lists_subcommands() {
+        let mut output = Vec::new();
+        write_man_page(&mut output).expect("man page generation should succeed");
+        let page = String::from_utf8(output).expect("man page should be valid utf-8");
+        assert!(page.contains(".TH zeroclaw"), "man page should be roff");
+        assert!(page.contains("agent"));
+    }
+
     #[test]
     fn onboard_cli_accepts_force_flag() {
         let cli = Cli::try_parse_from(["zeroclaw", "onboard", "--force"])
@@ -1941,6 +3305,354 @@ mod tests {
         }
     }
 
+    #[test]
+    fn search_cli_joins_query_and_parses_filters() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "search",
+            "staging",
+            "deploy",
+            "--channel",
+            "telegram",
+            "--since",
+            "2026-01-01",
+            "--open",
+        ])
+        .expect("search invocation should parse");
+
+        match cli.command {
+            Commands::Search {
+                query,
+                channel,
+                since,
+                limit,
+                open,
+                ..
+            } => {
+                assert_eq!(query, vec!["staging", "deploy"]);
+                assert_eq!(channel.as_deref(), Some("telegram"));
+                assert_eq!(since.as_deref(), Some("2026-01-01"));
+                assert_eq!(limit, 20);
+                assert!(open);
+            }
+            other => panic!("expected search command, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "search"]).is_err());
+    }
+
+    #[test]
+    fn sessions_cli_parses_subcommands() {
+        let cli = Cli::try_parse_from(["zeroclaw", "sessions", "list", "--all"])
+            .expect("sessions list should parse");
+        match cli.command {
+            Commands::Sessions {
+                session_command: SessionCommands::List { all, limit, .. },
+            } => {
+                assert!(all);
+                assert_eq!(limit, 20);
+            }
+            other => panic!("expected sessions list, got {other:?}"),
+        }
+
+        let cli = Cli::try_parse_from(["zeroclaw", "sessions", "rename", "s1", "work"])
+            .expect("sessions rename should parse");
+        match cli.command {
+            Commands::Sessions {
+                session_command: SessionCommands::Rename { session, name },
+            } => {
+                assert_eq!(session, "s1");
+                assert_eq!(name.as_deref(), Some("work"));
+            }
+            other => panic!("expected sessions rename, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "sessions", "delete"]).is_err());
+    }
+
+    #[test]
+    fn status_cli_parses_watch_interval() {
+        let cli = Cli::try_parse_from(["zeroclaw", "status", "--watch", "--interval", "5"])
+            .expect("status --watch should parse");
+        match cli.command {
+            Commands::Status { watch, interval } => {
+                assert!(watch);
+                assert_eq!(interval, 5);
+            }
+            other => panic!("expected status, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "status", "--interval", "5"]).is_err());
+    }
+
+    #[test]
+    fn audit_cli_parses_show_filters() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw", "audit", "show", "--since", "24h", "--tool", "shell",
+        ])
+        .expect("audit show should parse");
+        match cli.command {
+            Commands::Audit {
+                audit_command:
+                    AuditCommands::Show {
+                        since,
+                        tool,
+                        limit,
+                        json,
+                        ..
+                    },
+            } => {
+                assert_eq!(since.as_deref(), Some("24h"));
+                assert_eq!(tool.as_deref(), Some("shell"));
+                assert_eq!(limit, 50);
+                assert!(!json);
+            }
+            other => panic!("expected audit show, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "audit", "verify"]).is_ok());
+    }
+
+    #[test]
+    fn pair_cli_parses_approve_role() {
+        let cli =
+            Cli::try_parse_from(["zeroclaw", "pair", "approve", "K7QH2M9X", "--role", "admin"])
+                .expect("pair approve should parse");
+        match cli.command {
+            Commands::Pair {
+                pair_command: PairCommands::Approve { code, role },
+            } => {
+                assert_eq!(code, "K7QH2M9X");
+                assert_eq!(role.as_deref(), Some("admin"));
+            }
+            other => panic!("expected pair approve, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "pair", "revoke", "telegram", "42"]).is_ok());
+    }
+
+    #[test]
+    fn tui_cli_parses_agent_flags() {
+        let cli = Cli::try_parse_from(["zeroclaw", "tui", "--session", "work", "-t", "0.2"])
+            .expect("tui should parse");
+        match cli.command {
+            Commands::Tui {
+                provider,
+                temperature,
+                session,
+                ..
+            } => {
+                assert!(provider.is_none());
+                assert!((temperature - 0.2).abs() < f64::EPSILON);
+                assert_eq!(session.as_deref(), Some("work"));
+            }
+            other => panic!("expected tui, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn branch_cli_requires_from() {
+        let cli = Cli::try_parse_from(["zeroclaw", "branch", "alice", "--from", "cp1"])
+            .expect("branch should parse");
+        match cli.command {
+            Commands::Branch {
+                session,
+                from,
+                name,
+            } => {
+                assert_eq!(session, "alice");
+                assert_eq!(from, "cp1");
+                assert!(name.is_none());
+            }
+            other => panic!("expected branch, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "branch", "alice"]).is_err());
+    }
+
+    #[test]
+    fn schedule_cli_parses_run_now() {
+        let cli = Cli::try_parse_from(["zeroclaw", "schedule", "run-now", "morning"])
+            .expect("schedule run-now should parse");
+        match cli.command {
+            Commands::Schedule {
+                schedule_command: ScheduleCommands::RunNow { name },
+            } => assert_eq!(name, "morning"),
+            other => panic!("expected schedule run-now, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn prompt_cli_parses_render_flags() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "prompt",
+            "render",
+            "--channel",
+            "telegram",
+            "--debug",
+        ])
+        .expect("prompt render should parse");
+        match cli.command {
+            Commands::Prompt {
+                prompt_command:
+                    PromptCommands::Render {
+                        channel,
+                        user,
+                        debug,
+                        ..
+                    },
+            } => {
+                assert_eq!(channel, "telegram");
+                assert!(user.is_none());
+                assert!(debug);
+            }
+            other => panic!("expected prompt render, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn replay_cli_parses_session_and_turn() {
+        let cli = Cli::try_parse_from(["zeroclaw", "replay", "telegram_alice", "--turn", "2"])
+            .expect("replay should parse");
+        match cli.command {
+            Commands::Replay {
+                session,
+                turn,
+                verbose,
+            } => {
+                assert_eq!(session.as_deref(), Some("telegram_alice"));
+                assert_eq!(turn, Some(2));
+                assert!(!verbose);
+            }
+            other => panic!("expected replay, got {other:?}"),
+        }
+        assert!(matches!(
+            Cli::try_parse_from(["zeroclaw", "replay"]).unwrap().command,
+            Commands::Replay { session: None, .. }
+        ));
+    }
+
+    #[test]
+    fn tasks_cli_parses_subcommands() {
+        let cli = Cli::try_parse_from(["zeroclaw", "tasks", "cancel", "3"])
+            .expect("tasks cancel should parse");
+        match cli.command {
+            Commands::Tasks {
+                task_command: TaskCommands::Cancel { id },
+            } => assert_eq!(id, 3),
+            other => panic!("expected tasks cancel, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "tasks", "show", "three"]).is_err());
+    }
+
+    #[test]
+    fn export_and_import_cli_parse_formats_and_rename() {
+        let cli = Cli::try_parse_from(["zeroclaw", "export", "s1", "--format", "html"])
+            .expect("export invocation should parse");
+        match cli.command {
+            Commands::Export {
+                session, format, ..
+            } => {
+                assert_eq!(session, "s1");
+                assert_eq!(format, conversations::archive::ExportFormat::Html);
+            }
+            other => panic!("expected export command, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "export", "s1", "--format", "pdf"]).is_err());
+
+        let cli = Cli::try_parse_from(["zeroclaw", "import", "a.json", "--as", "copy"])
+            .expect("import invocation should parse");
+        match cli.command {
+            Commands::Import {
+                rename, replace, ..
+            } => {
+                assert_eq!(rename.as_deref(), Some("copy"));
+                assert!(!replace);
+            }
+            other => panic!("expected import command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn mcp_serve_cli_collects_tool_filter() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "mcp-serve",
+            "--tool",
+            "memory_recall",
+            "--tool",
+            "web_search",
+            "--no-resources",
+        ])
+        .expect("mcp-serve invocation should parse");
+
+        match cli.command {
+            Commands::McpServe {
+                tools,
+                no_resources,
+            } => {
+                assert_eq!(tools, vec!["memory_recall", "web_search"]);
+                assert!(no_resources);
+            }
+            other => panic!("expected mcp-serve command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn config_validate_cli_parses_file_and_probe() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "config",
+            "validate",
+            "--file",
+            "staging.toml",
+            "--probe",
+        ])
+        .expect("config validate should parse");
+        match cli.command {
+            Commands::Config {
+                config_command: ConfigCommands::Validate { file, probe },
+            } => {
+                assert_eq!(file, Some(std::path::PathBuf::from("staging.toml")));
+                assert!(probe);
+            }
+            other => panic!("expected config validate command, got {other:?}"),
+        }
+
+        let cli = Cli::try_parse_from(["zeroclaw", "config", "migrate", "--dry-run"])
+            .expect("config migrate should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Config {
+                config_command: ConfigCommands::Migrate { dry_run: true },
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["zeroclaw", "config", "schema"])
+            .expect("config schema should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Config {
+                config_command: ConfigCommands::Schema { output: None }
+            }
+        ));
+    }
+
+    #[test]
+    fn cli_parses_index_watch_flags() {
+        let cli =
+            Cli::try_parse_from(["zeroclaw", "index", "notes", "--watch", "--interval", "30"])
+                .expect("index command should parse");
+
+        match cli.command {
+            Commands::Index {
+                dir,
+                watch,
+                interval,
+            } => {
+                assert_eq!(dir, std::path::PathBuf::from("notes"));
+                assert!(watch);
+                assert_eq!(interval, 30);
+            }
+            other => panic!("expected index command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn cli_parses_estop_default_engage() {
         let cli = Cli::try_parse_from(["zeroclaw", "estop"]).expect("estop command should parse");
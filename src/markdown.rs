@@ -0,0 +1,656 @@
+//! Terminal rendering of assistant markdown.
+//!
+//! [`render`] turns markdown into styled lines (headings, emphasis, lists,
+//! block quotes, tables and fenced code highlighted with syntect) that the
+//! CLI prints as ANSI text and the TUI converts to ratatui lines. Output
+//! falls back to the raw markdown when rich rendering is off or the terminal
+//! has no color support.
+
+use crate::util::truncate_with_width;
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::fmt::Write as _;
+use std::sync::LazyLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+use unicode_width::UnicodeWidthStr;
+
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+    ThemeSet::load_defaults()
+        .themes
+        .remove("base16-ocean.dark")
+        .unwrap_or_default()
+});
+
+/// Width of a horizontal rule when no width is given.
+const DEFAULT_RULE_WIDTH: usize = 40;
+
+/// Terminal color: one of the 16 indexed colors or 24-bit RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+const CYAN: Color = Color::Indexed(6);
+const YELLOW: Color = Color::Indexed(3);
+const BLUE: Color = Color::Indexed(4);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+impl Style {
+    fn dim() -> Self {
+        Self {
+            dim: true,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+/// One rendered terminal line.
+pub type Line = Vec<Span>;
+
+/// Whether to render markdown: turned on and stdout shows color (a
+/// terminal, with `NO_COLOR` and `CLICOLOR=0` unset).
+pub fn rich_output(enabled: bool) -> bool {
+    enabled && console::colors_enabled()
+}
+
+/// `markdown` ready to print: rendered as ANSI text when `rich`, unchanged
+/// otherwise.
+pub fn for_terminal(markdown: &str, rich: bool) -> String {
+    if rich {
+        to_ansi(&render(markdown, None))
+    } else {
+        markdown.to_string()
+    }
+}
+
+/// Lines as text with ANSI escapes.
+pub fn to_ansi(lines: &[Line]) -> String {
+    let mut out = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+        for span in line {
+            let style = span.style;
+            let mut codes = Vec::new();
+            if style.bold {
+                codes.push("1".to_string());
+            }
+            if style.dim {
+                codes.push("2".to_string());
+            }
+            if style.italic {
+                codes.push("3".to_string());
+            }
+            if style.underline {
+                codes.push("4".to_string());
+            }
+            if style.strikethrough {
+                codes.push("9".to_string());
+            }
+            match style.fg {
+                Some(Color::Indexed(n)) if n < 8 => codes.push((30 + n).to_string()),
+                Some(Color::Indexed(n)) => codes.push(format!("38;5;{n}")),
+                Some(Color::Rgb(r, g, b)) => codes.push(format!("38;2;{r};{g};{b}")),
+                None => {}
+            }
+            if codes.is_empty() {
+                out.push_str(&span.text);
+            } else {
+                let _ = write!(out, "\x1b[{}m{}\x1b[0m", codes.join(";"), span.text);
+            }
+        }
+    }
+    out
+}
+
+/// Highlight a fenced code block; unknown languages render as plain text.
+fn highlight(code: &str, lang: &str) -> Vec<Line> {
+    let syntax = SYNTAXES
+        .find_syntax_by_token(lang)
+        .unwrap_or_else(|| SYNTAXES.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, &THEME);
+    LinesWithEndings::from(code)
+        .map(|line| match highlighter.highlight_line(line, &SYNTAXES) {
+            Ok(regions) => regions
+                .into_iter()
+                .map(|(style, text)| Span {
+                    text: text.trim_end_matches(['\n', '\r']).to_string(),
+                    style: Style {
+                        fg: Some(Color::Rgb(
+                            style.foreground.r,
+                            style.foreground.g,
+                            style.foreground.b,
+                        )),
+                        bold: style.font_style.contains(FontStyle::BOLD),
+                        italic: style.font_style.contains(FontStyle::ITALIC),
+                        underline: style.font_style.contains(FontStyle::UNDERLINE),
+                        ..Style::default()
+                    },
+                })
+                .filter(|span| !span.text.is_empty())
+                .collect(),
+            Err(_) => vec![Span {
+                text: line.trim_end_matches(['\n', '\r']).to_string(),
+                style: Style::default(),
+            }],
+        })
+        .collect()
+}
+
+/// A block that prefixes the lines inside it.
+enum Container {
+    Quote,
+    /// A list item; the marker goes on its first line, spaces on the rest.
+    Item {
+        marker: String,
+        started: bool,
+    },
+}
+
+#[derive(Default)]
+struct Table {
+    alignments: Vec<Alignment>,
+    rows: Vec<Vec<Line>>,
+    head_rows: usize,
+}
+
+impl Table {
+    fn cell(&mut self) -> Option<&mut Line> {
+        self.rows.last_mut().and_then(|row| row.last_mut())
+    }
+}
+
+fn line_width(line: &[Span]) -> usize {
+    line.iter().map(|span| span.text.width()).sum()
+}
+
+fn push_span(line: &mut Line, text: &str, style: Style) {
+    if text.is_empty() {
+        return;
+    }
+    match line.last_mut() {
+        Some(last) if last.style == style => last.text.push_str(text),
+        _ => line.push(Span {
+            text: text.to_string(),
+            style,
+        }),
+    }
+}
+
+struct Renderer {
+    width: Option<usize>,
+    lines: Vec<Line>,
+    current: Line,
+    current_width: usize,
+    /// `current` holds text beyond the container prefixes.
+    has_text: bool,
+    containers: Vec<Container>,
+    styles: Vec<Style>,
+    /// Numbering of each open list; `None` for bullets.
+    lists: Vec<Option<u64>>,
+    code: Option<(String, String)>,
+    table: Option<Table>,
+    links: Vec<String>,
+    blank_before_block: bool,
+}
+
+impl Renderer {
+    fn new(width: Option<usize>) -> Self {
+        Self {
+            width,
+            lines: Vec::new(),
+            current: Vec::new(),
+            current_width: 0,
+            has_text: false,
+            containers: Vec::new(),
+            styles: vec![Style::default()],
+            lists: Vec::new(),
+            code: None,
+            table: None,
+            links: Vec::new(),
+            blank_before_block: false,
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, change: impl FnOnce(&mut Style)) {
+        let mut style = self.style();
+        change(&mut style);
+        self.styles.push(style);
+    }
+
+    fn pop_style(&mut self) {
+        if self.styles.len() > 1 {
+            self.styles.pop();
+        }
+    }
+
+    /// Container prefixes for a new line.
+    fn open_line(&mut self) {
+        if !self.current.is_empty() {
+            return;
+        }
+        for container in &mut self.containers {
+            match container {
+                Container::Quote => push_span(&mut self.current, "│ ", Style::dim()),
+                Container::Item { marker, started } => {
+                    if *started {
+                        let indent = " ".repeat(marker.width());
+                        push_span(&mut self.current, &indent, Style::default());
+                    } else {
+                        push_span(&mut self.current, marker, Style::default());
+                        *started = true;
+                    }
+                }
+            }
+        }
+        self.current_width = line_width(&self.current);
+    }
+
+    fn flush(&mut self) {
+        if !self.current.is_empty() {
+            self.lines.push(std::mem::take(&mut self.current));
+        }
+        self.current_width = 0;
+        self.has_text = false;
+    }
+
+    /// Start a block, separating it from the previous one.
+    fn start_block(&mut self) {
+        self.flush();
+        if self.blank_before_block && !self.lines.is_empty() {
+            self.open_line();
+            let prefix_only = std::mem::take(&mut self.current);
+            // Keep quote bars on the separator, drop list markers.
+            let separator = prefix_only
+                .into_iter()
+                .filter(|span| span.style == Style::dim())
+                .collect();
+            self.lines.push(separator);
+            self.current_width = 0;
+        }
+        self.blank_before_block = false;
+    }
+
+    fn end_block(&mut self) {
+        self.flush();
+        self.blank_before_block = true;
+    }
+
+    fn text(&mut self, text: &str, style: Style) {
+        if let Some(table) = self.table.as_mut() {
+            if let Some(cell) = table.cell() {
+                push_span(cell, text, style);
+            }
+            return;
+        }
+        let Some(width) = self.width else {
+            self.open_line();
+            push_span(&mut self.current, text, style);
+            self.has_text = true;
+            return;
+        };
+        for word in text.split_inclusive(' ') {
+            self.open_line();
+            let word_width = word.trim_end().width();
+            if self.has_text && self.current_width + word_width > width {
+                if let Some(last) = self.current.last_mut() {
+                    last.text.truncate(last.text.trim_end().len());
+                }
+                self.flush();
+                self.open_line();
+            }
+            let word = if self.has_text {
+                word
+            } else {
+                word.trim_start()
+            };
+            push_span(&mut self.current, word, style);
+            self.current_width += word.width();
+            self.has_text |= !word.is_empty();
+        }
+    }
+
+    fn code_block(&mut self, lang: &str, code: &str) {
+        for line in highlight(code, lang) {
+            self.open_line();
+            push_span(&mut self.current, "  ", Style::default());
+            self.current.extend(line);
+            self.has_text = true;
+            self.flush();
+        }
+    }
+
+    fn table(&mut self, table: Table) {
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut widths = vec![0; columns];
+        for row in &table.rows {
+            for (column, cell) in row.iter().enumerate() {
+                widths[column] = widths[column].max(line_width(cell));
+            }
+        }
+        if let Some(width) = self.width {
+            // Shrink the widest columns until the table fits.
+            let overhead = columns * 3;
+            while widths.iter().sum::<usize>() + overhead > width {
+                let Some(widest) = widths.iter_mut().max().filter(|w| **w > 3) else {
+                    break;
+                };
+                *widest -= 1;
+            }
+        }
+        let bar = Style::dim();
+        for (index, row) in table.rows.iter().enumerate() {
+            self.open_line();
+            for (column, column_width) in widths.iter().enumerate() {
+                if column > 0 {
+                    push_span(&mut self.current, "│", bar);
+                }
+                let empty = Vec::new();
+                let cell = row.get(column).unwrap_or(&empty);
+                let cell = fit_cell(cell, *column_width);
+                let pad = column_width.saturating_sub(line_width(&cell));
+                let (left, right) = match table.alignments.get(column) {
+                    Some(Alignment::Right) => (pad, 0),
+                    Some(Alignment::Center) => (pad / 2, pad - pad / 2),
+                    _ => (0, pad),
+                };
+                push_span(&mut self.current, &" ".repeat(left + 1), Style::default());
+                for span in cell {
+                    let mut style = span.style;
+                    style.bold |= index < table.head_rows;
+                    push_span(&mut self.current, &span.text, style);
+                }
+                push_span(&mut self.current, &" ".repeat(right + 1), Style::default());
+            }
+            self.has_text = true;
+            self.flush();
+            if index + 1 == table.head_rows {
+                self.open_line();
+                let rule: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+                push_span(&mut self.current, &rule.join("┼"), bar);
+                self.flush();
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.start_block(),
+            Tag::Heading { level, .. } => {
+                self.start_block();
+                self.push_style(|style| {
+                    style.bold = true;
+                    style.fg = Some(CYAN);
+                    style.underline = level == HeadingLevel::H1;
+                });
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block();
+                self.containers.push(Container::Quote);
+                self.push_style(|style| style.italic = true);
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code = Some((lang, String::new()));
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.start_block();
+                } else {
+                    self.flush();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let marker = format!("{number}. ");
+                        *number += 1;
+                        marker
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.containers.push(Container::Item {
+                    marker,
+                    started: false,
+                });
+            }
+            Tag::Emphasis => self.push_style(|style| style.italic = true),
+            Tag::Strong => self.push_style(|style| style.bold = true),
+            Tag::Strikethrough => self.push_style(|style| style.strikethrough = true),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.links.push(dest_url.to_string());
+                self.push_style(|style| {
+                    style.underline = true;
+                    style.fg = Some(BLUE);
+                });
+            }
+            Tag::Table(alignments) => {
+                self.start_block();
+                self.table = Some(Table {
+                    alignments,
+                    ..Table::default()
+                });
+            }
+            Tag::TableHead | Tag::TableRow => {
+                if let Some(table) = self.table.as_mut() {
+                    table.rows.push(Vec::new());
+                }
+            }
+            Tag::TableCell => {
+                if let Some(row) = self.table.as_mut().and_then(|t| t.rows.last_mut()) {
+                    row.push(Vec::new());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.end_block(),
+            TagEnd::Heading(_) => {
+                self.pop_style();
+                self.end_block();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.pop_style();
+                self.flush();
+                self.containers.pop();
+                self.blank_before_block = true;
+            }
+            TagEnd::CodeBlock => {
+                if let Some((lang, code)) = self.code.take() {
+                    self.code_block(&lang, &code);
+                }
+                self.end_block();
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                self.blank_before_block = self.lists.is_empty();
+            }
+            TagEnd::Item => {
+                self.flush();
+                self.containers.pop();
+                self.blank_before_block = false;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => self.pop_style(),
+            TagEnd::Link | TagEnd::Image => {
+                self.pop_style();
+                if let Some(url) = self.links.pop().filter(|url| !url.is_empty()) {
+                    let shown = self
+                        .current
+                        .last()
+                        .is_some_and(|span| span.text.trim_end().ends_with(url.as_str()));
+                    if !shown {
+                        self.text(&format!(" ({url})"), Style::dim());
+                    }
+                }
+            }
+            TagEnd::TableHead => {
+                if let Some(table) = self.table.as_mut() {
+                    table.head_rows = table.rows.len();
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.table(table);
+                }
+                self.end_block();
+            }
+            _ => {}
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match self.code.as_mut() {
+                Some((_, code)) => code.push_str(&text),
+                None => self.text(&text, self.style()),
+            },
+            Event::Code(code) => {
+                let mut style = self.style();
+                style.fg = Some(YELLOW);
+                self.text(&code, style);
+            }
+            Event::SoftBreak => self.text(" ", self.style()),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.start_block();
+                let width = self.width.unwrap_or(DEFAULT_RULE_WIDTH);
+                self.open_line();
+                push_span(&mut self.current, &"─".repeat(width), Style::dim());
+                self.end_block();
+            }
+            Event::TaskListMarker(done) => {
+                self.text(if done { "[x] " } else { "[ ] " }, self.style());
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                self.text(html.trim_end_matches('\n'), Style::dim());
+            }
+            Event::FootnoteReference(label) => self.text(&format!("[^{label}]"), self.style()),
+            _ => {}
+        }
+    }
+}
+
+/// Shorten a table cell to `width` columns.
+fn fit_cell(cell: &[Span], width: usize) -> Line {
+    if line_width(cell) <= width {
+        return cell.to_vec();
+    }
+    let text: String = cell.iter().map(|span| span.text.as_str()).collect();
+    let style = cell.first().map(|span| span.style).unwrap_or_default();
+    vec![Span {
+        text: truncate_with_width(&text, width, "…"),
+        style,
+    }]
+}
+
+/// Render markdown as styled lines, word-wrapped to `width` columns when
+/// given (code blocks and tables are not wrapped).
+pub fn render(markdown: &str, width: Option<usize>) -> Vec<Line> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let mut renderer = Renderer::new(width.map(|w| w.max(1)));
+    for event in Parser::new_ext(markdown, options) {
+        renderer.event(event);
+    }
+    renderer.flush();
+    renderer.lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.iter().map(|span| span.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn renders_blocks_lists_and_tables() {
+        let markdown = "# Title\n\nSome **bold** and `code`.\n\n\
+                        - one\n- two\n  1. nested\n\n\
+                        > quoted\n\n\
+                        | Name | Qty |\n|:-----|----:|\n| apple | 3 |\n| kiwi | 12 |\n";
+        let lines = render(markdown, None);
+        assert_eq!(
+            plain(&lines),
+            [
+                "Title",
+                "",
+                "Some bold and code.",
+                "",
+                "• one",
+                "• two",
+                "  1. nested",
+                "",
+                "│ quoted",
+                "",
+                " Name  │ Qty ",
+                "───────┼─────",
+                " apple │   3 ",
+                " kiwi  │  12 ",
+            ]
+        );
+        assert!(lines[0][0].style.bold && lines[0][0].style.underline);
+        let bold = lines[2].iter().find(|span| span.text == "bold").unwrap();
+        assert!(bold.style.bold);
+    }
+
+    #[test]
+    fn code_is_highlighted_and_text_wraps() {
+        let lines = render("```rust\nfn main() {}\n```\n\nalpha beta gamma", Some(11));
+        assert_eq!(plain(&lines), ["  fn main() {}", "", "alpha beta", "gamma"]);
+        assert!(lines[0]
+            .iter()
+            .any(|span| matches!(span.style.fg, Some(Color::Rgb(..)))));
+
+        let ansi = to_ansi(&render("**hi**", None));
+        assert_eq!(ansi, "\x1b[1mhi\x1b[0m");
+        assert_eq!(for_terminal("**hi**", false), "**hi**");
+    }
+}
@@ -0,0 +1,710 @@
+//! MCP client: connects to one server over stdio or HTTP+SSE and performs
+//! JSON-RPC requests against it.
+
+use super::protocol::{self, Incoming, ResourceInfo, RpcError, ToolInfo};
+use crate::config::{McpServerConfig, McpTransport};
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+type Pending = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value, RpcError>>>>>;
+
+/// Upper bound on paginated list calls, so a misbehaving server cannot loop forever.
+const MAX_LIST_PAGES: usize = 50;
+
+/// Where outgoing messages go.
+#[derive(Clone)]
+enum Outbound {
+    Stdio(Arc<tokio::sync::Mutex<ChildStdin>>),
+    Sse {
+        client: reqwest::Client,
+        endpoint: String,
+        headers: reqwest::header::HeaderMap,
+    },
+}
+
+impl Outbound {
+    async fn send(&self, message: &Value) -> Result<()> {
+        match self {
+            Self::Stdio(stdin) => {
+                let mut line = serde_json::to_vec(message)?;
+                line.push(b'\n');
+                let mut stdin = stdin.lock().await;
+                stdin.write_all(&line).await?;
+                stdin.flush().await?;
+            }
+            Self::Sse {
+                client,
+                endpoint,
+                headers,
+            } => {
+                let response = client
+                    .post(endpoint)
+                    .headers(headers.clone())
+                    .json(message)
+                    .send()
+                    .await?;
+                let status = response.status();
+                if !status.is_success() {
+                    anyhow::bail!("POST {endpoint} failed: HTTP {status}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Live connection to a single MCP server.
+pub struct McpClient {
+    name: String,
+    outbound: Outbound,
+    pending: Pending,
+    next_id: AtomicU64,
+    request_timeout: Duration,
+    server_version: String,
+    instructions: Option<String>,
+    capabilities: Value,
+    tasks: Vec<JoinHandle<()>>,
+    // Held so the process is killed (`kill_on_drop`) when the client goes away.
+    _child: Option<Child>,
+}
+
+impl McpClient {
+    /// Connect to a configured server and complete the `initialize` handshake.
+    pub async fn connect(
+        name: &str,
+        config: &McpServerConfig,
+        workspace_dir: &Path,
+        startup_timeout: Duration,
+        request_timeout: Duration,
+    ) -> Result<Self> {
+        let mut client = tokio::time::timeout(startup_timeout, async {
+            let mut client = match config.transport {
+                McpTransport::Stdio => Self::spawn_stdio(name, config, workspace_dir)?,
+                McpTransport::Sse => Self::connect_sse(name, config).await?,
+            };
+            client.request_timeout = startup_timeout;
+            client.initialize().await?;
+            Ok::<_, anyhow::Error>(client)
+        })
+        .await
+        .with_context(|| {
+            format!(
+                "MCP server '{name}' did not finish starting within {}s",
+                startup_timeout.as_secs()
+            )
+        })??;
+        client.request_timeout = request_timeout;
+        Ok(client)
+    }
+
+    fn new(name: &str, outbound: Outbound, pending: Pending) -> Self {
+        Self {
+            name: name.to_string(),
+            outbound,
+            pending,
+            next_id: AtomicU64::new(1),
+            request_timeout: Duration::from_secs(30),
+            server_version: String::new(),
+            instructions: None,
+            capabilities: Value::Null,
+            tasks: Vec::new(),
+            _child: None,
+        }
+    }
+
+    fn spawn_stdio(name: &str, config: &McpServerConfig, workspace_dir: &Path) -> Result<Self> {
+        let command = config
+            .command
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .with_context(|| format!("mcp.servers.{name}.command is not set"))?;
+        let cwd = match config.cwd.as_deref() {
+            Some(dir) => {
+                let dir = shellexpand::tilde(dir).into_owned();
+                workspace_dir.join(dir)
+            }
+            None => workspace_dir.to_path_buf(),
+        };
+
+        let mut child = Command::new(command)
+            .args(&config.args)
+            .envs(&config.env)
+            .current_dir(&cwd)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to spawn MCP server '{name}' ({command})"))?;
+
+        let stdin = child.stdin.take().context("MCP server stdin unavailable")?;
+        let stdout = child
+            .stdout
+            .take()
+            .context("MCP server stdout unavailable")?;
+        let stderr = child.stderr.take();
+
+        let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+        let outbound = Outbound::Stdio(Arc::new(tokio::sync::Mutex::new(stdin)));
+        let mut client = Self::new(name, outbound.clone(), pending.clone());
+
+        let server = name.to_string();
+        client.tasks.push(tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            loop {
+                match lines.next_line().await {
+                    Ok(Some(line)) if line.trim().is_empty() => {}
+                    Ok(Some(line)) => match serde_json::from_str::<Value>(&line) {
+                        Ok(message) => dispatch(&server, message, &pending, &outbound).await,
+                        Err(e) => {
+                            tracing::debug!(server = %server, "Ignoring non-JSON MCP output: {e}")
+                        }
+                    },
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::warn!(server = %server, "MCP server stdout read failed: {e}");
+                        break;
+                    }
+                }
+            }
+            tracing::info!(server = %server, "MCP server closed its output");
+            pending.lock().clear();
+        }));
+
+        if let Some(stderr) = stderr {
+            let server = name.to_string();
+            client.tasks.push(tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::debug!(server = %server, "mcp stderr: {line}");
+                }
+            }));
+        }
+
+        client._child = Some(child);
+        Ok(client)
+    }
+
+    async fn connect_sse(name: &str, config: &McpServerConfig) -> Result<Self> {
+        let url = config
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .with_context(|| format!("mcp.servers.{name}.url is not set"))?;
+        let base = reqwest::Url::parse(url).with_context(|| format!("Invalid MCP URL: {url}"))?;
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (key, value) in &config.headers {
+            headers.insert(
+                reqwest::header::HeaderName::from_bytes(key.as_bytes())
+                    .with_context(|| format!("Invalid header name '{key}'"))?,
+                reqwest::header::HeaderValue::from_str(value)
+                    .with_context(|| format!("Invalid value for header '{key}'"))?,
+            );
+        }
+
+        let http = crate::config::build_runtime_proxy_client("tool.mcp");
+        let response = http
+            .get(base.clone())
+            .headers(headers.clone())
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to MCP server '{name}'"))?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("MCP server '{name}' SSE connect failed: HTTP {status}");
+        }
+
+        let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+        let (endpoint_tx, endpoint_rx) = oneshot::channel::<String>();
+        let (outbound_tx, outbound_rx) = oneshot::channel::<Outbound>();
+
+        let server = name.to_string();
+        let reader_pending = pending.clone();
+        let reader = tokio::spawn(async move {
+            let mut endpoint_tx = Some(endpoint_tx);
+            let mut outbound_rx = Some(outbound_rx);
+            let mut outbound: Option<Outbound> = None;
+            let mut parser = SseParser::default();
+            let mut stream = response.bytes_stream();
+            while let Some(chunk) = stream.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        tracing::warn!(server = %server, "MCP SSE stream error: {e}");
+                        break;
+                    }
+                };
+                for (event, data) in parser.push(&chunk) {
+                    if event == "endpoint" {
+                        if let Some(tx) = endpoint_tx.take() {
+                            let _ = tx.send(data);
+                        }
+                        continue;
+                    }
+                    if outbound.is_none() {
+                        if let Some(rx) = outbound_rx.take() {
+                            outbound = rx.await.ok();
+                        }
+                    }
+                    let Some(outbound) = outbound.as_ref() else {
+                        continue;
+                    };
+                    match serde_json::from_str::<Value>(&data) {
+                        Ok(message) => dispatch(&server, message, &reader_pending, outbound).await,
+                        Err(e) => {
+                            tracing::debug!(server = %server, "Ignoring non-JSON SSE event: {e}")
+                        }
+                    }
+                }
+            }
+            tracing::info!(server = %server, "MCP SSE stream closed");
+            reader_pending.lock().clear();
+        });
+
+        let endpoint = endpoint_rx
+            .await
+            .ok()
+            .and_then(|endpoint| base.join(endpoint.trim()).ok());
+        let Some(endpoint) = endpoint else {
+            reader.abort();
+            anyhow::bail!("MCP server '{name}' did not announce a valid message endpoint");
+        };
+        let endpoint = endpoint.to_string();
+        let outbound = Outbound::Sse {
+            client: http,
+            endpoint,
+            headers,
+        };
+        let _ = outbound_tx.send(outbound.clone());
+
+        let mut client = Self::new(name, outbound, pending);
+        client.tasks.push(reader);
+        Ok(client)
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        let result = self
+            .request(
+                "initialize",
+                Some(json!({
+                    "protocolVersion": protocol::PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {"name": "zeroclaw", "version": env!("CARGO_PKG_VERSION")},
+                })),
+            )
+            .await?;
+        self.capabilities = result.get("capabilities").cloned().unwrap_or(Value::Null);
+        self.server_version = result
+            .pointer("/serverInfo/version")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        self.instructions = result
+            .get("instructions")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        self.outbound
+            .send(&protocol::notification("notifications/initialized", None))
+            .await?;
+        Ok(())
+    }
+
+    /// Configured server name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Server version reported during `initialize` (may be empty).
+    pub fn server_version(&self) -> &str {
+        &self.server_version
+    }
+
+    /// Usage instructions the server sent during `initialize`, if any.
+    pub fn instructions(&self) -> Option<&str> {
+        self.instructions.as_deref()
+    }
+
+    fn has_capability(&self, name: &str) -> bool {
+        self.capabilities.get(name).is_some_and(|c| !c.is_null())
+    }
+
+    /// Send a request and wait for its response.
+    pub async fn request(&self, method: &str, params: Option<Value>) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().insert(id, tx);
+
+        if let Err(e) = self
+            .outbound
+            .send(&protocol::request(id, method, params))
+            .await
+        {
+            self.pending.lock().remove(&id);
+            return Err(e.context(format!(
+                "Failed to send {method} to MCP server '{}'",
+                self.name
+            )));
+        }
+
+        match tokio::time::timeout(self.request_timeout, rx).await {
+            Ok(Ok(Ok(result))) => Ok(result),
+            Ok(Ok(Err(error))) => {
+                anyhow::bail!("MCP server '{}' rejected {method}: {error}", self.name)
+            }
+            Ok(Err(_)) => anyhow::bail!("MCP server '{}' disconnected", self.name),
+            Err(_) => {
+                self.pending.lock().remove(&id);
+                anyhow::bail!(
+                    "MCP server '{}' did not answer {method} within {}s",
+                    self.name,
+                    self.request_timeout.as_secs()
+                )
+            }
+        }
+    }
+
+    /// Follow `nextCursor` pagination for a `*/list` method.
+    async fn list_all(&self, method: &str, field: &str) -> Result<Vec<Value>> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_LIST_PAGES {
+            let params = cursor.as_ref().map(|c| json!({"cursor": c}));
+            let result = self.request(method, params).await?;
+            if let Some(Value::Array(page)) = result.get(field) {
+                items.extend(page.iter().cloned());
+            }
+            cursor = result
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .filter(|c| !c.is_empty())
+                .map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// Discover the server's tools.
+    pub async fn list_tools(&self) -> Result<Vec<ToolInfo>> {
+        if !self.has_capability("tools") {
+            return Ok(Vec::new());
+        }
+        let tools = self.list_all("tools/list", "tools").await?;
+        Ok(tools
+            .into_iter()
+            .filter_map(|tool| match serde_json::from_value::<ToolInfo>(tool) {
+                Ok(info) => Some(info),
+                Err(e) => {
+                    tracing::warn!(server = %self.name, "Skipping malformed MCP tool: {e}");
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Discover the server's resources (empty when unsupported).
+    pub async fn list_resources(&self) -> Result<Vec<ResourceInfo>> {
+        if !self.has_capability("resources") {
+            return Ok(Vec::new());
+        }
+        let resources = self.list_all("resources/list", "resources").await?;
+        Ok(resources
+            .into_iter()
+            .filter_map(|resource| serde_json::from_value(resource).ok())
+            .collect())
+    }
+
+    /// Call a tool. Returns the rendered content and the server's `isError` flag.
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<(String, bool)> {
+        let arguments = if arguments.is_null() {
+            json!({})
+        } else {
+            arguments
+        };
+        let result = self
+            .request(
+                "tools/call",
+                Some(json!({"name": name, "arguments": arguments})),
+            )
+            .await?;
+        let is_error = result
+            .get("isError")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let mut text = result
+            .get("content")
+            .and_then(Value::as_array)
+            .map(|blocks| protocol::render_content(blocks))
+            .unwrap_or_default();
+        if text.is_empty() {
+            text = result
+                .get("structuredContent")
+                .map(Value::to_string)
+                .unwrap_or_default();
+        }
+        Ok((text, is_error))
+    }
+
+    /// Read a resource and render its contents as text.
+    pub async fn read_resource(&self, uri: &str) -> Result<String> {
+        let result = self
+            .request("resources/read", Some(json!({"uri": uri})))
+            .await?;
+        Ok(result
+            .get("contents")
+            .and_then(Value::as_array)
+            .map(|blocks| protocol::render_content(blocks))
+            .unwrap_or_default())
+    }
+}
+
+impl Drop for McpClient {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Route one incoming message: resolve pending requests, answer server pings.
+async fn dispatch(server: &str, message: Value, pending: &Pending, outbound: &Outbound) {
+    match protocol::classify(message) {
+        Some(Incoming::Response { id, result }) => {
+            if let Some(tx) = pending.lock().remove(&id) {
+                let _ = tx.send(result);
+            }
+        }
+        Some(Incoming::Request { id, method, .. }) => {
+            let reply = if method == "ping" {
+                protocol::response(id, json!({}))
+            } else {
+                protocol::error_response(
+                    id,
+                    protocol::METHOD_NOT_FOUND,
+                    &format!("Method not supported by zeroclaw: {method}"),
+                )
+            };
+            if let Err(e) = outbound.send(&reply).await {
+                tracing::debug!(server = %server, "Failed to answer MCP {method}: {e}");
+            }
+        }
+        Some(Incoming::Notification { method, params }) => {
+            if method == "notifications/message" {
+                tracing::info!(server = %server, "mcp log: {}", params.get("data").unwrap_or(&params));
+            } else {
+                tracing::debug!(server = %server, "MCP notification {method}");
+            }
+        }
+        None => tracing::debug!(server = %server, "Ignoring malformed JSON-RPC message"),
+    }
+}
+
+/// Incremental Server-Sent Events parser yielding `(event, data)` pairs.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    event: String,
+    data: String,
+}
+
+impl SseParser {
+    fn push(&mut self, chunk: &[u8]) -> Vec<(String, String)> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|b| *b == b'\n') {
+            let raw: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&raw);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    let event = if self.event.is_empty() {
+                        "message".to_string()
+                    } else {
+                        std::mem::take(&mut self.event)
+                    };
+                    events.push((event, std::mem::take(&mut self.data)));
+                }
+                self.event.clear();
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => self.event = value.to_string(),
+                "data" => {
+                    if !self.data.is_empty() {
+                        self.data.push('\n');
+                    }
+                    self.data.push_str(value);
+                }
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_parser_handles_split_chunks_and_comments() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b": keepalive\n\nevent: endp").is_empty());
+        assert_eq!(
+            parser.push(b"oint\ndata: /messages?session=1\r\n\r\n"),
+            vec![("endpoint".to_string(), "/messages?session=1".to_string())]
+        );
+        assert_eq!(
+            parser.push(b"data: {\"a\":\ndata: 1}\n\n"),
+            vec![("message".to_string(), "{\"a\":\n1}".to_string())]
+        );
+    }
+
+    fn stdio_config(command: &str) -> McpServerConfig {
+        McpServerConfig {
+            enabled: true,
+            transport: McpTransport::Stdio,
+            command: Some(command.into()),
+            args: Vec::new(),
+            env: HashMap::new(),
+            cwd: None,
+            url: None,
+            headers: HashMap::new(),
+            allowed_tools: Vec::new(),
+            resources: true,
+        }
+    }
+
+    #[cfg(unix)]
+    fn fake_server(dir: &Path, replies: &[Value]) -> McpServerConfig {
+        use std::fmt::Write as _;
+        use std::os::unix::fs::PermissionsExt;
+
+        // Answers each request line with the next canned reply; the
+        // `notifications/initialized` line gets no reply.
+        let mut script = String::from("#!/bin/sh\nread line\n");
+        for (i, reply) in replies.iter().enumerate() {
+            let reply = reply.to_string().replace('\'', "'\\''");
+            let _ = writeln!(script, "echo '{reply}'");
+            if i == 0 {
+                script.push_str("read line\n");
+            }
+            script.push_str("read line\n");
+        }
+        let path = dir.join("fake-mcp");
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        stdio_config(&path.to_string_lossy())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stdio_handshake_discovery_and_call() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = fake_server(
+            tmp.path(),
+            &[
+                json!({"jsonrpc": "2.0", "id": 1, "result": {
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": {"tools": {}},
+                    "serverInfo": {"name": "fake", "version": "0.1.0"},
+                    "instructions": "Be nice"
+                }}),
+                json!({"jsonrpc": "2.0", "id": 2, "result": {"tools": [
+                    {"name": "echo", "description": "Echo text", "inputSchema": {"type": "object"}}
+                ]}}),
+                json!({"jsonrpc": "2.0", "id": 3, "result": {
+                    "content": [{"type": "text", "text": "hi there"}],
+                    "isError": false
+                }}),
+            ],
+        );
+
+        let client = McpClient::connect(
+            "fake",
+            &config,
+            tmp.path(),
+            Duration::from_secs(10),
+            Duration::from_secs(10),
+        )
+        .await
+        .unwrap();
+        assert_eq!(client.server_version(), "0.1.0");
+        assert_eq!(client.instructions(), Some("Be nice"));
+
+        let tools = client.list_tools().await.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "echo");
+        // No `resources` capability: no request is sent.
+        assert!(client.list_resources().await.unwrap().is_empty());
+
+        let (text, is_error) = client
+            .call_tool("echo", json!({"text": "hi"}))
+            .await
+            .unwrap();
+        assert_eq!(text, "hi there");
+        assert!(!is_error);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn server_exit_during_handshake_is_reported() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = fake_server(tmp.path(), &[]);
+        let error = McpClient::connect(
+            "flaky",
+            &config,
+            tmp.path(),
+            Duration::from_millis(300),
+            Duration::from_secs(10),
+        )
+        .await
+        .err()
+        .unwrap();
+        let message = format!("{error:#}");
+        assert!(
+            message.contains("flaky") && message.contains("disconnected"),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_command_is_reported() {
+        let config = stdio_config("zeroclaw-missing-mcp-server");
+        let error = McpClient::connect(
+            "missing",
+            &config,
+            &std::env::temp_dir(),
+            Duration::from_secs(5),
+            Duration::from_secs(5),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(error
+            .to_string()
+            .contains("Failed to spawn MCP server 'missing'"));
+    }
+}
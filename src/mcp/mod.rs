@@ -0,0 +1,412 @@
+//! Model Context Protocol (MCP) integration.
+//!
+//! [`create_mcp_tools`] connects to every server under `[mcp.servers.*]`
+//! (stdio or HTTP+SSE), discovers its tools and resources, and wraps them as
+//! regular [`Tool`]s so the model sees them next to the built-in ones.
+//! Exposed tool names are `<server>__<tool>`.
+//!
+//! The reverse direction lives in [`server`]: `zeroclaw mcp-serve` offers
+//! zeroclaw's own tools and memory to other MCP clients over stdio.
+
+pub mod client;
+pub mod protocol;
+pub mod server;
+
+pub use client::McpClient;
+pub use server::McpServer;
+
+use crate::config::{McpConfig, McpServerConfig};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use crate::tools::traits::{Tool, ToolResult, ToolRisk};
+use async_trait::async_trait;
+use protocol::{ResourceInfo, ToolInfo};
+use serde_json::json;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Provider APIs limit function names to 64 characters.
+const MAX_TOOL_NAME_LEN: usize = 64;
+/// Resources listed in the `read_resource` tool description.
+const MAX_LISTED_RESOURCES: usize = 25;
+
+/// Connect to the configured MCP servers and build their tools.
+///
+/// A server that fails to start is logged and skipped so one broken entry
+/// does not take the agent down.
+pub async fn create_mcp_tools(
+    config: &McpConfig,
+    security: &Arc<SecurityPolicy>,
+    workspace_dir: &Path,
+) -> Vec<Box<dyn Tool>> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    let mut names: Vec<&String> = config
+        .servers
+        .iter()
+        .filter(|(_, server)| server.enabled)
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+
+    let startup_timeout = Duration::from_secs(config.startup_timeout_secs.max(1));
+    let request_timeout = Duration::from_secs(config.tool_timeout_secs.max(1));
+    let connections = futures_util::future::join_all(names.iter().map(|name| {
+        let server = &config.servers[*name];
+        async move {
+            let client = McpClient::connect(
+                name,
+                server,
+                workspace_dir,
+                startup_timeout,
+                request_timeout,
+            )
+            .await?;
+            let tools = client.list_tools().await?;
+            let resources = if server.resources {
+                client.list_resources().await.unwrap_or_else(|e| {
+                    tracing::warn!(server = %name, "MCP resource discovery failed: {e}");
+                    Vec::new()
+                })
+            } else {
+                Vec::new()
+            };
+            Ok::<_, anyhow::Error>((Arc::new(client), tools, resources))
+        }
+    }))
+    .await;
+
+    let mut registry: Vec<Box<dyn Tool>> = Vec::new();
+    let mut seen = HashSet::new();
+    for (name, connection) in names.into_iter().zip(connections) {
+        let (client, tools, resources) = match connection {
+            Ok(connection) => connection,
+            Err(e) => {
+                tracing::warn!(server = %name, "Skipping MCP server: {e:#}");
+                continue;
+            }
+        };
+        let server = &config.servers[name];
+        let before = registry.len();
+
+        for info in tools {
+            if !is_tool_allowed(server, &info.name) {
+                continue;
+            }
+            let tool = McpTool::new(client.clone(), security.clone(), info);
+            if seen.insert(tool.name.clone()) {
+                registry.push(Box::new(tool));
+            } else {
+                tracing::warn!(tool = %tool.name, "Duplicate MCP tool name; skipping");
+            }
+        }
+        if !resources.is_empty() {
+            let tool = McpResourceTool::new(client.clone(), &resources);
+            if seen.insert(tool.name.clone()) {
+                registry.push(Box::new(tool));
+            }
+        }
+
+        tracing::info!(
+            server = %name,
+            version = client.server_version(),
+            tools = registry.len() - before,
+            resources = resources.len(),
+            "MCP server connected"
+        );
+    }
+    registry
+}
+
+fn is_tool_allowed(server: &McpServerConfig, tool: &str) -> bool {
+    server.allowed_tools.is_empty() || server.allowed_tools.iter().any(|t| t == tool)
+}
+
+/// Build a provider-safe `<server>__<tool>` name.
+fn exposed_tool_name(server: &str, tool: &str) -> String {
+    let mut name: String = format!("{server}__{tool}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    name.truncate(MAX_TOOL_NAME_LEN);
+    name
+}
+
+/// A tool provided by an MCP server.
+pub struct McpTool {
+    client: Arc<McpClient>,
+    security: Arc<SecurityPolicy>,
+    name: String,
+    remote_name: String,
+    description: String,
+    schema: serde_json::Value,
+    read_only: bool,
+}
+
+impl McpTool {
+    fn new(client: Arc<McpClient>, security: Arc<SecurityPolicy>, info: ToolInfo) -> Self {
+        let name = exposed_tool_name(client.name(), &info.name);
+        let description = format!(
+            "[MCP server '{}'] {}",
+            client.name(),
+            info.description
+                .as_deref()
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .unwrap_or(&info.name)
+        );
+        let schema = if info.input_schema.is_object() {
+            info.input_schema
+        } else {
+            protocol::empty_object_schema()
+        };
+        let read_only = info
+            .annotations
+            .as_ref()
+            .and_then(|a| a.read_only_hint)
+            .unwrap_or(false);
+        Self {
+            client,
+            security,
+            name,
+            remote_name: info.name,
+            description,
+            schema,
+            read_only,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for McpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.schema.clone()
+    }
+
+    fn risk(&self) -> ToolRisk {
+        if self.read_only {
+            ToolRisk::Low
+        } else {
+            ToolRisk::Medium
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        // Tools the server marks read-only run in read-only autonomy; anything
+        // else may have side effects and is treated as an action.
+        let operation = if self.read_only {
+            ToolOperation::Read
+        } else {
+            ToolOperation::Act
+        };
+        if let Err(error) = self.security.enforce_tool_operation(operation, &self.name) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        match self.client.call_tool(&self.remote_name, args).await {
+            Ok((output, false)) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Ok((output, true)) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(if output.is_empty() {
+                    "MCP tool reported an error".into()
+                } else {
+                    output
+                }),
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("{e:#}")),
+            }),
+        }
+    }
+}
+
+/// Reads resources advertised by an MCP server (`<server>__read_resource`).
+pub struct McpResourceTool {
+    client: Arc<McpClient>,
+    name: String,
+    description: String,
+}
+
+impl McpResourceTool {
+    fn new(client: Arc<McpClient>, resources: &[ResourceInfo]) -> Self {
+        let name = exposed_tool_name(client.name(), "read_resource");
+        let mut description = format!(
+            "Read a resource from MCP server '{}' by URI. Available resources:",
+            client.name()
+        );
+        for resource in resources.iter().take(MAX_LISTED_RESOURCES) {
+            let _ = write!(description, "\n- {}", resource.uri);
+            if let Some(label) = resource
+                .name
+                .as_deref()
+                .or(resource.description.as_deref())
+                .filter(|l| !l.is_empty())
+            {
+                let _ = write!(description, " ({label})");
+            }
+        }
+        if resources.len() > MAX_LISTED_RESOURCES {
+            let _ = write!(
+                description,
+                "\n- ... and {} more",
+                resources.len() - MAX_LISTED_RESOURCES
+            );
+        }
+        Self {
+            client,
+            name,
+            description,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for McpResourceTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "uri": {"type": "string", "description": "Resource URI"}
+            },
+            "required": ["uri"]
+        })
+    }
+
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let uri = args
+            .get("uri")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'uri' parameter"))?;
+        match self.client.read_resource(uri).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("{e:#}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::McpTransport;
+    use std::collections::HashMap;
+
+    #[test]
+    fn exposed_names_are_provider_safe() {
+        assert_eq!(
+            exposed_tool_name("github", "list_issues"),
+            "github__list_issues"
+        );
+        assert_eq!(exposed_tool_name("fs", "read.file/v2"), "fs__read_file_v2");
+        assert_eq!(
+            exposed_tool_name("s", &"x".repeat(100)).len(),
+            MAX_TOOL_NAME_LEN
+        );
+    }
+
+    #[test]
+    fn allowed_tools_filter() {
+        let mut server = McpServerConfig {
+            enabled: true,
+            transport: McpTransport::Stdio,
+            command: Some("server".into()),
+            args: Vec::new(),
+            env: HashMap::new(),
+            cwd: None,
+            url: None,
+            headers: HashMap::new(),
+            allowed_tools: Vec::new(),
+            resources: true,
+        };
+        assert!(is_tool_allowed(&server, "anything"));
+        server.allowed_tools = vec!["search".into()];
+        assert!(is_tool_allowed(&server, "search"));
+        assert!(!is_tool_allowed(&server, "delete_repo"));
+    }
+
+    #[tokio::test]
+    async fn disabled_config_creates_no_tools() {
+        let security = Arc::new(SecurityPolicy::default());
+        let config = McpConfig::default();
+        assert!(create_mcp_tools(&config, &security, &std::env::temp_dir())
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn failing_server_is_skipped() {
+        let security = Arc::new(SecurityPolicy::default());
+        let mut config = McpConfig {
+            enabled: true,
+            startup_timeout_secs: 5,
+            ..McpConfig::default()
+        };
+        config.servers.insert(
+            "broken".into(),
+            McpServerConfig {
+                enabled: true,
+                transport: McpTransport::Stdio,
+                command: Some("zeroclaw-missing-mcp-server".into()),
+                args: Vec::new(),
+                env: HashMap::new(),
+                cwd: None,
+                url: None,
+                headers: HashMap::new(),
+                allowed_tools: Vec::new(),
+                resources: true,
+            },
+        );
+        assert!(create_mcp_tools(&config, &security, &std::env::temp_dir())
+            .await
+            .is_empty());
+    }
+}
@@ -0,0 +1,274 @@
+//! JSON-RPC 2.0 framing and the subset of MCP message types zeroclaw uses.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+
+/// MCP protocol revision spoken by the client (HTTP+SSE transport era).
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// JSON-RPC "parse error".
+pub const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC "method not found".
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC "invalid params".
+pub const INVALID_PARAMS: i64 = -32602;
+/// JSON-RPC "internal error".
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// Build a JSON-RPC request.
+pub fn request(id: u64, method: &str, params: Option<Value>) -> Value {
+    let mut message = json!({"jsonrpc": "2.0", "id": id, "method": method});
+    if let Some(params) = params {
+        message["params"] = params;
+    }
+    message
+}
+
+/// Build a JSON-RPC notification (no id, no response expected).
+pub fn notification(method: &str, params: Option<Value>) -> Value {
+    let mut message = json!({"jsonrpc": "2.0", "method": method});
+    if let Some(params) = params {
+        message["params"] = params;
+    }
+    message
+}
+
+/// Build a successful JSON-RPC response.
+pub fn response(id: Value, result: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "result": result})
+}
+
+/// Build a JSON-RPC error response.
+pub fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+}
+
+/// An incoming JSON-RPC message, classified by shape.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Incoming {
+    /// Peer-initiated request that expects a response.
+    Request {
+        id: Value,
+        method: String,
+        params: Value,
+    },
+    /// Peer-initiated notification.
+    Notification { method: String, params: Value },
+    /// Response to one of our requests.
+    Response {
+        id: u64,
+        result: Result<Value, RpcError>,
+    },
+}
+
+/// JSON-RPC error object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (code {})", self.message, self.code)
+    }
+}
+
+/// Classify a decoded JSON-RPC message. Returns `None` for anything that is
+/// not a well-formed request, notification or response.
+pub fn classify(message: Value) -> Option<Incoming> {
+    let Value::Object(mut map) = message else {
+        return None;
+    };
+    let params = map.remove("params").unwrap_or(Value::Null);
+    if let Some(Value::String(method)) = map.remove("method") {
+        return Some(match map.remove("id") {
+            Some(id) if !id.is_null() => Incoming::Request { id, method, params },
+            _ => Incoming::Notification { method, params },
+        });
+    }
+    let id = map.get("id").and_then(Value::as_u64)?;
+    if let Some(error) = map.remove("error") {
+        let error = serde_json::from_value(error).unwrap_or_else(|_| RpcError {
+            code: INTERNAL_ERROR,
+            message: "malformed error object".into(),
+            data: None,
+        });
+        return Some(Incoming::Response {
+            id,
+            result: Err(error),
+        });
+    }
+    Some(Incoming::Response {
+        id,
+        result: Ok(map.remove("result").unwrap_or(Value::Null)),
+    })
+}
+
+/// A tool advertised by an MCP server (`tools/list`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolInfo {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default = "empty_object_schema")]
+    pub input_schema: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+}
+
+/// Behaviour hints attached to a tool.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only_hint: Option<bool>,
+}
+
+/// A resource advertised by an MCP server (`resources/list`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceInfo {
+    pub uri: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+pub fn empty_object_schema() -> Value {
+    json!({"type": "object", "properties": {}})
+}
+
+/// Render MCP content blocks (`tools/call` content, `resources/read` contents)
+/// as text for the model.
+pub fn render_content(blocks: &[Value]) -> String {
+    let mut output = String::new();
+    for block in blocks {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        // `resources/read` returns bare resource contents; `tools/call` wraps
+        // them in `{type: "resource", resource: {...}}`.
+        let resource = block.get("resource").unwrap_or(block);
+        match block.get("type").and_then(Value::as_str) {
+            Some("text") => {
+                output.push_str(block.get("text").and_then(Value::as_str).unwrap_or(""));
+            }
+            Some("image" | "audio") => {
+                let kind = block["type"].as_str().unwrap_or("media");
+                let mime = block
+                    .get("mimeType")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown type");
+                let size = block
+                    .get("data")
+                    .and_then(Value::as_str)
+                    .map_or(0, |data| data.len() / 4 * 3);
+                let _ = write!(output, "[{kind} content: {mime}, ~{size} bytes]");
+            }
+            _ => render_resource(&mut output, resource),
+        }
+    }
+    output
+}
+
+fn render_resource(output: &mut String, resource: &Value) {
+    let uri = resource.get("uri").and_then(Value::as_str).unwrap_or("");
+    if let Some(text) = resource.get("text").and_then(Value::as_str) {
+        if !uri.is_empty() {
+            let _ = writeln!(output, "[resource {uri}]");
+        }
+        output.push_str(text);
+    } else if let Some(blob) = resource.get("blob").and_then(Value::as_str) {
+        let mime = resource
+            .get("mimeType")
+            .and_then(Value::as_str)
+            .unwrap_or("application/octet-stream");
+        let _ = write!(
+            output,
+            "[binary resource {uri}: {mime}, ~{} bytes]",
+            blob.len() / 4 * 3
+        );
+    } else {
+        output.push_str(&resource.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_messages() {
+        assert_eq!(
+            classify(json!({"jsonrpc": "2.0", "id": 7, "result": {"ok": true}})),
+            Some(Incoming::Response {
+                id: 7,
+                result: Ok(json!({"ok": true}))
+            })
+        );
+        assert!(matches!(
+            classify(
+                json!({"jsonrpc": "2.0", "id": 3, "error": {"code": -32601, "message": "nope"}})
+            ),
+            Some(Incoming::Response {
+                id: 3,
+                result: Err(RpcError { code: -32601, .. })
+            })
+        ));
+        assert_eq!(
+            classify(json!({"jsonrpc": "2.0", "id": "abc", "method": "ping"})),
+            Some(Incoming::Request {
+                id: json!("abc"),
+                method: "ping".into(),
+                params: Value::Null
+            })
+        );
+        assert!(matches!(
+            classify(json!({"jsonrpc": "2.0", "method": "notifications/tools/list_changed"})),
+            Some(Incoming::Notification { .. })
+        ));
+        assert_eq!(classify(json!([1, 2])), None);
+        assert_eq!(
+            classify(json!({"jsonrpc": "2.0", "id": "x", "result": {}})),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_tool_info_defaults() {
+        let info: ToolInfo = serde_json::from_value(json!({"name": "echo"})).unwrap();
+        assert_eq!(info.input_schema, empty_object_schema());
+        assert!(info.annotations.is_none());
+
+        let info: ToolInfo = serde_json::from_value(json!({
+            "name": "list_issues",
+            "description": "List issues",
+            "inputSchema": {"type": "object", "properties": {"repo": {"type": "string"}}},
+            "annotations": {"readOnlyHint": true}
+        }))
+        .unwrap();
+        assert_eq!(info.annotations.unwrap().read_only_hint, Some(true));
+    }
+
+    #[test]
+    fn renders_content_blocks() {
+        let rendered = render_content(&[
+            json!({"type": "text", "text": "hello"}),
+            json!({"type": "image", "mimeType": "image/png", "data": "AAAA"}),
+            json!({"type": "resource", "resource": {"uri": "file:///a.txt", "text": "body"}}),
+            json!({"uri": "db://blob", "blob": "AAAAAAAA", "mimeType": "application/zip"}),
+        ]);
+        assert_eq!(
+            rendered,
+            "hello\n[image content: image/png, ~3 bytes]\n[resource file:///a.txt]\nbody\n[binary resource db://blob: application/zip, ~6 bytes]"
+        );
+    }
+}
@@ -0,0 +1,449 @@
+//! MCP server mode (`zeroclaw mcp-serve`): exposes zeroclaw's tools and
+//! memory to other MCP clients over stdio.
+
+use super::protocol::{self, Incoming};
+use crate::config::Config;
+use crate::memory::{self, Memory, MemoryEntry};
+use crate::security::audit::{self, ApprovalDecision, ToolExecutionLog};
+use crate::security::SecurityPolicy;
+use crate::tools::{self, Tool};
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+/// Protocol revisions the server accepts from clients; anything else gets ours.
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+/// Resource URI scheme for memory entries.
+const MEMORY_URI_PREFIX: &str = "memory://";
+
+/// Request handler shared by the transport loop.
+pub struct McpServer {
+    tools: Vec<Box<dyn Tool>>,
+    memory: Option<Arc<dyn Memory>>,
+}
+
+impl McpServer {
+    /// `memory` enables `resources/*` over stored memory entries.
+    pub fn new(tools: Vec<Box<dyn Tool>>, memory: Option<Arc<dyn Memory>>) -> Self {
+        Self { tools, memory }
+    }
+
+    /// Handle one decoded message. Returns the response for requests and
+    /// `None` for notifications and stray responses.
+    pub async fn handle(&self, message: Value) -> Option<Value> {
+        let Some(Incoming::Request { id, method, params }) = protocol::classify(message) else {
+            return None;
+        };
+        Some(match self.dispatch(&method, params).await {
+            Ok(result) => protocol::response(id, result),
+            Err((code, message)) => protocol::error_response(id, code, &message),
+        })
+    }
+
+    async fn dispatch(&self, method: &str, params: Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => Ok(self.initialize(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({
+                "tools": self
+                    .tools
+                    .iter()
+                    .map(|tool| json!({
+                        "name": tool.name(),
+                        "description": tool.description(),
+                        "inputSchema": tool.parameters_schema(),
+                    }))
+                    .collect::<Vec<_>>()
+            })),
+            "tools/call" => self.call_tool(params).await,
+            "resources/list" if self.memory.is_some() => self.list_resources().await,
+            "resources/read" if self.memory.is_some() => self.read_resource(&params).await,
+            _ => Err((
+                protocol::METHOD_NOT_FOUND,
+                format!("Method not found: {method}"),
+            )),
+        }
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let requested = params
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .unwrap_or(protocol::PROTOCOL_VERSION);
+        let version = if SUPPORTED_PROTOCOL_VERSIONS.contains(&requested) {
+            requested
+        } else {
+            protocol::PROTOCOL_VERSION
+        };
+        let mut capabilities = json!({"tools": {}});
+        if self.memory.is_some() {
+            capabilities["resources"] = json!({});
+        }
+        json!({
+            "protocolVersion": version,
+            "capabilities": capabilities,
+            "serverInfo": {"name": "zeroclaw", "version": env!("CARGO_PKG_VERSION")},
+            "instructions": "Tools and long-term memory from a zeroclaw agent. \
+                Memory entries are exposed as memory:// resources; use memory_recall to search them.",
+        })
+    }
+
+    async fn call_tool(&self, params: Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or((protocol::INVALID_PARAMS, "Missing tool name".to_string()))?;
+        let tool = self
+            .tools
+            .iter()
+            .find(|tool| tool.name() == name)
+            .ok_or_else(|| (protocol::INVALID_PARAMS, format!("Unknown tool: {name}")))?;
+        let arguments = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+
+        let start = std::time::Instant::now();
+        // Tool failures are reported in-band (`isError`) so the calling model
+        // can see and react to them, as the MCP spec recommends. Each call
+        // counts as a turn of its own for per-turn limits.
+        let executed = crate::tools::turn::run_with(tool.execute(arguments.clone())).await;
+        let (text, is_error) = match executed {
+            Ok(result) if result.success => (result.output, false),
+            Ok(result) => (
+                result
+                    .error
+                    .filter(|e| !e.is_empty())
+                    .unwrap_or(result.output),
+                true,
+            ),
+            Err(e) => (format!("{e:#}"), true),
+        };
+        audit::record_tool_execution(ToolExecutionLog {
+            channel: "mcp",
+            session: None,
+            tool: name,
+            arguments: &arguments,
+            output: &text,
+            success: !is_error,
+            error: is_error.then_some(text.as_str()),
+            duration_ms: u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+            risk_level: tool.risk().as_str(),
+            approval: ApprovalDecision::NotRequired,
+        });
+        Ok(json!({
+            "content": [{"type": "text", "text": text}],
+            "isError": is_error,
+        }))
+    }
+
+    async fn list_resources(&self) -> Result<Value, (i64, String)> {
+        let memory = self.memory.as_ref().expect("checked by dispatch");
+        let entries = memory
+            .list(None, None)
+            .await
+            .map_err(|e| (protocol::INTERNAL_ERROR, format!("{e:#}")))?;
+        Ok(json!({
+            "resources": entries.iter().map(memory_resource).collect::<Vec<_>>()
+        }))
+    }
+
+    async fn read_resource(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let memory = self.memory.as_ref().expect("checked by dispatch");
+        let uri = params
+            .get("uri")
+            .and_then(Value::as_str)
+            .ok_or((protocol::INVALID_PARAMS, "Missing uri".to_string()))?;
+        let key = uri
+            .strip_prefix(MEMORY_URI_PREFIX)
+            .and_then(|key| urlencoding::decode(key).ok())
+            .ok_or_else(|| (protocol::INVALID_PARAMS, format!("Unknown resource: {uri}")))?;
+        let entry = memory
+            .get(&key)
+            .await
+            .map_err(|e| (protocol::INTERNAL_ERROR, format!("{e:#}")))?
+            .ok_or_else(|| {
+                (
+                    protocol::INVALID_PARAMS,
+                    format!("Resource not found: {uri}"),
+                )
+            })?;
+        Ok(json!({
+            "contents": [{"uri": uri, "mimeType": "text/plain", "text": entry.content}]
+        }))
+    }
+}
+
+fn memory_resource(entry: &MemoryEntry) -> Value {
+    json!({
+        "uri": format!("{MEMORY_URI_PREFIX}{}", urlencoding::encode(&entry.key)),
+        "name": entry.key,
+        "description": format!("{} memory, updated {}", entry.category, entry.timestamp),
+        "mimeType": "text/plain",
+    })
+}
+
+/// Build the tool registry from config and serve MCP over stdin/stdout until
+/// the client closes stdin. `only_tools` restricts the exposed tools.
+pub async fn serve_stdio(config: Config, only_tools: &[String], resources: bool) -> Result<()> {
+    let runtime: Arc<dyn crate::runtime::RuntimeAdapter> =
+        Arc::from(crate::runtime::create_runtime(&config.runtime)?);
+    let security = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
+    ));
+    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
+        &config.memory,
+        &config.embedding_routes,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?);
+    let (composio_key, composio_entity_id) = if config.composio.enabled {
+        (
+            config.composio.api_key.as_deref(),
+            Some(config.composio.entity_id.as_str()),
+        )
+    } else {
+        (None, None)
+    };
+    let mut registry = tools::all_tools_with_runtime(
+        Arc::new(config.clone()),
+        &security,
+        runtime,
+        mem.clone(),
+        composio_key,
+        composio_entity_id,
+        &config.browser,
+        &config.http_request,
+        &config.web_fetch,
+        &config.workspace_dir,
+        &config.agents,
+        config.api_key.as_deref(),
+        &config,
+    );
+    if !only_tools.is_empty() {
+        registry.retain(|tool| only_tools.iter().any(|name| name == tool.name()));
+    }
+    tracing::info!(tools = registry.len(), "MCP server ready on stdio");
+
+    let server = Arc::new(McpServer::new(registry, resources.then_some(mem)));
+    let (tx, mut rx) = mpsc::channel::<Value>(64);
+
+    let writer = tokio::spawn(async move {
+        let mut stdout = tokio::io::stdout();
+        while let Some(message) = rx.recv().await {
+            let mut line = serde_json::to_vec(&message)?;
+            line.push(b'\n');
+            stdout.write_all(&line).await?;
+            stdout.flush().await?;
+        }
+        Ok::<_, anyhow::Error>(())
+    });
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                let _ = tx
+                    .send(protocol::error_response(
+                        Value::Null,
+                        protocol::PARSE_ERROR,
+                        &format!("Parse error: {e}"),
+                    ))
+                    .await;
+                continue;
+            }
+        };
+        // Requests run concurrently so a slow tool does not block pings.
+        let server = server.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            if let Some(response) = server.handle(message).await {
+                let _ = tx.send(response).await;
+            }
+        });
+    }
+
+    drop(tx);
+    writer.await??;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryCategory, NoneMemory, SqliteMemory};
+    use crate::tools::ToolResult;
+    use async_trait::async_trait;
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo text back"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({"type": "object", "properties": {"text": {"type": "string"}}})
+        }
+
+        async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+            match args.get("text").and_then(Value::as_str) {
+                Some(text) => Ok(ToolResult {
+                    success: true,
+                    output: text.to_string(),
+                    error: None,
+                }),
+                None => Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("text is required".into()),
+                }),
+            }
+        }
+    }
+
+    fn request(id: u64, method: &str, params: Value) -> Value {
+        protocol::request(id, method, Some(params))
+    }
+
+    #[tokio::test]
+    async fn initialize_negotiates_version_and_capabilities() {
+        let server = McpServer::new(vec![Box::new(EchoTool)], None);
+        let response = server
+            .handle(request(
+                1,
+                "initialize",
+                json!({"protocolVersion": "2025-03-26"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+        assert!(response["result"]["capabilities"]
+            .get("resources")
+            .is_none());
+
+        let response = server
+            .handle(request(
+                2,
+                "initialize",
+                json!({"protocolVersion": "1999-01-01"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            response["result"]["protocolVersion"],
+            protocol::PROTOCOL_VERSION
+        );
+
+        assert!(server
+            .handle(protocol::notification("notifications/initialized", None))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn lists_and_calls_tools() {
+        let server = McpServer::new(vec![Box::new(EchoTool)], None);
+        let listed = server
+            .handle(request(1, "tools/list", json!({})))
+            .await
+            .unwrap();
+        assert_eq!(listed["result"]["tools"][0]["name"], "echo");
+        assert_eq!(
+            listed["result"]["tools"][0]["inputSchema"]["type"],
+            "object"
+        );
+
+        let ok = server
+            .handle(request(
+                2,
+                "tools/call",
+                json!({"name": "echo", "arguments": {"text": "hi"}}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(ok["result"]["content"][0]["text"], "hi");
+        assert_eq!(ok["result"]["isError"], false);
+
+        let failed = server
+            .handle(request(3, "tools/call", json!({"name": "echo"})))
+            .await
+            .unwrap();
+        assert_eq!(failed["result"]["isError"], true);
+        assert_eq!(failed["result"]["content"][0]["text"], "text is required");
+
+        let unknown = server
+            .handle(request(4, "tools/call", json!({"name": "nope"})))
+            .await
+            .unwrap();
+        assert_eq!(unknown["error"]["code"], protocol::INVALID_PARAMS);
+
+        let missing = server
+            .handle(request(5, "prompts/list", json!({})))
+            .await
+            .unwrap();
+        assert_eq!(missing["error"]["code"], protocol::METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn memory_is_exposed_as_resources() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        mem.store(
+            "team lead",
+            "Dana leads the platform team",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        let server = McpServer::new(Vec::new(), Some(mem));
+
+        let listed = server
+            .handle(request(1, "resources/list", json!({})))
+            .await
+            .unwrap();
+        let resources = listed["result"]["resources"].as_array().unwrap();
+        assert!(!resources.is_empty());
+        let uri = resources
+            .iter()
+            .find(|r| r["name"] == "team lead")
+            .map(|r| r["uri"].as_str().unwrap().to_string())
+            .unwrap();
+        assert_eq!(uri, "memory://team%20lead");
+
+        let read = server
+            .handle(request(2, "resources/read", json!({"uri": uri})))
+            .await
+            .unwrap();
+        assert!(read["result"]["contents"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("Dana leads"));
+
+        let none = McpServer::new(Vec::new(), Some(Arc::new(NoneMemory::new())));
+        let missing = none
+            .handle(request(
+                3,
+                "resources/read",
+                json!({"uri": "memory://ghost"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(missing["error"]["code"], protocol::INVALID_PARAMS);
+    }
+}
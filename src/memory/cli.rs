@@ -4,9 +4,7 @@ use super::{
     MemoryBackendKind,
 };
 use crate::config::Config;
-#[cfg(feature = "memory-postgres")]
-use anyhow::Context;
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use console::style;
 
 /// Handle `zeroclaw memory <subcommand>` CLI commands.
@@ -23,9 +21,34 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
         crate::MemoryCommands::Clear { key, category, yes } => {
             handle_clear(config, key, category, yes).await
         }
+        crate::MemoryCommands::Add {
+            content,
+            key,
+            category,
+            file,
+        } => handle_add(config, content, key, category, file).await,
+        crate::MemoryCommands::Search { query, limit } => {
+            handle_search(config, &query, limit).await
+        }
+        crate::MemoryCommands::Forget { key, yes } => {
+            let mem = create_cli_memory(config)?;
+            handle_clear_key(&*mem, &key, yes).await
+        }
+        crate::MemoryCommands::Compact => handle_compact(config).await,
     }
 }
 
+/// Create the full memory backend, including the embedding provider, for
+/// commands that write or rank by meaning (`add`, `search`).
+fn create_semantic_memory(config: &Config) -> Result<Box<dyn Memory>> {
+    super::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )
+}
+
 /// Create a lightweight memory backend for CLI management operations.
 ///
 /// CLI commands (list/get/stats/clear) never use vector search, so we skip
@@ -71,6 +94,7 @@ fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
         MemoryBackendKind::Postgres => {
             bail!("memory backend 'postgres' requires the 'memory-postgres' feature to be enabled");
         }
+        MemoryBackendKind::Redis => create_semantic_memory(config),
         _ => create_memory_for_migration(&backend, &config.workspace_dir),
     }
 }
@@ -156,7 +180,10 @@ async fn handle_get(config: &Config, key: &str) -> Result<()> {
 fn print_entry(entry: &super::traits::MemoryEntry) {
     println!("Key:       {}", style(&entry.key).white().bold());
     println!("Category:  {}", entry.category);
-    println!("Timestamp: {}", entry.timestamp);
+    println!(
+        "Timestamp: {}",
+        crate::time::display_stored(&entry.timestamp, "%Y-%m-%d %H:%M:%S %Z")
+    );
     if let Some(sid) = &entry.session_id {
         println!("Session:   {sid}");
     }
@@ -297,6 +324,165 @@ async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()>
     Ok(())
 }
 
+async fn handle_add(
+    config: &Config,
+    content: Option<String>,
+    key: Option<String>,
+    category: Option<String>,
+    file: Option<std::path::PathBuf>,
+) -> Result<()> {
+    let mem = create_semantic_memory(config)?;
+
+    if let Some(path) = file {
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let cat = parse_category(category.as_deref().unwrap_or("document"));
+        let prefix = key.unwrap_or_else(|| document_key_prefix(&path));
+        let chunks = super::chunker::chunk_markdown(&text, config.memory.chunk_max_tokens);
+        if chunks.is_empty() {
+            bail!("{} is empty", path.display());
+        }
+        for chunk in &chunks {
+            let chunk_key = format!("{prefix}:{}", chunk.index);
+            mem.store(&chunk_key, &chunk.content, cat.clone(), None)
+                .await?;
+        }
+        println!(
+            "{} Stored {} chunks from {} as '{prefix}:*' [{cat}]",
+            style("✓").green().bold(),
+            chunks.len(),
+            path.display(),
+        );
+        return Ok(());
+    }
+
+    let Some(content) = content.filter(|c| !c.trim().is_empty()) else {
+        bail!("Provide the text to remember or --file <path>");
+    };
+    let cat = parse_category(category.as_deref().unwrap_or("core"));
+    let key = key.unwrap_or_else(|| format!("note_{}", uuid::Uuid::new_v4()));
+    mem.store(&key, content.trim(), cat.clone(), None).await?;
+    println!("{} Stored key: {key} [{cat}]", style("✓").green().bold());
+    Ok(())
+}
+
+async fn handle_compact(config: &Config) -> Result<()> {
+    let mem = create_semantic_memory(config)?;
+    let embedder = super::create_embedder(&config.memory, config.api_key.as_deref());
+    let provider = crate::providers::create_provider(
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config.api_key.as_deref(),
+    )?;
+    let compaction = &config.memory.compaction;
+    let model = compaction
+        .model
+        .clone()
+        .or_else(|| config.default_model.clone())
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
+
+    let report =
+        super::compaction::compact(&*mem, &*embedder, &*provider, &model, compaction).await?;
+    println!(
+        "{} Compaction: {} expired, {} episodic entries summarized into {} facts, {} duplicates removed",
+        style("✓").green().bold(),
+        report.expired,
+        report.summarized,
+        report.facts_stored,
+        report.deduplicated,
+    );
+    Ok(())
+}
+
+/// Handle `zeroclaw index <dir>`: index once, or keep re-indexing with `--watch`.
+pub async fn handle_index(
+    config: &Config,
+    dir: &std::path::Path,
+    watch: bool,
+    interval_secs: u64,
+) -> Result<()> {
+    let mem = create_semantic_memory(config)?;
+    let max_tokens = config.memory.chunk_max_tokens;
+    let report =
+        super::indexer::index_directory(&*mem, &config.workspace_dir, dir, max_tokens).await?;
+    print_index_report(dir, &report);
+    if !watch {
+        return Ok(());
+    }
+
+    println!(
+        "Watching {} for changes every {}s (Ctrl+C to stop)",
+        dir.display(),
+        interval_secs.max(1)
+    );
+    let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs.max(1)));
+    ticker.tick().await;
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+        match super::indexer::index_directory(&*mem, &config.workspace_dir, dir, max_tokens).await {
+            Ok(report) if report.indexed > 0 || report.removed > 0 => {
+                print_index_report(dir, &report);
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("{} Re-index failed: {e:#}", style("✗").red().bold()),
+        }
+    }
+}
+
+fn print_index_report(dir: &std::path::Path, report: &super::indexer::IndexReport) {
+    println!(
+        "{} {}: {} indexed ({} chunks), {} unchanged, {} removed",
+        style("✓").green().bold(),
+        dir.display(),
+        report.indexed,
+        report.chunks,
+        report.unchanged,
+        report.removed,
+    );
+    for (path, reason) in &report.skipped {
+        println!(
+            "  {} skipped {}: {reason}",
+            style("!").yellow(),
+            path.display()
+        );
+    }
+}
+
+async fn handle_search(config: &Config, query: &str, limit: usize) -> Result<()> {
+    let mem = create_semantic_memory(config)?;
+    let entries = mem.recall(query, limit.max(1), None).await?;
+
+    if entries.is_empty() {
+        println!("No memories match: {query}");
+        return Ok(());
+    }
+
+    for entry in &entries {
+        let score = entry
+            .score
+            .map_or_else(|| "   -".to_string(), |s| format!("{s:.2}"));
+        println!(
+            "{score}  {} [{}]",
+            style(&entry.key).white().bold(),
+            entry.category,
+        );
+        println!("      {}", truncate_content(&entry.content, 80));
+    }
+
+    Ok(())
+}
+
+/// Key prefix for document chunks: `doc:<file name>`.
+fn document_key_prefix(path: &std::path::Path) -> String {
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    );
+    format!("doc:{name}")
+}
+
 fn parse_category(s: &str) -> MemoryCategory {
     match s.trim().to_ascii_lowercase().as_str() {
         "core" => MemoryCategory::Core,
@@ -353,6 +539,14 @@ mod tests {
         assert_eq!(truncate_content("first\nsecond", 20), "first");
     }
 
+    #[test]
+    fn document_key_prefix_uses_file_name() {
+        assert_eq!(
+            document_key_prefix(std::path::Path::new("notes/architecture.md")),
+            "doc:architecture.md"
+        );
+    }
+
     #[test]
     fn truncate_content_empty_string() {
         assert_eq!(truncate_content("", 10), "");
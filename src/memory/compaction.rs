@@ -0,0 +1,448 @@
+// Memory compaction — keeps the store from growing without bound.
+//
+// A pass runs three steps in order:
+// 1. TTL pruning: entries older than `ttl_days[category]` are deleted.
+// 2. Consolidation: episodic entries (`conversation`, `daily`) older than
+//    `summarize_after_days` are summarized in batches into `core` facts, and
+//    the originals are removed once their batch is summarized.
+// 3. Deduplication: `core` entries whose embeddings are at least
+//    `dedup_threshold` similar collapse to the most recent one.
+
+use super::embeddings::EmbeddingProvider;
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::vector::cosine_similarity;
+use crate::config::{Config, MemoryCompactionConfig};
+use crate::providers::Provider;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::fmt::Write;
+
+/// Cap on `core` entries compared during deduplication (pairwise cost).
+const DEDUP_MAX_ENTRIES: usize = 2_000;
+
+/// Texts embedded per request during deduplication.
+const EMBED_BATCH: usize = 64;
+
+const SUMMARY_PROMPT: &str = "You consolidate an AI assistant's old conversation notes into \
+long-term memory. Return a JSON array of short, self-contained facts about the user or their \
+work that are still worth knowing: preferences, decisions, names, goals, commitments, \
+environment details. Merge repeated information and drop small talk and transient requests. \
+Return [] when nothing qualifies. Output only the JSON array.";
+
+/// What one compaction pass did.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CompactionReport {
+    pub expired: usize,
+    pub summarized: usize,
+    pub facts_stored: usize,
+    pub deduplicated: usize,
+}
+
+impl CompactionReport {
+    pub fn total_actions(&self) -> usize {
+        self.expired + self.summarized + self.facts_stored + self.deduplicated
+    }
+}
+
+/// Run one compaction pass over `memory`.
+pub async fn compact(
+    memory: &dyn Memory,
+    embedder: &dyn EmbeddingProvider,
+    provider: &dyn Provider,
+    model: &str,
+    config: &MemoryCompactionConfig,
+) -> Result<CompactionReport> {
+    let now = Utc::now();
+    let mut report = CompactionReport {
+        expired: prune_expired(memory, config, now).await?,
+        ..CompactionReport::default()
+    };
+
+    if config.summarize_after_days > 0 {
+        let cutoff = now - Duration::days(i64::from(config.summarize_after_days));
+        let (summarized, stored) =
+            consolidate_episodic(memory, provider, model, config.batch_size, cutoff).await?;
+        report.summarized = summarized;
+        report.facts_stored = stored;
+    }
+
+    if config.dedup_threshold > 0.0 {
+        report.deduplicated = deduplicate(memory, embedder, config.dedup_threshold).await?;
+    }
+
+    Ok(report)
+}
+
+/// Daemon worker: run a compaction pass every `interval_hours`.
+pub async fn run(config: Config) -> Result<()> {
+    let compaction = config.memory.compaction.clone();
+    let memory = super::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let embedder = super::create_embedder(&config.memory, config.api_key.as_deref());
+    let provider = crate::providers::create_resilient_provider_with_options(
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &crate::providers::ProviderRuntimeOptions {
+            auth_profile_override: None,
+            provider_api_url: config.api_url.clone(),
+            zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+            secrets_encrypt: config.secrets.encrypt,
+            reasoning_enabled: config.runtime.reasoning_enabled,
+        },
+    )?;
+    let model = compaction
+        .model
+        .clone()
+        .or_else(|| config.default_model.clone())
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
+
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        u64::from(compaction.interval_hours.max(1)) * 3600,
+    ));
+    loop {
+        interval.tick().await;
+        match compact(&*memory, &*embedder, &*provider, &model, &compaction).await {
+            Ok(report) => {
+                crate::health::mark_component_ok("memory_compaction");
+                if report.total_actions() > 0 {
+                    tracing::info!(
+                        expired = report.expired,
+                        summarized = report.summarized,
+                        facts_stored = report.facts_stored,
+                        deduplicated = report.deduplicated,
+                        "memory compaction complete"
+                    );
+                }
+            }
+            Err(e) => {
+                crate::health::mark_component_error("memory_compaction", e.to_string());
+                tracing::warn!("memory compaction failed: {e:#}");
+            }
+        }
+    }
+}
+
+async fn prune_expired(
+    memory: &dyn Memory,
+    config: &MemoryCompactionConfig,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let mut removed = 0;
+    for (category, days) in &config.ttl_days {
+        if *days == 0 {
+            continue;
+        }
+        let category = parse_category(category);
+        let cutoff = now - Duration::days(i64::from(*days));
+        for entry in memory.list(Some(&category), None).await? {
+            if is_older_than(&entry, cutoff) && memory.forget(&entry.key).await? {
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// Summarize old episodic entries into facts. Returns (entries summarized,
+/// facts stored). A batch whose summary fails is left untouched.
+async fn consolidate_episodic(
+    memory: &dyn Memory,
+    provider: &dyn Provider,
+    model: &str,
+    batch_size: usize,
+    cutoff: DateTime<Utc>,
+) -> Result<(usize, usize)> {
+    let mut old = Vec::new();
+    for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
+        old.extend(
+            memory
+                .list(Some(&category), None)
+                .await?
+                .into_iter()
+                .filter(|e| is_older_than(e, cutoff) && !super::is_assistant_autosave_key(&e.key)),
+        );
+    }
+    old.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+    let mut summarized = 0;
+    let mut stored = 0;
+    for batch in old.chunks(batch_size.max(1)) {
+        let mut notes = String::new();
+        for entry in batch {
+            let _ = writeln!(notes, "- [{}] {}", entry.timestamp, entry.content);
+        }
+        let raw = match provider
+            .chat_with_system(Some(SUMMARY_PROMPT), &notes, model, 0.0)
+            .await
+        {
+            Ok(raw) => raw,
+            Err(e) => {
+                tracing::warn!("memory consolidation summary failed: {e}");
+                continue;
+            }
+        };
+        let facts = super::facts::parse_facts(&raw);
+        stored += super::facts::remember_facts(memory, &facts, None).await;
+        for entry in batch {
+            if memory.forget(&entry.key).await? {
+                summarized += 1;
+            }
+        }
+    }
+    Ok((summarized, stored))
+}
+
+/// Collapse near-identical `core` entries, keeping the most recent.
+///
+/// Falls back to normalized-text comparison when no embedding provider is
+/// configured.
+async fn deduplicate(
+    memory: &dyn Memory,
+    embedder: &dyn EmbeddingProvider,
+    threshold: f64,
+) -> Result<usize> {
+    let mut entries = memory.list(Some(&MemoryCategory::Core), None).await?;
+    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    entries.truncate(DEDUP_MAX_ENTRIES);
+
+    let mut vectors: Vec<Vec<f32>> = Vec::with_capacity(entries.len());
+    for batch in entries.chunks(EMBED_BATCH) {
+        let texts: Vec<&str> = batch.iter().map(|e| e.content.as_str()).collect();
+        let embedded = embedder.embed(&texts).await?;
+        if embedded.len() != texts.len() {
+            vectors.clear();
+            break;
+        }
+        vectors.extend(embedded);
+    }
+    let use_vectors = vectors.len() == entries.len();
+
+    let mut kept: Vec<usize> = Vec::new();
+    let mut removed = 0;
+    for (i, entry) in entries.iter().enumerate() {
+        let duplicate = kept.iter().any(|&k| {
+            if use_vectors {
+                f64::from(cosine_similarity(&vectors[i], &vectors[k])) >= threshold
+            } else {
+                normalize(&entries[k].content) == normalize(&entry.content)
+            }
+        });
+        if duplicate {
+            if memory.forget(&entry.key).await? {
+                removed += 1;
+            }
+        } else {
+            kept.push(i);
+        }
+    }
+    Ok(removed)
+}
+
+fn is_older_than(entry: &MemoryEntry, cutoff: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(&entry.timestamp)
+        .map(|ts| ts.with_timezone(&Utc) < cutoff)
+        .unwrap_or(false)
+}
+
+fn parse_category(raw: &str) -> MemoryCategory {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "core" => MemoryCategory::Core,
+        "daily" => MemoryCategory::Daily,
+        "conversation" => MemoryCategory::Conversation,
+        other => MemoryCategory::Custom(other.to_string()),
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches('.')
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::embeddings::NoopEmbedding;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    struct FixedProvider(&'static str);
+
+    #[async_trait]
+    impl Provider for FixedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    /// In-memory backend with controllable timestamps.
+    #[derive(Default)]
+    struct VecMemory(Mutex<Vec<MemoryEntry>>);
+
+    impl VecMemory {
+        fn insert(&self, key: &str, content: &str, category: MemoryCategory, days_ago: i64) {
+            self.0.lock().unwrap().push(MemoryEntry {
+                id: key.into(),
+                key: key.into(),
+                content: content.into(),
+                category,
+                timestamp: (Utc::now() - Duration::days(days_ago)).to_rfc3339(),
+                session_id: None,
+                score: None,
+            });
+        }
+
+        fn keys(&self) -> Vec<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|e| e.key.clone())
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl Memory for VecMemory {
+        fn name(&self) -> &str {
+            "vec"
+        }
+
+        async fn store(
+            &self,
+            key: &str,
+            content: &str,
+            category: MemoryCategory,
+            _session_id: Option<&str>,
+        ) -> Result<()> {
+            self.insert(key, content, category, 0);
+            Ok(())
+        }
+
+        async fn recall(
+            &self,
+            _query: &str,
+            _limit: usize,
+            _session_id: Option<&str>,
+        ) -> Result<Vec<MemoryEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|e| e.key == key)
+                .cloned())
+        }
+
+        async fn list(
+            &self,
+            category: Option<&MemoryCategory>,
+            _session_id: Option<&str>,
+        ) -> Result<Vec<MemoryEntry>> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| category.is_none_or(|c| &e.category == c))
+                .cloned()
+                .collect())
+        }
+
+        async fn forget(&self, key: &str) -> Result<bool> {
+            let mut entries = self.0.lock().unwrap();
+            let before = entries.len();
+            entries.retain(|e| e.key != key);
+            Ok(entries.len() != before)
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(self.0.lock().unwrap().len())
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+    }
+
+    fn config() -> MemoryCompactionConfig {
+        MemoryCompactionConfig {
+            enabled: true,
+            ..MemoryCompactionConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn ttl_prunes_only_expired_entries_of_listed_categories() {
+        let mem = VecMemory::default();
+        mem.insert("old_daily", "a", MemoryCategory::Daily, 100);
+        mem.insert("new_daily", "b", MemoryCategory::Daily, 1);
+        mem.insert("old_core", "c", MemoryCategory::Core, 100);
+        let mut cfg = config();
+        cfg.summarize_after_days = 0;
+        cfg.ttl_days.insert("daily".into(), 30);
+
+        let report = compact(&mem, &NoopEmbedding, &FixedProvider("[]"), "m", &cfg)
+            .await
+            .unwrap();
+        assert_eq!(report.expired, 1);
+        assert_eq!(mem.keys(), vec!["new_daily", "old_core"]);
+    }
+
+    #[tokio::test]
+    async fn old_episodic_entries_become_core_facts() {
+        let mem = VecMemory::default();
+        mem.insert(
+            "conv_1",
+            "I moved to Lisbon",
+            MemoryCategory::Conversation,
+            30,
+        );
+        mem.insert(
+            "conv_2",
+            "what time is it",
+            MemoryCategory::Conversation,
+            30,
+        );
+        mem.insert("conv_recent", "hello", MemoryCategory::Conversation, 1);
+        let provider = FixedProvider("[\"User lives in Lisbon\"]");
+
+        let report = compact(&mem, &NoopEmbedding, &provider, "m", &config())
+            .await
+            .unwrap();
+        assert_eq!((report.summarized, report.facts_stored), (2, 1));
+        let core = mem.list(Some(&MemoryCategory::Core), None).await.unwrap();
+        assert_eq!(core[0].content, "User lives in Lisbon");
+        assert!(mem.get("conv_recent").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn dedup_without_embeddings_keeps_newest_text_match() {
+        let mem = VecMemory::default();
+        mem.insert("older", "Prefers tabs.", MemoryCategory::Core, 5);
+        mem.insert("newer", "prefers  tabs", MemoryCategory::Core, 1);
+        mem.insert("other", "Uses Rust", MemoryCategory::Core, 3);
+
+        let removed = deduplicate(&mem, &NoopEmbedding, 0.95).await.unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(mem.keys(), vec!["newer", "other"]);
+    }
+}
@@ -0,0 +1,161 @@
+// Salient-fact extraction — turns a finished exchange into durable memories.
+//
+// After a reply, the model is asked for facts worth keeping (preferences,
+// decisions, names, commitments). Each fact is stored as its own `core`
+// entry so the backend embeds it and later turns can recall it by meaning.
+
+use super::traits::{Memory, MemoryCategory};
+use crate::providers::Provider;
+
+/// Upper bound on facts kept from one exchange.
+const MAX_FACTS_PER_TURN: usize = 5;
+
+/// Exchanges longer than this are cut before extraction.
+const MAX_EXCHANGE_CHARS: usize = 8_000;
+
+const EXTRACTION_PROMPT: &str = "You extract long-term memories from a conversation exchange. \
+Return a JSON array of short, self-contained facts about the user or their work that will still \
+matter in future conversations: preferences, decisions, names, goals, commitments, environment \
+details. Skip greetings, transient requests, tool output and anything already obvious. \
+When the exchange names the speaker, write each fact about them with their name \
+(\"Alice prefers…\", not \"The user prefers…\"). \
+Return [] when nothing qualifies. Output only the JSON array.";
+
+/// Ask `provider` for durable facts in one user/assistant exchange;
+/// `speaker` names the person who wrote `user_message`, when known.
+pub async fn extract_facts(
+    provider: &dyn Provider,
+    model: &str,
+    user_message: &str,
+    assistant_reply: &str,
+    speaker: Option<&str>,
+) -> anyhow::Result<Vec<String>> {
+    let speaker = speaker.map_or_else(|| "User".to_string(), |name| format!("User ({name})"));
+    let exchange: String = format!("{speaker}: {user_message}\n\nAssistant: {assistant_reply}")
+        .chars()
+        .take(MAX_EXCHANGE_CHARS)
+        .collect();
+    let raw = provider
+        .chat_with_system(Some(EXTRACTION_PROMPT), &exchange, model, 0.0)
+        .await?;
+    Ok(parse_facts(&raw))
+}
+
+/// Store facts that are not already remembered. Returns how many were stored.
+pub async fn remember_facts(
+    memory: &dyn Memory,
+    facts: &[String],
+    session_id: Option<&str>,
+) -> usize {
+    let mut stored = 0;
+    for fact in facts {
+        if is_known(memory, fact).await {
+            continue;
+        }
+        let key = format!("fact_{}", uuid::Uuid::new_v4());
+        match memory
+            .store(&key, fact, MemoryCategory::Core, session_id)
+            .await
+        {
+            Ok(()) => stored += 1,
+            Err(e) => tracing::warn!("Failed to store extracted fact: {e}"),
+        }
+    }
+    stored
+}
+
+/// Extract facts from an exchange and store the new ones, logging failures.
+pub async fn extract_and_remember(
+    provider: &dyn Provider,
+    model: &str,
+    memory: &dyn Memory,
+    user_message: &str,
+    assistant_reply: &str,
+    speaker: Option<&str>,
+    session_id: Option<&str>,
+) {
+    match extract_facts(provider, model, user_message, assistant_reply, speaker).await {
+        Ok(facts) if !facts.is_empty() => {
+            let stored = remember_facts(memory, &facts, session_id).await;
+            tracing::debug!(extracted = facts.len(), stored, "Fact extraction complete");
+        }
+        Ok(_) => {}
+        Err(e) => tracing::debug!("Fact extraction failed: {e}"),
+    }
+}
+
+async fn is_known(memory: &dyn Memory, fact: &str) -> bool {
+    let normalized = normalize(fact);
+    memory
+        .recall(fact, 3, None)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .any(|entry| normalize(&entry.content) == normalized)
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches('.')
+        .to_lowercase()
+}
+
+/// Parse the model's reply, tolerating code fences and surrounding prose.
+pub(super) fn parse_facts(raw: &str) -> Vec<String> {
+    let Some(start) = raw.find('[') else {
+        return Vec::new();
+    };
+    let Some(end) = raw.rfind(']') else {
+        return Vec::new();
+    };
+    if end < start {
+        return Vec::new();
+    }
+    serde_json::from_str::<Vec<serde_json::Value>>(&raw[start..=end])
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|v| v.as_str().map(|s| s.trim().to_string()))
+        .filter(|s| !s.is_empty())
+        .take(MAX_FACTS_PER_TURN)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parse_facts_handles_fences_and_empty() {
+        let raw = "```json\n[\"Prefers metric units\", \"\", \"Works at ACME\"]\n```";
+        assert_eq!(
+            parse_facts(raw),
+            vec![
+                "Prefers metric units".to_string(),
+                "Works at ACME".to_string()
+            ]
+        );
+        assert!(parse_facts("[]").is_empty());
+        assert!(parse_facts("nothing to remember").is_empty());
+    }
+
+    #[test]
+    fn parse_facts_caps_count() {
+        let raw = serde_json::to_string(&vec!["fact"; 20]).unwrap();
+        assert_eq!(parse_facts(&raw).len(), MAX_FACTS_PER_TURN);
+    }
+
+    #[tokio::test]
+    async fn remember_facts_skips_known_facts() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let facts = vec!["User prefers metric units".to_string()];
+        assert_eq!(remember_facts(&mem, &facts, None).await, 1);
+        let again = vec!["user prefers  metric units.".to_string()];
+        assert_eq!(remember_facts(&mem, &again, None).await, 0);
+        assert_eq!(mem.count().await.unwrap(), 1);
+    }
+}
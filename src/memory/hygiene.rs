@@ -1,7 +1,7 @@
 use crate::config::MemoryConfig;
 use anyhow::Result;
 use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
-use rusqlite::{params, Connection};
+use rusqlite::params;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -305,10 +305,10 @@ fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<
         return Ok(0);
     }
 
-    let conn = Connection::open(db_path)?;
+    let conn = crate::security::storage_encryption::open(&db_path)?;
     // Use WAL so hygiene pruning doesn't block agent reads
     conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
-    let cutoff = (Local::now() - Duration::days(i64::from(retention_days))).to_rfc3339();
+    let cutoff = (Utc::now() - Duration::days(i64::from(retention_days))).to_rfc3339();
 
     let affected = conn.execute(
         "DELETE FROM memories WHERE category = 'conversation' AND updated_at < ?1",
@@ -378,6 +378,7 @@ fn split_name(filename: &str) -> (&str, &str) {
 mod tests {
     use super::*;
     use crate::memory::{Memory, MemoryCategory, SqliteMemory};
+    use rusqlite::Connection;
     use tempfile::TempDir;
 
     fn default_cfg() -> MemoryConfig {
@@ -510,7 +511,7 @@ mod tests {
 
         let db_path = workspace.join("memory").join("brain.db");
         let conn = Connection::open(&db_path).unwrap();
-        let old_cutoff = (Local::now() - Duration::days(60)).to_rfc3339();
+        let old_cutoff = (Utc::now() - Duration::days(60)).to_rfc3339();
         conn.execute(
             "UPDATE memories SET created_at = ?1, updated_at = ?1 WHERE key = 'conv_old'",
             params![old_cutoff],
@@ -0,0 +1,411 @@
+// Document indexer — turns a directory of notes into citable memories.
+//
+// Markdown, text and (with `rag-pdf`) PDF files are split into line-spanned
+// chunks and stored as `document` entries keyed `index:<path>#L<a>-L<b>`, so
+// recall results carry their own citation. A manifest in `state/` records
+// each file's hash and chunk keys; re-runs only touch files that changed
+// and drop chunks of files that disappeared.
+
+use super::traits::{Memory, MemoryCategory};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Key prefix for indexed document chunks.
+pub const INDEX_KEY_PREFIX: &str = "index:";
+
+/// Memory category used for indexed chunks.
+pub const DOCUMENT_CATEGORY: &str = "document";
+
+const MANIFEST_FILE: &str = "index_manifest.json";
+
+const TEXT_EXTENSIONS: &[&str] = &["md", "markdown", "txt", "text", "rst", "org"];
+
+/// A chunk of a source file with its 1-based inclusive line span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineChunk {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct FileEntry {
+    hash: String,
+    modified: u64,
+    size: u64,
+    keys: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    files: BTreeMap<PathBuf, FileEntry>,
+}
+
+/// What one indexing pass did.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IndexReport {
+    pub indexed: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    pub chunks: usize,
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+/// Index every supported file under `root` into `memory`.
+///
+/// Files whose size, mtime and content hash match the manifest are left
+/// alone; changed files have their old chunks forgotten before the new ones
+/// are stored.
+pub async fn index_directory(
+    memory: &dyn Memory,
+    workspace_dir: &Path,
+    root: &Path,
+    max_tokens: usize,
+) -> Result<IndexReport> {
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("Cannot index {}", root.display()))?;
+    anyhow::ensure!(root.is_dir(), "{} is not a directory", root.display());
+
+    let manifest_path = manifest_path(workspace_dir);
+    let mut manifest = load_manifest(&manifest_path);
+    let mut report = IndexReport::default();
+    let category = MemoryCategory::Custom(DOCUMENT_CATEGORY.into());
+
+    let files = collect_files(&root);
+    for path in &files {
+        let meta = match std::fs::metadata(path) {
+            Ok(meta) => meta,
+            Err(e) => {
+                report.skipped.push((path.clone(), e.to_string()));
+                continue;
+            }
+        };
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs());
+        let size = meta.len();
+
+        if let Some(entry) = manifest.files.get(path) {
+            if entry.modified == modified && entry.size == size {
+                report.unchanged += 1;
+                continue;
+            }
+        }
+
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                report.skipped.push((path.clone(), e.to_string()));
+                continue;
+            }
+        };
+        let hash = hex::encode(Sha256::digest(&bytes));
+        if let Some(entry) = manifest.files.get_mut(path) {
+            if entry.hash == hash {
+                entry.modified = modified;
+                entry.size = size;
+                report.unchanged += 1;
+                continue;
+            }
+        }
+
+        let text = match extract_text(path, &bytes) {
+            Ok(text) => text,
+            Err(e) => {
+                report.skipped.push((path.clone(), e.to_string()));
+                continue;
+            }
+        };
+
+        if let Some(old) = manifest.files.remove(path) {
+            forget_keys(memory, &old.keys).await;
+        }
+
+        let mut keys = Vec::new();
+        for chunk in chunk_lines(&text, max_tokens) {
+            let key = chunk_key(path, &chunk);
+            memory
+                .store(&key, &chunk.content, category.clone(), None)
+                .await
+                .with_context(|| format!("Failed to store {key}"))?;
+            keys.push(key);
+        }
+        report.chunks += keys.len();
+        report.indexed += 1;
+        manifest.files.insert(
+            path.clone(),
+            FileEntry {
+                hash,
+                modified,
+                size,
+                keys,
+            },
+        );
+    }
+
+    let stale: Vec<PathBuf> = manifest
+        .files
+        .keys()
+        .filter(|p| p.starts_with(&root) && files.binary_search(p).is_err())
+        .cloned()
+        .collect();
+    for path in stale {
+        if let Some(entry) = manifest.files.remove(&path) {
+            forget_keys(memory, &entry.keys).await;
+            report.removed += 1;
+        }
+    }
+
+    save_manifest(&manifest_path, &manifest)?;
+    Ok(report)
+}
+
+/// The `path:Lstart-Lend` citation for an indexed chunk key, if it is one.
+pub fn document_citation(key: &str) -> Option<String> {
+    let rest = key.strip_prefix(INDEX_KEY_PREFIX)?;
+    let (path, span) = rest.rsplit_once("#L")?;
+    let (start, end) = span.split_once("-L")?;
+    if start == end {
+        Some(format!("{path}:L{start}"))
+    } else {
+        Some(format!("{path}:L{start}-L{end}"))
+    }
+}
+
+/// Label for a memory-context line: the citation for indexed chunks, the
+/// key otherwise. The flag is true when the entry is a citable source.
+pub fn context_label(key: &str) -> (String, bool) {
+    match document_citation(key) {
+        Some(citation) => (format!("source {citation}"), true),
+        None => (key.to_string(), false),
+    }
+}
+
+/// Appended to memory context that contains indexed sources.
+pub const CITATION_HINT: &str =
+    "When you use a `source` entry above, cite it as (path:Lstart-Lend) in your answer.\n";
+
+/// Split `text` into chunks of at most `max_tokens` (≈4 chars per token),
+/// breaking at headings and blank lines where possible.
+pub fn chunk_lines(text: &str, max_tokens: usize) -> Vec<LineChunk> {
+    let max_chars = max_tokens.max(1) * 4;
+    let mut chunks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut current_chars = 0usize;
+    let mut start_line = 1usize;
+    let mut last_blank: Option<usize> = None;
+
+    let flush = |lines: &[&str], start: usize, chunks: &mut Vec<LineChunk>| {
+        let content = lines.join("\n");
+        if content.trim().is_empty() {
+            return;
+        }
+        let leading = lines.iter().take_while(|l| l.trim().is_empty()).count();
+        let trailing = lines
+            .iter()
+            .rev()
+            .take_while(|l| l.trim().is_empty())
+            .count();
+        chunks.push(LineChunk {
+            start_line: start + leading,
+            end_line: start + lines.len() - 1 - trailing,
+            content: content.trim().to_string(),
+        });
+    };
+
+    for (idx, line) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let is_heading = line.starts_with('#') && line.trim_start_matches('#').starts_with(' ');
+
+        if is_heading && current.iter().any(|l| !l.trim().is_empty()) {
+            flush(&current, start_line, &mut chunks);
+            current.clear();
+            current_chars = 0;
+            start_line = line_no;
+            last_blank = None;
+        } else if current_chars + line.len() > max_chars && !current.is_empty() {
+            // Prefer to break at the last blank line so paragraphs stay whole.
+            let split = last_blank.filter(|&b| b > 0).unwrap_or(current.len());
+            let tail: Vec<&str> = current.split_off(split);
+            flush(&current, start_line, &mut chunks);
+            start_line += current.len();
+            current = tail;
+            current_chars = current.iter().map(|l| l.len() + 1).sum();
+            last_blank = None;
+        }
+
+        if line.trim().is_empty() {
+            last_blank = Some(current.len());
+        }
+        current.push(line);
+        current_chars += line.len() + 1;
+    }
+    flush(&current, start_line, &mut chunks);
+    chunks
+}
+
+fn chunk_key(path: &Path, chunk: &LineChunk) -> String {
+    format!(
+        "{INDEX_KEY_PREFIX}{}#L{}-L{}",
+        path.display(),
+        chunk.start_line,
+        chunk.end_line
+    )
+}
+
+async fn forget_keys(memory: &dyn Memory, keys: &[String]) {
+    for key in keys {
+        if let Err(e) = memory.forget(key).await {
+            tracing::warn!("Failed to forget stale chunk {key}: {e}");
+        }
+    }
+}
+
+fn collect_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if hidden || file_type.is_symlink() {
+                continue;
+            }
+            if file_type.is_dir() {
+                dirs.push(path);
+            } else if is_supported(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn is_supported(path: &Path) -> bool {
+    let ext = extension(path);
+    TEXT_EXTENSIONS.contains(&ext.as_str()) || (cfg!(feature = "rag-pdf") && ext == "pdf")
+}
+
+fn extract_text(path: &Path, bytes: &[u8]) -> Result<String> {
+    if extension(path) == "pdf" {
+        return extract_pdf(bytes);
+    }
+    String::from_utf8(bytes.to_vec()).map_err(|_| anyhow::anyhow!("not valid UTF-8"))
+}
+
+#[cfg(feature = "rag-pdf")]
+fn extract_pdf(bytes: &[u8]) -> Result<String> {
+    pdf_extract::extract_text_from_mem(bytes)
+        .map_err(|e| anyhow::anyhow!("PDF extraction failed: {e}"))
+}
+
+#[cfg(not(feature = "rag-pdf"))]
+fn extract_pdf(_bytes: &[u8]) -> Result<String> {
+    anyhow::bail!("PDF indexing requires the 'rag-pdf' feature")
+}
+
+fn manifest_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(MANIFEST_FILE)
+}
+
+fn load_manifest(path: &Path) -> Manifest {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_manifest(path: &Path, manifest: &Manifest) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_vec_pretty(manifest)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[test]
+    fn chunk_lines_tracks_spans_and_headings() {
+        let text = "# Intro\nhello\n\n# Setup\nstep one\nstep two\n";
+        let chunks = chunk_lines(text, 512);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!((chunks[0].start_line, chunks[0].end_line), (1, 2));
+        assert_eq!((chunks[1].start_line, chunks[1].end_line), (4, 6));
+        assert!(chunks[1].content.starts_with("# Setup"));
+    }
+
+    #[test]
+    fn chunk_lines_splits_long_text_at_blank_lines() {
+        let para = "word ".repeat(15);
+        let text = format!("{para}\n{para}\n\n{para}\n{para}");
+        let chunks = chunk_lines(&text, 50);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!((chunks[0].start_line, chunks[0].end_line), (1, 2));
+        assert_eq!((chunks[1].start_line, chunks[1].end_line), (4, 5));
+    }
+
+    #[test]
+    fn citation_parses_index_keys_only() {
+        assert_eq!(
+            document_citation("index:/notes/a.md#L3-L9").as_deref(),
+            Some("/notes/a.md:L3-L9")
+        );
+        assert_eq!(
+            document_citation("index:/notes/a.md#L4-L4").as_deref(),
+            Some("/notes/a.md:L4")
+        );
+        assert!(document_citation("fact_123").is_none());
+    }
+
+    #[tokio::test]
+    async fn reindex_skips_unchanged_and_drops_removed_files() {
+        let workspace = TempDir::new().unwrap();
+        let docs = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(workspace.path()).unwrap();
+        std::fs::write(docs.path().join("a.md"), "# A\nalpha notes").unwrap();
+        std::fs::write(docs.path().join("b.txt"), "beta notes").unwrap();
+        std::fs::write(docs.path().join("image.png"), [0u8, 1, 2]).unwrap();
+
+        let first = index_directory(&mem, workspace.path(), docs.path(), 512)
+            .await
+            .unwrap();
+        assert_eq!((first.indexed, first.chunks), (2, 2));
+
+        let second = index_directory(&mem, workspace.path(), docs.path(), 512)
+            .await
+            .unwrap();
+        assert_eq!((second.indexed, second.unchanged), (0, 2));
+
+        std::fs::remove_file(docs.path().join("b.txt")).unwrap();
+        let third = index_directory(&mem, workspace.path(), docs.path(), 512)
+            .await
+            .unwrap();
+        assert_eq!(third.removed, 1);
+        assert_eq!(mem.count().await.unwrap(), 1);
+    }
+}
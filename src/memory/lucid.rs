@@ -1,7 +1,7 @@
 use super::sqlite::SqliteMemory;
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::Utc;
 use parking_lot::Mutex;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
@@ -188,7 +188,7 @@ impl LucidMemory {
     fn parse_lucid_context(raw: &str) -> Vec<MemoryEntry> {
         let mut in_context_block = false;
         let mut entries = Vec::new();
-        let now = Local::now().to_rfc3339();
+        let now = Utc::now().to_rfc3339();
 
         for line in raw.lines().map(str::trim) {
             if line == "<lucid-context>" {
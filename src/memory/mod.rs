@@ -1,15 +1,21 @@
 pub mod backend;
 pub mod chunker;
 pub mod cli;
+pub mod compaction;
 pub mod embeddings;
+pub mod facts;
 pub mod hygiene;
+pub mod indexer;
 pub mod lucid;
 pub mod markdown;
 pub mod none;
 #[cfg(feature = "memory-postgres")]
 pub mod postgres;
 pub mod qdrant;
+#[cfg(feature = "memory-redis")]
+pub mod redis;
 pub mod response_cache;
+pub mod scope;
 pub mod snapshot;
 pub mod sqlite;
 pub mod traits;
@@ -26,6 +32,8 @@ pub use none::NoneMemory;
 #[cfg(feature = "memory-postgres")]
 pub use postgres::PostgresMemory;
 pub use qdrant::QdrantMemory;
+#[cfg(feature = "memory-redis")]
+pub use redis::RedisMemory;
 pub use response_cache::ResponseCache;
 pub use sqlite::SqliteMemory;
 pub use traits::Memory;
@@ -55,7 +63,7 @@ where
             Ok(Box::new(LucidMemory::new(workspace_dir, local)))
         }
         MemoryBackendKind::Postgres => postgres_builder(),
-        MemoryBackendKind::Qdrant | MemoryBackendKind::Markdown => {
+        MemoryBackendKind::Qdrant | MemoryBackendKind::Redis | MemoryBackendKind::Markdown => {
             Ok(Box::new(MarkdownMemory::new(workspace_dir)))
         }
         MemoryBackendKind::None => Ok(Box::new(NoneMemory::new())),
@@ -188,6 +196,21 @@ pub fn create_memory_with_storage(
     create_memory_with_storage_and_routes(config, &[], storage_provider, workspace_dir, api_key)
 }
 
+/// Factory: the embedding provider configured in `[memory]`, for callers that
+/// compare entries directly rather than through a backend.
+pub fn create_embedder(
+    config: &MemoryConfig,
+    api_key: Option<&str>,
+) -> Box<dyn embeddings::EmbeddingProvider> {
+    let resolved = resolve_embedding_config(config, &[], api_key);
+    embeddings::create_embedding_provider(
+        &resolved.provider,
+        resolved.api_key.as_deref(),
+        &resolved.model,
+        resolved.dimensions,
+    )
+}
+
 /// Factory: create memory with optional storage-provider override and embedding routes.
 pub fn create_memory_with_storage_and_routes(
     config: &MemoryConfig,
@@ -267,7 +290,9 @@ pub fn create_memory_with_storage_and_routes(
 
     #[cfg(feature = "memory-postgres")]
     fn build_postgres_memory(
+        config: &MemoryConfig,
         storage_provider: Option<&StorageProviderConfig>,
+        resolved_embedding: &ResolvedEmbeddingConfig,
     ) -> anyhow::Result<Box<dyn Memory>> {
         let storage_provider = storage_provider
             .context("memory backend 'postgres' requires [storage.provider.config] settings")?;
@@ -280,6 +305,27 @@ pub fn create_memory_with_storage_and_routes(
                 "memory backend 'postgres' requires [storage.provider.config].db_url (or dbURL)",
             )?;
 
+        if storage_provider.pgvector {
+            let embedder: Arc<dyn embeddings::EmbeddingProvider> =
+                Arc::from(embeddings::create_embedding_provider(
+                    &resolved_embedding.provider,
+                    resolved_embedding.api_key.as_deref(),
+                    &resolved_embedding.model,
+                    resolved_embedding.dimensions,
+                ));
+            #[allow(clippy::cast_possible_truncation)]
+            let memory = PostgresMemory::with_vector_search(
+                db_url,
+                &storage_provider.schema,
+                &storage_provider.table,
+                storage_provider.connect_timeout_secs,
+                embedder,
+                config.vector_weight as f32,
+                config.keyword_weight as f32,
+            )?;
+            return Ok(Box::new(memory));
+        }
+
         let memory = PostgresMemory::new(
             db_url,
             &storage_provider.schema,
@@ -291,7 +337,9 @@ pub fn create_memory_with_storage_and_routes(
 
     #[cfg(not(feature = "memory-postgres"))]
     fn build_postgres_memory(
+        _config: &MemoryConfig,
         _storage_provider: Option<&StorageProviderConfig>,
+        _resolved_embedding: &ResolvedEmbeddingConfig,
     ) -> anyhow::Result<Box<dyn Memory>> {
         anyhow::bail!(
             "memory backend 'postgres' requested but this build was compiled without `memory-postgres`; rebuild with `--features memory-postgres`"
@@ -339,11 +387,60 @@ pub fn create_memory_with_storage_and_routes(
         )));
     }
 
+    #[cfg(feature = "memory-redis")]
+    fn build_redis_memory(
+        config: &MemoryConfig,
+        resolved_embedding: &ResolvedEmbeddingConfig,
+    ) -> anyhow::Result<Box<dyn Memory>> {
+        let url = config
+            .redis
+            .url
+            .clone()
+            .filter(|s| !s.trim().is_empty())
+            .or_else(|| std::env::var("REDIS_URL").ok())
+            .filter(|s| !s.trim().is_empty())
+            .context("Redis memory backend requires url in [memory.redis] or REDIS_URL env var")?;
+        let embedder: Arc<dyn embeddings::EmbeddingProvider> =
+            Arc::from(embeddings::create_embedding_provider(
+                &resolved_embedding.provider,
+                resolved_embedding.api_key.as_deref(),
+                &resolved_embedding.model,
+                resolved_embedding.dimensions,
+            ));
+        tracing::info!(
+            "📦 Redis memory backend configured (prefix: {})",
+            config.redis.key_prefix
+        );
+        #[allow(clippy::cast_possible_truncation)]
+        let memory = RedisMemory::new_lazy(
+            &url,
+            &config.redis.key_prefix,
+            embedder,
+            config.vector_weight as f32,
+            config.keyword_weight as f32,
+        )?;
+        Ok(Box::new(memory))
+    }
+
+    #[cfg(not(feature = "memory-redis"))]
+    fn build_redis_memory(
+        _config: &MemoryConfig,
+        _resolved_embedding: &ResolvedEmbeddingConfig,
+    ) -> anyhow::Result<Box<dyn Memory>> {
+        anyhow::bail!(
+            "memory backend 'redis' requested but this build was compiled without `memory-redis`; rebuild with `--features memory-redis`"
+        );
+    }
+
+    if matches!(backend_kind, MemoryBackendKind::Redis) {
+        return build_redis_memory(config, &resolved_embedding);
+    }
+
     create_memory_with_builders(
         &backend_name,
         workspace_dir,
         || build_sqlite_memory(config, workspace_dir, &resolved_embedding),
-        || build_postgres_memory(storage_provider),
+        || build_postgres_memory(config, storage_provider, &resolved_embedding),
         "",
     )
 }
@@ -1,4 +1,6 @@
+use super::embeddings::EmbeddingProvider;
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::vector;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -13,11 +15,19 @@ const POSTGRES_CONNECT_TIMEOUT_CAP_SECS: u64 = 300;
 
 /// PostgreSQL-backed persistent memory.
 ///
-/// This backend focuses on reliable CRUD and keyword recall using SQL, without
-/// requiring extension setup (for example pgvector).
+/// By default this backend does CRUD and keyword recall in plain SQL, without
+/// requiring extension setup. [`PostgresMemory::with_vector_search`] adds a
+/// pgvector `embedding` column and hybrid vector + keyword recall.
 pub struct PostgresMemory {
     client: Arc<Mutex<Client>>,
     qualified_table: String,
+    vector: Option<VectorSearch>,
+}
+
+struct VectorSearch {
+    embedder: Arc<dyn EmbeddingProvider>,
+    vector_weight: f32,
+    keyword_weight: f32,
 }
 
 impl PostgresMemory {
@@ -26,6 +36,40 @@ impl PostgresMemory {
         schema: &str,
         table: &str,
         connect_timeout_secs: Option<u64>,
+    ) -> Result<Self> {
+        Self::connect(db_url, schema, table, connect_timeout_secs, None)
+    }
+
+    /// Connect with pgvector recall: embeddings are stored next to each
+    /// entry and recall merges cosine similarity with keyword matches.
+    pub fn with_vector_search(
+        db_url: &str,
+        schema: &str,
+        table: &str,
+        connect_timeout_secs: Option<u64>,
+        embedder: Arc<dyn EmbeddingProvider>,
+        vector_weight: f32,
+        keyword_weight: f32,
+    ) -> Result<Self> {
+        Self::connect(
+            db_url,
+            schema,
+            table,
+            connect_timeout_secs,
+            Some(VectorSearch {
+                embedder,
+                vector_weight,
+                keyword_weight,
+            }),
+        )
+    }
+
+    fn connect(
+        db_url: &str,
+        schema: &str,
+        table: &str,
+        connect_timeout_secs: Option<u64>,
+        vector: Option<VectorSearch>,
     ) -> Result<Self> {
         validate_identifier(schema, "storage schema")?;
         validate_identifier(table, "storage table")?;
@@ -34,16 +78,19 @@ impl PostgresMemory {
         let table_ident = quote_identifier(table);
         let qualified_table = format!("{schema_ident}.{table_ident}");
 
+        let vector_dims = vector.as_ref().map(|v| v.embedder.dimensions());
         let client = Self::initialize_client(
             db_url.to_string(),
             connect_timeout_secs,
             schema_ident.clone(),
             qualified_table.clone(),
+            vector_dims,
         )?;
 
         Ok(Self {
             client: Arc::new(Mutex::new(client)),
             qualified_table,
+            vector,
         })
     }
 
@@ -52,6 +99,7 @@ impl PostgresMemory {
         connect_timeout_secs: Option<u64>,
         schema_ident: String,
         qualified_table: String,
+        vector_dims: Option<usize>,
     ) -> Result<Client> {
         let init_handle = std::thread::Builder::new()
             .name("postgres-memory-init".to_string())
@@ -70,6 +118,9 @@ impl PostgresMemory {
                     .context("failed to connect to PostgreSQL memory backend")?;
 
                 Self::init_schema(&mut client, &schema_ident, &qualified_table)?;
+                if let Some(dims) = vector_dims {
+                    Self::init_vector_column(&mut client, &qualified_table, dims)?;
+                }
                 Ok(client)
             })
             .context("failed to spawn PostgreSQL initializer thread")?;
@@ -105,6 +156,111 @@ impl PostgresMemory {
         Ok(())
     }
 
+    fn init_vector_column(client: &mut Client, qualified_table: &str, dims: usize) -> Result<()> {
+        let column_type = if dims > 0 {
+            format!("vector({dims})")
+        } else {
+            "vector".to_string()
+        };
+        client
+            .batch_execute(&format!(
+                "
+                CREATE EXTENSION IF NOT EXISTS vector;
+                ALTER TABLE {qualified_table} ADD COLUMN IF NOT EXISTS embedding {column_type};
+                "
+            ))
+            .context("failed to enable pgvector (is the `vector` extension installed?)")?;
+        Ok(())
+    }
+
+    /// Embed `text` as a pgvector literal, or `None` when vector search is
+    /// off or the embedding call fails.
+    async fn embed_literal(&self, text: &str) -> Option<String> {
+        let search = self.vector.as_ref()?;
+        match search.embedder.embed_one(text).await {
+            Ok(embedding) if !embedding.is_empty() => Some(to_vector_literal(&embedding)),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!("pgvector embedding failed, storing without vector: {e}");
+                None
+            }
+        }
+    }
+
+    async fn keyword_recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let client = self.client.clone();
+        let qualified_table = self.qualified_table.clone();
+        let query = query.to_string();
+        let sid = session_id.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<MemoryEntry>> {
+            let mut client = client.lock();
+            let stmt = format!(
+                "
+                SELECT id, key, content, category, created_at, session_id,
+                       (
+                         CASE WHEN key ILIKE '%' || $1 || '%' THEN 2.0 ELSE 0.0 END +
+                         CASE WHEN content ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.0 END
+                       )::FLOAT8 AS score
+                FROM {qualified_table}
+                WHERE ($2::TEXT IS NULL OR session_id = $2)
+                  AND ($1 = '' OR key ILIKE '%' || $1 || '%' OR content ILIKE '%' || $1 || '%')
+                ORDER BY score DESC, updated_at DESC
+                LIMIT $3
+                "
+            );
+
+            #[allow(clippy::cast_possible_wrap)]
+            let limit_i64 = limit as i64;
+
+            let rows = client.query(&stmt, &[&query, &sid, &limit_i64])?;
+            rows.iter()
+                .map(Self::row_to_entry)
+                .collect::<Result<Vec<MemoryEntry>>>()
+        })
+        .await?
+    }
+
+    async fn vector_recall(
+        &self,
+        query_vector: String,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let client = self.client.clone();
+        let qualified_table = self.qualified_table.clone();
+        let sid = session_id.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<MemoryEntry>> {
+            let mut client = client.lock();
+            let stmt = format!(
+                "
+                SELECT id, key, content, category, created_at, session_id,
+                       (1 - (embedding <=> CAST($1::TEXT AS vector)))::FLOAT8 AS score
+                FROM {qualified_table}
+                WHERE embedding IS NOT NULL
+                  AND ($2::TEXT IS NULL OR session_id = $2)
+                ORDER BY embedding <=> CAST($1::TEXT AS vector)
+                LIMIT $3
+                "
+            );
+
+            #[allow(clippy::cast_possible_wrap)]
+            let limit_i64 = limit as i64;
+
+            let rows = client.query(&stmt, &[&query_vector, &sid, &limit_i64])?;
+            rows.iter()
+                .map(Self::row_to_entry)
+                .collect::<Result<Vec<MemoryEntry>>>()
+        })
+        .await?
+    }
+
     fn category_to_str(category: &MemoryCategory) -> String {
         match category {
             MemoryCategory::Core => "core".to_string(),
@@ -165,6 +321,12 @@ fn quote_identifier(value: &str) -> String {
     format!("\"{value}\"")
 }
 
+/// pgvector text form: `[0.1,0.2,...]`.
+fn to_vector_literal(embedding: &[f32]) -> String {
+    let parts: Vec<String> = embedding.iter().map(f32::to_string).collect();
+    format!("[{}]", parts.join(","))
+}
+
 #[async_trait]
 impl Memory for PostgresMemory {
     fn name(&self) -> &str {
@@ -184,10 +346,36 @@ impl Memory for PostgresMemory {
         let content = content.to_string();
         let category = Self::category_to_str(&category);
         let sid = session_id.map(str::to_string);
+        let with_vector = self.vector.is_some();
+        let embedding = self.embed_literal(&content).await;
 
         tokio::task::spawn_blocking(move || -> Result<()> {
             let now = Utc::now();
             let mut client = client.lock();
+            let id = Uuid::new_v4().to_string();
+
+            if with_vector {
+                let stmt = format!(
+                    "
+                    INSERT INTO {qualified_table}
+                        (id, key, content, category, created_at, updated_at, session_id, embedding)
+                    VALUES
+                        ($1, $2, $3, $4, $5, $6, $7, CAST($8::TEXT AS vector))
+                    ON CONFLICT (key) DO UPDATE SET
+                        content = EXCLUDED.content,
+                        category = EXCLUDED.category,
+                        updated_at = EXCLUDED.updated_at,
+                        session_id = EXCLUDED.session_id,
+                        embedding = EXCLUDED.embedding
+                    "
+                );
+                client.execute(
+                    &stmt,
+                    &[&id, &key, &content, &category, &now, &now, &sid, &embedding],
+                )?;
+                return Ok(());
+            }
+
             let stmt = format!(
                 "
                 INSERT INTO {qualified_table}
@@ -201,8 +389,6 @@ impl Memory for PostgresMemory {
                     session_id = EXCLUDED.session_id
                 "
             );
-
-            let id = Uuid::new_v4().to_string();
             client.execute(&stmt, &[&id, &key, &content, &category, &now, &now, &sid])?;
             Ok(())
         })
@@ -215,37 +401,45 @@ impl Memory for PostgresMemory {
         limit: usize,
         session_id: Option<&str>,
     ) -> Result<Vec<MemoryEntry>> {
-        let client = self.client.clone();
-        let qualified_table = self.qualified_table.clone();
         let query = query.trim().to_string();
-        let sid = session_id.map(str::to_string);
-
-        tokio::task::spawn_blocking(move || -> Result<Vec<MemoryEntry>> {
-            let mut client = client.lock();
-            let stmt = format!(
-                "
-                SELECT id, key, content, category, created_at, session_id,
-                       (
-                         CASE WHEN key ILIKE '%' || $1 || '%' THEN 2.0 ELSE 0.0 END +
-                         CASE WHEN content ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.0 END
-                       ) AS score
-                FROM {qualified_table}
-                WHERE ($2::TEXT IS NULL OR session_id = $2)
-                  AND ($1 = '' OR key ILIKE '%' || $1 || '%' OR content ILIKE '%' || $1 || '%')
-                ORDER BY score DESC, updated_at DESC
-                LIMIT $3
-                "
-            );
-
-            #[allow(clippy::cast_possible_wrap)]
-            let limit_i64 = limit as i64;
+        let keyword = self.keyword_recall(&query, limit, session_id).await?;
+
+        let Some(search) = self.vector.as_ref().filter(|_| !query.is_empty()) else {
+            return Ok(keyword);
+        };
+        let Some(query_vector) = self.embed_literal(&query).await else {
+            return Ok(keyword);
+        };
+        let semantic = self.vector_recall(query_vector, limit, session_id).await?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let as_pairs = |entries: &[MemoryEntry]| -> Vec<(String, f32)> {
+            entries
+                .iter()
+                .map(|e| (e.key.clone(), e.score.unwrap_or(0.0) as f32))
+                .collect()
+        };
+        let merged = vector::hybrid_merge(
+            &as_pairs(&semantic),
+            &as_pairs(&keyword),
+            search.vector_weight,
+            search.keyword_weight,
+            limit,
+        );
 
-            let rows = client.query(&stmt, &[&query, &sid, &limit_i64])?;
-            rows.iter()
-                .map(Self::row_to_entry)
-                .collect::<Result<Vec<MemoryEntry>>>()
-        })
-        .await?
+        let mut by_key: std::collections::HashMap<String, MemoryEntry> = semantic
+            .into_iter()
+            .chain(keyword)
+            .map(|e| (e.key.clone(), e))
+            .collect();
+        Ok(merged
+            .into_iter()
+            .filter_map(|scored| {
+                let mut entry = by_key.remove(&scored.id)?;
+                entry.score = Some(f64::from(scored.final_score));
+                Some(entry)
+            })
+            .collect())
     }
 
     async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
@@ -373,6 +567,11 @@ mod tests {
         );
     }
 
+    #[test]
+    fn vector_literal_uses_pgvector_text_form() {
+        assert_eq!(to_vector_literal(&[0.5, -1.0, 2.25]), "[0.5,-1,2.25]");
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn new_does_not_panic_inside_tokio_runtime() {
         let outcome = std::panic::catch_unwind(|| {
@@ -0,0 +1,378 @@
+use super::embeddings::EmbeddingProvider;
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::vector;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::aio::MultiplexedConnection;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+type Fields = HashMap<String, Vec<u8>>;
+
+/// Redis-backed shared memory.
+///
+/// Each entry is a hash at `<prefix>:memory:<key>`; a sorted set at
+/// `<prefix>:memory_keys` indexes keys by last update. Embeddings (when an
+/// embedding provider is configured) are stored in the hash and recall
+/// merges cosine similarity with keyword matches, so several instances
+/// pointed at the same Redis share one memory.
+pub struct RedisMemory {
+    client: redis::Client,
+    connection: OnceCell<MultiplexedConnection>,
+    key_prefix: String,
+    embedder: Arc<dyn EmbeddingProvider>,
+    vector_weight: f32,
+    keyword_weight: f32,
+}
+
+impl RedisMemory {
+    /// Create a Redis memory backend. The connection is opened lazily on the
+    /// first operation, so this can be called from the sync factory.
+    pub fn new_lazy(
+        url: &str,
+        key_prefix: &str,
+        embedder: Arc<dyn EmbeddingProvider>,
+        vector_weight: f32,
+        keyword_weight: f32,
+    ) -> Result<Self> {
+        let client = redis::Client::open(url).context("invalid Redis URL")?;
+        Ok(Self {
+            client,
+            connection: OnceCell::new(),
+            key_prefix: key_prefix.trim_end_matches(':').to_string(),
+            embedder,
+            vector_weight,
+            keyword_weight,
+        })
+    }
+
+    async fn connection(&self) -> Result<MultiplexedConnection> {
+        let conn = self
+            .connection
+            .get_or_try_init(|| async {
+                self.client
+                    .get_multiplexed_async_connection()
+                    .await
+                    .context("failed to connect to Redis memory backend")
+            })
+            .await?;
+        Ok(conn.clone())
+    }
+
+    fn entry_key(&self, key: &str) -> String {
+        format!("{}:memory:{key}", self.key_prefix)
+    }
+
+    fn index_key(&self) -> String {
+        format!("{}:memory_keys", self.key_prefix)
+    }
+
+    async fn embed(&self, text: &str) -> Option<Vec<f32>> {
+        if self.embedder.dimensions() == 0 {
+            return None;
+        }
+        match self.embedder.embed_one(text).await {
+            Ok(embedding) if !embedding.is_empty() => Some(embedding),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!("Redis memory embedding failed: {e}");
+                None
+            }
+        }
+    }
+
+    /// Load every entry, most recently updated first.
+    async fn load_all(&self) -> Result<Vec<(MemoryEntry, Option<Vec<f32>>)>> {
+        let mut conn = self.connection().await?;
+        let keys: Vec<String> = redis::cmd("ZREVRANGE")
+            .arg(self.index_key())
+            .arg(0)
+            .arg(-1)
+            .query_async(&mut conn)
+            .await?;
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut pipe = redis::pipe();
+        for key in &keys {
+            pipe.cmd("HGETALL").arg(self.entry_key(key));
+        }
+        let rows: Vec<Fields> = pipe.query_async(&mut conn).await?;
+        Ok(rows.into_iter().filter_map(entry_from_fields).collect())
+    }
+}
+
+fn parse_category(value: &str) -> MemoryCategory {
+    match value {
+        "core" => MemoryCategory::Core,
+        "daily" => MemoryCategory::Daily,
+        "conversation" => MemoryCategory::Conversation,
+        other => MemoryCategory::Custom(other.to_string()),
+    }
+}
+
+fn field(fields: &Fields, name: &str) -> Option<String> {
+    fields
+        .get(name)
+        .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+}
+
+fn entry_from_fields(fields: Fields) -> Option<(MemoryEntry, Option<Vec<f32>>)> {
+    let key = field(&fields, "key")?;
+    let entry = MemoryEntry {
+        id: field(&fields, "id").unwrap_or_default(),
+        key,
+        content: field(&fields, "content").unwrap_or_default(),
+        category: parse_category(&field(&fields, "category").unwrap_or_default()),
+        timestamp: field(&fields, "timestamp").unwrap_or_default(),
+        session_id: field(&fields, "session_id").filter(|s| !s.is_empty()),
+        score: None,
+    };
+    let embedding = fields
+        .get("embedding")
+        .filter(|bytes| !bytes.is_empty())
+        .map(|bytes| vector::bytes_to_vec(bytes));
+    Some((entry, embedding))
+}
+
+/// Fraction of query terms found in the entry's key or content.
+#[allow(clippy::cast_precision_loss)]
+fn keyword_score(terms: &[String], key: &str, content: &str) -> f32 {
+    if terms.is_empty() {
+        return 0.0;
+    }
+    let haystack = format!("{key} {content}").to_lowercase();
+    let hits = terms
+        .iter()
+        .filter(|t| haystack.contains(t.as_str()))
+        .count();
+    hits as f32 / terms.len() as f32
+}
+
+fn update_score(timestamp: &str) -> i64 {
+    DateTime::parse_from_rfc3339(timestamp).map_or(0, |ts| ts.timestamp_millis())
+}
+
+#[async_trait]
+impl Memory for RedisMemory {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        let embedding = self.embed(content).await;
+        let timestamp = Utc::now().to_rfc3339();
+        let entry_key = self.entry_key(key);
+        let mut conn = self.connection().await?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .cmd("HSETNX")
+            .arg(&entry_key)
+            .arg("id")
+            .arg(Uuid::new_v4().to_string())
+            .ignore()
+            .cmd("HSET")
+            .arg(&entry_key)
+            .arg("key")
+            .arg(key)
+            .arg("content")
+            .arg(content)
+            .arg("category")
+            .arg(category.to_string())
+            .arg("timestamp")
+            .arg(&timestamp)
+            .arg("session_id")
+            .arg(session_id.unwrap_or_default())
+            .ignore();
+        match embedding {
+            Some(embedding) => {
+                pipe.cmd("HSET")
+                    .arg(&entry_key)
+                    .arg("embedding")
+                    .arg(vector::vec_to_bytes(&embedding))
+                    .ignore();
+            }
+            None => {
+                pipe.cmd("HDEL").arg(&entry_key).arg("embedding").ignore();
+            }
+        }
+        pipe.cmd("ZADD")
+            .arg(self.index_key())
+            .arg(update_score(&timestamp))
+            .arg(key)
+            .ignore();
+        let _: () = pipe.query_async(&mut conn).await?;
+        Ok(())
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let entries: Vec<_> = self
+            .load_all()
+            .await?
+            .into_iter()
+            .filter(|(e, _)| session_id.is_none_or(|sid| e.session_id.as_deref() == Some(sid)))
+            .collect();
+
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if terms.is_empty() {
+            return Ok(entries.into_iter().take(limit).map(|(e, _)| e).collect());
+        }
+
+        let query_vector = self.embed(query).await;
+        let mut vector_hits = Vec::new();
+        let mut keyword_hits = Vec::new();
+        for (entry, embedding) in &entries {
+            if let (Some(q), Some(v)) = (&query_vector, embedding) {
+                vector_hits.push((entry.key.clone(), vector::cosine_similarity(q, v)));
+            }
+            let score = keyword_score(&terms, &entry.key, &entry.content);
+            if score > 0.0 {
+                keyword_hits.push((entry.key.clone(), score));
+            }
+        }
+
+        let merged = vector::hybrid_merge(
+            &vector_hits,
+            &keyword_hits,
+            self.vector_weight,
+            self.keyword_weight,
+            limit,
+        );
+        let mut by_key: HashMap<String, MemoryEntry> = entries
+            .into_iter()
+            .map(|(e, _)| (e.key.clone(), e))
+            .collect();
+        Ok(merged
+            .into_iter()
+            .filter_map(|scored| {
+                let mut entry = by_key.remove(&scored.id)?;
+                entry.score = Some(f64::from(scored.final_score));
+                Some(entry)
+            })
+            .collect())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+        let mut conn = self.connection().await?;
+        let fields: Fields = redis::cmd("HGETALL")
+            .arg(self.entry_key(key))
+            .query_async(&mut conn)
+            .await?;
+        Ok(entry_from_fields(fields).map(|(entry, _)| entry))
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        Ok(self
+            .load_all()
+            .await?
+            .into_iter()
+            .map(|(e, _)| e)
+            .filter(|e| category.is_none_or(|c| &e.category == c))
+            .filter(|e| session_id.is_none_or(|sid| e.session_id.as_deref() == Some(sid)))
+            .collect())
+    }
+
+    async fn forget(&self, key: &str) -> Result<bool> {
+        let mut conn = self.connection().await?;
+        let (deleted, _): (i64, i64) = redis::pipe()
+            .atomic()
+            .cmd("DEL")
+            .arg(self.entry_key(key))
+            .cmd("ZREM")
+            .arg(self.index_key())
+            .arg(key)
+            .query_async(&mut conn)
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    async fn count(&self) -> Result<usize> {
+        let mut conn = self.connection().await?;
+        let count: usize = redis::cmd("ZCARD")
+            .arg(self.index_key())
+            .query_async(&mut conn)
+            .await?;
+        Ok(count)
+    }
+
+    async fn health_check(&self) -> bool {
+        let Ok(mut conn) = self.connection().await else {
+            return false;
+        };
+        let pong: redis::RedisResult<String> = redis::cmd("PING").query_async(&mut conn).await;
+        pong.is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::embeddings::NoopEmbedding;
+
+    fn fields(pairs: &[(&str, &[u8])]) -> Fields {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), v.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn entry_from_fields_roundtrips_embedding() {
+        let embedding = vector::vec_to_bytes(&[0.25, 0.5]);
+        let (entry, vec) = entry_from_fields(fields(&[
+            ("id", b"id-1"),
+            ("key", b"lang"),
+            ("content", b"Rust"),
+            ("category", b"core"),
+            ("timestamp", b"2026-02-16T00:00:00+00:00"),
+            ("session_id", b""),
+            ("embedding", &embedding),
+        ]))
+        .unwrap();
+        assert_eq!(entry.key, "lang");
+        assert_eq!(entry.category, MemoryCategory::Core);
+        assert!(entry.session_id.is_none());
+        assert_eq!(vec, Some(vec![0.25, 0.5]));
+    }
+
+    #[test]
+    fn missing_hash_is_not_an_entry() {
+        assert!(entry_from_fields(Fields::new()).is_none());
+    }
+
+    #[test]
+    fn keyword_score_is_fraction_of_terms() {
+        let terms = vec!["rust".to_string(), "tokio".to_string()];
+        assert!((keyword_score(&terms, "lang", "Rust is great") - 0.5).abs() < f32::EPSILON);
+        assert!(keyword_score(&terms, "x", "nothing").abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn invalid_url_is_rejected_without_connecting() {
+        let embedder: Arc<dyn EmbeddingProvider> = Arc::new(NoopEmbedding);
+        assert!(RedisMemory::new_lazy("not a url", "zc", embedder.clone(), 0.7, 0.3).is_err());
+        let mem = RedisMemory::new_lazy("redis://127.0.0.1:1/0", "zc:", embedder, 0.7, 0.3)
+            .expect("valid URL should not connect eagerly");
+        assert_eq!(mem.entry_key("a"), "zc:memory:a");
+    }
+}
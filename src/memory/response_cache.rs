@@ -6,7 +6,7 @@
 //! default — users opt in via `[memory] response_cache_enabled = true`.
 
 use anyhow::Result;
-use chrono::{Duration, Local};
+use chrono::{Duration, Utc};
 use parking_lot::Mutex;
 use rusqlite::{params, Connection};
 use sha2::{Digest, Sha256};
@@ -31,7 +31,7 @@ impl ResponseCache {
         std::fs::create_dir_all(&db_dir)?;
         let db_path = db_dir.join("response_cache.db");
 
-        let conn = Connection::open(&db_path)?;
+        let conn = crate::security::storage_encryption::open(&db_path)?;
 
         conn.execute_batch(
             "PRAGMA journal_mode = WAL;
@@ -79,7 +79,7 @@ impl ResponseCache {
     pub fn get(&self, key: &str) -> Result<Option<String>> {
         let conn = self.conn.lock();
 
-        let now = Local::now();
+        let now = Utc::now();
         let cutoff = (now - Duration::minutes(self.ttl_minutes)).to_rfc3339();
 
         let mut stmt = conn.prepare(
@@ -107,7 +107,7 @@ impl ResponseCache {
     pub fn put(&self, key: &str, model: &str, response: &str, token_count: u32) -> Result<()> {
         let conn = self.conn.lock();
 
-        let now = Local::now().to_rfc3339();
+        let now = Utc::now().to_rfc3339();
 
         conn.execute(
             "INSERT OR REPLACE INTO response_cache
@@ -117,7 +117,7 @@ impl ResponseCache {
         )?;
 
         // Evict expired entries
-        let cutoff = (Local::now() - Duration::minutes(self.ttl_minutes)).to_rfc3339();
+        let cutoff = (Utc::now() - Duration::minutes(self.ttl_minutes)).to_rfc3339();
         conn.execute(
             "DELETE FROM response_cache WHERE created_at <= ?1",
             params![cutoff],
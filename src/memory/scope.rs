@@ -0,0 +1,429 @@
+// Memory scopes — isolation between channels and contacts.
+//
+// Every channel message is handled inside a task-local `MemoryScope`
+// resolved from `[memory.scopes]`. `ScopedMemory` wraps the backend and reads
+// that scope on each call, so stores are tagged with it and recall, list,
+// get and forget only reach entries of the same scope (plus the shared
+// global scope when allowed). Because the check sits in the memory layer,
+// the memory tools the model calls are bound by it too.
+//
+// Scopes map onto the backends' session column: the global scope is "no
+// session", a channel scope is the channel name (matching what channels
+// stored before scopes existed), and a contact scope is `<channel>:<sender>`.
+// Outside a scoped task (CLI, gateway, cron) calls pass through unchanged.
+
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use crate::config::{Config, MemoryScopeMode};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Global entries are mixed into recall of other scopes, so fetch extra
+/// candidates before dropping entries of scopes the caller cannot see.
+const RECALL_OVERFETCH: usize = 4;
+
+tokio::task_local! {
+    static CURRENT_SCOPE: MemoryScope;
+}
+
+/// The memory a piece of work may read and write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryScope {
+    /// Entries stored without a session, shared by everyone.
+    Global,
+    /// Entries of one channel or contact, keyed by session id.
+    Private(String),
+}
+
+impl MemoryScope {
+    /// Session id used by the backends for this scope.
+    pub fn session_id(&self) -> Option<&str> {
+        match self {
+            Self::Global => None,
+            Self::Private(id) => Some(id),
+        }
+    }
+}
+
+impl std::fmt::Display for MemoryScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Global => write!(f, "global"),
+            Self::Private(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+/// Resolved `[memory.scopes]`.
+#[derive(Debug, Clone)]
+pub struct MemoryScopePolicy {
+    default: MemoryScopeMode,
+    channels: HashMap<String, MemoryScopeMode>,
+    read_global: bool,
+}
+
+impl Default for MemoryScopePolicy {
+    fn default() -> Self {
+        Self {
+            default: MemoryScopeMode::Channel,
+            channels: HashMap::new(),
+            read_global: true,
+        }
+    }
+}
+
+impl MemoryScopePolicy {
+    pub fn from_config(config: &Config) -> Self {
+        let scopes = &config.memory.scopes;
+        Self {
+            default: if config.channels_config.shared_memory {
+                MemoryScopeMode::Global
+            } else {
+                scopes.default
+            },
+            channels: scopes.channels.clone(),
+            read_global: scopes.read_global,
+        }
+    }
+
+    /// Whether private scopes also see global entries.
+    pub fn read_global(&self) -> bool {
+        self.read_global
+    }
+
+    /// Scope for a message from `sender` on `channel`.
+    pub fn resolve(&self, channel: &str, sender: &str) -> MemoryScope {
+        match self.channels.get(channel).copied().unwrap_or(self.default) {
+            MemoryScopeMode::Global => MemoryScope::Global,
+            MemoryScopeMode::Channel => MemoryScope::Private(channel.to_string()),
+            MemoryScopeMode::Contact => MemoryScope::Private(format!("{channel}:{sender}")),
+        }
+    }
+
+    /// Short description for startup banners, e.g. `per-channel (+global)`.
+    pub fn describe(&self) -> String {
+        let base = match self.default {
+            MemoryScopeMode::Global => "shared",
+            MemoryScopeMode::Channel => "per-channel",
+            MemoryScopeMode::Contact => "per-contact",
+        };
+        let mut out = base.to_string();
+        if !self.channels.is_empty() {
+            let _ = write!(out, ", {} override(s)", self.channels.len());
+        }
+        if self.read_global && self.default != MemoryScopeMode::Global {
+            out.push_str(" (+global)");
+        }
+        out
+    }
+}
+
+/// Run `future` with `scope` as the current memory scope.
+pub async fn run_in_scope<F: Future>(scope: MemoryScope, future: F) -> F::Output {
+    CURRENT_SCOPE.scope(scope, future).await
+}
+
+/// The scope of the running task, if any.
+pub fn current() -> Option<MemoryScope> {
+    CURRENT_SCOPE.try_with(Clone::clone).ok()
+}
+
+/// Memory wrapper that enforces the task's [`MemoryScope`].
+pub struct ScopedMemory {
+    inner: Arc<dyn Memory>,
+    read_global: bool,
+}
+
+impl ScopedMemory {
+    pub fn new(inner: Arc<dyn Memory>, read_global: bool) -> Self {
+        Self { inner, read_global }
+    }
+
+    fn can_see(&self, scope: &MemoryScope, entry: &MemoryEntry) -> bool {
+        match (scope, entry.session_id.as_deref()) {
+            (_, None) => matches!(scope, MemoryScope::Global) || self.read_global,
+            (MemoryScope::Private(id), Some(sid)) => id == sid,
+            (MemoryScope::Global, Some(_)) => false,
+        }
+    }
+
+    /// Key under which `scope` reads and writes `key`. A key already taken
+    /// by another scope (or by a global entry, for a private scope) is
+    /// namespaced as `<scope>/<key>` instead of overwriting that entry.
+    async fn resolve_key(&self, scope: &MemoryScope, key: &str) -> anyhow::Result<String> {
+        match self.inner.get(key).await? {
+            Some(entry) if !self.owns(scope, &entry) => Ok(format!("{scope}/{key}")),
+            _ => Ok(key.to_string()),
+        }
+    }
+
+    /// Whether `scope` may modify `entry` (global entries are read-only to
+    /// private scopes).
+    fn owns(&self, scope: &MemoryScope, entry: &MemoryEntry) -> bool {
+        entry.session_id.as_deref() == scope.session_id()
+    }
+}
+
+#[async_trait]
+impl Memory for ScopedMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let Some(scope) = current() else {
+            return self.inner.store(key, content, category, session_id).await;
+        };
+        let key = self.resolve_key(&scope, key).await?;
+        self.inner
+            .store(&key, content, category, scope.session_id())
+            .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let Some(scope) = current() else {
+            return self.inner.recall(query, limit, session_id).await;
+        };
+        let mut entries = match &scope {
+            MemoryScope::Private(id) => self.inner.recall(query, limit, Some(id)).await?,
+            MemoryScope::Global => Vec::new(),
+        };
+        if matches!(scope, MemoryScope::Global) || self.read_global {
+            let global = self
+                .inner
+                .recall(query, limit.saturating_mul(RECALL_OVERFETCH), None)
+                .await?;
+            entries.extend(global.into_iter().filter(|e| e.session_id.is_none()));
+        }
+        entries.retain(|e| self.can_see(&scope, e));
+        entries.sort_by(|a, b| {
+            b.score
+                .unwrap_or(0.0)
+                .partial_cmp(&a.score.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut seen = std::collections::HashSet::new();
+        entries.retain(|e| seen.insert(e.id.clone()));
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let Some(scope) = current() else {
+            return self.inner.get(key).await;
+        };
+        // The scope's own (possibly namespaced) entry wins over a global one.
+        let own_key = self.resolve_key(&scope, key).await?;
+        if own_key != key {
+            if let Some(entry) = self.inner.get(&own_key).await? {
+                return Ok(Some(entry).filter(|e| self.can_see(&scope, e)));
+            }
+        }
+        Ok(self
+            .inner
+            .get(key)
+            .await?
+            .filter(|e| self.can_see(&scope, e)))
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let Some(scope) = current() else {
+            return self.inner.list(category, session_id).await;
+        };
+        let mut entries = match &scope {
+            MemoryScope::Private(id) => self.inner.list(category, Some(id)).await?,
+            MemoryScope::Global => Vec::new(),
+        };
+        if matches!(scope, MemoryScope::Global) || self.read_global {
+            entries.extend(
+                self.inner
+                    .list(category, None)
+                    .await?
+                    .into_iter()
+                    .filter(|e| e.session_id.is_none()),
+            );
+        }
+        entries.retain(|e| self.can_see(&scope, e));
+        Ok(entries)
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        let Some(scope) = current() else {
+            return self.inner.forget(key).await;
+        };
+        let key = self.resolve_key(&scope, key).await?;
+        match self.inner.get(&key).await? {
+            Some(entry) if self.owns(&scope, &entry) => self.inner.forget(&key).await,
+            _ => Ok(false),
+        }
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        if current().is_none() {
+            return self.inner.count().await;
+        }
+        Ok(self.list(None, None).await?.len())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn scoped(read_global: bool) -> (TempDir, ScopedMemory) {
+        let tmp = TempDir::new().unwrap();
+        let inner: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        (tmp, ScopedMemory::new(inner, read_global))
+    }
+
+    fn private(id: &str) -> MemoryScope {
+        MemoryScope::Private(id.to_string())
+    }
+
+    #[test]
+    fn policy_resolves_overrides_and_contacts() {
+        let mut config = Config::default();
+        config.memory.scopes.default = MemoryScopeMode::Channel;
+        config
+            .memory
+            .scopes
+            .channels
+            .insert("telegram".into(), MemoryScopeMode::Contact);
+        config
+            .memory
+            .scopes
+            .channels
+            .insert("cli".into(), MemoryScopeMode::Global);
+        let policy = MemoryScopePolicy::from_config(&config);
+        assert_eq!(policy.resolve("discord", "bob"), private("discord"));
+        assert_eq!(
+            policy.resolve("telegram", "alice"),
+            private("telegram:alice")
+        );
+        assert_eq!(policy.resolve("cli", "user"), MemoryScope::Global);
+
+        config.channels_config.shared_memory = true;
+        let shared = MemoryScopePolicy::from_config(&config);
+        assert_eq!(shared.resolve("discord", "bob"), MemoryScope::Global);
+        assert_eq!(
+            shared.resolve("telegram", "alice"),
+            private("telegram:alice")
+        );
+    }
+
+    #[tokio::test]
+    async fn private_scopes_do_not_see_each_other() {
+        let (_tmp, mem) = scoped(true);
+        run_in_scope(private("telegram:alice"), async {
+            mem.store(
+                "secret",
+                "Alice's address is 1 Main St",
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        })
+        .await;
+        mem.store(
+            "motto",
+            "Main address book is public",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        run_in_scope(private("discord"), async {
+            let hits = mem.recall("address", 10, None).await.unwrap();
+            let keys: Vec<&str> = hits.iter().map(|e| e.key.as_str()).collect();
+            assert_eq!(keys, ["motto"]);
+            assert!(mem.get("secret").await.unwrap().is_none());
+            assert!(!mem.forget("secret").await.unwrap());
+            assert_eq!(mem.count().await.unwrap(), 1);
+        })
+        .await;
+
+        run_in_scope(private("telegram:alice"), async {
+            assert_eq!(mem.recall("address", 10, None).await.unwrap().len(), 2);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn global_scope_is_optional_and_read_only() {
+        let (_tmp, mem) = scoped(false);
+        mem.store("motto", "shared fact", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        run_in_scope(private("discord"), async {
+            assert!(mem
+                .recall("shared fact", 10, None)
+                .await
+                .unwrap()
+                .is_empty());
+        })
+        .await;
+
+        let (_tmp, mem) = scoped(true);
+        mem.store("motto", "shared fact", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        run_in_scope(private("discord"), async {
+            // A private write to a global key is namespaced, not an overwrite.
+            mem.store("motto", "discord fact", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+            assert_eq!(
+                mem.get("motto").await.unwrap().unwrap().content,
+                "discord fact"
+            );
+            assert!(mem.forget("motto").await.unwrap());
+            assert_eq!(
+                mem.get("motto").await.unwrap().unwrap().content,
+                "shared fact"
+            );
+            assert!(!mem.forget("motto").await.unwrap());
+            mem.store("motto", "discord fact", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        })
+        .await;
+        assert_eq!(
+            mem.get("motto").await.unwrap().unwrap().content,
+            "shared fact"
+        );
+        assert_eq!(
+            mem.get("discord/motto")
+                .await
+                .unwrap()
+                .unwrap()
+                .session_id
+                .as_deref(),
+            Some("discord")
+        );
+    }
+}
@@ -7,7 +7,7 @@
 //! re-indexes all entries back into a fresh SQLite database.
 
 use anyhow::Result;
-use chrono::Local;
+use chrono::Utc;
 use rusqlite::{params, Connection};
 use std::fmt::Write;
 use std::fs;
@@ -31,6 +31,11 @@ pub fn export_snapshot(workspace_dir: &Path) -> Result<usize> {
         tracing::debug!("snapshot export skipped: brain.db does not exist");
         return Ok(0);
     }
+    if crate::security::storage_encryption::is_enabled() {
+        // The snapshot is plaintext markdown; writing it would undo encryption at rest.
+        tracing::debug!("snapshot export skipped: storage encryption is enabled");
+        return Ok(0);
+    }
 
     let conn = Connection::open(&db_path)?;
     conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
@@ -63,7 +68,7 @@ pub fn export_snapshot(workspace_dir: &Path) -> Result<usize> {
     let mut output = String::with_capacity(rows.len() * 200);
     output.push_str(SNAPSHOT_HEADER);
 
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let now = crate::time::display(Utc::now());
     write!(output, "**Last exported:** {now}\n\n").unwrap();
     write!(output, "**Total core memories:** {}\n\n---\n\n", rows.len()).unwrap();
 
@@ -111,7 +116,7 @@ pub fn hydrate_from_snapshot(workspace_dir: &Path) -> Result<usize> {
     fs::create_dir_all(&db_dir)?;
 
     let db_path = db_dir.join("brain.db");
-    let conn = Connection::open(&db_path)?;
+    let conn = crate::security::storage_encryption::open(&db_path)?;
     conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
 
     // Initialize schema (same as SqliteMemory::init_schema)
@@ -139,7 +144,7 @@ pub fn hydrate_from_snapshot(workspace_dir: &Path) -> Result<usize> {
         );",
     )?;
 
-    let now = Local::now().to_rfc3339();
+    let now = Utc::now().to_rfc3339();
     let mut hydrated = 0;
 
     for (key, content) in &entries {
@@ -360,7 +365,7 @@ Rule 3: Protect the user.
         )
         .unwrap();
 
-        let now = Local::now().to_rfc3339();
+        let now = Utc::now().to_rfc3339();
         conn.execute(
             "INSERT INTO memories (id, key, content, category, created_at, updated_at)
              VALUES ('id1', 'identity', 'I am a test agent', 'core', ?1, ?2)",
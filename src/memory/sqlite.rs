@@ -3,7 +3,7 @@ use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use super::vector;
 use anyhow::Context;
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::Utc;
 use parking_lot::Mutex;
 use rusqlite::{params, Connection};
 use std::fmt::Write as _;
@@ -104,7 +104,7 @@ impl SqliteMemory {
             let capped = secs.min(SQLITE_OPEN_TIMEOUT_CAP_SECS);
             let (tx, rx) = mpsc::channel();
             thread::spawn(move || {
-                let result = Connection::open(&path_buf);
+                let result = crate::security::storage_encryption::open(&path_buf);
                 let _ = tx.send(result);
             });
             match rx.recv_timeout(Duration::from_secs(capped)) {
@@ -118,7 +118,8 @@ impl SqliteMemory {
                 }
             }
         } else {
-            Connection::open(&path_buf).context("SQLite failed to open database")?
+            crate::security::storage_encryption::open(&path_buf)
+                .context("SQLite failed to open database")?
         };
 
         Ok(conn)
@@ -183,6 +184,23 @@ impl SqliteMemory {
             )?;
         }
 
+        // Migration: timestamps used to be written with the local offset;
+        // rewrite them in UTC so they compare and sort as instants (idempotent)
+        for (table, column) in [
+            ("memories", "created_at"),
+            ("memories", "updated_at"),
+            ("embedding_cache", "created_at"),
+            ("embedding_cache", "accessed_at"),
+        ] {
+            conn.execute(
+                &format!(
+                    "UPDATE {table} SET {column} = strftime('%Y-%m-%dT%H:%M:%f+00:00', {column})
+                     WHERE {column} NOT LIKE '%+00:00' AND julianday({column}) IS NOT NULL"
+                ),
+                [],
+            )?;
+        }
+
         Ok(())
     }
 
@@ -228,7 +246,7 @@ impl SqliteMemory {
         }
 
         let hash = Self::content_hash(text);
-        let now = Local::now().to_rfc3339();
+        let now = Utc::now().to_rfc3339();
 
         // Check cache (offloaded to blocking thread)
         let conn = self.conn.clone();
@@ -456,7 +474,7 @@ impl Memory for SqliteMemory {
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
             let conn = conn.lock();
-            let now = Local::now().to_rfc3339();
+            let now = Utc::now().to_rfc3339();
             let cat = Self::category_to_str(&category);
             let id = Uuid::new_v4().to_string();
 
@@ -1785,6 +1803,31 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn schema_migration_rewrites_local_timestamps_in_utc() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store("k1", "offset", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let conn = mem.conn.lock();
+        conn.execute(
+            "UPDATE memories SET created_at = '2026-03-10T14:00:00.5+01:00' WHERE key = 'k1'",
+            [],
+        )
+        .unwrap();
+
+        SqliteMemory::init_schema(&conn).unwrap();
+        SqliteMemory::init_schema(&conn).unwrap();
+        let created_at: String = conn
+            .query_row(
+                "SELECT created_at FROM memories WHERE key = 'k1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(created_at, "2026-03-10T13:00:00.500+00:00");
+    }
+
     // ── §4.1 Concurrent write contention tests ──────────────
 
     #[tokio::test]
@@ -1,8 +1,9 @@
-use crate::config::{build_runtime_proxy_client_with_timeouts, MultimodalConfig};
-use crate::providers::ChatMessage;
+use crate::config::{build_runtime_proxy_client_with_timeouts, MultimodalConfig, OcrConfig};
+use crate::providers::{ChatMessage, ChatRequest};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use reqwest::Client;
 use std::path::Path;
+use std::time::Duration;
 
 const IMAGE_MARKER_PREFIX: &str = "[IMAGE:";
 const ALLOWED_IMAGE_MIME_TYPES: &[&str] = &[
@@ -170,6 +171,174 @@ pub async fn prepare_messages_for_provider(
     })
 }
 
+/// Load one image marker reference (local path, data URI or URL) as a
+/// normalized data URI, applying the same limits as provider preparation.
+pub async fn load_image_reference(
+    reference: &str,
+    config: &MultimodalConfig,
+) -> anyhow::Result<String> {
+    let (_, max_image_size_mb) = config.effective_limits();
+    let max_bytes = max_image_size_mb.saturating_mul(1024 * 1024);
+    let remote_client = build_runtime_proxy_client_with_timeouts("provider.ollama", 30, 10);
+    normalize_image_reference(reference, config, max_bytes, &remote_client).await
+}
+
+const OCR_VISION_PROMPT: &str = "Transcribe all text visible in the image exactly as written, \
+preserving line breaks. Output only the transcribed text. If there is no text, output nothing.";
+
+/// Extract text from an image reference.
+///
+/// Uses the tesseract CLI; if that fails and `ocr.vision_provider` is set,
+/// the image is transcribed by that provider instead.
+pub async fn ocr_image(reference: &str, config: &MultimodalConfig) -> anyhow::Result<String> {
+    let data_uri = load_image_reference(reference, config).await?;
+    let ocr = &config.ocr;
+    let payload = data_uri.split_once(',').map_or("", |(_, payload)| payload);
+    let bytes = STANDARD.decode(payload)?;
+
+    let text = match run_tesseract(&bytes, ocr).await {
+        Ok(text) => text,
+        Err(tesseract_error) => match ocr
+            .vision_provider
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            Some(provider) => vision_transcribe(provider, &data_uri, ocr)
+                .await
+                .map_err(|error| {
+                    anyhow::anyhow!(
+                        "OCR failed: {tesseract_error}; vision fallback via '{provider}' failed: {error}"
+                    )
+                })?,
+            None => return Err(tesseract_error),
+        },
+    };
+
+    Ok(truncate_ocr_text(text.trim(), ocr.max_chars))
+}
+
+async fn run_tesseract(bytes: &[u8], ocr: &OcrConfig) -> anyhow::Result<String> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut child = tokio::process::Command::new(&ocr.tesseract_command)
+        .args(["stdin", "stdout", "-l", ocr.languages.trim()])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|error| {
+            if error.kind() == std::io::ErrorKind::NotFound {
+                anyhow::anyhow!(
+                    "'{}' not found; install tesseract-ocr or set [multimodal.ocr].vision_provider",
+                    ocr.tesseract_command
+                )
+            } else {
+                anyhow::anyhow!("failed to start '{}': {error}", ocr.tesseract_command)
+            }
+        })?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(bytes).await?;
+    }
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(ocr.timeout_secs.max(1)),
+        child.wait_with_output(),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("tesseract timed out after {}s", ocr.timeout_secs))??;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("tesseract exited with {}: {}", output.status, stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+async fn vision_transcribe(
+    provider_name: &str,
+    data_uri: &str,
+    ocr: &OcrConfig,
+) -> anyhow::Result<String> {
+    let model = ocr
+        .vision_model
+        .as_deref()
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("[multimodal.ocr].vision_model is not set"))?;
+    let provider = crate::providers::create_provider(provider_name, None)?;
+    if !provider.supports_vision() {
+        anyhow::bail!("provider '{provider_name}' does not support vision input");
+    }
+
+    let messages = [
+        ChatMessage::system(OCR_VISION_PROMPT),
+        ChatMessage::user(format!("{IMAGE_MARKER_PREFIX}{data_uri}]")),
+    ];
+    let request = ChatRequest {
+        messages: &messages,
+        tools: None,
+    };
+    let response = tokio::time::timeout(
+        Duration::from_secs(ocr.timeout_secs.max(1)),
+        provider.chat(request, model, 0.0),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("vision transcription timed out after {}s", ocr.timeout_secs))??;
+    Ok(response.text_or_empty().to_string())
+}
+
+fn truncate_ocr_text(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars).collect();
+    truncated.push_str("\n[... OCR text truncated]");
+    truncated
+}
+
+/// Replace image markers in user messages with their OCR text so a provider
+/// without vision can still work with the content. Extraction failures are
+/// reported inline rather than failing the turn. Returns the number of
+/// images replaced.
+pub async fn apply_ocr_fallback(messages: &mut [ChatMessage], config: &MultimodalConfig) -> usize {
+    let mut replaced = 0;
+    for message in messages.iter_mut().filter(|m| m.role == "user") {
+        let (cleaned_text, refs) = parse_image_markers(&message.content);
+        if refs.is_empty() {
+            continue;
+        }
+
+        let mut content = cleaned_text;
+        for reference in &refs {
+            let label = if reference.starts_with("data:") {
+                "attached image"
+            } else {
+                reference.as_str()
+            };
+            let block = match ocr_image(reference, config).await {
+                Ok(text) if text.is_empty() => {
+                    format!("[Image ({label}): no recognizable text]")
+                }
+                Ok(text) => format!("[Image text (OCR) from {label}]\n{text}\n[End of image text]"),
+                Err(error) => {
+                    tracing::warn!("OCR fallback failed for {label}: {error}");
+                    format!("[Image ({label}): text extraction failed: {error}]")
+                }
+            };
+            if !content.is_empty() {
+                content.push_str("\n\n");
+            }
+            content.push_str(&block);
+            replaced += 1;
+        }
+        message.content = content;
+    }
+    replaced
+}
+
 fn compose_multimodal_message(text: &str, data_uris: &[String]) -> String {
     let mut content = String::new();
     let trimmed = text.trim();
@@ -419,6 +588,11 @@ fn mime_from_extension(ext: &str) -> Option<&'static str> {
     }
 }
 
+/// MIME type of a supported image format, judged by its leading bytes.
+pub fn sniff_image_mime(bytes: &[u8]) -> Option<&'static str> {
+    mime_from_magic(bytes)
+}
+
 fn mime_from_magic(bytes: &[u8]) -> Option<&'static str> {
     if bytes.len() >= 8 && bytes.starts_with(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']) {
         return Some("image/png");
@@ -507,6 +681,7 @@ mod tests {
             max_images: 1,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            ocr: crate::config::OcrConfig::default(),
         };
 
         let error = prepare_messages_for_provider(&messages, &config)
@@ -549,6 +724,7 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 1,
             allow_remote_fetch: false,
+            ocr: crate::config::OcrConfig::default(),
         };
 
         let error = prepare_messages_for_provider(&messages, &config)
@@ -566,4 +742,68 @@ mod tests {
             .expect("payload should be extracted");
         assert_eq!(payload, "abcd==");
     }
+
+    fn ocr_config(tesseract_command: &str) -> MultimodalConfig {
+        MultimodalConfig {
+            ocr: OcrConfig {
+                tesseract_command: tesseract_command.to_string(),
+                ..OcrConfig::default()
+            },
+            ..MultimodalConfig::default()
+        }
+    }
+
+    #[test]
+    fn truncate_ocr_text_marks_cut() {
+        assert_eq!(truncate_ocr_text("short", 10), "short");
+        let cut = truncate_ocr_text("abcdefghij", 4);
+        assert!(cut.starts_with("abcd\n"));
+        assert!(cut.contains("truncated"));
+    }
+
+    #[tokio::test]
+    async fn ocr_fallback_reports_missing_tesseract_inline() {
+        let image = format!(
+            "data:image/png;base64,{}",
+            STANDARD.encode(b"not-really-png")
+        );
+        let mut messages = vec![
+            ChatMessage::system("sys [IMAGE:/ignored.png]"),
+            ChatMessage::user(format!("What does this say? [IMAGE:{image}]")),
+        ];
+
+        let replaced =
+            apply_ocr_fallback(&mut messages, &ocr_config("zeroclaw-missing-tesseract")).await;
+
+        assert_eq!(replaced, 1);
+        assert_eq!(messages[0].content, "sys [IMAGE:/ignored.png]");
+        assert!(messages[1].content.starts_with("What does this say?"));
+        assert!(messages[1].content.contains("attached image"));
+        assert!(messages[1].content.contains("not found"));
+        assert_eq!(count_image_markers(&messages), 0);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn ocr_fallback_inlines_extracted_text() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().unwrap();
+        let script = temp.path().join("fake-tesseract");
+        std::fs::write(&script, "#!/bin/sh\ncat > /dev/null\necho 'INVOICE 42'\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let image_path = temp.path().join("scan.png");
+        std::fs::write(&image_path, b"\x89PNG\r\n\x1a\nrest").unwrap();
+
+        let config = ocr_config(script.to_str().unwrap());
+        let mut messages = vec![ChatMessage::user(format!(
+            "[IMAGE:{}]",
+            image_path.display()
+        ))];
+        let replaced = apply_ocr_fallback(&mut messages, &config).await;
+
+        assert_eq!(replaced, 1);
+        assert!(messages[0].content.contains("INVOICE 42"));
+        assert!(messages[0].content.contains("scan.png"));
+    }
 }
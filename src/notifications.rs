@@ -0,0 +1,86 @@
+//! Desktop notifications for output the agent produces on its own —
+//! heartbeat ticks, scheduled jobs and finished background tasks — when that
+//! output is not delivered to a chat channel.
+//!
+//! Needs the `desktop-notifications` build feature; without it, or without
+//! a notification service (headless servers), nothing is shown.
+
+use crate::config::{Config, NotificationsConfig};
+use crate::util::truncate_with_width;
+
+/// Notification title shown for every agent-initiated message.
+const APP_NAME: &str = "ZeroClaw";
+
+/// Show `output` as a desktop notification titled `title` when
+/// `[notifications].desktop` is on. `open_command` is the CLI command that
+/// opens the full result, shown under the preview.
+pub fn agent_output(config: &Config, title: &str, output: &str, open_command: Option<&str>) {
+    if !config.notifications.desktop {
+        return;
+    }
+    let summary = format!("{APP_NAME}: {title}");
+    let body = notification_body(&config.notifications, output, open_command);
+    // Showing a notification blocks on D-Bus/the OS service; keep it off the runtime.
+    std::thread::spawn(move || {
+        if let Err(e) = show(&summary, &body) {
+            tracing::debug!("Desktop notification not shown: {e}");
+        }
+    });
+}
+
+/// The preview (one line, cut to `preview_width` columns) plus the command
+/// that opens the full output.
+fn notification_body(
+    config: &NotificationsConfig,
+    output: &str,
+    open_command: Option<&str>,
+) -> String {
+    let flattened = output.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut body = truncate_with_width(&flattened, config.preview_width, "…");
+    if let Some(command) = open_command {
+        body.push_str("\n\nOpen with: ");
+        body.push_str(command);
+    }
+    body
+}
+
+#[cfg(feature = "desktop-notifications")]
+fn show(summary: &str, body: &str) -> anyhow::Result<()> {
+    notify_rust::Notification::new()
+        .appname(APP_NAME)
+        .summary(summary)
+        .body(body)
+        .show()?;
+    Ok(())
+}
+
+#[cfg(not(feature = "desktop-notifications"))]
+fn show(_summary: &str, _body: &str) -> anyhow::Result<()> {
+    anyhow::bail!(
+        "desktop notification support is not enabled. \
+         Rebuild with: cargo build --features desktop-notifications"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_is_a_one_line_preview_with_open_hint() {
+        let config = NotificationsConfig {
+            desktop: true,
+            preview_width: 20,
+        };
+        let body = notification_body(
+            &config,
+            "Weather today:\n\n  sunny and warm, 24°C with light wind",
+            Some("zeroclaw agent --session heartbeat:1"),
+        );
+        assert_eq!(
+            body,
+            "Weather today: sunn…\n\nOpen with: zeroclaw agent --session heartbeat:1"
+        );
+        assert_eq!(notification_body(&config, "short", None), "short");
+    }
+}
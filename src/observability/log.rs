@@ -38,6 +38,21 @@ impl Observer for LogObserver {
                 let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
                 info!(tool = %tool, duration_ms = ms, success = success, "tool.call");
             }
+            ObserverEvent::TurnStart {
+                channel,
+                provider,
+                model,
+            } => {
+                info!(channel = %channel, provider = %provider, model = %model, "turn.start");
+            }
+            ObserverEvent::TurnEnd {
+                duration,
+                success,
+                error_message,
+            } => {
+                let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+                info!(duration_ms = ms, success = success, error = ?error_message, "turn.end");
+            }
             ObserverEvent::TurnComplete => {
                 info!("turn.complete");
             }
@@ -0,0 +1,460 @@
+//! Process-wide `tracing` subscriber: console and file output, level filters
+//! and secret redaction, all driven by the `[logging]` config section.
+
+use crate::config::LoggingConfig;
+use crate::security::redaction;
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate};
+use serde::Deserialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer, Registry};
+
+const LOG_FILE_PREFIX: &str = "zeroclaw";
+const REDACTED: &str = "[REDACTED]";
+/// Shorter configured values are too likely to collide with ordinary words.
+const MIN_SECRET_LEN: usize = 8;
+
+static SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Where console log lines go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleTarget {
+    Stdout,
+    /// For commands whose stdout carries data (MCP protocol, pipe output).
+    Stderr,
+    /// For full-screen UIs; file logging still applies.
+    Off,
+}
+
+/// Install the global subscriber. `config_dir` anchors a relative
+/// `logging.dir`.
+pub fn init(config: &LoggingConfig, config_dir: &Path, console: ConsoleTarget) -> Result<()> {
+    let redact = config.redact;
+    let mut layers: Vec<Box<dyn Layer<Registry> + Send + Sync>> = Vec::new();
+
+    match console {
+        ConsoleTarget::Stdout => {
+            layers.push(console_layer(
+                &config.format,
+                Redact::new(io::stdout, redact),
+            ));
+        }
+        ConsoleTarget::Stderr => {
+            layers.push(console_layer(
+                &config.format,
+                Redact::new(io::stderr, redact),
+            ));
+        }
+        ConsoleTarget::Off => {}
+    }
+
+    if let Some(dir) = config.dir.as_deref().filter(|d| !d.trim().is_empty()) {
+        let dir = config_dir.join(shellexpand::tilde(dir).as_ref());
+        let files = RollingFile::new(dir, config.max_file_mb, config.max_files)?;
+        let writer = Redact::new(files, redact);
+        let layer = fmt::layer().with_ansi(false).with_writer(writer);
+        layers.push(if config.file_format.eq_ignore_ascii_case("text") {
+            layer.boxed()
+        } else {
+            layer.json().boxed()
+        });
+    }
+
+    tracing_subscriber::registry()
+        .with(layers)
+        .with(build_filter(config))
+        .try_init()
+        .context("Failed to install the log subscriber")
+}
+
+/// Read only `[logging]` from the config file, so logging can start before
+/// the full config (secret decryption, validation) is loaded. A missing or
+/// unreadable file yields the defaults.
+pub fn read_logging_section(config_path: &Path) -> LoggingConfig {
+    #[derive(Deserialize)]
+    struct Section {
+        #[serde(default)]
+        logging: LoggingConfig,
+    }
+
+    fs::read_to_string(config_path)
+        .ok()
+        .and_then(|raw| toml::from_str::<Section>(&raw).ok())
+        .map(|s| s.logging)
+        .unwrap_or_default()
+}
+
+/// Mask these values in every log line from now on. Called once the full
+/// config, with decrypted secrets, is loaded.
+pub fn register_secrets(values: impl IntoIterator<Item = String>) {
+    let mut secrets = SECRETS.write().unwrap_or_else(|e| e.into_inner());
+    for value in values {
+        let value = value.trim().to_string();
+        if value.len() >= MIN_SECRET_LEN && !secrets.contains(&value) {
+            secrets.push(value);
+        }
+    }
+    // Longest first, so a secret containing another is masked whole.
+    secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+}
+
+fn console_layer<W>(format: &str, writer: W) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let layer = fmt::layer().with_writer(writer);
+    if format.eq_ignore_ascii_case("json") {
+        layer.json().boxed()
+    } else {
+        layer.boxed()
+    }
+}
+
+/// `RUST_LOG` when set; otherwise `level` plus the per-module overrides.
+fn build_filter(config: &LoggingConfig) -> EnvFilter {
+    if let Ok(filter) = EnvFilter::try_from_default_env() {
+        return filter;
+    }
+    let directives = filter_directives(config);
+    EnvFilter::try_new(&directives).unwrap_or_else(|e| {
+        eprintln!("Warning: invalid [logging] levels ({e}); using \"info\"");
+        EnvFilter::new("info")
+    })
+}
+
+fn filter_directives(config: &LoggingConfig) -> String {
+    std::iter::once(config.level.trim().to_string())
+        .chain(
+            config
+                .modules
+                .iter()
+                .map(|(module, level)| format!("{}={}", module.trim(), level.trim())),
+        )
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Mask configured secrets and recognizable credentials in `line`.
+fn redact_line(line: &str) -> String {
+    let mut line = line.to_string();
+    {
+        let secrets = SECRETS.read().unwrap_or_else(|e| e.into_inner());
+        for secret in secrets.iter() {
+            if line.contains(secret.as_str()) {
+                line = line.replace(secret.as_str(), REDACTED);
+            }
+        }
+    }
+    redaction::current().apply(&line)
+}
+
+/// Buffers each formatted event and writes it redacted in one piece, so a
+/// secret is never split across two `write` calls.
+struct Redact<M> {
+    inner: M,
+    enabled: bool,
+}
+
+impl<M> Redact<M> {
+    fn new(inner: M, enabled: bool) -> Self {
+        Self { inner, enabled }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Redact<M> {
+    type Writer = RedactWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactWriter {
+            inner: self.inner.make_writer(),
+            buf: Vec::new(),
+            enabled: self.enabled,
+        }
+    }
+}
+
+struct RedactWriter<W: Write> {
+    inner: W,
+    buf: Vec<u8>,
+    enabled: bool,
+}
+
+impl<W: Write> Write for RedactWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.enabled {
+            return self.inner.write(buf);
+        }
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            let line = String::from_utf8_lossy(&self.buf);
+            let redacted = redact_line(&line);
+            self.buf.clear();
+            self.inner.write_all(redacted.as_bytes())?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for RedactWriter<W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Daily log files in one directory: `zeroclaw.YYYY-MM-DD.log`, then
+/// `zeroclaw.YYYY-MM-DD.1.log` and so on once a file reaches the size cap.
+#[derive(Clone)]
+struct RollingFile {
+    state: Arc<Mutex<RollingState>>,
+}
+
+struct RollingState {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    date: NaiveDate,
+    index: u32,
+    file: Option<File>,
+    written: u64,
+}
+
+impl RollingFile {
+    fn new(dir: PathBuf, max_file_mb: u64, max_files: usize) -> Result<Self> {
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create log directory {}", dir.display()))?;
+        let mut state = RollingState {
+            dir,
+            max_bytes: max_file_mb.max(1).saturating_mul(1024 * 1024),
+            max_files: max_files.max(1),
+            date: Local::now().date_naive(),
+            index: 0,
+            file: None,
+            written: 0,
+        };
+        state.open_current()?;
+        Ok(Self {
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+}
+
+impl RollingState {
+    fn path_for(&self, date: NaiveDate, index: u32) -> PathBuf {
+        let name = if index == 0 {
+            format!("{LOG_FILE_PREFIX}.{date}.log")
+        } else {
+            format!("{LOG_FILE_PREFIX}.{date}.{index}.log")
+        };
+        self.dir.join(name)
+    }
+
+    /// Open (appending) the first file for `self.date` still under the cap.
+    fn open_current(&mut self) -> io::Result<()> {
+        loop {
+            let path = self.path_for(self.date, self.index);
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            if size < self.max_bytes {
+                self.file = Some(OpenOptions::new().create(true).append(true).open(&path)?);
+                self.written = size;
+                break;
+            }
+            self.index += 1;
+        }
+        self.prune();
+        Ok(())
+    }
+
+    fn roll_if_needed(&mut self) -> io::Result<()> {
+        let today = Local::now().date_naive();
+        if today != self.date {
+            self.date = today;
+            self.index = 0;
+            self.open_current()
+        } else if self.written >= self.max_bytes {
+            self.index += 1;
+            self.open_current()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Delete the oldest log files beyond `max_files`.
+    fn prune(&self) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .filter_map(Result::ok)
+            .filter(|e| {
+                let name = e.file_name();
+                let name = name.to_string_lossy();
+                name.starts_with(&format!("{LOG_FILE_PREFIX}.")) && name.ends_with(".log")
+            })
+            .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+            .collect();
+        if files.len() <= self.max_files {
+            return;
+        }
+        files.sort();
+        let excess = files.len() - self.max_files;
+        for (_, path) in files.into_iter().take(excess) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.roll_if_needed()?;
+        let n = match state.file.as_mut() {
+            Some(file) => file.write(buf)?,
+            None => buf.len(),
+        };
+        state.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl MakeWriter<'_> for RollingFile {
+    type Writer = RollingFile;
+
+    fn make_writer(&self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn filter_directives_join_level_and_modules() {
+        let config = LoggingConfig {
+            level: "warn".into(),
+            modules: BTreeMap::from([
+                ("hyper".to_string(), "error".to_string()),
+                ("zeroclaw::channels".to_string(), "debug".to_string()),
+            ]),
+            ..LoggingConfig::default()
+        };
+        assert_eq!(
+            filter_directives(&config),
+            "warn,hyper=error,zeroclaw::channels=debug"
+        );
+    }
+
+    #[test]
+    fn read_logging_section_ignores_other_sections() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        fs::write(
+            &path,
+            "default_temperature = 0.7\n\n[logging]\nformat = \"json\"\nlevel = \"debug\"\n",
+        )
+        .unwrap();
+        let config = read_logging_section(&path);
+        assert_eq!(config.format, "json");
+        assert_eq!(config.level, "debug");
+        assert!(config.redact);
+    }
+
+    #[test]
+    fn read_logging_section_defaults_when_missing() {
+        let config = read_logging_section(Path::new("/nonexistent/config.toml"));
+        assert_eq!(config.level, "info");
+        assert!(config.dir.is_none());
+    }
+
+    #[test]
+    fn registered_secrets_are_masked() {
+        register_secrets(["zc-test-secret-value-1234".to_string(), "short".to_string()]);
+        let line = redact_line("token=zc-test-secret-value-1234 user=short");
+        assert!(!line.contains("zc-test-secret-value-1234"));
+        assert!(line.contains(REDACTED));
+        assert!(line.contains("short"));
+    }
+
+    #[test]
+    fn redact_writer_masks_across_split_writes() {
+        register_secrets(["zc-split-secret-abcdef".to_string()]);
+        let out = Arc::new(Mutex::new(Vec::new()));
+        {
+            let mut writer = RedactWriter {
+                inner: SharedBuf(out.clone()),
+                buf: Vec::new(),
+                enabled: true,
+            };
+            writer.write_all(b"key zc-split-").unwrap();
+            writer.write_all(b"secret-abcdef\n").unwrap();
+        }
+        let written = String::from_utf8(out.lock().unwrap().clone()).unwrap();
+        assert_eq!(written, format!("key {REDACTED}\n"));
+    }
+
+    #[test]
+    fn rolling_file_starts_new_file_at_size_cap() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut files = RollingFile::new(tmp.path().to_path_buf(), 1, 10).unwrap();
+        files.state.lock().unwrap().max_bytes = 16;
+        files.write_all(b"0123456789abcdef").unwrap();
+        files.write_all(b"next\n").unwrap();
+
+        let state = files.state.lock().unwrap();
+        assert_eq!(state.index, 1);
+        assert!(state.path_for(state.date, 0).exists());
+        assert_eq!(
+            fs::read_to_string(state.path_for(state.date, 1)).unwrap(),
+            "next\n"
+        );
+    }
+
+    #[test]
+    fn rolling_file_prunes_oldest_beyond_max_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        for day in ["2026-01-01", "2026-01-02", "2026-01-03"] {
+            fs::write(tmp.path().join(format!("zeroclaw.{day}.log")), "old\n").unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let _files = RollingFile::new(tmp.path().to_path_buf(), 1, 2).unwrap();
+
+        let remaining: Vec<_> = fs::read_dir(tmp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(!remaining.contains(&"zeroclaw.2026-01-01.log".to_string()));
+    }
+
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod log;
+pub mod logging;
 pub mod multi;
 pub mod noop;
 #[cfg(feature = "observability-otel")]
@@ -26,7 +27,7 @@ use crate::config::ObservabilityConfig;
 pub fn create_observer(config: &ObservabilityConfig) -> Box<dyn Observer> {
     match config.backend.as_str() {
         "log" => Box::new(LogObserver::new()),
-        "prometheus" => Box::new(PrometheusObserver::new()),
+        "prometheus" => Box::new(PrometheusObserver::shared()),
         "otel" | "opentelemetry" | "otlp" => {
             #[cfg(feature = "observability-otel")]
             match OtelObserver::new(
@@ -1,17 +1,42 @@
 use super::traits::{Observer, ObserverEvent, ObserverMetric};
 use opentelemetry::metrics::{Counter, Gauge, Histogram};
-use opentelemetry::trace::{Span, SpanKind, Status, Tracer};
-use opentelemetry::{global, KeyValue};
+use opentelemetry::trace::{Span, SpanBuilder, SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{global, Context, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::metrics::SdkMeterProvider;
 use opentelemetry_sdk::trace::SdkTracerProvider;
+use parking_lot::Mutex;
 use std::any::Any;
-use std::time::SystemTime;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+
+/// A turn whose task died before `TurnEnd` is closed once it has been open
+/// this long and another turn starts.
+const STALE_TURN_AFTER: Duration = Duration::from_secs(3600);
+
+/// The `agent.turn` span of a turn in progress, and when its pending child
+/// calls started.
+struct OpenTurn {
+    cx: Context,
+    opened: Instant,
+    llm_started: Option<SystemTime>,
+    tools_started: HashMap<String, Vec<SystemTime>>,
+    llm_calls: u64,
+    tool_calls: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+}
 
 /// OpenTelemetry-backed observer — exports traces and metrics via OTLP.
+///
+/// Each turn of the agent loop becomes an `agent.turn` span with its
+/// `llm.call` and `tool.call` spans as children. Turns are told apart by
+/// the tokio task running them, so concurrent channel conversations get
+/// separate traces.
 pub struct OtelObserver {
     tracer_provider: SdkTracerProvider,
     meter_provider: SdkMeterProvider,
+    turns: Mutex<HashMap<tokio::task::Id, OpenTurn>>,
 
     // Metrics instruments
     agent_starts: Counter<u64>,
@@ -155,6 +180,7 @@ impl OtelObserver {
         Ok(Self {
             tracer_provider,
             meter_provider: meter_provider_clone,
+            turns: Mutex::new(HashMap::new()),
             agent_starts,
             agent_duration,
             llm_calls,
@@ -172,6 +198,103 @@ impl OtelObserver {
     }
 }
 
+impl OtelObserver {
+    /// Start a span under the current task's turn, if one is open.
+    fn child_span(&self, builder: SpanBuilder) -> global::BoxedSpan {
+        let tracer = global::tracer("zeroclaw");
+        let parent = tokio::task::try_id()
+            .and_then(|id| self.turns.lock().get(&id).map(|turn| turn.cx.clone()));
+        match parent {
+            Some(cx) => tracer.build_with_context(builder, &cx),
+            None => tracer.build(builder),
+        }
+    }
+
+    /// Run `f` on the current task's open turn.
+    fn with_turn<T>(&self, f: impl FnOnce(&mut OpenTurn) -> T) -> Option<T> {
+        let id = tokio::task::try_id()?;
+        self.turns.lock().get_mut(&id).map(f)
+    }
+
+    fn open_turn(&self, channel: &str, provider: &str, model: &str) {
+        let Some(id) = tokio::task::try_id() else {
+            return;
+        };
+        let span = global::tracer("zeroclaw").build(
+            SpanBuilder::from_name("agent.turn")
+                .with_kind(SpanKind::Internal)
+                .with_attributes(vec![
+                    KeyValue::new("channel", channel.to_string()),
+                    KeyValue::new("provider", provider.to_string()),
+                    KeyValue::new("model", model.to_string()),
+                    KeyValue::new("gen_ai.system", provider.to_string()),
+                    KeyValue::new("gen_ai.request.model", model.to_string()),
+                ]),
+        );
+        let turn = OpenTurn {
+            cx: Context::new().with_span(span),
+            opened: Instant::now(),
+            llm_started: None,
+            tools_started: HashMap::new(),
+            llm_calls: 0,
+            tool_calls: 0,
+            input_tokens: 0,
+            output_tokens: 0,
+        };
+        let mut turns = self.turns.lock();
+        turns.retain(|_, open| {
+            let stale = open.opened.elapsed() > STALE_TURN_AFTER;
+            if stale {
+                let span = open.cx.span();
+                span.set_status(Status::error("turn abandoned"));
+                span.end();
+            }
+            !stale
+        });
+        if let Some(previous) = turns.insert(id, turn) {
+            previous.cx.span().end();
+        }
+    }
+
+    fn close_turn(&self, duration: Duration, success: bool, error_message: Option<&str>) {
+        let Some(turn) = tokio::task::try_id().and_then(|id| self.turns.lock().remove(&id)) else {
+            return;
+        };
+        let span = turn.cx.span();
+        span.set_attribute(KeyValue::new("duration_s", duration.as_secs_f64()));
+        span.set_attribute(KeyValue::new("llm_calls", to_i64(turn.llm_calls)));
+        span.set_attribute(KeyValue::new("tool_calls", to_i64(turn.tool_calls)));
+        span.set_attribute(KeyValue::new(
+            "gen_ai.usage.input_tokens",
+            to_i64(turn.input_tokens),
+        ));
+        span.set_attribute(KeyValue::new(
+            "gen_ai.usage.output_tokens",
+            to_i64(turn.output_tokens),
+        ));
+        if success {
+            span.set_status(Status::Ok);
+        } else {
+            span.set_status(Status::error(error_message.unwrap_or_default().to_string()));
+        }
+        span.end();
+    }
+}
+
+fn to_i64(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+/// When a call that took `duration` and started at `started` (if known)
+/// began.
+fn start_time(started: Option<SystemTime>, duration: Duration) -> SystemTime {
+    started.unwrap_or_else(|| {
+        SystemTime::now()
+            .checked_sub(duration)
+            .unwrap_or(SystemTime::now())
+    })
+}
+
 impl Observer for OtelObserver {
     fn record_event(&self, event: &ObserverEvent) {
         let tracer = global::tracer("zeroclaw");
@@ -186,17 +309,36 @@ impl Observer for OtelObserver {
                     ],
                 );
             }
-            ObserverEvent::LlmRequest { .. }
-            | ObserverEvent::ToolCallStart { .. }
-            | ObserverEvent::TurnComplete => {}
+            ObserverEvent::TurnStart {
+                channel,
+                provider,
+                model,
+            } => self.open_turn(channel, provider, model),
+            ObserverEvent::TurnEnd {
+                duration,
+                success,
+                error_message,
+            } => self.close_turn(*duration, *success, error_message.as_deref()),
+            ObserverEvent::LlmRequest { .. } => {
+                self.with_turn(|turn| turn.llm_started = Some(SystemTime::now()));
+            }
+            ObserverEvent::ToolCallStart { tool } => {
+                self.with_turn(|turn| {
+                    turn.tools_started
+                        .entry(tool.clone())
+                        .or_default()
+                        .push(SystemTime::now());
+                });
+            }
+            ObserverEvent::TurnComplete => {}
             ObserverEvent::LlmResponse {
                 provider,
                 model,
                 duration,
                 success,
-                error_message: _,
-                input_tokens: _,
-                output_tokens: _,
+                error_message,
+                input_tokens,
+                output_tokens,
             } => {
                 let secs = duration.as_secs_f64();
                 let attrs = [
@@ -207,25 +349,40 @@ impl Observer for OtelObserver {
                 self.llm_calls.add(1, &attrs);
                 self.llm_duration.record(secs, &attrs);
 
+                let started = self
+                    .with_turn(|turn| {
+                        turn.llm_calls += 1;
+                        turn.input_tokens += input_tokens.unwrap_or(0);
+                        turn.output_tokens += output_tokens.unwrap_or(0);
+                        turn.llm_started.take()
+                    })
+                    .flatten();
+
                 // Create a completed span for visibility in trace backends.
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("llm.call")
-                        .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
-                        .with_attributes(vec![
-                            KeyValue::new("provider", provider.clone()),
-                            KeyValue::new("model", model.clone()),
-                            KeyValue::new("success", *success),
-                            KeyValue::new("duration_s", secs),
-                        ]),
+                let mut attributes = vec![
+                    KeyValue::new("provider", provider.clone()),
+                    KeyValue::new("model", model.clone()),
+                    KeyValue::new("gen_ai.system", provider.clone()),
+                    KeyValue::new("gen_ai.request.model", model.clone()),
+                    KeyValue::new("success", *success),
+                    KeyValue::new("duration_s", secs),
+                ];
+                if let Some(tokens) = input_tokens {
+                    attributes.push(KeyValue::new("gen_ai.usage.input_tokens", to_i64(*tokens)));
+                }
+                if let Some(tokens) = output_tokens {
+                    attributes.push(KeyValue::new("gen_ai.usage.output_tokens", to_i64(*tokens)));
+                }
+                let mut span = self.child_span(
+                    SpanBuilder::from_name("llm.call")
+                        .with_kind(SpanKind::Client)
+                        .with_start_time(start_time(started, *duration))
+                        .with_attributes(attributes),
                 );
                 if *success {
                     span.set_status(Status::Ok);
                 } else {
-                    span.set_status(Status::error(""));
+                    span.set_status(Status::error(error_message.clone().unwrap_or_default()));
                 }
                 span.end();
             }
@@ -243,7 +400,7 @@ impl Observer for OtelObserver {
 
                 // Create a completed span with correct timing
                 let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("agent.invocation")
+                    SpanBuilder::from_name("agent.invocation")
                         .with_kind(SpanKind::Internal)
                         .with_start_time(start_time)
                         .with_attributes(vec![
@@ -276,9 +433,13 @@ impl Observer for OtelObserver {
                 success,
             } => {
                 let secs = duration.as_secs_f64();
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
+                let started = self
+                    .with_turn(|turn| {
+                        turn.tool_calls += 1;
+                        let starts = turn.tools_started.get_mut(tool)?;
+                        (!starts.is_empty()).then(|| starts.remove(0))
+                    })
+                    .flatten();
 
                 let status = if *success {
                     Status::Ok
@@ -286,10 +447,10 @@ impl Observer for OtelObserver {
                     Status::error("")
                 };
 
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("tool.call")
+                let mut span = self.child_span(
+                    SpanBuilder::from_name("tool.call")
                         .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
+                        .with_start_time(start_time(started, *duration))
                         .with_attributes(vec![
                             KeyValue::new("tool.name", tool.clone()),
                             KeyValue::new("tool.success", *success),
@@ -321,8 +482,8 @@ impl Observer for OtelObserver {
             }
             ObserverEvent::Error { component, message } => {
                 // Create an error span for visibility in trace backends
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("error")
+                let mut span = self.child_span(
+                    SpanBuilder::from_name("error")
                         .with_kind(SpanKind::Internal)
                         .with_attributes(vec![
                             KeyValue::new("component", component.clone()),
@@ -457,6 +618,70 @@ mod tests {
         });
     }
 
+    #[test]
+    fn turn_events_open_and_close_a_parent_span_per_task() {
+        let obs = test_observer();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            obs.record_event(&ObserverEvent::TurnStart {
+                channel: "telegram".into(),
+                provider: "openrouter".into(),
+                model: "claude-sonnet".into(),
+            });
+            // `block_on` is not a task, so there is no turn to attach to.
+            assert!(obs.with_turn(|_| ()).is_none());
+        });
+
+        let obs = std::sync::Arc::new(obs);
+        let task_obs = obs.clone();
+        runtime.block_on(async move {
+            tokio::spawn(async move {
+                task_obs.record_event(&ObserverEvent::TurnStart {
+                    channel: "cli".into(),
+                    provider: "openrouter".into(),
+                    model: "claude-sonnet".into(),
+                });
+                task_obs.record_event(&ObserverEvent::LlmRequest {
+                    provider: "openrouter".into(),
+                    model: "claude-sonnet".into(),
+                    messages_count: 2,
+                });
+                task_obs.record_event(&ObserverEvent::LlmResponse {
+                    provider: "openrouter".into(),
+                    model: "claude-sonnet".into(),
+                    duration: Duration::from_millis(250),
+                    success: true,
+                    error_message: None,
+                    input_tokens: Some(100),
+                    output_tokens: Some(50),
+                });
+                task_obs.record_event(&ObserverEvent::ToolCallStart {
+                    tool: "shell".into(),
+                });
+                task_obs.record_event(&ObserverEvent::ToolCall {
+                    tool: "shell".into(),
+                    duration: Duration::from_millis(10),
+                    success: true,
+                });
+                let totals = task_obs
+                    .with_turn(|turn| (turn.llm_calls, turn.tool_calls, turn.input_tokens))
+                    .unwrap();
+                assert_eq!(totals, (1, 1, 100));
+                task_obs.record_event(&ObserverEvent::TurnEnd {
+                    duration: Duration::from_millis(300),
+                    success: true,
+                    error_message: None,
+                });
+                assert!(task_obs.with_turn(|_| ()).is_none());
+            })
+            .await
+            .unwrap();
+        });
+        assert!(obs.turns.lock().is_empty());
+    }
+
     #[test]
     fn records_all_metrics_without_panic() {
         let obs = test_observer();
@@ -2,8 +2,12 @@ use super::traits::{Observer, ObserverEvent, ObserverMetric};
 use prometheus::{
     Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, Registry, TextEncoder,
 };
+use std::sync::OnceLock;
 
 /// Prometheus-backed observer — exposes metrics for scraping via `/metrics`.
+///
+/// Clones share the same registry and metrics.
+#[derive(Clone)]
 pub struct PrometheusObserver {
     registry: Registry,
 
@@ -19,6 +23,7 @@ pub struct PrometheusObserver {
 
     // Histograms
     agent_duration: HistogramVec,
+    llm_duration: HistogramVec,
     tool_duration: HistogramVec,
     request_latency: Histogram,
 
@@ -91,6 +96,16 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let llm_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_llm_request_duration_seconds",
+                "LLM provider request duration in seconds",
+            )
+            .buckets(vec![0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0]),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
         let tool_duration = HistogramVec::new(
             HistogramOpts::new(
                 "zeroclaw_tool_duration_seconds",
@@ -140,6 +155,7 @@ impl PrometheusObserver {
         registry.register(Box::new(heartbeat_ticks.clone())).ok();
         registry.register(Box::new(errors.clone())).ok();
         registry.register(Box::new(agent_duration.clone())).ok();
+        registry.register(Box::new(llm_duration.clone())).ok();
         registry.register(Box::new(tool_duration.clone())).ok();
         registry.register(Box::new(request_latency.clone())).ok();
         registry.register(Box::new(tokens_used.clone())).ok();
@@ -157,6 +173,7 @@ impl PrometheusObserver {
             heartbeat_ticks,
             errors,
             agent_duration,
+            llm_duration,
             tool_duration,
             request_latency,
             tokens_used,
@@ -165,6 +182,14 @@ impl PrometheusObserver {
         }
     }
 
+    /// The process-wide observer. Every agent loop, channel and the
+    /// gateway's `/metrics` handler share its registry, so one scrape sees
+    /// all of them.
+    pub fn shared() -> Self {
+        static SHARED: OnceLock<PrometheusObserver> = OnceLock::new();
+        SHARED.get_or_init(Self::new).clone()
+    }
+
     /// Encode all registered metrics into Prometheus text exposition format.
     pub fn encode(&self) -> String {
         let encoder = TextEncoder::new();
@@ -201,6 +226,7 @@ impl Observer for PrometheusObserver {
             ObserverEvent::LlmResponse {
                 provider,
                 model,
+                duration,
                 success,
                 input_tokens,
                 output_tokens,
@@ -210,6 +236,9 @@ impl Observer for PrometheusObserver {
                 self.llm_requests
                     .with_label_values(&[provider.as_str(), model.as_str(), success_str])
                     .inc();
+                self.llm_duration
+                    .with_label_values(&[provider.as_str(), model.as_str()])
+                    .observe(duration.as_secs_f64());
                 if let Some(input) = input_tokens {
                     self.tokens_input_total
                         .with_label_values(&[provider.as_str(), model.as_str()])
@@ -223,6 +252,8 @@ impl Observer for PrometheusObserver {
             }
             ObserverEvent::ToolCallStart { tool: _ }
             | ObserverEvent::TurnComplete
+            | ObserverEvent::TurnStart { .. }
+            | ObserverEvent::TurnEnd { .. }
             | ObserverEvent::LlmRequest { .. } => {}
             ObserverEvent::ToolCall {
                 tool,
@@ -469,6 +500,22 @@ mod tests {
         assert!(output.contains(
             r#"zeroclaw_tokens_output_total{model="claude-sonnet",provider="openrouter"} 130"#
         ));
+        assert!(output.contains(
+            r#"zeroclaw_llm_request_duration_seconds_count{model="claude-sonnet",provider="openrouter"} 2"#
+        ));
+    }
+
+    #[test]
+    fn shared_observers_report_into_one_registry() {
+        let channel_side = PrometheusObserver::shared();
+        let gateway_side = PrometheusObserver::shared();
+        channel_side.record_event(&ObserverEvent::ChannelMessage {
+            channel: "shared-test".into(),
+            direction: "inbound".into(),
+        });
+        assert!(gateway_side.encode().contains(
+            r#"zeroclaw_channel_messages_total{channel="shared-test",direction="inbound"} 1"#
+        ));
     }
 
     #[test]
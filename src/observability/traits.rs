@@ -47,6 +47,20 @@ pub enum ObserverEvent {
         duration: Duration,
         success: bool,
     },
+    /// The tool-call loop started on one user message. Provider and tool
+    /// events until the matching `TurnEnd` belong to this turn.
+    TurnStart {
+        channel: String,
+        provider: String,
+        model: String,
+    },
+    /// The tool-call loop finished the turn opened by `TurnStart`, with an
+    /// answer or an error.
+    TurnEnd {
+        duration: Duration,
+        success: bool,
+        error_message: Option<String>,
+    },
     /// The agent produced a final answer for the current user message.
     TurnComplete,
     /// A message was sent or received through a channel.
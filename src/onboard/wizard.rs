@@ -4,17 +4,18 @@ use crate::config::schema::{
 };
 use crate::config::{
     AutonomyConfig, BrowserConfig, ChannelsConfig, ComposioConfig, Config, DiscordConfig,
-    HeartbeatConfig, IMessageConfig, LarkConfig, MatrixConfig, MemoryConfig, ObservabilityConfig,
-    RuntimeConfig, SecretsConfig, SlackConfig, StorageConfig, TelegramConfig, WebhookConfig,
+    HeartbeatConfig, IMessageConfig, LarkConfig, LoggingConfig, MatrixConfig, MemoryConfig,
+    ObservabilityConfig, RuntimeConfig, SecretsConfig, SlackConfig, StorageConfig, TelegramConfig,
+    WebhookConfig,
 };
 use crate::hardware::{self, HardwareConfig};
 use crate::memory::{
     default_memory_backend_key, memory_backend_profile, selectable_memory_backends,
 };
 use crate::providers::{
-    canonical_china_provider_name, is_glm_alias, is_glm_cn_alias, is_minimax_alias,
-    is_moonshot_alias, is_qianfan_alias, is_qwen_alias, is_qwen_oauth_alias, is_zai_alias,
-    is_zai_cn_alias,
+    canonical_china_provider_name, create_provider_with_url, is_glm_alias, is_glm_cn_alias,
+    is_minimax_alias, is_moonshot_alias, is_qianfan_alias, is_qwen_alias, is_qwen_oauth_alias,
+    is_zai_alias, is_zai_cn_alias,
 };
 use anyhow::{bail, Context, Result};
 use console::style;
@@ -60,6 +61,7 @@ const MODEL_PREVIEW_LIMIT: usize = 20;
 const MODEL_CACHE_FILE: &str = "models_cache.json";
 const MODEL_CACHE_TTL_SECS: u64 = 12 * 60 * 60;
 const CUSTOM_MODEL_SENTINEL: &str = "__custom_model__";
+const PROVIDER_VERIFY_TIMEOUT: Duration = Duration::from_secs(30);
 
 fn has_launchable_channels(channels: &ChannelsConfig) -> bool {
     channels.channels_except_webhook().iter().any(|(_, ok)| *ok)
@@ -98,7 +100,13 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
     }
 
     print_step(2, 9, "AI Provider & API Key");
-    let (provider, api_key, model, provider_api_url) = setup_provider(&workspace_dir).await?;
+    let (provider, api_key, model, provider_api_url) = loop {
+        let selection = setup_provider(&workspace_dir).await?;
+        let (provider, api_key, model, provider_api_url) = &selection;
+        if verify_provider(provider, api_key, model, provider_api_url.as_deref()).await? {
+            break selection;
+        }
+    };
 
     print_step(3, 9, "Channels (How You Talk to ZeroClaw)");
     let channels_config = setup_channels()?;
@@ -137,6 +145,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         model_providers: std::collections::HashMap::new(),
         default_temperature: 0.7,
         observability: ObservabilityConfig::default(),
+        logging: LoggingConfig::default(),
         autonomy: AutonomyConfig::default(),
         security: crate::config::SecurityConfig::default(),
         runtime: RuntimeConfig::default(),
@@ -148,6 +157,9 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        schedule: std::collections::HashMap::new(),
+        tasks: crate::config::TasksConfig::default(),
+        feeds: crate::config::FeedsConfig::default(),
         channels_config,
         memory: memory_config, // User-selected memory backend
         storage: StorageConfig::default(),
@@ -160,11 +172,31 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         multimodal: crate::config::MultimodalConfig::default(),
         web_fetch: crate::config::WebFetchConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
+        code_interpreter: crate::config::CodeInterpreterConfig::default(),
+        git: crate::config::GitToolConfig::default(),
+        sql: crate::config::SqlToolConfig::default(),
+        documents: crate::config::DocumentToolConfig::default(),
+        image_generation: crate::config::ImageGenerationConfig::default(),
+        clipboard: crate::config::ClipboardConfig::default(),
+        notifications: crate::config::NotificationsConfig::default(),
+        calendar: crate::config::CalendarConfig::default(),
+        mcp: crate::config::McpConfig::default(),
+        plugins: crate::config::PluginsConfig::default(),
+        custom_tools: Vec::new(),
+        conversations: crate::config::ConversationsConfig::default(),
+        profiles: std::collections::HashMap::new(),
+        active_profile: None,
+        schema_version: crate::config::migrate::CURRENT_VERSION,
         proxy: crate::config::ProxyConfig::default(),
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
+        budget: crate::config::BudgetConfig::default(),
+        guardrails: crate::config::GuardrailsConfig::default(),
+        replay: crate::config::ReplayConfig::default(),
+        locale: crate::config::LocaleConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
+        routing: crate::config::RoutingConfig::default(),
         hooks: crate::config::HooksConfig::default(),
         hardware: hardware_config,
         query_classification: crate::config::QueryClassificationConfig::default(),
@@ -183,7 +215,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         if config.memory.auto_save { "on" } else { "off" }
     );
 
-    config.save().await?;
+    config.save_commented().await?;
     persist_workspace_selection(&config.config_path).await?;
 
     // ── Final summary ────────────────────────────────────────────
@@ -381,6 +413,10 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
             0
         },
         chunk_max_tokens: 512,
+        fact_extraction: false,
+        fact_extraction_model: None,
+        scopes: crate::config::MemoryScopesConfig::default(),
+        compaction: crate::config::MemoryCompactionConfig::default(),
         response_cache_enabled: false,
         response_cache_ttl_minutes: 60,
         response_cache_max_entries: 5_000,
@@ -389,6 +425,7 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
         qdrant: crate::config::QdrantConfig::default(),
+        redis: crate::config::RedisMemoryConfig::default(),
     }
 }
 
@@ -488,6 +525,7 @@ async fn run_quick_setup_with_home(
         model_providers: std::collections::HashMap::new(),
         default_temperature: 0.7,
         observability: ObservabilityConfig::default(),
+        logging: LoggingConfig::default(),
         autonomy: AutonomyConfig::default(),
         security: crate::config::SecurityConfig::default(),
         runtime: RuntimeConfig::default(),
@@ -499,6 +537,9 @@ async fn run_quick_setup_with_home(
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        schedule: std::collections::HashMap::new(),
+        tasks: crate::config::TasksConfig::default(),
+        feeds: crate::config::FeedsConfig::default(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
         storage: StorageConfig::default(),
@@ -511,18 +552,38 @@ async fn run_quick_setup_with_home(
         multimodal: crate::config::MultimodalConfig::default(),
         web_fetch: crate::config::WebFetchConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
+        code_interpreter: crate::config::CodeInterpreterConfig::default(),
+        git: crate::config::GitToolConfig::default(),
+        sql: crate::config::SqlToolConfig::default(),
+        documents: crate::config::DocumentToolConfig::default(),
+        image_generation: crate::config::ImageGenerationConfig::default(),
+        clipboard: crate::config::ClipboardConfig::default(),
+        notifications: crate::config::NotificationsConfig::default(),
+        calendar: crate::config::CalendarConfig::default(),
+        mcp: crate::config::McpConfig::default(),
+        plugins: crate::config::PluginsConfig::default(),
+        custom_tools: Vec::new(),
+        conversations: crate::config::ConversationsConfig::default(),
+        profiles: std::collections::HashMap::new(),
+        active_profile: None,
+        schema_version: crate::config::migrate::CURRENT_VERSION,
         proxy: crate::config::ProxyConfig::default(),
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
+        budget: crate::config::BudgetConfig::default(),
+        guardrails: crate::config::GuardrailsConfig::default(),
+        replay: crate::config::ReplayConfig::default(),
+        locale: crate::config::LocaleConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
+        routing: crate::config::RoutingConfig::default(),
         hooks: crate::config::HooksConfig::default(),
         hardware: crate::config::HardwareConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
     };
 
-    config.save().await?;
+    config.save_commented().await?;
     persist_workspace_selection(&config.config_path).await?;
 
     // Scaffold minimal workspace files
@@ -2824,6 +2885,56 @@ async fn setup_provider(workspace_dir: &Path) -> Result<(String, String, String,
     Ok((provider_name.to_string(), api_key, model, provider_api_url))
 }
 
+/// Send a one-line test request with the chosen provider, key and model.
+/// Returns `false` when it failed and the user wants to re-enter them.
+async fn verify_provider(
+    provider_name: &str,
+    api_key: &str,
+    model: &str,
+    api_url: Option<&str>,
+) -> Result<bool> {
+    print_bullet("Testing your credentials with a short request...");
+    let api_key = (!api_key.trim().is_empty()).then_some(api_key);
+    let result = match create_provider_with_url(provider_name, api_key, api_url) {
+        Ok(provider) => tokio::time::timeout(
+            PROVIDER_VERIFY_TIMEOUT,
+            provider.simple_chat("Reply with the single word: ok", model, 0.0),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(anyhow::anyhow!(
+                "no response within {}s",
+                PROVIDER_VERIFY_TIMEOUT.as_secs()
+            ))
+        }),
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(_) => {
+            println!(
+                "  {} {} answered using {}",
+                style("✓").green().bold(),
+                style(provider_name).green(),
+                style(model).green()
+            );
+            Ok(true)
+        }
+        Err(e) => {
+            println!(
+                "  {} Test request failed: {}",
+                style("✗").red().bold(),
+                crate::providers::sanitize_api_error(&format!("{e:#}"))
+            );
+            let choice = Select::new()
+                .with_prompt("  What next?")
+                .items(&["Re-enter provider, API key and model", "Continue anyway"])
+                .default(0)
+                .interact()?;
+            Ok(choice == 1)
+        }
+    }
+}
+
 fn local_provider_choices() -> Vec<(&'static str, &'static str)> {
     vec![
         ("ollama", "Ollama — local models (Llama, Mistral, Phi)"),
@@ -2972,7 +3083,10 @@ fn setup_tool_mode() -> Result<(ComposioConfig, SecretsConfig)> {
         .default(true)
         .interact()?;
 
-    let secrets_config = SecretsConfig { encrypt };
+    let secrets_config = SecretsConfig {
+        encrypt,
+        ..SecretsConfig::default()
+    };
 
     if encrypt {
         println!(
@@ -3717,6 +3831,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     allowed_users,
                     listen_to_bots: false,
                     mention_only: false,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
                 });
             }
             ChannelMenuChoice::Slack => {
@@ -3844,6 +3960,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(channel)
                     },
                     allowed_users,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
                 });
             }
             ChannelMenuChoice::IMessage => {
@@ -5853,6 +5971,7 @@ mod tests {
         let config_raw = tokio::fs::read_to_string(config.config_path).await.unwrap();
         assert!(config_raw.contains("default_provider = \"openrouter\""));
         assert!(config_raw.contains("default_model = \"custom-model-946\""));
+        assert!(config_raw.contains("# Default provider ID or alias"));
     }
 
     #[tokio::test]
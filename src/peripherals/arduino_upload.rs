@@ -4,7 +4,7 @@
 //! sketch code and calls this tool. ZeroClaw compiles and uploads it — no
 //! manual IDE or file editing.
 
-use crate::tools::traits::{Tool, ToolResult};
+use crate::tools::traits::{Tool, ToolResult, ToolRisk};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::process::Command;
@@ -44,6 +44,10 @@ impl Tool for ArduinoUploadTool {
         })
     }
 
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::High
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         let code = args
             .get("code")
@@ -0,0 +1,402 @@
+//! WASM plugin tools.
+//!
+//! Every `*.wasm` file in `[plugins].dir` is loaded as a WASI preview 2
+//! component implementing the `zeroclaw:plugin/tool` interface from
+//! `wit/zeroclaw-plugin.wit`, and registered next to the built-in tools.
+//!
+//! Plugins start with no capabilities: no filesystem, no environment, no
+//! network. Grants are per plugin under `[plugins.permissions.<id>]`, where
+//! `<id>` is the file stem. Each call runs in a fresh instance with fuel,
+//! memory and wall-clock limits. The wasmtime host is behind the
+//! `plugins-wasm` cargo feature.
+
+#[cfg(feature = "plugins-wasm")]
+mod wasm;
+
+use crate::config::{PluginPermissions, PluginWorkspaceAccess, PluginsConfig};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use crate::tools::traits::Tool;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Provider APIs limit function names to 64 characters.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Resource limits applied to every plugin call.
+#[derive(Debug, Clone, Copy)]
+pub struct PluginLimits {
+    pub fuel_limit: u64,
+    pub memory_limit_mb: u64,
+    pub max_output_bytes: usize,
+}
+
+impl From<&PluginsConfig> for PluginLimits {
+    fn from(config: &PluginsConfig) -> Self {
+        Self {
+            fuel_limit: config.fuel_limit,
+            memory_limit_mb: config.memory_limit_mb,
+            max_output_bytes: config.max_output_bytes,
+        }
+    }
+}
+
+/// Tool metadata returned by a plugin's `describe` export.
+#[derive(Debug, Clone)]
+pub struct PluginSpec {
+    pub name: String,
+    pub description: String,
+    pub parameters: String,
+}
+
+/// Resolve the plugin directory (relative paths are workspace-relative).
+pub fn plugin_dir(config: &PluginsConfig, workspace_dir: &Path) -> PathBuf {
+    let dir = Path::new(&config.dir);
+    if dir.is_absolute() {
+        dir.to_path_buf()
+    } else {
+        workspace_dir.join(dir)
+    }
+}
+
+/// List `*.wasm` files in `dir`, sorted for a stable registry order.
+pub fn discover_plugins(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "wasm"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Plugin id used for permission lookups: the file stem.
+pub fn plugin_id(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Plugins that can change the workspace or reach the network are actions;
+/// everything else is allowed in read-only autonomy.
+pub fn operation_for(permissions: &PluginPermissions) -> ToolOperation {
+    if permissions.network || permissions.workspace == PluginWorkspaceAccess::Write {
+        ToolOperation::Act
+    } else {
+        ToolOperation::Read
+    }
+}
+
+/// Check the name a plugin reports for itself.
+fn validate_tool_name(name: &str) -> anyhow::Result<()> {
+    if name.is_empty() || name.len() > MAX_TOOL_NAME_LEN {
+        anyhow::bail!("tool name must be 1-{MAX_TOOL_NAME_LEN} characters");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        anyhow::bail!("tool name '{name}' may only contain [A-Za-z0-9_-]");
+    }
+    Ok(())
+}
+
+/// Load every plugin in the configured directory.
+///
+/// `reserved` holds the names of tools already registered; a plugin may not
+/// shadow them. Broken plugins are logged and skipped.
+pub fn load_plugin_tools(
+    config: &PluginsConfig,
+    security: &Arc<SecurityPolicy>,
+    workspace_dir: &Path,
+    reserved: &[&str],
+) -> Vec<Arc<dyn Tool>> {
+    if !config.enabled {
+        return Vec::new();
+    }
+    let dir = plugin_dir(config, workspace_dir);
+    let paths = discover_plugins(&dir);
+    if paths.is_empty() {
+        tracing::debug!(dir = %dir.display(), "No WASM plugins found");
+        return Vec::new();
+    }
+    load_discovered(config, security, workspace_dir, reserved, &paths)
+}
+
+#[cfg(not(feature = "plugins-wasm"))]
+fn load_discovered(
+    _config: &PluginsConfig,
+    _security: &Arc<SecurityPolicy>,
+    _workspace_dir: &Path,
+    _reserved: &[&str],
+    paths: &[PathBuf],
+) -> Vec<Arc<dyn Tool>> {
+    tracing::warn!(
+        plugins = paths.len(),
+        "WASM plugins found but this build lacks plugin support; \
+         rebuild with `cargo build --features plugins-wasm`"
+    );
+    Vec::new()
+}
+
+#[cfg(feature = "plugins-wasm")]
+fn load_discovered(
+    config: &PluginsConfig,
+    security: &Arc<SecurityPolicy>,
+    workspace_dir: &Path,
+    reserved: &[&str],
+    paths: &[PathBuf],
+) -> Vec<Arc<dyn Tool>> {
+    let mut seen: std::collections::HashSet<String> =
+        reserved.iter().map(|name| (*name).to_string()).collect();
+    let mut tools: Vec<Arc<dyn Tool>> = Vec::new();
+    for path in paths {
+        let id = plugin_id(path);
+        let permissions = config.permissions.get(&id).cloned().unwrap_or_default();
+        match tool::PluginTool::load(path, &id, permissions, config, security, workspace_dir) {
+            Ok(tool) => {
+                if seen.insert(tool.name().to_string()) {
+                    tracing::info!(plugin = %id, tool = %tool.name(), "WASM plugin loaded");
+                    tools.push(Arc::new(tool));
+                } else {
+                    tracing::warn!(
+                        plugin = %id,
+                        tool = %tool.name(),
+                        "Plugin tool name collides with an existing tool; skipping"
+                    );
+                }
+            }
+            Err(e) => tracing::warn!(plugin = %id, "Skipping WASM plugin: {e:#}"),
+        }
+    }
+    tools
+}
+
+#[cfg(feature = "plugins-wasm")]
+mod tool {
+    use super::wasm::LoadedPlugin;
+    use super::{operation_for, validate_tool_name, PluginLimits};
+    use crate::config::{PluginPermissions, PluginsConfig};
+    use crate::security::policy::ToolOperation;
+    use crate::security::SecurityPolicy;
+    use crate::tools::traits::{Tool, ToolResult, ToolRisk};
+    use anyhow::Context;
+    use async_trait::async_trait;
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// A tool backed by a WASM plugin component.
+    pub struct PluginTool {
+        plugin: Arc<LoadedPlugin>,
+        security: Arc<SecurityPolicy>,
+        id: String,
+        name: String,
+        description: String,
+        schema: serde_json::Value,
+        operation: ToolOperation,
+        timeout: Duration,
+    }
+
+    impl PluginTool {
+        pub fn load(
+            path: &Path,
+            id: &str,
+            permissions: PluginPermissions,
+            config: &PluginsConfig,
+            security: &Arc<SecurityPolicy>,
+            workspace_dir: &Path,
+        ) -> anyhow::Result<Self> {
+            let operation = operation_for(&permissions);
+            let plugin =
+                LoadedPlugin::load(path, permissions, PluginLimits::from(config), workspace_dir)?;
+            // `describe` runs guest code synchronously; keep it off the async
+            // worker thread in case it is slow.
+            let spec = std::thread::scope(|scope| {
+                scope
+                    .spawn(|| plugin.describe())
+                    .join()
+                    .map_err(|_| anyhow::anyhow!("plugin panicked during describe"))?
+            })
+            .context("describe failed")?;
+            validate_tool_name(&spec.name)?;
+            let schema: serde_json::Value =
+                serde_json::from_str(&spec.parameters).context("parameters is not valid JSON")?;
+            if !schema.is_object() {
+                anyhow::bail!("parameters must be a JSON Schema object");
+            }
+            Ok(Self {
+                plugin: Arc::new(plugin),
+                security: security.clone(),
+                id: id.to_string(),
+                name: spec.name,
+                description: format!("[plugin '{id}'] {}", spec.description.trim()),
+                schema,
+                operation,
+                timeout: Duration::from_secs(config.timeout_secs.max(1)),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Tool for PluginTool {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn description(&self) -> &str {
+            &self.description
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            self.schema.clone()
+        }
+
+        fn risk(&self) -> ToolRisk {
+            if self.operation == ToolOperation::Read {
+                ToolRisk::Low
+            } else {
+                ToolRisk::Medium
+            }
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            if let Err(error) = self
+                .security
+                .enforce_tool_operation(self.operation, &self.name)
+            {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+
+            // Guest code is synchronous; run it on its own thread so it never
+            // blocks the runtime. Fuel bounds CPU, the timeout bounds the wait.
+            let plugin = self.plugin.clone();
+            let args = args.to_string();
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            std::thread::spawn(move || {
+                let _ = tx.send(plugin.invoke(&args));
+            });
+            let error = match tokio::time::timeout(self.timeout, rx).await {
+                Ok(Ok(Ok(invocation))) => {
+                    if !invocation.stderr.is_empty() {
+                        tracing::debug!(plugin = %self.id, "stderr: {}", invocation.stderr.trim_end());
+                    }
+                    return Ok(ToolResult {
+                        success: invocation.success,
+                        output: invocation.output,
+                        error: invocation.error.or_else(|| {
+                            (!invocation.success).then(|| "plugin reported failure".into())
+                        }),
+                    });
+                }
+                Ok(Ok(Err(e))) => format!("Plugin '{}' trapped: {e:#}", self.id),
+                Ok(Err(_)) => format!("Plugin '{}' crashed", self.id),
+                Err(_) => format!(
+                    "Plugin '{}' timed out after {}s",
+                    self.id,
+                    self.timeout.as_secs()
+                ),
+            };
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn permissions_map_to_operations() {
+        let mut permissions = PluginPermissions::default();
+        assert_eq!(operation_for(&permissions), ToolOperation::Read);
+        permissions.workspace = PluginWorkspaceAccess::Read;
+        permissions.env = vec!["HOME".into()];
+        assert_eq!(operation_for(&permissions), ToolOperation::Read);
+        permissions.workspace = PluginWorkspaceAccess::Write;
+        assert_eq!(operation_for(&permissions), ToolOperation::Act);
+        permissions.workspace = PluginWorkspaceAccess::None;
+        permissions.network = true;
+        assert_eq!(operation_for(&permissions), ToolOperation::Act);
+    }
+
+    #[test]
+    fn discovers_only_wasm_files() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("b.wasm"), b"").unwrap();
+        std::fs::write(tmp.path().join("a.wasm"), b"").unwrap();
+        std::fs::write(tmp.path().join("notes.txt"), b"").unwrap();
+        std::fs::create_dir(tmp.path().join("dir.wasm")).unwrap();
+
+        let found = discover_plugins(tmp.path());
+        let ids: Vec<String> = found.iter().map(|p| plugin_id(p)).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert!(discover_plugins(&tmp.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn plugin_dir_is_workspace_relative() {
+        let config = PluginsConfig::default();
+        assert_eq!(
+            plugin_dir(&config, Path::new("/ws")),
+            PathBuf::from("/ws/plugins")
+        );
+        let config = PluginsConfig {
+            dir: "/opt/zeroclaw/plugins".into(),
+            ..PluginsConfig::default()
+        };
+        assert_eq!(
+            plugin_dir(&config, Path::new("/ws")),
+            PathBuf::from("/opt/zeroclaw/plugins")
+        );
+    }
+
+    #[test]
+    fn validates_tool_names() {
+        assert!(validate_tool_name("word_count").is_ok());
+        assert!(validate_tool_name("").is_err());
+        assert!(validate_tool_name("has space").is_err());
+        assert!(validate_tool_name(&"x".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn disabled_or_empty_dir_loads_nothing() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        assert!(
+            load_plugin_tools(&PluginsConfig::default(), &security, tmp.path(), &[]).is_empty()
+        );
+
+        let config = PluginsConfig {
+            enabled: true,
+            ..PluginsConfig::default()
+        };
+        assert!(load_plugin_tools(&config, &security, tmp.path(), &[]).is_empty());
+    }
+
+    #[cfg(not(feature = "plugins-wasm"))]
+    #[test]
+    fn plugins_are_skipped_without_feature() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("plugins")).unwrap();
+        std::fs::write(tmp.path().join("plugins/echo.wasm"), b"\0asm").unwrap();
+        let config = PluginsConfig {
+            enabled: true,
+            ..PluginsConfig::default()
+        };
+        let security = Arc::new(SecurityPolicy::default());
+        assert!(load_plugin_tools(&config, &security, tmp.path(), &[]).is_empty());
+    }
+}
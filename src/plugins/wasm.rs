@@ -0,0 +1,161 @@
+//! wasmtime host for WASI preview 2 plugin components.
+
+use super::{PluginLimits, PluginSpec};
+use crate::config::{PluginPermissions, PluginWorkspaceAccess};
+use anyhow::{Context, Result};
+use std::path::Path;
+use wasmtime::component::{Component, Linker, ResourceTable};
+use wasmtime::{Config, Engine, Store, StoreLimits, StoreLimitsBuilder};
+use wasmtime_wasi::pipe::MemoryOutputPipe;
+use wasmtime_wasi::{DirPerms, FilePerms, WasiCtx, WasiCtxBuilder, WasiView};
+
+wasmtime::component::bindgen!({
+    path: "wit/zeroclaw-plugin.wit",
+    world: "plugin",
+});
+
+/// Guest path the workspace is mounted at when a plugin may access it.
+const WORKSPACE_GUEST_PATH: &str = "/workspace";
+
+struct HostState {
+    wasi: WasiCtx,
+    table: ResourceTable,
+    limits: StoreLimits,
+}
+
+impl WasiView for HostState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+}
+
+/// A compiled plugin component plus the capabilities it was granted.
+pub struct LoadedPlugin {
+    engine: Engine,
+    component: Component,
+    linker: Linker<HostState>,
+    permissions: PluginPermissions,
+    limits: PluginLimits,
+    workspace_dir: std::path::PathBuf,
+}
+
+/// Output of one plugin invocation.
+pub struct Invocation {
+    pub success: bool,
+    pub output: String,
+    pub error: Option<String>,
+    pub stderr: String,
+}
+
+impl LoadedPlugin {
+    /// Compile the component at `path` and link WASI.
+    pub fn load(
+        path: &Path,
+        permissions: PluginPermissions,
+        limits: PluginLimits,
+        workspace_dir: &Path,
+    ) -> Result<Self> {
+        let mut config = Config::new();
+        config.wasm_component_model(true);
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let component = Component::from_file(&engine, path)
+            .with_context(|| format!("Failed to compile plugin {}", path.display()))?;
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::add_to_linker_sync(&mut linker)?;
+        Ok(Self {
+            engine,
+            component,
+            linker,
+            permissions,
+            limits,
+            workspace_dir: workspace_dir.to_path_buf(),
+        })
+    }
+
+    /// Build a store whose WASI context only carries the granted capabilities.
+    fn store(&self) -> Result<(Store<HostState>, MemoryOutputPipe, MemoryOutputPipe)> {
+        let stdout = MemoryOutputPipe::new(self.limits.max_output_bytes);
+        let stderr = MemoryOutputPipe::new(self.limits.max_output_bytes);
+
+        let mut builder = WasiCtxBuilder::new();
+        builder.stdout(stdout.clone()).stderr(stderr.clone());
+        for name in &self.permissions.env {
+            if let Ok(value) = std::env::var(name) {
+                builder.env(name, value);
+            }
+        }
+        match self.permissions.workspace {
+            PluginWorkspaceAccess::None => {}
+            PluginWorkspaceAccess::Read => {
+                builder.preopened_dir(
+                    &self.workspace_dir,
+                    WORKSPACE_GUEST_PATH,
+                    DirPerms::READ,
+                    FilePerms::READ,
+                )?;
+            }
+            PluginWorkspaceAccess::Write => {
+                builder.preopened_dir(
+                    &self.workspace_dir,
+                    WORKSPACE_GUEST_PATH,
+                    DirPerms::all(),
+                    FilePerms::all(),
+                )?;
+            }
+        }
+        if self.permissions.network {
+            builder.inherit_network().allow_ip_name_lookup(true);
+        }
+
+        let memory_bytes = usize::try_from(self.limits.memory_limit_mb.saturating_mul(1024 * 1024))
+            .unwrap_or(usize::MAX);
+        let state = HostState {
+            wasi: builder.build(),
+            table: ResourceTable::new(),
+            limits: StoreLimitsBuilder::new().memory_size(memory_bytes).build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(self.limits.fuel_limit)?;
+        Ok((store, stdout, stderr))
+    }
+
+    /// Call `describe` in a fresh instance.
+    pub fn describe(&self) -> Result<PluginSpec> {
+        let (mut store, _, _) = self.store()?;
+        let plugin = Plugin::instantiate(&mut store, &self.component, &self.linker)?;
+        let spec = plugin.zeroclaw_plugin_tool().call_describe(&mut store)?;
+        Ok(PluginSpec {
+            name: spec.name,
+            description: spec.description,
+            parameters: spec.parameters,
+        })
+    }
+
+    /// Call `invoke` in a fresh instance, so no state leaks between calls.
+    pub fn invoke(&self, args: &str) -> Result<Invocation> {
+        let (mut store, _stdout, stderr) = self.store()?;
+        let plugin = Plugin::instantiate(&mut store, &self.component, &self.linker)?;
+        let outcome = plugin
+            .zeroclaw_plugin_tool()
+            .call_invoke(&mut store, args)
+            .map_err(|e| {
+                if store.get_fuel().is_ok_and(|fuel| fuel == 0) {
+                    anyhow::anyhow!("plugin exceeded its fuel limit")
+                } else {
+                    e
+                }
+            })?;
+        Ok(Invocation {
+            success: outcome.success,
+            output: outcome.output,
+            error: outcome.error,
+            stderr: String::from_utf8_lossy(&stderr.contents()).into_owned(),
+        })
+    }
+}
@@ -772,7 +772,13 @@ pub async fn api_error(provider: &str, response: reqwest::Response) -> anyhow::E
         .await
         .unwrap_or_else(|_| "<failed to read provider error body>".to_string());
     let sanitized = sanitize_api_error(&body);
-    anyhow::anyhow!("{provider} API error ({status}): {sanitized}")
+    let message = format!("{provider} API error ({status}): {sanitized}");
+    let provider = provider.to_string();
+    match status.as_u16() {
+        401 | 403 => crate::error::Error::ProviderAuth { provider, message }.into(),
+        429 => crate::error::Error::RateLimited { provider, message }.into(),
+        _ => anyhow::anyhow!(message),
+    }
 }
 
 /// Resolve API key for a provider from config and environment variables.
@@ -780,7 +786,8 @@ pub async fn api_error(provider: &str, response: reqwest::Response) -> anyhow::E
 /// Resolution order:
 /// 1. Explicitly provided `api_key` parameter (trimmed, filtered if empty)
 /// 2. Provider-specific environment variable (e.g., `ANTHROPIC_OAUTH_TOKEN`, `OPENROUTER_API_KEY`)
-/// 3. Generic fallback variables (`ZEROCLAW_API_KEY`, `API_KEY`)
+/// 3. Key stored in the OS keyring by `zeroclaw auth login <provider>`
+/// 4. Generic fallback variables (`ZEROCLAW_API_KEY`, `API_KEY`)
 ///
 /// For Anthropic, the provider-specific env var is `ANTHROPIC_OAUTH_TOKEN` (for setup-tokens)
 /// followed by `ANTHROPIC_API_KEY` (for regular API keys).
@@ -860,6 +867,10 @@ fn resolve_provider_credential(name: &str, credential_override: Option<&str>) ->
         }
     }
 
+    if let Some(key) = crate::security::keyring::provider_key(name) {
+        return Some(key);
+    }
+
     if is_minimax_alias(name) {
         if let Some(credential) = resolve_minimax_oauth_refresh_token(name) {
             return Some(credential);
@@ -882,6 +893,12 @@ fn resolve_provider_credential(name: &str, credential_override: Option<&str>) ->
     None
 }
 
+/// Whether a credential for `name` is available from `credential_override`,
+/// the provider's environment variables or the OS keyring.
+pub fn has_credential(name: &str, credential_override: Option<&str>) -> bool {
+    resolve_provider_credential(name, credential_override).is_some()
+}
+
 fn parse_custom_provider_url(
     raw_url: &str,
     provider_label: &str,
@@ -1239,11 +1256,14 @@ fn create_provider_with_url_and_options(
             )))
         }
 
-        _ => anyhow::bail!(
-            "Unknown provider: {name}. Check README for supported providers or run `zeroclaw onboard --interactive` to reconfigure.\n\
-             Tip: Use \"custom:https://your-api.com\" for OpenAI-compatible endpoints.\n\
-             Tip: Use \"anthropic-custom:https://your-api.com\" for Anthropic-compatible endpoints."
-        ),
+        _ => Err(crate::error::Error::Config {
+            message: format!(
+                "Unknown provider: {name}. Check README for supported providers or run `zeroclaw onboard --interactive` to reconfigure.\n\
+                 Tip: Use \"custom:https://your-api.com\" for OpenAI-compatible endpoints.\n\
+                 Tip: Use \"anthropic-custom:https://your-api.com\" for Anthropic-compatible endpoints."
+            ),
+        }
+        .into()),
     }
 }
 
@@ -1339,7 +1359,8 @@ pub fn create_resilient_provider_with_options(
         reliability.provider_backoff_ms,
     )
     .with_api_keys(reliability.api_keys.clone())
-    .with_model_fallbacks(reliability.model_fallbacks.clone());
+    .with_model_fallbacks(reliability.model_fallbacks.clone())
+    .with_circuit_breakers();
 
     Ok(Box::new(reliable))
 }
@@ -2454,6 +2475,13 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_backoff_max_ms: 10_000,
+            backoff_jitter: true,
+            retry_budget_per_minute: 30,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 30,
+            tool_retries: 2,
+            tool_backoff_ms: 500,
         };
 
         let provider = create_resilient_provider(
@@ -2493,6 +2521,13 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_backoff_max_ms: 10_000,
+            backoff_jitter: true,
+            retry_budget_per_minute: 30,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 30,
+            tool_retries: 2,
+            tool_backoff_ms: 500,
         };
 
         // Primary uses a ZAI key; fallbacks (lmstudio, ollama) should NOT
@@ -2515,6 +2550,13 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_backoff_max_ms: 10_000,
+            backoff_jitter: true,
+            retry_budget_per_minute: 30,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 30,
+            tool_retries: 2,
+            tool_backoff_ms: 500,
         };
 
         let provider =
@@ -2541,6 +2583,13 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_backoff_max_ms: 10_000,
+            backoff_jitter: true,
+            retry_budget_per_minute: 30,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 30,
+            tool_retries: 2,
+            tool_backoff_ms: 500,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2573,6 +2622,13 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_backoff_max_ms: 10_000,
+            backoff_jitter: true,
+            retry_budget_per_minute: 30,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 30,
+            tool_retries: 2,
+            tool_backoff_ms: 500,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2866,6 +2922,13 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_backoff_max_ms: 10_000,
+            backoff_jitter: true,
+            retry_budget_per_minute: 30,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 30,
+            tool_retries: 2,
+            tool_backoff_ms: 500,
         };
 
         // openai-codex resolves its own OAuth credential; it should not
@@ -2895,6 +2958,13 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_backoff_max_ms: 10_000,
+            backoff_jitter: true,
+            retry_budget_per_minute: 30,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 30,
+            tool_retries: 2,
+            tool_backoff_ms: 500,
         };
 
         let provider = create_resilient_provider("ollama", None, None, &reliability);
@@ -2,10 +2,12 @@ use super::traits::{
     ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
 };
 use super::Provider;
+use crate::resilience::Endpoint;
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 // ── Error Classification ─────────────────────────────────────────────────
@@ -231,6 +233,8 @@ pub struct ReliableProvider {
     key_index: AtomicUsize,
     /// Per-model fallback chains: model_name → [fallback_model_1, fallback_model_2, ...]
     model_fallbacks: HashMap<String, Vec<String>>,
+    /// Shared circuit breakers, one per provider (`None` = no breaking).
+    circuits: Option<Vec<Arc<Endpoint>>>,
 }
 
 impl ReliableProvider {
@@ -246,9 +250,22 @@ impl ReliableProvider {
             api_keys: Vec::new(),
             key_index: AtomicUsize::new(0),
             model_fallbacks: HashMap::new(),
+            circuits: None,
         }
     }
 
+    /// Guard each provider with the shared `provider:<name>` circuit breaker
+    /// and retry budget from [`crate::resilience`].
+    pub fn with_circuit_breakers(mut self) -> Self {
+        self.circuits = Some(
+            self.providers
+                .iter()
+                .map(|(name, _)| crate::resilience::endpoint(&format!("provider:{name}")))
+                .collect(),
+        );
+        self
+    }
+
     /// Set additional API keys for round-robin rotation on rate-limit errors.
     pub fn with_api_keys(mut self, keys: Vec<String>) -> Self {
         self.api_keys = keys;
@@ -279,6 +296,76 @@ impl ReliableProvider {
         Some(&self.api_keys[idx])
     }
 
+    fn circuit(&self, index: usize) -> Option<&Endpoint> {
+        self.circuits
+            .as_ref()
+            .map(|circuits| circuits[index].as_ref())
+    }
+
+    /// Whether provider `index` may be called; records a failure entry when
+    /// its circuit is open.
+    fn circuit_allows(&self, index: usize, model: &str, failures: &mut Vec<String>) -> bool {
+        let Some(circuit) = self.circuit(index) else {
+            return true;
+        };
+        if circuit.allow() {
+            return true;
+        }
+        push_failure(
+            failures,
+            &self.providers[index].0,
+            model,
+            0,
+            self.max_retries + 1,
+            "circuit_open",
+            &format!(
+                "marked unhealthy; next probe in {}s",
+                circuit.retry_after_secs()
+            ),
+        );
+        false
+    }
+
+    /// Whether another retry of provider `index` is allowed by its breaker
+    /// and retry budget.
+    fn may_retry(&self, index: usize) -> bool {
+        self.circuit(index)
+            .map_or(true, |circuit| circuit.allow() && circuit.try_retry())
+    }
+
+    fn record_outcome(&self, index: usize, failure: Option<&str>) {
+        if let Some(circuit) = self.circuit(index) {
+            match failure {
+                Some(error) => circuit.record_failure(error),
+                None => circuit.record_success(),
+            }
+        }
+    }
+
+    /// Record a failed attempt against provider `index`'s breaker. A
+    /// non-retryable error other than a rate limit means the provider
+    /// answered, so it counts as healthy even though the request itself was
+    /// rejected.
+    fn record_attempt_error(
+        &self,
+        index: usize,
+        non_retryable: bool,
+        rate_limited: bool,
+        detail: &str,
+    ) {
+        self.record_outcome(index, (!non_retryable || rate_limited).then_some(detail));
+    }
+
+    /// Delay before the next retry: Retry-After when the provider sent one,
+    /// otherwise the exponential step with jitter.
+    fn retry_delay(&self, base: u64, err: &anyhow::Error) -> u64 {
+        if parse_retry_after_ms(err).is_some() {
+            self.compute_backoff(base, err)
+        } else {
+            crate::resilience::jittered(base)
+        }
+    }
+
     /// Compute backoff duration, respecting Retry-After if present.
     fn compute_backoff(&self, base: u64, err: &anyhow::Error) -> u64 {
         if let Some(retry_after) = parse_retry_after_ms(err) {
@@ -317,7 +404,10 @@ impl Provider for ReliableProvider {
         // immediately. On non-retryable error, break to next provider. On
         // retryable error, sleep with exponential backoff and retry.
         for current_model in &models {
-            for (provider_name, provider) in &self.providers {
+            for (index, (provider_name, provider)) in self.providers.iter().enumerate() {
+                if !self.circuit_allows(index, current_model, &mut failures) {
+                    continue;
+                }
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
@@ -326,6 +416,7 @@ impl Provider for ReliableProvider {
                         .await
                     {
                         Ok(resp) => {
+                            self.record_outcome(index, None);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -369,6 +460,13 @@ impl Provider for ReliableProvider {
                                 }
                             }
 
+                            self.record_attempt_error(
+                                index,
+                                non_retryable,
+                                rate_limited,
+                                &error_detail,
+                            );
+
                             if non_retryable {
                                 tracing::warn!(
                                     provider = provider_name,
@@ -388,7 +486,14 @@ impl Provider for ReliableProvider {
                             }
 
                             if attempt < self.max_retries {
-                                let wait = self.compute_backoff(backoff_ms, &e);
+                                if !self.may_retry(index) {
+                                    tracing::warn!(
+                                        provider = provider_name,
+                                        "Retry skipped: circuit open or retry budget spent"
+                                    );
+                                    break;
+                                }
+                                let wait = self.retry_delay(backoff_ms, &e);
                                 tracing::warn!(
                                     provider = provider_name,
                                     model = *current_model,
@@ -399,7 +504,8 @@ impl Provider for ReliableProvider {
                                     "Provider call failed, retrying"
                                 );
                                 tokio::time::sleep(Duration::from_millis(wait)).await;
-                                backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                                backoff_ms = (backoff_ms.saturating_mul(2))
+                                    .min(crate::resilience::policy().backoff_max_ms);
                             }
                         }
                     }
@@ -437,7 +543,10 @@ impl Provider for ReliableProvider {
         let mut failures = Vec::new();
 
         for current_model in &models {
-            for (provider_name, provider) in &self.providers {
+            for (index, (provider_name, provider)) in self.providers.iter().enumerate() {
+                if !self.circuit_allows(index, current_model, &mut failures) {
+                    continue;
+                }
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
@@ -446,6 +555,7 @@ impl Provider for ReliableProvider {
                         .await
                     {
                         Ok(resp) => {
+                            self.record_outcome(index, None);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -487,6 +597,13 @@ impl Provider for ReliableProvider {
                                 }
                             }
 
+                            self.record_attempt_error(
+                                index,
+                                non_retryable,
+                                rate_limited,
+                                &error_detail,
+                            );
+
                             if non_retryable {
                                 tracing::warn!(
                                     provider = provider_name,
@@ -506,7 +623,14 @@ impl Provider for ReliableProvider {
                             }
 
                             if attempt < self.max_retries {
-                                let wait = self.compute_backoff(backoff_ms, &e);
+                                if !self.may_retry(index) {
+                                    tracing::warn!(
+                                        provider = provider_name,
+                                        "Retry skipped: circuit open or retry budget spent"
+                                    );
+                                    break;
+                                }
+                                let wait = self.retry_delay(backoff_ms, &e);
                                 tracing::warn!(
                                     provider = provider_name,
                                     model = *current_model,
@@ -517,7 +641,8 @@ impl Provider for ReliableProvider {
                                     "Provider call failed, retrying"
                                 );
                                 tokio::time::sleep(Duration::from_millis(wait)).await;
-                                backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                                backoff_ms = (backoff_ms.saturating_mul(2))
+                                    .min(crate::resilience::policy().backoff_max_ms);
                             }
                         }
                     }
@@ -561,7 +686,10 @@ impl Provider for ReliableProvider {
         let mut failures = Vec::new();
 
         for current_model in &models {
-            for (provider_name, provider) in &self.providers {
+            for (index, (provider_name, provider)) in self.providers.iter().enumerate() {
+                if !self.circuit_allows(index, current_model, &mut failures) {
+                    continue;
+                }
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
@@ -570,6 +698,7 @@ impl Provider for ReliableProvider {
                         .await
                     {
                         Ok(resp) => {
+                            self.record_outcome(index, None);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -611,6 +740,13 @@ impl Provider for ReliableProvider {
                                 }
                             }
 
+                            self.record_attempt_error(
+                                index,
+                                non_retryable,
+                                rate_limited,
+                                &error_detail,
+                            );
+
                             if non_retryable {
                                 tracing::warn!(
                                     provider = provider_name,
@@ -630,7 +766,14 @@ impl Provider for ReliableProvider {
                             }
 
                             if attempt < self.max_retries {
-                                let wait = self.compute_backoff(backoff_ms, &e);
+                                if !self.may_retry(index) {
+                                    tracing::warn!(
+                                        provider = provider_name,
+                                        "Retry skipped: circuit open or retry budget spent"
+                                    );
+                                    break;
+                                }
+                                let wait = self.retry_delay(backoff_ms, &e);
                                 tracing::warn!(
                                     provider = provider_name,
                                     model = *current_model,
@@ -641,7 +784,8 @@ impl Provider for ReliableProvider {
                                     "Provider call failed, retrying"
                                 );
                                 tokio::time::sleep(Duration::from_millis(wait)).await;
-                                backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                                backoff_ms = (backoff_ms.saturating_mul(2))
+                                    .min(crate::resilience::policy().backoff_max_ms);
                             }
                         }
                     }
@@ -671,7 +815,10 @@ impl Provider for ReliableProvider {
         let mut failures = Vec::new();
 
         for current_model in &models {
-            for (provider_name, provider) in &self.providers {
+            for (index, (provider_name, provider)) in self.providers.iter().enumerate() {
+                if !self.circuit_allows(index, current_model, &mut failures) {
+                    continue;
+                }
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
@@ -681,6 +828,7 @@ impl Provider for ReliableProvider {
                     };
                     match provider.chat(req, current_model, temperature).await {
                         Ok(resp) => {
+                            self.record_outcome(index, None);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -722,6 +870,13 @@ impl Provider for ReliableProvider {
                                 }
                             }
 
+                            self.record_attempt_error(
+                                index,
+                                non_retryable,
+                                rate_limited,
+                                &error_detail,
+                            );
+
                             if non_retryable {
                                 tracing::warn!(
                                     provider = provider_name,
@@ -741,7 +896,14 @@ impl Provider for ReliableProvider {
                             }
 
                             if attempt < self.max_retries {
-                                let wait = self.compute_backoff(backoff_ms, &e);
+                                if !self.may_retry(index) {
+                                    tracing::warn!(
+                                        provider = provider_name,
+                                        "Retry skipped: circuit open or retry budget spent"
+                                    );
+                                    break;
+                                }
+                                let wait = self.retry_delay(backoff_ms, &e);
                                 tracing::warn!(
                                     provider = provider_name,
                                     model = *current_model,
@@ -752,7 +914,8 @@ impl Provider for ReliableProvider {
                                     "Provider call failed, retrying"
                                 );
                                 tokio::time::sleep(Duration::from_millis(wait)).await;
-                                backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                                backoff_ms = (backoff_ms.saturating_mul(2))
+                                    .min(crate::resilience::policy().backoff_max_ms);
                             }
                         }
                     }
@@ -794,10 +957,13 @@ impl Provider for ReliableProvider {
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
         // Try each provider/model combination for streaming
         // For streaming, we use the first provider that supports it and has streaming enabled
-        for (provider_name, provider) in &self.providers {
+        for (index, (provider_name, provider)) in self.providers.iter().enumerate() {
             if !provider.supports_streaming() || !options.enabled {
                 continue;
             }
+            if self.circuit(index).is_some_and(Endpoint::is_open) {
+                continue;
+            }
 
             // Clone provider data for the stream
             let provider_clone = provider_name.clone();
@@ -946,6 +1112,42 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn circuit_breaker_stops_retries_and_skips_unhealthy_provider() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let name = format!("breaker-{}", uuid::Uuid::new_v4());
+        let provider = ReliableProvider::new(
+            vec![(
+                name.clone(),
+                Box::new(MockProvider {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: usize::MAX,
+                    response: "never",
+                    error: "503 Service Unavailable",
+                }),
+            )],
+            10,
+            1,
+        )
+        .with_circuit_breakers();
+        let threshold = crate::resilience::policy().breaker_threshold as usize;
+
+        assert!(provider.simple_chat("hello", "test", 0.0).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), threshold);
+
+        let err = provider
+            .simple_chat("hello", "test", 0.0)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("circuit_open"), "{err}");
+        assert_eq!(calls.load(Ordering::SeqCst), threshold);
+        assert_eq!(
+            crate::resilience::snapshot()[&format!("provider:{name}")].state,
+            crate::resilience::CircuitState::Open
+        );
+    }
+
     #[tokio::test]
     async fn retries_then_recovers() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -0,0 +1,450 @@
+//! Shared resilience layer for provider and tool network calls.
+//!
+//! Each remote endpoint (`provider:<name>`, `tool:<name>:<host>`) gets a
+//! circuit breaker and a retry budget from one process-wide registry,
+//! configured from `[reliability]`. Retries wait with jittered exponential
+//! backoff. The daemon writes [`snapshot_json`] into `daemon_state.json`,
+//! which is where `zeroclaw status` reads breaker state from.
+
+use crate::config::ReliabilityConfig;
+use anyhow::{bail, Result};
+use chrono::Utc;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+const RETRY_BUDGET_WINDOW: Duration = Duration::from_secs(60);
+
+/// Settings from `[reliability]` shared by every endpoint.
+#[derive(Debug, Clone)]
+pub struct ResiliencePolicy {
+    pub backoff_max_ms: u64,
+    pub jitter: bool,
+    /// Retries allowed per endpoint per minute (0 = unlimited).
+    pub retry_budget_per_minute: u32,
+    /// Consecutive failures that open a breaker (0 = never).
+    pub breaker_threshold: u32,
+    pub breaker_cooldown: Duration,
+    pub tool_retries: u32,
+    pub tool_backoff_ms: u64,
+}
+
+impl Default for ResiliencePolicy {
+    fn default() -> Self {
+        Self::from_config(&ReliabilityConfig::default())
+    }
+}
+
+impl ResiliencePolicy {
+    pub fn from_config(config: &ReliabilityConfig) -> Self {
+        Self {
+            backoff_max_ms: config.provider_backoff_max_ms.max(50),
+            jitter: config.backoff_jitter,
+            retry_budget_per_minute: config.retry_budget_per_minute,
+            breaker_threshold: config.circuit_breaker_threshold,
+            breaker_cooldown: Duration::from_secs(config.circuit_breaker_cooldown_secs.max(1)),
+            tool_retries: config.tool_retries,
+            tool_backoff_ms: config.tool_backoff_ms.max(50),
+        }
+    }
+}
+
+/// Breaker state of one endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls flow normally.
+    Closed,
+    /// Calls are refused until the cooldown ends.
+    Open,
+    /// One probe call is allowed; its outcome closes or reopens the breaker.
+    HalfOpen,
+}
+
+/// Serializable view of one endpoint, for status output.
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub successes: u64,
+    pub failures: u64,
+    pub retries: u64,
+    pub opened_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug)]
+struct Circuit {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened: Option<Instant>,
+    opened_at: Option<String>,
+    probe_in_flight: bool,
+    successes: u64,
+    failures: u64,
+    retries: u64,
+    recent_retries: VecDeque<Instant>,
+    last_error: Option<String>,
+}
+
+/// Breaker and retry budget for one remote endpoint.
+#[derive(Debug)]
+pub struct Endpoint {
+    name: String,
+    circuit: Mutex<Circuit>,
+}
+
+impl Endpoint {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            circuit: Mutex::new(Circuit {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened: None,
+                opened_at: None,
+                probe_in_flight: false,
+                successes: 0,
+                failures: 0,
+                retries: 0,
+                recent_retries: VecDeque::new(),
+                last_error: None,
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether a call may go out now. An open breaker whose cooldown has
+    /// passed lets exactly one probe through.
+    pub fn allow(&self) -> bool {
+        let cooldown = policy().breaker_cooldown;
+        let mut circuit = self.circuit.lock();
+        match circuit.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => {
+                if circuit.opened.is_some_and(|at| at.elapsed() >= cooldown) {
+                    circuit.state = CircuitState::HalfOpen;
+                    circuit.probe_in_flight = true;
+                    true
+                } else {
+                    false
+                }
+            }
+            CircuitState::HalfOpen => {
+                if circuit.probe_in_flight {
+                    false
+                } else {
+                    circuit.probe_in_flight = true;
+                    true
+                }
+            }
+        }
+    }
+
+    /// Whether the breaker is open and still cooling down. Unlike
+    /// [`Self::allow`], this never claims the half-open probe.
+    pub fn is_open(&self) -> bool {
+        let cooldown = policy().breaker_cooldown;
+        let circuit = self.circuit.lock();
+        circuit.state == CircuitState::Open
+            && circuit.opened.is_some_and(|at| at.elapsed() < cooldown)
+    }
+
+    /// Seconds until an open breaker lets a probe through.
+    pub fn retry_after_secs(&self) -> u64 {
+        let cooldown = policy().breaker_cooldown;
+        let circuit = self.circuit.lock();
+        circuit
+            .opened
+            .map_or(0, |at| cooldown.saturating_sub(at.elapsed()).as_secs())
+    }
+
+    pub fn record_success(&self) {
+        let mut circuit = self.circuit.lock();
+        if circuit.state != CircuitState::Closed {
+            tracing::info!(endpoint = %self.name, "Circuit closed");
+        }
+        circuit.state = CircuitState::Closed;
+        circuit.consecutive_failures = 0;
+        circuit.opened = None;
+        circuit.opened_at = None;
+        circuit.probe_in_flight = false;
+        circuit.successes += 1;
+    }
+
+    pub fn record_failure(&self, error: &str) {
+        let threshold = policy().breaker_threshold;
+        let mut circuit = self.circuit.lock();
+        circuit.failures += 1;
+        circuit.consecutive_failures = circuit.consecutive_failures.saturating_add(1);
+        circuit.last_error = Some(crate::util::truncate_with_ellipsis(error, 200));
+        let trips = circuit.state == CircuitState::HalfOpen
+            || (threshold > 0 && circuit.consecutive_failures >= threshold);
+        if trips && circuit.state != CircuitState::Open {
+            tracing::warn!(
+                endpoint = %self.name,
+                failures = circuit.consecutive_failures,
+                "Circuit opened"
+            );
+            circuit.state = CircuitState::Open;
+            circuit.opened = Some(Instant::now());
+            circuit.opened_at = Some(Utc::now().to_rfc3339());
+        }
+        circuit.probe_in_flight = false;
+    }
+
+    /// Spend one retry from the per-minute budget; `false` when it is used up.
+    pub fn try_retry(&self) -> bool {
+        let budget = policy().retry_budget_per_minute;
+        let mut circuit = self.circuit.lock();
+        let now = Instant::now();
+        while circuit
+            .recent_retries
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= RETRY_BUDGET_WINDOW)
+        {
+            circuit.recent_retries.pop_front();
+        }
+        if budget > 0 && circuit.recent_retries.len() >= budget as usize {
+            return false;
+        }
+        circuit.recent_retries.push_back(now);
+        circuit.retries += 1;
+        true
+    }
+
+    pub fn status(&self) -> EndpointStatus {
+        let circuit = self.circuit.lock();
+        EndpointStatus {
+            state: circuit.state,
+            consecutive_failures: circuit.consecutive_failures,
+            successes: circuit.successes,
+            failures: circuit.failures,
+            retries: circuit.retries,
+            opened_at: circuit.opened_at.clone(),
+            last_error: circuit.last_error.clone(),
+        }
+    }
+}
+
+static POLICY: OnceLock<RwLock<ResiliencePolicy>> = OnceLock::new();
+static ENDPOINTS: OnceLock<Mutex<HashMap<String, Arc<Endpoint>>>> = OnceLock::new();
+
+fn policy_lock() -> &'static RwLock<ResiliencePolicy> {
+    POLICY.get_or_init(|| RwLock::new(ResiliencePolicy::default()))
+}
+
+/// Apply `[reliability]` to every endpoint, including existing ones.
+pub fn configure(config: &ReliabilityConfig) {
+    *policy_lock().write() = ResiliencePolicy::from_config(config);
+}
+
+pub fn policy() -> ResiliencePolicy {
+    policy_lock().read().clone()
+}
+
+/// The shared endpoint registered under `name`.
+pub fn endpoint(name: &str) -> Arc<Endpoint> {
+    let mut endpoints = ENDPOINTS.get_or_init(|| Mutex::new(HashMap::new())).lock();
+    Arc::clone(
+        endpoints
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(Endpoint::new(name))),
+    )
+}
+
+pub fn snapshot() -> BTreeMap<String, EndpointStatus> {
+    ENDPOINTS
+        .get()
+        .map(|endpoints| {
+            endpoints
+                .lock()
+                .iter()
+                .map(|(name, endpoint)| (name.clone(), endpoint.status()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn snapshot_json() -> serde_json::Value {
+    serde_json::to_value(snapshot()).unwrap_or_else(|_| serde_json::json!({}))
+}
+
+/// One line per endpoint from a daemon state file's `circuits` object,
+/// unhealthy endpoints first.
+pub fn describe_circuits(circuits: &serde_json::Value) -> Vec<String> {
+    let Some(map) = circuits.as_object() else {
+        return Vec::new();
+    };
+    let mut entries: Vec<(&String, &serde_json::Value)> = map.iter().collect();
+    entries.sort_by(|(a_name, a), (b_name, b)| {
+        (a["state"] == "closed", a_name).cmp(&(b["state"] == "closed", b_name))
+    });
+    entries
+        .into_iter()
+        .map(|(name, status)| {
+            let counts = format!(
+                "{} ok, {} failed, {} retries",
+                status["successes"].as_u64().unwrap_or(0),
+                status["failures"].as_u64().unwrap_or(0),
+                status["retries"].as_u64().unwrap_or(0)
+            );
+            let state = match status["state"].as_str().unwrap_or("closed") {
+                "open" => format!(
+                    "🔴 unhealthy since {}",
+                    status["opened_at"].as_str().unwrap_or("?")
+                ),
+                "half_open" => "🟡 probing".to_string(),
+                _ => "🟢 healthy".to_string(),
+            };
+            match status["last_error"].as_str() {
+                Some(error) if status["state"] != "closed" => {
+                    format!("  {name}: {state} ({counts}; last error: {error})")
+                }
+                _ => format!("  {name}: {state} ({counts})"),
+            }
+        })
+        .collect()
+}
+
+/// `delay_ms` with "equal jitter": a random value in `[delay/2, delay]`.
+pub fn jittered(delay_ms: u64) -> u64 {
+    if !policy().jitter || delay_ms < 2 {
+        return delay_ms;
+    }
+    let half = delay_ms / 2;
+    half + rand::random::<u64>() % (delay_ms - half + 1)
+}
+
+/// Backoff before retry number `attempt` (0-based), capped and jittered.
+pub fn backoff_delay(base_ms: u64, attempt: u32) -> Duration {
+    let policy = policy();
+    let exp = base_ms
+        .saturating_mul(1u64 << attempt.min(20))
+        .min(policy.backoff_max_ms);
+    Duration::from_millis(jittered(exp))
+}
+
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Send a tool's HTTP request through the endpoint's breaker. With `retry`
+/// (idempotent requests only) connection errors, timeouts, 429 and 5xx
+/// responses are retried up to `[reliability].tool_retries` times.
+pub async fn send_http(
+    endpoint_name: &str,
+    retry: bool,
+    build: impl Fn() -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response> {
+    let endpoint = endpoint(endpoint_name);
+    if !endpoint.allow() {
+        bail!(
+            "{endpoint_name} is temporarily unavailable after repeated failures (circuit open, retry in {}s)",
+            endpoint.retry_after_secs()
+        );
+    }
+    let policy = policy();
+    let max_retries = if retry { policy.tool_retries } else { 0 };
+    let mut attempt = 0;
+    loop {
+        let outcome = build().send().await;
+        let retryable = match &outcome {
+            Ok(response) => is_retryable_status(response.status()),
+            Err(e) => e.is_connect() || e.is_timeout(),
+        };
+        let failure = match &outcome {
+            Ok(response) if retryable => Some(format!("HTTP {}", response.status())),
+            Ok(_) => None,
+            Err(e) => Some(e.to_string()),
+        };
+        match failure {
+            None => {
+                endpoint.record_success();
+                return Ok(outcome?);
+            }
+            Some(error) => endpoint.record_failure(&error),
+        }
+        if !retryable || attempt >= max_retries || !endpoint.try_retry() || !endpoint.allow() {
+            return outcome.map_err(Into::into);
+        }
+        tokio::time::sleep(backoff_delay(policy.tool_backoff_ms, attempt)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique(prefix: &str) -> Arc<Endpoint> {
+        endpoint(&format!("{prefix}-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn breaker_opens_after_threshold_and_half_opens_after_cooldown() {
+        let threshold = policy().breaker_threshold;
+        let endpoint = unique("breaker");
+        for _ in 0..threshold {
+            assert!(endpoint.allow());
+            endpoint.record_failure("boom");
+        }
+        assert_eq!(endpoint.status().state, CircuitState::Open);
+        assert!(!endpoint.allow());
+
+        // Pretend the cooldown has passed.
+        endpoint.circuit.lock().opened = Some(Instant::now() - policy().breaker_cooldown);
+        assert!(endpoint.allow());
+        assert_eq!(endpoint.status().state, CircuitState::HalfOpen);
+        assert!(!endpoint.allow(), "only one probe while half-open");
+        endpoint.record_success();
+        assert_eq!(endpoint.status().state, CircuitState::Closed);
+        assert!(snapshot().contains_key(endpoint.name()));
+    }
+
+    #[test]
+    fn retry_budget_limits_retries_per_window() {
+        let budget = policy().retry_budget_per_minute;
+        let endpoint = unique("budget");
+        for _ in 0..budget {
+            assert!(endpoint.try_retry());
+        }
+        assert!(!endpoint.try_retry());
+        assert_eq!(endpoint.status().retries, u64::from(budget));
+    }
+
+    #[test]
+    fn describe_circuits_lists_unhealthy_endpoints_first() {
+        let circuits = serde_json::json!({
+            "provider:openai": {"state": "closed", "successes": 3, "failures": 0, "retries": 0},
+            "tool:web_fetch:example.com": {
+                "state": "open", "successes": 0, "failures": 5, "retries": 4,
+                "opened_at": "2026-01-01T00:00:00Z", "last_error": "HTTP 503"
+            },
+        });
+        let lines = describe_circuits(&circuits);
+        assert_eq!(
+            lines[0],
+            "  tool:web_fetch:example.com: 🔴 unhealthy since 2026-01-01T00:00:00Z \
+             (0 ok, 5 failed, 4 retries; last error: HTTP 503)"
+        );
+        assert_eq!(
+            lines[1],
+            "  provider:openai: 🟢 healthy (3 ok, 0 failed, 0 retries)"
+        );
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        let max = Duration::from_millis(policy().backoff_max_ms);
+        let first = backoff_delay(100, 0);
+        assert!((Duration::from_millis(50)..=Duration::from_millis(100)).contains(&first));
+        let capped = backoff_delay(100, 30);
+        assert!(capped <= max && capped >= max / 2);
+    }
+}
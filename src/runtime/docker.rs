@@ -27,7 +27,10 @@ impl DockerRuntime {
         }
 
         if resolved == Path::new("/") {
-            anyhow::bail!("Refusing to mount filesystem root (/) into docker runtime");
+            return Err(crate::error::Error::SandboxDenied {
+                message: "Refusing to mount filesystem root (/) into docker runtime".into(),
+            }
+            .into());
         }
 
         if self.config.allowed_workspace_roots.is_empty() {
@@ -42,10 +45,13 @@ impl DockerRuntime {
         });
 
         if !allowed {
-            anyhow::bail!(
-                "Workspace path {} is not in runtime.docker.allowed_workspace_roots",
-                resolved.display()
-            );
+            return Err(crate::error::Error::SandboxDenied {
+                message: format!(
+                    "Workspace path {} is not in runtime.docker.allowed_workspace_roots",
+                    resolved.display()
+                ),
+            }
+            .into());
         }
 
         Ok(resolved)
@@ -88,7 +94,7 @@ impl RuntimeAdapter for DockerRuntime {
         command: &str,
         workspace_dir: &Path,
     ) -> anyhow::Result<tokio::process::Command> {
-        let mut process = tokio::process::Command::new("docker");
+        let mut process = tokio::process::Command::new(self.config.binary.trim());
         process
             .arg("run")
             .arg("--rm")
@@ -158,6 +164,7 @@ mod tests {
     #[test]
     fn docker_build_shell_command_includes_runtime_flags() {
         let cfg = DockerRuntimeConfig {
+            binary: "docker".into(),
             image: "alpine:3.20".into(),
             network: "none".into(),
             memory_limit_mb: Some(128),
@@ -7,6 +7,7 @@ pub use native::NativeRuntime;
 pub use traits::RuntimeAdapter;
 
 use crate::config::RuntimeConfig;
+use std::sync::Arc;
 
 /// Factory: create the right runtime from config
 pub fn create_runtime(config: &RuntimeConfig) -> anyhow::Result<Box<dyn RuntimeAdapter>> {
@@ -16,11 +17,39 @@ pub fn create_runtime(config: &RuntimeConfig) -> anyhow::Result<Box<dyn RuntimeA
         "cloudflare" => anyhow::bail!(
             "runtime.kind='cloudflare' is not implemented yet. Use runtime.kind='native' for now."
         ),
-        other if other.trim().is_empty() => {
-            anyhow::bail!("runtime.kind cannot be empty. Supported values: native, docker")
+        other if other.trim().is_empty() => Err(crate::error::Error::Config {
+            message: "runtime.kind cannot be empty. Supported values: native, docker".into(),
         }
-        other => anyhow::bail!("Unknown runtime kind '{other}'. Supported values: native, docker"),
+        .into()),
+        other => Err(crate::error::Error::Config {
+            message: format!("Unknown runtime kind '{other}'. Supported values: native, docker"),
+        }
+        .into()),
+    }
+}
+
+/// Runtime for `tool`: its `[runtime.tools.<tool>]` backend, else `default`.
+pub fn runtime_for_tool(
+    config: &RuntimeConfig,
+    tool: &str,
+    default: &Arc<dyn RuntimeAdapter>,
+) -> anyhow::Result<Arc<dyn RuntimeAdapter>> {
+    let Some(tool_config) = config.tools.get(tool) else {
+        return Ok(Arc::clone(default));
+    };
+    let mut docker = config.docker.clone();
+    if let Some(image) = &tool_config.image {
+        docker.image.clone_from(image);
     }
+    if let Some(network) = &tool_config.network {
+        docker.network.clone_from(network);
+    }
+    let runtime = create_runtime(&RuntimeConfig {
+        kind: tool_config.kind.clone(),
+        docker,
+        ..config.clone()
+    })?;
+    Ok(Arc::from(runtime))
 }
 
 #[cfg(test)]
@@ -49,6 +78,29 @@ mod tests {
         assert!(rt.has_shell_access());
     }
 
+    #[test]
+    fn tools_can_override_the_runtime() {
+        let mut cfg = RuntimeConfig::default();
+        cfg.tools.insert(
+            "run_code".into(),
+            crate::config::ToolRuntimeConfig {
+                kind: "docker".into(),
+                image: Some("python:3.12-slim".into()),
+                network: None,
+            },
+        );
+        let default: Arc<dyn RuntimeAdapter> = Arc::new(NativeRuntime::new());
+        let shell = runtime_for_tool(&cfg, "shell", &default).unwrap();
+        assert_eq!(shell.name(), "native");
+        let run_code = runtime_for_tool(&cfg, "run_code", &default).unwrap();
+        assert_eq!(run_code.name(), "docker");
+        let cmd = run_code
+            .build_shell_command("python3 main.py", &std::env::temp_dir())
+            .unwrap();
+        let debug = format!("{cmd:?}");
+        assert!(debug.contains("python:3.12-slim") && debug.contains("none"));
+    }
+
     #[test]
     fn factory_cloudflare_errors() {
         let cfg = RuntimeConfig {
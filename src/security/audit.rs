@@ -1,13 +1,21 @@
 //! Audit logging for security events
+//!
+//! Events are appended one JSON object per line. Each carries the hash of
+//! the previous line and its own hash, so editing or removing an entry
+//! breaks the chain; `zeroclaw audit verify` checks it.
 
-use crate::config::AuditConfig;
-use anyhow::Result;
+use crate::config::{AuditConfig, Config};
+use crate::AuditCommands;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
-use std::fs::OpenOptions;
-use std::io::Write;
-use std::path::PathBuf;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 use uuid::Uuid;
 
 /// Audit event types
@@ -21,6 +29,8 @@ pub enum AuditEventType {
     AuthFailure,
     PolicyViolation,
     SecurityEvent,
+    ToolApproval,
+    ToolExecution,
 }
 
 /// Actor information (who performed the action)
@@ -57,6 +67,46 @@ pub struct SecurityContext {
     pub sandbox_backend: Option<String>,
 }
 
+/// How a tool call got (or failed to get) permission to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    /// Allowed without asking (policy, allowlist or no approval manager).
+    NotRequired,
+    Approved,
+    /// Approved and added to the session allowlist.
+    AlwaysApproved,
+    Denied,
+    DeniedByPolicy,
+}
+
+impl ApprovalDecision {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotRequired => "not_required",
+            Self::Approved => "approved",
+            Self::AlwaysApproved => "always_approved",
+            Self::Denied => "denied",
+            Self::DeniedByPolicy => "denied_by_policy",
+        }
+    }
+
+    pub fn allowed(self) -> bool {
+        !matches!(self, Self::Denied | Self::DeniedByPolicy)
+    }
+}
+
+/// Tool invocation details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolInvocation {
+    pub name: String,
+    /// Arguments exactly as passed to the tool
+    pub arguments: serde_json::Value,
+    /// SHA-256 of the tool output, hex-encoded
+    pub output_sha256: String,
+    pub approval: ApprovalDecision,
+}
+
 /// Complete audit event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEvent {
@@ -67,6 +117,16 @@ pub struct AuditEvent {
     pub action: Option<Action>,
     pub result: Option<ExecutionResult>,
     pub security: SecurityContext,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<ToolInvocation>,
+    /// Hash of the preceding entry; `None` for the first in a chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    /// SHA-256 of this entry serialized without `hash`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
 }
 
 impl AuditEvent {
@@ -84,6 +144,10 @@ impl AuditEvent {
                 rate_limit_remaining: None,
                 sandbox_backend: None,
             },
+            session: None,
+            tool: None,
+            prev_hash: None,
+            hash: None,
         }
     }
 
@@ -141,6 +205,26 @@ impl AuditEvent {
         self.security.sandbox_backend = sandbox_backend;
         self
     }
+
+    /// Set the conversation session
+    pub fn with_session(mut self, session: Option<String>) -> Self {
+        self.session = session;
+        self
+    }
+
+    /// Set the tool invocation details
+    pub fn with_tool(mut self, tool: ToolInvocation) -> Self {
+        self.tool = Some(tool);
+        self
+    }
+
+    /// SHA-256 over the entry as serialized without its own `hash`.
+    fn compute_hash(&self) -> Result<String> {
+        let mut unhashed = self.clone();
+        unhashed.hash = None;
+        let json = serde_json::to_string(&unhashed)?;
+        Ok(hex::encode(Sha256::digest(json.as_bytes())))
+    }
 }
 
 /// Audit logger
@@ -148,6 +232,16 @@ pub struct AuditLogger {
     log_path: PathBuf,
     config: AuditConfig,
     buffer: Mutex<Vec<AuditEvent>>,
+    chain: Mutex<ChainState>,
+}
+
+/// Tail of the hash chain as last written by this logger.
+#[derive(Default)]
+struct ChainState {
+    last_hash: Option<String>,
+    /// File length after our last write. A different length means another
+    /// process appended or rotated, so the tail is re-read.
+    file_len: Option<u64>,
 }
 
 /// Structured command execution details for audit logging.
@@ -162,6 +256,68 @@ pub struct CommandExecutionLog<'a> {
     pub duration_ms: u64,
 }
 
+/// Structured tool execution details for audit logging.
+#[derive(Debug, Clone)]
+pub struct ToolExecutionLog<'a> {
+    pub channel: &'a str,
+    pub session: Option<&'a str>,
+    pub tool: &'a str,
+    pub arguments: &'a serde_json::Value,
+    pub output: &'a str,
+    pub success: bool,
+    pub error: Option<&'a str>,
+    pub duration_ms: u64,
+    pub risk_level: &'a str,
+    pub approval: ApprovalDecision,
+}
+
+static SHARED: OnceLock<Mutex<HashMap<PathBuf, Arc<AuditLogger>>>> = OnceLock::new();
+static CURRENT: OnceLock<RwLock<Option<Arc<AuditLogger>>>> = OnceLock::new();
+
+fn current_lock() -> &'static RwLock<Option<Arc<AuditLogger>>> {
+    CURRENT.get_or_init(|| RwLock::new(None))
+}
+
+/// Use `[security.audit]` of `config` for tool execution entries.
+pub fn configure(config: &Config) {
+    *current_lock().write() = if config.security.audit.enabled {
+        match AuditLogger::shared(config.security.audit.clone(), zeroclaw_dir(config)) {
+            Ok(logger) => Some(logger),
+            Err(e) => {
+                tracing::warn!("Audit log unavailable: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+}
+
+/// The audit logger set by [`configure`], if auditing is enabled.
+pub fn current() -> Option<Arc<AuditLogger>> {
+    current_lock().read().clone()
+}
+
+/// Write `entry` to the configured audit log, if any. Failures are logged,
+/// never returned: auditing must not break the tool call it records.
+pub fn record_tool_execution(entry: ToolExecutionLog<'_>) {
+    let Some(logger) = current() else {
+        return;
+    };
+    let tool = entry.tool;
+    if let Err(e) = logger.log_tool_execution(entry) {
+        tracing::warn!(tool, "Failed to write tool audit entry: {e}");
+    }
+}
+
+fn zeroclaw_dir(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
 impl AuditLogger {
     /// Create a new audit logger
     pub fn new(config: AuditConfig, zeroclaw_dir: PathBuf) -> Result<Self> {
@@ -170,31 +326,94 @@ impl AuditLogger {
             log_path,
             config,
             buffer: Mutex::new(Vec::new()),
+            chain: Mutex::new(ChainState::default()),
         })
     }
 
-    /// Log an event
+    /// The process-wide logger for the resolved log path. Every writer to a
+    /// file must go through one instance to keep its hash chain linear.
+    pub fn shared(config: AuditConfig, zeroclaw_dir: PathBuf) -> Result<Arc<Self>> {
+        let log_path = zeroclaw_dir.join(&config.log_path);
+        let mut loggers = SHARED.get_or_init(|| Mutex::new(HashMap::new())).lock();
+        if let Some(logger) = loggers.get(&log_path) {
+            return Ok(Arc::clone(logger));
+        }
+        let logger = Arc::new(Self::new(config, zeroclaw_dir)?);
+        loggers.insert(log_path, Arc::clone(&logger));
+        Ok(logger)
+    }
+
+    pub fn log_path(&self) -> &Path {
+        &self.log_path
+    }
+
+    /// Log an event, linking it to the previous entry
     pub fn log(&self, event: &AuditEvent) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
         }
 
+        // Held through the write so concurrent callers chain in order.
+        let mut chain = self.chain.lock();
+
         // Check log size and rotate if needed
-        self.rotate_if_needed()?;
+        if self.rotate_if_needed()? {
+            chain.file_len = Some(0);
+        }
+
+        let current_len = std::fs::metadata(&self.log_path).map_or(0, |m| m.len());
+        if chain.file_len != Some(current_len) {
+            chain.last_hash = read_last_hash(&self.log_path)?;
+        }
+
+        let mut event = event.clone();
+        event.prev_hash = chain.last_hash.clone();
+        event.hash = None;
+        let hash = event.compute_hash()?;
+        event.hash = Some(hash.clone());
 
         // Serialize and write
-        let line = serde_json::to_string(event)?;
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.log_path)?;
+        let line = serde_json::to_string(&event)?;
+        let mut file = open_append(&self.log_path)?;
 
         writeln!(file, "{}", line)?;
         file.sync_all()?;
 
+        chain.last_hash = Some(hash);
+        chain.file_len = Some(file.metadata()?.len());
         Ok(())
     }
 
+    /// Log one tool invocation: full arguments, outcome, a digest of the
+    /// output and the approval decision that let it run (or stopped it).
+    pub fn log_tool_execution(&self, entry: ToolExecutionLog<'_>) -> Result<()> {
+        let event = AuditEvent::new(AuditEventType::ToolExecution)
+            .with_actor(entry.channel.to_string(), None, None)
+            .with_action(
+                entry.tool.to_string(),
+                entry.risk_level.to_string(),
+                matches!(
+                    entry.approval,
+                    ApprovalDecision::Approved | ApprovalDecision::AlwaysApproved
+                ),
+                entry.approval.allowed(),
+            )
+            .with_result(
+                entry.success,
+                None,
+                entry.duration_ms,
+                entry.error.map(str::to_string),
+            )
+            .with_session(entry.session.map(str::to_string))
+            .with_tool(ToolInvocation {
+                name: entry.tool.to_string(),
+                arguments: entry.arguments.clone(),
+                output_sha256: hex::encode(Sha256::digest(entry.output.as_bytes())),
+                approval: entry.approval,
+            });
+        self.log(&event)
+    }
+
     /// Log a command execution event.
     pub fn log_command_event(&self, entry: CommandExecutionLog<'_>) -> Result<()> {
         let event = AuditEvent::new(AuditEventType::CommandExecution)
@@ -210,6 +429,28 @@ impl AuditLogger {
         self.log(&event)
     }
 
+    /// Log an approval decision for a tool call. `allowed` is whether the
+    /// call went ahead; `approved` is whether a human said yes.
+    pub fn log_tool_approval(
+        &self,
+        channel: &str,
+        user: Option<&str>,
+        tool_call: &str,
+        risk_level: &str,
+        approved: bool,
+        allowed: bool,
+    ) -> Result<()> {
+        let event = AuditEvent::new(AuditEventType::ToolApproval)
+            .with_actor(channel.to_string(), user.map(str::to_string), None)
+            .with_action(
+                tool_call.to_string(),
+                risk_level.to_string(),
+                approved,
+                allowed,
+            );
+        self.log(&event)
+    }
+
     /// Backward-compatible helper to log a command execution event.
     #[allow(clippy::too_many_arguments)]
     pub fn log_command(
@@ -233,15 +474,16 @@ impl AuditLogger {
         })
     }
 
-    /// Rotate log if it exceeds max size
-    fn rotate_if_needed(&self) -> Result<()> {
+    /// Rotate log if it exceeds max size; `true` when it rotated
+    fn rotate_if_needed(&self) -> Result<bool> {
         if let Ok(metadata) = std::fs::metadata(&self.log_path) {
             let current_size_mb = metadata.len() / (1024 * 1024);
             if current_size_mb >= u64::from(self.config.max_size_mb) {
                 self.rotate()?;
+                return Ok(true);
             }
         }
-        Ok(())
+        Ok(false)
     }
 
     /// Rotate the log file
@@ -258,6 +500,248 @@ impl AuditLogger {
     }
 }
 
+fn open_append(path: &Path) -> Result<File> {
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .with_context(|| format!("Failed to open audit log {}", path.display()))
+}
+
+/// `hash` of the last entry in `path`, reading only the tail of the file.
+fn read_last_hash(path: &Path) -> Result<Option<String>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let len = file.metadata()?.len();
+    let mut window: u64 = 8 * 1024;
+    loop {
+        let start = len.saturating_sub(window);
+        file.seek(SeekFrom::Start(start))?;
+        let mut tail = Vec::new();
+        file.read_to_end(&mut tail)?;
+        let text = String::from_utf8_lossy(&tail);
+        let mut lines = text.lines().rev().filter(|l| !l.trim().is_empty());
+        let last = lines.next();
+        // The last line is complete once another line (or the file start)
+        // precedes it in the window.
+        if let Some(last) = last {
+            if start == 0 || lines.next().is_some() {
+                return Ok(serde_json::from_str::<AuditEvent>(last)
+                    .ok()
+                    .and_then(|event| event.hash));
+            }
+        } else if start == 0 {
+            return Ok(None);
+        }
+        window = window.saturating_mul(2);
+    }
+}
+
+/// Every parseable entry in `path`, in file order, with its line number.
+pub fn read_events(path: &Path) -> Result<Vec<(usize, AuditEvent)>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+    let mut events = Vec::new();
+    for (idx, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event: AuditEvent = serde_json::from_str(&line)
+            .with_context(|| format!("{}:{}: not an audit entry", path.display(), idx + 1))?;
+        events.push((idx + 1, event));
+    }
+    Ok(events)
+}
+
+/// A break in the hash chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainBreak {
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Check every entry's hash and its link to the entry before it. Entries
+/// written before chaining existed (no `hash`) are skipped; the first
+/// chained entry may link to a rotated file.
+pub fn verify_chain(events: &[(usize, AuditEvent)]) -> std::result::Result<usize, ChainBreak> {
+    let mut previous: Option<&str> = None;
+    let mut verified = 0;
+    for (line, event) in events {
+        let Some(hash) = event.hash.as_deref() else {
+            previous = None;
+            continue;
+        };
+        let expected = event.compute_hash().map_err(|e| ChainBreak {
+            line: *line,
+            reason: e.to_string(),
+        })?;
+        if expected != hash {
+            return Err(ChainBreak {
+                line: *line,
+                reason: "entry was modified (hash mismatch)".into(),
+            });
+        }
+        if let Some(previous) = previous {
+            if event.prev_hash.as_deref() != Some(previous) {
+                return Err(ChainBreak {
+                    line: *line,
+                    reason: "an entry before this one was removed or reordered".into(),
+                });
+            }
+        }
+        previous = Some(hash);
+        verified += 1;
+    }
+    Ok(verified)
+}
+
+/// Parse `--since`: a duration back from now (`30m`, `24h`, `7d`) or a
+/// date (`YYYY-MM-DD` / RFC 3339).
+fn parse_since(value: &str) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Some((split, _)) = value.char_indices().last() {
+        if let Ok(n) = value[..split].parse::<u32>() {
+            let n = i64::from(n);
+            let duration = match &value[split..] {
+                "s" => Some(chrono::Duration::seconds(n)),
+                "m" => Some(chrono::Duration::minutes(n)),
+                "h" => Some(chrono::Duration::hours(n)),
+                "d" => Some(chrono::Duration::days(n)),
+                "w" => Some(chrono::Duration::weeks(n)),
+                _ => None,
+            };
+            if let Some(duration) = duration {
+                return Ok(Utc::now() - duration);
+            }
+        }
+    }
+    crate::conversations::cli::parse_date_bound(value, false)
+        .with_context(|| format!("Invalid --since '{value}' (expected 24h, 7d or a date)"))
+}
+
+/// Handle `zeroclaw audit` subcommands.
+pub fn handle_command(command: AuditCommands, config: &Config) -> Result<()> {
+    let log_path = zeroclaw_dir(config).join(&config.security.audit.log_path);
+    match command {
+        AuditCommands::Show {
+            since,
+            tool,
+            session,
+            limit,
+            json,
+        } => {
+            let since = since.as_deref().map(parse_since).transpose()?;
+            let events: Vec<AuditEvent> = read_events(&log_path)?
+                .into_iter()
+                .map(|(_, event)| event)
+                .filter(|e| since.is_none_or(|since| e.timestamp >= since))
+                .filter(|e| {
+                    tool.as_deref()
+                        .is_none_or(|tool| e.tool.as_ref().is_some_and(|t| t.name == tool))
+                })
+                .filter(|e| {
+                    session
+                        .as_deref()
+                        .is_none_or(|session| e.session.as_deref() == Some(session))
+                })
+                .collect();
+            let skip = events.len().saturating_sub(limit);
+            let shown = &events[skip..];
+            if json {
+                for event in shown {
+                    println!("{}", serde_json::to_string(event)?);
+                }
+                return Ok(());
+            }
+            if shown.is_empty() {
+                println!("No audit entries in {}.", log_path.display());
+                return Ok(());
+            }
+            for event in shown {
+                print_event(event);
+            }
+            if skip > 0 {
+                println!("({skip} earlier entries not shown; raise --limit)");
+            }
+            Ok(())
+        }
+        AuditCommands::Verify => {
+            let events = read_events(&log_path)?;
+            match verify_chain(&events) {
+                Ok(verified) => {
+                    println!(
+                        "✅ {}: {verified} chained entries intact",
+                        log_path.display()
+                    );
+                    Ok(())
+                }
+                Err(broken) => anyhow::bail!(
+                    "{}:{}: hash chain broken: {}",
+                    log_path.display(),
+                    broken.line,
+                    broken.reason
+                ),
+            }
+        }
+    }
+}
+
+fn print_event(event: &AuditEvent) {
+    let time = event
+        .timestamp
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M:%S");
+    let channel = event.actor.as_ref().map_or("-", |a| a.channel.as_str());
+    let session = event.session.as_deref().unwrap_or("-");
+    let status = match &event.result {
+        Some(r) if r.success => "ok",
+        Some(_) => "failed",
+        None => "-",
+    };
+    if let Some(tool) = &event.tool {
+        let duration = event
+            .result
+            .as_ref()
+            .and_then(|r| r.duration_ms)
+            .unwrap_or(0);
+        println!(
+            "{time}  {channel}  {session}  {}  {status}  {duration}ms  approval={}",
+            tool.name,
+            tool.approval.as_str()
+        );
+        println!("    args: {}", tool.arguments);
+        if let Some(error) = event.result.as_ref().and_then(|r| r.error.as_deref()) {
+            println!("    error: {error}");
+        }
+        println!("    output sha256: {}", tool.output_sha256);
+    } else {
+        let kind = serde_json::to_value(&event.event_type)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let command = event
+            .action
+            .as_ref()
+            .and_then(|a| a.command.as_deref())
+            .unwrap_or("-");
+        println!("{time}  {channel}  {session}  {kind}  {command}  {status}");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -396,6 +880,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn audit_log_tool_approval_records_decision() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let logger = AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf())?;
+
+        logger.log_tool_approval(
+            "telegram",
+            Some("alice"),
+            "shell(command: ls)",
+            "high",
+            false,
+            false,
+        )?;
+
+        let content = tokio::fs::read_to_string(tmp.path().join("audit.log")).await?;
+        let parsed: AuditEvent = serde_json::from_str(content.trim())?;
+        assert!(matches!(parsed.event_type, AuditEventType::ToolApproval));
+        assert_eq!(parsed.actor.unwrap().user_id.as_deref(), Some("alice"));
+        let action = parsed.action.unwrap();
+        assert_eq!(action.risk_level.as_deref(), Some("high"));
+        assert!(!action.approved && !action.allowed);
+        Ok(())
+    }
+
     #[test]
     fn audit_rotation_creates_numbered_backup() -> Result<()> {
         let tmp = TempDir::new()?;
@@ -420,4 +928,109 @@ mod tests {
         );
         Ok(())
     }
+
+    fn tool_entry<'a>(arguments: &'a serde_json::Value, output: &'a str) -> ToolExecutionLog<'a> {
+        ToolExecutionLog {
+            channel: "cli",
+            session: Some("cli:abc"),
+            tool: "shell",
+            arguments,
+            output,
+            success: true,
+            error: None,
+            duration_ms: 7,
+            risk_level: "high",
+            approval: ApprovalDecision::Approved,
+        }
+    }
+
+    #[test]
+    fn audit_log_tool_execution_records_arguments_digest_and_approval() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let logger = AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf())?;
+        let args = serde_json::json!({"command": "ls -la /etc"});
+
+        logger.log_tool_execution(tool_entry(&args, "passwd\nhosts\n"))?;
+
+        let events = read_events(&tmp.path().join("audit.log"))?;
+        let (_, event) = &events[0];
+        assert!(matches!(event.event_type, AuditEventType::ToolExecution));
+        assert_eq!(event.session.as_deref(), Some("cli:abc"));
+        let tool = event.tool.as_ref().unwrap();
+        assert_eq!(tool.arguments, args);
+        assert_eq!(tool.approval, ApprovalDecision::Approved);
+        assert_eq!(
+            tool.output_sha256,
+            hex::encode(Sha256::digest(b"passwd\nhosts\n"))
+        );
+        assert!(event.result.as_ref().unwrap().success);
+        Ok(())
+    }
+
+    #[test]
+    fn audit_entries_form_a_hash_chain() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let logger = AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf())?;
+        let args = serde_json::json!({"command": "date"});
+        for _ in 0..3 {
+            logger.log_tool_execution(tool_entry(&args, "ok"))?;
+        }
+
+        let events = read_events(&tmp.path().join("audit.log"))?;
+        assert!(events[0].1.prev_hash.is_none());
+        assert_eq!(events[1].1.prev_hash, events[0].1.hash);
+        assert_eq!(events[2].1.prev_hash, events[1].1.hash);
+        assert_eq!(verify_chain(&events), Ok(3));
+        Ok(())
+    }
+
+    #[test]
+    fn audit_chain_continues_across_logger_instances() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let args = serde_json::json!({});
+        AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf())?
+            .log_tool_execution(tool_entry(&args, "first"))?;
+        AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf())?
+            .log_tool_execution(tool_entry(&args, "second"))?;
+
+        let events = read_events(&tmp.path().join("audit.log"))?;
+        assert_eq!(events[1].1.prev_hash, events[0].1.hash);
+        assert_eq!(verify_chain(&events), Ok(2));
+        Ok(())
+    }
+
+    #[test]
+    fn verify_chain_detects_edited_and_removed_entries() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let logger = AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf())?;
+        let args = serde_json::json!({"command": "rm -rf build"});
+        for _ in 0..3 {
+            logger.log_tool_execution(tool_entry(&args, "done"))?;
+        }
+        let path = tmp.path().join("audit.log");
+        let original = std::fs::read_to_string(&path)?;
+
+        std::fs::write(&path, original.replacen("rm -rf build", "ls", 1))?;
+        let broken = verify_chain(&read_events(&path)?).unwrap_err();
+        assert_eq!(broken.line, 1);
+
+        let lines: Vec<&str> = original.lines().collect();
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2]))?;
+        let broken = verify_chain(&read_events(&path)?).unwrap_err();
+        assert_eq!(broken.line, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_since_accepts_durations_and_dates() -> Result<()> {
+        let day_ago = parse_since("24h")?;
+        let expected = Utc::now() - chrono::Duration::hours(24);
+        assert!((day_ago - expected).num_seconds().abs() < 5);
+        assert_eq!(
+            parse_since("2026-03-01")?.to_rfc3339(),
+            "2026-03-01T00:00:00+00:00"
+        );
+        assert!(parse_since("soon").is_err());
+        Ok(())
+    }
 }
@@ -2,10 +2,13 @@
 
 use crate::config::{SandboxBackend, SecurityConfig};
 use crate::security::traits::Sandbox;
+use std::path::Path;
 use std::sync::Arc;
 
-/// Create a sandbox based on auto-detection or explicit config
-pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
+/// Create a sandbox based on auto-detection or explicit config.
+///
+/// `workspace_dir` is the directory sandboxed commands may write to.
+pub fn create_sandbox(config: &SecurityConfig, workspace_dir: &Path) -> Arc<dyn Sandbox> {
     let backend = &config.sandbox.backend;
 
     // If explicitly disabled, return noop
@@ -20,7 +23,9 @@ pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
             {
                 #[cfg(target_os = "linux")]
                 {
-                    if let Ok(sandbox) = super::landlock::LandlockSandbox::new() {
+                    if let Ok(sandbox) = super::landlock::LandlockSandbox::with_workspace(Some(
+                        workspace_dir.to_path_buf(),
+                    )) {
                         return Arc::new(sandbox);
                     }
                 }
@@ -64,29 +69,44 @@ pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
             tracing::warn!("Docker requested but not available, falling back to application-layer");
             Arc::new(super::traits::NoopSandbox)
         }
+        SandboxBackend::Seccomp => {
+            if let Ok(sandbox) = super::seccomp::SeccompSandbox::new() {
+                return Arc::new(sandbox);
+            }
+            tracing::warn!(
+                "Seccomp requested but not available, falling back to application-layer"
+            );
+            Arc::new(super::traits::NoopSandbox)
+        }
         SandboxBackend::Auto | SandboxBackend::None => {
             // Auto-detect best available
-            detect_best_sandbox()
+            detect_best_sandbox(workspace_dir)
         }
     }
 }
 
-/// Auto-detect the best available sandbox
-fn detect_best_sandbox() -> Arc<dyn Sandbox> {
+/// Auto-detect the best available sandbox.
+///
+/// Only in-process backends are auto-selected on Linux: wrapper backends
+/// (Firejail, Docker) replace the home directory or filesystem view and would
+/// hide the workspace, so they must be requested explicitly.
+fn detect_best_sandbox(workspace_dir: &Path) -> Arc<dyn Sandbox> {
     #[cfg(target_os = "linux")]
     {
         // Try Landlock first (native, no dependencies)
         #[cfg(feature = "sandbox-landlock")]
         {
-            if let Ok(sandbox) = super::landlock::LandlockSandbox::probe() {
+            if let Ok(sandbox) =
+                super::landlock::LandlockSandbox::with_workspace(Some(workspace_dir.to_path_buf()))
+            {
                 tracing::info!("Landlock sandbox enabled (Linux kernel 5.13+)");
                 return Arc::new(sandbox);
             }
         }
 
-        // Try Firejail second (user-space tool)
-        if let Ok(sandbox) = super::firejail::FirejailSandbox::probe() {
-            tracing::info!("Firejail sandbox enabled");
+        // Seccomp syscall filter needs no extra binaries or features
+        if let Ok(sandbox) = super::seccomp::SeccompSandbox::probe() {
+            tracing::info!("Seccomp sandbox enabled");
             return Arc::new(sandbox);
         }
     }
@@ -103,13 +123,8 @@ fn detect_best_sandbox() -> Arc<dyn Sandbox> {
         }
     }
 
-    // Docker is heavy but works everywhere if docker is installed
-    if let Ok(sandbox) = super::docker::DockerSandbox::probe() {
-        tracing::info!("Docker sandbox enabled");
-        return Arc::new(sandbox);
-    }
-
-    // Fallback: application-layer security only
+    // Fallback: application-layer security only (restricted env + cwd jail)
+    let _ = workspace_dir;
     tracing::info!("No sandbox backend available, using application-layer security");
     Arc::new(super::traits::NoopSandbox)
 }
@@ -121,7 +136,7 @@ mod tests {
 
     #[test]
     fn detect_best_sandbox_returns_something() {
-        let sandbox = detect_best_sandbox();
+        let sandbox = detect_best_sandbox(&std::env::temp_dir());
         // Should always return at least NoopSandbox
         assert!(sandbox.is_available());
     }
@@ -136,7 +151,7 @@ mod tests {
             },
             ..Default::default()
         };
-        let sandbox = create_sandbox(&config);
+        let sandbox = create_sandbox(&config, &std::env::temp_dir());
         assert_eq!(sandbox.name(), "none");
     }
 
@@ -150,7 +165,7 @@ mod tests {
             },
             ..Default::default()
         };
-        let sandbox = create_sandbox(&config);
+        let sandbox = create_sandbox(&config, &std::env::temp_dir());
         // Should return some sandbox (at least NoopSandbox)
         assert!(sandbox.is_available());
     }
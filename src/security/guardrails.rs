@@ -0,0 +1,324 @@
+//! Guardrails: configurable content filters for prompts and replies.
+//!
+//! Built from `[guardrails]`. Regex/keyword rules run first and can block or
+//! rewrite text; an optional classifier model then gives an allow/block
+//! decision. Each rule and the classifier can be limited to some channels,
+//! so a public Discord can be filtered more strictly than the local CLI.
+
+use crate::config::{GuardrailAction, GuardrailDirection, GuardrailsConfig};
+use crate::providers::Provider;
+use anyhow::{Context, Result};
+use regex::Regex;
+
+/// Cap on text sent to the classifier model.
+const CLASSIFIER_MAX_INPUT_CHARS: usize = 4_000;
+
+/// Outcome of a guardrail check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardrailVerdict {
+    /// Pass the text through unchanged.
+    Allow,
+    /// Pass this rewritten text through instead.
+    Rewrite(String),
+    /// Drop the text; `message` is what the user sees instead.
+    Block { rule: String, message: String },
+}
+
+struct CompiledRule {
+    name: String,
+    direction: GuardrailDirection,
+    regex: Regex,
+    action: GuardrailAction,
+    replacement: String,
+    message: Option<String>,
+    channels: Vec<String>,
+}
+
+struct Classifier {
+    model: String,
+    policy: String,
+    direction: GuardrailDirection,
+    channels: Vec<String>,
+    fail_closed: bool,
+}
+
+/// Compiled `[guardrails]` pipeline.
+pub struct Guardrails {
+    rules: Vec<CompiledRule>,
+    classifier: Option<Classifier>,
+    blocked_input_message: String,
+    blocked_output_message: String,
+}
+
+fn applies_to_channel(channels: &[String], channel: &str) -> bool {
+    channels.is_empty() || channels.iter().any(|c| c == channel)
+}
+
+impl Guardrails {
+    /// Compile the configured rules; `None` when guardrails are disabled or empty.
+    pub fn from_config(config: &GuardrailsConfig) -> Result<Option<Self>> {
+        if !config.enabled || (config.rules.is_empty() && config.classifier.is_none()) {
+            return Ok(None);
+        }
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| {
+                let source = match &rule.pattern {
+                    Some(pattern) => pattern.clone(),
+                    None if rule.keywords.is_empty() => {
+                        anyhow::bail!("guardrails rule '{}' has no pattern or keywords", rule.name)
+                    }
+                    None => format!(
+                        "(?i)(?:{})",
+                        rule.keywords
+                            .iter()
+                            .map(|keyword| regex::escape(keyword))
+                            .collect::<Vec<_>>()
+                            .join("|")
+                    ),
+                };
+                let regex = Regex::new(&source)
+                    .with_context(|| format!("guardrails rule '{}'", rule.name))?;
+                Ok(CompiledRule {
+                    name: rule.name.clone(),
+                    direction: rule.direction,
+                    regex,
+                    action: rule.action,
+                    replacement: rule.replacement.clone(),
+                    message: rule.message.clone(),
+                    channels: rule.channels.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let classifier = config.classifier.as_ref().map(|c| Classifier {
+            model: c.model.clone(),
+            policy: c.policy.clone(),
+            direction: c.direction,
+            channels: c.channels.clone(),
+            fail_closed: c.fail_closed,
+        });
+        Ok(Some(Self {
+            rules,
+            classifier,
+            blocked_input_message: config.blocked_input_message.clone(),
+            blocked_output_message: config.blocked_output_message.clone(),
+        }))
+    }
+
+    /// Whether any rule or the classifier inspects `channel`'s replies.
+    /// Streaming drafts are turned off for such channels so unfiltered text
+    /// never reaches the user.
+    pub fn screens_output(&self, channel: &str) -> bool {
+        self.rules.iter().any(|rule| {
+            rule.direction.covers(GuardrailDirection::Output)
+                && applies_to_channel(&rule.channels, channel)
+        }) || self.classifier.as_ref().is_some_and(|c| {
+            c.direction.covers(GuardrailDirection::Output)
+                && applies_to_channel(&c.channels, channel)
+        })
+    }
+
+    fn blocked_message(&self, direction: GuardrailDirection) -> String {
+        if direction == GuardrailDirection::Output {
+            self.blocked_output_message.clone()
+        } else {
+            self.blocked_input_message.clone()
+        }
+    }
+
+    /// Run the regex/keyword rules only.
+    pub fn check_rules(
+        &self,
+        direction: GuardrailDirection,
+        channel: &str,
+        text: &str,
+    ) -> GuardrailVerdict {
+        let mut current = text.to_string();
+        for rule in &self.rules {
+            if !rule.direction.covers(direction) || !applies_to_channel(&rule.channels, channel) {
+                continue;
+            }
+            if !rule.regex.is_match(&current) {
+                continue;
+            }
+            match rule.action {
+                GuardrailAction::Block => {
+                    return GuardrailVerdict::Block {
+                        rule: rule.name.clone(),
+                        message: rule
+                            .message
+                            .clone()
+                            .unwrap_or_else(|| self.blocked_message(direction)),
+                    };
+                }
+                GuardrailAction::Rewrite => {
+                    current = rule
+                        .regex
+                        .replace_all(&current, rule.replacement.as_str())
+                        .into_owned();
+                }
+            }
+        }
+        if current == text {
+            GuardrailVerdict::Allow
+        } else {
+            GuardrailVerdict::Rewrite(current)
+        }
+    }
+
+    /// Run the rules, then the classifier on whatever text they let through.
+    pub async fn check(
+        &self,
+        provider: &dyn Provider,
+        direction: GuardrailDirection,
+        channel: &str,
+        text: &str,
+    ) -> GuardrailVerdict {
+        let verdict = self.check_rules(direction, channel, text);
+        if matches!(verdict, GuardrailVerdict::Block { .. }) {
+            return verdict;
+        }
+        let screened = match &verdict {
+            GuardrailVerdict::Rewrite(rewritten) => rewritten.as_str(),
+            _ => text,
+        };
+        let Some(classifier) = self
+            .classifier
+            .as_ref()
+            .filter(|c| c.direction.covers(direction) && applies_to_channel(&c.channels, channel))
+        else {
+            return verdict;
+        };
+
+        let kind = if direction == GuardrailDirection::Output {
+            "assistant reply"
+        } else {
+            "user message"
+        };
+        let system = format!(
+            "You are a content moderator. Policy:\n{}\n\n\
+             Decide whether the {kind} below violates the policy. \
+             Answer with exactly one word: ALLOW or BLOCK.",
+            classifier.policy
+        );
+        let sample: String = screened.chars().take(CLASSIFIER_MAX_INPUT_CHARS).collect();
+        let blocked = match provider
+            .chat_with_system(Some(&system), &sample, &classifier.model, 0.0)
+            .await
+        {
+            Ok(answer) => parse_classifier_answer(&answer).unwrap_or(classifier.fail_closed),
+            Err(e) => {
+                tracing::warn!(model = %classifier.model, "Guardrail classifier failed: {e}");
+                classifier.fail_closed
+            }
+        };
+        if blocked {
+            GuardrailVerdict::Block {
+                rule: "classifier".into(),
+                message: self.blocked_message(direction),
+            }
+        } else {
+            verdict
+        }
+    }
+}
+
+/// `Some(true)` for BLOCK, `Some(false)` for ALLOW, `None` when unclear.
+fn parse_classifier_answer(answer: &str) -> Option<bool> {
+    let word: String = answer
+        .trim()
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect::<String>()
+        .to_ascii_uppercase();
+    match word.as_str() {
+        "BLOCK" => Some(true),
+        "ALLOW" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GuardrailClassifierConfig, GuardrailRuleConfig};
+
+    fn rule(name: &str, keywords: &[&str], action: GuardrailAction) -> GuardrailRuleConfig {
+        GuardrailRuleConfig {
+            name: name.into(),
+            direction: GuardrailDirection::Both,
+            pattern: None,
+            keywords: keywords.iter().map(|k| (*k).to_string()).collect(),
+            action,
+            replacement: "[removed]".into(),
+            message: None,
+            channels: vec!["discord".into()],
+        }
+    }
+
+    fn guardrails(rules: Vec<GuardrailRuleConfig>) -> Guardrails {
+        Guardrails::from_config(&GuardrailsConfig {
+            enabled: true,
+            rules,
+            ..GuardrailsConfig::default()
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn rules_block_or_rewrite_per_channel() {
+        let mut email = rule("email", &[], GuardrailAction::Rewrite);
+        email.pattern = Some(r"[\w.]+@[\w.]+".into());
+        email.keywords.clear();
+        email.direction = GuardrailDirection::Output;
+        let g = guardrails(vec![
+            rule("slur", &["BadWord"], GuardrailAction::Block),
+            email,
+        ]);
+
+        assert_eq!(
+            g.check_rules(GuardrailDirection::Input, "discord", "you badword"),
+            GuardrailVerdict::Block {
+                rule: "slur".into(),
+                message: "Sorry, I can't help with that request.".into(),
+            }
+        );
+        assert_eq!(
+            g.check_rules(GuardrailDirection::Input, "cli", "you badword"),
+            GuardrailVerdict::Allow
+        );
+        assert_eq!(
+            g.check_rules(GuardrailDirection::Output, "discord", "mail a.b@c.io now"),
+            GuardrailVerdict::Rewrite("mail [removed] now".into())
+        );
+        assert!(g.screens_output("discord"));
+        assert!(!g.screens_output("telegram"));
+    }
+
+    #[test]
+    fn classifier_answers_parse_and_unclear_ones_fall_back() {
+        assert_eq!(parse_classifier_answer(" block."), Some(true));
+        assert_eq!(parse_classifier_answer("ALLOW\n"), Some(false));
+        assert_eq!(parse_classifier_answer("Maybe"), None);
+
+        let g = Guardrails::from_config(&GuardrailsConfig {
+            enabled: true,
+            classifier: Some(GuardrailClassifierConfig {
+                model: "cheap".into(),
+                policy: "be nice".into(),
+                direction: GuardrailDirection::Input,
+                channels: Vec::new(),
+                fail_closed: false,
+            }),
+            ..GuardrailsConfig::default()
+        })
+        .unwrap()
+        .unwrap();
+        assert!(!g.screens_output("discord"));
+        assert!(Guardrails::from_config(&GuardrailsConfig::default())
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,200 @@
+//! OS keyring access: the macOS Keychain, Windows Credential Manager and the
+//! Secret Service on Linux.
+//!
+//! `zeroclaw auth login <provider>` stores API keys here under
+//! `[secrets].keyring_service`, one entry per provider, so they never appear
+//! in config.toml. Providers read them through [`provider_key`] when no key
+//! is configured; `keyring:<account>` secret references and the storage
+//! passphrase go through [`get`].
+//!
+//! Needs the `keyring` build feature. Without it there is never an entry to
+//! read or delete, and storing a key fails with a rebuild hint.
+
+use crate::config::SecretsConfig;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Keyring service for provider keys. Unset until [`configure`] runs, so
+/// library users and tests never touch the OS keyring.
+static SERVICE: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+
+/// Provider keys already looked up, including misses: a locked or absent
+/// Secret Service can be slow to answer.
+static PROVIDER_KEYS: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+
+fn service_lock() -> &'static Mutex<Option<String>> {
+    SERVICE.get_or_init(|| Mutex::new(None))
+}
+
+fn provider_keys() -> &'static Mutex<HashMap<String, Option<String>>> {
+    PROVIDER_KEYS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Read provider keys from `[secrets].keyring_service` from now on.
+pub fn configure(config: &SecretsConfig) {
+    *service_lock().lock().unwrap_or_else(|e| e.into_inner()) =
+        Some(config.keyring_service.clone());
+    provider_keys()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Keyring account holding the API key of `provider`.
+pub fn provider_account(provider: &str) -> String {
+    let provider = provider.trim().to_ascii_lowercase();
+    crate::providers::canonical_china_provider_name(&provider).map_or(provider, ToString::to_string)
+}
+
+/// API key stored for `provider` by `zeroclaw auth login`, if any.
+pub fn provider_key(provider: &str) -> Option<String> {
+    let service = service_lock()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()?;
+    let account = provider_account(provider);
+    if let Some(cached) = provider_keys()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&account)
+    {
+        return cached.clone();
+    }
+    let key = match get(&service, &account) {
+        Ok(key) => key,
+        Err(e) => {
+            tracing::debug!(%service, %account, "OS keyring lookup failed: {e:#}");
+            None
+        }
+    };
+    provider_keys()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(account, key.clone());
+    key
+}
+
+/// Store the API key for `provider` under `service`.
+pub fn set_provider_key(service: &str, provider: &str, key: &str) -> Result<()> {
+    let account = provider_account(provider);
+    set(service, &account, key)?;
+    provider_keys()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&account);
+    Ok(())
+}
+
+/// Remove the API key for `provider` under `service`. Returns whether
+/// there was one.
+pub fn delete_provider_key(service: &str, provider: &str) -> Result<bool> {
+    let account = provider_account(provider);
+    let removed = delete(service, &account)?;
+    provider_keys()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&account);
+    Ok(removed)
+}
+
+#[cfg(all(
+    feature = "keyring",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+mod os {
+    use anyhow::{Context, Result};
+    use keyring::{Entry, Error};
+
+    pub fn get(service: &str, account: &str) -> Result<Option<String>> {
+        match Entry::new(service, account)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(Error::NoEntry) => Ok(None),
+            Err(e) => Err(e).context("failed to read from the OS keyring"),
+        }
+    }
+
+    pub fn set(service: &str, account: &str, secret: &str) -> Result<()> {
+        Entry::new(service, account)?
+            .set_password(secret)
+            .context("failed to write to the OS keyring")
+    }
+
+    pub fn delete(service: &str, account: &str) -> Result<bool> {
+        match Entry::new(service, account)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(Error::NoEntry) => Ok(false),
+            Err(e) => Err(e).context("failed to delete from the OS keyring"),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "keyring",
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows"))
+))]
+mod os {
+    use anyhow::Result;
+
+    pub fn get(_service: &str, _account: &str) -> Result<Option<String>> {
+        anyhow::bail!("the OS keyring is supported on Linux, macOS and Windows only")
+    }
+
+    pub fn set(_service: &str, _account: &str, _secret: &str) -> Result<()> {
+        anyhow::bail!("the OS keyring is supported on Linux, macOS and Windows only")
+    }
+
+    pub fn delete(_service: &str, _account: &str) -> Result<bool> {
+        anyhow::bail!("the OS keyring is supported on Linux, macOS and Windows only")
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+mod os {
+    use anyhow::Result;
+
+    pub fn get(_service: &str, _account: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    pub fn set(_service: &str, _account: &str, _secret: &str) -> Result<()> {
+        anyhow::bail!("OS keyring support is not compiled in. Rebuild with --features keyring")
+    }
+
+    pub fn delete(_service: &str, _account: &str) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Read `account` under `service`; `None` when there is no such entry or
+/// keyring support is not compiled in.
+pub fn get(service: &str, account: &str) -> Result<Option<String>> {
+    os::get(service, account)
+}
+
+/// Create or replace `account` under `service`.
+pub fn set(service: &str, account: &str, secret: &str) -> Result<()> {
+    os::set(service, account, secret)
+}
+
+/// Remove `account` under `service`. Returns whether it existed.
+pub fn delete(service: &str, account: &str) -> Result<bool> {
+    os::delete(service, account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_accounts_use_the_canonical_name() {
+        assert_eq!(provider_account("OpenAI"), "openai");
+        assert_eq!(provider_account("kimi-intl"), "moonshot");
+        assert_eq!(provider_account("dashscope-us"), "qwen");
+    }
+
+    #[test]
+    fn provider_keys_are_not_read_before_configure() {
+        assert!(provider_key("openai").is_none());
+    }
+}
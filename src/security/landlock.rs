@@ -4,10 +4,11 @@
 //! This module uses the pure-Rust `landlock` crate for filesystem access control.
 
 #[cfg(all(feature = "sandbox-landlock", target_os = "linux"))]
-use landlock::{AccessFs, PathBeneath, PathFd, Ruleset, RulesetAttr, RulesetCreatedAttr};
+use landlock::{
+    AccessFs, PathBeneath, PathFd, Ruleset, RulesetAttr, RulesetCreated, RulesetCreatedAttr,
+};
 
 use crate::security::traits::Sandbox;
-use std::path::Path;
 
 /// Landlock sandbox backend for Linux
 #[cfg(all(feature = "sandbox-landlock", target_os = "linux"))]
@@ -47,89 +48,86 @@ impl LandlockSandbox {
         Self::new()
     }
 
-    /// Apply Landlock restrictions to the current process
-    fn apply_restrictions(&self) -> std::io::Result<()> {
-        let mut ruleset = Ruleset::default()
-            .handle_access(
-                AccessFs::ReadFile
-                    | AccessFs::WriteFile
-                    | AccessFs::ReadDir
-                    | AccessFs::RemoveDir
-                    | AccessFs::RemoveFile
-                    | AccessFs::MakeChar
-                    | AccessFs::MakeSock
-                    | AccessFs::MakeFifo
-                    | AccessFs::MakeBlock
-                    | AccessFs::MakeReg
-                    | AccessFs::MakeSym,
-            )
-            .and_then(|ruleset| ruleset.create())
-            .map_err(|e| std::io::Error::other(e.to_string()))?;
-
-        // Allow workspace directory (read/write)
+    /// Build the ruleset for a sandboxed command.
+    ///
+    /// The workspace and `/tmp` are fully writable, `/dev` is read/write for
+    /// device nodes such as `/dev/null`, and system directories needed to run
+    /// ordinary tools are read-only. Everything else is inaccessible.
+    fn build_ruleset(&self) -> std::io::Result<RulesetCreated> {
+        let read_write = AccessFs::ReadFile
+            | AccessFs::WriteFile
+            | AccessFs::ReadDir
+            | AccessFs::RemoveDir
+            | AccessFs::RemoveFile
+            | AccessFs::MakeChar
+            | AccessFs::MakeSock
+            | AccessFs::MakeFifo
+            | AccessFs::MakeBlock
+            | AccessFs::MakeReg
+            | AccessFs::MakeSym
+            | AccessFs::MakeDir;
+        let read_only = AccessFs::ReadFile | AccessFs::ReadDir;
+        let devices = AccessFs::ReadFile | AccessFs::WriteFile | AccessFs::ReadDir;
+
+        let mut rules = vec![(std::path::PathBuf::from("/tmp"), read_write)];
         if let Some(ref workspace) = self.workspace_dir {
-            if workspace.exists() {
-                let workspace_fd =
-                    PathFd::new(workspace).map_err(|e| std::io::Error::other(e.to_string()))?;
-                ruleset = ruleset
-                    .add_rule(PathBeneath::new(
-                        workspace_fd,
-                        AccessFs::ReadFile | AccessFs::WriteFile | AccessFs::ReadDir,
-                    ))
-                    .map_err(|e| std::io::Error::other(e.to_string()))?;
-            }
+            rules.push((workspace.clone(), read_write));
+        }
+        rules.push((std::path::PathBuf::from("/dev"), devices));
+        for dir in [
+            "/usr", "/bin", "/sbin", "/lib", "/lib32", "/lib64", "/etc", "/opt", "/proc", "/sys",
+            "/nix",
+        ] {
+            rules.push((std::path::PathBuf::from(dir), read_only));
         }
 
-        // Allow /tmp for general operations
-        let tmp_fd =
-            PathFd::new(Path::new("/tmp")).map_err(|e| std::io::Error::other(e.to_string()))?;
-        ruleset = ruleset
-            .add_rule(PathBeneath::new(
-                tmp_fd,
-                AccessFs::ReadFile | AccessFs::WriteFile,
-            ))
-            .map_err(|e| std::io::Error::other(e.to_string()))?;
-
-        // Allow /usr and /bin for executing commands
-        let usr_fd =
-            PathFd::new(Path::new("/usr")).map_err(|e| std::io::Error::other(e.to_string()))?;
-        ruleset = ruleset
-            .add_rule(PathBeneath::new(
-                usr_fd,
-                AccessFs::ReadFile | AccessFs::ReadDir,
-            ))
-            .map_err(|e| std::io::Error::other(e.to_string()))?;
-
-        let bin_fd =
-            PathFd::new(Path::new("/bin")).map_err(|e| std::io::Error::other(e.to_string()))?;
-        ruleset = ruleset
-            .add_rule(PathBeneath::new(
-                bin_fd,
-                AccessFs::ReadFile | AccessFs::ReadDir,
-            ))
+        let mut ruleset = Ruleset::default()
+            .handle_access(read_write)
+            .and_then(|ruleset| ruleset.create())
             .map_err(|e| std::io::Error::other(e.to_string()))?;
 
-        // Apply the ruleset
-        match ruleset.restrict_self() {
-            Ok(_) => {
-                tracing::debug!("Landlock restrictions applied successfully");
-                Ok(())
-            }
-            Err(e) => {
-                tracing::warn!("Failed to apply Landlock restrictions: {}", e);
-                Err(std::io::Error::other(e.to_string()))
+        for (path, access) in rules {
+            if !path.exists() {
+                continue;
             }
+            let fd = PathFd::new(&path).map_err(|e| std::io::Error::other(e.to_string()))?;
+            ruleset = ruleset
+                .add_rule(PathBeneath::new(fd, access))
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
         }
+
+        Ok(ruleset)
     }
 }
 
 #[cfg(all(feature = "sandbox-landlock", target_os = "linux"))]
 impl Sandbox for LandlockSandbox {
-    fn wrap_command(&self, _cmd: &mut std::process::Command) -> std::io::Result<()> {
-        // Apply Landlock restrictions before executing the command
-        // Note: This affects the current process, not the child process
-        // Child processes inherit the Landlock restrictions
-        self.apply_restrictions()
+    fn wrap_command(&self, cmd: &mut std::process::Command) -> std::io::Result<()> {
+        use std::os::unix::process::CommandExt;
+
+        // Build the ruleset here and only enforce it in the child, so the
+        // agent process itself is never restricted.
+        let ruleset = std::sync::Mutex::new(Some(self.build_ruleset()?));
+        // SAFETY: the hook only calls landlock_restrict_self on a ruleset
+        // created before fork; it allocates only on the error path.
+        unsafe {
+            cmd.pre_exec(move || {
+                let ruleset = ruleset.lock().ok().and_then(|mut guard| guard.take());
+                match ruleset {
+                    Some(ruleset) => ruleset
+                        .restrict_self()
+                        .map(|_| ())
+                        .map_err(|e| std::io::Error::other(e.to_string())),
+                    None => Ok(()),
+                }
+            });
+        }
+
+        // Stack the syscall denylist on top of filesystem restrictions.
+        if let Ok(seccomp) = crate::security::seccomp::SeccompSandbox::new() {
+            seccomp.attach(cmd);
+        }
+        Ok(())
     }
 
     fn is_available(&self) -> bool {
@@ -145,7 +143,7 @@ impl Sandbox for LandlockSandbox {
     }
 
     fn description(&self) -> &str {
-        "Linux kernel LSM sandboxing (filesystem access control)"
+        "Linux kernel LSM sandboxing (filesystem access control + seccomp syscall filter)"
     }
 }
 
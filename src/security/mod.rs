@@ -21,6 +21,7 @@
 pub mod audit;
 #[cfg(feature = "sandbox-bubblewrap")]
 pub mod bubblewrap;
+pub mod command_rules;
 pub mod detect;
 pub mod docker;
 
@@ -29,6 +30,9 @@ pub mod domain_matcher;
 pub mod estop;
 #[cfg(target_os = "linux")]
 pub mod firejail;
+pub mod guardrails;
+pub mod injection;
+pub mod keyring;
 #[cfg(feature = "sandbox-landlock")]
 pub mod landlock;
 pub mod leak_detector;
@@ -36,8 +40,14 @@ pub mod otp;
 pub mod pairing;
 pub mod policy;
 pub mod prompt_guard;
+pub mod redaction;
+pub mod roles;
+pub mod seccomp;
+pub mod secret_refs;
 pub mod secrets;
+pub mod storage_encryption;
 pub mod traits;
+pub mod workspace;
 
 #[allow(unused_imports)]
 pub use audit::{AuditEvent, AuditEventType, AuditLogger};
@@ -47,10 +57,15 @@ pub use domain_matcher::DomainMatcher;
 #[allow(unused_imports)]
 pub use estop::{EstopLevel, EstopManager, EstopState, ResumeSelector};
 #[allow(unused_imports)]
+pub use guardrails::{GuardrailVerdict, Guardrails};
+#[allow(unused_imports)]
 pub use otp::OtpValidator;
 #[allow(unused_imports)]
 pub use pairing::PairingGuard;
-pub use policy::{AutonomyLevel, SecurityPolicy};
+pub use policy::{AutonomyLevel, PolicyLists, SecurityPolicy};
+pub use roles::Role;
+#[allow(unused_imports)]
+pub use secret_refs::{SecretResolver, SecretResolvers};
 #[allow(unused_imports)]
 pub use secrets::SecretStore;
 #[allow(unused_imports)]
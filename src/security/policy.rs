@@ -1,7 +1,11 @@
+use super::command_rules;
+use crate::config::CommandRulesConfig;
 use parking_lot::Mutex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 use std::time::Instant;
 
 /// How much autonomy the agent has
@@ -84,16 +88,66 @@ pub struct SecurityPolicy {
     pub workspace_dir: PathBuf,
     pub workspace_only: bool,
     pub allowed_commands: Vec<String>,
+    pub denied_commands: Vec<String>,
     pub forbidden_paths: Vec<String>,
     pub allowed_roots: Vec<PathBuf>,
+    pub command_rules: CommandRulesConfig,
     pub max_actions_per_hour: u32,
     pub max_cost_per_day_cents: u32,
     pub require_approval_for_medium_risk: bool,
     pub block_high_risk_commands: bool,
     pub shell_env_passthrough: Vec<String>,
+    pub shell_timeout_secs: u64,
+    pub shell_max_output_bytes: usize,
+    pub workspace_quota_mb: u64,
     pub tracker: ActionTracker,
 }
 
+/// Command and path lists that a config reload replaces in running policies.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyLists {
+    pub allowed_commands: Vec<String>,
+    pub denied_commands: Vec<String>,
+    pub forbidden_paths: Vec<String>,
+    pub allowed_roots: Vec<PathBuf>,
+    pub command_rules: CommandRulesConfig,
+}
+
+impl PolicyLists {
+    pub fn from_config(
+        autonomy_config: &crate::config::AutonomyConfig,
+        workspace_dir: &Path,
+    ) -> Self {
+        Self {
+            allowed_commands: autonomy_config.allowed_commands.clone(),
+            denied_commands: autonomy_config.denied_commands.clone(),
+            forbidden_paths: autonomy_config.forbidden_paths.clone(),
+            allowed_roots: autonomy_config
+                .allowed_roots
+                .iter()
+                .map(|root| {
+                    let expanded = expand_user_path(root);
+                    if expanded.is_absolute() {
+                        expanded
+                    } else {
+                        workspace_dir.join(expanded)
+                    }
+                })
+                .collect(),
+            command_rules: autonomy_config.command_rules.clone(),
+        }
+    }
+}
+
+/// Lists applied by config reloads, keyed by workspace. Policies are cloned
+/// into every tool at startup, so they look their workspace up here instead
+/// of being rebuilt.
+fn reloaded_lists() -> &'static parking_lot::RwLock<HashMap<PathBuf, Arc<PolicyLists>>> {
+    static LISTS: OnceLock<parking_lot::RwLock<HashMap<PathBuf, Arc<PolicyLists>>>> =
+        OnceLock::new();
+    LISTS.get_or_init(|| parking_lot::RwLock::new(HashMap::new()))
+}
+
 impl Default for SecurityPolicy {
     fn default() -> Self {
         Self {
@@ -115,6 +169,7 @@ impl Default for SecurityPolicy {
                 "tail".into(),
                 "date".into(),
             ],
+            denied_commands: Vec::new(),
             forbidden_paths: vec![
                 // System directories (blocked even when workspace_only=false)
                 "/etc".into(),
@@ -138,11 +193,15 @@ impl Default for SecurityPolicy {
                 "~/.config".into(),
             ],
             allowed_roots: Vec::new(),
+            command_rules: CommandRulesConfig::default(),
             max_actions_per_hour: 20,
             max_cost_per_day_cents: 500,
             require_approval_for_medium_risk: true,
             block_high_risk_commands: true,
             shell_env_passthrough: vec![],
+            shell_timeout_secs: 60,
+            shell_max_output_bytes: 1_048_576,
+            workspace_quota_mb: 0,
             tracker: ActionTracker::new(),
         }
     }
@@ -661,9 +720,10 @@ impl SecurityPolicy {
     // ── Command Execution Policy Gate ──────────────────────────────────────
     // Validation follows a strict precedence order:
     //   1. Allowlist check (is the base command permitted at all?)
-    //   2. Risk classification (high / medium / low)
-    //   3. Policy flags (block_high_risk_commands, require_approval_for_medium_risk)
-    //   4. Autonomy level × approval status (supervised requires explicit approval)
+    //   2. Command rules (argument-level allow/deny, `[autonomy.command_rules]`)
+    //   3. Risk classification (high / medium / low)
+    //   4. Policy flags (block_high_risk_commands, require_approval_for_medium_risk)
+    //   5. Autonomy level × approval status (supervised requires explicit approval)
     // This ordering ensures deny-by-default: unknown commands are rejected
     // before any risk or autonomy logic runs.
 
@@ -673,9 +733,10 @@ impl SecurityPolicy {
         command: &str,
         approved: bool,
     ) -> Result<CommandRiskLevel, String> {
-        if !self.is_command_allowed(command) {
+        if !self.passes_allowlist(command) {
             return Err(format!("Command not allowed by security policy: {command}"));
         }
+        self.enforce_command_rules(command)?;
 
         let risk = self.command_risk_level(command);
 
@@ -718,7 +779,32 @@ impl SecurityPolicy {
     /// - Blocks single `&` background chaining (`&&` remains supported)
     /// - Blocks shell redirections (`<`, `>`, `>>`) that can bypass path policy
     /// - Blocks dangerous arguments (e.g. `find -exec`, `git config`)
+    /// - Applies `[autonomy.command_rules]`
     pub fn is_command_allowed(&self, command: &str) -> bool {
+        self.passes_allowlist(command) && self.enforce_command_rules(command).is_ok()
+    }
+
+    /// Check `[autonomy.command_rules]`. In dry-run mode a violation is
+    /// logged and the command allowed.
+    fn enforce_command_rules(&self, command: &str) -> Result<(), String> {
+        let reloaded = self.reloaded();
+        let rules = reloaded
+            .as_deref()
+            .map_or(&self.command_rules, |lists| &lists.command_rules);
+        let Some(reason) = command_rules::violation(rules, command) else {
+            return Ok(());
+        };
+        if rules.dry_run {
+            tracing::warn!(
+                command,
+                "Command rules would block this command (dry run): {reason}"
+            );
+            return Ok(());
+        }
+        Err(format!("Command blocked by command rules: {reason}"))
+    }
+
+    fn passes_allowlist(&self, command: &str) -> bool {
         if self.autonomy == AutonomyLevel::ReadOnly {
             return false;
         }
@@ -758,6 +844,13 @@ impl SecurityPolicy {
             return false;
         }
 
+        let reloaded = self.reloaded();
+        let (allowed_commands, denied_commands) = reloaded
+            .as_deref()
+            .map_or((&self.allowed_commands, &self.denied_commands), |lists| {
+                (&lists.allowed_commands, &lists.denied_commands)
+            });
+
         // Split on unquoted command separators and validate each sub-command.
         let segments = split_unquoted_segments(command);
         for segment in &segments {
@@ -772,8 +865,15 @@ impl SecurityPolicy {
                 continue;
             }
 
-            if !self
-                .allowed_commands
+            // The denylist wins over the allowlist, including `"*"`.
+            if denied_commands
+                .iter()
+                .any(|denied| is_allowlist_entry_match(denied, executable, base_cmd))
+            {
+                return false;
+            }
+
+            if !allowed_commands
                 .iter()
                 .any(|allowed| is_allowlist_entry_match(allowed, executable, base_cmd))
             {
@@ -928,7 +1028,11 @@ impl SecurityPolicy {
         }
 
         // Block forbidden paths using path-component-aware matching
-        for forbidden in &self.forbidden_paths {
+        let reloaded = self.reloaded();
+        let forbidden_paths = reloaded
+            .as_deref()
+            .map_or(&self.forbidden_paths, |lists| &lists.forbidden_paths);
+        for forbidden in forbidden_paths {
             let forbidden_path = expand_user_path(forbidden);
             if expanded_path.starts_with(forbidden_path) {
                 return false;
@@ -951,10 +1055,17 @@ impl SecurityPolicy {
             return true;
         }
 
+        let reloaded = self.reloaded();
+        let (allowed_roots, forbidden_paths) = reloaded
+            .as_deref()
+            .map_or((&self.allowed_roots, &self.forbidden_paths), |lists| {
+                (&lists.allowed_roots, &lists.forbidden_paths)
+            });
+
         // Check extra allowed roots (e.g. shared skills directories) before
         // forbidden checks so explicit allowlists can coexist with broad
         // default forbidden roots such as `/home` and `/tmp`.
-        for root in &self.allowed_roots {
+        for root in allowed_roots {
             let canonical = root.canonicalize().unwrap_or_else(|_| root.clone());
             if resolved.starts_with(&canonical) {
                 return true;
@@ -963,7 +1074,7 @@ impl SecurityPolicy {
 
         // For paths outside workspace/allowlist, block forbidden roots to
         // prevent symlink escapes and sensitive directory access.
-        for forbidden in &self.forbidden_paths {
+        for forbidden in forbidden_paths {
             let forbidden_path = expand_user_path(forbidden);
             if resolved.starts_with(&forbidden_path) {
                 return false;
@@ -1042,34 +1153,48 @@ impl SecurityPolicy {
         self.tracker.count() >= self.max_actions_per_hour as usize
     }
 
+    /// Replace the command and path lists of every policy for `workspace_dir`,
+    /// including policies already held by tools. Used by config hot reload.
+    pub fn apply_reloaded_lists(lists: PolicyLists, workspace_dir: &Path) {
+        reloaded_lists()
+            .write()
+            .insert(workspace_dir.to_path_buf(), Arc::new(lists));
+    }
+
+    /// Lists from the latest config reload for this workspace, if any.
+    fn reloaded(&self) -> Option<Arc<PolicyLists>> {
+        reloaded_lists().read().get(&self.workspace_dir).cloned()
+    }
+
     /// Build from config sections
     pub fn from_config(
         autonomy_config: &crate::config::AutonomyConfig,
         workspace_dir: &Path,
     ) -> Self {
+        let PolicyLists {
+            allowed_commands,
+            denied_commands,
+            forbidden_paths,
+            allowed_roots,
+            command_rules,
+        } = PolicyLists::from_config(autonomy_config, workspace_dir);
         Self {
             autonomy: autonomy_config.level,
             workspace_dir: workspace_dir.to_path_buf(),
             workspace_only: autonomy_config.workspace_only,
-            allowed_commands: autonomy_config.allowed_commands.clone(),
-            forbidden_paths: autonomy_config.forbidden_paths.clone(),
-            allowed_roots: autonomy_config
-                .allowed_roots
-                .iter()
-                .map(|root| {
-                    let expanded = expand_user_path(root);
-                    if expanded.is_absolute() {
-                        expanded
-                    } else {
-                        workspace_dir.join(expanded)
-                    }
-                })
-                .collect(),
+            allowed_commands,
+            denied_commands,
+            forbidden_paths,
+            allowed_roots,
+            command_rules,
             max_actions_per_hour: autonomy_config.max_actions_per_hour,
             max_cost_per_day_cents: autonomy_config.max_cost_per_day_cents,
             require_approval_for_medium_risk: autonomy_config.require_approval_for_medium_risk,
             block_high_risk_commands: autonomy_config.block_high_risk_commands,
             shell_env_passthrough: autonomy_config.shell_env_passthrough.clone(),
+            shell_timeout_secs: autonomy_config.shell_timeout_secs,
+            shell_max_output_bytes: autonomy_config.shell_max_output_bytes,
+            workspace_quota_mb: autonomy_config.workspace_quota_mb,
             tracker: ActionTracker::new(),
         }
     }
@@ -1231,6 +1356,48 @@ mod tests {
         assert!(blocked.unwrap_err().contains("high-risk"));
     }
 
+    #[test]
+    fn command_rules_block_unless_dry_run() {
+        let mut p = SecurityPolicy {
+            allowed_commands: vec!["*".into()],
+            command_rules: CommandRulesConfig {
+                allow: vec!["git *".into(), "cargo build".into()],
+                deny: vec!["git push --force*".into()],
+                dry_run: false,
+            },
+            ..default_policy()
+        };
+        assert!(p.is_command_allowed("git status"));
+        assert!(!p.is_command_allowed("git push --force"));
+        assert!(!p.is_command_allowed("cargo build --release"));
+        assert_eq!(
+            p.validate_command_execution("cargo build --release", true)
+                .unwrap_err(),
+            "Command blocked by command rules: `cargo build --release` matches no allow rule"
+        );
+
+        p.command_rules.dry_run = true;
+        assert!(p.is_command_allowed("git push --force"));
+        assert!(p.is_command_allowed("cargo build --release"));
+    }
+
+    #[test]
+    fn denylist_overrides_allowlist_and_wildcard() {
+        let p = SecurityPolicy {
+            allowed_commands: vec!["*".into()],
+            denied_commands: vec!["curl".into(), "/usr/bin/wget".into()],
+            ..SecurityPolicy::default()
+        };
+
+        assert!(p.is_command_allowed("python3 --version"));
+        assert!(!p.is_command_allowed("curl https://example.com"));
+        assert!(!p.is_command_allowed("/opt/bin/curl https://example.com"));
+        assert!(!p.is_command_allowed("ls && curl https://example.com"));
+        assert!(!p.is_command_allowed("/usr/bin/wget https://example.com"));
+        // Path-like deny entries only match that exact path.
+        assert!(p.is_command_allowed("wget https://example.com"));
+    }
+
     #[test]
     fn empty_command_blocked() {
         let p = default_policy();
@@ -1450,6 +1617,34 @@ mod tests {
         assert_eq!(policy.workspace_dir, PathBuf::from("/tmp/test-workspace"));
     }
 
+    #[test]
+    fn reloaded_lists_apply_to_existing_policies_of_that_workspace() {
+        let workspace = PathBuf::from("/tmp/zeroclaw-reload-policy-test");
+        let autonomy_config = crate::config::AutonomyConfig {
+            allowed_commands: vec!["git".into()],
+            workspace_only: false,
+            ..crate::config::AutonomyConfig::default()
+        };
+        let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
+        let other = SecurityPolicy::from_config(&autonomy_config, Path::new("/tmp/other-ws"));
+        assert!(!policy.is_command_allowed("docker ps"));
+        assert!(policy.is_path_allowed("/secret/key"));
+
+        let reloaded = crate::config::AutonomyConfig {
+            allowed_commands: vec!["docker".into()],
+            forbidden_paths: vec!["/secret".into()],
+            ..crate::config::AutonomyConfig::default()
+        };
+        SecurityPolicy::apply_reloaded_lists(
+            PolicyLists::from_config(&reloaded, &workspace),
+            &workspace,
+        );
+        assert!(policy.is_command_allowed("docker ps"));
+        assert!(!policy.is_command_allowed("git status"));
+        assert!(!policy.is_path_allowed("/secret/key"));
+        assert!(!other.is_command_allowed("docker ps"));
+    }
+
     #[test]
     fn from_config_normalizes_allowed_roots() {
         let autonomy_config = crate::config::AutonomyConfig {
@@ -0,0 +1,191 @@
+//! Role-based access for chat channel users (`[security.roles]`).
+//!
+//! Every tool and slash command has a minimum [`Role`]: tools declare theirs
+//! with [`Tool::min_role`] (read-only tools are open to members, anything
+//! that changes state or runs code needs an admin) and commands in
+//! [`COMMANDS`](crate::agent::commands::COMMANDS); `[security.roles.tools]`
+//! and `[security.roles.commands]` override them. Channels resolve the
+//! sender's role with [`resolve`] and run the turn inside [`run_with`]; the
+//! tool dispatcher refuses calls above [`current`]. Gateway clients run as
+//! `[gateway] role`. Work started outside any scope (the local CLI, cron)
+//! runs as admin.
+
+use crate::config::RolesConfig;
+use crate::tools::Tool;
+use parking_lot::RwLock;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+
+/// What a chat channel user may do. Each role can do everything the one
+/// before it can.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Conversation and basic slash commands, no tools.
+    Guest,
+    /// Read-only tools. Pairing stored this role as `user`.
+    #[default]
+    #[serde(alias = "user")]
+    Member,
+    /// Every tool and command, including shell.
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Guest => "guest",
+            Self::Member => "member",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "guest" => Ok(Self::Guest),
+            "member" | "user" => Ok(Self::Member),
+            "admin" => Ok(Self::Admin),
+            other => anyhow::bail!("unknown role '{other}' (expected admin, member or guest)"),
+        }
+    }
+}
+
+tokio::task_local! {
+    static CURRENT_ROLE: Role;
+}
+
+static CONFIG: OnceLock<RwLock<Arc<RolesConfig>>> = OnceLock::new();
+
+fn config_lock() -> &'static RwLock<Arc<RolesConfig>> {
+    CONFIG.get_or_init(|| RwLock::new(Arc::new(RolesConfig::default())))
+}
+
+/// Use `[security.roles]` from now on.
+pub fn configure(config: &RolesConfig) {
+    *config_lock().write() = Arc::new(config.clone());
+}
+
+/// Run `fut` as a user with `role`.
+pub async fn run_with<F: Future>(role: Role, fut: F) -> F::Output {
+    CURRENT_ROLE.scope(role, fut).await
+}
+
+/// Role of the user the current turn answers; admin outside a channel turn.
+pub fn current() -> Role {
+    scoped().unwrap_or(Role::Admin)
+}
+
+/// The role set by an enclosing [`run_with`], if any: `Some` means the work
+/// answers a remote user rather than the local operator.
+pub fn scoped() -> Option<Role> {
+    CURRENT_ROLE.try_with(|role| *role).ok()
+}
+
+/// Role of `sender` on `channel`: their `[security.roles.users]` entry,
+/// else the role they were paired with, else `[security.roles] default`.
+pub fn resolve(channel: &str, sender: &str, paired: Option<Role>) -> Role {
+    let config = config_lock().read();
+    config
+        .users
+        .get(&format!("{channel}:{sender}"))
+        .copied()
+        .or(paired)
+        .unwrap_or(config.default)
+}
+
+/// Minimum role for `tool`, after `[security.roles.tools]`.
+pub fn tool_min_role(tool: &dyn Tool) -> Role {
+    config_lock()
+        .read()
+        .tools
+        .get(tool.name())
+        .copied()
+        .unwrap_or_else(|| tool.min_role())
+}
+
+/// Minimum role for the slash command `name` (e.g. `/model`), after
+/// `[security.roles.commands]`.
+pub fn command_min_role(name: &str, declared: Role) -> Role {
+    let key = name.trim_start_matches('/');
+    config_lock()
+        .read()
+        .commands
+        .iter()
+        .find(|(command, _)| command.trim_start_matches('/') == key)
+        .map_or(declared, |(_, role)| *role)
+}
+
+/// Refuse `tool` when the current user's role is below its minimum.
+pub fn check_tool(tool: &dyn Tool) -> Result<(), String> {
+    let required = tool_min_role(tool);
+    if current() >= required {
+        Ok(())
+    } else {
+        Err(format!(
+            "Tool `{}` requires the {required} role; this user is a {}",
+            tool.name(),
+            current()
+        ))
+    }
+}
+
+/// Tools a user with `role` may not call, so they are left out of the prompt.
+pub fn excluded_tools<'a>(
+    role: Role,
+    tools: impl IntoIterator<Item = &'a Box<dyn Tool>>,
+) -> Vec<String> {
+    tools
+        .into_iter()
+        .filter(|tool| role < tool_min_role(tool.as_ref()))
+        .map(|tool| tool.name().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_are_ordered_and_parse_the_pairing_name() {
+        assert!(Role::Guest < Role::Member && Role::Member < Role::Admin);
+        assert_eq!("user".parse::<Role>().unwrap(), Role::Member);
+        assert_eq!("Admin".parse::<Role>().unwrap(), Role::Admin);
+        assert!("owner".parse::<Role>().is_err());
+        let parsed: Role = serde_json::from_str("\"user\"").unwrap();
+        assert_eq!(parsed, Role::Member);
+    }
+
+    #[tokio::test]
+    async fn current_role_is_scoped_to_the_turn() {
+        assert_eq!(current(), Role::Admin);
+        assert_eq!(scoped(), None);
+        let inner = run_with(Role::Guest, async { (current(), scoped()) }).await;
+        assert_eq!(inner, (Role::Guest, Some(Role::Guest)));
+        assert_eq!(current(), Role::Admin);
+    }
+}
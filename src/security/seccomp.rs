@@ -0,0 +1,259 @@
+//! Seccomp-BPF syscall filter (Linux, x86_64 / aarch64)
+//!
+//! Installs a small denylist filter in the child process just before `exec`,
+//! blocking syscalls a shell command has no business making (kernel module
+//! loading, mounts, ptrace, namespace creation, ...). Denied calls fail with
+//! `EPERM` instead of killing the process, so tools degrade gracefully.
+//! No external crates are required; the filter is assembled by hand.
+
+use crate::security::traits::Sandbox;
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod imp {
+    use std::sync::Arc;
+
+    // Classic BPF opcodes (linux/filter.h).
+    const BPF_LD: u16 = 0x00;
+    const BPF_W: u16 = 0x00;
+    const BPF_ABS: u16 = 0x20;
+    const BPF_JMP: u16 = 0x05;
+    const BPF_JEQ: u16 = 0x10;
+    const BPF_K: u16 = 0x00;
+    const BPF_RET: u16 = 0x06;
+
+    // linux/seccomp.h
+    const SECCOMP_MODE_FILTER: libc::c_ulong = 2;
+    const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+    const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+
+    // Offsets into `struct seccomp_data`.
+    const SECCOMP_DATA_NR: u32 = 0;
+    const SECCOMP_DATA_ARCH: u32 = 4;
+
+    #[cfg(target_arch = "x86_64")]
+    const AUDIT_ARCH_CURRENT: u32 = 0xc000_003e;
+    #[cfg(target_arch = "aarch64")]
+    const AUDIT_ARCH_CURRENT: u32 = 0xc000_00b7;
+
+    /// Syscalls denied to sandboxed commands.
+    pub(super) const DENIED_SYSCALLS: &[libc::c_long] = &[
+        libc::SYS_ptrace,
+        libc::SYS_process_vm_readv,
+        libc::SYS_process_vm_writev,
+        libc::SYS_mount,
+        libc::SYS_umount2,
+        libc::SYS_pivot_root,
+        libc::SYS_unshare,
+        libc::SYS_setns,
+        libc::SYS_reboot,
+        libc::SYS_kexec_load,
+        libc::SYS_init_module,
+        libc::SYS_finit_module,
+        libc::SYS_delete_module,
+        libc::SYS_swapon,
+        libc::SYS_swapoff,
+        libc::SYS_bpf,
+        libc::SYS_perf_event_open,
+        libc::SYS_keyctl,
+        libc::SYS_add_key,
+        libc::SYS_request_key,
+        libc::SYS_open_by_handle_at,
+        libc::SYS_userfaultfd,
+    ];
+
+    fn stmt(code: u16, k: u32) -> libc::sock_filter {
+        libc::sock_filter {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        }
+    }
+
+    fn jump(code: u16, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+        libc::sock_filter { code, jt, jf, k }
+    }
+
+    /// Assemble the filter program.
+    ///
+    /// Layout: reject foreign-architecture syscalls, load the syscall number,
+    /// then one `JEQ nr` / `RET ERRNO` pair per denied syscall, ending in
+    /// `RET ALLOW`.
+    pub(super) fn build_filter() -> Vec<libc::sock_filter> {
+        let deny = SECCOMP_RET_ERRNO | (libc::EPERM as u32 & 0xffff);
+        let mut program = vec![
+            stmt(BPF_LD | BPF_W | BPF_ABS, SECCOMP_DATA_ARCH),
+            jump(BPF_JMP | BPF_JEQ | BPF_K, AUDIT_ARCH_CURRENT, 1, 0),
+            stmt(BPF_RET | BPF_K, deny),
+            stmt(BPF_LD | BPF_W | BPF_ABS, SECCOMP_DATA_NR),
+        ];
+        for &nr in DENIED_SYSCALLS {
+            program.push(jump(BPF_JMP | BPF_JEQ | BPF_K, nr as u32, 0, 1));
+            program.push(stmt(BPF_RET | BPF_K, deny));
+        }
+        program.push(stmt(BPF_RET | BPF_K, SECCOMP_RET_ALLOW));
+        program
+    }
+
+    /// Install `filter` on the calling thread.
+    ///
+    /// Runs between `fork` and `exec`, so it must not allocate.
+    pub(super) fn install(filter: &[libc::sock_filter]) -> std::io::Result<()> {
+        let prog = libc::sock_fprog {
+            len: u16::try_from(filter.len())
+                .map_err(|_| std::io::Error::other("filter too long"))?,
+            filter: filter.as_ptr().cast_mut(),
+        };
+        // SAFETY: plain prctl calls; `prog` outlives the call and points at a
+        // valid, immutable BPF program.
+        unsafe {
+            if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            if libc::prctl(
+                libc::PR_SET_SECCOMP,
+                SECCOMP_MODE_FILTER,
+                &prog as *const libc::sock_fprog,
+            ) != 0
+            {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+
+    /// Seccomp is usable when the kernel reports a `Seccomp:` status line.
+    pub(super) fn supported() -> bool {
+        std::fs::read_to_string("/proc/self/status")
+            .map(|status| status.lines().any(|line| line.starts_with("Seccomp:")))
+            .unwrap_or(false)
+    }
+
+    /// Register the filter to be installed in the child of `cmd`.
+    pub(super) fn attach(cmd: &mut std::process::Command, filter: Arc<Vec<libc::sock_filter>>) {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: the closure only issues prctl syscalls on pre-built data.
+        unsafe {
+            cmd.pre_exec(move || install(&filter));
+        }
+    }
+}
+
+/// Seccomp syscall-denylist sandbox backend.
+#[derive(Clone)]
+pub struct SeccompSandbox {
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    filter: std::sync::Arc<Vec<libc::sock_filter>>,
+}
+
+impl SeccompSandbox {
+    /// Build the filter, failing when seccomp is unavailable on this platform.
+    pub fn new() -> std::io::Result<Self> {
+        #[cfg(all(
+            target_os = "linux",
+            any(target_arch = "x86_64", target_arch = "aarch64")
+        ))]
+        {
+            if imp::supported() {
+                return Ok(Self {
+                    filter: std::sync::Arc::new(imp::build_filter()),
+                });
+            }
+        }
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "seccomp is only supported on Linux (x86_64/aarch64)",
+        ))
+    }
+
+    /// Probe if seccomp is available (for auto-detection)
+    pub fn probe() -> std::io::Result<Self> {
+        Self::new()
+    }
+
+    /// Install the syscall filter in `cmd`'s child before `exec`.
+    ///
+    /// Exposed separately so other backends (e.g. Landlock) can stack it.
+    pub fn attach(&self, cmd: &mut std::process::Command) {
+        #[cfg(all(
+            target_os = "linux",
+            any(target_arch = "x86_64", target_arch = "aarch64")
+        ))]
+        imp::attach(cmd, std::sync::Arc::clone(&self.filter));
+        #[cfg(not(all(
+            target_os = "linux",
+            any(target_arch = "x86_64", target_arch = "aarch64")
+        )))]
+        let _ = cmd;
+    }
+}
+
+impl Sandbox for SeccompSandbox {
+    fn wrap_command(&self, cmd: &mut std::process::Command) -> std::io::Result<()> {
+        self.attach(cmd);
+        Ok(())
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(all(
+            target_os = "linux",
+            any(target_arch = "x86_64", target_arch = "aarch64")
+        ))
+    }
+
+    fn name(&self) -> &str {
+        "seccomp"
+    }
+
+    fn description(&self) -> &str {
+        "Linux seccomp-BPF syscall denylist (no mounts, ptrace, module loading or namespaces)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    #[test]
+    fn filter_has_pair_per_denied_syscall() {
+        let filter = imp::build_filter();
+        assert_eq!(filter.len(), 4 + imp::DENIED_SYSCALLS.len() * 2 + 1);
+        assert_eq!(filter.last().map(|f| f.k), Some(0x7fff_0000));
+    }
+
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    #[test]
+    fn sandboxed_child_still_runs_ordinary_commands() {
+        let Ok(sandbox) = SeccompSandbox::new() else {
+            return;
+        };
+        let mut cmd = std::process::Command::new("sh");
+        cmd.arg("-c").arg("echo ok");
+        sandbox.wrap_command(&mut cmd).unwrap();
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+    }
+
+    #[cfg(not(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    )))]
+    #[test]
+    fn seccomp_unsupported_elsewhere() {
+        assert!(SeccompSandbox::new().is_err());
+    }
+}
@@ -0,0 +1,298 @@
+// Secret references in config values.
+//
+// Any secret field in config.toml may hold a reference instead of the secret
+// itself:
+//
+//   api_key = "env:OPENAI_API_KEY"          environment variable
+//   api_key = "file:/run/secrets/openai"    file contents (trailing newline trimmed)
+//   api_key = "keyring:openai"              OS keyring, service `[secrets].keyring_service`
+//   api_key = "op://Private/OpenAI/key"     1Password CLI (`op read`)
+//
+// References are resolved when the config is loaded. Each scheme is a
+// `SecretResolver`; register more with `SecretResolvers::register`.
+//
+// Resolved values are remembered so that saving the config writes the
+// reference back instead of the secret it pointed to.
+
+use crate::config::SecretsConfig;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// Resolves references of one scheme, e.g. `env:NAME`.
+pub trait SecretResolver: Send + Sync {
+    /// Scheme prefix without the colon, e.g. `"env"`.
+    fn scheme(&self) -> &str;
+
+    /// Resolve a full reference (`env:NAME`) to the secret it names.
+    fn resolve(&self, reference: &str) -> Result<String>;
+}
+
+/// `env:NAME`
+pub struct EnvResolver;
+
+impl SecretResolver for EnvResolver {
+    fn scheme(&self) -> &str {
+        "env"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String> {
+        let name = target(reference);
+        std::env::var(name).with_context(|| format!("environment variable {name} is not set"))
+    }
+}
+
+/// `file:/path/to/secret`, relative paths resolve against the config directory.
+pub struct FileResolver {
+    base_dir: std::path::PathBuf,
+}
+
+impl SecretResolver for FileResolver {
+    fn scheme(&self) -> &str {
+        "file"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String> {
+        let expanded = shellexpand::tilde(target(reference)).into_owned();
+        let path = self.base_dir.join(expanded);
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+        Ok(contents.trim_end_matches(['\r', '\n']).to_string())
+    }
+}
+
+/// `keyring:account`, looked up under the configured keyring service.
+pub struct KeyringResolver {
+    service: String,
+}
+
+impl SecretResolver for KeyringResolver {
+    fn scheme(&self) -> &str {
+        "keyring"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String> {
+        keyring_lookup(&self.service, target(reference))
+    }
+}
+
+/// `op://vault/item/field`, read with the 1Password CLI.
+pub struct OnePasswordResolver {
+    binary: String,
+}
+
+impl SecretResolver for OnePasswordResolver {
+    fn scheme(&self) -> &str {
+        "op"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String> {
+        let output = std::process::Command::new(&self.binary)
+            .args(["read", "--no-newline", reference])
+            .output()
+            .with_context(|| format!("failed to run `{}`", self.binary))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "`{} read` failed: {}",
+                self.binary,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// The set of schemes recognized in config values.
+pub struct SecretResolvers {
+    resolvers: Vec<Box<dyn SecretResolver>>,
+}
+
+impl SecretResolvers {
+    /// The built-in resolvers, configured from `[secrets]`. `config_dir` is
+    /// the directory holding config.toml.
+    pub fn from_config(config: &SecretsConfig, config_dir: &Path) -> Self {
+        Self {
+            resolvers: vec![
+                Box::new(EnvResolver),
+                Box::new(FileResolver {
+                    base_dir: config_dir.to_path_buf(),
+                }),
+                Box::new(KeyringResolver {
+                    service: config.keyring_service.clone(),
+                }),
+                Box::new(OnePasswordResolver {
+                    binary: config.op_path.clone(),
+                }),
+            ],
+        }
+    }
+
+    /// Add a resolver; it takes precedence over any existing one with the same scheme.
+    pub fn register(&mut self, resolver: Box<dyn SecretResolver>) {
+        self.resolvers.insert(0, resolver);
+    }
+
+    fn resolver_for(&self, value: &str) -> Option<&dyn SecretResolver> {
+        let (scheme, _) = value.split_once(':')?;
+        self.resolvers
+            .iter()
+            .find(|r| r.scheme() == scheme)
+            .map(|r| r.as_ref())
+    }
+
+    /// Whether `value` is a reference to a registered scheme.
+    pub fn is_reference(&self, value: &str) -> bool {
+        self.resolver_for(value).is_some()
+    }
+
+    /// Replace a reference with the secret it names; other values are left
+    /// unchanged. `field_name` is used in error messages.
+    pub fn resolve_in_place(&self, value: &mut String, field_name: &str) -> Result<()> {
+        let Some(resolver) = self.resolver_for(value) else {
+            return Ok(());
+        };
+        let resolved = resolver
+            .resolve(value)
+            .with_context(|| format!("Failed to resolve {field_name} ({value})"))?;
+        remember(&resolved, value);
+        *value = resolved;
+        Ok(())
+    }
+
+    /// Like [`Self::resolve_in_place`] for optional fields.
+    pub fn resolve_optional(&self, value: &mut Option<String>, field_name: &str) -> Result<()> {
+        match value {
+            Some(value) => self.resolve_in_place(value, field_name),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The part of a reference after `scheme:`.
+fn target(reference: &str) -> &str {
+    reference
+        .split_once(':')
+        .map_or(reference, |(_, rest)| rest)
+        .trim()
+}
+
+/// Resolved secret → the reference it came from.
+fn resolved_references() -> &'static Mutex<HashMap<String, String>> {
+    static RESOLVED: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    RESOLVED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn remember(resolved: &str, reference: &str) {
+    if resolved.is_empty() {
+        return;
+    }
+    resolved_references()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(resolved.to_string(), reference.to_string());
+}
+
+/// The reference `value` was resolved from at load time, if any. Used when
+/// saving so a resolved secret is written back as its reference.
+pub fn reference_for(value: &str) -> Option<String> {
+    resolved_references()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(value)
+        .cloned()
+}
+
+/// Read `account` under `service` from the OS keyring. On Linux, entries
+/// stored with `secret-tool store ... service <service> account <account>`
+/// are found as well.
+pub(crate) fn keyring_lookup(service: &str, account: &str) -> Result<String> {
+    if let Some(secret) = super::keyring::get(service, account)? {
+        return Ok(secret);
+    }
+    if cfg!(target_os = "linux") {
+        let output = std::process::Command::new("secret-tool")
+            .args(["lookup", "service", service, "account", account])
+            .output()
+            .ok()
+            .filter(|output| output.status.success());
+        if let Some(output) = output {
+            return Ok(String::from_utf8_lossy(&output.stdout)
+                .trim_end_matches(['\r', '\n'])
+                .to_string());
+        }
+    }
+    anyhow::bail!("no secret in the OS keyring for service '{service}', account '{account}'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn resolvers(dir: &Path) -> SecretResolvers {
+        SecretResolvers::from_config(&SecretsConfig::default(), dir)
+    }
+
+    #[test]
+    fn env_and_file_references_resolve() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("token"), "s3cret\n").unwrap();
+        std::env::set_var("ZEROCLAW_TEST_SECRET_REF", "from-env");
+        let resolvers = resolvers(tmp.path());
+
+        let mut value = "env:ZEROCLAW_TEST_SECRET_REF".to_string();
+        resolvers.resolve_in_place(&mut value, "api_key").unwrap();
+        assert_eq!(value, "from-env");
+        assert_eq!(
+            reference_for("from-env").as_deref(),
+            Some("env:ZEROCLAW_TEST_SECRET_REF")
+        );
+
+        let mut value = Some("file:token".to_string());
+        resolvers.resolve_optional(&mut value, "api_key").unwrap();
+        assert_eq!(value.as_deref(), Some("s3cret"));
+    }
+
+    #[test]
+    fn plain_values_and_unknown_schemes_are_untouched() {
+        let resolvers = resolvers(Path::new("."));
+        for raw in ["sk-plain", "postgres://user@host/db", "enc2:abcd"] {
+            let mut value = raw.to_string();
+            resolvers.resolve_in_place(&mut value, "x").unwrap();
+            assert_eq!(value, raw);
+            assert!(!resolvers.is_reference(raw));
+        }
+        assert!(resolvers.is_reference("op://vault/item/field"));
+    }
+
+    #[test]
+    fn missing_env_reference_names_field_and_variable() {
+        let resolvers = resolvers(Path::new("."));
+        let mut value = "env:ZEROCLAW_TEST_SECRET_REF_UNSET".to_string();
+        let err = resolvers
+            .resolve_in_place(&mut value, "config.api_key")
+            .unwrap_err();
+        let message = format!("{err:#}");
+        assert!(message.contains("config.api_key"));
+        assert!(message.contains("ZEROCLAW_TEST_SECRET_REF_UNSET"));
+    }
+
+    #[test]
+    fn registered_resolver_handles_its_scheme() {
+        struct Fixed;
+        impl SecretResolver for Fixed {
+            fn scheme(&self) -> &str {
+                "vault"
+            }
+            fn resolve(&self, reference: &str) -> Result<String> {
+                Ok(format!("resolved:{}", target(reference)))
+            }
+        }
+        let mut resolvers = resolvers(Path::new("."));
+        resolvers.register(Box::new(Fixed));
+        let mut value = "vault:kv/openai".to_string();
+        resolvers.resolve_in_place(&mut value, "api_key").unwrap();
+        assert_eq!(value, "resolved:kv/openai");
+    }
+}
@@ -0,0 +1,215 @@
+// Encryption at rest for the local SQLite databases.
+//
+// When `[storage.encryption]` is enabled, the conversation store, the task
+// store, the outbound message queue and the memory databases are opened
+// through SQLCipher with a passphrase from the environment, a terminal
+// prompt, or the OS keyring. SQLCipher derives the page key from the
+// passphrase (PBKDF2-HMAC-SHA512), so nothing but ciphertext ever reaches
+// the disk.
+//
+// The key is resolved once at startup by `init` and kept for the process.
+// Databases that still hold plaintext are exported into an encrypted copy
+// the first time they are opened with a key.
+
+use crate::config::{StorageEncryptionConfig, StorageKeySource};
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Header of every unencrypted SQLite database file.
+const PLAINTEXT_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Keyring account under `keyring_service` that holds the passphrase.
+const KEYRING_ACCOUNT: &str = "storage";
+
+static DB_KEY: OnceLock<Option<String>> = OnceLock::new();
+
+/// Resolve the database key from config. Call once at startup, before any
+/// database is opened; later calls are ignored.
+pub fn init(config: &StorageEncryptionConfig) -> Result<()> {
+    if DB_KEY.get().is_some() {
+        return Ok(());
+    }
+    let key = if config.enabled {
+        Some(resolve_key(config)?)
+    } else {
+        None
+    };
+    let _ = DB_KEY.set(key);
+    Ok(())
+}
+
+/// Whether databases are being opened encrypted.
+pub fn is_enabled() -> bool {
+    DB_KEY.get().is_some_and(Option::is_some)
+}
+
+/// Open `path`, decrypting it when encryption is enabled.
+pub fn open(path: &Path) -> Result<Connection> {
+    open_with_key(path, DB_KEY.get().and_then(Option::as_deref))
+}
+
+fn open_with_key(path: &Path, key: Option<&str>) -> Result<Connection> {
+    let Some(key) = key else {
+        return Ok(Connection::open(path)?);
+    };
+    if is_plaintext_database(path) {
+        encrypt_in_place(path, key)
+            .with_context(|| format!("Failed to encrypt existing database {}", path.display()))?;
+    }
+    let conn = Connection::open(path)?;
+    unlock(&conn, key).with_context(|| format!("Cannot open {}", path.display()))?;
+    Ok(conn)
+}
+
+fn unlock(conn: &Connection, key: &str) -> Result<()> {
+    conn.pragma_update(None, "key", key)?;
+    let cipher: Option<String> = conn
+        .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+        .optional()?;
+    if cipher.is_none() {
+        anyhow::bail!(
+            "storage.encryption is enabled but this build lacks SQLCipher; rebuild with `--features storage-encryption`"
+        );
+    }
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+        .context("wrong storage encryption key, or the database is corrupt")?;
+    Ok(())
+}
+
+fn is_plaintext_database(path: &Path) -> bool {
+    use std::io::Read;
+    let mut header = [0u8; 16];
+    std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .is_ok_and(|()| &header == PLAINTEXT_HEADER)
+}
+
+/// Replace a plaintext database with an encrypted copy via `sqlcipher_export`.
+fn encrypt_in_place(path: &Path, key: &str) -> Result<()> {
+    let tmp = sibling(path, "encrypting");
+    let _ = std::fs::remove_file(&tmp);
+    {
+        let conn = Connection::open(path)?;
+        let has_cipher: Option<String> = conn
+            .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+            .optional()?;
+        if has_cipher.is_none() {
+            anyhow::bail!(
+                "storage.encryption is enabled but this build lacks SQLCipher; rebuild with `--features storage-encryption`"
+            );
+        }
+        conn.execute(
+            "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+            params![tmp.to_string_lossy(), key],
+        )?;
+        conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))?;
+        conn.execute("DETACH DATABASE encrypted", [])?;
+    }
+    std::fs::rename(&tmp, path)?;
+    // The WAL and shared-memory files belong to the plaintext database.
+    for suffix in ["wal", "shm"] {
+        let _ = std::fs::remove_file(sibling(path, suffix));
+    }
+    tracing::info!("Encrypted existing database {}", path.display());
+    Ok(())
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push("-");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn resolve_key(config: &StorageEncryptionConfig) -> Result<String> {
+    let key = match config.key_source {
+        StorageKeySource::Env => std::env::var(&config.key_env).with_context(|| {
+            format!(
+                "storage.encryption uses key_source = \"env\" but {} is not set",
+                config.key_env
+            )
+        })?,
+        StorageKeySource::Prompt => {
+            if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+                anyhow::bail!(
+                    "storage.encryption uses key_source = \"prompt\" but stdin is not a terminal"
+                );
+            }
+            dialoguer::Password::new()
+                .with_prompt("Storage passphrase")
+                .interact()?
+        }
+        StorageKeySource::Keyring => {
+            super::secret_refs::keyring_lookup(&config.keyring_service, KEYRING_ACCOUNT)?
+        }
+    };
+    if key.trim().is_empty() {
+        anyhow::bail!("storage encryption passphrase is empty");
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn open_without_key_is_plain_sqlite() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("plain.db");
+        let conn = open_with_key(&path, None).unwrap();
+        conn.execute_batch("CREATE TABLE t (x INTEGER);").unwrap();
+        drop(conn);
+        assert!(is_plaintext_database(&path));
+    }
+
+    #[test]
+    fn plaintext_detection_ignores_missing_and_other_files() {
+        let tmp = TempDir::new().unwrap();
+        assert!(!is_plaintext_database(&tmp.path().join("missing.db")));
+        let other = tmp.path().join("other.db");
+        std::fs::write(&other, [7u8; 32]).unwrap();
+        assert!(!is_plaintext_database(&other));
+    }
+
+    #[test]
+    fn env_key_source_requires_variable() {
+        let config = StorageEncryptionConfig {
+            enabled: true,
+            key_env: "ZEROCLAW_TEST_DB_KEY_UNSET".into(),
+            ..StorageEncryptionConfig::default()
+        };
+        let err = resolve_key(&config).unwrap_err();
+        assert!(err.to_string().contains("ZEROCLAW_TEST_DB_KEY_UNSET"));
+    }
+
+    #[cfg(feature = "storage-encryption")]
+    #[test]
+    fn existing_database_is_encrypted_and_reopened() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("brain.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch("CREATE TABLE t (x TEXT); INSERT INTO t VALUES ('secret');")
+            .unwrap();
+
+        let conn = open_with_key(&path, Some("hunter2")).unwrap();
+        let value: String = conn.query_row("SELECT x FROM t", [], |r| r.get(0)).unwrap();
+        assert_eq!(value, "secret");
+        drop(conn);
+
+        assert!(!is_plaintext_database(&path));
+        assert!(open_with_key(&path, Some("wrong")).is_err());
+    }
+
+    #[cfg(not(feature = "storage-encryption"))]
+    #[test]
+    fn key_without_sqlcipher_is_refused() {
+        let tmp = TempDir::new().unwrap();
+        let err = open_with_key(&tmp.path().join("new.db"), Some("k")).unwrap_err();
+        assert!(format!("{err:#}").contains("storage-encryption"));
+    }
+}
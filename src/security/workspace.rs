@@ -0,0 +1,340 @@
+//! The workspace jail.
+//!
+//! Every path the agent touches goes through [`Workspace`]: file tools
+//! resolve what they read and write with it, the code interpreter gets its
+//! run directories from it, and attachments from the CLI and channels are
+//! stored through it. Paths are canonicalized and checked against the
+//! [`SecurityPolicy`] after symlinks are followed, writes never go through a
+//! symlink, writes count against `[autonomy] workspace_quota_mb`, and each
+//! session gets a temp directory under [`SESSIONS_DIR`] that is removed
+//! when the session ends (`/new`, idle archiving, `zeroclaw sessions
+//! reset`/`delete`, or the end of an unnamed `zeroclaw agent` run).
+
+use super::SecurityPolicy;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+/// Per-session temp directories live here, relative to the workspace.
+pub const SESSIONS_DIR: &str = ".zeroclaw/sessions";
+
+/// Session directory used outside a session (gateway, cron, scheduled work).
+const DEFAULT_SESSION: &str = "default";
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+tokio::task_local! {
+    static CURRENT_SESSION: String;
+}
+
+/// Run `fut` as part of `session`, so its temp files go to that session's
+/// directory.
+pub async fn run_in_session<F: Future>(session: String, fut: F) -> F::Output {
+    CURRENT_SESSION.scope(session, fut).await
+}
+
+/// Session of the current task, if any.
+pub fn current_session() -> Option<String> {
+    CURRENT_SESSION.try_with(Clone::clone).ok()
+}
+
+/// Temp directory of `session` in `workspace_dir`.
+pub fn session_dir(workspace_dir: &Path, session: &str) -> PathBuf {
+    workspace_dir
+        .join(SESSIONS_DIR)
+        .join(safe_file_name(session))
+}
+
+/// Remove the temp directory of `session`.
+pub fn end_session(workspace_dir: &Path, session: &str) {
+    let dir = session_dir(workspace_dir, session);
+    match std::fs::remove_dir_all(&dir) {
+        Ok(()) => tracing::debug!("Removed session directory {}", dir.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => tracing::warn!("Failed to remove session directory {}: {e}", dir.display()),
+    }
+}
+
+/// `name` reduced to a single path component: separators, `..` and control
+/// characters cannot survive, so untrusted names (uploaded file names,
+/// session keys) stay in the directory they are joined to.
+pub fn safe_file_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | '@' | '+') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "file".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+impl SecurityPolicy {
+    /// The workspace this policy confines tools to.
+    pub fn workspace(&self) -> Workspace<'_> {
+        Workspace { policy: self }
+    }
+}
+
+/// Path resolution, quotas and temp directories for one workspace, under
+/// the rules of its [`SecurityPolicy`].
+#[derive(Clone, Copy)]
+pub struct Workspace<'a> {
+    policy: &'a SecurityPolicy,
+}
+
+impl Workspace<'_> {
+    pub fn root(&self) -> &Path {
+        &self.policy.workspace_dir
+    }
+
+    /// Reject `path` before touching the filesystem: null bytes, `..`,
+    /// absolute paths under `workspace_only` and forbidden paths.
+    pub fn check(&self, path: &str) -> Result<(), String> {
+        if self.policy.is_path_allowed(path) {
+            Ok(())
+        } else {
+            Err(format!("Path not allowed by security policy: {path}"))
+        }
+    }
+
+    /// Resolve an existing `path` (relative to the workspace), following
+    /// symlinks, and check where it really points.
+    pub async fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        self.check(path)?;
+        let resolved = tokio::fs::canonicalize(self.root().join(path))
+            .await
+            .map_err(|e| format!("Failed to resolve file path: {e}"))?;
+        self.check_resolved(&resolved)?;
+        Ok(resolved)
+    }
+
+    /// Check an already canonicalized path.
+    pub fn check_resolved(&self, resolved: &Path) -> Result<(), String> {
+        if self.policy.is_resolved_path_allowed(resolved) {
+            Ok(())
+        } else {
+            Err(self.policy.resolved_path_violation_message(resolved))
+        }
+    }
+
+    /// Resolve `path` for writing.
+    ///
+    /// The nearest existing ancestor of the parent directory is
+    /// canonicalized and checked, and writing through a symlink is refused.
+    /// Only then, with `create_parent`, are the missing directories created,
+    /// so a symlinked ancestor can never get them made outside the
+    /// workspace; without it previews of new files never touch the
+    /// filesystem.
+    pub async fn resolve_for_write(
+        &self,
+        path: &str,
+        create_parent: bool,
+    ) -> Result<PathBuf, String> {
+        self.check(path)?;
+
+        let full_path = self.root().join(path);
+        let parent = full_path
+            .parent()
+            .ok_or_else(|| "Invalid path: missing parent directory".to_string())?;
+
+        let mut existing = parent;
+        let mut missing = Vec::new();
+        while tokio::fs::symlink_metadata(existing).await.is_err() {
+            let (Some(name), Some(up)) = (existing.file_name(), existing.parent()) else {
+                break;
+            };
+            missing.push(name.to_os_string());
+            existing = up;
+        }
+
+        let mut resolved_parent = tokio::fs::canonicalize(existing)
+            .await
+            .map_err(|e| format!("Failed to resolve file path: {e}"))?;
+        resolved_parent.extend(missing.iter().rev());
+        self.check_resolved(&resolved_parent)?;
+        if create_parent && !missing.is_empty() {
+            tokio::fs::create_dir_all(&resolved_parent)
+                .await
+                .map_err(|e| format!("Failed to create parent directory: {e}"))?;
+        }
+
+        let file_name = full_path
+            .file_name()
+            .ok_or_else(|| "Invalid path: missing file name".to_string())?;
+        let resolved_target = resolved_parent.join(file_name);
+
+        if let Ok(meta) = tokio::fs::symlink_metadata(&resolved_target).await {
+            if meta.file_type().is_symlink() {
+                return Err(format!(
+                    "Refusing to write through symlink: {}",
+                    resolved_target.display()
+                ));
+            }
+        }
+
+        Ok(resolved_target)
+    }
+
+    /// Refuse writing `bytes` to `target` when that would take the workspace
+    /// over `[autonomy] workspace_quota_mb`. Targets outside the workspace
+    /// (`allowed_roots`) are not counted.
+    pub async fn check_quota(&self, target: &Path, bytes: u64) -> Result<(), String> {
+        let quota_mb = self.policy.workspace_quota_mb;
+        if quota_mb == 0 {
+            return Ok(());
+        }
+        let root = tokio::fs::canonicalize(self.root())
+            .await
+            .unwrap_or_else(|_| self.root().to_path_buf());
+        if !target.starts_with(&root) {
+            return Ok(());
+        }
+        let used = tokio::task::spawn_blocking(move || disk_usage(&root))
+            .await
+            .unwrap_or(0);
+        let replaced = tokio::fs::metadata(target).await.map_or(0, |m| m.len());
+        let after = used.saturating_sub(replaced).saturating_add(bytes);
+        if after > quota_mb.saturating_mul(BYTES_PER_MB) {
+            return Err(format!(
+                "Workspace quota exceeded: this write would bring the workspace to {:.1} MB, \
+                 over the {quota_mb} MB limit ([autonomy] workspace_quota_mb)",
+                after as f64 / BYTES_PER_MB as f64
+            ));
+        }
+        Ok(())
+    }
+
+    /// Store an uploaded or attached file as `dir/<prefix>_<name>`, with
+    /// `name` reduced by [`safe_file_name`], and return its path.
+    pub async fn store_upload(
+        &self,
+        dir: &str,
+        name: &str,
+        bytes: &[u8],
+    ) -> Result<PathBuf, String> {
+        let prefix = &uuid::Uuid::new_v4().simple().to_string()[..8];
+        let relative = Path::new(dir).join(format!("{prefix}_{}", safe_file_name(name)));
+        let target = self
+            .resolve_for_write(&relative.to_string_lossy(), true)
+            .await?;
+        self.check_quota(&target, bytes.len() as u64).await?;
+        tokio::fs::write(&target, bytes)
+            .await
+            .map_err(|e| format!("Failed to write {}: {e}", target.display()))?;
+        Ok(target)
+    }
+
+    /// Temp directory of the current session, created on first use.
+    pub async fn session_dir(&self) -> std::io::Result<PathBuf> {
+        let session = current_session().unwrap_or_else(|| DEFAULT_SESSION.to_string());
+        let dir = session_dir(self.root(), &session);
+        tokio::fs::create_dir_all(&dir).await?;
+        Ok(dir)
+    }
+}
+
+/// Bytes used by regular files under `dir`, not following symlinks.
+fn disk_usage(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => disk_usage(&entry.path()),
+            Ok(kind) if kind.is_file() => entry.metadata().map_or(0, |m| m.len()),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(dir: &Path, quota_mb: u64) -> SecurityPolicy {
+        SecurityPolicy {
+            workspace_dir: dir.to_path_buf(),
+            workspace_quota_mb: quota_mb,
+            ..SecurityPolicy::default()
+        }
+    }
+
+    #[test]
+    fn untrusted_names_stay_one_component() {
+        assert_eq!(safe_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(safe_file_name("..\\boot.ini"), "boot.ini");
+        assert_eq!(safe_file_name(".."), "file");
+        assert_eq!(safe_file_name("my report (1).pdf"), "my_report__1_.pdf");
+        assert_eq!(safe_file_name("telegram_42@coder"), "telegram_42@coder");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlink_escapes_are_refused() {
+        let workspace = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret"), "x").unwrap();
+        std::os::unix::fs::symlink(outside.path(), workspace.path().join("link")).unwrap();
+        let policy = policy(workspace.path(), 0);
+        let ws = policy.workspace();
+
+        assert!(ws.resolve("link/secret").await.is_err());
+        assert!(ws.resolve_for_write("link/new.txt", false).await.is_err());
+        assert!(ws.resolve_for_write("link/a/new.txt", true).await.is_err());
+        assert!(
+            !outside.path().join("a").exists(),
+            "no directories are created outside the workspace"
+        );
+        let inside = ws.resolve_for_write("sub/dir/new.txt", true).await.unwrap();
+        assert!(inside.parent().unwrap().is_dir());
+        assert!(ws.resolve("../secret").await.is_err());
+        std::fs::write(workspace.path().join("ok.txt"), "x").unwrap();
+        assert!(ws.resolve("ok.txt").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn uploads_are_renamed_and_count_against_the_quota() {
+        let workspace = tempfile::tempdir().unwrap();
+        let policy = policy(workspace.path(), 1);
+        let ws = policy.workspace();
+
+        let stored = ws
+            .store_upload("uploads", "../../evil.sh", b"echo hi")
+            .await
+            .unwrap();
+        let root = workspace.path().canonicalize().unwrap();
+        assert!(stored.starts_with(root.join("uploads")));
+        assert!(stored.to_string_lossy().ends_with("_evil.sh"));
+
+        let big = vec![0u8; 2 * 1024 * 1024];
+        let err = ws
+            .store_upload("uploads", "big.bin", &big)
+            .await
+            .unwrap_err();
+        assert!(err.contains("workspace_quota_mb"));
+    }
+
+    #[tokio::test]
+    async fn session_dirs_are_removed_when_the_session_ends() {
+        let workspace = tempfile::tempdir().unwrap();
+        let policy = policy(workspace.path(), 0);
+        let dir = run_in_session("telegram_42".into(), async {
+            policy.workspace().session_dir().await.unwrap()
+        })
+        .await;
+        assert_eq!(dir, session_dir(workspace.path(), "telegram_42"));
+        assert!(dir.is_dir());
+        end_session(workspace.path(), "telegram_42");
+        assert!(!dir.exists());
+    }
+}
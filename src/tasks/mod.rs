@@ -0,0 +1,71 @@
+//! Long-running background tasks.
+//!
+//! The agent enqueues multi-step jobs with the `task` tool; the daemon's
+//! runner works through them one step at a time, checkpointing each result,
+//! and sends progress notifications to the channel the task was started from.
+
+pub mod runner;
+pub mod store;
+
+pub use store::{Task, TaskNotify, TaskStatus, TaskStore};
+
+use crate::config::Config;
+use anyhow::{bail, Result};
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn handle_command(command: crate::TaskCommands, config: &Config) -> Result<()> {
+    let store = TaskStore::open(&config.workspace_dir)?;
+    match command {
+        crate::TaskCommands::List { all, limit } => {
+            let tasks = store.list(all, limit)?;
+            if tasks.is_empty() {
+                println!("No {}tasks.", if all { "" } else { "queued or running " });
+                return Ok(());
+            }
+            for task in tasks {
+                println!("{}", task.summary_line());
+            }
+            Ok(())
+        }
+        crate::TaskCommands::Show { id } => {
+            let Some(task) = store.get(id)? else {
+                bail!("Task #{id} not found");
+            };
+            print!("{}", describe(&task));
+            Ok(())
+        }
+        crate::TaskCommands::Cancel { id } => {
+            if store.cancel(id)? {
+                println!("🛑 Cancelled task #{id}");
+                Ok(())
+            } else if store.get(id)?.is_some() {
+                bail!("Task #{id} has already finished")
+            } else {
+                bail!("Task #{id} not found")
+            }
+        }
+    }
+}
+
+/// Multi-line status report: summary, then each step with its checkpointed result.
+pub fn describe(task: &Task) -> String {
+    let mut out = format!("{}\n", task.summary_line());
+    if let Some(error) = &task.error {
+        out.push_str(&format!("error: {error}\n"));
+    }
+    for (i, step) in task.steps.iter().enumerate() {
+        let marker = match (task.results.get(i), i == task.completed_steps()) {
+            (Some(_), _) => "✓",
+            (None, true) if task.status == TaskStatus::Running => "…",
+            _ => " ",
+        };
+        out.push_str(&format!("{marker} {}. {step}\n", i + 1));
+        if let Some(result) = task.results.get(i) {
+            out.push_str(&format!(
+                "     {}\n",
+                crate::util::truncate_with_ellipsis(result.trim(), 200).replace('\n', " ")
+            ));
+        }
+    }
+    out
+}
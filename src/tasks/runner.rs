@@ -0,0 +1,280 @@
+//! Background task runner (daemon component).
+//!
+//! Claims queued tasks and runs them one agent turn per step. Each finished
+//! step is checkpointed before the next one starts, so a restarted daemon
+//! resumes a task where it stopped. Cancellation is checked while a step
+//! runs and between steps.
+
+use super::store::{Task, TaskStatus, TaskStore};
+use crate::config::Config;
+use crate::util::truncate_with_ellipsis;
+use anyhow::Result;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tokio::time::{self, Duration};
+
+const POLL_SECONDS: u64 = 5;
+const CANCEL_CHECK_SECONDS: u64 = 2;
+const COMPONENT: &str = "tasks";
+/// Characters of each earlier step's result carried into the next step's prompt.
+const CARRIED_RESULT_CHARS: usize = 2000;
+/// Characters of a step result quoted in a progress notification.
+const NOTIFY_EXCERPT_CHARS: usize = 300;
+
+pub async fn run(config: Config) -> Result<()> {
+    let store = Arc::new(TaskStore::open(&config.workspace_dir)?);
+    match store.requeue_interrupted() {
+        Ok(0) => {}
+        Ok(count) => tracing::info!("Resuming {count} interrupted task(s) from their checkpoints"),
+        Err(e) => tracing::warn!("Failed to requeue interrupted tasks: {e}"),
+    }
+
+    let max_concurrent = config.tasks.max_concurrent.max(1);
+    let mut running = JoinSet::new();
+    let mut interval = time::interval(Duration::from_secs(POLL_SECONDS));
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+    crate::health::mark_component_ok(COMPONENT);
+
+    loop {
+        interval.tick().await;
+        crate::health::mark_component_ok(COMPONENT);
+        while running.try_join_next().is_some() {}
+
+        while running.len() < max_concurrent {
+            match store.claim_next() {
+                Ok(Some(task)) => {
+                    let config = config.clone();
+                    let store = Arc::clone(&store);
+                    running.spawn(async move { run_task(&config, &store, task).await });
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    crate::health::mark_component_error(COMPONENT, e.to_string());
+                    tracing::warn!("Task queue query failed: {e}");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+enum StepOutcome {
+    Done(String),
+    Failed(String),
+    Cancelled,
+}
+
+async fn run_task(config: &Config, store: &TaskStore, mut task: Task) {
+    if task.completed_steps() == 0 {
+        notify(
+            config,
+            &task,
+            &format!(
+                "▶️ Task #{} started: {} ({} steps)",
+                task.id,
+                task.title,
+                task.steps.len()
+            ),
+        )
+        .await;
+    }
+
+    while let Some(step) = task.next_step().map(str::to_string) {
+        let index = task.completed_steps() + 1;
+        let prompt = step_prompt(&task, index, &step);
+        match run_step(config, store, task.id, prompt).await {
+            StepOutcome::Done(result) => {
+                match store.checkpoint(task.id, &result) {
+                    Ok(true) => {}
+                    Ok(false) => return notify_cancelled(config, &task).await,
+                    Err(e) => return fail(config, store, &task, &e.to_string()).await,
+                }
+                task.results.push(result);
+                if config.tasks.notify_each_step && task.next_step().is_some() {
+                    notify(
+                        config,
+                        &task,
+                        &format!(
+                            "⏳ Task #{}: step {index}/{} done — {}\n{}",
+                            task.id,
+                            task.steps.len(),
+                            step,
+                            excerpt(task.results.last().map_or("", String::as_str)),
+                        ),
+                    )
+                    .await;
+                }
+            }
+            StepOutcome::Failed(error) => {
+                return fail(config, store, &task, &format!("step {index}: {error}")).await;
+            }
+            StepOutcome::Cancelled => return notify_cancelled(config, &task).await,
+        }
+    }
+
+    if let Err(e) = store.finish(task.id, TaskStatus::Completed, None) {
+        tracing::warn!("Failed to mark task #{} completed: {e}", task.id);
+    }
+    notify_end(
+        config,
+        &task,
+        &format!(
+            "✅ Task #{} finished: {}\n{}",
+            task.id,
+            task.title,
+            task.results.last().map_or("", String::as_str)
+        ),
+    )
+    .await;
+}
+
+/// Run one step as an agent turn, aborting it if the task is cancelled or
+/// the step exceeds `[tasks].step_timeout_secs`.
+async fn run_step(config: &Config, store: &TaskStore, id: i64, prompt: String) -> StepOutcome {
+    let timeout = Duration::from_secs(config.tasks.step_timeout_secs.max(1));
+    let turn = crate::agent::run(
+        config.clone(),
+        Some(prompt),
+        None,
+        None,
+        config.default_temperature,
+        vec![],
+        false,
+        None,
+    );
+    let cancelled = async {
+        let mut check = time::interval(Duration::from_secs(CANCEL_CHECK_SECONDS));
+        loop {
+            check.tick().await;
+            if matches!(store.status(id), Ok(Some(status)) if status.is_finished()) {
+                return;
+            }
+        }
+    };
+
+    tokio::select! {
+        result = time::timeout(timeout, turn) => match result {
+            Ok(Ok(output)) if output.trim().is_empty() => StepOutcome::Done("(no output)".into()),
+            Ok(Ok(output)) => StepOutcome::Done(output),
+            Ok(Err(e)) => StepOutcome::Failed(e.to_string()),
+            Err(_) => StepOutcome::Failed(format!("timed out after {}s", timeout.as_secs())),
+        },
+        () = cancelled => StepOutcome::Cancelled,
+    }
+}
+
+/// The prompt for step `index`: the task's plan, the results carried over
+/// from finished steps, then the step itself.
+fn step_prompt(task: &Task, index: usize, step: &str) -> String {
+    let mut prompt = format!(
+        "[task:{} {}] You are working through a background task, one step at a time.\n\nPlan:\n",
+        task.id, task.title
+    );
+    for (i, planned) in task.steps.iter().enumerate() {
+        prompt.push_str(&format!("{}. {planned}\n", i + 1));
+    }
+    if !task.results.is_empty() {
+        prompt.push_str("\nResults so far:\n");
+        for (i, result) in task.results.iter().enumerate() {
+            prompt.push_str(&format!(
+                "Step {}: {}\n",
+                i + 1,
+                truncate_with_ellipsis(result.trim(), CARRIED_RESULT_CHARS)
+            ));
+        }
+    }
+    prompt.push_str(&format!(
+        "\nDo step {index} of {} now and reply with its result: {step}",
+        task.steps.len()
+    ));
+    prompt
+}
+
+fn excerpt(result: &str) -> String {
+    truncate_with_ellipsis(result.trim(), NOTIFY_EXCERPT_CHARS)
+}
+
+async fn fail(config: &Config, store: &TaskStore, task: &Task, error: &str) {
+    tracing::warn!("Task #{} failed: {error}", task.id);
+    if let Err(e) = store.finish(task.id, TaskStatus::Failed, Some(error)) {
+        tracing::warn!("Failed to mark task #{} failed: {e}", task.id);
+    }
+    notify_end(
+        config,
+        task,
+        &format!(
+            "❌ Task #{} failed after {}/{} steps: {error}",
+            task.id,
+            task.completed_steps(),
+            task.steps.len()
+        ),
+    )
+    .await;
+}
+
+async fn notify_cancelled(config: &Config, task: &Task) {
+    tracing::info!("Task #{} cancelled", task.id);
+    notify_end(
+        config,
+        task,
+        &format!(
+            "🛑 Task #{} cancelled after {}/{} steps",
+            task.id,
+            task.completed_steps(),
+            task.steps.len()
+        ),
+    )
+    .await;
+}
+
+/// Report how a task ended: to its notify target, or as a desktop
+/// notification when it has none. Progress updates only go to the target.
+async fn notify_end(config: &Config, task: &Task, message: &str) {
+    if task.notify.is_none() {
+        crate::notifications::agent_output(
+            config,
+            &format!("Task #{}", task.id),
+            message,
+            Some(&format!("zeroclaw tasks show {}", task.id)),
+        );
+    }
+    notify(config, task, message).await;
+}
+
+async fn notify(config: &Config, task: &Task, message: &str) {
+    let Some(target) = task.notify.as_ref() else {
+        return;
+    };
+    if let Err(e) =
+        crate::cron::scheduler::deliver_announcement(config, &target.channel, &target.to, message)
+            .await
+    {
+        tracing::warn!("Task #{} notification failed: {e}", task.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_prompt_carries_plan_and_checkpointed_results() {
+        let task = Task {
+            id: 3,
+            title: "Audit deps".into(),
+            steps: vec!["List dependencies".into(), "Check each for updates".into()],
+            results: vec!["serde, tokio".into()],
+            status: TaskStatus::Running,
+            notify: None,
+            error: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let prompt = step_prompt(&task, 2, "Check each for updates");
+        assert!(prompt.starts_with("[task:3 Audit deps]"));
+        assert!(prompt.contains("1. List dependencies\n2. Check each for updates\n"));
+        assert!(prompt.contains("Step 1: serde, tokio"));
+        assert!(prompt
+            .ends_with("Do step 2 of 2 now and reply with its result: Check each for updates"));
+    }
+}
@@ -0,0 +1,364 @@
+//! SQLite task store.
+//!
+//! One database (`state/tasks.db` in the workspace) holds every task with its
+//! steps and the checkpointed result of each finished step. Task IDs are small
+//! integers so users can refer to "task #3".
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Schema history, applied by [`crate::util::sqlite::migrate`].
+const MIGRATIONS: &[&str] = &[
+    // v1: tasks with step checkpoints
+    "CREATE TABLE tasks (
+        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+        title       TEXT NOT NULL,
+        steps       TEXT NOT NULL,
+        results     TEXT NOT NULL DEFAULT '[]',
+        status      TEXT NOT NULL DEFAULT 'queued',
+        notify      TEXT,
+        error       TEXT,
+        created_at  TEXT NOT NULL,
+        updated_at  TEXT NOT NULL
+    );
+    CREATE INDEX idx_tasks_status ON tasks(status, id);",
+];
+
+const TASK_COLUMNS: &str =
+    "id, title, steps, results, status, notify, error, created_at, updated_at";
+
+/// Lifecycle of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl TaskStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    fn parse(raw: &str) -> Self {
+        match raw {
+            "running" => Self::Running,
+            "completed" => Self::Completed,
+            "failed" => Self::Failed,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Queued,
+        }
+    }
+
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// Where progress notifications for a task are sent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskNotify {
+    pub channel: String,
+    pub to: String,
+}
+
+/// A persisted task. `results[i]` is the checkpointed output of `steps[i]`;
+/// a resumed task continues at `steps[results.len()]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Task {
+    pub id: i64,
+    pub title: String,
+    pub steps: Vec<String>,
+    pub results: Vec<String>,
+    pub status: TaskStatus,
+    pub notify: Option<TaskNotify>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl Task {
+    /// Number of steps with a checkpointed result.
+    pub fn completed_steps(&self) -> usize {
+        self.results.len()
+    }
+
+    /// The next step to run, if any remain.
+    pub fn next_step(&self) -> Option<&str> {
+        self.steps.get(self.results.len()).map(String::as_str)
+    }
+
+    /// One-line summary, e.g. `#3 [running] Migrate the wiki (2/5 steps)`.
+    pub fn summary_line(&self) -> String {
+        format!(
+            "#{} [{}] {} ({}/{} steps)",
+            self.id,
+            self.status.as_str(),
+            self.title,
+            self.completed_steps(),
+            self.steps.len()
+        )
+    }
+}
+
+/// SQLite-backed task queue.
+pub struct TaskStore {
+    conn: Mutex<Connection>,
+}
+
+impl TaskStore {
+    /// Open (or create) the store in `workspace_dir/state/tasks.db`.
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = workspace_dir.join("state").join("tasks.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = crate::security::storage_encryption::open(&db_path)
+            .with_context(|| format!("Failed to open task store {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        crate::util::sqlite::migrate(&conn, "Task store", MIGRATIONS)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Queue a new task.
+    pub fn enqueue(
+        &self,
+        title: &str,
+        steps: &[String],
+        notify: Option<&TaskNotify>,
+    ) -> Result<Task> {
+        if steps.is_empty() {
+            anyhow::bail!("A task needs at least one step");
+        }
+        let now = Utc::now().to_rfc3339();
+        let id = {
+            let conn = self.conn.lock();
+            conn.execute(
+                "INSERT INTO tasks (title, steps, notify, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?4)",
+                params![
+                    title,
+                    serde_json::to_string(steps)?,
+                    notify.map(serde_json::to_string).transpose()?,
+                    now
+                ],
+            )?;
+            conn.last_insert_rowid()
+        };
+        self.get(id)?
+            .ok_or_else(|| anyhow::anyhow!("Task #{id} vanished after insert"))
+    }
+
+    pub fn get(&self, id: i64) -> Result<Option<Task>> {
+        let conn = self.conn.lock();
+        conn.query_row(
+            &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
+            params![id],
+            task_from_row,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    /// Tasks, newest first. Finished tasks are only included with `all`.
+    pub fn list(&self, all: bool, limit: usize) -> Result<Vec<Task>> {
+        let conn = self.conn.lock();
+        let filter = if all {
+            ""
+        } else {
+            "WHERE status IN ('queued', 'running')"
+        };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks {filter} ORDER BY id DESC LIMIT ?1"
+        ))?;
+        let rows = stmt.query_map(
+            params![i64::try_from(limit).unwrap_or(i64::MAX)],
+            task_from_row,
+        )?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    /// Mark the oldest queued task as running and return it.
+    pub fn claim_next(&self) -> Result<Option<Task>> {
+        let id: Option<i64> = {
+            let conn = self.conn.lock();
+            conn.query_row(
+                "UPDATE tasks SET status = 'running', updated_at = ?1
+                 WHERE id = (SELECT id FROM tasks WHERE status = 'queued' ORDER BY id LIMIT 1)
+                 RETURNING id",
+                params![Utc::now().to_rfc3339()],
+                |row| row.get(0),
+            )
+            .optional()?
+        };
+        match id {
+            Some(id) => self.get(id),
+            None => Ok(None),
+        }
+    }
+
+    /// Put tasks left `running` by a previous process back in the queue.
+    /// They resume after their last checkpoint.
+    pub fn requeue_interrupted(&self) -> Result<usize> {
+        let conn = self.conn.lock();
+        let count = conn.execute(
+            "UPDATE tasks SET status = 'queued', updated_at = ?1 WHERE status = 'running'",
+            params![Utc::now().to_rfc3339()],
+        )?;
+        Ok(count)
+    }
+
+    /// Record the result of the next step. Returns `false` (and records
+    /// nothing) when the task is no longer running, e.g. it was cancelled.
+    pub fn checkpoint(&self, id: i64, result: &str) -> Result<bool> {
+        let conn = self.conn.lock();
+        let updated = conn.execute(
+            "UPDATE tasks SET results = json_insert(results, '$[#]', ?2), updated_at = ?3
+             WHERE id = ?1 AND status = 'running'",
+            params![id, result, Utc::now().to_rfc3339()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Move a running task to a final status.
+    pub fn finish(&self, id: i64, status: TaskStatus, error: Option<&str>) -> Result<bool> {
+        let conn = self.conn.lock();
+        let updated = conn.execute(
+            "UPDATE tasks SET status = ?2, error = ?3, updated_at = ?4
+             WHERE id = ?1 AND status = 'running'",
+            params![id, status.as_str(), error, Utc::now().to_rfc3339()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Cancel a queued or running task. Returns `false` if it had already finished.
+    pub fn cancel(&self, id: i64) -> Result<bool> {
+        let conn = self.conn.lock();
+        let updated = conn.execute(
+            "UPDATE tasks SET status = 'cancelled', updated_at = ?2
+             WHERE id = ?1 AND status IN ('queued', 'running')",
+            params![id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    pub fn status(&self, id: i64) -> Result<Option<TaskStatus>> {
+        let conn = self.conn.lock();
+        let status: Option<String> = conn
+            .query_row(
+                "SELECT status FROM tasks WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(status.as_deref().map(TaskStatus::parse))
+    }
+}
+
+fn task_from_row(row: &Row<'_>) -> rusqlite::Result<Task> {
+    let json_column = |index: usize, raw: String| {
+        serde_json::from_str(&raw).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, e.into())
+        })
+    };
+    let notify: Option<String> = row.get(5)?;
+    Ok(Task {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        steps: json_column(2, row.get(2)?)?,
+        results: json_column(3, row.get(3)?)?,
+        status: TaskStatus::parse(&row.get::<_, String>(4)?),
+        notify: notify.map(|raw| json_column(5, raw)).transpose()?,
+        error: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn steps(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn tasks_run_in_order_and_checkpoint_each_step() {
+        let tmp = TempDir::new().unwrap();
+        let store = TaskStore::open(tmp.path()).unwrap();
+        let notify = TaskNotify {
+            channel: "telegram".into(),
+            to: "42".into(),
+        };
+        let first = store
+            .enqueue("Audit", &steps(&["list deps", "check each"]), Some(&notify))
+            .unwrap();
+        let second = store.enqueue("Later", &steps(&["wait"]), None).unwrap();
+        assert_eq!(second.id, first.id + 1);
+        assert!(store.enqueue("Empty", &[], None).is_err());
+
+        let claimed = store.claim_next().unwrap().unwrap();
+        assert_eq!(claimed.id, first.id);
+        assert_eq!(claimed.status, TaskStatus::Running);
+        assert_eq!(claimed.notify, Some(notify));
+        assert_eq!(claimed.next_step(), Some("list deps"));
+
+        assert!(store.checkpoint(first.id, "serde, tokio").unwrap());
+        let task = store.get(first.id).unwrap().unwrap();
+        assert_eq!(task.results, vec!["serde, tokio"]);
+        assert_eq!(task.next_step(), Some("check each"));
+        assert_eq!(task.summary_line(), "#1 [running] Audit (1/2 steps)");
+
+        assert!(store.finish(first.id, TaskStatus::Completed, None).unwrap());
+        assert_eq!(store.list(false, 10).unwrap().len(), 1);
+        assert_eq!(store.list(true, 10).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn cancel_stops_checkpoints_and_interrupted_tasks_resume() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let store = TaskStore::open(tmp.path()).unwrap();
+            let task = store.enqueue("Long", &steps(&["a", "b"]), None).unwrap();
+            store.claim_next().unwrap();
+            store.checkpoint(task.id, "done a").unwrap();
+        }
+
+        // A restart leaves the task running; it goes back to the queue with its checkpoint.
+        let store = TaskStore::open(tmp.path()).unwrap();
+        assert_eq!(store.requeue_interrupted().unwrap(), 1);
+        let resumed = store.claim_next().unwrap().unwrap();
+        assert_eq!(resumed.next_step(), Some("b"));
+
+        assert!(store.cancel(resumed.id).unwrap());
+        assert!(!store.cancel(resumed.id).unwrap());
+        assert!(!store.checkpoint(resumed.id, "done b").unwrap());
+        assert_eq!(
+            store.status(resumed.id).unwrap(),
+            Some(TaskStatus::Cancelled)
+        );
+        assert!(store.claim_next().unwrap().is_none());
+    }
+}
@@ -0,0 +1,209 @@
+//! The user's timezone and locale.
+//!
+//! `[locale] timezone` names the IANA zone the user lives in; unset, the
+//! system zone is used. Prompts state the current time in it
+//! ([`prompt_section`], [`now_stamp`]), wall-clock times the user or the
+//! model write are read in it ([`parse`]), and stored instants are shown in
+//! it ([`display`]). What gets stored is always UTC.
+
+pub mod parse;
+
+use crate::config::LocaleConfig;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use parking_lot::RwLock;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+
+pub use parse::{parse_instant, parse_when, When};
+
+/// An IANA zone or the system zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Named(chrono_tz::Tz),
+    Local,
+}
+
+impl Zone {
+    /// The zone called `name` (`"Europe/Berlin"`); blank means the system zone.
+    pub fn named(name: Option<&str>) -> anyhow::Result<Self> {
+        match name.map(str::trim).filter(|n| !n.is_empty()) {
+            Some(name) => chrono_tz::Tz::from_str(name)
+                .map(Self::Named)
+                .map_err(|_| anyhow::anyhow!("Unknown IANA timezone: {name}")),
+            None => Ok(Self::Local),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Self::Named(tz) => tz.name().to_string(),
+            Self::Local => "system local time".to_string(),
+        }
+    }
+
+    /// Interpret a wall-clock time in this zone (earliest instant on DST overlaps).
+    pub fn localize(self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Named(tz) => tz
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc)),
+            Self::Local => chrono::Local
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
+
+    pub fn format(self, instant: DateTime<Utc>, fmt: &str) -> String {
+        match self {
+            Self::Named(tz) => instant.with_timezone(&tz).format(fmt).to_string(),
+            Self::Local => instant
+                .with_timezone(&chrono::Local)
+                .format(fmt)
+                .to_string(),
+        }
+    }
+
+    /// The wall-clock time `instant` shows in this zone.
+    pub fn naive(self, instant: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Named(tz) => instant.with_timezone(&tz).naive_local(),
+            Self::Local => instant.with_timezone(&chrono::Local).naive_local(),
+        }
+    }
+
+    /// The calendar day `instant` falls on in this zone.
+    pub fn date_of(self, instant: DateTime<Utc>) -> NaiveDate {
+        self.naive(instant).date()
+    }
+}
+
+struct Settings {
+    zone: Zone,
+    /// `[locale] language`, if set.
+    language: Option<String>,
+}
+
+static SETTINGS: OnceLock<RwLock<Arc<Settings>>> = OnceLock::new();
+
+fn settings_lock() -> &'static RwLock<Arc<Settings>> {
+    SETTINGS.get_or_init(|| {
+        RwLock::new(Arc::new(Settings {
+            zone: Zone::Local,
+            language: None,
+        }))
+    })
+}
+
+/// Use `[locale]` from now on. An unknown timezone falls back to the system
+/// zone; `Config::validate` rejects it before this runs.
+pub fn configure(config: &LocaleConfig) {
+    let zone = Zone::named(config.timezone.as_deref()).unwrap_or_else(|error| {
+        tracing::warn!("{error}; using system local time");
+        Zone::Local
+    });
+    let language = config
+        .language
+        .as_deref()
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+        .map(str::to_string);
+    tracing::debug!(timezone = %zone.label(), "Timezone set");
+    *settings_lock().write() = Arc::new(Settings { zone, language });
+}
+
+/// The user's zone.
+pub fn zone() -> Zone {
+    settings_lock().read().zone
+}
+
+/// The current time as prefixed to user messages, e.g. `2026-03-10 14:00:00 CET`.
+pub fn now_stamp() -> String {
+    zone().format(Utc::now(), "%Y-%m-%d %H:%M:%S %Z")
+}
+
+/// The user's current time of day.
+pub fn time_of_day() -> NaiveTime {
+    zone().naive(Utc::now()).time()
+}
+
+/// `instant` in the user's zone, e.g. `2026-03-10 14:00 CET`.
+pub fn display(instant: DateTime<Utc>) -> String {
+    zone().format(instant, "%Y-%m-%d %H:%M %Z")
+}
+
+/// A stored timestamp (RFC 3339, or SQLite's `YYYY-MM-DD HH:MM:SS` in UTC).
+pub fn parse_stored(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|naive| naive.and_utc())
+        })
+}
+
+/// A stored timestamp shown in the user's zone with `fmt`; unchanged if it
+/// does not parse.
+pub fn display_stored(timestamp: &str, fmt: &str) -> String {
+    parse_stored(timestamp).map_or_else(|| timestamp.to_string(), |at| zone().format(at, fmt))
+}
+
+/// System prompt section with the current date, time, timezone and locale.
+pub fn prompt_section() -> String {
+    let settings = settings_lock().read().clone();
+    prompt_section_at(&settings, Utc::now())
+}
+
+fn prompt_section_at(settings: &Settings, now: DateTime<Utc>) -> String {
+    let zone = settings.zone;
+    let mut section = format!(
+        "## Current Date & Time\n\n{} ({})\n\nTimezone: {}\n",
+        zone.format(now, "%A, %Y-%m-%d %H:%M:%S"),
+        zone.format(now, "%Z"),
+        zone.label()
+    );
+    if let Some(language) = &settings.language {
+        section.push_str(&format!("Locale: {language}\n"));
+    }
+    section.push_str(
+        "Times the user mentions without a zone are in this timezone. Tools that take times \
+         accept them in this timezone, and relative phrases like 'tomorrow 9am' or \
+         'next friday 3pm'.\n",
+    );
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_section_names_zone_and_locale() {
+        let settings = Settings {
+            zone: Zone::named(Some("Europe/Berlin")).unwrap(),
+            language: Some("de".into()),
+        };
+        let now = DateTime::parse_from_rfc3339("2026-03-10T13:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let section = prompt_section_at(&settings, now);
+        assert!(section.starts_with("## Current Date & Time\n\nTuesday, 2026-03-10 14:00:00 (CET)"));
+        assert!(section.contains("Timezone: Europe/Berlin\n"));
+        assert!(section.contains("Locale: de\n"));
+    }
+
+    #[test]
+    fn stored_timestamps_parse_as_utc() {
+        let expected = DateTime::parse_from_rfc3339("2026-03-10T13:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_stored("2026-03-10T14:00:00+01:00"), Some(expected));
+        assert_eq!(parse_stored("2026-03-10 13:00:00"), Some(expected));
+        assert_eq!(parse_stored("yesterday"), None);
+        assert!(Zone::named(Some("Mars/Olympus")).is_err());
+        assert_eq!(Zone::named(Some(" ")).unwrap(), Zone::Local);
+    }
+}
@@ -0,0 +1,402 @@
+//! Times as people write them: `tomorrow 9am`, `next friday 3pm`,
+//! `in 2 hours`, `march 10`, alongside RFC 3339 and ISO dates.
+
+use super::Zone;
+use chrono::{
+    DateTime, Datelike, Duration, Month, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday,
+};
+
+/// A parsed time: an instant, or a whole day when no time of day was given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum When {
+    At(DateTime<Utc>),
+    Day(NaiveDate),
+}
+
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+
+/// Words that carry nothing here, as in `on friday at 3pm`.
+const FILLER: &[&str] = &["at", "on", "the", "of"];
+
+/// Parse `input` as of `now`, reading wall-clock times in `zone`.
+///
+/// Accepts RFC 3339 (`2026-03-10T14:00:00+01:00`), `YYYY-MM-DD` with an
+/// optional `THH:MM[:SS]`, and English phrases:
+///
+/// - offsets: `now`, `in 2 hours`, `in 1 hour and 30 minutes`, `45 min from now`
+/// - days: `today`, `tomorrow`, `yesterday`, `friday`, `this friday`,
+///   `next friday`, `march 10`, `10th of march 2027`
+/// - times of day: `3pm`, `3:30 pm`, `15:00`, `noon`, `midnight`
+/// - a day and a time in either order: `next friday 3pm`, `9am tomorrow`
+///
+/// A weekday alone or with `this` is the nearest one from today on; with
+/// `next` it is the nearest one after today. A month and day without a year
+/// is the next one from today on. A time of day without a day is the next
+/// time the clock shows it. Without a time of day the result is a [`When::Day`].
+pub fn parse_when(input: &str, zone: Zone, now: DateTime<Utc>) -> anyhow::Result<When> {
+    let raw = input.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(When::At(at.with_timezone(&Utc)));
+    }
+    for fmt in NAIVE_FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(raw, fmt) {
+            return localize(zone, naive, raw).map(When::At);
+        }
+    }
+    let today = zone.date_of(now);
+    match phrase(raw, today) {
+        Some(Phrase::After(offset)) => now
+            .checked_add_signed(offset)
+            .map(When::At)
+            .ok_or_else(|| anyhow::anyhow!("'{raw}' is too far in the future")),
+        Some(Phrase::On(Some(day), None)) => Ok(When::Day(day)),
+        Some(Phrase::On(day, Some(time))) => {
+            let at = localize(zone, day.unwrap_or(today).and_time(time), raw)?;
+            if day.is_none() && at <= now {
+                let tomorrow = today + Duration::days(1);
+                return localize(zone, tomorrow.and_time(time), raw).map(When::At);
+            }
+            Ok(When::At(at))
+        }
+        Some(Phrase::On(None, None)) | None => anyhow::bail!(
+            "Unrecognized time '{raw}': use YYYY-MM-DDTHH:MM (optionally with offset), \
+             YYYY-MM-DD, or a phrase like 'tomorrow 9am', 'next friday 3pm' or 'in 2 hours'"
+        ),
+    }
+}
+
+/// [`parse_when`] for callers that need a moment, not a day: `friday`
+/// alone is an error asking for a time of day.
+pub fn parse_instant(input: &str, zone: Zone, now: DateTime<Utc>) -> anyhow::Result<DateTime<Utc>> {
+    match parse_when(input, zone, now)? {
+        When::At(at) => Ok(at),
+        When::Day(_) => {
+            let raw = input.trim();
+            anyhow::bail!("'{raw}' has no time of day; add one, e.g. '{raw} 9am'")
+        }
+    }
+}
+
+fn localize(zone: Zone, naive: NaiveDateTime, raw: &str) -> anyhow::Result<DateTime<Utc>> {
+    zone.localize(naive)
+        .ok_or_else(|| anyhow::anyhow!("'{raw}' does not exist in {}", zone.label()))
+}
+
+enum Phrase {
+    /// `now`, `in 2 hours`, `2 hours from now`.
+    After(Duration),
+    /// A day, a time of day, or both.
+    On(Option<NaiveDate>, Option<NaiveTime>),
+}
+
+fn phrase(raw: &str, today: NaiveDate) -> Option<Phrase> {
+    let lowered = raw.to_lowercase().replace(',', " ");
+    let tokens: Vec<&str> = lowered
+        .split_whitespace()
+        .filter(|token| !FILLER.contains(token))
+        .collect();
+    match tokens.as_slice() {
+        [] => None,
+        ["now"] | ["right", "now"] => Some(Phrase::After(Duration::zero())),
+        ["in", rest @ ..] | [rest @ .., "from", "now"] => offset(rest).map(Phrase::After),
+        _ => {
+            let (mut day, mut time) = (None, None);
+            let mut rest = tokens.as_slice();
+            while !rest.is_empty() {
+                if day.is_none() {
+                    if let Some((found, used)) = day_of(rest, today) {
+                        day = Some(found);
+                        rest = &rest[used..];
+                        continue;
+                    }
+                }
+                if time.is_none() {
+                    if let Some((found, used)) = time_of_day(rest) {
+                        time = Some(found);
+                        rest = &rest[used..];
+                        continue;
+                    }
+                }
+                return None;
+            }
+            Some(Phrase::On(day, time))
+        }
+    }
+}
+
+/// `2 hours and 30 minutes`, `an hour`, `90m`.
+fn offset(tokens: &[&str]) -> Option<Duration> {
+    if tokens.is_empty() {
+        return None;
+    }
+    let mut total = Duration::zero();
+    let mut rest = tokens;
+    while let Some((&first, tail)) = rest.split_first() {
+        let (amount, unit, tail) = match first.find(|c: char| !c.is_ascii_digit()) {
+            Some(split) if split > 0 => (&first[..split], &first[split..], tail),
+            _ => {
+                let (&unit, tail) = tail.split_first()?;
+                (first, unit, tail)
+            }
+        };
+        let amount = match amount {
+            "a" | "an" => 1,
+            number => number.parse().ok()?,
+        };
+        total = total.checked_add(&unit_length(unit)?.checked_mul(amount)?)?;
+        rest = match tail {
+            ["and", tail @ ..] => tail,
+            _ => tail,
+        };
+    }
+    Some(total)
+}
+
+fn unit_length(unit: &str) -> Option<Duration> {
+    Some(match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Duration::seconds(1),
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::minutes(1),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::hours(1),
+        "d" | "day" | "days" => Duration::days(1),
+        "w" | "week" | "weeks" => Duration::weeks(1),
+        _ => return None,
+    })
+}
+
+/// A day at the start of `tokens`, with the number of tokens it took.
+fn day_of(tokens: &[&str], today: NaiveDate) -> Option<(NaiveDate, usize)> {
+    let first = *tokens.first()?;
+    match first {
+        "today" | "tonight" => return Some((today, 1)),
+        "tomorrow" => return Some((today.succ_opt()?, 1)),
+        "yesterday" => return Some((today.pred_opt()?, 1)),
+        "this" | "next" => {
+            let weekday: Weekday = tokens.get(1)?.parse().ok()?;
+            let ahead = days_until(today, weekday);
+            let ahead = if first == "next" && ahead == 0 {
+                7
+            } else {
+                ahead
+            };
+            return Some((today + Duration::days(ahead), 2));
+        }
+        _ => {}
+    }
+    if let Ok(weekday) = first.parse::<Weekday>() {
+        return Some((today + Duration::days(days_until(today, weekday)), 1));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(first, "%Y-%m-%d") {
+        return Some((date, 1));
+    }
+    let (month, day) = match tokens {
+        [a, b, ..] if month_number(a).is_some() => (month_number(a)?, day_number(b)?),
+        [a, b, ..] if month_number(b).is_some() => (month_number(b)?, day_number(a)?),
+        _ => return None,
+    };
+    if let Some(year) = tokens.get(2).and_then(|token| year_number(token)) {
+        return Some((NaiveDate::from_ymd_opt(year, month, day)?, 3));
+    }
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+    match this_year {
+        Some(date) if date >= today => Some((date, 2)),
+        _ => Some((NaiveDate::from_ymd_opt(today.year() + 1, month, day)?, 2)),
+    }
+}
+
+fn days_until(today: NaiveDate, weekday: Weekday) -> i64 {
+    let from = today.weekday().num_days_from_monday();
+    i64::from((weekday.num_days_from_monday() + 7 - from) % 7)
+}
+
+fn month_number(token: &str) -> Option<u32> {
+    token
+        .trim_end_matches('.')
+        .parse::<Month>()
+        .ok()
+        .map(|month| month.number_from_month())
+}
+
+/// `10`, `10th`, `1st`.
+fn day_number(token: &str) -> Option<u32> {
+    let digits = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| token.strip_suffix(suffix))
+        .unwrap_or(token);
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+fn year_number(token: &str) -> Option<i32> {
+    if token.len() != 4 {
+        return None;
+    }
+    token
+        .parse()
+        .ok()
+        .filter(|year| (1970..=9999).contains(year))
+}
+
+/// A time of day at the start of `tokens`, with the number of tokens it took.
+fn time_of_day(tokens: &[&str]) -> Option<(NaiveTime, usize)> {
+    let first = *tokens.first()?;
+    match first {
+        "noon" | "midday" => return Some((NaiveTime::from_hms_opt(12, 0, 0)?, 1)),
+        "midnight" => return Some((NaiveTime::MIN, 1)),
+        _ => {}
+    }
+    let (clock, pm, used) = match split_meridiem(first) {
+        Some((clock, pm)) => (clock, Some(pm), 1),
+        None => match tokens.get(1).and_then(|token| meridiem(token)) {
+            Some(pm) => (first, Some(pm), 2),
+            None => (first, None, 1),
+        },
+    };
+    // A bare number is a day of the month, not a time.
+    if pm.is_none() && !clock.contains([':', '.']) {
+        return None;
+    }
+    let mut parts = clock.split([':', '.']);
+    let hour: u32 = parts.next()?.parse().ok()?;
+    let minute: u32 = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+    let second: u32 = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let hour = match pm {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+        None => hour,
+    };
+    Some((NaiveTime::from_hms_opt(hour, minute, second)?, used))
+}
+
+/// `true` for pm, `false` for am.
+fn meridiem(token: &str) -> Option<bool> {
+    match token {
+        "am" | "a.m." => Some(false),
+        "pm" | "p.m." => Some(true),
+        _ => None,
+    }
+}
+
+/// `3pm` into `3` and pm.
+fn split_meridiem(token: &str) -> Option<(&str, bool)> {
+    ["am", "a.m.", "pm", "p.m."].iter().find_map(|suffix| {
+        let clock = token.strip_suffix(suffix)?;
+        let starts_with_digit = clock.starts_with(|c: char| c.is_ascii_digit());
+        starts_with_digit.then(|| (clock, meridiem(suffix).unwrap_or_default()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn berlin() -> Zone {
+        Zone::Named(chrono_tz::Europe::Berlin)
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    /// Tuesday 2026-03-10, 14:00 in Berlin.
+    fn now() -> DateTime<Utc> {
+        utc("2026-03-10T13:00:00Z")
+    }
+
+    fn at(input: &str) -> DateTime<Utc> {
+        match parse_when(input, berlin(), now()).unwrap() {
+            When::At(at) => at,
+            When::Day(day) => panic!("'{input}' parsed as the day {day}"),
+        }
+    }
+
+    fn day(input: &str) -> NaiveDate {
+        match parse_when(input, berlin(), now()).unwrap() {
+            When::Day(day) => day,
+            When::At(at) => panic!("'{input}' parsed as the instant {at}"),
+        }
+    }
+
+    #[test]
+    fn parses_absolute_formats() {
+        assert_eq!(at("2026-03-10T14:00"), utc("2026-03-10T13:00:00Z"));
+        assert_eq!(at("2026-03-10T14:00:00-05:00"), utc("2026-03-10T19:00:00Z"));
+        assert_eq!(
+            day("2026-03-12"),
+            NaiveDate::from_ymd_opt(2026, 3, 12).unwrap()
+        );
+        // Skipped by the spring-forward transition.
+        assert!(parse_when("2026-03-29T02:30", berlin(), now()).is_err());
+    }
+
+    #[test]
+    fn parses_days_and_times_of_day() {
+        assert_eq!(at("tomorrow 9am"), utc("2026-03-11T08:00:00Z"));
+        assert_eq!(at("9:30 pm tomorrow"), utc("2026-03-11T20:30:00Z"));
+        assert_eq!(at("next friday at 3pm"), utc("2026-03-13T14:00:00Z"));
+        assert_eq!(at("Friday, 15:00"), utc("2026-03-13T14:00:00Z"));
+        assert_eq!(at("March 20th at noon"), utc("2026-03-20T11:00:00Z"));
+        // Later today, or tomorrow once the time has passed.
+        assert_eq!(at("5pm"), utc("2026-03-10T16:00:00Z"));
+        assert_eq!(at("9am"), utc("2026-03-11T08:00:00Z"));
+
+        assert_eq!(
+            day("this tuesday"),
+            NaiveDate::from_ymd_opt(2026, 3, 10).unwrap()
+        );
+        assert_eq!(
+            day("next tuesday"),
+            NaiveDate::from_ymd_opt(2026, 3, 17).unwrap()
+        );
+        assert_eq!(
+            day("the 1st of march"),
+            NaiveDate::from_ymd_opt(2027, 3, 1).unwrap()
+        );
+        assert_eq!(
+            day("10 jan 2027"),
+            NaiveDate::from_ymd_opt(2027, 1, 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_offsets_from_now() {
+        assert_eq!(at("now"), now());
+        assert_eq!(at("in 2 hours"), utc("2026-03-10T15:00:00Z"));
+        assert_eq!(at("in an hour and 30 minutes"), utc("2026-03-10T14:30:00Z"));
+        assert_eq!(at("45min from now"), utc("2026-03-10T13:45:00Z"));
+    }
+
+    #[test]
+    fn instants_need_a_time_of_day() {
+        assert_eq!(
+            parse_instant("friday 9am", berlin(), now()).unwrap(),
+            utc("2026-03-13T08:00:00Z")
+        );
+        let error = parse_instant("friday", berlin(), now()).unwrap_err();
+        assert!(error.to_string().contains("'friday 9am'"));
+    }
+
+    #[test]
+    fn rejects_what_it_cannot_place() {
+        for input in [
+            "",
+            "soon",
+            "next week",
+            "in 2 fortnights",
+            "13pm",
+            "friday friday",
+        ] {
+            let error = parse_when(input, berlin(), now()).unwrap_err();
+            assert!(
+                error.to_string().contains("tomorrow 9am"),
+                "{input}: {error}"
+            );
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use super::file_changes::{self, FilePatch};
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Apply a unified diff to one or more files.
+///
+/// Every file section is validated and applied in memory before anything is
+/// written, so a patch either lands completely or not at all. Overwritten and
+/// deleted files are backed up like [`super::file_write::FileWriteTool`].
+pub struct ApplyPatchTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl ApplyPatchTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+/// A fully computed change for one file.
+struct PlannedChange {
+    label: String,
+    target: PathBuf,
+    before: String,
+    /// `None` deletes the file.
+    after: Option<String>,
+}
+
+impl PlannedChange {
+    fn diff(&self) -> String {
+        file_changes::diff_preview(
+            &self.label,
+            &self.before,
+            self.after.as_deref().unwrap_or(""),
+        )
+    }
+}
+
+fn failure(error: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+    }
+}
+
+impl ApplyPatchTool {
+    async fn plan(&self, file: &FilePatch) -> Result<PlannedChange, String> {
+        if let (Some(old), Some(new)) = (&file.old_path, &file.new_path) {
+            if old != new {
+                return Err(format!("Renames are not supported ({old} -> {new})"));
+            }
+        }
+        let label = file.target().to_string();
+        let workspace = self.security.workspace();
+        let target = workspace.resolve_for_write(&label, false).await?;
+
+        let before = if file.old_path.is_none() {
+            if tokio::fs::symlink_metadata(&target).await.is_ok() {
+                return Err(format!("{label} already exists; patch expects a new file"));
+            }
+            String::new()
+        } else {
+            tokio::fs::read_to_string(&target)
+                .await
+                .map_err(|e| format!("Failed to read {label}: {e}"))?
+        };
+
+        let after =
+            file_changes::apply_hunks(&before, &file.hunks).map_err(|e| format!("{label}: {e}"))?;
+        if file.new_path.is_none() && !after.is_empty() {
+            return Err(format!(
+                "{label}: deletion patch does not remove the whole file"
+            ));
+        }
+        if file.new_path.is_some() {
+            workspace.check_quota(&target, after.len() as u64).await?;
+        }
+
+        Ok(PlannedChange {
+            label,
+            target,
+            before,
+            after: file.new_path.as_ref().map(|_| after),
+        })
+    }
+
+    async fn commit(&self, change: &PlannedChange) -> Result<String, String> {
+        // Resolve again so missing directories are created through the
+        // workspace jail and a tree changed since planning is caught.
+        let target = self
+            .security
+            .workspace()
+            .resolve_for_write(&change.label, change.after.is_some())
+            .await?;
+        if target != change.target {
+            return Err(format!(
+                "{} changed while the patch was being applied",
+                change.label
+            ));
+        }
+
+        let backup = file_changes::backup_existing(&self.security.workspace_dir, &change.target)
+            .await
+            .map_err(|e| format!("Failed to back up {}: {e}", change.label))?;
+
+        let summary = match &change.after {
+            Some(after) => {
+                tokio::fs::write(&change.target, after)
+                    .await
+                    .map_err(|e| format!("Failed to write {}: {e}", change.label))?;
+                format!("Patched {} ({} bytes)", change.label, after.len())
+            }
+            None => {
+                tokio::fs::remove_file(&change.target)
+                    .await
+                    .map_err(|e| format!("Failed to delete {}: {e}", change.label))?;
+                format!("Deleted {}", change.label)
+            }
+        };
+
+        Ok(match backup {
+            Some(path) => format!("{summary}; backup: {}", path.display()),
+            None => summary,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for ApplyPatchTool {
+    fn name(&self) -> &str {
+        "apply_patch"
+    }
+
+    fn description(&self) -> &str {
+        "Apply a unified diff (as produced by `diff -u` or `git diff`) to files in the workspace. \
+         Supports multiple files, new files (--- /dev/null) and deletions (+++ /dev/null). \
+         All hunks must apply or nothing is written; set preview=true to check without writing."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff text with '--- a/path' / '+++ b/path' headers and '@@' hunks"
+                },
+                "preview": {
+                    "type": "boolean",
+                    "description": "Validate the patch and return the resulting diff without writing (default: false)"
+                }
+            },
+            "required": ["patch"]
+        })
+    }
+
+    async fn dry_run(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        file_changes::dry_run_preview(self, args).await
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let patch = args
+            .get("patch")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'patch' parameter"))?;
+        let preview = args
+            .get("preview")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        if !self.security.can_act() {
+            return Ok(failure("Action blocked: autonomy is read-only".into()));
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour".into(),
+            ));
+        }
+
+        let files = match file_changes::parse_unified_patch(patch) {
+            Ok(files) => files,
+            Err(e) => return Ok(failure(format!("Invalid patch: {e}"))),
+        };
+
+        let mut changes = Vec::with_capacity(files.len());
+        for file in &files {
+            let change = match self.plan(file).await {
+                Ok(change) => change,
+                Err(e) => return Ok(failure(format!("Patch not applied: {e}"))),
+            };
+            // Compare resolved paths, so `a.txt` and `./a.txt` count as one file.
+            if changes
+                .iter()
+                .any(|planned: &PlannedChange| planned.target == change.target)
+            {
+                return Ok(failure(format!(
+                    "Patch touches {} more than once",
+                    change.label
+                )));
+            }
+            changes.push(change);
+        }
+
+        let diffs = changes
+            .iter()
+            .map(PlannedChange::diff)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if preview {
+            return Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Patch applies cleanly to {} file(s) (not written):\n{diffs}",
+                    changes.len()
+                ),
+                error: None,
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(failure(
+                "Rate limit exceeded: action budget exhausted".into(),
+            ));
+        }
+
+        let mut summaries = Vec::with_capacity(changes.len());
+        for change in &changes {
+            match self.commit(change).await {
+                Ok(summary) => summaries.push(summary),
+                Err(e) => {
+                    let done = if summaries.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" (already applied: {})", summaries.join("; "))
+                    };
+                    return Ok(failure(format!("{e}{done}")));
+                }
+            }
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!("{}\n{diffs}", summaries.join("\n")),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(
+        workspace: std::path::PathBuf,
+        autonomy: AutonomyLevel,
+    ) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn tool(dir: &tempfile::TempDir) -> ApplyPatchTool {
+        ApplyPatchTool::new(test_security(
+            dir.path().to_path_buf(),
+            AutonomyLevel::Supervised,
+        ))
+    }
+
+    #[tokio::test]
+    async fn applies_multi_file_patch_with_creation_and_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("a.txt"), "one\ntwo\nthree\n")
+            .await
+            .unwrap();
+
+        let patch = "--- a/a.txt\n+++ b/a.txt\n@@ -1,3 +1,3 @@\n one\n-two\n+TWO\n three\n\
+                     --- /dev/null\n+++ b/docs/new.md\n@@ -0,0 +1,1 @@\n+# New\n";
+        let result = tool(&dir).execute(json!({"patch": patch})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Patched a.txt"));
+        assert!(result.output.contains("backup:"));
+
+        assert_eq!(
+            tokio::fs::read_to_string(dir.path().join("a.txt"))
+                .await
+                .unwrap(),
+            "one\nTWO\nthree\n"
+        );
+        assert_eq!(
+            tokio::fs::read_to_string(dir.path().join("docs/new.md"))
+                .await
+                .unwrap(),
+            "# New\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn failing_hunk_leaves_every_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("a.txt"), "one\n")
+            .await
+            .unwrap();
+        tokio::fs::write(dir.path().join("b.txt"), "two\n")
+            .await
+            .unwrap();
+
+        let patch = "--- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-one\n+ONE\n\
+                     --- a/b.txt\n+++ b/b.txt\n@@ -1 +1 @@\n-missing\n+TWO\n";
+        let result = tool(&dir).execute(json!({"patch": patch})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("b.txt"));
+        assert_eq!(
+            tokio::fs::read_to_string(dir.path().join("a.txt"))
+                .await
+                .unwrap(),
+            "one\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn preview_and_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("gone.txt"), "bye\n")
+            .await
+            .unwrap();
+        let patch = "--- a/gone.txt\n+++ /dev/null\n@@ -1 +0,0 @@\n-bye\n";
+
+        let result = tool(&dir)
+            .execute(json!({"patch": patch, "preview": true}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("-bye"));
+        assert!(dir.path().join("gone.txt").exists());
+
+        let result = tool(&dir).execute(json!({"patch": patch})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(!dir.path().join("gone.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn rejects_the_same_file_under_two_spellings() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("a.txt"), "one\n")
+            .await
+            .unwrap();
+
+        let patch = "--- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-one\n+ONE\n\
+                     --- a/./a.txt\n+++ b/./a.txt\n@@ -1 +1 @@\n-one\n+uno\n";
+        let result = tool(&dir).execute(json!({"patch": patch})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("more than once"));
+        assert_eq!(
+            tokio::fs::read_to_string(dir.path().join("a.txt"))
+                .await
+                .unwrap(),
+            "one\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_workspace_and_readonly_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let patch = "--- /dev/null\n+++ b/../../etc/evil\n@@ -0,0 +1 @@\n+x\n";
+        let result = tool(&dir).execute(json!({"patch": patch})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+
+        let readonly = ApplyPatchTool::new(test_security(
+            dir.path().to_path_buf(),
+            AutonomyLevel::ReadOnly,
+        ));
+        let patch = "--- /dev/null\n+++ b/x.txt\n@@ -0,0 +1 @@\n+x\n";
+        let result = readonly.execute(json!({"patch": patch})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
@@ -67,6 +67,8 @@ pub struct BrowserTool {
     native_webdriver_url: String,
     native_chrome_path: Option<String>,
     computer_use: ComputerUseConfig,
+    /// Hard cap on actions per agent turn (0 = unlimited).
+    max_actions_per_turn: usize,
     #[cfg(feature = "browser-native")]
     native_state: tokio::sync::Mutex<native_backend::NativeBrowserState>,
 }
@@ -234,11 +236,23 @@ impl BrowserTool {
             native_webdriver_url,
             native_chrome_path,
             computer_use,
+            max_actions_per_turn: 0,
             #[cfg(feature = "browser-native")]
             native_state: tokio::sync::Mutex::new(native_backend::NativeBrowserState::default()),
         }
     }
 
+    /// Limit how many browser actions a single agent turn may perform.
+    pub fn with_max_actions_per_turn(mut self, max_actions_per_turn: usize) -> Self {
+        self.max_actions_per_turn = max_actions_per_turn;
+        self
+    }
+
+    /// Reserve one action from the running turn's budget.
+    fn take_turn_action(&self) -> bool {
+        crate::tools::turn::take_action(self.name(), self.max_actions_per_turn)
+    }
+
     /// Check if agent-browser CLI is available
     pub async fn is_agent_browser_available() -> bool {
         Command::new("agent-browser")
@@ -1026,6 +1040,18 @@ impl Tool for BrowserTool {
             });
         }
 
+        if !self.take_turn_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Action blocked: browser budget of {} actions per turn exhausted. \
+                     Summarize progress and ask the user before continuing.",
+                    self.max_actions_per_turn
+                )),
+            });
+        }
+
         if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
@@ -2398,6 +2424,42 @@ mod tests {
         assert_eq!(tool.name(), "browser");
     }
 
+    #[tokio::test]
+    async fn browser_action_budget_is_per_turn() {
+        let security = Arc::new(SecurityPolicy::default());
+        let tool = BrowserTool::new(security, vec!["example.com".into()], None)
+            .with_max_actions_per_turn(2);
+        let (to_b, mut b_turn) = tokio::sync::mpsc::channel::<()>(1);
+        let (to_a, mut a_turn) = tokio::sync::mpsc::channel::<()>(1);
+
+        // Two sessions' turns interleave on the one shared tool instance:
+        // B starting mid-way through A neither resets nor shares A's budget.
+        let turn_a = crate::tools::turn::run_with(async {
+            assert!(tool.take_turn_action());
+            to_b.send(()).await.unwrap();
+            a_turn.recv().await.unwrap();
+            assert!(tool.take_turn_action());
+            assert!(!tool.take_turn_action());
+        });
+        let turn_b = crate::tools::turn::run_with(async {
+            b_turn.recv().await.unwrap();
+            assert!(tool.take_turn_action());
+            assert!(tool.take_turn_action());
+            assert!(!tool.take_turn_action());
+            to_a.send(()).await.unwrap();
+        });
+        tokio::join!(turn_a, turn_b);
+
+        crate::tools::turn::run_with(async { assert!(tool.take_turn_action()) }).await;
+
+        let unlimited = BrowserTool::new(
+            Arc::new(SecurityPolicy::default()),
+            vec!["example.com".into()],
+            None,
+        );
+        assert!((0..100).all(|_| unlimited.take_turn_action()));
+    }
+
     #[test]
     fn browser_tool_validates_url() {
         let security = Arc::new(SecurityPolicy::default());
@@ -0,0 +1,1212 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::CalendarConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use crate::time::{When, Zone};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+
+/// Default look-ahead for `list` when no range is given.
+const DEFAULT_LIST_DAYS: i64 = 7;
+/// Default event length for `create` when neither `end` nor `duration_minutes` is given.
+const DEFAULT_EVENT_MINUTES: i64 = 60;
+
+/// Start or end of an event: a point in time or a whole day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventTime {
+    At(DateTime<Utc>),
+    Day(NaiveDate),
+}
+
+/// The fields of a `VEVENT` the tool understands.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CalendarEvent {
+    uid: String,
+    summary: String,
+    start: Option<EventTime>,
+    end: Option<EventTime>,
+    location: Option<String>,
+    description: Option<String>,
+    recurring: bool,
+}
+
+/// An event resource on the server.
+#[derive(Debug, Clone)]
+struct EventResource {
+    href: String,
+    etag: Option<String>,
+    ics: String,
+}
+
+/// Field changes requested by `create`/`update`.
+#[derive(Debug, Default)]
+struct EventChanges {
+    summary: Option<String>,
+    start: Option<EventTime>,
+    end: Option<EventTime>,
+    location: Option<String>,
+    description: Option<String>,
+}
+
+/// List, create, update and delete events on a CalDAV calendar
+/// (Nextcloud, Fastmail, Radicale, ...).
+///
+/// Writes are gated: with `require_confirmation` the first call only returns
+/// a preview, and the change happens when repeated with `approved=true`.
+pub struct CalendarTool {
+    security: Arc<SecurityPolicy>,
+    config: CalendarConfig,
+    client: OnceLock<reqwest::Client>,
+}
+
+impl CalendarTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: CalendarConfig) -> Self {
+        Self {
+            security,
+            config,
+            client: OnceLock::new(),
+        }
+    }
+
+    fn error(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        self.client.get_or_init(|| {
+            crate::config::build_runtime_proxy_client_with_timeouts(
+                "tool.calendar",
+                self.config.timeout_secs,
+                10,
+            )
+        })
+    }
+
+    fn collection_url(&self) -> anyhow::Result<String> {
+        let url = self.config.url.trim();
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            anyhow::bail!("[calendar].url must be the http(s) URL of a CalDAV calendar collection");
+        }
+        Ok(format!("{}/", url.trim_end_matches('/')))
+    }
+
+    /// Resolve a server `href` (usually an absolute path) against the collection URL.
+    fn resource_url(&self, href: &str) -> anyhow::Result<String> {
+        if href.starts_with("http://") || href.starts_with("https://") {
+            return Ok(href.to_string());
+        }
+        let base = reqwest::Url::parse(&self.collection_url()?)?;
+        Ok(base.join(href)?.to_string())
+    }
+
+    fn request(&self, method: &str, url: &str) -> anyhow::Result<reqwest::RequestBuilder> {
+        let method = reqwest::Method::from_bytes(method.as_bytes())?;
+        let mut request = self.client().request(method, url);
+        if !self.config.username.is_empty() {
+            request = request.basic_auth(&self.config.username, self.config.password.as_deref());
+        }
+        Ok(request)
+    }
+
+    async fn report(&self, filter: &str) -> anyhow::Result<Vec<EventResource>> {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/><c:calendar-data/></d:prop>
+  <c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VEVENT">{filter}</c:comp-filter></c:comp-filter></c:filter>
+</c:calendar-query>"#
+        );
+        let response = self
+            .request("REPORT", &self.collection_url()?)?
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("CalDAV REPORT failed: HTTP {status}");
+        }
+        Ok(parse_multistatus(&text))
+    }
+
+    async fn find_event(&self, uid: &str) -> anyhow::Result<EventResource> {
+        let filter = format!(
+            r#"<c:prop-filter name="UID"><c:text-match collation="i;octet">{}</c:text-match></c:prop-filter>"#,
+            xml_escape(uid)
+        );
+        self.report(&filter)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No event with uid '{uid}' found"))
+    }
+
+    async fn put_event(&self, url: &str, ics: String, etag: Option<&str>) -> anyhow::Result<()> {
+        let mut request = self
+            .request("PUT", url)?
+            .header("Content-Type", "text/calendar; charset=utf-8");
+        request = match etag {
+            Some(etag) => request.header("If-Match", etag),
+            None => request.header("If-None-Match", "*"),
+        };
+        let response = request.body(ics).send().await?;
+        let status = response.status();
+        if status == reqwest::StatusCode::PRECONDITION_FAILED {
+            anyhow::bail!(
+                "The event changed on the server in the meantime; list it again and retry"
+            );
+        }
+        if !status.is_success() {
+            anyhow::bail!("CalDAV PUT failed: HTTP {status}");
+        }
+        Ok(())
+    }
+
+    async fn list(&self, args: &serde_json::Value, zone: Zone) -> anyhow::Result<String> {
+        let start = match args.get("start").and_then(|v| v.as_str()) {
+            Some(raw) => event_time_to_utc(parse_event_time(raw, zone)?, zone)?,
+            None => Utc::now(),
+        };
+        let end = match args.get("end").and_then(|v| v.as_str()) {
+            Some(raw) => match parse_event_time(raw, zone)? {
+                // A date as the end of a range means "through that day".
+                EventTime::Day(day) => {
+                    event_time_to_utc(EventTime::Day(day + Duration::days(1)), zone)?
+                }
+                at => event_time_to_utc(at, zone)?,
+            },
+            None => start + Duration::days(DEFAULT_LIST_DAYS),
+        };
+        if end <= start {
+            anyhow::bail!("'end' must be after 'start'");
+        }
+
+        let filter = format!(
+            r#"<c:time-range start="{}" end="{}"/>"#,
+            start.format("%Y%m%dT%H%M%SZ"),
+            end.format("%Y%m%dT%H%M%SZ")
+        );
+        let mut events: Vec<CalendarEvent> = self
+            .report(&filter)
+            .await?
+            .iter()
+            .filter_map(|resource| parse_event(&resource.ics, zone))
+            .collect();
+        events.sort_by_key(|event| event.start.and_then(|s| event_time_to_utc(s, zone).ok()));
+
+        let mut output = format!(
+            "Events from {} to {} ({}):\n",
+            zone.format(start, "%a %Y-%m-%d %H:%M"),
+            zone.format(end, "%a %Y-%m-%d %H:%M"),
+            zone.label()
+        );
+        if events.is_empty() {
+            output.push_str("(no events)");
+        }
+        for event in &events {
+            let _ = writeln!(output, "- {}", describe_event(event, zone));
+        }
+        Ok(output)
+    }
+
+    /// `[calendar].timezone`, else the user's zone.
+    fn zone(&self) -> Result<Zone, String> {
+        match self.config.timezone.as_deref().map(str::trim) {
+            Some(name) if !name.is_empty() => Zone::named(Some(name))
+                .map_err(|_| format!("Invalid IANA timezone in [calendar].timezone: {name}")),
+            _ => Ok(crate::time::zone()),
+        }
+    }
+
+    async fn write_action(
+        &self,
+        action: &str,
+        args: &serde_json::Value,
+        zone: Zone,
+    ) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(Self::error(
+                "Security policy: read-only mode, cannot perform 'calendar'",
+            ));
+        }
+
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let uid = event_uid(args);
+        let changes = parse_changes(args, zone)?;
+        let preview = write_preview(action, uid, &changes, zone)?;
+
+        if self.config.require_confirmation && !approved {
+            return Ok(Self::error(format!(
+                "Confirmation required before changing the calendar:\n{preview}\n\
+                 Ask the user to confirm, then repeat the call with approved=true."
+            )));
+        }
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "calendar")
+        {
+            return Ok(Self::error(error));
+        }
+
+        let message = match (action, uid) {
+            ("create", _) => {
+                let uid = format!("{}@zeroclaw", uuid::Uuid::new_v4());
+                let ics = build_event_ics(&uid, &changes, Utc::now())?;
+                let url = format!("{}{uid}.ics", self.collection_url()?);
+                self.put_event(&url, ics, None).await?;
+                format!(
+                    "Created event (uid: {uid}): {}",
+                    describe_changes(&changes, zone)
+                )
+            }
+            ("update", Some(uid)) => {
+                let resource = self.find_event(uid).await?;
+                let ics = apply_changes(&resource.ics, &changes, zone, Utc::now())?;
+                let url = self.resource_url(&resource.href)?;
+                self.put_event(&url, ics, resource.etag.as_deref()).await?;
+                format!("Updated event {uid}")
+            }
+            ("delete", Some(uid)) => {
+                let resource = self.find_event(uid).await?;
+                let url = self.resource_url(&resource.href)?;
+                let mut request = self.request("DELETE", &url)?;
+                if let Some(etag) = resource.etag.as_deref() {
+                    request = request.header("If-Match", etag);
+                }
+                let status = request.send().await?.status();
+                if !status.is_success() {
+                    anyhow::bail!("CalDAV DELETE failed: HTTP {status}");
+                }
+                format!("Deleted event {uid}")
+            }
+            _ => unreachable!("validated above"),
+        };
+
+        Ok(ToolResult {
+            success: true,
+            output: message,
+            error: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for CalendarTool {
+    fn name(&self) -> &str {
+        "calendar"
+    }
+
+    fn description(&self) -> &str {
+        "Manage the user's CalDAV calendar. Actions: list (events in a time range), \
+         create, update (by uid), delete (by uid). Times without an offset are in the \
+         calendar's configured timezone; phrases like 'tomorrow 9am' or 'next friday 3pm' \
+         work too. Pass a day only (YYYY-MM-DD, 'friday') for all-day events. \
+         Changes require user confirmation: the first call returns a preview; after the user \
+         agrees, repeat it with approved=true."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "create", "update", "delete"],
+                    "description": "Operation to perform"
+                },
+                "start": {
+                    "type": "string",
+                    "description": "Start time, e.g. 2026-03-10T14:00, 2026-03-10T14:00:00+01:00, 2026-03-10 or 'tomorrow 9am' (list: range start, default now)"
+                },
+                "end": {
+                    "type": "string",
+                    "description": "End time in the same formats (list: range end, default start + 7 days)"
+                },
+                "duration_minutes": {
+                    "type": "integer",
+                    "description": "Event length when 'end' is omitted (create default: 60)",
+                    "minimum": 1
+                },
+                "summary": {"type": "string", "description": "Event title"},
+                "location": {"type": "string", "description": "Event location"},
+                "description": {"type": "string", "description": "Event notes"},
+                "uid": {"type": "string", "description": "Event uid (from list) for update/delete"},
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true only after the user confirmed the previewed change",
+                    "default": false
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn dry_run(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let zone = self.zone().map_err(|e| anyhow::anyhow!(e))?;
+        match action {
+            "list" => self.list(&args, zone).await,
+            "create" | "update" | "delete" => {
+                if !self.security.can_act() {
+                    anyhow::bail!("Security policy: read-only mode, cannot perform 'calendar'");
+                }
+                let changes = parse_changes(&args, zone)?;
+                let preview = write_preview(action, event_uid(&args), &changes, zone)?;
+                Ok(format!(
+                    "Would {}{}",
+                    preview[..1].to_lowercase(),
+                    &preview[1..]
+                ))
+            }
+            other => anyhow::bail!("Unknown action '{other}'. Use list, create, update or delete."),
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+
+        let zone = match self.zone() {
+            Ok(zone) => zone,
+            Err(error) => return Ok(Self::error(error)),
+        };
+
+        if self.security.is_rate_limited() {
+            return Ok(Self::error(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        let result = match action {
+            "list" => {
+                if !self.security.record_action() {
+                    return Ok(Self::error("Rate limit exceeded: action budget exhausted"));
+                }
+                self.list(&args, zone).await.map(|output| ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
+            "create" | "update" | "delete" => self.write_action(action, &args, zone).await,
+            other => {
+                return Ok(Self::error(format!(
+                    "Unknown action '{other}'. Use list, create, update or delete."
+                )))
+            }
+        };
+
+        Ok(result.unwrap_or_else(|e| Self::error(e.to_string())))
+    }
+}
+
+/// The `uid` argument of update and delete.
+fn event_uid(args: &serde_json::Value) -> Option<&str> {
+    args.get("uid")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+}
+
+/// What a write action will change, as shown for confirmation.
+fn write_preview(
+    action: &str,
+    uid: Option<&str>,
+    changes: &EventChanges,
+    zone: Zone,
+) -> anyhow::Result<String> {
+    Ok(match (action, uid) {
+        ("create", _) => {
+            let summary = changes
+                .summary
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("Missing 'summary' for create"))?;
+            if changes.start.is_none() {
+                anyhow::bail!("Missing 'start' for create");
+            }
+            format!(
+                "Create event \"{summary}\" {}",
+                describe_changes(changes, zone)
+            )
+        }
+        ("update", Some(uid)) => {
+            format!("Update event {uid}: {}", describe_changes(changes, zone))
+        }
+        ("delete", Some(uid)) => format!("Delete event {uid}"),
+        (_, None) => anyhow::bail!("Missing 'uid' for {action}"),
+        _ => unreachable!("write_action is only called for create/update/delete"),
+    })
+}
+
+// ── Time handling ────────────────────────────────────────────────
+
+/// Parse user input: RFC 3339 with offset, a wall-clock time in `zone`, a
+/// date, or a phrase like "next friday 3pm" (see [`crate::time::parse_when`]).
+fn parse_event_time(raw: &str, zone: Zone) -> anyhow::Result<EventTime> {
+    Ok(match crate::time::parse_when(raw, zone, Utc::now())? {
+        When::At(at) => EventTime::At(at),
+        When::Day(day) => EventTime::Day(day),
+    })
+}
+
+fn event_time_to_utc(time: EventTime, zone: Zone) -> anyhow::Result<DateTime<Utc>> {
+    match time {
+        EventTime::At(at) => Ok(at),
+        EventTime::Day(day) => zone
+            .localize(day.and_hms_opt(0, 0, 0).expect("midnight is valid"))
+            .ok_or_else(|| anyhow::anyhow!("Midnight of {day} does not exist in {}", zone.label())),
+    }
+}
+
+/// Parse an iCalendar date/time value with its property parameters.
+fn parse_ics_time(params: &str, value: &str, zone: Zone) -> Option<EventTime> {
+    let value = value.trim();
+    if params.to_ascii_uppercase().contains("VALUE=DATE")
+        && !params.to_ascii_uppercase().contains("VALUE=DATE-TIME")
+    {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(EventTime::Day);
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(EventTime::At(Utc.from_utc_datetime(&naive)));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok();
+    let Some(naive) = naive else {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(EventTime::Day);
+    };
+    let tzid = params
+        .split(';')
+        .find_map(|param| param.strip_prefix("TZID="))
+        .map(|tz| tz.trim_matches('"'));
+    let event_zone = tzid
+        .and_then(|name| chrono_tz::Tz::from_str(name).ok())
+        .map_or(zone, Zone::Named);
+    event_zone.localize(naive).map(EventTime::At)
+}
+
+fn format_ics_time(name: &str, time: EventTime) -> String {
+    match time {
+        EventTime::At(at) => format!("{name}:{}", at.format("%Y%m%dT%H%M%SZ")),
+        EventTime::Day(day) => format!("{name};VALUE=DATE:{}", day.format("%Y%m%d")),
+    }
+}
+
+// ── iCalendar ────────────────────────────────────────────────────
+
+/// Unfold RFC 5545 content lines (continuations start with a space or tab).
+fn unfold_lines(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.split('\n').map(|l| l.trim_end_matches('\r')) {
+        if let Some(continuation) = line.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(continuation);
+                continue;
+            }
+        }
+        lines.push(line.to_string());
+    }
+    lines
+}
+
+/// Split `NAME;PARAMS:VALUE` into (name, params, value).
+fn split_content_line(line: &str) -> Option<(String, &str, &str)> {
+    let colon = find_value_colon(line)?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let (name, params) = head.split_once(';').unwrap_or((head, ""));
+    Some((name.to_ascii_uppercase(), params, value))
+}
+
+/// The first colon outside double-quoted parameter values.
+fn find_value_colon(line: &str) -> Option<usize> {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ':' if !quoted => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n' | 'N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets without splitting UTF-8 sequences.
+fn fold_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out
+}
+
+/// Parse the first `VEVENT` of an iCalendar object.
+fn parse_event(ics: &str, zone: Zone) -> Option<CalendarEvent> {
+    let mut event = CalendarEvent::default();
+    let mut in_event = false;
+    let mut duration: Option<Duration> = None;
+    for line in unfold_lines(ics) {
+        let Some((name, params, value)) = split_content_line(&line) else {
+            continue;
+        };
+        match (name.as_str(), value.trim()) {
+            ("BEGIN", "VEVENT") if !in_event && event.uid.is_empty() => in_event = true,
+            ("END", "VEVENT") if in_event => break,
+            _ if !in_event => {}
+            ("UID", _) => event.uid = value.trim().to_string(),
+            ("SUMMARY", _) => event.summary = unescape_text(value),
+            ("LOCATION", _) => event.location = Some(unescape_text(value)),
+            ("DESCRIPTION", _) => event.description = Some(unescape_text(value)),
+            ("DTSTART", _) => event.start = parse_ics_time(params, value, zone),
+            ("DTEND", _) => event.end = parse_ics_time(params, value, zone),
+            ("DURATION", _) => duration = parse_ics_duration(value),
+            ("RRULE", _) => event.recurring = true,
+            _ => {}
+        }
+    }
+    if event.end.is_none() {
+        if let (Some(EventTime::At(start)), Some(duration)) = (event.start, duration) {
+            event.end = Some(EventTime::At(start + duration));
+        }
+    }
+    (!event.uid.is_empty()).then_some(event)
+}
+
+/// Parse the common subset of RFC 5545 durations (`PT1H30M`, `P1D`, `P1W`).
+fn parse_ics_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, value) = value
+        .strip_prefix('-')
+        .map_or((false, value.trim_start_matches('+')), |v| (true, v));
+    let mut rest = value.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut in_time = false;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('T') {
+            in_time = true;
+            rest = after;
+            continue;
+        }
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let amount: i64 = rest[..digits].parse().ok()?;
+        let unit = rest[digits..].chars().next()?;
+        total += match (unit, in_time) {
+            ('W', false) => Duration::weeks(amount),
+            ('D', false) => Duration::days(amount),
+            ('H', true) => Duration::hours(amount),
+            ('M', true) => Duration::minutes(amount),
+            ('S', true) => Duration::seconds(amount),
+            _ => return None,
+        };
+        rest = &rest[digits + unit.len_utf8()..];
+    }
+    Some(if negative { -total } else { total })
+}
+
+fn parse_changes(args: &serde_json::Value, zone: Zone) -> anyhow::Result<EventChanges> {
+    let text = |key: &str| {
+        args.get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+    };
+    let start = text("start")
+        .map(|raw| parse_event_time(&raw, zone))
+        .transpose()?;
+    let mut end = text("end")
+        .map(|raw| parse_event_time(&raw, zone))
+        .transpose()?;
+    let duration = args
+        .get("duration_minutes")
+        .and_then(serde_json::Value::as_i64)
+        .filter(|minutes| *minutes > 0);
+
+    if end.is_none() {
+        end = match (start, duration) {
+            (Some(EventTime::At(at)), Some(minutes)) => {
+                Some(EventTime::At(at + Duration::minutes(minutes)))
+            }
+            (Some(EventTime::Day(day)), _) => Some(EventTime::Day(day + Duration::days(1))),
+            _ => None,
+        };
+    }
+    if let (Some(start), Some(end)) = (start, end) {
+        if event_time_to_utc(end, zone)? <= event_time_to_utc(start, zone)? {
+            anyhow::bail!("Event end must be after its start");
+        }
+    }
+
+    Ok(EventChanges {
+        summary: text("summary").filter(|s| !s.is_empty()),
+        start,
+        end,
+        location: text("location"),
+        description: text("description"),
+    })
+}
+
+/// Build a new single-event iCalendar object.
+fn build_event_ics(
+    uid: &str,
+    changes: &EventChanges,
+    now: DateTime<Utc>,
+) -> anyhow::Result<String> {
+    let start = changes
+        .start
+        .ok_or_else(|| anyhow::anyhow!("Missing 'start' for create"))?;
+    let end = match (changes.end, start) {
+        (Some(end), _) => end,
+        (None, EventTime::At(at)) => EventTime::At(at + Duration::minutes(DEFAULT_EVENT_MINUTES)),
+        (None, EventTime::Day(day)) => EventTime::Day(day + Duration::days(1)),
+    };
+
+    let stamp = now.format("%Y%m%dT%H%M%SZ");
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//zeroclaw//calendar tool//EN".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{uid}"),
+        format!("DTSTAMP:{stamp}"),
+        format!("CREATED:{stamp}"),
+        format_ics_time("DTSTART", start),
+        format_ics_time("DTEND", end),
+        format!(
+            "SUMMARY:{}",
+            escape_text(changes.summary.as_deref().unwrap_or("Event"))
+        ),
+    ];
+    if let Some(location) = changes.location.as_deref().filter(|l| !l.is_empty()) {
+        lines.push(format!("LOCATION:{}", escape_text(location)));
+    }
+    if let Some(description) = changes.description.as_deref().filter(|d| !d.is_empty()) {
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+    }
+    lines.push("END:VEVENT".to_string());
+    lines.push("END:VCALENDAR".to_string());
+    Ok(join_ics(&lines))
+}
+
+fn join_ics(lines: &[String]) -> String {
+    let mut ics = String::new();
+    for line in lines {
+        ics.push_str(&fold_line(line));
+        ics.push_str("\r\n");
+    }
+    ics
+}
+
+/// Rewrite the first `VEVENT` of an existing object with `changes`, keeping
+/// every property the tool does not touch.
+fn apply_changes(
+    ics: &str,
+    changes: &EventChanges,
+    zone: Zone,
+    now: DateTime<Utc>,
+) -> anyhow::Result<String> {
+    let current = parse_event(ics, zone)
+        .ok_or_else(|| anyhow::anyhow!("Server returned an event without a VEVENT/UID"))?;
+
+    // Moving the start without an explicit end keeps the event's length.
+    let mut end = changes.end;
+    if end.is_none() {
+        if let (
+            Some(EventTime::At(new_start)),
+            Some(EventTime::At(old_start)),
+            Some(EventTime::At(old_end)),
+        ) = (changes.start, current.start, current.end)
+        {
+            end = Some(EventTime::At(new_start + (old_end - old_start)));
+        }
+    }
+
+    let mut replacements: Vec<(&str, String)> = Vec::new();
+    if let Some(summary) = &changes.summary {
+        replacements.push(("SUMMARY", format!("SUMMARY:{}", escape_text(summary))));
+    }
+    if let Some(location) = &changes.location {
+        replacements.push(("LOCATION", format!("LOCATION:{}", escape_text(location))));
+    }
+    if let Some(description) = &changes.description {
+        replacements.push((
+            "DESCRIPTION",
+            format!("DESCRIPTION:{}", escape_text(description)),
+        ));
+    }
+    if let Some(start) = changes.start {
+        replacements.push(("DTSTART", format_ics_time("DTSTART", start)));
+    }
+    if let Some(end) = end {
+        replacements.push(("DTEND", format_ics_time("DTEND", end)));
+    }
+    if replacements.is_empty() {
+        anyhow::bail!("Nothing to update: pass summary, start, end, duration_minutes, location or description");
+    }
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    replacements.push(("DTSTAMP", format!("DTSTAMP:{stamp}")));
+    replacements.push(("LAST-MODIFIED", format!("LAST-MODIFIED:{stamp}")));
+
+    let mut out: Vec<String> = Vec::new();
+    let mut state = 0; // 0 = before first VEVENT, 1 = inside it, 2 = after it
+    let mut sequence: Option<u64> = None;
+    for line in unfold_lines(ics) {
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = split_content_line(&line);
+        let name = parsed.as_ref().map_or("", |(name, _, _)| name.as_str());
+        let value = parsed.as_ref().map_or("", |(_, _, value)| value.trim());
+        if state == 0 && name == "BEGIN" && value == "VEVENT" {
+            state = 1;
+        } else if state == 1 && name == "END" && value == "VEVENT" {
+            for (_, replacement) in &replacements {
+                out.push(replacement.clone());
+            }
+            out.push(format!("SEQUENCE:{}", sequence.map_or(1, |s| s + 1)));
+            state = 2;
+        } else if state == 1 {
+            if name == "SEQUENCE" {
+                sequence = value.parse().ok();
+                continue;
+            }
+            let replaced = replacements.iter().any(|(prop, _)| *prop == name)
+                || (name == "DURATION" && end.is_some());
+            if replaced {
+                continue;
+            }
+        }
+        out.push(line);
+    }
+    if state != 2 {
+        anyhow::bail!("Server returned a malformed event (unterminated VEVENT)");
+    }
+    Ok(join_ics(&out))
+}
+
+fn describe_time(time: EventTime, zone: Zone) -> String {
+    match time {
+        EventTime::At(at) => zone.format(at, "%a %Y-%m-%d %H:%M"),
+        EventTime::Day(day) => day.format("%a %Y-%m-%d").to_string(),
+    }
+}
+
+fn describe_event(event: &CalendarEvent, zone: Zone) -> String {
+    let when = match (event.start, event.end) {
+        (Some(EventTime::Day(start)), Some(EventTime::Day(end)))
+            if end > start + Duration::days(1) =>
+        {
+            format!(
+                "{} – {} (all day)",
+                start.format("%a %Y-%m-%d"),
+                (end - Duration::days(1)).format("%a %Y-%m-%d")
+            )
+        }
+        (Some(EventTime::Day(start)), _) => format!("{} (all day)", start.format("%a %Y-%m-%d")),
+        (Some(EventTime::At(start)), Some(EventTime::At(end)))
+            if zone.format(start, "%Y-%m-%d") == zone.format(end, "%Y-%m-%d") =>
+        {
+            format!(
+                "{}–{}",
+                zone.format(start, "%a %Y-%m-%d %H:%M"),
+                zone.format(end, "%H:%M")
+            )
+        }
+        (Some(start), Some(end)) => format!(
+            "{} – {}",
+            describe_time(start, zone),
+            describe_time(end, zone)
+        ),
+        (Some(start), None) => describe_time(start, zone),
+        (None, _) => "(no start time)".to_string(),
+    };
+    let mut line = format!(
+        "{when} {}",
+        if event.summary.is_empty() {
+            "(untitled)"
+        } else {
+            &event.summary
+        }
+    );
+    if let Some(location) = event.location.as_deref().filter(|l| !l.is_empty()) {
+        let _ = write!(line, " @ {location}");
+    }
+    if event.recurring {
+        line.push_str(" [recurring]");
+    }
+    let _ = write!(line, " (uid: {})", event.uid);
+    line
+}
+
+fn describe_changes(changes: &EventChanges, zone: Zone) -> String {
+    let mut parts = Vec::new();
+    if let Some(summary) = &changes.summary {
+        parts.push(format!("title \"{summary}\""));
+    }
+    if let Some(start) = changes.start {
+        parts.push(format!("start {}", describe_time(start, zone)));
+    }
+    if let Some(end) = changes.end {
+        parts.push(format!("end {}", describe_time(end, zone)));
+    }
+    if let Some(location) = &changes.location {
+        parts.push(format!("location \"{location}\""));
+    }
+    if let Some(description) = &changes.description {
+        parts.push(format!("notes \"{description}\""));
+    }
+    if parts.is_empty() {
+        "(no changes)".to_string()
+    } else {
+        format!("{} ({})", parts.join(", "), zone.label())
+    }
+}
+
+// ── WebDAV XML ───────────────────────────────────────────────────
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Inner text of every `<prefix:name>` element (any namespace prefix).
+fn xml_elements<'a>(xml: &'a str, local_name: &str) -> Vec<&'a str> {
+    let pattern = format!(
+        r"(?s)<(?:[A-Za-z0-9_.-]+:)?{name}(?:\s[^>]*)?>(.*?)</(?:[A-Za-z0-9_.-]+:)?{name}\s*>",
+        name = regex::escape(local_name)
+    );
+    let Ok(re) = regex::Regex::new(&pattern) else {
+        return Vec::new();
+    };
+    re.captures_iter(xml)
+        .filter_map(|caps| caps.get(1).map(|m| m.as_str()))
+        .collect()
+}
+
+fn xml_text(raw: &str) -> String {
+    let raw = raw.trim();
+    match raw
+        .strip_prefix("<![CDATA[")
+        .and_then(|rest| rest.strip_suffix("]]>"))
+    {
+        Some(cdata) => cdata.to_string(),
+        None => super::html_extract::decode_entities(raw),
+    }
+}
+
+/// Parse a CalDAV multistatus response into event resources.
+fn parse_multistatus(xml: &str) -> Vec<EventResource> {
+    xml_elements(xml, "response")
+        .into_iter()
+        .filter_map(|response| {
+            let href = xml_elements(response, "href")
+                .first()
+                .map(|h| xml_text(h))?;
+            let ics = xml_elements(response, "calendar-data")
+                .first()
+                .map(|data| xml_text(data))
+                .filter(|ics| ics.contains("BEGIN:VEVENT"))?;
+            let etag = xml_elements(response, "getetag")
+                .first()
+                .map(|etag| xml_text(etag))
+                .filter(|etag| !etag.is_empty());
+            Some(EventResource { href, etag, ics })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn berlin() -> Zone {
+        Zone::Named(chrono_tz::Europe::Berlin)
+    }
+
+    fn utc(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn test_tool(autonomy: AutonomyLevel, require_confirmation: bool) -> CalendarTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        });
+        CalendarTool::new(
+            security,
+            CalendarConfig {
+                enabled: true,
+                url: "https://dav.example.com/calendars/alice/personal/".into(),
+                timezone: Some("Europe/Berlin".into()),
+                require_confirmation,
+                ..CalendarConfig::default()
+            },
+        )
+    }
+
+    const SAMPLE_ICS: &str = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VTIMEZONE\r\nTZID:Europe/Berlin\r\nEND:VTIMEZONE\r\nBEGIN:VEVENT\r\nUID:abc-123\r\nDTSTAMP:20260301T090000Z\r\nDTSTART;TZID=Europe/Berlin:20260310T140000\r\nDTEND;TZID=Europe/Berlin:20260310T150000\r\nSUMMARY:Call with Dana\\, re: budget\r\nDESCRIPTION:Line one\\nline two that is long enough to be folded across two physical\r\n  lines\r\nLOCATION:Room 4\r\nSEQUENCE:2\r\nX-CUSTOM:keep me\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+    #[test]
+    fn parses_event_with_tzid_folding_and_escapes() {
+        let event = parse_event(SAMPLE_ICS, Zone::Named(chrono_tz::UTC)).unwrap();
+        assert_eq!(event.uid, "abc-123");
+        assert_eq!(event.summary, "Call with Dana, re: budget");
+        assert_eq!(
+            event.start,
+            Some(EventTime::At(utc("2026-03-10T13:00:00Z")))
+        );
+        assert_eq!(event.end, Some(EventTime::At(utc("2026-03-10T14:00:00Z"))));
+        assert_eq!(
+            event.description.as_deref(),
+            Some("Line one\nline two that is long enough to be folded across two physical lines")
+        );
+        assert_eq!(event.location.as_deref(), Some("Room 4"));
+        assert!(!event.recurring);
+    }
+
+    #[test]
+    fn parses_all_day_and_duration_events() {
+        let all_day = "BEGIN:VEVENT\nUID:d1\nDTSTART;VALUE=DATE:20260311\nDTEND;VALUE=DATE:20260312\nSUMMARY:Offsite\nRRULE:FREQ=YEARLY\nEND:VEVENT\n";
+        let event = parse_event(all_day, berlin()).unwrap();
+        assert_eq!(
+            event.start,
+            Some(EventTime::Day(
+                NaiveDate::from_ymd_opt(2026, 3, 11).unwrap()
+            ))
+        );
+        assert!(event.recurring);
+        assert_eq!(
+            describe_event(&event, berlin()),
+            "Wed 2026-03-11 (all day) Offsite [recurring] (uid: d1)"
+        );
+
+        let with_duration =
+            "BEGIN:VEVENT\nUID:d2\nDTSTART:20260310T080000Z\nDURATION:PT1H30M\nEND:VEVENT\n";
+        let event = parse_event(with_duration, berlin()).unwrap();
+        assert_eq!(event.end, Some(EventTime::At(utc("2026-03-10T09:30:00Z"))));
+        assert_eq!(parse_ics_duration("P1W"), Some(Duration::weeks(1)));
+        assert_eq!(parse_ics_duration("garbage"), None);
+    }
+
+    #[test]
+    fn user_times_respect_configured_timezone() {
+        assert_eq!(
+            parse_event_time("2026-03-10T14:00", berlin()).unwrap(),
+            EventTime::At(utc("2026-03-10T13:00:00Z"))
+        );
+        // Summer time: UTC+2.
+        assert_eq!(
+            parse_event_time("2026-07-10 14:00", berlin()).unwrap(),
+            EventTime::At(utc("2026-07-10T12:00:00Z"))
+        );
+        assert_eq!(
+            parse_event_time("2026-03-10T14:00:00-05:00", berlin()).unwrap(),
+            EventTime::At(utc("2026-03-10T19:00:00Z"))
+        );
+        assert_eq!(
+            parse_event_time("2026-03-10", berlin()).unwrap(),
+            EventTime::Day(NaiveDate::from_ymd_opt(2026, 3, 10).unwrap())
+        );
+        // Skipped by the spring-forward transition.
+        assert!(parse_event_time("2026-03-29T02:30", berlin()).is_err());
+        assert!(matches!(
+            parse_event_time("next tuesday", berlin()),
+            Ok(EventTime::Day(_))
+        ));
+        assert!(parse_event_time("someday", berlin()).is_err());
+    }
+
+    #[test]
+    fn builds_new_event_in_utc() {
+        let changes = parse_changes(
+            &json!({"summary": "Sync, weekly", "start": "2026-03-10T14:00", "duration_minutes": 30}),
+            berlin(),
+        )
+        .unwrap();
+        let ics = build_event_ics("u1@zeroclaw", &changes, utc("2026-03-01T00:00:00Z")).unwrap();
+        assert!(ics.contains("DTSTART:20260310T130000Z\r\n"));
+        assert!(ics.contains("DTEND:20260310T133000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Sync\\, weekly\r\n"));
+        let event = parse_event(&ics, berlin()).unwrap();
+        assert_eq!(event.summary, "Sync, weekly");
+    }
+
+    #[test]
+    fn update_keeps_unknown_properties_and_duration() {
+        let changes = EventChanges {
+            start: Some(parse_event_time("2026-03-12T10:00", berlin()).unwrap()),
+            ..EventChanges::default()
+        };
+        let updated =
+            apply_changes(SAMPLE_ICS, &changes, berlin(), utc("2026-03-02T00:00:00Z")).unwrap();
+        assert!(updated.contains("X-CUSTOM:keep me"));
+        assert!(updated.contains("BEGIN:VTIMEZONE"));
+        assert!(updated.contains("DTSTART:20260312T090000Z"));
+        // One-hour length preserved.
+        assert!(updated.contains("DTEND:20260312T100000Z"));
+        assert!(updated.contains("SEQUENCE:3"));
+        assert!(!updated.contains("TZID=Europe/Berlin:20260310"));
+        let event = parse_event(&updated, berlin()).unwrap();
+        assert_eq!(event.summary, "Call with Dana, re: budget");
+
+        assert!(apply_changes(SAMPLE_ICS, &EventChanges::default(), berlin(), Utc::now()).is_err());
+    }
+
+    #[test]
+    fn long_lines_are_folded() {
+        let line = format!("DESCRIPTION:{}", "é".repeat(60));
+        let folded = fold_line(&line);
+        assert!(folded.split("\r\n").all(|part| part.len() <= 75));
+        assert_eq!(unfold_lines(&folded), vec![line]);
+    }
+
+    #[test]
+    fn parses_multistatus_with_any_prefix() {
+        let xml = r#"<?xml version="1.0"?>
+<D:multistatus xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:response>
+    <D:href>/calendars/alice/personal/abc-123.ics</D:href>
+    <D:propstat><D:prop>
+      <D:getetag>"etag-1"</D:getetag>
+      <C:calendar-data>BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:abc-123
+SUMMARY:Tom &amp; Jerry
+DTSTART:20260310T130000Z
+END:VEVENT
+END:VCALENDAR</C:calendar-data>
+    </D:prop></D:propstat>
+  </D:response>
+  <d:response xmlns:d="DAV:"><d:href>/calendars/alice/personal/</d:href></d:response>
+</D:multistatus>"#;
+        let resources = parse_multistatus(xml);
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].href, "/calendars/alice/personal/abc-123.ics");
+        assert_eq!(resources[0].etag.as_deref(), Some("\"etag-1\""));
+        assert!(resources[0].ics.contains("SUMMARY:Tom & Jerry"));
+
+        let tool = test_tool(AutonomyLevel::Supervised, true);
+        assert_eq!(
+            tool.resource_url(&resources[0].href).unwrap(),
+            "https://dav.example.com/calendars/alice/personal/abc-123.ics"
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_need_confirmation_first() {
+        let tool = test_tool(AutonomyLevel::Supervised, true);
+        let result = tool
+            .execute(json!({"action": "create", "summary": "Call", "start": "2026-03-10T14:00"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.contains("Confirmation required"));
+        assert!(error.contains("Create event \"Call\""));
+        assert!(error.contains("Tue 2026-03-10 14:00"));
+        assert!(error.contains("approved=true"));
+    }
+
+    #[tokio::test]
+    async fn dry_run_describes_the_change() {
+        let tool = test_tool(AutonomyLevel::Supervised, false);
+        let plan = tool
+            .dry_run(json!({"action": "delete", "uid": "abc"}))
+            .await
+            .unwrap();
+        assert_eq!(plan, "Would delete event abc");
+        assert!(tool
+            .dry_run(json!({"action": "create", "summary": "Call"}))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Missing 'start'"));
+    }
+
+    #[tokio::test]
+    async fn read_only_mode_blocks_writes() {
+        let tool = test_tool(AutonomyLevel::ReadOnly, true);
+        let result = tool
+            .execute(json!({"action": "delete", "uid": "abc", "approved": true}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_are_rejected_before_network() {
+        let tool = test_tool(AutonomyLevel::Supervised, true);
+        let missing_uid = tool.execute(json!({"action": "update"})).await.unwrap();
+        assert!(missing_uid.error.unwrap().contains("Missing 'uid'"));
+
+        let backwards = tool
+            .execute(json!({"action": "create", "summary": "x", "start": "2026-03-10T14:00", "end": "2026-03-10T13:00"}))
+            .await
+            .unwrap();
+        assert!(backwards.error.unwrap().contains("end must be after"));
+
+        let unknown = tool.execute(json!({"action": "purge"})).await.unwrap();
+        assert!(unknown.error.unwrap().contains("Unknown action"));
+
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+}
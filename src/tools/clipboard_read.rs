@@ -0,0 +1,137 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::ClipboardConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Read text from the system clipboard (desktop/CLI use).
+///
+/// Requires the `clipboard` build feature and `[clipboard].enabled = true`.
+pub struct ClipboardReadTool {
+    security: Arc<SecurityPolicy>,
+    config: ClipboardConfig,
+}
+
+impl ClipboardReadTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: ClipboardConfig) -> Self {
+        Self { security, config }
+    }
+}
+
+#[cfg(feature = "clipboard")]
+fn read_clipboard_text() -> anyhow::Result<String> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| anyhow::anyhow!("Clipboard unavailable: {e}"))?;
+    match clipboard.get_text() {
+        Ok(text) => Ok(text),
+        Err(arboard::Error::ContentNotAvailable) => Ok(String::new()),
+        Err(e) => Err(anyhow::anyhow!("Failed to read clipboard: {e}")),
+    }
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn read_clipboard_text() -> anyhow::Result<String> {
+    anyhow::bail!(
+        "Clipboard support is not enabled. Rebuild with: cargo build --features clipboard"
+    )
+}
+
+#[async_trait]
+impl Tool for ClipboardReadTool {
+    fn name(&self) -> &str {
+        "clipboard_read"
+    }
+
+    fn description(&self) -> &str {
+        "Read the text currently on the user's system clipboard. \
+         Use when the user refers to something they just copied."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        // Platform clipboards are synchronous (X11/Wayland round-trips); keep them off the executor.
+        let text = match tokio::task::spawn_blocking(read_clipboard_text).await {
+            Ok(Ok(text)) => text,
+            Ok(Err(e)) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e.to_string()),
+                })
+            }
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Clipboard task panicked: {e}")),
+                })
+            }
+        };
+
+        if text.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: "Clipboard is empty or does not contain text".into(),
+                error: None,
+            });
+        }
+
+        let max_chars = self.config.max_read_chars;
+        let output = if text.chars().count() > max_chars {
+            let mut truncated: String = text.chars().take(max_chars).collect();
+            use std::fmt::Write as _;
+            let _ = write!(truncated, "\n\n... [truncated at {max_chars} chars]");
+            truncated
+        } else {
+            text
+        };
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_tool() -> ClipboardReadTool {
+        ClipboardReadTool::new(
+            Arc::new(SecurityPolicy::default()),
+            ClipboardConfig::default(),
+        )
+    }
+
+    #[test]
+    fn name_and_schema() {
+        let tool = test_tool();
+        assert_eq!(tool.name(), "clipboard_read");
+        assert!(tool.parameters_schema()["properties"].is_object());
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    #[tokio::test]
+    async fn without_feature_returns_clear_error() {
+        let result = test_tool().execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("--features clipboard"));
+    }
+}
@@ -0,0 +1,162 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::ClipboardConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Put text on the system clipboard (desktop/CLI use).
+///
+/// Requires the `clipboard` build feature and `[clipboard].enabled = true`.
+pub struct ClipboardWriteTool {
+    security: Arc<SecurityPolicy>,
+    config: ClipboardConfig,
+}
+
+impl ClipboardWriteTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: ClipboardConfig) -> Self {
+        Self { security, config }
+    }
+}
+
+#[cfg(feature = "clipboard")]
+fn write_clipboard_text(text: String) -> anyhow::Result<()> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| anyhow::anyhow!("Clipboard unavailable: {e}"))?;
+    clipboard
+        .set_text(text)
+        .map_err(|e| anyhow::anyhow!("Failed to write clipboard: {e}"))
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn write_clipboard_text(_text: String) -> anyhow::Result<()> {
+    anyhow::bail!(
+        "Clipboard support is not enabled. Rebuild with: cargo build --features clipboard"
+    )
+}
+
+#[async_trait]
+impl Tool for ClipboardWriteTool {
+    fn name(&self) -> &str {
+        "clipboard_write"
+    }
+
+    fn description(&self) -> &str {
+        "Replace the user's system clipboard with the given text, \
+         e.g. a generated snippet or command the user asked to copy."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "text": {
+                    "type": "string",
+                    "description": "Text to place on the clipboard"
+                }
+            },
+            "required": ["text"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let text = args
+            .get("text")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'text' parameter"))?
+            .to_string();
+
+        let chars = text.chars().count();
+        if chars > self.config.max_write_chars {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Text too long for clipboard: {chars} chars (limit: {})",
+                    self.config.max_write_chars
+                )),
+            });
+        }
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "clipboard_write")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        match tokio::task::spawn_blocking(move || write_clipboard_text(text)).await {
+            Ok(Ok(())) => Ok(ToolResult {
+                success: true,
+                output: format!("Copied {chars} characters to the clipboard"),
+                error: None,
+            }),
+            Ok(Err(e)) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Clipboard task panicked: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_tool(autonomy: AutonomyLevel) -> ClipboardWriteTool {
+        ClipboardWriteTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy,
+                ..SecurityPolicy::default()
+            }),
+            ClipboardConfig {
+                max_write_chars: 10,
+                ..ClipboardConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn name_and_schema() {
+        let tool = test_tool(AutonomyLevel::Supervised);
+        assert_eq!(tool.name(), "clipboard_write");
+        assert_eq!(tool.parameters_schema()["required"], json!(["text"]));
+    }
+
+    #[tokio::test]
+    async fn missing_text_is_an_error() {
+        let tool = test_tool(AutonomyLevel::Supervised);
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn oversized_text_is_rejected() {
+        let tool = test_tool(AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({"text": "this is far too long"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("too long"));
+    }
+
+    #[tokio::test]
+    async fn read_only_mode_blocks_write() {
+        let tool = test_tool(AutonomyLevel::ReadOnly);
+        let result = tool.execute(json!({"text": "hi"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
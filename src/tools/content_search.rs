@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolResult, ToolRisk};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -97,6 +97,10 @@ impl Tool for ContentSearchTool {
         })
     }
 
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         // --- Parse parameters ---
         let pattern = args
@@ -187,13 +191,12 @@ impl Tool for ContentSearchTool {
             });
         }
 
-        if !self.security.is_path_allowed(search_path) {
+        let ws = self.security.workspace();
+        if let Err(error) = ws.check(search_path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(format!(
-                    "Path '{search_path}' is not allowed by security policy."
-                )),
+                error: Some(error),
             });
         }
 
@@ -208,29 +211,17 @@ impl Tool for ContentSearchTool {
 
         // --- Resolve search directory ---
         let workspace = &self.security.workspace_dir;
-        let resolved_path = workspace.join(search_path);
-
-        let resolved_canon = match std::fs::canonicalize(&resolved_path) {
-            Ok(p) => p,
-            Err(e) => {
+        let resolved_canon = match ws.resolve(search_path).await {
+            Ok(path) => path,
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Cannot resolve path '{search_path}': {e}")),
+                    error: Some(format!("Cannot search '{search_path}': {error}")),
                 });
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_canon) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!(
-                    "Resolved path for '{search_path}' is outside the allowed workspace."
-                )),
-            });
-        }
-
         // --- Multiline check for grep fallback ---
         if multiline && !self.has_rg {
             return Ok(ToolResult {
@@ -68,7 +68,7 @@ impl Tool for CronAddTool {
                 "name": { "type": "string" },
                 "schedule": {
                     "type": "object",
-                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms}"
+                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms}. 'at' takes RFC3339 or a phrase like 'tomorrow 9am'; times without an offset and cron without tz use the user's timezone."
                 },
                 "job_type": { "type": "string", "enum": ["shell", "agent"] },
                 "command": { "type": "string" },
@@ -106,7 +106,7 @@ impl Tool for CronAddTool {
         }
 
         let schedule = match args.get("schedule") {
-            Some(v) => match serde_json::from_value::<Schedule>(v.clone()) {
+            Some(v) => match resolve_schedule(v) {
                 Ok(schedule) => schedule,
                 Err(e) => {
                     return Ok(ToolResult {
@@ -246,6 +246,7 @@ impl Tool for CronAddTool {
                     delete_after_run,
                 )
             }
+            JobType::Declared => unreachable!("cron_add only parses shell and agent job types"),
         };
 
         match result {
@@ -270,6 +271,33 @@ impl Tool for CronAddTool {
     }
 }
 
+/// Parse the `schedule` argument. `{kind:'at'}` also takes phrases like
+/// "tomorrow 9am", and cron expressions without `tz` run in the user's
+/// timezone when one is configured.
+fn resolve_schedule(value: &serde_json::Value) -> anyhow::Result<Schedule> {
+    let mut value = value.clone();
+    match value.get("kind").and_then(serde_json::Value::as_str) {
+        Some("at") => {
+            if let Some(raw) = value.get("at").and_then(serde_json::Value::as_str) {
+                let at = crate::time::parse_instant(raw, crate::time::zone(), chrono::Utc::now())?;
+                value["at"] = json!(at.to_rfc3339());
+            }
+        }
+        Some("cron")
+            if value
+                .get("tz")
+                .and_then(serde_json::Value::as_str)
+                .is_none() =>
+        {
+            if let crate::time::Zone::Named(tz) = crate::time::zone() {
+                value["tz"] = json!(tz.name());
+            }
+        }
+        _ => {}
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -482,4 +510,19 @@ mod tests {
             .unwrap_or_default()
             .contains("Missing 'prompt'"));
     }
+
+    #[test]
+    fn one_shot_schedules_accept_phrases() {
+        let before = chrono::Utc::now();
+        let Schedule::At { at } =
+            resolve_schedule(&json!({ "kind": "at", "at": "in 2 hours" })).unwrap()
+        else {
+            panic!("expected a one-shot schedule");
+        };
+        assert!(at >= before + chrono::Duration::hours(2));
+        assert!(at <= chrono::Utc::now() + chrono::Duration::hours(2));
+
+        let error = resolve_schedule(&json!({ "kind": "at", "at": "friday" })).unwrap_err();
+        assert!(error.to_string().contains("no time of day"));
+    }
 }
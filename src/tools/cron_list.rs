@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolResult, ToolRisk};
 use crate::config::Config;
 use crate::cron;
 use async_trait::async_trait;
@@ -33,6 +33,10 @@ impl Tool for CronListTool {
         })
     }
 
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
     async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolResult, ToolRisk};
 use crate::config::Config;
 use crate::cron;
 use async_trait::async_trait;
@@ -50,6 +50,10 @@ impl Tool for CronRunsTool {
         })
     }
 
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
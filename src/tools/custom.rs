@@ -0,0 +1,517 @@
+use super::shell::{collect_allowed_shell_env_vars, truncate_at_char_boundary};
+use super::traits::{Tool, ToolResult, ToolRisk};
+use crate::config::{CustomToolConfig, CustomToolHttpConfig};
+use crate::runtime::RuntimeAdapter;
+use crate::security::policy::ToolOperation;
+use crate::security::{Sandbox, SecurityPolicy};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Placeholder name that expands to the whole argument object.
+pub const ARGS_PLACEHOLDER: &str = "args";
+
+/// Maximum HTTP response body returned to the model.
+const MAX_HTTP_RESPONSE_BYTES: usize = 1_048_576;
+
+/// A tool defined in `[[custom_tools]]`: a shell command or HTTP request
+/// template filled in from the model's arguments.
+///
+/// `{{name}}` placeholders are replaced with the argument of that name,
+/// escaped for where they appear: shell-quoted in commands, percent-encoded
+/// in URLs, JSON-encoded in request bodies and verbatim in headers.
+/// `{{args}}` stands for the whole argument object. Command placeholders may
+/// not sit inside quotes, and HTTP requests only reach hosts in
+/// `[http_request].allowed_domains`.
+pub struct CustomTool {
+    config: CustomToolConfig,
+    security: Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    sandbox: Arc<dyn Sandbox>,
+    allowed_domains: Vec<String>,
+    client: OnceLock<reqwest::Client>,
+}
+
+impl CustomTool {
+    pub fn new(
+        config: CustomToolConfig,
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+        sandbox: Arc<dyn Sandbox>,
+    ) -> Self {
+        Self {
+            config,
+            security,
+            runtime,
+            sandbox,
+            allowed_domains: Vec::new(),
+            client: OnceLock::new(),
+        }
+    }
+
+    /// Hosts HTTP templates may reach, as in `[http_request].allowed_domains`.
+    pub fn with_allowed_domains(mut self, domains: Vec<String>) -> Self {
+        self.allowed_domains = super::http_request::normalize_allowed_domains(domains);
+        self
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        self.client.get_or_init(|| {
+            crate::config::build_runtime_proxy_client_with_timeouts(
+                "tool.custom",
+                self.config.timeout_secs,
+                10,
+            )
+        })
+    }
+
+    async fn run_command(&self, template: &str, args: &Value) -> anyhow::Result<ToolResult> {
+        if let Some(name) = quoted_placeholder(template) {
+            return Ok(Self::failure(format!(
+                "Placeholder {{{{{name}}}}} is inside quotes in the command template"
+            )));
+        }
+        let command = render_template(template, args, shell_quote)?;
+
+        let mut cmd = match self
+            .runtime
+            .build_shell_command(&command, &self.security.workspace_dir)
+        {
+            Ok(cmd) => cmd,
+            Err(e) => {
+                return Ok(Self::failure(format!(
+                    "Failed to build runtime command: {e}"
+                )))
+            }
+        };
+        if self.runtime.name() == "native" {
+            if let Err(e) = self.sandbox.wrap_command(cmd.as_std_mut()) {
+                return Ok(Self::failure(format!(
+                    "Failed to apply {} sandbox: {e}",
+                    self.sandbox.name()
+                )));
+            }
+            cmd.current_dir(&self.security.workspace_dir);
+        }
+        // Same environment hygiene as the shell tool: no inherited secrets.
+        cmd.env_clear();
+        for var in collect_allowed_shell_env_vars(&self.security) {
+            if let Ok(val) = std::env::var(&var) {
+                cmd.env(&var, val);
+            }
+        }
+        cmd.kill_on_drop(true);
+
+        let timeout_secs = self.config.timeout_secs.max(1);
+        match tokio::time::timeout(Duration::from_secs(timeout_secs), cmd.output()).await {
+            Ok(Ok(output)) => {
+                let max_output_bytes = self.security.shell_max_output_bytes.max(1);
+                let mut stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+                let mut stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+                if stdout.len() > max_output_bytes {
+                    truncate_at_char_boundary(&mut stdout, max_output_bytes);
+                    stdout.push_str("\n... [output truncated]");
+                }
+                truncate_at_char_boundary(&mut stderr, max_output_bytes);
+                Ok(ToolResult {
+                    success: output.status.success(),
+                    output: stdout,
+                    error: (!stderr.is_empty()).then_some(stderr),
+                })
+            }
+            Ok(Err(e)) => Ok(Self::failure(format!("Failed to execute command: {e}"))),
+            Err(_) => Ok(Self::failure(format!(
+                "Command timed out after {timeout_secs}s and was killed"
+            ))),
+        }
+    }
+
+    async fn run_http(
+        &self,
+        http: &CustomToolHttpConfig,
+        args: &Value,
+    ) -> anyhow::Result<ToolResult> {
+        let url = render_template(&http.url, args, |value| {
+            urlencoding::encode(&value_text(value)).into_owned()
+        })?;
+        if let Err(e) = super::http_request::check_url(&url, &self.allowed_domains) {
+            return Ok(Self::failure(e.to_string()));
+        }
+        let method =
+            reqwest::Method::from_bytes(http.method.trim().to_ascii_uppercase().as_bytes())
+                .map_err(|_| anyhow::anyhow!("Invalid HTTP method '{}'", http.method))?;
+
+        let mut request = self.client().request(method, &url);
+        for (name, value) in &http.headers {
+            let value = render_template(value, args, value_text)?;
+            if value.contains(['\r', '\n']) {
+                return Ok(Self::failure(format!(
+                    "Header '{name}' would contain a line break"
+                )));
+            }
+            request = request.header(name, value);
+        }
+        if let Some(body) = &http.body {
+            request = request.body(render_template(body, args, Value::to_string)?);
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => return Ok(Self::failure(format!("HTTP request failed: {e}"))),
+        };
+        let status = response.status();
+        let mut body = response.text().await.unwrap_or_default();
+        if body.len() > MAX_HTTP_RESPONSE_BYTES {
+            truncate_at_char_boundary(&mut body, MAX_HTTP_RESPONSE_BYTES);
+            body.push_str("\n... [response truncated]");
+        }
+        if status.is_success() {
+            Ok(ToolResult {
+                success: true,
+                output: body,
+                error: None,
+            })
+        } else {
+            Ok(ToolResult {
+                success: false,
+                output: body,
+                error: Some(format!("HTTP {status}")),
+            })
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CustomTool {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn description(&self) -> &str {
+        &self.config.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.config.parameters.clone()
+    }
+
+    fn risk(&self) -> ToolRisk {
+        self.config.risk
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let operation = if self.config.risk == ToolRisk::Low {
+            ToolOperation::Read
+        } else {
+            ToolOperation::Act
+        };
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(operation, &self.config.name)
+        {
+            return Ok(Self::failure(error));
+        }
+        let args = if args.is_null() {
+            Value::Object(serde_json::Map::new())
+        } else {
+            args
+        };
+        if !args.is_object() {
+            return Ok(Self::failure("Arguments must be a JSON object"));
+        }
+        if let Some(missing) = missing_required(&self.config.parameters, &args) {
+            return Err(anyhow::anyhow!("Missing '{missing}' parameter"));
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+        if !self.security.record_action() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        if let Some(command) = &self.config.command {
+            self.run_command(command, &args).await
+        } else if let Some(http) = &self.config.http {
+            self.run_http(http, &args).await
+        } else {
+            Ok(Self::failure(
+                "Custom tool has neither a command nor an http template",
+            ))
+        }
+    }
+}
+
+/// Names referenced by `{{name}}` placeholders in `template`.
+pub fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        names.push(after[..end].trim());
+        rest = &after[end + 2..];
+    }
+    names
+}
+
+/// First placeholder in a command template that sits inside single or double
+/// quotes, backticks or a here-document. Quoting there would not stop the
+/// shell from expanding `$(...)` in the value, so such templates are refused.
+pub fn quoted_placeholder(command: &str) -> Option<&str> {
+    let bytes = command.as_bytes();
+    let (mut single, mut double, mut backtick, mut heredoc) = (false, false, false, false);
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"{{") {
+            let after = &command[i + 2..];
+            let end = after.find("}}")?;
+            if single || double || backtick || heredoc {
+                return Some(after[..end].trim());
+            }
+            i += end + 4;
+            continue;
+        }
+        match bytes[i] {
+            b'\\' if !single => i += 1,
+            b'\'' if !double => single = !single,
+            b'"' if !single => double = !double,
+            b'`' if !single => backtick = !backtick,
+            b'<' if !single && !double && bytes[i..].starts_with(b"<<") => {
+                if bytes[i..].starts_with(b"<<<") {
+                    i += 2;
+                } else {
+                    heredoc = true;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Replace `{{name}}` placeholders with `escape(value)`. Missing optional
+/// arguments expand to `escape(null)`.
+fn render_template(
+    template: &str,
+    args: &Value,
+    escape: impl Fn(&Value) -> String,
+) -> anyhow::Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            anyhow::bail!("Unterminated '{{{{' placeholder in template");
+        };
+        let name = after[..end].trim();
+        let value = if name == ARGS_PLACEHOLDER {
+            args
+        } else {
+            args.get(name).unwrap_or(&Value::Null)
+        };
+        out.push_str(&escape(value));
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Plain-text form of an argument: strings without quotes, null as empty.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// POSIX single-quote an argument so the shell sees it as one literal word.
+fn shell_quote(value: &Value) -> String {
+    format!("'{}'", value_text(value).replace('\'', r"'\''"))
+}
+
+fn missing_required<'a>(schema: &'a Value, args: &Value) -> Option<&'a str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)?
+        .iter()
+        .filter_map(Value::as_str)
+        .find(|name| args.get(name).map_or(true, Value::is_null))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::NativeRuntime;
+    use crate::security::{AutonomyLevel, NoopSandbox};
+    use serde_json::json;
+
+    fn command_tool(command: &str, security: SecurityPolicy) -> CustomTool {
+        CustomTool::new(
+            CustomToolConfig {
+                name: "greet".into(),
+                description: "Say hello".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}},
+                    "required": ["name"]
+                }),
+                command: Some(command.into()),
+                http: None,
+                risk: ToolRisk::Medium,
+                timeout_secs: 10,
+            },
+            Arc::new(security),
+            Arc::new(NativeRuntime::new()),
+            Arc::new(NoopSandbox),
+        )
+    }
+
+    fn workspace_policy(tmp: &tempfile::TempDir) -> SecurityPolicy {
+        SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        }
+    }
+
+    #[test]
+    fn finds_placeholders() {
+        assert_eq!(
+            placeholders("curl {{ url }} -d {{args}} {{"),
+            vec!["url", "args"]
+        );
+        assert!(placeholders("echo hi").is_empty());
+    }
+
+    #[test]
+    fn escapes_per_context() {
+        let args = json!({"q": "it's a b&c", "n": 3});
+        assert_eq!(
+            render_template("echo {{q}} {{n}} {{missing}}", &args, shell_quote).unwrap(),
+            r"echo 'it'\''s a b&c' '3' ''"
+        );
+        assert_eq!(
+            render_template("https://x.test/?q={{q}}", &args, |v| urlencoding::encode(
+                &value_text(v)
+            )
+            .into_owned())
+            .unwrap(),
+            "https://x.test/?q=it%27s%20a%20b%26c"
+        );
+        assert_eq!(
+            render_template(
+                r#"{"query": {{q}}, "all": {{args}}}"#,
+                &args,
+                Value::to_string
+            )
+            .unwrap(),
+            r#"{"query": "it's a b&c", "all": {"n":3,"q":"it's a b&c"}}"#
+        );
+        assert!(render_template("echo {{q", &args, shell_quote).is_err());
+    }
+
+    #[tokio::test]
+    async fn runs_command_with_quoted_arguments() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = command_tool("printf '%s' {{name}}", workspace_policy(&tmp));
+        let result = tool
+            .execute(json!({"name": "Ada; rm -rf /"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "Ada; rm -rf /");
+    }
+
+    #[test]
+    fn finds_quoted_placeholders() {
+        assert_eq!(quoted_placeholder(r#"echo "{{msg}}""#), Some("msg"));
+        assert_eq!(quoted_placeholder("echo '{{ msg }}'"), Some("msg"));
+        assert_eq!(quoted_placeholder("echo `cat {{file}}`"), Some("file"));
+        assert_eq!(quoted_placeholder("cat <<EOF\n{{msg}}\nEOF"), Some("msg"));
+        assert_eq!(quoted_placeholder(r#"echo "it's" {{msg}} \" {{n}}"#), None);
+        assert_eq!(quoted_placeholder("grep -c x <<< {{msg}}"), None);
+        assert_eq!(quoted_placeholder("printf '%s' {{name}}"), None);
+    }
+
+    #[tokio::test]
+    async fn quoted_command_placeholder_is_refused() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = command_tool(r#"echo "{{name}}""#, workspace_policy(&tmp));
+        let result = tool.execute(json!({"name": "$(id)"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("inside quotes"));
+    }
+
+    #[tokio::test]
+    async fn http_template_is_limited_to_allowed_domains() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = CustomTool::new(
+            CustomToolConfig {
+                name: "lookup".into(),
+                description: "Look something up".into(),
+                parameters: json!({"type": "object", "properties": {"host": {"type": "string"}}}),
+                command: None,
+                http: Some(CustomToolHttpConfig {
+                    method: "GET".into(),
+                    url: "https://{{host}}/lookup".into(),
+                    headers: std::collections::HashMap::new(),
+                    body: None,
+                }),
+                risk: ToolRisk::Low,
+                timeout_secs: 5,
+            },
+            Arc::new(workspace_policy(&tmp)),
+            Arc::new(NativeRuntime::new()),
+            Arc::new(NoopSandbox),
+        )
+        .with_allowed_domains(vec!["api.example.com".into()]);
+
+        let result = tool
+            .execute(json!({"host": "attacker.example.net"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allowed_domains"));
+    }
+
+    #[tokio::test]
+    async fn missing_required_argument_is_an_error() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = command_tool("echo {{name}}", workspace_policy(&tmp));
+        let err = tool.execute(json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("Missing 'name'"));
+    }
+
+    #[tokio::test]
+    async fn read_only_mode_blocks_side_effecting_tools() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = command_tool(
+            "echo {{name}}",
+            SecurityPolicy {
+                autonomy: AutonomyLevel::ReadOnly,
+                ..workspace_policy(&tmp)
+            },
+        );
+        let result = tool.execute(json!({"name": "x"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
@@ -1,10 +1,11 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolResult, ToolRisk};
 use crate::agent::loop_::run_tool_call_loop;
 use crate::config::DelegateAgentConfig;
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{Role, SecurityPolicy};
+use crate::util::truncate_with_ellipsis;
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
@@ -15,6 +16,10 @@ use std::time::Duration;
 const DELEGATE_TIMEOUT_SECS: u64 = 120;
 /// Default timeout for agentic sub-agent runs.
 const DELEGATE_AGENTIC_TIMEOUT_SECS: u64 = 300;
+/// Instructions for condensing an over-long sub-agent result.
+const SUMMARY_SYSTEM_PROMPT: &str = "You condense the result of a delegated subtask for the \
+     agent that delegated it. Keep every concrete finding, value, file path, command and \
+     decision. Drop narration, pleasantries and repeated material. Reply with the summary only.";
 
 /// Tool that delegates a subtask to a named agent with a different
 /// provider/model configuration. Enables multi-agent workflows where
@@ -33,6 +38,8 @@ pub struct DelegateTool {
     parent_tools: Arc<Vec<Arc<dyn Tool>>>,
     /// Inherited multimodal handling config for sub-agent loops.
     multimodal_config: crate::config::MultimodalConfig,
+    /// Inherited tool-output processing for sub-agent loops.
+    tool_output_config: crate::config::ToolOutputConfig,
 }
 
 impl DelegateTool {
@@ -63,6 +70,7 @@ impl DelegateTool {
             depth: 0,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            tool_output_config: crate::config::ToolOutputConfig::default(),
         }
     }
 
@@ -99,6 +107,7 @@ impl DelegateTool {
             depth,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            tool_output_config: crate::config::ToolOutputConfig::default(),
         }
     }
 
@@ -113,6 +122,12 @@ impl DelegateTool {
         self.multimodal_config = config;
         self
     }
+
+    /// Attach tool-output processing configuration for sub-agent tool loops.
+    pub fn with_tool_output_config(mut self, config: crate::config::ToolOutputConfig) -> Self {
+        self.tool_output_config = config;
+        self
+    }
 }
 
 #[async_trait]
@@ -123,8 +138,11 @@ impl Tool for DelegateTool {
 
     fn description(&self) -> &str {
         "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model \
-         (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single \
-         prompt by default; with agentic=true it can iterate with a filtered tool-call loop."
+         (e.g. fast summarization, deep reasoning, code generation) or a focused subtask would \
+         otherwise fill your context. The sub-agent runs a single prompt by default; with \
+         agentic=true it can iterate with a filtered tool-call loop. Narrow its tools and budget \
+         per call with `tools`, `max_iterations` and `timeout_secs`. Long results come back \
+         summarized."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -153,6 +171,21 @@ impl Tool for DelegateTool {
                 "context": {
                     "type": "string",
                     "description": "Optional context to prepend (e.g. relevant code, prior findings)"
+                },
+                "tools": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Agentic agents only: subset of the agent's allowed_tools to expose for this subtask"
+                },
+                "max_iterations": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Lower the agent's tool-call iteration budget for this subtask"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Lower the agent's time budget for this subtask, in seconds"
                 }
             },
             "required": ["agent", "prompt"]
@@ -215,6 +248,18 @@ impl Tool for DelegateTool {
             }
         };
 
+        let narrowed = match narrow_for_call(agent_config, &args) {
+            Ok(narrowed) => narrowed,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+        };
+        let agent_config = &narrowed;
+
         // Check recursion depth (immutable — set at construction, incremented for sub-agents)
         if self.depth >= agent_config.max_depth {
             return Ok(ToolResult {
@@ -289,8 +334,9 @@ impl Tool for DelegateTool {
         }
 
         // Wrap the provider call in a timeout to prevent indefinite blocking
+        let timeout_secs = run_timeout_secs(agent_config);
         let result = tokio::time::timeout(
-            Duration::from_secs(DELEGATE_TIMEOUT_SECS),
+            Duration::from_secs(timeout_secs),
             provider.chat_with_system(
                 agent_config.system_prompt.as_deref(),
                 &full_prompt,
@@ -307,7 +353,7 @@ impl Tool for DelegateTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!(
-                        "Agent '{agent_name}' timed out after {DELEGATE_TIMEOUT_SECS}s"
+                        "Agent '{agent_name}' timed out after {timeout_secs}s"
                     )),
                 });
             }
@@ -319,11 +365,12 @@ impl Tool for DelegateTool {
                 if rendered.trim().is_empty() {
                     rendered = "[Empty response]".to_string();
                 }
+                let (rendered, note) = condense(&*provider, agent_config, rendered).await;
 
                 Ok(ToolResult {
                     success: true,
                     output: format!(
-                        "[Agent '{agent_name}' ({provider}/{model})]\n{rendered}",
+                        "[Agent '{agent_name}' ({provider}/{model}{note})]\n{rendered}",
                         provider = agent_config.provider,
                         model = agent_config.model
                     ),
@@ -392,8 +439,9 @@ impl DelegateTool {
 
         let noop_observer = NoopObserver;
 
+        let timeout_secs = run_timeout_secs(agent_config);
         let result = tokio::time::timeout(
-            Duration::from_secs(DELEGATE_AGENTIC_TIMEOUT_SECS),
+            Duration::from_secs(timeout_secs),
             run_tool_call_loop(
                 provider,
                 &mut history,
@@ -411,6 +459,8 @@ impl DelegateTool {
                 None,
                 None,
                 &[],
+                Some(&self.tool_output_config),
+                None,
             ),
         )
         .await;
@@ -422,11 +472,12 @@ impl DelegateTool {
                 } else {
                     response
                 };
+                let (rendered, note) = condense(provider, agent_config, rendered).await;
 
                 Ok(ToolResult {
                     success: true,
                     output: format!(
-                        "[Agent '{agent_name}' ({provider}/{model}, agentic)]\n{rendered}",
+                        "[Agent '{agent_name}' ({provider}/{model}, agentic{note})]\n{rendered}",
                         provider = agent_config.provider,
                         model = agent_config.model
                     ),
@@ -442,13 +493,102 @@ impl DelegateTool {
                 success: false,
                 output: String::new(),
                 error: Some(format!(
-                    "Agent '{agent_name}' timed out after {DELEGATE_AGENTIC_TIMEOUT_SECS}s"
+                    "Agent '{agent_name}' timed out after {timeout_secs}s"
                 )),
             }),
         }
     }
 }
 
+/// Apply the per-call `tools`, `max_iterations` and `timeout_secs` arguments.
+/// They can only narrow what the agent's config allows.
+fn narrow_for_call(
+    config: &DelegateAgentConfig,
+    args: &serde_json::Value,
+) -> Result<DelegateAgentConfig, String> {
+    let mut narrowed = config.clone();
+    if let Some(tools) = args.get("tools") {
+        let tools = tools
+            .as_array()
+            .and_then(|items| items.iter().map(|t| t.as_str()).collect::<Option<Vec<_>>>())
+            .ok_or("'tools' must be an array of tool names")?;
+        if !config.agentic {
+            return Err(
+                "'tools' narrows the toolset of an agentic agent; this agent has agentic=false"
+                    .into(),
+            );
+        }
+        if let Some(tool) = tools
+            .iter()
+            .find(|tool| !config.allowed_tools.iter().any(|a| a == tool.trim()))
+        {
+            return Err(format!(
+                "Tool '{tool}' is not in the agent's allowed_tools ({})",
+                config.allowed_tools.join(", ")
+            ));
+        }
+        narrowed.allowed_tools = tools.iter().map(|t| t.trim().to_string()).collect();
+    }
+    if let Some(limit) = args.get("max_iterations") {
+        let limit = limit
+            .as_u64()
+            .filter(|n| *n > 0)
+            .ok_or("'max_iterations' must be a positive integer")?;
+        narrowed.max_iterations = narrowed
+            .max_iterations
+            .min(usize::try_from(limit).unwrap_or(usize::MAX));
+    }
+    if let Some(limit) = args.get("timeout_secs") {
+        let limit = limit
+            .as_u64()
+            .filter(|n| *n > 0)
+            .ok_or("'timeout_secs' must be a positive integer")?;
+        narrowed.timeout_secs = Some(run_timeout_secs(config).min(limit));
+    }
+    Ok(narrowed)
+}
+
+fn run_timeout_secs(config: &DelegateAgentConfig) -> u64 {
+    config.timeout_secs.unwrap_or(if config.agentic {
+        DELEGATE_AGENTIC_TIMEOUT_SECS
+    } else {
+        DELEGATE_TIMEOUT_SECS
+    })
+}
+
+/// Summarize a result longer than the agent's `max_result_chars` with the
+/// sub-agent's own model, so it does not flood the caller's context. Falls
+/// back to truncation. Returns the text and a note for the result header.
+async fn condense(
+    provider: &dyn Provider,
+    config: &DelegateAgentConfig,
+    result: String,
+) -> (String, String) {
+    let limit = config.max_result_chars;
+    let length = result.chars().count();
+    if limit == 0 || length <= limit {
+        return (result, String::new());
+    }
+    let request = format!("Summarize in under {limit} characters:\n\n{result}");
+    let summary = match tokio::time::timeout(
+        Duration::from_secs(DELEGATE_TIMEOUT_SECS),
+        provider.chat_with_system(Some(SUMMARY_SYSTEM_PROMPT), &request, &config.model, 0.2),
+    )
+    .await
+    {
+        Ok(Ok(summary)) if !summary.trim().is_empty() => summary,
+        Ok(Err(e)) => {
+            tracing::warn!("Failed to summarize delegated result: {e}");
+            result
+        }
+        _ => result,
+    };
+    (
+        truncate_with_ellipsis(summary.trim(), limit),
+        format!(", summarized from {length} chars"),
+    )
+}
+
 struct ToolArcRef {
     inner: Arc<dyn Tool>,
 }
@@ -476,6 +616,18 @@ impl Tool for ToolArcRef {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
     }
+
+    async fn dry_run(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        self.inner.dry_run(args).await
+    }
+
+    fn risk(&self) -> ToolRisk {
+        self.inner.risk()
+    }
+
+    fn min_role(&self) -> Role {
+        self.inner.min_role()
+    }
 }
 
 struct NoopObserver;
@@ -519,6 +671,9 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: 0,
             },
         );
         agents.insert(
@@ -533,6 +688,9 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: 0,
             },
         );
         agents
@@ -686,6 +844,9 @@ mod tests {
             agentic: true,
             allowed_tools,
             max_iterations,
+            memory_scope: None,
+            timeout_secs: None,
+            max_result_chars: 0,
         }
     }
 
@@ -703,6 +864,9 @@ mod tests {
         assert_eq!(schema["additionalProperties"], json!(false));
         assert_eq!(schema["properties"]["agent"]["minLength"], json!(1));
         assert_eq!(schema["properties"]["prompt"]["minLength"], json!(1));
+        assert_eq!(schema["properties"]["tools"]["type"], json!("array"));
+        assert!(schema["properties"]["max_iterations"].is_object());
+        assert!(schema["properties"]["timeout_secs"].is_object());
     }
 
     #[test]
@@ -794,6 +958,9 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: 0,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -900,6 +1067,9 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: 0,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -935,6 +1105,9 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: 0,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -1099,4 +1272,55 @@ mod tests {
             .unwrap_or("")
             .contains("provider boom"));
     }
+
+    #[test]
+    fn narrow_for_call_only_narrows() {
+        let mut config = agentic_config(vec!["echo_tool".into(), "file_read".into()], 10);
+        config.timeout_secs = Some(60);
+        let narrowed = narrow_for_call(
+            &config,
+            &json!({"tools": ["echo_tool"], "max_iterations": 50, "timeout_secs": 5}),
+        )
+        .unwrap();
+        assert_eq!(narrowed.allowed_tools, vec!["echo_tool"]);
+        assert_eq!(narrowed.max_iterations, 10);
+        assert_eq!(narrowed.timeout_secs, Some(5));
+
+        let err = narrow_for_call(&config, &json!({"tools": ["shell"]})).unwrap_err();
+        assert!(err.contains("'shell' is not in the agent's allowed_tools"));
+        assert!(narrow_for_call(&config, &json!({"max_iterations": 0})).is_err());
+    }
+
+    #[tokio::test]
+    async fn tools_argument_rejected_for_non_agentic_agent() {
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let result = tool
+            .execute(json!({"agent": "researcher", "prompt": "test", "tools": ["shell"]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("agentic=false"));
+    }
+
+    #[tokio::test]
+    async fn long_results_are_summarized_by_the_sub_agent_model() {
+        let mut config = agentic_config(vec!["echo_tool".to_string()], 10);
+        config.max_result_chars = 3;
+        let tool = DelegateTool::new(HashMap::new(), None, test_security())
+            .with_parent_tools(Arc::new(vec![Arc::new(EchoTool)]));
+
+        // The final answer "done" exceeds the limit; the summary comes from
+        // `chat_with_system`, which answers "unused", then gets truncated.
+        let provider = OneToolThenFinalProvider;
+        let result = tool
+            .execute_agentic("agentic", &config, &provider, "run", 0.2)
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert!(result
+            .output
+            .contains("(openrouter/model-test, agentic, summarized from 4 chars)"));
+        assert!(result.output.ends_with("\nuns..."));
+    }
 }
@@ -0,0 +1,568 @@
+//! Shared helpers for file-mutating tools: unified diff previews, backups of
+//! overwritten files and unified-patch application. Write targets are
+//! resolved by [`crate::security::workspace::Workspace::resolve_for_write`].
+
+use super::traits::Tool;
+use std::path::{Path, PathBuf};
+
+/// Maximum characters of diff included in a tool result.
+pub const MAX_DIFF_PREVIEW_CHARS: usize = 8_000;
+/// Backups live under this workspace-relative directory.
+pub const BACKUP_DIR: &str = ".zeroclaw/backups";
+/// Older backups of the same file beyond this count are pruned.
+const MAX_BACKUPS_PER_FILE: usize = 10;
+/// Unchanged lines shown around each hunk.
+const CONTEXT_LINES: usize = 3;
+/// Above this many LCS cells the diff degrades to "replace everything".
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Dry-run report of a file tool: what it returns with `preview: true`.
+pub async fn dry_run_preview(
+    tool: &dyn Tool,
+    mut args: serde_json::Value,
+) -> anyhow::Result<String> {
+    if let Some(args) = args.as_object_mut() {
+        args.insert("preview".into(), serde_json::Value::Bool(true));
+    }
+    let result = tool.execute(args).await?;
+    if result.success {
+        Ok(result.output)
+    } else {
+        anyhow::bail!(result.error.unwrap_or(result.output))
+    }
+}
+
+/// Copy an existing regular file into the workspace backup directory, and
+/// note its content in the turn's undo journal ([`crate::agent::undo`]).
+/// Call it before every change to `target`, even when it does not exist yet.
+///
+/// Returns the backup path, or `None` when there was nothing to back up.
+/// Files outside the workspace are stored under `external/<absolute path>`.
+pub async fn backup_existing(
+    workspace_dir: &Path,
+    target: &Path,
+) -> std::io::Result<Option<PathBuf>> {
+    crate::agent::undo::record(target).await;
+    match tokio::fs::metadata(target).await {
+        Ok(meta) if meta.is_file() => {}
+        _ => return Ok(None),
+    }
+
+    let workspace = tokio::fs::canonicalize(workspace_dir)
+        .await
+        .unwrap_or_else(|_| workspace_dir.to_path_buf());
+    let backup_root = workspace.join(BACKUP_DIR);
+    if target.starts_with(&backup_root) {
+        return Ok(None);
+    }
+
+    let relative = match target.strip_prefix(&workspace) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => Path::new("external").join(
+            target
+                .components()
+                .filter(|c| matches!(c, std::path::Component::Normal(_)))
+                .collect::<PathBuf>(),
+        ),
+    };
+    let Some(file_name) = relative
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+    else {
+        return Ok(None);
+    };
+    let dir = backup_root.join(relative.parent().unwrap_or_else(|| Path::new("")));
+    tokio::fs::create_dir_all(&dir).await?;
+
+    let stamp = chrono::Local::now().format("%Y%m%dT%H%M%S%.3f");
+    let backup_path = dir.join(format!("{file_name}.{stamp}"));
+    tokio::fs::copy(target, &backup_path).await?;
+    prune_backups(&dir, &file_name).await;
+
+    Ok(Some(backup_path))
+}
+
+async fn prune_backups(dir: &Path, file_name: &str) {
+    let prefix = format!("{file_name}.");
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return;
+    };
+    let mut versions = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name
+            .strip_prefix(&prefix)
+            .is_some_and(|stamp| stamp.len() == 19 && stamp.as_bytes()[8] == b'T')
+        {
+            versions.push(name);
+        }
+    }
+    // Timestamps are fixed-width, so lexical order is chronological.
+    versions.sort_unstable_by(|a, b| b.cmp(a));
+    for stale in versions.into_iter().skip(MAX_BACKUPS_PER_FILE) {
+        let _ = tokio::fs::remove_file(dir.join(stale)).await;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffOp {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+fn diff_ops(old: &[&str], new: &[&str]) -> Vec<DiffOp> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<DiffOp> = (0..prefix).map(|i| DiffOp::Equal(i, i)).collect();
+    let (n, m) = (a.len(), b.len());
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        ops.extend((0..n).map(|i| DiffOp::Delete(prefix + i)));
+        ops.extend((0..m).map(|j| DiffOp::Insert(prefix + j)));
+    } else {
+        let width = m + 1;
+        let mut lcs = vec![0u32; (n + 1) * width];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if a[i] == b[j] {
+                ops.push(DiffOp::Equal(prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+                ops.push(DiffOp::Delete(prefix + i));
+                i += 1;
+            } else {
+                ops.push(DiffOp::Insert(prefix + j));
+                j += 1;
+            }
+        }
+        ops.extend((i..n).map(|i| DiffOp::Delete(prefix + i)));
+        ops.extend((j..m).map(|j| DiffOp::Insert(prefix + j)));
+    }
+
+    let (old_tail, new_tail) = (old.len() - suffix, new.len() - suffix);
+    ops.extend((0..suffix).map(|k| DiffOp::Equal(old_tail + k, new_tail + k)));
+    ops
+}
+
+/// Render a unified diff between two texts. Returns an empty string when equal.
+pub fn unified_diff(label: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&old_lines, &new_lines);
+
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for (index, op) in ops.iter().enumerate() {
+        if matches!(op, DiffOp::Equal(..)) {
+            continue;
+        }
+        match groups.last_mut() {
+            Some((_, last)) if index - *last <= 2 * CONTEXT_LINES + 1 => *last = index,
+            _ => groups.push((index, index)),
+        }
+    }
+    if groups.is_empty() {
+        return String::new();
+    }
+
+    // Line offsets (0-based) before each op.
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut old_pos, mut new_pos) = (0usize, 0usize);
+    for op in &ops {
+        positions.push((old_pos, new_pos));
+        match op {
+            DiffOp::Equal(..) => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            DiffOp::Delete(_) => old_pos += 1,
+            DiffOp::Insert(_) => new_pos += 1,
+        }
+    }
+
+    let mut out = format!("--- a/{label}\n+++ b/{label}\n");
+    for (first, last) in groups {
+        let start = first.saturating_sub(CONTEXT_LINES);
+        let end = (last + CONTEXT_LINES + 1).min(ops.len());
+        let slice = &ops[start..end];
+        let old_count = slice
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Insert(_)))
+            .count();
+        let new_count = slice
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Delete(_)))
+            .count();
+        let (old_start, new_start) = positions[start];
+        let old_start = if old_count == 0 {
+            old_start
+        } else {
+            old_start + 1
+        };
+        let new_start = if new_count == 0 {
+            new_start
+        } else {
+            new_start + 1
+        };
+        out.push_str(&format!(
+            "@@ -{old_start},{old_count} +{new_start},{new_count} @@\n"
+        ));
+        for op in slice {
+            match *op {
+                DiffOp::Equal(i, _) => out.push_str(&format!(" {}\n", old_lines[i])),
+                DiffOp::Delete(i) => out.push_str(&format!("-{}\n", old_lines[i])),
+                DiffOp::Insert(j) => out.push_str(&format!("+{}\n", new_lines[j])),
+            }
+        }
+    }
+    out
+}
+
+/// Diff text for a tool result, truncated to [`MAX_DIFF_PREVIEW_CHARS`].
+pub fn diff_preview(label: &str, old: &str, new: &str) -> String {
+    let diff = unified_diff(label, old, new);
+    if diff.is_empty() {
+        "(no changes)".to_string()
+    } else {
+        crate::util::truncate_with_ellipsis(&diff, MAX_DIFF_PREVIEW_CHARS)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// 1-based start line in the original file (as written in the header).
+    pub old_start: usize,
+    pub lines: Vec<HunkLine>,
+}
+
+/// One file section of a unified patch. `None` paths mean `/dev/null`
+/// (file creation or deletion).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePatch {
+    pub old_path: Option<String>,
+    pub new_path: Option<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// The path this patch writes to (or deletes).
+    pub fn target(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+fn patch_path(header: &str) -> Option<String> {
+    let raw = header.split('\t').next().unwrap_or("").trim();
+    if raw == "/dev/null" || raw.is_empty() {
+        return None;
+    }
+    let stripped = raw
+        .strip_prefix("a/")
+        .or_else(|| raw.strip_prefix("b/"))
+        .unwrap_or(raw);
+    Some(stripped.to_string())
+}
+
+fn parse_range(range: &str) -> Result<(usize, usize), String> {
+    let (start, count) = match range.split_once(',') {
+        Some((start, count)) => (start, count),
+        None => (range, "1"),
+    };
+    let start = start
+        .parse()
+        .map_err(|_| format!("invalid hunk range '{range}'"))?;
+    let count = count
+        .parse()
+        .map_err(|_| format!("invalid hunk range '{range}'"))?;
+    Ok((start, count))
+}
+
+/// Parse a (possibly multi-file) unified diff.
+pub fn parse_unified_patch(patch: &str) -> Result<Vec<FilePatch>, String> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let mut files = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some(old_header) = lines[i].strip_prefix("--- ") else {
+            i += 1;
+            continue;
+        };
+        let new_header = lines
+            .get(i + 1)
+            .and_then(|line| line.strip_prefix("+++ "))
+            .ok_or_else(|| format!("line {}: expected '+++' after '---'", i + 2))?;
+        let mut file = FilePatch {
+            old_path: patch_path(old_header),
+            new_path: patch_path(new_header),
+            hunks: Vec::new(),
+        };
+        if file.old_path.is_none() && file.new_path.is_none() {
+            return Err(format!("line {}: patch has no file path", i + 1));
+        }
+        i += 2;
+
+        while let Some(header) = lines.get(i).and_then(|line| line.strip_prefix("@@ -")) {
+            let ranges = header
+                .split(" @@")
+                .next()
+                .ok_or_else(|| format!("line {}: malformed hunk header", i + 1))?;
+            let (old_range, new_range) = ranges
+                .split_once(" +")
+                .ok_or_else(|| format!("line {}: malformed hunk header", i + 1))?;
+            let (old_start, mut old_left) = parse_range(old_range)?;
+            let (_, mut new_left) = parse_range(new_range)?;
+            i += 1;
+
+            let mut hunk = Hunk {
+                old_start,
+                lines: Vec::new(),
+            };
+            while old_left > 0 || new_left > 0 {
+                let Some(line) = lines.get(i) else {
+                    return Err(format!(
+                        "hunk at original line {old_start} in {} ends early",
+                        file.target()
+                    ));
+                };
+                if line.starts_with('\\') {
+                    i += 1;
+                    continue;
+                }
+                let parsed = match line.chars().next() {
+                    Some('+') if new_left > 0 => {
+                        new_left -= 1;
+                        HunkLine::Add(line[1..].to_string())
+                    }
+                    Some('-') if old_left > 0 => {
+                        old_left -= 1;
+                        HunkLine::Remove(line[1..].to_string())
+                    }
+                    Some(' ') | None if old_left > 0 && new_left > 0 => {
+                        old_left -= 1;
+                        new_left -= 1;
+                        HunkLine::Context(line.get(1..).unwrap_or("").to_string())
+                    }
+                    _ => {
+                        return Err(format!(
+                            "line {}: unexpected '{line}' in hunk for {}",
+                            i + 1,
+                            file.target()
+                        ))
+                    }
+                };
+                hunk.lines.push(parsed);
+                i += 1;
+            }
+            while lines.get(i).is_some_and(|line| line.starts_with('\\')) {
+                i += 1;
+            }
+            file.hunks.push(hunk);
+        }
+
+        if file.hunks.is_empty() {
+            return Err(format!("patch for {} has no hunks", file.target()));
+        }
+        files.push(file);
+    }
+
+    if files.is_empty() {
+        return Err("no file sections found (expected '--- ' / '+++ ' headers)".into());
+    }
+    Ok(files)
+}
+
+/// Apply hunks to `original`. Hunks must apply in order; each is located at
+/// its stated line or, failing that, at the nearest exact context match.
+pub fn apply_hunks(original: &str, hunks: &[Hunk]) -> Result<String, String> {
+    let trailing_newline = original.is_empty() || original.ends_with('\n');
+    let mut lines: Vec<String> = original.lines().map(String::from).collect();
+    let mut offset: isize = 0;
+    let mut min_pos = 0usize;
+
+    for (number, hunk) in hunks.iter().enumerate() {
+        let expected: Vec<&str> = hunk
+            .lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Remove(text) => Some(text.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect();
+        let replacement: Vec<String> = hunk
+            .lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Add(text) => Some(text.clone()),
+                HunkLine::Remove(_) => None,
+            })
+            .collect();
+
+        let stated = if expected.is_empty() {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        let anchor = usize::try_from(stated as isize + offset)
+            .unwrap_or(0)
+            .max(min_pos);
+
+        let position = if expected.is_empty() {
+            (anchor <= lines.len()).then_some(anchor)
+        } else if lines.len() < expected.len() {
+            None
+        } else {
+            (min_pos..=lines.len() - expected.len())
+                .filter(|&p| {
+                    lines[p..p + expected.len()]
+                        .iter()
+                        .zip(&expected)
+                        .all(|(have, want)| have == want)
+                })
+                .min_by_key(|&p| p.abs_diff(anchor))
+        };
+        let Some(position) = position else {
+            return Err(format!(
+                "hunk {} (original line {}) does not apply: context not found",
+                number + 1,
+                hunk.old_start
+            ));
+        };
+
+        let added = replacement.len();
+        lines.splice(position..position + expected.len(), replacement);
+        offset += added as isize - expected.len() as isize;
+        min_pos = position + added;
+    }
+
+    let mut out = lines.join("\n");
+    if trailing_newline && !lines.is_empty() {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_renders_hunk_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "a\nb\nc\nd\nE\nf\ng\nh\n";
+        let diff = unified_diff("x.txt", old, new);
+        assert!(diff.starts_with("--- a/x.txt\n+++ b/x.txt\n@@ -2,7 +2,7 @@\n"));
+        assert!(diff.contains("-e\n+E\n"));
+        assert!(unified_diff("x.txt", old, old).is_empty());
+    }
+
+    #[test]
+    fn unified_diff_handles_new_file() {
+        let diff = unified_diff("new.txt", "", "one\ntwo\n");
+        assert!(diff.contains("@@ -0,0 +1,2 @@\n+one\n+two\n"));
+    }
+
+    #[test]
+    fn generated_diff_round_trips_through_apply() {
+        let old = "fn main() {\n    println!(\"hi\");\n}\n\nfn other() {}\n";
+        let new = "fn main() {\n    println!(\"hello\");\n    run();\n}\n\nfn other() {}\n";
+        let patch = unified_diff("src/main.rs", old, new);
+        let files = parse_unified_patch(&patch).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].target(), "src/main.rs");
+        assert_eq!(apply_hunks(old, &files[0].hunks).unwrap(), new);
+    }
+
+    #[test]
+    fn apply_tolerates_shifted_hunks() {
+        let patch = "--- a/f\n+++ b/f\n@@ -1,2 +1,2 @@\n x\n-y\n+Y\n";
+        let files = parse_unified_patch(patch).unwrap();
+        let shifted = "header\nx\ny\n";
+        assert_eq!(
+            apply_hunks(shifted, &files[0].hunks).unwrap(),
+            "header\nx\nY\n"
+        );
+        assert!(apply_hunks("x\nz\n", &files[0].hunks).is_err());
+    }
+
+    #[test]
+    fn parse_handles_creation_and_deletion() {
+        let patch = "diff --git a/new b/new\n--- /dev/null\n+++ b/new\n@@ -0,0 +1 @@\n+hi\n\
+                     --- a/old\n+++ /dev/null\n@@ -1 +0,0 @@\n-bye\n";
+        let files = parse_unified_patch(patch).unwrap();
+        assert_eq!(files[0].old_path, None);
+        assert_eq!(files[0].new_path.as_deref(), Some("new"));
+        assert_eq!(apply_hunks("", &files[0].hunks).unwrap(), "hi\n");
+        assert_eq!(files[1].new_path, None);
+        assert_eq!(files[1].target(), "old");
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert!(parse_unified_patch("just some text").is_err());
+        assert!(parse_unified_patch("--- a/f\n+++ b/f\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn backup_keeps_copy_and_prunes_old_versions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("notes.txt");
+        tokio::fs::write(&file, "v1").await.unwrap();
+
+        let backup = backup_existing(tmp.path(), &file).await.unwrap().unwrap();
+        assert_eq!(tokio::fs::read_to_string(&backup).await.unwrap(), "v1");
+        assert!(backup.starts_with(
+            tokio::fs::canonicalize(tmp.path())
+                .await
+                .unwrap()
+                .join(BACKUP_DIR)
+        ));
+
+        let dir = backup.parent().unwrap().to_path_buf();
+        for i in 0..MAX_BACKUPS_PER_FILE + 3 {
+            let name = format!("notes.txt.20240101T0000{i:02}.000");
+            tokio::fs::write(dir.join(name), "old").await.unwrap();
+        }
+        backup_existing(tmp.path(), &file).await.unwrap();
+        let mut count = 0;
+        let mut entries = tokio::fs::read_dir(&dir).await.unwrap();
+        while entries.next_entry().await.unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, MAX_BACKUPS_PER_FILE);
+
+        assert!(backup_existing(tmp.path(), &tmp.path().join("missing"))
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
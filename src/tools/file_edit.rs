@@ -1,3 +1,4 @@
+use super::file_changes;
 use super::traits::{Tool, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -9,7 +10,8 @@ use std::sync::Arc;
 /// Uses `old_string` → `new_string` precise replacement within the workspace.
 /// The `old_string` must appear exactly once in the file (zero matches = not
 /// found, multiple matches = ambiguous). `new_string` may be empty to delete
-/// the matched text. Security checks mirror [`super::file_write::FileWriteTool`].
+/// the matched text. Security checks, backups and `preview` mirror
+/// [`super::file_write::FileWriteTool`].
 pub struct FileEditTool {
     security: Arc<SecurityPolicy>,
 }
@@ -45,12 +47,20 @@ impl Tool for FileEditTool {
                 "new_string": {
                     "type": "string",
                     "description": "The replacement text (empty string to delete the matched text)"
+                },
+                "preview": {
+                    "type": "boolean",
+                    "description": "Return a unified diff of the edit without writing (default: false)"
                 }
             },
             "required": ["path", "old_string", "new_string"]
         })
     }
 
+    async fn dry_run(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        file_changes::dry_run_preview(self, args).await
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         // ── 1. Extract parameters ──────────────────────────────────
         let path = args
@@ -94,83 +104,25 @@ impl Tool for FileEditTool {
             });
         }
 
-        // ── 4. Path pre-validation ─────────────────────────────────
-        if !self.security.is_path_allowed(path) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Path not allowed by security policy: {path}")),
-            });
-        }
-
-        let full_path = self.security.workspace_dir.join(path);
-
-        // ── 5. Canonicalize parent ─────────────────────────────────
-        let Some(parent) = full_path.parent() else {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Invalid path: missing parent directory".into()),
-            });
-        };
+        let preview = args
+            .get("preview")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
-        let resolved_parent = match tokio::fs::canonicalize(parent).await {
-            Ok(p) => p,
-            Err(e) => {
+        // ── 4. Path validation (pre, resolved, symlink) ───────────
+        let workspace = self.security.workspace();
+        let resolved_target = match workspace.resolve_for_write(path, false).await {
+            Ok(target) => target,
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
+                    error: Some(error),
                 });
             }
         };
 
-        // ── 6. Resolved path post-validation ───────────────────────
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_parent),
-                ),
-            });
-        }
-
-        let Some(file_name) = full_path.file_name() else {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Invalid path: missing file name".into()),
-            });
-        };
-
-        let resolved_target = resolved_parent.join(file_name);
-
-        // ── 7. Symlink check ───────────────────────────────────────
-        if let Ok(meta) = tokio::fs::symlink_metadata(&resolved_target).await {
-            if meta.file_type().is_symlink() {
-                return Ok(ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!(
-                        "Refusing to edit through symlink: {}",
-                        resolved_target.display()
-                    )),
-                });
-            }
-        }
-
-        // ── 8. Record action ───────────────────────────────────────
-        if !self.security.record_action() {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Rate limit exceeded: action budget exhausted".into()),
-            });
-        }
-
-        // ── 9. Read → match → replace → write ─────────────────────
+        // ── 5. Read → match → replace ─────────────────────────────
         let content = match tokio::fs::read_to_string(&resolved_target).await {
             Ok(c) => c,
             Err(e) => {
@@ -203,16 +155,68 @@ impl Tool for FileEditTool {
         }
 
         let new_content = content.replacen(old_string, new_string, 1);
+        let diff = file_changes::diff_preview(path, &content, &new_content);
 
-        match tokio::fs::write(&resolved_target, &new_content).await {
-            Ok(()) => Ok(ToolResult {
+        if preview {
+            return Ok(ToolResult {
                 success: true,
-                output: format!(
+                output: format!("Preview of {path} (not written):\n{diff}"),
+                error: None,
+            });
+        }
+
+        // ── 6. Quota → record action ───────────────────────────────
+        if let Err(error) = workspace
+            .check_quota(&resolved_target, new_content.len() as u64)
+            .await
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        // ── 7. Back up → write ─────────────────────────────────────
+        let backup =
+            match file_changes::backup_existing(&self.security.workspace_dir, &resolved_target)
+                .await
+            {
+                Ok(backup) => backup,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Failed to back up existing file: {e}")),
+                    });
+                }
+            };
+
+        match tokio::fs::write(&resolved_target, &new_content).await {
+            Ok(()) => {
+                let mut output = format!(
                     "Edited {path}: replaced 1 occurrence ({} bytes)",
                     new_content.len()
-                ),
-                error: None,
-            }),
+                );
+                if let Some(backup) = backup {
+                    output.push_str(&format!("\nBackup: {}", backup.display()));
+                }
+                output.push('\n');
+                output.push_str(&diff);
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -297,6 +301,33 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn file_edit_preview_leaves_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("test.txt"), "hello world\n")
+            .await
+            .unwrap();
+
+        let tool = FileEditTool::new(test_security(dir.path().to_path_buf()));
+        let result = tool
+            .execute(json!({
+                "path": "test.txt",
+                "old_string": "hello",
+                "new_string": "goodbye",
+                "preview": true
+            }))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("-hello world\n+goodbye world"));
+        let content = tokio::fs::read_to_string(dir.path().join("test.txt"))
+            .await
+            .unwrap();
+        assert_eq!(content, "hello world\n");
+        assert!(!dir.path().join(file_changes::BACKUP_DIR).exists());
+    }
+
     #[tokio::test]
     async fn file_edit_not_found() {
         let dir = std::env::temp_dir().join("zeroclaw_test_file_edit_notfound");
@@ -1,10 +1,14 @@
+use super::file_changes;
 use super::traits::{Tool, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
 
-/// Write file contents with path sandboxing
+/// Write file contents with path sandboxing.
+///
+/// Existing files are backed up under `.zeroclaw/backups/` before being
+/// overwritten, and `preview: true` returns the diff without writing.
 pub struct FileWriteTool {
     security: Arc<SecurityPolicy>,
 }
@@ -22,7 +26,7 @@ impl Tool for FileWriteTool {
     }
 
     fn description(&self) -> &str {
-        "Write contents to a file in the workspace"
+        "Write contents to a file in the workspace. Overwritten files are backed up; set preview=true to see the diff without writing"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -36,12 +40,20 @@ impl Tool for FileWriteTool {
                 "content": {
                     "type": "string",
                     "description": "Content to write to the file"
+                },
+                "preview": {
+                    "type": "boolean",
+                    "description": "Return a unified diff of the change without writing (default: false)"
                 }
             },
             "required": ["path", "content"]
         })
     }
 
+    async fn dry_run(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        file_changes::dry_run_preview(self, args).await
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let path = args
             .get("path")
@@ -69,73 +81,48 @@ impl Tool for FileWriteTool {
             });
         }
 
-        // Security check: validate path is within workspace
-        if !self.security.is_path_allowed(path) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Path not allowed by security policy: {path}")),
-            });
-        }
-
-        let full_path = self.security.workspace_dir.join(path);
-
-        let Some(parent) = full_path.parent() else {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Invalid path: missing parent directory".into()),
-            });
-        };
-
-        // Ensure parent directory exists
-        tokio::fs::create_dir_all(parent).await?;
+        let preview = args
+            .get("preview")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
-        // Resolve parent AFTER creation to block symlink escapes.
-        let resolved_parent = match tokio::fs::canonicalize(parent).await {
-            Ok(p) => p,
-            Err(e) => {
+        let workspace = self.security.workspace();
+        let resolved_target = match workspace.resolve_for_write(path, !preview).await {
+            Ok(target) => target,
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
+                    error: Some(error),
                 });
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+        let previous = tokio::fs::read(&resolved_target)
+            .await
+            .ok()
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+
+        if preview {
             return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_parent),
+                success: true,
+                output: format!(
+                    "Preview of {path} (not written):\n{}",
+                    file_changes::diff_preview(path, previous.as_deref().unwrap_or(""), content)
                 ),
+                error: None,
             });
         }
 
-        let Some(file_name) = full_path.file_name() else {
+        if let Err(error) = workspace
+            .check_quota(&resolved_target, content.len() as u64)
+            .await
+        {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some("Invalid path: missing file name".into()),
+                error: Some(error),
             });
-        };
-
-        let resolved_target = resolved_parent.join(file_name);
-
-        // If the target already exists and is a symlink, refuse to follow it
-        if let Ok(meta) = tokio::fs::symlink_metadata(&resolved_target).await {
-            if meta.file_type().is_symlink() {
-                return Ok(ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!(
-                        "Refusing to write through symlink: {}",
-                        resolved_target.display()
-                    )),
-                });
-            }
         }
 
         if !self.security.record_action() {
@@ -146,12 +133,36 @@ impl Tool for FileWriteTool {
             });
         }
 
+        let backup =
+            match file_changes::backup_existing(&self.security.workspace_dir, &resolved_target)
+                .await
+            {
+                Ok(backup) => backup,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Failed to back up existing file: {e}")),
+                    });
+                }
+            };
+
         match tokio::fs::write(&resolved_target, content).await {
-            Ok(()) => Ok(ToolResult {
-                success: true,
-                output: format!("Written {} bytes to {path}", content.len()),
-                error: None,
-            }),
+            Ok(()) => {
+                let mut output = format!("Written {} bytes to {path}", content.len());
+                if let Some(backup) = backup {
+                    output.push_str(&format!("\nBackup: {}", backup.display()));
+                }
+                if let Some(previous) = previous {
+                    output.push('\n');
+                    output.push_str(&file_changes::diff_preview(path, &previous, content));
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -271,6 +282,57 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn file_write_backs_up_and_reports_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("notes.txt"), "one\ntwo\n")
+            .await
+            .unwrap();
+
+        let tool = FileWriteTool::new(test_security(dir.path().to_path_buf()));
+        let result = tool
+            .execute(json!({"path": "notes.txt", "content": "one\nTWO\n"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("-two\n+TWO"));
+
+        let backup_line = result
+            .output
+            .lines()
+            .find_map(|line| line.strip_prefix("Backup: "))
+            .expect("backup path reported");
+        let backup = tokio::fs::read_to_string(backup_line).await.unwrap();
+        assert_eq!(backup, "one\ntwo\n");
+    }
+
+    #[tokio::test]
+    async fn file_write_preview_does_not_write() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("notes.txt"), "old\n")
+            .await
+            .unwrap();
+
+        let tool = FileWriteTool::new(test_security(dir.path().to_path_buf()));
+        let result = tool
+            .execute(json!({"path": "new/dir/notes.txt", "content": "hi\n", "preview": true}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("+hi"));
+        assert!(!dir.path().join("new").exists());
+
+        let result = tool
+            .execute(json!({"path": "notes.txt", "content": "new\n", "preview": true}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("-old\n+new"));
+        let content = tokio::fs::read_to_string(dir.path().join("notes.txt"))
+            .await
+            .unwrap();
+        assert_eq!(content, "old\n");
+    }
+
     #[tokio::test]
     async fn file_write_blocks_path_traversal() {
         let dir = std::env::temp_dir().join("zeroclaw_test_file_write_traversal");
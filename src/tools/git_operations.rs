@@ -2,21 +2,83 @@ use super::traits::{Tool, ToolResult};
 use crate::security::{AutonomyLevel, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Maximum characters of patch text returned by `show`.
+const MAX_SHOW_PATCH_CHARS: usize = 20_000;
+
 /// Git operations tool for structured repository management.
 /// Provides safe, parsed git operations with JSON output.
+///
+/// Operates on the workspace repository by default; the optional `repo`
+/// argument selects one of the configured `[git].repositories` instead.
 pub struct GitOperationsTool {
     security: Arc<SecurityPolicy>,
-    workspace_dir: std::path::PathBuf,
+    workspace_dir: PathBuf,
+    repositories: Vec<PathBuf>,
 }
 
 impl GitOperationsTool {
-    pub fn new(security: Arc<SecurityPolicy>, workspace_dir: std::path::PathBuf) -> Self {
+    pub fn new(security: Arc<SecurityPolicy>, workspace_dir: PathBuf) -> Self {
         Self {
             security,
             workspace_dir,
+            repositories: Vec::new(),
+        }
+    }
+
+    /// Allow operating on these repositories in addition to the workspace.
+    pub fn with_repositories(mut self, repositories: Vec<PathBuf>) -> Self {
+        self.repositories = repositories;
+        self
+    }
+
+    /// Resolve the `repo` argument to an allowed directory.
+    ///
+    /// Accepts a configured repository path or its directory name, or any
+    /// directory inside the workspace. Defaults to the workspace itself.
+    fn resolve_repo(&self, requested: Option<&str>) -> Result<PathBuf, String> {
+        let Some(requested) = requested.map(str::trim).filter(|r| !r.is_empty()) else {
+            return Ok(self.workspace_dir.clone());
+        };
+
+        let by_name = self
+            .repositories
+            .iter()
+            .find(|repo| repo.file_name().is_some_and(|name| name == requested));
+        let target = match by_name {
+            Some(repo) => repo.clone(),
+            None if Path::new(requested).is_absolute() => PathBuf::from(requested),
+            None => self.workspace_dir.join(requested),
+        };
+        let target = std::fs::canonicalize(&target)
+            .map_err(|_| format!("Repository not found: {requested}"))?;
+
+        let inside_workspace = std::fs::canonicalize(&self.workspace_dir)
+            .is_ok_and(|workspace| target.starts_with(workspace));
+        let configured = self
+            .repositories
+            .iter()
+            .filter_map(|repo| std::fs::canonicalize(repo).ok())
+            .any(|repo| repo == target);
+        if inside_workspace || configured {
+            return Ok(target);
         }
+
+        let names: Vec<String> = self
+            .repositories
+            .iter()
+            .map(|repo| repo.display().to_string())
+            .collect();
+        Err(if names.is_empty() {
+            format!("Repository '{requested}' is outside the workspace and no [git].repositories are configured")
+        } else {
+            format!(
+                "Repository '{requested}' is not configured. Allowed: workspace, {}",
+                names.join(", ")
+            )
+        })
     }
 
     /// Sanitize git arguments to prevent injection attacks
@@ -53,10 +115,26 @@ impl GitOperationsTool {
     fn requires_write_access(&self, operation: &str) -> bool {
         matches!(
             operation,
-            "commit" | "add" | "checkout" | "stash" | "reset" | "revert"
+            "commit" | "add" | "checkout" | "stash" | "reset" | "revert" | "apply"
         )
     }
 
+    /// Why the autonomy level forbids `operation`, if it writes (creating a
+    /// branch writes refs).
+    fn write_blocked(&self, operation: &str, args: &serde_json::Value) -> Option<&'static str> {
+        let creates_branch = operation == "branch" && args.get("name").is_some();
+        if !self.requires_write_access(operation) && !creates_branch {
+            return None;
+        }
+        if !self.security.can_act() {
+            return Some("Action blocked: git write operations require higher autonomy level");
+        }
+        match self.security.autonomy {
+            AutonomyLevel::ReadOnly => Some("Action blocked: read-only mode"),
+            AutonomyLevel::Supervised | AutonomyLevel::Full => None,
+        }
+    }
+
     /// Check if an operation is read-only
     fn is_read_only(&self, operation: &str) -> bool {
         matches!(
@@ -65,10 +143,10 @@ impl GitOperationsTool {
         )
     }
 
-    async fn run_git_command(&self, args: &[&str]) -> anyhow::Result<String> {
+    async fn run_git_command(&self, repo: &Path, args: &[&str]) -> anyhow::Result<String> {
         let output = tokio::process::Command::new("git")
             .args(args)
-            .current_dir(&self.workspace_dir)
+            .current_dir(repo)
             .output()
             .await?;
 
@@ -80,9 +158,52 @@ impl GitOperationsTool {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
-    async fn git_status(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    async fn run_git_command_with_stdin(
+        &self,
+        repo: &Path,
+        args: &[&str],
+        input: &str,
+    ) -> anyhow::Result<String> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut child = tokio::process::Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes()).await?;
+        }
+
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git command failed: {stderr}");
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Dry-run report of an operation that changes nothing: its real output.
+    async fn read_only_report(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        let result = self.execute(args).await?;
+        if result.success {
+            Ok(result.output)
+        } else {
+            anyhow::bail!(result.error.unwrap_or(result.output))
+        }
+    }
+
+    async fn git_status(
+        &self,
+        repo: &Path,
+        _args: serde_json::Value,
+    ) -> anyhow::Result<ToolResult> {
         let output = self
-            .run_git_command(&["status", "--porcelain=2", "--branch"])
+            .run_git_command(repo, &["status", "--porcelain=2", "--branch"])
             .await?;
 
         // Parse git status output into structured format
@@ -131,7 +252,7 @@ impl GitOperationsTool {
         })
     }
 
-    async fn git_diff(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    async fn git_diff(&self, repo: &Path, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let files = args.get("files").and_then(|v| v.as_str()).unwrap_or(".");
         let cached = args
             .get("cached")
@@ -148,7 +269,7 @@ impl GitOperationsTool {
         git_args.push("--");
         git_args.push(files);
 
-        let output = self.run_git_command(&git_args).await?;
+        let output = self.run_git_command(repo, &git_args).await?;
 
         // Parse diff into structured hunks
         let mut result = serde_json::Map::new();
@@ -210,18 +331,21 @@ impl GitOperationsTool {
         })
     }
 
-    async fn git_log(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    async fn git_log(&self, repo: &Path, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let limit_raw = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10);
         let limit = usize::try_from(limit_raw).unwrap_or(usize::MAX).min(1000);
         let limit_str = limit.to_string();
 
         let output = self
-            .run_git_command(&[
-                "log",
-                &format!("-{limit_str}"),
-                "--pretty=format:%H|%an|%ae|%ad|%s",
-                "--date=iso",
-            ])
+            .run_git_command(
+                repo,
+                &[
+                    "log",
+                    &format!("-{limit_str}"),
+                    "--pretty=format:%H|%an|%ae|%ad|%s",
+                    "--date=iso",
+                ],
+            )
             .await?;
 
         let mut commits = Vec::new();
@@ -247,9 +371,13 @@ impl GitOperationsTool {
         })
     }
 
-    async fn git_branch(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    async fn git_branch(&self, repo: &Path, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Some(name) = args.get("name").and_then(|v| v.as_str()) {
+            return self.git_create_branch(repo, name).await;
+        }
+
         let output = self
-            .run_git_command(&["branch", "--format=%(refname:short)|%(HEAD)"])
+            .run_git_command(repo, &["branch", "--format=%(refname:short)|%(HEAD)"])
             .await?;
 
         let mut branches = Vec::new();
@@ -279,6 +407,120 @@ impl GitOperationsTool {
         })
     }
 
+    /// Validate the name of a branch to create.
+    async fn new_branch_name(&self, repo: &Path, name: &str) -> anyhow::Result<String> {
+        let sanitized = self.sanitize_git_args(name)?;
+        if sanitized.len() != 1 || sanitized[0].starts_with('-') {
+            anyhow::bail!("Invalid branch name");
+        }
+        let name = &sanitized[0];
+
+        if self
+            .run_git_command(repo, &["check-ref-format", "--branch", name])
+            .await
+            .is_err()
+        {
+            anyhow::bail!("Invalid branch name: {name}");
+        }
+        Ok(name.clone())
+    }
+
+    async fn git_create_branch(&self, repo: &Path, name: &str) -> anyhow::Result<ToolResult> {
+        let name = self.new_branch_name(repo, name).await?;
+
+        match self.run_git_command(repo, &["branch", &name]).await {
+            Ok(_) => Ok(ToolResult {
+                success: true,
+                output: format!("Created branch: {name}"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Branch creation failed: {e}")),
+            }),
+        }
+    }
+
+    async fn git_show(&self, repo: &Path, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let rev = args.get("rev").and_then(|v| v.as_str()).unwrap_or("HEAD");
+        let sanitized = self.sanitize_git_args(rev)?;
+        if sanitized.len() != 1 || sanitized[0].starts_with('-') {
+            anyhow::bail!("Invalid revision");
+        }
+        let rev = &sanitized[0];
+
+        let header = self
+            .run_git_command(
+                repo,
+                &[
+                    "show",
+                    "--no-patch",
+                    "--pretty=format:%H|%an|%ae|%ad|%B",
+                    "--date=iso",
+                    rev,
+                    "--",
+                ],
+            )
+            .await?;
+        let stat = self
+            .run_git_command(
+                repo,
+                &[
+                    "show",
+                    "--stat",
+                    "--pretty=format:",
+                    "--no-color",
+                    rev,
+                    "--",
+                ],
+            )
+            .await?;
+        let patch = self
+            .run_git_command(
+                repo,
+                &[
+                    "show",
+                    "--patch",
+                    "--pretty=format:",
+                    "--no-color",
+                    rev,
+                    "--",
+                ],
+            )
+            .await?;
+
+        let parts: Vec<&str> = header.splitn(5, '|').collect();
+        if parts.len() < 5 {
+            anyhow::bail!("Unexpected git show output for {rev}");
+        }
+        let patch_chars = patch.chars().count();
+        let (patch, truncated) = if patch_chars > MAX_SHOW_PATCH_CHARS {
+            (
+                patch.chars().take(MAX_SHOW_PATCH_CHARS).collect::<String>(),
+                true,
+            )
+        } else {
+            (patch, false)
+        };
+
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&json!({
+                "hash": parts[0],
+                "author": parts[1],
+                "email": parts[2],
+                "date": parts[3],
+                "message": parts[4].trim_end(),
+                "stat": stat.trim(),
+                "patch": patch.trim(),
+                "patch_truncated": truncated
+            }))
+            .unwrap_or_default(),
+            error: None,
+        })
+    }
+
     fn truncate_commit_message(message: &str) -> String {
         if message.chars().count() > 2000 {
             format!("{}...", message.chars().take(1997).collect::<String>())
@@ -287,7 +529,8 @@ impl GitOperationsTool {
         }
     }
 
-    async fn git_commit(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    /// The sanitized, length-limited `message` argument of `commit`.
+    fn commit_message(args: &serde_json::Value) -> anyhow::Result<String> {
         let message = args
             .get("message")
             .and_then(|v| v.as_str())
@@ -306,9 +549,15 @@ impl GitOperationsTool {
         }
 
         // Limit message length
-        let message = Self::truncate_commit_message(&sanitized);
+        Ok(Self::truncate_commit_message(&sanitized))
+    }
 
-        let output = self.run_git_command(&["commit", "-m", &message]).await;
+    async fn git_commit(&self, repo: &Path, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let message = Self::commit_message(&args)?;
+
+        let output = self
+            .run_git_command(repo, &["commit", "-m", &message])
+            .await;
 
         match output {
             Ok(_) => Ok(ToolResult {
@@ -324,7 +573,7 @@ impl GitOperationsTool {
         }
     }
 
-    async fn git_add(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    async fn git_add(&self, repo: &Path, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let paths = args
             .get("paths")
             .and_then(|v| v.as_str())
@@ -333,7 +582,7 @@ impl GitOperationsTool {
         // Validate paths against injection patterns
         self.sanitize_git_args(paths)?;
 
-        let output = self.run_git_command(&["add", "--", paths]).await;
+        let output = self.run_git_command(repo, &["add", "--", paths]).await;
 
         match output {
             Ok(_) => Ok(ToolResult {
@@ -349,7 +598,8 @@ impl GitOperationsTool {
         }
     }
 
-    async fn git_checkout(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    /// The validated `branch` argument of `checkout`.
+    fn checkout_branch(&self, args: &serde_json::Value) -> anyhow::Result<String> {
         let branch = args
             .get("branch")
             .and_then(|v| v.as_str())
@@ -368,8 +618,19 @@ impl GitOperationsTool {
         if branch_name.contains('@') || branch_name.contains('^') || branch_name.contains('~') {
             anyhow::bail!("Branch name contains invalid characters");
         }
+        Ok(branch_name.clone())
+    }
 
-        let output = self.run_git_command(&["checkout", branch_name]).await;
+    async fn git_checkout(
+        &self,
+        repo: &Path,
+        args: serde_json::Value,
+    ) -> anyhow::Result<ToolResult> {
+        let branch_name = self.checkout_branch(&args)?;
+
+        let output = self
+            .run_git_command(repo, &["checkout", &branch_name])
+            .await;
 
         match output {
             Ok(_) => Ok(ToolResult {
@@ -385,7 +646,52 @@ impl GitOperationsTool {
         }
     }
 
-    async fn git_stash(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    async fn git_apply(&self, repo: &Path, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let patch = args
+            .get("patch")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'patch' parameter"))?;
+
+        if patch.trim().is_empty() {
+            anyhow::bail!("Patch cannot be empty");
+        }
+
+        // Validate first so a partially applicable patch never touches the tree.
+        if let Err(e) = self
+            .run_git_command_with_stdin(repo, &["apply", "--check", "-"], patch)
+            .await
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Patch does not apply: {e}")),
+            });
+        }
+
+        let output = self
+            .run_git_command_with_stdin(repo, &["apply", "--stat", "--apply", "-"], patch)
+            .await;
+
+        match output {
+            Ok(stat) => Ok(ToolResult {
+                success: true,
+                output: format!("Applied patch:\n{}", stat.trim_end()),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Apply failed: {e}")),
+            }),
+        }
+    }
+
+    fn stash_index(args: &serde_json::Value) -> anyhow::Result<i32> {
+        let index_raw = args.get("index").and_then(|v| v.as_u64()).unwrap_or(0);
+        i32::try_from(index_raw).map_err(|_| anyhow::anyhow!("stash index too large: {index_raw}"))
+    }
+
+    async fn git_stash(&self, repo: &Path, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let action = args
             .get("action")
             .and_then(|v| v.as_str())
@@ -393,16 +699,14 @@ impl GitOperationsTool {
 
         let output = match action {
             "push" | "save" => {
-                self.run_git_command(&["stash", "push", "-m", "auto-stash"])
+                self.run_git_command(repo, &["stash", "push", "-m", "auto-stash"])
                     .await
             }
-            "pop" => self.run_git_command(&["stash", "pop"]).await,
-            "list" => self.run_git_command(&["stash", "list"]).await,
+            "pop" => self.run_git_command(repo, &["stash", "pop"]).await,
+            "list" => self.run_git_command(repo, &["stash", "list"]).await,
             "drop" => {
-                let index_raw = args.get("index").and_then(|v| v.as_u64()).unwrap_or(0);
-                let index = i32::try_from(index_raw)
-                    .map_err(|_| anyhow::anyhow!("stash index too large: {index_raw}"))?;
-                self.run_git_command(&["stash", "drop", &format!("stash@{{{index}}}")])
+                let index = Self::stash_index(&args)?;
+                self.run_git_command(repo, &["stash", "drop", &format!("stash@{{{index}}}")])
                     .await
             }
             _ => anyhow::bail!("Unknown stash action: {action}. Use: push, pop, list, drop"),
@@ -430,7 +734,7 @@ impl Tool for GitOperationsTool {
     }
 
     fn description(&self) -> &str {
-        "Perform structured Git operations (status, diff, log, branch, commit, add, checkout, stash). Provides parsed JSON output and integrates with security policy for autonomy controls."
+        "Perform structured Git operations (status, diff, log, show, branch, commit, add, checkout, stash, apply) on the workspace or a configured repository. Provides parsed JSON output and integrates with security policy for autonomy controls."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -439,9 +743,21 @@ impl Tool for GitOperationsTool {
             "properties": {
                 "operation": {
                     "type": "string",
-                    "enum": ["status", "diff", "log", "branch", "commit", "add", "checkout", "stash"],
+                    "enum": ["status", "diff", "log", "show", "branch", "commit", "add", "checkout", "stash", "apply"],
                     "description": "Git operation to perform"
                 },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository to operate on: a configured repository path or name, or a directory inside the workspace (default: workspace)"
+                },
+                "rev": {
+                    "type": "string",
+                    "description": "Commit, tag or branch to inspect (for 'show' operation, default: HEAD)"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "New branch name (for 'branch' operation; omit to list branches)"
+                },
                 "message": {
                     "type": "string",
                     "description": "Commit message (for 'commit' operation)"
@@ -454,6 +770,10 @@ impl Tool for GitOperationsTool {
                     "type": "string",
                     "description": "Branch name (for 'checkout' operation)"
                 },
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff to apply to the working tree (for 'apply' operation)"
+                },
                 "files": {
                     "type": "string",
                     "description": "File or path to diff (for 'diff' operation, default: '.')"
@@ -480,6 +800,90 @@ impl Tool for GitOperationsTool {
         })
     }
 
+    async fn dry_run(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        let operation = args
+            .get("operation")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'operation' parameter"))?;
+        let repo = self
+            .resolve_repo(args.get("repo").and_then(|v| v.as_str()))
+            .map_err(|e| anyhow::anyhow!(e))?;
+        if let Some(reason) = self.write_blocked(operation, &args) {
+            anyhow::bail!(reason);
+        }
+
+        match operation {
+            "commit" => {
+                let message = Self::commit_message(&args)?;
+                let staged = self
+                    .run_git_command(&repo, &["diff", "--cached", "--stat"])
+                    .await?;
+                if staged.trim().is_empty() {
+                    anyhow::bail!("Nothing is staged to commit");
+                }
+                Ok(format!(
+                    "Would commit with message: {message}\n{}",
+                    staged.trim_end()
+                ))
+            }
+            "add" => {
+                let paths = args
+                    .get("paths")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'paths' parameter"))?;
+                self.sanitize_git_args(paths)?;
+                let staged = self
+                    .run_git_command(&repo, &["add", "--dry-run", "--", paths])
+                    .await?;
+                Ok(format!("Would stage:\n{}", staged.trim_end()))
+            }
+            "checkout" => Ok(format!(
+                "Would switch to branch: {}",
+                self.checkout_branch(&args)?
+            )),
+            "branch" if args.get("name").is_some() => {
+                let name = args.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                Ok(format!(
+                    "Would create branch: {}",
+                    self.new_branch_name(&repo, name).await?
+                ))
+            }
+            "apply" => {
+                let patch = args
+                    .get("patch")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'patch' parameter"))?;
+                if patch.trim().is_empty() {
+                    anyhow::bail!("Patch cannot be empty");
+                }
+                self.run_git_command_with_stdin(&repo, &["apply", "--check", "-"], patch)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Patch does not apply: {e}"))?;
+                let stat = self
+                    .run_git_command_with_stdin(&repo, &["apply", "--stat", "-"], patch)
+                    .await?;
+                Ok(format!("Would apply patch:\n{}", stat.trim_end()))
+            }
+            "stash" => match args
+                .get("action")
+                .and_then(|v| v.as_str())
+                .unwrap_or("push")
+            {
+                "push" | "save" => Ok("Would stash the uncommitted changes".into()),
+                "pop" => Ok("Would apply and drop stash@{0}".into()),
+                "drop" => Ok(format!(
+                    "Would drop stash@{{{}}}",
+                    Self::stash_index(&args)?
+                )),
+                "list" => self.read_only_report(args).await,
+                action => {
+                    anyhow::bail!("Unknown stash action: {action}. Use: push, pop, list, drop")
+                }
+            },
+            _ => self.read_only_report(args).await,
+        }
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let operation = match args.get("operation").and_then(|v| v.as_str()) {
             Some(op) => op,
@@ -492,10 +896,21 @@ impl Tool for GitOperationsTool {
             }
         };
 
+        let repo = match self.resolve_repo(args.get("repo").and_then(|v| v.as_str())) {
+            Ok(repo) => repo,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e),
+                });
+            }
+        };
+
         // Check if we're in a git repository
-        if !self.workspace_dir.join(".git").exists() {
+        if !repo.join(".git").exists() {
             // Try to find .git in parent directories
-            let mut current_dir = self.workspace_dir.as_path();
+            let mut current_dir = repo.as_path();
             let mut found_git = false;
             while current_dir.parent().is_some() {
                 if current_dir.join(".git").exists() {
@@ -514,28 +929,12 @@ impl Tool for GitOperationsTool {
             }
         }
 
-        // Check autonomy level for write operations
-        if self.requires_write_access(operation) {
-            if !self.security.can_act() {
-                return Ok(ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(
-                        "Action blocked: git write operations require higher autonomy level".into(),
-                    ),
-                });
-            }
-
-            match self.security.autonomy {
-                AutonomyLevel::ReadOnly => {
-                    return Ok(ToolResult {
-                        success: false,
-                        output: String::new(),
-                        error: Some("Action blocked: read-only mode".into()),
-                    });
-                }
-                AutonomyLevel::Supervised | AutonomyLevel::Full => {}
-            }
+        if let Some(reason) = self.write_blocked(operation, &args) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(reason.into()),
+            });
         }
 
         // Record action for rate limiting
@@ -549,14 +948,16 @@ impl Tool for GitOperationsTool {
 
         // Execute the requested operation
         match operation {
-            "status" => self.git_status(args).await,
-            "diff" => self.git_diff(args).await,
-            "log" => self.git_log(args).await,
-            "branch" => self.git_branch(args).await,
-            "commit" => self.git_commit(args).await,
-            "add" => self.git_add(args).await,
-            "checkout" => self.git_checkout(args).await,
-            "stash" => self.git_stash(args).await,
+            "status" => self.git_status(&repo, args).await,
+            "diff" => self.git_diff(&repo, args).await,
+            "log" => self.git_log(&repo, args).await,
+            "show" => self.git_show(&repo, args).await,
+            "branch" => self.git_branch(&repo, args).await,
+            "commit" => self.git_commit(&repo, args).await,
+            "add" => self.git_add(&repo, args).await,
+            "checkout" => self.git_checkout(&repo, args).await,
+            "stash" => self.git_stash(&repo, args).await,
+            "apply" => self.git_apply(&repo, args).await,
             _ => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -660,6 +1061,7 @@ mod tests {
         assert!(tool.requires_write_access("commit"));
         assert!(tool.requires_write_access("add"));
         assert!(tool.requires_write_access("checkout"));
+        assert!(tool.requires_write_access("apply"));
 
         assert!(!tool.requires_write_access("status"));
         assert!(!tool.requires_write_access("diff"));
@@ -803,6 +1205,157 @@ mod tests {
             .contains("Unknown operation"));
     }
 
+    fn init_repo_with_commit(dir: &std::path::Path) {
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap()
+        };
+        git(&["init"]);
+        std::fs::write(dir.join("README.md"), "hello\n").unwrap();
+        git(&["add", "README.md"]);
+        git(&[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Initial commit",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn show_returns_commit_details_and_branch_creation_is_gated() {
+        let tmp = TempDir::new().unwrap();
+        init_repo_with_commit(tmp.path());
+        let tool = test_tool(tmp.path());
+
+        let result = tool.execute(json!({"operation": "show"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let parsed: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(parsed["message"], "Initial commit");
+        assert!(parsed["patch"].as_str().unwrap().contains("+hello"));
+
+        let result = tool
+            .execute(json!({"operation": "branch", "name": "feature/x"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let listed = tool.execute(json!({"operation": "branch"})).await.unwrap();
+        assert!(listed.output.contains("feature/x"));
+
+        let readonly = GitOperationsTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy: AutonomyLevel::ReadOnly,
+                ..SecurityPolicy::default()
+            }),
+            tmp.path().to_path_buf(),
+        );
+        let result = readonly
+            .execute(json!({"operation": "branch", "name": "other"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn repo_argument_is_limited_to_workspace_and_configured_repos() {
+        let workspace = TempDir::new().unwrap();
+        let external = TempDir::new().unwrap();
+        let unlisted = TempDir::new().unwrap();
+        init_repo_with_commit(external.path());
+        init_repo_with_commit(unlisted.path());
+
+        let tool =
+            test_tool(workspace.path()).with_repositories(vec![external.path().to_path_buf()]);
+
+        let name = external.path().file_name().unwrap().to_str().unwrap();
+        let result = tool
+            .execute(json!({"operation": "log", "repo": name}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Initial commit"));
+
+        let result = tool
+            .execute(json!({"operation": "status", "repo": unlisted.path().to_str().unwrap()}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not configured"));
+    }
+
+    #[tokio::test]
+    async fn apply_patches_working_tree_and_rejects_bad_patch() {
+        let tmp = TempDir::new().unwrap();
+        std::process::Command::new("git")
+            .args(["init"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        std::fs::write(tmp.path().join("hello.txt"), "hello\n").unwrap();
+
+        let tool = test_tool(tmp.path());
+        let patch = "--- a/hello.txt\n+++ b/hello.txt\n@@ -1 +1 @@\n-hello\n+hello world\n";
+
+        let result = tool
+            .execute(json!({"operation": "apply", "patch": patch}))
+            .await
+            .unwrap();
+        assert!(result.success, "apply failed: {:?}", result.error);
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("hello.txt")).unwrap(),
+            "hello world\n"
+        );
+
+        // Applying the same patch again no longer matches the tree.
+        let result = tool
+            .execute(json!({"operation": "apply", "patch": patch}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("does not apply"));
+    }
+
+    #[tokio::test]
+    async fn dry_run_describes_writes_without_making_them() {
+        let tmp = TempDir::new().unwrap();
+        init_repo_with_commit(tmp.path());
+        let tool = test_tool(tmp.path());
+        std::fs::write(tmp.path().join("notes.txt"), "draft\n").unwrap();
+
+        let plan = tool
+            .dry_run(json!({"operation": "add", "paths": "notes.txt"}))
+            .await
+            .unwrap();
+        assert!(plan.contains("notes.txt"), "{plan}");
+        let status = tool.execute(json!({"operation": "status"})).await.unwrap();
+        let status: serde_json::Value = serde_json::from_str(&status.output).unwrap();
+        assert_eq!(status["staged"], json!([]));
+        assert_eq!(status["untracked"], json!(["notes.txt"]));
+
+        let error = tool
+            .dry_run(json!({"operation": "commit", "message": "Add notes"}))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Nothing is staged"));
+
+        let plan = tool
+            .dry_run(json!({"operation": "branch", "name": "feature"}))
+            .await
+            .unwrap();
+        assert_eq!(plan, "Would create branch: feature");
+        let branches = tool.execute(json!({"operation": "branch"})).await.unwrap();
+        assert!(!branches.output.contains("feature"));
+    }
+
     #[test]
     fn truncates_multibyte_commit_message_without_panicking() {
         let long = "🦀".repeat(2500);
@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolResult, ToolRisk};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -42,6 +42,10 @@ impl Tool for GlobSearchTool {
         })
     }
 
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let pattern = args
             .get("pattern")
@@ -99,6 +103,7 @@ impl Tool for GlobSearchTool {
             }
         };
 
+        let ws = self.security.workspace();
         let workspace_canon = match std::fs::canonicalize(workspace) {
             Ok(p) => p,
             Err(e) => {
@@ -119,15 +124,14 @@ impl Tool for GlobSearchTool {
                 Err(_) => continue, // skip unreadable entries
             };
 
-            // Canonicalize to resolve symlinks, then verify still inside workspace
-            let resolved = match std::fs::canonicalize(&path) {
-                Ok(p) => p,
-                Err(_) => continue, // skip broken symlinks / unresolvable paths
+            // Resolve symlinks through the workspace jail; broken links,
+            // forbidden paths and symlink escapes are silently left out.
+            let Ok(relative) = path.strip_prefix(workspace) else {
+                continue;
+            };
+            let Ok(resolved) = ws.resolve(&relative.to_string_lossy()).await else {
+                continue;
             };
-
-            if !self.security.is_resolved_path_allowed(&resolved) {
-                continue; // silently filter symlink escapes
-            }
 
             // Only include files, not directories
             if resolved.is_dir() {
@@ -0,0 +1,978 @@
+//! Readability-style main-content extraction and HTML → Markdown conversion.
+//!
+//! A small tolerant tokenizer builds a loose element tree, boilerplate
+//! (navigation, headers, footers, sidebars, cookie banners, ...) is pruned,
+//! the densest text container is picked as the article body, and that
+//! subtree is rendered as Markdown. Malformed markup degrades to extra or
+//! missing text, never to errors.
+
+/// Extraction result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extracted {
+    pub title: Option<String>,
+    pub markdown: String,
+}
+
+/// Extract the main content of `html` as Markdown.
+///
+/// `base_url` resolves relative links and images. With `readability` off
+/// the whole `<body>` is converted (boilerplate elements are still dropped).
+pub fn extract(html: &str, base_url: &str, readability: bool) -> Extracted {
+    let root = build_tree(html);
+    let title = find_first(&root, "title")
+        .map(|el| collapse_whitespace(&text_content(el)))
+        .filter(|t| !t.is_empty())
+        .or_else(|| {
+            find_first(&root, "h1")
+                .map(|el| collapse_whitespace(&text_content(el)))
+                .filter(|t| !t.is_empty())
+        });
+
+    let body = find_first(&root, "body").unwrap_or(&root);
+    let content = if readability {
+        main_content(body)
+    } else {
+        body
+    };
+
+    let mut writer = MarkdownWriter::new(base_url);
+    writer.render_element(content);
+    Extracted {
+        title,
+        markdown: writer.finish(),
+    }
+}
+
+// ── Tree ─────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+struct Element {
+    tag: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    fn new(tag: &str, attrs: Vec<(String, String)>) -> Self {
+        Self {
+            tag: tag.to_string(),
+            attrs,
+            children: Vec::new(),
+        }
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(el) => Some(el),
+            Node::Text(_) => None,
+        })
+    }
+}
+
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+/// Elements whose content is never text for the reader.
+const SKIPPED_RAW_TAGS: &[&str] = &["script", "style", "noscript", "template", "textarea"];
+/// Block elements that implicitly close an open `<p>`.
+const CLOSES_P: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "div",
+    "dl",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+/// Always dropped when rendering.
+const BOILERPLATE_TAGS: &[&str] = &[
+    "head", "nav", "header", "footer", "aside", "form", "button", "iframe", "svg", "canvas",
+    "select", "input", "dialog", "object", "embed", "menu", "title",
+];
+const NEGATIVE_HINTS: &[&str] = &[
+    "sidebar",
+    "footer",
+    "header",
+    "navbar",
+    "nav-",
+    "menu",
+    "breadcrumb",
+    "comment",
+    "share",
+    "social",
+    "promo",
+    "advert",
+    "sponsor",
+    "cookie",
+    "consent",
+    "banner",
+    "related",
+    "recommend",
+    "subscribe",
+    "newsletter",
+    "popup",
+    "modal",
+    "skip-link",
+];
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "content", "entry", "main", "post", "story", "text",
+];
+
+fn build_tree(html: &str) -> Element {
+    let lower = html.to_ascii_lowercase();
+    let mut stack = vec![Element::new("#root", Vec::new())];
+    let mut cursor = 0;
+
+    while cursor < html.len() {
+        let Some(offset) = html[cursor..].find('<') else {
+            push_text(&mut stack, &html[cursor..]);
+            break;
+        };
+        let lt = cursor + offset;
+        push_text(&mut stack, &html[cursor..lt]);
+        let rest = &html[lt..];
+
+        if rest.starts_with("<!--") {
+            cursor = lower[lt..]
+                .find("-->")
+                .map_or(html.len(), |end| lt + end + 3);
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            cursor = html[lt..].find('>').map_or(html.len(), |end| lt + end + 1);
+            continue;
+        }
+
+        let closing = rest.starts_with("</");
+        let name_start = lt + if closing { 2 } else { 1 };
+        let name_len = html[name_start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == ':'))
+            .unwrap_or(html.len() - name_start);
+        if name_len == 0 || !html.as_bytes()[name_start].is_ascii_alphabetic() {
+            push_text(&mut stack, "<");
+            cursor = lt + 1;
+            continue;
+        }
+        let name = lower[name_start..name_start + name_len].to_string();
+        let Some(tag_end) = find_tag_end(html, name_start + name_len) else {
+            break;
+        };
+        cursor = tag_end + 1;
+
+        if closing {
+            close_element(&mut stack, &name);
+            continue;
+        }
+
+        let raw_attrs = &html[name_start + name_len..tag_end];
+        let self_closing = raw_attrs.trim_end().ends_with('/');
+
+        if SKIPPED_RAW_TAGS.contains(&name.as_str()) || name == "title" {
+            let close = format!("</{name}");
+            let end = lower[cursor..]
+                .find(&close)
+                .map_or(html.len(), |i| cursor + i);
+            if name == "title" {
+                let mut title = Element::new("title", Vec::new());
+                title
+                    .children
+                    .push(Node::Text(decode_entities(&html[cursor..end])));
+                append(&mut stack, title);
+            }
+            cursor = html[end..].find('>').map_or(html.len(), |i| end + i + 1);
+            continue;
+        }
+
+        if CLOSES_P.contains(&name.as_str()) && stack.last().is_some_and(|el| el.tag == "p") {
+            close_element(&mut stack, "p");
+        }
+        if name == "li" {
+            close_until_sibling(&mut stack, "li", &["ul", "ol"]);
+        } else if name == "tr" {
+            close_until_sibling(&mut stack, "tr", &["table", "tbody", "thead", "tfoot"]);
+        } else if name == "td" || name == "th" {
+            close_until_sibling(&mut stack, "td", &["tr", "table"]);
+            close_until_sibling(&mut stack, "th", &["tr", "table"]);
+        }
+
+        let element = Element::new(&name, parse_attrs(raw_attrs));
+        if self_closing || VOID_TAGS.contains(&name.as_str()) {
+            append(&mut stack, element);
+        } else {
+            stack.push(element);
+        }
+    }
+
+    while stack.len() > 1 {
+        let el = stack.pop().expect("stack has more than one element");
+        append(&mut stack, el);
+    }
+    stack.pop().expect("root element")
+}
+
+/// Index of the `>` closing a tag, honoring quoted attribute values.
+fn find_tag_end(html: &str, from: usize) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in html[from..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(from + i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn push_text(stack: &mut [Element], raw: &str) {
+    if raw.is_empty() {
+        return;
+    }
+    if let Some(top) = stack.last_mut() {
+        top.children.push(Node::Text(decode_entities(raw)));
+    }
+}
+
+fn append(stack: &mut [Element], element: Element) {
+    if let Some(top) = stack.last_mut() {
+        top.children.push(Node::Element(element));
+    }
+}
+
+/// Pop up to and including the nearest open `name`; unmatched end tags are ignored.
+fn close_element(stack: &mut Vec<Element>, name: &str) {
+    let Some(position) = stack.iter().rposition(|el| el.tag == name) else {
+        return;
+    };
+    if position == 0 {
+        return;
+    }
+    while stack.len() > position {
+        let el = stack.pop().expect("stack longer than position");
+        append(stack, el);
+    }
+}
+
+/// Close an open `tag` unless one of `scopes` is nearer (e.g. nested lists).
+fn close_until_sibling(stack: &mut Vec<Element>, tag: &str, scopes: &[&str]) {
+    for el in stack.iter().rev() {
+        if el.tag == tag {
+            close_element(stack, tag);
+            return;
+        }
+        if scopes.contains(&el.tag.as_str()) {
+            return;
+        }
+    }
+}
+
+pub(crate) fn parse_attrs(raw: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    let bytes = raw.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+            i += 1;
+        }
+        let name_start = i;
+        while i < bytes.len() && !bytes[i].is_ascii_whitespace() && !matches!(bytes[i], b'=' | b'/')
+        {
+            i += 1;
+        }
+        if name_start == i {
+            break;
+        }
+        let name = raw[name_start..i].to_ascii_lowercase();
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let mut value = String::new();
+        if i < bytes.len() && bytes[i] == b'=' {
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            if i < bytes.len() && matches!(bytes[i], b'"' | b'\'') {
+                let quote = bytes[i];
+                let start = i + 1;
+                let end = raw[start..]
+                    .bytes()
+                    .position(|b| b == quote)
+                    .map_or(raw.len(), |p| start + p);
+                value = decode_entities(&raw[start..end]);
+                i = (end + 1).min(bytes.len());
+            } else {
+                let start = i;
+                while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                value = decode_entities(&raw[start..i]);
+            }
+        }
+        attrs.push((name, value));
+    }
+    attrs
+}
+
+pub(crate) fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let decoded = after[1..].find(';').filter(|&i| i <= 10).and_then(|semi| {
+            let entity = &after[1..=semi];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" | "#39" => Some('\''),
+                "nbsp" => Some(' '),
+                "mdash" => Some('\u{2014}'),
+                "ndash" => Some('\u{2013}'),
+                "hellip" => Some('\u{2026}'),
+                "lsquo" => Some('\u{2018}'),
+                "rsquo" => Some('\u{2019}'),
+                "ldquo" => Some('\u{201c}'),
+                "rdquo" => Some('\u{201d}'),
+                "laquo" => Some('\u{ab}'),
+                "raquo" => Some('\u{bb}'),
+                "bull" => Some('\u{2022}'),
+                "middot" => Some('\u{b7}'),
+                "copy" => Some('\u{a9}'),
+                "reg" => Some('\u{ae}'),
+                "trade" => Some('\u{2122}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, semi + 2))
+        });
+        match decoded {
+            Some((c, consumed)) => {
+                out.push(c);
+                rest = &after[consumed..];
+            }
+            None => {
+                out.push('&');
+                rest = &after[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn find_first<'a>(el: &'a Element, tag: &str) -> Option<&'a Element> {
+    if el.tag == tag {
+        return Some(el);
+    }
+    el.elements().find_map(|child| find_first(child, tag))
+}
+
+fn text_content(el: &Element) -> String {
+    let mut out = String::new();
+    collect_text(el, &mut out);
+    out
+}
+
+fn collect_text(el: &Element, out: &mut String) {
+    for node in &el.children {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(child) => collect_text(child, out),
+        }
+    }
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// ── Readability ──────────────────────────────────────────────────
+
+fn is_boilerplate(el: &Element) -> bool {
+    if BOILERPLATE_TAGS.contains(&el.tag.as_str()) {
+        return true;
+    }
+    if el.attr("hidden").is_some() || el.attr("aria-hidden") == Some("true") {
+        return true;
+    }
+    if el
+        .attr("style")
+        .is_some_and(|style| style.replace(' ', "").contains("display:none"))
+    {
+        return true;
+    }
+    if matches!(
+        el.attr("role"),
+        Some("navigation" | "banner" | "contentinfo" | "complementary" | "dialog")
+    ) {
+        return true;
+    }
+    if matches!(el.tag.as_str(), "html" | "body" | "main" | "article") {
+        return false;
+    }
+
+    let hints = format!(
+        "{} {}",
+        el.attr("class").unwrap_or(""),
+        el.attr("id").unwrap_or("")
+    )
+    .to_ascii_lowercase();
+    NEGATIVE_HINTS.iter().any(|hint| hints.contains(hint))
+        && !POSITIVE_HINTS.iter().any(|hint| hints.contains(hint))
+}
+
+/// Visible text length and the part of it inside links.
+fn text_stats(el: &Element, in_link: bool) -> (usize, usize) {
+    let mut total = 0;
+    let mut linked = 0;
+    for node in &el.children {
+        match node {
+            Node::Text(text) => {
+                let len = text
+                    .split_whitespace()
+                    .map(|w| w.chars().count() + 1)
+                    .sum::<usize>();
+                total += len;
+                if in_link {
+                    linked += len;
+                }
+            }
+            Node::Element(child) if !is_boilerplate(child) => {
+                let (t, l) = text_stats(child, in_link || child.tag == "a");
+                total += t;
+                linked += l;
+            }
+            Node::Element(_) => {}
+        }
+    }
+    (total, linked)
+}
+
+fn is_paragraph_like(el: &Element) -> bool {
+    matches!(
+        el.tag.as_str(),
+        "p" | "pre" | "blockquote" | "li" | "td" | "h2" | "h3" | "h4"
+    )
+}
+
+fn paragraph_score(el: &Element) -> f64 {
+    let text = text_content(el);
+    let len = text.trim().chars().count();
+    if len < 25 {
+        return 0.0;
+    }
+    1.0 + text.matches(',').count() as f64 + (len as f64 / 100.0).min(3.0)
+}
+
+/// Direct paragraph children count fully, grandchildren half.
+fn container_score(el: &Element) -> f64 {
+    let mut score = 0.0;
+    for child in el.elements().filter(|c| !is_boilerplate(c)) {
+        if is_paragraph_like(child) {
+            score += paragraph_score(child);
+        } else {
+            score += child
+                .elements()
+                .filter(|g| is_paragraph_like(g) && !is_boilerplate(g))
+                .map(paragraph_score)
+                .sum::<f64>()
+                / 2.0;
+        }
+    }
+    let (total, linked) = text_stats(el, false);
+    if total == 0 {
+        return 0.0;
+    }
+    score * (1.0 - linked as f64 / total as f64)
+}
+
+fn best_container<'a>(el: &'a Element, best: &mut Option<(f64, &'a Element)>) {
+    if is_boilerplate(el) {
+        return;
+    }
+    if matches!(
+        el.tag.as_str(),
+        "div" | "section" | "article" | "main" | "td" | "body" | "#root"
+    ) {
+        let score = container_score(el);
+        if best.is_none_or(|(top, _)| score > top) {
+            *best = Some((score, el));
+        }
+    }
+    for child in el.elements() {
+        best_container(child, best);
+    }
+}
+
+/// Pick the article body: a substantial `<article>`/`<main>` wins, otherwise
+/// the highest-scoring text container.
+fn main_content(body: &Element) -> &Element {
+    let mut semantic: Option<(usize, &Element)> = None;
+    collect_semantic(body, &mut semantic);
+    if let Some((len, el)) = semantic {
+        if len >= 250 {
+            return el;
+        }
+    }
+
+    let mut best = None;
+    best_container(body, &mut best);
+    match best {
+        Some((score, el)) if score > 0.0 => el,
+        _ => body,
+    }
+}
+
+fn collect_semantic<'a>(el: &'a Element, best: &mut Option<(usize, &'a Element)>) {
+    if is_boilerplate(el) {
+        return;
+    }
+    if matches!(el.tag.as_str(), "article" | "main") || el.attr("role") == Some("main") {
+        let (total, _) = text_stats(el, false);
+        if best.is_none_or(|(len, _)| total > len) {
+            *best = Some((total, el));
+        }
+    }
+    for child in el.elements() {
+        collect_semantic(child, best);
+    }
+}
+
+// ── Markdown rendering ───────────────────────────────────────────
+
+struct MarkdownWriter<'a> {
+    base_url: &'a str,
+    blocks: Vec<String>,
+    inline: String,
+}
+
+impl<'a> MarkdownWriter<'a> {
+    fn new(base_url: &'a str) -> Self {
+        Self {
+            base_url,
+            blocks: Vec::new(),
+            inline: String::new(),
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        self.blocks.join("\n\n")
+    }
+
+    fn flush(&mut self) {
+        let text = self
+            .inline
+            .split('\n')
+            .map(collapse_whitespace)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.inline.clear();
+        if !text.is_empty() {
+            self.blocks.push(text);
+        }
+    }
+
+    fn push_block(&mut self, block: String) {
+        self.flush();
+        if !block.trim().is_empty() {
+            self.blocks.push(block);
+        }
+    }
+
+    /// Render `el`'s children into a standalone Markdown string.
+    fn render_nested(&self, el: &Element) -> String {
+        let mut nested = MarkdownWriter::new(self.base_url);
+        nested.render_element(el);
+        nested.finish()
+    }
+
+    fn render_element(&mut self, el: &Element) {
+        for node in &el.children {
+            match node {
+                // Source newlines are just whitespace; only `<br>` breaks lines.
+                Node::Text(text) => self.inline.push_str(&text.replace(['\n', '\r'], " ")),
+                Node::Element(child) => self.render_node(child),
+            }
+        }
+    }
+
+    fn render_node(&mut self, el: &Element) {
+        if is_boilerplate(el) {
+            return;
+        }
+        match el.tag.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = usize::from(el.tag.as_bytes()[1] - b'0');
+                let text = collapse_whitespace(&self.inline_text(el));
+                if !text.is_empty() {
+                    self.push_block(format!("{} {text}", "#".repeat(level)));
+                }
+            }
+            "br" => self.inline.push('\n'),
+            "hr" => self.push_block("---".into()),
+            "pre" => {
+                let code = text_content(el);
+                let code = code.trim_matches('\n');
+                if !code.trim().is_empty() {
+                    self.push_block(format!("```\n{code}\n```"));
+                }
+            }
+            "blockquote" => {
+                let inner = self.render_nested(el);
+                let quoted = inner
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            ">".to_string()
+                        } else {
+                            format!("> {line}")
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                self.push_block(quoted);
+            }
+            "ul" | "ol" => {
+                let ordered = el.tag == "ol";
+                let items = el
+                    .elements()
+                    .filter(|item| item.tag == "li" && !is_boilerplate(item))
+                    .map(|item| self.render_nested(item))
+                    .filter(|text| !text.is_empty())
+                    .enumerate()
+                    .map(|(i, text)| {
+                        let marker = if ordered {
+                            format!("{}. ", i + 1)
+                        } else {
+                            "- ".to_string()
+                        };
+                        let indent = " ".repeat(marker.len());
+                        text.lines()
+                            .enumerate()
+                            .map(|(n, line)| {
+                                if n == 0 {
+                                    format!("{marker}{line}")
+                                } else if line.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!("{indent}{line}")
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    })
+                    .collect::<Vec<_>>();
+                self.push_block(items.join("\n"));
+            }
+            "table" => {
+                let table = self.render_table(el);
+                self.push_block(table);
+            }
+            "a" | "strong" | "b" | "em" | "i" | "code" | "img" | "span" | "small" | "sup"
+            | "sub" | "abbr" | "cite" | "q" | "mark" | "time" | "u" | "s" | "del" | "ins"
+            | "kbd" | "label" => {
+                let text = self.inline_text_for(el);
+                self.inline.push_str(&text);
+            }
+            _ => {
+                self.flush();
+                self.render_element(el);
+                self.flush();
+            }
+        }
+    }
+
+    fn render_table(&self, table: &Element) -> String {
+        let mut rows = Vec::new();
+        collect_rows(table, &mut rows);
+        let mut lines = Vec::new();
+        for (index, row) in rows.iter().enumerate() {
+            let cells = row
+                .elements()
+                .filter(|cell| matches!(cell.tag.as_str(), "td" | "th"))
+                .map(|cell| collapse_whitespace(&self.inline_text(cell)).replace('|', "\\|"))
+                .collect::<Vec<_>>();
+            if cells.is_empty() {
+                continue;
+            }
+            lines.push(format!("| {} |", cells.join(" | ")));
+            if index == 0 {
+                lines.push(format!("|{}", " --- |".repeat(cells.len())));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Inline Markdown for the children of `el`.
+    fn inline_text(&self, el: &Element) -> String {
+        let mut out = String::new();
+        for node in &el.children {
+            match node {
+                Node::Text(text) => out.push_str(&text.replace(['\n', '\r'], " ")),
+                Node::Element(child) if !is_boilerplate(child) => {
+                    out.push_str(&self.inline_text_for(child));
+                }
+                Node::Element(_) => {}
+            }
+        }
+        out
+    }
+
+    /// Inline Markdown for `el` itself.
+    fn inline_text_for(&self, el: &Element) -> String {
+        match el.tag.as_str() {
+            "br" => "\n".into(),
+            "img" => {
+                let alt = collapse_whitespace(el.attr("alt").unwrap_or(""));
+                match el
+                    .attr("src")
+                    .and_then(|src| resolve_url(self.base_url, src))
+                {
+                    Some(src) if !alt.is_empty() => format!("![{alt}]({src})"),
+                    _ => String::new(),
+                }
+            }
+            "a" => {
+                let text = collapse_whitespace(&self.inline_text(el));
+                match el
+                    .attr("href")
+                    .and_then(|href| resolve_url(self.base_url, href))
+                {
+                    Some(href) if !text.is_empty() && text != href => format!("[{text}]({href})"),
+                    _ => text,
+                }
+            }
+            "strong" | "b" => wrap_inline(&self.inline_text(el), "**"),
+            "em" | "i" => wrap_inline(&self.inline_text(el), "*"),
+            "code" | "kbd" => wrap_inline(&text_content(el), "`"),
+            _ => self.inline_text(el),
+        }
+    }
+}
+
+fn collect_rows<'a>(el: &'a Element, rows: &mut Vec<&'a Element>) {
+    for child in el.elements() {
+        match child.tag.as_str() {
+            "tr" => rows.push(child),
+            "thead" | "tbody" | "tfoot" => collect_rows(child, rows),
+            _ => {}
+        }
+    }
+}
+
+/// Wrap trimmed `text` in `marker`, keeping surrounding spaces outside.
+fn wrap_inline(text: &str, marker: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+    let lead = if text.starts_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    let trail = if text.ends_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    format!(
+        "{lead}{marker}{}{marker}{trail}",
+        collapse_whitespace(trimmed)
+    )
+}
+
+/// Resolve `href` against `base`; `None` for fragments and script links.
+fn resolve_url(base: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() || href.starts_with('#') {
+        return None;
+    }
+    let lower = href.to_ascii_lowercase();
+    if lower.starts_with("javascript:") || lower.starts_with("data:") {
+        return None;
+    }
+    if lower.starts_with("http://") || lower.starts_with("https://") || lower.starts_with("mailto:")
+    {
+        return Some(href.to_string());
+    }
+
+    let (scheme, rest) = base.split_once("://")?;
+    if let Some(protocol_relative) = href.strip_prefix("//") {
+        return Some(format!("{scheme}://{protocol_relative}"));
+    }
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let origin = format!("{scheme}://{}", &rest[..authority_end]);
+    if href.starts_with('/') {
+        return Some(format!("{origin}{href}"));
+    }
+
+    let path = &rest[authority_end..];
+    let path = path.split(['?', '#']).next().unwrap_or("");
+    let dir = path.rfind('/').map_or("/", |i| &path[..=i]);
+    let mut segments: Vec<&str> = dir.split('/').filter(|s| !s.is_empty()).collect();
+    let (href_path, suffix) = match href.find(['?', '#']) {
+        Some(i) => (&href[..i], &href[i..]),
+        None => (href, ""),
+    };
+    for part in href_path.split('/') {
+        match part {
+            "." | "" => {}
+            ".." => {
+                segments.pop();
+            }
+            other => segments.push(other),
+        }
+    }
+    let trailing = if href_path.ends_with('/') && !segments.is_empty() {
+        "/"
+    } else {
+        ""
+    };
+    Some(format!("{origin}/{}{trailing}{suffix}", segments.join("/")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE: &str = r#"<!DOCTYPE html>
+<html><head><title>Rust 2024 &amp; beyond</title><style>p { color: red }</style></head>
+<body>
+  <header><nav><a href="/">Home</a> <a href="/blog">Blog</a></nav></header>
+  <div class="sidebar"><p>Subscribe to our newsletter, it is great, really, truly.</p></div>
+  <div id="content">
+    <h1>Rust 2024</h1>
+    <p>The new edition brings <strong>async closures</strong>, better <em>ergonomics</em>, and more.</p>
+    <p>See the <a href="guide/edition.html">edition guide</a> for details, migration steps, and caveats.</p>
+    <ul><li>First item</li><li>Second <code>item</code></li></ul>
+    <pre><code>fn main() {
+    println!("hi");
+}</code></pre>
+    <script>track();</script>
+  </div>
+  <footer><p>Copyright 2024, all rights reserved, do not copy.</p></footer>
+</body></html>"#;
+
+    #[test]
+    fn extracts_main_content_as_markdown() {
+        let extracted = extract(ARTICLE, "https://blog.example.org/posts/rust.html", true);
+        assert_eq!(extracted.title.as_deref(), Some("Rust 2024 & beyond"));
+
+        let md = extracted.markdown;
+        assert!(md.starts_with("# Rust 2024"), "{md}");
+        assert!(md.contains("brings **async closures**, better *ergonomics*, and more."));
+        assert!(md.contains("[edition guide](https://blog.example.org/posts/guide/edition.html)"));
+        assert!(md.contains("- First item\n- Second `item`"));
+        assert!(md.contains("```\nfn main() {\n    println!(\"hi\");\n}\n```"));
+        assert!(!md.contains("Subscribe"));
+        assert!(!md.contains("Copyright"));
+        assert!(!md.contains("Home"));
+        assert!(!md.contains("track()"));
+    }
+
+    #[test]
+    fn raw_mode_keeps_whole_body_but_drops_chrome() {
+        let html = "<body><div><p>Tiny teaser.</p></div>\
+            <div><p>A long paragraph, with commas, that clearly carries the actual article text.</p>\
+            <p>Another paragraph, also long enough, to count towards the container score.</p></div>\
+            <footer>Footer links</footer></body>";
+
+        let focused = extract(html, "https://example.com/", true).markdown;
+        assert!(!focused.contains("Tiny teaser"));
+        assert!(focused.starts_with("A long paragraph"));
+
+        let raw = extract(html, "https://example.com/", false).markdown;
+        assert!(raw.starts_with("Tiny teaser."));
+        assert!(!raw.contains("Footer"));
+    }
+
+    #[test]
+    fn source_newlines_do_not_break_paragraphs() {
+        let md = extract("<p>one\ntwo<br>three</p>", "https://example.com/", false).markdown;
+        assert_eq!(md, "one two\nthree");
+    }
+
+    #[test]
+    fn tolerates_unclosed_tags_and_renders_tables() {
+        let html = "<p>One<p>Two<table><tr><th>A<th>B<tr><td>1<td>x|y</table>";
+        let md = extract(html, "https://example.com", false).markdown;
+        assert_eq!(md, "One\n\nTwo\n\n| A | B |\n| --- | --- |\n| 1 | x\\|y |");
+    }
+
+    #[test]
+    fn resolves_relative_urls() {
+        let base = "https://example.com/a/b/page.html?x=1";
+        assert_eq!(
+            resolve_url(base, "../c.html#top").as_deref(),
+            Some("https://example.com/a/c.html#top")
+        );
+        assert_eq!(
+            resolve_url(base, "/root").as_deref(),
+            Some("https://example.com/root")
+        );
+        assert_eq!(
+            resolve_url(base, "//cdn.example.com/x.png").as_deref(),
+            Some("https://cdn.example.com/x.png")
+        );
+        assert_eq!(resolve_url(base, "#frag"), None);
+        assert_eq!(resolve_url(base, "javascript:void(0)"), None);
+    }
+
+    #[test]
+    fn decodes_entities_and_quoted_attributes() {
+        assert_eq!(
+            decode_entities("a&nbsp;&mdash;&#x41;&bogus;"),
+            "a \u{2014}A&bogus;"
+        );
+        let attrs = parse_attrs(r#" href="/x?a=1&amp;b=2" data-x='y' hidden"#);
+        assert_eq!(attrs[0], ("href".into(), "/x?a=1&b=2".into()));
+        assert_eq!(attrs[1], ("data-x".into(), "y".into()));
+        assert_eq!(attrs[2], ("hidden".into(), String::new()));
+    }
+}
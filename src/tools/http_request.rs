@@ -1,17 +1,26 @@
 use super::traits::{Tool, ToolResult};
+use crate::config::HttpSecretConfig;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
+use std::collections::HashMap;
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
+const SECRET_TEMPLATE_PREFIX: &str = "{{secret:";
+const SECRET_TEMPLATE_SUFFIX: &str = "}}";
+
 /// HTTP request tool for API interactions.
 /// Supports GET, POST, PUT, DELETE methods with configurable security.
+///
+/// Header values may reference configured secrets as `{{secret:NAME}}`; they
+/// are substituted just before sending and scrubbed from the response.
 pub struct HttpRequestTool {
     security: Arc<SecurityPolicy>,
     allowed_domains: Vec<String>,
     max_response_size: usize,
     timeout_secs: u64,
+    secrets: HashMap<String, HttpSecretConfig>,
     client: OnceLock<Result<reqwest::Client, String>>,
 }
 
@@ -27,41 +36,72 @@ impl HttpRequestTool {
             allowed_domains: normalize_allowed_domains(allowed_domains),
             max_response_size,
             timeout_secs,
+            secrets: HashMap::new(),
             client: OnceLock::new(),
         }
     }
 
-    fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
-        let url = raw_url.trim();
-
-        if url.is_empty() {
-            anyhow::bail!("URL cannot be empty");
-        }
-
-        if url.chars().any(char::is_whitespace) {
-            anyhow::bail!("URL cannot contain whitespace");
-        }
-
-        if !url.starts_with("http://") && !url.starts_with("https://") {
-            anyhow::bail!("Only http:// and https:// URLs are allowed");
-        }
-
-        if self.allowed_domains.is_empty() {
-            anyhow::bail!(
-                "HTTP request tool is enabled but no allowed_domains are configured. Add [http_request].allowed_domains in config.toml"
-            );
-        }
-
-        let host = extract_host(url)?;
+    /// Secrets available to `{{secret:NAME}}` header templates.
+    pub fn with_secrets(mut self, secrets: HashMap<String, HttpSecretConfig>) -> Self {
+        self.secrets = secrets
+            .into_iter()
+            .map(|(name, mut secret)| {
+                secret.domains = normalize_allowed_domains(secret.domains);
+                (name, secret)
+            })
+            .collect();
+        self
+    }
 
-        if is_private_or_local_host(&host) {
-            anyhow::bail!("Blocked local/private host: {host}");
-        }
+    fn secret_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.secrets.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
 
-        if !host_matches_allowlist(&host, &self.allowed_domains) {
-            anyhow::bail!("Host '{host}' is not in http_request.allowed_domains");
+    /// Replace `{{secret:NAME}}` placeholders in header values.
+    ///
+    /// Returns the expanded headers plus the secret values used, so they can
+    /// be scrubbed from the response.
+    fn expand_secret_headers(
+        &self,
+        headers: Vec<(String, String)>,
+        host: &str,
+    ) -> anyhow::Result<(Vec<(String, String)>, Vec<String>)> {
+        let mut used = Vec::new();
+        let mut expanded = Vec::with_capacity(headers.len());
+        for (key, value) in headers {
+            let mut out = String::with_capacity(value.len());
+            let mut rest = value.as_str();
+            while let Some(start) = rest.find(SECRET_TEMPLATE_PREFIX) {
+                out.push_str(&rest[..start]);
+                let after = &rest[start + SECRET_TEMPLATE_PREFIX.len()..];
+                let Some(end) = after.find(SECRET_TEMPLATE_SUFFIX) else {
+                    anyhow::bail!("Unterminated secret template in header '{key}'");
+                };
+                let name = after[..end].trim();
+                let Some(secret) = self.secrets.get(name) else {
+                    anyhow::bail!(
+                        "Unknown secret '{name}' in header '{key}'. Available: {}",
+                        self.secret_names().join(", ")
+                    );
+                };
+                if !secret.domains.is_empty() && !host_matches_allowlist(host, &secret.domains) {
+                    anyhow::bail!("Secret '{name}' may not be sent to host '{host}'");
+                }
+                out.push_str(&secret.value);
+                used.push(secret.value.clone());
+                rest = &after[end + SECRET_TEMPLATE_SUFFIX.len()..];
+            }
+            out.push_str(rest);
+            expanded.push((key, out));
         }
+        Ok((expanded, used))
+    }
 
+    fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
+        let url = raw_url.trim();
+        check_url(url, &self.allowed_domains)?;
         Ok(url.to_string())
     }
 
@@ -117,17 +157,27 @@ impl HttpRequestTool {
         body: Option<&str>,
     ) -> anyhow::Result<reqwest::Response> {
         let client = self.http_client()?;
-        let mut request = client.request(method, url);
-
-        for (key, value) in headers {
-            request = request.header(&key, &value);
-        }
-
-        if let Some(body_str) = body {
-            request = request.body(body_str.to_string());
-        }
+        let endpoint = format!(
+            "tool:http_request:{}",
+            extract_host(url).unwrap_or_default()
+        );
+        // Only idempotent methods are retried.
+        let retry = matches!(
+            method,
+            reqwest::Method::GET | reqwest::Method::HEAD | reqwest::Method::OPTIONS
+        );
 
-        Ok(request.send().await?)
+        crate::resilience::send_http(&endpoint, retry, || {
+            let mut request = client.request(method.clone(), url);
+            for (key, value) in &headers {
+                request = request.header(key, value);
+            }
+            if let Some(body_str) = body {
+                request = request.body(body_str.to_string());
+            }
+            request
+        })
+        .await
     }
 
     fn http_client(&self) -> anyhow::Result<&reqwest::Client> {
@@ -142,7 +192,8 @@ impl HttpRequestTool {
                 .timeout(Duration::from_secs(timeout_secs))
                 .connect_timeout(Duration::from_secs(10))
                 .redirect(reqwest::redirect::Policy::none());
-            let builder = crate::config::apply_runtime_proxy_to_builder(builder, "tool.http_request");
+            let builder =
+                crate::config::apply_runtime_proxy_to_builder(builder, "tool.http_request");
             builder.build().map_err(|e| e.to_string())
         });
         match result {
@@ -177,6 +228,7 @@ impl Tool for HttpRequestTool {
 
     fn description(&self) -> &str {
         "Make HTTP requests to external APIs. Supports GET, POST, PUT, DELETE, PATCH, HEAD, OPTIONS methods. \
+        Header values may reference configured secrets as {{secret:NAME}} instead of literal credentials. \
         Security constraints: allowlist-only domains, no local/private hosts, configurable timeout and response size limits."
     }
 
@@ -195,7 +247,10 @@ impl Tool for HttpRequestTool {
                 },
                 "headers": {
                     "type": "object",
-                    "description": "Optional HTTP headers as key-value pairs (e.g., {\"Authorization\": \"Bearer token\", \"Content-Type\": \"application/json\"})",
+                    "description": format!(
+                        "Optional HTTP headers as key-value pairs (e.g., {{\"Authorization\": \"Bearer {{{{secret:NAME}}}}\", \"Content-Type\": \"application/json\"}}). Configured secrets: {}",
+                        if self.secrets.is_empty() { "none".to_string() } else { self.secret_names().join(", ") }
+                    ),
                     "default": {}
                 },
                 "body": {
@@ -256,6 +311,18 @@ impl Tool for HttpRequestTool {
         };
 
         let request_headers = self.parse_headers(&headers_val);
+        let host = extract_host(&url)?;
+        let (request_headers, used_secrets) =
+            match self.expand_secret_headers(request_headers, &host) {
+                Ok(expanded) => expanded,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(e.to_string()),
+                    })
+                }
+            };
 
         match self
             .execute_request(&url, method, request_headers, body)
@@ -279,9 +346,9 @@ impl Tool for HttpRequestTool {
                     .collect::<Vec<_>>()
                     .join(", ");
 
-                // Get response body with size limit
+                // Get response body with size limit; never echo injected secrets back
                 let response_text = match response.text().await {
-                    Ok(text) => self.truncate_response(&text),
+                    Ok(text) => self.truncate_response(&scrub_secrets(&text, &used_secrets)),
                     Err(e) => format!("[Failed to read response body: {e}]"),
                 };
 
@@ -312,9 +379,52 @@ impl Tool for HttpRequestTool {
     }
 }
 
+fn scrub_secrets(text: &str, secrets: &[String]) -> String {
+    secrets
+        .iter()
+        .filter(|secret| !secret.is_empty())
+        .fold(text.to_string(), |acc, secret| {
+            acc.replace(secret.as_str(), "***REDACTED***")
+        })
+}
+
 // Helper functions similar to browser_open.rs
 
-fn normalize_allowed_domains(domains: Vec<String>) -> Vec<String> {
+/// Check `url` against the `http_request` rules: http(s) only, no local or
+/// private hosts, and a host in `allowed_domains` (normalized).
+pub(crate) fn check_url(url: &str, allowed_domains: &[String]) -> anyhow::Result<()> {
+    if url.is_empty() {
+        anyhow::bail!("URL cannot be empty");
+    }
+
+    if url.chars().any(char::is_whitespace) {
+        anyhow::bail!("URL cannot contain whitespace");
+    }
+
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        anyhow::bail!("Only http:// and https:// URLs are allowed");
+    }
+
+    if allowed_domains.is_empty() {
+        anyhow::bail!(
+            "No allowed_domains are configured for HTTP requests. Add [http_request].allowed_domains in config.toml"
+        );
+    }
+
+    let host = extract_host(url)?;
+
+    if is_private_or_local_host(&host) {
+        anyhow::bail!("Blocked local/private host: {host}");
+    }
+
+    if !host_matches_allowlist(&host, allowed_domains) {
+        anyhow::bail!("Host '{host}' is not in http_request.allowed_domains");
+    }
+
+    Ok(())
+}
+
+pub(crate) fn normalize_allowed_domains(domains: Vec<String>) -> Vec<String> {
     let mut normalized = domains
         .into_iter()
         .filter_map(|d| normalize_domain(&d))
@@ -477,6 +587,55 @@ mod tests {
         )
     }
 
+    fn secret(value: &str, domains: &[&str]) -> HttpSecretConfig {
+        HttpSecretConfig {
+            value: value.into(),
+            domains: domains.iter().map(|d| (*d).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn secret_templates_expand_for_permitted_hosts() {
+        let tool = test_tool(vec!["*"]).with_secrets(HashMap::from([
+            ("crm".to_string(), secret("s3cret", &["crm.example.com"])),
+            ("any".to_string(), secret("open", &[])),
+        ]));
+
+        let (headers, used) = tool
+            .expand_secret_headers(
+                vec![
+                    ("Authorization".into(), "Bearer {{secret:crm}}".into()),
+                    ("X-Key".into(), "{{secret: any }}".into()),
+                    ("Accept".into(), "application/json".into()),
+                ],
+                "api.crm.example.com",
+            )
+            .unwrap();
+        assert_eq!(headers[0].1, "Bearer s3cret");
+        assert_eq!(headers[1].1, "open");
+        assert_eq!(headers[2].1, "application/json");
+        assert_eq!(used, vec!["s3cret".to_string(), "open".to_string()]);
+
+        let err = tool
+            .expand_secret_headers(
+                vec![("Authorization".into(), "Bearer {{secret:crm}}".into())],
+                "evil.example.net",
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("may not be sent"));
+
+        let err = tool
+            .expand_secret_headers(vec![("A".into(), "{{secret:nope}}".into())], "x.com")
+            .unwrap_err();
+        assert!(err.to_string().contains("Available: any, crm"));
+    }
+
+    #[test]
+    fn scrub_secrets_hides_echoed_values() {
+        let scrubbed = scrub_secrets("token=s3cret ok", &["s3cret".to_string()]);
+        assert_eq!(scrubbed, "token=***REDACTED*** ok");
+    }
+
     #[test]
     fn normalize_domain_strips_scheme_path_and_case() {
         let got = normalize_domain("  HTTPS://Docs.Example.com/path ").unwrap();
@@ -0,0 +1,543 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{ImageGenerationConfig, ImageGenerationProvider};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use base64::Engine as _;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const OPENAI_DEFAULT_URL: &str = "https://api.openai.com/v1";
+const OPENAI_DEFAULT_MODEL: &str = "dall-e-3";
+const STABILITY_DEFAULT_URL: &str = "https://api.stability.ai";
+const SD_WEBUI_DEFAULT_URL: &str = "http://127.0.0.1:7860";
+
+/// Aspect ratios accepted by the Stability API.
+const STABILITY_ASPECT_RATIOS: &[(&str, u32, u32)] = &[
+    ("1:1", 1, 1),
+    ("16:9", 16, 9),
+    ("21:9", 21, 9),
+    ("2:3", 2, 3),
+    ("3:2", 3, 2),
+    ("4:5", 4, 5),
+    ("5:4", 5, 4),
+    ("9:16", 9, 16),
+    ("9:21", 9, 21),
+];
+
+/// Image returned by a backend: inline bytes or a URL to download.
+#[derive(Debug, PartialEq, Eq)]
+enum ImagePayload {
+    Bytes(Vec<u8>),
+    Url(String),
+}
+
+/// Generate images from a text prompt via OpenAI Images, Stability AI or a
+/// local Stable Diffusion web UI, and save them in the workspace.
+///
+/// The saved path is returned together with the `[IMAGE:<path>]` marker that
+/// channels with attachment support turn into an uploaded image.
+pub struct ImageGenTool {
+    security: Arc<SecurityPolicy>,
+    config: ImageGenerationConfig,
+}
+
+impl ImageGenTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: ImageGenerationConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn error(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn base_url(&self) -> String {
+        let default = match self.config.provider {
+            ImageGenerationProvider::Openai => OPENAI_DEFAULT_URL,
+            ImageGenerationProvider::Stability => STABILITY_DEFAULT_URL,
+            ImageGenerationProvider::SdWebui => SD_WEBUI_DEFAULT_URL,
+        };
+        self.config
+            .api_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// Configured key, falling back to the provider's conventional env var.
+    fn api_key(&self) -> Option<String> {
+        let env_var = match self.config.provider {
+            ImageGenerationProvider::Openai => Some("OPENAI_API_KEY"),
+            ImageGenerationProvider::Stability => Some("STABILITY_API_KEY"),
+            ImageGenerationProvider::SdWebui => None,
+        };
+        self.config
+            .api_key
+            .clone()
+            .filter(|key| !key.trim().is_empty())
+            .or_else(|| env_var.and_then(|name| std::env::var(name).ok()))
+            .filter(|key| !key.trim().is_empty())
+    }
+
+    async fn request_image(
+        &self,
+        client: &reqwest::Client,
+        prompt: &str,
+        negative_prompt: Option<&str>,
+        (width, height): (u32, u32),
+    ) -> anyhow::Result<ImagePayload> {
+        let api_key = self.api_key();
+        if api_key.is_none() && self.config.provider != ImageGenerationProvider::SdWebui {
+            anyhow::bail!(
+                "No API key configured for image generation. Set [image_generation].api_key or the provider's API key environment variable."
+            );
+        }
+
+        let base = self.base_url();
+        let request = match self.config.provider {
+            ImageGenerationProvider::Openai => {
+                let model = self.config.model.as_deref().unwrap_or(OPENAI_DEFAULT_MODEL);
+                let mut body = json!({
+                    "model": model,
+                    "prompt": prompt,
+                    "n": 1,
+                    "size": format!("{width}x{height}"),
+                });
+                // gpt-image models always answer with base64 and reject the field.
+                if !model.starts_with("gpt-image") {
+                    body["response_format"] = json!("b64_json");
+                }
+                client
+                    .post(format!("{base}/images/generations"))
+                    .json(&body)
+            }
+            ImageGenerationProvider::Stability => {
+                let mut form = reqwest::multipart::Form::new()
+                    .text("prompt", prompt.to_string())
+                    .text("output_format", "png")
+                    .text("aspect_ratio", closest_aspect_ratio(width, height));
+                if let Some(negative) = negative_prompt {
+                    form = form.text("negative_prompt", negative.to_string());
+                }
+                client
+                    .post(format!("{base}/v2beta/stable-image/generate/core"))
+                    .header("Accept", "application/json")
+                    .multipart(form)
+            }
+            ImageGenerationProvider::SdWebui => client
+                .post(format!("{base}/sdapi/v1/txt2img"))
+                .json(&json!({
+                    "prompt": prompt,
+                    "negative_prompt": negative_prompt.unwrap_or(""),
+                    "width": width,
+                    "height": height,
+                })),
+        };
+        let request = match api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        };
+
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            let detail: String = body.chars().take(300).collect();
+            anyhow::bail!("Image backend returned HTTP {status}: {detail}");
+        }
+        let value: serde_json::Value = serde_json::from_str(&body)
+            .map_err(|e| anyhow::anyhow!("Image backend returned invalid JSON: {e}"))?;
+        parse_image_response(self.config.provider, &value)
+    }
+
+    async fn download(client: &reqwest::Client, url: &str) -> anyhow::Result<Vec<u8>> {
+        let response = client.get(url).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Downloading generated image failed: HTTP {}",
+                response.status()
+            );
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Resolve where the image for `prompt` goes, creating the output
+    /// directory inside the workspace.
+    async fn output_path(&self, prompt: &str) -> Result<PathBuf, String> {
+        let relative = Path::new(self.config.output_dir.trim()).join(image_file_name(prompt));
+        self.security
+            .workspace()
+            .resolve_for_write(&relative.to_string_lossy(), true)
+            .await
+    }
+}
+
+#[async_trait]
+impl Tool for ImageGenTool {
+    fn name(&self) -> &str {
+        "generate_image"
+    }
+
+    fn description(&self) -> &str {
+        "Generate an image from a text prompt and save it as PNG in the workspace. \
+         Returns the file path; include the returned [IMAGE:<path>] marker in your reply \
+         to send the image on channels that support attachments."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "prompt": {
+                    "type": "string",
+                    "description": "Detailed description of the image to generate"
+                },
+                "size": {
+                    "type": "string",
+                    "description": format!(
+                        "Image size as WIDTHxHEIGHT (default: {})",
+                        self.config.default_size
+                    )
+                },
+                "negative_prompt": {
+                    "type": "string",
+                    "description": "Things to avoid in the image (Stability and SD web UI only)"
+                }
+            },
+            "required": ["prompt"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let prompt = args
+            .get("prompt")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'prompt' parameter"))?;
+        let size_raw = args
+            .get("size")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&self.config.default_size);
+        let Some(size) = parse_size(size_raw) else {
+            return Ok(Self::error(format!(
+                "Invalid size '{size_raw}': expected WIDTHxHEIGHT, e.g. 1024x1024"
+            )));
+        };
+        let negative_prompt = args
+            .get("negative_prompt")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|p| !p.is_empty());
+
+        if self.security.is_rate_limited() {
+            return Ok(Self::error(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "generate_image")
+        {
+            return Ok(Self::error(error));
+        }
+
+        let path = match self.output_path(prompt).await {
+            Ok(path) => path,
+            Err(e) => return Ok(Self::error(format!("Cannot prepare output directory: {e}"))),
+        };
+
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.image_generation",
+            self.config.timeout_secs,
+            10,
+        );
+        let bytes = match self
+            .request_image(&client, prompt, negative_prompt, size)
+            .await
+        {
+            Ok(ImagePayload::Bytes(bytes)) => bytes,
+            Ok(ImagePayload::Url(url)) => match Self::download(&client, &url).await {
+                Ok(bytes) => bytes,
+                Err(e) => return Ok(Self::error(e.to_string())),
+            },
+            Err(e) => return Ok(Self::error(format!("Image generation failed: {e}"))),
+        };
+
+        if let Err(e) = self
+            .security
+            .workspace()
+            .check_quota(&path, bytes.len() as u64)
+            .await
+        {
+            return Ok(Self::error(e));
+        }
+        if let Err(e) = tokio::fs::write(&path, &bytes).await {
+            return Ok(Self::error(format!("Failed to save image: {e}")));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Generated {}x{} image ({} bytes) saved to {}\n[IMAGE:{}]",
+                size.0,
+                size.1,
+                bytes.len(),
+                path.display(),
+                path.display()
+            ),
+            error: None,
+        })
+    }
+}
+
+/// Parse `WIDTHxHEIGHT` (64–4096 px per side).
+fn parse_size(raw: &str) -> Option<(u32, u32)> {
+    let (w, h) = raw
+        .trim()
+        .to_ascii_lowercase()
+        .split_once('x')
+        .map(|(w, h)| (w.trim().parse::<u32>(), h.trim().parse::<u32>()))?;
+    let (w, h) = (w.ok()?, h.ok()?);
+    let valid = |side: u32| (64..=4096).contains(&side);
+    (valid(w) && valid(h)).then_some((w, h))
+}
+
+/// Stability only takes aspect ratios; pick the nearest supported one.
+fn closest_aspect_ratio(width: u32, height: u32) -> &'static str {
+    let target = (f64::from(width) / f64::from(height)).ln();
+    STABILITY_ASPECT_RATIOS
+        .iter()
+        .min_by(|a, b| {
+            let da = ((f64::from(a.1) / f64::from(a.2)).ln() - target).abs();
+            let db = ((f64::from(b.1) / f64::from(b.2)).ln() - target).abs();
+            da.total_cmp(&db)
+        })
+        .map_or("1:1", |&(ratio, _, _)| ratio)
+}
+
+fn parse_image_response(
+    provider: ImageGenerationProvider,
+    value: &serde_json::Value,
+) -> anyhow::Result<ImagePayload> {
+    let b64 = match provider {
+        ImageGenerationProvider::Openai => {
+            let first = value
+                .pointer("/data/0")
+                .ok_or_else(|| anyhow::anyhow!("Response contains no images"))?;
+            if let Some(b64) = first.get("b64_json").and_then(|v| v.as_str()) {
+                b64
+            } else if let Some(url) = first.get("url").and_then(|v| v.as_str()) {
+                return Ok(ImagePayload::Url(url.to_string()));
+            } else {
+                anyhow::bail!("Response contains no image data");
+            }
+        }
+        ImageGenerationProvider::Stability => {
+            if value.get("finish_reason").and_then(|v| v.as_str()) == Some("CONTENT_FILTERED") {
+                anyhow::bail!("The prompt was rejected by the provider's content filter");
+            }
+            value
+                .get("image")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Response contains no image data"))?
+        }
+        ImageGenerationProvider::SdWebui => value
+            .pointer("/images/0")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Response contains no images"))?,
+    };
+    // SD web UI may prefix a data URL header.
+    let b64 = b64.split_once("base64,").map_or(b64, |(_, data)| data);
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(b64.trim())
+        .map_err(|e| anyhow::anyhow!("Invalid base64 image data: {e}"))?;
+    Ok(ImagePayload::Bytes(bytes))
+}
+
+/// `<timestamp>-<prompt-slug>-<id>.png`
+fn image_file_name(prompt: &str) -> String {
+    let slug = prompt
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(6)
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug: String = slug.chars().take(40).collect();
+    let slug = slug.trim_end_matches('-');
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    if slug.is_empty() {
+        format!("{stamp}-{}.png", &id[..8])
+    } else {
+        format!("{stamp}-{slug}-{}.png", &id[..8])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_tool(workspace: &std::path::Path, config: ImageGenerationConfig) -> ImageGenTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        ImageGenTool::new(security, config)
+    }
+
+    #[test]
+    fn name_and_schema() {
+        let tool = test_tool(&std::env::temp_dir(), ImageGenerationConfig::default());
+        assert_eq!(tool.name(), "generate_image");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["prompt"].is_object());
+        assert!(schema["properties"]["size"]["description"]
+            .as_str()
+            .unwrap()
+            .contains("1024x1024"));
+        assert_eq!(schema["required"], json!(["prompt"]));
+    }
+
+    #[test]
+    fn size_parsing() {
+        assert_eq!(parse_size("1024x1024"), Some((1024, 1024)));
+        assert_eq!(parse_size(" 1792X1024 "), Some((1792, 1024)));
+        assert_eq!(parse_size("10x10"), None);
+        assert_eq!(parse_size("1024"), None);
+        assert_eq!(parse_size("axb"), None);
+    }
+
+    #[test]
+    fn aspect_ratio_snaps_to_supported_values() {
+        assert_eq!(closest_aspect_ratio(1024, 1024), "1:1");
+        assert_eq!(closest_aspect_ratio(1792, 1024), "16:9");
+        assert_eq!(closest_aspect_ratio(1024, 1792), "9:16");
+        assert_eq!(closest_aspect_ratio(1200, 800), "3:2");
+    }
+
+    #[test]
+    fn responses_are_parsed_per_provider() {
+        let png = base64::engine::general_purpose::STANDARD.encode(b"png-bytes");
+
+        let openai = json!({"data": [{"b64_json": png}]});
+        assert_eq!(
+            parse_image_response(ImageGenerationProvider::Openai, &openai).unwrap(),
+            ImagePayload::Bytes(b"png-bytes".to_vec())
+        );
+        let openai_url = json!({"data": [{"url": "https://img.example.com/a.png"}]});
+        assert_eq!(
+            parse_image_response(ImageGenerationProvider::Openai, &openai_url).unwrap(),
+            ImagePayload::Url("https://img.example.com/a.png".into())
+        );
+
+        let stability = json!({"image": png, "finish_reason": "SUCCESS"});
+        assert!(parse_image_response(ImageGenerationProvider::Stability, &stability).is_ok());
+        let filtered = json!({"image": "", "finish_reason": "CONTENT_FILTERED"});
+        assert!(
+            parse_image_response(ImageGenerationProvider::Stability, &filtered)
+                .unwrap_err()
+                .to_string()
+                .contains("content filter")
+        );
+
+        let sd = json!({"images": [format!("data:image/png;base64,{png}")]});
+        assert_eq!(
+            parse_image_response(ImageGenerationProvider::SdWebui, &sd).unwrap(),
+            ImagePayload::Bytes(b"png-bytes".to_vec())
+        );
+        assert!(parse_image_response(ImageGenerationProvider::SdWebui, &json!({})).is_err());
+    }
+
+    #[test]
+    fn file_names_are_slugged_and_unique() {
+        let a = image_file_name("A cat, wearing a tiny hat! In space");
+        let b = image_file_name("A cat, wearing a tiny hat! In space");
+        assert!(a.contains("-a-cat-wearing-a-tiny-hat-"));
+        assert!(a.ends_with(".png"));
+        assert_ne!(a, b);
+        assert!(image_file_name("???").ends_with(".png"));
+    }
+
+    #[test]
+    fn base_url_defaults_and_overrides() {
+        let mut config = ImageGenerationConfig::default();
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(
+            test_tool(tmp.path(), config.clone()).base_url(),
+            OPENAI_DEFAULT_URL
+        );
+        config.provider = ImageGenerationProvider::SdWebui;
+        assert_eq!(
+            test_tool(tmp.path(), config.clone()).base_url(),
+            SD_WEBUI_DEFAULT_URL
+        );
+        config.api_url = Some("http://gpu-box:7860/".into());
+        assert_eq!(
+            test_tool(tmp.path(), config).base_url(),
+            "http://gpu-box:7860"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_prompt_is_an_error() {
+        let tool = test_tool(&std::env::temp_dir(), ImageGenerationConfig::default());
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn invalid_size_is_rejected() {
+        let tool = test_tool(&std::env::temp_dir(), ImageGenerationConfig::default());
+        let result = tool
+            .execute(json!({"prompt": "a lighthouse", "size": "huge"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Invalid size"));
+    }
+
+    #[tokio::test]
+    async fn read_only_mode_blocks_generation() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = ImageGenTool::new(security, ImageGenerationConfig::default());
+        let result = tool
+            .execute(json!({"prompt": "a lighthouse"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn output_dir_outside_workspace_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let config = ImageGenerationConfig {
+            output_dir: "../escape".into(),
+            ..ImageGenerationConfig::default()
+        };
+        let tool = test_tool(tmp.path(), config);
+        let result = tool
+            .execute(json!({"prompt": "a lighthouse"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("output directory"));
+    }
+}
@@ -1,9 +1,8 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolResult, ToolRisk};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
-use std::path::Path;
 use std::sync::Arc;
 
 /// Maximum file size we will read and base64-encode (5 MB).
@@ -145,6 +144,10 @@ impl Tool for ImageInfoTool {
         })
     }
 
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let path_str = args
             .get("path")
@@ -156,20 +159,16 @@ impl Tool for ImageInfoTool {
             .and_then(serde_json::Value::as_bool)
             .unwrap_or(false);
 
-        let path = Path::new(path_str);
-
-        // Restrict reads to workspace directory to prevent arbitrary file exfiltration
-        if !self.security.is_path_allowed(path_str) {
+        let workspace = self.security.workspace();
+        if let Err(error) = workspace.check(path_str) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(format!(
-                    "Path not allowed: {path_str} (must be within workspace)"
-                )),
+                error: Some(error),
             });
         }
 
-        if !path.exists() {
+        if !workspace.root().join(path_str).exists() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -177,7 +176,19 @@ impl Tool for ImageInfoTool {
             });
         }
 
-        let metadata = tokio::fs::metadata(path)
+        // Resolve before reading so symlinks cannot point outside the workspace.
+        let path = match workspace.resolve(path_str).await {
+            Ok(path) => path,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+        };
+
+        let metadata = tokio::fs::metadata(&path)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to read file metadata: {e}"))?;
 
@@ -193,7 +204,7 @@ impl Tool for ImageInfoTool {
             });
         }
 
-        let bytes = tokio::fs::read(path)
+        let bytes = tokio::fs::read(&path)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to read image file: {e}"))?;
 
@@ -464,6 +475,41 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn execute_blocks_symlink_escape() {
+        use std::os::unix::fs::symlink;
+
+        let root = std::env::temp_dir().join("zeroclaw_image_info_symlink_escape");
+        let workspace = root.join("workspace");
+        let outside = root.join("outside");
+        let _ = tokio::fs::remove_dir_all(&root).await;
+        tokio::fs::create_dir_all(&workspace).await.unwrap();
+        tokio::fs::create_dir_all(&outside).await.unwrap();
+        tokio::fs::write(outside.join("secret.png"), b"\x89PNG\r\n\x1a\n")
+            .await
+            .unwrap();
+        symlink(outside.join("secret.png"), workspace.join("escape.png")).unwrap();
+
+        let tool = ImageInfoTool::new(Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        }));
+        let result = tool
+            .execute(json!({"path": "escape.png", "include_base64": true}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("escapes workspace"));
+
+        let _ = tokio::fs::remove_dir_all(&root).await;
+    }
+
     #[tokio::test]
     async fn execute_with_base64() {
         let dir = std::env::temp_dir().join("zeroclaw_image_info_b64");
@@ -0,0 +1,216 @@
+use super::traits::{Tool, ToolResult, ToolRisk};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+const MAX_ENTRIES: usize = 500;
+
+/// List a directory's entries with path sandboxing.
+pub struct ListDirTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl ListDirTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for ListDirTool {
+    fn name(&self) -> &str {
+        "list_dir"
+    }
+
+    fn description(&self) -> &str {
+        "List the entries of a directory (directories first, then files with sizes). \
+         Defaults to the workspace root."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory to list. Relative paths resolve from workspace; outside paths require policy allowlist. Default: '.'"
+                },
+                "show_hidden": {
+                    "type": "boolean",
+                    "description": "Include entries whose names start with '.' (default: false)"
+                }
+            }
+        })
+    }
+
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let show_hidden = args
+            .get("show_hidden")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        if let Err(error) = self.security.workspace().check(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let resolved_path = match self.security.workspace().resolve(path).await {
+            Ok(p) => p,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+        };
+
+        let mut reader = match tokio::fs::read_dir(&resolved_path).await {
+            Ok(reader) => reader,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to list directory: {e}")),
+                });
+            }
+        };
+
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+        while let Some(entry) = reader.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !show_hidden && name.starts_with('.') {
+                continue;
+            }
+            // symlink_metadata: report links as links rather than following them.
+            let Ok(meta) = tokio::fs::symlink_metadata(entry.path()).await else {
+                continue;
+            };
+            if meta.is_dir() {
+                dirs.push(format!("{name}/"));
+            } else if meta.file_type().is_symlink() {
+                files.push(format!("{name}@"));
+            } else {
+                files.push(format!("{name}  ({} bytes)", meta.len()));
+            }
+        }
+        dirs.sort();
+        files.sort();
+
+        let total = dirs.len() + files.len();
+        if total == 0 {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("{path} is empty"),
+                error: None,
+            });
+        }
+
+        let mut output = dirs
+            .into_iter()
+            .chain(files)
+            .take(MAX_ENTRIES)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if total > MAX_ENTRIES {
+            output.push_str(&format!(
+                "\n[{} more entries not shown]",
+                total - MAX_ENTRIES
+            ));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn list_dir_lists_dirs_then_files() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::create_dir(dir.path().join("src")).await.unwrap();
+        tokio::fs::write(dir.path().join("b.txt"), "12345")
+            .await
+            .unwrap();
+        tokio::fs::write(dir.path().join("a.txt"), "")
+            .await
+            .unwrap();
+        tokio::fs::write(dir.path().join(".hidden"), "")
+            .await
+            .unwrap();
+
+        let tool = ListDirTool::new(test_security(dir.path().to_path_buf()));
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "src/\na.txt  (0 bytes)\nb.txt  (5 bytes)");
+
+        let result = tool.execute(json!({"show_hidden": true})).await.unwrap();
+        assert!(result.output.contains(".hidden"));
+    }
+
+    #[tokio::test]
+    async fn list_dir_blocks_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = ListDirTool::new(test_security(dir.path().to_path_buf()));
+        let result = tool.execute(json!({"path": "../.."})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn list_dir_blocks_symlink_escape() {
+        let root = tempfile::tempdir().unwrap();
+        let workspace = root.path().join("workspace");
+        let outside = root.path().join("outside");
+        tokio::fs::create_dir_all(&workspace).await.unwrap();
+        tokio::fs::create_dir_all(&outside).await.unwrap();
+        std::os::unix::fs::symlink(&outside, workspace.join("escape")).unwrap();
+
+        let tool = ListDirTool::new(test_security(workspace));
+        let result = tool.execute(json!({"path": "escape"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("escapes workspace"));
+    }
+}
@@ -5,7 +5,7 @@
 //! which requires a name, description, JSON parameter schema, and an async
 //! `execute` method returning a structured [`ToolResult`].
 //!
-//! Tools are assembled into registries by [`default_tools`] (shell, file read/write)
+//! Tools are assembled into registries by [`default_tools`] (shell, file read/write/patch)
 //! and [`all_tools`] (full set including memory, browser, cron, HTTP, delegation,
 //! and optional integrations). Security policy enforcement is injected via
 //! [`SecurityPolicy`](crate::security::SecurityPolicy) at construction time.
@@ -15,9 +15,13 @@
 //! To add a new tool, implement [`Tool`] in a new submodule and register it in
 //! [`all_tools_with_runtime`]. See `AGENTS.md` §7.3 for the full change playbook.
 
+pub mod apply_patch;
 pub mod browser;
 pub mod browser_open;
+pub mod calendar;
 pub mod cli_discovery;
+pub mod clipboard_read;
+pub mod clipboard_write;
 pub mod composio;
 pub mod content_search;
 pub mod cron_add;
@@ -26,7 +30,9 @@ pub mod cron_remove;
 pub mod cron_run;
 pub mod cron_runs;
 pub mod cron_update;
+pub mod custom;
 pub mod delegate;
+pub mod file_changes;
 pub mod file_edit;
 pub mod file_read;
 pub mod file_write;
@@ -38,25 +44,39 @@ pub mod hardware_board_info;
 pub mod hardware_memory_map;
 #[cfg(feature = "hardware")]
 pub mod hardware_memory_read;
+pub mod html_extract;
 pub mod http_request;
+pub mod image_gen;
 pub mod image_info;
+pub mod list_dir;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
 pub mod model_routing_config;
+pub mod ocr;
 pub mod pdf_read;
 pub mod proxy_config;
 pub mod pushover;
+pub mod read_document;
+pub mod run_code;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
 pub mod shell;
+pub mod sql_query;
+pub mod task;
+pub mod toggles;
 pub mod traits;
+pub mod turn;
 pub mod web_fetch;
 pub mod web_search_tool;
 
+pub use apply_patch::ApplyPatchTool;
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
+pub use calendar::CalendarTool;
+pub use clipboard_read::ClipboardReadTool;
+pub use clipboard_write::ClipboardWriteTool;
 pub use composio::ComposioTool;
 pub use content_search::ContentSearchTool;
 pub use cron_add::CronAddTool;
@@ -65,6 +85,7 @@ pub use cron_remove::CronRemoveTool;
 pub use cron_run::CronRunTool;
 pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
+pub use custom::CustomTool;
 pub use delegate::DelegateTool;
 pub use file_edit::FileEditTool;
 pub use file_read::FileReadTool;
@@ -78,29 +99,36 @@ pub use hardware_memory_map::HardwareMemoryMapTool;
 #[cfg(feature = "hardware")]
 pub use hardware_memory_read::HardwareMemoryReadTool;
 pub use http_request::HttpRequestTool;
+pub use image_gen::ImageGenTool;
 pub use image_info::ImageInfoTool;
+pub use list_dir::ListDirTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
 pub use model_routing_config::ModelRoutingConfigTool;
+pub use ocr::OcrTool;
 pub use pdf_read::PdfReadTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
+pub use read_document::ReadDocumentTool;
+pub use run_code::RunCodeTool;
 pub use schedule::ScheduleTool;
 #[allow(unused_imports)]
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
 pub use shell::ShellTool;
+pub use sql_query::SqlQueryTool;
+pub use task::TaskTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
-pub use traits::{ToolResult, ToolSpec};
+pub use traits::{ToolResult, ToolRisk, ToolSpec};
 pub use web_fetch::WebFetchTool;
 pub use web_search_tool::WebSearchTool;
 
 use crate::config::{Config, DelegateAgentConfig};
 use crate::memory::Memory;
 use crate::runtime::{NativeRuntime, RuntimeAdapter};
-use crate::security::SecurityPolicy;
+use crate::security::{Role, SecurityPolicy};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -133,6 +161,18 @@ impl Tool for ArcDelegatingTool {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
     }
+
+    async fn dry_run(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        self.inner.dry_run(args).await
+    }
+
+    fn risk(&self) -> ToolRisk {
+        self.inner.risk()
+    }
+
+    fn min_role(&self) -> Role {
+        self.inner.min_role()
+    }
 }
 
 fn boxed_registry_from_arcs(tools: Vec<Arc<dyn Tool>>) -> Vec<Box<dyn Tool>> {
@@ -154,6 +194,8 @@ pub fn default_tools_with_runtime(
         Box::new(FileReadTool::new(security.clone())),
         Box::new(FileWriteTool::new(security.clone())),
         Box::new(FileEditTool::new(security.clone())),
+        Box::new(ApplyPatchTool::new(security.clone())),
+        Box::new(ListDirTool::new(security.clone())),
         Box::new(GlobSearchTool::new(security.clone())),
         Box::new(ContentSearchTool::new(security)),
     ]
@@ -209,11 +251,28 @@ pub fn all_tools_with_runtime(
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
+    let shell_sandbox =
+        crate::security::create_sandbox(&root_config.security, &security.workspace_dir);
+    // `Config::validate` rejects unknown `[runtime.tools]` kinds.
+    let runtime_for = |tool: &str| {
+        crate::runtime::runtime_for_tool(&root_config.runtime, tool, &runtime).unwrap_or_else(|e| {
+            tracing::warn!(
+                tool,
+                "Invalid [runtime.tools] entry, using the default runtime: {e}"
+            );
+            runtime.clone()
+        })
+    };
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
-        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(
+            ShellTool::new(security.clone(), runtime_for("shell"))
+                .with_sandbox(shell_sandbox.clone()),
+        ),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
         Arc::new(FileEditTool::new(security.clone())),
+        Arc::new(ApplyPatchTool::new(security.clone())),
+        Arc::new(ListDirTool::new(security.clone())),
         Arc::new(GlobSearchTool::new(security.clone())),
         Arc::new(ContentSearchTool::new(security.clone())),
         Arc::new(CronAddTool::new(config.clone(), security.clone())),
@@ -224,23 +283,27 @@ pub fn all_tools_with_runtime(
         Arc::new(CronRunsTool::new(config.clone())),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
-        Arc::new(MemoryForgetTool::new(memory, security.clone())),
+        Arc::new(MemoryForgetTool::new(memory.clone(), security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(ModelRoutingConfigTool::new(
             config.clone(),
             security.clone(),
         )),
         Arc::new(ProxyConfigTool::new(config.clone(), security.clone())),
-        Arc::new(GitOperationsTool::new(
-            security.clone(),
-            workspace_dir.to_path_buf(),
-        )),
+        Arc::new(
+            GitOperationsTool::new(security.clone(), workspace_dir.to_path_buf())
+                .with_repositories(root_config.git.repository_paths()),
+        ),
         Arc::new(PushoverTool::new(
             security.clone(),
             workspace_dir.to_path_buf(),
         )),
     ];
 
+    if root_config.tasks.enabled {
+        tool_arcs.push(Arc::new(TaskTool::new(config.clone(), security.clone())));
+    }
+
     if browser_config.enabled {
         // Add legacy browser_open tool for simple URL opening
         tool_arcs.push(Arc::new(BrowserOpenTool::new(
@@ -248,57 +311,122 @@ pub fn all_tools_with_runtime(
             browser_config.allowed_domains.clone(),
         )));
         // Add full browser automation tool (pluggable backend)
-        tool_arcs.push(Arc::new(BrowserTool::new_with_backend(
+        tool_arcs.push(Arc::new(
+            BrowserTool::new_with_backend(
+                security.clone(),
+                browser_config.allowed_domains.clone(),
+                browser_config.session_name.clone(),
+                browser_config.backend.clone(),
+                browser_config.native_headless,
+                browser_config.native_webdriver_url.clone(),
+                browser_config.native_chrome_path.clone(),
+                ComputerUseConfig {
+                    endpoint: browser_config.computer_use.endpoint.clone(),
+                    api_key: browser_config.computer_use.api_key.clone(),
+                    timeout_ms: browser_config.computer_use.timeout_ms,
+                    allow_remote_endpoint: browser_config.computer_use.allow_remote_endpoint,
+                    window_allowlist: browser_config.computer_use.window_allowlist.clone(),
+                    max_coordinate_x: browser_config.computer_use.max_coordinate_x,
+                    max_coordinate_y: browser_config.computer_use.max_coordinate_y,
+                },
+            )
+            .with_max_actions_per_turn(browser_config.max_actions_per_turn),
+        ));
+    }
+
+    if http_config.enabled {
+        tool_arcs.push(Arc::new(
+            HttpRequestTool::new(
+                security.clone(),
+                http_config.allowed_domains.clone(),
+                http_config.max_response_size,
+                http_config.timeout_secs,
+            )
+            .with_secrets(http_config.secrets.clone()),
+        ));
+    }
+
+    if web_fetch_config.enabled {
+        tool_arcs.push(Arc::new(
+            WebFetchTool::new(
+                security.clone(),
+                web_fetch_config.allowed_domains.clone(),
+                web_fetch_config.blocked_domains.clone(),
+                web_fetch_config.max_response_size,
+                web_fetch_config.timeout_secs,
+            )
+            .with_max_output_tokens(web_fetch_config.max_output_tokens),
+        ));
+    }
+
+    if root_config.code_interpreter.enabled {
+        tool_arcs.push(Arc::new(
+            RunCodeTool::new(security.clone(), root_config.code_interpreter.clone())
+                .with_sandbox(shell_sandbox.clone())
+                .with_runtime(runtime_for("run_code")),
+        ));
+    }
+
+    if root_config.sql.enabled {
+        tool_arcs.push(Arc::new(SqlQueryTool::new(
             security.clone(),
-            browser_config.allowed_domains.clone(),
-            browser_config.session_name.clone(),
-            browser_config.backend.clone(),
-            browser_config.native_headless,
-            browser_config.native_webdriver_url.clone(),
-            browser_config.native_chrome_path.clone(),
-            ComputerUseConfig {
-                endpoint: browser_config.computer_use.endpoint.clone(),
-                api_key: browser_config.computer_use.api_key.clone(),
-                timeout_ms: browser_config.computer_use.timeout_ms,
-                allow_remote_endpoint: browser_config.computer_use.allow_remote_endpoint,
-                window_allowlist: browser_config.computer_use.window_allowlist.clone(),
-                max_coordinate_x: browser_config.computer_use.max_coordinate_x,
-                max_coordinate_y: browser_config.computer_use.max_coordinate_y,
-            },
+            root_config.sql.clone(),
         )));
     }
 
-    if http_config.enabled {
-        tool_arcs.push(Arc::new(HttpRequestTool::new(
+    if root_config.multimodal.ocr.enabled {
+        tool_arcs.push(Arc::new(OcrTool::new(
             security.clone(),
-            http_config.allowed_domains.clone(),
-            http_config.max_response_size,
-            http_config.timeout_secs,
+            root_config.multimodal.clone(),
         )));
     }
 
-    if web_fetch_config.enabled {
-        tool_arcs.push(Arc::new(WebFetchTool::new(
+    if root_config.clipboard.enabled {
+        tool_arcs.push(Arc::new(ClipboardReadTool::new(
+            security.clone(),
+            root_config.clipboard.clone(),
+        )));
+        tool_arcs.push(Arc::new(ClipboardWriteTool::new(
+            security.clone(),
+            root_config.clipboard.clone(),
+        )));
+    }
+
+    if root_config.image_generation.enabled {
+        tool_arcs.push(Arc::new(ImageGenTool::new(
+            security.clone(),
+            root_config.image_generation.clone(),
+        )));
+    }
+
+    if root_config.calendar.enabled {
+        tool_arcs.push(Arc::new(CalendarTool::new(
             security.clone(),
-            web_fetch_config.allowed_domains.clone(),
-            web_fetch_config.blocked_domains.clone(),
-            web_fetch_config.max_response_size,
-            web_fetch_config.timeout_secs,
+            root_config.calendar.clone(),
         )));
     }
 
     // Web search tool (enabled by default for GLM and other models)
     if root_config.web_search.enabled {
-        tool_arcs.push(Arc::new(WebSearchTool::new(
-            root_config.web_search.provider.clone(),
-            root_config.web_search.brave_api_key.clone(),
-            root_config.web_search.max_results,
-            root_config.web_search.timeout_secs,
-        )));
+        tool_arcs.push(Arc::new(
+            WebSearchTool::new(
+                root_config.web_search.provider.clone(),
+                root_config.web_search.brave_api_key.clone(),
+                root_config.web_search.max_results,
+                root_config.web_search.timeout_secs,
+            )
+            .with_searxng_url(root_config.web_search.searxng_url.clone()),
+        ));
     }
 
     // PDF extraction (feature-gated at compile time via rag-pdf)
     tool_arcs.push(Arc::new(PdfReadTool::new(security.clone())));
+    // PDF/DOCX/EPUB ingestion (rag-pdf / rag-documents), optionally indexed into memory
+    tool_arcs.push(Arc::new(ReadDocumentTool::new(
+        security.clone(),
+        memory,
+        root_config.documents.clone(),
+    )));
 
     // Vision tools are always available
     tool_arcs.push(Arc::new(ScreenshotTool::new(security.clone())));
@@ -314,6 +442,38 @@ pub fn all_tools_with_runtime(
         }
     }
 
+    // Config-defined tools may not shadow built-in tools
+    for custom in &root_config.custom_tools {
+        if tool_arcs.iter().any(|tool| tool.name() == custom.name) {
+            tracing::warn!(
+                tool = %custom.name,
+                "Custom tool name collides with a built-in tool; skipping"
+            );
+            continue;
+        }
+        tool_arcs.push(Arc::new(
+            CustomTool::new(
+                custom.clone(),
+                security.clone(),
+                runtime_for(&custom.name),
+                shell_sandbox.clone(),
+            )
+            .with_allowed_domains(http_config.allowed_domains.clone()),
+        ));
+    }
+
+    // WASM plugins may not shadow built-in tools
+    if root_config.plugins.enabled {
+        let reserved: Vec<&str> = tool_arcs.iter().map(|tool| tool.name()).collect();
+        let plugin_tools = crate::plugins::load_plugin_tools(
+            &root_config.plugins,
+            security,
+            workspace_dir,
+            &reserved,
+        );
+        tool_arcs.extend(plugin_tools);
+    }
+
     // Add delegation tool when agents are configured
     if !agents.is_empty() {
         let delegate_agents: HashMap<String, DelegateAgentConfig> = agents
@@ -341,7 +501,8 @@ pub fn all_tools_with_runtime(
             },
         )
         .with_parent_tools(parent_tools)
-        .with_multimodal_config(root_config.multimodal.clone());
+        .with_multimodal_config(root_config.multimodal.clone())
+        .with_tool_output_config(root_config.agent.tool_output.clone());
         tool_arcs.push(Arc::new(delegate_tool));
     }
 
@@ -366,7 +527,7 @@ mod tests {
     fn default_tools_has_expected_count() {
         let security = Arc::new(SecurityPolicy::default());
         let tools = default_tools(security);
-        assert_eq!(tools.len(), 6);
+        assert_eq!(tools.len(), 8);
     }
 
     #[test]
@@ -462,6 +623,8 @@ mod tests {
         assert!(names.contains(&"file_read"));
         assert!(names.contains(&"file_write"));
         assert!(names.contains(&"file_edit"));
+        assert!(names.contains(&"apply_patch"));
+        assert!(names.contains(&"list_dir"));
         assert!(names.contains(&"glob_search"));
         assert!(names.contains(&"content_search"));
     }
@@ -578,6 +741,9 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: 0,
             },
         );
 
@@ -10,6 +10,7 @@ use std::sync::Arc;
 
 const DEFAULT_AGENT_MAX_DEPTH: u32 = 3;
 const DEFAULT_AGENT_MAX_ITERATIONS: usize = 10;
+const DEFAULT_AGENT_MAX_RESULT_CHARS: usize = 4000;
 
 pub struct ModelRoutingConfigTool {
     config: Arc<Config>,
@@ -201,6 +202,21 @@ impl ModelRoutingConfigTool {
         Ok(Some(value))
     }
 
+    fn parse_optional_bool_update(args: &Value, field: &str) -> anyhow::Result<MaybeSet<bool>> {
+        let Some(raw) = args.get(field) else {
+            return Ok(MaybeSet::Unset);
+        };
+
+        if raw.is_null() {
+            return Ok(MaybeSet::Null);
+        }
+
+        let value = raw
+            .as_bool()
+            .ok_or_else(|| anyhow::anyhow!("'{field}' must be a boolean or null"))?;
+        Ok(MaybeSet::Set(value))
+    }
+
     fn scenario_row(route: &ModelRouteConfig, rule: Option<&ClassificationRule>) -> Value {
         let classification = rule.map(|r| {
             json!({
@@ -208,6 +224,9 @@ impl ModelRoutingConfigTool {
                 "patterns": r.patterns,
                 "min_length": r.min_length,
                 "max_length": r.max_length,
+                "code": r.code,
+                "min_tool_calls": r.min_tool_calls,
+                "hours": r.hours,
                 "priority": r.priority,
             })
         });
@@ -251,6 +270,9 @@ impl ModelRoutingConfigTool {
                     "patterns": rule.patterns,
                     "min_length": rule.min_length,
                     "max_length": rule.max_length,
+                    "code": rule.code,
+                    "min_tool_calls": rule.min_tool_calls,
+                    "hours": rule.hours,
                     "priority": rule.priority,
                 })
             })
@@ -312,6 +334,9 @@ impl ModelRoutingConfigTool {
             || !rule.patterns.is_empty()
             || rule.min_length.is_some()
             || rule.max_length.is_some()
+            || rule.code.is_some()
+            || rule.min_tool_calls.is_some()
+            || rule.hours.is_some()
     }
 
     fn ensure_rule_defaults(rule: &mut ClassificationRule, hint: &str) {
@@ -444,6 +469,14 @@ impl ModelRoutingConfigTool {
         };
         let min_length_update = Self::parse_optional_usize_update(args, "min_length")?;
         let max_length_update = Self::parse_optional_usize_update(args, "max_length")?;
+        let code_update = Self::parse_optional_bool_update(args, "code")?;
+        let min_tool_calls_update = Self::parse_optional_usize_update(args, "min_tool_calls")?;
+        let hours_update = Self::parse_optional_string_update(args, "hours")?;
+        if let MaybeSet::Set(hours) = &hours_update {
+            if crate::agent::classifier::parse_hours(hours).is_none() {
+                anyhow::bail!("'hours' must look like \"22-7\" or \"22:30-07:00\"");
+            }
+        }
         let priority_update = Self::parse_optional_i32_update(args, "priority")?;
         let classification_enabled = Self::parse_optional_bool(args, "classification_enabled")?;
 
@@ -452,6 +485,9 @@ impl ModelRoutingConfigTool {
             || patterns_update.is_some()
             || !matches!(min_length_update, MaybeSet::Unset)
             || !matches!(max_length_update, MaybeSet::Unset)
+            || !matches!(code_update, MaybeSet::Unset)
+            || !matches!(min_tool_calls_update, MaybeSet::Unset)
+            || !matches!(hours_update, MaybeSet::Unset)
             || !matches!(priority_update, MaybeSet::Unset);
 
         let mut cfg = self.load_config_without_env()?;
@@ -520,6 +556,24 @@ impl ModelRoutingConfigTool {
                     MaybeSet::Unset => {}
                 }
 
+                match code_update {
+                    MaybeSet::Set(value) => next_rule.code = Some(value),
+                    MaybeSet::Null => next_rule.code = None,
+                    MaybeSet::Unset => {}
+                }
+
+                match min_tool_calls_update {
+                    MaybeSet::Set(value) => next_rule.min_tool_calls = Some(value),
+                    MaybeSet::Null => next_rule.min_tool_calls = None,
+                    MaybeSet::Unset => {}
+                }
+
+                match hours_update {
+                    MaybeSet::Set(value) => next_rule.hours = Some(value),
+                    MaybeSet::Null => next_rule.hours = None,
+                    MaybeSet::Unset => {}
+                }
+
                 match priority_update {
                     MaybeSet::Set(value) => next_rule.priority = value,
                     MaybeSet::Null => next_rule.priority = 0,
@@ -532,7 +586,7 @@ impl ModelRoutingConfigTool {
 
                 if !Self::has_rule_matcher(&next_rule) {
                     anyhow::bail!(
-                        "Classification rule for hint '{hint}' has no matching criteria. Provide keywords/patterns or set min_length/max_length."
+                        "Classification rule for hint '{hint}' has no matching criteria. Provide keywords/patterns or set min_length/max_length/code/min_tool_calls/hours."
                     );
                 }
 
@@ -638,6 +692,9 @@ impl ModelRoutingConfigTool {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: DEFAULT_AGENT_MAX_ITERATIONS,
+                memory_scope: None,
+                timeout_secs: None,
+                max_result_chars: DEFAULT_AGENT_MAX_RESULT_CHARS,
             });
 
         next_agent.provider = provider;
@@ -807,6 +864,19 @@ impl Tool for ModelRoutingConfigTool {
                     "minimum": 0,
                     "description": "Optional maximum message length matcher"
                 },
+                "code": {
+                    "type": ["boolean", "null"],
+                    "description": "Optional matcher: message contains code (true) or not (false)"
+                },
+                "min_tool_calls": {
+                    "type": ["integer", "null"],
+                    "minimum": 0,
+                    "description": "Optional matcher: tool calls already made in the turn"
+                },
+                "hours": {
+                    "type": ["string", "null"],
+                    "description": "Optional local-time window matcher, e.g. \"22-7\" (cheap mode hours)"
+                },
                 "priority": {
                     "type": ["integer", "null"],
                     "description": "Classification priority (higher runs first)"
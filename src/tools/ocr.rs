@@ -0,0 +1,168 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::MultimodalConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Extract text from screenshots, scans and photos in the workspace.
+///
+/// Uses the tesseract CLI, falling back to the configured vision provider
+/// (`[multimodal.ocr]`) when tesseract is unavailable.
+pub struct OcrTool {
+    security: Arc<SecurityPolicy>,
+    config: MultimodalConfig,
+}
+
+impl OcrTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: MultimodalConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn error(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for OcrTool {
+    fn name(&self) -> &str {
+        "ocr"
+    }
+
+    fn description(&self) -> &str {
+        "Extract text from an image file (screenshot, scanned page, photo of a document) \
+         in the workspace using OCR. Supports PNG, JPEG, WebP, GIF and BMP."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the image file. Relative paths resolve from workspace; outside paths require policy allowlist."
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+
+        if self.security.is_rate_limited() {
+            return Ok(Self::error(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        if let Err(error) = self.security.workspace().check(path) {
+            return Ok(Self::error(error));
+        }
+
+        // Record action before canonicalization so path-probing still consumes budget.
+        if !self.security.record_action() {
+            return Ok(Self::error("Rate limit exceeded: action budget exhausted"));
+        }
+
+        let resolved_path = match self.security.workspace().resolve(path).await {
+            Ok(p) => p,
+            Err(error) => return Ok(Self::error(error)),
+        };
+
+        let reference = resolved_path.to_string_lossy();
+        match crate::multimodal::ocr_image(&reference, &self.config).await {
+            // Agent dispatchers forward `error` only when `success=false`;
+            // keep "no text" as a successful, explicit result.
+            Ok(text) if text.is_empty() => Ok(ToolResult {
+                success: true,
+                output: "No recognizable text found in the image".into(),
+                error: None,
+            }),
+            Ok(text) => Ok(ToolResult {
+                success: true,
+                output: text,
+                error: None,
+            }),
+            Err(e) => Ok(Self::error(format!("OCR failed: {e}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_tool(workspace: &std::path::Path, tesseract_command: &str) -> OcrTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let mut config = MultimodalConfig::default();
+        config.ocr.tesseract_command = tesseract_command.into();
+        OcrTool::new(security, config)
+    }
+
+    #[test]
+    fn name_and_schema() {
+        let tool = test_tool(&std::env::temp_dir(), "tesseract");
+        assert_eq!(tool.name(), "ocr");
+        assert_eq!(tool.parameters_schema()["required"], json!(["path"]));
+    }
+
+    #[tokio::test]
+    async fn path_traversal_is_blocked() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(tmp.path(), "tesseract");
+        let result = tool
+            .execute(json!({"path": "../../etc/shot.png"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn missing_tesseract_without_vision_fallback_is_reported() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("shot.png"), b"\x89PNG\r\n\x1a\nrest")
+            .await
+            .unwrap();
+        let tool = test_tool(tmp.path(), "zeroclaw-missing-tesseract");
+        let result = tool.execute(json!({"path": "shot.png"})).await.unwrap();
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.contains("OCR failed"));
+        assert!(error.contains("vision_provider"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn returns_tesseract_output() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = TempDir::new().unwrap();
+        let script = tmp.path().join("fake-tesseract");
+        std::fs::write(&script, "#!/bin/sh\ncat > /dev/null\necho 'Total: 19.99'\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        tokio::fs::write(tmp.path().join("receipt.jpg"), b"\xff\xd8\xffjpeg")
+            .await
+            .unwrap();
+
+        let tool = test_tool(tmp.path(), script.to_str().unwrap());
+        let result = tool.execute(json!({"path": "receipt.jpg"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "Total: 19.99");
+    }
+}
@@ -0,0 +1,839 @@
+use super::traits::{Tool, ToolResult, ToolRisk};
+use crate::config::DocumentToolConfig;
+use crate::memory::chunker::chunk_markdown;
+use crate::memory::{Memory, MemoryCategory};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Document formats understood by `read_document`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentKind {
+    Pdf,
+    Docx,
+    Epub,
+}
+
+impl DocumentKind {
+    fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "pdf" => Some(Self::Pdf),
+            "docx" => Some(Self::Docx),
+            "epub" => Some(Self::Epub),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Pdf => "PDF",
+            Self::Docx => "DOCX",
+            Self::Epub => "EPUB",
+        }
+    }
+
+    /// What one addressable unit is called: PDFs and DOCX files have pages,
+    /// EPUBs have spine sections (chapters).
+    fn unit(self) -> &'static str {
+        match self {
+            Self::Pdf | Self::Docx => "page",
+            Self::Epub => "section",
+        }
+    }
+}
+
+/// Extract text from PDF, DOCX and EPUB files, chunked to fit the context
+/// window, and optionally index the chunks into memory.
+///
+/// PDF support requires the `rag-pdf` feature; DOCX and EPUB require
+/// `rag-documents`. The tool is always registered so the LLM gets an
+/// actionable error instead of a missing tool.
+pub struct ReadDocumentTool {
+    security: Arc<SecurityPolicy>,
+    memory: Arc<dyn Memory>,
+    config: DocumentToolConfig,
+}
+
+impl ReadDocumentTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        memory: Arc<dyn Memory>,
+        config: DocumentToolConfig,
+    ) -> Self {
+        Self {
+            security,
+            memory,
+            config,
+        }
+    }
+
+    fn error(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ReadDocumentTool {
+    fn name(&self) -> &str {
+        "read_document"
+    }
+
+    fn description(&self) -> &str {
+        "Extract text from a PDF, DOCX, or EPUB file in the workspace. \
+         Select pages (PDF/DOCX) or sections (EPUB) with 'pages', e.g. \"3-7\" or \"1,4-6\". \
+         Long documents are split into chunks; request further chunks with 'chunk'. \
+         Set 'index' to store every chunk in memory for later memory_recall."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the document. Relative paths resolve from workspace; outside paths require policy allowlist."
+                },
+                "pages": {
+                    "type": "string",
+                    "description": "Page/section selection, 1-based: \"5\", \"3-7\", \"10-\" or \"1,4-6\". Defaults to the whole document."
+                },
+                "chunk": {
+                    "type": "integer",
+                    "description": "Which chunk of the selected text to return (1-based, default: 1)",
+                    "minimum": 1
+                },
+                "index": {
+                    "type": "boolean",
+                    "description": "Also store all chunks of the selection in memory (default: false)"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let pages_spec = args.get("pages").and_then(|v| v.as_str());
+        let chunk_number = args
+            .get("chunk")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(1, |n| usize::try_from(n).unwrap_or(usize::MAX).max(1));
+        let index = args
+            .get("index")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        let Some(kind) = DocumentKind::from_path(Path::new(path)) else {
+            return Ok(Self::error(format!(
+                "Unsupported document type: {path} (supported: .pdf, .docx, .epub)"
+            )));
+        };
+
+        if index {
+            if let Err(error) = self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, "read_document")
+            {
+                return Ok(Self::error(error));
+            }
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(Self::error(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        if let Err(error) = self.security.workspace().check(path) {
+            return Ok(Self::error(error));
+        }
+
+        // Record action before canonicalization so path-probing still consumes budget.
+        if !self.security.record_action() {
+            return Ok(Self::error("Rate limit exceeded: action budget exhausted"));
+        }
+
+        let resolved_path = match self.security.workspace().resolve(path).await {
+            Ok(p) => p,
+            Err(error) => return Ok(Self::error(error)),
+        };
+
+        let max_bytes = self.config.max_file_mb.saturating_mul(1024 * 1024);
+        match tokio::fs::metadata(&resolved_path).await {
+            Ok(meta) if meta.len() > max_bytes => {
+                return Ok(Self::error(format!(
+                    "Document too large: {} bytes (limit: {max_bytes} bytes)",
+                    meta.len()
+                )));
+            }
+            Ok(_) => {}
+            Err(e) => return Ok(Self::error(format!("Failed to read file metadata: {e}"))),
+        }
+
+        let bytes = match tokio::fs::read(&resolved_path).await {
+            Ok(b) => b,
+            Err(e) => return Ok(Self::error(format!("Failed to read document: {e}"))),
+        };
+
+        tracing::debug!(
+            "Reading {} document: {}",
+            kind.label(),
+            resolved_path.display()
+        );
+
+        // Extraction is blocking and CPU-bound; keep it off the async executor.
+        let pages = match tokio::task::spawn_blocking(move || extract_units(kind, &bytes)).await {
+            Ok(Ok(pages)) => pages,
+            Ok(Err(e)) => return Ok(Self::error(e.to_string())),
+            Err(e) => {
+                return Ok(Self::error(format!(
+                    "Document extraction task panicked: {e}"
+                )))
+            }
+        };
+
+        if pages.iter().all(|p| p.trim().is_empty()) {
+            return Ok(ToolResult {
+                success: true,
+                // Agent dispatchers forward `error` only when `success=false`;
+                // surface the warning in `output` instead.
+                output: format!(
+                    "{} contains no extractable text (may be image-only or encrypted)",
+                    kind.label()
+                ),
+                error: None,
+            });
+        }
+
+        let selected = match pages_spec {
+            Some(spec) => match parse_page_selection(spec, pages.len()) {
+                Ok(selected) => selected,
+                Err(e) => {
+                    return Ok(Self::error(format!(
+                        "{e} ({} {}s)",
+                        pages.len(),
+                        kind.unit()
+                    )))
+                }
+            },
+            None => (1..=pages.len()).collect(),
+        };
+
+        let text = join_units(&pages, &selected, kind.unit());
+        let chunks = split_into_chunks(&text, self.config.chunk_tokens);
+        let total_chunks = chunks.len();
+        if chunk_number > total_chunks {
+            return Ok(Self::error(format!(
+                "Chunk {chunk_number} out of range: selection has {total_chunks} chunk(s)"
+            )));
+        }
+
+        let selection_label =
+            pages_spec.map_or_else(|| "all".to_string(), |s| s.trim().to_string());
+        let mut output = format!(
+            "Document: {path} ({}, {} {}s; {}s: {selection_label})\n",
+            kind.label(),
+            pages.len(),
+            kind.unit(),
+            kind.unit()
+        );
+
+        if index {
+            let category = MemoryCategory::Custom(self.config.memory_category.clone());
+            for (i, chunk) in chunks.iter().enumerate() {
+                let key = format!("document:{path}#{selection_label}:{}", i + 1);
+                let content = format!("[{path}, {}s {selection_label}]\n{chunk}", kind.unit());
+                if let Err(e) = self
+                    .memory
+                    .store(&key, &content, category.clone(), None)
+                    .await
+                {
+                    return Ok(Self::error(format!("Failed to index chunk {}: {e}", i + 1)));
+                }
+            }
+            let _ = writeln!(
+                output,
+                "Indexed {total_chunks} chunk(s) into memory category '{}'.",
+                self.config.memory_category
+            );
+        }
+
+        let _ = write!(output, "Chunk {chunk_number} of {total_chunks}");
+        if chunk_number < total_chunks {
+            let _ = write!(
+                output,
+                " (call again with chunk={} to continue)",
+                chunk_number + 1
+            );
+        }
+        let _ = write!(output, "\n\n{}", chunks[chunk_number - 1]);
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+/// Extract the text of every page/section of a document.
+fn extract_units(kind: DocumentKind, bytes: &[u8]) -> anyhow::Result<Vec<String>> {
+    match kind {
+        DocumentKind::Pdf => extract_pdf(bytes),
+        DocumentKind::Docx | DocumentKind::Epub => extract_zipped(kind, bytes),
+    }
+}
+
+#[cfg(feature = "rag-pdf")]
+fn extract_pdf(bytes: &[u8]) -> anyhow::Result<Vec<String>> {
+    pdf_extract::extract_text_from_mem_by_pages(bytes)
+        .map_err(|e| anyhow::anyhow!("PDF extraction failed: {e}"))
+}
+
+#[cfg(not(feature = "rag-pdf"))]
+fn extract_pdf(_bytes: &[u8]) -> anyhow::Result<Vec<String>> {
+    anyhow::bail!("PDF extraction is not enabled. Rebuild with: cargo build --features rag-pdf")
+}
+
+#[cfg(feature = "rag-documents")]
+fn extract_zipped(kind: DocumentKind, bytes: &[u8]) -> anyhow::Result<Vec<String>> {
+    match kind {
+        DocumentKind::Docx => office::docx_pages(bytes),
+        _ => office::epub_sections(bytes),
+    }
+}
+
+#[cfg(not(feature = "rag-documents"))]
+fn extract_zipped(kind: DocumentKind, _bytes: &[u8]) -> anyhow::Result<Vec<String>> {
+    anyhow::bail!(
+        "{} extraction is not enabled. Rebuild with: cargo build --features rag-documents",
+        kind.label()
+    )
+}
+
+/// Parse a 1-based page selection like `"1,4-6,10-"` into sorted, unique
+/// page numbers.
+fn parse_page_selection(spec: &str, total: usize) -> anyhow::Result<Vec<usize>> {
+    let mut selected = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => {
+                let start = if start.trim().is_empty() {
+                    1
+                } else {
+                    parse_page_number(start)?
+                };
+                let end = if end.trim().is_empty() {
+                    total
+                } else {
+                    parse_page_number(end)?
+                };
+                (start, end)
+            }
+            None => {
+                let page = parse_page_number(part)?;
+                (page, page)
+            }
+        };
+        if start > end {
+            anyhow::bail!("Invalid page range '{part}'");
+        }
+        if end > total {
+            anyhow::bail!("Page range '{part}' is out of bounds");
+        }
+        selected.extend(start..=end);
+    }
+    if selected.is_empty() {
+        anyhow::bail!("Empty page selection '{spec}'");
+    }
+    selected.sort_unstable();
+    selected.dedup();
+    Ok(selected)
+}
+
+fn parse_page_number(raw: &str) -> anyhow::Result<usize> {
+    match raw.trim().parse::<usize>() {
+        Ok(0) | Err(_) => anyhow::bail!("Invalid page number '{}'", raw.trim()),
+        Ok(n) => Ok(n),
+    }
+}
+
+/// Join the selected units, each introduced by a `--- Page N ---` marker so
+/// chunks stay attributable.
+fn join_units(pages: &[String], selected: &[usize], unit: &str) -> String {
+    let mut text = String::new();
+    let mut unit_title: String = unit.to_string();
+    if let Some(first) = unit_title.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    for &number in selected {
+        let body = pages[number - 1].trim();
+        if body.is_empty() {
+            continue;
+        }
+        let _ = write!(text, "--- {unit_title} {number} ---\n\n{body}\n\n");
+    }
+    text
+}
+
+/// Split text into chunks of roughly `chunk_tokens` tokens, breaking on
+/// headings and paragraphs where possible.
+///
+/// The markdown chunker splits at every heading, so small neighbouring
+/// pieces are packed back together up to the budget.
+fn split_into_chunks(text: &str, chunk_tokens: usize) -> Vec<String> {
+    let max_chars = chunk_tokens.max(1).saturating_mul(4);
+    let mut chunks: Vec<String> = Vec::new();
+    for piece in chunk_markdown(text, chunk_tokens.max(1)) {
+        if piece.content.is_empty() {
+            continue;
+        }
+        match chunks.last_mut() {
+            Some(last) if last.len() + 2 + piece.content.len() <= max_chars => {
+                last.push_str("\n\n");
+                last.push_str(&piece.content);
+            }
+            _ => chunks.push(piece.content),
+        }
+    }
+    if chunks.is_empty() {
+        chunks.push(text.trim().to_string());
+    }
+    chunks
+}
+
+/// DOCX and EPUB are ZIP containers of XML/XHTML parts.
+#[cfg(feature = "rag-documents")]
+mod office {
+    use crate::tools::html_extract::{self, decode_entities, parse_attrs};
+    use std::io::{Cursor, Read};
+
+    /// Upper bound for a single decompressed part, to defuse zip bombs.
+    const MAX_PART_BYTES: u64 = 64 * 1024 * 1024;
+
+    type Archive<'a> = zip::ZipArchive<Cursor<&'a [u8]>>;
+
+    fn open(bytes: &[u8]) -> anyhow::Result<Archive<'_>> {
+        zip::ZipArchive::new(Cursor::new(bytes))
+            .map_err(|e| anyhow::anyhow!("Not a valid DOCX/EPUB archive: {e}"))
+    }
+
+    fn read_part(archive: &mut Archive<'_>, name: &str) -> anyhow::Result<String> {
+        let part = archive
+            .by_name(name)
+            .map_err(|e| anyhow::anyhow!("Missing archive entry '{name}': {e}"))?;
+        let mut raw = Vec::new();
+        part.take(MAX_PART_BYTES).read_to_end(&mut raw)?;
+        Ok(String::from_utf8_lossy(&raw).into_owned())
+    }
+
+    /// Visit every tag in an XML document in order, together with the text
+    /// preceding it.
+    fn for_each_tag(xml: &str, mut visit: impl FnMut(&str, &str)) {
+        let mut rest = xml;
+        while let Some(lt) = rest.find('<') {
+            let text = &rest[..lt];
+            let Some(gt) = rest[lt..].find('>') else {
+                break;
+            };
+            visit(text, &rest[lt + 1..lt + gt]);
+            rest = &rest[lt + gt + 1..];
+        }
+    }
+
+    fn tag_name(tag: &str) -> &str {
+        tag.trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+    }
+
+    fn attr(tag: &str, name: &str) -> Option<String> {
+        let body = tag.trim_end_matches('/');
+        let attrs = body
+            .find(char::is_whitespace)
+            .map_or("", |start| &body[start..]);
+        parse_attrs(attrs)
+            .into_iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Text of `word/document.xml`, split at page breaks (explicit breaks and
+    /// the breaks Word recorded when the file was last rendered).
+    pub(super) fn docx_pages(bytes: &[u8]) -> anyhow::Result<Vec<String>> {
+        let mut archive = open(bytes)?;
+        let xml = read_part(&mut archive, "word/document.xml")?;
+        Ok(docx_pages_from_xml(&xml))
+    }
+
+    pub(super) fn docx_pages_from_xml(xml: &str) -> Vec<String> {
+        let mut pages = vec![String::new()];
+        let mut in_text = false;
+        for_each_tag(xml, |text, tag| {
+            let page = pages.last_mut().expect("at least one page");
+            if in_text {
+                page.push_str(&decode_entities(text));
+            }
+            let closing = tag.starts_with('/');
+            let self_closing = tag.ends_with('/');
+            let mut page_break = false;
+            match (closing, tag_name(tag)) {
+                (false, "w:t") => in_text = !self_closing,
+                (true, "w:t") => in_text = false,
+                (false, "w:tab") => page.push('\t'),
+                (false, "w:br") if attr(tag, "w:type").as_deref() == Some("page") => {
+                    page_break = true;
+                }
+                (false, "w:br" | "w:cr") => page.push('\n'),
+                (false, "w:lastRenderedPageBreak") => page_break = true,
+                (true, "w:p") => page.push_str("\n\n"),
+                _ => {}
+            }
+            if page_break && !page.trim().is_empty() {
+                pages.push(String::new());
+            }
+        });
+        pages.into_iter().map(|p| p.trim().to_string()).collect()
+    }
+
+    /// Text of every spine item of an EPUB, in reading order.
+    pub(super) fn epub_sections(bytes: &[u8]) -> anyhow::Result<Vec<String>> {
+        let mut archive = open(bytes)?;
+        let container = read_part(&mut archive, "META-INF/container.xml")?;
+        let mut opf_path = None;
+        for_each_tag(&container, |_, tag| {
+            if opf_path.is_none() && tag_name(tag) == "rootfile" {
+                opf_path = attr(tag, "full-path");
+            }
+        });
+        let opf_path = opf_path.ok_or_else(|| anyhow::anyhow!("EPUB container has no rootfile"))?;
+        let opf = read_part(&mut archive, &opf_path)?;
+        let base_dir = opf_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+
+        let mut sections = Vec::new();
+        for href in spine_hrefs(&opf) {
+            let name = resolve_href(base_dir, &href);
+            let Ok(xhtml) = read_part(&mut archive, &name) else {
+                continue;
+            };
+            let extracted = html_extract::extract(&xhtml, "", false);
+            sections.push(extracted.markdown.trim().to_string());
+        }
+        if sections.is_empty() {
+            anyhow::bail!("EPUB spine references no readable sections");
+        }
+        Ok(sections)
+    }
+
+    /// Manifest hrefs of the spine items, in spine order.
+    pub(super) fn spine_hrefs(opf: &str) -> Vec<String> {
+        let mut manifest = Vec::new();
+        let mut spine = Vec::new();
+        for_each_tag(opf, |_, tag| match tag_name(tag) {
+            "item" | "opf:item" => {
+                if let (Some(id), Some(href)) = (attr(tag, "id"), attr(tag, "href")) {
+                    manifest.push((id, href));
+                }
+            }
+            "itemref" | "opf:itemref" => {
+                if attr(tag, "linear").as_deref() != Some("no") {
+                    if let Some(idref) = attr(tag, "idref") {
+                        spine.push(idref);
+                    }
+                }
+            }
+            _ => {}
+        });
+        spine
+            .iter()
+            .filter_map(|idref| {
+                manifest
+                    .iter()
+                    .find(|(id, _)| id == idref)
+                    .map(|(_, href)| href.clone())
+            })
+            .collect()
+    }
+
+    /// Resolve a manifest href against the OPF directory, dropping fragments
+    /// and normalizing `..` segments.
+    pub(super) fn resolve_href(base_dir: &str, href: &str) -> String {
+        let href = href.split('#').next().unwrap_or("").replace("%20", " ");
+        let mut segments: Vec<&str> = base_dir.split('/').filter(|s| !s.is_empty()).collect();
+        for segment in href.split('/') {
+            match segment {
+                "" | "." => {}
+                ".." => {
+                    segments.pop();
+                }
+                other => segments.push(other),
+            }
+        }
+        segments.join("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+    use tempfile::TempDir;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_tool(workspace: &Path) -> (TempDir, ReadDocumentTool) {
+        let mem_dir = TempDir::new().unwrap();
+        let memory: Arc<dyn Memory> = Arc::new(SqliteMemory::new(mem_dir.path()).unwrap());
+        let tool = ReadDocumentTool::new(
+            test_security(workspace.to_path_buf()),
+            memory,
+            DocumentToolConfig::default(),
+        );
+        (mem_dir, tool)
+    }
+
+    #[test]
+    fn name_and_schema() {
+        let (_mem, tool) = test_tool(&std::env::temp_dir());
+        assert_eq!(tool.name(), "read_document");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["pages"].is_object());
+        assert!(schema["properties"]["chunk"].is_object());
+        assert!(schema["properties"]["index"].is_object());
+        assert_eq!(schema["required"], json!(["path"]));
+    }
+
+    #[test]
+    fn document_kind_from_extension() {
+        assert_eq!(
+            DocumentKind::from_path(Path::new("a/B.PDF")),
+            Some(DocumentKind::Pdf)
+        );
+        assert_eq!(
+            DocumentKind::from_path(Path::new("spec.docx")),
+            Some(DocumentKind::Docx)
+        );
+        assert_eq!(
+            DocumentKind::from_path(Path::new("book.epub")),
+            Some(DocumentKind::Epub)
+        );
+        assert_eq!(DocumentKind::from_path(Path::new("notes.txt")), None);
+    }
+
+    #[test]
+    fn page_selection_parsing() {
+        assert_eq!(parse_page_selection("3", 10).unwrap(), vec![3]);
+        assert_eq!(
+            parse_page_selection("1, 4-6, 5", 10).unwrap(),
+            vec![1, 4, 5, 6]
+        );
+        assert_eq!(parse_page_selection("9-", 10).unwrap(), vec![9, 10]);
+        assert_eq!(parse_page_selection("-2", 10).unwrap(), vec![1, 2]);
+        assert!(parse_page_selection("0", 10).is_err());
+        assert!(parse_page_selection("5-3", 10).is_err());
+        assert!(parse_page_selection("8-12", 10).is_err());
+        assert!(parse_page_selection("abc", 10).is_err());
+        assert!(parse_page_selection(" , ", 10).is_err());
+    }
+
+    #[test]
+    fn join_units_marks_pages_and_skips_blank_ones() {
+        let pages = vec!["first".to_string(), "  ".to_string(), "third".to_string()];
+        let text = join_units(&pages, &[1, 2, 3], "page");
+        assert!(text.contains("--- Page 1 ---\n\nfirst"));
+        assert!(!text.contains("Page 2"));
+        assert!(text.contains("--- Page 3 ---\n\nthird"));
+    }
+
+    #[test]
+    fn chunks_respect_token_budget() {
+        let text = (1..=40)
+            .map(|i| format!("Paragraph {i} with some filler text to make it longer."))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let chunks = split_into_chunks(&text, 50);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.len() <= 50 * 4 + 60));
+        assert!(chunks[0].starts_with("Paragraph 1 "));
+    }
+
+    #[tokio::test]
+    async fn missing_path_param_returns_error() {
+        let (_mem, tool) = test_tool(&std::env::temp_dir());
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn unsupported_extension_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let (_mem, tool) = test_tool(tmp.path());
+        let result = tool.execute(json!({"path": "notes.txt"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unsupported document type"));
+    }
+
+    #[tokio::test]
+    async fn path_traversal_is_blocked() {
+        let tmp = TempDir::new().unwrap();
+        let (_mem, tool) = test_tool(tmp.path());
+        let result = tool
+            .execute(json!({"path": "../../../etc/book.epub"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn index_is_blocked_in_read_only_mode() {
+        let tmp = TempDir::new().unwrap();
+        let mem_dir = TempDir::new().unwrap();
+        let memory: Arc<dyn Memory> = Arc::new(SqliteMemory::new(mem_dir.path()).unwrap());
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = ReadDocumentTool::new(security, memory, DocumentToolConfig::default());
+        let result = tool
+            .execute(json!({"path": "doc.pdf", "index": true}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[cfg(not(feature = "rag-documents"))]
+    #[tokio::test]
+    async fn docx_without_feature_returns_clear_error() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("spec.docx"), b"PK fake")
+            .await
+            .unwrap();
+        let (_mem, tool) = test_tool(tmp.path());
+        let result = tool.execute(json!({"path": "spec.docx"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("rag-documents"));
+    }
+
+    #[cfg(feature = "rag-documents")]
+    mod office_formats {
+        use super::*;
+        use std::io::Write;
+
+        fn zip_bytes(entries: &[(&str, &str)]) -> Vec<u8> {
+            let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+            for (name, content) in entries {
+                writer
+                    .start_file(*name, zip::write::SimpleFileOptions::default())
+                    .unwrap();
+                writer.write_all(content.as_bytes()).unwrap();
+            }
+            writer.finish().unwrap().into_inner()
+        }
+
+        const DOCX_XML: &str = r#"<?xml version="1.0"?><w:document><w:body>
+            <w:p><w:r><w:t>Intro &amp; scope</w:t></w:r></w:p>
+            <w:p><w:r><w:t xml:space="preserve">Tab</w:t><w:tab/><w:t>bed</w:t></w:r></w:p>
+            <w:p><w:r><w:br w:type="page"/><w:t>Second page</w:t></w:r></w:p>
+            </w:body></w:document>"#;
+
+        #[test]
+        fn docx_text_is_split_at_page_breaks() {
+            let pages = office::docx_pages_from_xml(DOCX_XML);
+            assert_eq!(pages.len(), 2);
+            assert_eq!(pages[0], "Intro & scope\n\nTab\tbed");
+            assert_eq!(pages[1], "Second page");
+        }
+
+        #[test]
+        fn epub_spine_order_and_href_resolution() {
+            let opf = r#"<package><manifest>
+                <item id="c2" href="text/ch2.xhtml" media-type="application/xhtml+xml"/>
+                <item id="c1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+                <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml"/>
+                </manifest><spine>
+                <itemref idref="c1"/><itemref idref="nav" linear="no"/><itemref idref="c2"/>
+                </spine></package>"#;
+            assert_eq!(
+                office::spine_hrefs(opf),
+                vec!["text/ch1.xhtml", "text/ch2.xhtml"]
+            );
+            assert_eq!(
+                office::resolve_href("OEBPS", "../images/My%20Cover.xhtml#top"),
+                "images/My Cover.xhtml"
+            );
+        }
+
+        #[tokio::test]
+        async fn reads_and_indexes_epub_sections() {
+            let tmp = TempDir::new().unwrap();
+            let epub = zip_bytes(&[
+                (
+                    "META-INF/container.xml",
+                    r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf"/></rootfiles></container>"#,
+                ),
+                (
+                    "OEBPS/content.opf",
+                    r#"<package><manifest><item id="a" href="a.xhtml"/><item id="b" href="b.xhtml"/></manifest><spine><itemref idref="a"/><itemref idref="b"/></spine></package>"#,
+                ),
+                (
+                    "OEBPS/a.xhtml",
+                    "<html><body><h1>Chapter One</h1><p>It begins.</p></body></html>",
+                ),
+                (
+                    "OEBPS/b.xhtml",
+                    "<html><body><h1>Chapter Two</h1><p>It ends.</p></body></html>",
+                ),
+            ]);
+            tokio::fs::write(tmp.path().join("book.epub"), epub)
+                .await
+                .unwrap();
+
+            let mem_dir = TempDir::new().unwrap();
+            let memory: Arc<dyn Memory> = Arc::new(SqliteMemory::new(mem_dir.path()).unwrap());
+            let tool = ReadDocumentTool::new(
+                test_security(tmp.path().to_path_buf()),
+                memory.clone(),
+                DocumentToolConfig::default(),
+            );
+            let result = tool
+                .execute(json!({"path": "book.epub", "pages": "2", "index": true}))
+                .await
+                .unwrap();
+            assert!(result.success, "{:?}", result.error);
+            assert!(result.output.contains("EPUB, 2 sections"));
+            assert!(result.output.contains("--- Section 2 ---"));
+            assert!(result.output.contains("It ends."));
+            assert!(!result.output.contains("It begins."));
+            assert!(result.output.contains("Indexed 1 chunk(s)"));
+
+            let stored = memory.get("document:book.epub#2:1").await.unwrap().unwrap();
+            assert!(stored.content.contains("Chapter Two"));
+        }
+    }
+}
@@ -0,0 +1,625 @@
+use super::traits::{Tool, ToolResult, ToolRisk};
+use crate::config::CodeInterpreterConfig;
+use crate::runtime::RuntimeAdapter;
+use crate::security::{NoopSandbox, Sandbox, SecurityPolicy};
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Run directories live here, relative to the session's temp directory.
+pub const RUNS_DIR: &str = "runs";
+/// Older run directories beyond this count are pruned.
+const MAX_KEPT_RUNS: usize = 20;
+/// Produced files listed back to the model.
+const MAX_LISTED_FILES: usize = 50;
+
+/// Environment variables passed through to interpreters.
+const SAFE_ENV_VARS: &[&str] = &["PATH", "LANG", "LC_ALL", "LC_CTYPE", "TERM", "TZ"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Python,
+    JavaScript,
+}
+
+impl Language {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "python" | "python3" | "py" => Some(Self::Python),
+            "javascript" | "js" | "node" | "nodejs" => Some(Self::JavaScript),
+            _ => None,
+        }
+    }
+
+    fn script_name(self) -> &'static str {
+        match self {
+            Self::Python => "main.py",
+            Self::JavaScript => "main.js",
+        }
+    }
+}
+
+/// Execute short Python or JavaScript snippets in a throwaway run directory.
+///
+/// Each run gets its own directory under [`RUNS_DIR`] in the session's temp
+/// directory (also used as `HOME`), a scrubbed environment, the shell tool's
+/// OS sandbox, and CPU / memory / file-size rlimits on Unix. With a container
+/// runtime (`[runtime.tools.run_code]`) the snippet runs in a fresh container
+/// instead, limited by the runtime's settings. Files the snippet writes are
+/// listed back so the model can read them with `file_read`.
+pub struct RunCodeTool {
+    security: Arc<SecurityPolicy>,
+    config: CodeInterpreterConfig,
+    sandbox: Arc<dyn Sandbox>,
+    container: Option<Arc<dyn RuntimeAdapter>>,
+}
+
+impl RunCodeTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: CodeInterpreterConfig) -> Self {
+        Self {
+            security,
+            config,
+            sandbox: Arc::new(NoopSandbox),
+            container: None,
+        }
+    }
+
+    /// Run snippets through `runtime`; anything but the native runtime runs
+    /// them in a container with the workspace mounted.
+    pub fn with_runtime(mut self, runtime: Arc<dyn RuntimeAdapter>) -> Self {
+        self.container = (runtime.name() != "native").then_some(runtime);
+        self
+    }
+
+    /// Run snippets under an OS-level sandbox (see [`crate::security::create_sandbox`]).
+    pub fn with_sandbox(mut self, sandbox: Arc<dyn Sandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    fn effective_timeout_secs(&self, requested: Option<u64>) -> u64 {
+        let ceiling = self.config.timeout_secs.max(1);
+        requested.map_or(ceiling, |secs| secs.clamp(1, ceiling))
+    }
+
+    fn build_command(&self, language: Language, run_dir: &Path) -> std::process::Command {
+        let script = run_dir.join(language.script_name());
+        let mut cmd = match language {
+            Language::Python => {
+                let mut cmd = std::process::Command::new(&self.config.python_command);
+                // -I: isolated mode (ignore PYTHON* env vars and user site-packages).
+                cmd.arg("-I").arg(&script);
+                cmd
+            }
+            Language::JavaScript => {
+                let mut cmd = std::process::Command::new(&self.config.node_command);
+                // V8 reserves far more address space than it uses, so cap the
+                // heap here instead of via RLIMIT_AS.
+                if self.config.max_memory_mb > 0 {
+                    cmd.arg(format!(
+                        "--max-old-space-size={}",
+                        self.config.max_memory_mb
+                    ));
+                }
+                cmd.arg(&script);
+                cmd
+            }
+        };
+        cmd.current_dir(run_dir);
+        cmd
+    }
+
+    /// Command running the script in `run_dir` inside a container started by
+    /// `runtime`, which mounts the workspace as its working directory.
+    fn container_command(
+        &self,
+        runtime: &dyn RuntimeAdapter,
+        language: Language,
+        run_dir: &Path,
+    ) -> anyhow::Result<tokio::process::Command> {
+        let workspace_dir = &self.security.workspace_dir;
+        let relative = run_dir.strip_prefix(workspace_dir).unwrap_or(run_dir);
+        let interpreter = match language {
+            Language::Python => format!("{} -I", self.config.python_command),
+            Language::JavaScript if self.config.max_memory_mb > 0 => format!(
+                "{} --max-old-space-size={}",
+                self.config.node_command, self.config.max_memory_mb
+            ),
+            Language::JavaScript => self.config.node_command.clone(),
+        };
+        let script = format!(
+            "cd '{}' && export HOME=\"$PWD\" TMPDIR=\"$PWD\" PYTHONDONTWRITEBYTECODE=1 PYTHONUNBUFFERED=1 MPLBACKEND=Agg && exec {interpreter} {}",
+            relative.display(),
+            language.script_name()
+        );
+        let mut cmd = runtime.build_shell_command(&script, workspace_dir)?;
+        cmd.env_clear();
+        for var in SAFE_ENV_VARS {
+            if let Ok(val) = std::env::var(var) {
+                cmd.env(var, val);
+            }
+        }
+        Ok(cmd)
+    }
+
+    /// Command running the script in `run_dir` on the host, under the OS
+    /// sandbox and rlimits.
+    fn native_command(
+        &self,
+        language: Language,
+        run_dir: &Path,
+    ) -> anyhow::Result<tokio::process::Command> {
+        let mut std_cmd = self.build_command(language, run_dir);
+        let memory_bytes = (language == Language::Python && self.config.max_memory_mb > 0)
+            .then(|| self.config.max_memory_mb.saturating_mul(1024 * 1024));
+        apply_rlimits(&mut std_cmd, self.config.max_cpu_secs, memory_bytes);
+        self.sandbox
+            .wrap_command(&mut std_cmd)
+            .map_err(|e| anyhow::anyhow!("Failed to apply {} sandbox: {e}", self.sandbox.name()))?;
+        // Wrapper backends may replace the command wholesale; re-pin the directory.
+        std_cmd.current_dir(run_dir);
+        std_cmd.env_clear();
+        for var in SAFE_ENV_VARS {
+            if let Ok(val) = std::env::var(var) {
+                std_cmd.env(var, val);
+            }
+        }
+        std_cmd
+            .env("HOME", run_dir)
+            .env("TMPDIR", run_dir)
+            .env("PYTHONDONTWRITEBYTECODE", "1")
+            .env("PYTHONUNBUFFERED", "1")
+            .env("MPLBACKEND", "Agg");
+        Ok(tokio::process::Command::from(std_cmd))
+    }
+}
+
+/// Create a fresh run directory in `session_dir` and prune old ones.
+async fn create_run_dir(session_dir: &Path) -> std::io::Result<PathBuf> {
+    let runs_root = session_dir.join(RUNS_DIR);
+    tokio::fs::create_dir_all(&runs_root).await?;
+
+    let mut existing = Vec::new();
+    let mut reader = tokio::fs::read_dir(&runs_root).await?;
+    while let Some(entry) = reader.next_entry().await? {
+        if entry.file_type().await.is_ok_and(|t| t.is_dir()) {
+            existing.push(entry.path());
+        }
+    }
+    // Names start with a sortable timestamp.
+    existing.sort();
+    let excess = (existing.len() + 1).saturating_sub(MAX_KEPT_RUNS);
+    for old in existing.into_iter().take(excess) {
+        let _ = tokio::fs::remove_dir_all(old).await;
+    }
+
+    let stamp = chrono::Local::now().format("%Y%m%dT%H%M%S%.3f");
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let run_dir = runs_root.join(format!("{stamp}-{}", &id[..8]));
+    tokio::fs::create_dir(&run_dir).await?;
+    Ok(run_dir)
+}
+
+/// Files under `run_dir` other than the script, as (workspace-relative path, size).
+fn collect_produced_files(
+    workspace_dir: &Path,
+    run_dir: &Path,
+    script_name: &str,
+) -> Vec<(String, u64)> {
+    let mut files = Vec::new();
+    let mut stack = vec![run_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(meta) = std::fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if meta.is_dir() {
+                stack.push(path);
+            } else if meta.is_file() && !(dir == run_dir && entry.file_name() == script_name) {
+                let rel = path.strip_prefix(workspace_dir).unwrap_or(&path);
+                files.push((rel.display().to_string(), meta.len()));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn truncate_output(bytes: &[u8], max_bytes: usize, label: &str) -> String {
+    let mut text = String::from_utf8_lossy(bytes).to_string();
+    if text.len() > max_bytes {
+        let mut cutoff = max_bytes;
+        while cutoff > 0 && !text.is_char_boundary(cutoff) {
+            cutoff -= 1;
+        }
+        text.truncate(cutoff);
+        let _ = write!(text, "\n... [{label} truncated at {max_bytes} bytes]");
+    }
+    text
+}
+
+/// Apply CPU, address-space and file-size limits in the child before `exec`.
+#[cfg(unix)]
+fn apply_rlimits(cmd: &mut std::process::Command, cpu_secs: u64, memory_bytes: Option<u64>) {
+    use std::os::unix::process::CommandExt;
+
+    let file_bytes: libc::rlim_t = 256 * 1024 * 1024;
+    let cpu: libc::rlim_t = cpu_secs.max(1);
+    let memory: Option<libc::rlim_t> = memory_bytes;
+    // SAFETY: setrlimit is async-signal-safe and only reads stack data.
+    unsafe {
+        cmd.pre_exec(move || {
+            let set = |resource, value: libc::rlim_t| {
+                let limit = libc::rlimit {
+                    rlim_cur: value,
+                    rlim_max: value,
+                };
+                if libc::setrlimit(resource, &limit) == 0 {
+                    Ok(())
+                } else {
+                    Err(std::io::Error::last_os_error())
+                }
+            };
+            set(libc::RLIMIT_CPU, cpu)?;
+            set(libc::RLIMIT_FSIZE, file_bytes)?;
+            if let Some(memory) = memory {
+                set(libc::RLIMIT_AS, memory)?;
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_rlimits(_cmd: &mut std::process::Command, _cpu_secs: u64, _memory_bytes: Option<u64>) {}
+
+#[async_trait]
+impl Tool for RunCodeTool {
+    fn name(&self) -> &str {
+        "run_code"
+    }
+
+    fn description(&self) -> &str {
+        "Run a short Python or JavaScript program in an isolated subprocess with CPU, memory and \
+         time limits. Returns stdout, stderr and any files the program wrote (readable with \
+         file_read). Use for calculations, data analysis and file conversions."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "language": {
+                    "type": "string",
+                    "enum": ["python", "javascript"],
+                    "description": "Interpreter to use"
+                },
+                "code": {
+                    "type": "string",
+                    "description": "Complete program source. Print results to stdout; write output files to the current directory."
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Optional wall-clock limit in seconds; capped at the configured limit",
+                    "minimum": 1
+                }
+            },
+            "required": ["language", "code"]
+        })
+    }
+
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::High
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let language_raw = args
+            .get("language")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'language' parameter"))?;
+        let code = args
+            .get("code")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'code' parameter"))?;
+        let timeout_secs =
+            self.effective_timeout_secs(args.get("timeout_secs").and_then(|v| v.as_u64()));
+
+        let Some(language) = Language::parse(language_raw) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Unsupported language '{language_raw}'. Use 'python' or 'javascript'"
+                )),
+            });
+        };
+
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let workspace_dir = &self.security.workspace_dir;
+        let run_dir = match self.security.workspace().session_dir().await {
+            Ok(session_dir) => create_run_dir(&session_dir).await,
+            Err(e) => Err(e),
+        };
+        let run_dir = match run_dir {
+            Ok(dir) => dir,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to create run directory: {e}")),
+                });
+            }
+        };
+        if let Err(e) = tokio::fs::write(run_dir.join(language.script_name()), code).await {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to write script: {e}")),
+            });
+        }
+
+        let built = match &self.container {
+            Some(runtime) => self.container_command(runtime.as_ref(), language, &run_dir),
+            None => self.native_command(language, &run_dir),
+        };
+        let mut cmd = match built {
+            Ok(cmd) => cmd,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("{e:#}")),
+                });
+            }
+        };
+        cmd.stdin(std::process::Stdio::null());
+        cmd.kill_on_drop(true);
+
+        let output =
+            match tokio::time::timeout(Duration::from_secs(timeout_secs), cmd.output()).await {
+                Ok(Ok(output)) => output,
+                Ok(Err(e)) => {
+                    let interpreter = match (&self.container, language) {
+                        (Some(runtime), _) => runtime.name(),
+                        (None, Language::Python) => self.config.python_command.as_str(),
+                        (None, Language::JavaScript) => self.config.node_command.as_str(),
+                    };
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Failed to start '{interpreter}': {e}")),
+                    });
+                }
+                Err(_) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Program timed out after {timeout_secs}s and was killed"
+                        )),
+                    });
+                }
+            };
+
+        let max_output_bytes = self.config.max_output_bytes.max(1);
+        let stdout = truncate_output(&output.stdout, max_output_bytes, "stdout");
+        let stderr = truncate_output(&output.stderr, max_output_bytes, "stderr");
+
+        let run_dir_for_scan = run_dir.clone();
+        let workspace_for_scan = workspace_dir.clone();
+        let files = tokio::task::spawn_blocking(move || {
+            collect_produced_files(
+                &workspace_for_scan,
+                &run_dir_for_scan,
+                language.script_name(),
+            )
+        })
+        .await
+        .unwrap_or_default();
+
+        let mut report = format!("Exit status: {}\n", output.status);
+        if !stdout.is_empty() {
+            let _ = write!(report, "\nstdout:\n{}\n", stdout.trim_end());
+        }
+        if !stderr.is_empty() {
+            let _ = write!(report, "\nstderr:\n{}\n", stderr.trim_end());
+        }
+        if !files.is_empty() {
+            report.push_str("\nFiles written:\n");
+            for (path, size) in files.iter().take(MAX_LISTED_FILES) {
+                let _ = writeln!(report, "- {path} ({size} bytes)");
+            }
+            if files.len() > MAX_LISTED_FILES {
+                let _ = writeln!(report, "- ... and {} more", files.len() - MAX_LISTED_FILES);
+            }
+        }
+
+        let success = output.status.success();
+        Ok(ToolResult {
+            success,
+            output: report.clone(),
+            error: (!success).then_some(report),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_tool(workspace: &Path, autonomy: AutonomyLevel) -> RunCodeTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        RunCodeTool::new(
+            security,
+            CodeInterpreterConfig {
+                enabled: true,
+                timeout_secs: 10,
+                ..CodeInterpreterConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn container_runtime_runs_the_script_in_its_run_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let runtime = Arc::new(crate::runtime::DockerRuntime::new(
+            crate::config::DockerRuntimeConfig {
+                image: "python:3.12-slim".into(),
+                ..crate::config::DockerRuntimeConfig::default()
+            },
+        ));
+        let tool = test_tool(tmp.path(), AutonomyLevel::Full).with_runtime(runtime);
+        let run_dir = crate::security::workspace::session_dir(tmp.path(), "default")
+            .join(RUNS_DIR)
+            .join("run-1");
+        let cmd = tool
+            .container_command(
+                tool.container.as_deref().unwrap(),
+                Language::Python,
+                &run_dir,
+            )
+            .unwrap();
+        let debug = format!("{cmd:?}");
+        assert!(debug.contains("python:3.12-slim"));
+        assert!(debug.contains("--network"));
+        assert!(debug.contains("cd '.zeroclaw/sessions/default/runs/run-1'"));
+        assert!(debug.contains("exec python3 -I main.py"));
+
+        let native = test_tool(tmp.path(), AutonomyLevel::Full)
+            .with_runtime(Arc::new(crate::runtime::NativeRuntime::new()));
+        assert!(native.container.is_none());
+    }
+
+    fn python_available() -> bool {
+        std::process::Command::new("python3")
+            .arg("--version")
+            .output()
+            .is_ok_and(|o| o.status.success())
+    }
+
+    #[test]
+    fn language_aliases_parse() {
+        assert_eq!(Language::parse("Python3"), Some(Language::Python));
+        assert_eq!(Language::parse("node"), Some(Language::JavaScript));
+        assert_eq!(Language::parse("ruby"), None);
+    }
+
+    #[test]
+    fn timeout_is_capped_by_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = test_tool(dir.path(), AutonomyLevel::Supervised);
+        assert_eq!(tool.effective_timeout_secs(None), 10);
+        assert_eq!(tool.effective_timeout_secs(Some(3)), 3);
+        assert_eq!(tool.effective_timeout_secs(Some(999)), 10);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_language_and_readonly_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = test_tool(dir.path(), AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({"language": "ruby", "code": "puts 1"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Unsupported language"));
+
+        let readonly = test_tool(dir.path(), AutonomyLevel::ReadOnly);
+        let result = readonly
+            .execute(json!({"language": "python", "code": "print(1)"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn python_captures_output_and_files() {
+        if !python_available() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let tool = test_tool(dir.path(), AutonomyLevel::Supervised);
+        let code = "import sys\nprint(6 * 7)\nprint('warn', file=sys.stderr)\nopen('out.csv', 'w').write('a,b\\n')\n";
+        let result = tool
+            .execute(json!({"language": "python", "code": code}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("stdout:\n42"));
+        assert!(result.output.contains("stderr:\nwarn"));
+        assert!(result.output.contains("out.csv (4 bytes)"));
+        assert!(!result.output.contains("main.py"));
+    }
+
+    #[tokio::test]
+    async fn python_failure_and_timeout_are_reported() {
+        if !python_available() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let tool = test_tool(dir.path(), AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({"language": "python", "code": "raise SystemExit(3)"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Exit status"));
+
+        let result = tool
+            .execute(json!({
+                "language": "python",
+                "code": "import time\ntime.sleep(30)",
+                "timeout_secs": 1
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("timed out after 1s"));
+    }
+
+    #[tokio::test]
+    async fn old_run_directories_are_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        for _ in 0..(MAX_KEPT_RUNS + 3) {
+            create_run_dir(dir.path()).await.unwrap();
+        }
+        let count = std::fs::read_dir(dir.path().join(RUNS_DIR))
+            .unwrap()
+            .count();
+        assert_eq!(count, MAX_KEPT_RUNS);
+    }
+}
@@ -4,7 +4,7 @@ use crate::cron;
 use crate::security::SecurityPolicy;
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use serde_json::json;
 use std::sync::Arc;
 
@@ -52,7 +52,7 @@ impl Tool for ScheduleTool {
                 },
                 "run_at": {
                     "type": "string",
-                    "description": "Absolute RFC3339 time for one-shot tasks (e.g. '2030-01-01T00:00:00Z')."
+                    "description": "Time for one-shot tasks: RFC3339 (e.g. '2030-01-01T00:00:00Z'), a wall-clock time in the user's timezone (e.g. '2030-01-01T09:00') or a phrase (e.g. 'tomorrow 9am', 'next friday 3pm')."
                 },
                 "command": {
                     "type": "string",
@@ -193,13 +193,13 @@ impl ScheduleTool {
             };
             let last_run = job
                 .last_run
-                .map_or_else(|| "never".to_string(), |value| value.to_rfc3339());
+                .map_or_else(|| "never".to_string(), crate::time::display);
             let last_status = job.last_status.unwrap_or_else(|| "n/a".to_string());
             lines.push(format!(
                 "- {} | {} | next={} | last={} ({}){} | cmd: {}",
                 job.id,
                 job.expression,
-                job.next_run.to_rfc3339(),
+                crate::time::display(job.next_run),
                 last_run,
                 last_status,
                 flags,
@@ -317,7 +317,7 @@ impl ScheduleTool {
                     "Created recurring job {} (expr: {}, next: {}, cmd: {})",
                     job.id,
                     job.expression,
-                    job.next_run.to_rfc3339(),
+                    crate::time::display(job.next_run),
                     job.command
                 ),
                 error: None,
@@ -331,7 +331,7 @@ impl ScheduleTool {
                 output: format!(
                     "Created one-shot job {} (runs at: {}, cmd: {})",
                     job.id,
-                    job.next_run.to_rfc3339(),
+                    crate::time::display(job.next_run),
                     job.command
                 ),
                 error: None,
@@ -339,9 +339,8 @@ impl ScheduleTool {
         }
 
         let run_at_raw = run_at.ok_or_else(|| anyhow::anyhow!("Missing scheduling parameters"))?;
-        let run_at_parsed: DateTime<Utc> = DateTime::parse_from_rfc3339(run_at_raw)
-            .map_err(|error| anyhow::anyhow!("Invalid run_at timestamp: {error}"))?
-            .with_timezone(&Utc);
+        let run_at_parsed = crate::time::parse_instant(run_at_raw, crate::time::zone(), Utc::now())
+            .map_err(|error| anyhow::anyhow!("Invalid run_at: {error}"))?;
 
         let job = cron::add_once_at(&self.config, run_at_parsed, command)?;
         Ok(ToolResult {
@@ -349,7 +348,7 @@ impl ScheduleTool {
             output: format!(
                 "Created one-shot job {} (runs at: {}, cmd: {})",
                 job.id,
-                job.next_run.to_rfc3339(),
+                crate::time::display(job.next_run),
                 job.command
             ),
             error: None,
@@ -1,16 +1,12 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolResult, ToolRisk};
 use crate::runtime::RuntimeAdapter;
-use crate::security::SecurityPolicy;
+use crate::security::{NoopSandbox, Sandbox, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
-/// Maximum shell command execution time before kill.
-const SHELL_TIMEOUT_SECS: u64 = 60;
-/// Maximum output size in bytes (1MB).
-const MAX_OUTPUT_BYTES: usize = 1_048_576;
 /// Environment variables safe to pass to shell commands.
 /// Only functional variables are included — never API keys or secrets.
 const SAFE_ENV_VARS: &[&str] = &[
@@ -21,11 +17,31 @@ const SAFE_ENV_VARS: &[&str] = &[
 pub struct ShellTool {
     security: Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
+    sandbox: Arc<dyn Sandbox>,
 }
 
 impl ShellTool {
     pub fn new(security: Arc<SecurityPolicy>, runtime: Arc<dyn RuntimeAdapter>) -> Self {
-        Self { security, runtime }
+        Self {
+            security,
+            runtime,
+            sandbox: Arc::new(NoopSandbox),
+        }
+    }
+
+    /// Run commands under an OS-level sandbox (see [`crate::security::create_sandbox`]).
+    ///
+    /// Only applied with the native runtime; the Docker runtime already
+    /// isolates commands in a container.
+    pub fn with_sandbox(mut self, sandbox: Arc<dyn Sandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// Effective timeout: the caller may shorten the policy timeout, never extend it.
+    fn effective_timeout_secs(&self, requested: Option<u64>) -> u64 {
+        let ceiling = self.security.shell_timeout_secs.max(1);
+        requested.map_or(ceiling, |secs| secs.clamp(1, ceiling))
     }
 }
 
@@ -38,7 +54,7 @@ fn is_valid_env_var_name(name: &str) -> bool {
     chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
 }
 
-fn collect_allowed_shell_env_vars(security: &SecurityPolicy) -> Vec<String> {
+pub(crate) fn collect_allowed_shell_env_vars(security: &SecurityPolicy) -> Vec<String> {
     let mut out = Vec::new();
     let mut seen = HashSet::new();
     for key in SAFE_ENV_VARS
@@ -57,7 +73,7 @@ fn collect_allowed_shell_env_vars(security: &SecurityPolicy) -> Vec<String> {
     out
 }
 
-fn truncate_at_char_boundary(s: &mut String, max_bytes: usize) {
+pub(crate) fn truncate_at_char_boundary(s: &mut String, max_bytes: usize) {
     if s.len() <= max_bytes {
         return;
     }
@@ -90,12 +106,47 @@ impl Tool for ShellTool {
                     "type": "boolean",
                     "description": "Set true to explicitly approve medium/high-risk commands in supervised mode",
                     "default": false
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Optional timeout in seconds; capped at the configured shell timeout",
+                    "minimum": 1
                 }
             },
             "required": ["command"]
         })
     }
 
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::High
+    }
+
+    async fn dry_run(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        let command = args
+            .get("command")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'command' parameter"))?;
+        let approved = args
+            .get("approved")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let timeout_secs =
+            self.effective_timeout_secs(args.get("timeout_secs").and_then(|v| v.as_u64()));
+
+        self.security
+            .validate_command_execution(command, approved)
+            .map_err(|reason| anyhow::anyhow!(reason))?;
+        if let Some(path) = self.security.forbidden_path_argument(command) {
+            anyhow::bail!("Path blocked by security policy: {path}");
+        }
+        Ok(format!(
+            "Would run `{command}` in {} ({} runtime, {} sandbox, timeout {timeout_secs}s)",
+            self.security.workspace_dir.display(),
+            self.runtime.name(),
+            self.sandbox.name()
+        ))
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let command = args
             .get("command")
@@ -105,6 +156,8 @@ impl Tool for ShellTool {
             .get("approved")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
+        let timeout_secs =
+            self.effective_timeout_secs(args.get("timeout_secs").and_then(|v| v.as_u64()));
 
         if self.security.is_rate_limited() {
             return Ok(ToolResult {
@@ -157,6 +210,21 @@ impl Tool for ShellTool {
                 });
             }
         };
+        // Wrapper backends may replace the command wholesale, so apply the
+        // sandbox first and re-pin the working directory afterwards.
+        if self.runtime.name() == "native" {
+            if let Err(e) = self.sandbox.wrap_command(cmd.as_std_mut()) {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Failed to apply {} sandbox: {e}",
+                        self.sandbox.name()
+                    )),
+                });
+            }
+            cmd.current_dir(&self.security.workspace_dir);
+        }
         cmd.env_clear();
 
         for var in collect_allowed_shell_env_vars(&self.security) {
@@ -167,8 +235,7 @@ impl Tool for ShellTool {
         // Ensure timeout cancellation also terminates the child process.
         cmd.kill_on_drop(true);
 
-        let result =
-            tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
+        let result = tokio::time::timeout(Duration::from_secs(timeout_secs), cmd.output()).await;
 
         match result {
             Ok(Ok(output)) => {
@@ -176,13 +243,18 @@ impl Tool for ShellTool {
                 let mut stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
                 // Truncate output to prevent OOM
-                if stdout.len() > MAX_OUTPUT_BYTES {
-                    truncate_at_char_boundary(&mut stdout, MAX_OUTPUT_BYTES);
-                    stdout.push_str("\n... [output truncated at 1MB]");
+                let max_output_bytes = self.security.shell_max_output_bytes.max(1);
+                if stdout.len() > max_output_bytes {
+                    truncate_at_char_boundary(&mut stdout, max_output_bytes);
+                    stdout.push_str(&format!(
+                        "\n... [output truncated at {max_output_bytes} bytes]"
+                    ));
                 }
-                if stderr.len() > MAX_OUTPUT_BYTES {
-                    truncate_at_char_boundary(&mut stderr, MAX_OUTPUT_BYTES);
-                    stderr.push_str("\n... [stderr truncated at 1MB]");
+                if stderr.len() > max_output_bytes {
+                    truncate_at_char_boundary(&mut stderr, max_output_bytes);
+                    stderr.push_str(&format!(
+                        "\n... [stderr truncated at {max_output_bytes} bytes]"
+                    ));
                 }
 
                 Ok(ToolResult {
@@ -204,7 +276,7 @@ impl Tool for ShellTool {
                 success: false,
                 output: String::new(),
                 error: Some(format!(
-                    "Command timed out after {SHELL_TIMEOUT_SECS}s and was killed"
+                    "Command timed out after {timeout_secs}s and was killed"
                 )),
             }),
         }
@@ -292,6 +364,18 @@ mod tests {
             .contains("not allowed"));
     }
 
+    #[tokio::test]
+    async fn shell_dry_run_describes_without_running() {
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
+        let plan = tool
+            .dry_run(json!({"command": "echo hello"}))
+            .await
+            .expect("allowed command should have a plan");
+        assert!(plan.starts_with("Would run `echo hello` in "));
+        assert!(plan.contains("native runtime"));
+        assert!(tool.dry_run(json!({"command": "rm -rf /"})).await.is_err());
+    }
+
     #[tokio::test]
     async fn shell_missing_command_param() {
         let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
@@ -563,18 +647,76 @@ mod tests {
     // ── §5.2 Shell timeout enforcement tests ─────────────────
 
     #[test]
-    fn shell_timeout_constant_is_reasonable() {
-        assert_eq!(SHELL_TIMEOUT_SECS, 60, "shell timeout must be 60 seconds");
+    fn shell_timeout_default_is_reasonable() {
+        assert_eq!(
+            SecurityPolicy::default().shell_timeout_secs,
+            60,
+            "shell timeout must default to 60 seconds"
+        );
     }
 
     #[test]
-    fn shell_output_limit_is_1mb() {
+    fn shell_output_limit_defaults_to_1mb() {
         assert_eq!(
-            MAX_OUTPUT_BYTES, 1_048_576,
-            "max output must be 1 MB to prevent OOM"
+            SecurityPolicy::default().shell_max_output_bytes,
+            1_048_576,
+            "max output must default to 1 MB to prevent OOM"
         );
     }
 
+    #[test]
+    fn shell_timeout_request_cannot_exceed_policy() {
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
+        assert_eq!(tool.effective_timeout_secs(None), 60);
+        assert_eq!(tool.effective_timeout_secs(Some(5)), 5);
+        assert_eq!(tool.effective_timeout_secs(Some(0)), 1);
+        assert_eq!(tool.effective_timeout_secs(Some(3600)), 60);
+    }
+
+    #[tokio::test]
+    async fn shell_kills_command_after_requested_timeout() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: std::env::temp_dir(),
+            allowed_commands: vec!["sleep".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool = ShellTool::new(security, test_runtime());
+        let result = tool
+            .execute(json!({"command": "sleep 5", "timeout_secs": 1}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("timed out after 1s"));
+    }
+
+    #[tokio::test]
+    async fn shell_truncates_output_at_policy_limit() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: std::env::temp_dir(),
+            shell_max_output_bytes: 4,
+            ..SecurityPolicy::default()
+        });
+        let tool = ShellTool::new(security, test_runtime());
+        let result = tool
+            .execute(json!({"command": "echo hello-world"}))
+            .await
+            .unwrap();
+        assert!(result.output.starts_with("hell\n"));
+        assert!(result.output.contains("[output truncated at 4 bytes]"));
+    }
+
+    #[tokio::test]
+    async fn shell_runs_under_noop_sandbox_in_workspace() {
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime())
+            .with_sandbox(Arc::new(NoopSandbox));
+        let result = tool.execute(json!({"command": "pwd"})).await.unwrap();
+        assert!(result.success);
+        let cwd = std::fs::canonicalize(result.output.trim()).unwrap();
+        assert_eq!(cwd, std::fs::canonicalize(std::env::temp_dir()).unwrap());
+    }
+
     // ── §5.3 Non-UTF8 binary output tests ────────────────────
 
     #[test]
@@ -4,7 +4,7 @@ use std::sync::{Arc, Mutex};
 use async_trait::async_trait;
 use serde_json::json;
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolResult, ToolRisk};
 use crate::sop::{SopEngine, SopMetricsCollector};
 
 /// Query SOP execution status — active runs, finished runs, or a specific run by ID.
@@ -124,6 +124,10 @@ impl Tool for SopStatusTool {
         })
     }
 
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let run_id = args.get("run_id").and_then(|v| v.as_str());
         let sop_name = args.get("sop_name").and_then(|v| v.as_str());
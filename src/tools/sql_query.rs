@@ -0,0 +1,728 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{SqlConnectionConfig, SqlToolConfig};
+use crate::security::SecurityPolicy;
+use crate::util::truncate_with_width;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+const REDACTED: &str = "[REDACTED]";
+/// Hard ceiling on rows regardless of what the model asks for.
+const MAX_ROWS_CEILING: usize = 1_000;
+
+/// Rows and columns returned by a query.
+#[derive(Debug, Default)]
+struct QueryOutput {
+    columns: Vec<String>,
+    /// Table column each result column comes from, when the driver knows.
+    sources: Vec<Option<String>>,
+    rows: Vec<Vec<Option<String>>>,
+    /// More rows were available than returned.
+    truncated: bool,
+    /// Rows changed by a data-modifying statement.
+    affected: Option<u64>,
+}
+
+/// Run SQL against named SQLite / PostgreSQL connections from `[sql]`.
+///
+/// Connections are read-only unless configured otherwise: SQLite databases are
+/// opened with `SQLITE_OPEN_READ_ONLY` and statements must pass
+/// `sqlite3_stmt_readonly`; PostgreSQL queries run inside a
+/// `READ ONLY` transaction that is always rolled back.
+///
+/// Redacted columns are shown as `[REDACTED]`. Aliases and expressions must
+/// not get around that: SQLite reads every redacted column as NULL through
+/// an authorizer, and PostgreSQL queries that name a redacted column are
+/// refused.
+pub struct SqlQueryTool {
+    security: Arc<SecurityPolicy>,
+    config: SqlToolConfig,
+}
+
+impl SqlQueryTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: SqlToolConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn connection_names(&self) -> String {
+        let mut names: Vec<&str> = self.config.connections.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names.join(", ")
+    }
+
+    fn sqlite_path(&self, connection: &SqlConnectionConfig) -> Result<PathBuf, String> {
+        let raw = connection
+            .path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .ok_or("SQLite connection has no `path` configured")?;
+        let expanded = PathBuf::from(shellexpand::tilde(raw).into_owned());
+        Ok(if expanded.is_absolute() {
+            expanded
+        } else {
+            self.security.workspace_dir.join(expanded)
+        })
+    }
+
+    fn redaction_patterns(&self, connection: &SqlConnectionConfig) -> Vec<String> {
+        self.config
+            .redact_columns
+            .iter()
+            .chain(&connection.redact_columns)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Case-insensitive column match with optional leading/trailing `*` wildcards.
+fn column_matches(pattern: &str, column: &str) -> bool {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    let column = column.to_ascii_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+    if pattern == "*" {
+        return true;
+    }
+    match (pattern.strip_prefix('*'), pattern.strip_suffix('*')) {
+        (Some(_), Some(_)) => column.contains(&pattern[1..pattern.len() - 1]),
+        (Some(suffix), None) => column.ends_with(suffix),
+        (None, Some(prefix)) => column.starts_with(prefix),
+        (None, None) => column == pattern,
+    }
+}
+
+fn is_redacted(patterns: &[String], column: &str) -> bool {
+    patterns.iter().any(|p| column_matches(p, column))
+}
+
+/// Hide result columns whose name or source column is redacted.
+fn redact(output: &mut QueryOutput, patterns: &[String]) {
+    let redacted: Vec<bool> = output
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            is_redacted(patterns, column)
+                || output
+                    .sources
+                    .get(i)
+                    .and_then(Option::as_deref)
+                    .is_some_and(|source| is_redacted(patterns, source))
+        })
+        .collect();
+    for row in &mut output.rows {
+        for (cell, hide) in row.iter_mut().zip(&redacted) {
+            if *hide {
+                *cell = Some(REDACTED.into());
+            }
+        }
+    }
+}
+
+/// First identifier in `query` that matches a redaction pattern.
+#[cfg(feature = "sql-postgres")]
+fn redacted_identifier<'q>(query: &'q str, patterns: &[String]) -> Option<&'q str> {
+    query
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .find(|word| !word.is_empty() && is_redacted(patterns, word))
+}
+
+fn escape_cell(value: &str, max_width: usize) -> String {
+    let flat = value.replace(['\r', '\n'], " ");
+    truncate_with_width(&flat, max_width.max(4), "…").replace('|', "\\|")
+}
+
+/// Render rows as a Markdown table with cells capped at `max_width` columns.
+fn render_markdown(output: &QueryOutput, max_width: usize) -> String {
+    if output.columns.is_empty() {
+        return match output.affected {
+            Some(n) => format!("Statement executed; {n} row(s) affected."),
+            None => "Statement executed.".into(),
+        };
+    }
+
+    let mut table = String::new();
+    let header: Vec<String> = output
+        .columns
+        .iter()
+        .map(|c| escape_cell(c, max_width))
+        .collect();
+    let _ = writeln!(table, "| {} |", header.join(" | "));
+    let _ = writeln!(table, "|{}|", vec!["---"; header.len()].join("|"));
+    for row in &output.rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| {
+                cell.as_deref()
+                    .map_or("NULL".into(), |v| escape_cell(v, max_width))
+            })
+            .collect();
+        let _ = writeln!(table, "| {} |", cells.join(" | "));
+    }
+
+    let count = output.rows.len();
+    if output.truncated {
+        let _ = write!(
+            table,
+            "\n({count} rows shown; more rows available — add LIMIT/WHERE to narrow the query)"
+        );
+    } else {
+        let _ = write!(
+            table,
+            "\n({count} row{})",
+            if count == 1 { "" } else { "s" }
+        );
+    }
+    table
+}
+
+/// Leading SQL keyword, skipping whitespace and comments.
+fn first_keyword(query: &str) -> String {
+    let mut rest = query.trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix("--") {
+            rest = after
+                .split_once('\n')
+                .map_or("", |(_, tail)| tail)
+                .trim_start();
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after
+                .split_once("*/")
+                .map_or("", |(_, tail)| tail)
+                .trim_start();
+        } else {
+            break;
+        }
+    }
+    rest.chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+fn run_sqlite(
+    path: &std::path::Path,
+    query: &str,
+    read_only: bool,
+    redact_columns: Vec<String>,
+    max_rows: usize,
+    timeout: Duration,
+) -> anyhow::Result<QueryOutput> {
+    use rusqlite::hooks::{AuthAction, AuthContext, Authorization};
+    use rusqlite::types::ValueRef;
+    use rusqlite::OpenFlags;
+
+    if read_only && matches!(first_keyword(query).as_str(), "ATTACH" | "DETACH") {
+        anyhow::bail!("ATTACH/DETACH is not allowed on read-only connections");
+    }
+
+    let mut flags = OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    flags |= if read_only {
+        OpenFlags::SQLITE_OPEN_READ_ONLY
+    } else {
+        OpenFlags::SQLITE_OPEN_READ_WRITE
+    };
+    let conn = rusqlite::Connection::open_with_flags(path, flags)?;
+
+    // Every read of a redacted column, wherever it appears in the statement
+    // (aliases, expressions, subqueries, views, WHERE clauses), yields NULL.
+    conn.authorizer(Some(move |ctx: AuthContext<'_>| match ctx.action {
+        AuthAction::Read { column_name, .. } if is_redacted(&redact_columns, column_name) => {
+            Authorization::Ignore
+        }
+        _ => Authorization::Allow,
+    }));
+
+    // Interrupt long-running statements from a watchdog thread.
+    let interrupt = conn.get_interrupt_handle();
+    let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+    let watchdog = std::thread::spawn(move || {
+        if done_rx.recv_timeout(timeout).is_err() {
+            interrupt.interrupt();
+        }
+    });
+
+    let result = (|| -> anyhow::Result<QueryOutput> {
+        // `prepare` rejects input containing more than one statement.
+        let mut stmt = conn.prepare(query)?;
+        if read_only && !stmt.readonly() {
+            anyhow::bail!("Connection is read-only; only SELECT-style statements are allowed");
+        }
+
+        let columns: Vec<String> = stmt
+            .column_names()
+            .iter()
+            .map(|c| (*c).to_string())
+            .collect();
+        if columns.is_empty() {
+            let affected = stmt.execute([])?;
+            return Ok(QueryOutput {
+                affected: Some(affected as u64),
+                ..QueryOutput::default()
+            });
+        }
+
+        let mut output = QueryOutput {
+            columns,
+            ..QueryOutput::default()
+        };
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            if output.rows.len() == max_rows {
+                output.truncated = true;
+                break;
+            }
+            let mut values = Vec::with_capacity(output.columns.len());
+            for i in 0..output.columns.len() {
+                values.push(match row.get_ref(i)? {
+                    ValueRef::Null => None,
+                    ValueRef::Integer(v) => Some(v.to_string()),
+                    ValueRef::Real(v) => Some(v.to_string()),
+                    ValueRef::Text(v) => Some(String::from_utf8_lossy(v).into_owned()),
+                    ValueRef::Blob(v) => Some(format!("<blob {} bytes>", v.len())),
+                });
+            }
+            output.rows.push(values);
+        }
+        Ok(output)
+    })();
+
+    let _ = done_tx.send(());
+    let _ = watchdog.join();
+    result.map_err(|e| match e.downcast_ref::<rusqlite::Error>() {
+        Some(rusqlite::Error::SqliteFailure(err, _))
+            if err.code == rusqlite::ErrorCode::OperationInterrupted =>
+        {
+            anyhow::anyhow!("Query timed out after {}s", timeout.as_secs())
+        }
+        _ => e,
+    })
+}
+
+#[cfg(feature = "sql-postgres")]
+fn run_postgres(
+    url: &str,
+    query: &str,
+    read_only: bool,
+    redact_columns: Vec<String>,
+    max_rows: usize,
+    timeout: Duration,
+) -> anyhow::Result<QueryOutput> {
+    use postgres::SimpleQueryMessage;
+
+    // A redacted column can only ever be shown as `[REDACTED]`, so naming one
+    // is only useful to compute with or filter on it; `SELECT *` still works.
+    if let Some(column) = redacted_identifier(query, &redact_columns) {
+        anyhow::bail!("Query references redacted column '{column}'");
+    }
+
+    let mut config: postgres::Config = url
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid PostgreSQL connection URL"))?;
+    config.connect_timeout(timeout);
+    let mut client = config.connect(postgres::NoTls)?;
+
+    // Extended-protocol prepare rejects multiple statements, so the simple
+    // query below can never smuggle a COMMIT out of the read-only transaction.
+    let statement = client.prepare(query)?;
+
+    // Source columns catch redacted columns renamed by a view.
+    let mut sources = Vec::with_capacity(statement.columns().len());
+    for column in statement.columns() {
+        sources.push(match (column.table_oid(), column.column_id()) {
+            (Some(table), Some(attnum)) => client
+                .query_opt(
+                    "SELECT attname::text FROM pg_attribute WHERE attrelid = $1 AND attnum = $2",
+                    &[&table, &attnum],
+                )?
+                .map(|row| row.get::<_, String>(0)),
+            _ => None,
+        });
+    }
+
+    let mut tx = client.build_transaction().read_only(read_only).start()?;
+    // Running any statement first also locks the transaction's access mode.
+    tx.batch_execute(&format!(
+        "SET LOCAL statement_timeout = {}",
+        timeout.as_millis()
+    ))?;
+
+    let mut output = QueryOutput {
+        sources,
+        ..QueryOutput::default()
+    };
+    for message in tx.simple_query(query)? {
+        match message {
+            SimpleQueryMessage::RowDescription(columns) => {
+                output.columns = columns.iter().map(|c| c.name().to_string()).collect();
+            }
+            SimpleQueryMessage::Row(row) => {
+                if output.columns.is_empty() {
+                    output.columns = row.columns().iter().map(|c| c.name().to_string()).collect();
+                }
+                if output.rows.len() == max_rows {
+                    output.truncated = true;
+                    continue;
+                }
+                output.rows.push(
+                    (0..row.len())
+                        .map(|i| row.get(i).map(str::to_string))
+                        .collect(),
+                );
+            }
+            SimpleQueryMessage::CommandComplete(n) => {
+                if output.columns.is_empty() {
+                    output.affected = Some(n);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if read_only {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+    }
+    Ok(output)
+}
+
+#[cfg(not(feature = "sql-postgres"))]
+fn run_postgres(
+    _url: &str,
+    _query: &str,
+    _read_only: bool,
+    _redact_columns: Vec<String>,
+    _max_rows: usize,
+    _timeout: Duration,
+) -> anyhow::Result<QueryOutput> {
+    anyhow::bail!("PostgreSQL support is not compiled in. Rebuild with --features sql-postgres")
+}
+
+#[async_trait]
+impl Tool for SqlQueryTool {
+    fn name(&self) -> &str {
+        "sql"
+    }
+
+    fn description(&self) -> &str {
+        "Run a single SQL statement against a configured database connection (SQLite or \
+         PostgreSQL) and return the result as a Markdown table. Connections are read-only unless \
+         configured otherwise; sensitive columns are redacted."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "connection": {
+                    "type": "string",
+                    "description": format!("Connection name. Configured: {}", self.connection_names())
+                },
+                "query": {
+                    "type": "string",
+                    "description": "A single SQL statement"
+                },
+                "max_rows": {
+                    "type": "integer",
+                    "description": "Maximum rows to return (capped by config)",
+                    "minimum": 1
+                }
+            },
+            "required": ["connection", "query"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let name = args
+            .get("connection")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'connection' parameter"))?;
+        let query = args
+            .get("query")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
+        let ceiling = self.config.max_rows.clamp(1, MAX_ROWS_CEILING);
+        let max_rows = args
+            .get("max_rows")
+            .and_then(|v| v.as_u64())
+            .and_then(|n| usize::try_from(n).ok())
+            .map_or(ceiling, |n| n.clamp(1, ceiling));
+
+        let Some(connection) = self.config.connections.get(name) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Unknown connection '{name}'. Configured: {}",
+                    self.connection_names()
+                )),
+            });
+        };
+
+        if !connection.read_only && !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    "Action blocked: writable SQL connections require higher autonomy level".into(),
+                ),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let timeout = Duration::from_secs(self.config.timeout_secs.max(1));
+        let read_only = connection.read_only;
+        let query_owned = query.to_string();
+        let patterns = self.redaction_patterns(connection);
+        let redact_columns = patterns.clone();
+        let result = match connection.driver.trim().to_ascii_lowercase().as_str() {
+            "sqlite" => match self.sqlite_path(connection) {
+                Ok(path) => tokio::task::spawn_blocking(move || {
+                    run_sqlite(
+                        &path,
+                        &query_owned,
+                        read_only,
+                        redact_columns,
+                        max_rows,
+                        timeout,
+                    )
+                })
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|r| r),
+                Err(e) => Err(anyhow::anyhow!(e)),
+            },
+            "postgres" | "postgresql" => match connection.url.clone() {
+                Some(url) => tokio::task::spawn_blocking(move || {
+                    run_postgres(
+                        &url,
+                        &query_owned,
+                        read_only,
+                        redact_columns,
+                        max_rows,
+                        timeout,
+                    )
+                })
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|r| r),
+                None => Err(anyhow::anyhow!(
+                    "PostgreSQL connection has no `url` configured"
+                )),
+            },
+            other => Err(anyhow::anyhow!(
+                "Unsupported driver '{other}'. Use 'sqlite' or 'postgres'"
+            )),
+        };
+
+        match result {
+            Ok(mut output) => {
+                redact(&mut output, &patterns);
+                Ok(ToolResult {
+                    success: true,
+                    output: render_markdown(&output, self.config.max_cell_width),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Query failed on '{name}': {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use std::collections::HashMap;
+
+    fn sqlite_fixture(dir: &std::path::Path) {
+        let conn = rusqlite::Connection::open(dir.join("app.db")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (id INTEGER, name TEXT, password_hash TEXT, note TEXT);
+             INSERT INTO users VALUES (1, 'ada', 'x1', 'a | b');
+             INSERT INTO users VALUES (2, 'grace', 'x2', NULL);
+             INSERT INTO users VALUES (3, 'linus', 'x3', 'a very long note that keeps going');",
+        )
+        .unwrap();
+    }
+
+    fn tool(dir: &std::path::Path, read_only: bool, autonomy: AutonomyLevel) -> SqlQueryTool {
+        let mut connections = HashMap::new();
+        connections.insert(
+            "app".to_string(),
+            SqlConnectionConfig {
+                driver: "sqlite".into(),
+                path: Some("app.db".into()),
+                url: None,
+                read_only,
+                redact_columns: vec!["*_hash".into()],
+            },
+        );
+        SqlQueryTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy,
+                workspace_dir: dir.to_path_buf(),
+                ..SecurityPolicy::default()
+            }),
+            SqlToolConfig {
+                enabled: true,
+                connections,
+                max_cell_width: 16,
+                ..SqlToolConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn column_patterns_support_wildcards() {
+        assert!(column_matches("password", "PASSWORD"));
+        assert!(column_matches("*_hash", "password_hash"));
+        assert!(column_matches("secret*", "secret_key"));
+        assert!(column_matches("*token*", "api_token_value"));
+        assert!(!column_matches("password", "password_hint"));
+    }
+
+    #[test]
+    fn first_keyword_skips_comments() {
+        assert_eq!(first_keyword("  -- hi\n/* x */ attach 'a' as b"), "ATTACH");
+        assert_eq!(first_keyword("select 1"), "SELECT");
+    }
+
+    #[tokio::test]
+    async fn sqlite_select_renders_redacted_markdown_table() {
+        let dir = tempfile::tempdir().unwrap();
+        sqlite_fixture(dir.path());
+        let tool = tool(dir.path(), true, AutonomyLevel::ReadOnly);
+
+        let result = tool
+            .execute(json!({"connection": "app", "query": "SELECT * FROM users ORDER BY id"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let lines: Vec<&str> = result.output.lines().collect();
+        assert_eq!(lines[0], "| id | name | password_hash | note |");
+        assert_eq!(lines[1], "|---|---|---|---|");
+        assert_eq!(lines[2], "| 1 | ada | [REDACTED] | a \\| b |");
+        assert_eq!(lines[3], "| 2 | grace | [REDACTED] | NULL |");
+        assert!(lines[4].contains("| a very long not… |"));
+        assert!(result.output.ends_with("(3 rows)"));
+
+        let result = tool
+            .execute(json!({"connection": "app", "query": "SELECT id FROM users", "max_rows": 2}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("more rows available"));
+    }
+
+    #[tokio::test]
+    async fn sqlite_aliases_expressions_and_filters_cannot_read_redacted_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        sqlite_fixture(dir.path());
+        let tool = tool(dir.path(), true, AutonomyLevel::ReadOnly);
+
+        let result = tool
+            .execute(json!({
+                "connection": "app",
+                "query": "SELECT name, password_hash AS p, upper(password_hash) AS u, \
+                          (SELECT password_hash FROM users LIMIT 1) AS s \
+                          FROM (SELECT name, password_hash FROM users) ORDER BY name"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("| ada |"));
+        assert!(!result.output.to_ascii_lowercase().contains("x1"));
+
+        let result = tool
+            .execute(json!({
+                "connection": "app",
+                "query": "SELECT name FROM users WHERE password_hash = 'x1'"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.ends_with("(0 rows)"));
+    }
+
+    #[tokio::test]
+    async fn read_only_connection_rejects_writes_and_multiple_statements() {
+        let dir = tempfile::tempdir().unwrap();
+        sqlite_fixture(dir.path());
+        let tool = tool(dir.path(), true, AutonomyLevel::Supervised);
+
+        for query in [
+            "DELETE FROM users",
+            "SELECT 1; DELETE FROM users",
+            "ATTACH DATABASE 'other.db' AS other",
+        ] {
+            let result = tool
+                .execute(json!({"connection": "app", "query": query}))
+                .await
+                .unwrap();
+            assert!(!result.success, "{query} should fail");
+        }
+
+        let conn = rusqlite::Connection::open(dir.path().join("app.db")).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM users", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 3);
+    }
+
+    #[tokio::test]
+    async fn writable_connection_reports_affected_rows_and_respects_autonomy() {
+        let dir = tempfile::tempdir().unwrap();
+        sqlite_fixture(dir.path());
+
+        let readonly_agent = tool(dir.path(), false, AutonomyLevel::ReadOnly);
+        let result = readonly_agent
+            .execute(json!({"connection": "app", "query": "DELETE FROM users WHERE id = 1"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+
+        let tool = tool(dir.path(), false, AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({"connection": "app", "query": "DELETE FROM users WHERE id = 1"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "Statement executed; 1 row(s) affected.");
+    }
+
+    #[tokio::test]
+    async fn unknown_connection_lists_configured_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = tool(dir.path(), true, AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({"connection": "prod", "query": "SELECT 1"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Configured: app"));
+    }
+}
@@ -0,0 +1,279 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use crate::tasks::{self, TaskNotify, TaskStore};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Tool that lets the agent enqueue, inspect and cancel background tasks.
+pub struct TaskTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl TaskTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    fn failure(error: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error.into()),
+        }
+    }
+
+    fn enqueue(&self, store: &TaskStore, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "task")
+        {
+            return Ok(Self::failure(error));
+        }
+        let Some(title) = args
+            .get("title")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        else {
+            return Ok(Self::failure("Missing 'title' for enqueue"));
+        };
+        let steps: Vec<String> = args
+            .get("steps")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str())
+                    .map(str::trim)
+                    .filter(|step| !step.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if steps.is_empty() {
+            return Ok(Self::failure(
+                "'steps' must list at least one step for enqueue",
+            ));
+        }
+        let notify = match args.get("notify") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(value) => match serde_json::from_value::<TaskNotify>(value.clone()) {
+                Ok(notify) => Some(notify),
+                Err(e) => {
+                    return Ok(Self::failure(format!(
+                        "Invalid 'notify' (expected {{\"channel\", \"to\"}}): {e}"
+                    )))
+                }
+            },
+        };
+
+        let task = store.enqueue(title, &steps, notify.as_ref())?;
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Queued task #{} '{}' with {} steps. It runs in the background{}.",
+                task.id,
+                task.title,
+                task.steps.len(),
+                if notify.is_some() {
+                    "; progress is reported to the user as steps finish"
+                } else {
+                    ""
+                }
+            ),
+            error: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for TaskTool {
+    fn name(&self) -> &str {
+        "task"
+    }
+
+    fn description(&self) -> &str {
+        "Run long, multi-step jobs in the background. Actions: enqueue (title + steps; each \
+         step runs as its own agent turn with the earlier steps' results), status (e.g. when the \
+         user asks \"how's task #3?\"), list, cancel. When started from a channel, pass \
+         notify={\"channel\":..., \"to\":...} so the user gets progress updates."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["enqueue", "status", "list", "cancel"],
+                    "description": "Action to perform"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Short task title. Required for enqueue."
+                },
+                "steps": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Ordered step instructions. Required for enqueue."
+                },
+                "notify": {
+                    "type": "object",
+                    "properties": {
+                        "channel": {"type": "string"},
+                        "to": {"type": "string"}
+                    },
+                    "required": ["channel", "to"],
+                    "description": "Where to send progress notifications"
+                },
+                "id": {
+                    "type": "integer",
+                    "description": "Task number. Required for status and cancel."
+                },
+                "all": {
+                    "type": "boolean",
+                    "description": "For list: include finished tasks",
+                    "default": false
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.tasks.enabled {
+            return Ok(Self::failure(
+                "Background tasks are disabled ([tasks].enabled = false)",
+            ));
+        }
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let store = TaskStore::open(&self.config.workspace_dir)?;
+        let id = args.get("id").and_then(serde_json::Value::as_i64);
+
+        match (action, id) {
+            ("enqueue", _) => self.enqueue(&store, &args),
+            ("list", _) => {
+                let all = args
+                    .get("all")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false);
+                let tasks = store.list(all, 20)?;
+                let output = if tasks.is_empty() {
+                    "No tasks.".to_string()
+                } else {
+                    tasks
+                        .iter()
+                        .map(tasks::Task::summary_line)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
+            ("status" | "cancel", None) => Ok(Self::failure(format!("Missing 'id' for {action}"))),
+            ("status", Some(id)) => Ok(match store.get(id)? {
+                Some(task) => ToolResult {
+                    success: true,
+                    output: tasks::describe(&task),
+                    error: None,
+                },
+                None => Self::failure(format!("Task #{id} not found")),
+            }),
+            ("cancel", Some(id)) => {
+                if let Err(error) = self
+                    .security
+                    .enforce_tool_operation(ToolOperation::Act, "task")
+                {
+                    return Ok(Self::failure(error));
+                }
+                Ok(if store.cancel(id)? {
+                    ToolResult {
+                        success: true,
+                        output: format!("Cancelled task #{id}"),
+                        error: None,
+                    }
+                } else if store.get(id)?.is_some() {
+                    Self::failure(format!("Task #{id} has already finished"))
+                } else {
+                    Self::failure(format!("Task #{id} not found"))
+                })
+            }
+            (other, _) => Ok(Self::failure(format!(
+                "Unknown action '{other}'. Use enqueue, status, list or cancel."
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tool(tmp: &TempDir) -> TaskTool {
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        TaskTool::new(Arc::new(config), Arc::new(SecurityPolicy::default()))
+    }
+
+    #[tokio::test]
+    async fn enqueue_status_and_cancel() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp);
+
+        let queued = tool
+            .execute(json!({
+                "action": "enqueue",
+                "title": "Audit deps",
+                "steps": ["List dependencies", "Check each for updates"],
+                "notify": {"channel": "telegram", "to": "42"}
+            }))
+            .await
+            .unwrap();
+        assert!(queued.success, "{:?}", queued.error);
+        assert!(queued
+            .output
+            .starts_with("Queued task #1 'Audit deps' with 2 steps"));
+
+        let status = tool
+            .execute(json!({"action": "status", "id": 1}))
+            .await
+            .unwrap();
+        assert!(status.output.contains("#1 [queued] Audit deps (0/2 steps)"));
+        assert!(status.output.contains("2. Check each for updates"));
+
+        let cancelled = tool
+            .execute(json!({"action": "cancel", "id": 1}))
+            .await
+            .unwrap();
+        assert!(cancelled.success);
+        let again = tool
+            .execute(json!({"action": "cancel", "id": 1}))
+            .await
+            .unwrap();
+        assert!(again.error.unwrap().contains("already finished"));
+    }
+
+    #[tokio::test]
+    async fn enqueue_requires_steps() {
+        let tmp = TempDir::new().unwrap();
+        let result = tool(&tmp)
+            .execute(json!({"action": "enqueue", "title": "Nothing", "steps": []}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("at least one step"));
+    }
+}
@@ -0,0 +1,48 @@
+//! Process-wide tool switches set through the daemon control socket.
+//!
+//! Tools disabled here are excluded from every turn the process runs, on
+//! top of per-conversation `/tools disable` and `non_cli_excluded_tools`.
+
+use parking_lot::Mutex;
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+static DISABLED: OnceLock<Mutex<BTreeSet<String>>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<BTreeSet<String>> {
+    DISABLED.get_or_init(|| Mutex::new(BTreeSet::new()))
+}
+
+/// Names of the tools currently disabled, sorted.
+pub fn disabled() -> Vec<String> {
+    registry().lock().iter().cloned().collect()
+}
+
+/// Enable or disable `names` and return the resulting disabled list.
+pub fn set_enabled(names: &[String], enabled: bool) -> Vec<String> {
+    let mut disabled = registry().lock();
+    for name in names {
+        if enabled {
+            disabled.remove(name);
+        } else {
+            disabled.insert(name.clone());
+        }
+    }
+    disabled.iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggles_round_trip() {
+        let names = vec!["toggle_test_a".to_string(), "toggle_test_b".to_string()];
+        let after = set_enabled(&names, false);
+        assert!(names.iter().all(|name| after.contains(name)));
+        let after = set_enabled(&names[..1], true);
+        assert!(!after.contains(&names[0]));
+        assert!(disabled().contains(&names[1]));
+        set_enabled(&names, true);
+    }
+}
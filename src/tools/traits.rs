@@ -1,4 +1,6 @@
+use crate::security::Role;
 use async_trait::async_trait;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Result of a tool execution
@@ -17,6 +19,32 @@ pub struct ToolSpec {
     pub parameters: serde_json::Value,
 }
 
+/// How much damage a tool call can do if the model gets it wrong.
+///
+/// `[autonomy.risk_approval]` maps each level to allow, deny or ask.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolRisk {
+    /// Reads data without side effects.
+    Low,
+    /// Changes local state or talks to external services.
+    Medium,
+    /// Runs arbitrary code or makes hard-to-reverse changes.
+    High,
+}
+
+impl ToolRisk {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
 /// Core tool trait — implement for any capability
 #[async_trait]
 pub trait Tool: Send + Sync {
@@ -32,6 +60,30 @@ pub trait Tool: Send + Sync {
     /// Execute the tool with given arguments
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult>;
 
+    /// Describe what [`Tool::execute`] would do with `args` without doing
+    /// it, for dry-run mode (`--dry-run`, `/dryrun on`). Only asked of tools
+    /// above [`ToolRisk::Low`]; the default names the call and its arguments.
+    async fn dry_run(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        Ok(format!("Would call {} with {args}", self.name()))
+    }
+
+    /// Risk level used by the approval policy. Tools with no side effects
+    /// should override this with [`ToolRisk::Low`].
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Medium
+    }
+
+    /// Least privileged chat user role that may call this tool
+    /// (`[security.roles]`). Read-only tools are open to members; the rest
+    /// need an admin.
+    fn min_role(&self) -> Role {
+        if self.risk() == ToolRisk::Low {
+            Role::Member
+        } else {
+            Role::Admin
+        }
+    }
+
     /// Get the full spec for LLM registration
     fn spec(&self) -> ToolSpec {
         ToolSpec {
@@ -80,6 +132,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn default_risk_is_medium() {
+        assert_eq!(DummyTool.risk(), ToolRisk::Medium);
+        assert_eq!(DummyTool.min_role(), Role::Admin);
+        assert!(ToolRisk::Low < ToolRisk::Medium && ToolRisk::Medium < ToolRisk::High);
+        assert_eq!(ToolRisk::High.as_str(), "high");
+    }
+
     #[test]
     fn spec_uses_tool_metadata_and_schema() {
         let tool = DummyTool;
@@ -0,0 +1,39 @@
+//! Per-turn action counts for tools that cap how much one agent turn may do
+//! (e.g. `[browser].max_actions_per_turn`).
+//!
+//! Tool instances are shared by every session of a channel or the gateway,
+//! so the counts live with the turn instead: each turn runs inside
+//! [`run_with`], and concurrent turns count separately.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+tokio::task_local! {
+    static CURRENT: Arc<Mutex<HashMap<String, usize>>>;
+}
+
+/// Run one agent turn with fresh action counts.
+pub async fn run_with<F: Future>(future: F) -> F::Output {
+    CURRENT.scope(Arc::default(), future).await
+}
+
+/// Reserve one of `tool`'s `limit` actions in the running turn (0 =
+/// unlimited). Calls outside a turn are not counted.
+pub fn take_action(tool: &str, limit: usize) -> bool {
+    if limit == 0 {
+        return true;
+    }
+    CURRENT
+        .try_with(|counts| {
+            let mut counts = counts.lock().unwrap_or_else(|e| e.into_inner());
+            let used = counts.entry(tool.to_string()).or_default();
+            if *used < limit {
+                *used += 1;
+                true
+            } else {
+                false
+            }
+        })
+        .unwrap_or(true)
+}
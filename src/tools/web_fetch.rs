@@ -1,3 +1,4 @@
+use super::html_extract;
 use super::traits::{Tool, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -6,13 +7,18 @@ use serde_json::json;
 use std::sync::Arc;
 use std::time::Duration;
 
-/// Web fetch tool: fetches a web page and converts HTML to plain text for LLM consumption.
+/// Rough characters-per-token ratio used for the output budget.
+const CHARS_PER_TOKEN: usize = 4;
+const DEFAULT_MAX_OUTPUT_TOKENS: usize = 8_000;
+
+/// Web fetch tool: fetches a web page and converts it to Markdown for LLM consumption.
 ///
 /// Unlike `http_request` (an API client returning raw responses), this tool:
 /// - Only supports GET
 /// - Follows redirects (up to 10)
-/// - Converts HTML to clean plain text via `nanohtml2text`
+/// - Extracts the main content of HTML pages (readability-style) as Markdown
 /// - Passes through text/plain, text/markdown, and application/json as-is
+/// - Truncates the result to a token budget
 /// - Sets a descriptive User-Agent
 pub struct WebFetchTool {
     security: Arc<SecurityPolicy>,
@@ -20,6 +26,7 @@ pub struct WebFetchTool {
     blocked_domains: Vec<String>,
     max_response_size: usize,
     timeout_secs: u64,
+    max_output_tokens: usize,
 }
 
 impl WebFetchTool {
@@ -36,9 +43,16 @@ impl WebFetchTool {
             blocked_domains: normalize_allowed_domains(blocked_domains),
             max_response_size,
             timeout_secs,
+            max_output_tokens: DEFAULT_MAX_OUTPUT_TOKENS,
         }
     }
 
+    /// Upper bound on the (estimated) tokens returned to the model.
+    pub fn with_max_output_tokens(mut self, max_output_tokens: usize) -> Self {
+        self.max_output_tokens = max_output_tokens.max(1);
+        self
+    }
+
     fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
         validate_target_url(
             raw_url,
@@ -87,9 +101,10 @@ impl Tool for WebFetchTool {
     }
 
     fn description(&self) -> &str {
-        "Fetch a web page and return its content as clean plain text. \
-         HTML pages are automatically converted to readable text. \
+        "Fetch a web page and return its main content as Markdown. \
+         Navigation, ads and other boilerplate are stripped from HTML pages; \
          JSON and plain text responses are returned as-is. \
+         Output is truncated to a token budget. \
          Only GET requests; follows redirects. \
          Security: allowlist-only domains, no local/private hosts."
     }
@@ -101,6 +116,14 @@ impl Tool for WebFetchTool {
                 "url": {
                     "type": "string",
                     "description": "The HTTP or HTTPS URL to fetch"
+                },
+                "max_tokens": {
+                    "type": "integer",
+                    "description": "Approximate token budget for the returned content (capped by config)"
+                },
+                "raw": {
+                    "type": "boolean",
+                    "description": "Convert the whole page instead of only the extracted main content (default: false)"
                 }
             },
             "required": ["url"]
@@ -112,6 +135,14 @@ impl Tool for WebFetchTool {
             .get("url")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'url' parameter"))?;
+        let raw = args.get("raw").and_then(|v| v.as_bool()).unwrap_or(false);
+        let max_tokens = args
+            .get("max_tokens")
+            .and_then(|v| v.as_u64())
+            .and_then(|n| usize::try_from(n).ok())
+            .map_or(self.max_output_tokens, |n| {
+                n.clamp(1, self.max_output_tokens)
+            });
 
         if !self.security.can_act() {
             return Ok(ToolResult {
@@ -187,16 +218,18 @@ impl Tool for WebFetchTool {
             }
         };
 
-        let response = match client.get(&url).send().await {
-            Ok(r) => r,
-            Err(e) => {
-                return Ok(ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("HTTP request failed: {e}")),
-                })
-            }
-        };
+        let endpoint = format!("tool:web_fetch:{}", extract_host(&url).unwrap_or_default());
+        let response =
+            match crate::resilience::send_http(&endpoint, true, || client.get(&url)).await {
+                Ok(r) => r,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("HTTP request failed: {e}")),
+                    })
+                }
+            };
 
         let status = response.status();
         if !status.is_success() {
@@ -211,6 +244,8 @@ impl Tool for WebFetchTool {
             });
         }
 
+        let final_url = response.url().to_string();
+
         // Determine content type for processing strategy
         let content_type = response
             .headers()
@@ -249,12 +284,18 @@ impl Tool for WebFetchTool {
         };
 
         let text = if body_mode == "html" {
-            nanohtml2text::html2text(&body)
+            let extracted = html_extract::extract(&body, &final_url, !raw);
+            let mut text = String::new();
+            if let Some(title) = extracted.title {
+                text.push_str(&format!("# {title}\n"));
+            }
+            text.push_str(&format!("Source: {final_url}\n\n{}", extracted.markdown));
+            text
         } else {
             body
         };
 
-        let output = self.truncate_response(&text);
+        let output = truncate_to_token_budget(&self.truncate_response(&text), max_tokens);
 
         Ok(ToolResult {
             success: true,
@@ -264,6 +305,30 @@ impl Tool for WebFetchTool {
     }
 }
 
+/// Cut `text` to roughly `max_tokens`, preferring a paragraph boundary.
+fn truncate_to_token_budget(text: &str, max_tokens: usize) -> String {
+    let max_chars = max_tokens.saturating_mul(CHARS_PER_TOKEN);
+    let total_chars = text.chars().count();
+    if total_chars <= max_chars {
+        return text.to_string();
+    }
+
+    let cut = text
+        .char_indices()
+        .nth(max_chars)
+        .map_or(text.len(), |(i, _)| i);
+    let head = &text[..cut];
+    let head = match head.rfind("\n\n") {
+        Some(i) if i >= cut / 2 => &head[..i],
+        _ => head,
+    };
+    format!(
+        "{}\n\n[Truncated: showing ~{max_tokens} of ~{} tokens. Request a larger max_tokens to read more.]",
+        head.trim_end(),
+        total_chars / CHARS_PER_TOKEN
+    )
+}
+
 // ── Helper functions (independent from http_request.rs per DRY rule-of-three) ──
 
 fn validate_target_url(
@@ -549,13 +614,13 @@ mod tests {
         assert!(required.iter().any(|v| v.as_str() == Some("url")));
     }
 
-    // ── HTML to text conversion ──────────────────────────────────
+    // ── HTML to Markdown conversion ──────────────────────────────
 
     #[test]
     fn html_to_text_conversion() {
         let html = "<html><body><h1>Title</h1><p>Hello <b>world</b></p></body></html>";
-        let text = nanohtml2text::html2text(html);
-        assert!(text.contains("Title"));
+        let text = html_extract::extract(html, "https://example.com/", true).markdown;
+        assert!(text.contains("# Title"));
         assert!(text.contains("Hello"));
         assert!(text.contains("world"));
         assert!(!text.contains("<h1>"));
@@ -761,6 +826,23 @@ mod tests {
         assert!(truncated.contains("[Response truncated"));
     }
 
+    #[test]
+    fn token_budget_truncates_at_paragraph_boundary() {
+        let text = format!("{}\n\n{}", "a".repeat(30), "b".repeat(30));
+        let truncated = truncate_to_token_budget(&text, 10);
+        assert!(truncated.starts_with(&"a".repeat(30)));
+        assert!(!truncated.contains('b'));
+        assert!(truncated.contains("[Truncated: showing ~10 of ~15 tokens"));
+
+        assert_eq!(truncate_to_token_budget("short", 10), "short");
+    }
+
+    #[test]
+    fn max_output_tokens_builder_enforces_minimum() {
+        let tool = test_tool(vec!["example.com"]).with_max_output_tokens(0);
+        assert_eq!(tool.max_output_tokens, 1);
+    }
+
     // ── Domain normalization ─────────────────────────────────────
 
     #[test]
@@ -1,43 +1,60 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolResult, ToolRisk};
 use async_trait::async_trait;
 use regex::Regex;
 use serde_json::json;
 use std::time::Duration;
 
-/// Web search tool for searching the internet.
-/// Supports multiple providers: DuckDuckGo (free), Brave (requires API key).
-pub struct WebSearchTool {
-    provider: String,
-    brave_api_key: Option<String>,
-    max_results: usize,
-    timeout_secs: u64,
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// A single search hit the model can cite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
 }
 
-impl WebSearchTool {
-    pub fn new(
-        provider: String,
-        brave_api_key: Option<String>,
+/// A web search backend.
+///
+/// Engines only fetch and parse; result limiting, formatting and client
+/// construction (timeouts, proxy) are shared by [`WebSearchTool`].
+#[async_trait]
+pub trait SearchEngine: Send + Sync {
+    /// Display name used in tool output.
+    fn name(&self) -> &str;
+
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
         max_results: usize,
-        timeout_secs: u64,
-    ) -> Self {
-        Self {
-            provider: provider.trim().to_lowercase(),
-            brave_api_key,
-            max_results: max_results.clamp(1, 10),
-            timeout_secs: timeout_secs.max(1),
-        }
-    }
+    ) -> anyhow::Result<Vec<SearchResult>>;
+}
 
-    async fn search_duckduckgo(&self, query: &str) -> anyhow::Result<String> {
-        let encoded_query = urlencoding::encode(query);
-        let search_url = format!("https://html.duckduckgo.com/html/?q={}", encoded_query);
+/// DuckDuckGo HTML endpoint (no API key).
+pub struct DuckDuckGoEngine;
 
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(self.timeout_secs))
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-            .build()?;
+#[async_trait]
+impl SearchEngine for DuckDuckGoEngine {
+    fn name(&self) -> &str {
+        "DuckDuckGo"
+    }
 
-        let response = client.get(&search_url).send().await?;
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        let search_url = format!(
+            "https://html.duckduckgo.com/html/?q={}",
+            urlencoding::encode(query)
+        );
+        let response = client
+            .get(&search_url)
+            .header("User-Agent", BROWSER_USER_AGENT)
+            .send()
+            .await?;
 
         if !response.status().is_success() {
             anyhow::bail!(
@@ -47,121 +64,262 @@ impl WebSearchTool {
         }
 
         let html = response.text().await?;
-        self.parse_duckduckgo_results(&html, query)
+        parse_duckduckgo_results(&html, max_results)
     }
+}
 
-    fn parse_duckduckgo_results(&self, html: &str, query: &str) -> anyhow::Result<String> {
-        // Extract result links: <a class="result__a" href="...">Title</a>
-        let link_regex = Regex::new(
-            r#"<a[^>]*class="[^"]*result__a[^"]*"[^>]*href="([^"]+)"[^>]*>([\s\S]*?)</a>"#,
-        )?;
-
-        // Extract snippets: <a class="result__snippet">...</a>
-        let snippet_regex = Regex::new(r#"<a class="result__snippet[^"]*"[^>]*>([\s\S]*?)</a>"#)?;
+/// Brave Search API (requires `brave_api_key`).
+pub struct BraveEngine {
+    api_key: String,
+}
 
-        let link_matches: Vec<_> = link_regex
-            .captures_iter(html)
-            .take(self.max_results + 2)
-            .collect();
+#[async_trait]
+impl SearchEngine for BraveEngine {
+    fn name(&self) -> &str {
+        "Brave"
+    }
 
-        let snippet_matches: Vec<_> = snippet_regex
-            .captures_iter(html)
-            .take(self.max_results + 2)
-            .collect();
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        let search_url = format!(
+            "https://api.search.brave.com/res/v1/web/search?q={}&count={}",
+            urlencoding::encode(query),
+            max_results
+        );
+        let response = client
+            .get(&search_url)
+            .header("Accept", "application/json")
+            .header("X-Subscription-Token", &self.api_key)
+            .send()
+            .await?;
 
-        if link_matches.is_empty() {
-            return Ok(format!("No results found for: {}", query));
+        if !response.status().is_success() {
+            anyhow::bail!("Brave search failed with status: {}", response.status());
         }
 
-        let mut lines = vec![format!("Search results for: {} (via DuckDuckGo)", query)];
-
-        let count = link_matches.len().min(self.max_results);
-
-        for i in 0..count {
-            let caps = &link_matches[i];
-            let url_str = decode_ddg_redirect_url(&caps[1]);
-            let title = strip_tags(&caps[2]);
-
-            lines.push(format!("{}. {}", i + 1, title.trim()));
-            lines.push(format!("   {}", url_str.trim()));
+        let json: serde_json::Value = response.json().await?;
+        parse_brave_results(&json, max_results)
+    }
+}
 
-            // Add snippet if available
-            if i < snippet_matches.len() {
-                let snippet = strip_tags(&snippet_matches[i][1]);
-                let snippet = snippet.trim();
-                if !snippet.is_empty() {
-                    lines.push(format!("   {}", snippet));
-                }
-            }
-        }
+/// Self-hosted SearXNG instance (JSON output must be enabled in its
+/// `settings.yml` under `search.formats`).
+pub struct SearxngEngine {
+    base_url: String,
+}
 
-        Ok(lines.join("\n"))
+#[async_trait]
+impl SearchEngine for SearxngEngine {
+    fn name(&self) -> &str {
+        "SearXNG"
     }
 
-    async fn search_brave(&self, query: &str) -> anyhow::Result<String> {
-        let api_key = self
-            .brave_api_key
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Brave API key not configured"))?;
-
-        let encoded_query = urlencoding::encode(query);
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<SearchResult>> {
         let search_url = format!(
-            "https://api.search.brave.com/res/v1/web/search?q={}&count={}",
-            encoded_query, self.max_results
+            "{}/search?q={}&format=json",
+            self.base_url,
+            urlencoding::encode(query)
         );
-
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(self.timeout_secs))
-            .build()?;
-
         let response = client
             .get(&search_url)
             .header("Accept", "application/json")
-            .header("X-Subscription-Token", api_key)
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("Brave search failed with status: {}", response.status());
+        let status = response.status();
+        if status == reqwest::StatusCode::FORBIDDEN {
+            anyhow::bail!(
+                "SearXNG rejected the JSON request (403); add `json` to search.formats in the instance settings"
+            );
+        }
+        if !status.is_success() {
+            anyhow::bail!("SearXNG search failed with status: {status}");
         }
 
         let json: serde_json::Value = response.json().await?;
-        self.parse_brave_results(&json, query)
+        parse_searxng_results(&json, max_results)
     }
+}
 
-    fn parse_brave_results(&self, json: &serde_json::Value, query: &str) -> anyhow::Result<String> {
-        let results = json
-            .get("web")
-            .and_then(|w| w.get("results"))
-            .and_then(|r| r.as_array())
-            .ok_or_else(|| anyhow::anyhow!("Invalid Brave API response"))?;
-
-        if results.is_empty() {
-            return Ok(format!("No results found for: {}", query));
+/// Build the engine for `provider`, validating its required settings.
+pub fn create_search_engine(
+    provider: &str,
+    brave_api_key: Option<&str>,
+    searxng_url: Option<&str>,
+) -> anyhow::Result<Box<dyn SearchEngine>> {
+    match provider.trim().to_ascii_lowercase().as_str() {
+        "duckduckgo" | "ddg" => Ok(Box::new(DuckDuckGoEngine)),
+        "brave" => {
+            let api_key = brave_api_key
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .ok_or_else(|| anyhow::anyhow!("Brave API key not configured"))?;
+            Ok(Box::new(BraveEngine {
+                api_key: api_key.to_string(),
+            }))
         }
-
-        let mut lines = vec![format!("Search results for: {} (via Brave)", query)];
-
-        for (i, result) in results.iter().take(self.max_results).enumerate() {
-            let title = result
-                .get("title")
-                .and_then(|t| t.as_str())
-                .unwrap_or("No title");
-            let url = result.get("url").and_then(|u| u.as_str()).unwrap_or("");
-            let description = result
-                .get("description")
-                .and_then(|d| d.as_str())
-                .unwrap_or("");
-
-            lines.push(format!("{}. {}", i + 1, title));
-            lines.push(format!("   {}", url));
-            if !description.is_empty() {
-                lines.push(format!("   {}", description));
+        "searxng" | "searx" => {
+            let base_url = searxng_url
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("SearXNG URL not configured (set web_search.searxng_url)")
+                })?;
+            if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+                anyhow::bail!("web_search.searxng_url must start with http:// or https://");
             }
+            Ok(Box::new(SearxngEngine {
+                base_url: base_url.trim_end_matches('/').to_string(),
+            }))
+        }
+        other => anyhow::bail!(
+            "Unknown search provider: '{other}'. Set web_search.provider to 'duckduckgo', 'brave' or 'searxng' in config.toml"
+        ),
+    }
+}
+
+/// Web search tool for searching the internet.
+/// Supports multiple engines: DuckDuckGo (free), Brave (requires API key),
+/// SearXNG (self-hosted).
+pub struct WebSearchTool {
+    provider: String,
+    brave_api_key: Option<String>,
+    searxng_url: Option<String>,
+    max_results: usize,
+    timeout_secs: u64,
+}
+
+impl WebSearchTool {
+    pub fn new(
+        provider: String,
+        brave_api_key: Option<String>,
+        max_results: usize,
+        timeout_secs: u64,
+    ) -> Self {
+        Self {
+            provider: provider.trim().to_lowercase(),
+            brave_api_key,
+            searxng_url: None,
+            max_results: max_results.clamp(1, 10),
+            timeout_secs: timeout_secs.max(1),
         }
+    }
+
+    /// Base URL of the SearXNG instance used when `provider = "searxng"`.
+    pub fn with_searxng_url(mut self, searxng_url: Option<String>) -> Self {
+        self.searxng_url = searxng_url;
+        self
+    }
+
+    fn build_client(&self) -> anyhow::Result<reqwest::Client> {
+        let builder = reqwest::Client::builder().timeout(Duration::from_secs(self.timeout_secs));
+        let builder = crate::config::apply_runtime_proxy_to_builder(builder, "tool.web_search");
+        Ok(builder.build()?)
+    }
+}
 
-        Ok(lines.join("\n"))
+/// Render results as a numbered list of title / URL / snippet.
+fn format_results(engine: &str, query: &str, results: &[SearchResult]) -> String {
+    if results.is_empty() {
+        return format!("No results found for: {query}");
+    }
+
+    let mut lines = vec![format!("Search results for: {query} (via {engine})")];
+    for (i, result) in results.iter().enumerate() {
+        lines.push(format!("{}. {}", i + 1, result.title));
+        lines.push(format!("   {}", result.url));
+        if !result.snippet.is_empty() {
+            lines.push(format!("   {}", result.snippet));
+        }
     }
+    lines.join("\n")
+}
+
+fn parse_duckduckgo_results(html: &str, max_results: usize) -> anyhow::Result<Vec<SearchResult>> {
+    // Extract result links: <a class="result__a" href="...">Title</a>
+    let link_regex =
+        Regex::new(r#"<a[^>]*class="[^"]*result__a[^"]*"[^>]*href="([^"]+)"[^>]*>([\s\S]*?)</a>"#)?;
+
+    // Extract snippets: <a class="result__snippet">...</a>
+    let snippet_regex = Regex::new(r#"<a class="result__snippet[^"]*"[^>]*>([\s\S]*?)</a>"#)?;
+
+    let snippets: Vec<String> = snippet_regex
+        .captures_iter(html)
+        .take(max_results)
+        .map(|caps| strip_tags(&caps[1]).trim().to_string())
+        .collect();
+
+    Ok(link_regex
+        .captures_iter(html)
+        .take(max_results)
+        .enumerate()
+        .map(|(i, caps)| SearchResult {
+            title: strip_tags(&caps[2]).trim().to_string(),
+            url: decode_ddg_redirect_url(&caps[1]).trim().to_string(),
+            snippet: snippets.get(i).cloned().unwrap_or_default(),
+        })
+        .collect())
+}
+
+fn json_str<'a>(value: &'a serde_json::Value, key: &str) -> &'a str {
+    value.get(key).and_then(|v| v.as_str()).unwrap_or("")
+}
+
+fn parse_brave_results(
+    json: &serde_json::Value,
+    max_results: usize,
+) -> anyhow::Result<Vec<SearchResult>> {
+    let results = json
+        .get("web")
+        .and_then(|w| w.get("results"))
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid Brave API response"))?;
+
+    Ok(results
+        .iter()
+        .take(max_results)
+        .map(|result| SearchResult {
+            title: match json_str(result, "title") {
+                "" => "No title".to_string(),
+                title => strip_tags(title),
+            },
+            url: json_str(result, "url").to_string(),
+            snippet: strip_tags(json_str(result, "description")),
+        })
+        .collect())
+}
+
+fn parse_searxng_results(
+    json: &serde_json::Value,
+    max_results: usize,
+) -> anyhow::Result<Vec<SearchResult>> {
+    let results = json
+        .get("results")
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid SearXNG response"))?;
+
+    Ok(results
+        .iter()
+        .filter(|result| !json_str(result, "url").is_empty())
+        .take(max_results)
+        .map(|result| SearchResult {
+            title: match json_str(result, "title") {
+                "" => "No title".to_string(),
+                title => title.to_string(),
+            },
+            url: json_str(result, "url").to_string(),
+            snippet: json_str(result, "content").trim().to_string(),
+        })
+        .collect())
 }
 
 fn decode_ddg_redirect_url(raw_url: &str) -> String {
@@ -188,7 +346,7 @@ impl Tool for WebSearchTool {
     }
 
     fn description(&self) -> &str {
-        "Search the web for information. Returns relevant search results with titles, URLs, and descriptions. Use this to find current information, news, or research topics."
+        "Search the web for information. Returns numbered results with title, URL, and snippet; cite results by URL. Use this to find current information, news, or research topics."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -198,12 +356,20 @@ impl Tool for WebSearchTool {
                 "query": {
                     "type": "string",
                     "description": "The search query. Be specific for better results."
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Number of results to return (1-10, default from config)"
                 }
             },
             "required": ["query"]
         })
     }
 
+    fn risk(&self) -> ToolRisk {
+        ToolRisk::Low
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let query = args
             .get("query")
@@ -214,20 +380,27 @@ impl Tool for WebSearchTool {
             anyhow::bail!("Search query cannot be empty");
         }
 
-        tracing::info!("Searching web for: {}", query);
+        let max_results = args
+            .get("max_results")
+            .and_then(|v| v.as_u64())
+            .map_or(self.max_results, |n| {
+                usize::try_from(n).unwrap_or(10).clamp(1, 10)
+            });
+
+        let engine = create_search_engine(
+            &self.provider,
+            self.brave_api_key.as_deref(),
+            self.searxng_url.as_deref(),
+        )?;
+
+        tracing::info!("Searching web for: {} (via {})", query, engine.name());
 
-        let result = match self.provider.as_str() {
-            "duckduckgo" | "ddg" => self.search_duckduckgo(query).await?,
-            "brave" => self.search_brave(query).await?,
-            _ => anyhow::bail!(
-                "Unknown search provider: '{}'. Set tools.web_search.provider to 'duckduckgo' or 'brave' in config.toml",
-                self.provider
-            ),
-        };
+        let client = self.build_client()?;
+        let results = engine.search(&client, query, max_results).await?;
 
         Ok(ToolResult {
             success: true,
-            output: result,
+            output: format_results(engine.name(), query, &results),
             error: None,
         })
     }
@@ -263,48 +436,105 @@ mod tests {
         assert_eq!(strip_tags(html), "Hello World");
     }
 
+    #[test]
+    fn test_format_results_empty() {
+        let output = format_results("DuckDuckGo", "test", &[]);
+        assert!(output.contains("No results found"));
+    }
+
     #[test]
     fn test_parse_duckduckgo_results_empty() {
-        let tool = WebSearchTool::new("duckduckgo".to_string(), None, 5, 15);
-        let result = tool
-            .parse_duckduckgo_results("<html>No results here</html>", "test")
-            .unwrap();
-        assert!(result.contains("No results found"));
+        let results = parse_duckduckgo_results("<html>No results here</html>", 5).unwrap();
+        assert!(results.is_empty());
     }
 
     #[test]
     fn test_parse_duckduckgo_results_with_data() {
-        let tool = WebSearchTool::new("duckduckgo".to_string(), None, 5, 15);
         let html = r#"
             <a class="result__a" href="https://example.com">Example Title</a>
             <a class="result__snippet">This is a description</a>
         "#;
-        let result = tool.parse_duckduckgo_results(html, "test").unwrap();
-        assert!(result.contains("Example Title"));
-        assert!(result.contains("https://example.com"));
+        let results = parse_duckduckgo_results(html, 5).unwrap();
+        assert_eq!(
+            results,
+            vec![SearchResult {
+                title: "Example Title".into(),
+                url: "https://example.com".into(),
+                snippet: "This is a description".into(),
+            }]
+        );
+
+        let output = format_results("DuckDuckGo", "test", &results);
+        assert!(output.starts_with("Search results for: test (via DuckDuckGo)"));
+        assert!(
+            output.contains("1. Example Title\n   https://example.com\n   This is a description")
+        );
     }
 
     #[test]
     fn test_parse_duckduckgo_results_decodes_redirect_url() {
-        let tool = WebSearchTool::new("duckduckgo".to_string(), None, 5, 15);
         let html = r#"
             <a class="result__a" href="https://duckduckgo.com/l/?uddg=https%3A%2F%2Fexample.com%2Fpath%3Fa%3D1&amp;rut=test">Example Title</a>
             <a class="result__snippet">This is a description</a>
         "#;
-        let result = tool.parse_duckduckgo_results(html, "test").unwrap();
-        assert!(result.contains("https://example.com/path?a=1"));
-        assert!(!result.contains("rut=test"));
+        let results = parse_duckduckgo_results(html, 5).unwrap();
+        assert_eq!(results[0].url, "https://example.com/path?a=1");
+    }
+
+    #[test]
+    fn test_parse_brave_results() {
+        let json = json!({"web": {"results": [
+            {"title": "<strong>Rust</strong>", "url": "https://rust-lang.org", "description": "A language"},
+            {"title": "Second", "url": "https://example.com"}
+        ]}});
+        let results = parse_brave_results(&json, 1).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Rust");
+        assert_eq!(results[0].snippet, "A language");
+
+        assert!(parse_brave_results(&json!({}), 5).is_err());
+    }
+
+    #[test]
+    fn test_parse_searxng_results() {
+        let json = json!({"results": [
+            {"title": "No url"},
+            {"title": "SearXNG", "url": "https://docs.searxng.org", "content": " Privacy search "}
+        ]});
+        let results = parse_searxng_results(&json, 5).unwrap();
+        assert_eq!(
+            results,
+            vec![SearchResult {
+                title: "SearXNG".into(),
+                url: "https://docs.searxng.org".into(),
+                snippet: "Privacy search".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_create_search_engine_validates_settings() {
+        assert_eq!(
+            create_search_engine("DDG", None, None).unwrap().name(),
+            "DuckDuckGo"
+        );
+        assert_eq!(
+            create_search_engine("searxng", None, Some("https://search.example.org/"))
+                .unwrap()
+                .name(),
+            "SearXNG"
+        );
+        assert!(create_search_engine("searxng", None, None).is_err());
+        assert!(create_search_engine("searxng", None, Some("search.example.org")).is_err());
+        assert!(create_search_engine("brave", Some("  "), None).is_err());
+        assert!(create_search_engine("bing", None, None).is_err());
     }
 
     #[test]
     fn test_constructor_clamps_web_search_limits() {
         let tool = WebSearchTool::new("duckduckgo".to_string(), None, 0, 0);
-        let html = r#"
-            <a class="result__a" href="https://example.com">Example Title</a>
-            <a class="result__snippet">This is a description</a>
-        "#;
-        let result = tool.parse_duckduckgo_results(html, "test").unwrap();
-        assert!(result.contains("Example Title"));
+        assert_eq!(tool.max_results, 1);
+        assert_eq!(tool.timeout_secs, 1);
     }
 
     #[tokio::test]
@@ -328,4 +558,11 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("API key"));
     }
+
+    #[tokio::test]
+    async fn test_execute_searxng_without_url() {
+        let tool = WebSearchTool::new("searxng".to_string(), None, 5, 15);
+        let result = tool.execute(json!({"query": "test"})).await;
+        assert!(result.unwrap_err().to_string().contains("searxng_url"));
+    }
 }
@@ -0,0 +1,399 @@
+//! TUI state: the transcript, the streaming reply, tool-call activity and
+//! the multi-line input box, plus how key and mouse events change them.
+
+use ratatui::crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEventKind,
+};
+
+/// Tool-call activity lines kept for the sidebar.
+const MAX_ACTIVITY_LINES: usize = 200;
+
+/// Lines scrolled per mouse wheel notch.
+const WHEEL_LINES: usize = 3;
+
+/// Lines scrolled per PageUp/PageDown.
+const PAGE_LINES: usize = 10;
+
+/// Who a transcript entry is from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Assistant,
+    /// Command output, approval prompts and other local notes.
+    Notice,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub role: Role,
+    pub text: String,
+}
+
+/// What the event loop should do after an input event.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+    None,
+    /// Send this message (or command) to the agent.
+    Submit(String),
+    /// Stop the running turn.
+    Interrupt,
+    /// Open the input box's text in `$EDITOR`.
+    Edit(String),
+    Quit,
+}
+
+/// Multi-line input with a cursor; `col` counts chars, not bytes.
+#[derive(Debug)]
+pub struct Input {
+    lines: Vec<String>,
+    row: usize,
+    col: usize,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+        }
+    }
+}
+
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices().nth(col).map_or(line.len(), |(i, _)| i)
+}
+
+impl Input {
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Cursor as (row, char column).
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.len() == 1 && self.lines[0].is_empty()
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Take the text and reset the box.
+    pub fn take(&mut self) -> String {
+        let text = self.text();
+        *self = Self::default();
+        text
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        let line = &mut self.lines[self.row];
+        line.insert(byte_index(line, self.col), c);
+        self.col += 1;
+    }
+
+    pub fn insert_str(&mut self, text: &str) {
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.newline();
+            }
+            for c in part.chars().filter(|c| *c != '\r') {
+                self.insert_char(c);
+            }
+        }
+    }
+
+    pub fn newline(&mut self) {
+        let line = &mut self.lines[self.row];
+        let rest = line.split_off(byte_index(line, self.col));
+        self.row += 1;
+        self.col = 0;
+        self.lines.insert(self.row, rest);
+    }
+
+    pub fn backspace(&mut self) {
+        if self.col > 0 {
+            let line = &mut self.lines[self.row];
+            line.remove(byte_index(line, self.col - 1));
+            self.col -= 1;
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.lines[self.row].chars().count();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        let len = self.lines[self.row].chars().count();
+        if self.col < len {
+            let line = &mut self.lines[self.row];
+            line.remove(byte_index(line, self.col));
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    pub fn left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.lines[self.row].chars().count();
+        }
+    }
+
+    pub fn right(&mut self) {
+        if self.col < self.lines[self.row].chars().count() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    pub fn up(&mut self) {
+        if self.row > 0 {
+            self.row -= 1;
+            self.col = self.col.min(self.lines[self.row].chars().count());
+        }
+    }
+
+    pub fn down(&mut self) {
+        if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = self.col.min(self.lines[self.row].chars().count());
+        }
+    }
+
+    pub fn home(&mut self) {
+        self.col = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.col = self.lines[self.row].chars().count();
+    }
+}
+
+/// Everything the TUI draws.
+pub struct App {
+    pub title: String,
+    pub entries: Vec<Entry>,
+    /// The reply being streamed, once the agent has started its answer.
+    pub streaming: Option<String>,
+    /// Progress lines of the current and earlier turns, newest last.
+    pub activity: Vec<String>,
+    pub input: Input,
+    /// Transcript lines scrolled up from the bottom.
+    pub scroll: usize,
+    /// A turn is running.
+    pub busy: bool,
+    /// Render replies as markdown.
+    pub markdown: bool,
+    /// Generated title of the session, shown atop the sidebar.
+    pub session_title: Option<String>,
+}
+
+impl App {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            entries: Vec::new(),
+            streaming: None,
+            activity: Vec::new(),
+            input: Input::default(),
+            scroll: 0,
+            busy: false,
+            markdown: true,
+            session_title: None,
+        }
+    }
+
+    pub fn push(&mut self, role: Role, text: impl Into<String>) {
+        self.entries.push(Entry {
+            role,
+            text: text.into(),
+        });
+        self.scroll = 0;
+    }
+
+    /// A turn started: clear the streamed text and mark the activity log.
+    pub fn start_turn(&mut self) {
+        self.busy = true;
+        self.streaming = None;
+        if !self.activity.is_empty() {
+            self.activity.push(String::new());
+        }
+    }
+
+    /// A turn ended; `reply` replaces whatever was streamed.
+    pub fn finish_turn(&mut self, reply: Option<(Role, String)>) {
+        self.busy = false;
+        self.streaming = None;
+        if let Some((role, text)) = reply {
+            self.push(role, text);
+        }
+    }
+
+    /// Route a progress or streaming update from the tool-call loop: progress
+    /// lines go to the activity sidebar until the loop clears the draft, after
+    /// which the chunks are the reply.
+    pub fn apply_delta(&mut self, delta: &str) {
+        if delta == crate::agent::loop_::DRAFT_CLEAR_SENTINEL {
+            self.streaming = Some(String::new());
+            return;
+        }
+        if let Some(streaming) = self.streaming.as_mut() {
+            streaming.push_str(delta);
+            return;
+        }
+        self.activity.extend(
+            delta
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string),
+        );
+        let excess = self.activity.len().saturating_sub(MAX_ACTIVITY_LINES);
+        self.activity.drain(..excess);
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> Action {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::Paste(text) => {
+                self.input.insert_str(text);
+                Action::None
+            }
+            Event::Mouse(mouse) => {
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => self.scroll += WHEEL_LINES,
+                    MouseEventKind::ScrollDown => {
+                        self.scroll = self.scroll.saturating_sub(WHEEL_LINES);
+                    }
+                    _ => {}
+                }
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn handle_key(&mut self, key: &KeyEvent) -> Action {
+        if key.kind == KeyEventKind::Release {
+            return Action::None;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if ctrl => {
+                return if self.busy {
+                    Action::Interrupt
+                } else {
+                    Action::Quit
+                };
+            }
+            KeyCode::Char('d') if ctrl && self.input.is_empty() && !self.busy => {
+                return Action::Quit;
+            }
+            KeyCode::Char('j') if ctrl => self.input.newline(),
+            KeyCode::Char('e') if ctrl && !self.busy => return Action::Edit(self.input.take()),
+            KeyCode::Esc if self.busy => return Action::Interrupt,
+            KeyCode::Enter
+                if key
+                    .modifiers
+                    .intersects(KeyModifiers::ALT | KeyModifiers::SHIFT) =>
+            {
+                self.input.newline();
+            }
+            KeyCode::Enter => {
+                if self.input.text().trim().is_empty() {
+                    return Action::None;
+                }
+                return Action::Submit(self.input.take());
+            }
+            KeyCode::Char(c) if !ctrl => self.input.insert_char(c),
+            KeyCode::Backspace => self.input.backspace(),
+            KeyCode::Delete => self.input.delete(),
+            KeyCode::Left => self.input.left(),
+            KeyCode::Right => self.input.right(),
+            KeyCode::Up => self.input.up(),
+            KeyCode::Down => self.input.down(),
+            KeyCode::Home => self.input.home(),
+            KeyCode::End => self.input.end(),
+            KeyCode::PageUp => self.scroll += PAGE_LINES,
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_sub(PAGE_LINES),
+            _ => {}
+        }
+        Action::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn input_edits_across_lines_and_submits_on_enter() {
+        let mut app = App::new("test");
+        for c in "héllo".chars() {
+            app.handle_event(&key(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        app.handle_event(&key(KeyCode::Left, KeyModifiers::NONE));
+        app.handle_event(&key(KeyCode::Left, KeyModifiers::NONE));
+        app.handle_event(&key(KeyCode::Enter, KeyModifiers::ALT));
+        assert_eq!(app.input.lines(), ["hél", "lo"]);
+        assert_eq!(app.input.cursor(), (1, 0));
+
+        app.handle_event(&key(KeyCode::Backspace, KeyModifiers::NONE));
+        assert_eq!(app.input.lines(), ["héllo"]);
+        assert_eq!(app.input.cursor(), (0, 3));
+        app.input.insert_str("\nworld");
+
+        assert_eq!(
+            app.handle_event(&key(KeyCode::Enter, KeyModifiers::NONE)),
+            Action::Submit("hél\nworldlo".into())
+        );
+        assert!(app.input.is_empty());
+        assert_eq!(
+            app.handle_event(&key(KeyCode::Enter, KeyModifiers::NONE)),
+            Action::None
+        );
+        assert_eq!(
+            app.handle_event(&key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Action::Quit
+        );
+    }
+
+    #[test]
+    fn deltas_fill_activity_until_the_reply_streams() {
+        let mut app = App::new("test");
+        app.start_turn();
+        app.apply_delta("🤔 Thinking...\n");
+        app.apply_delta("⏳ shell: ls\n");
+        app.apply_delta("✅ shell (0s)\n");
+        assert!(app.streaming.is_none());
+        app.apply_delta(crate::agent::loop_::DRAFT_CLEAR_SENTINEL);
+        app.apply_delta("Here are ");
+        app.apply_delta("the files");
+        assert_eq!(app.streaming.as_deref(), Some("Here are the files"));
+        assert_eq!(app.activity.len(), 3);
+        assert_eq!(
+            app.handle_event(&key(KeyCode::Esc, KeyModifiers::NONE)),
+            Action::Interrupt
+        );
+
+        app.finish_turn(Some((Role::Assistant, "Here are the files.".into())));
+        assert!(!app.busy);
+        assert!(app.streaming.is_none());
+        assert_eq!(app.entries.last().unwrap().text, "Here are the files.");
+    }
+}
@@ -0,0 +1,401 @@
+//! `zeroclaw tui`: a full-screen terminal chat.
+//!
+//! Runs the same agent as `zeroclaw agent` (tools, memory, approvals,
+//! sessions, guardrails) behind a ratatui interface: a scrollable transcript
+//! with the reply streaming in, a sidebar of tool-call activity and a
+//! multi-line input box. Typing while a turn runs steers it, as in the line
+//! based CLI.
+
+pub mod app;
+pub mod ui;
+
+use crate::agent::commands::{self, SlashCommand};
+use crate::agent::loop_::{is_tool_loop_cancelled, CliRuntime};
+use crate::approval::{ChannelApprovalPrompter, PendingApprovals};
+use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::{Config, GuardrailDirection};
+use crate::i18n;
+use crate::providers::ChatMessage;
+use anyhow::Result;
+use app::{Action, App, Role};
+use async_trait::async_trait;
+use ratatui::crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event,
+};
+use ratatui::crossterm::execute;
+use ratatui::DefaultTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// Approval scope for answers typed into the TUI.
+const TUI_APPROVAL_SCOPE: &str = "tui";
+
+/// Key hints appended to the `/help` listing.
+const KEY_HELP: &str = "\
+Enter sends, Alt+Enter or Ctrl+J adds a line, Ctrl+E opens the message in \
+$EDITOR. Esc interrupts a running turn. \
+PgUp/PgDn and the mouse wheel scroll the transcript.";
+
+/// Delivers approval prompts into the transcript; answers come back through
+/// the input box.
+struct TuiChannel {
+    notices: mpsc::UnboundedSender<String>,
+}
+
+#[async_trait]
+impl Channel for TuiChannel {
+    fn name(&self) -> &str {
+        "tui"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.notices
+            .send(message.content.clone())
+            .map_err(|_| anyhow::anyhow!("TUI closed"))
+    }
+
+    async fn listen(&self, _tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        // Input is read by the TUI's own event loop.
+        Ok(())
+    }
+}
+
+/// How long the event thread waits for input before checking `paused`.
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Terminal events are read on their own thread, like stdin lines in the
+/// line-based CLI, so the event loop can wait on them alongside a turn.
+/// Reading stops while `paused` is set, so an editor can have the terminal.
+fn spawn_terminal_events(paused: Arc<AtomicBool>) -> mpsc::UnboundedReceiver<Event> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    std::thread::spawn(move || loop {
+        if paused.load(Ordering::Acquire) {
+            std::thread::sleep(EVENT_POLL_INTERVAL);
+            continue;
+        }
+        let event = match event::poll(EVENT_POLL_INTERVAL) {
+            Ok(false) => continue,
+            Ok(true) => event::read(),
+            Err(e) => Err(e),
+        };
+        match event {
+            Ok(event) => {
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Error reading terminal input: {e}");
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Run the TUI until the user quits.
+pub async fn run(
+    config: Config,
+    provider_override: Option<String>,
+    model_override: Option<String>,
+    temperature: f64,
+    session: Option<String>,
+) -> Result<()> {
+    let mut runtime = CliRuntime::new(
+        config,
+        provider_override,
+        model_override,
+        temperature,
+        Vec::new(),
+        true,
+        session.as_deref(),
+    )
+    .await?;
+    let started = Instant::now();
+
+    let (notice_tx, notices) = mpsc::unbounded_channel();
+    let pending_approvals = Arc::new(PendingApprovals::new());
+    let approval_timeout =
+        Duration::from_secs(runtime.config.autonomy.risk_approval.timeout_secs.max(1));
+    runtime.approval_manager = runtime.approval_manager.take().map(|manager| {
+        manager.with_prompter(Arc::new(ChannelApprovalPrompter::new(
+            Arc::new(TuiChannel { notices: notice_tx }),
+            "user",
+            None,
+            TUI_APPROVAL_SCOPE,
+            Arc::clone(&pending_approvals),
+            approval_timeout,
+        )))
+    });
+
+    let mut app = App::new(format!(
+        "{} / {}",
+        runtime.provider_name, runtime.model_name
+    ));
+    app.markdown = runtime.rich_output();
+    app.session_title = runtime.session_title();
+    for message in &runtime.resumed {
+        let role = if message.role == "user" {
+            Role::User
+        } else {
+            Role::Assistant
+        };
+        app.push(role, message.content.clone());
+    }
+    if let (Some(session), false) = (session.as_deref(), runtime.resumed.is_empty()) {
+        app.push(
+            Role::Notice,
+            i18n::t_args(
+                "session-resumed",
+                &[
+                    ("session", session.into()),
+                    ("count", runtime.resumed.len().into()),
+                ],
+            ),
+        );
+    }
+    app.push(Role::Notice, i18n::t("interactive-hint"));
+
+    let mut terminal = ratatui::init();
+    let _ = execute!(std::io::stdout(), EnableMouseCapture, EnableBracketedPaste);
+    let history = runtime.initial_history();
+    let events_paused = Arc::new(AtomicBool::new(false));
+    let mut session = Session {
+        runtime: &mut runtime,
+        app,
+        history,
+        events: spawn_terminal_events(Arc::clone(&events_paused)),
+        events_paused,
+        notices,
+        pending_approvals,
+        confirm_clear: false,
+    };
+    let result = session.run(&mut terminal).await;
+    leave_terminal();
+    runtime.finish(started);
+    result
+}
+
+/// Give the terminal back: leave the alternate screen and raw mode.
+fn leave_terminal() {
+    let _ = execute!(
+        std::io::stdout(),
+        DisableBracketedPaste,
+        DisableMouseCapture
+    );
+    ratatui::restore();
+}
+
+/// The event loop's state.
+struct Session<'a> {
+    runtime: &'a mut CliRuntime,
+    app: App,
+    history: Vec<ChatMessage>,
+    events: mpsc::UnboundedReceiver<Event>,
+    /// Set while `$EDITOR` owns the terminal.
+    events_paused: Arc<AtomicBool>,
+    notices: mpsc::UnboundedReceiver<String>,
+    pending_approvals: Arc<PendingApprovals>,
+    /// `/clear` is waiting for a yes/no answer.
+    confirm_clear: bool,
+}
+
+impl Session<'_> {
+    async fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        // Input that interrupted the previous turn, or was composed in
+        // `$EDITOR`, handled next.
+        let mut steering: Option<String> = None;
+        loop {
+            let input = if let Some(input) = steering.take() {
+                input
+            } else {
+                terminal.draw(|frame| ui::draw(frame, &self.app))?;
+                let action = tokio::select! {
+                    event = self.events.recv() => {
+                        let Some(event) = event else { return Ok(()) };
+                        self.app.handle_event(&event)
+                    }
+                    Some(notice) = self.notices.recv() => {
+                        self.app.push(Role::Notice, notice);
+                        continue;
+                    }
+                };
+                match action {
+                    Action::Submit(text) => text,
+                    Action::Edit(draft) => {
+                        steering = self.edit(terminal, &draft).await;
+                        continue;
+                    }
+                    Action::Quit => return Ok(()),
+                    Action::None | Action::Interrupt => continue,
+                }
+            };
+
+            let input = input.trim().to_string();
+            if self.confirm_clear {
+                self.confirm_clear = false;
+                if i18n::is_yes(&input) {
+                    let cleared = self.runtime.clear(&mut self.history).await;
+                    self.app.entries.clear();
+                    self.app.activity.clear();
+                    self.app.session_title = None;
+                    self.app.push(
+                        Role::Notice,
+                        i18n::t_args("conversation-cleared", &[("count", cleared.into())]),
+                    );
+                } else {
+                    self.app.push(Role::Notice, i18n::t("cancelled"));
+                }
+                continue;
+            }
+            match commands::parse(&input) {
+                Some(Ok(SlashCommand::Quit)) => return Ok(()),
+                Some(Ok(SlashCommand::New)) => {
+                    self.app.push(
+                        Role::Notice,
+                        format!(
+                            "{} {} {}",
+                            i18n::t("clear-warning"),
+                            i18n::t("clear-keeps-core"),
+                            i18n::t("confirm-continue")
+                        ),
+                    );
+                    self.confirm_clear = true;
+                    continue;
+                }
+                Some(Ok(SlashCommand::Edit(initial))) => {
+                    steering = self
+                        .edit(terminal, initial.as_deref().unwrap_or_default())
+                        .await;
+                    continue;
+                }
+                Some(Ok(SlashCommand::Help)) => {
+                    self.app.push(
+                        Role::Notice,
+                        format!("{}\n{KEY_HELP}", commands::help_text()),
+                    );
+                    continue;
+                }
+                Some(Ok(command)) => {
+                    let reply = self.runtime.run_command(command, &mut self.history).await;
+                    // `/model` and `/models` may have changed the title.
+                    self.app.title = format!(
+                        "{} / {}",
+                        self.runtime.provider_name, self.runtime.model_name
+                    );
+                    self.app.push(Role::Notice, reply);
+                    continue;
+                }
+                Some(Err(usage)) => {
+                    self.app.push(Role::Notice, usage);
+                    continue;
+                }
+                None => {}
+            }
+
+            self.app.push(Role::User, input.clone());
+            let input = match self.runtime.guard(GuardrailDirection::Input, input).await {
+                Ok(input) => input,
+                Err(blocked) => {
+                    self.app.push(Role::Notice, blocked);
+                    continue;
+                }
+            };
+            steering = self.turn(terminal, &input).await?;
+            if steering.is_none() && self.runtime.compact_history(&mut self.history).await {
+                self.app.push(Role::Notice, "🧹 Auto-compaction complete");
+            }
+        }
+    }
+
+    /// Hand the terminal to `$EDITOR` with `draft`; returns the message to
+    /// send, or puts the draft back in the input box when nothing was saved.
+    async fn edit(&mut self, terminal: &mut DefaultTerminal, draft: &str) -> Option<String> {
+        self.events_paused.store(true, Ordering::Release);
+        // Let the event thread finish its current poll.
+        tokio::time::sleep(EVENT_POLL_INTERVAL * 2).await;
+        leave_terminal();
+        let initial = draft.to_string();
+        let composed = tokio::task::spawn_blocking(move || crate::agent::editor::compose(&initial))
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("editor task failed: {e}")));
+        *terminal = ratatui::init();
+        let _ = execute!(std::io::stdout(), EnableMouseCapture, EnableBracketedPaste);
+        self.events_paused.store(false, Ordering::Release);
+
+        match composed {
+            Ok(Some(text)) => return Some(text),
+            Ok(None) => self.app.push(Role::Notice, "Empty message; nothing sent."),
+            Err(e) => self.app.push(Role::Error, format!("{e:#}")),
+        }
+        self.app.input.insert_str(draft);
+        None
+    }
+
+    /// Run one turn while keeping the screen live. Returns input that
+    /// interrupted it, to be handled next.
+    async fn turn(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        input: &str,
+    ) -> Result<Option<String>> {
+        let runtime = &*self.runtime;
+        runtime.prepare_turn(&mut self.history, input).await;
+        self.app.start_turn();
+
+        let (delta_tx, mut deltas) = mpsc::channel::<String>(64);
+        let cancellation = CancellationToken::new();
+        let mut steering = None;
+        let result = {
+            let turn = runtime.run_turn(
+                &mut self.history,
+                input,
+                Some(cancellation.clone()),
+                Some(delta_tx),
+            );
+            tokio::pin!(turn);
+            loop {
+                terminal.draw(|frame| ui::draw(frame, &self.app))?;
+                tokio::select! {
+                    result = &mut turn => break result,
+                    Some(delta) = deltas.recv() => self.app.apply_delta(&delta),
+                    Some(notice) = self.notices.recv() => self.app.push(Role::Notice, notice),
+                    Some(event) = self.events.recv() => match self.app.handle_event(&event) {
+                        Action::Submit(text) => {
+                            if self.pending_approvals.resolve(TUI_APPROVAL_SCOPE, &text) {
+                                self.app.push(Role::User, text);
+                            } else if steering.is_none() {
+                                self.app.push(Role::Notice, i18n::t("interrupting"));
+                                steering = Some(text);
+                                cancellation.cancel();
+                            }
+                        }
+                        Action::Interrupt | Action::Quit => cancellation.cancel(),
+                        Action::None | Action::Edit(_) => {}
+                    },
+                }
+            }
+        };
+
+        match result {
+            Ok(response) => {
+                let response = runtime.finish_turn(input, response).await;
+                self.app.session_title = runtime.session_title();
+                self.app.finish_turn(Some((Role::Assistant, response)));
+            }
+            Err(e) if is_tool_loop_cancelled(&e) => {
+                runtime.interrupt_turn(&mut self.history, input);
+                self.app
+                    .finish_turn(Some((Role::Notice, i18n::t("interrupted"))));
+            }
+            Err(e) => self
+                .app
+                .finish_turn(Some((Role::Error, crate::error::terminal_message(&e)))),
+        }
+        Ok(steering)
+    }
+}
@@ -0,0 +1,270 @@
+//! Drawing the TUI: transcript, activity sidebar, input box and status line.
+
+use super::app::{App, Role};
+use crate::markdown;
+use crate::util::truncate_with_width;
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::Frame;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Width of the activity sidebar, borders included.
+const SIDEBAR_WIDTH: u16 = 34;
+
+/// The sidebar is hidden on terminals narrower than this.
+const SIDEBAR_MIN_TERMINAL_WIDTH: u16 = 80;
+
+/// Input rows shown before the box scrolls.
+const MAX_INPUT_ROWS: usize = 6;
+
+/// Word-wrap `text` to `width` display columns, breaking words that do not
+/// fit on a line of their own.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut out = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for word in paragraph.split_inclusive(' ') {
+            let word_width = UnicodeWidthStr::width(word);
+            if line_width > 0 && line_width + UnicodeWidthStr::width(word.trim_end()) > width {
+                out.push(line.trim_end().to_string());
+                line.clear();
+                line_width = 0;
+            }
+            if word_width <= width {
+                line.push_str(word);
+                line_width += word_width;
+                continue;
+            }
+            for c in word.chars() {
+                let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+                if line_width > 0 && line_width + char_width > width {
+                    out.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(c);
+                line_width += char_width;
+            }
+        }
+        out.push(line.trim_end().to_string());
+    }
+    out
+}
+
+fn role_header(role: Role) -> Span<'static> {
+    let (label, color) = match role {
+        Role::User => ("you", Color::Cyan),
+        Role::Assistant => ("zeroclaw", Color::Green),
+        Role::Notice => ("·", Color::DarkGray),
+        Role::Error => ("error", Color::Red),
+    };
+    Span::styled(
+        label,
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )
+}
+
+fn to_color(color: markdown::Color) -> Color {
+    match color {
+        markdown::Color::Indexed(n) => Color::Indexed(n),
+        markdown::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
+    }
+}
+
+fn markdown_line(line: markdown::Line) -> Line<'static> {
+    Line::from(
+        line.into_iter()
+            .map(|span| {
+                let mut style = Style::default();
+                if let Some(color) = span.style.fg {
+                    style = style.fg(to_color(color));
+                }
+                for (on, modifier) in [
+                    (span.style.bold, Modifier::BOLD),
+                    (span.style.dim, Modifier::DIM),
+                    (span.style.italic, Modifier::ITALIC),
+                    (span.style.underline, Modifier::UNDERLINED),
+                    (span.style.strikethrough, Modifier::CROSSED_OUT),
+                ] {
+                    if on {
+                        style = style.add_modifier(modifier);
+                    }
+                }
+                Span::styled(span.text, style)
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn transcript_lines(app: &App, width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut push_entry = |role: Role, text: &str| {
+        lines.push(Line::from(role_header(role)));
+        if role == Role::Assistant && app.markdown {
+            lines.extend(
+                markdown::render(text, Some(width))
+                    .into_iter()
+                    .map(markdown_line),
+            );
+        } else {
+            let style = match role {
+                Role::Notice => Style::default().fg(Color::DarkGray),
+                Role::Error => Style::default().fg(Color::Red),
+                Role::User | Role::Assistant => Style::default(),
+            };
+            lines.extend(
+                wrap(text, width)
+                    .into_iter()
+                    .map(|line| Line::styled(line, style)),
+            );
+        }
+        lines.push(Line::default());
+    };
+    for entry in &app.entries {
+        push_entry(entry.role, &entry.text);
+    }
+    if let Some(streaming) = app.streaming.as_deref() {
+        push_entry(Role::Assistant, &format!("{streaming}▌"));
+    }
+    lines
+}
+
+fn draw_transcript(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(format!(" ZeroClaw · {} ", app.title));
+    let inner = block.inner(area);
+    let lines = transcript_lines(app, usize::from(inner.width));
+    let height = usize::from(inner.height);
+    let bottom = lines
+        .len()
+        .saturating_sub(app.scroll.min(lines.len().saturating_sub(height)));
+    let top = bottom.saturating_sub(height);
+    let visible: Vec<Line> = lines[top..bottom].to_vec();
+    frame.render_widget(Paragraph::new(visible).block(block), area);
+}
+
+fn draw_activity(frame: &mut Frame, app: &App, area: Rect) {
+    let title = match app.session_title.as_deref() {
+        Some(title) => format!(
+            " {} ",
+            truncate_with_width(title, usize::from(area.width.saturating_sub(4)), "…")
+        ),
+        None => " Activity ".to_string(),
+    };
+    let block = Block::bordered().title(title);
+    let inner = block.inner(area);
+    let width = usize::from(inner.width);
+    let height = usize::from(inner.height);
+    let skip = app.activity.len().saturating_sub(height);
+    let lines: Vec<Line> = app.activity[skip..]
+        .iter()
+        .map(|line| Line::raw(truncate_with_width(line, width, "…")))
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// The tail of `line` that keeps char column `col` in view, and the
+/// cursor's x offset within it.
+fn visible_tail(line: &str, col: usize, width: usize) -> (String, usize) {
+    let mut chars: Vec<char> = line.chars().collect();
+    let mut cursor_x: usize = chars[..col.min(chars.len())]
+        .iter()
+        .map(|c| UnicodeWidthChar::width(*c).unwrap_or(0))
+        .sum();
+    let mut start = 0;
+    while cursor_x >= width && start < col {
+        cursor_x -= UnicodeWidthChar::width(chars[start]).unwrap_or(0);
+        start += 1;
+    }
+    chars.drain(..start);
+    let tail: String = chars.into_iter().collect();
+    (truncate_with_width(&tail, width, ""), cursor_x)
+}
+
+fn draw_input(frame: &mut Frame, app: &App, area: Rect) {
+    let hint = if app.busy {
+        " Message · Enter steers the running turn "
+    } else {
+        " Message · Enter sends · Alt+Enter adds a line · Ctrl+E opens $EDITOR "
+    };
+    let block = Block::bordered().title(hint);
+    let inner = block.inner(area);
+    let width = usize::from(inner.width).max(1);
+    let (row, col) = app.input.cursor();
+    let first = row.saturating_sub(MAX_INPUT_ROWS - 1);
+    let mut cursor = Position::new(inner.x, inner.y);
+    let lines: Vec<Line> = app
+        .input
+        .lines()
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(MAX_INPUT_ROWS)
+        .map(|(index, line)| {
+            if index == row {
+                let (tail, x) = visible_tail(line, col, width);
+                cursor = Position::new(
+                    inner.x + u16::try_from(x).unwrap_or(inner.width),
+                    inner.y + u16::try_from(index - first).unwrap_or(0),
+                );
+                Line::raw(tail)
+            } else {
+                Line::raw(truncate_with_width(line, width, "…"))
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+    frame.set_cursor_position(cursor);
+}
+
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let text = if app.busy {
+        " Working… Esc interrupts · PgUp/PgDn or the mouse wheel scroll"
+    } else {
+        " /help for commands · PgUp/PgDn or the mouse wheel scroll · Ctrl+C quits"
+    };
+    frame.render_widget(
+        Paragraph::new(Line::styled(text, Style::default().fg(Color::DarkGray))),
+        area,
+    );
+}
+
+/// Draw the whole screen.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let input_rows = app.input.lines().len().clamp(1, MAX_INPUT_ROWS);
+    let input_height = u16::try_from(input_rows).unwrap_or(1) + 2;
+    let [main, input, status] = Layout::vertical([
+        Constraint::Min(3),
+        Constraint::Length(input_height),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    if main.width >= SIDEBAR_MIN_TERMINAL_WIDTH {
+        let [transcript, activity] =
+            Layout::horizontal([Constraint::Min(20), Constraint::Length(SIDEBAR_WIDTH)])
+                .areas(main);
+        draw_transcript(frame, app, transcript);
+        draw_activity(frame, app, activity);
+    } else {
+        draw_transcript(frame, app, main);
+    }
+    draw_input(frame, app, input);
+    draw_status(frame, app, status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_breaks_on_words_and_splits_long_ones() {
+        assert_eq!(wrap("the quick brown fox", 10), ["the quick", "brown fox"]);
+        assert_eq!(wrap("abcdefghij kl", 4), ["abcd", "efgh", "ij", "kl"]);
+        assert_eq!(wrap("a\n\nb", 5), ["a", "", "b"]);
+        assert_eq!(wrap("你好世界", 5), ["你好", "世界"]);
+        assert_eq!(visible_tail("abcdefgh", 8, 5), ("efgh".to_string(), 4));
+    }
+}
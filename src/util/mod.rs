@@ -2,6 +2,8 @@
 //!
 //! This module contains reusable helper functions used across the codebase.
 
+pub mod sqlite;
+
 use unicode_width::UnicodeWidthStr;
 
 /// Truncate a string to at most `max_chars` characters, appending "..." if truncated.
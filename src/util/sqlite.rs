@@ -0,0 +1,78 @@
+//! Helpers shared by the SQLite-backed stores.
+
+use anyhow::{bail, Context, Result};
+use rusqlite::Connection;
+
+/// Bring `conn` up to date with `migrations`.
+///
+/// The schema version is kept in `PRAGMA user_version`: version `n` means the
+/// first `n` entries have been applied. Each newer entry runs in its own
+/// transaction together with the version bump, so a failed migration leaves
+/// the database at the previous version. Entries are applied in order and are
+/// append-only — never edit a released entry; add a new one. A database newer
+/// than `migrations` is refused rather than opened. `store` names the
+/// database in errors ("Outbox", "Task store").
+pub fn migrate(conn: &Connection, store: &str, migrations: &[&str]) -> Result<()> {
+    let current: usize = conn
+        .query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+        .map(|v| usize::try_from(v).unwrap_or(0))?;
+    if current > migrations.len() {
+        bail!(
+            "{store} schema v{current} is newer than this build supports (v{})",
+            migrations.len()
+        );
+    }
+    for (index, sql) in migrations.iter().enumerate().skip(current) {
+        let version = index + 1;
+        conn.execute_batch(&format!(
+            "BEGIN;\n{sql}\nPRAGMA user_version = {version};\nCOMMIT;"
+        ))
+        .with_context(|| format!("{store} migration v{version} failed"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(conn: &Connection) -> i64 {
+        conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn migrate_applies_only_newer_entries() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn, "Test store", &["CREATE TABLE a (x INTEGER);"]).unwrap();
+        assert_eq!(version(&conn), 1);
+
+        let migrations = [
+            "CREATE TABLE a (x INTEGER);",
+            "ALTER TABLE a ADD COLUMN y INTEGER;",
+        ];
+        migrate(&conn, "Test store", &migrations).unwrap();
+        migrate(&conn, "Test store", &migrations).unwrap();
+        assert_eq!(version(&conn), 2);
+        conn.execute("INSERT INTO a (x, y) VALUES (1, 2)", [])
+            .unwrap();
+    }
+
+    #[test]
+    fn migrate_refuses_newer_schemas_and_rolls_back_failures() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA user_version = 3;").unwrap();
+        let err = migrate(&conn, "Test store", &["CREATE TABLE a (x INTEGER);"]).unwrap_err();
+        assert!(err.to_string().contains("Test store schema v3 is newer"));
+
+        let conn = Connection::open_in_memory().unwrap();
+        let err = migrate(
+            &conn,
+            "Test store",
+            &["CREATE TABLE a (x INTEGER);", "CREATE TABLE a (x INTEGER);"],
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Test store migration v2 failed");
+        assert_eq!(version(&conn), 1);
+    }
+}
@@ -20,6 +20,7 @@ fn channel_message_sender_field_holds_platform_user_id() {
     let msg = ChannelMessage {
         id: "msg_1".into(),
         sender: "123456789".into(), // numeric chat_id
+        sender_name: None,
         reply_target: "msg_0".into(),
         content: "test message".into(),
         channel: "telegram".into(),
@@ -41,7 +42,8 @@ fn channel_message_reply_target_distinct_from_sender() {
     // Simulates Discord: reply_target should be channel_id, not sender user_id
     let msg = ChannelMessage {
         id: "msg_1".into(),
-        sender: "user_987654".into(),       // Discord user ID
+        sender: "user_987654".into(), // Discord user ID
+        sender_name: None,
         reply_target: "channel_123".into(), // Discord channel ID for replies
         content: "test message".into(),
         channel: "discord".into(),
@@ -62,6 +64,7 @@ fn channel_message_fields_not_swapped() {
     let msg = ChannelMessage {
         id: "msg_42".into(),
         sender: "sender_value".into(),
+        sender_name: None,
         reply_target: "target_value".into(),
         content: "payload".into(),
         channel: "test".into(),
@@ -88,6 +91,7 @@ fn channel_message_preserves_all_fields_on_clone() {
     let original = ChannelMessage {
         id: "clone_test".into(),
         sender: "sender_123".into(),
+        sender_name: None,
         reply_target: "target_456".into(),
         content: "cloned content".into(),
         channel: "test_channel".into(),
@@ -181,6 +185,7 @@ impl Channel for CapturingChannel {
         tx.send(ChannelMessage {
             id: "listen_1".into(),
             sender: "test_sender".into(),
+            sender_name: None,
             reply_target: "test_target".into(),
             content: "incoming".into(),
             channel: "capturing".into(),
@@ -88,6 +88,18 @@ export default function AgentChat() {
           ]);
           break;
 
+        case 'approval':
+          setMessages((prev) => [
+            ...prev,
+            {
+              id: crypto.randomUUID(),
+              role: 'agent',
+              content: msg.content ?? '',
+              timestamp: new Date(),
+            },
+          ]);
+          break;
+
         case 'error':
           setMessages((prev) => [
             ...prev,